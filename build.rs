@@ -1,7 +1,24 @@
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
+/// Assets smaller than this are served uncompressed; the encoding overhead
+/// outweighs the savings.
+const PRECOMPRESS_MIN_BYTES: u64 = 1024;
+
+/// Extensions worth precompressing. Images and fonts are already compressed
+/// and source maps are only fetched with devtools open.
+const PRECOMPRESS_EXTENSIONS: &[&str] = &["html", "js", "mjs", "css", "svg", "json", "txt", "wasm"];
+
 fn main() {
+    // rust-embed requires the precompressed folder to exist even if empty.
+    let precompressed = precompressed_dir();
+    std::fs::create_dir_all(&precompressed).ok();
+
     if std::env::var("SPACEBOT_SKIP_FRONTEND_BUILD").is_ok() {
+        // The frontend was built out-of-band (Docker, Nix). Still produce the
+        // compressed variants so they get embedded alongside the originals.
+        precompress_dist(&precompressed);
         return;
     }
     // Re-run if interface source files change
@@ -15,7 +32,7 @@ fn main() {
 
     // Skip if bun isn't installed or node_modules is missing (CI without frontend deps)
     if !interface_dir.join("node_modules").exists() {
-        eprintln!(
+        println!(
             "cargo:warning=interface/node_modules not found, skipping frontend build. Run `bun install` in interface/"
         );
         ensure_dist_dir();
@@ -28,14 +45,14 @@ fn main() {
         .status();
 
     match status {
-        Ok(s) if s.success() => {}
+        Ok(s) if s.success() => precompress_dist(&precompressed),
         Ok(s) => {
-            eprintln!(
+            println!(
                 "cargo:warning=frontend build exited with {s}, the binary will serve a stale or empty UI"
             );
         }
         Err(e) => {
-            eprintln!(
+            println!(
                 "cargo:warning=failed to run `bun run build`: {e}. Install bun to build the frontend."
             );
             ensure_dist_dir();
@@ -50,3 +67,100 @@ fn ensure_dist_dir() {
         std::fs::create_dir_all(dist).ok();
     }
 }
+
+/// Where the precompressed variants go. Kept in `OUT_DIR` so the build never
+/// writes into the source tree.
+fn precompressed_dir() -> PathBuf {
+    let out_dir = std::env::var_os("OUT_DIR").expect("cargo sets OUT_DIR for build scripts");
+    PathBuf::from(out_dir).join("interface-precompressed")
+}
+
+/// Write `.gz` and `.br` variants of every compressible file in
+/// `interface/dist` to `output`, mirroring the dist layout, so
+/// `static_handler` can serve them with the matching `Content-Encoding`
+/// instead of compressing on every request.
+fn precompress_dist(output: &Path) {
+    let dist = Path::new("interface/dist");
+    if !dist.exists() {
+        return;
+    }
+
+    let mut stack = vec![dist.to_path_buf()];
+    while let Some(directory) = stack.pop() {
+        let Ok(entries) = std::fs::read_dir(&directory) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                stack.push(path);
+                continue;
+            }
+            let Ok(relative) = path.strip_prefix(dist) else {
+                continue;
+            };
+            if let Err(error) = precompress_file(&path, &output.join(relative)) {
+                println!(
+                    "cargo:warning=failed to precompress {}: {error}",
+                    path.display()
+                );
+            }
+        }
+    }
+}
+
+/// Compress `path`, writing `<target>.gz` and `<target>.br`.
+fn precompress_file(path: &Path, target: &Path) -> std::io::Result<()> {
+    let is_compressible = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| PRECOMPRESS_EXTENSIONS.contains(&extension));
+    let metadata = std::fs::metadata(path)?;
+    if !is_compressible || metadata.len() < PRECOMPRESS_MIN_BYTES {
+        return Ok(());
+    }
+
+    let data = std::fs::read(path)?;
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let gzip_path = target.with_file_name(format!("{}.gz", file_name(target)));
+    if is_stale(&gzip_path, &metadata) {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::best());
+        encoder.write_all(&data)?;
+        write_if_smaller(&gzip_path, &encoder.finish()?, data.len())?;
+    }
+
+    let brotli_path = target.with_file_name(format!("{}.br", file_name(target)));
+    if is_stale(&brotli_path, &metadata) {
+        let mut encoder = brotli::CompressorWriter::new(Vec::new(), 4096, 11, 22);
+        encoder.write_all(&data)?;
+        write_if_smaller(&brotli_path, &encoder.into_inner(), data.len())?;
+    }
+
+    Ok(())
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+fn is_stale(variant: &Path, source: &std::fs::Metadata) -> bool {
+    let Ok(variant_metadata) = std::fs::metadata(variant) else {
+        return true;
+    };
+    match (variant_metadata.modified(), source.modified()) {
+        (Ok(variant_time), Ok(source_time)) => variant_time < source_time,
+        _ => true,
+    }
+}
+
+fn write_if_smaller(path: &Path, compressed: &[u8], original_len: usize) -> std::io::Result<()> {
+    if compressed.len() >= original_len {
+        return Ok(());
+    }
+    std::fs::write(path, compressed)
+}
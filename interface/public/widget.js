@@ -0,0 +1,185 @@
+/*
+ * Spacebot chat widget.
+ *
+ *   <script src="https://spacebot.example.com/widget.js" async></script>
+ *
+ * Talks to the agent configured under [api.widget] on the Spacebot instance
+ * that served this file. Optional attributes on the script tag:
+ *   data-title     header text (default "Chat")
+ *   data-color     accent color (default "#4f46e5")
+ *   data-position  "right" (default) or "left"
+ *   data-api       instance URL, when the script is hosted elsewhere
+ */
+(function () {
+	"use strict";
+
+	var script = document.currentScript;
+	if (!script || window.__spacebotWidget) return;
+	window.__spacebotWidget = true;
+
+	var api = (script.getAttribute("data-api") || new URL(script.src).origin).replace(/\/$/, "");
+	var title = script.getAttribute("data-title") || "Chat";
+	var color = script.getAttribute("data-color") || "#4f46e5";
+	var side = script.getAttribute("data-position") === "left" ? "left" : "right";
+	var storageKey = "spacebot-widget:" + api;
+
+	var session = null;
+	var events = null;
+	var typingRow = null;
+
+	var root = document.createElement("div");
+	var shadow = root.attachShadow({ mode: "open" });
+	shadow.innerHTML =
+		"<style>" +
+		":host{all:initial;position:fixed;bottom:20px;" + side + ":20px;z-index:2147483000;font:14px/1.4 system-ui,sans-serif}" +
+		".launcher{width:56px;height:56px;border-radius:50%;border:0;background:" + color + ";color:#fff;font-size:24px;cursor:pointer;box-shadow:0 4px 12px rgba(0,0,0,.2)}" +
+		".panel{display:none;flex-direction:column;width:340px;height:460px;max-height:calc(100vh - 100px);margin-bottom:12px;background:#fff;color:#111;border-radius:12px;overflow:hidden;box-shadow:0 8px 30px rgba(0,0,0,.2)}" +
+		".open .panel{display:flex}" +
+		".header{padding:12px 16px;background:" + color + ";color:#fff;font-weight:600}" +
+		".log{flex:1;overflow-y:auto;padding:12px;display:flex;flex-direction:column;gap:8px}" +
+		".msg{max-width:80%;padding:8px 12px;border-radius:12px;white-space:pre-wrap;word-wrap:break-word}" +
+		".user{align-self:flex-end;background:" + color + ";color:#fff}" +
+		".assistant{align-self:flex-start;background:#f1f1f4}" +
+		".typing{align-self:flex-start;color:#888;font-style:italic}" +
+		"form{display:flex;border-top:1px solid #eee}" +
+		"input{flex:1;border:0;padding:12px;font:inherit;outline:none}" +
+		"button.send{border:0;background:none;color:" + color + ";font-weight:600;padding:0 16px;cursor:pointer}" +
+		"</style>" +
+		'<div class="widget"><div class="panel"><div class="header"></div><div class="log"></div>' +
+		'<form><input placeholder="Type a message..." autocomplete="off"><button class="send" type="submit">Send</button></form></div>' +
+		'<button class="launcher" aria-label="Open chat">&#128172;</button></div>';
+
+	var container = shadow.querySelector(".widget");
+	shadow.querySelector(".header").textContent = title;
+	var log = shadow.querySelector(".log");
+	var form = shadow.querySelector("form");
+	var input = shadow.querySelector("input");
+
+	function append(role, text) {
+		var row = document.createElement("div");
+		row.className = "msg " + role;
+		row.textContent = text;
+		log.insertBefore(row, typingRow && typingRow.parentNode ? typingRow : null);
+		log.scrollTop = log.scrollHeight;
+	}
+
+	function setTyping(typing) {
+		if (typing && !typingRow) {
+			typingRow = document.createElement("div");
+			typingRow.className = "typing";
+			typingRow.textContent = "typing...";
+			log.appendChild(typingRow);
+			log.scrollTop = log.scrollHeight;
+		} else if (!typing && typingRow) {
+			typingRow.remove();
+			typingRow = null;
+		}
+	}
+
+	function request(method, path, body) {
+		var headers = { "Content-Type": "application/json" };
+		if (session) headers.Authorization = "Bearer " + session.token;
+		return fetch(api + path, {
+			method: method,
+			headers: headers,
+			body: body ? JSON.stringify(body) : undefined,
+		}).then(function (response) {
+			if (response.status === 401) {
+				forget();
+				throw new Error("session expired");
+			}
+			if (!response.ok) throw new Error("request failed: " + response.status);
+			return response.status === 202 ? null : response.json();
+		});
+	}
+
+	function forget() {
+		session = null;
+		if (events) events.close();
+		events = null;
+		try {
+			localStorage.removeItem(storageKey);
+		} catch (_) {}
+	}
+
+	function connect() {
+		events = new EventSource(api + "/api/widget/events?token=" + encodeURIComponent(session.token));
+		events.addEventListener("message", function (event) {
+			setTyping(false);
+			append("assistant", JSON.parse(event.data).text);
+		});
+		events.addEventListener("typing", function (event) {
+			setTyping(JSON.parse(event.data).typing);
+		});
+	}
+
+	function start() {
+		try {
+			session = JSON.parse(localStorage.getItem(storageKey));
+		} catch (_) {
+			session = null;
+		}
+		if (session && new Date(session.expires_at) > new Date()) {
+			return request("GET", "/api/widget/messages")
+				.then(function (messages) {
+					messages.forEach(function (message) {
+						append(message.role, message.content);
+					});
+					connect();
+				})
+				.catch(issue);
+		}
+		return issue();
+	}
+
+	function issue() {
+		session = null;
+		return request("POST", "/api/widget/session").then(function (issued) {
+			session = issued;
+			try {
+				localStorage.setItem(storageKey, JSON.stringify(issued));
+			} catch (_) {}
+			if (issued.greeting) append("assistant", issued.greeting);
+			connect();
+		});
+	}
+
+	var ready = null;
+	function ensureSession() {
+		if (!ready) {
+			ready = start().catch(function (error) {
+				ready = null;
+				throw error;
+			});
+		}
+		return ready;
+	}
+
+	shadow.querySelector(".launcher").addEventListener("click", function () {
+		container.classList.toggle("open");
+		if (container.classList.contains("open")) {
+			ensureSession().catch(function () {
+				append("assistant", "Chat is unavailable right now.");
+			});
+			input.focus();
+		}
+	});
+
+	form.addEventListener("submit", function (event) {
+		event.preventDefault();
+		var text = input.value.trim();
+		if (!text) return;
+		input.value = "";
+		append("user", text);
+		ensureSession()
+			.then(function () {
+				return request("POST", "/api/widget/messages", { message: text });
+			})
+			.catch(function () {
+				ready = null;
+				append("assistant", "Your message couldn't be sent. Please try again.");
+			});
+	});
+
+	document.body.appendChild(root);
+})();
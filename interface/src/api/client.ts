@@ -2,11 +2,31 @@ export const BASE_PATH: string = (window as any).__SPACEBOT_BASE_PATH || "";
 export const IS_TAURI: boolean = !!(window as any).__TAURI_INTERNALS__;
 const API_BASE = BASE_PATH + "/api";
 
+export interface LeaderStatus {
+	election_enabled: boolean;
+	is_leader: boolean;
+	holder_id?: string;
+	expires_at?: string;
+}
+
 export interface StatusResponse {
 	status: string;
 	version: string;
 	pid: number;
 	uptime_seconds: number;
+	instance_id: string;
+	leadership: {
+		election_enabled: boolean;
+		agents: Record<string, LeaderStatus>;
+	};
+	config_issues: ConfigIssue[];
+}
+
+export interface ConfigIssue {
+	scope: string;
+	field: string;
+	message: string;
+	resolution: "corrected" | "unresolved";
 }
 
 export interface ChannelInfo {
@@ -23,6 +43,17 @@ export interface ChannelsResponse {
 	channels: ChannelInfo[];
 }
 
+export interface ChannelPurgeResponse {
+	success: boolean;
+	dry_run: boolean;
+	messages: number;
+	branch_runs: number;
+	worker_runs: number;
+	total: number;
+}
+
+export type ChannelLookupPolicy = "open" | "private";
+
 export type ProcessType = "channel" | "branch" | "worker";
 
 export interface InboundMessageEvent {
@@ -49,6 +80,14 @@ export interface OutboundMessageDeltaEvent {
 	aggregated_text: string;
 }
 
+export interface ChannelUsageEvent {
+	type: "channel_usage";
+	agent_id: string;
+	channel_id: string;
+	input_tokens: number;
+	output_tokens: number;
+}
+
 export interface TypingStateEvent {
 	type: "typing_state";
 	agent_id: string;
@@ -166,6 +205,7 @@ export type ApiEvent =
 	| InboundMessageEvent
 	| OutboundMessageEvent
 	| OutboundMessageDeltaEvent
+	| ChannelUsageEvent
 	| TypingStateEvent
 	| WorkerStartedEvent
 	| WorkerStatusEvent
@@ -194,9 +234,23 @@ export interface TimelineMessage {
 	sender_name: string | null;
 	sender_id: string | null;
 	content: string;
+	metadata?: Record<string, unknown>;
+	/** Present on assistant replies sent through the outbox. */
+	delivery?: DeliveryStatus;
 	created_at: string;
 }
 
+export interface DeliveryStatus {
+	state: "pending" | "retrying" | "delivered" | "dead";
+	attempts: number;
+	last_error?: string;
+	updated_at: string;
+	/** The platform reports receipts, so a missing `read_at` means unread. */
+	receipts: boolean;
+	delivered_at?: string;
+	read_at?: string;
+}
+
 export interface TimelineBranchRun {
 	type: "branch_run";
 	id: string;
@@ -221,6 +275,8 @@ export type TimelineItem = TimelineMessage | TimelineBranchRun | TimelineWorkerR
 export interface MessagesResponse {
 	items: TimelineItem[];
 	has_more: boolean;
+	/** Pass as `cursor` to fetch the page before this one. */
+	next_cursor: string | null;
 }
 
 export interface WorkerStatusInfo {
@@ -467,6 +523,12 @@ export interface MemoryItem {
 	source: string | null;
 	channel_id: string | null;
 	forgotten: boolean;
+	forgotten_at?: string;
+	/** Memory scope this was saved under; absent for unscoped memories. */
+	scope?: string;
+	tags?: string[];
+	/** When the memory stops being true; absent for memories that never expire. */
+	expires_at?: string;
 }
 
 export interface MemoriesListResponse {
@@ -478,6 +540,12 @@ export interface MemorySearchResultItem {
 	memory: MemoryItem;
 	score: number;
 	rank: number;
+	links?: LinkedMemoryItem[];
+}
+
+export interface LinkedMemoryItem {
+	association: AssociationItem;
+	memory: MemoryItem;
 }
 
 export interface MemoriesSearchResponse {
@@ -490,7 +558,16 @@ export type RelationType =
 	| "contradicts"
 	| "caused_by"
 	| "result_of"
-	| "part_of";
+	| "part_of"
+	| "supports"
+	| "about_person";
+
+export interface MemoryLink {
+	source_id: string;
+	target_id: string;
+	relation_type: RelationType;
+	weight?: number;
+}
 
 export interface AssociationItem {
 	id: string;
@@ -534,6 +611,133 @@ export interface MemoriesListParams {
 export interface MemoriesSearchParams {
 	limit?: number;
 	memory_type?: MemoryType;
+	include_trashed?: boolean;
+}
+
+export type TodoStatus = "open" | "in_progress" | "done" | "cancelled";
+
+export interface TodoItem {
+	memory: MemoryItem;
+	status: TodoStatus;
+	priority: TaskPriority;
+	due_at: string | null;
+	channel_id: string | null;
+	completed_at: string | null;
+	overdue_reviewed_at: string | null;
+}
+
+export interface TodoListParams {
+	status?: TodoStatus;
+	include_closed?: boolean;
+	memory_type?: "todo" | "goal";
+	channel_id?: string;
+	limit?: number;
+}
+
+export interface MemoryCreate {
+	content: string;
+	memory_type?: MemoryType;
+	importance?: number;
+	tags?: string[];
+	source?: string;
+	/** "global" (default), "channel:<id>", or "user:<platform>:<id>". */
+	scope?: string;
+	/** RFC 3339 timestamp after which the memory is forgotten. */
+	expires_at?: string;
+}
+
+export interface MemoryUpdate {
+	content?: string;
+	memory_type?: MemoryType;
+	importance?: number;
+	/** Why the memory is being corrected, kept in its history. */
+	reason?: string;
+}
+
+export type MemoryMutationKind =
+	| "create"
+	| "update"
+	| "merge"
+	| "decay"
+	| "forget"
+	| "restore"
+	| "delete";
+
+export interface MemoryMutation {
+	id: number;
+	memory_id: string;
+	kind: MemoryMutationKind;
+	actor: string;
+	reason: string | null;
+	/** The memory after the mutation; null for deletions. */
+	snapshot: MemoryItem | null;
+	created_at: string;
+}
+
+export interface MemoryHistoryResponse {
+	memory_id: string;
+	mutations: MemoryMutation[];
+}
+
+export interface MemoriesAsOfResponse {
+	at: string;
+	memories: MemoryItem[];
+	total: number;
+}
+
+export interface EntityMention {
+	message_id: string;
+	channel_id: string;
+	role: string;
+	sender_name: string | null;
+	content: string;
+	created_at: string;
+}
+
+export interface EntityProfile {
+	name: string;
+	/** Memories that mention the entity, most important first. */
+	memories: MemoryItem[];
+	/** Graph neighbours of those memories that don't name the entity. */
+	related: MemoryItem[];
+	associations: AssociationItem[];
+	/** Recent conversation mentions, newest first. */
+	mentions: EntityMention[];
+}
+
+export type BackfillStatus = "running" | "completed" | "failed" | "cancelled";
+
+export interface BackfillJob {
+	id: string;
+	/** Only this channel is backfilled; all channels when null. */
+	channel_id: string | null;
+	from: string;
+	to: string;
+	batch_size: number;
+	batch_delay_ms: number;
+	status: BackfillStatus;
+	total_messages: number;
+	processed_messages: number;
+	completed_batches: number;
+	memories_saved: number;
+	last_error: string | null;
+	created_at: string;
+	updated_at: string;
+	completed_at: string | null;
+}
+
+export interface BackfillCreate {
+	from: string;
+	to?: string;
+	channel_id?: string;
+	batch_size?: number;
+	batch_delay_ms?: number;
+}
+
+export interface MemoryTrashResponse {
+	memories: MemoryItem[];
+	total: number;
+	retention_days: number;
 }
 
 export type CortexEventType =
@@ -543,20 +747,22 @@ export type CortexEventType =
 	| "memory_merged"
 	| "memory_decayed"
 	| "memory_pruned"
+	| "memory_recalibrated"
 	| "association_created"
 	| "contradiction_flagged"
 	| "worker_killed"
 	| "branch_killed"
 	| "circuit_breaker_tripped"
 	| "observation_created"
+	| "todo_overdue"
 	| "health_check";
 
 export const CORTEX_EVENT_TYPES: CortexEventType[] = [
 	"bulletin_generated", "bulletin_failed",
-	"maintenance_run", "memory_merged", "memory_decayed", "memory_pruned",
+	"maintenance_run", "memory_merged", "memory_decayed", "memory_pruned", "memory_recalibrated",
 	"association_created", "contradiction_flagged",
 	"worker_killed", "branch_killed", "circuit_breaker_tripped",
-	"observation_created", "health_check",
+	"observation_created", "todo_overdue", "health_check",
 ];
 
 export interface CortexEvent {
@@ -605,6 +811,7 @@ export interface CortexChatMessagesResponse {
 
 export interface CortexChatThread {
 	thread_id: string;
+	title: string | null;
 	preview: string;
 	message_count: number;
 	first_message_at: string;
@@ -655,6 +862,15 @@ export interface IdentityUpdateRequest {
 	role?: string | null;
 }
 
+export interface WorkspaceCommit {
+	hash: string;
+	/** Who made the change: "api", "agent", "factory", ... */
+	actor: string | null;
+	summary: string;
+	committed_at: string;
+	files: string[];
+}
+
 // -- Agent Config Types --
 
 export interface RoutingSection {
@@ -708,6 +924,7 @@ export interface CoalesceSection {
 export interface MemoryPersistenceSection {
 	enabled: boolean;
 	message_interval: number;
+	embedding_batch_size: number;
 }
 
 export interface BrowserSection {
@@ -807,6 +1024,7 @@ export interface CoalesceUpdate {
 export interface MemoryPersistenceUpdate {
 	enabled?: boolean;
 	message_interval?: number;
+	embedding_batch_size?: number;
 }
 
 export interface BrowserUpdate {
@@ -854,6 +1072,16 @@ export interface AgentConfigUpdateRequest {
 	projects?: ProjectsUpdate;
 }
 
+export interface ConfigValidationIssue {
+	field: string;
+	message: string;
+}
+
+export interface ConfigValidationResponse {
+	valid: boolean;
+	errors: ConfigValidationIssue[];
+}
+
 // -- Cron Types --
 
 export interface CronJobWithStats {
@@ -1371,6 +1599,16 @@ export interface RawConfigUpdateResponse {
 	message: string;
 }
 
+export interface ConfigHistoryEntry {
+	hash: string;
+	recorded_at: string;
+	/** "startup", "file_change", "raw_editor", or "rollback". */
+	source: string;
+	bytes: number;
+	/** Changes from the previous version; null for the oldest one. */
+	diff: string | null;
+}
+
 // -- Agent Links & Topology --
 
 export type LinkDirection = "one_way" | "two_way";
@@ -1669,9 +1907,39 @@ export const api = {
 		if (!response.ok) throw new Error(`API error: ${response.status}`);
 		return response.json() as Promise<{ success: boolean }>;
 	},
-	channelMessages: (channelId: string, limit = 20, before?: string) => {
+	setChannelArchived: async (agentId: string, channelId: string, archived: boolean) => {
+		const response = await fetch(`${API_BASE}/channels/archive`, {
+			method: "PUT",
+			headers: { "Content-Type": "application/json" },
+			body: JSON.stringify({ agent_id: agentId, channel_id: channelId, archived }),
+		});
+		if (!response.ok) throw new Error(`API error: ${response.status}`);
+		return response.json() as Promise<{ success: boolean; archived: boolean; is_active: boolean }>;
+	},
+	/** Whether other channels may pull excerpts of this one with `lookup_channel`. */
+	setChannelLookupPolicy: async (agentId: string, channelId: string, policy: ChannelLookupPolicy) => {
+		const response = await fetch(`${API_BASE}/channels/lookup-policy`, {
+			method: "PUT",
+			headers: { "Content-Type": "application/json" },
+			body: JSON.stringify({ agent_id: agentId, channel_id: channelId, policy }),
+		});
+		if (!response.ok) throw new Error(`API error: ${response.status}`);
+		return response.json() as Promise<{ success: boolean; policy: ChannelLookupPolicy }>;
+	},
+	/** Without `confirm` this is a dry run that only reports the counts. */
+	purgeChannel: async (agentId: string, channelId: string, confirm = false) => {
+		const response = await fetch(`${API_BASE}/channels/purge`, {
+			method: "POST",
+			headers: { "Content-Type": "application/json" },
+			body: JSON.stringify({ agent_id: agentId, channel_id: channelId, confirm }),
+		});
+		if (!response.ok) throw new Error(`API error: ${response.status}`);
+		return response.json() as Promise<ChannelPurgeResponse>;
+	},
+	channelMessages: (channelId: string, limit = 20, cursor?: string, agentId?: string) => {
 		const params = new URLSearchParams({ channel_id: channelId, limit: String(limit) });
-		if (before) params.set("before", before);
+		if (cursor) params.set("cursor", cursor);
+		if (agentId) params.set("agent_id", agentId);
 		return fetchJson<MessagesResponse>(`/channels/messages?${params}`);
 	},
 	channelStatus: () => fetchJson<ChannelStatusResponse>("/channels/status"),
@@ -1715,8 +1983,141 @@ export const api = {
 		const search = new URLSearchParams({ agent_id: agentId, q: query });
 		if (params.limit) search.set("limit", String(params.limit));
 		if (params.memory_type) search.set("memory_type", params.memory_type);
+		if (params.include_trashed) search.set("include_trashed", "true");
 		return fetchJson<MemoriesSearchResponse>(`/agents/memories/search?${search}`);
 	},
+	entityProfile: (agentId: string, name: string, params: { memory_limit?: number; mention_limit?: number } = {}) => {
+		const search = new URLSearchParams({ agent_id: agentId });
+		if (params.memory_limit) search.set("memory_limit", String(params.memory_limit));
+		if (params.mention_limit !== undefined) search.set("mention_limit", String(params.mention_limit));
+		return fetchJson<EntityProfile>(`/agents/entities/${encodeURIComponent(name)}?${search}`);
+	},
+	memoryHistory: (agentId: string, memoryId: string) => {
+		const search = new URLSearchParams({ agent_id: agentId });
+		return fetchJson<MemoryHistoryResponse>(
+			`/agents/memories/${encodeURIComponent(memoryId)}/history?${search}`,
+		);
+	},
+	memoriesAsOf: (
+		agentId: string,
+		at: string,
+		params: { include_forgotten?: boolean; limit?: number; offset?: number } = {},
+	) => {
+		const search = new URLSearchParams({ agent_id: agentId, at });
+		if (params.include_forgotten) search.set("include_forgotten", "true");
+		if (params.limit) search.set("limit", String(params.limit));
+		if (params.offset) search.set("offset", String(params.offset));
+		return fetchJson<MemoriesAsOfResponse>(`/agents/memories/as-of?${search}`);
+	},
+	agentTodos: (agentId: string, params: TodoListParams = {}) => {
+		const search = new URLSearchParams({ agent_id: agentId });
+		if (params.status) search.set("status", params.status);
+		if (params.include_closed) search.set("include_closed", "true");
+		if (params.memory_type) search.set("memory_type", params.memory_type);
+		if (params.channel_id) search.set("channel_id", params.channel_id);
+		if (params.limit) search.set("limit", String(params.limit));
+		return fetchJson<{ todos: TodoItem[] }>(`/agents/todos?${search}`);
+	},
+	memoryTrash: (agentId: string, params: { limit?: number; offset?: number } = {}) => {
+		const search = new URLSearchParams({ agent_id: agentId });
+		if (params.limit) search.set("limit", String(params.limit));
+		if (params.offset) search.set("offset", String(params.offset));
+		return fetchJson<MemoryTrashResponse>(`/agents/memories/trash?${search}`);
+	},
+	restoreMemory: async (agentId: string, memoryId: string) => {
+		const response = await fetch(`${API_BASE}/agents/memories/trash/restore`, {
+			method: "POST",
+			headers: { "Content-Type": "application/json" },
+			body: JSON.stringify({ agent_id: agentId, memory_id: memoryId }),
+		});
+		if (!response.ok) {
+			throw new Error(`API error: ${response.status}`);
+		}
+		return response.json() as Promise<{ success: boolean }>;
+	},
+	linkMemories: async (agentId: string, link: MemoryLink) => {
+		const response = await fetch(`${API_BASE}/agents/memories/link`, {
+			method: "POST",
+			headers: { "Content-Type": "application/json" },
+			body: JSON.stringify({ agent_id: agentId, ...link }),
+		});
+		if (!response.ok) {
+			throw new Error(`API error: ${response.status}`);
+		}
+		return response.json() as Promise<AssociationItem>;
+	},
+	createMemory: async (agentId: string, memory: MemoryCreate) => {
+		const response = await fetch(`${API_BASE}/agents/memories`, {
+			method: "POST",
+			headers: { "Content-Type": "application/json" },
+			body: JSON.stringify({ agent_id: agentId, ...memory }),
+		});
+		if (!response.ok) {
+			throw new Error(`API error: ${response.status}`);
+		}
+		return response.json() as Promise<MemoryItem>;
+	},
+	updateMemory: async (agentId: string, memoryId: string, update: MemoryUpdate) => {
+		const response = await fetch(`${API_BASE}/agents/memories/${encodeURIComponent(memoryId)}`, {
+			method: "PUT",
+			headers: { "Content-Type": "application/json" },
+			body: JSON.stringify({ agent_id: agentId, ...update }),
+		});
+		if (!response.ok) {
+			throw new Error(`API error: ${response.status}`);
+		}
+		return response.json() as Promise<MemoryItem>;
+	},
+	deleteMemory: async (agentId: string, memoryId: string, reason?: string) => {
+		const search = new URLSearchParams({ agent_id: agentId });
+		if (reason) search.set("reason", reason);
+		const response = await fetch(`${API_BASE}/agents/memories/${encodeURIComponent(memoryId)}?${search}`, {
+			method: "DELETE",
+		});
+		if (!response.ok) {
+			throw new Error(`API error: ${response.status}`);
+		}
+		return response.json() as Promise<{ success: boolean }>;
+	},
+	startMemoryBackfill: async (agentId: string, request: BackfillCreate) => {
+		const response = await fetch(`${API_BASE}/agents/${encodeURIComponent(agentId)}/memories/backfill`, {
+			method: "POST",
+			headers: { "Content-Type": "application/json" },
+			body: JSON.stringify(request),
+		});
+		if (!response.ok) {
+			throw new Error(`API error: ${response.status}`);
+		}
+		return response.json() as Promise<BackfillJob>;
+	},
+	memoryBackfills: (agentId: string, limit = 20) =>
+		fetchJson<{ jobs: BackfillJob[] }>(
+			`/agents/${encodeURIComponent(agentId)}/memories/backfill?limit=${limit}`,
+		),
+	memoryBackfill: (agentId: string, jobId: string) =>
+		fetchJson<BackfillJob>(
+			`/agents/${encodeURIComponent(agentId)}/memories/backfill/${encodeURIComponent(jobId)}`,
+		),
+	cancelMemoryBackfill: async (agentId: string, jobId: string) => {
+		const response = await fetch(
+			`${API_BASE}/agents/${encodeURIComponent(agentId)}/memories/backfill/${encodeURIComponent(jobId)}/cancel`,
+			{ method: "POST" },
+		);
+		if (!response.ok) {
+			throw new Error(`API error: ${response.status}`);
+		}
+		return response.json() as Promise<{ success: boolean }>;
+	},
+	resumeMemoryBackfill: async (agentId: string, jobId: string) => {
+		const response = await fetch(
+			`${API_BASE}/agents/${encodeURIComponent(agentId)}/memories/backfill/${encodeURIComponent(jobId)}/resume`,
+			{ method: "POST" },
+		);
+		if (!response.ok) {
+			throw new Error(`API error: ${response.status}`);
+		}
+		return response.json() as Promise<BackfillJob>;
+	},
 	memoryGraph: (agentId: string, params: MemoryGraphParams = {}) => {
 		const search = new URLSearchParams({ agent_id: agentId });
 		if (params.limit) search.set("limit", String(params.limit));
@@ -1754,6 +2155,34 @@ export const api = {
 				channel_id: channelId ?? null,
 			}),
 		}),
+	cortexChatRegenerate: (agentId: string, threadId: string, channelId?: string) =>
+		fetch(`${API_BASE}/cortex-chat/regenerate`, {
+			method: "POST",
+			headers: { "Content-Type": "application/json" },
+			body: JSON.stringify({
+				agent_id: agentId,
+				thread_id: threadId,
+				channel_id: channelId ?? null,
+			}),
+		}),
+	cortexChatEdit: (
+		agentId: string,
+		threadId: string,
+		messageId: string,
+		message: string,
+		channelId?: string,
+	) =>
+		fetch(`${API_BASE}/cortex-chat/edit`, {
+			method: "POST",
+			headers: { "Content-Type": "application/json" },
+			body: JSON.stringify({
+				agent_id: agentId,
+				thread_id: threadId,
+				message_id: messageId,
+				message,
+				channel_id: channelId ?? null,
+			}),
+		}),
 	cortexChatThreads: (agentId: string) =>
 		fetchJson<CortexChatThreadsResponse>(
 			`/cortex-chat/threads?agent_id=${encodeURIComponent(agentId)}`,
@@ -1766,6 +2195,14 @@ export const api = {
 		});
 		if (!response.ok) throw new Error(`HTTP ${response.status}`);
 	},
+	cortexChatRenameThread: async (agentId: string, threadId: string, title: string | null) => {
+		const response = await fetch(`${API_BASE}/cortex-chat/thread`, {
+			method: "PUT",
+			headers: { "Content-Type": "application/json" },
+			body: JSON.stringify({ agent_id: agentId, thread_id: threadId, title }),
+		});
+		if (!response.ok) throw new Error(`HTTP ${response.status}`);
+	},
 	agentProfile: (agentId: string) =>
 		fetchJson<AgentProfileResponse>(`/agents/profile?agent_id=${encodeURIComponent(agentId)}`),
 	agentIdentity: (agentId: string) =>
@@ -1781,6 +2218,24 @@ export const api = {
 		}
 		return response.json() as Promise<IdentityFiles>;
 	},
+	workspaceLog: (agentId: string, limit = 50) =>
+		fetchJson<{ commits: WorkspaceCommit[] }>(
+			`/agents/${encodeURIComponent(agentId)}/workspace/log?limit=${limit}`,
+		),
+	revertWorkspace: async (agentId: string, commit: string) => {
+		const response = await fetch(
+			`${API_BASE}/agents/${encodeURIComponent(agentId)}/workspace/revert`,
+			{
+				method: "POST",
+				headers: { "Content-Type": "application/json" },
+				body: JSON.stringify({ commit }),
+			},
+		);
+		if (!response.ok) {
+			throw new Error(`API error: ${response.status}`);
+		}
+		return response.json() as Promise<{ commit: string | null }>;
+	},
 	createAgent: async (agentId: string, displayName?: string, role?: string) => {
 		const response = await fetch(`${API_BASE}/agents`, {
 			method: "POST",
@@ -1806,8 +2261,7 @@ export const api = {
 	},
 
 	deleteAgent: async (agentId: string) => {
-		const params = new URLSearchParams({ agent_id: agentId });
-		const response = await fetch(`${API_BASE}/agents?${params}`, {
+		const response = await fetch(`${API_BASE}/agents/${encodeURIComponent(agentId)}`, {
 			method: "DELETE",
 		});
 		if (!response.ok) {
@@ -1859,6 +2313,18 @@ export const api = {
 		return response.json() as Promise<AgentConfigResponse>;
 	},
 
+	validateAgentConfig: async (request: AgentConfigUpdateRequest) => {
+		const response = await fetch(`${API_BASE}/agents/config/validate`, {
+			method: "POST",
+			headers: { "Content-Type": "application/json" },
+			body: JSON.stringify(request),
+		});
+		if (!response.ok) {
+			throw new Error(`API error: ${response.status}`);
+		}
+		return response.json() as Promise<ConfigValidationResponse>;
+	},
+
 	// Cron API
 	listCronJobs: (agentId: string) =>
 		fetchJson<CronListResponse>(`/agents/cron?agent_id=${encodeURIComponent(agentId)}`),
@@ -2159,6 +2625,19 @@ export const api = {
 		}
 		return response.json() as Promise<RawConfigUpdateResponse>;
 	},
+	configHistory: (limit = 50) =>
+		fetchJson<{ entries: ConfigHistoryEntry[] }>(`/config/history?limit=${limit}`),
+	rollbackConfig: async (hash: string) => {
+		const response = await fetch(`${API_BASE}/config/rollback`, {
+			method: "POST",
+			headers: { "Content-Type": "application/json" },
+			body: JSON.stringify({ hash }),
+		});
+		if (!response.ok) {
+			throw new Error(`API error: ${response.status}`);
+		}
+		return response.json() as Promise<RawConfigUpdateResponse>;
+	},
 
 	// Changelog API
 	changelog: async (): Promise<string> => {
@@ -5,7 +5,7 @@ import {ToolCall, type ToolCallPair} from "@/components/ToolCall";
 import {api, type CortexChatToolCall, type CortexChatThread} from "@/api/client";
 import {Button} from "@/ui";
 import {Popover, PopoverContent, PopoverTrigger} from "@/ui/Popover";
-import {PlusSignIcon, Cancel01Icon, Clock01Icon, Delete02Icon} from "@hugeicons/core-free-icons";
+import {PlusSignIcon, Cancel01Icon, Clock01Icon, Delete02Icon, PencilEdit02Icon} from "@hugeicons/core-free-icons";
 import {HugeiconsIcon} from "@hugeicons/react";
 
 interface CortexChatPanelProps {
@@ -293,6 +293,24 @@ function ThreadList({
 		[agentId],
 	);
 
+	const handleRename = useCallback(
+		async (event: React.MouseEvent, thread: CortexChatThread) => {
+			event.stopPropagation();
+			const input = window.prompt("Thread title (empty to clear)", thread.title ?? "");
+			if (input === null) return;
+			const title = input.trim() || null;
+			try {
+				await api.cortexChatRenameThread(agentId, thread.thread_id, title);
+				setThreads((prev) =>
+					prev.map((t) => (t.thread_id === thread.thread_id ? { ...t, title } : t)),
+				);
+			} catch (error) {
+				console.warn("Failed to rename thread:", error);
+			}
+		},
+		[agentId],
+	);
+
 	if (loading) {
 		return (
 			<div className="flex items-center justify-center py-6">
@@ -314,9 +332,10 @@ function ThreadList({
 			{threads.map((thread) => {
 				const isActive = thread.thread_id === currentThreadId;
 				const preview =
-					thread.preview.length > 80
+					thread.title ??
+					(thread.preview.length > 80
 						? `${thread.preview.slice(0, 80)}...`
-						: thread.preview;
+						: thread.preview);
 
 				return (
 					<button
@@ -337,6 +356,14 @@ function ThreadList({
 								<span>{formatRelativeTime(thread.last_message_at)}</span>
 							</div>
 						</div>
+						<button
+							type="button"
+							onClick={(event) => handleRename(event, thread)}
+							className="mt-0.5 shrink-0 rounded p-0.5 text-ink-faint opacity-0 transition-all hover:bg-app-hover/40 hover:text-ink group-hover:opacity-100"
+							title="Rename thread"
+						>
+							<HugeiconsIcon icon={PencilEdit02Icon} className="h-3 w-3" />
+						</button>
 						{!isActive && (
 							<button
 								type="button"
@@ -37,6 +37,8 @@ const EDGE_COLORS: Record<RelationType, string> = {
 	caused_by: "#fb923c",
 	result_of: "#fb923c",
 	part_of: "#60a5fa",
+	supports: "#34d399",
+	about_person: "#c084fc",
 };
 
 const FADED_NODE_COLOR = "#333333";
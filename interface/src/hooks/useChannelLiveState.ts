@@ -56,6 +56,8 @@ export interface ChannelLiveState {
 	historyLoaded: boolean;
 	hasMore: boolean;
 	loadingMore: boolean;
+	/** Cursor for the page before the oldest loaded item. */
+	nextCursor: string | null;
 }
 
 const PAGE_SIZE = 50;
@@ -70,6 +72,7 @@ function emptyLiveState(): ChannelLiveState {
 		historyLoaded: false,
 		hasMore: true,
 		loadingMore: false,
+		nextCursor: null,
 	};
 }
 
@@ -108,6 +111,8 @@ function assistantMessageItem(
  */
 export function useChannelLiveState(channels: ChannelInfo[]) {
 	const [liveStates, setLiveStates] = useState<Record<string, ChannelLiveState>>({});
+	const channelAgents = useRef<Record<string, string>>({});
+	for (const channel of channels) channelAgents.current[channel.id] = channel.agent_id;
 
 	// Load conversation history for each channel on first appearance
 	useEffect(() => {
@@ -120,7 +125,7 @@ export function useChannelLiveState(channels: ChannelInfo[]) {
 					[channel.id]: { ...(prev[channel.id] ?? emptyLiveState()), historyLoaded: true },
 				};
 
-				api.channelMessages(channel.id, PAGE_SIZE).then((data) => {
+				api.channelMessages(channel.id, PAGE_SIZE, undefined, channel.agent_id).then((data) => {
 					const history: TimelineItem[] = data.items;
 
 					setLiveStates((current) => {
@@ -137,6 +142,7 @@ export function useChannelLiveState(channels: ChannelInfo[]) {
 								...existing,
 								timeline: [...history, ...newSseItems],
 								hasMore: data.has_more,
+								nextCursor: data.next_cursor,
 							},
 						};
 					});
@@ -767,11 +773,11 @@ export function useChannelLiveState(channels: ChannelInfo[]) {
 
 			const oldestItem = state.timeline[0];
 			if (!oldestItem) return prev;
-			const before = itemTimestamp(oldestItem);
+			const cursor = state.nextCursor ?? itemTimestamp(oldestItem);
 
 			// Mark as loading, then kick off the fetch outside setState
 			setTimeout(() => {
-				api.channelMessages(channelId, PAGE_SIZE, before).then((data) => {
+				api.channelMessages(channelId, PAGE_SIZE, cursor, channelAgents.current[channelId]).then((data) => {
 					setLiveStates((current) => {
 						const existing = current[channelId];
 						if (!existing) return current;
@@ -784,6 +790,7 @@ export function useChannelLiveState(channels: ChannelInfo[]) {
 								...existing,
 								timeline: [...olderItems, ...existing.timeline],
 								hasMore,
+								nextCursor: data.next_cursor,
 								loadingMore: false,
 							},
 						};
@@ -20,6 +20,9 @@ const BACKOFF_MULTIPLIER = 2;
 /**
  * SSE hook with exponential backoff, connection state tracking,
  * and reconnect notification for state recovery.
+ *
+ * Reconnects resume from the last received event ID, so the server replays
+ * what was missed. If it can't, it sends a `lagged` event and we re-sync.
  */
 export function useEventSource(url: string, options: UseEventSourceOptions) {
 	const { handlers, enabled = true, onReconnect } = options;
@@ -35,6 +38,7 @@ export function useEventSource(url: string, options: UseEventSourceOptions) {
 	const eventSourceRef = useRef<EventSource | null>(null);
 	const retryDelayRef = useRef(INITIAL_RETRY_MS);
 	const hadConnectionRef = useRef(false);
+	const lastEventIdRef = useRef<string | null>(null);
 
 	const connect = useCallback(() => {
 		if (eventSourceRef.current) {
@@ -43,7 +47,13 @@ export function useEventSource(url: string, options: UseEventSourceOptions) {
 
 		setConnectionState(hadConnectionRef.current ? "reconnecting" : "connecting");
 
-		const source = new EventSource(url);
+		// The manual reconnect below creates a fresh EventSource, which won't
+		// send Last-Event-ID on its own, so pass it as a query parameter.
+		const resumeFrom = lastEventIdRef.current;
+		const connectUrl = resumeFrom
+			? `${url}${url.includes("?") ? "&" : "?"}last_event_id=${encodeURIComponent(resumeFrom)}`
+			: url;
+		const source = new EventSource(connectUrl);
 		eventSourceRef.current = source;
 
 		source.onopen = () => {
@@ -52,7 +62,9 @@ export function useEventSource(url: string, options: UseEventSourceOptions) {
 			retryDelayRef.current = INITIAL_RETRY_MS;
 			setConnectionState("connected");
 
-			if (wasReconnect) {
+			// A resumed stream replays missed events; a full re-sync is only
+			// needed when there was nothing to resume from.
+			if (wasReconnect && !resumeFrom) {
 				onReconnectRef.current?.();
 			}
 		};
@@ -60,6 +72,9 @@ export function useEventSource(url: string, options: UseEventSourceOptions) {
 		// Register a listener for each event type in handlers
 		for (const eventType of Object.keys(handlersRef.current)) {
 			source.addEventListener(eventType, (event: MessageEvent) => {
+				if (event.lastEventId) {
+					lastEventIdRef.current = event.lastEventId;
+				}
 				try {
 					const data = JSON.parse(event.data);
 					handlersRef.current[eventType]?.(data);
@@ -97,6 +112,7 @@ export function useEventSource(url: string, options: UseEventSourceOptions) {
 			return;
 		}
 
+		lastEventIdRef.current = null;
 		connect();
 
 		return () => {
@@ -1140,6 +1140,14 @@ function ConfigSectionEditor({ sectionId, label, description, detail, config, on
 							max={200}
 							suffix=" messages"
 						/>
+						<NumberStepper
+							label="Embedding Batch Size"
+							description="Most saved memories embedded in one provider request"
+							value={localValues.embedding_batch_size as number}
+							onChange={(v) => handleChange("embedding_batch_size", v)}
+							min={1}
+							max={512}
+						/>
 					</div>
 				);
 			case "browser":
@@ -21,21 +21,23 @@ const EVENT_CATEGORY_COLORS: Record<string, string> = {
 	memory_merged: "bg-green-500/15 text-green-400",
 	memory_decayed: "bg-green-500/15 text-green-400",
 	memory_pruned: "bg-green-500/15 text-green-400",
+	memory_recalibrated: "bg-green-500/15 text-green-400",
 	association_created: "bg-violet-500/15 text-violet-400",
 	contradiction_flagged: "bg-violet-500/15 text-violet-400",
 	worker_killed: "bg-amber-500/15 text-amber-400",
 	branch_killed: "bg-amber-500/15 text-amber-400",
 	circuit_breaker_tripped: "bg-amber-500/15 text-amber-400",
 	observation_created: "bg-cyan-500/15 text-cyan-400",
+	todo_overdue: "bg-violet-500/15 text-violet-400",
 	health_check: "bg-blue-500/15 text-blue-400",
 };
 
 /** Groups for the filter pills — reduces clutter vs showing all 13 types. */
 const FILTER_GROUPS: { label: string; types: CortexEventType[] }[] = [
 	{ label: "Bulletin", types: ["bulletin_generated", "bulletin_failed"] },
-	{ label: "Maintenance", types: ["maintenance_run", "memory_merged", "memory_decayed", "memory_pruned"] },
+	{ label: "Maintenance", types: ["maintenance_run", "memory_merged", "memory_decayed", "memory_pruned", "memory_recalibrated"] },
 	{ label: "Health", types: ["worker_killed", "branch_killed", "circuit_breaker_tripped", "health_check"] },
-	{ label: "Consolidation", types: ["association_created", "contradiction_flagged", "observation_created"] },
+	{ label: "Consolidation", types: ["association_created", "contradiction_flagged", "observation_created", "todo_overdue"] },
 ];
 
 function EventTypeBadge({ eventType }: { eventType: string }) {
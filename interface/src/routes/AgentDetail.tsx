@@ -741,12 +741,14 @@ const CORTEX_EVENT_COLORS: Record<string, string> = {
 	memory_merged: "bg-cyan-500/20 text-cyan-400",
 	memory_decayed: "bg-yellow-500/20 text-yellow-400",
 	memory_pruned: "bg-orange-500/20 text-orange-400",
+	memory_recalibrated: "bg-teal-500/20 text-teal-400",
 	association_created: "bg-purple-500/20 text-purple-400",
 	contradiction_flagged: "bg-red-500/20 text-red-400",
 	worker_killed: "bg-red-500/20 text-red-400",
 	branch_killed: "bg-red-500/20 text-red-400",
 	circuit_breaker_tripped: "bg-amber-500/20 text-amber-400",
 	observation_created: "bg-indigo-500/20 text-indigo-400",
+	todo_overdue: "bg-rose-500/20 text-rose-400",
 	health_check: "bg-gray-500/20 text-gray-400",
 };
 
@@ -26,10 +26,23 @@ export function base64UrlEncode(value: string): string {
 	return btoa(binary).replace(/\+/g, "-").replace(/\//g, "_").replace(/=/g, "");
 }
 
-const STATUS_FILTERS = ["all", "running", "idle", "done", "failed"] as const;
+const STATUS_FILTERS = [
+	"all",
+	"running",
+	"idle",
+	"done",
+	"failed",
+	"cancelled",
+] as const;
 type StatusFilter = (typeof STATUS_FILTERS)[number];
 
-const KNOWN_STATUSES = new Set(["running", "idle", "done", "failed"]);
+const KNOWN_STATUSES = new Set([
+	"running",
+	"idle",
+	"done",
+	"failed",
+	"cancelled",
+]);
 
 function normalizeStatus(status: string): string {
 	if (KNOWN_STATUSES.has(status)) return status;
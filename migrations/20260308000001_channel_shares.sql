@@ -0,0 +1,17 @@
+-- Tokenized read-only share links for a range of channel messages.
+CREATE TABLE IF NOT EXISTS channel_shares (
+    id TEXT PRIMARY KEY,
+    token TEXT NOT NULL UNIQUE,
+    channel_id TEXT NOT NULL,
+    title TEXT,
+    start_message_id TEXT NOT NULL,
+    end_message_id TEXT NOT NULL,
+    range_start TIMESTAMP NOT NULL,  -- created_at of start_message_id
+    range_end TIMESTAMP NOT NULL,    -- created_at of end_message_id
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    expires_at TIMESTAMP,            -- NULL = never expires
+    revoked_at TIMESTAMP,
+    FOREIGN KEY (channel_id) REFERENCES channels(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_channel_shares_channel ON channel_shares(channel_id, created_at);
@@ -0,0 +1,8 @@
+-- Singleton-duty lease for multi-replica deployments. One row per lease
+-- name; times are unix epoch milliseconds.
+CREATE TABLE IF NOT EXISTS leader_leases (
+    name TEXT PRIMARY KEY,
+    holder_id TEXT NOT NULL,
+    acquired_at INTEGER NOT NULL,
+    expires_at INTEGER NOT NULL
+);
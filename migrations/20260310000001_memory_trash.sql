@@ -0,0 +1,9 @@
+-- Trash for soft-deleted memories: when a memory was forgotten, so the
+-- maintenance pass can purge it once the retention period has passed.
+ALTER TABLE memories ADD COLUMN forgotten_at TIMESTAMP;
+
+-- Memories already in the trash start their retention window now rather than
+-- at their last update, so the first purge after upgrade doesn't take them.
+UPDATE memories SET forgotten_at = CURRENT_TIMESTAMP WHERE forgotten = 1;
+
+CREATE INDEX IF NOT EXISTS idx_memories_forgotten_at ON memories(forgotten_at);
@@ -0,0 +1,25 @@
+-- Usage signals feeding memory importance recalibration. Rows are consumed
+-- (deleted) by each recalibration pass.
+CREATE TABLE IF NOT EXISTS memory_signals (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    memory_id TEXT NOT NULL,
+    signal TEXT NOT NULL,            -- retrieved | cited | corrected
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_memory_signals_memory ON memory_signals(memory_id);
+
+-- Audit log of importance changes made by recalibration.
+CREATE TABLE IF NOT EXISTS memory_importance_adjustments (
+    id TEXT PRIMARY KEY,
+    memory_id TEXT NOT NULL,
+    previous_importance REAL NOT NULL,
+    new_importance REAL NOT NULL,
+    retrieved INTEGER NOT NULL,
+    cited INTEGER NOT NULL,
+    corrected INTEGER NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_memory_importance_adjustments_memory
+    ON memory_importance_adjustments(memory_id, created_at);
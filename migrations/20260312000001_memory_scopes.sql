@@ -0,0 +1,5 @@
+-- Memory scopes: the namespace (platform, workspace, or channel group) a
+-- memory was saved under. NULL means unscoped and visible everywhere.
+ALTER TABLE memories ADD COLUMN scope TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_memories_scope ON memories(scope);
@@ -0,0 +1,15 @@
+-- Structured state for todo and goal memories. A todo memory without a row
+-- here is treated as open, medium priority, with no due date.
+CREATE TABLE IF NOT EXISTS memory_todos (
+    memory_id TEXT PRIMARY KEY,
+    status TEXT NOT NULL DEFAULT 'open',      -- open | in_progress | done | cancelled
+    priority TEXT NOT NULL DEFAULT 'medium',  -- critical | high | medium | low
+    due_at TIMESTAMP,
+    channel_id TEXT,
+    completed_at TIMESTAMP,
+    overdue_reviewed_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_memory_todos_status_due ON memory_todos(status, due_at);
@@ -0,0 +1,14 @@
+-- Append-only log of memory mutations with a snapshot of the memory after
+-- each change. Never updated or pruned.
+CREATE TABLE IF NOT EXISTS memory_mutations (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    memory_id TEXT NOT NULL,
+    kind TEXT NOT NULL,              -- create | update | merge | decay | forget | restore | delete
+    actor TEXT NOT NULL,             -- agent | api | maintenance | recalibration
+    reason TEXT,
+    snapshot TEXT,                   -- JSON memory after the change; NULL for delete
+    created_at TIMESTAMP NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_memory_mutations_memory ON memory_mutations(memory_id, id);
+CREATE INDEX IF NOT EXISTS idx_memory_mutations_created ON memory_mutations(created_at);
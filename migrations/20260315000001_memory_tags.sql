@@ -0,0 +1,2 @@
+-- Free-form memory tags, stored as a JSON array. NULL means untagged.
+ALTER TABLE memories ADD COLUMN tags TEXT;
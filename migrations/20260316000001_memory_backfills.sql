@@ -0,0 +1,25 @@
+-- Memory backfill jobs: extraction runs over historical conversation messages.
+-- The cursor is the last processed message in (channel_id, created_at, id)
+-- order, so an interrupted job resumes from the next batch.
+CREATE TABLE IF NOT EXISTS memory_backfills (
+    id TEXT PRIMARY KEY,
+    channel_id TEXT,
+    from_time TEXT NOT NULL,
+    to_time TEXT NOT NULL,
+    batch_size INTEGER NOT NULL,
+    batch_delay_ms INTEGER NOT NULL,
+    status TEXT NOT NULL DEFAULT 'running',
+    total_messages INTEGER NOT NULL DEFAULT 0,
+    processed_messages INTEGER NOT NULL DEFAULT 0,
+    completed_batches INTEGER NOT NULL DEFAULT 0,
+    memories_saved INTEGER NOT NULL DEFAULT 0,
+    cursor_channel_id TEXT,
+    cursor_created_at TEXT,
+    cursor_message_id TEXT,
+    last_error TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    completed_at TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_memory_backfills_status ON memory_backfills(status);
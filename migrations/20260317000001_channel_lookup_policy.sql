@@ -0,0 +1,3 @@
+-- Whether other channels may pull excerpts of this channel's history with
+-- the lookup_channel tool: 'open' or 'private'.
+ALTER TABLE channels ADD COLUMN lookup_policy TEXT NOT NULL DEFAULT 'open';
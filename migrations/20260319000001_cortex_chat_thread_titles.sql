@@ -0,0 +1,7 @@
+-- User-set titles for cortex chat threads. Threads without a row here are
+-- shown with a preview of their first message.
+CREATE TABLE IF NOT EXISTS cortex_chat_threads (
+    thread_id TEXT PRIMARY KEY,
+    title TEXT NOT NULL,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
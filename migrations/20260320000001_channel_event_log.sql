@@ -0,0 +1,12 @@
+-- Ordered record of everything that drove a channel's state: inbound
+-- messages, recorded LLM responses, and tool results. Replayed offline by
+-- `spacebot replay` to step through how a response came to be.
+CREATE TABLE IF NOT EXISTS channel_event_log (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    channel_id TEXT NOT NULL,
+    kind TEXT NOT NULL,              -- inbound | llm_response | tool_result
+    payload TEXT NOT NULL,           -- JSON-encoded ChannelEvent
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_channel_event_log_channel ON channel_event_log(channel_id, id);
@@ -0,0 +1,39 @@
+-- Full-text index over conversation messages. conversation_messages has a
+-- TEXT primary key (its implicit rowids aren't stable across VACUUM), so the
+-- index keys rows by message id. The id column is tokenized too so triggers
+-- can find a row through the index instead of scanning it; searches are
+-- scoped to the content column.
+CREATE VIRTUAL TABLE IF NOT EXISTS conversation_messages_fts USING fts5(
+    content,
+    message_id,
+    channel_id UNINDEXED,
+    tokenize = 'porter unicode61'
+);
+
+INSERT INTO conversation_messages_fts (content, message_id, channel_id)
+SELECT content, id, channel_id FROM conversation_messages;
+
+CREATE TRIGGER IF NOT EXISTS conversation_messages_fts_insert
+AFTER INSERT ON conversation_messages
+BEGIN
+    INSERT INTO conversation_messages_fts (content, message_id, channel_id)
+    VALUES (new.content, new.id, new.channel_id);
+END;
+
+CREATE TRIGGER IF NOT EXISTS conversation_messages_fts_delete
+AFTER DELETE ON conversation_messages
+BEGIN
+    DELETE FROM conversation_messages_fts
+    WHERE conversation_messages_fts MATCH 'message_id : "' || replace(old.id, '"', '""') || '"'
+      AND message_id = old.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS conversation_messages_fts_update
+AFTER UPDATE OF content, channel_id ON conversation_messages
+BEGIN
+    DELETE FROM conversation_messages_fts
+    WHERE conversation_messages_fts MATCH 'message_id : "' || replace(old.id, '"', '""') || '"'
+      AND message_id = old.id;
+    INSERT INTO conversation_messages_fts (content, message_id, channel_id)
+    VALUES (new.content, new.id, new.channel_id);
+END;
@@ -0,0 +1,17 @@
+-- Tool calls with side effects outside Spacebot (e.g. issue tracker writes)
+-- that wait here until a person approves or rejects them.
+CREATE TABLE IF NOT EXISTS tool_approvals (
+    id TEXT PRIMARY KEY,
+    tool_name TEXT NOT NULL,
+    summary TEXT NOT NULL,
+    payload TEXT NOT NULL,            -- JSON arguments for the deferred call
+    channel_id TEXT,
+    requested_by TEXT NOT NULL,       -- branch | worker:<id>
+    status TEXT NOT NULL DEFAULT 'pending',  -- pending | approved | rejected | executed | failed
+    result TEXT,
+    decided_by TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    decided_at TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_tool_approvals_status ON tool_approvals(status, created_at);
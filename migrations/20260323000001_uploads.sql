@@ -0,0 +1,11 @@
+-- Files uploaded through the API (e.g. from the dashboard) for the cortex to
+-- work on. Stored under the agent workspace's uploads/ directory.
+CREATE TABLE IF NOT EXISTS uploads (
+    id TEXT PRIMARY KEY,
+    original_filename TEXT NOT NULL,
+    saved_filename TEXT NOT NULL,
+    mime_type TEXT NOT NULL,
+    size_bytes INTEGER NOT NULL,
+    disk_path TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
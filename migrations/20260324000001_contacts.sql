@@ -0,0 +1,25 @@
+-- People the agent can reach on someone's behalf ("send that to Alex").
+CREATE TABLE IF NOT EXISTS contacts (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    aliases TEXT NOT NULL DEFAULT '[]',  -- JSON array of alternate names
+    emails TEXT NOT NULL DEFAULT '[]',   -- JSON array, preferred first
+    notes TEXT,
+    human_id TEXT,                       -- linked org-level [[humans]] entry
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_contacts_name ON contacts(name COLLATE NOCASE);
+
+-- Platform identities linked to a contact. An identity belongs to at most one
+-- contact, so inbound senders map back to a single person.
+CREATE TABLE IF NOT EXISTS contact_handles (
+    contact_id TEXT NOT NULL REFERENCES contacts(id) ON DELETE CASCADE,
+    platform TEXT NOT NULL,   -- adapter name: discord, slack, telegram, signal, email, signal:work
+    handle TEXT NOT NULL,     -- platform user ID, phone number, or address
+    position INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (platform, handle)
+);
+
+CREATE INDEX IF NOT EXISTS idx_contact_handles_contact ON contact_handles(contact_id, position);
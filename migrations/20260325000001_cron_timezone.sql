@@ -0,0 +1,3 @@
+-- Add optional per-job timezone to cron_jobs.
+-- NULL means "use the agent's cron_timezone".
+ALTER TABLE cron_jobs ADD COLUMN timezone TEXT;
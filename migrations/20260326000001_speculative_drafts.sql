@@ -0,0 +1,16 @@
+-- One row per speculative draft-and-verify turn, for measuring how often the
+-- fast model's draft survives review.
+CREATE TABLE IF NOT EXISTS speculative_drafts (
+    id TEXT PRIMARY KEY,
+    channel_id TEXT NOT NULL,
+    draft_model TEXT NOT NULL,
+    review_model TEXT NOT NULL,
+    outcome TEXT NOT NULL,        -- kept, replaced, escalated
+    divergence REAL,              -- word edit distance / longer length; NULL when escalated
+    shown_early INTEGER NOT NULL, -- draft was visible before review finished
+    draft_ms INTEGER NOT NULL,
+    review_ms INTEGER NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_speculative_drafts_channel ON speculative_drafts(channel_id, created_at);
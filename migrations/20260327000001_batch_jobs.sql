@@ -0,0 +1,39 @@
+-- Batch jobs: one task template run by a worker over every item of an input
+-- file or conversation search. Items are the job's durable queue: a worker
+-- claims a pending item, and items left running by a restart go back to
+-- pending, so an interrupted job picks up where it stopped.
+CREATE TABLE IF NOT EXISTS batch_jobs (
+    id TEXT PRIMARY KEY,
+    template TEXT NOT NULL,
+    -- 'file' or 'query', with the path or search text in source_detail.
+    source TEXT NOT NULL,
+    source_detail TEXT NOT NULL,
+    concurrency INTEGER NOT NULL,
+    item_delay_ms INTEGER NOT NULL,
+    status TEXT NOT NULL DEFAULT 'running',
+    total_items INTEGER NOT NULL DEFAULT 0,
+    completed_items INTEGER NOT NULL DEFAULT 0,
+    failed_items INTEGER NOT NULL DEFAULT 0,
+    output_path TEXT,
+    last_error TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    completed_at TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_batch_jobs_status ON batch_jobs(status);
+
+CREATE TABLE IF NOT EXISTS batch_items (
+    job_id TEXT NOT NULL REFERENCES batch_jobs(id) ON DELETE CASCADE,
+    item_index INTEGER NOT NULL,
+    input TEXT NOT NULL,
+    -- pending, running, done, or failed.
+    status TEXT NOT NULL DEFAULT 'pending',
+    attempts INTEGER NOT NULL DEFAULT 0,
+    output TEXT,
+    error TEXT,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (job_id, item_index)
+);
+
+CREATE INDEX IF NOT EXISTS idx_batch_items_status ON batch_items(job_id, status, item_index);
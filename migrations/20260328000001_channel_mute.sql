@@ -0,0 +1,2 @@
+-- Muted channels keep recording and remembering messages but never reply.
+ALTER TABLE channels ADD COLUMN muted INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,28 @@
+-- Curated Q&A pairs matched against inbound questions by embedding. The
+-- question's embedding is stored as little-endian f32s.
+CREATE TABLE IF NOT EXISTS faq_entries (
+    id TEXT PRIMARY KEY,
+    question TEXT NOT NULL,
+    answer TEXT NOT NULL,
+    embedding BLOB NOT NULL,
+    enabled INTEGER NOT NULL DEFAULT 1,
+    hit_count INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- One row per inbound question checked against the store. outcome is
+-- 'answered' (sent the stored answer), 'context' (added the pair to the
+-- turn), or 'miss'.
+CREATE TABLE IF NOT EXISTS faq_matches (
+    id TEXT PRIMARY KEY,
+    channel_id TEXT NOT NULL,
+    question TEXT NOT NULL,
+    entry_id TEXT,
+    similarity REAL,
+    outcome TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_faq_matches_created ON faq_matches(created_at);
+CREATE INDEX IF NOT EXISTS idx_faq_matches_entry ON faq_matches(entry_id);
@@ -0,0 +1,13 @@
+-- Q&A store management: which channels an entry answers in, agent-proposed
+-- entries awaiting operator review, and where a proposal came from.
+--
+-- channels is a JSON array of channel ID patterns (a trailing '*' matches any
+-- suffix); empty means every channel. scope limits the entry to channels that
+-- can recall that memory scope. status is 'active', 'proposed', or
+-- 'rejected'; only active entries are matched.
+ALTER TABLE faq_entries ADD COLUMN channels TEXT NOT NULL DEFAULT '[]';
+ALTER TABLE faq_entries ADD COLUMN scope TEXT;
+ALTER TABLE faq_entries ADD COLUMN status TEXT NOT NULL DEFAULT 'active';
+ALTER TABLE faq_entries ADD COLUMN source_channel_id TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_faq_entries_status ON faq_entries(status);
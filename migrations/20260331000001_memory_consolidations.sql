@@ -0,0 +1,15 @@
+-- Clusters of near-duplicate memories reviewed by the consolidation pass.
+-- Merged clusters record the canonical memory and the memories it absorbed;
+-- distinct clusters are kept so they aren't reviewed again.
+CREATE TABLE IF NOT EXISTS memory_consolidations (
+    id TEXT PRIMARY KEY,
+    outcome TEXT NOT NULL,
+    canonical_id TEXT,
+    merged_ids TEXT NOT NULL DEFAULT '[]',
+    memory_ids TEXT NOT NULL,
+    model TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_memory_consolidations_created_at ON memory_consolidations(created_at);
+CREATE INDEX IF NOT EXISTS idx_memory_consolidations_memory_ids ON memory_consolidations(memory_ids);
@@ -0,0 +1,10 @@
+-- The embedding provider, model, and dimension this agent's memories were
+-- embedded with. Checked at startup so switching models can't silently mix
+-- vectors from different spaces. Single row.
+CREATE TABLE IF NOT EXISTS embedding_metadata (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    provider TEXT NOT NULL,
+    model TEXT NOT NULL,
+    dimensions INTEGER NOT NULL,
+    recorded_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
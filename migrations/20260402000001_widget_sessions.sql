@@ -0,0 +1,11 @@
+-- Anonymous visitor sessions issued to the embeddable website chat widget.
+CREATE TABLE IF NOT EXISTS widget_sessions (
+    id TEXT PRIMARY KEY,
+    token TEXT NOT NULL UNIQUE,
+    origin TEXT NOT NULL,            -- page origin the session was issued to
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_seen_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    expires_at TIMESTAMP NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_widget_sessions_expires ON widget_sessions(expires_at);
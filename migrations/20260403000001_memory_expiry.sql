@@ -0,0 +1,6 @@
+-- Memories that stop being true at a known time ("the meeting is at 3pm
+-- Friday"). NULL means the memory never expires. Maintenance moves expired
+-- memories to the trash, or deletes them outright.
+ALTER TABLE memories ADD COLUMN expires_at TIMESTAMP;
+
+CREATE INDEX IF NOT EXISTS idx_memories_expires_at ON memories(expires_at);
@@ -0,0 +1,8 @@
+-- Memories saved by a backfill batch whose cursor hasn't advanced yet. Each
+-- save is journaled before the memory is written; a batch that is retried or
+-- resumed after a crash deletes these first, so it never saves twice.
+CREATE TABLE IF NOT EXISTS memory_backfill_saves (
+    job_id TEXT NOT NULL,
+    memory_id TEXT NOT NULL,
+    PRIMARY KEY (job_id, memory_id)
+);
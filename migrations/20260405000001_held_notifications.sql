@@ -0,0 +1,13 @@
+-- Operator alerts that arrived during the recipient's quiet hours, held
+-- until those hours end. Rows are deleted as they're sent.
+CREATE TABLE IF NOT EXISTS held_notifications (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    category TEXT NOT NULL,
+    adapter TEXT NOT NULL,
+    target TEXT NOT NULL,
+    text TEXT NOT NULL,
+    release_at TIMESTAMP NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_held_notifications_release_at ON held_notifications(release_at);
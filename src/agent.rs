@@ -1,5 +1,7 @@
 //! Agent processes: channels, branches, workers, compactor, cortex.
 
+pub mod backfill;
+pub mod batch;
 pub mod branch;
 pub mod channel;
 pub mod channel_attachments;
@@ -9,12 +11,18 @@ pub mod channel_prompt;
 pub mod compactor;
 pub mod cortex;
 pub mod cortex_chat;
+pub mod event_log;
+pub mod faq;
 pub mod ingestion;
 #[cfg(test)]
 mod invariant_harness;
 pub mod process_control;
+pub mod prompt_cache;
 pub mod prompt_snapshot;
+pub mod runtime;
+pub mod speculative;
 pub mod status;
+pub mod verification;
 pub mod worker;
 
 pub(crate) fn panic_payload_to_string(panic_payload: &(dyn std::any::Any + Send)) -> String {
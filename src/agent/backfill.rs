@@ -0,0 +1,744 @@
+//! Memory backfill: runs memory extraction over historical conversations.
+//!
+//! Conversation history imported before memory persistence was enabled was
+//! never mined for memories. A backfill job walks the stored messages in a
+//! time range, one channel at a time, and feeds them in batches through the
+//! same extraction prompt and tools as the periodic memory persistence branch.
+//!
+//! Progress is tracked per job in SQLite with a cursor over
+//! `(channel_id, created_at, id)`. The cursor advances only after a batch
+//! succeeds, so a job interrupted by a restart or a provider failure resumes
+//! from the first unprocessed batch. Every memory a batch saves is journaled
+//! before it is written and the journal is cleared with the cursor, so a
+//! batch that runs again first deletes what its earlier attempt saved rather
+//! than saving it twice. Jobs only make progress on the replica holding the
+//! agent's leader lease. Batches are spaced by a configurable delay so a
+//! large backfill doesn't crowd out live traffic.
+
+use crate::AgentDeps;
+use crate::ProcessId;
+use crate::ProcessType;
+use crate::hooks::SpacebotHook;
+use crate::llm::SpacebotModel;
+use crate::memory::MemorySearch;
+use crate::tools::MemoryPersistenceContractState;
+
+use anyhow::Context as _;
+use chrono::{DateTime, Utc};
+use rig::agent::AgentBuilder;
+use rig::completion::{CompletionModel, PromptError};
+use serde::Serialize;
+use sqlx::{Row as _, SqlitePool};
+use uuid::Uuid;
+
+use std::collections::HashSet;
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::Duration;
+
+/// Timestamp format matching SQLite's `datetime()`, used for all range and
+/// cursor comparisons so imported and live messages sort together.
+const SQLITE_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// Attempts per batch before the job is marked failed.
+const MAX_BATCH_ATTEMPTS: u32 = 3;
+
+/// How often a job on a follower replica checks for leadership.
+const LEADER_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Cap on a single message's length in a batch transcript.
+const MAX_MESSAGE_CHARS: usize = 4_000;
+
+pub const DEFAULT_BATCH_SIZE: i64 = 50;
+pub const DEFAULT_BATCH_DELAY_MS: u64 = 2_000;
+
+/// Jobs with a running task in this process, so a resume can't start a
+/// second task for a job that is already being worked.
+static ACTIVE_JOBS: LazyLock<Mutex<HashSet<String>>> = LazyLock::new(|| Mutex::new(HashSet::new()));
+
+/// Lifecycle of a backfill job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BackfillStatus {
+    Running,
+    Completed,
+    /// Stopped after a batch kept failing; can be resumed.
+    Failed,
+    Cancelled,
+}
+
+impl BackfillStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            BackfillStatus::Running => "running",
+            BackfillStatus::Completed => "completed",
+            BackfillStatus::Failed => "failed",
+            BackfillStatus::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "running" => Some(BackfillStatus::Running),
+            "completed" => Some(BackfillStatus::Completed),
+            "failed" => Some(BackfillStatus::Failed),
+            "cancelled" => Some(BackfillStatus::Cancelled),
+            _ => None,
+        }
+    }
+}
+
+/// A backfill job and its progress.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct BackfillJob {
+    pub id: String,
+    /// Restrict the backfill to one channel; all channels when absent.
+    pub channel_id: Option<String>,
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub batch_size: i64,
+    pub batch_delay_ms: i64,
+    pub status: BackfillStatus,
+    /// Messages in range when the job was created.
+    pub total_messages: i64,
+    pub processed_messages: i64,
+    pub completed_batches: i64,
+    pub memories_saved: i64,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+/// Parameters for a new backfill job.
+#[derive(Debug, Clone)]
+pub struct BackfillRequest {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub channel_id: Option<String>,
+    pub batch_size: i64,
+    pub batch_delay_ms: u64,
+}
+
+/// Record a new job. Returns `None` if another job for the agent is still
+/// running; only one runs at a time.
+pub async fn create_job(
+    pool: &SqlitePool,
+    request: &BackfillRequest,
+) -> anyhow::Result<Option<BackfillJob>> {
+    let running: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM memory_backfills WHERE status = 'running'")
+            .fetch_one(pool)
+            .await
+            .context("failed to check for running backfills")?;
+    if running > 0 {
+        return Ok(None);
+    }
+
+    let total_messages = count_messages(pool, request).await?;
+    let id = Uuid::new_v4().to_string();
+    sqlx::query(
+        "INSERT INTO memory_backfills \
+         (id, channel_id, from_time, to_time, batch_size, batch_delay_ms, total_messages) \
+         VALUES (?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&id)
+    .bind(&request.channel_id)
+    .bind(request.from)
+    .bind(request.to)
+    .bind(request.batch_size)
+    .bind(request.batch_delay_ms as i64)
+    .bind(total_messages)
+    .execute(pool)
+    .await
+    .context("failed to create memory backfill")?;
+
+    get_job(pool, &id).await
+}
+
+pub async fn get_job(pool: &SqlitePool, id: &str) -> anyhow::Result<Option<BackfillJob>> {
+    let row = sqlx::query(&format!("{JOB_COLUMNS} WHERE id = ?"))
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .context("failed to load memory backfill")?;
+    Ok(row.as_ref().and_then(row_to_job))
+}
+
+/// Jobs, newest first.
+pub async fn list_jobs(pool: &SqlitePool, limit: i64) -> anyhow::Result<Vec<BackfillJob>> {
+    let rows = sqlx::query(&format!("{JOB_COLUMNS} ORDER BY created_at DESC LIMIT ?"))
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+        .context("failed to list memory backfills")?;
+    Ok(rows.iter().filter_map(row_to_job).collect())
+}
+
+/// Stop a running job after its current batch. Returns false if the job
+/// doesn't exist or isn't running.
+pub async fn cancel_job(pool: &SqlitePool, id: &str) -> anyhow::Result<bool> {
+    let result = sqlx::query(
+        "UPDATE memory_backfills SET status = 'cancelled', updated_at = CURRENT_TIMESTAMP, \
+         completed_at = CURRENT_TIMESTAMP WHERE id = ? AND status = 'running'",
+    )
+    .bind(id)
+    .execute(pool)
+    .await
+    .context("failed to cancel memory backfill")?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Put a failed or cancelled job back into the running state so it can be
+/// spawned again from its cursor. Returns false if the job can't be resumed.
+pub async fn reopen_job(pool: &SqlitePool, id: &str) -> anyhow::Result<bool> {
+    let result = sqlx::query(
+        "UPDATE memory_backfills SET status = 'running', last_error = NULL, \
+         completed_at = NULL, updated_at = CURRENT_TIMESTAMP \
+         WHERE id = ? AND status IN ('failed', 'cancelled') \
+         AND NOT EXISTS (SELECT 1 FROM memory_backfills WHERE status = 'running')",
+    )
+    .bind(id)
+    .execute(pool)
+    .await
+    .context("failed to reopen memory backfill")?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Spawn the task working a running job. Does nothing if the job is already
+/// being worked in this process.
+pub fn spawn_job(job_id: String, deps: AgentDeps) -> Option<tokio::task::JoinHandle<()>> {
+    if !ACTIVE_JOBS
+        .lock()
+        .expect("backfill registry poisoned")
+        .insert(job_id.clone())
+    {
+        return None;
+    }
+
+    Some(tokio::spawn(async move {
+        if let Err(error) = run_job(&job_id, &deps).await {
+            tracing::error!(%error, agent_id = %deps.agent_id, %job_id, "memory backfill exited with error");
+            let _ = mark_failed(&deps.sqlite_pool, &job_id, &error.to_string()).await;
+        }
+        ACTIVE_JOBS
+            .lock()
+            .expect("backfill registry poisoned")
+            .remove(&job_id);
+    }))
+}
+
+/// Resume jobs left running by a previous process. On a follower replica
+/// the jobs wait for leadership before processing anything.
+pub async fn resume_running_jobs(deps: &AgentDeps) -> anyhow::Result<usize> {
+    let ids: Vec<String> =
+        sqlx::query_scalar("SELECT id FROM memory_backfills WHERE status = 'running'")
+            .fetch_all(&deps.sqlite_pool)
+            .await
+            .context("failed to find running memory backfills")?;
+    let count = ids.len();
+    for id in ids {
+        tracing::info!(agent_id = %deps.agent_id, job_id = %id, "resuming memory backfill");
+        spawn_job(id, deps.clone());
+    }
+    Ok(count)
+}
+
+#[tracing::instrument(skip(deps), fields(agent_id = %deps.agent_id))]
+async fn run_job(job_id: &str, deps: &AgentDeps) -> anyhow::Result<()> {
+    loop {
+        let Some(job) = get_job(&deps.sqlite_pool, job_id).await? else {
+            return Ok(());
+        };
+        if job.status != BackfillStatus::Running {
+            tracing::info!(status = job.status.as_str(), "memory backfill stopped");
+            return Ok(());
+        }
+        if deps.runtime_config.is_paused() {
+            deps.runtime_config.wait_until_resumed().await;
+            continue;
+        }
+        if !deps.leader.is_leader() {
+            tokio::time::sleep(LEADER_POLL_INTERVAL).await;
+            continue;
+        }
+
+        let batch = next_batch(&deps.sqlite_pool, job_id, &job).await?;
+        if batch.is_empty() {
+            sqlx::query(
+                "UPDATE memory_backfills SET status = 'completed', updated_at = CURRENT_TIMESTAMP, \
+                 completed_at = CURRENT_TIMESTAMP WHERE id = ? AND status = 'running'",
+            )
+            .bind(job_id)
+            .execute(&deps.sqlite_pool)
+            .await
+            .context("failed to complete memory backfill")?;
+            tracing::info!(
+                batches = job.completed_batches,
+                messages = job.processed_messages,
+                memories = job.memories_saved,
+                "memory backfill complete"
+            );
+            return Ok(());
+        }
+
+        let batch_number = job.completed_batches + 1;
+        let mut attempt = 0;
+        let saved = loop {
+            attempt += 1;
+            // Whatever an interrupted or failed attempt saved is redone below.
+            roll_back_uncommitted(&deps.sqlite_pool, &deps.memory_search, job_id).await?;
+            match process_batch(job_id, &batch, batch_number, deps).await {
+                Ok(saved) => break saved,
+                Err(error) if attempt < MAX_BATCH_ATTEMPTS => {
+                    tracing::warn!(%error, batch_number, attempt, "memory backfill batch failed, retrying");
+                    tokio::time::sleep(Duration::from_secs(5 * 2u64.pow(attempt - 1))).await;
+                }
+                Err(error) => {
+                    tracing::error!(%error, batch_number, "memory backfill batch failed, stopping");
+                    roll_back_uncommitted(&deps.sqlite_pool, &deps.memory_search, job_id).await?;
+                    mark_failed(&deps.sqlite_pool, job_id, &error.to_string()).await?;
+                    return Ok(());
+                }
+            }
+        };
+
+        let last = batch.last().expect("batch is not empty");
+        let mut transaction = deps
+            .sqlite_pool
+            .begin()
+            .await
+            .context("failed to begin memory backfill checkpoint")?;
+        sqlx::query(
+            "UPDATE memory_backfills SET \
+             cursor_channel_id = ?, cursor_created_at = ?, cursor_message_id = ?, \
+             processed_messages = processed_messages + ?, completed_batches = completed_batches + 1, \
+             memories_saved = memories_saved + ?, updated_at = CURRENT_TIMESTAMP \
+             WHERE id = ?",
+        )
+        .bind(&last.channel_id)
+        .bind(&last.created_at)
+        .bind(&last.id)
+        .bind(batch.len() as i64)
+        .bind(saved as i64)
+        .bind(job_id)
+        .execute(&mut *transaction)
+        .await
+        .context("failed to record memory backfill progress")?;
+        sqlx::query("DELETE FROM memory_backfill_saves WHERE job_id = ?")
+            .bind(job_id)
+            .execute(&mut *transaction)
+            .await
+            .context("failed to clear memory backfill journal")?;
+        transaction
+            .commit()
+            .await
+            .context("failed to commit memory backfill checkpoint")?;
+
+        tokio::time::sleep(Duration::from_millis(job.batch_delay_ms.max(0) as u64)).await;
+    }
+}
+
+/// Journal a memory a batch of `job_id` is about to save.
+pub(crate) async fn journal_save(
+    pool: &SqlitePool,
+    job_id: &str,
+    memory_id: &str,
+) -> anyhow::Result<()> {
+    sqlx::query("INSERT OR IGNORE INTO memory_backfill_saves (job_id, memory_id) VALUES (?, ?)")
+        .bind(job_id)
+        .bind(memory_id)
+        .execute(pool)
+        .await
+        .context("failed to journal memory backfill save")?;
+    Ok(())
+}
+
+/// Delete the memories journaled by a batch of `job_id` that never reached
+/// its checkpoint. Returns how many were deleted.
+async fn roll_back_uncommitted(
+    pool: &SqlitePool,
+    memory_search: &MemorySearch,
+    job_id: &str,
+) -> anyhow::Result<usize> {
+    let memory_ids: Vec<String> =
+        sqlx::query_scalar("SELECT memory_id FROM memory_backfill_saves WHERE job_id = ?")
+            .bind(job_id)
+            .fetch_all(pool)
+            .await
+            .context("failed to read memory backfill journal")?;
+    let mut deleted = 0;
+    for memory_id in &memory_ids {
+        if memory_search.delete_memory(memory_id).await? {
+            deleted += 1;
+        }
+        sqlx::query("DELETE FROM memory_backfill_saves WHERE job_id = ? AND memory_id = ?")
+            .bind(job_id)
+            .bind(memory_id)
+            .execute(pool)
+            .await
+            .context("failed to clear memory backfill journal")?;
+    }
+    if deleted > 0 {
+        tracing::info!(%job_id, deleted, "rolled back memories from an unfinished backfill batch");
+    }
+    Ok(deleted)
+}
+
+/// A stored message as it appears in a backfill batch.
+#[derive(Debug, Clone)]
+struct BatchMessage {
+    id: String,
+    channel_id: String,
+    role: String,
+    sender_name: Option<String>,
+    content: String,
+    /// Normalized by SQLite's `datetime()`.
+    created_at: String,
+}
+
+/// The next run of up to `batch_size` messages after the job's cursor, all
+/// from the same channel.
+async fn next_batch(
+    pool: &SqlitePool,
+    job_id: &str,
+    job: &BackfillJob,
+) -> anyhow::Result<Vec<BatchMessage>> {
+    let cursor = sqlx::query(
+        "SELECT cursor_channel_id, cursor_created_at, cursor_message_id \
+         FROM memory_backfills WHERE id = ?",
+    )
+    .bind(job_id)
+    .fetch_one(pool)
+    .await
+    .context("failed to load memory backfill cursor")?;
+    let cursor_channel: Option<String> = cursor.try_get("cursor_channel_id").ok().flatten();
+    let cursor_time: Option<String> = cursor.try_get("cursor_created_at").ok().flatten();
+    let cursor_id: Option<String> = cursor.try_get("cursor_message_id").ok().flatten();
+
+    let mut sql = String::from(
+        "SELECT id, channel_id, role, sender_name, content, datetime(created_at) AS ts \
+         FROM conversation_messages \
+         WHERE datetime(created_at) >= ? AND datetime(created_at) < ?",
+    );
+    if job.channel_id.is_some() {
+        sql.push_str(" AND channel_id = ?");
+    }
+    if cursor_id.is_some() {
+        sql.push_str(
+            " AND (channel_id > ? OR (channel_id = ? AND (datetime(created_at) > ? \
+             OR (datetime(created_at) = ? AND id > ?))))",
+        );
+    }
+    sql.push_str(" ORDER BY channel_id, datetime(created_at), id LIMIT ?");
+
+    let mut query = sqlx::query(&sql)
+        .bind(job.from.format(SQLITE_TIME_FORMAT).to_string())
+        .bind(job.to.format(SQLITE_TIME_FORMAT).to_string());
+    if let Some(channel_id) = &job.channel_id {
+        query = query.bind(channel_id);
+    }
+    if let (Some(channel), Some(time), Some(id)) = (&cursor_channel, &cursor_time, &cursor_id) {
+        query = query
+            .bind(channel)
+            .bind(channel)
+            .bind(time)
+            .bind(time)
+            .bind(id);
+    }
+    let rows = query
+        .bind(job.batch_size)
+        .fetch_all(pool)
+        .await
+        .context("failed to load memory backfill batch")?;
+
+    let mut batch: Vec<BatchMessage> = rows
+        .into_iter()
+        .map(|row| BatchMessage {
+            id: row.try_get("id").unwrap_or_default(),
+            channel_id: row.try_get("channel_id").unwrap_or_default(),
+            role: row.try_get("role").unwrap_or_default(),
+            sender_name: row.try_get("sender_name").ok().flatten(),
+            content: row.try_get("content").unwrap_or_default(),
+            created_at: row.try_get("ts").unwrap_or_default(),
+        })
+        .collect();
+    if let Some(first_channel) = batch.first().map(|message| message.channel_id.clone()) {
+        batch.retain(|message| message.channel_id == first_channel);
+    }
+    Ok(batch)
+}
+
+async fn count_messages(pool: &SqlitePool, request: &BackfillRequest) -> anyhow::Result<i64> {
+    let mut sql = String::from(
+        "SELECT COUNT(*) FROM conversation_messages \
+         WHERE datetime(created_at) >= ? AND datetime(created_at) < ?",
+    );
+    if request.channel_id.is_some() {
+        sql.push_str(" AND channel_id = ?");
+    }
+    let mut query = sqlx::query_scalar::<_, i64>(&sql)
+        .bind(request.from.format(SQLITE_TIME_FORMAT).to_string())
+        .bind(request.to.format(SQLITE_TIME_FORMAT).to_string());
+    if let Some(channel_id) = &request.channel_id {
+        query = query.bind(channel_id);
+    }
+    query
+        .fetch_one(pool)
+        .await
+        .context("failed to count messages to backfill")
+}
+
+/// Run one batch through the memory persistence prompt and tools. Returns
+/// the number of memories saved.
+async fn process_batch(
+    job_id: &str,
+    batch: &[BatchMessage],
+    batch_number: i64,
+    deps: &AgentDeps,
+) -> anyhow::Result<usize> {
+    let first = batch.first().expect("batch is not empty");
+    let last = batch.last().expect("batch is not empty");
+    let channel_id = first.channel_id.as_str();
+
+    let prompt_engine = deps.runtime_config.prompts.load();
+    let system_prompt = prompt_engine.render_static("memory_persistence")?;
+    let user_prompt = prompt_engine.render_system_memory_backfill_batch(
+        channel_id,
+        batch_number,
+        &first.created_at,
+        &last.created_at,
+        &format_transcript(batch),
+    )?;
+
+    let routing = deps.runtime_config.routing.load();
+    let model_name = routing.resolve(ProcessType::Branch, None).to_string();
+    let model = SpacebotModel::make(&deps.llm_manager, &model_name)
+        .with_context(&*deps.agent_id, "branch")
+        .with_worker_type("memory_backfill")
+        .with_routing((**routing).clone());
+
+    let contract_state = Arc::new(MemoryPersistenceContractState::for_backfill(
+        deps.sqlite_pool.clone(),
+        job_id,
+    ));
+    let tool_server = crate::tools::create_branch_tool_server(
+        None,
+        Some(channel_id),
+        deps.agent_id.clone(),
+        deps.task_store.clone(),
+        deps.memory_search.clone(),
+        deps.runtime_config.clone(),
+        deps.memory_event_tx.clone(),
+        crate::conversation::history::ConversationLogger::new(deps.sqlite_pool.clone()),
+        crate::conversation::ChannelStore::new(deps.sqlite_pool.clone()),
+        crate::conversation::ProcessRunLogger::new(deps.sqlite_pool.clone()),
+        crate::tools::BranchToolProfile::MemoryPersistence {
+            contract_state: contract_state.clone(),
+        },
+    );
+
+    let agent = AgentBuilder::new(model)
+        .preamble(&system_prompt)
+        .default_max_turns(**deps.runtime_config.branch_max_turns.load())
+        .tool_server_handle(tool_server)
+        .build();
+
+    let hook = SpacebotHook::new(
+        deps.agent_id.clone(),
+        ProcessId::Branch(Uuid::new_v4()),
+        ProcessType::Branch,
+        None,
+        deps.event_tx.clone(),
+    );
+
+    let mut history = Vec::new();
+    match hook.prompt_once(&agent, &mut history, &user_prompt).await {
+        Ok(_) => {}
+        Err(PromptError::MaxTurnsError { .. }) => {
+            anyhow::bail!("batch {batch_number} hit max turns");
+        }
+        Err(error) => return Err(error.into()),
+    }
+
+    if !contract_state.has_terminal_outcome() {
+        tracing::warn!(
+            batch_number,
+            %channel_id,
+            "memory backfill batch completed without memory_persistence_complete signal"
+        );
+    }
+
+    Ok(contract_state.saved_memory_ids().len())
+}
+
+fn format_transcript(batch: &[BatchMessage]) -> String {
+    let mut transcript = String::new();
+    for message in batch {
+        let speaker = message.sender_name.as_deref().unwrap_or(&message.role);
+        let content: String = message.content.chars().take(MAX_MESSAGE_CHARS).collect();
+        transcript.push_str(&format!("[{}] {speaker}: {content}\n", message.created_at));
+    }
+    transcript
+}
+
+async fn mark_failed(pool: &SqlitePool, job_id: &str, error: &str) -> anyhow::Result<()> {
+    sqlx::query(
+        "UPDATE memory_backfills SET status = 'failed', last_error = ?, \
+         updated_at = CURRENT_TIMESTAMP WHERE id = ? AND status = 'running'",
+    )
+    .bind(error)
+    .bind(job_id)
+    .execute(pool)
+    .await
+    .context("failed to mark memory backfill failed")?;
+    Ok(())
+}
+
+const JOB_COLUMNS: &str = "SELECT id, channel_id, from_time, to_time, batch_size, batch_delay_ms, \
+     status, total_messages, processed_messages, completed_batches, memories_saved, last_error, \
+     created_at, updated_at, completed_at FROM memory_backfills";
+
+fn row_to_job(row: &sqlx::sqlite::SqliteRow) -> Option<BackfillJob> {
+    let status: String = row.try_get("status").ok()?;
+    Some(BackfillJob {
+        id: row.try_get("id").ok()?,
+        channel_id: row.try_get("channel_id").ok().flatten(),
+        from: row.try_get("from_time").ok()?,
+        to: row.try_get("to_time").ok()?,
+        batch_size: row.try_get("batch_size").unwrap_or(DEFAULT_BATCH_SIZE),
+        batch_delay_ms: row.try_get("batch_delay_ms").unwrap_or_default(),
+        status: BackfillStatus::parse(&status)?,
+        total_messages: row.try_get("total_messages").unwrap_or_default(),
+        processed_messages: row.try_get("processed_messages").unwrap_or_default(),
+        completed_batches: row.try_get("completed_batches").unwrap_or_default(),
+        memories_saved: row.try_get("memories_saved").unwrap_or_default(),
+        last_error: row.try_get("last_error").ok().flatten(),
+        created_at: row.try_get("created_at").unwrap_or_else(|_| Utc::now()),
+        updated_at: row.try_get("updated_at").unwrap_or_else(|_| Utc::now()),
+        completed_at: row.try_get("completed_at").ok().flatten(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::{Memory, MemoryStore, MemoryType};
+
+    async fn insert_message(pool: &SqlitePool, id: &str, channel_id: &str, created_at: &str) {
+        sqlx::query(
+            "INSERT INTO conversation_messages (id, channel_id, role, content, created_at) \
+             VALUES (?, ?, 'user', 'hello', ?)",
+        )
+        .bind(id)
+        .bind(channel_id)
+        .bind(created_at)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn batches_stay_within_one_channel_and_follow_the_cursor() {
+        let store = MemoryStore::connect_in_memory().await;
+        let pool = store.pool();
+        insert_message(pool, "a1", "chan-a", "2025-01-01 10:00:00").await;
+        insert_message(pool, "a2", "chan-a", "2025-01-01T11:00:00Z").await;
+        insert_message(pool, "b1", "chan-b", "2025-01-01 09:00:00").await;
+        insert_message(pool, "late", "chan-a", "2025-06-01 00:00:00").await;
+
+        let request = BackfillRequest {
+            from: "2025-01-01T00:00:00Z".parse().unwrap(),
+            to: "2025-02-01T00:00:00Z".parse().unwrap(),
+            channel_id: None,
+            batch_size: 10,
+            batch_delay_ms: 0,
+        };
+        let job = create_job(pool, &request).await.unwrap().unwrap();
+        assert_eq!(job.total_messages, 3);
+        assert!(create_job(pool, &request).await.unwrap().is_none());
+
+        let first = next_batch(pool, &job.id, &job).await.unwrap();
+        let ids: Vec<&str> = first.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, ["a1", "a2"]);
+
+        sqlx::query(
+            "UPDATE memory_backfills SET cursor_channel_id = ?, cursor_created_at = ?, \
+             cursor_message_id = ? WHERE id = ?",
+        )
+        .bind("chan-a")
+        .bind(&first[1].created_at)
+        .bind("a2")
+        .bind(&job.id)
+        .execute(pool)
+        .await
+        .unwrap();
+
+        let second = next_batch(pool, &job.id, &job).await.unwrap();
+        assert_eq!(second.len(), 1);
+        assert_eq!(second[0].id, "b1");
+
+        assert!(cancel_job(pool, &job.id).await.unwrap());
+        assert!(reopen_job(pool, &job.id).await.unwrap());
+    }
+
+    struct FixedProvider;
+
+    #[async_trait::async_trait]
+    impl crate::memory::embedding::EmbeddingProvider for FixedProvider {
+        fn name(&self) -> &'static str {
+            "test"
+        }
+
+        fn model(&self) -> &str {
+            "fixed"
+        }
+
+        fn dimensions(&self) -> usize {
+            2
+        }
+
+        async fn embed(&self, texts: Vec<String>) -> crate::error::Result<Vec<Vec<f32>>> {
+            Ok(texts.iter().map(|_| vec![1.0, 0.0]).collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn unfinished_batches_are_rolled_back_before_running_again() {
+        let lance_dir = tempfile::tempdir().unwrap();
+        let connection = lancedb::connect(lance_dir.path().to_str().unwrap())
+            .execute()
+            .await
+            .unwrap();
+        let search = MemorySearch::new(
+            MemoryStore::connect_in_memory().await,
+            crate::memory::EmbeddingTable::open_or_create(&connection, 2)
+                .await
+                .unwrap(),
+            Arc::new(crate::memory::EmbeddingModel::from_provider(Arc::new(
+                FixedProvider,
+            ))),
+        );
+        let pool = search.store().pool().clone();
+
+        let kept = Memory::new("saved by a finished batch", MemoryType::Fact);
+        let partial = Memory::new("saved before the crash", MemoryType::Fact);
+        search.store().save(&kept).await.unwrap();
+        journal_save(&pool, "job", &partial.id).await.unwrap();
+        search.store().save(&partial).await.unwrap();
+        // Journaled, but the process stopped before the memory was written.
+        journal_save(&pool, "job", "never-written").await.unwrap();
+        journal_save(&pool, "other-job", &kept.id).await.unwrap();
+
+        assert_eq!(
+            roll_back_uncommitted(&pool, &search, "job").await.unwrap(),
+            1
+        );
+        assert!(search.store().load(&partial.id).await.unwrap().is_none());
+        assert!(search.store().load(&kept.id).await.unwrap().is_some());
+        let journaled: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM memory_backfill_saves")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(journaled, 1);
+    }
+}
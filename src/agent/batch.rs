@@ -0,0 +1,826 @@
+//! Batch jobs: one task template run over many items, offline.
+//!
+//! A job takes its items from a workspace file or a full-text search over
+//! stored conversation, renders the task template for each, and hands each
+//! rendered task to a detached worker. Items live in SQLite as the job's
+//! queue: at most `concurrency` are in flight at once, worker starts are
+//! spaced by `item_delay_ms`, and a failed item is retried once before it's
+//! recorded as failed. A restart puts in-flight items back in the queue and
+//! resumes the job. Items are only handed out on the replica holding the
+//! agent's leader lease.
+//!
+//! When the queue drains (or the job is cancelled), every item's input,
+//! status, and output are written in order to `batch/<job id>.jsonl` in the
+//! agent's workspace.
+
+use crate::AgentDeps;
+use crate::agent::worker::Worker;
+use crate::conversation::history::{ConversationLogger, ProcessRunLogger};
+use crate::{ProcessEvent, WorkerId};
+
+use anyhow::Context as _;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{Row as _, SqlitePool};
+use uuid::Uuid;
+
+use std::collections::HashSet;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+/// Attempts per item before it is recorded as failed.
+const MAX_ITEM_ATTEMPTS: i64 = 2;
+
+/// Largest job accepted, in items.
+pub const MAX_ITEMS: usize = 50_000;
+
+pub const DEFAULT_CONCURRENCY: i64 = 4;
+pub const DEFAULT_ITEM_DELAY_MS: u64 = 1_000;
+pub const DEFAULT_QUERY_LIMIT: i64 = 1_000;
+
+/// How often a job on a follower replica checks whether it has become leader.
+const LEADER_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Jobs with a running task in this process, so a resume can't start a
+/// second task for a job that is already being worked.
+static ACTIVE_JOBS: LazyLock<Mutex<HashSet<String>>> = LazyLock::new(|| Mutex::new(HashSet::new()));
+
+/// Lifecycle of a batch job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchStatus {
+    Running,
+    /// Every item was attempted; some may have failed.
+    Completed,
+    /// The job itself broke (e.g. the output couldn't be written).
+    Failed,
+    Cancelled,
+}
+
+impl BatchStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            BatchStatus::Running => "running",
+            BatchStatus::Completed => "completed",
+            BatchStatus::Failed => "failed",
+            BatchStatus::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "running" => Some(BatchStatus::Running),
+            "completed" => Some(BatchStatus::Completed),
+            "failed" => Some(BatchStatus::Failed),
+            "cancelled" => Some(BatchStatus::Cancelled),
+            _ => None,
+        }
+    }
+}
+
+/// Where a job's items come from.
+#[derive(Debug, Clone)]
+pub enum BatchSource {
+    /// A file in the agent's workspace: JSON Lines (`.jsonl`), a JSON array
+    /// (`.json`), or one item per non-empty line.
+    File { path: String },
+    /// Stored conversation messages matching a full-text search, best match
+    /// first.
+    Query {
+        query: String,
+        channel_id: Option<String>,
+        limit: i64,
+    },
+}
+
+impl BatchSource {
+    fn kind(&self) -> &'static str {
+        match self {
+            BatchSource::File { .. } => "file",
+            BatchSource::Query { .. } => "query",
+        }
+    }
+
+    fn detail(&self) -> &str {
+        match self {
+            BatchSource::File { path } => path,
+            BatchSource::Query { query, .. } => query,
+        }
+    }
+}
+
+/// A batch job and its progress.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct BatchJob {
+    pub id: String,
+    pub template: String,
+    /// `file` or `query`.
+    pub source: String,
+    /// The input path or search text.
+    pub source_detail: String,
+    pub concurrency: i64,
+    pub item_delay_ms: i64,
+    pub status: BatchStatus,
+    pub total_items: i64,
+    pub completed_items: i64,
+    pub failed_items: i64,
+    /// Items waiting for or held by a worker.
+    pub pending_items: i64,
+    /// Results file, relative to the workspace. Set once the job stops.
+    pub output_path: Option<String>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+/// Parameters for a new batch job.
+#[derive(Debug, Clone)]
+pub struct BatchRequest {
+    /// Task for each item. `{{item}}` and `{{index}}` are replaced; without
+    /// `{{item}}` the item is appended.
+    pub template: String,
+    pub source: BatchSource,
+    pub concurrency: i64,
+    pub item_delay_ms: u64,
+}
+
+/// Read the items a source names. Fails if there are none or more than
+/// [`MAX_ITEMS`].
+pub async fn load_items(
+    pool: &SqlitePool,
+    workspace_dir: &Path,
+    source: &BatchSource,
+) -> anyhow::Result<Vec<String>> {
+    let items = match source {
+        BatchSource::File { path } => {
+            let relative = Path::new(path);
+            if relative.is_absolute()
+                || relative
+                    .components()
+                    .any(|component| !matches!(component, Component::Normal(_)))
+            {
+                anyhow::bail!("input file must be a relative path inside the workspace");
+            }
+            let contents = tokio::fs::read_to_string(workspace_dir.join(relative))
+                .await
+                .with_context(|| format!("failed to read {path}"))?;
+            parse_items(path, &contents)?
+        }
+        BatchSource::Query {
+            query,
+            channel_id,
+            limit,
+        } => ConversationLogger::new(pool.clone())
+            .search_messages(query, channel_id.as_deref(), *limit)
+            .await?
+            .into_iter()
+            .map(|hit| {
+                let message = hit.message;
+                let sender = message.sender_name.as_deref().unwrap_or(&message.role);
+                format!(
+                    "[{}] {sender} in {}:\n{}",
+                    message.created_at.format("%Y-%m-%d %H:%M"),
+                    message.channel_id,
+                    message.content
+                )
+            })
+            .collect(),
+    };
+
+    if items.is_empty() {
+        anyhow::bail!("the input has no items");
+    }
+    if items.len() > MAX_ITEMS {
+        anyhow::bail!(
+            "the input has {} items; the limit is {MAX_ITEMS}",
+            items.len()
+        );
+    }
+    Ok(items)
+}
+
+fn parse_items(path: &str, contents: &str) -> anyhow::Result<Vec<String>> {
+    let lines = contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty());
+    if path.ends_with(".jsonl") {
+        lines
+            .enumerate()
+            .map(|(index, line)| {
+                serde_json::from_str::<serde_json::Value>(line)
+                    .with_context(|| format!("line {} is not valid JSON", index + 1))
+                    .map(|value| json_item(&value))
+            })
+            .collect()
+    } else if path.ends_with(".json") {
+        let values: Vec<serde_json::Value> =
+            serde_json::from_str(contents).context("expected a JSON array of items")?;
+        Ok(values.iter().map(json_item).collect())
+    } else {
+        Ok(lines.map(str::to_string).collect())
+    }
+}
+
+/// Strings go in as-is; anything else as compact JSON.
+fn json_item(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// The task a worker gets for one item.
+pub fn render_task(template: &str, index: i64, item: &str) -> String {
+    let task = template.replace("{{index}}", &index.to_string());
+    if task.contains("{{item}}") {
+        task.replace("{{item}}", item)
+    } else {
+        format!("{task}\n\n{item}")
+    }
+}
+
+/// Record a new job with its items queued.
+pub async fn create_job(
+    pool: &SqlitePool,
+    request: &BatchRequest,
+    items: &[String],
+) -> anyhow::Result<Option<BatchJob>> {
+    let id = Uuid::new_v4().to_string();
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("failed to start batch job transaction")?;
+    sqlx::query(
+        "INSERT INTO batch_jobs \
+         (id, template, source, source_detail, concurrency, item_delay_ms, total_items) \
+         VALUES (?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&id)
+    .bind(&request.template)
+    .bind(request.source.kind())
+    .bind(request.source.detail())
+    .bind(request.concurrency)
+    .bind(request.item_delay_ms as i64)
+    .bind(items.len() as i64)
+    .execute(&mut *transaction)
+    .await
+    .context("failed to create batch job")?;
+    for (index, item) in items.iter().enumerate() {
+        sqlx::query("INSERT INTO batch_items (job_id, item_index, input) VALUES (?, ?, ?)")
+            .bind(&id)
+            .bind(index as i64)
+            .bind(item)
+            .execute(&mut *transaction)
+            .await
+            .context("failed to queue batch item")?;
+    }
+    transaction
+        .commit()
+        .await
+        .context("failed to commit batch job")?;
+
+    get_job(pool, &id).await
+}
+
+pub async fn get_job(pool: &SqlitePool, id: &str) -> anyhow::Result<Option<BatchJob>> {
+    let row = sqlx::query(&format!("{JOB_COLUMNS} WHERE id = ?"))
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .context("failed to load batch job")?;
+    Ok(row.as_ref().and_then(row_to_job))
+}
+
+/// Jobs, newest first.
+pub async fn list_jobs(pool: &SqlitePool, limit: i64) -> anyhow::Result<Vec<BatchJob>> {
+    let rows = sqlx::query(&format!("{JOB_COLUMNS} ORDER BY created_at DESC LIMIT ?"))
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+        .context("failed to list batch jobs")?;
+    Ok(rows.iter().filter_map(row_to_job).collect())
+}
+
+/// Stop handing out items. Items already with a worker finish, then the
+/// results so far are written out. Returns false if the job doesn't exist
+/// or isn't running.
+pub async fn cancel_job(pool: &SqlitePool, id: &str) -> anyhow::Result<bool> {
+    let result = sqlx::query(
+        "UPDATE batch_jobs SET status = 'cancelled', updated_at = CURRENT_TIMESTAMP \
+         WHERE id = ? AND status = 'running'",
+    )
+    .bind(id)
+    .execute(pool)
+    .await
+    .context("failed to cancel batch job")?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Spawn the task working a running job. Does nothing if the job is already
+/// being worked in this process.
+pub fn spawn_job(job_id: String, deps: AgentDeps) -> Option<tokio::task::JoinHandle<()>> {
+    if !ACTIVE_JOBS
+        .lock()
+        .expect("batch registry poisoned")
+        .insert(job_id.clone())
+    {
+        return None;
+    }
+
+    Some(tokio::spawn(async move {
+        if let Err(error) = run_job(&job_id, &deps).await {
+            tracing::error!(%error, agent_id = %deps.agent_id, %job_id, "batch job exited with error");
+            let _ = mark_failed(&deps.sqlite_pool, &job_id, &error.to_string()).await;
+        }
+        ACTIVE_JOBS
+            .lock()
+            .expect("batch registry poisoned")
+            .remove(&job_id);
+    }))
+}
+
+/// Resume jobs left running by a previous process. On a follower replica
+/// the jobs wait for leadership before re-queueing the items a previous
+/// leader had in flight or handing out new ones.
+pub async fn resume_running_jobs(deps: &AgentDeps) -> anyhow::Result<usize> {
+    let ids: Vec<String> = sqlx::query_scalar("SELECT id FROM batch_jobs WHERE status = 'running'")
+        .fetch_all(&deps.sqlite_pool)
+        .await
+        .context("failed to find running batch jobs")?;
+    let count = ids.len();
+    for id in ids {
+        tracing::info!(agent_id = %deps.agent_id, job_id = %id, "resuming batch job");
+        spawn_job(id, deps.clone());
+    }
+    Ok(count)
+}
+
+/// An item handed to a worker.
+#[derive(Debug, Clone)]
+struct ClaimedItem {
+    index: i64,
+    input: String,
+    attempts: i64,
+}
+
+#[tracing::instrument(skip(deps), fields(agent_id = %deps.agent_id))]
+async fn run_job(job_id: &str, deps: &AgentDeps) -> anyhow::Result<()> {
+    let mut in_flight = tokio::task::JoinSet::new();
+    let mut last_start: Option<Instant> = None;
+    let mut requeued = false;
+
+    loop {
+        let Some(job) = get_job(&deps.sqlite_pool, job_id).await? else {
+            return Ok(());
+        };
+        let running = job.status == BatchStatus::Running;
+        // While the agent is paused, or this replica isn't the leader, items
+        // already with a worker finish but no new ones are handed out.
+        let paused = deps.runtime_config.is_paused();
+        let leading = deps.leader.is_leader();
+
+        // Items marked running before this task claimed anything belonged to
+        // a process that is gone. Only the leader puts them back, so a
+        // follower starting up can't re-run the leader's in-flight items.
+        if running && leading && !requeued {
+            requeue_interrupted_items(&deps.sqlite_pool, job_id).await?;
+            requeued = true;
+        }
+
+        while running && !paused && leading && in_flight.len() < job.concurrency.max(1) as usize {
+            if let Some(started) = last_start {
+                let delay = Duration::from_millis(job.item_delay_ms.max(0) as u64);
+                tokio::time::sleep(delay.saturating_sub(started.elapsed())).await;
+            }
+            let Some(item) = claim_next_item(&deps.sqlite_pool, job_id).await? else {
+                break;
+            };
+            last_start = Some(Instant::now());
+
+            let deps = deps.clone();
+            let task = render_task(&job.template, item.index, &item.input);
+            in_flight.spawn(async move {
+                let result = run_item(&deps, &task).await;
+                (item, result)
+            });
+        }
+
+        let Some(joined) = in_flight.join_next().await else {
+            if running && paused {
+                deps.runtime_config.wait_until_resumed().await;
+                continue;
+            }
+            if running && !leading {
+                tokio::time::sleep(LEADER_POLL_INTERVAL).await;
+                continue;
+            }
+            return finish_job(deps, job_id).await;
+        };
+        match joined {
+            Ok((item, result)) => record_item(&deps.sqlite_pool, job_id, &item, result).await?,
+            Err(error) => {
+                tracing::error!(%error, "batch worker task panicked");
+            }
+        }
+    }
+}
+
+/// Put the items a previous process had with workers back in the queue.
+async fn requeue_interrupted_items(pool: &SqlitePool, job_id: &str) -> anyhow::Result<()> {
+    sqlx::query(
+        "UPDATE batch_items SET status = 'pending', updated_at = CURRENT_TIMESTAMP \
+         WHERE job_id = ? AND status = 'running'",
+    )
+    .bind(job_id)
+    .execute(pool)
+    .await
+    .context("failed to re-queue interrupted batch items")?;
+    Ok(())
+}
+
+/// Move the next pending item to running. Only this job's task claims its
+/// items, so the read and the update don't race.
+async fn claim_next_item(pool: &SqlitePool, job_id: &str) -> anyhow::Result<Option<ClaimedItem>> {
+    let row = sqlx::query(
+        "SELECT item_index, input, attempts FROM batch_items \
+         WHERE job_id = ? AND status = 'pending' ORDER BY item_index LIMIT 1",
+    )
+    .bind(job_id)
+    .fetch_optional(pool)
+    .await
+    .context("failed to load next batch item")?;
+    let Some(row) = row else {
+        return Ok(None);
+    };
+    let item = ClaimedItem {
+        index: row.try_get("item_index")?,
+        input: row.try_get("input")?,
+        attempts: row.try_get::<i64, _>("attempts")? + 1,
+    };
+
+    sqlx::query(
+        "UPDATE batch_items SET status = 'running', attempts = ?, updated_at = CURRENT_TIMESTAMP \
+         WHERE job_id = ? AND item_index = ?",
+    )
+    .bind(item.attempts)
+    .bind(job_id)
+    .bind(item.index)
+    .execute(pool)
+    .await
+    .context("failed to claim batch item")?;
+    Ok(Some(item))
+}
+
+async fn record_item(
+    pool: &SqlitePool,
+    job_id: &str,
+    item: &ClaimedItem,
+    result: anyhow::Result<String>,
+) -> anyhow::Result<()> {
+    let (status, output, error) = match result {
+        Ok(output) => ("done", Some(output), None),
+        Err(error) if item.attempts < MAX_ITEM_ATTEMPTS => {
+            tracing::warn!(%error, item = item.index, "batch item failed, re-queueing");
+            ("pending", None, Some(error.to_string()))
+        }
+        Err(error) => {
+            tracing::warn!(%error, item = item.index, "batch item failed");
+            ("failed", None, Some(error.to_string()))
+        }
+    };
+
+    sqlx::query(
+        "UPDATE batch_items SET status = ?, output = ?, error = ?, updated_at = CURRENT_TIMESTAMP \
+         WHERE job_id = ? AND item_index = ?",
+    )
+    .bind(status)
+    .bind(output)
+    .bind(error)
+    .bind(job_id)
+    .bind(item.index)
+    .execute(pool)
+    .await
+    .context("failed to record batch item")?;
+    update_counts(pool, job_id).await
+}
+
+async fn update_counts(pool: &SqlitePool, job_id: &str) -> anyhow::Result<()> {
+    sqlx::query(
+        "UPDATE batch_jobs SET \
+         completed_items = (SELECT COUNT(*) FROM batch_items WHERE job_id = ?1 AND status = 'done'), \
+         failed_items = (SELECT COUNT(*) FROM batch_items WHERE job_id = ?1 AND status = 'failed'), \
+         updated_at = CURRENT_TIMESTAMP \
+         WHERE id = ?1",
+    )
+    .bind(job_id)
+    .execute(pool)
+    .await
+    .context("failed to update batch progress")?;
+    Ok(())
+}
+
+/// Run one rendered task on a detached worker and return its result.
+async fn run_item(deps: &AgentDeps, task: &str) -> anyhow::Result<String> {
+    let rc = &deps.runtime_config;
+    let prompt_engine = rc.prompts.load();
+    let secrets = rc.secrets.load();
+    let tool_secret_names = match (*secrets).as_ref() {
+        Some(store) => store.tool_secret_names(),
+        None => Vec::new(),
+    };
+    let browser_config = (**rc.browser_config.load()).clone();
+    let system_info = crate::agent::status::SystemInfo::from_runtime_config(rc, &deps.sandbox);
+    let temporal_context = crate::agent::channel_prompt::TemporalContext::from_runtime(rc.as_ref());
+    let system_prompt = prompt_engine
+        .render_worker_prompt(
+            &rc.instance_dir.display().to_string(),
+            &rc.workspace_dir.display().to_string(),
+            deps.sandbox.mode_enabled(),
+            deps.sandbox.containment_active(),
+            deps.sandbox.prompt_read_allowlist(),
+            deps.sandbox.prompt_write_allowlist(),
+            &tool_secret_names,
+            browser_config.persist_session,
+            Some(system_info.render_for_worker(&temporal_context.current_time_line())),
+        )
+        .map_err(|error| anyhow::anyhow!("failed to render worker prompt: {error}"))?;
+
+    let spacebot_dir = rc.workspace_dir.join(".spacebot");
+    let (worker, inject_tx) = Worker::new(
+        None,
+        task,
+        system_prompt,
+        deps.clone(),
+        browser_config,
+        spacebot_dir.join("screenshots"),
+        (**rc.brave_search_key.load()).clone(),
+        spacebot_dir.join("logs"),
+    );
+    // Batch items take no mid-flight context.
+    drop(inject_tx);
+
+    let worker_id: WorkerId = worker.id;
+    let summary: String = task.chars().take(200).collect();
+    let run_logger = ProcessRunLogger::new(deps.sqlite_pool.clone());
+    run_logger.log_worker_started(
+        None,
+        worker_id,
+        &summary,
+        "batch",
+        &deps.agent_id,
+        false,
+        None,
+    );
+    let _ = deps.event_tx.send(ProcessEvent::WorkerStarted {
+        agent_id: deps.agent_id.clone(),
+        worker_id,
+        channel_id: None,
+        task: summary,
+        worker_type: "batch".to_string(),
+        interactive: false,
+        directory: None,
+    });
+
+    let result = worker.run().await.map_err(|error| anyhow::anyhow!(error));
+    let scrub = |text: String| {
+        let text = match (*secrets).as_ref() {
+            Some(store) => crate::secrets::scrub::scrub_with_store(&text, store),
+            None => text,
+        };
+        crate::secrets::scrub::scrub_leaks(&text)
+    };
+    let (text, success) = match &result {
+        Ok(output) => (scrub(output.clone()), true),
+        Err(error) => (scrub(error.to_string()), false),
+    };
+    run_logger.log_worker_completed(worker_id, &text, success);
+    let _ = deps.event_tx.send(ProcessEvent::WorkerComplete {
+        agent_id: deps.agent_id.clone(),
+        worker_id,
+        channel_id: None,
+        result: text.clone(),
+        notify: false,
+        success,
+    });
+
+    if success {
+        Ok(text)
+    } else {
+        Err(anyhow::anyhow!(text))
+    }
+}
+
+/// Write the results file and close the job: completed if it was still
+/// running, otherwise left as cancelled.
+async fn finish_job(deps: &AgentDeps, job_id: &str) -> anyhow::Result<()> {
+    let pool = &deps.sqlite_pool;
+    // Nothing is in flight any more, so a running item lost its worker.
+    sqlx::query(
+        "UPDATE batch_items SET status = 'failed', error = 'worker exited without a result', \
+         updated_at = CURRENT_TIMESTAMP WHERE job_id = ? AND status = 'running'",
+    )
+    .bind(job_id)
+    .execute(pool)
+    .await
+    .context("failed to close out batch items")?;
+    update_counts(pool, job_id).await?;
+
+    let relative = write_output(pool, &deps.runtime_config.workspace_dir, job_id).await?;
+    sqlx::query(
+        "UPDATE batch_jobs SET output_path = ?, \
+         status = CASE WHEN status = 'running' THEN 'completed' ELSE status END, \
+         updated_at = CURRENT_TIMESTAMP, completed_at = CURRENT_TIMESTAMP WHERE id = ?",
+    )
+    .bind(relative.display().to_string())
+    .bind(job_id)
+    .execute(pool)
+    .await
+    .context("failed to complete batch job")?;
+
+    if let Some(job) = get_job(pool, job_id).await? {
+        tracing::info!(
+            status = job.status.as_str(),
+            completed = job.completed_items,
+            failed = job.failed_items,
+            output = %relative.display(),
+            "batch job finished"
+        );
+    }
+    Ok(())
+}
+
+/// One line of the results file.
+#[derive(Debug, Serialize)]
+struct OutputLine {
+    index: i64,
+    input: String,
+    status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    output: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Write every item in order to `batch/<job id>.jsonl` under the workspace.
+/// Returns the path relative to the workspace.
+async fn write_output(
+    pool: &SqlitePool,
+    workspace_dir: &Path,
+    job_id: &str,
+) -> anyhow::Result<PathBuf> {
+    let rows = sqlx::query(
+        "SELECT item_index, input, status, output, error FROM batch_items \
+         WHERE job_id = ? ORDER BY item_index",
+    )
+    .bind(job_id)
+    .fetch_all(pool)
+    .await
+    .context("failed to load batch results")?;
+
+    let mut contents = String::new();
+    for row in rows {
+        let line = OutputLine {
+            index: row.try_get("item_index")?,
+            input: row.try_get("input")?,
+            status: row.try_get("status")?,
+            output: row.try_get("output").ok().flatten(),
+            error: row.try_get("error").ok().flatten(),
+        };
+        contents.push_str(&serde_json::to_string(&line)?);
+        contents.push('\n');
+    }
+
+    let relative = Path::new("batch").join(format!("{job_id}.jsonl"));
+    let path = workspace_dir.join(&relative);
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    tokio::fs::write(&path, contents)
+        .await
+        .with_context(|| format!("failed to write {}", path.display()))?;
+    Ok(relative)
+}
+
+async fn mark_failed(pool: &SqlitePool, job_id: &str, error: &str) -> anyhow::Result<()> {
+    sqlx::query(
+        "UPDATE batch_jobs SET status = 'failed', last_error = ?, \
+         updated_at = CURRENT_TIMESTAMP, completed_at = CURRENT_TIMESTAMP \
+         WHERE id = ? AND status IN ('running', 'cancelled')",
+    )
+    .bind(error)
+    .bind(job_id)
+    .execute(pool)
+    .await
+    .context("failed to mark batch job failed")?;
+    Ok(())
+}
+
+const JOB_COLUMNS: &str = "SELECT id, template, source, source_detail, concurrency, item_delay_ms, \
+     status, total_items, completed_items, failed_items, output_path, last_error, \
+     created_at, updated_at, completed_at FROM batch_jobs";
+
+fn row_to_job(row: &sqlx::sqlite::SqliteRow) -> Option<BatchJob> {
+    let status: String = row.try_get("status").ok()?;
+    let total_items: i64 = row.try_get("total_items").unwrap_or_default();
+    let completed_items: i64 = row.try_get("completed_items").unwrap_or_default();
+    let failed_items: i64 = row.try_get("failed_items").unwrap_or_default();
+    Some(BatchJob {
+        id: row.try_get("id").ok()?,
+        template: row.try_get("template").unwrap_or_default(),
+        source: row.try_get("source").unwrap_or_default(),
+        source_detail: row.try_get("source_detail").unwrap_or_default(),
+        concurrency: row.try_get("concurrency").unwrap_or(DEFAULT_CONCURRENCY),
+        item_delay_ms: row.try_get("item_delay_ms").unwrap_or_default(),
+        status: BatchStatus::parse(&status)?,
+        total_items,
+        completed_items,
+        failed_items,
+        pending_items: (total_items - completed_items - failed_items).max(0),
+        output_path: row.try_get("output_path").ok().flatten(),
+        last_error: row.try_get("last_error").ok().flatten(),
+        created_at: row.try_get("created_at").unwrap_or_else(|_| Utc::now()),
+        updated_at: row.try_get("updated_at").unwrap_or_else(|_| Utc::now()),
+        completed_at: row.try_get("completed_at").ok().flatten(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::MemoryStore;
+
+    #[tokio::test]
+    async fn items_queue_retry_and_write_out_in_order() {
+        assert_eq!(
+            parse_items("tickets.jsonl", "{\"id\":1}\n\n\"plain\"\n").unwrap(),
+            ["{\"id\":1}", "plain"]
+        );
+        assert!(parse_items("tickets.jsonl", "{oops").is_err());
+        assert_eq!(
+            parse_items("tickets.txt", "a\n  \nb\n").unwrap(),
+            ["a", "b"]
+        );
+        assert_eq!(
+            render_task("Triage #{{index}}: {{item}}", 3, "printer"),
+            "Triage #3: printer"
+        );
+        assert_eq!(
+            render_task("Triage this", 0, "printer"),
+            "Triage this\n\nprinter"
+        );
+
+        let store = MemoryStore::connect_in_memory().await;
+        let pool = store.pool();
+        let request = BatchRequest {
+            template: "Summarize {{item}}".to_string(),
+            source: BatchSource::File {
+                path: "tickets.txt".to_string(),
+            },
+            concurrency: 2,
+            item_delay_ms: 0,
+        };
+        let items = vec!["first".to_string(), "second".to_string()];
+        let job = create_job(pool, &request, &items).await.unwrap().unwrap();
+        assert_eq!(job.total_items, 2);
+        assert_eq!(job.pending_items, 2);
+
+        let first = claim_next_item(pool, &job.id).await.unwrap().unwrap();
+        let second = claim_next_item(pool, &job.id).await.unwrap().unwrap();
+        assert_eq!((first.index, second.index), (0, 1));
+        assert!(claim_next_item(pool, &job.id).await.unwrap().is_none());
+
+        record_item(pool, &job.id, &first, Ok("done".to_string()))
+            .await
+            .unwrap();
+        record_item(pool, &job.id, &second, Err(anyhow::anyhow!("timeout")))
+            .await
+            .unwrap();
+        let retry = claim_next_item(pool, &job.id).await.unwrap().unwrap();
+        assert_eq!((retry.index, retry.attempts), (1, 2));
+        record_item(pool, &job.id, &retry, Err(anyhow::anyhow!("timeout")))
+            .await
+            .unwrap();
+
+        let job = get_job(pool, &job.id).await.unwrap().unwrap();
+        assert_eq!(
+            (job.completed_items, job.failed_items, job.pending_items),
+            (1, 1, 0)
+        );
+
+        let workspace = tempfile::tempdir().unwrap();
+        let relative = write_output(pool, workspace.path(), &job.id).await.unwrap();
+        let output = std::fs::read_to_string(workspace.path().join(relative)).unwrap();
+        let lines: Vec<serde_json::Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines[0]["output"], "done");
+        assert_eq!(lines[1]["status"], "failed");
+        assert_eq!(lines[1]["error"], "timeout");
+    }
+}
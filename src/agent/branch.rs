@@ -220,6 +220,7 @@ impl Branch {
                         self.hook.set_completion_contract_request_active(false);
                     }
                     tracing::error!(branch_id = %self.id, %error, "branch LLM call failed");
+                    self.flush_pending_embeddings().await;
                     return Err(crate::error::AgentError::Other(error.into()).into());
                 }
             }
@@ -228,6 +229,7 @@ impl Branch {
         if enforce_memory_contract {
             self.hook.set_completion_contract_request_active(false);
         }
+        self.flush_pending_embeddings().await;
 
         // Scrub tool secret values from the conclusion before sending to the
         // channel. Branches can spawn workers whose output may contain secrets.
@@ -240,6 +242,22 @@ impl Branch {
         };
         let conclusion = crate::secrets::scrub::scrub_leaks(&conclusion);
 
+        // Credit recalled memories the conclusion actually drew on, so the
+        // cortex can recalibrate importance from real usage.
+        let recalled = recalled_memories(&self.history);
+        if !recalled.is_empty() {
+            let cited = crate::memory::feedback::cited_memory_ids(&conclusion, &recalled);
+            if let Err(error) = crate::memory::feedback::record_signals(
+                self.deps.memory_search.store(),
+                &cited,
+                crate::memory::feedback::MemorySignal::Cited,
+            )
+            .await
+            {
+                tracing::warn!(branch_id = %self.id, %error, "failed to record memory citations");
+            }
+        }
+
         // Send conclusion back to the channel
         let _ = self.deps.event_tx.send(ProcessEvent::BranchResult {
             agent_id: self.deps.agent_id.clone(),
@@ -253,6 +271,24 @@ impl Branch {
         Ok(conclusion)
     }
 
+    /// Embed memories a persistence run saved but never flushed, e.g. when it
+    /// ended without calling memory_persistence_complete.
+    async fn flush_pending_embeddings(&self) {
+        let Some(contract_state) = &self.memory_persistence_contract else {
+            return;
+        };
+        let failed = contract_state
+            .flush_pending_embeddings(&self.deps.memory_search)
+            .await;
+        if !failed.is_empty() {
+            tracing::warn!(
+                branch_id = %self.id,
+                discarded = ?failed,
+                "discarded memories whose embeddings could not be computed"
+            );
+        }
+    }
+
     /// Compact history if approaching context window limit.
     /// Removes the oldest 50% of messages when usage exceeds 70%.
     fn maybe_compact_history(&mut self) {
@@ -302,3 +338,46 @@ impl Branch {
         self.history.insert(0, rig::message::Message::from(marker));
     }
 }
+
+/// `(id, content)` of every memory returned by `memory_recall` during the
+/// branch, read back from the tool results in history.
+fn recalled_memories(history: &[rig::message::Message]) -> Vec<(String, String)> {
+    let mut recalled = Vec::new();
+    for message in history {
+        let rig::message::Message::User { content } = message else {
+            continue;
+        };
+        for item in content.iter() {
+            let rig::message::UserContent::ToolResult(result) = item else {
+                continue;
+            };
+            for part in result.content.iter() {
+                let rig::message::ToolResultContent::Text(text) = part else {
+                    continue;
+                };
+                let Ok(mut value) = serde_json::from_str::<serde_json::Value>(&text.text) else {
+                    continue;
+                };
+                // Tool output may arrive double-encoded as a JSON string.
+                if let Some(inner) = value.as_str()
+                    && let Ok(parsed) = serde_json::from_str(inner)
+                {
+                    value = parsed;
+                }
+                let Some(memories) = value.get("memories").and_then(|m| m.as_array()) else {
+                    continue;
+                };
+                for memory in memories {
+                    if let (Some(id), Some(content)) = (
+                        memory.get("id").and_then(|v| v.as_str()),
+                        memory.get("content").and_then(|v| v.as_str()),
+                    ) && !recalled.iter().any(|(seen, _)| seen == id)
+                    {
+                        recalled.push((id.to_string(), content.to_string()));
+                    }
+                }
+            }
+        }
+    }
+    recalled
+}
@@ -12,13 +12,19 @@ use crate::agent::channel_prompt::{
     MAX_RETRIGGERS_PER_TURN, RETRIGGER_DEBOUNCE_MS, RETRIGGER_MAX_TURNS, TemporalContext,
 };
 use crate::agent::compactor::Compactor;
+use crate::agent::event_log::{ChannelEvent, EventLog};
+use crate::agent::faq;
 use crate::agent::process_control::ControlActionResult;
+use crate::agent::prompt_cache::{PromptSection, content_hash};
+use crate::agent::speculative;
 use crate::agent::status::{StatusBlock, SystemInfo};
 use crate::agent::worker::Worker;
 use crate::conversation::{ChannelStore, ConversationLogger, ProcessRunLogger};
 use crate::error::{AgentError, Result};
 use crate::hooks::SpacebotHook;
 use crate::llm::SpacebotModel;
+use crate::llm::smart_router::{Tier, TurnSignals};
+use crate::messaging::commands::SlashCommand;
 use crate::{
     AgentDeps, BranchId, ChannelId, InboundMessage, OutboundResponse, ProcessEvent, ProcessId,
     ProcessType, RoutedResponse, RoutedSender, WorkerId,
@@ -110,6 +116,12 @@ pub struct ChannelState {
     pub process_run_logger: ProcessRunLogger,
     /// Discord message ID to reply to for work spawned in the current turn.
     pub reply_target_message_id: Arc<RwLock<Option<String>>>,
+    /// Language of the user's latest message, set when auto-translation is
+    /// on. Replies are translated back into it.
+    pub user_language: Arc<RwLock<Option<String>>>,
+    /// Memory context scope of the person who sent the latest message. A
+    /// std lock so branch tool servers can read it while being built.
+    pub user_scope: Arc<std::sync::RwLock<Option<String>>>,
     pub channel_store: ChannelStore,
     pub screenshot_dir: std::path::PathBuf,
     pub logs_dir: std::path::PathBuf,
@@ -240,7 +252,7 @@ impl ChannelState {
         };
 
         self.process_run_logger
-            .log_worker_completed(worker_id, &result, false);
+            .log_worker_cancelled(worker_id, &result);
         if let Err(error) = self.deps.event_tx.send(ProcessEvent::WorkerComplete {
             agent_id: self.deps.agent_id.clone(),
             worker_id,
@@ -398,6 +410,8 @@ pub struct Channel {
     pub deps: AgentDeps,
     pub hook: SpacebotHook,
     pub state: ChannelState,
+    /// Ordered inbound/response/tool log for `spacebot replay`.
+    event_log: EventLog,
     /// Per-channel tool server (isolated from other channels).
     pub tool_server: rig::tool::server::ToolServerHandle,
     /// Input channel for receiving messages.
@@ -500,6 +514,12 @@ impl Channel {
             Some(id.clone()),
             deps.event_tx.clone(),
         );
+        let event_log = EventLog::new(
+            deps.sqlite_pool.clone(),
+            id.clone(),
+            deps.runtime_config.clone(),
+        );
+        let hook = hook.with_event_log(event_log.clone());
         let status_block = Arc::new(RwLock::new(StatusBlock::new()));
         let history = Arc::new(RwLock::new(Vec::new()));
         let active_branches = Arc::new(RwLock::new(HashMap::new()));
@@ -526,6 +546,8 @@ impl Channel {
             conversation_logger,
             process_run_logger,
             reply_target_message_id: Arc::new(RwLock::new(None)),
+            user_language: Arc::new(RwLock::new(None)),
+            user_scope: Arc::new(std::sync::RwLock::new(None)),
             channel_store: channel_store.clone(),
             screenshot_dir,
             logs_dir,
@@ -564,6 +586,7 @@ impl Channel {
             deps,
             hook,
             state,
+            event_log,
             tool_server,
             message_rx,
             event_rx,
@@ -608,6 +631,224 @@ impl Channel {
             .unwrap_or(self.deps.agent_id.as_ref())
     }
 
+    /// `/timezone` shows the resolved time; `/timezone <zone> [location]`
+    /// sets the channel's, `/timezone me <zone> [location]` the sender's, and
+    /// `clear` in place of a zone removes either.
+    fn timezone_command(&self, args: &str, message: &InboundMessage) -> String {
+        let Some(settings) = self.deps.runtime_config.settings.load().as_ref().clone() else {
+            return "settings store unavailable; timezones can't be saved right now.".to_string();
+        };
+        // Native commands render their argument as `zone: ...`.
+        let args = args.strip_prefix("zone:").map(str::trim).unwrap_or(args);
+        let (for_sender, args) = match args.strip_prefix("me") {
+            Some(rest) if rest.is_empty() || rest.starts_with(char::is_whitespace) => {
+                (true, rest.trim())
+            }
+            _ => (false, args),
+        };
+
+        if args.is_empty() {
+            let context = self.temporal_context(Some(message));
+            let scope = if for_sender { "you" } else { "this chat" };
+            return format!(
+                "time for {scope}: {}
+set with /timezone <zone> [location] (this chat) or /timezone me <zone> [location] (just you).",
+                context.current_time_line()
+            );
+        }
+
+        let (zone, location) = args.split_once(char::is_whitespace).unwrap_or((args, ""));
+        let locale = if zone.eq_ignore_ascii_case("clear") {
+            crate::settings::Locale::default()
+        } else if zone.parse::<chrono_tz::Tz>().is_err() {
+            return format!(
+                "'{zone}' isn't a timezone i know. use an IANA name like Europe/Berlin or America/New_York."
+            );
+        } else {
+            let location = location.trim();
+            crate::settings::Locale {
+                timezone: Some(zone.to_string()),
+                location: (!location.is_empty()).then(|| location.to_string()),
+            }
+        };
+
+        let result = if for_sender {
+            settings.set_user_locale(&message.source, &message.sender_id, &locale)
+        } else {
+            settings.set_channel_locale(self.id.as_ref(), &locale)
+        };
+        if let Err(error) = result {
+            tracing::warn!(%error, channel_id = %self.id, "failed to persist locale");
+            return "couldn't save that timezone; try again later.".to_string();
+        }
+
+        let scope = if for_sender { "your" } else { "this chat's" };
+        match &locale.timezone {
+            None => format!("cleared {scope} timezone and location."),
+            Some(zone) => {
+                let now = self.temporal_context(Some(message)).current_time_line();
+                format!("{scope} timezone is now {zone}. it's {now}.")
+            }
+        }
+    }
+
+    /// `/model` shows how smart routing picks this channel's model;
+    /// `/model fast|strong` pins a tier and `/model auto` unpins it.
+    fn model_command(&self, args: &str) -> String {
+        let routing = self.deps.runtime_config.routing.load();
+        let smart = &routing.smart;
+        if !smart.enabled {
+            return format!(
+                "smart routing is off; this chat always uses {}.",
+                routing.resolve(ProcessType::Channel, None)
+            );
+        }
+        let Some(settings) = self.deps.runtime_config.settings.load().as_ref().clone() else {
+            return "settings store unavailable; the model can't be pinned right now.".to_string();
+        };
+        // Native commands render their argument as `tier: ...`.
+        let args = args.strip_prefix("tier:").map(str::trim).unwrap_or(args);
+
+        let tier = match args.to_ascii_lowercase().as_str() {
+            "" => {
+                let fast = smart.fast_model(&routing);
+                let strong = smart.strong_model(&routing);
+                return match settings.channel_model_tier(self.id.as_ref()) {
+                    Some(tier) => format!(
+                        "this chat is pinned to the {tier} model (fast: {fast}, strong: {strong}). /model auto to unpin."
+                    ),
+                    None => format!(
+                        "picking per message between fast ({fast}) and strong ({strong}). /model fast or /model strong to pin one."
+                    ),
+                };
+            }
+            "auto" => None,
+            other => match other.parse::<Tier>() {
+                Ok(tier) => Some(tier),
+                Err(_) => return "usage: /model [fast|strong|auto]".to_string(),
+            },
+        };
+
+        if let Err(error) = settings.set_channel_model_tier(self.id.as_ref(), tier) {
+            tracing::warn!(%error, channel_id = %self.id, "failed to persist model tier");
+            return "couldn't save that; try again later.".to_string();
+        }
+        match tier {
+            Some(tier) => format!("this chat now always uses the {tier} model."),
+            None => "back to picking the model per message.".to_string(),
+        }
+    }
+
+    /// `/draft` shows whether replies here start as speculative drafts;
+    /// `/draft on|off` overrides the agent setting and `/draft default`
+    /// clears the override.
+    fn draft_command(&self, args: &str) -> String {
+        let Some(settings) = self.deps.runtime_config.settings.load().as_ref().clone() else {
+            return "settings store unavailable; drafts can't be toggled right now.".to_string();
+        };
+        // Native commands render their argument as `mode: ...`.
+        let args = args.strip_prefix("mode:").map(str::trim).unwrap_or(args);
+
+        let enabled = match args.to_ascii_lowercase().as_str() {
+            "" => {
+                let source = match settings.channel_speculative_drafts(self.id.as_ref()) {
+                    Some(_) => "set for this chat",
+                    None => "agent default",
+                };
+                let state = if self.speculative_drafts_enabled() {
+                    "on"
+                } else {
+                    "off"
+                };
+                return format!("speculative drafts are {state} ({source}).");
+            }
+            "on" => Some(true),
+            "off" => Some(false),
+            "default" => None,
+            _ => return "usage: /draft [on|off|default]".to_string(),
+        };
+
+        if let Err(error) = settings.set_channel_speculative_drafts(self.id.as_ref(), enabled) {
+            tracing::warn!(%error, channel_id = %self.id, "failed to persist speculative drafts");
+            return "couldn't save that; try again later.".to_string();
+        }
+        match enabled {
+            Some(true) => "replies here now start as quick drafts that get checked.".to_string(),
+            Some(false) => "speculative drafts are off for this chat.".to_string(),
+            None => format!(
+                "back to the agent default: drafts {}.",
+                if self.speculative_drafts_enabled() {
+                    "on"
+                } else {
+                    "off"
+                }
+            ),
+        }
+    }
+
+    /// `/verify` shows how replies here are fact-checked; `/verify
+    /// annotate|soften|regenerate|off` overrides the agent setting and
+    /// `/verify default` clears the override.
+    fn verify_command(&self, args: &str) -> String {
+        let Some(settings) = self.deps.runtime_config.settings.load().as_ref().clone() else {
+            return "settings store unavailable; verification can't be changed right now."
+                .to_string();
+        };
+        // Native commands render their argument as `mode: ...`.
+        let args = args.strip_prefix("mode:").map(str::trim).unwrap_or(args);
+        let current =
+            || super::verification::channel_setting(&self.deps.runtime_config, self.id.as_ref());
+
+        let verification = match args.to_ascii_lowercase().as_str() {
+            "" => {
+                let source = match settings.channel_reply_verification(self.id.as_ref()) {
+                    Some(_) => "set for this chat",
+                    None => "agent default",
+                };
+                return format!("reply verification is {} ({source}).", current().as_str());
+            }
+            "default" => None,
+            other => match crate::config::ReplyVerification::parse(other) {
+                Some(verification) => Some(verification),
+                None => {
+                    return "usage: /verify [annotate|soften|regenerate|off|default]".to_string();
+                }
+            },
+        };
+
+        if let Err(error) = settings.set_channel_reply_verification(self.id.as_ref(), verification)
+        {
+            tracing::warn!(%error, channel_id = %self.id, "failed to persist reply verification");
+            return "couldn't save that; try again later.".to_string();
+        }
+        match verification {
+            Some(crate::config::ReplyVerification::Off) => {
+                "replies here are no longer fact-checked.".to_string()
+            }
+            Some(verification) => format!(
+                "replies here are now fact-checked against memory ({}).",
+                verification.as_str()
+            ),
+            None => format!(
+                "back to the agent default: verification {}.",
+                current().as_str()
+            ),
+        }
+    }
+
+    /// Time context for this channel, using the locale set for `message`'s
+    /// sender or the channel before the agent's timezone.
+    fn temporal_context(&self, message: Option<&InboundMessage>) -> TemporalContext {
+        let sender = message
+            .filter(|message| message.source != "system")
+            .map(|message| (message.source.as_str(), message.sender_id.as_str()));
+        TemporalContext::for_conversation(
+            self.deps.runtime_config.as_ref(),
+            self.id.as_ref(),
+            sender,
+        )
+    }
+
     fn current_adapter(&self) -> Option<&str> {
         self.source_adapter
             .as_deref()
@@ -659,6 +900,21 @@ impl Channel {
         };
     }
 
+    /// Read the channel's mute flag (set through the API) and mirror it into
+    /// the status block. Read per message so a mute applies to the very next
+    /// one.
+    async fn sync_muted(&self) -> bool {
+        let muted = match self.state.channel_store.is_muted(self.id.as_ref()).await {
+            Ok(muted) => muted,
+            Err(error) => {
+                tracing::warn!(%error, channel_id = %self.id, "failed to read channel mute state");
+                false
+            }
+        };
+        self.state.status_block.write().await.muted = muted;
+        muted
+    }
+
     fn set_listen_only_mode(&mut self, enabled: bool) -> bool {
         let mut persisted = false;
         let settings_store = self
@@ -699,6 +955,7 @@ impl Channel {
         message: &InboundMessage,
         raw_text: &str,
         saved_attachments: Option<&[channel_attachments::SavedAttachmentMeta]>,
+        translation: Option<&crate::translation::Translated>,
     ) {
         if message.source == "system" {
             return;
@@ -710,7 +967,7 @@ impl Channel {
             .unwrap_or(&message.sender_id);
 
         // If attachments were saved, enrich the metadata with their info
-        let metadata = if let Some(saved) = saved_attachments {
+        let mut metadata = if let Some(saved) = saved_attachments {
             let mut enriched = message.metadata.clone();
             if let Ok(attachments_json) = serde_json::to_value(saved) {
                 enriched.insert("attachments".to_string(), attachments_json);
@@ -719,6 +976,12 @@ impl Channel {
         } else {
             message.metadata.clone()
         };
+        if let Some(translation) = translation {
+            metadata.insert(
+                "translation".to_string(),
+                self.translation_metadata(translation),
+            );
+        }
 
         self.state.conversation_logger.log_user_message(
             &self.state.channel_id,
@@ -732,6 +995,151 @@ impl Channel {
             .upsert(&message.conversation_id, &metadata);
     }
 
+    /// Translate a user message into the agent's working language when
+    /// `translation.auto_translate` is on, and remember the user's language
+    /// so replies go back in it. `None` when there's nothing to translate or
+    /// the provider fails; the original text is used then.
+    async fn translate_inbound(
+        &self,
+        message: &InboundMessage,
+        raw_text: &str,
+    ) -> Option<crate::translation::Translated> {
+        let config = self.deps.runtime_config.translation.load();
+        let trimmed = raw_text.trim();
+        if !config.auto_translate
+            || message.source == "system"
+            || trimmed.is_empty()
+            || trimmed.starts_with('/')
+        {
+            return None;
+        }
+
+        let translator = crate::translation::translator(
+            &config,
+            self.deps.llm_manager.clone(),
+            self.deps.runtime_config.clone(),
+            self.deps.agent_id.clone(),
+        )
+        .inspect_err(|error| {
+            tracing::warn!(%error, channel_id = %self.id, "failed to build inbound translator")
+        })
+        .ok()?;
+
+        match translator
+            .translate(raw_text, &config.working_language, None)
+            .await
+        {
+            Ok(translated) => {
+                if let Some(language) = &translated.source_language {
+                    *self.state.user_language.write().await = Some(language.clone());
+                    if crate::translation::same_language(language, &config.working_language) {
+                        return None;
+                    }
+                }
+                Some(translated)
+            }
+            Err(error) => {
+                tracing::warn!(
+                    %error,
+                    channel_id = %self.id,
+                    message_id = %message.id,
+                    "failed to translate inbound message, using the original"
+                );
+                None
+            }
+        }
+    }
+
+    /// History metadata for a translated inbound message. The row's content
+    /// stays the user's original text.
+    fn translation_metadata(
+        &self,
+        translation: &crate::translation::Translated,
+    ) -> serde_json::Value {
+        serde_json::json!({
+            "text": translation.text,
+            "language": self.deps.runtime_config.translation.load().working_language,
+            "source_language": translation.source_language,
+        })
+    }
+
+    /// Point user-scoped memory access at the sender of `message`.
+    fn set_user_scope(&self, message: &InboundMessage) {
+        let scope = crate::memory::scope::user_scope(&message.source, &message.sender_id);
+        *self
+            .state
+            .user_scope
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(scope);
+    }
+
+    /// Tag the sender with the contact their platform identity is linked to,
+    /// or the org-level human whose IDs match, so the model knows who's
+    /// talking and can refer to them by name elsewhere.
+    async fn attribute_sender(&self, message: &mut InboundMessage) {
+        if message.source == "system" {
+            return;
+        }
+        let store = crate::contacts::ContactStore::new(self.deps.sqlite_pool.clone());
+        let platforms = [message.adapter.as_deref(), Some(message.source.as_str())];
+        let mut name = None;
+        for platform in platforms.into_iter().flatten() {
+            match store.find_by_handle(platform, &message.sender_id).await {
+                Ok(Some(contact)) => {
+                    name = Some(contact.name);
+                    break;
+                }
+                Ok(None) => {}
+                Err(error) => {
+                    tracing::warn!(%error, channel_id = %self.id, "failed to look up sender contact");
+                    return;
+                }
+            }
+        }
+        let name = name.or_else(|| {
+            self.deps.humans.load().iter().find_map(|human| {
+                crate::contacts::human_handles(human)
+                    .iter()
+                    .any(|handle| {
+                        handle.platform == message.source && handle.handle == message.sender_id
+                    })
+                    .then(|| {
+                        human
+                            .display_name
+                            .clone()
+                            .unwrap_or_else(|| human.id.clone())
+                    })
+            })
+        });
+        let Some(name) = name else {
+            return;
+        };
+
+        let existing = message
+            .metadata
+            .get("sender_context")
+            .and_then(|value| value.as_str())
+            .filter(|value| !value.is_empty());
+        let context = match existing {
+            Some(existing) => format!("{existing} [contact: {name}]"),
+            None => format!("[contact: {name}]"),
+        };
+        message
+            .metadata
+            .insert("sender_context".into(), serde_json::Value::String(context));
+    }
+
+    async fn record_inbound_event(&self, message: &InboundMessage) {
+        self.event_log
+            .record(ChannelEvent::Inbound {
+                message_id: message.id.clone(),
+                source: message.source.clone(),
+                sender_id: message.sender_id.clone(),
+                text: message.content.to_string(),
+            })
+            .await;
+    }
+
     fn suppress_plaintext_fallback(&self) -> bool {
         matches!(self.current_adapter(), Some("email"))
     }
@@ -742,7 +1150,23 @@ impl Channel {
         self.control_handle.clone()
     }
 
-    fn rewrite_tool_routed_command_prompt(&self, raw_text: &str) -> Option<String> {
+    fn rewrite_tool_routed_command_prompt(
+        &self,
+        raw_text: &str,
+        content: &crate::MessageContent,
+        temporal_context: &TemporalContext,
+    ) -> Option<String> {
+        // Native slash commands arrive structured; typed ones are parsed into the same shape.
+        let command = match content {
+            crate::MessageContent::Command(command) => Some(command.clone()),
+            _ => SlashCommand::parse(raw_text),
+        };
+        if let Some(command) = command
+            && let Some(prompt) = slash_command_prompt(&command, temporal_context.timezone_name())
+        {
+            return Some(prompt);
+        }
+
         match raw_text.trim() {
             "/tasks" => Some(
                 "use channel tools to fetch my ready tasks (limit 10) and reply exactly with:\n\
@@ -793,6 +1217,7 @@ impl Channel {
             Some(target) => RoutedResponse {
                 response,
                 target: target.clone(),
+                message_id: None,
             },
             None => {
                 tracing::warn!(
@@ -802,6 +1227,7 @@ impl Channel {
                 RoutedResponse {
                     response,
                     target: InboundMessage::empty(),
+                    message_id: None,
                 }
             }
         };
@@ -860,13 +1286,53 @@ impl Channel {
             return Ok(false);
         }
 
-        let temporal_context = TemporalContext::from_runtime(self.deps.runtime_config.as_ref());
+        if let Some(args) = text.strip_prefix("/timezone")
+            && (args.is_empty() || args.starts_with(char::is_whitespace))
+        {
+            let body = self.timezone_command(args.trim(), message);
+            self.send_builtin_text(body, "timezone").await;
+            return Ok(true);
+        }
+
+        if let Some(args) = text.strip_prefix("/model")
+            && (args.is_empty() || args.starts_with(char::is_whitespace))
+        {
+            let body = self.model_command(args.trim());
+            self.send_builtin_text(body, "model").await;
+            return Ok(true);
+        }
+
+        if let Some(args) = text.strip_prefix("/draft")
+            && (args.is_empty() || args.starts_with(char::is_whitespace))
+        {
+            let body = self.draft_command(args.trim());
+            self.send_builtin_text(body, "draft").await;
+            return Ok(true);
+        }
+
+        if let Some(args) = text.strip_prefix("/verify")
+            && (args.is_empty() || args.starts_with(char::is_whitespace))
+        {
+            let body = self.verify_command(args.trim());
+            self.send_builtin_text(body, "verify").await;
+            return Ok(true);
+        }
+
+        let temporal_context = self.temporal_context(Some(message));
         let now_line = temporal_context.current_time_line();
 
         match text {
             "/status" => {
                 let routing = self.deps.runtime_config.routing.load();
-                let channel_model = routing.resolve(ProcessType::Channel, None).to_string();
+                let channel_model = if routing.smart.enabled {
+                    format!(
+                        "{} (fast) / {} (strong), smart routing",
+                        routing.smart.fast_model(&routing),
+                        routing.smart.strong_model(&routing)
+                    )
+                } else {
+                    routing.resolve(ProcessType::Channel, None).to_string()
+                };
                 let branch_model = routing.resolve(ProcessType::Branch, None).to_string();
                 let mode = if self.listen_only_mode {
                     "quiet"
@@ -922,6 +1388,13 @@ impl Channel {
                     "- /today: in-progress + ready task snapshot".to_string(),
                     "- /tasks: ready task list".to_string(),
                     "- /digest: one-shot day digest (00:00 -> now)".to_string(),
+                    "- /remind <when> <what>: one-time reminder in this chat".to_string(),
+                    "- /summarize [since]: summary of the recent conversation".to_string(),
+                    "- /memory search <query>: search memories".to_string(),
+                    "- /timezone [me] [zone] [location]: show or set the timezone for this chat or just you".to_string(),
+                    "- /model [fast|strong|auto]: show or pin the model smart routing uses here".to_string(),
+                    "- /draft [on|off|default]: speculative draft-and-verify replies in this chat".to_string(),
+                    "- /verify [annotate|soften|regenerate|off|default]: fact-check replies against memory in this chat".to_string(),
                     "- /quiet: listen-only mode".to_string(),
                     "- /active: normal reply mode".to_string(),
                     "- /agent-id: runtime agent id".to_string(),
@@ -1078,6 +1551,7 @@ impl Channel {
                 .as_deref()
                 .is_some_and(|value| value.trim_start().starts_with('/')),
             crate::MessageContent::Interaction { .. } => false,
+            crate::MessageContent::Command(_) => true,
         };
         if looks_like_command {
             return false;
@@ -1165,10 +1639,14 @@ impl Channel {
     /// individually to conversation history, then presents them as one user turn
     /// with a coalesce hint telling the LLM this is a fast-moving conversation.
     #[tracing::instrument(skip(self, messages), fields(channel_id = %self.id, agent_id = %self.deps.agent_id, message_count = messages.len()))]
-    async fn handle_message_batch(&mut self, messages: Vec<InboundMessage>) -> Result<()> {
+    async fn handle_message_batch(&mut self, mut messages: Vec<InboundMessage>) -> Result<()> {
         // Apply runtime-config updates immediately without requiring a restart.
         self.sync_listen_only_mode_from_runtime();
 
+        for message in &mut messages {
+            self.attribute_sender(message).await;
+        }
+
         let message_count = messages.len();
         let batch_start_timestamp = messages
             .iter()
@@ -1273,10 +1751,11 @@ impl Channel {
             Option<Vec<channel_attachments::SavedAttachmentWithBytes>>,
         )> = Vec::new();
         let mut conversation_id = String::new();
-        let temporal_context = TemporalContext::from_runtime(self.deps.runtime_config.as_ref());
+        let temporal_context = self.temporal_context(messages.last());
         let mut batch_has_invoke = false;
 
         for message in &messages {
+            self.record_inbound_event(message).await;
             if message.source != "system" {
                 let sender_name = message
                     .metadata
@@ -1289,8 +1768,9 @@ impl Channel {
                     crate::MessageContent::Media { text, attachments } => {
                         (text.clone().unwrap_or_default(), attachments.clone())
                     }
-                    // Render interactions as their Display form so the LLM sees plain text.
-                    crate::MessageContent::Interaction { .. } => {
+                    // Render interactions and commands as their Display form so the LLM sees plain text.
+                    crate::MessageContent::Interaction { .. }
+                    | crate::MessageContent::Command(_) => {
                         (message.content.to_string(), Vec::new())
                     }
                 };
@@ -1319,7 +1799,7 @@ impl Channel {
                 };
 
                 // Enrich metadata with saved attachment info
-                let metadata = if let Some(ref data) = saved_data {
+                let mut metadata = if let Some(ref data) = saved_data {
                     let metas: Vec<_> = data.iter().map(|(meta, _)| meta.clone()).collect();
                     let mut enriched = message.metadata.clone();
                     if let Ok(json) = serde_json::to_value(&metas) {
@@ -1330,6 +1810,14 @@ impl Channel {
                     message.metadata.clone()
                 };
 
+                let translation = self.translate_inbound(message, &raw_text).await;
+                if let Some(translation) = &translation {
+                    metadata.insert(
+                        "translation".to_string(),
+                        self.translation_metadata(translation),
+                    );
+                }
+
                 self.state.conversation_logger.log_user_message(
                     &self.state.channel_id,
                     sender_name,
@@ -1363,13 +1851,26 @@ impl Channel {
                     display_name,
                     &absolute_timestamp,
                     &relative_text,
-                    &raw_text,
+                    translation
+                        .as_ref()
+                        .map_or(raw_text.as_str(), |translation| &translation.text),
                 );
 
                 pending_batch_entries.push((formatted_text, attachments, saved_data));
             }
         }
 
+        if self.sync_muted().await {
+            tracing::debug!(
+                channel_id = %self.id,
+                message_count,
+                "channel muted: suppressing coalesced batch"
+            );
+            self.message_count += message_count;
+            self.check_memory_persistence().await;
+            return Ok(());
+        }
+
         if self.listen_only_mode && !batch_has_invoke {
             tracing::debug!(
                 channel_id = %self.id,
@@ -1453,6 +1954,7 @@ impl Channel {
         // metadata (e.g. Slack thread_ts) for outbound responses.
         if let Some(last_real) = messages.iter().rev().find(|m| m.source != "system") {
             self.current_inbound = Some(last_real.clone());
+            self.set_user_scope(last_real);
         }
 
         // Run agent turn with any image/audio attachments preserved
@@ -1481,6 +1983,52 @@ impl Channel {
         Ok(())
     }
 
+    /// Skills listing and worker capabilities for the system prompt, reused
+    /// from the agent's prompt section cache while their inputs hash the
+    /// same.
+    async fn cached_prompt_sections(
+        &self,
+        prompt_engine: &crate::prompts::PromptEngine,
+    ) -> Result<(String, String)> {
+        let rc = &self.deps.runtime_config;
+        let cache = &rc.prompt_sections;
+        let agent_id = self.deps.agent_id.as_ref();
+
+        let skills = rc.skills.load();
+        let skills_prompt = cache.get_or_render(
+            agent_id,
+            PromptSection::Skills,
+            content_hash((skills.channel_listing_hash(), prompt_engine.language())),
+            || skills.render_channel_prompt(prompt_engine),
+        )?;
+
+        let browser_enabled = rc.browser_config.load().enabled;
+        let web_search_enabled = rc.brave_search_key.load().is_some();
+        let opencode_enabled = rc.opencode.load().enabled;
+        let mcp_tool_names = self.deps.mcp_manager.get_tool_names().await;
+        let worker_capabilities = cache.get_or_render(
+            agent_id,
+            PromptSection::WorkerCapabilities,
+            content_hash((
+                browser_enabled,
+                web_search_enabled,
+                opencode_enabled,
+                &mcp_tool_names,
+                prompt_engine.language(),
+            )),
+            || {
+                prompt_engine.render_worker_capabilities(
+                    browser_enabled,
+                    web_search_enabled,
+                    opencode_enabled,
+                    &mcp_tool_names,
+                )
+            },
+        )?;
+
+        Ok((skills_prompt, worker_capabilities))
+    }
+
     /// Build system prompt with coalesce hint for batched messages.
     async fn build_system_prompt_with_coalesce(
         &self,
@@ -1492,23 +2040,12 @@ impl Channel {
         let prompt_engine = rc.prompts.load();
 
         let identity_context = rc.identity.load().render();
+        let (skills_prompt, worker_capabilities) =
+            self.cached_prompt_sections(&prompt_engine).await?;
         let memory_bulletin = rc.memory_bulletin.load();
-        let skills = rc.skills.load();
-        let skills_prompt = skills.render_channel_prompt(&prompt_engine)?;
-
-        let browser_enabled = rc.browser_config.load().enabled;
-        let web_search_enabled = rc.brave_search_key.load().is_some();
-        let opencode_enabled = rc.opencode.load().enabled;
         let sandbox_enabled = self.deps.sandbox.containment_active();
-        let mcp_tool_names = self.deps.mcp_manager.get_tool_names().await;
-        let worker_capabilities = prompt_engine.render_worker_capabilities(
-            browser_enabled,
-            web_search_enabled,
-            opencode_enabled,
-            &mcp_tool_names,
-        )?;
 
-        let temporal_context = TemporalContext::from_runtime(rc.as_ref());
+        let temporal_context = self.temporal_context(self.current_inbound.as_ref());
         let current_time_line = temporal_context.current_time_line();
         let system_info = self.build_system_info().await;
         let status_text = {
@@ -1532,7 +2069,7 @@ impl Channel {
 
         let empty_to_none = |s: String| if s.is_empty() { None } else { Some(s) };
 
-        let project_context = self.build_project_context(&prompt_engine).await;
+        let project_context = self.cached_project_context(&prompt_engine).await;
 
         prompt_engine.render_channel_prompt_with_links(
             empty_to_none(identity_context),
@@ -1557,15 +2094,18 @@ impl Channel {
     /// spawn_worker (to delegate), route (to follow up with a worker), cancel, or
     /// memory_save. The tools act on the channel's shared state directly.
     #[tracing::instrument(skip(self, message), fields(channel_id = %self.id, agent_id = %self.deps.agent_id, message_id = %message.id))]
-    async fn handle_message(&mut self, message: InboundMessage) -> Result<()> {
+    async fn handle_message(&mut self, mut message: InboundMessage) -> Result<()> {
         // Apply runtime-config updates immediately without requiring a restart.
         self.sync_listen_only_mode_from_runtime();
 
+        self.attribute_sender(&mut message).await;
+
         // Track the inbound message that triggered this turn so outbound
         // responses carry the correct routing metadata (e.g. Slack thread_ts).
         // System retrigger messages keep the previous inbound target.
         if message.source != "system" {
             self.current_inbound = Some(message.clone());
+            self.set_user_scope(&message);
         }
 
         tracing::info!(
@@ -1574,6 +2114,8 @@ impl Channel {
             "handling message"
         );
 
+        self.record_inbound_event(&message).await;
+
         #[cfg(feature = "metrics")]
         let _duration_guard = {
             let channel_type = if message.source != "system" {
@@ -1616,8 +2158,10 @@ impl Channel {
             crate::MessageContent::Media { text, attachments } => {
                 (text.clone().unwrap_or_default(), attachments.clone())
             }
-            // Render interactions as their Display form so the LLM sees plain text.
-            crate::MessageContent::Interaction { .. } => (message.content.to_string(), Vec::new()),
+            // Render interactions and commands as their Display form so the LLM sees plain text.
+            crate::MessageContent::Interaction { .. } | crate::MessageContent::Command(_) => {
+                (message.content.to_string(), Vec::new())
+            }
         };
 
         // Save attachments to disk when enabled, capturing bytes for LLM reuse
@@ -1647,7 +2191,23 @@ impl Channel {
             .as_ref()
             .map(|data| data.iter().map(|(meta, _)| meta.clone()).collect());
 
-        self.persist_inbound_user_message(&message, &raw_text, saved_metas.as_deref());
+        let inbound_translation = self.translate_inbound(&message, &raw_text).await;
+
+        self.persist_inbound_user_message(
+            &message,
+            &raw_text,
+            saved_metas.as_deref(),
+            inbound_translation.as_ref(),
+        );
+
+        // Muted channels keep history and memory capture but never reply,
+        // not even to commands.
+        if self.sync_muted().await {
+            tracing::debug!(channel_id = %self.id, "channel muted: suppressing reply");
+            self.message_count += 1;
+            self.check_memory_persistence().await;
+            return Ok(());
+        }
 
         // Deterministic built-in command: bypass model output drift for agent identity checks.
         if message.source != "system" && raw_text.trim() == "/agent-id" {
@@ -1700,14 +2260,23 @@ impl Channel {
             return Ok(());
         }
 
+        // The model works in the agent's language; history keeps the original.
+        let model_text = inbound_translation
+            .map(|translation| translation.text)
+            .unwrap_or_else(|| raw_text.clone());
+
+        let temporal_context = self.temporal_context(Some(&message));
         let rewritten_text = if message.source == "system" {
-            raw_text.clone()
+            model_text
         } else {
-            self.rewrite_tool_routed_command_prompt(&raw_text)
-                .unwrap_or_else(|| raw_text.clone())
+            self.rewrite_tool_routed_command_prompt(
+                &model_text,
+                &message.content,
+                &temporal_context,
+            )
+            .unwrap_or(model_text)
         };
 
-        let temporal_context = TemporalContext::from_runtime(self.deps.runtime_config.as_ref());
         let message_timestamp = temporal_context.format_timestamp(message.timestamp);
         let user_text = format_user_message(&rewritten_text, &message, &message_timestamp);
 
@@ -1736,7 +2305,47 @@ impl Channel {
             }
         }
 
-        let system_prompt = self.build_system_prompt().await?;
+        // A close curated answer goes out without a model call; a looser
+        // match is handed to the model as context.
+        let faq_match = if message.source != "system" && attachments.is_empty() {
+            self.match_faq(&rewritten_text).await
+        } else {
+            None
+        };
+        if let Some((faq_match, faq::MatchOutcome::Answered)) = &faq_match {
+            let answer = faq_match.entry.answer.clone();
+            self.send_outbound_text(answer.clone(), "failed to send faq answer")
+                .await;
+            self.state
+                .conversation_logger
+                .log_bot_message(&self.state.channel_id, &answer);
+            {
+                let mut history = self.state.history.write().await;
+                history.push(rig::message::Message::user(&user_text));
+                history.push(rig::message::Message::assistant(answer));
+            }
+            self.retrigger_count = 0;
+            self.message_count += 1;
+            self.check_memory_persistence().await;
+            return Ok(());
+        }
+
+        let mut system_prompt = self.build_system_prompt().await?;
+        if let Some((faq_match, faq::MatchOutcome::Context)) = &faq_match {
+            match self.deps.runtime_config.prompts.load().render_faq_match(
+                &faq_match.entry.question,
+                &faq_match.entry.answer,
+                faq_match.similarity,
+            ) {
+                Ok(fragment) => {
+                    system_prompt.push_str("\n\n");
+                    system_prompt.push_str(&fragment);
+                }
+                Err(error) => {
+                    tracing::warn!(%error, channel_id = %self.id, "failed to render faq match");
+                }
+            }
+        }
 
         {
             let mut reply_target = self.state.reply_target_message_id.write().await;
@@ -1782,6 +2391,22 @@ impl Channel {
             .adapter
             .as_deref()
             .or_else(|| self.current_adapter());
+        if !is_retrigger
+            && attachment_content.is_empty()
+            && self.speculative_drafts_enabled()
+            && self
+                .try_speculative_reply(&user_text, &system_prompt, adapter)
+                .await
+        {
+            if let Err(error) = self.compactor.check_and_compact().await {
+                tracing::warn!(channel_id = %self.id, %error, "compaction check failed");
+            }
+            self.retrigger_count = 0;
+            self.message_count += 1;
+            self.check_memory_persistence().await;
+            return Ok(());
+        }
+
         let (result, skip_flag, replied_flag, retrigger_reply_preserved) = self
             .run_agent_turn(
                 &user_text,
@@ -2030,48 +2655,58 @@ impl Channel {
     /// Fetches all active projects with their repos and worktrees, converts them
     /// to prompt-friendly structs, and renders via the projects_context template.
     /// Returns `None` if no projects exist or if rendering fails.
-    async fn build_project_context(
+    /// The project context, reused from the agent's prompt section cache
+    /// until a project, repo, or worktree changes.
+    async fn cached_project_context(
         &self,
         prompt_engine: &crate::prompts::engine::PromptEngine,
     ) -> Option<String> {
+        let cache = &self.deps.runtime_config.prompt_sections;
+        let agent_id = self.deps.agent_id.as_ref();
+        let key = content_hash((
+            self.deps.project_store.generation(),
+            prompt_engine.language(),
+        ));
+        let text = match cache.get(agent_id, PromptSection::Projects, key) {
+            Some(text) => text,
+            None => match self.build_project_context(prompt_engine).await {
+                Ok(text) => {
+                    let text = text.unwrap_or_default();
+                    cache.insert(PromptSection::Projects, key, text.clone());
+                    text
+                }
+                // Not cached, so the next turn tries again.
+                Err(error) => {
+                    tracing::warn!(%error, "failed to load projects for prompt injection");
+                    return None;
+                }
+            },
+        };
+        (!text.is_empty()).then_some(text)
+    }
+
+    async fn build_project_context(
+        &self,
+        prompt_engine: &crate::prompts::engine::PromptEngine,
+    ) -> Result<Option<String>> {
         use crate::prompts::engine::{ProjectContext, ProjectRepoContext, ProjectWorktreeContext};
 
         let store = &self.deps.project_store;
-        let projects = match store
+        let projects = store
             .list_projects(
                 &self.deps.agent_id,
                 Some(crate::projects::ProjectStatus::Active),
             )
-            .await
-        {
-            Ok(projects) => projects,
-            Err(error) => {
-                tracing::warn!(%error, "failed to load projects for prompt injection");
-                return None;
-            }
-        };
+            .await?;
 
         if projects.is_empty() {
-            return None;
+            return Ok(None);
         }
 
         let mut contexts = Vec::with_capacity(projects.len());
         for project in &projects {
-            let repos = match store.list_repos(&project.id).await {
-                Ok(repos) => repos,
-                Err(error) => {
-                    tracing::warn!(%error, project_id = %project.id, "failed to load repos for project");
-                    Vec::new()
-                }
-            };
-
-            let worktrees = match store.list_worktrees_with_repos(&project.id).await {
-                Ok(worktrees) => worktrees,
-                Err(error) => {
-                    tracing::warn!(%error, project_id = %project.id, "failed to load worktrees for project");
-                    Vec::new()
-                }
-            };
+            let repos = store.list_repos(&project.id).await?;
+            let worktrees = store.list_worktrees_with_repos(&project.id).await?;
 
             contexts.push(ProjectContext {
                 name: project.name.clone(),
@@ -2110,15 +2745,11 @@ impl Channel {
         match prompt_engine.render_projects_context(contexts) {
             Ok(rendered) => {
                 let rendered = rendered.trim().to_string();
-                if rendered.is_empty() {
-                    None
-                } else {
-                    Some(rendered)
-                }
+                Ok((!rendered.is_empty()).then_some(rendered))
             }
             Err(error) => {
                 tracing::warn!(%error, "failed to render projects context");
-                None
+                Ok(None)
             }
         }
     }
@@ -2147,23 +2778,12 @@ impl Channel {
         let prompt_engine = rc.prompts.load();
 
         let identity_context = rc.identity.load().render();
+        let (skills_prompt, worker_capabilities) =
+            self.cached_prompt_sections(&prompt_engine).await?;
         let memory_bulletin = rc.memory_bulletin.load();
-        let skills = rc.skills.load();
-        let skills_prompt = skills.render_channel_prompt(&prompt_engine)?;
-
-        let browser_enabled = rc.browser_config.load().enabled;
-        let web_search_enabled = rc.brave_search_key.load().is_some();
-        let opencode_enabled = rc.opencode.load().enabled;
         let sandbox_enabled = self.deps.sandbox.containment_active();
-        let mcp_tool_names = self.deps.mcp_manager.get_tool_names().await;
-        let worker_capabilities = prompt_engine.render_worker_capabilities(
-            browser_enabled,
-            web_search_enabled,
-            opencode_enabled,
-            &mcp_tool_names,
-        )?;
 
-        let temporal_context = TemporalContext::from_runtime(rc.as_ref());
+        let temporal_context = self.temporal_context(self.current_inbound.as_ref());
         let current_time_line = temporal_context.current_time_line();
         let system_info = self.build_system_info().await;
         let status_text = {
@@ -2179,7 +2799,7 @@ impl Channel {
             .current_adapter()
             .and_then(|adapter| prompt_engine.render_channel_adapter_prompt(adapter));
 
-        let project_context = self.build_project_context(&prompt_engine).await;
+        let project_context = self.cached_project_context(&prompt_engine).await;
 
         let empty_to_none = |s: String| if s.is_empty() { None } else { Some(s) };
 
@@ -2200,6 +2820,266 @@ impl Channel {
         )
     }
 
+    /// Check a question against the agent's curated Q&A store and record the
+    /// result. Returns the best entry unless it was a miss. Nothing is checked
+    /// or recorded when matching is off, the message is a command, or no
+    /// active entry applies to this channel.
+    async fn match_faq(&self, text: &str) -> Option<(faq::FaqMatch, faq::MatchOutcome)> {
+        let channel_config = **self.deps.runtime_config.channel_config.load();
+        let text = text.trim();
+        if !channel_config.faq_matching || text.is_empty() || text.starts_with('/') {
+            return None;
+        }
+
+        let embedding = match self
+            .deps
+            .memory_search
+            .embedding_model_arc()
+            .embed_one(text)
+            .await
+        {
+            Ok(embedding) => embedding,
+            Err(error) => {
+                tracing::warn!(%error, channel_id = %self.id, "failed to embed question for faq matching");
+                return None;
+            }
+        };
+        let scope_filter = self
+            .deps
+            .runtime_config
+            .memory_scopes
+            .load()
+            .filter_for_channel(self.id.as_ref());
+        let best = match faq::best_match(
+            &self.deps.sqlite_pool,
+            &embedding,
+            self.id.as_ref(),
+            &scope_filter,
+        )
+        .await
+        {
+            Ok(best) => best?,
+            Err(error) => {
+                tracing::warn!(%error, channel_id = %self.id, "faq lookup failed");
+                return None;
+            }
+        };
+
+        let outcome = faq::MatchOutcome::classify(
+            best.similarity,
+            channel_config.faq_answer_threshold,
+            channel_config.faq_context_threshold,
+        );
+        tracing::debug!(
+            channel_id = %self.id,
+            entry_id = %best.entry.id,
+            similarity = best.similarity,
+            outcome = outcome.as_str(),
+            "faq match checked"
+        );
+        faq::record_match(
+            &self.deps.sqlite_pool,
+            self.id.as_ref(),
+            text,
+            Some(&best),
+            outcome,
+        )
+        .await;
+        (outcome != faq::MatchOutcome::Miss).then_some((best, outcome))
+    }
+
+    /// Whether this channel drafts replies speculatively: its `/draft`
+    /// override, else the agent's `speculative_drafts`.
+    fn speculative_drafts_enabled(&self) -> bool {
+        let rc = &self.deps.runtime_config;
+        rc.settings
+            .load()
+            .as_ref()
+            .as_ref()
+            .and_then(|settings| settings.channel_speculative_drafts(self.id.as_ref()))
+            .unwrap_or(rc.channel_config.load().speculative_drafts)
+    }
+
+    /// Answer with a fast-model draft reviewed by the channel model (see
+    /// [`speculative`]). Returns false, having sent nothing final, when the
+    /// normal turn should run instead: drafting failed or produced nothing
+    /// sendable, or the review escalated.
+    async fn try_speculative_reply(
+        &self,
+        user_text: &str,
+        system_prompt: &str,
+        adapter: Option<&str>,
+    ) -> bool {
+        let rc = &self.deps.runtime_config;
+        let routing = rc.routing.load();
+        let draft_model = routing.smart.fast_model(&routing).to_string();
+        let review_model = routing.resolve(ProcessType::Channel, None).to_string();
+        if draft_model == review_model {
+            return false;
+        }
+        let prompt_engine = rc.prompts.load();
+        let (Ok(draft_instructions), Ok(review_instructions)) = (
+            prompt_engine.render_static("fragments/speculative_draft"),
+            prompt_engine.render_static("fragments/speculative_verify"),
+        ) else {
+            tracing::warn!(channel_id = %self.id, "failed to render speculative draft prompts");
+            return false;
+        };
+        let history = self.state.history.read().await.clone();
+
+        let started = std::time::Instant::now();
+        let draft = match self
+            .speculative_completion(
+                &draft_model,
+                &format!("{system_prompt}\n\n{draft_instructions}"),
+                history.clone(),
+                user_text,
+            )
+            .await
+        {
+            Ok(draft) => draft.trim().to_string(),
+            Err(error) => {
+                tracing::warn!(%error, channel_id = %self.id, "speculative draft failed");
+                return false;
+            }
+        };
+        let draft_ms = started.elapsed().as_millis() as u64;
+        if !speculative::is_sendable(&draft) {
+            return false;
+        }
+
+        let shown_early = adapter.is_some_and(speculative::supports_edits);
+        if shown_early {
+            self.send_routed(OutboundResponse::StreamStart).await.ok();
+            self.send_routed(OutboundResponse::StreamChunk(draft.clone()))
+                .await
+                .ok();
+        }
+
+        let review_started = std::time::Instant::now();
+        let review_prompt = format!("{user_text}\n\n---\nDraft reply:\n{draft}");
+        let verdict = match self
+            .speculative_completion(
+                &review_model,
+                &format!("{system_prompt}\n\n{review_instructions}"),
+                history,
+                &review_prompt,
+            )
+            .await
+        {
+            Ok(response) => speculative::Verdict::parse(&response),
+            // A draft the user can already see stays; an unseen one isn't sent
+            // unreviewed.
+            Err(error) => {
+                tracing::warn!(%error, channel_id = %self.id, "speculative review failed");
+                if shown_early {
+                    speculative::Verdict::Approve
+                } else {
+                    speculative::Verdict::Escalate
+                }
+            }
+        };
+        let review_ms = review_started.elapsed().as_millis() as u64;
+
+        let max_divergence = rc.channel_config.load().draft_max_divergence;
+        let (outcome, divergence, reply) = match verdict {
+            speculative::Verdict::Approve => (speculative::Outcome::Kept, Some(0.0), draft),
+            speculative::Verdict::Edit(edited) if speculative::is_sendable(&edited) => {
+                let divergence = speculative::divergence(&draft, &edited);
+                if divergence <= max_divergence {
+                    (speculative::Outcome::Kept, Some(divergence), draft)
+                } else {
+                    (speculative::Outcome::Replaced, Some(divergence), edited)
+                }
+            }
+            speculative::Verdict::Edit(_) | speculative::Verdict::Escalate => {
+                (speculative::Outcome::Escalated, None, String::new())
+            }
+        };
+
+        tracing::info!(
+            channel_id = %self.id,
+            outcome = outcome.as_str(),
+            divergence = ?divergence,
+            draft_model = %draft_model,
+            review_model = %review_model,
+            draft_ms,
+            review_ms,
+            "speculative reply reviewed"
+        );
+        #[cfg(feature = "metrics")]
+        if let Some(divergence) = divergence {
+            crate::telemetry::Metrics::global()
+                .speculative_draft_divergence
+                .with_label_values(&[&self.deps.agent_id, outcome.as_str()])
+                .observe(divergence);
+        }
+        speculative::record(
+            &self.deps.sqlite_pool,
+            &speculative::DraftRecord {
+                channel_id: self.id.as_ref(),
+                draft_model: &draft_model,
+                review_model: &review_model,
+                outcome,
+                divergence,
+                shown_early,
+                draft_ms,
+                review_ms,
+            },
+        )
+        .await;
+
+        if outcome == speculative::Outcome::Escalated {
+            if shown_early {
+                self.send_routed(OutboundResponse::StreamChunk(
+                    "one sec, looking into this properly.".to_string(),
+                ))
+                .await
+                .ok();
+                self.send_routed(OutboundResponse::StreamEnd).await.ok();
+            }
+            return false;
+        }
+
+        if shown_early {
+            if outcome == speculative::Outcome::Replaced {
+                self.send_routed(OutboundResponse::StreamChunk(reply.clone()))
+                    .await
+                    .ok();
+            }
+            self.send_routed(OutboundResponse::StreamEnd).await.ok();
+        } else {
+            self.send_outbound_text(reply.clone(), "failed to send speculative reply")
+                .await;
+        }
+        self.state
+            .conversation_logger
+            .log_bot_message(&self.state.channel_id, &reply);
+
+        let mut history = self.state.history.write().await;
+        history.push(rig::message::Message::user(user_text));
+        history.push(rig::message::Message::assistant(reply));
+        true
+    }
+
+    /// One tool-free completion for a speculative draft or review.
+    async fn speculative_completion(
+        &self,
+        model_name: &str,
+        preamble: &str,
+        mut history: Vec<rig::message::Message>,
+        prompt: &str,
+    ) -> std::result::Result<String, rig::completion::PromptError> {
+        use rig::completion::Prompt as _;
+
+        let routing = self.deps.runtime_config.routing.load();
+        let model = SpacebotModel::make(&self.deps.llm_manager, model_name)
+            .with_context(&*self.deps.agent_id, "channel")
+            .with_routing((**routing).clone());
+        let agent = AgentBuilder::new(model).preamble(preamble).build();
+        agent.prompt(prompt).with_history(&mut history).await
+    }
+
     /// Register per-turn tools, run the LLM agentic loop, and clean up.
     ///
     /// Returns the prompt result and per-turn flags for the caller to dispatch.
@@ -2244,6 +3124,20 @@ impl Channel {
             .and_then(|v| v.as_str())
             .map(|s| s.to_string());
 
+        // New cron jobs default to the timezone set for this chat or its user.
+        let cron_tool = self.deps.cron_tool.clone().map(|tool| {
+            let sender = (current_inbound.source != "system").then_some((
+                current_inbound.source.as_str(),
+                current_inbound.sender_id.as_str(),
+            ));
+            let locale = crate::agent::channel_prompt::conversation_locale(
+                self.deps.runtime_config.as_ref(),
+                self.id.as_ref(),
+                sender,
+            );
+            tool.with_default_timezone(locale.timezone)
+        });
+
         if let Err(error) = crate::tools::add_channel_tools(
             &self.tool_server,
             self.state.clone(),
@@ -2251,7 +3145,7 @@ impl Channel {
             conversation_id,
             skip_flag.clone(),
             replied_flag.clone(),
-            self.deps.cron_tool.clone(),
+            cron_tool,
             send_agent_message_tool,
             allow_direct_reply,
             adapter.map(|s| s.to_string()),
@@ -2270,8 +3164,40 @@ impl Channel {
         } else {
             **rc.max_turns.load()
         };
-        let model_name = routing.resolve(ProcessType::Channel, None);
-        let model = SpacebotModel::make(&self.deps.llm_manager, model_name)
+        let model_name = if routing.smart.enabled {
+            let pinned = rc
+                .settings
+                .load()
+                .as_ref()
+                .as_ref()
+                .and_then(|settings| settings.channel_model_tier(self.id.as_ref()));
+            let signals = TurnSignals {
+                text: user_text,
+                has_attachments: !attachment_content.is_empty(),
+                is_retrigger,
+            };
+            let decision = routing
+                .smart
+                .route(
+                    &routing,
+                    &self.deps.llm_manager,
+                    &rc.prompts.load(),
+                    &signals,
+                    pinned,
+                )
+                .await;
+            tracing::info!(
+                channel_id = %self.id,
+                tier = %decision.tier,
+                model = %decision.model,
+                reason = %decision.reason,
+                "smart routing picked channel model"
+            );
+            decision.model
+        } else {
+            routing.resolve(ProcessType::Channel, None).to_string()
+        };
+        let model = SpacebotModel::make(&self.deps.llm_manager, &model_name)
             .with_context(&*self.deps.agent_id, "channel")
             .with_routing((**routing).clone());
 
@@ -3061,7 +3987,7 @@ impl Channel {
 
     /// Get the current status block as a string.
     pub async fn get_status(&self) -> String {
-        let temporal_context = TemporalContext::from_runtime(self.deps.runtime_config.as_ref());
+        let temporal_context = self.temporal_context(self.current_inbound.as_ref());
         let current_time_line = temporal_context.current_time_line();
         let system_info = self.build_system_info().await;
         let status = self.state.status_block.read().await;
@@ -3255,6 +4181,71 @@ fn compute_listen_mode_invocation(message: &InboundMessage, raw_text: &str) -> (
     (invoked_by_command, invoked_by_mention, invoked_by_reply)
 }
 
+/// Prompt for slash commands that take arguments and are carried out with
+/// agent tools. `None` for anything else, including argument-less built-ins.
+fn slash_command_prompt(command: &SlashCommand, timezone: Option<&str>) -> Option<String> {
+    match (command.name.as_str(), command.subcommand.as_deref()) {
+        ("remind", None) => {
+            let request = match (command.option("when"), command.option("what")) {
+                (Some(when), Some(what)) => format!("when: {when}\nreminder: {what}"),
+                _ => command.text(),
+            };
+            if request.is_empty() {
+                return Some(
+                    "the user ran /remind without saying when or what. reply with one line of usage: /remind <when> <what>"
+                        .to_string(),
+                );
+            }
+            let timezone = timezone
+                .map(|timezone| {
+                    format!("the user's local time is {timezone}; pass it as the job's timezone. ")
+                })
+                .unwrap_or_default();
+            Some(format!(
+                "set a one-time reminder in this conversation using the cron tool: create a run_once job \
+                 with a cron_expr for the requested local time, delivered here, whose prompt posts the reminder. \
+                 {timezone}if the time is ambiguous, pick the nearest sensible future time.\n\
+                 {request}\n\
+                 reply with one line confirming what you'll remind about and when."
+            ))
+        }
+        ("summarize", None) => {
+            let since = command
+                .option("since")
+                .map(str::to_string)
+                .unwrap_or_else(|| command.text());
+            let window = if since.is_empty() {
+                "the recent conversation in this channel".to_string()
+            } else {
+                format!("this channel's conversation since {since}")
+            };
+            Some(format!(
+                "summarize {window} using channel context and channel tools. \
+                 reply with short sections for decisions, open questions, and action items (with owners when known); \
+                 skip empty sections. if nothing meaningful happened, reply exactly: nothing to summarize."
+            ))
+        }
+        ("memory", Some("search")) => {
+            let query = command
+                .option("query")
+                .map(str::to_string)
+                .unwrap_or_else(|| command.text());
+            if query.is_empty() {
+                return Some(
+                    "the user ran /memory search without a query. reply with one line of usage: /memory search <query>"
+                        .to_string(),
+                );
+            }
+            Some(format!(
+                "branch to search memory for: {query}\n\
+                 reply with the most relevant memories as a short list, one line each. \
+                 if nothing relevant is found, reply exactly: no memories found for \"{query}\"."
+            ))
+        }
+        _ => None,
+    }
+}
+
 fn looks_like_liveness_ping(text: &str) -> bool {
     let text = text.trim().to_lowercase();
     text.contains("you here")
@@ -3309,11 +4300,12 @@ fn should_send_quiet_mode_fallback(
 #[cfg(test)]
 mod tests {
     use super::{
-        QuietModeFallbackState, compute_listen_mode_invocation, recv_channel_event,
+        Channel, QuietModeFallbackState, compute_listen_mode_invocation, recv_channel_event,
         should_process_event_for_channel, should_send_discord_quiet_mode_ping_ack,
-        should_send_quiet_mode_fallback,
+        should_send_quiet_mode_fallback, slash_command_prompt,
     };
     use crate::memory::MemoryType;
+    use crate::messaging::commands::SlashCommand;
     use crate::{AgentId, ChannelId, InboundMessage, MessageContent, ProcessEvent, ProcessId};
     use std::collections::HashMap;
     use std::sync::Arc;
@@ -3342,6 +4334,229 @@ mod tests {
         }
     }
 
+    struct NoopEmbedder;
+
+    #[async_trait::async_trait]
+    impl crate::memory::embedding::EmbeddingProvider for NoopEmbedder {
+        fn name(&self) -> &'static str {
+            "test"
+        }
+
+        fn model(&self) -> &str {
+            "noop"
+        }
+
+        fn dimensions(&self) -> usize {
+            2
+        }
+
+        async fn embed(&self, texts: Vec<String>) -> crate::error::Result<Vec<Vec<f32>>> {
+            Ok(texts.iter().map(|_| vec![0.0, 0.0]).collect())
+        }
+    }
+
+    /// A channel backed by a migrated in-memory database, with no LLM
+    /// provider configured. Returns the channel, the receiver its replies go
+    /// to, the database, and the directory everything else lives in.
+    async fn test_channel(
+        channel_id: &str,
+    ) -> (
+        Channel,
+        tokio::sync::mpsc::Receiver<crate::RoutedResponse>,
+        sqlx::SqlitePool,
+        tempfile::TempDir,
+    ) {
+        let instance_dir = tempfile::tempdir().unwrap();
+        let config = crate::config::Config::load_from_str(
+            "[[agents]]\nid = \"main\"\n",
+            instance_dir.path(),
+        )
+        .unwrap();
+        let agent_config = config.resolve_agents().remove(0);
+
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("failed to create sqlite memory pool");
+        sqlx::migrate!("./migrations")
+            .run(&pool)
+            .await
+            .expect("migrations");
+        sqlx::query("INSERT INTO channels (id, platform) VALUES (?, 'portal')")
+            .bind(channel_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let lance = lancedb::connect(instance_dir.path().join("lance").to_str().unwrap())
+            .execute()
+            .await
+            .unwrap();
+        let memory_search = Arc::new(crate::memory::MemorySearch::new(
+            crate::memory::MemoryStore::new(pool.clone()),
+            crate::memory::EmbeddingTable::open_or_create(&lance, 2)
+                .await
+                .unwrap(),
+            Arc::new(crate::memory::EmbeddingModel::from_provider(Arc::new(
+                NoopEmbedder,
+            ))),
+        ));
+        let runtime_config = Arc::new(crate::config::RuntimeConfig::new(
+            instance_dir.path(),
+            &agent_config,
+            &config.defaults,
+            crate::prompts::PromptEngine::new("en").unwrap(),
+            Default::default(),
+            Default::default(),
+        ));
+        let (event_tx, memory_event_tx) = crate::create_process_event_buses();
+        let agent_id: AgentId = Arc::from(agent_config.id.as_str());
+        let deps = crate::AgentDeps {
+            agent_id: agent_id.clone(),
+            memory_search,
+            llm_manager: Arc::new(
+                crate::llm::LlmManager::new(config.llm.clone())
+                    .await
+                    .unwrap(),
+            ),
+            mcp_manager: Arc::new(crate::mcp::McpManager::new(Vec::new())),
+            task_store: Arc::new(crate::tasks::TaskStore::new(pool.clone())),
+            project_store: Arc::new(crate::projects::ProjectStore::new(pool.clone())),
+            cron_tool: None,
+            runtime_config,
+            event_tx: event_tx.clone(),
+            memory_event_tx,
+            sqlite_pool: pool.clone(),
+            messaging_manager: None,
+            sandbox: Arc::new(crate::sandbox::Sandbox::new_for_test(
+                Arc::new(arc_swap::ArcSwap::from_pointee(
+                    agent_config.sandbox.clone(),
+                )),
+                agent_config.workspace.clone(),
+            )),
+            links: Arc::new(arc_swap::ArcSwap::from_pointee(Vec::new())),
+            agent_names: Arc::new(HashMap::new()),
+            humans: Arc::new(arc_swap::ArcSwap::from_pointee(Vec::new())),
+            task_store_registry: Arc::new(arc_swap::ArcSwap::from_pointee(HashMap::new())),
+            process_control_registry: Arc::new(
+                crate::agent::process_control::ProcessControlRegistry::new(),
+            ),
+            injection_tx: tokio::sync::mpsc::channel(1).0,
+            leader: crate::leader::LeaderLease::disabled(),
+            quota: crate::quota::AgentQuota::new(agent_id, &agent_config.quotas, pool.clone()),
+            runtime: None,
+        };
+
+        let (response_tx, response_rx) = tokio::sync::mpsc::channel(8);
+        let (channel, _message_tx) = Channel::new(
+            Arc::from(channel_id),
+            deps,
+            response_tx,
+            event_tx.subscribe(),
+            instance_dir.path().join("screenshots"),
+            instance_dir.path().join("logs"),
+            None,
+            None,
+        );
+        (channel, response_rx, pool, instance_dir)
+    }
+
+    /// Wait for the channel's background writes to log `expected` user
+    /// messages.
+    async fn wait_for_user_messages(pool: &sqlx::SqlitePool, channel_id: &str, expected: i64) {
+        let mut logged = 0;
+        for _ in 0..100 {
+            logged = sqlx::query_scalar(
+                "SELECT COUNT(*) FROM conversation_messages WHERE channel_id = ? AND role = 'user'",
+            )
+            .bind(channel_id)
+            .fetch_one(pool)
+            .await
+            .unwrap();
+            if logged >= expected {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert_eq!(logged, expected);
+    }
+
+    fn portal_message(channel_id: &str, content: &str) -> InboundMessage {
+        let mut message = inbound_message("portal", &[], content);
+        message.id = uuid::Uuid::new_v4().to_string();
+        message.conversation_id = channel_id.to_string();
+        message
+    }
+
+    #[tokio::test]
+    async fn muted_channel_records_messages_without_replying() {
+        let (mut channel, mut responses, pool, _dir) = test_channel("portal:muted").await;
+        assert!(
+            channel
+                .state
+                .channel_store
+                .set_muted("portal:muted", true)
+                .await
+                .unwrap()
+        );
+
+        // `/agent-id` always answers when the channel isn't muted.
+        channel
+            .handle_message(portal_message("portal:muted", "/agent-id"))
+            .await
+            .unwrap();
+        wait_for_user_messages(&pool, "portal:muted", 1).await;
+        assert!(responses.try_recv().is_err());
+        assert!(channel.state.status_block.read().await.muted);
+    }
+
+    #[tokio::test]
+    async fn muted_channel_suppresses_coalesced_batches() {
+        let (mut channel, mut responses, pool, _dir) = test_channel("portal:batch").await;
+        channel
+            .state
+            .channel_store
+            .set_muted("portal:batch", true)
+            .await
+            .unwrap();
+
+        channel
+            .handle_message_batch(vec![
+                portal_message("portal:batch", "first"),
+                portal_message("portal:batch", "second"),
+            ])
+            .await
+            .unwrap();
+        wait_for_user_messages(&pool, "portal:batch", 2).await;
+        assert!(responses.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn unmuting_restores_replies() {
+        let (mut channel, mut responses, pool, _dir) = test_channel("portal:unmuted").await;
+        let store = channel.state.channel_store.clone();
+        store.set_muted("portal:unmuted", true).await.unwrap();
+        channel
+            .handle_message(portal_message("portal:unmuted", "/agent-id"))
+            .await
+            .unwrap();
+        assert!(responses.try_recv().is_err());
+
+        // The flag is read per message, so the next one is answered.
+        store.set_muted("portal:unmuted", false).await.unwrap();
+        channel
+            .handle_message(portal_message("portal:unmuted", "/agent-id"))
+            .await
+            .unwrap();
+        let reply = responses.try_recv().expect("unmuted channel should reply");
+        assert!(
+            matches!(reply.response, crate::OutboundResponse::Text(ref text) if text == "main")
+        );
+        assert!(!channel.state.status_block.read().await.muted);
+        wait_for_user_messages(&pool, "portal:unmuted", 2).await;
+    }
+
     #[tokio::test]
     async fn channel_event_loop_continues_after_lagged_broadcast() {
         let (event_tx, mut event_rx) = tokio::sync::broadcast::channel::<ProcessEvent>(2);
@@ -3501,6 +4716,43 @@ mod tests {
         assert!(!invoked_by_reply);
     }
 
+    #[test]
+    fn slash_command_prompts_accept_structured_and_typed_arguments() {
+        let structured = SlashCommand {
+            name: "memory".into(),
+            subcommand: Some("search".into()),
+            options: vec![("query".into(), "budget numbers".into())],
+        };
+        let typed = SlashCommand::parse("/memory search budget numbers").unwrap();
+        let prompt = slash_command_prompt(&structured, None).unwrap();
+        assert!(prompt.contains("search memory for: budget numbers"));
+        assert_eq!(slash_command_prompt(&typed, None), Some(prompt));
+
+        let remind = SlashCommand {
+            name: "remind".into(),
+            subcommand: None,
+            options: vec![
+                ("when".into(), "in 2 hours".into()),
+                ("what".into(), "stretch".into()),
+            ],
+        };
+        assert!(
+            slash_command_prompt(&remind, None)
+                .unwrap()
+                .contains("when: in 2 hours\nreminder: stretch")
+        );
+        assert!(
+            slash_command_prompt(&remind, Some("Europe/Berlin"))
+                .unwrap()
+                .contains("local time is Europe/Berlin")
+        );
+
+        assert!(slash_command_prompt(&SlashCommand::parse("/status").unwrap(), None).is_none());
+        assert!(
+            slash_command_prompt(&SlashCommand::parse("/memory forget x").unwrap(), None).is_none()
+        );
+    }
+
     #[test]
     fn discord_quiet_mode_ping_ack_requires_directed_ping() {
         let directed_message = inbound_message(
@@ -12,7 +12,7 @@ use crate::AgentDeps;
 use crate::config::ApiType;
 use rig::message::{ImageMediaType, MimeType, UserContent};
 use serde::{Deserialize, Serialize};
-use std::path::{Path, PathBuf};
+use std::path::Path;
 
 /// Image MIME types we support for vision.
 const IMAGE_MIME_PREFIXES: &[&str] = &["image/jpeg", "image/png", "image/gif", "image/webp"];
@@ -526,18 +526,6 @@ pub(crate) async fn save_channel_attachments(
     let mut results = Vec::with_capacity(attachments.len());
 
     for attachment in attachments {
-        let safe_name = match sanitize_filename(&attachment.filename) {
-            Ok(name) => name,
-            Err(error) => {
-                tracing::warn!(
-                    %error,
-                    filename = %attachment.filename,
-                    "rejected unsafe attachment filename"
-                );
-                continue;
-            }
-        };
-
         let bytes = match download_attachment_bytes(http, attachment).await {
             Ok(bytes) => bytes,
             Err(error) => {
@@ -550,85 +538,92 @@ pub(crate) async fn save_channel_attachments(
             }
         };
 
-        let saved_filename = match deduplicate_filename(pool, saved_dir, &safe_name).await {
-            Ok(name) => name,
+        match persist_attachment(
+            pool,
+            channel_id,
+            saved_dir,
+            &attachment.filename,
+            &attachment.mime_type,
+            &bytes,
+        )
+        .await
+        {
+            Ok(meta) => results.push((meta, bytes)),
             Err(error) => {
                 tracing::warn!(
                     %error,
                     filename = %attachment.filename,
-                    "failed to compute unique filename"
+                    "failed to save channel attachment"
                 );
-                continue;
-            }
-        };
-
-        let disk_path = saved_dir.join(&saved_filename);
-
-        // Use create_new for atomic creation — prevents race conditions where
-        // two concurrent saves compute the same deduplicated name.
-        match write_file_atomic(&disk_path, &bytes).await {
-            Ok(()) => {}
-            Err(error) => {
-                tracing::warn!(
-                    %error,
-                    path = %disk_path.display(),
-                    "failed to write attachment to disk"
-                );
-                continue;
             }
         }
+    }
 
-        let id = uuid::Uuid::new_v4().to_string();
-        let size_bytes = bytes.len() as u64;
-        let disk_path_str = disk_path.to_string_lossy().to_string();
-
-        let insert_result = sqlx::query(
-            "INSERT INTO saved_attachments \
-             (id, channel_id, original_filename, saved_filename, mime_type, size_bytes, disk_path) \
-             VALUES (?, ?, ?, ?, ?, ?, ?)",
-        )
-        .bind(&id)
-        .bind(channel_id)
-        .bind(&attachment.filename)
-        .bind(&saved_filename)
-        .bind(&attachment.mime_type)
-        .bind(size_bytes as i64)
-        .bind(&disk_path_str)
-        .execute(pool)
-        .await;
-
-        if let Err(error) = insert_result {
-            tracing::warn!(
-                %error,
-                filename = %attachment.filename,
-                "failed to record saved attachment in database"
-            );
-            // File is on disk but not tracked — clean up
-            let _ = tokio::fs::remove_file(&disk_path).await;
-            continue;
-        }
-
-        tracing::info!(
-            attachment_id = %id,
-            original = %attachment.filename,
-            saved = %saved_filename,
-            size = size_bytes,
-            "saved channel attachment"
-        );
+    results
+}
 
-        results.push((
-            SavedAttachmentMeta {
-                id,
-                filename: attachment.filename.clone(),
-                saved_filename,
-                mime_type: attachment.mime_type.clone(),
-                size_bytes,
-            },
-            bytes,
+/// Write `bytes` to `workspace/saved/` under a unique name derived from
+/// `original_filename` and record it in `saved_attachments` against
+/// `channel_id`. Shared by downloaded attachments and files agents generate
+/// themselves, such as charts.
+pub(crate) async fn persist_attachment(
+    pool: &sqlx::SqlitePool,
+    channel_id: &str,
+    saved_dir: &Path,
+    original_filename: &str,
+    mime_type: &str,
+    bytes: &[u8],
+) -> std::result::Result<SavedAttachmentMeta, String> {
+    let safe_name = sanitize_filename(original_filename);
+    let saved_filename = deduplicate_filename(pool, saved_dir, &safe_name).await?;
+    let disk_path = saved_dir.join(&saved_filename);
+
+    // Use create_new for atomic creation — prevents race conditions where
+    // two concurrent saves compute the same deduplicated name.
+    write_file_atomic(&disk_path, bytes).await?;
+
+    let id = uuid::Uuid::new_v4().to_string();
+    let size_bytes = bytes.len() as u64;
+    let disk_path_str = disk_path.to_string_lossy().to_string();
+
+    let insert_result = sqlx::query(
+        "INSERT INTO saved_attachments \
+         (id, channel_id, original_filename, saved_filename, mime_type, size_bytes, disk_path) \
+         VALUES (?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&id)
+    .bind(channel_id)
+    .bind(original_filename)
+    .bind(&saved_filename)
+    .bind(mime_type)
+    .bind(size_bytes as i64)
+    .bind(&disk_path_str)
+    .execute(pool)
+    .await;
+
+    if let Err(error) = insert_result {
+        // File is on disk but not tracked — clean up
+        let _ = tokio::fs::remove_file(&disk_path).await;
+        return Err(format!(
+            "failed to record saved attachment in database: {error}"
         ));
     }
 
-    results
+    tracing::info!(
+        attachment_id = %id,
+        original = %original_filename,
+        saved = %saved_filename,
+        size = size_bytes,
+        "saved attachment"
+    );
+
+    Ok(SavedAttachmentMeta {
+        id,
+        filename: original_filename.to_string(),
+        saved_filename,
+        mime_type: mime_type.to_string(),
+        size_bytes,
+    })
 }
 
 /// Build a text annotation summarising saved attachments for inclusion in
@@ -688,29 +683,55 @@ pub(crate) fn annotation_from_metadata(metadata: &serde_json::Value) -> Option<S
 /// Sanitize a user-provided filename to prevent path traversal attacks.
 ///
 /// Extracts only the file name component (strips directory separators and
-/// parent references), rejects `.`, `..`, and empty results. Falls back to
-/// `attachment` if the stem is entirely stripped.
-fn sanitize_filename(raw: &str) -> Result<String, String> {
+/// parent references) and replaces control characters, backslashes, and
+/// colons. Falls back to `attachment` if nothing but dots is left.
+pub(crate) fn sanitize_filename(raw: &str) -> String {
     // Extract just the filename component — strips any directory prefixes
     let basename = Path::new(raw)
         .file_name()
         .map(|name| name.to_string_lossy().to_string())
         .unwrap_or_default();
 
+    // Windows-style paths survive `file_name` on Unix, so neutralize their
+    // separators too
+    let cleaned: String = basename
+        .trim()
+        .chars()
+        .map(|character| {
+            if character.is_control() || matches!(character, '/' | '\\' | ':') {
+                '_'
+            } else {
+                character
+            }
+        })
+        .collect();
+
     // Reject dangerous or empty names
-    let trimmed = basename.trim();
-    if trimmed.is_empty() || trimmed == "." || trimmed == ".." {
+    if cleaned.chars().all(|character| character == '.') {
         // If the original had an extension, preserve it with a safe stem
         let extension = Path::new(raw)
             .extension()
             .map(|e| e.to_string_lossy().to_string());
         return match extension {
-            Some(ext) => Ok(format!("attachment.{ext}")),
-            None => Ok("attachment".to_string()),
+            Some(ext) => format!("attachment.{ext}"),
+            None => "attachment".to_string(),
         };
     }
 
-    Ok(trimmed.to_string())
+    cleaned
+}
+
+/// `name` with `_N` inserted before its extension.
+pub(crate) fn numbered_filename(name: &str, counter: u32) -> String {
+    let path = Path::new(name);
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| name.to_string());
+    match path.extension() {
+        Some(ext) => format!("{stem}_{counter}.{}", ext.to_string_lossy()),
+        None => format!("{stem}_{counter}"),
+    }
 }
 
 /// Compute a unique filename within `saved_dir`, appending `_N` suffixes
@@ -725,21 +746,8 @@ async fn deduplicate_filename(
         return Ok(original.to_string());
     }
 
-    // Split into stem + extension
-    let path = PathBuf::from(original);
-    let stem = path
-        .file_stem()
-        .map(|s| s.to_string_lossy().to_string())
-        .unwrap_or_else(|| original.to_string());
-    let extension = path
-        .extension()
-        .map(|e| format!(".{}", e.to_string_lossy()));
-
     for counter in 2..=999 {
-        let candidate = match &extension {
-            Some(ext) => format!("{stem}_{counter}{ext}"),
-            None => format!("{stem}_{counter}"),
-        };
+        let candidate = numbered_filename(original, counter);
         if !filename_taken(pool, saved_dir, &candidate).await {
             return Ok(candidate);
         }
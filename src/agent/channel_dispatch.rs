@@ -156,7 +156,14 @@ pub(crate) async fn spawn_memory_persistence_branch(
     state: &ChannelState,
     deps: &AgentDeps,
 ) -> std::result::Result<BranchId, AgentError> {
-    let contract_state = Arc::new(MemoryPersistenceContractState::default());
+    let embedding_batch_size = deps
+        .runtime_config
+        .memory_persistence
+        .load()
+        .embedding_batch_size;
+    let contract_state = Arc::new(MemoryPersistenceContractState::with_batched_embeddings(
+        embedding_batch_size,
+    ));
 
     let prompt_engine = deps.runtime_config.prompts.load();
     let system_prompt = prompt_engine
@@ -256,6 +263,7 @@ async fn spawn_branch(
 
     let tool_server = crate::tools::create_branch_tool_server(
         Some(state.clone()),
+        None,
         state.deps.agent_id.clone(),
         state.deps.task_store.clone(),
         state.deps.memory_search.clone(),
@@ -299,29 +307,31 @@ async fn spawn_branch(
         description = %description,
     );
     let handle = tokio::spawn(
-        async move {
-            if let Err(error) = branch.run(&prompt).await {
-                tracing::error!(branch_id = %branch_id, %error, "branch failed");
-                // Scrub the failure message in case the error contains secrets
-                // (e.g. from failed tool calls echoing back prompt content).
-                // Layer 1: exact-match redaction of known secrets from the store.
-                // Layer 2: regex-based redaction of unknown secret patterns.
-                let raw = format!("Branch failed: {error}");
-                let conclusion = if let Some(store) = secrets_snapshot.as_ref() {
-                    crate::secrets::scrub::scrub_with_store(&raw, store)
-                } else {
-                    raw
-                };
-                let conclusion = crate::secrets::scrub::scrub_leaks(&conclusion);
-                let _ = event_tx.send(crate::ProcessEvent::BranchResult {
-                    agent_id,
-                    branch_id,
-                    channel_id,
-                    conclusion,
-                });
+        state.deps.quota.gate(
+            async move {
+                if let Err(error) = branch.run(&prompt).await {
+                    tracing::error!(branch_id = %branch_id, %error, "branch failed");
+                    // Scrub the failure message in case the error contains secrets
+                    // (e.g. from failed tool calls echoing back prompt content).
+                    // Layer 1: exact-match redaction of known secrets from the store.
+                    // Layer 2: regex-based redaction of unknown secret patterns.
+                    let raw = format!("Branch failed: {error}");
+                    let conclusion = if let Some(store) = secrets_snapshot.as_ref() {
+                        crate::secrets::scrub::scrub_with_store(&raw, store)
+                    } else {
+                        raw
+                    };
+                    let conclusion = crate::secrets::scrub::scrub_leaks(&conclusion);
+                    let _ = event_tx.send(crate::ProcessEvent::BranchResult {
+                        agent_id,
+                        branch_id,
+                        channel_id,
+                        conclusion,
+                    });
+                }
             }
-        }
-        .instrument(branch_span),
+            .instrument(branch_span),
+        ),
     );
 
     {
@@ -556,7 +566,7 @@ async fn spawn_worker_inner(
         Some(state.channel_id.clone()),
         secrets_store,
         "builtin",
-        worker.run().instrument(worker_span),
+        state.deps.quota.gate(worker.run().instrument(worker_span)),
     );
 
     state.worker_handles.write().await.insert(worker_id, handle);
@@ -713,41 +723,43 @@ async fn spawn_opencode_worker_inner(
         Some(state.channel_id.clone()),
         oc_secrets_store,
         "opencode",
-        async move {
-            let result = worker.run().await.map_err(SpacebotError::from);
-
-            // Release the directory claim regardless of success or failure.
-            release_pool.release_directory(&release_directory).await;
-
-            let result = result?;
+        state.deps.quota.gate(
+            async move {
+                let result = worker.run().await.map_err(SpacebotError::from);
+
+                // Release the directory claim regardless of success or failure.
+                release_pool.release_directory(&release_directory).await;
+
+                let result = result?;
+
+                // Persist the transcript built from SSE events so the worker detail
+                // view can show the full conversation (text + tool calls + results).
+                if !result.transcript.is_empty() {
+                    let blob = crate::conversation::worker_transcript::serialize_steps(
+                        &result.transcript,
+                    );
+                    let tool_calls = result.tool_calls;
+                    let wid = worker_id.to_string();
+                    let pool = sqlite_pool.clone();
+                    tokio::spawn(async move {
+                        if let Err(error) = sqlx::query(
+                            "UPDATE worker_runs SET transcript = ?, tool_calls = ? WHERE id = ?",
+                        )
+                        .bind(&blob)
+                        .bind(tool_calls)
+                        .bind(&wid)
+                        .execute(&pool)
+                        .await
+                        {
+                            tracing::warn!(%error, worker_id = wid, "failed to persist OpenCode transcript");
+                        }
+                    });
+                }
 
-            // Persist the transcript built from SSE events so the worker detail
-            // view can show the full conversation (text + tool calls + results).
-            if !result.transcript.is_empty() {
-                let blob = crate::conversation::worker_transcript::serialize_steps(
-                    &result.transcript,
-                );
-                let tool_calls = result.tool_calls;
-                let wid = worker_id.to_string();
-                let pool = sqlite_pool.clone();
-                tokio::spawn(async move {
-                    if let Err(error) = sqlx::query(
-                        "UPDATE worker_runs SET transcript = ?, tool_calls = ? WHERE id = ?",
-                    )
-                    .bind(&blob)
-                    .bind(tool_calls)
-                    .bind(&wid)
-                    .execute(&pool)
-                    .await
-                    {
-                        tracing::warn!(%error, worker_id = wid, "failed to persist OpenCode transcript");
-                    }
-                });
+                Ok::<String, SpacebotError>(result.result_text)
             }
-
-            Ok::<String, SpacebotError>(result.result_text)
-        }
-        .instrument(worker_span),
+            .instrument(worker_span),
+        ),
     );
 
     state.worker_handles.write().await.insert(worker_id, handle);
@@ -966,33 +978,35 @@ pub async fn resume_idle_worker_into_state(
                 Some(state.channel_id.clone()),
                 oc_secrets_store,
                 "opencode",
-                async move {
-                    let result = worker.run().await.map_err(SpacebotError::from)?;
-                    // Persist final transcript.
-                    if !result.transcript.is_empty() {
-                        let blob = crate::conversation::worker_transcript::serialize_steps(
-                            &result.transcript,
-                        );
-                        let tool_calls = result.tool_calls;
-                        let wid = worker_id.to_string();
-                        let pool = sqlite_pool.clone();
-                        tokio::spawn(async move {
-                            if let Err(error) = sqlx::query(
-                                "UPDATE worker_runs SET transcript = ?, tool_calls = ? WHERE id = ?",
-                            )
-                            .bind(&blob)
-                            .bind(tool_calls)
-                            .bind(&wid)
-                            .execute(&pool)
-                            .await
-                            {
-                                tracing::warn!(%error, worker_id = wid, "failed to persist OpenCode transcript");
-                            }
-                        });
+                state.deps.quota.gate(
+                    async move {
+                        let result = worker.run().await.map_err(SpacebotError::from)?;
+                        // Persist final transcript.
+                        if !result.transcript.is_empty() {
+                            let blob = crate::conversation::worker_transcript::serialize_steps(
+                                &result.transcript,
+                            );
+                            let tool_calls = result.tool_calls;
+                            let wid = worker_id.to_string();
+                            let pool = sqlite_pool.clone();
+                            tokio::spawn(async move {
+                                if let Err(error) = sqlx::query(
+                                    "UPDATE worker_runs SET transcript = ?, tool_calls = ? WHERE id = ?",
+                                )
+                                .bind(&blob)
+                                .bind(tool_calls)
+                                .bind(&wid)
+                                .execute(&pool)
+                                .await
+                                {
+                                    tracing::warn!(%error, worker_id = wid, "failed to persist OpenCode transcript");
+                                }
+                            });
+                        }
+                        Ok::<String, SpacebotError>(result.result_text)
                     }
-                    Ok::<String, SpacebotError>(result.result_text)
-                }
-                .instrument(worker_span),
+                    .instrument(worker_span),
+                ),
             );
 
             state.worker_handles.write().await.insert(worker_id, handle);
@@ -1098,7 +1112,7 @@ pub async fn resume_idle_worker_into_state(
                 Some(state.channel_id.clone()),
                 secrets_store,
                 "builtin",
-                worker.run().instrument(worker_span),
+                state.deps.quota.gate(worker.run().instrument(worker_span)),
             );
 
             state.worker_handles.write().await.insert(worker_id, handle);
@@ -434,6 +434,7 @@ pub(crate) fn event_is_for_channel(event: &ProcessEvent, channel_id: &ChannelId)
         | ProcessEvent::StatusUpdate { .. }
         | ProcessEvent::TaskUpdated { .. }
         | ProcessEvent::WorkerText { .. }
+        | ProcessEvent::ChannelUsage { .. }
         | ProcessEvent::CortexChatUpdate { .. } => false,
     }
 }
@@ -1041,6 +1042,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn temporal_context_time_line_includes_location() {
+        let context = crate::agent::channel_prompt::TemporalContext {
+            now_utc: chrono::DateTime::parse_from_rfc3339("2026-03-01T12:00:00Z")
+                .unwrap()
+                .to_utc(),
+            timezone: crate::agent::channel_prompt::TemporalContext::resolve_timezone_from_names(
+                Some("Europe/Berlin".to_string()),
+                None,
+            ),
+            location: Some("Berlin, Germany".to_string()),
+        };
+        let line = context.current_time_line();
+        assert!(line.starts_with("2026-03-01 13:00:00 CET (Europe/Berlin, UTC+01:00)"));
+        assert!(line.ends_with("; location: Berlin, Germany"));
+        assert_eq!(context.timezone_name(), Some("Europe/Berlin"));
+    }
+
     #[test]
     fn format_batched_message_includes_absolute_and_relative_time() {
         let formatted = super::format_batched_user_message(
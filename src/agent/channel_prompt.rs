@@ -30,6 +30,7 @@ pub(crate) enum TemporalTimezone {
 pub(crate) struct TemporalContext {
     pub(crate) now_utc: DateTime<Utc>,
     pub(crate) timezone: TemporalTimezone,
+    pub(crate) location: Option<String>,
 }
 
 impl TemporalContext {
@@ -41,6 +42,37 @@ impl TemporalContext {
         Self {
             now_utc,
             timezone: Self::resolve_timezone_from_names(user_timezone, cron_timezone),
+            location: None,
+        }
+    }
+
+    /// Like [`Self::from_runtime`], but a timezone or location set for the
+    /// sender (`(platform, sender_id)`) or the channel takes precedence over
+    /// the agent's.
+    pub(crate) fn for_conversation(
+        runtime_config: &crate::config::RuntimeConfig,
+        channel_id: &str,
+        sender: Option<(&str, &str)>,
+    ) -> Self {
+        let locale = conversation_locale(runtime_config, channel_id, sender);
+        let mut context = Self::from_runtime(runtime_config);
+        if let Some(timezone_name) = locale.timezone
+            && let Ok(timezone) = timezone_name.parse::<Tz>()
+        {
+            context.timezone = TemporalTimezone::Named {
+                timezone_name,
+                timezone,
+            };
+        }
+        context.location = locale.location;
+        context
+    }
+
+    /// The IANA name of the resolved timezone, or `None` for system local.
+    pub(crate) fn timezone_name(&self) -> Option<&str> {
+        match &self.timezone {
+            TemporalTimezone::Named { timezone_name, .. } => Some(timezone_name),
+            TemporalTimezone::SystemLocal => None,
         }
     }
 
@@ -115,10 +147,51 @@ impl TemporalContext {
     }
 
     pub(crate) fn current_time_line(&self) -> String {
-        format!(
+        let line = format!(
             "{}; UTC {}",
             self.format_timestamp(self.now_utc),
             self.now_utc.format("%Y-%m-%d %H:%M:%S UTC")
-        )
+        );
+        match &self.location {
+            Some(location) => format!("{line}; location: {location}"),
+            None => line,
+        }
+    }
+}
+
+/// The locale that applies to a conversation: each field comes from the
+/// sender's locale when set there, otherwise the channel's. Timezones that
+/// don't name a real zone are skipped.
+pub(crate) fn conversation_locale(
+    runtime_config: &crate::config::RuntimeConfig,
+    channel_id: &str,
+    sender: Option<(&str, &str)>,
+) -> crate::settings::Locale {
+    let Some(settings) = runtime_config.settings.load().as_ref().clone() else {
+        return crate::settings::Locale::default();
+    };
+    let user = sender
+        .and_then(|(platform, sender_id)| {
+            settings
+                .user_locale(platform, sender_id)
+                .inspect_err(|error| tracing::warn!(%error, "failed to read user locale"))
+                .ok()
+                .flatten()
+        })
+        .unwrap_or_default();
+    let channel = settings
+        .channel_locale(channel_id)
+        .inspect_err(|error| tracing::warn!(%error, channel_id, "failed to read channel locale"))
+        .ok()
+        .flatten()
+        .unwrap_or_default();
+
+    let timezone = [&user, &channel]
+        .into_iter()
+        .find(|locale| locale.tz().is_some())
+        .and_then(|locale| locale.timezone.clone());
+    crate::settings::Locale {
+        timezone,
+        location: user.location.or(channel.location),
     }
 }
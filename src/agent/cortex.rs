@@ -17,7 +17,9 @@ use crate::agent::worker::Worker;
 use crate::error::Result;
 use crate::hooks::CortexHook;
 use crate::llm::SpacebotModel;
+use crate::memory::consolidation;
 use crate::memory::maintenance as memory_maintenance;
+use crate::memory::rescore;
 use crate::memory::search::{SearchConfig, SearchMode, SearchSort};
 use crate::memory::types::{Association, MemoryType, RelationType};
 use crate::tasks::{TaskStatus, UpdateTaskInput};
@@ -1343,6 +1345,7 @@ fn signal_from_event(event: ProcessEvent) -> Option<Signal> {
         | ProcessEvent::OpenCodePartUpdated { .. }
         | ProcessEvent::WorkerInitialResult { .. }
         | ProcessEvent::WorkerText { .. }
+        | ProcessEvent::ChannelUsage { .. }
         | ProcessEvent::CortexChatUpdate { .. } => return None,
     })
 }
@@ -1549,6 +1552,10 @@ pub fn spawn_warmup_loop(deps: AgentDeps, logger: CortexLogger) -> tokio::task::
             };
             tokio::time::sleep(Duration::from_secs(sleep_secs)).await;
 
+            if deps.runtime_config.is_paused() {
+                continue;
+            }
+
             if !completed_initial_pass {
                 completed_initial_pass =
                     has_completed_initial_warmup(deps.runtime_config.warmup_status.load().as_ref());
@@ -1840,6 +1847,9 @@ async fn run_cortex_loop(
                 }
             },
             _ = tick_timer.tick() => {
+                if cortex.deps.runtime_config.is_paused() {
+                    continue;
+                }
                 if let Err(error) = cortex.run_health_tick(logger).await {
                     tracing::warn!(%error, "cortex health tick failed");
                 }
@@ -1939,9 +1949,12 @@ async fn run_cortex_loop(
                                 "maintenance_completed",
                                 "Memory maintenance completed",
                                 Some(serde_json::json!({
+                                    "expired": report.expired,
                                     "decayed": report.decayed,
-                                    "pruned": report.pruned,
+                                    "archived": report.archived,
                                     "merged": report.merged,
+                                    "purged": report.purged,
+                                    "mutations_pruned": report.mutations_pruned,
                                 })),
                             );
                         }
@@ -2064,7 +2077,11 @@ async fn run_cortex_loop(
                 ) {
                     tracing::info!("cortex maintenance circuit closed; retries re-enabled");
                 }
-                if refresh_task.is_none()
+                // Bulletins and maintenance are singleton duties; only the
+                // replica holding the agent's lease runs them.
+                let is_leader = cortex.deps.leader.is_leader();
+                if is_leader
+                    && refresh_task.is_none()
                     && !bulletin_refresh_circuit_open
                     && last_bulletin_refresh.elapsed() >= bulletin_interval
                     && now >= next_bulletin_refresh_allowed_at
@@ -2075,24 +2092,35 @@ async fn run_cortex_loop(
                     ));
                 }
 
-                if last_maintenance.elapsed() >= Duration::from_secs(
-                    cortex_config.maintenance_interval_secs.max(1),
-                ) {
+                if is_leader
+                    && let Err(error) = review_overdue_todos(&cortex.deps, logger).await
+                {
+                    tracing::warn!(%error, "overdue todo review failed");
+                }
+
+                if is_leader
+                    && last_maintenance.elapsed()
+                        >= Duration::from_secs(cortex_config.maintenance_interval_secs.max(1))
+                {
                     if maintenance_task.is_none() && maintenance_disabled_at.is_none() {
                         maintenance_task_started_at = Some(Instant::now());
                         let maintenance_config = memory_maintenance::MaintenanceConfig {
                             prune_threshold: cortex_config.maintenance_prune_threshold,
-                            decay_rate: cortex_config.maintenance_decay_rate,
+                            half_lives: cortex_config.maintenance_half_life_days,
                             min_age_days: cortex_config.maintenance_min_age_days,
                             merge_similarity_threshold: cortex_config
                                 .maintenance_merge_similarity_threshold,
+                            trash_retention_days: cortex_config
+                                .maintenance_trash_retention_days,
+                            delete_expired: cortex_config.maintenance_delete_expired,
+                            mutation_retention_days: cortex_config
+                                .maintenance_mutation_retention_days,
                         };
                         let memory_search = cortex.deps.memory_search.clone();
                         logger.log(
                             "maintenance_started",
                             "Memory maintenance started",
                             Some(serde_json::json!({
-                                "decay_rate": maintenance_config.decay_rate,
                                 "prune_threshold": maintenance_config.prune_threshold,
                                 "min_age_days": maintenance_config.min_age_days,
                                 "merge_similarity_threshold": maintenance_config.merge_similarity_threshold,
@@ -2121,6 +2149,21 @@ async fn run_cortex_loop(
                         );
                     }
 
+                    let event_log_retention_days = cortex
+                        .deps
+                        .runtime_config
+                        .channel_config
+                        .load()
+                        .event_log_retention_days;
+                    if let Err(error) = crate::agent::event_log::prune(
+                        &cortex.deps.sqlite_pool,
+                        event_log_retention_days,
+                    )
+                    .await
+                    {
+                        tracing::warn!(%error, "channel event log pruning failed");
+                    }
+
                     last_maintenance = Instant::now();
                 }
 
@@ -2212,6 +2255,10 @@ const BULLETIN_SECTIONS: &[BulletinSection] = &[
 /// Returns formatted sections ready for LLM synthesis.
 async fn gather_bulletin_sections(deps: &AgentDeps) -> String {
     let mut output = String::new();
+    // The bulletin reaches every channel, so it leaves out channel- and
+    // user-scoped memories, and once memory scopes are in use it is built
+    // from unscoped memories only.
+    let bulletin_scope_filter = deps.runtime_config.memory_scopes.load().shared_filter();
 
     for section in BULLETIN_SECTIONS {
         let config = SearchConfig {
@@ -2219,6 +2266,7 @@ async fn gather_bulletin_sections(deps: &AgentDeps) -> String {
             memory_type: section.memory_type,
             sort_by: section.sort_by,
             max_results: section.max_results,
+            scope_filter: bulletin_scope_filter.clone(),
             ..Default::default()
         };
 
@@ -2264,9 +2312,115 @@ async fn gather_bulletin_sections(deps: &AgentDeps) -> String {
         _ => {}
     }
 
+    match gather_open_todos(deps, &bulletin_scope_filter).await {
+        Ok(section) if !section.is_empty() => output.push_str(&section),
+        Err(error) => {
+            tracing::warn!(%error, "failed to gather open todos for bulletin");
+        }
+        _ => {}
+    }
+
+    match gather_unread_replies(deps).await {
+        Ok(section) if !section.is_empty() => output.push_str(&section),
+        Err(error) => {
+            tracing::warn!(%error, "failed to gather unread replies for bulletin");
+        }
+        _ => {}
+    }
+
+    let weather_config = deps.runtime_config.weather.load();
+    match crate::weather::bulletin_section(&weather_config).await {
+        Ok(section) => output.push_str(&section),
+        Err(error) => {
+            tracing::warn!(%error, "failed to fetch forecast for bulletin");
+        }
+    }
+
     output
 }
 
+/// Format open todo and goal memories, overdue and soonest-due first.
+async fn gather_open_todos(
+    deps: &AgentDeps,
+    scope_filter: &crate::memory::scope::ScopeFilter,
+) -> anyhow::Result<String> {
+    let items = crate::memory::todos::list_todos(
+        deps.memory_search.store(),
+        &crate::memory::todos::TodoFilter::default(),
+        20,
+    )
+    .await?;
+
+    let now = chrono::Utc::now();
+    let mut output = String::new();
+    for item in items
+        .iter()
+        .filter(|item| scope_filter.allows(item.memory.scope.as_deref()))
+    {
+        let due = match item.details.due_at {
+            Some(due_at) if item.details.is_overdue(now) => {
+                format!(", OVERDUE since {}", due_at.format("%Y-%m-%d"))
+            }
+            Some(due_at) => format!(", due {}", due_at.format("%Y-%m-%d")),
+            None => String::new(),
+        };
+        output.push_str(&format!(
+            "- [{}] ({}, {}{due}) {}\n",
+            item.memory.memory_type,
+            item.details.status,
+            item.details.priority,
+            item.memory
+                .content
+                .lines()
+                .next()
+                .unwrap_or(&item.memory.content),
+        ));
+    }
+
+    if output.is_empty() {
+        return Ok(output);
+    }
+    Ok(format!("### Open Todos & Goals\n\n{output}\n"))
+}
+
+/// How long an important reply can sit unread before the bulletin flags it.
+const UNREAD_REPLY_GRACE_MINUTES: i64 = 30;
+
+/// Format replies the agent marked important that the platform still
+/// reports unread, so a channel can follow up on them.
+async fn gather_unread_replies(deps: &AgentDeps) -> anyhow::Result<String> {
+    let Some(outbox) = deps
+        .messaging_manager
+        .as_ref()
+        .and_then(|manager| manager.outbox())
+    else {
+        return Ok(String::new());
+    };
+    let sent_before = chrono::Utc::now() - chrono::Duration::minutes(UNREAD_REPLY_GRACE_MINUTES);
+    let unread = outbox.unread(sent_before)?;
+    let ids: Vec<String> = unread.into_iter().map(|(id, _)| id).collect();
+    let messages = crate::conversation::ConversationLogger::new(deps.sqlite_pool.clone())
+        .load_important(&ids)
+        .await?;
+
+    let mut output = String::new();
+    for message in &messages {
+        output.push_str(&format!(
+            "- [{}] (sent {}) {}\n",
+            message.channel_id,
+            message.created_at.format("%Y-%m-%d %H:%M UTC"),
+            message.content.lines().next().unwrap_or(&message.content),
+        ));
+    }
+
+    if output.is_empty() {
+        return Ok(output);
+    }
+    Ok(format!(
+        "### Unread Important Replies\n\nDelivered but not opened yet. Follow up in the channel if it still matters.\n\n{output}\n"
+    ))
+}
+
 /// Query the task store for non-done tasks and format them as a bulletin section.
 async fn gather_active_tasks(deps: &AgentDeps) -> anyhow::Result<String> {
     use crate::tasks::TaskStatus;
@@ -2638,6 +2792,509 @@ pub fn spawn_association_loop(
     })
 }
 
+/// Spawn the importance recalibration loop for an agent.
+///
+/// Periodically folds retrieval, citation, and correction signals into
+/// memory importance and logs each adjustment.
+pub fn spawn_recalibration_loop(
+    deps: AgentDeps,
+    logger: CortexLogger,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        if let Err(error) = run_recalibration_loop(&deps, &logger).await {
+            tracing::error!(%error, "cortex recalibration loop exited with error");
+        }
+    })
+}
+
+async fn run_recalibration_loop(deps: &AgentDeps, logger: &CortexLogger) -> anyhow::Result<()> {
+    tracing::info!("cortex recalibration loop started");
+
+    loop {
+        let interval = deps
+            .runtime_config
+            .cortex
+            .load()
+            .recalibration_interval_secs;
+        if interval == 0 {
+            // Disabled; re-check periodically in case config is hot-reloaded.
+            // Signals are still recorded as memories are used, so drop them
+            // rather than let the table grow with nothing to consume it.
+            tokio::time::sleep(Duration::from_secs(300)).await;
+            if deps.leader.is_leader()
+                && let Err(error) =
+                    crate::memory::feedback::discard_signals(deps.memory_search.store()).await
+            {
+                tracing::warn!(%error, "failed to discard memory signals");
+            }
+            continue;
+        }
+        tokio::time::sleep(Duration::from_secs(interval)).await;
+
+        if !deps.leader.is_leader() || deps.runtime_config.is_paused() {
+            continue;
+        }
+
+        let report =
+            match crate::memory::feedback::recalibrate_importance(deps.memory_search.store()).await
+            {
+                Ok(report) => report,
+                Err(error) => {
+                    tracing::warn!(%error, "importance recalibration pass failed");
+                    continue;
+                }
+            };
+        if report.adjustments.is_empty() {
+            continue;
+        }
+
+        tracing::info!(
+            considered = report.considered,
+            adjusted = report.adjustments.len(),
+            "importance recalibration pass complete"
+        );
+        logger.log(
+            "memory_recalibrated",
+            &format!(
+                "Recalibrated importance for {} of {} memories with usage signals",
+                report.adjustments.len(),
+                report.considered
+            ),
+            Some(serde_json::json!({
+                "considered": report.considered,
+                "adjusted": report.adjustments.len(),
+                "adjustments": report.adjustments.iter().take(20).collect::<Vec<_>>(),
+            })),
+        );
+    }
+}
+
+// -- Rescore loop --
+
+/// Spawn the importance rescoring loop for an agent.
+///
+/// Periodically re-evaluates old memories against recent activity so stale
+/// high-importance memories stop dominating search.
+pub fn spawn_rescore_loop(deps: AgentDeps, logger: CortexLogger) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        if let Err(error) = run_rescore_loop(&deps, &logger).await {
+            tracing::error!(%error, "cortex rescore loop exited with error");
+        }
+    })
+}
+
+async fn run_rescore_loop(deps: &AgentDeps, logger: &CortexLogger) -> anyhow::Result<()> {
+    tracing::info!("cortex rescore loop started");
+
+    loop {
+        let interval = deps.runtime_config.cortex.load().rescore_interval_secs;
+        if interval == 0 {
+            // Disabled; re-check periodically in case config is hot-reloaded.
+            tokio::time::sleep(Duration::from_secs(300)).await;
+            continue;
+        }
+        tokio::time::sleep(Duration::from_secs(interval)).await;
+
+        if !deps.leader.is_leader() || deps.runtime_config.is_paused() {
+            continue;
+        }
+
+        let report = match rescore_memories(
+            &deps.llm_manager,
+            &deps.runtime_config,
+            &deps.agent_id,
+            &deps.memory_search,
+        )
+        .await
+        {
+            Ok(report) => report,
+            Err(error) => {
+                tracing::warn!(%error, "importance rescore pass failed");
+                continue;
+            }
+        };
+        if report.adjustments.is_empty() {
+            continue;
+        }
+
+        tracing::info!(
+            examined = report.examined,
+            lowered = report.lowered,
+            raised = report.raised,
+            heuristic_scored = report.heuristic_scored,
+            "importance rescore pass complete"
+        );
+        logger.log(
+            "memory_rescored",
+            &format!(
+                "Rescored {} of {} old memories ({} lowered, {} raised)",
+                report.adjustments.len(),
+                report.examined,
+                report.lowered,
+                report.raised
+            ),
+            Some(serde_json::json!({
+                "examined": report.examined,
+                "lowered": report.lowered,
+                "raised": report.raised,
+                "heuristic_scored": report.heuristic_scored,
+                "adjustments": report.adjustments.iter().take(20).collect::<Vec<_>>(),
+            })),
+        );
+    }
+}
+
+/// Old memories sent to the model per rescoring prompt.
+const RESCORE_BATCH_SIZE: usize = 20;
+/// Recent related memories shown to the model per rescored memory.
+const RESCORE_RELATED_SHOWN: usize = 5;
+
+/// LLM response shape for scoring a batch of old memories.
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+struct RescoreLlmResponse {
+    #[serde(default)]
+    scores: Vec<RescoreLlmScore>,
+}
+
+/// The model's score for one memory in a rescore batch.
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+struct RescoreLlmScore {
+    /// 1-based label of the memory in the batch.
+    label: usize,
+    /// New importance, 0.0 to 1.0.
+    importance: f32,
+    /// Short explanation of the score.
+    #[serde(default)]
+    reason: String,
+}
+
+/// Re-evaluate the importance of old memories against recent activity.
+///
+/// The compactor model scores candidates in batches. Batches it fails to
+/// score, and memories it leaves out, fall back to the activity heuristic.
+pub async fn rescore_memories(
+    llm_manager: &Arc<crate::llm::LlmManager>,
+    runtime_config: &crate::config::RuntimeConfig,
+    agent_id: &str,
+    memory_search: &crate::memory::MemorySearch,
+) -> anyhow::Result<rescore::RescoreReport> {
+    let config = rescore::RescoreConfig::from_cortex(&runtime_config.cortex.load());
+    let store = memory_search.store();
+    let candidates =
+        rescore::find_candidates(store, memory_search.embedding_table(), &config).await?;
+    let mut report = rescore::RescoreReport {
+        examined: candidates.len(),
+        ..Default::default()
+    };
+    if candidates.is_empty() {
+        return Ok(report);
+    }
+
+    let prompt_engine = runtime_config.prompts.load();
+    let rescore_prompt = prompt_engine.render_static("memory_rescore")?;
+    let routing = runtime_config.routing.load();
+    let model_name = routing.resolve(ProcessType::Compactor, None).to_string();
+    let model = SpacebotModel::make(llm_manager, &model_name)
+        .with_context(agent_id, "compactor")
+        .with_routing((**routing).clone());
+    let agent = AgentBuilder::new(model)
+        .preamble(&rescore_prompt)
+        .hook(CortexHook::new())
+        .build();
+
+    for batch in candidates.chunks(RESCORE_BATCH_SIZE) {
+        let memories = batch
+            .iter()
+            .enumerate()
+            .map(|(index, candidate)| format_rescore_candidate(index + 1, candidate))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        let batch_prompt =
+            prompt_engine.render_system_memory_rescore_batch(config.window_days, &memories)?;
+
+        let scores = match agent
+            .prompt_typed::<RescoreLlmResponse>(&batch_prompt)
+            .await
+        {
+            Ok(response) => response.scores,
+            Err(error) => {
+                tracing::warn!(
+                    %error,
+                    batch_size = batch.len(),
+                    "importance rescore batch failed, using the activity heuristic"
+                );
+                Vec::new()
+            }
+        };
+
+        for (index, candidate) in batch.iter().enumerate() {
+            let score = scores
+                .iter()
+                .find(|score| score.label == index + 1 && (0.0..=1.0).contains(&score.importance));
+            match score {
+                Some(score) => {
+                    rescore::apply(
+                        store,
+                        candidate,
+                        score.importance,
+                        Some(&score.reason),
+                        &mut report,
+                    )
+                    .await?;
+                }
+                None => {
+                    report.heuristic_scored += 1;
+                    let importance = candidate.heuristic_importance(&config);
+                    rescore::apply(store, candidate, importance, None, &mut report).await?;
+                }
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+/// Render one rescore candidate, with its recent related memories, for the
+/// scoring prompt.
+fn format_rescore_candidate(label: usize, candidate: &rescore::RescoreCandidate) -> String {
+    let memory = &candidate.memory;
+    let mut text = format!(
+        "[{label}] ({}, saved {}, importance {:.2}, recent activity {}) {}",
+        memory.memory_type,
+        memory.created_at.format("%Y-%m-%d"),
+        memory.importance,
+        candidate.recent_activity,
+        memory.content
+    );
+    if candidate.related.is_empty() {
+        text.push_str("\n    No related memories were written or recalled recently.");
+    } else {
+        text.push_str("\n    Recent related memories:");
+        for related in candidate.related.iter().take(RESCORE_RELATED_SHOWN) {
+            let last_seen = related.created_at.max(related.last_accessed_at);
+            text.push_str(&format!(
+                "\n    - ({}) {}",
+                last_seen.format("%Y-%m-%d"),
+                related.content
+            ));
+        }
+    }
+    text
+}
+
+// -- Consolidation loop --
+
+/// Spawn the memory consolidation loop for an agent.
+///
+/// Periodically clusters memories by embedding similarity and has the
+/// compactor model fold each cluster's duplicates into one canonical memory.
+pub fn spawn_consolidation_loop(
+    deps: AgentDeps,
+    logger: CortexLogger,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        if let Err(error) = run_consolidation_loop(&deps, &logger).await {
+            tracing::error!(%error, "cortex consolidation loop exited with error");
+        }
+    })
+}
+
+async fn run_consolidation_loop(deps: &AgentDeps, logger: &CortexLogger) -> anyhow::Result<()> {
+    tracing::info!("cortex consolidation loop started");
+
+    loop {
+        let interval = deps
+            .runtime_config
+            .cortex
+            .load()
+            .consolidation_interval_secs;
+        if interval == 0 {
+            // Disabled; re-check periodically in case config is hot-reloaded.
+            tokio::time::sleep(Duration::from_secs(300)).await;
+            continue;
+        }
+        tokio::time::sleep(Duration::from_secs(interval)).await;
+
+        if !deps.leader.is_leader() || deps.runtime_config.is_paused() {
+            continue;
+        }
+
+        if let Err(error) = run_consolidation_pass(deps, logger).await {
+            tracing::warn!(%error, "memory consolidation pass failed");
+        }
+    }
+}
+
+/// LLM response shape for reviewing a consolidation cluster.
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+struct ConsolidationLlmResponse {
+    /// 1-based labels of the memories that duplicate each other.
+    #[serde(default)]
+    duplicates: Vec<usize>,
+    /// Canonical text replacing the duplicates.
+    #[serde(default)]
+    content: String,
+}
+
+/// Review one batch of clusters. Returns how many clusters were merged.
+async fn run_consolidation_pass(deps: &AgentDeps, logger: &CortexLogger) -> anyhow::Result<usize> {
+    let cortex_config = **deps.runtime_config.cortex.load();
+    let store = deps.memory_search.store();
+    let clusters = consolidation::find_clusters(
+        store,
+        deps.memory_search.embedding_table(),
+        cortex_config.consolidation_similarity_threshold,
+        cortex_config.consolidation_max_clusters_per_pass,
+    )
+    .await?;
+    if clusters.is_empty() {
+        return Ok(0);
+    }
+
+    let prompt_engine = deps.runtime_config.prompts.load();
+    let consolidation_prompt = prompt_engine.render_static("memory_consolidation")?;
+    let routing = deps.runtime_config.routing.load();
+    let model_name = routing.resolve(ProcessType::Compactor, None).to_string();
+    let model = SpacebotModel::make(&deps.llm_manager, &model_name)
+        .with_context(&*deps.agent_id, "compactor")
+        .with_routing((**routing).clone());
+    let agent = AgentBuilder::new(model)
+        .preamble(&consolidation_prompt)
+        .hook(CortexHook::new())
+        .build();
+
+    let started = Instant::now();
+    let reviewed = clusters.len();
+    let mut consolidations = Vec::new();
+    for cluster in clusters {
+        let memories = cluster
+            .iter()
+            .enumerate()
+            .map(|(index, memory)| {
+                format!(
+                    "[{}] ({}) {}",
+                    index + 1,
+                    memory.created_at.format("%Y-%m-%d"),
+                    memory.content
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        let cluster_prompt = prompt_engine.render_system_memory_consolidation_cluster(
+            &cluster[0].memory_type.to_string(),
+            &memories,
+        )?;
+
+        let response = match agent
+            .prompt_typed::<ConsolidationLlmResponse>(&cluster_prompt)
+            .await
+        {
+            Ok(response) => response,
+            Err(error) => {
+                // Left unrecorded so the cluster is reviewed again next pass.
+                tracing::warn!(%error, seed_id = %cluster[0].id, "consolidation review failed");
+                continue;
+            }
+        };
+
+        let plan = consolidation::resolve_merge(&cluster, &response.duplicates)
+            .filter(|_| !response.content.trim().is_empty());
+        let result = match plan {
+            Some(plan) => {
+                consolidation::consolidate(
+                    store,
+                    deps.memory_search.embedding_table(),
+                    deps.memory_search.embedding_model_arc(),
+                    &cluster,
+                    plan,
+                    &response.content,
+                    &model_name,
+                )
+                .await
+            }
+            None => consolidation::record_distinct(store, &cluster, &model_name).await,
+        };
+        match result {
+            Ok(consolidation) if consolidation.canonical_id.is_some() => {
+                consolidations.push(consolidation);
+            }
+            Ok(_) => {}
+            Err(error) => {
+                tracing::warn!(%error, seed_id = %cluster[0].id, "failed to apply consolidation");
+            }
+        }
+    }
+
+    let merged = consolidations.len();
+    let absorbed: usize = consolidations
+        .iter()
+        .map(|consolidation| consolidation.merged_ids.len())
+        .sum();
+    let duration_ms = started.elapsed().as_millis() as u64;
+    tracing::info!(
+        reviewed,
+        merged,
+        absorbed,
+        duration_ms,
+        "memory consolidation pass complete"
+    );
+    if merged > 0 {
+        logger.log(
+            "memory_consolidated",
+            &format!("Consolidated {absorbed} duplicate memories into {merged} canonical memories"),
+            Some(serde_json::json!({
+                "reviewed": reviewed,
+                "merged": merged,
+                "absorbed": absorbed,
+                "duration_ms": duration_ms,
+                "model": model_name,
+                "consolidations": consolidations,
+            })),
+        );
+    }
+    Ok(merged)
+}
+
+/// Maximum overdue todos flagged per cortex tick.
+const MAX_OVERDUE_TODOS_PER_TICK: i64 = 20;
+
+/// Flag todo and goal memories that have passed their due date. Each item is
+/// reviewed once per deadline: it is logged as a cortex event and surfaces
+/// in the bulletin's open-items section until it is completed or rescheduled.
+async fn review_overdue_todos(deps: &AgentDeps, logger: &CortexLogger) -> anyhow::Result<usize> {
+    let store = deps.memory_search.store();
+    let now = chrono::Utc::now();
+    let overdue =
+        crate::memory::todos::unreviewed_overdue(store, now, MAX_OVERDUE_TODOS_PER_TICK).await?;
+
+    for item in &overdue {
+        let preview = item
+            .memory
+            .content
+            .lines()
+            .next()
+            .unwrap_or(&item.memory.content);
+        logger.log(
+            "todo_overdue",
+            &format!("Overdue {}: {preview}", item.memory.memory_type),
+            Some(serde_json::json!({
+                "memory_id": item.memory.id,
+                "memory_type": item.memory.memory_type,
+                "status": item.details.status,
+                "priority": item.details.priority,
+                "due_at": item.details.due_at,
+                "channel_id": item.details.channel_id,
+            })),
+        );
+        crate::memory::todos::mark_overdue_reviewed(store, &item.memory.id, now).await?;
+    }
+
+    if !overdue.is_empty() {
+        tracing::info!(count = overdue.len(), "flagged overdue todos");
+    }
+    Ok(overdue.len())
+}
+
 /// Spawn a background loop that picks up ready tasks when idle.
 pub fn spawn_ready_task_loop(deps: AgentDeps, logger: CortexLogger) -> tokio::task::JoinHandle<()> {
     tokio::spawn(async move {
@@ -2657,6 +3314,9 @@ async fn run_ready_task_loop(deps: &AgentDeps, logger: &CortexLogger) -> anyhow:
         let interval = deps.runtime_config.cortex.load().tick_interval_secs;
         tokio::time::sleep(Duration::from_secs(interval.max(5))).await;
 
+        if !deps.leader.is_leader() || deps.runtime_config.is_paused() {
+            continue;
+        }
         if let Err(error) = pickup_one_ready_task(deps, logger).await {
             tracing::warn!(%error, "ready-task pickup pass failed");
         }
@@ -2814,6 +3474,7 @@ async fn pickup_one_ready_task(deps: &AgentDeps, logger: &CortexLogger) -> anyho
     let secrets_snapshot = deps.runtime_config.secrets.load().clone();
     let process_control_registry = deps.process_control_registry.clone();
     let runtime_config = deps.runtime_config.clone();
+    let messaging_manager = deps.messaging_manager.clone();
     tokio::spawn(async move {
         // Scrub known secrets and unknown leak patterns from all worker output
         // before persisting, logging, or emitting events.
@@ -2911,6 +3572,21 @@ async fn pickup_one_ready_task(deps: &AgentDeps, logger: &CortexLogger) -> anyho
                                 )
                                 .await;
 
+                                if let Some(messaging_manager) = &messaging_manager
+                                    && let Err(error) = crate::tasks::email::send_completion_reply(
+                                        &task,
+                                        &result_text,
+                                        messaging_manager,
+                                    )
+                                    .await
+                                {
+                                    tracing::warn!(
+                                        %error,
+                                        task_number = task.task_number,
+                                        "failed to reply to task email"
+                                    );
+                                }
+
                                 let _ = event_tx.send(ProcessEvent::WorkerComplete {
                                     agent_id: Arc::from(agent_id.as_str()),
                                     worker_id,
@@ -3384,12 +4060,15 @@ async fn run_association_loop(deps: &AgentDeps, logger: &CortexLogger) -> anyhow
     // Short delay on startup to let the bulletin and embeddings settle
     tokio::time::sleep(Duration::from_secs(10)).await;
 
-    // Backfill: process all existing memories on first run
-    let backfill_count = run_association_pass(deps, logger, None).await;
-    tracing::info!(
-        associations_created = backfill_count,
-        "association backfill complete"
-    );
+    // Backfill: process all existing memories on first run. Followers
+    // skip it; the leader replica has already covered existing memories.
+    if deps.leader.is_leader() {
+        let backfill_count = run_association_pass(deps, logger, None).await;
+        tracing::info!(
+            associations_created = backfill_count,
+            "association backfill complete"
+        );
+    }
 
     let mut last_pass_at = chrono::Utc::now();
 
@@ -3399,6 +4078,9 @@ async fn run_association_loop(deps: &AgentDeps, logger: &CortexLogger) -> anyhow
 
         tokio::time::sleep(Duration::from_secs(interval)).await;
 
+        if !deps.leader.is_leader() || deps.runtime_config.is_paused() {
+            continue;
+        }
         let since = Some(last_pass_at);
         last_pass_at = chrono::Utc::now();
 
@@ -23,7 +23,7 @@ use std::time::Duration;
 use tokio::sync::{Mutex, RwLock};
 
 /// A persisted cortex chat message.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
 pub struct CortexChatMessage {
     pub id: String,
     pub thread_id: String,
@@ -37,9 +37,11 @@ pub struct CortexChatMessage {
 }
 
 /// Summary of a cortex chat thread (returned by list_threads).
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
 pub struct CortexChatThread {
     pub thread_id: String,
+    /// User-set title; clients fall back to `preview` when absent.
+    pub title: Option<String>,
     pub preview: String,
     pub message_count: i64,
     pub first_message_at: String,
@@ -47,7 +49,7 @@ pub struct CortexChatThread {
 }
 
 /// A tool call + result pair persisted alongside assistant messages.
-#[derive(Debug, Clone, Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, Serialize, serde::Deserialize, utoipa::ToSchema)]
 pub struct CortexChatToolCall {
     pub id: String,
     pub tool: String,
@@ -89,6 +91,8 @@ pub enum CortexChatEvent {
 pub enum CortexChatSendError {
     #[error("cortex chat session is busy")]
     Busy,
+    #[error("no matching user message in thread")]
+    MessageNotFound,
     #[error(transparent)]
     Database(#[from] sqlx::Error),
     #[error(transparent)]
@@ -125,6 +129,18 @@ impl CortexChatHook {
     }
 }
 
+/// How a send's input message relates to the stored thread.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InputMode {
+    /// Save the input as a new user message.
+    Persist,
+    /// The input is already the thread's last message (regeneration).
+    AlreadyPersisted,
+    /// Send without saving; only the assistant's synthesis is kept. Used
+    /// for auto-triggered worker results.
+    Transient,
+}
+
 fn try_acquire_send_lock(
     send_lock: &Arc<Mutex<()>>,
 ) -> std::result::Result<tokio::sync::OwnedMutexGuard<()>, CortexChatSendError> {
@@ -293,12 +309,14 @@ struct CortexChatThreadRow {
     first_message_at: chrono::NaiveDateTime,
     last_message_at: chrono::NaiveDateTime,
     preview: String,
+    title: Option<String>,
 }
 
 impl CortexChatThreadRow {
     fn into_thread(self) -> CortexChatThread {
         CortexChatThread {
             thread_id: self.thread_id,
+            title: self.title,
             preview: self.preview,
             message_count: self.message_count as i64,
             first_message_at: self.first_message_at.and_utc().to_rfc3339(),
@@ -320,7 +338,8 @@ impl CortexChatStore {
     ) -> Result<Vec<CortexChatMessage>, sqlx::Error> {
         let rows: Vec<ChatMessageRow> = sqlx::query_as(
             "SELECT id, thread_id, role, content, channel_context, tool_calls, created_at \
-             FROM cortex_chat_messages WHERE thread_id = ? ORDER BY created_at DESC LIMIT ?",
+             FROM cortex_chat_messages WHERE thread_id = ? \
+             ORDER BY created_at DESC, rowid DESC LIMIT ?",
         )
         .bind(thread_id)
         .bind(limit)
@@ -360,6 +379,76 @@ impl CortexChatStore {
         Ok(id)
     }
 
+    /// Delete every message after the thread's last user message and return
+    /// that message so it can be sent again. `None` if the thread has no
+    /// user message.
+    pub async fn truncate_after_last_user_message(
+        &self,
+        thread_id: &str,
+    ) -> Result<Option<CortexChatMessage>, sqlx::Error> {
+        let mut transaction = self.pool.begin().await?;
+        let last_user: Option<(i64, String)> = sqlx::query_as(
+            "SELECT rowid, id FROM cortex_chat_messages \
+             WHERE thread_id = ? AND role = 'user' ORDER BY rowid DESC LIMIT 1",
+        )
+        .bind(thread_id)
+        .fetch_optional(&mut *transaction)
+        .await?;
+        let Some((rowid, id)) = last_user else {
+            return Ok(None);
+        };
+        let row: ChatMessageRow = sqlx::query_as(
+            "SELECT id, thread_id, role, content, channel_context, tool_calls, created_at \
+             FROM cortex_chat_messages WHERE id = ?",
+        )
+        .bind(&id)
+        .fetch_one(&mut *transaction)
+        .await?;
+        sqlx::query("DELETE FROM cortex_chat_messages WHERE thread_id = ? AND rowid > ?")
+            .bind(thread_id)
+            .bind(rowid)
+            .execute(&mut *transaction)
+            .await?;
+        transaction.commit().await?;
+        Ok(Some(row.into_message()))
+    }
+
+    /// Delete user message `message_id` and everything after it in the
+    /// thread. Returns the deleted message, or `None` if the thread has no
+    /// user message with that ID.
+    pub async fn truncate_from_user_message(
+        &self,
+        thread_id: &str,
+        message_id: &str,
+    ) -> Result<Option<CortexChatMessage>, sqlx::Error> {
+        let mut transaction = self.pool.begin().await?;
+        let target: Option<(i64,)> = sqlx::query_as(
+            "SELECT rowid FROM cortex_chat_messages \
+             WHERE thread_id = ? AND id = ? AND role = 'user'",
+        )
+        .bind(thread_id)
+        .bind(message_id)
+        .fetch_optional(&mut *transaction)
+        .await?;
+        let Some((rowid,)) = target else {
+            return Ok(None);
+        };
+        let row: ChatMessageRow = sqlx::query_as(
+            "SELECT id, thread_id, role, content, channel_context, tool_calls, created_at \
+             FROM cortex_chat_messages WHERE id = ?",
+        )
+        .bind(message_id)
+        .fetch_one(&mut *transaction)
+        .await?;
+        sqlx::query("DELETE FROM cortex_chat_messages WHERE thread_id = ? AND rowid >= ?")
+            .bind(thread_id)
+            .bind(rowid)
+            .execute(&mut *transaction)
+            .await?;
+        transaction.commit().await?;
+        Ok(Some(row.into_message()))
+    }
+
     /// Get the most recent thread_id, or None if no threads exist.
     pub async fn latest_thread_id(&self) -> Result<Option<String>, sqlx::Error> {
         let row: Option<(String,)> = sqlx::query_as(
@@ -382,7 +471,11 @@ impl CortexChatStore {
                      SELECT content FROM cortex_chat_messages m2 \
                      WHERE m2.thread_id = cortex_chat_messages.thread_id \
                      ORDER BY m2.created_at ASC LIMIT 1 \
-                 ) as preview \
+                 ) as preview, \
+                 ( \
+                     SELECT title FROM cortex_chat_threads t \
+                     WHERE t.thread_id = cortex_chat_messages.thread_id \
+                 ) as title \
              FROM cortex_chat_messages \
              GROUP BY thread_id \
              ORDER BY MAX(created_at) DESC",
@@ -393,14 +486,59 @@ impl CortexChatStore {
         Ok(rows.into_iter().map(|row| row.into_thread()).collect())
     }
 
-    /// Delete all messages in a thread.
+    /// Delete all messages in a thread, and its title.
     pub async fn delete_thread(&self, thread_id: &str) -> Result<u64, sqlx::Error> {
+        let mut transaction = self.pool.begin().await?;
         let result = sqlx::query("DELETE FROM cortex_chat_messages WHERE thread_id = ?")
             .bind(thread_id)
-            .execute(&self.pool)
+            .execute(&mut *transaction)
+            .await?;
+        sqlx::query("DELETE FROM cortex_chat_threads WHERE thread_id = ?")
+            .bind(thread_id)
+            .execute(&mut *transaction)
             .await?;
+        transaction.commit().await?;
         Ok(result.rows_affected())
     }
+
+    /// Set a thread's title, or clear it with `None`. Returns false if the
+    /// thread has no messages.
+    pub async fn rename_thread(
+        &self,
+        thread_id: &str,
+        title: Option<&str>,
+    ) -> Result<bool, sqlx::Error> {
+        let exists: Option<(i64,)> =
+            sqlx::query_as("SELECT 1 FROM cortex_chat_messages WHERE thread_id = ? LIMIT 1")
+                .bind(thread_id)
+                .fetch_optional(&self.pool)
+                .await?;
+        if exists.is_none() {
+            return Ok(false);
+        }
+
+        match title {
+            Some(title) => {
+                sqlx::query(
+                    "INSERT INTO cortex_chat_threads (thread_id, title, updated_at) \
+                     VALUES (?, ?, CURRENT_TIMESTAMP) \
+                     ON CONFLICT(thread_id) DO UPDATE SET \
+                       title = excluded.title, updated_at = excluded.updated_at",
+                )
+                .bind(thread_id)
+                .bind(title)
+                .execute(&self.pool)
+                .await?;
+            }
+            None => {
+                sqlx::query("DELETE FROM cortex_chat_threads WHERE thread_id = ?")
+                    .bind(thread_id)
+                    .execute(&self.pool)
+                    .await?;
+            }
+        }
+        Ok(true)
+    }
 }
 
 /// The cortex chat session for a single agent.
@@ -597,6 +735,58 @@ impl CortexChatSession {
             .await
     }
 
+    /// Drop the assistant reply (and anything else) after the thread's last
+    /// user message and generate a new one from the same context.
+    ///
+    /// Uses the original message's channel context unless one is given.
+    pub async fn regenerate_with_events(
+        self: &Arc<Self>,
+        thread_id: &str,
+        channel_context_id: Option<&str>,
+    ) -> std::result::Result<mpsc::Receiver<CortexChatEvent>, CortexChatSendError> {
+        let send_guard = try_acquire_send_lock(&self.send_lock)?;
+        let message = self
+            .store
+            .truncate_after_last_user_message(thread_id)
+            .await?
+            .ok_or(CortexChatSendError::MessageNotFound)?;
+        let channel_context_id = channel_context_id.or(message.channel_context.as_deref());
+        self.send_message_inner(
+            send_guard,
+            thread_id,
+            &message.content,
+            channel_context_id,
+            InputMode::AlreadyPersisted,
+        )
+        .await
+    }
+
+    /// Replace user message `message_id` with `user_text`, discarding it and
+    /// everything after it, then send the new text.
+    pub async fn edit_and_resend_with_events(
+        self: &Arc<Self>,
+        thread_id: &str,
+        message_id: &str,
+        user_text: &str,
+        channel_context_id: Option<&str>,
+    ) -> std::result::Result<mpsc::Receiver<CortexChatEvent>, CortexChatSendError> {
+        let send_guard = try_acquire_send_lock(&self.send_lock)?;
+        let message = self
+            .store
+            .truncate_from_user_message(thread_id, message_id)
+            .await?
+            .ok_or(CortexChatSendError::MessageNotFound)?;
+        let channel_context_id = channel_context_id.or(message.channel_context.as_deref());
+        self.send_message_inner(
+            send_guard,
+            thread_id,
+            user_text,
+            channel_context_id,
+            InputMode::Persist,
+        )
+        .await
+    }
+
     /// Like `send_message_with_events` but waits for the send lock instead of
     /// returning `Busy`. Used by the background event loop for auto-triggered turns.
     /// The input message is NOT persisted — only the assistant's synthesis gets saved.
@@ -607,8 +797,14 @@ impl CortexChatSession {
         channel_context_id: Option<&str>,
     ) -> std::result::Result<mpsc::Receiver<CortexChatEvent>, CortexChatSendError> {
         let send_guard = self.send_lock.clone().lock_owned().await;
-        self.send_message_inner(send_guard, thread_id, user_text, channel_context_id, false)
-            .await
+        self.send_message_inner(
+            send_guard,
+            thread_id,
+            user_text,
+            channel_context_id,
+            InputMode::Transient,
+        )
+        .await
     }
 
     async fn send_message_impl(
@@ -618,21 +814,25 @@ impl CortexChatSession {
         user_text: &str,
         channel_context_id: Option<&str>,
     ) -> std::result::Result<mpsc::Receiver<CortexChatEvent>, CortexChatSendError> {
-        self.send_message_inner(send_guard, thread_id, user_text, channel_context_id, true)
-            .await
+        self.send_message_inner(
+            send_guard,
+            thread_id,
+            user_text,
+            channel_context_id,
+            InputMode::Persist,
+        )
+        .await
     }
 
-    /// Core send implementation. When `persist_input` is false the incoming
-    /// message is sent to the LLM but not saved to the thread — used for
-    /// auto-triggered worker results where only the assistant's synthesis
-    /// should appear in history.
+    /// Core send implementation. See [`InputMode`] for how the incoming
+    /// message relates to the stored thread.
     async fn send_message_inner(
         self: &Arc<Self>,
         send_guard: tokio::sync::OwnedMutexGuard<()>,
         thread_id: &str,
         user_text: &str,
         channel_context_id: Option<&str>,
-        persist_input: bool,
+        input_mode: InputMode,
     ) -> std::result::Result<mpsc::Receiver<CortexChatEvent>, CortexChatSendError> {
         // Update the shared context so DetachedSpawnWorkerTool knows which
         // thread to associate spawned workers with.
@@ -640,7 +840,7 @@ impl CortexChatSession {
         *self.cortex_ctx.current_channel_context.write().await =
             channel_context_id.map(|s| s.to_string());
 
-        if persist_input {
+        if input_mode == InputMode::Persist {
             self.store
                 .save_message(thread_id, "user", user_text, channel_context_id, None)
                 .await?;
@@ -650,15 +850,16 @@ impl CortexChatSession {
         let system_prompt = self.build_system_prompt(channel_context_id).await?;
 
         // Load chat history and convert to Rig messages.
-        // When we persisted the input, the last message in history is the one
-        // we just saved — skip it since Rig adds it via `agent.prompt()`.
+        // When the input is stored in the thread, the last message in history
+        // is that input — skip it since Rig adds it via `agent.prompt()`.
         // When the input wasn't persisted (auto-triggered turns), include all
         // messages since none of them duplicate the prompt.
         let chat_messages = self.store.load_history(thread_id, 100).await?;
-        let history_end = if persist_input {
-            chat_messages.len().saturating_sub(1)
-        } else {
-            chat_messages.len()
+        let history_end = match input_mode {
+            InputMode::Persist | InputMode::AlreadyPersisted => {
+                chat_messages.len().saturating_sub(1)
+            }
+            InputMode::Transient => chat_messages.len(),
         };
         let mut history: Vec<rig::message::Message> = Vec::new();
         for message in &chat_messages[..history_end] {
@@ -887,7 +1088,7 @@ impl CortexChatSession {
 
 #[cfg(test)]
 mod tests {
-    use super::{CortexChatSendError, try_acquire_send_lock};
+    use super::{CortexChatSendError, CortexChatStore, try_acquire_send_lock};
     use std::sync::Arc;
     use std::time::Duration;
     use tokio::sync::Mutex;
@@ -935,4 +1136,64 @@ mod tests {
             "single-flight lock should be released after timeout path"
         );
     }
+
+    #[tokio::test]
+    async fn truncation_keeps_messages_before_the_cut() {
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("failed to create sqlite memory pool");
+        sqlx::migrate!("./migrations")
+            .run(&pool)
+            .await
+            .expect("migrations");
+        let store = CortexChatStore::new(pool);
+
+        let mut ids = Vec::new();
+        for (role, content) in [
+            ("user", "one"),
+            ("assistant", "reply one"),
+            ("user", "two"),
+            ("assistant", "reply two"),
+        ] {
+            ids.push(
+                store
+                    .save_message("t", role, content, None, None)
+                    .await
+                    .unwrap(),
+            );
+        }
+
+        let last = store
+            .truncate_after_last_user_message("t")
+            .await
+            .unwrap()
+            .expect("thread has a user message");
+        assert_eq!(last.content, "two");
+        let contents: Vec<String> = store
+            .load_history("t", 10)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|message| message.content)
+            .collect();
+        assert_eq!(contents, ["one", "reply one", "two"]);
+
+        assert!(
+            store
+                .truncate_from_user_message("t", &ids[1])
+                .await
+                .unwrap()
+                .is_none(),
+            "assistant messages can't be edited"
+        );
+        let edited = store
+            .truncate_from_user_message("t", &ids[2])
+            .await
+            .unwrap()
+            .expect("user message exists");
+        assert_eq!(edited.content, "two");
+        assert_eq!(store.load_history("t", 10).await.unwrap().len(), 2);
+    }
 }
@@ -0,0 +1,350 @@
+//! Ordered per-channel event log for offline replay.
+//!
+//! Every inbound message, LLM response, and tool result a channel handles is
+//! appended to the agent's `channel_event_log` table in the order it was
+//! observed. LLM responses are stored as recorded (a cassette), so replaying
+//! the log never contacts a provider or executes a tool: [`ReplayState`]
+//! folds the events back into the channel's state one step at a time.
+//!
+//! Recording is off unless `[channel] event_log` is set, and the cortex
+//! prunes entries older than `event_log_retention_days`. Tool arguments and
+//! results are scrubbed of known secrets before they are stored.
+
+use crate::ChannelId;
+use crate::config::RuntimeConfig;
+
+use anyhow::Context as _;
+use rig::message::AssistantContent;
+use rig::one_or_many::OneOrMany;
+use serde::{Deserialize, Serialize};
+use sqlx::{Row as _, SqlitePool};
+use std::path::Path;
+use std::sync::Arc;
+
+/// One entry in a channel's event log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ChannelEvent {
+    /// A message delivered to the channel, including system retriggers.
+    Inbound {
+        message_id: String,
+        source: String,
+        sender_id: String,
+        text: String,
+    },
+    /// A completion returned by the model.
+    LlmResponse {
+        text: String,
+        tool_calls: Vec<RecordedToolCall>,
+    },
+    /// The output of a tool the channel executed.
+    ToolResult {
+        tool_name: String,
+        args: String,
+        result: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedToolCall {
+    pub name: String,
+    pub args: serde_json::Value,
+}
+
+impl ChannelEvent {
+    pub fn from_completion(choice: &OneOrMany<AssistantContent>) -> Self {
+        let mut text = Vec::new();
+        let mut tool_calls = Vec::new();
+        for content in choice.iter() {
+            match content {
+                AssistantContent::Text(part) => text.push(part.text.as_str()),
+                AssistantContent::ToolCall(call) => tool_calls.push(RecordedToolCall {
+                    name: call.function.name.clone(),
+                    args: call.function.arguments.clone(),
+                }),
+                _ => {}
+            }
+        }
+        Self::LlmResponse {
+            text: text.join("\n\n"),
+            tool_calls,
+        }
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Inbound { .. } => "inbound",
+            Self::LlmResponse { .. } => "llm_response",
+            Self::ToolResult { .. } => "tool_result",
+        }
+    }
+}
+
+/// Appends events for one channel. Cheap to clone.
+#[derive(Clone)]
+pub struct EventLog {
+    pool: SqlitePool,
+    channel_id: ChannelId,
+    runtime_config: Arc<RuntimeConfig>,
+}
+
+impl EventLog {
+    pub fn new(
+        pool: SqlitePool,
+        channel_id: ChannelId,
+        runtime_config: Arc<RuntimeConfig>,
+    ) -> Self {
+        Self {
+            pool,
+            channel_id,
+            runtime_config,
+        }
+    }
+
+    /// Append an event if the agent has the event log enabled. Awaited
+    /// rather than spawned so rows land in the order the channel observed
+    /// them. Failures are logged, never surfaced.
+    pub async fn record(&self, event: ChannelEvent) {
+        if !self.runtime_config.channel_config.load().event_log {
+            return;
+        }
+        let payload = match serde_json::to_string(&event) {
+            Ok(payload) => payload,
+            Err(error) => {
+                tracing::warn!(%error, "failed to encode channel event");
+                return;
+            }
+        };
+        if let Err(error) = sqlx::query(
+            "INSERT INTO channel_event_log (channel_id, kind, payload) VALUES (?, ?, ?)",
+        )
+        .bind(self.channel_id.as_ref())
+        .bind(event.kind())
+        .bind(payload)
+        .execute(&self.pool)
+        .await
+        {
+            tracing::warn!(%error, channel_id = %self.channel_id, "failed to record channel event");
+        }
+    }
+}
+
+/// Delete events older than `retention_days` across all channels. Zero
+/// keeps everything. Returns how many events were removed.
+pub async fn prune(pool: &SqlitePool, retention_days: i64) -> crate::error::Result<u64> {
+    if retention_days <= 0 {
+        return Ok(0);
+    }
+    let result = sqlx::query("DELETE FROM channel_event_log WHERE created_at < datetime('now', ?)")
+        .bind(format!("-{retention_days} days"))
+        .execute(pool)
+        .await
+        .context("failed to prune channel event log")?;
+    Ok(result.rows_affected())
+}
+
+/// A stored event with its position in the log.
+#[derive(Debug, Clone)]
+pub struct LoggedEvent {
+    pub seq: i64,
+    pub created_at: String,
+    pub event: ChannelEvent,
+}
+
+/// Open an agent's database read-only, without running migrations, so a
+/// replay can't modify the instance it inspects.
+pub async fn open_read_only(data_dir: &Path) -> crate::error::Result<SqlitePool> {
+    let path = data_dir.join("spacebot.db");
+    let pool = SqlitePool::connect(&format!("sqlite:{}?mode=ro", path.display()))
+        .await
+        .with_context(|| format!("failed to open {}", path.display()))?;
+    Ok(pool)
+}
+
+/// Every event logged for `channel_id`, oldest first.
+pub async fn load(pool: &SqlitePool, channel_id: &str) -> crate::error::Result<Vec<LoggedEvent>> {
+    let rows = sqlx::query(
+        "SELECT id, payload, created_at FROM channel_event_log \
+         WHERE channel_id = ? ORDER BY id",
+    )
+    .bind(channel_id)
+    .fetch_all(pool)
+    .await
+    .context("failed to load channel event log")?;
+
+    let mut events = Vec::with_capacity(rows.len());
+    for row in rows {
+        let seq: i64 = row.try_get("id").unwrap_or_default();
+        let payload: String = row.try_get("payload").unwrap_or_default();
+        let event = serde_json::from_str(&payload)
+            .with_context(|| format!("malformed channel event #{seq}"))?;
+        events.push(LoggedEvent {
+            seq,
+            created_at: row.try_get("created_at").unwrap_or_default(),
+            event,
+        });
+    }
+    Ok(events)
+}
+
+/// Who produced a transcript entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayRole {
+    User,
+    Assistant,
+    Tool,
+}
+
+/// Channel state reconstructed from the log, advanced one event at a time.
+#[derive(Debug, Default)]
+pub struct ReplayState {
+    /// What the model has seen so far, in order.
+    pub transcript: Vec<(ReplayRole, String)>,
+    /// Tool calls from the latest LLM response that have no result yet.
+    pub pending_tool_calls: Vec<String>,
+    /// Content of the most recent `reply` tool call.
+    pub last_reply: Option<String>,
+    pub llm_turns: usize,
+}
+
+impl ReplayState {
+    /// Apply one event. Returns anomalies worth flagging at this step, such
+    /// as a response arriving while earlier tool calls never produced results.
+    pub fn apply(&mut self, event: &ChannelEvent) -> Vec<String> {
+        let mut anomalies = Vec::new();
+        match event {
+            ChannelEvent::Inbound {
+                sender_id, text, ..
+            } => {
+                self.transcript
+                    .push((ReplayRole::User, format!("{sender_id}: {text}")));
+            }
+            ChannelEvent::LlmResponse { text, tool_calls } => {
+                if !self.pending_tool_calls.is_empty() {
+                    anomalies.push(format!(
+                        "new response while tool calls had no result: {}",
+                        self.pending_tool_calls.join(", ")
+                    ));
+                }
+                self.llm_turns += 1;
+                if !text.is_empty() {
+                    self.transcript.push((ReplayRole::Assistant, text.clone()));
+                }
+                self.pending_tool_calls = tool_calls.iter().map(|call| call.name.clone()).collect();
+                for call in tool_calls {
+                    self.transcript.push((
+                        ReplayRole::Assistant,
+                        format!("→ {}({})", call.name, call.args),
+                    ));
+                }
+            }
+            ChannelEvent::ToolResult {
+                tool_name,
+                args,
+                result,
+            } => {
+                match self
+                    .pending_tool_calls
+                    .iter()
+                    .position(|name| name == tool_name)
+                {
+                    Some(index) => {
+                        self.pending_tool_calls.remove(index);
+                    }
+                    None => {
+                        anomalies.push(format!("result for `{tool_name}` with no matching call"))
+                    }
+                }
+                if tool_name == "reply"
+                    && let Ok(args) = serde_json::from_str::<serde_json::Value>(args)
+                    && let Some(content) = args.get("content").and_then(|value| value.as_str())
+                {
+                    self.last_reply = Some(content.to_string());
+                }
+                self.transcript
+                    .push((ReplayRole::Tool, format!("{tool_name} ← {result}")));
+            }
+        }
+        anomalies
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replay_reconstructs_state_and_flags_dangling_calls() {
+        let mut state = ReplayState::default();
+        let events = [
+            ChannelEvent::Inbound {
+                message_id: "1".into(),
+                source: "testbed".into(),
+                sender_id: "alice".into(),
+                text: "hi".into(),
+            },
+            ChannelEvent::LlmResponse {
+                text: String::new(),
+                tool_calls: vec![
+                    RecordedToolCall {
+                        name: "memory_recall".into(),
+                        args: serde_json::json!({"query": "alice"}),
+                    },
+                    RecordedToolCall {
+                        name: "reply".into(),
+                        args: serde_json::json!({"content": "hello"}),
+                    },
+                ],
+            },
+            ChannelEvent::ToolResult {
+                tool_name: "reply".into(),
+                args: r#"{"content":"hello"}"#.into(),
+                result: "ok".into(),
+            },
+        ];
+        for event in &events {
+            assert!(state.apply(event).is_empty());
+        }
+        assert_eq!(state.llm_turns, 1);
+        assert_eq!(state.last_reply.as_deref(), Some("hello"));
+        assert_eq!(state.pending_tool_calls, vec!["memory_recall".to_string()]);
+        assert_eq!(state.transcript.len(), 4);
+
+        let anomalies = state.apply(&ChannelEvent::LlmResponse {
+            text: "done".into(),
+            tool_calls: Vec::new(),
+        });
+        assert_eq!(anomalies.len(), 1);
+
+        let encoded = serde_json::to_string(&events[1]).unwrap();
+        assert!(encoded.contains(r#""kind":"llm_response""#));
+        assert_eq!(
+            serde_json::from_str::<ChannelEvent>(&encoded).unwrap(),
+            events[1]
+        );
+    }
+
+    #[tokio::test]
+    async fn prune_drops_events_past_retention() {
+        let store = crate::memory::MemoryStore::connect_in_memory().await;
+        let pool = store.pool();
+        for age in ["-30 days", "-1 days"] {
+            sqlx::query(
+                "INSERT INTO channel_event_log (channel_id, kind, payload, created_at) \
+                 VALUES ('chan', 'inbound', '{}', datetime('now', ?))",
+            )
+            .bind(age)
+            .execute(pool)
+            .await
+            .unwrap();
+        }
+
+        assert_eq!(prune(pool, 0).await.unwrap(), 0);
+        assert_eq!(prune(pool, 14).await.unwrap(), 1);
+        let remaining: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM channel_event_log")
+            .fetch_one(pool)
+            .await
+            .unwrap();
+        assert_eq!(remaining, 1);
+    }
+}
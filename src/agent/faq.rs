@@ -0,0 +1,760 @@
+//! FAQ matching against a curated Q&A store.
+//!
+//! With `faq_matching` on, each inbound question is embedded and compared to
+//! the stored questions by cosine similarity. A match at or above
+//! `faq_answer_threshold` is answered with the stored answer and no LLM call;
+//! one at or above `faq_context_threshold` is added to the turn's system
+//! prompt for the model to draw on. Every check is recorded in `faq_matches`
+//! for the analytics endpoint.
+//!
+//! Entries are managed through the API or bulk-imported from CSV or
+//! Markdown. Each can be limited to channel patterns and a memory scope.
+//! Branches can propose entries from resolved conversations with
+//! `faq_propose`; proposals aren't matched until an operator approves them.
+
+use crate::memory::EmbeddingModel;
+use crate::memory::scope::ScopeFilter;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Row as _, SqlitePool};
+use uuid::Uuid;
+
+use std::sync::Arc;
+
+/// Largest import accepted, in entries.
+pub const MAX_IMPORT_ENTRIES: usize = 5_000;
+
+const ENTRY_COLUMNS: &str = "id, question, answer, embedding, enabled, hit_count, channels, scope, \
+     status, source_channel_id, created_at, updated_at";
+
+/// A stored question and its answer.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct FaqEntry {
+    pub id: String,
+    pub question: String,
+    pub answer: String,
+    pub enabled: bool,
+    /// Times this entry answered a question or was added to context.
+    pub hit_count: i64,
+    /// Channel ID patterns the entry answers in; empty means all. A trailing
+    /// `*` matches any suffix.
+    pub channels: Vec<String>,
+    /// Memory scope the entry belongs to. Only channels that can recall the
+    /// scope match it.
+    pub scope: Option<String>,
+    pub status: EntryStatus,
+    /// Conversation a proposed entry came from.
+    pub source_channel_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl FaqEntry {
+    /// Whether the entry may answer in a channel with the given scope
+    /// visibility.
+    pub fn applies_to(&self, channel_id: &str, scope_filter: &ScopeFilter) -> bool {
+        let channel_allowed = self.channels.is_empty()
+            || self
+                .channels
+                .iter()
+                .any(|pattern| match pattern.strip_suffix('*') {
+                    Some(prefix) => channel_id.starts_with(prefix),
+                    None => channel_id == pattern,
+                });
+        channel_allowed && scope_filter.allows(self.scope.as_deref())
+    }
+}
+
+/// Review state of an entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryStatus {
+    /// Matched against inbound questions.
+    Active,
+    /// Proposed by the agent, waiting for an operator.
+    Proposed,
+    /// A proposal the operator turned down.
+    Rejected,
+}
+
+impl EntryStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            EntryStatus::Active => "active",
+            EntryStatus::Proposed => "proposed",
+            EntryStatus::Rejected => "rejected",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "active" => Some(EntryStatus::Active),
+            "proposed" => Some(EntryStatus::Proposed),
+            "rejected" => Some(EntryStatus::Rejected),
+            _ => None,
+        }
+    }
+}
+
+/// What a check against the store led to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchOutcome {
+    /// The stored answer was sent directly.
+    Answered,
+    /// The pair was added to the turn's context.
+    Context,
+    /// Nothing close enough.
+    Miss,
+}
+
+impl MatchOutcome {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MatchOutcome::Answered => "answered",
+            MatchOutcome::Context => "context",
+            MatchOutcome::Miss => "miss",
+        }
+    }
+
+    /// The outcome for a best-match similarity under the given thresholds.
+    pub fn classify(similarity: f32, answer_threshold: f64, context_threshold: f64) -> Self {
+        let similarity = f64::from(similarity);
+        if similarity >= answer_threshold {
+            MatchOutcome::Answered
+        } else if similarity >= context_threshold {
+            MatchOutcome::Context
+        } else {
+            MatchOutcome::Miss
+        }
+    }
+}
+
+/// The closest stored entry to a question.
+#[derive(Debug, Clone)]
+pub struct FaqMatch {
+    pub entry: FaqEntry,
+    pub similarity: f32,
+}
+
+/// The active, enabled entry most similar to `embedding` among those that
+/// apply to the channel, if any do.
+pub async fn best_match(
+    pool: &SqlitePool,
+    embedding: &[f32],
+    channel_id: &str,
+    scope_filter: &ScopeFilter,
+) -> anyhow::Result<Option<FaqMatch>> {
+    let rows = sqlx::query(&format!(
+        "SELECT {ENTRY_COLUMNS} FROM faq_entries WHERE enabled = 1 AND status = 'active'"
+    ))
+    .fetch_all(pool)
+    .await?;
+
+    let mut best: Option<FaqMatch> = None;
+    for row in &rows {
+        let Some(entry) = row_to_entry(row) else {
+            continue;
+        };
+        if !entry.applies_to(channel_id, scope_filter) {
+            continue;
+        }
+        let stored: Vec<u8> = row.try_get("embedding").unwrap_or_default();
+        let similarity = cosine_similarity(embedding, &decode_embedding(&stored));
+        if best
+            .as_ref()
+            .is_none_or(|best| similarity > best.similarity)
+        {
+            best = Some(FaqMatch { entry, similarity });
+        }
+    }
+    Ok(best)
+}
+
+/// A question and answer to add to the store.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewEntry {
+    pub question: String,
+    pub answer: String,
+    pub channels: Vec<String>,
+    pub scope: Option<String>,
+}
+
+/// Add entries in one transaction, embedding their questions in one batch.
+pub async fn create_entries(
+    pool: &SqlitePool,
+    embedding_model: &Arc<EmbeddingModel>,
+    entries: Vec<NewEntry>,
+    status: EntryStatus,
+    source_channel_id: Option<&str>,
+) -> anyhow::Result<Vec<FaqEntry>> {
+    if entries.is_empty() {
+        return Ok(Vec::new());
+    }
+    let embeddings = embedding_model
+        .embed_batch(entries.iter().map(|entry| entry.question.clone()).collect())
+        .await?;
+
+    let mut ids = Vec::with_capacity(entries.len());
+    let mut transaction = pool.begin().await?;
+    for (entry, embedding) in entries.iter().zip(&embeddings) {
+        let id = Uuid::new_v4().to_string();
+        sqlx::query(
+            "INSERT INTO faq_entries \
+             (id, question, answer, embedding, channels, scope, status, source_channel_id) \
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&id)
+        .bind(&entry.question)
+        .bind(&entry.answer)
+        .bind(encode_embedding(embedding))
+        .bind(serde_json::to_string(&entry.channels)?)
+        .bind(&entry.scope)
+        .bind(status.as_str())
+        .bind(source_channel_id)
+        .execute(&mut *transaction)
+        .await?;
+        ids.push(id);
+    }
+    transaction.commit().await?;
+
+    let mut created = Vec::with_capacity(ids.len());
+    for id in ids {
+        if let Some(entry) = get_entry(pool, &id).await? {
+            created.push(entry);
+        }
+    }
+    Ok(created)
+}
+
+pub async fn get_entry(pool: &SqlitePool, id: &str) -> anyhow::Result<Option<FaqEntry>> {
+    let row = sqlx::query(&format!(
+        "SELECT {ENTRY_COLUMNS} FROM faq_entries WHERE id = ?"
+    ))
+    .bind(id)
+    .fetch_optional(pool)
+    .await?;
+    Ok(row.as_ref().and_then(row_to_entry))
+}
+
+/// Entries, most hit first, optionally only those in one review state.
+pub async fn list_entries(
+    pool: &SqlitePool,
+    status: Option<EntryStatus>,
+    limit: i64,
+    offset: i64,
+) -> anyhow::Result<Vec<FaqEntry>> {
+    let rows = sqlx::query(&format!(
+        "SELECT {ENTRY_COLUMNS} FROM faq_entries WHERE (? IS NULL OR status = ?) \
+         ORDER BY hit_count DESC, created_at DESC LIMIT ? OFFSET ?"
+    ))
+    .bind(status.map(EntryStatus::as_str))
+    .bind(status.map(EntryStatus::as_str))
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows.iter().filter_map(row_to_entry).collect())
+}
+
+/// Changes to an entry. Unset fields are left alone; `scope: Some(None)`
+/// clears the scope.
+#[derive(Debug, Clone, Default)]
+pub struct EntryUpdate {
+    pub question: Option<String>,
+    pub answer: Option<String>,
+    pub channels: Option<Vec<String>>,
+    pub scope: Option<Option<String>>,
+    pub enabled: Option<bool>,
+}
+
+/// Apply an update, re-embedding the question if it changed. Returns `None`
+/// for an unknown entry.
+pub async fn update_entry(
+    pool: &SqlitePool,
+    embedding_model: &Arc<EmbeddingModel>,
+    id: &str,
+    update: EntryUpdate,
+) -> anyhow::Result<Option<FaqEntry>> {
+    let Some(existing) = get_entry(pool, id).await? else {
+        return Ok(None);
+    };
+    let question = update.question.unwrap_or(existing.question.clone());
+    let embedding = if question != existing.question {
+        Some(encode_embedding(
+            &embedding_model.embed_one(&question).await?,
+        ))
+    } else {
+        None
+    };
+
+    sqlx::query(
+        "UPDATE faq_entries SET question = ?, answer = ?, embedding = COALESCE(?, embedding), \
+         channels = ?, scope = ?, enabled = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?",
+    )
+    .bind(&question)
+    .bind(update.answer.unwrap_or(existing.answer))
+    .bind(embedding)
+    .bind(serde_json::to_string(
+        &update.channels.unwrap_or(existing.channels),
+    )?)
+    .bind(update.scope.unwrap_or(existing.scope))
+    .bind(update.enabled.unwrap_or(existing.enabled))
+    .bind(id)
+    .execute(pool)
+    .await?;
+    get_entry(pool, id).await
+}
+
+pub async fn delete_entry(pool: &SqlitePool, id: &str) -> anyhow::Result<bool> {
+    let result = sqlx::query("DELETE FROM faq_entries WHERE id = ?")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Approve or reject a pending proposal. Returns `None` if the entry isn't
+/// a pending proposal.
+pub async fn review_proposal(
+    pool: &SqlitePool,
+    id: &str,
+    approve: bool,
+) -> anyhow::Result<Option<FaqEntry>> {
+    let status = if approve {
+        EntryStatus::Active
+    } else {
+        EntryStatus::Rejected
+    };
+    let result = sqlx::query(
+        "UPDATE faq_entries SET status = ?, updated_at = CURRENT_TIMESTAMP \
+         WHERE id = ? AND status = 'proposed'",
+    )
+    .bind(status.as_str())
+    .bind(id)
+    .execute(pool)
+    .await?;
+    if result.rows_affected() == 0 {
+        return Ok(None);
+    }
+    get_entry(pool, id).await
+}
+
+/// Bulk import formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportFormat {
+    /// `question,answer[,channels][,scope]` rows. A header row naming the
+    /// columns is optional; channels within a cell are separated by `;`.
+    Csv,
+    /// Each `## ` heading is a question and the text under it, up to the
+    /// next `## ` heading, is its answer.
+    Markdown,
+}
+
+/// Parse an import into entries. Rows without a question or answer are
+/// skipped; the error names the first malformed line.
+pub fn parse_import(format: ImportFormat, content: &str) -> Result<Vec<NewEntry>, String> {
+    let entries = match format {
+        ImportFormat::Csv => parse_csv(content)?,
+        ImportFormat::Markdown => parse_markdown(content),
+    };
+    if entries.len() > MAX_IMPORT_ENTRIES {
+        return Err(format!(
+            "import has {} entries, the limit is {MAX_IMPORT_ENTRIES}",
+            entries.len()
+        ));
+    }
+    Ok(entries)
+}
+
+fn parse_csv(content: &str) -> Result<Vec<NewEntry>, String> {
+    let mut rows = csv_rows(content)?.into_iter();
+    let mut columns = ["question", "answer", "channels", "scope"]
+        .map(str::to_string)
+        .to_vec();
+    let mut pending_first = None;
+    if let Some(first) = rows.next() {
+        if first
+            .iter()
+            .any(|cell| cell.trim().eq_ignore_ascii_case("question"))
+        {
+            columns = first
+                .iter()
+                .map(|cell| cell.trim().to_ascii_lowercase())
+                .collect();
+        } else {
+            pending_first = Some(first);
+        }
+    }
+    let column = |name: &str| columns.iter().position(|column| column == name);
+    let (Some(question_column), Some(answer_column)) = (column("question"), column("answer"))
+    else {
+        return Err("csv header needs question and answer columns".to_string());
+    };
+    let channels_column = column("channels");
+    let scope_column = column("scope");
+
+    let cell = |row: &[String], index: Option<usize>| {
+        index
+            .and_then(|index| row.get(index))
+            .map(|value| value.trim().to_string())
+            .unwrap_or_default()
+    };
+    Ok(pending_first
+        .into_iter()
+        .chain(rows)
+        .filter_map(|row| {
+            let question = cell(&row, Some(question_column));
+            let answer = cell(&row, Some(answer_column));
+            if question.is_empty() || answer.is_empty() {
+                return None;
+            }
+            let scope = cell(&row, scope_column);
+            Some(NewEntry {
+                question,
+                answer,
+                channels: cell(&row, channels_column)
+                    .split(';')
+                    .map(str::trim)
+                    .filter(|pattern| !pattern.is_empty())
+                    .map(str::to_string)
+                    .collect(),
+                scope: (!scope.is_empty()).then_some(scope),
+            })
+        })
+        .collect())
+}
+
+/// Split CSV into rows of cells. Quoted cells may contain commas, newlines,
+/// and `""` for a literal quote.
+fn csv_rows(content: &str) -> Result<Vec<Vec<String>>, String> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut cell = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut chars = content.chars().peekable();
+    while let Some(character) = chars.next() {
+        match character {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                chars.next();
+                cell.push('"');
+            }
+            '"' if in_quotes => in_quotes = false,
+            '"' if cell.trim().is_empty() => {
+                cell.clear();
+                in_quotes = true;
+            }
+            ',' if !in_quotes => row.push(std::mem::take(&mut cell)),
+            '\r' if !in_quotes => {}
+            '\n' if !in_quotes => {
+                row.push(std::mem::take(&mut cell));
+                if row.iter().any(|cell| !cell.trim().is_empty()) {
+                    rows.push(std::mem::take(&mut row));
+                } else {
+                    row.clear();
+                }
+                line += 1;
+            }
+            other => {
+                if other == '\n' {
+                    line += 1;
+                }
+                cell.push(other);
+            }
+        }
+    }
+    if in_quotes {
+        return Err(format!("unterminated quoted cell ending on line {line}"));
+    }
+    row.push(cell);
+    if row.iter().any(|cell| !cell.trim().is_empty()) {
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+fn parse_markdown(content: &str) -> Vec<NewEntry> {
+    let mut entries = Vec::new();
+    let mut question: Option<String> = None;
+    let mut answer = String::new();
+    let mut finish = |question: Option<String>, answer: &mut String| {
+        let text = std::mem::take(answer).trim().to_string();
+        if let Some(question) = question
+            && !question.is_empty()
+            && !text.is_empty()
+        {
+            entries.push(NewEntry {
+                question,
+                answer: text,
+                channels: Vec::new(),
+                scope: None,
+            });
+        }
+    };
+    for line in content.lines() {
+        if let Some(heading) = line.strip_prefix("## ") {
+            finish(question.take(), &mut answer);
+            question = Some(heading.trim().to_string());
+        } else if question.is_some() {
+            answer.push_str(line);
+            answer.push('\n');
+        }
+    }
+    finish(question, &mut answer);
+    entries
+}
+
+/// Record a check against the store, counting a hit on the matched entry
+/// unless it was a miss.
+pub async fn record_match(
+    pool: &SqlitePool,
+    channel_id: &str,
+    question: &str,
+    best: Option<&FaqMatch>,
+    outcome: MatchOutcome,
+) {
+    let result = sqlx::query(
+        "INSERT INTO faq_matches (id, channel_id, question, entry_id, similarity, outcome) \
+         VALUES (?, ?, ?, ?, ?, ?)",
+    )
+    .bind(uuid::Uuid::new_v4().to_string())
+    .bind(channel_id)
+    .bind(question)
+    .bind(best.map(|best| best.entry.id.as_str()))
+    .bind(best.map(|best| f64::from(best.similarity)))
+    .bind(outcome.as_str())
+    .execute(pool)
+    .await;
+    if let Err(error) = result {
+        tracing::warn!(%error, %channel_id, "failed to record faq match");
+    }
+
+    if outcome != MatchOutcome::Miss
+        && let Some(best) = best
+        && let Err(error) =
+            sqlx::query("UPDATE faq_entries SET hit_count = hit_count + 1 WHERE id = ?")
+                .bind(&best.entry.id)
+                .execute(pool)
+                .await
+    {
+        tracing::warn!(%error, entry_id = %best.entry.id, "failed to count faq hit");
+    }
+}
+
+/// How often an entry matched over the analytics window.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct EntryStats {
+    pub entry_id: String,
+    pub question: String,
+    pub answered: i64,
+    pub context: i64,
+    pub average_similarity: f64,
+}
+
+/// A question nothing in the store came close to.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct MissedQuestion {
+    pub channel_id: String,
+    pub question: String,
+    /// Similarity of the closest entry, if the store had any.
+    pub best_similarity: Option<f64>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Match counts and the entries and misses behind them.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct FaqAnalytics {
+    pub checked: i64,
+    pub answered: i64,
+    pub context: i64,
+    pub missed: i64,
+    /// Entries by matches, most first.
+    pub top_entries: Vec<EntryStats>,
+    /// Latest misses, newest first. Good candidates for new entries.
+    pub recent_misses: Vec<MissedQuestion>,
+}
+
+/// Analytics over the last `days` days.
+pub async fn analytics(pool: &SqlitePool, days: i64, limit: i64) -> anyhow::Result<FaqAnalytics> {
+    let since = format!("-{days} days");
+
+    let totals = sqlx::query(
+        "SELECT COUNT(*) AS checked, \
+         COALESCE(SUM(outcome = 'answered'), 0) AS answered, \
+         COALESCE(SUM(outcome = 'context'), 0) AS context, \
+         COALESCE(SUM(outcome = 'miss'), 0) AS missed \
+         FROM faq_matches WHERE created_at >= datetime('now', ?)",
+    )
+    .bind(&since)
+    .fetch_one(pool)
+    .await?;
+
+    let top_entries = sqlx::query(
+        "SELECT m.entry_id, e.question, \
+         SUM(m.outcome = 'answered') AS answered, \
+         SUM(m.outcome = 'context') AS context, \
+         AVG(m.similarity) AS average_similarity \
+         FROM faq_matches m JOIN faq_entries e ON e.id = m.entry_id \
+         WHERE m.created_at >= datetime('now', ?) AND m.outcome != 'miss' \
+         GROUP BY m.entry_id ORDER BY COUNT(*) DESC LIMIT ?",
+    )
+    .bind(&since)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?
+    .iter()
+    .map(|row| EntryStats {
+        entry_id: row.try_get("entry_id").unwrap_or_default(),
+        question: row.try_get("question").unwrap_or_default(),
+        answered: row.try_get("answered").unwrap_or_default(),
+        context: row.try_get("context").unwrap_or_default(),
+        average_similarity: row.try_get("average_similarity").unwrap_or_default(),
+    })
+    .collect();
+
+    let recent_misses = sqlx::query(
+        "SELECT channel_id, question, similarity, created_at FROM faq_matches \
+         WHERE created_at >= datetime('now', ?) AND outcome = 'miss' ORDER BY created_at DESC LIMIT ?",
+    )
+    .bind(&since)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?
+    .iter()
+    .map(|row| MissedQuestion {
+        channel_id: row.try_get("channel_id").unwrap_or_default(),
+        question: row.try_get("question").unwrap_or_default(),
+        best_similarity: row.try_get("similarity").ok().flatten(),
+        created_at: row.try_get("created_at").unwrap_or_else(|_| Utc::now()),
+    })
+    .collect();
+
+    Ok(FaqAnalytics {
+        checked: totals.try_get("checked").unwrap_or_default(),
+        answered: totals.try_get("answered").unwrap_or_default(),
+        context: totals.try_get("context").unwrap_or_default(),
+        missed: totals.try_get("missed").unwrap_or_default(),
+        top_entries,
+        recent_misses,
+    })
+}
+
+pub fn encode_embedding(embedding: &[f32]) -> Vec<u8> {
+    embedding
+        .iter()
+        .flat_map(|value| value.to_le_bytes())
+        .collect()
+}
+
+pub fn decode_embedding(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+        .collect()
+}
+
+/// Cosine similarity, or 0.0 when the vectors differ in length or either is
+/// all zeros.
+pub fn cosine_similarity(left: &[f32], right: &[f32]) -> f32 {
+    if left.len() != right.len() || left.is_empty() {
+        return 0.0;
+    }
+    let (mut dot, mut left_norm, mut right_norm) = (0.0_f32, 0.0_f32, 0.0_f32);
+    for (a, b) in left.iter().zip(right) {
+        dot += a * b;
+        left_norm += a * a;
+        right_norm += b * b;
+    }
+    if left_norm == 0.0 || right_norm == 0.0 {
+        return 0.0;
+    }
+    dot / (left_norm.sqrt() * right_norm.sqrt())
+}
+
+pub(crate) fn row_to_entry(row: &sqlx::sqlite::SqliteRow) -> Option<FaqEntry> {
+    let channels: String = row.try_get("channels").unwrap_or_default();
+    let status: String = row.try_get("status").ok()?;
+    Some(FaqEntry {
+        id: row.try_get("id").ok()?,
+        question: row.try_get("question").unwrap_or_default(),
+        answer: row.try_get("answer").unwrap_or_default(),
+        enabled: row.try_get::<i64, _>("enabled").unwrap_or(1) != 0,
+        hit_count: row.try_get("hit_count").unwrap_or_default(),
+        channels: serde_json::from_str(&channels).unwrap_or_default(),
+        scope: row.try_get("scope").ok().flatten(),
+        status: EntryStatus::parse(&status)?,
+        source_channel_id: row.try_get("source_channel_id").ok().flatten(),
+        created_at: row.try_get("created_at").unwrap_or_else(|_| Utc::now()),
+        updated_at: row.try_get("updated_at").unwrap_or_else(|_| Utc::now()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn similarity_and_classification() {
+        let embedding = vec![0.25, -1.5, 3.0];
+        assert_eq!(decode_embedding(&encode_embedding(&embedding)), embedding);
+
+        assert!((cosine_similarity(&[1.0, 0.0], &[2.0, 0.0]) - 1.0).abs() < 1e-6);
+        assert!(cosine_similarity(&[1.0, 0.0], &[0.0, 1.0]).abs() < 1e-6);
+        assert_eq!(cosine_similarity(&[1.0, 0.0], &[1.0]), 0.0);
+        assert_eq!(cosine_similarity(&[0.0, 0.0], &[1.0, 0.0]), 0.0);
+
+        assert_eq!(
+            MatchOutcome::classify(0.95, 0.92, 0.8),
+            MatchOutcome::Answered
+        );
+        assert_eq!(
+            MatchOutcome::classify(0.85, 0.92, 0.8),
+            MatchOutcome::Context
+        );
+        assert_eq!(MatchOutcome::classify(0.5, 0.92, 0.8), MatchOutcome::Miss);
+    }
+
+    #[test]
+    fn imports_and_channel_rules() {
+        let csv = "Question,Answer,Channels\n\
+                   How do I reset my password?,\"Go to Settings, then \"\"Security\"\".\",discord:1:*;slack:T1:C1\n\
+                   \"Multi\nline?\",Yes\n\
+                   ,no question\n";
+        let entries = parse_import(ImportFormat::Csv, csv).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].answer, "Go to Settings, then \"Security\".");
+        assert_eq!(entries[0].channels, vec!["discord:1:*", "slack:T1:C1"]);
+        assert_eq!(entries[1].question, "Multi\nline?");
+        assert!(parse_import(ImportFormat::Csv, "a,\"b\n").is_err());
+
+        let markdown = "# Support FAQ\n\n## Where are invoices?\nUnder Billing.\n\nAsk support if missing.\n## Empty\n";
+        let entries = parse_import(ImportFormat::Markdown, markdown).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].question, "Where are invoices?");
+        assert_eq!(
+            entries[0].answer,
+            "Under Billing.\n\nAsk support if missing."
+        );
+
+        let entry = FaqEntry {
+            id: "1".into(),
+            question: String::new(),
+            answer: String::new(),
+            enabled: true,
+            hit_count: 0,
+            channels: vec!["discord:1:*".into()],
+            scope: Some("support".into()),
+            status: EntryStatus::Active,
+            source_channel_id: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        let support = ScopeFilter::Only(vec!["support".into()]);
+        assert!(entry.applies_to("discord:1:42", &support));
+        assert!(!entry.applies_to("discord:2:42", &support));
+        assert!(!entry.applies_to("discord:1:42", &ScopeFilter::Only(Vec::new())));
+    }
+}
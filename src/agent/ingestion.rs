@@ -48,7 +48,7 @@ async fn run_ingestion_loop(ingest_dir: &Path, deps: &AgentDeps) -> anyhow::Resu
     loop {
         let config = **deps.runtime_config.ingestion.load();
 
-        if !config.enabled {
+        if !config.enabled || deps.runtime_config.is_paused() {
             tokio::time::sleep(Duration::from_secs(config.poll_interval_secs)).await;
             continue;
         }
@@ -488,6 +488,7 @@ async fn process_chunk(
     let channel_store = crate::conversation::ChannelStore::new(deps.sqlite_pool.clone());
     let contract_state = Arc::new(MemoryPersistenceContractState::default());
     let tool_server: ToolServerHandle = crate::tools::create_branch_tool_server(
+        None,
         None,
         deps.agent_id.clone(),
         deps.task_store.clone(),
@@ -0,0 +1,169 @@
+//! Per-agent cache of rendered channel prompt sections.
+//!
+//! The project context costs a query per project plus two per project for
+//! repos and worktrees, and the skills listing and worker capabilities are
+//! templates over config that rarely changes, yet the channel prompt used to
+//! rebuild all three on every turn. Each section is stored with a hash of
+//! the inputs it was built from, and a turn whose inputs hash the same
+//! reuses the text. The project context is keyed by the project store's
+//! write generation, so creating, editing, or removing a project, repo, or
+//! worktree invalidates it. Runtime config reloads (the file watcher, the
+//! workspace and config APIs, skill installs) also clear the cache, so an
+//! entry never outlives the reload that replaced its inputs.
+//!
+//! Identity is joined from strings already in memory and the memory bulletin
+//! is cached by the cortex, so neither is covered. The available channels
+//! list is written to from too many places to invalidate cheaply and is
+//! still read each turn.
+
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::Mutex;
+
+/// A cached section of the channel system prompt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PromptSection {
+    Projects,
+    Skills,
+    WorkerCapabilities,
+}
+
+impl PromptSection {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Projects => "projects",
+            Self::Skills => "skills",
+            Self::WorkerCapabilities => "worker_capabilities",
+        }
+    }
+}
+
+/// Rendered sections keyed by the hash of their inputs.
+#[derive(Debug, Default)]
+pub struct PromptSectionCache {
+    entries: Mutex<HashMap<PromptSection, (u64, String)>>,
+}
+
+impl PromptSectionCache {
+    /// Return the cached text for `section` if it was rendered from inputs
+    /// hashing to `key`, otherwise render, store, and return it.
+    pub fn get_or_render<E>(
+        &self,
+        agent_id: &str,
+        section: PromptSection,
+        key: u64,
+        render: impl FnOnce() -> Result<String, E>,
+    ) -> Result<String, E> {
+        if let Some((cached_key, text)) = self.entries().get(&section)
+            && *cached_key == key
+        {
+            record(agent_id, section, "hit");
+            return Ok(text.clone());
+        }
+
+        // Rendered outside the lock; a concurrent miss just renders twice.
+        let text = render()?;
+        self.entries().insert(section, (key, text.clone()));
+        record(agent_id, section, "miss");
+        Ok(text)
+    }
+
+    /// The cached text for `section` if it was built from inputs hashing to
+    /// `key`. For sections built asynchronously; pair a miss with
+    /// [`insert`](Self::insert).
+    pub fn get(&self, agent_id: &str, section: PromptSection, key: u64) -> Option<String> {
+        let text = self
+            .entries()
+            .get(&section)
+            .filter(|(cached_key, _)| *cached_key == key)
+            .map(|(_, text)| text.clone());
+        record(
+            agent_id,
+            section,
+            if text.is_some() { "hit" } else { "miss" },
+        );
+        text
+    }
+
+    /// Store `text` for `section`, built from inputs hashing to `key`.
+    pub fn insert(&self, section: PromptSection, key: u64, text: String) {
+        self.entries().insert(section, (key, text));
+    }
+
+    /// Drop every cached section.
+    pub fn clear(&self) {
+        self.entries().clear();
+    }
+
+    fn entries(&self) -> std::sync::MutexGuard<'_, HashMap<PromptSection, (u64, String)>> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Hash of a section's inputs, used as its cache key.
+pub fn content_hash(inputs: impl Hash) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    inputs.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+fn record(agent_id: &str, section: PromptSection, result: &str) {
+    #[cfg(feature = "metrics")]
+    crate::telemetry::Metrics::global()
+        .prompt_section_cache_total
+        .with_label_values(&[agent_id, section.as_str(), result])
+        .inc();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_again_only_when_inputs_change() {
+        let cache = PromptSectionCache::default();
+        let mut renders = 0;
+        let mut render = |text: &str| {
+            renders += 1;
+            Ok::<_, ()>(text.to_string())
+        };
+
+        let key = content_hash(("listing", "en"));
+        let first = cache
+            .get_or_render("main", PromptSection::Skills, key, || render("v1"))
+            .unwrap();
+        let second = cache
+            .get_or_render("main", PromptSection::Skills, key, || render("v2"))
+            .unwrap();
+        assert_eq!((first.as_str(), second.as_str()), ("v1", "v1"));
+
+        let changed = content_hash(("new listing", "en"));
+        let third = cache
+            .get_or_render("main", PromptSection::Skills, changed, || render("v3"))
+            .unwrap();
+        assert_eq!(third, "v3");
+
+        cache.clear();
+        let fourth = cache
+            .get_or_render("main", PromptSection::Skills, changed, || render("v4"))
+            .unwrap();
+        assert_eq!(fourth, "v4");
+        assert_eq!(renders, 3);
+    }
+
+    #[test]
+    fn async_sections_hit_only_on_a_matching_key() {
+        let cache = PromptSectionCache::default();
+        assert_eq!(cache.get("main", PromptSection::Projects, 1), None);
+
+        cache.insert(PromptSection::Projects, 1, "projects v1".into());
+        assert_eq!(
+            cache.get("main", PromptSection::Projects, 1).as_deref(),
+            Some("projects v1")
+        );
+        assert_eq!(cache.get("main", PromptSection::Projects, 2), None);
+    }
+}
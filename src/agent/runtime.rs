@@ -0,0 +1,190 @@
+//! Dedicated tokio runtimes for agents that opt into isolation.
+//!
+//! By default every agent's tasks share the process runtime, so one busy
+//! agent queues behind another's work. With `[agents.runtime] dedicated`,
+//! the agent gets its own multi-threaded runtime, optionally pinned to a set
+//! of cores. The channel event loops, cortex loops, ingestion, and cron jobs
+//! are spawned onto it, and everything they spawn (branches, workers,
+//! compaction) stays there. API handlers and messaging adapters keep running
+//! on the main runtime.
+//!
+//! The runtime lives on its own OS thread, which shuts it down once the last
+//! [`AgentRuntime`] handle is dropped.
+
+use crate::config::AgentRuntimeConfig;
+use crate::error::Result;
+
+use anyhow::Context as _;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How long a dropped runtime waits for its tasks before abandoning them.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Interval between runtime metric samples.
+#[cfg(feature = "metrics")]
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// An agent's dedicated runtime.
+pub struct AgentRuntime {
+    handle: tokio::runtime::Handle,
+    /// Dropping this releases the runtime thread, which shuts the runtime down.
+    _shutdown: tokio::sync::oneshot::Sender<()>,
+}
+
+impl std::fmt::Debug for AgentRuntime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AgentRuntime").finish_non_exhaustive()
+    }
+}
+
+impl AgentRuntime {
+    /// Start the runtime `config` describes, or `None` when the agent
+    /// shares the main runtime.
+    pub fn start(agent_id: &str, config: &AgentRuntimeConfig) -> Result<Option<Arc<Self>>> {
+        if !config.dedicated {
+            return Ok(None);
+        }
+
+        let cores = config.cpu_affinity.clone();
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(config.worker_threads)
+            .thread_name(format!("agent-{agent_id}"))
+            .on_thread_start(move || pin_current_thread(&cores))
+            .enable_all()
+            .build()
+            .with_context(|| format!("failed to build runtime for agent '{agent_id}'"))?;
+        let handle = runtime.handle().clone();
+
+        let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+        let thread_agent_id = agent_id.to_string();
+        std::thread::Builder::new()
+            .name(format!("agent-{agent_id}-runtime"))
+            .spawn(move || {
+                // Resolves when the sender is dropped.
+                let _ = runtime.block_on(shutdown_rx);
+                runtime.shutdown_timeout(SHUTDOWN_TIMEOUT);
+                tracing::info!(agent_id = %thread_agent_id, "agent runtime stopped");
+            })
+            .with_context(|| format!("failed to start runtime thread for agent '{agent_id}'"))?;
+
+        tracing::info!(
+            agent_id,
+            worker_threads = config.worker_threads,
+            cpu_affinity = ?config.cpu_affinity,
+            "agent runtime started"
+        );
+        Ok(Some(Arc::new(Self {
+            handle,
+            _shutdown: shutdown_tx,
+        })))
+    }
+
+    pub fn handle(&self) -> &tokio::runtime::Handle {
+        &self.handle
+    }
+}
+
+/// Restrict the calling thread to `cores`. Called on each runtime thread as
+/// it starts; a failure leaves the thread unpinned.
+#[cfg(target_os = "linux")]
+fn pin_current_thread(cores: &[usize]) {
+    if cores.is_empty() {
+        return;
+    }
+    // SAFETY: cpu_set_t is plain data; CPU_ZERO/CPU_SET only write within
+    // it, and sched_setaffinity(0, ..) applies to the calling thread.
+    let result = unsafe {
+        let mut set = std::mem::zeroed::<libc::cpu_set_t>();
+        libc::CPU_ZERO(&mut set);
+        for &core in cores {
+            libc::CPU_SET(core, &mut set);
+        }
+        libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set)
+    };
+    if result != 0 {
+        tracing::warn!(
+            ?cores,
+            error = %std::io::Error::last_os_error(),
+            "failed to pin agent runtime thread"
+        );
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn pin_current_thread(cores: &[usize]) {
+    if !cores.is_empty() {
+        tracing::warn!(?cores, "cpu_affinity is only supported on Linux; ignoring");
+    }
+}
+
+/// Periodically record queue depth, live tasks, and worker busy time for the
+/// main runtime (labelled `shared`) and every dedicated agent runtime, so
+/// the effect of isolating an agent can be compared.
+#[cfg(feature = "metrics")]
+pub fn spawn_metrics_sampler(
+    runtimes: Vec<(String, tokio::runtime::Handle)>,
+) -> tokio::task::JoinHandle<()> {
+    let mut runtimes = runtimes;
+    runtimes.push(("shared".to_string(), tokio::runtime::Handle::current()));
+    tokio::spawn(async move {
+        let mut busy: Vec<Duration> = vec![Duration::ZERO; runtimes.len()];
+        let mut interval = tokio::time::interval(SAMPLE_INTERVAL);
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            let metrics = crate::telemetry::Metrics::global();
+            for ((label, handle), last_busy) in runtimes.iter().zip(busy.iter_mut()) {
+                let runtime_metrics = handle.metrics();
+                let workers = runtime_metrics.num_workers();
+                let total_busy: Duration = (0..workers)
+                    .map(|worker| runtime_metrics.worker_total_busy_duration(worker))
+                    .sum();
+                let busy_ratio = total_busy.saturating_sub(*last_busy).as_secs_f64()
+                    / (SAMPLE_INTERVAL.as_secs_f64() * workers.max(1) as f64);
+                *last_busy = total_busy;
+
+                metrics
+                    .runtime_alive_tasks
+                    .with_label_values(&[label])
+                    .set(runtime_metrics.num_alive_tasks() as i64);
+                metrics
+                    .runtime_global_queue_depth
+                    .with_label_values(&[label])
+                    .set(runtime_metrics.global_queue_depth() as i64);
+                metrics
+                    .runtime_busy_ratio
+                    .with_label_values(&[label])
+                    .set(busy_ratio.min(1.0));
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dedicated_runtime_runs_tasks_until_dropped() {
+        let config = AgentRuntimeConfig {
+            dedicated: true,
+            worker_threads: 1,
+            cpu_affinity: Vec::new(),
+        };
+        let runtime = AgentRuntime::start("test", &config).unwrap().unwrap();
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        runtime.handle().spawn(async move {
+            let name = std::thread::current().name().map(str::to_string);
+            tx.send(name).unwrap();
+        });
+        let thread_name = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(thread_name.as_deref(), Some("agent-test"));
+        drop(runtime);
+
+        let shared = AgentRuntimeConfig::default();
+        assert!(AgentRuntime::start("test", &shared).unwrap().is_none());
+    }
+}
@@ -0,0 +1,170 @@
+//! Speculative draft-and-verify replies.
+//!
+//! With `speculative_drafts` on, a channel turn first asks the fast model for
+//! a tool-free draft. On platforms that can edit sent messages the draft goes
+//! out right away; the channel model then reviews it and either approves it,
+//! rewrites it, or escalates to a normal tool-using turn. Rewrites that
+//! diverge past `draft_max_divergence` replace the draft in place. Every
+//! outcome is recorded in `speculative_drafts` with its divergence.
+
+use sqlx::SqlitePool;
+
+/// What the reviewing model made of a draft.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Approve,
+    Edit(String),
+    /// The reply needs tools, so the normal turn runs instead.
+    Escalate,
+}
+
+impl Verdict {
+    pub fn parse(response: &str) -> Self {
+        let trimmed = response.trim();
+        let marker = trimmed.trim_matches(|c: char| c == '`' || c == '*' || c == '.');
+        if marker.eq_ignore_ascii_case("approve") || trimmed.is_empty() {
+            Self::Approve
+        } else if marker.eq_ignore_ascii_case("escalate") {
+            Self::Escalate
+        } else {
+            Self::Edit(trimmed.to_string())
+        }
+    }
+}
+
+/// How a speculative turn ended, as recorded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// The draft was sent unchanged.
+    Kept,
+    /// The review changed the draft too much and its version was sent.
+    Replaced,
+    /// The review asked for a full turn.
+    Escalated,
+}
+
+impl Outcome {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Kept => "kept",
+            Self::Replaced => "replaced",
+            Self::Escalated => "escalated",
+        }
+    }
+}
+
+/// Whether the adapter edits a streamed message in place, so a draft can be
+/// shown before it's reviewed.
+pub fn supports_edits(adapter: &str) -> bool {
+    let platform = adapter.split(':').next().unwrap_or(adapter);
+    matches!(platform, "discord" | "slack" | "telegram")
+}
+
+/// Whether text is safe to show the user: no tool syntax or structured
+/// payloads, and nothing that looks like a secret.
+pub fn is_sendable(text: &str) -> bool {
+    !text.trim().is_empty()
+        && !crate::tools::should_block_user_visible_text(text)
+        && crate::secrets::scrub::scan_for_leaks(text).is_none()
+}
+
+/// Word-level edit distance between the draft and the reviewed reply, as a
+/// fraction of the longer one: 0.0 for identical text, 1.0 for nothing in
+/// common.
+pub fn divergence(draft: &str, reviewed: &str) -> f64 {
+    let draft: Vec<String> = words(draft);
+    let reviewed: Vec<String> = words(reviewed);
+    let longest = draft.len().max(reviewed.len());
+    if longest == 0 {
+        return 0.0;
+    }
+
+    let mut previous: Vec<usize> = (0..=reviewed.len()).collect();
+    let mut current = vec![0; reviewed.len() + 1];
+    for (i, draft_word) in draft.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, reviewed_word) in reviewed.iter().enumerate() {
+            let substitution = previous[j] + usize::from(draft_word != reviewed_word);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[reviewed.len()] as f64 / longest as f64
+}
+
+fn words(text: &str) -> Vec<String> {
+    text.split_whitespace()
+        .map(|word| {
+            word.trim_matches(|c: char| !c.is_alphanumeric())
+                .to_lowercase()
+        })
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+/// One speculative turn, for the `speculative_drafts` table.
+#[derive(Debug)]
+pub struct DraftRecord<'a> {
+    pub channel_id: &'a str,
+    pub draft_model: &'a str,
+    pub review_model: &'a str,
+    pub outcome: Outcome,
+    pub divergence: Option<f64>,
+    /// Whether the draft was visible before the review finished.
+    pub shown_early: bool,
+    pub draft_ms: u64,
+    pub review_ms: u64,
+}
+
+pub async fn record(pool: &SqlitePool, record: &DraftRecord<'_>) {
+    let result = sqlx::query(
+        "INSERT INTO speculative_drafts \
+         (id, channel_id, draft_model, review_model, outcome, divergence, shown_early, draft_ms, review_ms) \
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(uuid::Uuid::new_v4().to_string())
+    .bind(record.channel_id)
+    .bind(record.draft_model)
+    .bind(record.review_model)
+    .bind(record.outcome.as_str())
+    .bind(record.divergence)
+    .bind(record.shown_early)
+    .bind(record.draft_ms as i64)
+    .bind(record.review_ms as i64)
+    .execute(pool)
+    .await;
+    if let Err(error) = result {
+        tracing::warn!(%error, channel_id = %record.channel_id, "failed to record speculative draft");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verdicts_and_divergence() {
+        assert_eq!(Verdict::parse("APPROVE"), Verdict::Approve);
+        assert_eq!(Verdict::parse("`approve`.\n"), Verdict::Approve);
+        assert_eq!(Verdict::parse("ESCALATE"), Verdict::Escalate);
+        assert_eq!(
+            Verdict::parse("Sure, the meeting is at 3pm."),
+            Verdict::Edit("Sure, the meeting is at 3pm.".to_string())
+        );
+
+        assert_eq!(
+            divergence("The meeting is at 3pm.", "the meeting is at 3pm"),
+            0.0
+        );
+        let small = divergence(
+            "The meeting is at 3pm in room four.",
+            "The meeting is at 3pm in room five.",
+        );
+        assert!(small > 0.0 && small < 0.2, "{small}");
+        assert_eq!(divergence("yes", "no"), 1.0);
+        assert_eq!(divergence("", ""), 0.0);
+
+        assert!(supports_edits("telegram:ops"));
+        assert!(!supports_edits("signal"));
+    }
+}
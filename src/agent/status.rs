@@ -138,6 +138,8 @@ pub struct StatusBlock {
     pub completed_items: Vec<CompletedItem>,
     /// Active link conversations with other agents.
     pub active_link_conversations: Vec<LinkConversationStatus>,
+    /// The channel records messages but doesn't reply.
+    pub muted: bool,
 }
 
 /// Status of an active branch.
@@ -0,0 +1,284 @@
+//! Fact-checking channel replies before they're sent.
+//!
+//! With `reply_verification` on, the reply tool hands each drafted reply to
+//! a cheap model along with the agent's most relevant memories. The model
+//! lists the reply's factual claims and whether the memories back them up.
+//! Unsupported claims are then noted under the reply, hedged in a rewrite,
+//! or sent back to the channel model to revise, depending on the channel's
+//! setting. The check is stored on the logged message under `verification`
+//! metadata.
+
+use crate::ProcessType;
+use crate::config::{ReplyVerification, RuntimeConfig};
+use crate::error::Result;
+use crate::llm::{LlmManager, SpacebotModel};
+use crate::memory::scope::ScopeFilter;
+use crate::memory::{MemorySearch, SearchConfig, SearchMode};
+
+use anyhow::Context as _;
+use rig::agent::AgentBuilder;
+use rig::completion::{CompletionModel, TypedPrompt};
+use serde::{Deserialize, Serialize};
+
+use std::sync::Arc;
+
+/// Memories retrieved as evidence for one reply.
+const EVIDENCE_LIMIT: usize = 8;
+
+/// One factual claim from a reply and whether the evidence supports it.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ClaimCheck {
+    pub claim: String,
+    pub supported: bool,
+    /// The memory that backs the claim up, or why it couldn't be checked.
+    #[serde(default)]
+    pub evidence: Option<String>,
+}
+
+/// Structured model output for the check.
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+struct ClaimChecks {
+    claims: Vec<ClaimCheck>,
+}
+
+/// Structured model output for a softened reply.
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+struct SoftenedReply {
+    text: String,
+}
+
+/// How a checked reply went out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    /// Every claim was supported, so the reply was sent as written.
+    Verified,
+    /// A note listing the unsupported claims was added.
+    Annotated,
+    /// The reply was rewritten to hedge the unsupported claims.
+    Softened,
+    /// The channel model revised the reply after a failed check.
+    Regenerated,
+}
+
+/// The result of checking one reply.
+#[derive(Debug, Clone)]
+pub struct Verification {
+    pub claims: Vec<ClaimCheck>,
+    pub model: String,
+}
+
+impl Verification {
+    pub fn unsupported(&self) -> Vec<&str> {
+        self.claims
+            .iter()
+            .filter(|check| !check.supported)
+            .map(|check| check.claim.as_str())
+            .collect()
+    }
+
+    /// The `verification` metadata stored on the logged message.
+    pub fn metadata(&self, action: ReplyVerification, outcome: Outcome) -> serde_json::Value {
+        serde_json::json!({
+            "action": action,
+            "outcome": outcome,
+            "model": self.model,
+            "claims": self.claims,
+        })
+    }
+}
+
+/// Append a note naming the claims that couldn't be verified.
+pub fn annotate(reply: &str, unsupported: &[&str]) -> String {
+    if unsupported.is_empty() {
+        return reply.to_string();
+    }
+    format!(
+        "{}\n\n_Couldn't verify: {}_",
+        reply.trim_end(),
+        unsupported.join("; ")
+    )
+}
+
+/// Tool error text that sends a reply back to the channel model.
+pub fn regenerate_feedback(unsupported: &[&str]) -> String {
+    let claims = unsupported
+        .iter()
+        .map(|claim| format!("- {claim}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!(
+        "reply not sent: these claims aren't supported by memory. Drop them, hedge them, \
+         or check them with a branch first, then call reply again.\n{claims}"
+    )
+}
+
+/// The verification setting in effect for a channel: its `/verify`
+/// override, else the agent's `reply_verification`.
+pub fn channel_setting(runtime_config: &RuntimeConfig, channel_id: &str) -> ReplyVerification {
+    runtime_config
+        .settings
+        .load()
+        .as_ref()
+        .as_ref()
+        .and_then(|settings| settings.channel_reply_verification(channel_id))
+        .unwrap_or(runtime_config.channel_config.load().reply_verification)
+}
+
+/// Checks replies for one channel turn.
+#[derive(Clone)]
+pub struct ReplyVerifier {
+    pub action: ReplyVerification,
+    memory_search: Arc<MemorySearch>,
+    scope_filter: ScopeFilter,
+    llm_manager: Arc<LlmManager>,
+    runtime_config: Arc<RuntimeConfig>,
+    agent_id: crate::AgentId,
+}
+
+impl std::fmt::Debug for ReplyVerifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReplyVerifier")
+            .field("action", &self.action)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ReplyVerifier {
+    pub fn new(
+        action: ReplyVerification,
+        memory_search: Arc<MemorySearch>,
+        scope_filter: ScopeFilter,
+        llm_manager: Arc<LlmManager>,
+        runtime_config: Arc<RuntimeConfig>,
+        agent_id: crate::AgentId,
+    ) -> Self {
+        Self {
+            action,
+            memory_search,
+            scope_filter,
+            llm_manager,
+            runtime_config,
+            agent_id,
+        }
+    }
+
+    /// Routed like a branch with the `verification` task type, so a cheaper
+    /// model can be assigned through `routing.task_overrides.verification`.
+    fn model(&self, context: &str) -> (SpacebotModel, String) {
+        let routing = self.runtime_config.routing.load();
+        let model_name = routing
+            .resolve(ProcessType::Branch, Some("verification"))
+            .to_string();
+        let model = SpacebotModel::make(&self.llm_manager, &model_name)
+            .with_context(&*self.agent_id, context)
+            .with_routing((**routing).clone());
+        (model, model_name)
+    }
+
+    /// Check the factual claims in `reply` against the agent's memories.
+    pub async fn check(&self, reply: &str) -> Result<Verification> {
+        let config = SearchConfig {
+            mode: SearchMode::Hybrid,
+            max_results: EVIDENCE_LIMIT,
+            max_results_per_source: EVIDENCE_LIMIT * 2,
+            scope_filter: self.scope_filter.clone(),
+            ..Default::default()
+        };
+        let results = self
+            .memory_search
+            .search(reply, &config)
+            .await
+            .context("evidence search failed")?;
+        let evidence = if results.is_empty() {
+            "(no relevant memories)".to_string()
+        } else {
+            results
+                .iter()
+                .map(|result| {
+                    format!(
+                        "- [{}] {}",
+                        result.memory.memory_type, result.memory.content
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+
+        let preamble = self
+            .runtime_config
+            .prompts
+            .load()
+            .render_static("reply_verifier")?;
+        let (model, model_name) = self.model("verification");
+        let agent = AgentBuilder::new(model).preamble(&preamble).build();
+        let prompt = format!("Evidence:\n{evidence}\n\nReply:\n{reply}");
+        let checks = agent
+            .prompt_typed::<ClaimChecks>(&prompt)
+            .await
+            .context("verification model call failed")?;
+
+        Ok(Verification {
+            claims: checks.claims,
+            model: model_name,
+        })
+    }
+
+    /// Rewrite `reply` so the unsupported claims are hedged or dropped.
+    pub async fn soften(&self, reply: &str, unsupported: &[&str]) -> Result<String> {
+        let preamble = self
+            .runtime_config
+            .prompts
+            .load()
+            .render_static("reply_softener")?;
+        let (model, _) = self.model("verification");
+        let agent = AgentBuilder::new(model).preamble(&preamble).build();
+        let claims = unsupported
+            .iter()
+            .map(|claim| format!("- {claim}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let prompt = format!("Unsupported claims:\n{claims}\n\nReply:\n{reply}");
+        let softened = agent
+            .prompt_typed::<SoftenedReply>(&prompt)
+            .await
+            .context("softening model call failed")?;
+        Ok(softened.text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn annotation_lists_only_unsupported_claims() {
+        let verification = Verification {
+            claims: vec![
+                ClaimCheck {
+                    claim: "the deploy is on Friday".into(),
+                    supported: true,
+                    evidence: Some("deploys happen on Fridays".into()),
+                },
+                ClaimCheck {
+                    claim: "Sam owns billing".into(),
+                    supported: false,
+                    evidence: None,
+                },
+            ],
+            model: "test".into(),
+        };
+        let unsupported = verification.unsupported();
+        assert_eq!(unsupported, vec!["Sam owns billing"]);
+        assert_eq!(
+            annotate("Sure thing.\n", &unsupported),
+            "Sure thing.\n\n_Couldn't verify: Sam owns billing_"
+        );
+        assert_eq!(annotate("Sure thing.", &[]), "Sure thing.");
+
+        let metadata = verification.metadata(ReplyVerification::Annotate, Outcome::Annotated);
+        assert_eq!(metadata["action"], "annotate");
+        assert_eq!(metadata["outcome"], "annotated");
+        assert_eq!(metadata["claims"][1]["supported"], false);
+    }
+}
@@ -296,6 +296,20 @@ impl Worker {
         tracing::info!(worker_id = %self.id, task = %self.task, "worker starting");
 
         let mcp_tools = self.deps.mcp_manager.get_tools().await;
+        let issue_tracker =
+            (!self.deps.runtime_config.issue_trackers.load().is_empty()).then(|| {
+                crate::tools::IssueTrackerTool::new(
+                    self.deps.runtime_config.clone(),
+                    crate::approvals::ApprovalStore::new(self.deps.sqlite_pool.clone())
+                        .with_notifier(crate::notifications::OperatorNotifier::from_deps(
+                            &self.deps,
+                        )),
+                    self.channel_id
+                        .as_ref()
+                        .map(|channel_id| channel_id.to_string()),
+                    format!("worker:{}", self.id),
+                )
+            });
 
         // Create per-worker ToolServer with task tools
         let worker_tool_server = crate::tools::create_worker_tool_server(
@@ -310,6 +324,12 @@ impl Worker {
             self.deps.runtime_config.workspace_dir.clone(),
             self.deps.sandbox.clone(),
             mcp_tools,
+            crate::tools::ChartTool::new(
+                self.deps.sqlite_pool.clone(),
+                self.deps.runtime_config.saved_dir(),
+                self.channel_id.clone(),
+            ),
+            issue_tracker,
             self.deps.runtime_config.clone(),
         );
 
@@ -4,33 +4,66 @@
 //! managing agents, viewing status, and interacting with the system.
 //! Includes an SSE endpoint for realtime event streaming.
 
+mod access;
 pub mod agents;
+mod approvals;
+mod attachments;
+mod audit;
+mod backfill;
+mod batch;
 mod bindings;
+mod broadcast;
 mod channels;
 mod config;
+mod contacts;
 mod cortex;
 mod cron;
+mod dev_proxy;
+mod event_archive;
+mod export;
 mod factory;
+mod faq;
+mod graphql;
+mod idempotency;
 mod ingest;
 mod links;
+mod locale;
+mod logs;
 mod mcp;
 mod memories;
 mod messaging;
 mod models;
+mod openai;
+mod openapi;
 mod opencode_proxy;
+mod outbox;
+mod processes;
 mod projects;
 mod providers;
+mod rate_limit;
 mod secrets;
 mod server;
 mod settings;
+mod shares;
 mod skills;
 pub(crate) mod ssh;
 mod state;
 mod system;
 mod tasks;
+mod tls;
 mod tools;
+mod uploads;
+mod usage;
 mod webchat;
+mod webhooks;
+mod widget;
 mod workers;
+mod workspace;
 
-pub use server::start_http_server;
+pub use audit::AuditLog;
+pub use event_archive::{EventArchive, spawn_event_archiver};
+pub use idempotency::IdempotencyStore;
+pub use rate_limit::RateLimiter;
+pub use server::{ApiListener, start_http_server};
 pub use state::{AgentInfo, ApiEvent, ApiState};
+pub use webhooks::{WebhookRegistry, spawn_webhook_delivery};
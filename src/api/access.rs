@@ -0,0 +1,176 @@
+//! Agent ownership checks for agent-restricted API tokens.
+//!
+//! A token with `agents` set may only act on those agents. Handlers on agent
+//! routes take the request part that names their agent through
+//! [`AgentQuery`], [`AgentJson`], or [`AgentPath`], so the agent checked is
+//! the agent the handler goes on to use. Routes that span the instance sit
+//! behind [`deny_agent_restricted`] and refuse such tokens whatever
+//! parameters they carry.
+
+use super::server::ApiPrincipal;
+
+use axum::Json;
+use axum::extract::{FromRequest, FromRequestParts, Path, Query, Request};
+use axum::http::request::Parts;
+use axum::http::{Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use serde::de::DeserializeOwned;
+use serde_json::json;
+
+/// Instance-level GET routes that agent-restricted tokens may still call.
+/// `/agents` filters its listing to the token's agents; the rest expose
+/// nothing agent-specific.
+const AGENT_RESTRICTED_OPEN_ROUTES: &[&str] = &["/agents", "/health", "/models"];
+
+/// A request naming the agent its handler acts on.
+pub(super) trait AgentScoped {
+    /// The agent the request acts on, or `None` when it spans every agent.
+    fn agent_id(&self) -> Option<&str>;
+}
+
+/// An `/agents/{id}` path parameter.
+impl AgentScoped for String {
+    fn agent_id(&self) -> Option<&str> {
+        Some(self)
+    }
+}
+
+/// An `/agents/{id}/.../{other}` path, agent first.
+impl AgentScoped for (String, String) {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.0)
+    }
+}
+
+/// [`Query`] that refuses agents outside the caller's token.
+pub(super) struct AgentQuery<T>(pub T);
+
+/// [`Json`] that refuses agents outside the caller's token.
+pub(super) struct AgentJson<T>(pub T);
+
+/// [`Path`] that refuses agents outside the caller's token.
+pub(super) struct AgentPath<T>(pub T);
+
+impl<S, T> FromRequestParts<S> for AgentQuery<T>
+where
+    S: Send + Sync,
+    T: DeserializeOwned + AgentScoped + Send,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Query(value) = Query::<T>::from_request_parts(parts, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+        authorize(parts.extensions.get::<ApiPrincipal>(), value.agent_id()).map_err(forbidden)?;
+        Ok(Self(value))
+    }
+}
+
+impl<S, T> FromRequestParts<S> for AgentPath<T>
+where
+    S: Send + Sync,
+    T: DeserializeOwned + AgentScoped + Send,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Path(value) = Path::<T>::from_request_parts(parts, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+        authorize(parts.extensions.get::<ApiPrincipal>(), value.agent_id()).map_err(forbidden)?;
+        Ok(Self(value))
+    }
+}
+
+impl<S, T> FromRequest<S> for AgentJson<T>
+where
+    S: Send + Sync,
+    T: DeserializeOwned + AgentScoped,
+{
+    type Rejection = Response;
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let principal = request.extensions().get::<ApiPrincipal>().cloned();
+        let Json(value) = Json::<T>::from_request(request, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+        authorize(principal.as_ref(), value.agent_id()).map_err(forbidden)?;
+        Ok(Self(value))
+    }
+}
+
+/// Check `agent_id` against the caller's token. Unauthenticated instances
+/// and unrestricted tokens may act on any agent, or on all of them.
+fn authorize(principal: Option<&ApiPrincipal>, agent_id: Option<&str>) -> Result<(), String> {
+    let Some(principal) = principal.filter(|principal| !principal.agents.is_empty()) else {
+        return Ok(());
+    };
+    match agent_id {
+        Some(agent_id) if principal.allows_agent(agent_id) => Ok(()),
+        Some(agent_id) => {
+            tracing::debug!(
+                token = %principal.name,
+                agent_id,
+                "API request rejected: outside the token's agents"
+            );
+            Err(format!("this token can't act on agent '{agent_id}'"))
+        }
+        None => Err("name an agent with agent_id; this token can't act on every agent".into()),
+    }
+}
+
+/// Narrow the agents a request covers to the caller's token. With none
+/// requested that is all of the token's agents; otherwise every requested
+/// agent must belong to it. For endpoints that read several agents at once,
+/// like the event stream and its history.
+pub(super) fn restrict_to_agents(
+    principal: Option<&ApiPrincipal>,
+    agent_ids: &mut Vec<String>,
+) -> Result<(), String> {
+    let Some(principal) = principal.filter(|principal| !principal.agents.is_empty()) else {
+        return Ok(());
+    };
+    if agent_ids.is_empty() {
+        *agent_ids = principal.agents.clone();
+        return Ok(());
+    }
+    agent_ids
+        .iter()
+        .try_for_each(|agent_id| authorize(Some(principal), Some(agent_id)))
+}
+
+/// Refuse agent-restricted tokens on routes that span the instance, except
+/// for a few harmless GETs.
+pub(super) async fn deny_agent_restricted(request: Request, next: Next) -> Response {
+    let Some(principal) = request
+        .extensions()
+        .get::<ApiPrincipal>()
+        .filter(|principal| !principal.agents.is_empty())
+    else {
+        return next.run(request).await;
+    };
+
+    let path = request.uri().path();
+    let relative_path = path.strip_prefix("/api").unwrap_or(path);
+    if *request.method() == Method::GET && AGENT_RESTRICTED_OPEN_ROUTES.contains(&relative_path) {
+        return next.run(request).await;
+    }
+
+    tracing::debug!(
+        token = %principal.name,
+        method = %request.method(),
+        path = relative_path,
+        "API request rejected: instance-level route for an agent-restricted token"
+    );
+    forbidden("this endpoint spans every agent; use a token without an agent restriction".into())
+}
+
+fn forbidden(message: String) -> Response {
+    (
+        StatusCode::FORBIDDEN,
+        Json(json!({"error": "forbidden", "message": message})),
+    )
+        .into_response()
+}
@@ -1,11 +1,13 @@
+use super::access::{AgentJson, AgentPath, AgentQuery, AgentScoped};
+use super::server::ApiPrincipal;
 use super::state::{AgentInfo, ApiState};
 
 use crate::agent::cortex::CortexLogger;
 use crate::conversation::channels::ChannelStore;
 
-use axum::Json;
-use axum::extract::{Query, State};
+use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
+use axum::{Extension, Json};
 use serde::{Deserialize, Serialize};
 use sqlx::Row as _;
 use std::collections::{HashMap, HashSet};
@@ -23,7 +25,7 @@ fn hosted_agent_limit() -> Option<usize> {
         .filter(|value| *value > 0)
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, utoipa::ToSchema)]
 pub(super) struct AgentsResponse {
     agents: Vec<AgentInfo>,
 }
@@ -73,6 +75,7 @@ struct CronJobInfo {
     run_once: bool,
     active_hours: Option<(u8, u8)>,
     timeout_secs: Option<u64>,
+    timezone: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -100,19 +103,26 @@ pub(super) struct AgentProfileResponse {
     profile: Option<crate::agent::cortex::AgentProfile>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, utoipa::ToSchema)]
 pub(super) struct IdentityResponse {
     soul: Option<String>,
     identity: Option<String>,
     role: Option<String>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
 pub(super) struct IdentityQuery {
     agent_id: String,
 }
 
-#[derive(Deserialize)]
+impl AgentScoped for IdentityQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
 pub(super) struct IdentityUpdateRequest {
     agent_id: String,
     soul: Option<String>,
@@ -120,12 +130,24 @@ pub(super) struct IdentityUpdateRequest {
     role: Option<String>,
 }
 
+impl AgentScoped for IdentityUpdateRequest {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
 #[derive(Deserialize)]
 pub(super) struct AgentOverviewQuery {
     agent_id: String,
 }
 
-#[derive(Deserialize)]
+impl AgentScoped for AgentOverviewQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
 pub struct CreateAgentRequest {
     pub agent_id: String,
     pub display_name: Option<String>,
@@ -148,7 +170,8 @@ pub(super) struct UpdateAgentRequest {
     gradient_end: Option<String>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
 pub(super) struct DeleteAgentQuery {
     agent_id: String,
 }
@@ -158,12 +181,24 @@ pub(super) struct AgentMcpQuery {
     agent_id: String,
 }
 
+impl AgentScoped for AgentMcpQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
 #[derive(Deserialize)]
 pub(super) struct ReconnectMcpRequest {
     agent_id: String,
     server_name: String,
 }
 
+impl AgentScoped for ReconnectMcpRequest {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
 #[derive(Serialize)]
 pub(super) struct AgentMcpResponse {
     servers: Vec<crate::mcp::McpServerStatus>,
@@ -174,6 +209,12 @@ pub(super) struct WarmupQuery {
     agent_id: Option<String>,
 }
 
+impl AgentScoped for WarmupQuery {
+    fn agent_id(&self) -> Option<&str> {
+        self.agent_id.as_deref()
+    }
+}
+
 #[derive(Deserialize)]
 pub(super) struct WarmupTriggerRequest {
     agent_id: Option<String>,
@@ -181,6 +222,12 @@ pub(super) struct WarmupTriggerRequest {
     force: bool,
 }
 
+impl AgentScoped for WarmupTriggerRequest {
+    fn agent_id(&self) -> Option<&str> {
+        self.agent_id.as_deref()
+    }
+}
+
 #[derive(Serialize)]
 pub(super) struct WarmupStatusEntry {
     agent_id: String,
@@ -271,18 +318,200 @@ fn resolve_warmup_agent_ids(
     Ok(accepted_agents)
 }
 
-/// List all configured agents with their config summaries.
-pub(super) async fn list_agents(State(state): State<Arc<ApiState>>) -> Json<AgentsResponse> {
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct AgentHealthResponse {
+    agent_id: String,
+    /// `ok` when the agent is warm and every bound adapter is connected,
+    /// `degraded` otherwise.
+    #[schema(value_type = String)]
+    status: &'static str,
+    warmup: crate::config::WarmupStatus,
+    /// Connection state of the adapters this agent is bound to.
+    adapters: Vec<crate::messaging::connection::ConnectionStatus>,
+    /// Usage of the agent's task, LLM, and database budgets.
+    quotas: Vec<crate::quota::QuotaUsage>,
+    /// Whether any budget is fully in use, so new work is queueing.
+    quota_saturated: bool,
+    /// Whether the agent is paused via `POST /api/agents/{id}/pause`.
+    paused: bool,
+}
+
+/// Agent readiness plus the connection state of its platform adapters.
+#[utoipa::path(
+    get,
+    path = "/api/agents/{id}/health",
+    tag = "agents",
+    params(("id" = String, Path, description = "Agent ID")),
+    responses(
+        (status = 200, body = AgentHealthResponse),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
+pub(super) async fn agent_health(
+    State(state): State<Arc<ApiState>>,
+    AgentPath(agent_id): AgentPath<String>,
+) -> Result<Json<AgentHealthResponse>, StatusCode> {
+    let (warmup, paused) = {
+        let runtime_configs = state.runtime_configs.load();
+        let runtime_config = runtime_configs
+            .get(&agent_id)
+            .ok_or(StatusCode::NOT_FOUND)?;
+        (
+            hydrate_warmup_status(runtime_config),
+            runtime_config.is_paused(),
+        )
+    };
+
+    let bound_adapters: HashSet<String> = match state.bindings.read().await.as_ref() {
+        Some(bindings) => bindings
+            .load()
+            .iter()
+            .filter(|binding| binding.agent_id == agent_id)
+            .map(|binding| binding.runtime_adapter_key())
+            .collect(),
+        None => HashSet::new(),
+    };
+    let adapters = match state.messaging_manager.read().await.clone() {
+        Some(manager) => manager
+            .connection_statuses()
+            .await
+            .into_iter()
+            .filter(|status| bound_adapters.contains(&status.adapter))
+            .collect(),
+        None => Vec::new(),
+    };
+
+    let quotas = state
+        .agent_quotas
+        .load()
+        .get(&agent_id)
+        .map(|quota| quota.usage())
+        .unwrap_or_default();
+    let quota_saturated = quotas.iter().any(|usage| usage.in_use >= usage.limit);
+
+    let healthy = warmup.state == crate::config::WarmupState::Warm
+        && adapters.iter().all(|adapter| {
+            adapter.state == crate::messaging::connection::ConnectionState::Connected
+        });
+    Ok(Json(AgentHealthResponse {
+        agent_id,
+        status: if healthy { "ok" } else { "degraded" },
+        warmup,
+        adapters,
+        quotas,
+        quota_saturated,
+        paused,
+    }))
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct AgentPauseResponse {
+    agent_id: String,
+    paused: bool,
+    /// What happens to inbound messages while paused.
+    pause_behavior: crate::config::PauseBehavior,
+}
+
+/// Stop an agent from handling inbound messages and cortex ticks. The API
+/// stays fully readable. Pausing an already paused agent is a no-op.
+#[utoipa::path(
+    post,
+    path = "/api/agents/{id}/pause",
+    tag = "agents",
+    params(("id" = String, Path, description = "Agent ID")),
+    responses(
+        (status = 200, body = AgentPauseResponse),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
+pub(super) async fn pause_agent(
+    State(state): State<Arc<ApiState>>,
+    AgentPath(agent_id): AgentPath<String>,
+) -> Result<Json<AgentPauseResponse>, StatusCode> {
+    set_agent_paused(&state, agent_id, true)
+}
+
+/// Resume a paused agent. Messages held while it was paused are delivered in
+/// the order they arrived.
+#[utoipa::path(
+    post,
+    path = "/api/agents/{id}/resume",
+    tag = "agents",
+    params(("id" = String, Path, description = "Agent ID")),
+    responses(
+        (status = 200, body = AgentPauseResponse),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
+pub(super) async fn resume_agent(
+    State(state): State<Arc<ApiState>>,
+    AgentPath(agent_id): AgentPath<String>,
+) -> Result<Json<AgentPauseResponse>, StatusCode> {
+    let response = set_agent_paused(&state, agent_id, false)?;
+    state.agent_resumed.notify_one();
+    Ok(response)
+}
+
+fn set_agent_paused(
+    state: &ApiState,
+    agent_id: String,
+    paused: bool,
+) -> Result<Json<AgentPauseResponse>, StatusCode> {
+    let runtime_configs = state.runtime_configs.load();
+    let runtime_config = runtime_configs
+        .get(&agent_id)
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let was_paused = runtime_config.is_paused();
+    // Persisted first, so an agent paused while its identity files are edited
+    // doesn't come back answering after a restart.
+    if let Some(settings) = runtime_config.settings.load().as_ref()
+        && let Err(error) = settings.set_agent_paused(paused)
+    {
+        tracing::error!(%error, agent_id = %agent_id, "failed to persist agent pause state");
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    }
+    runtime_config.paused.store(Arc::new(paused));
+    let pause_behavior = runtime_config.channel_config.load().pause_behavior;
+    if was_paused != paused {
+        tracing::info!(agent_id = %agent_id, paused, ?pause_behavior, "agent pause state changed via API");
+    }
+
+    Ok(Json(AgentPauseResponse {
+        agent_id,
+        paused,
+        pause_behavior,
+    }))
+}
+
+/// List all configured agents with their config summaries. Agent-restricted
+/// API tokens only see their own agents.
+#[utoipa::path(
+    get,
+    path = "/api/agents",
+    tag = "agents",
+    responses((status = 200, body = AgentsResponse))
+)]
+pub(super) async fn list_agents(
+    State(state): State<Arc<ApiState>>,
+    principal: Option<Extension<ApiPrincipal>>,
+) -> Json<AgentsResponse> {
     let agents = state.agent_configs.load();
-    Json(AgentsResponse {
-        agents: agents.as_ref().clone(),
-    })
+    let agents = agents
+        .iter()
+        .filter(|agent| {
+            principal
+                .as_ref()
+                .is_none_or(|Extension(principal)| principal.allows_agent(&agent.id))
+        })
+        .cloned()
+        .collect();
+    Json(AgentsResponse { agents })
 }
 
 /// List MCP connection status for an agent.
 pub(super) async fn list_agent_mcp(
     State(state): State<Arc<ApiState>>,
-    Query(query): Query<AgentMcpQuery>,
+    AgentQuery(query): AgentQuery<AgentMcpQuery>,
 ) -> Result<Json<AgentMcpResponse>, StatusCode> {
     let managers = state.mcp_managers.load();
     let manager = managers
@@ -296,7 +525,7 @@ pub(super) async fn list_agent_mcp(
 /// Force reconnect for a single MCP server on an agent.
 pub(super) async fn reconnect_agent_mcp(
     State(state): State<Arc<ApiState>>,
-    Json(request): Json<ReconnectMcpRequest>,
+    AgentJson(request): AgentJson<ReconnectMcpRequest>,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
     let managers = state.mcp_managers.load();
     let manager = managers
@@ -327,7 +556,7 @@ pub(super) async fn reconnect_agent_mcp(
 /// Get warmup status for one agent or all agents.
 pub(super) async fn get_warmup_status(
     State(state): State<Arc<ApiState>>,
-    Query(query): Query<WarmupQuery>,
+    AgentQuery(query): AgentQuery<WarmupQuery>,
 ) -> Result<Json<WarmupStatusResponse>, StatusCode> {
     let runtime_configs = state.runtime_configs.load();
 
@@ -356,7 +585,7 @@ pub(super) async fn get_warmup_status(
 /// Trigger warmup for one agent or all agents.
 pub(super) async fn trigger_warmup(
     State(state): State<Arc<ApiState>>,
-    Json(request): Json<WarmupTriggerRequest>,
+    AgentJson(request): AgentJson<WarmupTriggerRequest>,
 ) -> Result<Json<WarmupTriggerResponse>, StatusCode> {
     let llm_manager = {
         let guard = state.llm_manager.read().await;
@@ -413,6 +642,24 @@ pub(super) async fn trigger_warmup(
         let task_store_registry = state.task_store_registry.clone();
         let injection_tx = state.injection_tx.clone();
         let humans = (**state.agent_humans.load()).clone();
+        let leader = state
+            .leader_leases
+            .load()
+            .get(&agent_id)
+            .cloned()
+            .unwrap_or_else(crate::leader::LeaderLease::disabled);
+        let quota = state
+            .agent_quotas
+            .load()
+            .get(&agent_id)
+            .cloned()
+            .unwrap_or_else(|| {
+                crate::quota::AgentQuota::new(
+                    Arc::from(agent_id.as_str()),
+                    &crate::config::QuotaConfig::default(),
+                    sqlite_pool.clone(),
+                )
+            });
         tokio::spawn(async move {
             let (event_tx, memory_event_tx) = crate::create_process_event_buses();
             let project_store =
@@ -439,6 +686,9 @@ pub(super) async fn trigger_warmup(
                     crate::agent::process_control::ProcessControlRegistry::new(),
                 ),
                 injection_tx,
+                leader,
+                quota,
+                runtime: None,
             };
             let logger = CortexLogger::new(sqlite_pool);
             crate::agent::cortex::run_warmup_once(&deps, &logger, "api_trigger", force).await;
@@ -453,6 +703,17 @@ pub(super) async fn trigger_warmup(
 }
 
 /// Create a new agent and initialize it live (directories, databases, memory, identity, cron, cortex).
+#[utoipa::path(
+    post,
+    path = "/api/agents",
+    tag = "agents",
+    request_body = CreateAgentRequest,
+    responses(
+        (status = 201, description = "Agent created; the body carries `success`, `agent_id`, and `message`"),
+        (status = 400, description = "Empty agent ID, or the agent limit is reached"),
+        (status = 409, description = "An agent with this ID already exists"),
+    )
+)]
 pub(super) async fn create_agent(
     State(state): State<Arc<ApiState>>,
     Json(request): Json<CreateAgentRequest>,
@@ -564,36 +825,7 @@ pub async fn create_agent_internal(
     // Release the config write mutex — remaining work doesn't touch config.toml.
     drop(_config_guard);
 
-    // Read defaults directly from the config we just wrote to disk rather than
-    // relying on the cached `defaults_config` which may be stale (e.g. if a
-    // provider was configured but the in-memory cache wasn't refreshed yet).
-    let disk_defaults = match crate::config::Config::load_from_path(&config_path) {
-        Ok(fresh_config) => {
-            // Also update the in-memory cache so subsequent operations
-            // (e.g. creating another agent) don't hit stale defaults.
-            state
-                .set_defaults_config(fresh_config.defaults.clone())
-                .await;
-            Some(fresh_config.defaults)
-        }
-        Err(error) => {
-            tracing::warn!(
-                %error,
-                "failed to reload config.toml for defaults; falling back to cached defaults"
-            );
-            None
-        }
-    };
-    let cached_defaults;
-    let defaults = if let Some(ref d) = disk_defaults {
-        d
-    } else {
-        cached_defaults = state.defaults_config.read().await;
-        cached_defaults.as_ref().ok_or_else(|| {
-            tracing::error!("defaults config not available");
-            "defaults config not available".to_string()
-        })?
-    };
+    let defaults = current_defaults(state, &config_path).await?;
 
     let raw_config = crate::config::AgentConfig {
         id: agent_id.clone(),
@@ -615,17 +847,79 @@ pub async fn create_agent_internal(
         ingestion: None,
         cortex: None,
         warmup: None,
+        quotas: None,
         browser: None,
         channel: None,
         mcp: None,
+        issue_trackers: None,
+        weather: None,
+        translation: None,
+        embedding: None,
+        runtime: None,
+        calculator: None,
+        broadcast: None,
         brave_search_key: None,
         cron_timezone: None,
         user_timezone: None,
         sandbox: None,
         projects: None,
         cron: Vec::new(),
+        memory_scopes: crate::memory::scope::MemoryScopes::default(),
+        workspace_git: false,
     };
-    let agent_config = raw_config.resolve(&instance_dir, defaults);
+    start_agent(state, &raw_config, &defaults, &instance_dir).await?;
+
+    tracing::info!(agent_id = %agent_id, "agent created and initialized via API");
+
+    Ok(CreateAgentResult {
+        success: true,
+        agent_id: agent_id.clone(),
+        message: format!("Agent '{agent_id}' created and running"),
+    })
+}
+
+/// Defaults from the config file on disk, falling back to the cached copy if
+/// it can't be read. A successful read refreshes the cache.
+async fn current_defaults(
+    state: &ApiState,
+    config_path: &std::path::Path,
+) -> Result<crate::config::DefaultsConfig, String> {
+    // Read defaults directly from the config on disk rather than relying on
+    // the cached `defaults_config`, which may be stale (e.g. if a provider was
+    // configured but the in-memory cache wasn't refreshed yet).
+    match crate::config::Config::load_from_path(config_path) {
+        Ok(fresh_config) => {
+            // Also update the in-memory cache so subsequent operations
+            // (e.g. creating another agent) don't hit stale defaults.
+            state
+                .set_defaults_config(fresh_config.defaults.clone())
+                .await;
+            Ok(fresh_config.defaults)
+        }
+        Err(error) => {
+            tracing::warn!(
+                %error,
+                "failed to reload config.toml for defaults; falling back to cached defaults"
+            );
+            state.defaults_config.read().await.clone().ok_or_else(|| {
+                tracing::error!("defaults config not available");
+                "defaults config not available".to_string()
+            })
+        }
+    }
+}
+
+/// Bring up an agent's runtime from its config (directories, databases,
+/// memory, identity, cron, cortex), hand it to the main loop, and register it
+/// in the API state. Used for new agents and restarts.
+async fn start_agent(
+    state: &Arc<ApiState>,
+    raw_config: &crate::config::AgentConfig,
+    defaults: &crate::config::DefaultsConfig,
+    instance_dir: &std::path::Path,
+) -> Result<(), String> {
+    let agent_id = raw_config.id.clone();
+    let agent_config = raw_config.resolve(instance_dir, defaults);
 
     for dir in [
         &agent_config.workspace,
@@ -640,12 +934,15 @@ pub async fn create_agent_internal(
         })?;
     }
 
-    let db = crate::db::Db::connect(&agent_config.data_dir)
-        .await
-        .map_err(|error| {
-            tracing::error!(%error, agent_id = %agent_id, "failed to connect agent databases");
-            format!("failed to connect databases: {error}")
-        })?;
+    let db = crate::db::Db::connect_with_pool_size(
+        &agent_config.data_dir,
+        agent_config.quotas.max_db_connections,
+    )
+    .await
+    .map_err(|error| {
+        tracing::error!(%error, agent_id = %agent_id, "failed to connect agent databases");
+        format!("failed to connect databases: {error}")
+    })?;
 
     let settings_path = agent_config.data_dir.join("settings.redb");
     let settings_store = std::sync::Arc::new(
@@ -666,13 +963,46 @@ pub async fn create_agent_internal(
             .clone()
     };
 
+    let llm_manager = {
+        let guard = state.llm_manager.read().await;
+        guard
+            .as_ref()
+            .ok_or_else(|| {
+                tracing::error!("LLM manager not available");
+                "LLM manager not available".to_string()
+            })?
+            .clone()
+    };
+
+    let embedding_model = crate::memory::EmbeddingModel::for_config(
+        &agent_config.embedding,
+        &embedding_model,
+        llm_manager.http_client(),
+    )
+    .map_err(|error| {
+        tracing::error!(%error, agent_id = %agent_id, "failed to init embedding model");
+        format!("failed to init embedding model: {error}")
+    })?;
+
     let memory_store = crate::memory::MemoryStore::new(db.sqlite.clone());
-    let embedding_table = crate::memory::EmbeddingTable::open_or_create(&db.lance)
-        .await
-        .map_err(|error| {
-            tracing::error!(%error, agent_id = %agent_id, "failed to init embeddings");
-            format!("failed to init embeddings: {error}")
-        })?;
+    let embedding_table =
+        crate::memory::EmbeddingTable::open_or_create(&db.lance, embedding_model.dimensions())
+            .await
+            .map_err(|error| {
+                tracing::error!(%error, agent_id = %agent_id, "failed to init embeddings");
+                format!("failed to init embeddings: {error}")
+            })?;
+    crate::memory::embedding::check_metadata(
+        &db.sqlite,
+        &agent_id,
+        &embedding_model,
+        &embedding_table,
+    )
+    .await
+    .map_err(|error| {
+        tracing::error!(%error, agent_id = %agent_id, "embedding metadata check failed");
+        error.to_string()
+    })?;
 
     if let Err(error) = embedding_table.ensure_fts_index().await {
         tracing::warn!(%error, agent_id = %agent_id, "failed to create FTS index");
@@ -711,40 +1041,28 @@ pub async fn create_agent_internal(
             .clone()
     };
 
-    let defaults_for_runtime = if let Some(d) = disk_defaults {
-        d
-    } else {
-        let guard = state.defaults_config.read().await;
-        guard
-            .as_ref()
-            .ok_or_else(|| {
-                tracing::error!("defaults config not available");
-                "defaults config not available".to_string()
-            })?
-            .clone()
-    };
-
     let runtime_config = std::sync::Arc::new(crate::config::RuntimeConfig::new(
-        &instance_dir,
+        instance_dir,
         &agent_config,
-        &defaults_for_runtime,
+        defaults,
         prompt_engine,
         identity,
         skills,
     ));
     let explicit_listen_only = raw_config.channel.map(|channel| channel.listen_only_mode);
     runtime_config.set_settings(settings_store.clone(), explicit_listen_only);
+    let secrets_store = (**state.secrets_store.load()).clone();
+    if let Some(secrets_store) = &secrets_store {
+        runtime_config.set_secrets(secrets_store.clone());
+    }
 
-    let llm_manager = {
-        let guard = state.llm_manager.read().await;
-        guard
-            .as_ref()
-            .ok_or_else(|| {
-                tracing::error!("LLM manager not available");
-                "LLM manager not available".to_string()
-            })?
-            .clone()
-    };
+    llm_manager.register_usage_pool(&agent_id, db.sqlite.clone());
+    let quota = crate::quota::AgentQuota::new(
+        arc_agent_id.clone(),
+        &agent_config.quotas,
+        db.sqlite.clone(),
+    );
+    llm_manager.register_quota(&agent_id, quota.clone());
 
     let mcp_manager = std::sync::Arc::new(crate::mcp::McpManager::new(agent_config.mcp.clone()));
     mcp_manager.connect_all().await;
@@ -758,6 +1076,9 @@ pub async fn create_agent_internal(
         )
         .await,
     );
+    if let Some(secrets_store) = secrets_store {
+        sandbox.set_secrets_store(secrets_store);
+    }
 
     let project_store = std::sync::Arc::new(crate::projects::ProjectStore::new(db.sqlite.clone()));
 
@@ -801,7 +1122,7 @@ pub async fn create_agent_internal(
                 })
                 .collect();
             names.entry(agent_id.clone()).or_insert_with(|| {
-                request
+                raw_config
                     .display_name
                     .clone()
                     .filter(|s| !s.is_empty())
@@ -812,7 +1133,16 @@ pub async fn create_agent_internal(
         humans: Arc::new(arc_swap::ArcSwap::from_pointee(
             (**state.agent_humans.load()).clone(),
         )),
+        leader: crate::leader::LeaderLease::from_config(&db.sqlite, &state.leader_election),
+        quota,
+        runtime: crate::agent::runtime::AgentRuntime::start(&agent_id, &agent_config.runtime)
+            .map_err(|error| {
+                tracing::error!(%error, agent_id = %agent_id, "failed to start agent runtime");
+                format!("failed to start agent runtime: {error}")
+            })?,
     };
+    deps.leader.spawn_renewal();
+    deps.quota.spawn_db_sampler();
 
     let event_rx = event_tx.subscribe();
     state.register_agent_events(agent_id.clone(), event_rx);
@@ -833,6 +1163,21 @@ pub async fn create_agent_internal(
     };
     let scheduler = std::sync::Arc::new(crate::cron::Scheduler::new(cron_context));
     runtime_config.set_cron(cron_store.clone(), scheduler.clone());
+    // A restarted agent already has jobs stored; a new one has none.
+    match cron_store.load_all().await {
+        Ok(cron_configs) => {
+            let last_times = cron_store.last_execution_times().await.unwrap_or_default();
+            for cron_config in cron_configs {
+                let anchor = last_times.get(&cron_config.id).map(String::as_str);
+                if let Err(error) = scheduler.register_with_anchor(cron_config, anchor).await {
+                    tracing::warn!(%error, agent_id = %agent_id, "failed to register cron job");
+                }
+            }
+        }
+        Err(error) => {
+            tracing::warn!(%error, agent_id = %agent_id, "failed to load cron jobs");
+        }
+    }
 
     let cron_tool = crate::tools::CronTool::new(cron_store.clone(), scheduler.clone());
 
@@ -879,18 +1224,38 @@ pub async fn create_agent_internal(
     .with_factory(true);
 
     let cortex_logger = crate::agent::cortex::CortexLogger::new(db.sqlite.clone());
-    let _warmup_loop = crate::agent::cortex::spawn_warmup_loop(deps.clone(), cortex_logger.clone());
-    let _cortex_loop = crate::agent::cortex::spawn_cortex_loop(deps.clone(), cortex_logger.clone());
-    let _association_loop =
-        crate::agent::cortex::spawn_association_loop(deps.clone(), cortex_logger);
-    crate::agent::cortex::spawn_ready_task_loop(
-        deps.clone(),
-        crate::agent::cortex::CortexLogger::new(db.sqlite.clone()),
-    );
+    let background_tasks = {
+        let _runtime = deps.enter_runtime();
+        let mut background_tasks = vec![
+            crate::agent::cortex::spawn_warmup_loop(deps.clone(), cortex_logger.clone()),
+            crate::agent::cortex::spawn_cortex_loop(deps.clone(), cortex_logger.clone()),
+            crate::agent::cortex::spawn_association_loop(deps.clone(), cortex_logger.clone()),
+            crate::agent::cortex::spawn_recalibration_loop(deps.clone(), cortex_logger.clone()),
+            crate::agent::cortex::spawn_rescore_loop(deps.clone(), cortex_logger.clone()),
+            crate::agent::cortex::spawn_consolidation_loop(deps.clone(), cortex_logger),
+            crate::agent::cortex::spawn_ready_task_loop(
+                deps.clone(),
+                crate::agent::cortex::CortexLogger::new(db.sqlite.clone()),
+            ),
+            crate::notifications::spawn_release_loop(deps.clone()),
+        ];
+
+        let ingestion_config = **runtime_config.ingestion.load();
+        if ingestion_config.enabled {
+            background_tasks.push(crate::agent::ingestion::spawn_ingestion_loop(
+                agent_config.ingest_dir(),
+                deps.clone(),
+            ));
+        }
+        background_tasks
+    };
+    state.track_agent_tasks(&agent_id, &background_tasks);
 
-    let ingestion_config = **runtime_config.ingestion.load();
-    if ingestion_config.enabled {
-        crate::agent::ingestion::spawn_ingestion_loop(agent_config.ingest_dir(), deps.clone());
+    if let Err(error) = crate::agent::backfill::resume_running_jobs(&deps).await {
+        tracing::warn!(%error, agent_id = %agent_id, "failed to resume memory backfills");
+    }
+    if let Err(error) = crate::agent::batch::resume_running_jobs(&deps).await {
+        tracing::warn!(%error, agent_id = %agent_id, "failed to resume batch jobs");
     }
 
     let sqlite_pool = db.sqlite.clone();
@@ -953,6 +1318,16 @@ pub async fn create_agent_internal(
         sandboxes.insert(agent_id.clone(), sandbox);
         state.sandboxes.store(std::sync::Arc::new(sandboxes));
 
+        let mut leader_leases = (**state.leader_leases.load()).clone();
+        leader_leases.insert(agent_id.clone(), deps.leader.clone());
+        state
+            .leader_leases
+            .store(std::sync::Arc::new(leader_leases));
+
+        let mut agent_quotas = (**state.agent_quotas.load()).clone();
+        agent_quotas.insert(agent_id.clone(), deps.quota.clone());
+        state.agent_quotas.store(std::sync::Arc::new(agent_quotas));
+
         let mut project_stores_map = (**state.project_stores.load()).clone();
         project_stores_map.insert(agent_id.clone(), project_store);
         state
@@ -960,7 +1335,7 @@ pub async fn create_agent_internal(
             .store(std::sync::Arc::new(project_stores_map));
 
         let mut agent_infos = (**state.agent_configs.load()).clone();
-        agent_infos.push(AgentInfo {
+        let info = AgentInfo {
             id: agent_config.id.clone(),
             display_name: agent_config.display_name.clone(),
             role: agent_config.role.clone(),
@@ -971,7 +1346,14 @@ pub async fn create_agent_internal(
             max_turns: agent_config.max_turns,
             max_concurrent_branches: agent_config.max_concurrent_branches,
             max_concurrent_workers: agent_config.max_concurrent_workers,
-        });
+        };
+        match agent_infos
+            .iter_mut()
+            .find(|existing| existing.id == info.id)
+        {
+            Some(existing) => *existing = info,
+            None => agent_infos.push(info),
+        }
         state.agent_configs.store(std::sync::Arc::new(agent_infos));
 
         let mut cron_stores = (**state.cron_stores.load()).clone();
@@ -993,13 +1375,7 @@ pub async fn create_agent_internal(
             .store(std::sync::Arc::new(sessions));
     }
 
-    tracing::info!(agent_id = %agent_id, "agent created and initialized via API");
-
-    Ok(CreateAgentResult {
-        success: true,
-        agent_id: agent_id.clone(),
-        message: format!("Agent '{agent_id}' created and running"),
-    })
+    Ok(())
 }
 
 /// Update an agent's display_name and role in config.toml.
@@ -1128,6 +1504,13 @@ pub(super) async fn update_agent(
 }
 
 /// Delete an agent: remove from config.toml, clean up API state, signal main loop.
+#[utoipa::path(
+    delete,
+    path = "/api/agents",
+    tag = "agents",
+    params(DeleteAgentQuery),
+    responses((status = 200, description = "The body carries `success` and `message`"))
+)]
 pub(super) async fn delete_agent(
     State(state): State<Arc<ApiState>>,
     Query(query): Query<DeleteAgentQuery>,
@@ -1195,98 +1578,409 @@ pub(super) async fn delete_agent(
             })?;
     }
 
+    unregister_agent_state(&state, &agent_id).await;
+
+    // Signal the main event loop to remove the agent
+    if let Err(error) = state.agent_remove_tx.send(agent_id.clone()).await {
+        tracing::error!(%error, "failed to send agent removal to main loop");
+    }
+
+    tracing::info!(agent_id = %agent_id, "agent deleted via API");
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "message": format!("Agent '{agent_id}' deleted")
+    })))
+}
+
+/// Stop an agent's background loops, cron timers, MCP connections, and
+/// database pool, and drop it from every API state map.
+async fn unregister_agent_state(state: &ApiState, agent_id: &str) {
+    state.abort_agent_tasks(agent_id);
+    if let Some(scheduler) = state.cron_schedulers.load().get(agent_id) {
+        scheduler.shutdown().await;
+    }
+
     // Close the SQLite pool before removing state
     {
         let pools = state.agent_pools.load();
-        if let Some(pool) = pools.get(&agent_id) {
+        if let Some(pool) = pools.get(agent_id) {
             pool.close().await;
         }
     }
 
-    // Remove from all API state maps
-    {
-        let mut mcp_managers = (**state.mcp_managers.load()).clone();
-        if let Some(mcp_manager) = mcp_managers.remove(&agent_id) {
-            mcp_manager.disconnect_all().await;
-        }
-        state.mcp_managers.store(std::sync::Arc::new(mcp_managers));
+    let mut mcp_managers = (**state.mcp_managers.load()).clone();
+    if let Some(mcp_manager) = mcp_managers.remove(agent_id) {
+        mcp_manager.disconnect_all().await;
+    }
+    state.mcp_managers.store(std::sync::Arc::new(mcp_managers));
+
+    let mut pools = (**state.agent_pools.load()).clone();
+    pools.remove(agent_id);
+    state.agent_pools.store(std::sync::Arc::new(pools));
+
+    let mut searches = (**state.memory_searches.load()).clone();
+    searches.remove(agent_id);
+    state.memory_searches.store(std::sync::Arc::new(searches));
+
+    let mut workspaces = (**state.agent_workspaces.load()).clone();
+    workspaces.remove(agent_id);
+    state
+        .agent_workspaces
+        .store(std::sync::Arc::new(workspaces));
+
+    let mut identity_dirs = (**state.agent_identity_dirs.load()).clone();
+    identity_dirs.remove(agent_id);
+    state
+        .agent_identity_dirs
+        .store(std::sync::Arc::new(identity_dirs));
+
+    let mut data_dirs = (**state.agent_data_dirs.load()).clone();
+    data_dirs.remove(agent_id);
+    state.agent_data_dirs.store(std::sync::Arc::new(data_dirs));
+
+    let mut configs = (**state.runtime_configs.load()).clone();
+    configs.remove(agent_id);
+    state.runtime_configs.store(std::sync::Arc::new(configs));
+
+    let mut sandboxes = (**state.sandboxes.load()).clone();
+    sandboxes.remove(agent_id);
+    state.sandboxes.store(std::sync::Arc::new(sandboxes));
+
+    let mut leader_leases = (**state.leader_leases.load()).clone();
+    if let Some(lease) = leader_leases.remove(agent_id) {
+        lease.release().await;
+    }
+    state
+        .leader_leases
+        .store(std::sync::Arc::new(leader_leases));
+
+    let mut agent_quotas = (**state.agent_quotas.load()).clone();
+    agent_quotas.remove(agent_id);
+    state.agent_quotas.store(std::sync::Arc::new(agent_quotas));
+
+    let mut agent_infos = (**state.agent_configs.load()).clone();
+    agent_infos.retain(|a| a.id != agent_id);
+    state.agent_configs.store(std::sync::Arc::new(agent_infos));
+
+    let mut cron_stores = (**state.cron_stores.load()).clone();
+    cron_stores.remove(agent_id);
+    state.cron_stores.store(std::sync::Arc::new(cron_stores));
+
+    let mut cron_schedulers = (**state.cron_schedulers.load()).clone();
+    cron_schedulers.remove(agent_id);
+    state
+        .cron_schedulers
+        .store(std::sync::Arc::new(cron_schedulers));
+
+    let mut sessions = (**state.cortex_chat_sessions.load()).clone();
+    sessions.remove(agent_id);
+    state
+        .cortex_chat_sessions
+        .store(std::sync::Arc::new(sessions));
+
+    let mut project_stores_map = (**state.project_stores.load()).clone();
+    project_stores_map.remove(agent_id);
+    state
+        .project_stores
+        .store(std::sync::Arc::new(project_stores_map));
+}
 
-        let mut pools = (**state.agent_pools.load()).clone();
-        pools.remove(&agent_id);
-        state.agent_pools.store(std::sync::Arc::new(pools));
+#[derive(Deserialize, Default, utoipa::ToSchema)]
+pub(super) struct RestartAgentRequest {
+    /// Seconds to wait for in-flight workers and branches before cancelling
+    /// them.
+    #[serde(default)]
+    drain_timeout_secs: Option<u64>,
+}
 
-        let mut searches = (**state.memory_searches.load()).clone();
-        searches.remove(&agent_id);
-        state.memory_searches.store(std::sync::Arc::new(searches));
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct RestartAgentResponse {
+    agent_id: String,
+    /// Whether all in-flight work finished within the timeout.
+    drained: bool,
+    cancelled_workers: usize,
+    cancelled_branches: usize,
+}
 
-        let mut workspaces = (**state.agent_workspaces.load()).clone();
-        workspaces.remove(&agent_id);
-        state
-            .agent_workspaces
-            .store(std::sync::Arc::new(workspaces));
+const DEFAULT_RESTART_DRAIN_SECS: u64 = 30;
 
-        let mut identity_dirs = (**state.agent_identity_dirs.load()).clone();
-        identity_dirs.remove(&agent_id);
-        state
-            .agent_identity_dirs
-            .store(std::sync::Arc::new(identity_dirs));
+/// Marks an agent as restarting until dropped.
+struct RestartGuard<'a> {
+    state: &'a ApiState,
+    agent_id: String,
+}
 
-        let mut data_dirs = (**state.agent_data_dirs.load()).clone();
-        data_dirs.remove(&agent_id);
-        state.agent_data_dirs.store(std::sync::Arc::new(data_dirs));
+impl<'a> RestartGuard<'a> {
+    /// `None` if the agent is already restarting.
+    fn acquire(state: &'a ApiState, agent_id: &str) -> Option<Self> {
+        let inserted = state
+            .restarting_agents
+            .lock()
+            .expect("restart registry poisoned")
+            .insert(agent_id.to_string());
+        inserted.then(|| Self {
+            state,
+            agent_id: agent_id.to_string(),
+        })
+    }
+}
+
+impl Drop for RestartGuard<'_> {
+    fn drop(&mut self) {
+        self.state
+            .restarting_agents
+            .lock()
+            .expect("restart registry poisoned")
+            .remove(&self.agent_id);
+    }
+}
 
+/// Put an agent whose restart failed back in the API state, so it can still
+/// be found and the restart retried. Its old runtime config stays paused, so
+/// the main loop keeps holding its messages until a restart succeeds.
+fn restore_failed_restart(
+    state: &ApiState,
+    agent_id: &str,
+    runtime_config: Option<Arc<crate::config::RuntimeConfig>>,
+    info: Option<AgentInfo>,
+) {
+    if let Some(runtime_config) = runtime_config {
+        runtime_config.paused.store(Arc::new(true));
         let mut configs = (**state.runtime_configs.load()).clone();
-        configs.remove(&agent_id);
-        state.runtime_configs.store(std::sync::Arc::new(configs));
+        configs.insert(agent_id.to_string(), runtime_config);
+        state.runtime_configs.store(Arc::new(configs));
+    }
+    if let Some(info) = info {
+        let mut agent_infos = (**state.agent_configs.load()).clone();
+        if agent_infos.iter().all(|agent| agent.id != agent_id) {
+            agent_infos.push(info);
+            state.agent_configs.store(Arc::new(agent_infos));
+        }
+    }
+}
 
-        let mut sandboxes = (**state.sandboxes.load()).clone();
-        sandboxes.remove(&agent_id);
-        state.sandboxes.store(std::sync::Arc::new(sandboxes));
+/// Tear down an agent's runtime and start it again from the current
+/// config.toml. New turns stop right away; in-flight workers and branches get
+/// the drain timeout to finish before they are cancelled. Messages that arrive
+/// during the restart are held and delivered once the agent is back.
+#[utoipa::path(
+    post,
+    path = "/api/agents/{id}/restart",
+    tag = "agents",
+    params(("id" = String, Path, description = "Agent ID")),
+    request_body(content = RestartAgentRequest, description = "Optional drain timeout"),
+    responses(
+        (status = 200, body = RestartAgentResponse),
+        (status = 404, description = "Unknown agent"),
+        (status = 409, description = "Already restarting, or no longer in config.toml"),
+        (status = 500, description = "Restart failed; the agent stays paused until one succeeds"),
+    )
+)]
+pub(super) async fn restart_agent(
+    State(state): State<Arc<ApiState>>,
+    AgentPath(agent_id): AgentPath<String>,
+    request: Option<Json<RestartAgentRequest>>,
+) -> Result<Json<RestartAgentResponse>, (StatusCode, String)> {
+    let request = request.map(|Json(request)| request).unwrap_or_default();
+    let previously_paused = {
+        let runtime_configs = state.runtime_configs.load();
+        let runtime_config = runtime_configs.get(&agent_id).ok_or((
+            StatusCode::NOT_FOUND,
+            format!("agent '{agent_id}' not found"),
+        ))?;
+        runtime_config.is_paused()
+    };
+    let Some(_restart_guard) = RestartGuard::acquire(&state, &agent_id) else {
+        return Err((
+            StatusCode::CONFLICT,
+            format!("agent '{agent_id}' is already restarting"),
+        ));
+    };
 
-        let mut agent_infos = (**state.agent_configs.load()).clone();
-        agent_infos.retain(|a| a.id != agent_id);
-        state.agent_configs.store(std::sync::Arc::new(agent_infos));
+    let config_path = state.config_path.read().await.clone();
+    let config = crate::config::Config::load_from_path(&config_path).map_err(|error| {
+        tracing::warn!(%error, agent_id = %agent_id, "failed to load config.toml for restart");
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("failed to load config: {error}"),
+        )
+    })?;
+    let raw_config = config
+        .agents
+        .iter()
+        .find(|agent| agent.id == agent_id)
+        .cloned()
+        .ok_or((
+            StatusCode::CONFLICT,
+            format!("agent '{agent_id}' is no longer in config.toml"),
+        ))?;
+    state.set_defaults_config(config.defaults.clone()).await;
+
+    // Pause so no new turns start while the current ones drain. Inbound
+    // messages are held by the main loop like any other pause.
+    if let Some(runtime_config) = state.runtime_configs.load().get(&agent_id) {
+        runtime_config.paused.store(Arc::new(true));
+    }
+    tracing::info!(agent_id = %agent_id, "restarting agent via API");
 
-        let mut cron_stores = (**state.cron_stores.load()).clone();
-        cron_stores.remove(&agent_id);
-        state.cron_stores.store(std::sync::Arc::new(cron_stores));
+    let drain_timeout = std::time::Duration::from_secs(
+        request
+            .drain_timeout_secs
+            .unwrap_or(DEFAULT_RESTART_DRAIN_SECS),
+    );
+    let (drained, cancelled_workers, cancelled_branches) =
+        drain_agent_channels(&state, &agent_id, drain_timeout).await;
 
-        let mut cron_schedulers = (**state.cron_schedulers.load()).clone();
-        cron_schedulers.remove(&agent_id);
-        state
-            .cron_schedulers
-            .store(std::sync::Arc::new(cron_schedulers));
+    let old_runtime_config = state.runtime_configs.load().get(&agent_id).cloned();
+    let old_info = state
+        .agent_configs
+        .load()
+        .iter()
+        .find(|agent| agent.id == agent_id)
+        .cloned();
+    unregister_agent_state(&state, &agent_id).await;
 
-        let mut sessions = (**state.cortex_chat_sessions.load()).clone();
-        sessions.remove(&agent_id);
-        state
-            .cortex_chat_sessions
-            .store(std::sync::Arc::new(sessions));
+    let instance_dir = (**state.instance_dir.load()).clone();
+    if let Err(error) = start_agent(&state, &raw_config, &config.defaults, &instance_dir).await {
+        tracing::error!(%error, agent_id = %agent_id, "failed to restart agent");
+        restore_failed_restart(&state, &agent_id, old_runtime_config, old_info);
+        return Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!(
+                "failed to restart agent: {error}. It stays paused, holding its messages, \
+                 until a restart succeeds"
+            ),
+        ));
+    }
 
-        let mut project_stores_map = (**state.project_stores.load()).clone();
-        project_stores_map.remove(&agent_id);
-        state
-            .project_stores
-            .store(std::sync::Arc::new(project_stores_map));
+    if previously_paused && let Some(runtime_config) = state.runtime_configs.load().get(&agent_id) {
+        runtime_config.paused.store(Arc::new(true));
     }
+    state.agent_resumed.notify_one();
+
+    tracing::info!(
+        agent_id = %agent_id,
+        drained,
+        cancelled_workers,
+        cancelled_branches,
+        "agent restarted via API"
+    );
+    Ok(Json(RestartAgentResponse {
+        agent_id,
+        drained,
+        cancelled_workers,
+        cancelled_branches,
+    }))
+}
 
-    // Signal the main event loop to remove the agent
-    if let Err(error) = state.agent_remove_tx.send(agent_id.clone()).await {
-        tracing::error!(%error, "failed to send agent removal to main loop");
+/// Wait for an agent's in-flight workers and branches, then cancel whatever
+/// is still running. Returns whether everything finished on its own and how
+/// many workers and branches were cancelled.
+async fn drain_agent_channels(
+    state: &ApiState,
+    agent_id: &str,
+    timeout: std::time::Duration,
+) -> (bool, usize, usize) {
+    let channels: Vec<crate::agent::channel::ChannelState> = state
+        .channel_states
+        .read()
+        .await
+        .values()
+        .filter(|channel| &*channel.deps.agent_id == agent_id)
+        .cloned()
+        .collect();
+
+    let deadline = tokio::time::Instant::now() + timeout;
+    loop {
+        let mut busy = false;
+        for channel in &channels {
+            if !channel.worker_handles.read().await.is_empty()
+                || !channel.active_branches.read().await.is_empty()
+            {
+                busy = true;
+                break;
+            }
+        }
+        if !busy {
+            return (true, 0, 0);
+        }
+        if tokio::time::Instant::now() >= deadline {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(250)).await;
     }
 
-    tracing::info!(agent_id = %agent_id, "agent deleted via API");
+    let mut cancelled_workers = 0;
+    let mut cancelled_branches = 0;
+    for channel in &channels {
+        let worker_ids: Vec<_> = channel
+            .worker_handles
+            .read()
+            .await
+            .keys()
+            .copied()
+            .collect();
+        for worker_id in worker_ids {
+            if channel
+                .cancel_worker_with_reason(worker_id, "agent restarting")
+                .await
+                .is_ok()
+            {
+                cancelled_workers += 1;
+            }
+        }
+        let branch_ids: Vec<_> = channel
+            .active_branches
+            .read()
+            .await
+            .keys()
+            .copied()
+            .collect();
+        for branch_id in branch_ids {
+            if channel
+                .cancel_branch_with_reason(branch_id, "agent restarting")
+                .await
+                .is_ok()
+            {
+                cancelled_branches += 1;
+            }
+        }
+    }
+    (false, cancelled_workers, cancelled_branches)
+}
 
-    Ok(Json(serde_json::json!({
-        "success": true,
-        "message": format!("Agent '{agent_id}' deleted")
-    })))
+/// Delete an agent addressed by path. Same as [`delete_agent`], but unknown
+/// agents are a 404 rather than an unsuccessful response body.
+#[utoipa::path(
+    delete,
+    path = "/api/agents/{id}",
+    tag = "agents",
+    params(("id" = String, Path, description = "Agent ID")),
+    responses(
+        (status = 200, description = "The body carries `success` and `message`"),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
+pub(super) async fn delete_agent_by_id(
+    State(state): State<Arc<ApiState>>,
+    Path(agent_id): Path<String>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let known = state
+        .agent_configs
+        .load()
+        .iter()
+        .any(|agent| agent.id == agent_id.trim());
+    if !known {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    delete_agent(State(state), Query(DeleteAgentQuery { agent_id })).await
 }
 
 /// Get overview stats for an agent: memory breakdown, channels, cron, cortex.
 pub(super) async fn agent_overview(
     State(state): State<Arc<ApiState>>,
-    Query(query): Query<AgentOverviewQuery>,
+    AgentQuery(query): AgentQuery<AgentOverviewQuery>,
 ) -> Result<Json<AgentOverviewResponse>, StatusCode> {
     let pools = state.agent_pools.load();
     let pool = pools.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
@@ -1315,7 +2009,7 @@ pub(super) async fn agent_overview(
     let channel_count = channels.len();
 
     let cron_rows = sqlx::query(
-        "SELECT id, prompt, cron_expr, interval_secs, delivery_target, active_start_hour, active_end_hour, enabled, run_once, timeout_secs FROM cron_jobs ORDER BY created_at ASC",
+        "SELECT id, prompt, cron_expr, interval_secs, delivery_target, active_start_hour, active_end_hour, enabled, run_once, timeout_secs, timezone FROM cron_jobs ORDER BY created_at ASC",
     )
     .fetch_all(pool)
     .await
@@ -1343,6 +2037,7 @@ pub(super) async fn agent_overview(
                     .ok()
                     .flatten()
                     .map(|t| t as u64),
+                timezone: row.try_get::<Option<String>, _>("timezone").ok().flatten(),
             }
         })
         .collect();
@@ -1552,7 +2247,7 @@ pub(super) async fn instance_overview(
 /// Get the cortex-generated profile for an agent.
 pub(super) async fn get_agent_profile(
     State(state): State<Arc<ApiState>>,
-    Query(query): Query<AgentOverviewQuery>,
+    AgentQuery(query): AgentQuery<AgentOverviewQuery>,
 ) -> Result<Json<AgentProfileResponse>, StatusCode> {
     let pools = state.agent_pools.load();
     let pool = pools.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
@@ -1563,9 +2258,19 @@ pub(super) async fn get_agent_profile(
 }
 
 /// Get identity files (SOUL.md, IDENTITY.md, ROLE.md) for an agent.
+#[utoipa::path(
+    get,
+    path = "/api/agents/identity",
+    tag = "agents",
+    params(IdentityQuery),
+    responses(
+        (status = 200, body = IdentityResponse),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
 pub(super) async fn get_identity(
     State(state): State<Arc<ApiState>>,
-    Query(query): Query<IdentityQuery>,
+    AgentQuery(query): AgentQuery<IdentityQuery>,
 ) -> Result<Json<IdentityResponse>, StatusCode> {
     let identity_dirs = state.agent_identity_dirs.load();
     let identity_dir = identity_dirs
@@ -1583,9 +2288,19 @@ pub(super) async fn get_identity(
 
 /// Update identity files for an agent. Only writes files for fields that are present.
 /// The file watcher will pick up changes and hot-reload identity into RuntimeConfig.
+#[utoipa::path(
+    put,
+    path = "/api/agents/identity",
+    tag = "agents",
+    request_body = IdentityUpdateRequest,
+    responses(
+        (status = 200, body = IdentityResponse),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
 pub(super) async fn update_identity(
     State(state): State<Arc<ApiState>>,
-    axum::Json(request): axum::Json<IdentityUpdateRequest>,
+    AgentJson(request): AgentJson<IdentityUpdateRequest>,
 ) -> Result<Json<IdentityResponse>, StatusCode> {
     let identity_dirs = state.agent_identity_dirs.load();
     let identity_dir = identity_dirs
@@ -1619,6 +2334,23 @@ pub(super) async fn update_identity(
             })?;
     }
 
+    let changed: Vec<&str> = [
+        (request.soul.is_some(), "SOUL.md"),
+        (request.identity.is_some(), "IDENTITY.md"),
+        (request.role.is_some(), "ROLE.md"),
+    ]
+    .into_iter()
+    .filter_map(|(present, file)| present.then_some(file))
+    .collect();
+    let runtime_config = state.runtime_configs.load().get(&request.agent_id).cloned();
+    if !changed.is_empty()
+        && let Some(runtime_config) = runtime_config
+    {
+        runtime_config
+            .record_workspace_change("api", &format!("Update {}", changed.join(", ")))
+            .await;
+    }
+
     let updated = crate::identity::Identity::load(identity_dir).await;
 
     Ok(Json(IdentityResponse {
@@ -1631,15 +2363,17 @@ pub(super) async fn update_identity(
 #[cfg(test)]
 mod tests {
     use super::{
-        ApiState, WarmupQuery, WarmupTriggerRequest, compute_bulletin_age_secs, get_warmup_status,
-        resolve_warmup_agent_ids, trigger_warmup,
+        ApiState, RestartGuard, WarmupQuery, WarmupTriggerRequest, compute_bulletin_age_secs,
+        get_warmup_status, pause_agent, resolve_warmup_agent_ids, restart_agent,
+        restore_failed_restart, resume_agent, trigger_warmup, unregister_agent_state,
     };
+    use crate::api::access::{AgentJson, AgentPath, AgentQuery};
+    use crate::api::state::AgentInfo;
     use crate::config::{Config, RuntimeConfig, WarmupState, WarmupStatus};
     use crate::identity::Identity;
     use crate::prompts::PromptEngine;
     use crate::skills::SkillSet;
-    use axum::Json;
-    use axum::extract::{Query, State};
+    use axum::extract::State;
     use axum::http::StatusCode;
     use std::collections::{HashMap, HashSet};
     use std::sync::Arc;
@@ -1719,7 +2453,7 @@ mod tests {
         runtime_configs.insert("alpha".to_string(), alpha_config);
         state.runtime_configs.store(Arc::new(runtime_configs));
 
-        let response = get_warmup_status(State(state), Query(WarmupQuery { agent_id: None }))
+        let response = get_warmup_status(State(state), AgentQuery(WarmupQuery { agent_id: None }))
             .await
             .expect("warmup status request failed")
             .0;
@@ -1737,13 +2471,166 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_pause_and_resume_toggle_the_agent() {
+        let state = test_api_state();
+        let tempdir = tempfile::tempdir().expect("failed to create tempdir");
+        let runtime_config = test_runtime_config(tempdir.path());
+        state.runtime_configs.store(Arc::new(HashMap::from([(
+            "alpha".to_string(),
+            runtime_config.clone(),
+        )])));
+
+        for _ in 0..2 {
+            let response = pause_agent(State(state.clone()), AgentPath("alpha".to_string()))
+                .await
+                .expect("pause request failed")
+                .0;
+            assert!(response.paused);
+            assert!(runtime_config.is_paused());
+        }
+
+        let response = resume_agent(State(state.clone()), AgentPath("alpha".to_string()))
+            .await
+            .expect("resume request failed")
+            .0;
+        assert!(!response.paused);
+        assert!(!runtime_config.is_paused());
+
+        let result = pause_agent(State(state), AgentPath("missing".to_string())).await;
+        assert_eq!(result.err(), Some(StatusCode::NOT_FOUND));
+    }
+
+    #[tokio::test]
+    async fn test_pause_survives_a_restart() {
+        let state = test_api_state();
+        let tempdir = tempfile::tempdir().expect("failed to create tempdir");
+        let settings_path = tempdir.path().join("settings.redb");
+        let runtime_config = test_runtime_config(tempdir.path());
+        let settings = Arc::new(
+            crate::settings::SettingsStore::new(&settings_path).expect("failed to open settings"),
+        );
+        runtime_config.set_settings(settings, None);
+        state.runtime_configs.store(Arc::new(HashMap::from([(
+            "alpha".to_string(),
+            runtime_config.clone(),
+        )])));
+
+        pause_agent(State(state.clone()), AgentPath("alpha".to_string()))
+            .await
+            .expect("pause request failed");
+        drop(runtime_config);
+        state.runtime_configs.store(Arc::new(HashMap::new()));
+
+        // A fresh runtime over the same settings store comes back paused.
+        let restarted = test_runtime_config(tempdir.path());
+        let settings = Arc::new(
+            crate::settings::SettingsStore::new(&settings_path).expect("failed to reopen settings"),
+        );
+        restarted.set_settings(settings, None);
+        assert!(restarted.is_paused());
+
+        state.runtime_configs.store(Arc::new(HashMap::from([(
+            "alpha".to_string(),
+            restarted.clone(),
+        )])));
+        resume_agent(State(state), AgentPath("alpha".to_string()))
+            .await
+            .expect("resume request failed");
+        let settings = restarted
+            .settings
+            .load()
+            .as_ref()
+            .clone()
+            .expect("settings");
+        assert!(!settings.agent_paused());
+    }
+
+    #[tokio::test]
+    async fn test_restart_is_refused_while_one_is_in_progress() {
+        let state = test_api_state();
+        let tempdir = tempfile::tempdir().expect("failed to create tempdir");
+        state.runtime_configs.store(Arc::new(HashMap::from([(
+            "alpha".to_string(),
+            test_runtime_config(tempdir.path()),
+        )])));
+
+        let guard = RestartGuard::acquire(&state, "alpha").expect("first restart");
+        assert!(RestartGuard::acquire(&state, "alpha").is_none());
+        let result =
+            restart_agent(State(state.clone()), AgentPath("alpha".to_string()), None).await;
+        assert_eq!(
+            result.err().map(|(status, _)| status),
+            Some(StatusCode::CONFLICT)
+        );
+
+        drop(guard);
+        assert!(RestartGuard::acquire(&state, "alpha").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_failed_restart_keeps_the_agent_paused_and_findable() {
+        let state = test_api_state();
+        let tempdir = tempfile::tempdir().expect("failed to create tempdir");
+        let runtime_config = test_runtime_config(tempdir.path());
+        state.runtime_configs.store(Arc::new(HashMap::from([(
+            "alpha".to_string(),
+            runtime_config.clone(),
+        )])));
+        let info = AgentInfo {
+            id: "alpha".into(),
+            display_name: None,
+            role: None,
+            gradient_start: None,
+            gradient_end: None,
+            workspace: Default::default(),
+            context_window: 0,
+            max_turns: 0,
+            max_concurrent_branches: 0,
+            max_concurrent_workers: 0,
+        };
+        state.agent_configs.store(Arc::new(vec![info.clone()]));
+
+        unregister_agent_state(&state, "alpha").await;
+        assert!(state.runtime_configs.load().get("alpha").is_none());
+
+        restore_failed_restart(&state, "alpha", Some(runtime_config), Some(info));
+        let runtime_configs = state.runtime_configs.load();
+        let restored = runtime_configs
+            .get("alpha")
+            .expect("runtime config restored");
+        assert!(restored.is_paused());
+        assert_eq!(state.agent_configs.load().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_wait_until_resumed_returns_on_resume() {
+        let tempdir = tempfile::tempdir().expect("failed to create tempdir");
+        let runtime_config = test_runtime_config(tempdir.path());
+        runtime_config.wait_until_resumed().await;
+
+        runtime_config.paused.store(Arc::new(true));
+        let waiter = tokio::spawn({
+            let runtime_config = runtime_config.clone();
+            async move { runtime_config.wait_until_resumed().await }
+        });
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        assert!(!waiter.is_finished());
+
+        runtime_config.paused.store(Arc::new(false));
+        tokio::time::timeout(std::time::Duration::from_secs(5), waiter)
+            .await
+            .expect("waiter did not finish after resume")
+            .expect("waiter panicked");
+    }
+
     #[tokio::test]
     async fn test_get_warmup_status_single_agent_not_found() {
         let state = test_api_state();
 
         let result = get_warmup_status(
             State(state),
-            Query(WarmupQuery {
+            AgentQuery(WarmupQuery {
                 agent_id: Some("missing".to_string()),
             }),
         )
@@ -1758,7 +2645,7 @@ mod tests {
 
         let result = trigger_warmup(
             State(state),
-            Json(WarmupTriggerRequest {
+            AgentJson(WarmupTriggerRequest {
                 agent_id: None,
                 force: false,
             }),
@@ -1782,7 +2669,7 @@ mod tests {
 
         let result = trigger_warmup(
             State(state),
-            Json(WarmupTriggerRequest {
+            AgentJson(WarmupTriggerRequest {
                 agent_id: Some("missing".to_string()),
                 force: false,
             }),
@@ -1819,10 +2706,16 @@ pub(super) struct AvatarQuery {
     agent_id: String,
 }
 
+impl AgentScoped for AvatarQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
 /// Serve the agent's avatar image.
 pub(super) async fn get_avatar(
     State(state): State<Arc<ApiState>>,
-    Query(query): Query<AvatarQuery>,
+    AgentQuery(query): AgentQuery<AvatarQuery>,
 ) -> Result<axum::response::Response, StatusCode> {
     let data_dir = state
         .agent_data_dirs
@@ -1858,7 +2751,7 @@ use axum::response::IntoResponse;
 /// Upload (or replace) the agent's avatar image.
 pub(super) async fn upload_avatar(
     State(state): State<Arc<ApiState>>,
-    Query(query): Query<AvatarQuery>,
+    AgentQuery(query): AgentQuery<AvatarQuery>,
     request: axum::extract::Request,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
     let data_dir = state
@@ -1915,7 +2808,7 @@ pub(super) async fn upload_avatar(
 /// Delete the agent's avatar image.
 pub(super) async fn delete_avatar(
     State(state): State<Arc<ApiState>>,
-    Query(query): Query<AvatarQuery>,
+    AgentQuery(query): AgentQuery<AvatarQuery>,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
     let data_dir = state
         .agent_data_dirs
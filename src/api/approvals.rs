@@ -0,0 +1,190 @@
+//! Approving or rejecting tool calls queued by agents (see
+//! [`crate::approvals`]).
+
+use super::access::{AgentJson, AgentQuery, AgentScoped};
+use super::state::ApiState;
+
+use crate::approvals::{ApprovalStatus, ApprovalStore, ToolApproval};
+
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Deserialize, utoipa::IntoParams)]
+pub(super) struct ApprovalListQuery {
+    agent_id: String,
+    /// `pending` (default), `rejected`, `executed`, `failed`, or `all`.
+    #[serde(default)]
+    status: Option<String>,
+    #[serde(default = "default_limit")]
+    limit: i64,
+}
+
+impl AgentScoped for ApprovalListQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+fn default_limit() -> i64 {
+    50
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
+pub(super) struct ApprovalDecisionRequest {
+    agent_id: String,
+    /// Who made the decision, recorded on the request.
+    #[serde(default)]
+    decided_by: Option<String>,
+}
+
+impl AgentScoped for ApprovalDecisionRequest {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct ApprovalListResponse {
+    approvals: Vec<ToolApproval>,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct ApprovalResponse {
+    approval: ToolApproval,
+}
+
+fn approval_store(state: &ApiState, agent_id: &str) -> Result<ApprovalStore, StatusCode> {
+    let pools = state.agent_pools.load();
+    let pool = pools.get(agent_id).ok_or(StatusCode::NOT_FOUND)?;
+    Ok(ApprovalStore::new(pool.clone()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/agents/approvals",
+    tag = "agents",
+    params(ApprovalListQuery),
+    responses(
+        (status = 200, description = "Tool calls awaiting or past approval", body = ApprovalListResponse),
+        (status = 400, description = "Unknown status filter"),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
+pub(super) async fn list_approvals(
+    State(state): State<Arc<ApiState>>,
+    AgentQuery(query): AgentQuery<ApprovalListQuery>,
+) -> Result<Json<ApprovalListResponse>, StatusCode> {
+    let store = approval_store(&state, &query.agent_id)?;
+    let status = match query.status.as_deref() {
+        None => Some(ApprovalStatus::Pending),
+        Some("all") => None,
+        Some(value) => Some(ApprovalStatus::parse(value).ok_or(StatusCode::BAD_REQUEST)?),
+    };
+
+    let approvals = store
+        .list(status, query.limit.clamp(1, 500))
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, agent_id = %query.agent_id, "failed to list tool approvals");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    Ok(Json(ApprovalListResponse { approvals }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/agents/approvals/{id}/approve",
+    tag = "agents",
+    params(("id" = String, Path, description = "Approval id")),
+    request_body = ApprovalDecisionRequest,
+    responses(
+        (status = 200, description = "Approved and executed; check `status` and `result`", body = ApprovalResponse),
+        (status = 404, description = "Unknown agent or approval"),
+        (status = 409, description = "Already decided"),
+    )
+)]
+pub(super) async fn approve(
+    State(state): State<Arc<ApiState>>,
+    Path(id): Path<String>,
+    AgentJson(request): AgentJson<ApprovalDecisionRequest>,
+) -> Result<Json<ApprovalResponse>, StatusCode> {
+    let store = approval_store(&state, &request.agent_id)?;
+    let runtime_config = state
+        .runtime_configs
+        .load()
+        .get(&request.agent_id)
+        .cloned()
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let approval = decide(&store, &id, ApprovalStatus::Approved, request.decided_by).await?;
+    let messaging_manager = state.messaging_manager.read().await.clone();
+    let outcome =
+        crate::approvals::execute(&approval, &runtime_config, messaging_manager.as_deref()).await;
+    if let Err(error) = &outcome {
+        tracing::warn!(%error, approval_id = %id, tool = %approval.tool_name, "approved tool call failed");
+    }
+    store.finish(&id, &outcome).await.map_err(|error| {
+        tracing::warn!(%error, approval_id = %id, "failed to record tool approval result");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let approval = load(&store, &id).await?;
+    Ok(Json(ApprovalResponse { approval }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/agents/approvals/{id}/reject",
+    tag = "agents",
+    params(("id" = String, Path, description = "Approval id")),
+    request_body = ApprovalDecisionRequest,
+    responses(
+        (status = 200, description = "Rejected", body = ApprovalResponse),
+        (status = 404, description = "Unknown agent or approval"),
+        (status = 409, description = "Already decided"),
+    )
+)]
+pub(super) async fn reject(
+    State(state): State<Arc<ApiState>>,
+    Path(id): Path<String>,
+    AgentJson(request): AgentJson<ApprovalDecisionRequest>,
+) -> Result<Json<ApprovalResponse>, StatusCode> {
+    let store = approval_store(&state, &request.agent_id)?;
+    let approval = decide(&store, &id, ApprovalStatus::Rejected, request.decided_by).await?;
+    Ok(Json(ApprovalResponse { approval }))
+}
+
+async fn decide(
+    store: &ApprovalStore,
+    id: &str,
+    to: ApprovalStatus,
+    decided_by: Option<String>,
+) -> Result<ToolApproval, StatusCode> {
+    let decided_by = decided_by.unwrap_or_else(|| "api".to_string());
+    match store.decide(id, to, &decided_by).await {
+        Ok(Some(approval)) => Ok(approval),
+        // Distinguish "never existed" from "someone got there first".
+        Ok(None) => match load(store, id).await {
+            Ok(_) => Err(StatusCode::CONFLICT),
+            Err(status) => Err(status),
+        },
+        Err(error) => {
+            tracing::warn!(%error, approval_id = %id, "failed to decide tool approval");
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+async fn load(store: &ApprovalStore, id: &str) -> Result<ToolApproval, StatusCode> {
+    store
+        .get(id)
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, approval_id = %id, "failed to load tool approval");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .ok_or(StatusCode::NOT_FOUND)
+}
@@ -0,0 +1,418 @@
+//! Saved attachment serving with HTTP range requests.
+//!
+//! Files persisted by `save_channel_attachments` are streamed from disk in
+//! fixed-size chunks so large audio/video never has to be buffered, and
+//! single `Range` requests are honored so browsers can seek in media players.
+
+use super::access::{AgentQuery, AgentScoped};
+use super::state::ApiState;
+
+use axum::body::{Body, Bytes};
+use axum::extract::{Path, State};
+use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use serde::Deserialize;
+use sqlx::Row as _;
+use tokio::io::{AsyncReadExt as _, AsyncSeekExt as _};
+
+use std::sync::Arc;
+
+/// Read size for each streamed body chunk.
+const STREAM_CHUNK_BYTES: usize = 64 * 1024;
+
+/// Files larger than this are always sent with `Content-Disposition:
+/// attachment`, even for media types a browser could render inline.
+const INLINE_MAX_BYTES: u64 = 50 * 1024 * 1024;
+
+/// Types served inline. Anything else (HTML, SVG, scripts, plain text a
+/// browser might sniff) is forced to download so an uploaded file can never
+/// run in the API's origin.
+const INLINE_MIME_TYPES: &[&str] = &[
+    "image/png",
+    "image/jpeg",
+    "image/gif",
+    "image/webp",
+    "image/avif",
+    "image/bmp",
+    "application/pdf",
+];
+
+/// Media families served inline in addition to [`INLINE_MIME_TYPES`].
+const INLINE_MIME_PREFIXES: &[&str] = &["audio/", "video/"];
+
+#[derive(Deserialize)]
+pub(super) struct AttachmentQuery {
+    agent_id: String,
+    /// Force `Content-Disposition: attachment` regardless of type and size.
+    #[serde(default)]
+    download: bool,
+}
+
+impl AgentScoped for AttachmentQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+/// A satisfiable byte range, inclusive on both ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct ByteRange {
+    start: u64,
+    end: u64,
+}
+
+impl ByteRange {
+    fn len(&self) -> u64 {
+        self.end - self.start + 1
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(super) enum RangeRequest {
+    /// No usable `Range` header; serve the whole file.
+    Full,
+    Partial(ByteRange),
+    Unsatisfiable,
+}
+
+/// Serve a saved attachment's bytes, honoring `Range` headers.
+pub(super) async fn get_attachment(
+    State(state): State<Arc<ApiState>>,
+    Path(attachment_id): Path<String>,
+    AgentQuery(query): AgentQuery<AttachmentQuery>,
+    headers: HeaderMap,
+) -> Result<Response, StatusCode> {
+    let pools = state.agent_pools.load();
+    let pool = pools.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
+
+    let row = sqlx::query(
+        "SELECT original_filename, mime_type, disk_path FROM saved_attachments WHERE id = ?",
+    )
+    .bind(&attachment_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|error| {
+        tracing::warn!(%error, %attachment_id, "failed to load attachment metadata");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?
+    .ok_or(StatusCode::NOT_FOUND)?;
+
+    let original_filename: String = row.try_get("original_filename").unwrap_or_default();
+    let stored_mime: String = row.try_get("mime_type").unwrap_or_default();
+    let disk_path: String = row
+        .try_get("disk_path")
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    // The path comes from our own table, but confirm it still resolves inside
+    // the agent workspace before handing out file contents.
+    let workspaces = state.agent_workspaces.load();
+    let workspace = workspaces
+        .get(&query.agent_id)
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let resolved = tokio::fs::canonicalize(&disk_path)
+        .await
+        .map_err(|_| StatusCode::NOT_FOUND)?;
+    let workspace = tokio::fs::canonicalize(workspace)
+        .await
+        .map_err(|_| StatusCode::NOT_FOUND)?;
+    if !resolved.starts_with(&workspace) {
+        tracing::warn!(%attachment_id, path = %resolved.display(), "attachment path escapes workspace");
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let mut file = tokio::fs::File::open(&resolved)
+        .await
+        .map_err(|_| StatusCode::NOT_FOUND)?;
+    let total_len = file
+        .metadata()
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .len();
+
+    let content_type = if stored_mime.is_empty() || stored_mime == "application/octet-stream" {
+        let mut head = [0u8; 16];
+        let read = file.read(&mut head).await.unwrap_or(0);
+        file.rewind()
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        sniff_mime(&head[..read])
+            .map(str::to_string)
+            .unwrap_or_else(|| {
+                mime_guess::from_path(&original_filename)
+                    .first_or_octet_stream()
+                    .to_string()
+            })
+    } else {
+        stored_mime
+    };
+
+    let range_header = headers
+        .get(header::RANGE)
+        .and_then(|value| value.to_str().ok());
+    let range = match range_header {
+        Some(value) => parse_range(value, total_len),
+        None => RangeRequest::Full,
+    };
+
+    let (status, start, length) = match range {
+        RangeRequest::Full => (StatusCode::OK, 0, total_len),
+        RangeRequest::Partial(range) => (StatusCode::PARTIAL_CONTENT, range.start, range.len()),
+        RangeRequest::Unsatisfiable => {
+            let mut response = StatusCode::RANGE_NOT_SATISFIABLE.into_response();
+            if let Ok(value) = HeaderValue::from_str(&format!("bytes */{total_len}")) {
+                response.headers_mut().insert(header::CONTENT_RANGE, value);
+            }
+            return Ok(response);
+        }
+    };
+
+    if start > 0 {
+        file.seek(std::io::SeekFrom::Start(start))
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    }
+
+    let stream = async_stream::stream! {
+        let mut remaining = length;
+        let mut buffer = vec![0u8; STREAM_CHUNK_BYTES];
+        while remaining > 0 {
+            let to_read = remaining.min(buffer.len() as u64) as usize;
+            match file.read(&mut buffer[..to_read]).await {
+                Ok(0) => break,
+                Ok(read) => {
+                    remaining -= read as u64;
+                    yield Ok::<_, std::io::Error>(Bytes::copy_from_slice(&buffer[..read]));
+                }
+                Err(error) => {
+                    yield Err(error);
+                    break;
+                }
+            }
+        }
+    };
+
+    let is_inline =
+        !query.download && total_len <= INLINE_MAX_BYTES && is_inline_safe(&content_type);
+    let disposition = content_disposition(is_inline, &original_filename);
+
+    let mut response = (status, Body::from_stream(stream)).into_response();
+    let response_headers = response.headers_mut();
+    if let Ok(value) = HeaderValue::from_str(&content_type) {
+        response_headers.insert(header::CONTENT_TYPE, value);
+    }
+    response_headers.insert(header::ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+    response_headers.insert(header::CONTENT_LENGTH, HeaderValue::from(length));
+    if status == StatusCode::PARTIAL_CONTENT
+        && let Ok(value) =
+            HeaderValue::from_str(&format!("bytes {start}-{}/{total_len}", start + length - 1))
+    {
+        response_headers.insert(header::CONTENT_RANGE, value);
+    }
+    if let Ok(value) = HeaderValue::from_str(&disposition) {
+        response_headers.insert(header::CONTENT_DISPOSITION, value);
+    }
+    response_headers.insert(
+        header::X_CONTENT_TYPE_OPTIONS,
+        HeaderValue::from_static("nosniff"),
+    );
+    response_headers.insert(
+        header::CONTENT_SECURITY_POLICY,
+        HeaderValue::from_static("sandbox"),
+    );
+
+    Ok(response)
+}
+
+/// Whether `content_type` is a passive format that is safe to render inline.
+pub(super) fn is_inline_safe(content_type: &str) -> bool {
+    let essence = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    INLINE_MIME_TYPES.contains(&essence.as_str())
+        || INLINE_MIME_PREFIXES
+            .iter()
+            .any(|prefix| essence.starts_with(prefix))
+}
+
+/// Parse a `Range` header against a resource of `total_len` bytes.
+///
+/// Only single `bytes=` ranges are supported. Multi-range requests fall back
+/// to a full response, which RFC 9110 permits.
+pub(super) fn parse_range(value: &str, total_len: u64) -> RangeRequest {
+    let Some(spec) = value.trim().strip_prefix("bytes=") else {
+        return RangeRequest::Full;
+    };
+    if spec.contains(',') {
+        return RangeRequest::Full;
+    }
+    let Some((start, end)) = spec.trim().split_once('-') else {
+        return RangeRequest::Full;
+    };
+    let (start, end) = (start.trim(), end.trim());
+
+    if total_len == 0 {
+        return RangeRequest::Unsatisfiable;
+    }
+
+    let range = if start.is_empty() {
+        // Suffix range: the last N bytes.
+        let Ok(suffix) = end.parse::<u64>() else {
+            return RangeRequest::Full;
+        };
+        if suffix == 0 {
+            return RangeRequest::Unsatisfiable;
+        }
+        ByteRange {
+            start: total_len.saturating_sub(suffix),
+            end: total_len - 1,
+        }
+    } else {
+        let Ok(start) = start.parse::<u64>() else {
+            return RangeRequest::Full;
+        };
+        let end = if end.is_empty() {
+            total_len - 1
+        } else {
+            let Ok(end) = end.parse::<u64>() else {
+                return RangeRequest::Full;
+            };
+            end.min(total_len - 1)
+        };
+        if start >= total_len || start > end {
+            return RangeRequest::Unsatisfiable;
+        }
+        ByteRange { start, end }
+    };
+
+    RangeRequest::Partial(range)
+}
+
+/// Identify common media formats from their leading magic bytes.
+pub(super) fn sniff_mime(head: &[u8]) -> Option<&'static str> {
+    let starts_with = |magic: &[u8]| head.starts_with(magic);
+    let riff_kind = head.get(8..12);
+
+    if starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("image/png")
+    } else if starts_with(b"\xFF\xD8\xFF") {
+        Some("image/jpeg")
+    } else if starts_with(b"GIF87a") || starts_with(b"GIF89a") {
+        Some("image/gif")
+    } else if starts_with(b"RIFF") && riff_kind == Some(b"WEBP") {
+        Some("image/webp")
+    } else if starts_with(b"RIFF") && riff_kind == Some(b"WAVE") {
+        Some("audio/wav")
+    } else if starts_with(b"%PDF-") {
+        Some("application/pdf")
+    } else if starts_with(b"OggS") {
+        Some("audio/ogg")
+    } else if starts_with(b"fLaC") {
+        Some("audio/flac")
+    } else if starts_with(b"ID3") || starts_with(b"\xFF\xFB") || starts_with(b"\xFF\xF3") {
+        Some("audio/mpeg")
+    } else if head.get(4..8) == Some(b"ftyp") {
+        Some("video/mp4")
+    } else if starts_with(b"\x1A\x45\xDF\xA3") {
+        Some("video/webm")
+    } else if starts_with(b"PK\x03\x04") {
+        Some("application/zip")
+    } else {
+        None
+    }
+}
+
+fn content_disposition(is_inline: bool, filename: &str) -> String {
+    let kind = if is_inline { "inline" } else { "attachment" };
+    let ascii_fallback = filename
+        .chars()
+        .map(|character| {
+            if character.is_ascii_graphic() && character != '"' && character != '\\' {
+                character
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    format!(
+        "{kind}; filename=\"{ascii_fallback}\"; filename*=UTF-8''{}",
+        urlencoding::encode(filename)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_explicit_and_open_ranges() {
+        assert_eq!(
+            parse_range("bytes=0-99", 1000),
+            RangeRequest::Partial(ByteRange { start: 0, end: 99 })
+        );
+        assert_eq!(
+            parse_range("bytes=500-", 1000),
+            RangeRequest::Partial(ByteRange {
+                start: 500,
+                end: 999
+            })
+        );
+        assert_eq!(
+            parse_range("bytes=900-5000", 1000),
+            RangeRequest::Partial(ByteRange {
+                start: 900,
+                end: 999
+            })
+        );
+    }
+
+    #[test]
+    fn parses_suffix_ranges() {
+        assert_eq!(
+            parse_range("bytes=-100", 1000),
+            RangeRequest::Partial(ByteRange {
+                start: 900,
+                end: 999
+            })
+        );
+        assert_eq!(
+            parse_range("bytes=-5000", 1000),
+            RangeRequest::Partial(ByteRange { start: 0, end: 999 })
+        );
+    }
+
+    #[test]
+    fn rejects_unsatisfiable_and_ignores_unsupported_ranges() {
+        assert_eq!(
+            parse_range("bytes=1000-", 1000),
+            RangeRequest::Unsatisfiable
+        );
+        assert_eq!(
+            parse_range("bytes=50-10", 1000),
+            RangeRequest::Unsatisfiable
+        );
+        assert_eq!(parse_range("bytes=0-10,20-30", 1000), RangeRequest::Full);
+        assert_eq!(parse_range("items=0-10", 1000), RangeRequest::Full);
+    }
+
+    #[test]
+    fn only_passive_types_are_inline() {
+        assert!(is_inline_safe("image/png"));
+        assert!(is_inline_safe("video/mp4"));
+        assert!(is_inline_safe("Application/PDF"));
+        assert!(!is_inline_safe("text/html; charset=utf-8"));
+        assert!(!is_inline_safe("text/plain"));
+        assert!(!is_inline_safe("image/svg+xml"));
+        assert!(!is_inline_safe("application/xhtml+xml"));
+    }
+
+    #[test]
+    fn sniffs_common_media_types() {
+        assert_eq!(sniff_mime(b"\x89PNG\r\n\x1a\n0000"), Some("image/png"));
+        assert_eq!(sniff_mime(b"RIFF\0\0\0\0WAVEfmt "), Some("audio/wav"));
+        assert_eq!(sniff_mime(b"\0\0\0\x18ftypmp42"), Some("video/mp4"));
+        assert_eq!(sniff_mime(b"plain text"), None);
+    }
+}
@@ -0,0 +1,525 @@
+//! Audit log of mutating API calls.
+//!
+//! Every POST, PUT, PATCH, and DELETE that reaches a handler is recorded in
+//! `audit.redb` with the calling token, the route, the target agent, the
+//! response status, and a redacted copy of the JSON request body. When a
+//! call changes config.toml or an agent's identity files, the entry also
+//! carries a line diff of the change, taken by snapshotting the files around
+//! the handler. The snapshots are best-effort: an edit made by something else
+//! at the same moment can be attributed to the request.
+
+use super::server::{ApiPrincipal, READ_ONLY_POST_ROUTES, buffer_json_body, request_agent_id};
+use super::state::ApiState;
+
+use crate::config::diff_config;
+
+use axum::Json;
+use axum::extract::{Query, Request, State};
+use axum::http::{Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::Response;
+use redb::{Database, ReadableTable, ReadableTableMetadata, TableDefinition};
+use serde::{Deserialize, Serialize};
+
+use std::path::Path;
+use std::sync::Arc;
+
+/// Table: entry ID -> JSON-encoded `AuditEntry`. IDs increase monotonically.
+const AUDIT_TABLE: TableDefinition<u64, &[u8]> = TableDefinition::new("audit_log");
+
+/// Entries kept before the oldest are pruned.
+const MAX_ENTRIES: u64 = 10_000;
+
+/// JSON bodies above this are summarized by size instead of stored.
+const MAX_RECORDED_BODY_BYTES: usize = 16 * 1024;
+
+/// Routes whose bodies are never stored: they carry credentials, or
+/// conversation content that doesn't belong in an operator log.
+const BODY_EXCLUDED_PREFIXES: &[&str] = &[
+    "/secrets",
+    "/providers",
+    "/ssh",
+    "/system/backup",
+    "/webchat",
+    "/cortex-chat",
+];
+
+/// JSON fields whose values are replaced before a body is stored.
+const REDACTED_FIELD_MARKERS: &[&str] = &["key", "token", "secret", "password", "passphrase"];
+
+const REDACTED: &str = "[redacted]";
+
+const IDENTITY_FILES: &[&str] = &["SOUL.md", "IDENTITY.md", "ROLE.md"];
+
+/// One recorded API call.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct AuditEntry {
+    pub id: u64,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Name of the API token used, or `unauthenticated` when API auth is off.
+    pub actor: String,
+    pub method: String,
+    pub path: String,
+    pub query: Option<String>,
+    pub agent_id: Option<String>,
+    pub status: u16,
+    /// Redacted JSON request body.
+    #[schema(value_type = Option<Object>)]
+    pub request: Option<serde_json::Value>,
+    /// Line diffs of files the call changed, keyed by file name.
+    pub changes: Vec<AuditChange>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct AuditChange {
+    pub file: String,
+    pub diff: String,
+}
+
+/// Persistent, append-only log of API mutations.
+pub struct AuditLog {
+    db: Database,
+}
+
+impl std::fmt::Debug for AuditLog {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AuditLog").finish_non_exhaustive()
+    }
+}
+
+impl AuditLog {
+    /// Open or create the log at `path`.
+    pub fn new(path: &Path) -> crate::error::Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|error| anyhow::anyhow!(error))?;
+        }
+        let db = Database::create(path).map_err(|error| {
+            anyhow::anyhow!("failed to open audit db at {}: {error}", path.display())
+        })?;
+
+        let write_txn = db.begin_write().map_err(|error| anyhow::anyhow!(error))?;
+        {
+            let _ = write_txn
+                .open_table(AUDIT_TABLE)
+                .map_err(|error| anyhow::anyhow!(error))?;
+        }
+        write_txn.commit().map_err(|error| anyhow::anyhow!(error))?;
+
+        Ok(Self { db })
+    }
+
+    /// Append an entry, assigning its ID, and prune past `MAX_ENTRIES`.
+    fn append(&self, mut entry: AuditEntry) -> crate::error::Result<u64> {
+        let write_txn = self
+            .db
+            .begin_write()
+            .map_err(|error| anyhow::anyhow!(error))?;
+        {
+            let mut table = write_txn
+                .open_table(AUDIT_TABLE)
+                .map_err(|error| anyhow::anyhow!(error))?;
+            let last_id = table
+                .last()
+                .map_err(|error| anyhow::anyhow!(error))?
+                .map(|(key, _)| key.value())
+                .unwrap_or(0);
+            entry.id = last_id + 1;
+
+            let data = serde_json::to_vec(&entry).map_err(|error| anyhow::anyhow!(error))?;
+            table
+                .insert(entry.id, data.as_slice())
+                .map_err(|error| anyhow::anyhow!(error))?;
+
+            while table.len().map_err(|error| anyhow::anyhow!(error))? > MAX_ENTRIES {
+                table.pop_first().map_err(|error| anyhow::anyhow!(error))?;
+            }
+        }
+        write_txn.commit().map_err(|error| anyhow::anyhow!(error))?;
+        Ok(entry.id)
+    }
+
+    /// Entries matching `filter`, newest first.
+    fn list(&self, filter: &AuditQuery, limit: usize) -> crate::error::Result<Vec<AuditEntry>> {
+        let read_txn = self
+            .db
+            .begin_read()
+            .map_err(|error| anyhow::anyhow!(error))?;
+        let table = read_txn
+            .open_table(AUDIT_TABLE)
+            .map_err(|error| anyhow::anyhow!(error))?;
+
+        let before = filter.before.unwrap_or(u64::MAX);
+        let mut entries = Vec::new();
+        for item in table
+            .range(..before)
+            .map_err(|error| anyhow::anyhow!(error))?
+            .rev()
+        {
+            let (_, value) = item.map_err(|error| anyhow::anyhow!(error))?;
+            let entry: AuditEntry =
+                serde_json::from_slice(value.value()).map_err(|error| anyhow::anyhow!(error))?;
+            if filter.matches(&entry) {
+                entries.push(entry);
+                if entries.len() >= limit {
+                    break;
+                }
+            }
+        }
+        Ok(entries)
+    }
+}
+
+#[derive(Debug, Default, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(super) struct AuditQuery {
+    #[serde(default = "default_limit")]
+    limit: usize,
+    /// Only entries with an ID below this, for paging.
+    before: Option<u64>,
+    actor: Option<String>,
+    agent_id: Option<String>,
+    /// Only entries whose path starts with this, e.g. `/agents/config`.
+    path: Option<String>,
+}
+
+fn default_limit() -> usize {
+    50
+}
+
+impl AuditQuery {
+    fn matches(&self, entry: &AuditEntry) -> bool {
+        self.actor
+            .as_ref()
+            .is_none_or(|actor| *actor == entry.actor)
+            && self
+                .agent_id
+                .as_ref()
+                .is_none_or(|agent_id| entry.agent_id.as_ref() == Some(agent_id))
+            && self
+                .path
+                .as_ref()
+                .is_none_or(|path| entry.path.starts_with(path.as_str()))
+    }
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub(super) struct AuditListResponse {
+    entries: Vec<AuditEntry>,
+    /// Pass as `before` to fetch the next page; absent on the last page.
+    next_before: Option<u64>,
+}
+
+/// List audit entries, newest first.
+#[utoipa::path(
+    get,
+    path = "/api/audit",
+    tag = "system",
+    params(AuditQuery),
+    responses(
+        (status = 200, body = AuditListResponse),
+        (status = 404, description = "Audit log disabled"),
+    )
+)]
+pub(super) async fn list_audit(
+    State(state): State<Arc<ApiState>>,
+    Query(query): Query<AuditQuery>,
+) -> Result<Json<AuditListResponse>, StatusCode> {
+    let audit_log = state.audit_log.clone().ok_or(StatusCode::NOT_FOUND)?;
+    let limit = query.limit.clamp(1, 500);
+    let entries = tokio::task::spawn_blocking(move || audit_log.list(&query, limit))
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, "audit list task failed");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .map_err(|error| {
+            tracing::warn!(%error, "failed to read audit log");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    let next_before = (entries.len() == limit)
+        .then(|| entries.last().map(|entry| entry.id))
+        .flatten();
+    Ok(Json(AuditListResponse {
+        entries,
+        next_before,
+    }))
+}
+
+/// Record mutating requests in the audit log. Runs inside auth so the
+/// caller's token is known.
+pub(super) async fn audit_middleware(
+    State(state): State<Arc<ApiState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(audit_log) = state.audit_log.clone() else {
+        return next.run(request).await;
+    };
+    let path = request.uri().path();
+    let relative_path = path.strip_prefix("/api").unwrap_or(path).to_string();
+    let is_mutating = matches!(
+        *request.method(),
+        Method::POST | Method::PUT | Method::PATCH | Method::DELETE
+    ) && !READ_ONLY_POST_ROUTES.contains(&relative_path.as_str());
+    if !is_mutating {
+        return next.run(request).await;
+    }
+
+    let method = request.method().to_string();
+    let query = request.uri().query().map(str::to_string);
+    let actor = request
+        .extensions()
+        .get::<ApiPrincipal>()
+        .map(|principal| principal.name.clone())
+        .unwrap_or_else(|| "unauthenticated".into());
+
+    let (request, body) = match buffer_json_body(request).await {
+        Ok(buffered) => buffered,
+        Err(response) => return response,
+    };
+
+    let agent_id = request_agent_id(&state, &relative_path, query.as_deref(), body.as_ref());
+    let watched_files = watched_files(&state, agent_id.as_deref()).await;
+    let before = read_files(&watched_files).await;
+
+    let response = next.run(request).await;
+
+    let after = read_files(&watched_files).await;
+    let changes = watched_files
+        .iter()
+        .zip(before.iter().zip(after.iter()))
+        .filter(|(_, (before, after))| before != after)
+        .map(|((label, path), (before, after))| {
+            let diff = diff_config(
+                before.as_deref().unwrap_or_default(),
+                after.as_deref().unwrap_or_default(),
+            );
+            AuditChange {
+                file: label.clone(),
+                diff: if path
+                    .extension()
+                    .is_some_and(|extension| extension == "toml")
+                {
+                    redact_toml_diff(&diff)
+                } else {
+                    diff
+                },
+            }
+        })
+        .collect();
+
+    let record_body = !BODY_EXCLUDED_PREFIXES
+        .iter()
+        .any(|prefix| relative_path.starts_with(prefix));
+    let entry = AuditEntry {
+        id: 0,
+        timestamp: chrono::Utc::now(),
+        actor,
+        method,
+        path: relative_path,
+        query,
+        agent_id,
+        status: response.status().as_u16(),
+        request: body.filter(|_| record_body).map(summarize_body),
+        changes,
+    };
+    let result = tokio::task::spawn_blocking(move || audit_log.append(entry)).await;
+    match result {
+        Ok(Ok(_)) => {}
+        Ok(Err(error)) => tracing::warn!(%error, "failed to write audit entry"),
+        Err(error) => tracing::warn!(%error, "audit write task failed"),
+    }
+    response
+}
+
+/// Files whose changes are diffed into the entry: config.toml, plus the
+/// target agent's identity files.
+async fn watched_files(
+    state: &ApiState,
+    agent_id: Option<&str>,
+) -> Vec<(String, std::path::PathBuf)> {
+    let mut files = Vec::new();
+    let config_path = state.config_path.read().await.clone();
+    if !config_path.as_os_str().is_empty() {
+        files.push(("config.toml".to_string(), config_path));
+    }
+    if let Some(agent_id) = agent_id
+        && let Some(identity_dir) = state.agent_identity_dirs.load().get(agent_id)
+    {
+        for file in IDENTITY_FILES {
+            files.push((file.to_string(), identity_dir.join(file)));
+        }
+    }
+    files
+}
+
+async fn read_files(files: &[(String, std::path::PathBuf)]) -> Vec<Option<String>> {
+    let mut contents = Vec::with_capacity(files.len());
+    for (_, path) in files {
+        contents.push(tokio::fs::read_to_string(path).await.ok());
+    }
+    contents
+}
+
+/// Redact credential-looking fields and cap the stored size.
+fn summarize_body(mut body: serde_json::Value) -> serde_json::Value {
+    redact_json(&mut body);
+    let size = serde_json::to_string(&body).map_or(0, |text| text.len());
+    if size > MAX_RECORDED_BODY_BYTES {
+        return serde_json::json!({ "truncated": true, "bytes": size });
+    }
+    body
+}
+
+fn is_sensitive_field(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    REDACTED_FIELD_MARKERS
+        .iter()
+        .any(|marker| name.contains(marker))
+}
+
+fn redact_json(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (name, field) in map.iter_mut() {
+                if is_sensitive_field(name) && !field.is_null() {
+                    *field = serde_json::Value::String(REDACTED.into());
+                } else {
+                    redact_json(field);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(redact_json),
+        _ => {}
+    }
+}
+
+/// Blank out the values of credential-looking keys in a config.toml diff.
+fn redact_toml_diff(diff: &str) -> String {
+    diff.lines()
+        .map(|line| {
+            let (marker, content) = line.split_at(line.len().min(1));
+            format!("{marker}{}\n", redact_toml_line(content))
+        })
+        .collect()
+}
+
+/// Redact one line of TOML. Lines that parse on their own are walked so
+/// secrets inside inline tables are caught too; anything else (a line from
+/// the middle of a multi-line array, say) falls back to the key before `=`.
+fn redact_toml_line(line: &str) -> String {
+    if let Ok(mut document) = line.parse::<toml_edit::DocumentMut>() {
+        if redact_toml_table(document.as_table_mut()) {
+            return document.to_string().trim_end_matches('\n').to_string();
+        }
+        return line.to_string();
+    }
+    match line.split_once('=') {
+        Some((name, _)) if is_sensitive_field(name.trim()) => {
+            format!("{name}= \"{REDACTED}\"")
+        }
+        _ => line.to_string(),
+    }
+}
+
+fn redact_toml_table(table: &mut toml_edit::Table) -> bool {
+    let mut redacted = false;
+    for (key, item) in table.iter_mut() {
+        match item {
+            toml_edit::Item::Value(value) => redacted |= redact_toml_value(key.get(), value),
+            toml_edit::Item::Table(table) => redacted |= redact_toml_table(table),
+            _ => {}
+        }
+    }
+    redacted
+}
+
+fn redact_toml_value(name: &str, value: &mut toml_edit::Value) -> bool {
+    if is_sensitive_field(name) {
+        let decor = value.decor().clone();
+        *value = toml_edit::Value::from(REDACTED);
+        *value.decor_mut() = decor;
+        return true;
+    }
+    match value {
+        toml_edit::Value::InlineTable(table) => {
+            let mut redacted = false;
+            for (key, value) in table.iter_mut() {
+                redacted |= redact_toml_value(key.get(), value);
+            }
+            redacted
+        }
+        toml_edit::Value::Array(items) => {
+            let mut redacted = false;
+            for item in items.iter_mut() {
+                redacted |= redact_toml_value("", item);
+            }
+            redacted
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(actor: &str, path: &str) -> AuditEntry {
+        AuditEntry {
+            id: 0,
+            timestamp: chrono::Utc::now(),
+            actor: actor.into(),
+            method: "PUT".into(),
+            path: path.into(),
+            query: None,
+            agent_id: Some("main".into()),
+            status: 200,
+            request: None,
+            changes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn appends_lists_newest_first_and_redacts_credentials() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = AuditLog::new(&dir.path().join("audit.redb")).unwrap();
+        assert_eq!(log.append(entry("ops", "/agents/config")).unwrap(), 1);
+        assert_eq!(log.append(entry("ci", "/agents/identity")).unwrap(), 2);
+        assert_eq!(log.append(entry("ops", "/agents/identity")).unwrap(), 3);
+
+        let all = log.list(&AuditQuery::default(), 10).unwrap();
+        assert_eq!(
+            all.iter().map(|entry| entry.id).collect::<Vec<_>>(),
+            vec![3, 2, 1]
+        );
+        let filter = AuditQuery {
+            actor: Some("ops".into()),
+            before: Some(3),
+            ..AuditQuery::default()
+        };
+        let filtered = log.list(&filter, 10).unwrap();
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].path, "/agents/config");
+
+        let body = summarize_body(serde_json::json!({
+            "agent_id": "main",
+            "discord": { "token": "abc", "enabled": true },
+            "api_key": null,
+        }));
+        assert_eq!(body["discord"]["token"], REDACTED);
+        assert_eq!(body["discord"]["enabled"], true);
+        assert!(body["api_key"].is_null());
+
+        let diff =
+            redact_toml_diff(" [llm]\n-anthropic_key = \"sk-old\"\n+anthropic_key = \"sk-new\"\n");
+        assert!(!diff.contains("sk-"));
+        assert!(diff.contains("+anthropic_key = \"[redacted]\""));
+
+        let diff = redact_toml_diff(
+            "+discord = { enabled = true, token = \"bot-secret\" }\n+slack.app_token = \"xapp-1\"\n",
+        );
+        assert!(!diff.contains("bot-secret"));
+        assert!(!diff.contains("xapp-1"));
+        assert!(diff.contains("enabled = true"));
+    }
+}
@@ -0,0 +1,253 @@
+use super::state::ApiState;
+
+use super::access::AgentPath;
+use crate::agent::backfill::{self, BackfillJob, BackfillRequest};
+
+use axum::Json;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Deserialize, utoipa::ToSchema)]
+pub(super) struct BackfillCreateRequest {
+    /// Start of the range (inclusive), RFC 3339.
+    from: chrono::DateTime<chrono::Utc>,
+    /// End of the range (exclusive), RFC 3339. Defaults to now.
+    #[serde(default)]
+    to: Option<chrono::DateTime<chrono::Utc>>,
+    /// Only backfill this channel.
+    #[serde(default)]
+    channel_id: Option<String>,
+    /// Messages per extraction batch.
+    #[serde(default)]
+    batch_size: Option<i64>,
+    /// Pause between batches, in milliseconds.
+    #[serde(default)]
+    batch_delay_ms: Option<u64>,
+}
+
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(super) struct BackfillListQuery {
+    #[serde(default = "default_backfill_limit")]
+    limit: i64,
+}
+
+fn default_backfill_limit() -> i64 {
+    20
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct BackfillListResponse {
+    jobs: Vec<BackfillJob>,
+}
+
+/// Start extracting memories from stored conversation in a time range. The
+/// job runs in the background in rate-limited batches; poll it for progress.
+#[utoipa::path(
+    post,
+    path = "/api/agents/{id}/memories/backfill",
+    tag = "memories",
+    params(("id" = String, Path, description = "Agent ID")),
+    request_body = BackfillCreateRequest,
+    responses(
+        (status = 202, body = BackfillJob),
+        (status = 400, description = "Empty range or batch size outside 1-500"),
+        (status = 404, description = "Unknown agent"),
+        (status = 409, description = "A backfill is already running for this agent"),
+    )
+)]
+pub(super) async fn create_backfill(
+    State(state): State<Arc<ApiState>>,
+    AgentPath(agent_id): AgentPath<String>,
+    Json(request): Json<BackfillCreateRequest>,
+) -> Result<(StatusCode, Json<BackfillJob>), StatusCode> {
+    let deps = {
+        let sessions = state.cortex_chat_sessions.load();
+        sessions
+            .get(&agent_id)
+            .map(|session| session.deps.clone())
+            .ok_or(StatusCode::NOT_FOUND)?
+    };
+
+    let to = request.to.unwrap_or_else(chrono::Utc::now);
+    let batch_size = request.batch_size.unwrap_or(backfill::DEFAULT_BATCH_SIZE);
+    if request.from >= to || !(1..=500).contains(&batch_size) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let job = backfill::create_job(
+        &deps.sqlite_pool,
+        &BackfillRequest {
+            from: request.from,
+            to,
+            channel_id: request.channel_id,
+            batch_size,
+            batch_delay_ms: request
+                .batch_delay_ms
+                .unwrap_or(backfill::DEFAULT_BATCH_DELAY_MS),
+        },
+    )
+    .await
+    .map_err(|error| {
+        tracing::warn!(%error, %agent_id, "failed to create memory backfill");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?
+    .ok_or(StatusCode::CONFLICT)?;
+
+    tracing::info!(
+        %agent_id,
+        job_id = %job.id,
+        total_messages = job.total_messages,
+        "memory backfill started via API"
+    );
+    backfill::spawn_job(job.id.clone(), deps);
+    Ok((StatusCode::ACCEPTED, Json(job)))
+}
+
+/// List backfill jobs with their progress, newest first.
+#[utoipa::path(
+    get,
+    path = "/api/agents/{id}/memories/backfill",
+    tag = "memories",
+    params(("id" = String, Path, description = "Agent ID"), BackfillListQuery),
+    responses(
+        (status = 200, body = BackfillListResponse),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
+pub(super) async fn list_backfills(
+    State(state): State<Arc<ApiState>>,
+    AgentPath(agent_id): AgentPath<String>,
+    Query(query): Query<BackfillListQuery>,
+) -> Result<Json<BackfillListResponse>, StatusCode> {
+    let pools = state.agent_pools.load();
+    let pool = pools.get(&agent_id).ok_or(StatusCode::NOT_FOUND)?;
+
+    let jobs = backfill::list_jobs(pool, query.limit.clamp(1, 100))
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, %agent_id, "failed to list memory backfills");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    Ok(Json(BackfillListResponse { jobs }))
+}
+
+/// Progress of one backfill job.
+#[utoipa::path(
+    get,
+    path = "/api/agents/{id}/memories/backfill/{job_id}",
+    tag = "memories",
+    params(
+        ("id" = String, Path, description = "Agent ID"),
+        ("job_id" = String, Path, description = "Backfill job ID"),
+    ),
+    responses(
+        (status = 200, body = BackfillJob),
+        (status = 404, description = "Unknown agent or job"),
+    )
+)]
+pub(super) async fn get_backfill(
+    State(state): State<Arc<ApiState>>,
+    AgentPath((agent_id, job_id)): AgentPath<(String, String)>,
+) -> Result<Json<BackfillJob>, StatusCode> {
+    let pools = state.agent_pools.load();
+    let pool = pools.get(&agent_id).ok_or(StatusCode::NOT_FOUND)?;
+
+    let job = backfill::get_job(pool, &job_id)
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, %agent_id, %job_id, "failed to load memory backfill");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    Ok(Json(job))
+}
+
+/// Stop a running backfill after its current batch.
+#[utoipa::path(
+    post,
+    path = "/api/agents/{id}/memories/backfill/{job_id}/cancel",
+    tag = "memories",
+    params(
+        ("id" = String, Path, description = "Agent ID"),
+        ("job_id" = String, Path, description = "Backfill job ID"),
+    ),
+    responses(
+        (status = 200, description = "Backfill cancelled"),
+        (status = 404, description = "Unknown agent, or the job is not running"),
+    )
+)]
+pub(super) async fn cancel_backfill(
+    State(state): State<Arc<ApiState>>,
+    AgentPath((agent_id, job_id)): AgentPath<(String, String)>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let pools = state.agent_pools.load();
+    let pool = pools.get(&agent_id).ok_or(StatusCode::NOT_FOUND)?;
+
+    let cancelled = backfill::cancel_job(pool, &job_id).await.map_err(|error| {
+        tracing::warn!(%error, %agent_id, %job_id, "failed to cancel memory backfill");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    if !cancelled {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    tracing::info!(%agent_id, %job_id, "memory backfill cancelled via API");
+    Ok(Json(serde_json::json!({ "success": true })))
+}
+
+/// Resume a failed or cancelled backfill from the last completed batch.
+#[utoipa::path(
+    post,
+    path = "/api/agents/{id}/memories/backfill/{job_id}/resume",
+    tag = "memories",
+    params(
+        ("id" = String, Path, description = "Agent ID"),
+        ("job_id" = String, Path, description = "Backfill job ID"),
+    ),
+    responses(
+        (status = 202, body = BackfillJob),
+        (status = 404, description = "Unknown agent or job"),
+        (status = 409, description = "The job is not failed or cancelled, or another backfill is running"),
+    )
+)]
+pub(super) async fn resume_backfill(
+    State(state): State<Arc<ApiState>>,
+    AgentPath((agent_id, job_id)): AgentPath<(String, String)>,
+) -> Result<(StatusCode, Json<BackfillJob>), StatusCode> {
+    let deps = {
+        let sessions = state.cortex_chat_sessions.load();
+        sessions
+            .get(&agent_id)
+            .map(|session| session.deps.clone())
+            .ok_or(StatusCode::NOT_FOUND)?
+    };
+    let internal_error = |error: anyhow::Error| {
+        tracing::warn!(%error, %agent_id, %job_id, "failed to resume memory backfill");
+        StatusCode::INTERNAL_SERVER_ERROR
+    };
+
+    if backfill::get_job(&deps.sqlite_pool, &job_id)
+        .await
+        .map_err(internal_error)?
+        .is_none()
+    {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    if !backfill::reopen_job(&deps.sqlite_pool, &job_id)
+        .await
+        .map_err(internal_error)?
+    {
+        return Err(StatusCode::CONFLICT);
+    }
+
+    let job = backfill::get_job(&deps.sqlite_pool, &job_id)
+        .await
+        .map_err(internal_error)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    tracing::info!(%agent_id, %job_id, "memory backfill resumed via API");
+    backfill::spawn_job(job_id.clone(), deps);
+    Ok((StatusCode::ACCEPTED, Json(job)))
+}
@@ -0,0 +1,254 @@
+//! Batch jobs: a task template run by workers over every item of an input
+//! file or conversation search (see [`crate::agent::batch`]).
+
+use super::access::{AgentJson, AgentQuery, AgentScoped};
+use super::state::ApiState;
+
+use crate::agent::batch::{self, BatchJob, BatchRequest, BatchSource};
+
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Deserialize, utoipa::ToSchema)]
+pub(super) struct BatchCreateRequest {
+    agent_id: String,
+    /// Task for each item. `{{item}}` is replaced with the item and
+    /// `{{index}}` with its position; without `{{item}}` the item is
+    /// appended.
+    template: String,
+    /// Input file in the agent's workspace: `.jsonl`, a `.json` array, or
+    /// one item per line.
+    #[serde(default)]
+    input_file: Option<String>,
+    /// Full-text search over stored conversation; each matching message is
+    /// an item. Use instead of `input_file`.
+    #[serde(default)]
+    query: Option<String>,
+    /// Only search this channel.
+    #[serde(default)]
+    channel_id: Option<String>,
+    /// Most messages a query turns into items.
+    #[serde(default)]
+    limit: Option<i64>,
+    /// Workers running at once, 1-16.
+    #[serde(default)]
+    concurrency: Option<i64>,
+    /// Minimum gap between worker starts, in milliseconds.
+    #[serde(default)]
+    item_delay_ms: Option<u64>,
+}
+
+impl AgentScoped for BatchCreateRequest {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(super) struct BatchAgentQuery {
+    agent_id: String,
+}
+
+impl AgentScoped for BatchAgentQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(super) struct BatchListQuery {
+    agent_id: String,
+    #[serde(default = "default_batch_limit")]
+    limit: i64,
+}
+
+impl AgentScoped for BatchListQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+fn default_batch_limit() -> i64 {
+    20
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct BatchListResponse {
+    jobs: Vec<BatchJob>,
+}
+
+/// Queue a batch job. Items are worked in the background; poll the job for
+/// progress and the results file.
+#[utoipa::path(
+    post,
+    path = "/api/batch",
+    tag = "batch",
+    request_body = BatchCreateRequest,
+    responses(
+        (status = 202, body = BatchJob),
+        (status = 400, description = "Empty template, neither or both of input_file and query, concurrency outside 1-16, or an unreadable or empty input"),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
+pub(super) async fn create_batch(
+    State(state): State<Arc<ApiState>>,
+    AgentJson(request): AgentJson<BatchCreateRequest>,
+) -> Result<(StatusCode, Json<BatchJob>), StatusCode> {
+    let agent_id = request.agent_id;
+    let deps = {
+        let sessions = state.cortex_chat_sessions.load();
+        sessions
+            .get(&agent_id)
+            .map(|session| session.deps.clone())
+            .ok_or(StatusCode::NOT_FOUND)?
+    };
+
+    let concurrency = request.concurrency.unwrap_or(batch::DEFAULT_CONCURRENCY);
+    if request.template.trim().is_empty() || !(1..=16).contains(&concurrency) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let source = match (request.input_file, request.query) {
+        (Some(path), None) => BatchSource::File { path },
+        (None, Some(query)) if !query.trim().is_empty() => BatchSource::Query {
+            query,
+            channel_id: request.channel_id,
+            limit: request
+                .limit
+                .unwrap_or(batch::DEFAULT_QUERY_LIMIT)
+                .clamp(1, batch::MAX_ITEMS as i64),
+        },
+        _ => return Err(StatusCode::BAD_REQUEST),
+    };
+
+    let items = batch::load_items(
+        &deps.sqlite_pool,
+        &deps.runtime_config.workspace_dir,
+        &source,
+    )
+    .await
+    .map_err(|error| {
+        tracing::info!(%error, %agent_id, "rejected batch job input");
+        StatusCode::BAD_REQUEST
+    })?;
+
+    let job = batch::create_job(
+        &deps.sqlite_pool,
+        &BatchRequest {
+            template: request.template,
+            source,
+            concurrency,
+            item_delay_ms: request
+                .item_delay_ms
+                .unwrap_or(batch::DEFAULT_ITEM_DELAY_MS),
+        },
+        &items,
+    )
+    .await
+    .map_err(|error| {
+        tracing::warn!(%error, %agent_id, "failed to create batch job");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?
+    .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    tracing::info!(
+        %agent_id,
+        job_id = %job.id,
+        total_items = job.total_items,
+        "batch job started via API"
+    );
+    batch::spawn_job(job.id.clone(), deps);
+    Ok((StatusCode::ACCEPTED, Json(job)))
+}
+
+/// List an agent's batch jobs with their progress, newest first.
+#[utoipa::path(
+    get,
+    path = "/api/batch",
+    tag = "batch",
+    params(BatchListQuery),
+    responses(
+        (status = 200, body = BatchListResponse),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
+pub(super) async fn list_batches(
+    State(state): State<Arc<ApiState>>,
+    AgentQuery(query): AgentQuery<BatchListQuery>,
+) -> Result<Json<BatchListResponse>, StatusCode> {
+    let pools = state.agent_pools.load();
+    let pool = pools.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
+
+    let jobs = batch::list_jobs(pool, query.limit.clamp(1, 100))
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, agent_id = %query.agent_id, "failed to list batch jobs");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    Ok(Json(BatchListResponse { jobs }))
+}
+
+/// Progress of one batch job.
+#[utoipa::path(
+    get,
+    path = "/api/batch/{id}",
+    tag = "batch",
+    params(("id" = String, Path, description = "Batch job ID"), BatchAgentQuery),
+    responses(
+        (status = 200, body = BatchJob),
+        (status = 404, description = "Unknown agent or job"),
+    )
+)]
+pub(super) async fn get_batch(
+    State(state): State<Arc<ApiState>>,
+    Path(job_id): Path<String>,
+    AgentQuery(query): AgentQuery<BatchAgentQuery>,
+) -> Result<Json<BatchJob>, StatusCode> {
+    let pools = state.agent_pools.load();
+    let pool = pools.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
+
+    let job = batch::get_job(pool, &job_id)
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, agent_id = %query.agent_id, %job_id, "failed to load batch job");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    Ok(Json(job))
+}
+
+/// Stop handing out items. Items already with a worker finish and the
+/// results so far are written out.
+#[utoipa::path(
+    post,
+    path = "/api/batch/{id}/cancel",
+    tag = "batch",
+    params(("id" = String, Path, description = "Batch job ID"), BatchAgentQuery),
+    responses(
+        (status = 200, description = "Batch job cancelled"),
+        (status = 404, description = "Unknown agent, or the job is not running"),
+    )
+)]
+pub(super) async fn cancel_batch(
+    State(state): State<Arc<ApiState>>,
+    Path(job_id): Path<String>,
+    AgentQuery(query): AgentQuery<BatchAgentQuery>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let pools = state.agent_pools.load();
+    let pool = pools.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
+
+    let cancelled = batch::cancel_job(pool, &job_id).await.map_err(|error| {
+        tracing::warn!(%error, agent_id = %query.agent_id, %job_id, "failed to cancel batch job");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    if !cancelled {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    tracing::info!(agent_id = %query.agent_id, %job_id, "batch job cancelled via API");
+    Ok(Json(serde_json::json!({ "success": true })))
+}
@@ -0,0 +1,149 @@
+//! Operator broadcasts: one announcement fanned out to a group of channels
+//! (see [`crate::messaging::broadcast`]). Unlike the agent tool, these are
+//! never queued for approval.
+
+use super::access::{AgentJson, AgentQuery, AgentScoped};
+use super::state::ApiState;
+
+use crate::config::RuntimeConfig;
+use crate::messaging::broadcast::{Announcement, BroadcastReport, fan_out};
+use crate::messaging::target::parse_delivery_target;
+
+use axum::Json;
+use axum::extract::State;
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Deserialize, utoipa::IntoParams)]
+pub(super) struct BroadcastGroupsQuery {
+    agent_id: String,
+}
+
+impl AgentScoped for BroadcastGroupsQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct BroadcastGroupsResponse {
+    /// Group name to `adapter:target` delivery targets, in send order.
+    groups: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
+pub(super) struct BroadcastRequest {
+    agent_id: String,
+    /// A group from the agent's `[broadcast.groups]`.
+    #[serde(default)]
+    group: Option<String>,
+    /// Explicit `adapter:target` delivery targets, instead of a group.
+    #[serde(default)]
+    targets: Option<Vec<String>>,
+    #[serde(flatten)]
+    announcement: Announcement,
+    /// Overrides the agent's `stagger_ms`.
+    #[serde(default)]
+    stagger_ms: Option<u64>,
+}
+
+impl AgentScoped for BroadcastRequest {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+fn runtime_config(state: &ApiState, agent_id: &str) -> Result<Arc<RuntimeConfig>, StatusCode> {
+    state
+        .runtime_configs
+        .load()
+        .get(agent_id)
+        .cloned()
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/agents/broadcast/groups",
+    tag = "agents",
+    params(BroadcastGroupsQuery),
+    responses(
+        (status = 200, description = "Configured broadcast groups", body = BroadcastGroupsResponse),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
+pub(super) async fn list_broadcast_groups(
+    State(state): State<Arc<ApiState>>,
+    AgentQuery(query): AgentQuery<BroadcastGroupsQuery>,
+) -> Result<Json<BroadcastGroupsResponse>, StatusCode> {
+    let runtime_config = runtime_config(&state, &query.agent_id)?;
+    let groups = runtime_config
+        .broadcast
+        .load()
+        .groups
+        .iter()
+        .map(|(name, targets)| {
+            let targets = targets.iter().map(ToString::to_string).collect();
+            (name.clone(), targets)
+        })
+        .collect();
+    Ok(Json(BroadcastGroupsResponse { groups }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/agents/broadcast",
+    tag = "agents",
+    request_body = BroadcastRequest,
+    responses(
+        (status = 200, description = "Delivery report, one entry per target", body = BroadcastReport),
+        (status = 400, description = "Empty message, unknown group, invalid target, or neither/both of group and targets"),
+        (status = 404, description = "Unknown agent"),
+        (status = 503, description = "Messaging is not running"),
+    )
+)]
+pub(super) async fn broadcast(
+    State(state): State<Arc<ApiState>>,
+    AgentJson(request): AgentJson<BroadcastRequest>,
+) -> Result<Json<BroadcastReport>, StatusCode> {
+    let runtime_config = runtime_config(&state, &request.agent_id)?;
+    if request.announcement.message.trim().is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let config = runtime_config.broadcast.load();
+    let targets = match (&request.group, &request.targets) {
+        (Some(group), None) => config
+            .groups
+            .get(group.trim())
+            .cloned()
+            .ok_or(StatusCode::BAD_REQUEST)?,
+        (None, Some(targets)) if !targets.is_empty() => targets
+            .iter()
+            .map(|target| parse_delivery_target(target))
+            .collect::<Option<Vec<_>>>()
+            .ok_or(StatusCode::BAD_REQUEST)?,
+        _ => return Err(StatusCode::BAD_REQUEST),
+    };
+
+    let messaging_manager = state
+        .messaging_manager
+        .read()
+        .await
+        .clone()
+        .ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let stagger = Duration::from_millis(request.stagger_ms.unwrap_or(config.stagger_ms));
+    let report = fan_out(&messaging_manager, &targets, &request.announcement, stagger).await;
+
+    tracing::info!(
+        agent_id = %request.agent_id,
+        group = ?request.group,
+        sent = report.sent,
+        failed = report.failed,
+        "operator broadcast sent"
+    );
+    Ok(Json(report))
+}
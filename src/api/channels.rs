@@ -1,16 +1,22 @@
 use super::state::ApiState;
 
-use crate::conversation::channels::ChannelStore;
-use crate::conversation::history::ProcessRunLogger;
+use super::access::{AgentJson, AgentQuery, AgentScoped};
+use crate::conversation::channels::{ChannelLookupPolicy, ChannelPurgeCounts, ChannelStore};
+use crate::conversation::history::{
+    ConversationLogger, ConversationMessage, ProcessRunLogger, TimelineCursor, TimelineItem,
+};
+use crate::messaging::outbox::Outbox;
 
 use axum::Json;
+use axum::body::Body;
 use axum::extract::{Query, State};
-use axum::http::StatusCode;
+use axum::http::{HeaderValue, StatusCode, header};
+use axum::response::{IntoResponse as _, Response};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
 
-#[derive(Serialize)]
+#[derive(Serialize, utoipa::ToSchema)]
 pub(super) struct ChannelResponse {
     agent_id: String,
     id: String,
@@ -21,12 +27,13 @@ pub(super) struct ChannelResponse {
     created_at: String,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, utoipa::ToSchema)]
 pub(super) struct ChannelsResponse {
     channels: Vec<ChannelResponse>,
 }
 
-#[derive(Deserialize, Default)]
+#[derive(Deserialize, Default, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
 pub(super) struct ListChannelsQuery {
     #[serde(default)]
     include_inactive: bool,
@@ -34,6 +41,12 @@ pub(super) struct ListChannelsQuery {
     is_active: Option<bool>,
 }
 
+impl AgentScoped for ListChannelsQuery {
+    fn agent_id(&self) -> Option<&str> {
+        self.agent_id.as_deref()
+    }
+}
+
 type AgentChannel = (String, crate::conversation::channels::ChannelInfo);
 
 fn resolve_is_active_filter(query: &ListChannelsQuery) -> Option<bool> {
@@ -57,24 +70,61 @@ fn sort_channels_newest_first(channels: &mut [AgentChannel]) {
     );
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, utoipa::ToSchema)]
 pub(super) struct MessagesResponse {
+    /// Messages, branch runs, and worker runs, tagged by `type`.
+    #[schema(value_type = Vec<Object>)]
     items: Vec<crate::conversation::history::TimelineItem>,
     has_more: bool,
+    /// Pass back as `cursor` to fetch the page before this one. Absent when
+    /// there are no older items.
+    next_cursor: Option<String>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
 pub(super) struct MessagesQuery {
     channel_id: String,
-    #[serde(default = "default_message_limit")]
-    limit: i64,
+    /// Agent that owns the channel. Looked up from the channel index when
+    /// omitted.
+    agent_id: Option<String>,
+    /// Items per page for `json` (default 20, at most 100). For `ndjson`,
+    /// the total to stream; every item when omitted.
+    limit: Option<i64>,
+    /// Opaque cursor from a previous page's `next_cursor`.
+    cursor: Option<String>,
+    /// Only return items strictly before this timestamp. Superseded by
+    /// `cursor`, which also orders items that share a timestamp.
     before: Option<String>,
+    /// `json` (default) returns one page. `ndjson` streams items newest
+    /// first, one JSON object per line, reading the timeline a page at a
+    /// time so large channels are never held in memory.
+    #[serde(default)]
+    #[param(value_type = Option<String>)]
+    format: MessagesFormat,
+}
+
+impl AgentScoped for MessagesQuery {
+    fn agent_id(&self) -> Option<&str> {
+        self.agent_id.as_deref()
+    }
 }
 
 fn default_message_limit() -> i64 {
     20
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(super) enum MessagesFormat {
+    #[default]
+    Json,
+    Ndjson,
+}
+
+/// Timeline items fetched per database round trip when streaming.
+const STREAM_PAGE_SIZE: i64 = 200;
+
 #[derive(Deserialize)]
 pub(super) struct CancelProcessRequest {
     channel_id: String,
@@ -89,9 +139,16 @@ pub(super) struct CancelProcessResponse {
 }
 
 /// List channels across agents, with optional activity and agent filters.
+#[utoipa::path(
+    get,
+    path = "/api/channels",
+    tag = "channels",
+    params(ListChannelsQuery),
+    responses((status = 200, body = ChannelsResponse))
+)]
 pub(super) async fn list_channels(
     State(state): State<Arc<ApiState>>,
-    Query(query): Query<ListChannelsQuery>,
+    AgentQuery(query): AgentQuery<ListChannelsQuery>,
 ) -> Json<ChannelsResponse> {
     let pools = state.agent_pools.load();
     let mut collected_channels: Vec<AgentChannel> = Vec::new();
@@ -136,41 +193,324 @@ pub(super) async fn list_channels(
 
 /// Get the unified timeline for a channel: messages, branch runs, and worker runs
 /// interleaved chronologically.
+#[utoipa::path(
+    get,
+    path = "/api/channels/messages",
+    tag = "channels",
+    params(MessagesQuery),
+    responses(
+        (status = 200, description = "A page of the timeline, or with `format=ndjson` a stream of items newest first", content(
+            (MessagesResponse = "application/json"),
+            (String = "application/x-ndjson"),
+        )),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
 pub(super) async fn channel_messages(
     State(state): State<Arc<ApiState>>,
-    Query(query): Query<MessagesQuery>,
-) -> Json<MessagesResponse> {
+    AgentQuery(query): AgentQuery<MessagesQuery>,
+) -> Result<Response, StatusCode> {
+    let empty = MessagesResponse {
+        items: vec![],
+        has_more: false,
+        next_cursor: None,
+    };
+    let agent_id = match query.agent_id {
+        Some(agent_id) => agent_id,
+        None => match state.resolve_channel_agent(&query.channel_id).await {
+            Some(agent_id) => agent_id,
+            None if query.format == MessagesFormat::Ndjson => {
+                return Ok(ndjson_response(Body::empty()));
+            }
+            None => return Ok(Json(empty).into_response()),
+        },
+    };
     let pools = state.agent_pools.load();
-    let limit = query.limit.min(100);
+    let pool = pools.get(&agent_id).ok_or(StatusCode::NOT_FOUND)?;
+
+    let before = query
+        .cursor
+        .as_deref()
+        .or(query.before.as_deref())
+        .map(TimelineCursor::parse);
+    let logger = ProcessRunLogger::new(pool.clone());
+
+    if query.format == MessagesFormat::Ndjson {
+        return Ok(stream_timeline(
+            logger,
+            state.outbox.clone(),
+            agent_id,
+            query.channel_id,
+            before,
+            query.limit,
+        ));
+    }
+
+    let limit = query.limit.unwrap_or_else(default_message_limit).min(100);
     let fetch_limit = limit + 1;
+    let items = logger
+        .load_channel_timeline(&query.channel_id, fetch_limit, before.as_ref())
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, %agent_id, channel_id = %query.channel_id, "failed to load timeline");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    if items.is_empty() {
+        return Ok(Json(empty).into_response());
+    }
 
-    for (_agent_id, pool) in pools.iter() {
-        let logger = ProcessRunLogger::new(pool.clone());
-        match logger
-            .load_channel_timeline(&query.channel_id, fetch_limit, query.before.as_deref())
-            .await
-        {
-            Ok(items) if !items.is_empty() => {
-                let has_more = items.len() as i64 > limit;
-                let items = if has_more {
-                    items[items.len() - limit as usize..].to_vec()
-                } else {
-                    items
-                };
-                return Json(MessagesResponse { items, has_more });
+    let has_more = items.len() as i64 > limit;
+    let mut items = if has_more {
+        items[items.len() - limit as usize..].to_vec()
+    } else {
+        items
+    };
+    attach_delivery(state.outbox.as_deref(), &mut items);
+    let next_cursor = has_more
+        .then(|| items.first().map(|item| item.cursor().to_string()))
+        .flatten();
+    Ok(Json(MessagesResponse {
+        items,
+        has_more,
+        next_cursor,
+    })
+    .into_response())
+}
+
+/// Stream up to `limit` timeline items before `before`, newest first, one
+/// per line.
+fn stream_timeline(
+    logger: ProcessRunLogger,
+    outbox: Option<Arc<Outbox>>,
+    agent_id: String,
+    channel_id: String,
+    mut before: Option<TimelineCursor>,
+    limit: Option<i64>,
+) -> Response {
+    let stream = async_stream::stream! {
+        let mut remaining = limit.map(|limit| limit.max(0));
+        loop {
+            let page_size = remaining.map_or(STREAM_PAGE_SIZE, |remaining| remaining.min(STREAM_PAGE_SIZE));
+            if page_size == 0 {
+                break;
             }
-            Ok(_) => continue,
-            Err(error) => {
-                tracing::warn!(%error, channel_id = %query.channel_id, "failed to load timeline");
-                continue;
+            let mut page = match logger
+                .load_channel_timeline(&channel_id, page_size, before.as_ref())
+                .await
+            {
+                Ok(page) => page,
+                Err(error) => {
+                    tracing::warn!(%error, %agent_id, %channel_id, "timeline stream aborted");
+                    yield Err(std::io::Error::other(error.to_string()));
+                    return;
+                }
+            };
+            let Some(oldest) = page.first() else {
+                break;
+            };
+            before = Some(oldest.cursor());
+            attach_delivery(outbox.as_deref(), &mut page);
+
+            let mut chunk = String::new();
+            for item in page.iter().rev() {
+                chunk.push_str(&serde_json::to_string(item).unwrap_or_else(|_| "null".into()));
+                chunk.push('\n');
+            }
+            yield Ok::<_, std::io::Error>(chunk);
+
+            if let Some(remaining) = &mut remaining {
+                *remaining -= page.len() as i64;
             }
+            if (page.len() as i64) < page_size {
+                break;
+            }
+        }
+    };
+    ndjson_response(Body::from_stream(stream))
+}
+
+/// Fill in the outbox delivery state of the assistant replies in `items`.
+/// Replies without a status (delivered long ago, or sent before the outbox
+/// existed) are left as they are.
+fn attach_delivery(outbox: Option<&Outbox>, items: &mut [TimelineItem]) {
+    let Some(outbox) = outbox else {
+        return;
+    };
+    let ids: Vec<&str> = items
+        .iter()
+        .filter_map(|item| match item {
+            TimelineItem::Message { id, role, .. } if role == "assistant" => Some(id.as_str()),
+            _ => None,
+        })
+        .collect();
+    if ids.is_empty() {
+        return;
+    }
+    let mut statuses = match outbox.statuses(&ids) {
+        Ok(statuses) => statuses,
+        Err(error) => {
+            tracing::warn!(%error, "failed to load reply delivery statuses");
+            return;
+        }
+    };
+    for item in items {
+        if let TimelineItem::Message { id, delivery, .. } = item {
+            *delivery = statuses.remove(id.as_str());
         }
     }
+}
 
-    Json(MessagesResponse {
-        items: vec![],
-        has_more: false,
-    })
+fn ndjson_response(body: Body) -> Response {
+    let mut response = body.into_response();
+    response.headers_mut().insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/x-ndjson"),
+    );
+    response
+}
+
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(super) struct SearchMessagesQuery {
+    /// Words to find. Every word must appear; matching is stemmed, so
+    /// `promise` also finds `promised`.
+    q: String,
+    /// Restrict to one agent. All agents when omitted.
+    agent_id: Option<String>,
+    /// Restrict to one channel.
+    channel_id: Option<String>,
+    #[serde(default = "default_search_limit")]
+    limit: i64,
+    /// Messages to include on each side of a match.
+    #[serde(default = "default_search_context")]
+    context: i64,
+}
+
+impl AgentScoped for SearchMessagesQuery {
+    fn agent_id(&self) -> Option<&str> {
+        self.agent_id.as_deref()
+    }
+}
+
+fn default_search_limit() -> i64 {
+    20
+}
+
+fn default_search_context() -> i64 {
+    2
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct SearchMessagesResponse {
+    /// Best match first.
+    results: Vec<MessageSearchResult>,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct MessageSearchResult {
+    agent_id: String,
+    channel_id: String,
+    message_id: String,
+    role: String,
+    sender_name: Option<String>,
+    created_at: String,
+    /// Matched words wrapped in `[` `]`, trimmed to the surrounding text.
+    snippet: String,
+    /// The match and its neighbouring messages in the channel, oldest first.
+    context: Vec<SearchContextMessage>,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct SearchContextMessage {
+    id: String,
+    role: String,
+    sender_name: Option<String>,
+    content: String,
+    created_at: String,
+}
+
+impl From<ConversationMessage> for SearchContextMessage {
+    fn from(message: ConversationMessage) -> Self {
+        Self {
+            id: message.id,
+            role: message.role,
+            sender_name: message.sender_name,
+            content: message.content,
+            created_at: message.created_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Full-text search across conversation history, with surrounding messages
+/// for each match.
+#[utoipa::path(
+    get,
+    path = "/api/channels/search",
+    tag = "channels",
+    params(SearchMessagesQuery),
+    responses(
+        (status = 200, body = SearchMessagesResponse),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
+pub(super) async fn search_messages(
+    State(state): State<Arc<ApiState>>,
+    AgentQuery(query): AgentQuery<SearchMessagesQuery>,
+) -> Result<Json<SearchMessagesResponse>, StatusCode> {
+    let pools = state.agent_pools.load();
+    if let Some(agent_id) = &query.agent_id
+        && !pools.contains_key(agent_id)
+    {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    let limit = query.limit.clamp(1, 100);
+    let context = query.context.clamp(0, 10);
+
+    let mut hits = Vec::new();
+    for (agent_id, pool) in pools.iter() {
+        if query.agent_id.as_deref().is_some_and(|id| id != agent_id) {
+            continue;
+        }
+        let logger = ConversationLogger::new(pool.clone());
+        let agent_hits = logger
+            .search_messages(&query.q, query.channel_id.as_deref(), limit)
+            .await
+            .map_err(|error| {
+                tracing::warn!(%error, %agent_id, "failed to search messages");
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+        // Results from different agent databases interleave by position in
+        // each agent's ranking.
+        for (position, hit) in agent_hits.into_iter().enumerate() {
+            hits.push((position, agent_id.clone(), logger.clone(), hit));
+        }
+    }
+    hits.sort_by_key(|(position, ..)| *position);
+    hits.truncate(limit as usize);
+
+    let mut results = Vec::with_capacity(hits.len());
+    for (_, agent_id, logger, hit) in hits {
+        let surrounding = logger
+            .load_context(&hit.message, context)
+            .await
+            .map_err(|error| {
+                tracing::warn!(%error, %agent_id, "failed to load search context");
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+        let message = hit.message;
+        results.push(MessageSearchResult {
+            agent_id,
+            channel_id: message.channel_id,
+            message_id: message.id,
+            role: message.role,
+            sender_name: message.sender_name,
+            created_at: message.created_at.to_rfc3339(),
+            snippet: hit.snippet,
+            context: surrounding.into_iter().map(Into::into).collect(),
+        });
+    }
+
+    Ok(Json(SearchMessagesResponse { results }))
 }
 
 /// Get live status (active workers, branches, completed items) for all channels.
@@ -199,17 +539,79 @@ pub(super) struct DeleteChannelQuery {
     channel_id: String,
 }
 
-#[derive(Deserialize)]
+impl AgentScoped for DeleteChannelQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
 pub(super) struct SetChannelArchiveRequest {
     agent_id: String,
     channel_id: String,
     archived: bool,
 }
 
+impl AgentScoped for SetChannelArchiveRequest {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
+pub(super) struct SetLookupPolicyRequest {
+    agent_id: String,
+    channel_id: String,
+    policy: ChannelLookupPolicy,
+}
+
+impl AgentScoped for SetLookupPolicyRequest {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
+pub(super) struct MuteChannelRequest {
+    agent_id: String,
+    channel_id: String,
+}
+
+impl AgentScoped for MuteChannelRequest {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
+pub(super) struct PurgeChannelRequest {
+    agent_id: String,
+    channel_id: String,
+    /// Actually delete. Without it the purge is a dry run that only reports
+    /// what would be removed.
+    #[serde(default)]
+    confirm: bool,
+}
+
+impl AgentScoped for PurgeChannelRequest {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct PurgeChannelResponse {
+    success: bool,
+    dry_run: bool,
+    #[serde(flatten)]
+    counts: ChannelPurgeCounts,
+    total: u64,
+}
+
 /// Delete a channel and its message history.
 pub(super) async fn delete_channel(
     State(state): State<Arc<ApiState>>,
-    Query(query): Query<DeleteChannelQuery>,
+    AgentQuery(query): AgentQuery<DeleteChannelQuery>,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
     let pools = state.agent_pools.load();
     let pool = pools.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
@@ -223,6 +625,7 @@ pub(super) async fn delete_channel(
     if !deleted {
         return Err(StatusCode::NOT_FOUND);
     }
+    state.unregister_channel_agent(&query.channel_id).await;
 
     tracing::info!(
         agent_id = %query.agent_id,
@@ -234,9 +637,19 @@ pub(super) async fn delete_channel(
 }
 
 /// Archive or unarchive a channel without deleting its history.
+#[utoipa::path(
+    put,
+    path = "/api/channels/archive",
+    tag = "channels",
+    request_body = SetChannelArchiveRequest,
+    responses(
+        (status = 200, description = "The body carries `success`, `archived`, and `is_active`"),
+        (status = 404, description = "Unknown agent or channel"),
+    )
+)]
 pub(super) async fn set_channel_archive(
     State(state): State<Arc<ApiState>>,
-    Json(request): Json<SetChannelArchiveRequest>,
+    AgentJson(request): AgentJson<SetChannelArchiveRequest>,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
     let pools = state.agent_pools.load();
     let pool = pools.get(&request.agent_id).ok_or(StatusCode::NOT_FOUND)?;
@@ -265,6 +678,182 @@ pub(super) async fn set_channel_archive(
     Ok(Json(archive_update_response_payload(request.archived)))
 }
 
+/// Set whether other channels may pull excerpts of this channel's history
+/// with `lookup_channel`.
+#[utoipa::path(
+    put,
+    path = "/api/channels/lookup-policy",
+    tag = "channels",
+    request_body = SetLookupPolicyRequest,
+    responses(
+        (status = 200, description = "The body carries `success` and `policy`"),
+        (status = 404, description = "Unknown agent or channel"),
+    )
+)]
+pub(super) async fn set_lookup_policy(
+    State(state): State<Arc<ApiState>>,
+    AgentJson(request): AgentJson<SetLookupPolicyRequest>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let pools = state.agent_pools.load();
+    let pool = pools.get(&request.agent_id).ok_or(StatusCode::NOT_FOUND)?;
+    let store = ChannelStore::new(pool.clone());
+
+    let updated = store
+        .set_lookup_policy(&request.channel_id, request.policy)
+        .await
+        .map_err(|error| {
+            tracing::error!(%error, "failed to update channel lookup policy");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    if !updated {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    tracing::info!(
+        agent_id = %request.agent_id,
+        channel_id = %request.channel_id,
+        policy = request.policy.as_str(),
+        "channel lookup policy updated via API"
+    );
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "policy": request.policy,
+    })))
+}
+
+/// Mute a channel: the agent keeps recording its messages and capturing
+/// memories but never replies there.
+#[utoipa::path(
+    post,
+    path = "/api/channels/mute",
+    tag = "channels",
+    request_body = MuteChannelRequest,
+    responses(
+        (status = 200, description = "The body carries `success` and `muted`"),
+        (status = 404, description = "Unknown agent or channel"),
+    )
+)]
+pub(super) async fn mute_channel(
+    State(state): State<Arc<ApiState>>,
+    AgentJson(request): AgentJson<MuteChannelRequest>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    set_channel_muted(&state, request, true).await
+}
+
+/// Unmute a channel so the agent replies there again.
+#[utoipa::path(
+    post,
+    path = "/api/channels/unmute",
+    tag = "channels",
+    request_body = MuteChannelRequest,
+    responses(
+        (status = 200, description = "The body carries `success` and `muted`"),
+        (status = 404, description = "Unknown agent or channel"),
+    )
+)]
+pub(super) async fn unmute_channel(
+    State(state): State<Arc<ApiState>>,
+    AgentJson(request): AgentJson<MuteChannelRequest>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    set_channel_muted(&state, request, false).await
+}
+
+async fn set_channel_muted(
+    state: &ApiState,
+    request: MuteChannelRequest,
+    muted: bool,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let pools = state.agent_pools.load();
+    let pool = pools.get(&request.agent_id).ok_or(StatusCode::NOT_FOUND)?;
+    let store = ChannelStore::new(pool.clone());
+
+    let updated = store
+        .set_muted(&request.channel_id, muted)
+        .await
+        .map_err(|error| {
+            tracing::error!(%error, "failed to update channel mute state");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    if !updated {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    // Reflect the change in channel_status right away rather than on the
+    // channel's next message.
+    if let Some(status_block) = state
+        .channel_status_blocks
+        .read()
+        .await
+        .get(&request.channel_id)
+    {
+        status_block.write().await.muted = muted;
+    }
+
+    tracing::info!(
+        agent_id = %request.agent_id,
+        channel_id = %request.channel_id,
+        muted,
+        "channel mute state updated via API"
+    );
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "muted": muted,
+    })))
+}
+
+/// Purge a channel's messages and branch/worker runs while keeping the
+/// channel itself. Defaults to a dry run; pass `confirm: true` to delete.
+#[utoipa::path(
+    post,
+    path = "/api/channels/purge",
+    tag = "channels",
+    request_body = PurgeChannelRequest,
+    responses(
+        (status = 200, description = "Rows removed, or with `dry_run` the rows that would be", body = PurgeChannelResponse),
+        (status = 404, description = "Unknown agent or channel"),
+    )
+)]
+pub(super) async fn purge_channel(
+    State(state): State<Arc<ApiState>>,
+    AgentJson(request): AgentJson<PurgeChannelRequest>,
+) -> Result<Json<PurgeChannelResponse>, StatusCode> {
+    let pools = state.agent_pools.load();
+    let pool = pools.get(&request.agent_id).ok_or(StatusCode::NOT_FOUND)?;
+    let store = ChannelStore::new(pool.clone());
+
+    let dry_run = !request.confirm;
+    let counts = store
+        .purge(&request.channel_id, dry_run)
+        .await
+        .map_err(|error| {
+            tracing::error!(%error, "failed to purge channel");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    if !dry_run {
+        tracing::info!(
+            agent_id = %request.agent_id,
+            channel_id = %request.channel_id,
+            messages = counts.messages,
+            branch_runs = counts.branch_runs,
+            worker_runs = counts.worker_runs,
+            "channel purged via API"
+        );
+    }
+
+    Ok(Json(PurgeChannelResponse {
+        success: true,
+        dry_run,
+        counts,
+        total: counts.total(),
+    }))
+}
+
 fn archive_update_response_payload(archived: bool) -> serde_json::Value {
     serde_json::json!({
         "success": true,
@@ -434,7 +1023,11 @@ pub(super) async fn inspect_prompt(
         rc.as_ref(),
         &channel_state.deps.sandbox,
     );
-    let temporal_context = crate::agent::channel_prompt::TemporalContext::from_runtime(rc.as_ref());
+    let temporal_context = crate::agent::channel_prompt::TemporalContext::for_conversation(
+        rc.as_ref(),
+        &query.channel_id,
+        None,
+    );
     let current_time_line = temporal_context.current_time_line();
     let status_text = {
         let status = channel_state.status_block.read().await;
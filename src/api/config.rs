@@ -1,13 +1,14 @@
+use super::access::{AgentJson, AgentQuery, AgentScoped};
 use super::state::ApiState;
 use crate::config::ClosePolicy;
 
 use axum::Json;
-use axum::extract::{Query, State};
+use axum::extract::State;
 use axum::http::StatusCode;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, utoipa::ToSchema)]
 pub(super) struct RoutingSection {
     channel: String,
     branch: String,
@@ -18,7 +19,7 @@ pub(super) struct RoutingSection {
     rate_limit_cooldown_secs: u64,
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, utoipa::ToSchema)]
 pub(super) struct TuningSection {
     max_concurrent_branches: usize,
     max_concurrent_workers: usize,
@@ -28,14 +29,14 @@ pub(super) struct TuningSection {
     history_backfill_count: usize,
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, utoipa::ToSchema)]
 pub(super) struct CompactionSection {
     background_threshold: f32,
     aggressive_threshold: f32,
     emergency_threshold: f32,
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, utoipa::ToSchema)]
 pub(super) struct CortexSection {
     tick_interval_secs: u64,
     maintenance_interval_secs: u64,
@@ -47,13 +48,25 @@ pub(super) struct CortexSection {
     bulletin_interval_secs: u64,
     bulletin_max_words: usize,
     bulletin_max_turns: usize,
-    maintenance_decay_rate: f32,
+    maintenance_half_life_days: HalfLivesSection,
     maintenance_prune_threshold: f32,
     maintenance_min_age_days: i64,
     maintenance_merge_similarity_threshold: f32,
 }
 
-#[derive(Serialize, Debug)]
+/// Days without access for memory importance to halve, per memory type.
+#[derive(Serialize, Debug, utoipa::ToSchema)]
+pub(super) struct HalfLivesSection {
+    fact: f32,
+    preference: f32,
+    decision: f32,
+    event: f32,
+    observation: f32,
+    goal: f32,
+    todo: f32,
+}
+
+#[derive(Serialize, Debug, utoipa::ToSchema)]
 pub(super) struct WarmupSection {
     enabled: bool,
     eager_embedding_load: bool,
@@ -61,7 +74,7 @@ pub(super) struct WarmupSection {
     startup_delay_secs: u64,
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, utoipa::ToSchema)]
 pub(super) struct CoalesceSection {
     enabled: bool,
     debounce_ms: u64,
@@ -70,13 +83,14 @@ pub(super) struct CoalesceSection {
     multi_user_only: bool,
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, utoipa::ToSchema)]
 pub(super) struct MemoryPersistenceSection {
     enabled: bool,
     message_interval: usize,
+    embedding_batch_size: usize,
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, utoipa::ToSchema)]
 pub(super) struct BrowserSection {
     enabled: bool,
     headless: bool,
@@ -85,19 +99,19 @@ pub(super) struct BrowserSection {
     close_policy: String,
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, utoipa::ToSchema)]
 pub(super) struct ChannelSection {
     listen_only_mode: bool,
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, utoipa::ToSchema)]
 pub(super) struct SandboxSection {
     mode: String,
     writable_paths: Vec<String>,
     passthrough_env: Vec<String>,
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, utoipa::ToSchema)]
 pub(super) struct ProjectsSection {
     use_worktrees: bool,
     worktree_name_template: String,
@@ -107,13 +121,13 @@ pub(super) struct ProjectsSection {
     disk_usage_warning_threshold: u64,
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, utoipa::ToSchema)]
 pub(super) struct DiscordSection {
     enabled: bool,
     allow_bot_messages: bool,
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, utoipa::ToSchema)]
 pub(super) struct AgentConfigResponse {
     routing: RoutingSection,
     tuning: TuningSection,
@@ -129,12 +143,19 @@ pub(super) struct AgentConfigResponse {
     discord: DiscordSection,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
 pub(super) struct AgentConfigQuery {
     agent_id: String,
 }
 
-#[derive(Deserialize, Debug, Default)]
+impl AgentScoped for AgentConfigQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+#[derive(Deserialize, Debug, Default, utoipa::ToSchema)]
 pub(super) struct AgentConfigUpdateRequest {
     agent_id: String,
     #[serde(default)]
@@ -163,7 +184,13 @@ pub(super) struct AgentConfigUpdateRequest {
     discord: Option<DiscordUpdate>,
 }
 
-#[derive(Deserialize, Debug)]
+impl AgentScoped for AgentConfigUpdateRequest {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+#[derive(Deserialize, Debug, utoipa::ToSchema)]
 pub(super) struct RoutingUpdate {
     channel: Option<String>,
     branch: Option<String>,
@@ -174,7 +201,7 @@ pub(super) struct RoutingUpdate {
     rate_limit_cooldown_secs: Option<u64>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, utoipa::ToSchema)]
 pub(super) struct TuningUpdate {
     max_concurrent_branches: Option<usize>,
     max_concurrent_workers: Option<usize>,
@@ -184,14 +211,14 @@ pub(super) struct TuningUpdate {
     history_backfill_count: Option<usize>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, utoipa::ToSchema)]
 pub(super) struct CompactionUpdate {
     background_threshold: Option<f32>,
     aggressive_threshold: Option<f32>,
     emergency_threshold: Option<f32>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, utoipa::ToSchema)]
 pub(super) struct CortexUpdate {
     tick_interval_secs: Option<u64>,
     maintenance_interval_secs: Option<u64>,
@@ -203,13 +230,28 @@ pub(super) struct CortexUpdate {
     bulletin_interval_secs: Option<u64>,
     bulletin_max_words: Option<usize>,
     bulletin_max_turns: Option<usize>,
+    maintenance_half_life_days: Option<HalfLivesUpdate>,
+    /// Retired linear decay rate. Converted to a uniform
+    /// `maintenance_half_life_days` for the types not set in the same update.
+    #[schema(deprecated)]
     maintenance_decay_rate: Option<f32>,
     maintenance_prune_threshold: Option<f32>,
     maintenance_min_age_days: Option<i64>,
     maintenance_merge_similarity_threshold: Option<f32>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Default, utoipa::ToSchema)]
+pub(super) struct HalfLivesUpdate {
+    fact: Option<f32>,
+    preference: Option<f32>,
+    decision: Option<f32>,
+    event: Option<f32>,
+    observation: Option<f32>,
+    goal: Option<f32>,
+    todo: Option<f32>,
+}
+
+#[derive(Deserialize, Debug, utoipa::ToSchema)]
 pub(super) struct WarmupUpdate {
     enabled: Option<bool>,
     eager_embedding_load: Option<bool>,
@@ -217,7 +259,7 @@ pub(super) struct WarmupUpdate {
     startup_delay_secs: Option<u64>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, utoipa::ToSchema)]
 pub(super) struct CoalesceUpdate {
     enabled: Option<bool>,
     debounce_ms: Option<u64>,
@@ -226,34 +268,36 @@ pub(super) struct CoalesceUpdate {
     multi_user_only: Option<bool>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, utoipa::ToSchema)]
 pub(super) struct MemoryPersistenceUpdate {
     enabled: Option<bool>,
     message_interval: Option<usize>,
+    embedding_batch_size: Option<usize>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, utoipa::ToSchema)]
 pub(super) struct BrowserUpdate {
     enabled: Option<bool>,
     headless: Option<bool>,
     evaluate_enabled: Option<bool>,
     persist_session: Option<bool>,
+    #[schema(value_type = Option<String>)]
     close_policy: Option<ClosePolicy>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, utoipa::ToSchema)]
 pub(super) struct ChannelUpdate {
     listen_only_mode: Option<bool>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, utoipa::ToSchema)]
 pub(super) struct SandboxUpdate {
     mode: Option<String>,
     writable_paths: Option<Vec<String>>,
     passthrough_env: Option<Vec<String>>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, utoipa::ToSchema)]
 pub(super) struct ProjectsUpdate {
     use_worktrees: Option<bool>,
     worktree_name_template: Option<String>,
@@ -263,16 +307,26 @@ pub(super) struct ProjectsUpdate {
     disk_usage_warning_threshold: Option<u64>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, utoipa::ToSchema)]
 pub(super) struct DiscordUpdate {
     allow_bot_messages: Option<bool>,
 }
 
 /// Get the resolved configuration for an agent.
 /// Reads live values from the agent's RuntimeConfig (hot-reloaded via ArcSwap).
+#[utoipa::path(
+    get,
+    path = "/api/agents/config",
+    tag = "agents",
+    params(AgentConfigQuery),
+    responses(
+        (status = 200, body = AgentConfigResponse),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
 pub(super) async fn get_agent_config(
     State(state): State<Arc<ApiState>>,
-    Query(query): Query<AgentConfigQuery>,
+    AgentQuery(query): AgentQuery<AgentConfigQuery>,
 ) -> Result<Json<AgentConfigResponse>, StatusCode> {
     let runtime_configs = state.runtime_configs.load();
     let rc = runtime_configs
@@ -324,10 +378,18 @@ pub(super) async fn get_agent_config(
             bulletin_interval_secs: cortex.bulletin_interval_secs,
             bulletin_max_words: cortex.bulletin_max_words,
             bulletin_max_turns: cortex.bulletin_max_turns,
-            maintenance_decay_rate: cortex.maintenance_decay_rate,
             maintenance_prune_threshold: cortex.maintenance_prune_threshold,
             maintenance_min_age_days: cortex.maintenance_min_age_days,
             maintenance_merge_similarity_threshold: cortex.maintenance_merge_similarity_threshold,
+            maintenance_half_life_days: HalfLivesSection {
+                fact: cortex.maintenance_half_life_days.fact,
+                preference: cortex.maintenance_half_life_days.preference,
+                decision: cortex.maintenance_half_life_days.decision,
+                event: cortex.maintenance_half_life_days.event,
+                observation: cortex.maintenance_half_life_days.observation,
+                goal: cortex.maintenance_half_life_days.goal,
+                todo: cortex.maintenance_half_life_days.todo,
+            },
         },
         warmup: WarmupSection {
             enabled: warmup.enabled,
@@ -345,6 +407,7 @@ pub(super) async fn get_agent_config(
         memory_persistence: MemoryPersistenceSection {
             enabled: memory_persistence.enabled,
             message_interval: memory_persistence.message_interval,
+            embedding_batch_size: memory_persistence.embedding_batch_size,
         },
         browser: BrowserSection {
             enabled: browser.enabled,
@@ -398,10 +461,22 @@ pub(super) async fn get_agent_config(
 }
 
 /// Update agent configuration by editing config.toml with toml_edit.
-/// This preserves formatting and comments while writing the new values.
+/// This preserves formatting and comments while writing the new values, then
+/// hot-reloads the agent and returns its freshly resolved config.
+#[utoipa::path(
+    put,
+    path = "/api/agents/config",
+    tag = "agents",
+    request_body = AgentConfigUpdateRequest,
+    responses(
+        (status = 200, body = AgentConfigResponse),
+        (status = 400, description = "The update leaves config.toml unloadable"),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
 pub(super) async fn update_agent_config(
     State(state): State<Arc<ApiState>>,
-    axum::Json(request): axum::Json<AgentConfigUpdateRequest>,
+    AgentJson(request): AgentJson<AgentConfigUpdateRequest>,
 ) -> Result<Json<AgentConfigResponse>, StatusCode> {
     let config_path = state.config_path.read().await.clone();
     if config_path.as_os_str().is_empty() {
@@ -427,50 +502,22 @@ pub(super) async fn update_agent_config(
             StatusCode::INTERNAL_SERVER_ERROR
         })?;
 
-    let agent_idx = find_or_create_agent_table(&mut doc, &request.agent_id)?;
-
-    if let Some(routing) = &request.routing {
-        update_routing_table(&mut doc, agent_idx, routing)?;
-    }
-    if let Some(tuning) = &request.tuning {
-        update_tuning_table(&mut doc, agent_idx, tuning)?;
-    }
-    if let Some(compaction) = &request.compaction {
-        update_compaction_table(&mut doc, agent_idx, compaction)?;
-    }
-    if let Some(cortex) = &request.cortex {
-        update_cortex_table(&mut doc, agent_idx, cortex)?;
-    }
-    if let Some(warmup) = &request.warmup {
-        update_warmup_table(&mut doc, agent_idx, warmup)?;
-    }
-    if let Some(coalesce) = &request.coalesce {
-        update_coalesce_table(&mut doc, agent_idx, coalesce)?;
-    }
-    if let Some(memory_persistence) = &request.memory_persistence {
-        update_memory_persistence_table(&mut doc, agent_idx, memory_persistence)?;
-    }
-    if let Some(browser) = &request.browser {
-        update_browser_table(&mut doc, agent_idx, browser)?;
-    }
-    if let Some(channel) = &request.channel {
-        update_channel_table(&mut doc, agent_idx, channel)?;
-    }
-    if let Some(sandbox) = &request.sandbox {
-        update_sandbox_table(&mut doc, agent_idx, sandbox)?;
-    }
-    if let Some(projects) = &request.projects {
-        update_projects_table(&mut doc, agent_idx, projects)?;
-    }
-    if let Some(discord) = &request.discord {
-        update_discord_table(&mut doc, discord)?;
-    }
+    apply_agent_config_update(&mut doc, &request).map_err(|(_, status)| status)?;
 
+    // Load the result against the real instance directory before writing,
+    // so an update that can't be loaded is rejected with the file untouched.
     let updated_content = doc.to_string();
-    if let Err(error) = crate::config::Config::validate_toml(&updated_content) {
-        tracing::warn!(%error, "rejected config API update due to invalid resulting TOML");
-        return Err(StatusCode::BAD_REQUEST);
-    }
+    let instance_dir = config_path
+        .parent()
+        .map(std::path::Path::to_path_buf)
+        .unwrap_or_else(|| std::path::PathBuf::from("."));
+    let new_config = match crate::config::Config::load_from_str(&updated_content, &instance_dir) {
+        Ok(new_config) => new_config,
+        Err(error) => {
+            tracing::warn!(%error, "rejected config API update due to invalid resulting TOML");
+            return Err(StatusCode::BAD_REQUEST);
+        }
+    };
 
     tokio::fs::write(&config_path, updated_content)
         .await
@@ -484,50 +531,330 @@ pub(super) async fn update_agent_config(
 
     tracing::info!(agent_id = %request.agent_id, "config.toml updated via API");
 
-    match crate::config::Config::load_from_path(&config_path) {
-        Ok(new_config) => {
-            // Keep in-memory defaults fresh so newly created agents inherit
-            // the latest routing values.
-            state.set_defaults_config(new_config.defaults.clone()).await;
-
-            let runtime_configs = state.runtime_configs.load();
-            let mcp_managers = state.mcp_managers.load();
-            if let (Some(rc), Some(mcp_manager)) = (
-                runtime_configs.get(&request.agent_id).cloned(),
-                mcp_managers.get(&request.agent_id).cloned(),
-            ) {
-                rc.reload_config(&new_config, &request.agent_id, &mcp_manager)
-                    .await;
-            }
-            if request.discord.is_some()
-                && let Some(discord_config) = &new_config.messaging.discord
-            {
-                let new_perms = crate::config::DiscordPermissions::from_config(
-                    discord_config,
-                    &new_config.bindings,
-                );
-                let perms = state.discord_permissions.read().await;
-                if let Some(arc_swap) = perms.as_ref() {
-                    arc_swap.store(std::sync::Arc::new(new_perms));
-                }
-            }
-        }
-        Err(error) => {
-            tracing::warn!(%error, "config.toml written but failed to reload immediately");
+    // Keep in-memory defaults fresh so newly created agents inherit the
+    // latest routing values.
+    state.set_defaults_config(new_config.defaults.clone()).await;
+
+    let runtime_configs = state.runtime_configs.load();
+    let mcp_managers = state.mcp_managers.load();
+    if let (Some(rc), Some(mcp_manager)) = (
+        runtime_configs.get(&request.agent_id).cloned(),
+        mcp_managers.get(&request.agent_id).cloned(),
+    ) {
+        rc.reload_config(&new_config, &request.agent_id, &mcp_manager)
+            .await;
+    }
+    if request.discord.is_some()
+        && let Some(discord_config) = &new_config.messaging.discord
+    {
+        let new_perms =
+            crate::config::DiscordPermissions::from_config(discord_config, &new_config.bindings);
+        let perms = state.discord_permissions.read().await;
+        if let Some(arc_swap) = perms.as_ref() {
+            arc_swap.store(std::sync::Arc::new(new_perms));
         }
     }
 
     get_agent_config(
         State(state),
-        Query(AgentConfigQuery {
+        AgentQuery(AgentConfigQuery {
             agent_id: request.agent_id,
         }),
     )
     .await
 }
 
+#[derive(Serialize, Debug, PartialEq, utoipa::ToSchema)]
+pub(super) struct ConfigValidationIssue {
+    /// Dotted path of the offending setting, e.g. `routing.channel`.
+    field: String,
+    message: String,
+}
+
+impl ConfigValidationIssue {
+    fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
+#[derive(Serialize, Debug, utoipa::ToSchema)]
+pub(super) struct ConfigValidationResponse {
+    valid: bool,
+    errors: Vec<ConfigValidationIssue>,
+}
+
+/// Dry-run an agent config update: apply it to an in-memory copy of
+/// config.toml, resolve the agent the way the runtime does, and report what
+/// would be rejected or break at runtime. Nothing is written to disk.
+#[utoipa::path(
+    post,
+    path = "/api/agents/config/validate",
+    tag = "agents",
+    request_body = AgentConfigUpdateRequest,
+    responses((status = 200, body = ConfigValidationResponse))
+)]
+pub(super) async fn validate_agent_config(
+    State(state): State<Arc<ApiState>>,
+    AgentJson(request): AgentJson<AgentConfigUpdateRequest>,
+) -> Result<Json<ConfigValidationResponse>, StatusCode> {
+    let config_path = state.config_path.read().await.clone();
+    if config_path.as_os_str().is_empty() {
+        tracing::error!("config_path not set in ApiState");
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    let config_content = tokio::fs::read_to_string(&config_path)
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, "failed to read config.toml");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    let mut doc = config_content
+        .parse::<toml_edit::DocumentMut>()
+        .map_err(|error| {
+            tracing::warn!(%error, "failed to parse config.toml");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let mut errors = Vec::new();
+    if let Err((section, _)) = apply_agent_config_update(&mut doc, &request) {
+        errors.push(ConfigValidationIssue::new(
+            section,
+            "contains a value that is out of range or can't be stored in config.toml",
+        ));
+        return Ok(Json(ConfigValidationResponse {
+            valid: false,
+            errors,
+        }));
+    }
+
+    let instance_dir = config_path
+        .parent()
+        .map(std::path::Path::to_path_buf)
+        .unwrap_or_else(|| std::path::PathBuf::from("."));
+    let config = match crate::config::Config::load_from_str(&doc.to_string(), &instance_dir) {
+        Ok(config) => config,
+        Err(error) => {
+            errors.push(ConfigValidationIssue::new("config", format!("{error:#}")));
+            return Ok(Json(ConfigValidationResponse {
+                valid: false,
+                errors,
+            }));
+        }
+    };
+
+    let Some(agent) = config
+        .agents
+        .iter()
+        .find(|agent| agent.id == request.agent_id)
+    else {
+        errors.push(ConfigValidationIssue::new(
+            "agent_id",
+            format!("agent '{}' is not defined", request.agent_id),
+        ));
+        return Ok(Json(ConfigValidationResponse {
+            valid: false,
+            errors,
+        }));
+    };
+    let resolved = agent.resolve(&config.instance_dir, &config.defaults);
+    errors.extend(check_agent_thresholds(
+        &resolved.compaction,
+        &resolved.cortex,
+    ));
+
+    // Loading corrects what it can; what's left would show on /api/status.
+    let agent_scope = format!("agents.{}", request.agent_id);
+    errors.extend(
+        config
+            .issues
+            .iter()
+            .filter(|issue| {
+                issue.resolution == crate::config::IssueResolution::Unresolved
+                    && (issue.scope == "defaults" || issue.scope == agent_scope)
+            })
+            .map(|issue| ConfigValidationIssue::new(issue.field.clone(), issue.message.clone())),
+    );
+
+    let llm_manager = state.llm_manager.read().await.clone();
+    for (field, model) in routing_models(&resolved.routing) {
+        let provider = crate::llm::routing::provider_from_model(model).to_lowercase();
+        let provider_available = config.llm.providers.contains_key(&provider)
+            || match &llm_manager {
+                Some(manager) => manager.has_provider_credentials(&provider).await,
+                None => false,
+            };
+        if !provider_available {
+            errors.push(ConfigValidationIssue::new(
+                field,
+                format!("model '{model}' uses provider '{provider}', which has no credentials configured"),
+            ));
+        } else if super::models::catalog_lists_model(model).await == Some(false) {
+            errors.push(ConfigValidationIssue::new(
+                field,
+                format!("unknown model '{model}' for provider '{provider}'"),
+            ));
+        }
+    }
+
+    Ok(Json(ConfigValidationResponse {
+        valid: errors.is_empty(),
+        errors,
+    }))
+}
+
+/// Models the agent routes to, keyed by config field. Unset slots are skipped.
+fn routing_models(routing: &crate::llm::routing::RoutingConfig) -> Vec<(String, &str)> {
+    let mut models: Vec<(String, &str)> = [
+        ("routing.channel", routing.channel.as_str()),
+        ("routing.branch", routing.branch.as_str()),
+        ("routing.worker", routing.worker.as_str()),
+        ("routing.compactor", routing.compactor.as_str()),
+        ("routing.cortex", routing.cortex.as_str()),
+        ("routing.voice", routing.voice.as_str()),
+    ]
+    .into_iter()
+    .map(|(field, model)| (field.to_string(), model))
+    .collect();
+    let mut task_types: Vec<_> = routing.task_overrides.iter().collect();
+    task_types.sort();
+    for (task_type, model) in task_types {
+        models.push((
+            format!("routing.task_overrides.{task_type}"),
+            model.as_str(),
+        ));
+    }
+    models.retain(|(_, model)| !model.trim().is_empty());
+    models
+}
+
+/// Range checks on resolved thresholds the runtime relies on but config
+/// parsing accepts.
+fn check_agent_thresholds(
+    compaction: &crate::config::CompactionConfig,
+    cortex: &crate::config::CortexConfig,
+) -> Vec<ConfigValidationIssue> {
+    let mut errors = Vec::new();
+    let mut unit_interval = |field: &str, value: f32, allow_zero: bool| {
+        let lower_ok = if allow_zero {
+            value >= 0.0
+        } else {
+            value > 0.0
+        };
+        if !value.is_finite() || !lower_ok || value > 1.0 {
+            let range = if allow_zero { "[0, 1]" } else { "(0, 1]" };
+            errors.push(ConfigValidationIssue::new(
+                field,
+                format!("{value} is outside {range}"),
+            ));
+        }
+    };
+
+    unit_interval(
+        "compaction.background_threshold",
+        compaction.background_threshold,
+        false,
+    );
+    unit_interval(
+        "compaction.aggressive_threshold",
+        compaction.aggressive_threshold,
+        false,
+    );
+    unit_interval(
+        "compaction.emergency_threshold",
+        compaction.emergency_threshold,
+        false,
+    );
+
+    unit_interval(
+        "cortex.maintenance_prune_threshold",
+        cortex.maintenance_prune_threshold,
+        true,
+    );
+    unit_interval(
+        "cortex.maintenance_merge_similarity_threshold",
+        cortex.maintenance_merge_similarity_threshold,
+        true,
+    );
+
+    if !(compaction.background_threshold <= compaction.aggressive_threshold
+        && compaction.aggressive_threshold <= compaction.emergency_threshold)
+    {
+        errors.push(ConfigValidationIssue::new(
+            "compaction",
+            "thresholds must satisfy background <= aggressive <= emergency",
+        ));
+    }
+    if cortex.maintenance_interval_secs == 0 {
+        errors.push(ConfigValidationIssue::new(
+            "cortex.maintenance_interval_secs",
+            "must be at least 1",
+        ));
+    }
+    if cortex.maintenance_min_age_days < 0 {
+        errors.push(ConfigValidationIssue::new(
+            "cortex.maintenance_min_age_days",
+            "must not be negative",
+        ));
+    }
+    errors
+}
+
 // -- TOML edit helpers --
 
+/// Apply every section of an update request to `doc`. On failure, returns
+/// the section that was rejected along with the status to respond with.
+fn apply_agent_config_update(
+    doc: &mut toml_edit::DocumentMut,
+    request: &AgentConfigUpdateRequest,
+) -> Result<(), (&'static str, StatusCode)> {
+    let agent_idx = find_or_create_agent_table(doc, &request.agent_id)
+        .map_err(|status| ("agent_id", status))?;
+
+    if let Some(routing) = &request.routing {
+        update_routing_table(doc, agent_idx, routing).map_err(|status| ("routing", status))?;
+    }
+    if let Some(tuning) = &request.tuning {
+        update_tuning_table(doc, agent_idx, tuning).map_err(|status| ("tuning", status))?;
+    }
+    if let Some(compaction) = &request.compaction {
+        update_compaction_table(doc, agent_idx, compaction)
+            .map_err(|status| ("compaction", status))?;
+    }
+    if let Some(cortex) = &request.cortex {
+        update_cortex_table(doc, agent_idx, cortex).map_err(|status| ("cortex", status))?;
+    }
+    if let Some(warmup) = &request.warmup {
+        update_warmup_table(doc, agent_idx, warmup).map_err(|status| ("warmup", status))?;
+    }
+    if let Some(coalesce) = &request.coalesce {
+        update_coalesce_table(doc, agent_idx, coalesce).map_err(|status| ("coalesce", status))?;
+    }
+    if let Some(memory_persistence) = &request.memory_persistence {
+        update_memory_persistence_table(doc, agent_idx, memory_persistence)
+            .map_err(|status| ("memory_persistence", status))?;
+    }
+    if let Some(browser) = &request.browser {
+        update_browser_table(doc, agent_idx, browser).map_err(|status| ("browser", status))?;
+    }
+    if let Some(channel) = &request.channel {
+        update_channel_table(doc, agent_idx, channel).map_err(|status| ("channel", status))?;
+    }
+    if let Some(sandbox) = &request.sandbox {
+        update_sandbox_table(doc, agent_idx, sandbox).map_err(|status| ("sandbox", status))?;
+    }
+    if let Some(projects) = &request.projects {
+        update_projects_table(doc, agent_idx, projects).map_err(|status| ("projects", status))?;
+    }
+    if let Some(discord) = &request.discord {
+        update_discord_table(doc, discord).map_err(|status| ("discord", status))?;
+    }
+    Ok(())
+}
+
 /// Find the index of an agent table in the [[agents]] array, or create a new one.
 pub(super) fn find_or_create_agent_table(
     doc: &mut toml_edit::DocumentMut,
@@ -726,10 +1053,6 @@ fn update_cortex_table(
         table["maintenance_interval_secs"] =
             toml_edit::value(to_i64_from_u64("maintenance_interval_secs", v)?);
     }
-    if let Some(v) = cortex.maintenance_decay_rate {
-        validate_maintenance_unit_interval("maintenance_decay_rate", v)?;
-        table["maintenance_decay_rate"] = toml_edit::value(v as f64);
-    }
     if let Some(v) = cortex.maintenance_prune_threshold {
         validate_maintenance_unit_interval("maintenance_prune_threshold", v)?;
         table["maintenance_prune_threshold"] = toml_edit::value(v as f64);
@@ -748,6 +1071,112 @@ fn update_cortex_table(
         validate_maintenance_unit_interval("maintenance_merge_similarity_threshold", v)?;
         table["maintenance_merge_similarity_threshold"] = toml_edit::value(v as f64);
     }
+    update_half_lives_table(table, cortex)?;
+    Ok(())
+}
+
+/// Write per-type decay half-lives. A legacy `maintenance_decay_rate`, from
+/// the update or already in the file, is converted to a uniform half-life for
+/// the types that have none, the same way config loading treats it, and the
+/// legacy key is dropped from the file. An out-of-range rate in the update is
+/// rejected; one already in the file is dropped, as loading ignores it.
+fn update_half_lives_table(
+    table: &mut toml_edit::Table,
+    cortex: &CortexUpdate,
+) -> Result<(), StatusCode> {
+    let legacy_rate = match cortex.maintenance_decay_rate {
+        Some(rate) => {
+            validate_maintenance_unit_interval("maintenance_decay_rate", rate)?;
+            Some(rate)
+        }
+        None => table
+            .get("maintenance_decay_rate")
+            .and_then(|item| {
+                item.as_float()
+                    .or_else(|| item.as_integer().map(|v| v as f64))
+            })
+            .map(|rate| rate as f32),
+    };
+    let legacy = legacy_rate.and_then(|rate| {
+        let converted = crate::memory::maintenance::DecayHalfLives::from_legacy_decay_rate(rate);
+        match &converted {
+            Some(converted) => tracing::warn!(
+                maintenance_decay_rate = rate,
+                half_life_days = converted.fact,
+                "maintenance_decay_rate is deprecated, converted to a uniform half-life"
+            ),
+            None => tracing::warn!(
+                maintenance_decay_rate = rate,
+                "maintenance_decay_rate is deprecated and outside [0, 1], dropping it"
+            ),
+        }
+        converted
+    });
+    let Some(explicit) = cortex.maintenance_half_life_days.as_ref() else {
+        if legacy.is_none() {
+            if legacy_rate.is_some() {
+                table.remove("maintenance_decay_rate");
+            }
+            return Ok(());
+        }
+        return write_half_lives(table, &HalfLivesUpdate::default(), legacy);
+    };
+
+    for (field, days) in [
+        ("fact", explicit.fact),
+        ("preference", explicit.preference),
+        ("decision", explicit.decision),
+        ("event", explicit.event),
+        ("observation", explicit.observation),
+        ("goal", explicit.goal),
+        ("todo", explicit.todo),
+    ] {
+        if let Some(days) = days
+            && (!days.is_finite() || days < 0.0)
+        {
+            tracing::warn!(
+                field = %format!("maintenance_half_life_days.{field}"),
+                days,
+                "maintenance half-life must be >= 0"
+            );
+            return Err(StatusCode::BAD_REQUEST);
+        }
+    }
+    write_half_lives(table, explicit, legacy)
+}
+
+fn write_half_lives(
+    table: &mut toml_edit::Table,
+    explicit: &HalfLivesUpdate,
+    legacy: Option<crate::memory::maintenance::DecayHalfLives>,
+) -> Result<(), StatusCode> {
+    table.remove("maintenance_decay_rate");
+    let half_lives = get_or_create_subtable(table, "maintenance_half_life_days")?;
+    for (field, explicit, legacy) in [
+        ("fact", explicit.fact, legacy.map(|h| h.fact)),
+        (
+            "preference",
+            explicit.preference,
+            legacy.map(|h| h.preference),
+        ),
+        ("decision", explicit.decision, legacy.map(|h| h.decision)),
+        ("event", explicit.event, legacy.map(|h| h.event)),
+        (
+            "observation",
+            explicit.observation,
+            legacy.map(|h| h.observation),
+        ),
+        ("goal", explicit.goal, legacy.map(|h| h.goal)),
+        ("todo", explicit.todo, legacy.map(|h| h.todo)),
+    ] {
+        if let Some(days) = explicit {
+            half_lives[field] = toml_edit::value(f64::from(days));
+        } else if let Some(days) = legacy
+            && !half_lives.contains_key(field)
+        {
+            half_lives[field] = toml_edit::value(f64::from(days));
+        }
+    }
     Ok(())
 }
 
@@ -813,6 +1242,9 @@ fn update_memory_persistence_table(
     if let Some(v) = memory_persistence.message_interval {
         table["message_interval"] = toml_edit::value(v as i64);
     }
+    if let Some(v) = memory_persistence.embedding_batch_size {
+        table["embedding_batch_size"] = toml_edit::value(v as i64);
+    }
     Ok(())
 }
 
@@ -936,6 +1368,35 @@ fn update_discord_table(
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_check_agent_thresholds_reports_each_bad_field() {
+        let compaction = crate::config::CompactionConfig::default();
+        let cortex = crate::config::CortexConfig::default();
+        assert!(check_agent_thresholds(&compaction, &cortex).is_empty());
+
+        let compaction = crate::config::CompactionConfig {
+            background_threshold: 0.9,
+            aggressive_threshold: 0.8,
+            emergency_threshold: 1.5,
+        };
+        let cortex = crate::config::CortexConfig {
+            maintenance_interval_secs: 0,
+            ..Default::default()
+        };
+        let fields: Vec<String> = check_agent_thresholds(&compaction, &cortex)
+            .into_iter()
+            .map(|issue| issue.field)
+            .collect();
+        assert_eq!(
+            fields,
+            [
+                "compaction.emergency_threshold",
+                "compaction",
+                "cortex.maintenance_interval_secs",
+            ]
+        );
+    }
+
     #[test]
     fn test_update_warmup_table_writes_values() {
         let mut doc: toml_edit::DocumentMut = r#"
@@ -1099,6 +1560,7 @@ id = "main"
             bulletin_interval_secs: None,
             bulletin_max_words: None,
             bulletin_max_turns: None,
+            maintenance_half_life_days: None,
             maintenance_decay_rate: None,
             maintenance_prune_threshold: None,
             maintenance_min_age_days: None,
@@ -1123,6 +1585,7 @@ id = "main"
             bulletin_interval_secs: None,
             bulletin_max_words: None,
             bulletin_max_turns: None,
+            maintenance_half_life_days: None,
             maintenance_decay_rate: None,
             maintenance_prune_threshold: None,
             maintenance_min_age_days: None,
@@ -1146,7 +1609,7 @@ id = "main"
         let agent_idx =
             find_or_create_agent_table(&mut doc, "main").expect("failed to find/create agent");
 
-        let invalid_decay = CortexUpdate {
+        let invalid_prune_threshold = CortexUpdate {
             tick_interval_secs: None,
             maintenance_interval_secs: None,
             worker_timeout_secs: None,
@@ -1157,13 +1620,14 @@ id = "main"
             bulletin_interval_secs: None,
             bulletin_max_words: None,
             bulletin_max_turns: None,
-            maintenance_decay_rate: Some(1.1),
-            maintenance_prune_threshold: None,
+            maintenance_half_life_days: None,
+            maintenance_decay_rate: None,
+            maintenance_prune_threshold: Some(1.1),
             maintenance_min_age_days: None,
             maintenance_merge_similarity_threshold: None,
         };
         assert_eq!(
-            update_cortex_table(&mut doc, agent_idx, &invalid_decay),
+            update_cortex_table(&mut doc, agent_idx, &invalid_prune_threshold),
             Err(StatusCode::BAD_REQUEST)
         );
 
@@ -1178,6 +1642,7 @@ id = "main"
             bulletin_interval_secs: None,
             bulletin_max_words: None,
             bulletin_max_turns: None,
+            maintenance_half_life_days: None,
             maintenance_decay_rate: None,
             maintenance_prune_threshold: None,
             maintenance_min_age_days: Some(-1),
@@ -1199,6 +1664,7 @@ id = "main"
             bulletin_interval_secs: None,
             bulletin_max_words: None,
             bulletin_max_turns: None,
+            maintenance_half_life_days: None,
             maintenance_decay_rate: None,
             maintenance_prune_threshold: None,
             maintenance_min_age_days: None,
@@ -1232,7 +1698,8 @@ id = "main"
             bulletin_interval_secs: Some(120),
             bulletin_max_words: Some(4000),
             bulletin_max_turns: Some(5),
-            maintenance_decay_rate: Some(0.16),
+            maintenance_half_life_days: None,
+            maintenance_decay_rate: None,
             maintenance_prune_threshold: Some(0.17),
             maintenance_min_age_days: Some(15),
             maintenance_merge_similarity_threshold: Some(0.98),
@@ -1268,7 +1735,6 @@ id = "main"
         assert_eq!(cortex["bulletin_interval_secs"].as_integer(), Some(120));
         assert_eq!(cortex["bulletin_max_words"].as_integer(), Some(4000));
         assert_eq!(cortex["bulletin_max_turns"].as_integer(), Some(5));
-        assert!((cortex["maintenance_decay_rate"].as_float().unwrap_or(0.0) - 0.16).abs() < 1e-6);
         assert!(
             (cortex["maintenance_prune_threshold"]
                 .as_float()
@@ -1311,6 +1777,7 @@ id = "main"
             bulletin_interval_secs: None,
             bulletin_max_words: None,
             bulletin_max_turns: None,
+            maintenance_half_life_days: None,
             maintenance_decay_rate: None,
             maintenance_prune_threshold: None,
             maintenance_min_age_days: None,
@@ -1330,7 +1797,8 @@ id = "main"
             bulletin_interval_secs: None,
             bulletin_max_words: None,
             bulletin_max_turns: None,
-            maintenance_decay_rate: Some(0.2),
+            maintenance_half_life_days: None,
+            maintenance_decay_rate: None,
             maintenance_prune_threshold: None,
             maintenance_min_age_days: None,
             maintenance_merge_similarity_threshold: Some(0.85),
@@ -1353,7 +1821,6 @@ id = "main"
             cortex["maintenance_interval_secs"].as_integer(),
             Some(4_800)
         );
-        assert!((cortex["maintenance_decay_rate"].as_float().unwrap_or(0.0) - 0.2).abs() < 1e-6);
         assert!(
             (cortex["maintenance_merge_similarity_threshold"]
                 .as_float()
@@ -1365,4 +1832,69 @@ id = "main"
         assert!(cortex.get("worker_timeout_secs").is_none());
         assert!(cortex.get("maintenance_prune_threshold").is_none());
     }
+
+    #[test]
+    fn test_update_cortex_table_converts_legacy_decay_rate_to_half_lives() {
+        let mut doc: toml_edit::DocumentMut = r#"
+[[agents]]
+id = "main"
+
+[agents.cortex]
+maintenance_decay_rate = 0.05
+
+[agents.cortex.maintenance_half_life_days]
+observation = 3.0
+"#
+        .parse()
+        .expect("failed to parse test TOML");
+
+        let agent_idx =
+            find_or_create_agent_table(&mut doc, "main").expect("failed to find/create agent");
+        let update = |half_lives: HalfLivesUpdate| CortexUpdate {
+            tick_interval_secs: None,
+            maintenance_interval_secs: None,
+            worker_timeout_secs: None,
+            branch_timeout_secs: None,
+            detached_worker_timeout_retry_limit: None,
+            supervisor_kill_budget_per_tick: None,
+            circuit_breaker_threshold: None,
+            bulletin_interval_secs: None,
+            bulletin_max_words: None,
+            bulletin_max_turns: None,
+            maintenance_half_life_days: Some(half_lives),
+            maintenance_decay_rate: None,
+            maintenance_prune_threshold: None,
+            maintenance_min_age_days: None,
+            maintenance_merge_similarity_threshold: None,
+        };
+
+        let negative_half_life = update(HalfLivesUpdate {
+            fact: Some(-1.0),
+            ..Default::default()
+        });
+        assert_eq!(
+            update_cortex_table(&mut doc, agent_idx, &negative_half_life),
+            Err(StatusCode::BAD_REQUEST)
+        );
+
+        let event = update(HalfLivesUpdate {
+            event: Some(20.0),
+            ..Default::default()
+        });
+        update_cortex_table(&mut doc, agent_idx, &event).expect("failed to apply update");
+
+        let cortex = doc["agents"]
+            .as_array_of_tables()
+            .and_then(|agents| agents.get(agent_idx))
+            .and_then(|agent| agent.get("cortex"))
+            .and_then(|item| item.as_table())
+            .expect("missing cortex table");
+        assert!(cortex.get("maintenance_decay_rate").is_none());
+        let half_lives = cortex["maintenance_half_life_days"]
+            .as_table()
+            .expect("missing half-life table");
+        assert_eq!(half_lives["event"].as_float(), Some(20.0));
+        assert_eq!(half_lives["observation"].as_float(), Some(3.0));
+        assert_eq!(half_lives["fact"].as_float(), Some(10.0));
+    }
 }
@@ -0,0 +1,238 @@
+//! Managing an agent's contact book (see [`crate::contacts`]).
+
+use super::access::{AgentJson, AgentQuery, AgentScoped};
+use super::state::ApiState;
+
+use crate::contacts::{Contact, ContactHandle, ContactStore, ContactUpdate, NewContact};
+
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Deserialize, utoipa::IntoParams)]
+pub(super) struct ContactListQuery {
+    agent_id: String,
+    /// Case-insensitive match on name, aliases, emails, notes, or handles.
+    #[serde(default)]
+    q: Option<String>,
+    #[serde(default = "default_limit")]
+    limit: i64,
+}
+
+impl AgentScoped for ContactListQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+fn default_limit() -> i64 {
+    100
+}
+
+#[derive(Deserialize, utoipa::IntoParams)]
+pub(super) struct ContactQuery {
+    agent_id: String,
+}
+
+impl AgentScoped for ContactQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
+pub(super) struct CreateContactRequest {
+    agent_id: String,
+    #[serde(flatten)]
+    contact: NewContact,
+}
+
+impl AgentScoped for CreateContactRequest {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
+pub(super) struct UpdateContactRequest {
+    agent_id: String,
+    #[serde(flatten)]
+    update: ContactUpdate,
+}
+
+impl AgentScoped for UpdateContactRequest {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct ContactListResponse {
+    contacts: Vec<Contact>,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct ContactResponse {
+    contact: Contact,
+}
+
+fn contact_store(state: &ApiState, agent_id: &str) -> Result<ContactStore, StatusCode> {
+    let pools = state.agent_pools.load();
+    let pool = pools.get(agent_id).ok_or(StatusCode::NOT_FOUND)?;
+    Ok(ContactStore::new(pool.clone()))
+}
+
+fn valid_handles(handles: &[ContactHandle]) -> bool {
+    handles
+        .iter()
+        .all(|handle| !handle.platform.trim().is_empty() && !handle.handle.trim().is_empty())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/agents/contacts",
+    tag = "agents",
+    params(ContactListQuery),
+    responses(
+        (status = 200, description = "Contacts by name", body = ContactListResponse),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
+pub(super) async fn list_contacts(
+    State(state): State<Arc<ApiState>>,
+    AgentQuery(query): AgentQuery<ContactListQuery>,
+) -> Result<Json<ContactListResponse>, StatusCode> {
+    let store = contact_store(&state, &query.agent_id)?;
+    let contacts = store
+        .list(query.q.as_deref(), query.limit.clamp(1, 500))
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, agent_id = %query.agent_id, "failed to list contacts");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    Ok(Json(ContactListResponse { contacts }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/agents/contacts",
+    tag = "agents",
+    request_body = CreateContactRequest,
+    responses(
+        (status = 200, description = "Created. Handles already linked to another contact move to this one", body = ContactResponse),
+        (status = 400, description = "Empty name or incomplete handle"),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
+pub(super) async fn create_contact(
+    State(state): State<Arc<ApiState>>,
+    AgentJson(request): AgentJson<CreateContactRequest>,
+) -> Result<Json<ContactResponse>, StatusCode> {
+    let store = contact_store(&state, &request.agent_id)?;
+    if request.contact.name.trim().is_empty() || !valid_handles(&request.contact.handles) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let contact = store.create(request.contact).await.map_err(|error| {
+        tracing::warn!(%error, agent_id = %request.agent_id, "failed to create contact");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    Ok(Json(ContactResponse { contact }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/agents/contacts/{id}",
+    tag = "agents",
+    params(("id" = String, Path, description = "Contact id"), ContactQuery),
+    responses(
+        (status = 200, body = ContactResponse),
+        (status = 404, description = "Unknown agent or contact"),
+    )
+)]
+pub(super) async fn get_contact(
+    State(state): State<Arc<ApiState>>,
+    Path(id): Path<String>,
+    AgentQuery(query): AgentQuery<ContactQuery>,
+) -> Result<Json<ContactResponse>, StatusCode> {
+    let store = contact_store(&state, &query.agent_id)?;
+    let contact = store
+        .get(&id)
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, contact_id = %id, "failed to load contact");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    Ok(Json(ContactResponse { contact }))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/agents/contacts/{id}",
+    tag = "agents",
+    params(("id" = String, Path, description = "Contact id")),
+    request_body = UpdateContactRequest,
+    responses(
+        (status = 200, description = "Updated", body = ContactResponse),
+        (status = 400, description = "Empty name or incomplete handle"),
+        (status = 404, description = "Unknown agent or contact"),
+    )
+)]
+pub(super) async fn update_contact(
+    State(state): State<Arc<ApiState>>,
+    Path(id): Path<String>,
+    AgentJson(request): AgentJson<UpdateContactRequest>,
+) -> Result<Json<ContactResponse>, StatusCode> {
+    let store = contact_store(&state, &request.agent_id)?;
+    let empty_name = request
+        .update
+        .name
+        .as_deref()
+        .is_some_and(|name| name.trim().is_empty());
+    let bad_handles = request
+        .update
+        .handles
+        .as_deref()
+        .is_some_and(|handles| !valid_handles(handles));
+    if empty_name || bad_handles {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let contact = store
+        .update(&id, request.update)
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, contact_id = %id, "failed to update contact");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    Ok(Json(ContactResponse { contact }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/agents/contacts/{id}",
+    tag = "agents",
+    params(("id" = String, Path, description = "Contact id"), ContactQuery),
+    responses(
+        (status = 204, description = "Deleted with its handles"),
+        (status = 404, description = "Unknown agent or contact"),
+    )
+)]
+pub(super) async fn delete_contact(
+    State(state): State<Arc<ApiState>>,
+    Path(id): Path<String>,
+    AgentQuery(query): AgentQuery<ContactQuery>,
+) -> Result<StatusCode, StatusCode> {
+    let store = contact_store(&state, &query.agent_id)?;
+    let deleted = store.delete(&id).await.map_err(|error| {
+        tracing::warn!(%error, contact_id = %id, "failed to delete contact");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    if deleted {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(StatusCode::NOT_FOUND)
+    }
+}
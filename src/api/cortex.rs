@@ -1,12 +1,13 @@
 use super::state::ApiState;
 
+use super::access::{AgentJson, AgentQuery, AgentScoped};
 use crate::agent::cortex::{CortexEvent, CortexLogger};
 use crate::agent::cortex_chat::{
     CortexChatEvent, CortexChatMessage, CortexChatSendError, CortexChatStore, CortexChatThread,
 };
 
 use axum::Json;
-use axum::extract::{Query, State};
+use axum::extract::State;
 use axum::http::StatusCode;
 use axum::response::Sse;
 use futures::stream::Stream;
@@ -20,13 +21,14 @@ pub(super) struct CortexEventsResponse {
     total: i64,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, utoipa::ToSchema)]
 pub(super) struct CortexChatMessagesResponse {
     messages: Vec<CortexChatMessage>,
     thread_id: String,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
 pub(super) struct CortexChatMessagesQuery {
     agent_id: String,
     /// If omitted, loads the latest thread.
@@ -35,16 +37,63 @@ pub(super) struct CortexChatMessagesQuery {
     limit: i64,
 }
 
+impl AgentScoped for CortexChatMessagesQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
 fn default_cortex_chat_limit() -> i64 {
     50
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, utoipa::ToSchema)]
 pub(super) struct CortexChatSendRequest {
     agent_id: String,
     thread_id: String,
     message: String,
     channel_id: Option<String>,
+    /// Upload IDs from `POST /api/uploads` to hand the cortex with this message.
+    #[serde(default)]
+    attachments: Vec<String>,
+}
+
+impl AgentScoped for CortexChatSendRequest {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
+pub(super) struct CortexChatRegenerateRequest {
+    agent_id: String,
+    thread_id: String,
+    /// Defaults to the channel context of the message being regenerated.
+    channel_id: Option<String>,
+}
+
+impl AgentScoped for CortexChatRegenerateRequest {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
+pub(super) struct CortexChatEditRequest {
+    agent_id: String,
+    thread_id: String,
+    /// ID of the user message to replace.
+    message_id: String,
+    /// New text for that message.
+    message: String,
+    /// Defaults to the channel context of the message being edited.
+    channel_id: Option<String>,
+}
+
+impl AgentScoped for CortexChatEditRequest {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
 }
 
 #[derive(Deserialize)]
@@ -58,6 +107,12 @@ pub(super) struct CortexEventsQuery {
     event_type: Option<String>,
 }
 
+impl AgentScoped for CortexEventsQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
 fn default_cortex_events_limit() -> i64 {
     50
 }
@@ -65,6 +120,7 @@ fn default_cortex_events_limit() -> i64 {
 fn map_cortex_chat_send_error(error: &CortexChatSendError) -> StatusCode {
     match error {
         CortexChatSendError::Busy => StatusCode::CONFLICT,
+        CortexChatSendError::MessageNotFound => StatusCode::NOT_FOUND,
         _ => StatusCode::INTERNAL_SERVER_ERROR,
     }
 }
@@ -72,9 +128,19 @@ fn map_cortex_chat_send_error(error: &CortexChatSendError) -> StatusCode {
 /// Load persisted cortex chat history for a thread.
 /// If no thread_id is provided, loads the latest thread.
 /// If no threads exist, returns an empty list with a fresh thread_id.
+#[utoipa::path(
+    get,
+    path = "/api/cortex-chat/messages",
+    tag = "cortex",
+    params(CortexChatMessagesQuery),
+    responses(
+        (status = 200, body = CortexChatMessagesResponse),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
 pub(super) async fn cortex_chat_messages(
     State(state): State<Arc<ApiState>>,
-    Query(query): Query<CortexChatMessagesQuery>,
+    AgentQuery(query): AgentQuery<CortexChatMessagesQuery>,
 ) -> Result<Json<CortexChatMessagesResponse>, StatusCode> {
     let pools = state.agent_pools.load();
     let pool = pools.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
@@ -112,9 +178,20 @@ pub(super) async fn cortex_chat_messages(
 /// - `tool_completed` — a tool call finished (with result preview)
 /// - `done` — full response text
 /// - `error` — if something went wrong
+#[utoipa::path(
+    post,
+    path = "/api/cortex-chat/send",
+    tag = "cortex",
+    request_body = CortexChatSendRequest,
+    responses(
+        (status = 200, description = "Server-sent event stream of the cortex's progress", content_type = "text/event-stream", body = String),
+        (status = 400, description = "Unknown upload ID, or too many attachments"),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
 pub(super) async fn cortex_chat_send(
     State(state): State<Arc<ApiState>>,
-    axum::Json(request): axum::Json<CortexChatSendRequest>,
+    AgentJson(request): AgentJson<CortexChatSendRequest>,
 ) -> Result<Sse<impl Stream<Item = Result<axum::response::sse::Event, Infallible>>>, StatusCode> {
     let sessions = state.cortex_chat_sessions.load();
     let session = sessions
@@ -123,11 +200,18 @@ pub(super) async fn cortex_chat_send(
         .ok_or(StatusCode::NOT_FOUND)?;
 
     let thread_id = request.thread_id;
-    let message = request.message;
+    let message = if request.attachments.is_empty() {
+        request.message
+    } else {
+        let pools = state.agent_pools.load();
+        let pool = pools.get(&request.agent_id).ok_or(StatusCode::NOT_FOUND)?;
+        let uploads = super::uploads::load_uploads(pool, &request.attachments).await?;
+        super::uploads::with_attachment_note(&request.message, &uploads)
+    };
     let channel_id = request.channel_id;
 
     let channel_ref = channel_id.as_deref();
-    let mut event_rx = session
+    let event_rx = session
         .send_message_with_events(&thread_id, &message, channel_ref)
         .await
         .map_err(|error| {
@@ -138,6 +222,91 @@ pub(super) async fn cortex_chat_send(
             status
         })?;
 
+    Ok(cortex_chat_event_stream(event_rx))
+}
+
+/// Delete the last assistant turn in a thread and generate it again from the
+/// same context. Returns the same SSE stream as `/cortex-chat/send`.
+#[utoipa::path(
+    post,
+    path = "/api/cortex-chat/regenerate",
+    tag = "cortex",
+    request_body = CortexChatRegenerateRequest,
+    responses(
+        (status = 200, description = "Server-sent event stream of the cortex's progress", content_type = "text/event-stream", body = String),
+        (status = 404, description = "Unknown agent, or the thread has no user message"),
+        (status = 409, description = "Another cortex chat request is in progress"),
+    )
+)]
+pub(super) async fn cortex_chat_regenerate(
+    State(state): State<Arc<ApiState>>,
+    AgentJson(request): AgentJson<CortexChatRegenerateRequest>,
+) -> Result<Sse<impl Stream<Item = Result<axum::response::sse::Event, Infallible>>>, StatusCode> {
+    let sessions = state.cortex_chat_sessions.load();
+    let session = sessions
+        .get(&request.agent_id)
+        .cloned()
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let event_rx = session
+        .regenerate_with_events(&request.thread_id, request.channel_id.as_deref())
+        .await
+        .map_err(|error| {
+            let status = map_cortex_chat_send_error(&error);
+            if status == StatusCode::INTERNAL_SERVER_ERROR {
+                tracing::warn!(%error, "failed to regenerate cortex chat reply");
+            }
+            status
+        })?;
+
+    Ok(cortex_chat_event_stream(event_rx))
+}
+
+/// Replace a prior user message, discard everything after it, and send the
+/// new text. Returns the same SSE stream as `/cortex-chat/send`.
+#[utoipa::path(
+    post,
+    path = "/api/cortex-chat/edit",
+    tag = "cortex",
+    request_body = CortexChatEditRequest,
+    responses(
+        (status = 200, description = "Server-sent event stream of the cortex's progress", content_type = "text/event-stream", body = String),
+        (status = 404, description = "Unknown agent, or no such user message in the thread"),
+        (status = 409, description = "Another cortex chat request is in progress"),
+    )
+)]
+pub(super) async fn cortex_chat_edit(
+    State(state): State<Arc<ApiState>>,
+    AgentJson(request): AgentJson<CortexChatEditRequest>,
+) -> Result<Sse<impl Stream<Item = Result<axum::response::sse::Event, Infallible>>>, StatusCode> {
+    let sessions = state.cortex_chat_sessions.load();
+    let session = sessions
+        .get(&request.agent_id)
+        .cloned()
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let event_rx = session
+        .edit_and_resend_with_events(
+            &request.thread_id,
+            &request.message_id,
+            &request.message,
+            request.channel_id.as_deref(),
+        )
+        .await
+        .map_err(|error| {
+            let status = map_cortex_chat_send_error(&error);
+            if status == StatusCode::INTERNAL_SERVER_ERROR {
+                tracing::warn!(%error, "failed to resend edited cortex chat message");
+            }
+            status
+        })?;
+
+    Ok(cortex_chat_event_stream(event_rx))
+}
+
+fn cortex_chat_event_stream(
+    mut event_rx: tokio::sync::mpsc::Receiver<CortexChatEvent>,
+) -> Sse<impl Stream<Item = Result<axum::response::sse::Event, Infallible>>> {
     let stream = async_stream::stream! {
         yield Ok(axum::response::sse::Event::default()
             .event("thinking")
@@ -159,31 +328,54 @@ pub(super) async fn cortex_chat_send(
         }
     };
 
-    Ok(Sse::new(stream))
+    Sse::new(stream)
 }
 
 // -- Thread management --
 
-#[derive(Serialize)]
+#[derive(Serialize, utoipa::ToSchema)]
 pub(super) struct CortexChatThreadsResponse {
     threads: Vec<CortexChatThread>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
 pub(super) struct CortexChatThreadsQuery {
     agent_id: String,
 }
 
-#[derive(Deserialize)]
+impl AgentScoped for CortexChatThreadsQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
 pub(super) struct CortexChatDeleteThreadRequest {
     agent_id: String,
     thread_id: String,
 }
 
+impl AgentScoped for CortexChatDeleteThreadRequest {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
 /// List all cortex chat threads for an agent, newest first.
+#[utoipa::path(
+    get,
+    path = "/api/cortex-chat/threads",
+    tag = "cortex",
+    params(CortexChatThreadsQuery),
+    responses(
+        (status = 200, body = CortexChatThreadsResponse),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
 pub(super) async fn cortex_chat_threads(
     State(state): State<Arc<ApiState>>,
-    Query(query): Query<CortexChatThreadsQuery>,
+    AgentQuery(query): AgentQuery<CortexChatThreadsQuery>,
 ) -> Result<Json<CortexChatThreadsResponse>, StatusCode> {
     let pools = state.agent_pools.load();
     let pool = pools.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
@@ -198,9 +390,19 @@ pub(super) async fn cortex_chat_threads(
 }
 
 /// Delete a cortex chat thread and all its messages.
+#[utoipa::path(
+    delete,
+    path = "/api/cortex-chat/thread",
+    tag = "cortex",
+    request_body = CortexChatDeleteThreadRequest,
+    responses(
+        (status = 204, description = "Thread deleted"),
+        (status = 404, description = "Unknown agent or thread"),
+    )
+)]
 pub(super) async fn cortex_chat_delete_thread(
     State(state): State<Arc<ApiState>>,
-    axum::Json(request): axum::Json<CortexChatDeleteThreadRequest>,
+    AgentJson(request): AgentJson<CortexChatDeleteThreadRequest>,
 ) -> Result<StatusCode, StatusCode> {
     let pools = state.agent_pools.load();
     let pool = pools.get(&request.agent_id).ok_or(StatusCode::NOT_FOUND)?;
@@ -218,10 +420,72 @@ pub(super) async fn cortex_chat_delete_thread(
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// Longest accepted thread title, in characters.
+const MAX_THREAD_TITLE_CHARS: usize = 200;
+
+#[derive(Deserialize, utoipa::ToSchema)]
+pub(super) struct CortexChatRenameThreadRequest {
+    agent_id: String,
+    thread_id: String,
+    /// New title. Empty or omitted clears it.
+    #[serde(default)]
+    title: Option<String>,
+}
+
+impl AgentScoped for CortexChatRenameThreadRequest {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+/// Rename a cortex chat thread.
+#[utoipa::path(
+    put,
+    path = "/api/cortex-chat/thread",
+    tag = "cortex",
+    request_body = CortexChatRenameThreadRequest,
+    responses(
+        (status = 204, description = "Thread renamed"),
+        (status = 400, description = "Title too long"),
+        (status = 404, description = "Unknown agent or thread"),
+    )
+)]
+pub(super) async fn cortex_chat_rename_thread(
+    State(state): State<Arc<ApiState>>,
+    AgentJson(request): AgentJson<CortexChatRenameThreadRequest>,
+) -> Result<StatusCode, StatusCode> {
+    let title = request
+        .title
+        .as_deref()
+        .map(str::trim)
+        .filter(|title| !title.is_empty());
+    if title.is_some_and(|title| title.chars().count() > MAX_THREAD_TITLE_CHARS) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let pools = state.agent_pools.load();
+    let pool = pools.get(&request.agent_id).ok_or(StatusCode::NOT_FOUND)?;
+    let store = CortexChatStore::new(pool.clone());
+
+    let renamed = store
+        .rename_thread(&request.thread_id, title)
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, agent_id = %request.agent_id, thread_id = %request.thread_id, "failed to rename cortex chat thread");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    if !renamed {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
 /// List cortex events for an agent with optional type filter, newest first.
 pub(super) async fn cortex_events(
     State(state): State<Arc<ApiState>>,
-    Query(query): Query<CortexEventsQuery>,
+    AgentQuery(query): AgentQuery<CortexEventsQuery>,
 ) -> Result<Json<CortexEventsResponse>, StatusCode> {
     let pools = state.agent_pools.load();
     let pool = pools.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
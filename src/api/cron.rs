@@ -1,7 +1,8 @@
 use super::state::ApiState;
 
+use super::access::{AgentJson, AgentQuery, AgentScoped};
 use axum::Json;
-use axum::extract::{Query, State};
+use axum::extract::State;
 use axum::http::StatusCode;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
@@ -12,6 +13,12 @@ pub(super) struct CronQuery {
     agent_id: String,
 }
 
+impl AgentScoped for CronQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
 #[derive(Deserialize)]
 pub(super) struct CronExecutionsQuery {
     agent_id: String,
@@ -21,6 +28,12 @@ pub(super) struct CronExecutionsQuery {
     limit: i64,
 }
 
+impl AgentScoped for CronExecutionsQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
 fn default_cron_executions_limit() -> i64 {
     50
 }
@@ -45,6 +58,16 @@ pub(super) struct CreateCronRequest {
     run_once: bool,
     #[serde(default)]
     timeout_secs: Option<u64>,
+    /// IANA timezone for `cron_expr` and active hours. Defaults to the
+    /// agent's `cron_timezone`.
+    #[serde(default)]
+    timezone: Option<String>,
+}
+
+impl AgentScoped for CreateCronRequest {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
 }
 
 fn default_interval() -> u64 {
@@ -61,12 +84,24 @@ pub(super) struct DeleteCronRequest {
     cron_id: String,
 }
 
+impl AgentScoped for DeleteCronRequest {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
 #[derive(Deserialize)]
 pub(super) struct TriggerCronRequest {
     agent_id: String,
     cron_id: String,
 }
 
+impl AgentScoped for TriggerCronRequest {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
 #[derive(Deserialize)]
 pub(super) struct ToggleCronRequest {
     agent_id: String,
@@ -74,6 +109,12 @@ pub(super) struct ToggleCronRequest {
     enabled: bool,
 }
 
+impl AgentScoped for ToggleCronRequest {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
 #[derive(Serialize)]
 struct CronJobWithStats {
     id: String,
@@ -85,6 +126,7 @@ struct CronJobWithStats {
     run_once: bool,
     active_hours: Option<(u8, u8)>,
     timeout_secs: Option<u64>,
+    timezone: Option<String>,
     success_count: u64,
     failure_count: u64,
     last_executed_at: Option<String>,
@@ -110,7 +152,7 @@ pub(super) struct CronActionResponse {
 /// List all cron jobs for an agent with execution statistics.
 pub(super) async fn list_cron_jobs(
     State(state): State<Arc<ApiState>>,
-    Query(query): Query<CronQuery>,
+    AgentQuery(query): AgentQuery<CronQuery>,
 ) -> Result<Json<CronListResponse>, StatusCode> {
     let stores = state.cron_stores.load();
     let schedulers = state.cron_schedulers.load();
@@ -141,6 +183,7 @@ pub(super) async fn list_cron_jobs(
             run_once: config.run_once,
             active_hours: config.active_hours,
             timeout_secs: config.timeout_secs,
+            timezone: config.timezone,
             success_count: stats.success_count,
             failure_count: stats.failure_count,
             last_executed_at: stats.last_executed_at,
@@ -156,7 +199,7 @@ pub(super) async fn list_cron_jobs(
 /// Get execution history for cron jobs.
 pub(super) async fn cron_executions(
     State(state): State<Arc<ApiState>>,
-    Query(query): Query<CronExecutionsQuery>,
+    AgentQuery(query): AgentQuery<CronExecutionsQuery>,
 ) -> Result<Json<CronExecutionsResponse>, StatusCode> {
     let stores = state.cron_stores.load();
     let store = stores.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
@@ -244,6 +287,19 @@ fn validate_cron_request(request: &CreateCronRequest) -> Result<(), (StatusCode,
         ));
     }
 
+    if let Some(timezone) = request
+        .timezone
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        && timezone.parse::<chrono_tz::Tz>().is_err()
+    {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("timezone '{timezone}' is not a valid IANA timezone"),
+        ));
+    }
+
     if !request.delivery_target.contains(':') {
         return Err((
             StatusCode::BAD_REQUEST,
@@ -274,7 +330,7 @@ fn validate_cron_request(request: &CreateCronRequest) -> Result<(), (StatusCode,
 /// Create or update a cron job.
 pub(super) async fn create_or_update_cron(
     State(state): State<Arc<ApiState>>,
-    Json(request): Json<CreateCronRequest>,
+    AgentJson(request): AgentJson<CreateCronRequest>,
 ) -> Result<Json<CronActionResponse>, (StatusCode, Json<CronActionResponse>)> {
     if let Err((status, message)) = validate_cron_request(&request) {
         tracing::warn!(agent_id = %request.agent_id, cron_id = %request.id, %message, "cron validation failed");
@@ -333,6 +389,12 @@ pub(super) async fn create_or_update_cron(
         enabled: request.enabled,
         run_once: request.run_once,
         timeout_secs: request.timeout_secs,
+        timezone: request
+            .timezone
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(ToString::to_string),
     };
 
     store.save(&config).await.map_err(|error| {
@@ -354,7 +416,7 @@ pub(super) async fn create_or_update_cron(
 /// Delete a cron job.
 pub(super) async fn delete_cron(
     State(state): State<Arc<ApiState>>,
-    Query(query): Query<DeleteCronRequest>,
+    AgentQuery(query): AgentQuery<DeleteCronRequest>,
 ) -> Result<Json<CronActionResponse>, StatusCode> {
     let stores = state.cron_stores.load();
     let store = stores.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
@@ -380,7 +442,7 @@ pub(super) async fn delete_cron(
 /// Trigger a cron job immediately.
 pub(super) async fn trigger_cron(
     State(state): State<Arc<ApiState>>,
-    Json(request): Json<TriggerCronRequest>,
+    AgentJson(request): AgentJson<TriggerCronRequest>,
 ) -> Result<Json<CronActionResponse>, StatusCode> {
     let schedulers = state.cron_schedulers.load();
     let scheduler = schedulers
@@ -401,7 +463,7 @@ pub(super) async fn trigger_cron(
 /// Enable or disable a cron job.
 pub(super) async fn toggle_cron(
     State(state): State<Arc<ApiState>>,
-    Json(request): Json<ToggleCronRequest>,
+    AgentJson(request): AgentJson<ToggleCronRequest>,
 ) -> Result<Json<CronActionResponse>, StatusCode> {
     let stores = state.cron_stores.load();
     let store = stores.get(&request.agent_id).ok_or(StatusCode::NOT_FOUND)?;
@@ -0,0 +1,279 @@
+//! Frontend dev-server proxy.
+//!
+//! When `api.dev_proxy_url` is configured, every non-API request is forwarded
+//! to a local Vite dev server instead of being served from the embedded
+//! `interface/dist` assets. WebSocket upgrades (used by Vite for HMR) are
+//! tunnelled as raw byte streams so the dev server's protocol is untouched,
+//! which is why the URL must be plain `http://`.
+
+use super::opencode_proxy::is_hop_by_hop;
+
+use axum::body::Body;
+use axum::extract::Request;
+use axum::http::{HeaderMap, HeaderName, HeaderValue, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use futures::TryStreamExt as _;
+use hyper_util::rt::TokioIo;
+use tokio::io::{AsyncReadExt as _, AsyncWriteExt as _};
+
+use std::sync::LazyLock;
+
+/// Upper bound on the upstream handshake response head. Anything larger is
+/// not a sane WebSocket handshake.
+const MAX_HANDSHAKE_BYTES: usize = 16 * 1024;
+
+static CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
+    reqwest::Client::builder()
+        .no_proxy()
+        .connect_timeout(std::time::Duration::from_secs(5))
+        .pool_idle_timeout(None)
+        .build()
+        .unwrap_or_default()
+});
+
+/// Check a dev proxy URL at startup. WebSocket upgrades are tunnelled over
+/// plain TCP, so an `https://` dev server would serve pages but break HMR.
+pub(super) fn validate_dev_proxy_url(base_url: &str) -> anyhow::Result<()> {
+    let target = reqwest::Url::parse(base_url)
+        .map_err(|error| anyhow::anyhow!("invalid dev proxy URL {base_url:?}: {error}"))?;
+    if target.scheme() != "http" {
+        anyhow::bail!(
+            "dev proxy URL {base_url:?} must use http://; websocket (HMR) tunnelling doesn't \
+             support TLS, so point it at the dev server's plain HTTP address"
+        );
+    }
+    if target.host_str().is_none() {
+        anyhow::bail!("dev proxy URL {base_url:?} has no host");
+    }
+    Ok(())
+}
+
+/// Forward a frontend request to the dev server at `base_url`.
+pub(super) async fn proxy_to_dev_server(base_url: &str, request: Request) -> Response {
+    if is_websocket_upgrade(request.headers()) {
+        return proxy_websocket(base_url, request).await;
+    }
+
+    let path_and_query = request
+        .uri()
+        .path_and_query()
+        .map(|value| value.as_str())
+        .unwrap_or("/");
+    let target_url = format!("{}{}", base_url.trim_end_matches('/'), path_and_query);
+
+    let mut proxy_request = CLIENT.request(request.method().clone(), &target_url);
+    for (name, value) in request.headers() {
+        if name == header::HOST || is_hop_by_hop(name) {
+            continue;
+        }
+        proxy_request = proxy_request.header(name.clone(), value.clone());
+    }
+
+    let body_bytes = match axum::body::to_bytes(request.into_body(), 10 * 1024 * 1024).await {
+        Ok(bytes) => bytes,
+        Err(error) => {
+            tracing::warn!(%error, "failed to read dev proxy request body");
+            return (StatusCode::BAD_REQUEST, "failed to read request body").into_response();
+        }
+    };
+    if !body_bytes.is_empty() {
+        proxy_request = proxy_request.body(body_bytes);
+    }
+
+    let upstream_response = match proxy_request.send().await {
+        Ok(response) => response,
+        Err(error) => {
+            tracing::debug!(%error, %target_url, "dev proxy: upstream unreachable");
+            return (
+                StatusCode::BAD_GATEWAY,
+                "frontend dev server unreachable (is `bun run dev` running?)",
+            )
+                .into_response();
+        }
+    };
+
+    let mut response_builder = Response::builder().status(upstream_response.status().as_u16());
+    for (name, value) in upstream_response.headers() {
+        if is_hop_by_hop(name) {
+            continue;
+        }
+        response_builder = response_builder.header(name.clone(), value.clone());
+    }
+
+    let body_stream = upstream_response
+        .bytes_stream()
+        .map_err(std::io::Error::other);
+
+    match response_builder.body(Body::from_stream(body_stream)) {
+        Ok(response) => response,
+        Err(error) => {
+            tracing::warn!(%error, "failed to build dev proxy response");
+            (StatusCode::INTERNAL_SERVER_ERROR, "proxy response error").into_response()
+        }
+    }
+}
+
+fn is_websocket_upgrade(headers: &HeaderMap) -> bool {
+    let upgrade_is_websocket = headers
+        .get(header::UPGRADE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.eq_ignore_ascii_case("websocket"));
+    let connection_has_upgrade = headers
+        .get(header::CONNECTION)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| {
+            value
+                .split(',')
+                .any(|token| token.trim().eq_ignore_ascii_case("upgrade"))
+        });
+    upgrade_is_websocket && connection_has_upgrade
+}
+
+/// Tunnel a WebSocket upgrade to the dev server.
+///
+/// The handshake is replayed verbatim over a fresh TCP connection. Once the
+/// upstream answers `101 Switching Protocols`, the same status and headers are
+/// returned to the browser and both upgraded connections are spliced together.
+async fn proxy_websocket(base_url: &str, mut request: Request) -> Response {
+    let Ok(target) = reqwest::Url::parse(base_url) else {
+        return (StatusCode::BAD_GATEWAY, "invalid dev proxy URL").into_response();
+    };
+    let Some(host) = target.host_str().map(str::to_string) else {
+        return (StatusCode::BAD_GATEWAY, "dev proxy URL has no host").into_response();
+    };
+    let port = target.port_or_known_default().unwrap_or(80);
+
+    let mut upstream = match tokio::net::TcpStream::connect((host.as_str(), port)).await {
+        Ok(stream) => stream,
+        Err(error) => {
+            tracing::debug!(%error, %host, port, "dev proxy: websocket upstream unreachable");
+            return (StatusCode::BAD_GATEWAY, "frontend dev server unreachable").into_response();
+        }
+    };
+
+    let path_and_query = request
+        .uri()
+        .path_and_query()
+        .map(|value| value.as_str())
+        .unwrap_or("/");
+    let mut handshake = format!("{} {} HTTP/1.1\r\n", request.method(), path_and_query);
+    handshake.push_str(&format!("host: {host}:{port}\r\n"));
+    for (name, value) in request.headers() {
+        if name == header::HOST {
+            continue;
+        }
+        let Ok(value) = value.to_str() else {
+            continue;
+        };
+        handshake.push_str(&format!("{name}: {value}\r\n"));
+    }
+    handshake.push_str("\r\n");
+
+    if let Err(error) = upstream.write_all(handshake.as_bytes()).await {
+        tracing::debug!(%error, "dev proxy: failed to send websocket handshake");
+        return (StatusCode::BAD_GATEWAY, "websocket handshake failed").into_response();
+    }
+
+    let (status, response_headers, leftover) = match read_handshake_response(&mut upstream).await {
+        Ok(parsed) => parsed,
+        Err(error) => {
+            tracing::debug!(%error, "dev proxy: invalid websocket handshake response");
+            return (StatusCode::BAD_GATEWAY, "websocket handshake failed").into_response();
+        }
+    };
+
+    if status != StatusCode::SWITCHING_PROTOCOLS {
+        return (status, "dev server rejected websocket upgrade").into_response();
+    }
+
+    let on_upgrade = hyper::upgrade::on(&mut request);
+    tokio::spawn(async move {
+        let upgraded = match on_upgrade.await {
+            Ok(upgraded) => upgraded,
+            Err(error) => {
+                tracing::debug!(%error, "dev proxy: client websocket upgrade failed");
+                return;
+            }
+        };
+        let mut client = TokioIo::new(upgraded);
+        if !leftover.is_empty()
+            && let Err(error) = client.write_all(&leftover).await
+        {
+            tracing::debug!(%error, "dev proxy: failed to flush buffered websocket bytes");
+            return;
+        }
+        if let Err(error) = tokio::io::copy_bidirectional(&mut client, &mut upstream).await {
+            tracing::debug!(%error, "dev proxy: websocket tunnel closed");
+        }
+    });
+
+    let mut response = Response::builder().status(StatusCode::SWITCHING_PROTOCOLS);
+    if let Some(headers) = response.headers_mut() {
+        *headers = response_headers;
+    }
+    response.body(Body::empty()).unwrap_or_else(|error| {
+        tracing::warn!(%error, "failed to build websocket upgrade response");
+        StatusCode::INTERNAL_SERVER_ERROR.into_response()
+    })
+}
+
+/// Read and parse the upstream HTTP response head. Returns the status, the
+/// headers, and any bytes read past the end of the head (early frames).
+async fn read_handshake_response(
+    upstream: &mut tokio::net::TcpStream,
+) -> anyhow::Result<(StatusCode, HeaderMap, Vec<u8>)> {
+    let mut buffer = Vec::with_capacity(1024);
+    let mut chunk = [0u8; 1024];
+    let head_end = loop {
+        if let Some(position) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+            break position;
+        }
+        if buffer.len() > MAX_HANDSHAKE_BYTES {
+            anyhow::bail!("handshake response exceeds {MAX_HANDSHAKE_BYTES} bytes");
+        }
+        let read = upstream.read(&mut chunk).await?;
+        if read == 0 {
+            anyhow::bail!("upstream closed during handshake");
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    };
+
+    let head = std::str::from_utf8(&buffer[..head_end])?;
+    let mut lines = head.split("\r\n");
+    let status_line = lines.next().unwrap_or_default();
+    let status_code = status_line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|code| code.parse::<u16>().ok())
+        .ok_or_else(|| anyhow::anyhow!("malformed status line: {status_line}"))?;
+    let status = StatusCode::from_u16(status_code)?;
+
+    let mut headers = HeaderMap::new();
+    for line in lines {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(name.trim().as_bytes()),
+            HeaderValue::from_str(value.trim()),
+        ) else {
+            continue;
+        };
+        headers.append(name, value);
+    }
+
+    let leftover = buffer[head_end + 4..].to_vec();
+    Ok((status, headers, leftover))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::validate_dev_proxy_url;
+
+    #[test]
+    fn only_plain_http_dev_servers_are_accepted() {
+        assert!(validate_dev_proxy_url("http://localhost:19840").is_ok());
+        assert!(validate_dev_proxy_url("https://localhost:19840").is_err());
+        assert!(validate_dev_proxy_url("localhost:19840").is_err());
+    }
+}
@@ -0,0 +1,445 @@
+//! Persistent history of API events.
+//!
+//! The live stream keeps a few hundred events in memory, so a dashboard that
+//! connects late sees nothing that happened before it. The archive records
+//! the configured event types from the sequenced stream into `events.redb`,
+//! keyed by event ID, and prunes them by age and count. `GET
+//! /api/events/history` pages through it newest first.
+
+use super::access;
+use super::server::ApiPrincipal;
+use super::state::{ApiEvent, ApiState, SequencedEvent};
+use super::system::split_list;
+
+use crate::config::ApiEventHistoryConfig;
+
+use axum::Json;
+use axum::extract::{Extension, Query, State};
+use axum::http::StatusCode;
+use chrono::{DateTime, Utc};
+use redb::{Database, ReadableTable, ReadableTableMetadata, TableDefinition};
+use serde::{Deserialize, Serialize};
+
+use std::path::Path;
+use std::sync::Arc;
+
+/// Table: event ID -> JSON-encoded `ArchivedEvent`.
+const EVENTS_TABLE: TableDefinition<u64, &[u8]> = TableDefinition::new("api_events");
+
+/// An event as stored and returned by the history endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ArchivedEvent {
+    /// The event's ID on the live stream.
+    pub id: u64,
+    pub timestamp: DateTime<Utc>,
+    /// SSE event name, e.g. `worker_completed`.
+    pub event_type: String,
+    /// Agents the event concerns. Empty for instance-wide events.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub agent_ids: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel_id: Option<String>,
+    /// The event as sent on `/api/events`.
+    #[schema(value_type = Object)]
+    pub event: serde_json::Value,
+}
+
+impl ArchivedEvent {
+    fn new(sequenced: &SequencedEvent, timestamp: DateTime<Utc>) -> Option<Self> {
+        let event = serde_json::to_value(&sequenced.event).ok()?;
+        Some(Self {
+            id: sequenced.id,
+            timestamp,
+            event_type: sequenced.event.event_type().to_string(),
+            agent_ids: sequenced
+                .event
+                .agent_ids()
+                .into_iter()
+                .map(str::to_string)
+                .collect(),
+            channel_id: sequenced.event.channel_id().map(str::to_string),
+            event,
+        })
+    }
+}
+
+/// Persistent store of past API events.
+pub struct EventArchive {
+    db: Database,
+    config: ApiEventHistoryConfig,
+}
+
+impl std::fmt::Debug for EventArchive {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventArchive").finish_non_exhaustive()
+    }
+}
+
+impl EventArchive {
+    /// Open or create the archive at `path`.
+    pub fn new(path: &Path, config: &ApiEventHistoryConfig) -> crate::error::Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|error| anyhow::anyhow!(error))?;
+        }
+        let db = Database::create(path).map_err(|error| {
+            anyhow::anyhow!("failed to open event db at {}: {error}", path.display())
+        })?;
+
+        let write_txn = db.begin_write().map_err(|error| anyhow::anyhow!(error))?;
+        {
+            let _ = write_txn
+                .open_table(EVENTS_TABLE)
+                .map_err(|error| anyhow::anyhow!(error))?;
+        }
+        write_txn.commit().map_err(|error| anyhow::anyhow!(error))?;
+
+        Ok(Self {
+            db,
+            config: config.clone(),
+        })
+    }
+
+    /// Whether events of this type are kept.
+    fn records(&self, event_type: &str) -> bool {
+        self.config
+            .events
+            .iter()
+            .any(|wanted| wanted == "*" || wanted == event_type)
+    }
+
+    /// Store a batch of events, then prune past the retention window and
+    /// `max_events`.
+    fn append(&self, events: Vec<ArchivedEvent>) -> crate::error::Result<()> {
+        let cutoff = Utc::now() - chrono::Duration::days(self.config.retention_days as i64);
+        let write_txn = self
+            .db
+            .begin_write()
+            .map_err(|error| anyhow::anyhow!(error))?;
+        {
+            let mut table = write_txn
+                .open_table(EVENTS_TABLE)
+                .map_err(|error| anyhow::anyhow!(error))?;
+            for event in &events {
+                let data = serde_json::to_vec(event).map_err(|error| anyhow::anyhow!(error))?;
+                table
+                    .insert(event.id, data.as_slice())
+                    .map_err(|error| anyhow::anyhow!(error))?;
+            }
+
+            while table.len().map_err(|error| anyhow::anyhow!(error))? > self.config.max_events {
+                table.pop_first().map_err(|error| anyhow::anyhow!(error))?;
+            }
+            loop {
+                let expired = match table.first().map_err(|error| anyhow::anyhow!(error))? {
+                    Some((_, value)) => !serde_json::from_slice::<ArchivedEvent>(value.value())
+                        .is_ok_and(|event| event.timestamp >= cutoff),
+                    None => false,
+                };
+                if !expired {
+                    break;
+                }
+                table.pop_first().map_err(|error| anyhow::anyhow!(error))?;
+            }
+        }
+        write_txn.commit().map_err(|error| anyhow::anyhow!(error))?;
+        Ok(())
+    }
+
+    /// Events matching `filter`, newest first.
+    fn list(
+        &self,
+        filter: &HistoryFilter,
+        limit: usize,
+    ) -> crate::error::Result<Vec<ArchivedEvent>> {
+        let read_txn = self
+            .db
+            .begin_read()
+            .map_err(|error| anyhow::anyhow!(error))?;
+        let table = read_txn
+            .open_table(EVENTS_TABLE)
+            .map_err(|error| anyhow::anyhow!(error))?;
+
+        let before = filter.before.unwrap_or(u64::MAX);
+        let mut events = Vec::new();
+        for item in table
+            .range(..before)
+            .map_err(|error| anyhow::anyhow!(error))?
+            .rev()
+        {
+            let (_, value) = item.map_err(|error| anyhow::anyhow!(error))?;
+            let Ok(event) = serde_json::from_slice::<ArchivedEvent>(value.value()) else {
+                continue;
+            };
+            // IDs follow emission order, so nothing older can match.
+            if filter.since.is_some_and(|since| event.timestamp < since) {
+                break;
+            }
+            if filter.matches(&event) {
+                events.push(event);
+                if events.len() >= limit {
+                    break;
+                }
+            }
+        }
+        Ok(events)
+    }
+}
+
+/// Record the configured event types from the sequenced stream. Events that
+/// arrive while a write is in flight are stored together in the next one.
+pub fn spawn_event_archiver(state: Arc<ApiState>, archive: Arc<EventArchive>) {
+    let mut event_rx = state.sequenced_event_tx.subscribe();
+    tokio::spawn(async move {
+        loop {
+            let first = match crate::classify_broadcast_recv_result(event_rx.recv().await) {
+                crate::BroadcastRecvResult::Event(sequenced) => sequenced,
+                crate::BroadcastRecvResult::Lagged(count) => {
+                    tracing::warn!(count, "event archiver lagged, events not recorded");
+                    continue;
+                }
+                crate::BroadcastRecvResult::Closed => break,
+            };
+
+            let now = Utc::now();
+            let mut batch = Vec::new();
+            let mut next = Some(first);
+            while let Some(sequenced) = next.take() {
+                if archive.records(sequenced.event.event_type())
+                    && let Some(event) = ArchivedEvent::new(&sequenced, now)
+                {
+                    batch.push(event);
+                }
+                next = match event_rx.try_recv() {
+                    Ok(sequenced) => Some(sequenced),
+                    Err(tokio::sync::broadcast::error::TryRecvError::Lagged(count)) => {
+                        tracing::warn!(count, "event archiver lagged, events not recorded");
+                        event_rx.try_recv().ok()
+                    }
+                    Err(_) => None,
+                };
+            }
+            if batch.is_empty() {
+                continue;
+            }
+
+            let archive = archive.clone();
+            match tokio::task::spawn_blocking(move || archive.append(batch)).await {
+                Ok(Ok(())) => {}
+                Ok(Err(error)) => tracing::warn!(%error, "failed to record API events"),
+                Err(error) => tracing::warn!(%error, "event archive task failed"),
+            }
+        }
+    });
+}
+
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(super) struct EventHistoryQuery {
+    /// Comma-separated agent IDs.
+    #[serde(default)]
+    agent_id: Option<String>,
+    /// Comma-separated channel IDs.
+    #[serde(default)]
+    channel_id: Option<String>,
+    /// Comma-separated event names (e.g. `inbound_message,worker_started`).
+    #[serde(default)]
+    event_types: Option<String>,
+    /// Only events at or after this time (RFC 3339).
+    #[serde(default)]
+    #[param(value_type = Option<String>)]
+    since: Option<DateTime<Utc>>,
+    /// Only events before this time (RFC 3339).
+    #[serde(default)]
+    #[param(value_type = Option<String>)]
+    until: Option<DateTime<Utc>>,
+    /// Only events with an ID below this, for paging.
+    #[serde(default)]
+    before: Option<u64>,
+    #[serde(default = "default_limit")]
+    limit: usize,
+}
+
+fn default_limit() -> usize {
+    100
+}
+
+#[derive(Debug, Default)]
+struct HistoryFilter {
+    agent_ids: Vec<String>,
+    channel_ids: Vec<String>,
+    event_types: Vec<String>,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+    before: Option<u64>,
+}
+
+impl HistoryFilter {
+    /// Same matching as the live stream: instance-wide events pass the agent
+    /// filter, and a channel filter drops events without a channel.
+    fn matches(&self, event: &ArchivedEvent) -> bool {
+        if self.until.is_some_and(|until| event.timestamp >= until) {
+            return false;
+        }
+        if !self.event_types.is_empty() && !self.event_types.contains(&event.event_type) {
+            return false;
+        }
+        if event.event_type == "config_reloaded" {
+            return true;
+        }
+        if !self.agent_ids.is_empty()
+            && !event.agent_ids.is_empty()
+            && !event
+                .agent_ids
+                .iter()
+                .any(|agent_id| self.agent_ids.contains(agent_id))
+        {
+            return false;
+        }
+        if !self.channel_ids.is_empty() {
+            return event
+                .channel_id
+                .as_ref()
+                .is_some_and(|channel_id| self.channel_ids.contains(channel_id));
+        }
+        true
+    }
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct EventHistoryResponse {
+    events: Vec<ArchivedEvent>,
+    /// Pass as `before` to fetch the next page; absent on the last page.
+    next_before: Option<u64>,
+}
+
+/// Past events, newest first, with the same filters as `/api/events` plus a
+/// time range.
+#[utoipa::path(
+    get,
+    path = "/api/events/history",
+    tag = "system",
+    params(EventHistoryQuery),
+    responses(
+        (status = 200, body = EventHistoryResponse),
+        (status = 400, description = "Unknown event type"),
+        (status = 403, description = "Agent outside the token's agents"),
+        (status = 404, description = "Event history is disabled"),
+    )
+)]
+pub(super) async fn event_history(
+    State(state): State<Arc<ApiState>>,
+    principal: Option<Extension<ApiPrincipal>>,
+    Query(query): Query<EventHistoryQuery>,
+) -> Result<Json<EventHistoryResponse>, (StatusCode, String)> {
+    let mut filter = HistoryFilter {
+        agent_ids: split_list(query.agent_id),
+        channel_ids: split_list(query.channel_id),
+        event_types: split_list(query.event_types),
+        since: query.since,
+        until: query.until,
+        before: query.before,
+    };
+    if let Some(unknown) = filter
+        .event_types
+        .iter()
+        .find(|event_type| !ApiEvent::EVENT_TYPES.contains(&event_type.as_str()))
+    {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("unknown event type '{unknown}'"),
+        ));
+    }
+    // Tenants only ever see their own agents' events.
+    access::restrict_to_agents(principal.as_deref(), &mut filter.agent_ids)
+        .map_err(|error| (StatusCode::FORBIDDEN, error))?;
+    let archive = state.event_archive.clone().ok_or((
+        StatusCode::NOT_FOUND,
+        "event history is disabled".to_string(),
+    ))?;
+
+    let limit = query.limit.clamp(1, 1000);
+    let events = tokio::task::spawn_blocking(move || archive.list(&filter, limit))
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, "event history task failed");
+            (StatusCode::INTERNAL_SERVER_ERROR, String::new())
+        })?
+        .map_err(|error| {
+            tracing::warn!(%error, "failed to read event history");
+            (StatusCode::INTERNAL_SERVER_ERROR, String::new())
+        })?;
+    let next_before = (events.len() == limit)
+        .then(|| events.last().map(|event| event.id))
+        .flatten();
+    Ok(Json(EventHistoryResponse {
+        events,
+        next_before,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn archive(dir: &tempfile::TempDir, events: &[&str], max_events: u64) -> EventArchive {
+        let config = ApiEventHistoryConfig {
+            enabled: true,
+            events: events.iter().map(|event| event.to_string()).collect(),
+            retention_days: 7,
+            max_events,
+        };
+        EventArchive::new(&dir.path().join("events.redb"), &config).unwrap()
+    }
+
+    fn inbound(id: u64, agent_id: &str, minutes_ago: i64) -> ArchivedEvent {
+        let sequenced = SequencedEvent {
+            id,
+            event: ApiEvent::InboundMessage {
+                agent_id: agent_id.to_string(),
+                channel_id: "discord:1".to_string(),
+                sender_name: None,
+                sender_id: "42".to_string(),
+                text: "hi".to_string(),
+            },
+        };
+        ArchivedEvent::new(
+            &sequenced,
+            Utc::now() - chrono::Duration::minutes(minutes_ago),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn filters_by_agent_and_time_and_prunes_to_max() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = archive(&dir, &["inbound_message"], 3);
+        assert!(archive.records("inbound_message"));
+        assert!(!archive.records("outbound_message_delta"));
+
+        archive
+            .append(vec![
+                inbound(1, "main", 40),
+                inbound(2, "other", 30),
+                inbound(3, "main", 20),
+                inbound(4, "main", 10),
+            ])
+            .unwrap();
+
+        let all = archive.list(&HistoryFilter::default(), 10).unwrap();
+        let ids: Vec<u64> = all.iter().map(|event| event.id).collect();
+        assert_eq!(ids, vec![4, 3, 2]);
+
+        let filter = HistoryFilter {
+            agent_ids: vec!["main".to_string()],
+            since: Some(Utc::now() - chrono::Duration::minutes(25)),
+            ..HistoryFilter::default()
+        };
+        let ids: Vec<u64> = archive
+            .list(&filter, 10)
+            .unwrap()
+            .iter()
+            .map(|event| event.id)
+            .collect();
+        assert_eq!(ids, vec![4, 3]);
+    }
+}
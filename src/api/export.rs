@@ -0,0 +1,373 @@
+//! Downloadable channel transcripts.
+//!
+//! Walks a channel's whole timeline oldest-first, a page at a time, and
+//! streams it out as JSON, Markdown, or a standalone HTML page so large
+//! channels never have to be held in memory.
+
+use super::access::{AgentQuery, AgentScoped};
+use super::shares::{PAGE_STYLE, escape_html};
+use super::state::ApiState;
+
+use crate::conversation::channels::ChannelStore;
+use crate::conversation::history::{ProcessRunLogger, TimelineCursor, TimelineItem};
+
+use axum::body::Body;
+use axum::extract::State;
+use axum::http::{HeaderValue, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use serde::Deserialize;
+
+use std::sync::Arc;
+
+/// Timeline items fetched per database round trip.
+const EXPORT_PAGE_SIZE: i64 = 200;
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(super) enum ExportFormat {
+    #[default]
+    Json,
+    Markdown,
+    Html,
+}
+
+impl ExportFormat {
+    fn content_type(self) -> &'static str {
+        match self {
+            Self::Json => "application/json",
+            Self::Markdown => "text/markdown; charset=utf-8",
+            Self::Html => "text/html; charset=utf-8",
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Markdown => "md",
+            Self::Html => "html",
+        }
+    }
+
+    fn header(self, channel_id: &str, title: &str) -> String {
+        let exported_at = chrono::Utc::now().to_rfc3339();
+        match self {
+            Self::Json => format!(
+                "{{\"channel_id\":{},\"title\":{},\"exported_at\":{},\"items\":[",
+                json_string(channel_id),
+                json_string(title),
+                json_string(&exported_at),
+            ),
+            Self::Markdown => {
+                format!("# {title}\n\nChannel `{channel_id}` · exported {exported_at}\n\n")
+            }
+            Self::Html => format!(
+                "<!doctype html><html><head><meta charset=\"utf-8\">\
+                 <meta name=\"viewport\" content=\"width=device-width,initial-scale=1\">\
+                 <title>{title}</title><style>{PAGE_STYLE}.run{{color:#656d76}}</style></head><body>\
+                 <h1>{title}</h1><p class=\"meta\">{channel_id} · exported {exported_at}</p>",
+                title = escape_html(title),
+                channel_id = escape_html(channel_id),
+            ),
+        }
+    }
+
+    fn item(self, item: &TimelineItem, first: bool) -> String {
+        match self {
+            Self::Json => {
+                let encoded = serde_json::to_string(item).unwrap_or_else(|_| "null".into());
+                if first {
+                    encoded
+                } else {
+                    format!(",{encoded}")
+                }
+            }
+            Self::Markdown => render_markdown_item(item),
+            Self::Html => render_html_item(item),
+        }
+    }
+
+    fn footer(self) -> &'static str {
+        match self {
+            Self::Json => "]}",
+            Self::Markdown => "",
+            Self::Html => "</body></html>",
+        }
+    }
+}
+
+fn json_string(value: &str) -> String {
+    serde_json::Value::from(value).to_string()
+}
+
+fn sender_label(role: &str, sender_name: Option<&str>) -> String {
+    sender_name
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(|| match role {
+            "assistant" => "Assistant".into(),
+            "system" => "System".into(),
+            _ => "User".into(),
+        })
+}
+
+fn render_markdown_item(item: &TimelineItem) -> String {
+    match item {
+        TimelineItem::Message {
+            role,
+            sender_name,
+            content,
+            created_at,
+            ..
+        } => format!(
+            "**{}** · {created_at}\n\n{content}\n\n",
+            sender_label(role, sender_name.as_deref())
+        ),
+        TimelineItem::BranchRun {
+            description,
+            conclusion,
+            started_at,
+            ..
+        } => {
+            let mut block = format!("> **Branch** · {started_at} — {description}\n");
+            if let Some(conclusion) = conclusion {
+                block.push_str(&format!(">\n> {}\n", conclusion.replace('\n', "\n> ")));
+            }
+            block.push('\n');
+            block
+        }
+        TimelineItem::WorkerRun {
+            task,
+            result,
+            status,
+            started_at,
+            ..
+        } => {
+            let mut block = format!("> **Worker** ({status}) · {started_at} — {task}\n");
+            if let Some(result) = result.as_deref().filter(|result| !result.is_empty()) {
+                block.push_str(&format!(">\n> {}\n", result.replace('\n', "\n> ")));
+            }
+            block.push('\n');
+            block
+        }
+    }
+}
+
+fn render_html_item(item: &TimelineItem) -> String {
+    match item {
+        TimelineItem::Message {
+            role,
+            sender_name,
+            content,
+            created_at,
+            ..
+        } => format!(
+            "<div class=\"message {role}\"><span class=\"sender\">{sender}</span> \
+             <span class=\"meta\">{created_at}</span><div class=\"content\">{content}</div></div>",
+            role = escape_html(role),
+            sender = escape_html(&sender_label(role, sender_name.as_deref())),
+            created_at = escape_html(created_at),
+            content = escape_html(content),
+        ),
+        TimelineItem::BranchRun {
+            description,
+            conclusion,
+            started_at,
+            ..
+        } => format!(
+            "<div class=\"message run\"><span class=\"sender\">Branch</span> \
+             <span class=\"meta\">{started_at}</span><div class=\"content\">{description}{conclusion}</div></div>",
+            started_at = escape_html(started_at),
+            description = escape_html(description),
+            conclusion = conclusion
+                .as_deref()
+                .map(|conclusion| format!("\n\n{}", escape_html(conclusion)))
+                .unwrap_or_default(),
+        ),
+        TimelineItem::WorkerRun {
+            task,
+            result,
+            status,
+            started_at,
+            ..
+        } => format!(
+            "<div class=\"message run\"><span class=\"sender\">Worker ({status})</span> \
+             <span class=\"meta\">{started_at}</span><div class=\"content\">{task}{result}</div></div>",
+            status = escape_html(status),
+            started_at = escape_html(started_at),
+            task = escape_html(task),
+            result = result
+                .as_deref()
+                .filter(|result| !result.is_empty())
+                .map(|result| format!("\n\n{}", escape_html(result)))
+                .unwrap_or_default(),
+        ),
+    }
+}
+
+/// Filename-safe form of a channel ID (which may contain `:` and `/`).
+fn export_filename(channel_id: &str, format: ExportFormat) -> String {
+    let stem: String = channel_id
+        .chars()
+        .map(|character| {
+            if character.is_ascii_alphanumeric() || matches!(character, '-' | '_' | '.') {
+                character
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("channel-{stem}.{}", format.extension())
+}
+
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(super) struct ExportChannelQuery {
+    channel_id: String,
+    /// Agent that owns the channel. Looked up from the channel index when
+    /// omitted.
+    agent_id: Option<String>,
+    /// `json` (default), `markdown`, or `html`.
+    #[serde(default)]
+    #[param(value_type = Option<String>)]
+    format: ExportFormat,
+}
+
+impl AgentScoped for ExportChannelQuery {
+    fn agent_id(&self) -> Option<&str> {
+        self.agent_id.as_deref()
+    }
+}
+
+/// Download a channel's full timeline — messages, branch runs, and worker
+/// runs, oldest first — as a JSON, Markdown, or HTML file.
+#[utoipa::path(
+    get,
+    path = "/api/channels/export",
+    tag = "channels",
+    params(ExportChannelQuery),
+    responses(
+        (status = 200, description = "Transcript file, streamed", content_type = "application/octet-stream", body = String),
+        (status = 404, description = "Unknown agent or channel"),
+    )
+)]
+pub(super) async fn export_channel(
+    State(state): State<Arc<ApiState>>,
+    AgentQuery(query): AgentQuery<ExportChannelQuery>,
+) -> Result<Response, StatusCode> {
+    let agent_id = match query.agent_id {
+        Some(agent_id) => agent_id,
+        None => state
+            .resolve_channel_agent(&query.channel_id)
+            .await
+            .ok_or(StatusCode::NOT_FOUND)?,
+    };
+    let pools = state.agent_pools.load();
+    let pool = pools.get(&agent_id).ok_or(StatusCode::NOT_FOUND)?.clone();
+
+    let channel = ChannelStore::new(pool.clone())
+        .get(&query.channel_id)
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, %agent_id, channel_id = %query.channel_id, "failed to load channel for export");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let title = channel
+        .display_name
+        .unwrap_or_else(|| query.channel_id.clone());
+
+    let format = query.format;
+    let channel_id = query.channel_id;
+    let filename = export_filename(&channel_id, format);
+    let logger = ProcessRunLogger::new(pool);
+
+    let stream = async_stream::stream! {
+        yield Ok::<_, std::io::Error>(format.header(&channel_id, &title));
+
+        let mut cursor: Option<TimelineCursor> = None;
+        let mut first = true;
+        loop {
+            let page = match logger
+                .load_channel_timeline_after(&channel_id, EXPORT_PAGE_SIZE, cursor.as_ref())
+                .await
+            {
+                Ok(page) => page,
+                Err(error) => {
+                    tracing::warn!(%error, %channel_id, "channel export aborted");
+                    yield Err(std::io::Error::other(error.to_string()));
+                    return;
+                }
+            };
+            let Some(last) = page.last() else {
+                break;
+            };
+            cursor = Some(last.cursor());
+            let mut chunk = String::new();
+            for item in &page {
+                chunk.push_str(&format.item(item, first));
+                first = false;
+            }
+            yield Ok(chunk);
+        }
+
+        yield Ok(format.footer().to_string());
+    };
+
+    let mut response = Body::from_stream(stream).into_response();
+    let headers = response.headers_mut();
+    headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static(format.content_type()),
+    );
+    if let Ok(value) = HeaderValue::from_str(&format!("attachment; filename=\"{filename}\"")) {
+        headers.insert(header::CONTENT_DISPOSITION, value);
+    }
+    headers.insert(
+        header::X_CONTENT_TYPE_OPTIONS,
+        HeaderValue::from_static("nosniff"),
+    );
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_each_format() {
+        let item = TimelineItem::Message {
+            id: "m1".into(),
+            role: "user".into(),
+            sender_name: Some("alice".into()),
+            sender_id: None,
+            content: "<b>hi</b>".into(),
+            metadata: None,
+            delivery: None,
+            created_at: "2026-01-01T10:00:00+00:00".into(),
+        };
+
+        let json = format!(
+            "{}{}{}",
+            ExportFormat::Json.header("discord:1", "General"),
+            ExportFormat::Json.item(&item, true),
+            ExportFormat::Json.footer()
+        );
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed["items"][0]["content"], "<b>hi</b>");
+
+        assert!(
+            ExportFormat::Markdown
+                .item(&item, true)
+                .starts_with("**alice**")
+        );
+        assert!(
+            ExportFormat::Html
+                .item(&item, true)
+                .contains("&lt;b&gt;hi&lt;/b&gt;")
+        );
+        assert_eq!(
+            export_filename("discord:1/2", ExportFormat::Markdown),
+            "channel-discord_1_2.md"
+        );
+    }
+}
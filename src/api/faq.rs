@@ -0,0 +1,499 @@
+//! The curated Q&A store behind FAQ matching: entries, bulk import,
+//! proposal review, and match analytics (see [`crate::agent::faq`]).
+
+use super::access::{AgentJson, AgentQuery, AgentScoped};
+use super::state::ApiState;
+
+use crate::agent::faq::{
+    self, EntryStatus, EntryUpdate, FaqAnalytics, FaqEntry, ImportFormat, NewEntry,
+};
+use crate::memory::EmbeddingModel;
+
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(super) struct FaqAnalyticsQuery {
+    agent_id: String,
+    /// How many days back to count.
+    #[serde(default = "default_analytics_days")]
+    days: i64,
+    /// Most top entries and recent misses returned.
+    #[serde(default = "default_analytics_limit")]
+    limit: i64,
+}
+
+impl AgentScoped for FaqAnalyticsQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+fn default_analytics_days() -> i64 {
+    30
+}
+
+fn default_analytics_limit() -> i64 {
+    20
+}
+
+/// How inbound questions matched the agent's Q&A store: how many were
+/// answered directly, given the match as context, or missed, which entries
+/// matched most, and the latest questions that missed.
+#[utoipa::path(
+    get,
+    path = "/api/faq/analytics",
+    tag = "faq",
+    params(FaqAnalyticsQuery),
+    responses(
+        (status = 200, body = FaqAnalytics),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
+pub(super) async fn faq_analytics(
+    State(state): State<Arc<ApiState>>,
+    AgentQuery(query): AgentQuery<FaqAnalyticsQuery>,
+) -> Result<Json<FaqAnalytics>, StatusCode> {
+    let pools = state.agent_pools.load();
+    let pool = pools.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
+
+    let analytics = faq::analytics(pool, query.days.clamp(1, 365), query.limit.clamp(1, 100))
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, agent_id = %query.agent_id, "failed to load faq analytics");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    Ok(Json(analytics))
+}
+
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(super) struct FaqAgentQuery {
+    agent_id: String,
+}
+
+impl AgentScoped for FaqAgentQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(super) struct FaqListQuery {
+    agent_id: String,
+    /// Only entries in this review state.
+    #[serde(default)]
+    status: Option<EntryStatus>,
+    #[serde(default = "default_list_limit")]
+    limit: i64,
+    #[serde(default)]
+    offset: i64,
+}
+
+impl AgentScoped for FaqListQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+fn default_list_limit() -> i64 {
+    50
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct FaqListResponse {
+    entries: Vec<FaqEntry>,
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
+pub(super) struct FaqCreateRequest {
+    agent_id: String,
+    question: String,
+    answer: String,
+    /// Channel ID patterns the entry answers in (trailing `*` for any
+    /// suffix). Omit to answer everywhere.
+    #[serde(default)]
+    channels: Vec<String>,
+    /// Memory scope the entry belongs to.
+    #[serde(default)]
+    scope: Option<String>,
+}
+
+impl AgentScoped for FaqCreateRequest {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
+pub(super) struct FaqUpdateRequest {
+    agent_id: String,
+    #[serde(default)]
+    question: Option<String>,
+    #[serde(default)]
+    answer: Option<String>,
+    #[serde(default)]
+    channels: Option<Vec<String>>,
+    /// New memory scope; an empty string clears it.
+    #[serde(default)]
+    scope: Option<String>,
+    #[serde(default)]
+    enabled: Option<bool>,
+}
+
+impl AgentScoped for FaqUpdateRequest {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
+pub(super) struct FaqImportRequest {
+    agent_id: String,
+    format: ImportFormat,
+    /// The CSV or Markdown document.
+    content: String,
+    /// Channel patterns for entries that don't name their own.
+    #[serde(default)]
+    channels: Vec<String>,
+    /// Memory scope for entries that don't name their own.
+    #[serde(default)]
+    scope: Option<String>,
+}
+
+impl AgentScoped for FaqImportRequest {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct FaqImportResponse {
+    imported: usize,
+}
+
+/// The agent's pool and embedding model.
+fn agent_store(
+    state: &ApiState,
+    agent_id: &str,
+) -> Result<(SqlitePool, Arc<EmbeddingModel>), StatusCode> {
+    let pool = state
+        .agent_pools
+        .load()
+        .get(agent_id)
+        .cloned()
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let embedding_model = state
+        .memory_searches
+        .load()
+        .get(agent_id)
+        .map(|search| search.embedding_model_arc().clone())
+        .ok_or(StatusCode::NOT_FOUND)?;
+    Ok((pool, embedding_model))
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// List Q&A entries, most hit first.
+#[utoipa::path(
+    get,
+    path = "/api/faq",
+    tag = "faq",
+    params(FaqListQuery),
+    responses(
+        (status = 200, body = FaqListResponse),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
+pub(super) async fn list_faq(
+    State(state): State<Arc<ApiState>>,
+    AgentQuery(query): AgentQuery<FaqListQuery>,
+) -> Result<Json<FaqListResponse>, StatusCode> {
+    let pools = state.agent_pools.load();
+    let pool = pools.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
+
+    let entries = faq::list_entries(
+        pool,
+        query.status,
+        query.limit.clamp(1, 500),
+        query.offset.max(0),
+    )
+    .await
+    .map_err(|error| {
+        tracing::warn!(%error, agent_id = %query.agent_id, "failed to list faq entries");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    Ok(Json(FaqListResponse { entries }))
+}
+
+/// Add an entry. It's active right away.
+#[utoipa::path(
+    post,
+    path = "/api/faq",
+    tag = "faq",
+    request_body = FaqCreateRequest,
+    responses(
+        (status = 201, body = FaqEntry),
+        (status = 400, description = "Empty question or answer"),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
+pub(super) async fn create_faq(
+    State(state): State<Arc<ApiState>>,
+    AgentJson(request): AgentJson<FaqCreateRequest>,
+) -> Result<(StatusCode, Json<FaqEntry>), StatusCode> {
+    let (pool, embedding_model) = agent_store(&state, &request.agent_id)?;
+    let question = request.question.trim().to_string();
+    let answer = request.answer.trim().to_string();
+    if question.is_empty() || answer.is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let entry = faq::create_entries(
+        &pool,
+        &embedding_model,
+        vec![NewEntry {
+            question,
+            answer,
+            channels: request.channels,
+            scope: non_empty(request.scope),
+        }],
+        EntryStatus::Active,
+        None,
+    )
+    .await
+    .map_err(|error| {
+        tracing::warn!(%error, agent_id = %request.agent_id, "failed to create faq entry");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?
+    .into_iter()
+    .next()
+    .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    tracing::info!(agent_id = %request.agent_id, entry_id = %entry.id, "faq entry created via API");
+    Ok((StatusCode::CREATED, Json(entry)))
+}
+
+/// One Q&A entry.
+#[utoipa::path(
+    get,
+    path = "/api/faq/{id}",
+    tag = "faq",
+    params(("id" = String, Path, description = "Entry ID"), FaqAgentQuery),
+    responses(
+        (status = 200, body = FaqEntry),
+        (status = 404, description = "Unknown agent or entry"),
+    )
+)]
+pub(super) async fn get_faq(
+    State(state): State<Arc<ApiState>>,
+    Path(entry_id): Path<String>,
+    AgentQuery(query): AgentQuery<FaqAgentQuery>,
+) -> Result<Json<FaqEntry>, StatusCode> {
+    let pools = state.agent_pools.load();
+    let pool = pools.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
+
+    let entry = faq::get_entry(pool, &entry_id)
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, agent_id = %query.agent_id, %entry_id, "failed to load faq entry");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    Ok(Json(entry))
+}
+
+/// Edit an entry. Changing the question re-embeds it.
+#[utoipa::path(
+    put,
+    path = "/api/faq/{id}",
+    tag = "faq",
+    params(("id" = String, Path, description = "Entry ID")),
+    request_body = FaqUpdateRequest,
+    responses(
+        (status = 200, body = FaqEntry),
+        (status = 400, description = "Empty question or answer"),
+        (status = 404, description = "Unknown agent or entry"),
+    )
+)]
+pub(super) async fn update_faq(
+    State(state): State<Arc<ApiState>>,
+    Path(entry_id): Path<String>,
+    AgentJson(request): AgentJson<FaqUpdateRequest>,
+) -> Result<Json<FaqEntry>, StatusCode> {
+    let (pool, embedding_model) = agent_store(&state, &request.agent_id)?;
+    let question = request.question.map(|question| question.trim().to_string());
+    let answer = request.answer.map(|answer| answer.trim().to_string());
+    if question.as_deref().is_some_and(str::is_empty)
+        || answer.as_deref().is_some_and(str::is_empty)
+    {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let update = EntryUpdate {
+        question,
+        answer,
+        channels: request.channels,
+        scope: request.scope.map(|scope| non_empty(Some(scope))),
+        enabled: request.enabled,
+    };
+    let entry = faq::update_entry(&pool, &embedding_model, &entry_id, update)
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, agent_id = %request.agent_id, %entry_id, "failed to update faq entry");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    Ok(Json(entry))
+}
+
+/// Delete an entry. Its match history stays in the analytics.
+#[utoipa::path(
+    delete,
+    path = "/api/faq/{id}",
+    tag = "faq",
+    params(("id" = String, Path, description = "Entry ID"), FaqAgentQuery),
+    responses(
+        (status = 200, description = "Entry deleted"),
+        (status = 404, description = "Unknown agent or entry"),
+    )
+)]
+pub(super) async fn delete_faq(
+    State(state): State<Arc<ApiState>>,
+    Path(entry_id): Path<String>,
+    AgentQuery(query): AgentQuery<FaqAgentQuery>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let pools = state.agent_pools.load();
+    let pool = pools.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
+
+    let deleted = faq::delete_entry(pool, &entry_id).await.map_err(|error| {
+        tracing::warn!(%error, agent_id = %query.agent_id, %entry_id, "failed to delete faq entry");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    if !deleted {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    tracing::info!(agent_id = %query.agent_id, %entry_id, "faq entry deleted via API");
+    Ok(Json(serde_json::json!({ "success": true })))
+}
+
+/// Bulk-import entries from CSV or Markdown. Imported entries are active.
+#[utoipa::path(
+    post,
+    path = "/api/faq/import",
+    tag = "faq",
+    request_body = FaqImportRequest,
+    responses(
+        (status = 200, body = FaqImportResponse),
+        (status = 400, description = "Malformed document, no entries, or too many"),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
+pub(super) async fn import_faq(
+    State(state): State<Arc<ApiState>>,
+    AgentJson(request): AgentJson<FaqImportRequest>,
+) -> Result<Json<FaqImportResponse>, (StatusCode, String)> {
+    let (pool, embedding_model) = agent_store(&state, &request.agent_id)
+        .map_err(|status| (status, format!("agent '{}' not found", request.agent_id)))?;
+
+    let mut entries = faq::parse_import(request.format, &request.content)
+        .map_err(|error| (StatusCode::BAD_REQUEST, error))?;
+    if entries.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "no question and answer pairs found".to_string(),
+        ));
+    }
+    let default_scope = non_empty(request.scope);
+    for entry in &mut entries {
+        if entry.channels.is_empty() {
+            entry.channels = request.channels.clone();
+        }
+        if entry.scope.is_none() {
+            entry.scope = default_scope.clone();
+        }
+    }
+
+    let imported = faq::create_entries(&pool, &embedding_model, entries, EntryStatus::Active, None)
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, agent_id = %request.agent_id, "failed to import faq entries");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to import entries".to_string(),
+            )
+        })?
+        .len();
+
+    tracing::info!(agent_id = %request.agent_id, imported, "faq entries imported via API");
+    Ok(Json(FaqImportResponse { imported }))
+}
+
+/// Approve an agent-proposed entry so it starts matching.
+#[utoipa::path(
+    post,
+    path = "/api/faq/{id}/approve",
+    tag = "faq",
+    params(("id" = String, Path, description = "Entry ID"), FaqAgentQuery),
+    responses(
+        (status = 200, body = FaqEntry),
+        (status = 404, description = "Unknown agent, or the entry isn't a pending proposal"),
+    )
+)]
+pub(super) async fn approve_faq(
+    State(state): State<Arc<ApiState>>,
+    Path(entry_id): Path<String>,
+    AgentQuery(query): AgentQuery<FaqAgentQuery>,
+) -> Result<Json<FaqEntry>, StatusCode> {
+    review_faq(&state, &query.agent_id, &entry_id, true).await
+}
+
+/// Reject an agent-proposed entry. It's kept, marked rejected, and never
+/// matched.
+#[utoipa::path(
+    post,
+    path = "/api/faq/{id}/reject",
+    tag = "faq",
+    params(("id" = String, Path, description = "Entry ID"), FaqAgentQuery),
+    responses(
+        (status = 200, body = FaqEntry),
+        (status = 404, description = "Unknown agent, or the entry isn't a pending proposal"),
+    )
+)]
+pub(super) async fn reject_faq(
+    State(state): State<Arc<ApiState>>,
+    Path(entry_id): Path<String>,
+    AgentQuery(query): AgentQuery<FaqAgentQuery>,
+) -> Result<Json<FaqEntry>, StatusCode> {
+    review_faq(&state, &query.agent_id, &entry_id, false).await
+}
+
+async fn review_faq(
+    state: &ApiState,
+    agent_id: &str,
+    entry_id: &str,
+    approve: bool,
+) -> Result<Json<FaqEntry>, StatusCode> {
+    let pools = state.agent_pools.load();
+    let pool = pools.get(agent_id).ok_or(StatusCode::NOT_FOUND)?;
+
+    let entry = faq::review_proposal(pool, entry_id, approve)
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, %agent_id, %entry_id, "failed to review faq proposal");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    tracing::info!(%agent_id, %entry_id, approve, "faq proposal reviewed via API");
+    Ok(Json(entry))
+}
@@ -0,0 +1,432 @@
+//! Read-only GraphQL view over the query surface.
+//!
+//! Exposes agents, their channels and channel timelines, memories, and cortex
+//! events as one graph, so a dashboard screen can fetch everything it shows
+//! in a single `POST /api/graphql` instead of one REST call per panel. The
+//! resolvers read from the same stores as the REST handlers. There are no
+//! mutations; writes stay on the REST API. An agent-restricted token only
+//! sees its own agents: every other node hangs off a root resolver that
+//! filters by the caller's agents.
+
+use super::memories::{parse_memory_type, parse_sort};
+use super::server::ApiPrincipal;
+use super::state::ApiState;
+
+use crate::conversation::channels::{ChannelInfo, ChannelStore};
+use crate::conversation::history::{ProcessRunLogger, TimelineItem};
+
+use async_graphql::{
+    ComplexObject, Context, EmptyMutation, EmptySubscription, Object, Schema, SimpleObject,
+};
+use axum::extract::State;
+use axum::{Extension, Json};
+
+use std::sync::{Arc, LazyLock};
+
+/// Deepest selection a query may nest.
+const MAX_DEPTH: usize = 8;
+
+/// Upper bound on selected fields across a query, so a single request can't
+/// fan out into thousands of store reads.
+const MAX_COMPLEXITY: usize = 1000;
+
+/// Per-list caps, matching the REST endpoints.
+const MAX_CHANNELS: i64 = 200;
+const MAX_TIMELINE_ITEMS: i64 = 100;
+const MAX_MEMORIES: i64 = 200;
+const MAX_CORTEX_EVENTS: i64 = 200;
+
+type ApiSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+static SCHEMA: LazyLock<ApiSchema> = LazyLock::new(|| {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .limit_depth(MAX_DEPTH)
+        .limit_complexity(MAX_COMPLEXITY)
+        .finish()
+});
+
+/// Execute a GraphQL query. Errors are reported in the response body per
+/// the GraphQL spec, so this always answers 200.
+#[utoipa::path(
+    post,
+    path = "/api/graphql",
+    tag = "system",
+    request_body(content = Object, description = "GraphQL request: `query`, plus optional `variables` and `operationName`"),
+    responses(
+        (status = 200, description = "GraphQL response with `data` and, on failure, `errors`", body = Object),
+    )
+)]
+pub(super) async fn graphql(
+    State(state): State<Arc<ApiState>>,
+    principal: Option<Extension<ApiPrincipal>>,
+    Json(request): Json<async_graphql::Request>,
+) -> Json<async_graphql::Response> {
+    let mut request = request.data(state);
+    if let Some(Extension(principal)) = principal {
+        request = request.data(principal);
+    }
+    Json(SCHEMA.execute(request).await)
+}
+
+fn api_state<'a>(ctx: &Context<'a>) -> async_graphql::Result<&'a Arc<ApiState>> {
+    ctx.data::<Arc<ApiState>>()
+}
+
+/// Whether the caller's token may see `agent_id`. Unauthenticated instances
+/// see every agent.
+fn can_see_agent(ctx: &Context<'_>, agent_id: &str) -> bool {
+    ctx.data_opt::<ApiPrincipal>()
+        .is_none_or(|principal| principal.allows_agent(agent_id))
+}
+
+/// Log a store failure and return a generic error to the client.
+fn internal_error(error: impl std::fmt::Display, what: &str) -> async_graphql::Error {
+    tracing::warn!(%error, "graphql: failed to load {what}");
+    async_graphql::Error::new(format!("failed to load {what}"))
+}
+
+pub(super) struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Every configured agent the caller may see.
+    async fn agents(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<Agent>> {
+        let state = api_state(ctx)?;
+        Ok(state
+            .agent_configs
+            .load()
+            .iter()
+            .filter(|agent| can_see_agent(ctx, &agent.id))
+            .map(Agent::from)
+            .collect())
+    }
+
+    async fn agent(&self, ctx: &Context<'_>, id: String) -> async_graphql::Result<Option<Agent>> {
+        let state = api_state(ctx)?;
+        Ok(state
+            .agent_configs
+            .load()
+            .iter()
+            .find(|agent| agent.id == id && can_see_agent(ctx, &agent.id))
+            .map(Agent::from))
+    }
+
+    /// A channel by ID, on whichever agent owns it.
+    async fn channel(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> async_graphql::Result<Option<Channel>> {
+        let state = api_state(ctx)?;
+        let Some(agent_id) = state
+            .resolve_channel_agent(&id)
+            .await
+            .filter(|agent_id| can_see_agent(ctx, agent_id))
+        else {
+            return Ok(None);
+        };
+        let Some(pool) = state.agent_pools.load().get(&agent_id).cloned() else {
+            return Ok(None);
+        };
+        let channel = ChannelStore::new(pool)
+            .get(&id)
+            .await
+            .map_err(|error| internal_error(error, "channel"))?;
+        Ok(channel.map(|channel| Channel::new(agent_id, channel)))
+    }
+}
+
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub(super) struct Agent {
+    id: String,
+    display_name: Option<String>,
+    role: Option<String>,
+    context_window: u64,
+    max_turns: u64,
+    max_concurrent_branches: u64,
+    max_concurrent_workers: u64,
+}
+
+impl From<&super::AgentInfo> for Agent {
+    fn from(info: &super::AgentInfo) -> Self {
+        Self {
+            id: info.id.clone(),
+            display_name: info.display_name.clone(),
+            role: info.role.clone(),
+            context_window: info.context_window as u64,
+            max_turns: info.max_turns as u64,
+            max_concurrent_branches: info.max_concurrent_branches as u64,
+            max_concurrent_workers: info.max_concurrent_workers as u64,
+        }
+    }
+}
+
+#[ComplexObject]
+impl Agent {
+    /// The agent's channels, most recently active first.
+    async fn channels(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = false)] include_inactive: bool,
+        #[graphql(default = 50)] limit: i64,
+    ) -> async_graphql::Result<Vec<Channel>> {
+        let state = api_state(ctx)?;
+        let Some(pool) = state.agent_pools.load().get(&self.id).cloned() else {
+            return Ok(Vec::new());
+        };
+        let is_active_filter = (!include_inactive).then_some(true);
+        let mut channels = ChannelStore::new(pool)
+            .list(is_active_filter)
+            .await
+            .map_err(|error| internal_error(error, "channels"))?;
+        channels.sort_by_key(|channel| std::cmp::Reverse(channel.last_activity_at));
+        channels.truncate(limit.clamp(0, MAX_CHANNELS) as usize);
+        Ok(channels
+            .into_iter()
+            .map(|channel| Channel::new(self.id.clone(), channel))
+            .collect())
+    }
+
+    /// Memories, sorted by `recent`, `importance`, or `most_accessed`.
+    async fn memories(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 20)] limit: i64,
+        memory_type: Option<String>,
+        #[graphql(default_with = "String::from(\"recent\")")] sort: String,
+    ) -> async_graphql::Result<Vec<Memory>> {
+        let state = api_state(ctx)?;
+        let Some(memory_search) = state.memory_searches.load().get(&self.id).cloned() else {
+            return Ok(Vec::new());
+        };
+        let memories = memory_search
+            .store()
+            .get_sorted(
+                parse_sort(&sort),
+                limit.clamp(0, MAX_MEMORIES),
+                memory_type.as_deref().and_then(parse_memory_type),
+            )
+            .await
+            .map_err(|error| internal_error(error, "memories"))?;
+        Ok(memories.into_iter().map(Memory::from).collect())
+    }
+
+    /// Cortex events, newest first.
+    async fn cortex_events(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 20)] limit: i64,
+        event_type: Option<String>,
+    ) -> async_graphql::Result<Vec<CortexEvent>> {
+        let state = api_state(ctx)?;
+        let Some(pool) = state.agent_pools.load().get(&self.id).cloned() else {
+            return Ok(Vec::new());
+        };
+        let events = crate::agent::cortex::CortexLogger::new(pool)
+            .load_events(limit.clamp(0, MAX_CORTEX_EVENTS), 0, event_type.as_deref())
+            .await
+            .map_err(|error| internal_error(error, "cortex events"))?;
+        Ok(events.into_iter().map(CortexEvent::from).collect())
+    }
+}
+
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub(super) struct Channel {
+    id: String,
+    agent_id: String,
+    platform: String,
+    display_name: Option<String>,
+    is_active: bool,
+    last_activity_at: chrono::DateTime<chrono::Utc>,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl Channel {
+    fn new(agent_id: String, channel: ChannelInfo) -> Self {
+        Self {
+            id: channel.id,
+            agent_id,
+            platform: channel.platform,
+            display_name: channel.display_name,
+            is_active: channel.is_active,
+            last_activity_at: channel.last_activity_at,
+            created_at: channel.created_at,
+        }
+    }
+}
+
+#[ComplexObject]
+impl Channel {
+    /// The most recent messages, branch runs, and worker runs, oldest first.
+    async fn timeline(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 50)] limit: i64,
+    ) -> async_graphql::Result<Vec<TimelineEntry>> {
+        let state = api_state(ctx)?;
+        let Some(pool) = state.agent_pools.load().get(&self.agent_id).cloned() else {
+            return Ok(Vec::new());
+        };
+        let items = ProcessRunLogger::new(pool)
+            .load_channel_timeline(&self.id, limit.clamp(0, MAX_TIMELINE_ITEMS), None)
+            .await
+            .map_err(|error| internal_error(error, "timeline"))?;
+        Ok(items.into_iter().map(TimelineEntry::from).collect())
+    }
+}
+
+/// One timeline item. `kind` is `message`, `branch_run`, or `worker_run`;
+/// fields that don't apply to the kind are null.
+#[derive(SimpleObject)]
+pub(super) struct TimelineEntry {
+    kind: String,
+    id: String,
+    /// Message creation or run start.
+    timestamp: String,
+    role: Option<String>,
+    sender_name: Option<String>,
+    sender_id: Option<String>,
+    content: Option<String>,
+    description: Option<String>,
+    conclusion: Option<String>,
+    task: Option<String>,
+    result: Option<String>,
+    status: Option<String>,
+    completed_at: Option<String>,
+}
+
+impl From<TimelineItem> for TimelineEntry {
+    fn from(item: TimelineItem) -> Self {
+        let mut entry = Self {
+            kind: String::new(),
+            id: item.id().to_string(),
+            timestamp: item.timestamp().to_string(),
+            role: None,
+            sender_name: None,
+            sender_id: None,
+            content: None,
+            description: None,
+            conclusion: None,
+            task: None,
+            result: None,
+            status: None,
+            completed_at: None,
+        };
+        match item {
+            TimelineItem::Message {
+                role,
+                sender_name,
+                sender_id,
+                content,
+                ..
+            } => {
+                entry.kind = "message".into();
+                entry.role = Some(role);
+                entry.sender_name = sender_name;
+                entry.sender_id = sender_id;
+                entry.content = Some(content);
+            }
+            TimelineItem::BranchRun {
+                description,
+                conclusion,
+                completed_at,
+                ..
+            } => {
+                entry.kind = "branch_run".into();
+                entry.description = Some(description);
+                entry.conclusion = conclusion;
+                entry.completed_at = completed_at;
+            }
+            TimelineItem::WorkerRun {
+                task,
+                result,
+                status,
+                completed_at,
+                ..
+            } => {
+                entry.kind = "worker_run".into();
+                entry.task = Some(task);
+                entry.result = result;
+                entry.status = Some(status);
+                entry.completed_at = completed_at;
+            }
+        }
+        entry
+    }
+}
+
+#[derive(SimpleObject)]
+pub(super) struct Memory {
+    id: String,
+    content: String,
+    memory_type: String,
+    importance: f32,
+    created_at: chrono::DateTime<chrono::Utc>,
+    updated_at: chrono::DateTime<chrono::Utc>,
+    access_count: i64,
+    source: Option<String>,
+    channel_id: Option<String>,
+    scope: Option<String>,
+    tags: Vec<String>,
+}
+
+impl From<crate::memory::Memory> for Memory {
+    fn from(memory: crate::memory::Memory) -> Self {
+        Self {
+            id: memory.id,
+            content: memory.content,
+            memory_type: memory.memory_type.to_string(),
+            importance: memory.importance,
+            created_at: memory.created_at,
+            updated_at: memory.updated_at,
+            access_count: memory.access_count,
+            source: memory.source,
+            channel_id: memory.channel_id.map(|channel_id| channel_id.to_string()),
+            scope: memory.scope,
+            tags: memory.tags,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub(super) struct CortexEvent {
+    id: String,
+    event_type: String,
+    summary: String,
+    details: Option<async_graphql::Json<serde_json::Value>>,
+    created_at: String,
+}
+
+impl From<crate::agent::cortex::CortexEvent> for CortexEvent {
+    fn from(event: crate::agent::cortex::CortexEvent) -> Self {
+        Self {
+            id: event.id,
+            event_type: event.event_type,
+            summary: event.summary,
+            details: event.details.map(async_graphql::Json),
+            created_at: event.created_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schema_exposes_the_query_surface() {
+        let sdl = SCHEMA.sdl();
+        for field in [
+            "agents",
+            "channels(",
+            "timeline(",
+            "memories(",
+            "cortexEvents(",
+        ] {
+            assert!(sdl.contains(field), "schema is missing {field}");
+        }
+        assert!(!sdl.contains("type Mutation"));
+    }
+}
@@ -0,0 +1,584 @@
+//! `Idempotency-Key` support for mutating API requests.
+//!
+//! Clients that retry on timeout send the same `Idempotency-Key` header with
+//! each attempt. The first response is persisted (in a dedicated
+//! `idempotency.redb`) and replayed verbatim for any retry inside the
+//! retention window, so a retried config update or operator message is only
+//! applied once. Records are also indexed by creation time, and each save
+//! expires a bounded batch of the oldest past the window.
+
+use super::server::ApiPrincipal;
+use super::state::ApiState;
+
+use axum::Json;
+use axum::body::Body;
+use axum::extract::{Request, State};
+use axum::http::{HeaderName, HeaderValue, Method, StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use redb::{Database, ReadableTable, ReadableTableMetadata as _, TableDefinition};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest as _, Sha256};
+
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// Table: scoped idempotency key -> JSON-encoded `StoredResponse`.
+const RECORDS_TABLE: TableDefinition<&str, &[u8]> = TableDefinition::new("idempotency_records");
+
+/// Table: (created_at_ms, scoped key) -> (), for expiring oldest first.
+const CREATED_INDEX_TABLE: TableDefinition<(i64, &str), ()> =
+    TableDefinition::new("idempotency_created_index");
+
+/// Expired records removed per save, so one write never pays for a backlog.
+const EXPIRE_BATCH: usize = 64;
+
+/// Header carrying the client-chosen key.
+const IDEMPOTENCY_KEY_HEADER: HeaderName = HeaderName::from_static("idempotency-key");
+
+/// Set on replayed responses so clients can tell a replay from a fresh result.
+const REPLAYED_HEADER: HeaderName = HeaderName::from_static("idempotent-replayed");
+
+const MAX_KEY_LENGTH: usize = 255;
+
+/// Request bodies above this are rejected rather than fingerprinted.
+const MAX_REQUEST_BYTES: usize = 10 * 1024 * 1024;
+
+/// Responses above this aren't persisted; a retry re-executes instead.
+const MAX_STORED_RESPONSE_BYTES: usize = 1024 * 1024;
+
+/// A persisted first response for an idempotency key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StoredResponse {
+    /// SHA-256 of method, path, query, and body. Reusing a key for a
+    /// different request is a client bug and is rejected.
+    fingerprint: String,
+    status: u16,
+    content_type: Option<String>,
+    #[serde(with = "crate::base64_bytes")]
+    body: Vec<u8>,
+    created_at_ms: i64,
+}
+
+/// Persistent response cache for idempotent retries.
+pub struct IdempotencyStore {
+    db: Arc<Database>,
+    retention_ms: i64,
+    /// Keys whose first request is still executing. A concurrent retry gets a
+    /// 409 instead of running the mutation a second time.
+    in_flight: Mutex<HashSet<String>>,
+}
+
+impl std::fmt::Debug for IdempotencyStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IdempotencyStore")
+            .field("retention_ms", &self.retention_ms)
+            .finish()
+    }
+}
+
+impl IdempotencyStore {
+    /// Open or create the store at `path`, keeping responses for
+    /// `retention_secs`.
+    pub fn new(path: &Path, retention_secs: u64) -> crate::error::Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|error| anyhow::anyhow!(error))?;
+        }
+        let db = Database::create(path).map_err(|error| {
+            anyhow::anyhow!(
+                "failed to open idempotency db at {}: {error}",
+                path.display()
+            )
+        })?;
+
+        let write_txn = db.begin_write().map_err(|error| anyhow::anyhow!(error))?;
+        {
+            let records = write_txn
+                .open_table(RECORDS_TABLE)
+                .map_err(|error| anyhow::anyhow!(error))?;
+            let mut index = write_txn
+                .open_table(CREATED_INDEX_TABLE)
+                .map_err(|error| anyhow::anyhow!(error))?;
+            // Stores from before the index existed: index their records once.
+            if index.is_empty().map_err(|error| anyhow::anyhow!(error))? {
+                for entry in records.iter().map_err(|error| anyhow::anyhow!(error))? {
+                    let (key, value) = entry.map_err(|error| anyhow::anyhow!(error))?;
+                    let created_at_ms = serde_json::from_slice::<StoredResponse>(value.value())
+                        .map(|stored| stored.created_at_ms)
+                        .unwrap_or(i64::MIN);
+                    index
+                        .insert((created_at_ms, key.value()), ())
+                        .map_err(|error| anyhow::anyhow!(error))?;
+                }
+            }
+        }
+        write_txn.commit().map_err(|error| anyhow::anyhow!(error))?;
+
+        Ok(Self {
+            db: Arc::new(db),
+            retention_ms: (retention_secs as i64).saturating_mul(1000),
+            in_flight: Mutex::new(HashSet::new()),
+        })
+    }
+
+    async fn get(&self, key: &str) -> crate::error::Result<Option<StoredResponse>> {
+        let db = self.db.clone();
+        let retention_ms = self.retention_ms;
+        let key = key.to_string();
+        tokio::task::spawn_blocking(move || -> crate::error::Result<Option<StoredResponse>> {
+            let read_txn = db.begin_read().map_err(|error| anyhow::anyhow!(error))?;
+            let table = read_txn
+                .open_table(RECORDS_TABLE)
+                .map_err(|error| anyhow::anyhow!(error))?;
+            let Some(value) = table
+                .get(key.as_str())
+                .map_err(|error| anyhow::anyhow!(error))?
+            else {
+                return Ok(None);
+            };
+            let record: StoredResponse =
+                serde_json::from_slice(value.value()).map_err(|error| anyhow::anyhow!(error))?;
+            if is_expired(&record, retention_ms, chrono::Utc::now().timestamp_millis()) {
+                return Ok(None);
+            }
+            Ok(Some(record))
+        })
+        .await
+        .map_err(|error| anyhow::anyhow!("idempotency lookup task failed: {error}"))?
+    }
+
+    /// Persist a response and drop up to [`EXPIRE_BATCH`] of the oldest
+    /// records past the retention window.
+    async fn save(&self, key: &str, record: &StoredResponse) -> crate::error::Result<()> {
+        let data = serde_json::to_vec(record).map_err(|error| anyhow::anyhow!(error))?;
+        let db = self.db.clone();
+        let cutoff = chrono::Utc::now()
+            .timestamp_millis()
+            .saturating_sub(self.retention_ms);
+        let created_at_ms = record.created_at_ms;
+        let key = key.to_string();
+        tokio::task::spawn_blocking(move || -> crate::error::Result<()> {
+            let write_txn = db.begin_write().map_err(|error| anyhow::anyhow!(error))?;
+            {
+                let mut records = write_txn
+                    .open_table(RECORDS_TABLE)
+                    .map_err(|error| anyhow::anyhow!(error))?;
+                let mut index = write_txn
+                    .open_table(CREATED_INDEX_TABLE)
+                    .map_err(|error| anyhow::anyhow!(error))?;
+
+                let mut expired = Vec::new();
+                for entry in index
+                    .range(..(cutoff, ""))
+                    .map_err(|error| anyhow::anyhow!(error))?
+                    .take(EXPIRE_BATCH)
+                {
+                    let (entry, _) = entry.map_err(|error| anyhow::anyhow!(error))?;
+                    let (entry_created_at_ms, entry_key) = entry.value();
+                    expired.push((entry_created_at_ms, entry_key.to_string()));
+                }
+                for (entry_created_at_ms, entry_key) in &expired {
+                    index
+                        .remove((*entry_created_at_ms, entry_key.as_str()))
+                        .map_err(|error| anyhow::anyhow!(error))?;
+                    records
+                        .remove(entry_key.as_str())
+                        .map_err(|error| anyhow::anyhow!(error))?;
+                }
+
+                let previous = records
+                    .insert(key.as_str(), data.as_slice())
+                    .map_err(|error| anyhow::anyhow!(error))?
+                    .and_then(|previous| {
+                        serde_json::from_slice::<StoredResponse>(previous.value()).ok()
+                    });
+                if let Some(previous) = previous {
+                    index
+                        .remove((previous.created_at_ms, key.as_str()))
+                        .map_err(|error| anyhow::anyhow!(error))?;
+                }
+                index
+                    .insert((created_at_ms, key.as_str()), ())
+                    .map_err(|error| anyhow::anyhow!(error))?;
+            }
+            write_txn.commit().map_err(|error| anyhow::anyhow!(error))?;
+            Ok(())
+        })
+        .await
+        .map_err(|error| anyhow::anyhow!("idempotency save task failed: {error}"))?
+    }
+
+    fn begin(&self, key: &str) -> bool {
+        self.in_flight
+            .lock()
+            .map(|mut in_flight| in_flight.insert(key.to_string()))
+            .unwrap_or(false)
+    }
+
+    fn finish(&self, key: &str) {
+        if let Ok(mut in_flight) = self.in_flight.lock() {
+            in_flight.remove(key);
+        }
+    }
+
+    /// Mark `key` in flight, then look for a stored response. The marker is
+    /// held only when the caller should run the request.
+    async fn claim<'a>(
+        &'a self,
+        key: &'a str,
+        fingerprint: &str,
+    ) -> crate::error::Result<Claim<'a>> {
+        if !self.begin(key) {
+            return Ok(Claim::InProgress);
+        }
+        let guard = InFlightGuard { store: self, key };
+        Ok(match self.get(key).await? {
+            Some(record) if record.fingerprint == fingerprint => Claim::Replay(record),
+            Some(_) => Claim::Mismatch,
+            None => Claim::Run(guard),
+        })
+    }
+}
+
+/// Outcome of [`IdempotencyStore::claim`].
+enum Claim<'a> {
+    /// No stored response; run the request while holding the marker.
+    Run(InFlightGuard<'a>),
+    /// A matching response was stored; replay it.
+    Replay(StoredResponse),
+    /// The key was stored for a different request.
+    Mismatch,
+    /// Another request with this key is still executing.
+    InProgress,
+}
+
+/// Releases the in-flight marker even if the handler panics.
+struct InFlightGuard<'a> {
+    store: &'a IdempotencyStore,
+    key: &'a str,
+}
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        self.store.finish(self.key);
+    }
+}
+
+/// Replay or record responses for mutating requests that carry an
+/// `Idempotency-Key` header. Requests without the header pass through.
+pub(super) async fn idempotency_middleware(
+    State(state): State<Arc<ApiState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let is_mutating = matches!(
+        *request.method(),
+        Method::POST | Method::PUT | Method::PATCH | Method::DELETE
+    );
+    let Some(store) = state.idempotency_store.as_ref() else {
+        return next.run(request).await;
+    };
+    if !is_mutating {
+        return next.run(request).await;
+    }
+    let Some(client_key) = request.headers().get(&IDEMPOTENCY_KEY_HEADER) else {
+        return next.run(request).await;
+    };
+    let Some(client_key) = client_key
+        .to_str()
+        .ok()
+        .map(str::trim)
+        .filter(|key| !key.is_empty() && key.len() <= MAX_KEY_LENGTH)
+        .map(str::to_string)
+    else {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            format!("Idempotency-Key must be 1-{MAX_KEY_LENGTH} visible ASCII characters"),
+        );
+    };
+
+    // Keys are scoped to the authenticated token so two clients can't
+    // collide on (or read) each other's cached responses.
+    let principal = request
+        .extensions()
+        .get::<ApiPrincipal>()
+        .map(|principal| principal.name.clone())
+        .unwrap_or_default();
+    let scoped_key = format!("{principal}\u{1f}{client_key}");
+
+    let (parts, body) = request.into_parts();
+    let body_bytes = match axum::body::to_bytes(body, MAX_REQUEST_BYTES).await {
+        Ok(bytes) => bytes,
+        Err(error) => {
+            tracing::debug!(%error, "failed to buffer idempotent request body");
+            return error_response(StatusCode::PAYLOAD_TOO_LARGE, "request body too large");
+        }
+    };
+    let fingerprint = fingerprint(
+        &parts.method,
+        parts
+            .uri
+            .path_and_query()
+            .map(|value| value.as_str())
+            .unwrap_or("/"),
+        &body_bytes,
+    );
+
+    // The in-flight marker is taken before the lookup, so a retry can't miss
+    // a response the first request saves between the two.
+    let _guard = match store.claim(&scoped_key, &fingerprint).await {
+        Ok(Claim::Run(guard)) => guard,
+        Ok(Claim::Replay(record)) => {
+            tracing::debug!(key = %client_key, "replaying idempotent response");
+            return replay(record);
+        }
+        Ok(Claim::Mismatch) => {
+            return error_response(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "Idempotency-Key was already used for a different request",
+            );
+        }
+        Ok(Claim::InProgress) => {
+            return error_response(
+                StatusCode::CONFLICT,
+                "a request with this Idempotency-Key is still in progress",
+            );
+        }
+        Err(error) => {
+            // Failing open would risk a double-apply, so refuse the request.
+            tracing::warn!(%error, "idempotency lookup failed");
+            return error_response(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "idempotency store unavailable",
+            );
+        }
+    };
+
+    let request = Request::from_parts(parts, Body::from(body_bytes));
+    let response = next.run(request).await;
+
+    // Server errors are usually transient; let the retry run for real.
+    let is_streaming = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("text/event-stream"));
+    let is_oversized = response
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<usize>().ok())
+        .is_some_and(|length| length > MAX_STORED_RESPONSE_BYTES);
+    if response.status().is_server_error() || is_streaming || is_oversized {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let body_bytes = match axum::body::to_bytes(body, MAX_REQUEST_BYTES).await {
+        Ok(bytes) => bytes,
+        Err(error) => {
+            tracing::warn!(%error, key = %client_key, "failed to buffer idempotent response");
+            return error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to buffer response for an idempotent request",
+            );
+        }
+    };
+    if body_bytes.len() > MAX_STORED_RESPONSE_BYTES {
+        tracing::debug!(key = %client_key, "idempotent response too large to persist");
+        return Response::from_parts(parts, Body::from(body_bytes));
+    }
+
+    let record = StoredResponse {
+        fingerprint,
+        status: parts.status.as_u16(),
+        content_type: parts
+            .headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string),
+        body: body_bytes.to_vec(),
+        created_at_ms: chrono::Utc::now().timestamp_millis(),
+    };
+    if let Err(error) = store.save(&scoped_key, &record).await {
+        tracing::warn!(%error, key = %client_key, "failed to persist idempotent response");
+    }
+
+    parts
+        .headers
+        .insert(REPLAYED_HEADER, HeaderValue::from_static("false"));
+    Response::from_parts(parts, Body::from(body_bytes))
+}
+
+fn is_expired(record: &StoredResponse, retention_ms: i64, now_ms: i64) -> bool {
+    now_ms.saturating_sub(record.created_at_ms) > retention_ms
+}
+
+fn fingerprint(method: &Method, path_and_query: &str, body: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(method.as_str().as_bytes());
+    hasher.update([0]);
+    hasher.update(path_and_query.as_bytes());
+    hasher.update([0]);
+    hasher.update(body);
+    hex::encode(hasher.finalize())
+}
+
+fn replay(record: StoredResponse) -> Response {
+    let mut response = Response::builder()
+        .status(StatusCode::from_u16(record.status).unwrap_or(StatusCode::OK))
+        .header(REPLAYED_HEADER, "true");
+    if let Some(content_type) = record.content_type {
+        response = response.header(header::CONTENT_TYPE, content_type);
+    }
+    response
+        .body(Body::from(record.body))
+        .unwrap_or_else(|error| {
+            tracing::warn!(%error, "failed to build replayed response");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        })
+}
+
+fn error_response(status: StatusCode, message: impl Into<String>) -> Response {
+    (
+        status,
+        Json(json!({ "error": "idempotency", "message": message.into() })),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(fingerprint: &str, created_at_ms: i64) -> StoredResponse {
+        StoredResponse {
+            fingerprint: fingerprint.into(),
+            status: 200,
+            content_type: Some("application/json".into()),
+            body: br#"{"success":true}"#.to_vec(),
+            created_at_ms,
+        }
+    }
+
+    #[test]
+    fn fingerprint_covers_method_path_and_body() {
+        let base = fingerprint(&Method::POST, "/api/config", b"{}");
+        assert_eq!(base, fingerprint(&Method::POST, "/api/config", b"{}"));
+        assert_ne!(base, fingerprint(&Method::PUT, "/api/config", b"{}"));
+        assert_ne!(base, fingerprint(&Method::POST, "/api/config?x=1", b"{}"));
+        assert_ne!(base, fingerprint(&Method::POST, "/api/config", b"{ }"));
+    }
+
+    fn record_count(store: &IdempotencyStore) -> (u64, u64) {
+        let read_txn = store.db.begin_read().unwrap();
+        let records = read_txn.open_table(RECORDS_TABLE).unwrap();
+        let index = read_txn.open_table(CREATED_INDEX_TABLE).unwrap();
+        (records.len().unwrap(), index.len().unwrap())
+    }
+
+    #[tokio::test]
+    async fn stored_responses_round_trip_and_expire() {
+        let directory = tempfile::tempdir().unwrap();
+        let store = IdempotencyStore::new(&directory.path().join("idempotency.redb"), 60).unwrap();
+
+        let now = chrono::Utc::now().timestamp_millis();
+        store
+            .save("token\u{1f}fresh", &record("abc", now))
+            .await
+            .unwrap();
+        store
+            .save("token\u{1f}stale", &record("def", now - 120_000))
+            .await
+            .unwrap();
+
+        let fresh = store.get("token\u{1f}fresh").await.unwrap().unwrap();
+        assert_eq!(fresh.fingerprint, "abc");
+        assert_eq!(fresh.body, br#"{"success":true}"#);
+        assert!(store.get("token\u{1f}stale").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn saves_expire_old_records_in_bounded_batches() {
+        let directory = tempfile::tempdir().unwrap();
+        let store = IdempotencyStore::new(&directory.path().join("idempotency.redb"), 60).unwrap();
+
+        // Written straight to the tables, as if left behind by past saves.
+        let stale = chrono::Utc::now().timestamp_millis() - 120_000;
+        let data = serde_json::to_vec(&record("abc", stale)).unwrap();
+        let write_txn = store.db.begin_write().unwrap();
+        {
+            let mut records = write_txn.open_table(RECORDS_TABLE).unwrap();
+            let mut index = write_txn.open_table(CREATED_INDEX_TABLE).unwrap();
+            for index_key in 0..EXPIRE_BATCH + 10 {
+                let key = format!("token\u{1f}{index_key}");
+                records.insert(key.as_str(), data.as_slice()).unwrap();
+                index.insert((stale, key.as_str()), ()).unwrap();
+            }
+        }
+        write_txn.commit().unwrap();
+
+        let now = chrono::Utc::now().timestamp_millis();
+        store
+            .save("token\u{1f}fresh", &record("abc", now))
+            .await
+            .unwrap();
+        assert_eq!(record_count(&store), (11, 11));
+
+        // The next save clears the rest. Overwriting a key moves its index
+        // entry rather than adding one.
+        store
+            .save("token\u{1f}fresh", &record("abc", now + 1))
+            .await
+            .unwrap();
+        assert_eq!(record_count(&store), (1, 1));
+    }
+
+    #[test]
+    fn concurrent_use_of_a_key_is_rejected() {
+        let directory = tempfile::tempdir().unwrap();
+        let store = IdempotencyStore::new(&directory.path().join("idempotency.redb"), 60).unwrap();
+
+        assert!(store.begin("key"));
+        assert!(!store.begin("key"));
+        store.finish("key");
+        assert!(store.begin("key"));
+    }
+
+    #[tokio::test]
+    async fn retry_after_the_first_request_finishes_is_replayed() {
+        let directory = tempfile::tempdir().unwrap();
+        let store = IdempotencyStore::new(&directory.path().join("idempotency.redb"), 60).unwrap();
+        let key = "token\u{1f}retry";
+
+        let first = store.claim(key, "abc").await.unwrap();
+        let Claim::Run(first_guard) = first else {
+            panic!("first request should run");
+        };
+
+        // The retry lands while the first request is still executing.
+        assert!(matches!(
+            store.claim(key, "abc").await.unwrap(),
+            Claim::InProgress
+        ));
+
+        // The first request saves its response and releases the marker; the
+        // retry must replay it rather than run the handler again.
+        let now = chrono::Utc::now().timestamp_millis();
+        store.save(key, &record("abc", now)).await.unwrap();
+        drop(first_guard);
+
+        let Claim::Replay(replayed) = store.claim(key, "abc").await.unwrap() else {
+            panic!("retry should replay the stored response");
+        };
+        assert_eq!(replayed.body, br#"{"success":true}"#);
+        assert!(matches!(
+            store.claim(key, "def").await.unwrap(),
+            Claim::Mismatch
+        ));
+        // Neither the replay nor the mismatch leaves the key in flight.
+        assert!(store.begin(key));
+    }
+}
@@ -1,7 +1,8 @@
 use super::state::ApiState;
 
+use super::access::{AgentQuery, AgentScoped};
 use axum::Json;
-use axum::extract::{Query, State};
+use axum::extract::State;
 use axum::http::StatusCode;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
@@ -39,16 +40,28 @@ pub(super) struct IngestQuery {
     agent_id: String,
 }
 
+impl AgentScoped for IngestQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
 #[derive(Deserialize)]
 pub(super) struct IngestDeleteQuery {
     agent_id: String,
     content_hash: String,
 }
 
+impl AgentScoped for IngestDeleteQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
 /// List ingested files with progress info for in-progress ones.
 pub(super) async fn list_ingest_files(
     State(state): State<Arc<ApiState>>,
-    Query(query): Query<IngestQuery>,
+    AgentQuery(query): AgentQuery<IngestQuery>,
 ) -> Result<Json<IngestFilesResponse>, StatusCode> {
     use sqlx::Row as _;
 
@@ -97,7 +110,7 @@ pub(super) async fn list_ingest_files(
 /// Upload one or more files to the agent's ingest directory.
 pub(super) async fn upload_ingest_file(
     State(state): State<Arc<ApiState>>,
-    Query(query): Query<IngestQuery>,
+    AgentQuery(query): AgentQuery<IngestQuery>,
     mut multipart: axum::extract::Multipart,
 ) -> Result<Json<IngestUploadResponse>, StatusCode> {
     let workspaces = state.agent_workspaces.load();
@@ -197,7 +210,7 @@ pub(super) async fn upload_ingest_file(
 /// Delete a completed ingestion file record from history.
 pub(super) async fn delete_ingest_file(
     State(state): State<Arc<ApiState>>,
-    Query(query): Query<IngestDeleteQuery>,
+    AgentQuery(query): AgentQuery<IngestDeleteQuery>,
 ) -> Result<Json<IngestDeleteResponse>, StatusCode> {
     let pools = state.agent_pools.load();
     let pool = pools.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
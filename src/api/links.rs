@@ -834,6 +834,7 @@ pub async fn create_human(
         telegram_id: request.telegram_id.clone().filter(|s| !s.is_empty()),
         slack_id: request.slack_id.clone().filter(|s| !s.is_empty()),
         email: request.email.clone().filter(|s| !s.is_empty()),
+        notifications: None,
     };
     let mut humans = (**existing).clone();
     humans.push(new_human.clone());
@@ -0,0 +1,197 @@
+//! Timezones and locations set per channel or per user (see
+//! [`crate::settings::Locale`]). The same settings `/timezone` writes from
+//! chat.
+
+use super::access::{AgentJson, AgentQuery, AgentScoped};
+use super::state::ApiState;
+
+use crate::settings::{Locale, SettingsStore};
+
+use axum::Json;
+use axum::extract::State;
+use axum::http::StatusCode;
+use serde::Deserialize;
+use std::sync::Arc;
+
+#[derive(Deserialize, utoipa::IntoParams)]
+pub(super) struct ChannelLocaleQuery {
+    agent_id: String,
+    channel_id: String,
+}
+
+impl AgentScoped for ChannelLocaleQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+#[derive(Deserialize, utoipa::IntoParams)]
+pub(super) struct UserLocaleQuery {
+    agent_id: String,
+    /// `discord`, `slack`, `telegram`, ...
+    platform: String,
+    /// The user's platform ID.
+    user_id: String,
+}
+
+impl AgentScoped for UserLocaleQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
+pub(super) struct SetChannelLocaleRequest {
+    agent_id: String,
+    channel_id: String,
+    /// Leaving out both fields clears the channel's locale.
+    #[serde(flatten)]
+    locale: Locale,
+}
+
+impl AgentScoped for SetChannelLocaleRequest {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
+pub(super) struct SetUserLocaleRequest {
+    agent_id: String,
+    platform: String,
+    user_id: String,
+    /// Leaving out both fields clears the user's locale.
+    #[serde(flatten)]
+    locale: Locale,
+}
+
+impl AgentScoped for SetUserLocaleRequest {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+fn settings_store(state: &ApiState, agent_id: &str) -> Result<Arc<SettingsStore>, StatusCode> {
+    let runtime_config = state
+        .runtime_configs
+        .load()
+        .get(agent_id)
+        .cloned()
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let settings = runtime_config.settings.load();
+    settings.as_ref().clone().ok_or_else(|| {
+        tracing::warn!(agent_id, "no settings store available for locale");
+        StatusCode::SERVICE_UNAVAILABLE
+    })
+}
+
+/// Trim both fields, dropping blanks, and reject timezones that aren't IANA
+/// names.
+fn normalize(locale: Locale) -> Result<Locale, StatusCode> {
+    let trimmed = |value: Option<String>| {
+        value
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+    let locale = Locale {
+        timezone: trimmed(locale.timezone),
+        location: trimmed(locale.location),
+    };
+    if locale.timezone.is_some() && locale.tz().is_none() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    Ok(locale)
+}
+
+fn internal_error(error: crate::Error) -> StatusCode {
+    tracing::warn!(%error, "failed to access locale settings");
+    StatusCode::INTERNAL_SERVER_ERROR
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/agents/locale/channel",
+    tag = "agents",
+    params(ChannelLocaleQuery),
+    responses(
+        (status = 200, description = "The channel's locale; empty when none is set", body = Locale),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
+pub(super) async fn get_channel_locale(
+    State(state): State<Arc<ApiState>>,
+    AgentQuery(query): AgentQuery<ChannelLocaleQuery>,
+) -> Result<Json<Locale>, StatusCode> {
+    let settings = settings_store(&state, &query.agent_id)?;
+    let locale = settings
+        .channel_locale(&query.channel_id)
+        .map_err(internal_error)?;
+    Ok(Json(locale.unwrap_or_default()))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/agents/locale/channel",
+    tag = "agents",
+    request_body = SetChannelLocaleRequest,
+    responses(
+        (status = 200, description = "Saved", body = Locale),
+        (status = 400, description = "Not an IANA timezone"),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
+pub(super) async fn set_channel_locale(
+    State(state): State<Arc<ApiState>>,
+    AgentJson(request): AgentJson<SetChannelLocaleRequest>,
+) -> Result<Json<Locale>, StatusCode> {
+    let settings = settings_store(&state, &request.agent_id)?;
+    let locale = normalize(request.locale)?;
+    settings
+        .set_channel_locale(&request.channel_id, &locale)
+        .map_err(internal_error)?;
+    Ok(Json(locale))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/agents/locale/user",
+    tag = "agents",
+    params(UserLocaleQuery),
+    responses(
+        (status = 200, description = "The user's locale; empty when none is set", body = Locale),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
+pub(super) async fn get_user_locale(
+    State(state): State<Arc<ApiState>>,
+    AgentQuery(query): AgentQuery<UserLocaleQuery>,
+) -> Result<Json<Locale>, StatusCode> {
+    let settings = settings_store(&state, &query.agent_id)?;
+    let locale = settings
+        .user_locale(&query.platform, &query.user_id)
+        .map_err(internal_error)?;
+    Ok(Json(locale.unwrap_or_default()))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/agents/locale/user",
+    tag = "agents",
+    request_body = SetUserLocaleRequest,
+    responses(
+        (status = 200, description = "Saved", body = Locale),
+        (status = 400, description = "Not an IANA timezone"),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
+pub(super) async fn set_user_locale(
+    State(state): State<Arc<ApiState>>,
+    AgentJson(request): AgentJson<SetUserLocaleRequest>,
+) -> Result<Json<Locale>, StatusCode> {
+    let settings = settings_store(&state, &request.agent_id)?;
+    let locale = normalize(request.locale)?;
+    settings
+        .set_user_locale(&request.platform, &request.user_id, &locale)
+        .map_err(internal_error)?;
+    Ok(Json(locale))
+}
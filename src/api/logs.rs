@@ -0,0 +1,81 @@
+//! Live process logs over SSE.
+
+use crate::log_stream::{self, LogFilter};
+
+use axum::extract::Query;
+use axum::http::StatusCode;
+use axum::response::Sse;
+use futures::stream::Stream;
+use serde::Deserialize;
+use std::convert::Infallible;
+
+#[derive(Deserialize, utoipa::IntoParams)]
+pub(super) struct LogStreamQuery {
+    /// Least severe level to include: `error`, `warn`, `info` (default),
+    /// `debug`, or `trace`. Events below the process's own log level are
+    /// never produced.
+    #[serde(default)]
+    level: Option<String>,
+    /// Comma-separated target prefixes, e.g. `spacebot::agent::worker`.
+    #[serde(default)]
+    target: Option<String>,
+}
+
+/// Stream tracing output as it's emitted. Each `log` event carries a JSON
+/// `LogRecord`; a `lagged` event reports records dropped because the client
+/// fell behind. Nothing is replayed on connect.
+#[utoipa::path(
+    get,
+    path = "/api/logs/stream",
+    tag = "system",
+    params(LogStreamQuery),
+    responses(
+        (status = 200, description = "Server-sent event stream of `LogRecord`s", content_type = "text/event-stream", body = String),
+        (status = 400, description = "Unknown level"),
+    )
+)]
+pub(super) async fn log_stream(
+    Query(query): Query<LogStreamQuery>,
+) -> Result<
+    Sse<impl Stream<Item = Result<axum::response::sse::Event, Infallible>>>,
+    (StatusCode, String),
+> {
+    let level = match query.level.as_deref().map(str::trim) {
+        None | Some("") => tracing::Level::INFO,
+        Some(level) => level
+            .parse()
+            .map_err(|_| (StatusCode::BAD_REQUEST, format!("unknown level '{level}'")))?,
+    };
+    let filter = LogFilter {
+        level,
+        targets: super::system::split_list(query.target),
+    };
+
+    let mut rx = log_stream::subscribe();
+    let stream = async_stream::stream! {
+        loop {
+            match crate::classify_broadcast_recv_result(rx.recv().await) {
+                crate::BroadcastRecvResult::Event(record) => {
+                    if !filter.matches(&record) {
+                        continue;
+                    }
+                    if let Ok(json) = serde_json::to_string(&*record) {
+                        yield Ok(axum::response::sse::Event::default().event("log").data(json));
+                    }
+                }
+                crate::BroadcastRecvResult::Lagged(count) => {
+                    yield Ok(axum::response::sse::Event::default()
+                        .event("lagged")
+                        .data(format!("{{\"skipped\":{count}}}")));
+                }
+                crate::BroadcastRecvResult::Closed => break,
+            }
+        }
+    };
+
+    Ok(Sse::new(stream).keep_alive(
+        axum::response::sse::KeepAlive::new()
+            .interval(std::time::Duration::from_secs(15))
+            .text("ping"),
+    ))
+}
@@ -1,39 +1,55 @@
 use super::state::ApiState;
 
+use super::access::{AgentJson, AgentQuery, AgentScoped};
+use crate::conversation::history::ConversationLogger;
+use crate::memory::backup::{self, RestoreSummary};
+use crate::memory::consolidation::{self, Consolidation};
+use crate::memory::entities::{self, EntityProfile};
+use crate::memory::import::ImportSummary;
+use crate::memory::mutations::{self, ACTOR_API, MemoryMutation, MutationKind};
+use crate::memory::rescore::{self, RescoreConfig, RescoreReport};
+use crate::memory::scope::{GLOBAL_SCOPE, MemoryAccess};
 use crate::memory::search::{SearchConfig, SearchMode};
-use crate::memory::types::{Association, Memory, MemorySearchResult, MemoryType};
+use crate::memory::todos::{TodoFilter, TodoItem, TodoStatus};
+use crate::memory::types::{Association, Memory, MemorySearchResult, MemoryType, RelationType};
+use crate::tools::memory_save::{MAX_MEMORY_CONTENT_BYTES, MemorySaveArgs, MemorySaveTool};
 
 use axum::Json;
-use axum::extract::{Query, State};
-use axum::http::StatusCode;
+use axum::body::Body;
+use axum::extract::{Path, State};
+use axum::http::{HeaderValue, StatusCode, header};
+use axum::response::{IntoResponse as _, Response};
+use futures::StreamExt as _;
+use rig::tool::Tool as _;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
-#[derive(Serialize)]
+#[derive(Serialize, utoipa::ToSchema)]
 pub(super) struct MemoriesListResponse {
     memories: Vec<Memory>,
     total: usize,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, utoipa::ToSchema)]
 pub(super) struct MemoriesSearchResponse {
     results: Vec<MemorySearchResult>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, utoipa::ToSchema)]
 pub(super) struct MemoryGraphResponse {
     nodes: Vec<Memory>,
     edges: Vec<Association>,
     total: usize,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, utoipa::ToSchema)]
 pub(super) struct MemoryGraphNeighborsResponse {
     nodes: Vec<Memory>,
     edges: Vec<Association>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
 pub(super) struct MemoriesListQuery {
     agent_id: String,
     #[serde(default = "default_memories_limit")]
@@ -46,6 +62,12 @@ pub(super) struct MemoriesListQuery {
     sort: String,
 }
 
+impl AgentScoped for MemoriesListQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
 fn default_memories_limit() -> i64 {
     50
 }
@@ -76,7 +98,25 @@ pub(super) fn parse_memory_type(type_str: &str) -> Option<MemoryType> {
     }
 }
 
-#[derive(Deserialize)]
+fn parse_relation_type(type_str: &str) -> Option<RelationType> {
+    match type_str {
+        "related_to" => Some(RelationType::RelatedTo),
+        "updates" => Some(RelationType::Updates),
+        "contradicts" => Some(RelationType::Contradicts),
+        "caused_by" => Some(RelationType::CausedBy),
+        "result_of" => Some(RelationType::ResultOf),
+        "part_of" => Some(RelationType::PartOf),
+        "supports" => Some(RelationType::Supports),
+        "about_person" => Some(RelationType::AboutPerson),
+        _ => None,
+    }
+}
+
+/// Linked neighbors returned with each search result.
+const MAX_SEARCH_RESULT_LINKS: usize = 5;
+
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
 pub(super) struct MemoriesSearchQuery {
     agent_id: String,
     q: String,
@@ -84,13 +124,23 @@ pub(super) struct MemoriesSearchQuery {
     limit: usize,
     #[serde(default)]
     memory_type: Option<String>,
+    /// Also match memories that are in the trash.
+    #[serde(default)]
+    include_trashed: bool,
+}
+
+impl AgentScoped for MemoriesSearchQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
 }
 
 fn default_search_limit() -> usize {
     20
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
 pub(super) struct MemoryGraphQuery {
     agent_id: String,
     #[serde(default = "default_graph_limit")]
@@ -103,11 +153,18 @@ pub(super) struct MemoryGraphQuery {
     sort: String,
 }
 
+impl AgentScoped for MemoryGraphQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
 fn default_graph_limit() -> i64 {
     200
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
 pub(super) struct MemoryGraphNeighborsQuery {
     agent_id: String,
     memory_id: String,
@@ -118,14 +175,286 @@ pub(super) struct MemoryGraphNeighborsQuery {
     exclude: Option<String>,
 }
 
+impl AgentScoped for MemoryGraphNeighborsQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
 fn default_neighbor_depth() -> u32 {
     1
 }
 
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(super) struct MemoryTrashQuery {
+    agent_id: String,
+    #[serde(default = "default_memories_limit")]
+    limit: i64,
+    #[serde(default)]
+    offset: i64,
+}
+
+impl AgentScoped for MemoryTrashQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct MemoryTrashResponse {
+    memories: Vec<Memory>,
+    total: i64,
+    /// Days trashed memories are kept before being purged; zero means never.
+    retention_days: i64,
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
+pub(super) struct MemoryRestoreRequest {
+    agent_id: String,
+    memory_id: String,
+}
+
+impl AgentScoped for MemoryRestoreRequest {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(super) struct MemoryDeleteQuery {
+    agent_id: String,
+    /// Why the memory is being removed, kept in its history.
+    #[serde(default)]
+    reason: Option<String>,
+}
+
+impl AgentScoped for MemoryDeleteQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
+pub(super) struct MemoryCreateRequest {
+    agent_id: String,
+    content: String,
+    #[serde(default = "default_memory_type")]
+    memory_type: String,
+    /// Defaults to the memory type's usual importance.
+    #[serde(default)]
+    importance: Option<f32>,
+    #[serde(default)]
+    tags: Vec<String>,
+    /// Where the memory came from. Defaults to "api".
+    #[serde(default)]
+    source: Option<String>,
+    /// Who may recall it: "global" (default), `channel:<id>`, `user:<platform>:<id>`,
+    /// or a configured memory scope.
+    #[serde(default)]
+    scope: Option<String>,
+    /// When the memory stops being true. Expired memories are left out of
+    /// recall and moved to the trash by maintenance.
+    #[serde(default)]
+    expires_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl AgentScoped for MemoryCreateRequest {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+fn default_memory_type() -> String {
+    "fact".into()
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
+pub(super) struct MemoryLinkRequest {
+    agent_id: String,
+    source_id: String,
+    target_id: String,
+    /// `supports`, `contradicts`, `caused_by`, `about_person`, or any other
+    /// relation type.
+    relation_type: String,
+    /// Edge strength, 0-1. Defaults to 0.5.
+    #[serde(default)]
+    weight: Option<f32>,
+}
+
+impl AgentScoped for MemoryLinkRequest {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+/// Fields to change on a memory. Omitted fields are left as they are.
+#[derive(Deserialize, utoipa::ToSchema)]
+pub(super) struct MemoryUpdateRequest {
+    agent_id: String,
+    #[serde(default)]
+    content: Option<String>,
+    #[serde(default)]
+    memory_type: Option<String>,
+    #[serde(default)]
+    importance: Option<f32>,
+    /// Why the memory is being corrected, kept in its history.
+    #[serde(default)]
+    reason: Option<String>,
+}
+
+impl AgentScoped for MemoryUpdateRequest {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(super) struct TodoListQuery {
+    agent_id: String,
+    /// Only items with this status (open, in_progress, done, cancelled).
+    #[serde(default)]
+    status: Option<String>,
+    /// Include done and cancelled items when no status is given.
+    #[serde(default)]
+    include_closed: bool,
+    /// `todo` or `goal`; both when omitted.
+    #[serde(default)]
+    memory_type: Option<String>,
+    #[serde(default)]
+    channel_id: Option<String>,
+    #[serde(default = "default_memories_limit")]
+    limit: i64,
+}
+
+impl AgentScoped for TodoListQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct TodoListResponse {
+    todos: Vec<TodoItem>,
+}
+
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(super) struct MemoryHistoryQuery {
+    agent_id: String,
+    #[serde(default = "default_history_limit")]
+    limit: i64,
+}
+
+impl AgentScoped for MemoryHistoryQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+fn default_history_limit() -> i64 {
+    100
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct MemoryHistoryResponse {
+    memory_id: String,
+    mutations: Vec<MemoryMutation>,
+}
+
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(super) struct ConsolidationsQuery {
+    agent_id: String,
+    #[serde(default = "default_memories_limit")]
+    limit: i64,
+}
+
+impl AgentScoped for ConsolidationsQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct ConsolidationsResponse {
+    consolidations: Vec<Consolidation>,
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
+pub(super) struct MemoryRescoreRequest {
+    agent_id: String,
+}
+
+impl AgentScoped for MemoryRescoreRequest {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(super) struct MemoriesAsOfQuery {
+    agent_id: String,
+    /// RFC 3339 timestamp to reconstruct the store at.
+    at: String,
+    #[serde(default)]
+    include_forgotten: bool,
+    #[serde(default = "default_memories_limit")]
+    limit: i64,
+    #[serde(default)]
+    offset: usize,
+}
+
+impl AgentScoped for MemoriesAsOfQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct MemoriesAsOfResponse {
+    at: chrono::DateTime<chrono::Utc>,
+    memories: Vec<Memory>,
+    total: usize,
+}
+
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(super) struct EntityProfileQuery {
+    agent_id: String,
+    #[serde(default = "default_memories_limit")]
+    memory_limit: i64,
+    #[serde(default = "default_mention_limit")]
+    mention_limit: i64,
+}
+
+impl AgentScoped for EntityProfileQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+fn default_mention_limit() -> i64 {
+    20
+}
+
 /// List memories for an agent with sorting, filtering, and pagination.
+#[utoipa::path(
+    get,
+    path = "/api/agents/memories",
+    tag = "memories",
+    params(MemoriesListQuery),
+    responses(
+        (status = 200, body = MemoriesListResponse),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
 pub(super) async fn list_memories(
     State(state): State<Arc<ApiState>>,
-    Query(query): Query<MemoriesListQuery>,
+    AgentQuery(query): AgentQuery<MemoriesListQuery>,
 ) -> Result<Json<MemoriesListResponse>, StatusCode> {
     let searches = state.memory_searches.load();
     let memory_search = searches.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
@@ -150,10 +479,21 @@ pub(super) async fn list_memories(
     Ok(Json(MemoriesListResponse { memories, total }))
 }
 
-/// Search memories using hybrid search (vector + FTS + graph).
+/// Search memories using hybrid search (vector + FTS + graph). Each result
+/// carries its strongest links so the dashboard can draw them.
+#[utoipa::path(
+    get,
+    path = "/api/agents/memories/search",
+    tag = "memories",
+    params(MemoriesSearchQuery),
+    responses(
+        (status = 200, body = MemoriesSearchResponse),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
 pub(super) async fn search_memories(
     State(state): State<Arc<ApiState>>,
-    Query(query): Query<MemoriesSearchQuery>,
+    AgentQuery(query): AgentQuery<MemoriesSearchQuery>,
 ) -> Result<Json<MemoriesSearchResponse>, StatusCode> {
     let searches = state.memory_searches.load();
     let memory_search = searches.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
@@ -162,23 +502,40 @@ pub(super) async fn search_memories(
         mode: SearchMode::Hybrid,
         memory_type: query.memory_type.as_deref().and_then(parse_memory_type),
         max_results: query.limit.min(100),
+        include_forgotten: query.include_trashed,
         ..SearchConfig::default()
     };
 
-    let results = memory_search.search(&query.q, &config)
+    let mut results = memory_search.search(&query.q, &config)
         .await
         .map_err(|error| {
             tracing::warn!(%error, agent_id = %query.agent_id, query = %query.q, "memory search failed");
             StatusCode::INTERNAL_SERVER_ERROR
         })?;
+    if let Err(error) = memory_search
+        .attach_links(&mut results, MAX_SEARCH_RESULT_LINKS)
+        .await
+    {
+        tracing::warn!(%error, agent_id = %query.agent_id, "failed to load linked memories");
+    }
 
     Ok(Json(MemoriesSearchResponse { results }))
 }
 
 /// Get a subgraph of memories: nodes + all edges between them.
+#[utoipa::path(
+    get,
+    path = "/api/agents/memories/graph",
+    tag = "memories",
+    params(MemoryGraphQuery),
+    responses(
+        (status = 200, body = MemoryGraphResponse),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
 pub(super) async fn memory_graph(
     State(state): State<Arc<ApiState>>,
-    Query(query): Query<MemoryGraphQuery>,
+    AgentQuery(query): AgentQuery<MemoryGraphQuery>,
 ) -> Result<Json<MemoryGraphResponse>, StatusCode> {
     let searches = state.memory_searches.load();
     let memory_search = searches.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
@@ -216,11 +573,101 @@ pub(super) async fn memory_graph(
     }))
 }
 
+/// Link two memories with a typed edge. Linking the same pair with the same
+/// type again replaces the edge's weight.
+#[utoipa::path(
+    post,
+    path = "/api/agents/memories/link",
+    tag = "memories",
+    request_body = MemoryLinkRequest,
+    responses(
+        (status = 201, body = Association),
+        (status = 400, description = "Unknown relation type, weight outside 0-1, or a memory linked to itself"),
+        (status = 404, description = "Unknown agent or memory"),
+    )
+)]
+pub(super) async fn link_memories(
+    State(state): State<Arc<ApiState>>,
+    AgentJson(request): AgentJson<MemoryLinkRequest>,
+) -> Result<(StatusCode, Json<Association>), StatusCode> {
+    let searches = state.memory_searches.load();
+    let memory_search = searches
+        .get(&request.agent_id)
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let store = memory_search.store();
+
+    let relation_type =
+        parse_relation_type(&request.relation_type).ok_or(StatusCode::BAD_REQUEST)?;
+    if request.source_id == request.target_id {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    if let Some(weight) = request.weight
+        && !(0.0..=1.0).contains(&weight)
+    {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    for memory_id in [&request.source_id, &request.target_id] {
+        let memory = store.load(memory_id).await.map_err(|error| {
+            tracing::warn!(%error, agent_id = %request.agent_id, %memory_id, "failed to load memory to link");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+        if memory.is_none_or(|memory| memory.forgotten) {
+            return Err(StatusCode::NOT_FOUND);
+        }
+    }
+
+    let mut association = Association::new(&request.source_id, &request.target_id, relation_type);
+    if let Some(weight) = request.weight {
+        association = association.with_weight(weight);
+    }
+    store
+        .create_association(&association)
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, agent_id = %request.agent_id, "failed to link memories");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    // Relinking an existing pair keeps the edge's original ID.
+    let association = store
+        .get_associations(&association.source_id)
+        .await
+        .ok()
+        .and_then(|stored| {
+            stored.into_iter().find(|edge| {
+                edge.source_id == association.source_id
+                    && edge.target_id == association.target_id
+                    && edge.relation_type == relation_type
+            })
+        })
+        .unwrap_or(association);
+
+    tracing::info!(
+        agent_id = %request.agent_id,
+        source_id = %association.source_id,
+        target_id = %association.target_id,
+        %relation_type,
+        "memories linked via API"
+    );
+    Ok((StatusCode::CREATED, Json(association)))
+}
+
 /// Get the neighbors of a specific memory node. Returns new nodes
 /// and edges not already present in the client's graph.
+#[utoipa::path(
+    get,
+    path = "/api/agents/memories/graph/neighbors",
+    tag = "memories",
+    params(MemoryGraphNeighborsQuery),
+    responses(
+        (status = 200, body = MemoryGraphNeighborsResponse),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
 pub(super) async fn memory_graph_neighbors(
     State(state): State<Arc<ApiState>>,
-    Query(query): Query<MemoryGraphNeighborsQuery>,
+    AgentQuery(query): AgentQuery<MemoryGraphNeighborsQuery>,
 ) -> Result<Json<MemoryGraphNeighborsResponse>, StatusCode> {
     let searches = state.memory_searches.load();
     let memory_search = searches.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
@@ -245,3 +692,729 @@ pub(super) async fn memory_graph_neighbors(
 
     Ok(Json(MemoryGraphNeighborsResponse { nodes, edges }))
 }
+
+/// List forgotten memories awaiting purge, most recently trashed first.
+#[utoipa::path(
+    get,
+    path = "/api/agents/memories/trash",
+    tag = "memories",
+    params(MemoryTrashQuery),
+    responses(
+        (status = 200, body = MemoryTrashResponse),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
+pub(super) async fn list_memory_trash(
+    State(state): State<Arc<ApiState>>,
+    AgentQuery(query): AgentQuery<MemoryTrashQuery>,
+) -> Result<Json<MemoryTrashResponse>, StatusCode> {
+    let searches = state.memory_searches.load();
+    let memory_search = searches.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
+    let store = memory_search.store();
+
+    let limit = query.limit.clamp(1, 200);
+    let offset = query.offset.max(0);
+    let (memories, total) =
+        tokio::try_join!(store.list_forgotten(limit, offset), store.count_forgotten()).map_err(
+            |error| {
+                tracing::warn!(%error, agent_id = %query.agent_id, "failed to list memory trash");
+                StatusCode::INTERNAL_SERVER_ERROR
+            },
+        )?;
+
+    let retention_days = state
+        .runtime_configs
+        .load()
+        .get(&query.agent_id)
+        .map(|runtime_config| {
+            runtime_config
+                .cortex
+                .load()
+                .maintenance_trash_retention_days
+        })
+        .unwrap_or_default();
+
+    Ok(Json(MemoryTrashResponse {
+        memories,
+        total,
+        retention_days,
+    }))
+}
+
+/// Move a forgotten memory back out of the trash.
+#[utoipa::path(
+    post,
+    path = "/api/agents/memories/trash/restore",
+    tag = "memories",
+    request_body = MemoryRestoreRequest,
+    responses(
+        (status = 200, description = "Memory restored"),
+        (status = 404, description = "Unknown agent, or the memory is not in the trash"),
+    )
+)]
+pub(super) async fn restore_memory(
+    State(state): State<Arc<ApiState>>,
+    AgentJson(request): AgentJson<MemoryRestoreRequest>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let searches = state.memory_searches.load();
+    let memory_search = searches
+        .get(&request.agent_id)
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let restored = memory_search
+        .store()
+        .restore(&request.memory_id)
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, agent_id = %request.agent_id, memory_id = %request.memory_id, "failed to restore memory");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    if !restored {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    if let Ok(Some(memory)) = memory_search.store().load(&request.memory_id).await {
+        mutations::record_or_warn(
+            memory_search.store(),
+            MutationKind::Restore,
+            &memory.id,
+            Some(&memory),
+            ACTOR_API,
+            None,
+        )
+        .await;
+    }
+
+    tracing::info!(agent_id = %request.agent_id, memory_id = %request.memory_id, "memory restored from trash via API");
+    Ok(Json(serde_json::json!({ "success": true })))
+}
+
+/// Permanently delete a memory, its embedding, and its graph edges.
+#[utoipa::path(
+    delete,
+    path = "/api/agents/memories/{id}",
+    tag = "memories",
+    params(("id" = String, Path, description = "Memory ID"), MemoryDeleteQuery),
+    responses(
+        (status = 200, description = "Memory deleted"),
+        (status = 404, description = "Unknown agent or memory"),
+    )
+)]
+pub(super) async fn delete_memory(
+    State(state): State<Arc<ApiState>>,
+    Path(memory_id): Path<String>,
+    AgentQuery(query): AgentQuery<MemoryDeleteQuery>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let searches = state.memory_searches.load();
+    let memory_search = searches.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
+
+    let deleted = memory_search
+        .delete_memory(&memory_id)
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, agent_id = %query.agent_id, %memory_id, "failed to delete memory");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    if !deleted {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    mutations::record_or_warn(
+        memory_search.store(),
+        MutationKind::Delete,
+        &memory_id,
+        None,
+        ACTOR_API,
+        query.reason.as_deref(),
+    )
+    .await;
+
+    tracing::info!(agent_id = %query.agent_id, %memory_id, "memory deleted via API");
+    Ok(Json(serde_json::json!({ "success": true })))
+}
+
+/// Correct a memory's content, type, or importance. Changed content is
+/// re-embedded so search reflects the edit.
+#[utoipa::path(
+    put,
+    path = "/api/agents/memories/{id}",
+    tag = "memories",
+    params(("id" = String, Path, description = "Memory ID")),
+    request_body = MemoryUpdateRequest,
+    responses(
+        (status = 200, body = Memory),
+        (status = 400, description = "Empty or oversized content, unknown memory type, or importance outside 0-1"),
+        (status = 404, description = "Unknown agent or memory"),
+    )
+)]
+pub(super) async fn update_memory(
+    State(state): State<Arc<ApiState>>,
+    Path(memory_id): Path<String>,
+    AgentJson(request): AgentJson<MemoryUpdateRequest>,
+) -> Result<Json<Memory>, StatusCode> {
+    let searches = state.memory_searches.load();
+    let memory_search = searches
+        .get(&request.agent_id)
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let mut memory = memory_search
+        .store()
+        .load(&memory_id)
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, agent_id = %request.agent_id, %memory_id, "failed to load memory");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let mut content_changed = false;
+    if let Some(content) = request.content {
+        let content = content.trim();
+        if content.is_empty() || content.len() > MAX_MEMORY_CONTENT_BYTES {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+        content_changed = content != memory.content;
+        memory.content = content.to_string();
+    }
+    if let Some(memory_type) = request.memory_type.as_deref() {
+        memory.memory_type = parse_memory_type(memory_type).ok_or(StatusCode::BAD_REQUEST)?;
+    }
+    if let Some(importance) = request.importance {
+        if !(0.0..=1.0).contains(&importance) {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+        memory.importance = importance;
+    }
+    memory.updated_at = chrono::Utc::now();
+
+    memory_search
+        .update_memory(&memory, content_changed)
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, agent_id = %request.agent_id, %memory_id, "failed to update memory");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    mutations::record_or_warn(
+        memory_search.store(),
+        MutationKind::Update,
+        &memory_id,
+        Some(&memory),
+        ACTOR_API,
+        request.reason.as_deref(),
+    )
+    .await;
+
+    tracing::info!(agent_id = %request.agent_id, %memory_id, content_changed, "memory updated via API");
+    Ok(Json(memory))
+}
+
+/// Create a memory by hand. It goes through the same save path as the
+/// agent's `memory_save` tool, so it is embedded, indexed, and logged in the
+/// memory's history exactly like one the agent wrote itself.
+#[utoipa::path(
+    post,
+    path = "/api/agents/memories",
+    tag = "memories",
+    request_body = MemoryCreateRequest,
+    responses(
+        (status = 201, body = Memory),
+        (status = 400, description = "Empty or oversized content, unknown memory type, importance outside 0-1, an empty scope, or an expiry in the past"),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
+pub(super) async fn create_memory(
+    State(state): State<Arc<ApiState>>,
+    AgentJson(request): AgentJson<MemoryCreateRequest>,
+) -> Result<(StatusCode, Json<Memory>), StatusCode> {
+    let searches = state.memory_searches.load();
+    let memory_search = searches
+        .get(&request.agent_id)
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    // The tool is lenient with model input (unknown types fall back to
+    // fact), so reject bad requests here before anything is written.
+    let content = request.content.trim();
+    if content.is_empty() || content.len() > MAX_MEMORY_CONTENT_BYTES {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    if parse_memory_type(&request.memory_type).is_none() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    if let Some(importance) = request.importance
+        && !(0.0..=1.0).contains(&importance)
+    {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    if request
+        .expires_at
+        .is_some_and(|expires_at| expires_at <= chrono::Utc::now())
+    {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let scope = request
+        .scope
+        .map(|scope| scope.trim().to_string())
+        .filter(|scope| scope != GLOBAL_SCOPE);
+    if scope
+        .as_deref()
+        .is_some_and(|scope| matches!(scope, "" | "channel:" | "user:"))
+    {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let tool = MemorySaveTool::new(memory_search.clone())
+        .with_actor(ACTOR_API)
+        .with_access(MemoryAccess {
+            write_scope: scope,
+            ..Default::default()
+        });
+    let output = tool
+        .call(MemorySaveArgs {
+            content: content.to_string(),
+            memory_type: request.memory_type,
+            importance: request.importance,
+            source: Some(request.source.unwrap_or_else(|| "api".into())),
+            channel_id: None,
+            associations: Vec::new(),
+            priority: None,
+            due_at: None,
+            tags: request.tags,
+            scope: None,
+            expires_at: request.expires_at.map(|expires_at| expires_at.to_rfc3339()),
+        })
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, agent_id = %request.agent_id, "failed to create memory");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let memory = memory_search
+        .store()
+        .load(&output.memory_id)
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, agent_id = %request.agent_id, memory_id = %output.memory_id, "failed to load created memory");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    tracing::info!(agent_id = %request.agent_id, memory_id = %memory.id, "memory created via API");
+    Ok((StatusCode::CREATED, Json(memory)))
+}
+
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(super) struct MemoryImportQuery {
+    agent_id: String,
+}
+
+impl AgentScoped for MemoryImportQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+/// Bulk-create memories from a JSONL body, one memory per line:
+/// `{"content": "...", "type": "fact", "importance": 0.6, "created_at": "2024-05-01T12:00:00Z"}`.
+/// Only `content` is required. Lines are embedded and written in batches of
+/// 64, each in its own transaction; invalid lines and failed batches are
+/// listed in the response and don't stop the rest of the import.
+#[utoipa::path(
+    post,
+    path = "/api/agents/memories/import",
+    tag = "memories",
+    params(MemoryImportQuery),
+    request_body(content = String, content_type = "application/x-ndjson"),
+    responses(
+        (status = 200, description = "Import finished; see `errors` for skipped lines", body = ImportSummary),
+        (status = 400, description = "Body is not UTF-8 or contains no lines"),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
+pub(super) async fn import_memories(
+    State(state): State<Arc<ApiState>>,
+    AgentQuery(query): AgentQuery<MemoryImportQuery>,
+    body: String,
+) -> Result<Json<ImportSummary>, StatusCode> {
+    let searches = state.memory_searches.load();
+    let memory_search = searches.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
+    if body.trim().is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let summary = crate::memory::import::import_jsonl(memory_search, &body, ACTOR_API).await;
+    tracing::info!(
+        agent_id = %query.agent_id,
+        total = summary.total,
+        imported = summary.imported,
+        failed = summary.failed,
+        "memories imported via API"
+    );
+    Ok(Json(summary))
+}
+
+/// Download a full backup of an agent's memory as JSONL: a header line, then
+/// every memory (forgotten ones included) with its embedding, then every
+/// association. Restore it with `POST /api/agents/memories/backup`.
+#[utoipa::path(
+    get,
+    path = "/api/agents/memories/backup",
+    tag = "memories",
+    params(MemoryImportQuery),
+    responses(
+        (status = 200, description = "JSONL backup, streamed", content_type = "application/x-ndjson", body = String),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
+pub(super) async fn export_memory_backup(
+    State(state): State<Arc<ApiState>>,
+    AgentQuery(query): AgentQuery<MemoryImportQuery>,
+) -> Result<Response, StatusCode> {
+    let searches = state.memory_searches.load();
+    let memory_search = searches.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
+
+    let agent_id = query.agent_id.clone();
+    let stream = backup::export_stream(memory_search.clone()).map(move |chunk| {
+        chunk.map_err(|error| {
+            tracing::warn!(%error, %agent_id, "memory backup export aborted");
+            std::io::Error::other(error.to_string())
+        })
+    });
+    tracing::info!(agent_id = %query.agent_id, "memory backup export started via API");
+
+    let filename = format!(
+        "{}-memory-{}.jsonl",
+        query.agent_id,
+        chrono::Utc::now().format("%Y%m%d-%H%M%S")
+    );
+    let mut response = Body::from_stream(stream).into_response();
+    let headers = response.headers_mut();
+    headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/x-ndjson"),
+    );
+    if let Ok(value) = HeaderValue::from_str(&format!("attachment; filename=\"{filename}\"")) {
+        headers.insert(header::CONTENT_DISPOSITION, value);
+    }
+    Ok(response)
+}
+
+/// Restore a backup taken with `GET /api/agents/memories/backup`, keeping
+/// memory IDs, timestamps, embeddings, and graph edges. Memories that already
+/// exist by ID are skipped, so restoring the same backup twice is harmless.
+/// The file is validated first; any bad line rejects the whole restore.
+#[utoipa::path(
+    post,
+    path = "/api/agents/memories/backup",
+    tag = "memories",
+    params(MemoryImportQuery),
+    request_body(content = String, content_type = "application/x-ndjson"),
+    responses(
+        (status = 200, body = RestoreSummary),
+        (status = 400, description = "Not a memory backup, or a malformed line"),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
+pub(super) async fn restore_memory_backup(
+    State(state): State<Arc<ApiState>>,
+    AgentQuery(query): AgentQuery<MemoryImportQuery>,
+    body: String,
+) -> Result<Json<RestoreSummary>, (StatusCode, String)> {
+    let searches = state.memory_searches.load();
+    let memory_search = searches.get(&query.agent_id).ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            format!("agent '{}' not found", query.agent_id),
+        )
+    })?;
+
+    let (header, parsed) =
+        backup::parse_backup(&body).map_err(|error| (StatusCode::BAD_REQUEST, error))?;
+    let summary = backup::restore(memory_search, &header.agent_id, parsed, ACTOR_API)
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, agent_id = %query.agent_id, "failed to restore memory backup");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to restore backup".to_string(),
+            )
+        })?;
+    tracing::info!(
+        agent_id = %query.agent_id,
+        source_agent_id = %summary.source_agent_id,
+        memories = summary.memories,
+        skipped = summary.skipped,
+        associations = summary.associations,
+        "memory backup restored via API"
+    );
+    Ok(Json(summary))
+}
+
+/// List todo and goal memories with their status, priority, and due date,
+/// overdue and soonest-due first.
+#[utoipa::path(
+    get,
+    path = "/api/agents/todos",
+    tag = "memories",
+    params(TodoListQuery),
+    responses(
+        (status = 200, body = TodoListResponse),
+        (status = 400, description = "Unknown status or memory type"),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
+pub(super) async fn list_todos(
+    State(state): State<Arc<ApiState>>,
+    AgentQuery(query): AgentQuery<TodoListQuery>,
+) -> Result<Json<TodoListResponse>, StatusCode> {
+    let searches = state.memory_searches.load();
+    let memory_search = searches.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
+
+    let status = query
+        .status
+        .as_deref()
+        .map(|status| TodoStatus::parse(status).ok_or(StatusCode::BAD_REQUEST))
+        .transpose()?;
+    let memory_type = query
+        .memory_type
+        .as_deref()
+        .map(|memory_type| {
+            parse_memory_type(memory_type)
+                .filter(|memory_type| crate::memory::todos::is_trackable(*memory_type))
+                .ok_or(StatusCode::BAD_REQUEST)
+        })
+        .transpose()?;
+    let filter = TodoFilter {
+        status,
+        include_closed: query.include_closed,
+        memory_type,
+        channel_id: query.channel_id,
+    };
+
+    let todos =
+        crate::memory::todos::list_todos(memory_search.store(), &filter, query.limit.clamp(1, 200))
+            .await
+            .map_err(|error| {
+                tracing::warn!(%error, agent_id = %query.agent_id, "failed to list todos");
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+
+    Ok(Json(TodoListResponse { todos }))
+}
+
+/// Every recorded mutation of a memory, oldest first: who created, edited,
+/// merged, decayed, trashed, or deleted it, and why.
+#[utoipa::path(
+    get,
+    path = "/api/agents/memories/{id}/history",
+    tag = "memories",
+    params(("id" = String, Path, description = "Memory ID"), MemoryHistoryQuery),
+    responses(
+        (status = 200, body = MemoryHistoryResponse),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
+pub(super) async fn memory_history(
+    State(state): State<Arc<ApiState>>,
+    Path(memory_id): Path<String>,
+    AgentQuery(query): AgentQuery<MemoryHistoryQuery>,
+) -> Result<Json<MemoryHistoryResponse>, StatusCode> {
+    let searches = state.memory_searches.load();
+    let memory_search = searches.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
+
+    let mutations = mutations::history(memory_search.store(), &memory_id, query.limit.clamp(1, 1000))
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, agent_id = %query.agent_id, %memory_id, "failed to load memory history");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(Json(MemoryHistoryResponse {
+        memory_id,
+        mutations,
+    }))
+}
+
+/// Clusters reviewed by memory consolidation, newest first: which memories
+/// were merged into which canonical memory, and which were judged distinct.
+#[utoipa::path(
+    get,
+    path = "/api/agents/memories/consolidations",
+    tag = "memories",
+    params(ConsolidationsQuery),
+    responses(
+        (status = 200, body = ConsolidationsResponse),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
+pub(super) async fn list_consolidations(
+    State(state): State<Arc<ApiState>>,
+    AgentQuery(query): AgentQuery<ConsolidationsQuery>,
+) -> Result<Json<ConsolidationsResponse>, StatusCode> {
+    let searches = state.memory_searches.load();
+    let memory_search = searches.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
+
+    let consolidations = consolidation::list(memory_search.store(), query.limit.clamp(1, 500))
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, agent_id = %query.agent_id, "failed to list memory consolidations");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(Json(ConsolidationsResponse { consolidations }))
+}
+
+/// Re-evaluate the importance of old memories against recent activity now,
+/// instead of waiting for the next scheduled pass. Uses the agent's
+/// `rescore_*` cortex settings, and the activity heuristic when no model is
+/// available.
+#[utoipa::path(
+    post,
+    path = "/api/agents/memories/rescore",
+    tag = "memories",
+    request_body = MemoryRescoreRequest,
+    responses(
+        (status = 200, body = RescoreReport),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
+pub(super) async fn rescore_memories(
+    State(state): State<Arc<ApiState>>,
+    AgentJson(request): AgentJson<MemoryRescoreRequest>,
+) -> Result<Json<RescoreReport>, StatusCode> {
+    let searches = state.memory_searches.load();
+    let memory_search = searches
+        .get(&request.agent_id)
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let runtime_config = state.runtime_configs.load().get(&request.agent_id).cloned();
+    let llm_manager = state.llm_manager.read().await.clone();
+
+    let result = match (llm_manager, runtime_config) {
+        (Some(llm_manager), Some(runtime_config)) => {
+            crate::agent::cortex::rescore_memories(
+                &llm_manager,
+                &runtime_config,
+                &request.agent_id,
+                memory_search,
+            )
+            .await
+        }
+        // No model to ask yet: fall back to the activity heuristic.
+        (_, runtime_config) => {
+            let config = runtime_config
+                .map(|runtime_config| RescoreConfig::from_cortex(&runtime_config.cortex.load()))
+                .unwrap_or_else(|| RescoreConfig::from_cortex(&Default::default()));
+            rescore::rescore_importance(
+                memory_search.store(),
+                memory_search.embedding_table(),
+                &config,
+            )
+            .await
+            .map_err(anyhow::Error::from)
+        }
+    };
+    let report = result.map_err(|error| {
+        tracing::warn!(%error, agent_id = %request.agent_id, "failed to rescore memories");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    tracing::info!(
+        agent_id = %request.agent_id,
+        examined = report.examined,
+        lowered = report.lowered,
+        raised = report.raised,
+        heuristic_scored = report.heuristic_scored,
+        "memories rescored via API"
+    );
+    Ok(Json(report))
+}
+
+/// Reconstruct an agent's memories as they stood at a past time, newest
+/// first.
+#[utoipa::path(
+    get,
+    path = "/api/agents/memories/as-of",
+    tag = "memories",
+    params(MemoriesAsOfQuery),
+    responses(
+        (status = 200, body = MemoriesAsOfResponse),
+        (status = 400, description = "`at` is not an RFC 3339 timestamp"),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
+pub(super) async fn memories_as_of(
+    State(state): State<Arc<ApiState>>,
+    AgentQuery(query): AgentQuery<MemoriesAsOfQuery>,
+) -> Result<Json<MemoriesAsOfResponse>, StatusCode> {
+    let searches = state.memory_searches.load();
+    let memory_search = searches.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
+
+    let at = chrono::DateTime::parse_from_rfc3339(&query.at)
+        .map_err(|_| StatusCode::BAD_REQUEST)?
+        .with_timezone(&chrono::Utc);
+
+    let all = mutations::state_as_of(memory_search.store(), at, query.include_forgotten)
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, agent_id = %query.agent_id, %at, "failed to reconstruct memory state");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let total = all.len();
+    let memories = all
+        .into_iter()
+        .skip(query.offset)
+        .take(query.limit.clamp(1, 500) as usize)
+        .collect();
+
+    Ok(Json(MemoriesAsOfResponse {
+        at,
+        memories,
+        total,
+    }))
+}
+
+/// Everything known about a named person, project, or place: memories that
+/// mention it, their graph neighbours and edges, and recent conversation
+/// mentions.
+#[utoipa::path(
+    get,
+    path = "/api/agents/entities/{name}",
+    tag = "memories",
+    params(("name" = String, Path, description = "Entity name"), EntityProfileQuery),
+    responses(
+        (status = 200, body = EntityProfile),
+        (status = 400, description = "Name too short to search for"),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
+pub(super) async fn entity_profile(
+    State(state): State<Arc<ApiState>>,
+    Path(name): Path<String>,
+    AgentQuery(query): AgentQuery<EntityProfileQuery>,
+) -> Result<Json<EntityProfile>, StatusCode> {
+    let searches = state.memory_searches.load();
+    let memory_search = searches.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
+    let pools = state.agent_pools.load();
+    let pool = pools.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
+    let name = entities::normalize_name(&name).ok_or(StatusCode::BAD_REQUEST)?;
+
+    let profile = entities::build_profile(
+        memory_search.store(),
+        &ConversationLogger::new(pool.clone()),
+        name,
+        &crate::memory::scope::ScopeFilter::All,
+        &entities::MentionFilter::All,
+        query.memory_limit.clamp(1, 200),
+        query.mention_limit.clamp(0, 100),
+    )
+    .await
+    .map_err(|error| {
+        tracing::warn!(%error, agent_id = %query.agent_id, %name, "failed to build entity profile");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(profile))
+}
@@ -264,6 +264,27 @@ async fn ensure_models_cache() -> Vec<ModelInfo> {
     }
 }
 
+/// Whether the cached models.dev catalog lists `model_id`. `None` when the
+/// catalog hasn't been fetched yet or has nothing for the model's provider,
+/// so custom providers and offline instances aren't flagged. Never fetches.
+pub(super) async fn catalog_lists_model(model_id: &str) -> Option<bool> {
+    let model_id = if model_id.contains('/') {
+        model_id.to_string()
+    } else {
+        format!("anthropic/{model_id}")
+    };
+    let provider = crate::llm::routing::provider_from_model(&model_id);
+
+    let cache = MODELS_CACHE.read().await;
+    let mut provider_models = cache
+        .0
+        .iter()
+        .filter(|model| model.provider == provider)
+        .peekable();
+    provider_models.peek()?;
+    Some(provider_models.any(|model| model.id == model_id))
+}
+
 /// Helper: which providers have keys configured.
 pub(super) async fn configured_providers(config_path: &std::path::Path) -> Vec<&'static str> {
     let mut providers = Vec::new();
@@ -0,0 +1,478 @@
+//! OpenAI-compatible chat completions, for tools that only speak that API.
+//!
+//! `POST /v1/chat/completions` treats `model` as an agent ID and posts the
+//! last user message into one of the agent's webchat conversations: the one
+//! named by the non-standard `conversation_id` field, a persistent session per
+//! `user`, or else a fresh ephemeral session that gets the request's earlier
+//! messages as a transcript. The agent's replies for that turn are collected
+//! from the API event bus and returned as a completion, or streamed as
+//! `chat.completion.chunk` events when `stream` is set.
+
+use super::access::{AgentJson, AgentScoped};
+use super::state::{ApiEvent, ApiState};
+use crate::{InboundMessage, MessageContent};
+
+use axum::Json;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::sse::Event;
+use axum::response::{IntoResponse, Response, Sse};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tokio::sync::broadcast;
+use tokio::time::Instant;
+
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Path of the endpoint, outside the `/api` prefix.
+pub(super) const CHAT_COMPLETIONS_PATH: &str = "/v1/chat/completions";
+
+#[derive(Deserialize)]
+pub(super) struct ChatCompletionRequest {
+    /// Agent ID.
+    model: String,
+    messages: Vec<ChatMessage>,
+    #[serde(default)]
+    stream: bool,
+    #[serde(default)]
+    stream_options: Option<StreamOptions>,
+    /// End-user identifier. Requests with the same `user` share a session.
+    #[serde(default)]
+    user: Option<String>,
+    /// Spacebot extension: the webchat conversation to post into.
+    #[serde(default)]
+    conversation_id: Option<String>,
+}
+
+impl AgentScoped for ChatCompletionRequest {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.model)
+    }
+}
+
+#[derive(Deserialize)]
+struct StreamOptions {
+    #[serde(default)]
+    include_usage: bool,
+}
+
+#[derive(Deserialize)]
+struct ChatMessage {
+    role: String,
+    #[serde(default)]
+    content: Option<ChatContent>,
+}
+
+/// Message content: a string, or an array of content parts of which only
+/// text parts are used.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ChatContent {
+    Text(String),
+    Parts(Vec<ChatContentPart>),
+}
+
+#[derive(Deserialize)]
+struct ChatContentPart {
+    #[serde(default)]
+    text: Option<String>,
+}
+
+impl ChatMessage {
+    fn text(&self) -> String {
+        match &self.content {
+            Some(ChatContent::Text(text)) => text.clone(),
+            Some(ChatContent::Parts(parts)) => parts
+                .iter()
+                .filter_map(|part| part.text.as_deref())
+                .collect::<Vec<_>>()
+                .join("\n"),
+            None => String::new(),
+        }
+    }
+}
+
+#[derive(Serialize, Default, Clone, Copy, Debug, PartialEq, Eq)]
+struct CompletionUsage {
+    prompt_tokens: u64,
+    completion_tokens: u64,
+    total_tokens: u64,
+}
+
+/// Error in OpenAI's `{"error": {...}}` shape, so client libraries surface
+/// the message.
+pub(super) struct OpenAiError {
+    status: StatusCode,
+    message: String,
+    code: &'static str,
+}
+
+impl OpenAiError {
+    fn new(status: StatusCode, code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+            code,
+        }
+    }
+}
+
+impl IntoResponse for OpenAiError {
+    fn into_response(self) -> Response {
+        let kind = if self.status.is_server_error() {
+            "server_error"
+        } else {
+            "invalid_request_error"
+        };
+        let body = json!({
+            "error": {"message": self.message, "type": kind, "code": self.code},
+        });
+        (self.status, Json(body)).into_response()
+    }
+}
+
+/// What the agent produced during a turn.
+#[derive(Debug, PartialEq)]
+enum TurnEvent {
+    /// A reply message.
+    Reply(String),
+    /// Tokens used by one channel completion.
+    Usage { input: u64, output: u64 },
+}
+
+/// Follows one channel turn on the API event bus. The turn ends when the
+/// channel stops typing after having started, or at the deadline.
+struct TurnWatch {
+    events: broadcast::Receiver<ApiEvent>,
+    agent_id: String,
+    channel_id: String,
+    deadline: Instant,
+    started: bool,
+}
+
+impl TurnWatch {
+    async fn next(&mut self) -> Option<TurnEvent> {
+        loop {
+            let event = match tokio::time::timeout_at(self.deadline, self.events.recv()).await {
+                Ok(Ok(event)) => event,
+                Ok(Err(broadcast::error::RecvError::Lagged(skipped))) => {
+                    tracing::warn!(skipped, "chat completion fell behind the event bus");
+                    continue;
+                }
+                Ok(Err(broadcast::error::RecvError::Closed)) => return None,
+                Err(_) => {
+                    tracing::warn!(channel_id = %self.channel_id, "chat completion timed out");
+                    return None;
+                }
+            };
+            if event.channel_id() != Some(self.channel_id.as_str())
+                || !event.involves_agent(&self.agent_id)
+            {
+                continue;
+            }
+            match event {
+                ApiEvent::TypingState { is_typing, .. } => {
+                    if is_typing {
+                        self.started = true;
+                    } else if self.started {
+                        return None;
+                    }
+                }
+                ApiEvent::OutboundMessage { text, .. } => return Some(TurnEvent::Reply(text)),
+                ApiEvent::ChannelUsage {
+                    input_tokens,
+                    output_tokens,
+                    ..
+                } => {
+                    return Some(TurnEvent::Usage {
+                        input: input_tokens,
+                        output: output_tokens,
+                    });
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+impl CompletionUsage {
+    fn add(&mut self, input: u64, output: u64) {
+        self.prompt_tokens += input;
+        self.completion_tokens += output;
+        self.total_tokens = self.prompt_tokens + self.completion_tokens;
+    }
+}
+
+/// Render the messages before the last user message as a transcript, for
+/// ephemeral sessions that have no history of their own.
+fn render_transcript(messages: &[ChatMessage]) -> String {
+    messages
+        .iter()
+        .filter_map(|message| {
+            let text = message.text();
+            (!text.trim().is_empty()).then(|| format!("{}: {}", message.role, text.trim()))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub(super) async fn chat_completions(
+    State(state): State<Arc<ApiState>>,
+    AgentJson(request): AgentJson<ChatCompletionRequest>,
+) -> Result<Response, OpenAiError> {
+    let Some(settings) = state.openai else {
+        return Err(OpenAiError::new(
+            StatusCode::NOT_FOUND,
+            "not_enabled",
+            "the OpenAI-compatible endpoint is disabled; set api.openai.enabled",
+        ));
+    };
+    let agent_id = request.model.clone();
+    if !state
+        .agent_configs
+        .load()
+        .iter()
+        .any(|agent| agent.id == agent_id)
+    {
+        return Err(OpenAiError::new(
+            StatusCode::NOT_FOUND,
+            "model_not_found",
+            format!("no agent '{agent_id}'; use an agent ID as the model"),
+        ));
+    }
+    let Some(last_user) = request
+        .messages
+        .iter()
+        .rposition(|message| message.role == "user")
+    else {
+        return Err(OpenAiError::new(
+            StatusCode::BAD_REQUEST,
+            "missing_user_message",
+            "messages must include a user message",
+        ));
+    };
+    let manager = state
+        .messaging_manager
+        .read()
+        .await
+        .clone()
+        .ok_or_else(|| {
+            OpenAiError::new(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "unavailable",
+                "messaging is not running",
+            )
+        })?;
+
+    let mut text = request.messages[last_user].text();
+    let conversation_id = match (&request.conversation_id, &request.user) {
+        (Some(conversation_id), _) => conversation_id.clone(),
+        (None, Some(user)) => format!("openai:{agent_id}:{user}"),
+        (None, None) => {
+            let transcript = render_transcript(&request.messages[..last_user]);
+            if !transcript.is_empty() {
+                text = format!("Conversation so far:\n{transcript}\n\n{text}");
+            }
+            format!("openai:{agent_id}:{}", uuid::Uuid::new_v4())
+        }
+    };
+    let sender = request.user.clone().unwrap_or_else(|| "openai".into());
+
+    // Subscribe before injecting so no reply can slip past.
+    let mut turn = TurnWatch {
+        events: state.event_tx.subscribe(),
+        agent_id: agent_id.clone(),
+        channel_id: conversation_id.clone(),
+        deadline: Instant::now() + Duration::from_secs(settings.reply_timeout_secs),
+        started: false,
+    };
+
+    let mut metadata = HashMap::new();
+    metadata.insert(
+        "display_name".into(),
+        serde_json::Value::String(sender.clone()),
+    );
+    let inbound = InboundMessage {
+        id: uuid::Uuid::new_v4().to_string(),
+        source: "webchat".into(),
+        adapter: Some("webchat".into()),
+        conversation_id,
+        sender_id: sender.clone(),
+        agent_id: Some(agent_id.as_str().into()),
+        content: MessageContent::Text(text),
+        timestamp: chrono::Utc::now(),
+        metadata,
+        formatted_author: Some(sender),
+    };
+    manager.inject_message(inbound).await.map_err(|error| {
+        tracing::warn!(%error, "failed to inject chat completion message");
+        OpenAiError::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "inject_failed",
+            "failed to deliver the message to the agent",
+        )
+    })?;
+
+    let id = format!("chatcmpl-{}", uuid::Uuid::new_v4().simple());
+    let created = chrono::Utc::now().timestamp();
+
+    if !request.stream {
+        let mut replies = Vec::new();
+        let mut usage = CompletionUsage::default();
+        while let Some(event) = turn.next().await {
+            match event {
+                TurnEvent::Reply(reply) => replies.push(reply),
+                TurnEvent::Usage { input, output } => usage.add(input, output),
+            }
+        }
+        if replies.is_empty() && !turn.started {
+            return Err(OpenAiError::new(
+                StatusCode::GATEWAY_TIMEOUT,
+                "timeout",
+                "the agent did not respond in time",
+            ));
+        }
+        let body = json!({
+            "id": id,
+            "object": "chat.completion",
+            "created": created,
+            "model": agent_id,
+            "choices": [{
+                "index": 0,
+                "message": {"role": "assistant", "content": replies.join("\n\n")},
+                "finish_reason": "stop",
+            }],
+            "usage": usage,
+        });
+        return Ok(Json(body).into_response());
+    }
+
+    let include_usage = request
+        .stream_options
+        .is_some_and(|options| options.include_usage);
+    let chunk = move |delta: serde_json::Value, finish_reason: Option<&str>| {
+        Event::default().data(
+            json!({
+                "id": id,
+                "object": "chat.completion.chunk",
+                "created": created,
+                "model": agent_id,
+                "choices": [{"index": 0, "delta": delta, "finish_reason": finish_reason}],
+            })
+            .to_string(),
+        )
+    };
+    let stream = async_stream::stream! {
+        yield Ok::<_, Infallible>(chunk(json!({"role": "assistant", "content": ""}), None));
+
+        let mut usage = CompletionUsage::default();
+        let mut replied = false;
+        while let Some(event) = turn.next().await {
+            match event {
+                TurnEvent::Reply(reply) => {
+                    let content = if replied { format!("\n\n{reply}") } else { reply };
+                    replied = true;
+                    yield Ok(chunk(json!({"content": content}), None));
+                }
+                TurnEvent::Usage { input, output } => usage.add(input, output),
+            }
+        }
+        yield Ok(chunk(json!({}), Some("stop")));
+
+        if include_usage {
+            let usage_chunk = json!({
+                "object": "chat.completion.chunk",
+                "choices": [],
+                "usage": usage,
+            });
+            yield Ok(Event::default().data(usage_chunk.to_string()));
+        }
+        yield Ok(Event::default().data("[DONE]"));
+    };
+
+    Ok(Sse::new(stream).into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn watch(events: broadcast::Receiver<ApiEvent>) -> TurnWatch {
+        TurnWatch {
+            events,
+            agent_id: "agent".into(),
+            channel_id: "openai:agent:alice".into(),
+            deadline: Instant::now() + Duration::from_secs(5),
+            started: false,
+        }
+    }
+
+    fn typing(channel_id: &str, is_typing: bool) -> ApiEvent {
+        ApiEvent::TypingState {
+            agent_id: "agent".into(),
+            channel_id: channel_id.into(),
+            is_typing,
+        }
+    }
+
+    #[tokio::test]
+    async fn turn_watch_collects_one_turn_of_the_channel() {
+        let (event_tx, event_rx) = broadcast::channel(16);
+        let mut turn = watch(event_rx);
+        let channel = "openai:agent:alice";
+
+        // A previous turn finishing must not end this one.
+        event_tx.send(typing(channel, false)).unwrap();
+        event_tx.send(typing(channel, true)).unwrap();
+        event_tx
+            .send(ApiEvent::OutboundMessage {
+                agent_id: "agent".into(),
+                channel_id: "openai:agent:bob".into(),
+                text: "not for alice".into(),
+            })
+            .unwrap();
+        event_tx
+            .send(ApiEvent::ChannelUsage {
+                agent_id: "agent".into(),
+                channel_id: channel.into(),
+                input_tokens: 120,
+                output_tokens: 30,
+            })
+            .unwrap();
+        event_tx
+            .send(ApiEvent::OutboundMessage {
+                agent_id: "agent".into(),
+                channel_id: channel.into(),
+                text: "hi alice".into(),
+            })
+            .unwrap();
+        event_tx.send(typing(channel, false)).unwrap();
+
+        assert_eq!(
+            turn.next().await,
+            Some(TurnEvent::Usage {
+                input: 120,
+                output: 30
+            })
+        );
+        assert_eq!(turn.next().await, Some(TurnEvent::Reply("hi alice".into())));
+        assert_eq!(turn.next().await, None);
+    }
+
+    #[test]
+    fn message_text_joins_text_parts() {
+        let message: ChatMessage = serde_json::from_value(json!({
+            "role": "user",
+            "content": [
+                {"type": "text", "text": "first"},
+                {"type": "image_url", "image_url": {"url": "https://example.com/a.png"}},
+                {"type": "text", "text": "second"},
+            ],
+        }))
+        .unwrap();
+        assert_eq!(message.text(), "first\nsecond");
+    }
+}
@@ -0,0 +1,212 @@
+//! OpenAPI document and Swagger UI for the HTTP API.
+//!
+//! Handlers opt in with `#[utoipa::path]` and are listed in [`ApiDoc`];
+//! request and response schemas are collected from those annotations.
+
+use super::{
+    agents, approvals, audit, backfill, batch, broadcast, channels, config, contacts, cortex,
+    event_archive, export, faq, graphql, locale, logs, memories, outbox, processes, settings,
+    shares, system, uploads, usage, webhooks, workspace,
+};
+
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::{Modify, OpenApi};
+use utoipa_swagger_ui::SwaggerUi;
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Spacebot API",
+        description = "Control API for a Spacebot instance: agents, channels, memories, and the cortex."
+    ),
+    paths(
+        system::health,
+        system::healthz,
+        system::readyz,
+        system::idle,
+        system::status,
+        system::events_sse,
+        event_archive::event_history,
+        logs::log_stream,
+        graphql::graphql,
+        webhooks::list_webhooks,
+        webhooks::create_webhook,
+        webhooks::delete_webhook,
+        audit::list_audit,
+        outbox::list_dead,
+        outbox::retry_dead,
+        agents::list_agents,
+        agents::create_agent,
+        agents::delete_agent,
+        agents::delete_agent_by_id,
+        agents::get_identity,
+        agents::update_identity,
+        agents::agent_health,
+        agents::pause_agent,
+        agents::resume_agent,
+        agents::restart_agent,
+        config::get_agent_config,
+        config::update_agent_config,
+        config::validate_agent_config,
+        settings::config_history,
+        settings::config_rollback,
+        usage::agent_usage,
+        approvals::list_approvals,
+        approvals::approve,
+        approvals::reject,
+        broadcast::list_broadcast_groups,
+        broadcast::broadcast,
+        contacts::list_contacts,
+        contacts::create_contact,
+        contacts::get_contact,
+        contacts::update_contact,
+        contacts::delete_contact,
+        locale::get_channel_locale,
+        locale::set_channel_locale,
+        locale::get_user_locale,
+        locale::set_user_locale,
+        workspace::workspace_log,
+        workspace::workspace_revert,
+        channels::list_channels,
+        channels::channel_messages,
+        channels::search_messages,
+        channels::set_channel_archive,
+        channels::set_lookup_policy,
+        channels::mute_channel,
+        channels::unmute_channel,
+        channels::purge_channel,
+        shares::create_share,
+        shares::list_shares,
+        shares::revoke_share,
+        export::export_channel,
+        processes::list_processes,
+        processes::cancel_process,
+        memories::list_memories,
+        memories::create_memory,
+        memories::import_memories,
+        memories::export_memory_backup,
+        memories::restore_memory_backup,
+        memories::search_memories,
+        memories::memory_graph,
+        memories::memory_graph_neighbors,
+        memories::link_memories,
+        memories::list_memory_trash,
+        memories::restore_memory,
+        memories::update_memory,
+        memories::delete_memory,
+        memories::memory_history,
+        memories::memories_as_of,
+        memories::list_consolidations,
+        memories::rescore_memories,
+        memories::list_todos,
+        memories::entity_profile,
+        backfill::create_backfill,
+        backfill::list_backfills,
+        backfill::get_backfill,
+        backfill::cancel_backfill,
+        backfill::resume_backfill,
+        batch::create_batch,
+        batch::list_batches,
+        batch::get_batch,
+        batch::cancel_batch,
+        faq::list_faq,
+        faq::create_faq,
+        faq::get_faq,
+        faq::update_faq,
+        faq::delete_faq,
+        faq::import_faq,
+        faq::approve_faq,
+        faq::reject_faq,
+        faq::faq_analytics,
+        cortex::cortex_chat_messages,
+        cortex::cortex_chat_send,
+        cortex::cortex_chat_regenerate,
+        cortex::cortex_chat_edit,
+        cortex::cortex_chat_threads,
+        cortex::cortex_chat_delete_thread,
+        cortex::cortex_chat_rename_thread,
+        uploads::upload_files,
+    ),
+    modifiers(&BearerAuth),
+    security(("bearer_token" = [])),
+    tags(
+        (name = "system", description = "Health, status, the event stream, and live logs"),
+        (name = "agents", description = "Agent configuration"),
+        (name = "channels", description = "Conversations and their timelines"),
+        (name = "memories", description = "Agent memory store"),
+        (name = "batch", description = "Offline batch jobs run by workers"),
+        (name = "faq", description = "Curated Q&A store and matching"),
+        (name = "cortex", description = "Direct chat with an agent's cortex"),
+    )
+)]
+pub(super) struct ApiDoc;
+
+/// Registers the bearer scheme used by `[api] tokens` / `auth_token`.
+struct BearerAuth;
+
+impl Modify for BearerAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer_token",
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).build()),
+        );
+    }
+}
+
+/// Routes serving the document at `/api/openapi.json` and Swagger UI at
+/// `/api/docs`. The UI assets are embedded in the binary. Both sit outside
+/// API auth: they describe the surface, not any instance data.
+pub(super) fn routes() -> SwaggerUi {
+    SwaggerUi::new("/api/docs").url("/api/openapi.json", ApiDoc::openapi())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn document_covers_annotated_routes() {
+        let document = ApiDoc::openapi();
+        for path in [
+            "/api/status",
+            "/api/events",
+            "/api/agents/memories/trash",
+            "/api/cortex-chat/send",
+            "/api/agents/{id}",
+            "/api/agents/identity",
+            "/api/agents/config",
+            "/api/agents/config/validate",
+            "/readyz",
+            "/api/agents/{id}/health",
+            "/api/agents/{id}/pause",
+            "/api/agents/{id}/resume",
+            "/api/agents/{id}/restart",
+            "/api/channels/archive",
+            "/api/channels/purge",
+            "/api/channels/lookup-policy",
+            "/api/channels/mute",
+            "/api/channels/unmute",
+            "/api/channels/{id}/share",
+            "/api/channels/{id}/shares",
+            "/api/channels/{id}/shares/{share_id}",
+            "/api/webhooks",
+            "/api/webhooks/{id}",
+            "/api/audit",
+            "/api/outbox/dead",
+            "/api/outbox/{id}/retry",
+            "/api/graphql",
+            "/api/config/history",
+            "/api/config/rollback",
+        ] {
+            assert!(
+                document.paths.paths.contains_key(path),
+                "missing {path} in OpenAPI document"
+            );
+        }
+        let schemas = &document.components.expect("components").schemas;
+        assert!(schemas.contains_key("Memory"));
+        assert!(schemas.contains_key("StatusResponse"));
+        assert!(schemas.contains_key("AgentConfigResponse"));
+    }
+}
@@ -14,7 +14,7 @@ use axum::response::{IntoResponse, Response};
 use futures::TryStreamExt;
 
 /// Check if a header is a hop-by-hop header that must not be forwarded.
-fn is_hop_by_hop(name: &HeaderName) -> bool {
+pub(super) fn is_hop_by_hop(name: &HeaderName) -> bool {
     matches!(
         *name,
         header::CONNECTION
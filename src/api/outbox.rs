@@ -0,0 +1,81 @@
+//! Dead-lettered outbound replies: listing and manual retry.
+
+use super::state::ApiState;
+
+use crate::messaging::outbox::{DeadLetter, Outbox};
+
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use serde::Serialize;
+
+use std::sync::Arc;
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct DeadLetterListResponse {
+    dead: Vec<DeadLetter>,
+}
+
+fn outbox(state: &ApiState) -> Result<&Arc<Outbox>, (StatusCode, String)> {
+    state.outbox.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "outbox is unavailable".to_string(),
+    ))
+}
+
+/// Replies that ran out of delivery attempts, most recent first.
+#[utoipa::path(
+    get,
+    path = "/api/outbox/dead",
+    tag = "system",
+    responses(
+        (status = 200, body = DeadLetterListResponse),
+        (status = 503, description = "Outbox unavailable"),
+    )
+)]
+pub(super) async fn list_dead(
+    State(state): State<Arc<ApiState>>,
+) -> Result<Json<DeadLetterListResponse>, (StatusCode, String)> {
+    let outbox = outbox(&state)?;
+    match outbox.dead_letters() {
+        Ok(dead) => Ok(Json(DeadLetterListResponse { dead })),
+        Err(error) => {
+            tracing::warn!(%error, "failed to list dead-lettered replies");
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to list dead letters".to_string(),
+            ))
+        }
+    }
+}
+
+/// Queue a dead-lettered reply again. The outbox worker picks it up on its
+/// next pass with a fresh set of attempts.
+#[utoipa::path(
+    post,
+    path = "/api/outbox/{id}/retry",
+    tag = "system",
+    params(("id" = String, Path, description = "Dead letter id")),
+    responses(
+        (status = 204, description = "Queued for another delivery attempt"),
+        (status = 404, description = "Unknown dead letter"),
+        (status = 503, description = "Outbox unavailable"),
+    )
+)]
+pub(super) async fn retry_dead(
+    State(state): State<Arc<ApiState>>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    let outbox = outbox(&state)?;
+    match outbox.retry(&id) {
+        Ok(true) => Ok(StatusCode::NO_CONTENT),
+        Ok(false) => Err((StatusCode::NOT_FOUND, "dead letter not found".to_string())),
+        Err(error) => {
+            tracing::warn!(%error, %id, "failed to retry dead-lettered reply");
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to retry dead letter".to_string(),
+            ))
+        }
+    }
+}
@@ -0,0 +1,262 @@
+//! Live worker and branch control: list what every channel is running and
+//! cancel a process by id.
+//!
+//! The list is built from the channels' status blocks, the same data the
+//! channel sees in its prompt. Cancelling aborts the process's task, which
+//! drops any LLM request it has in flight, and records the run as
+//! `cancelled`.
+
+use super::access::{AgentQuery, AgentScoped};
+use super::state::ApiState;
+
+use crate::conversation::history::ProcessRunLogger;
+
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+const DEFAULT_CANCEL_REASON: &str = "cancelled via API";
+
+#[derive(Deserialize, utoipa::IntoParams)]
+pub(super) struct ProcessListQuery {
+    #[serde(default)]
+    agent_id: Option<String>,
+    #[serde(default)]
+    channel_id: Option<String>,
+}
+
+impl AgentScoped for ProcessListQuery {
+    fn agent_id(&self) -> Option<&str> {
+        self.agent_id.as_deref()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum ProcessType {
+    Worker,
+    Branch,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct ProcessInfo {
+    id: String,
+    process_type: ProcessType,
+    agent_id: String,
+    channel_id: String,
+    /// Worker task or branch description.
+    description: String,
+    /// Latest `set_status` text (workers only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_calls: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    interactive: Option<bool>,
+    started_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct ProcessListResponse {
+    processes: Vec<ProcessInfo>,
+}
+
+#[derive(Deserialize, Default, utoipa::ToSchema)]
+pub(super) struct CancelProcessBody {
+    /// Shown to the channel as the cancellation reason.
+    #[serde(default)]
+    reason: Option<String>,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct CancelProcessResult {
+    id: String,
+    process_type: ProcessType,
+    /// Owning channel; absent for detached workers reconciled from the run log.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    channel_id: Option<String>,
+    message: String,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/processes",
+    tag = "channels",
+    params(ProcessListQuery),
+    responses(
+        (status = 200, description = "Running workers and branches, oldest first", body = ProcessListResponse),
+    )
+)]
+pub(super) async fn list_processes(
+    State(state): State<Arc<ApiState>>,
+    AgentQuery(query): AgentQuery<ProcessListQuery>,
+) -> Json<ProcessListResponse> {
+    let channel_states: Vec<_> = {
+        let states = state.channel_states.read().await;
+        states.values().cloned().collect()
+    };
+
+    let mut processes = Vec::new();
+    for channel_state in channel_states {
+        let agent_id = channel_state.deps.agent_id.to_string();
+        let channel_id = channel_state.channel_id.to_string();
+        if query.agent_id.as_ref().is_some_and(|id| *id != agent_id)
+            || query
+                .channel_id
+                .as_ref()
+                .is_some_and(|id| *id != channel_id)
+        {
+            continue;
+        }
+
+        let block = channel_state.status_block.read().await;
+        processes.extend(block.active_workers.iter().map(|worker| ProcessInfo {
+            id: worker.id.to_string(),
+            process_type: ProcessType::Worker,
+            agent_id: agent_id.clone(),
+            channel_id: channel_id.clone(),
+            description: worker.task.clone(),
+            status: Some(worker.status.clone()),
+            tool_calls: Some(worker.tool_calls),
+            interactive: Some(worker.interactive),
+            started_at: worker.started_at,
+        }));
+        processes.extend(block.active_branches.iter().map(|branch| ProcessInfo {
+            id: branch.id.to_string(),
+            process_type: ProcessType::Branch,
+            agent_id: agent_id.clone(),
+            channel_id: channel_id.clone(),
+            description: branch.description.clone(),
+            status: None,
+            tool_calls: None,
+            interactive: None,
+            started_at: branch.started_at,
+        }));
+    }
+    processes.sort_by(|a, b| a.started_at.cmp(&b.started_at).then(a.id.cmp(&b.id)));
+
+    Json(ProcessListResponse { processes })
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/processes/{id}/cancel",
+    tag = "channels",
+    params(("id" = String, Path, description = "Worker or branch id")),
+    request_body(content = CancelProcessBody, description = "Optional cancellation reason"),
+    responses(
+        (status = 200, description = "Cancelled", body = CancelProcessResult),
+        (status = 400, description = "Malformed id"),
+        (status = 404, description = "No running worker or branch with this id"),
+    )
+)]
+pub(super) async fn cancel_process(
+    State(state): State<Arc<ApiState>>,
+    Path(id): Path<String>,
+    body: Option<Json<CancelProcessBody>>,
+) -> Result<Json<CancelProcessResult>, StatusCode> {
+    let process_id: uuid::Uuid = id.parse().map_err(|_| StatusCode::BAD_REQUEST)?;
+    let reason = body
+        .and_then(|Json(body)| body.reason)
+        .filter(|reason| !reason.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_CANCEL_REASON.to_string());
+
+    let channel_states: Vec<_> = {
+        let states = state.channel_states.read().await;
+        states.values().cloned().collect()
+    };
+
+    for channel_state in channel_states {
+        let (is_worker, is_branch) = {
+            let block = channel_state.status_block.read().await;
+            (
+                block
+                    .active_workers
+                    .iter()
+                    .any(|worker| worker.id == process_id),
+                block
+                    .active_branches
+                    .iter()
+                    .any(|branch| branch.id == process_id),
+            )
+        };
+        let is_worker = is_worker
+            || channel_state
+                .worker_handles
+                .read()
+                .await
+                .contains_key(&process_id);
+        let is_branch = is_branch
+            || channel_state
+                .active_branches
+                .read()
+                .await
+                .contains_key(&process_id);
+
+        let (process_type, outcome) = if is_worker {
+            (
+                ProcessType::Worker,
+                channel_state
+                    .cancel_worker_with_reason(process_id, &reason)
+                    .await,
+            )
+        } else if is_branch {
+            (
+                ProcessType::Branch,
+                channel_state
+                    .cancel_branch_with_reason(process_id, &reason)
+                    .await,
+            )
+        } else {
+            continue;
+        };
+
+        let channel_id = channel_state.channel_id.to_string();
+        return match outcome {
+            Ok(()) => {
+                tracing::info!(
+                    process_id = %id,
+                    ?process_type,
+                    channel_id = %channel_id,
+                    %reason,
+                    "process cancelled via API"
+                );
+                Ok(Json(CancelProcessResult {
+                    id,
+                    process_type,
+                    channel_id: Some(channel_id),
+                    message: reason,
+                }))
+            }
+            // Finished between the lookup and the cancel.
+            Err(_) => Err(StatusCode::NOT_FOUND),
+        };
+    }
+
+    // Workers without a live channel (detached, or orphaned by a restart)
+    // only exist in the run log.
+    let pools = state.agent_pools.load();
+    for pool in pools.values() {
+        let logger = ProcessRunLogger::new(pool.clone());
+        match logger.cancel_running_detached_worker(process_id).await {
+            Ok(true) => {
+                return Ok(Json(CancelProcessResult {
+                    id,
+                    process_type: ProcessType::Worker,
+                    channel_id: None,
+                    message: "detached worker run marked cancelled".to_string(),
+                }));
+            }
+            Ok(false) => {}
+            Err(error) => {
+                tracing::warn!(%error, process_id = %id, "failed to cancel detached worker run");
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        }
+    }
+
+    Err(StatusCode::NOT_FOUND)
+}
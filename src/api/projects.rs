@@ -1,9 +1,10 @@
 //! REST API handlers for project, repo, and worktree management.
 
+use super::access::{AgentJson, AgentQuery, AgentScoped};
 use super::state::ApiState;
 
 use axum::Json;
-use axum::extract::{Path, Query, State};
+use axum::extract::{Path, State};
 use axum::http::StatusCode;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -49,10 +50,16 @@ fn sanitize_segment(name: &str) -> Result<String, StatusCode> {
 // ---------------------------------------------------------------------------
 
 #[derive(Deserialize)]
-pub(super) struct AgentQuery {
+pub(super) struct ProjectAgentQuery {
     agent_id: String,
 }
 
+impl AgentScoped for ProjectAgentQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
 #[derive(Deserialize)]
 pub(super) struct ProjectListQuery {
     agent_id: String,
@@ -60,6 +67,12 @@ pub(super) struct ProjectListQuery {
     status: Option<String>,
 }
 
+impl AgentScoped for ProjectListQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
 #[derive(Deserialize)]
 pub(super) struct CreateProjectRequest {
     agent_id: String,
@@ -78,6 +91,12 @@ pub(super) struct CreateProjectRequest {
     auto_discover: bool,
 }
 
+impl AgentScoped for CreateProjectRequest {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
 #[derive(Deserialize)]
 pub(super) struct UpdateProjectRequest {
     agent_id: String,
@@ -95,6 +114,12 @@ pub(super) struct UpdateProjectRequest {
     status: Option<String>,
 }
 
+impl AgentScoped for UpdateProjectRequest {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
 #[derive(Deserialize)]
 pub(super) struct CreateRepoRequest {
     agent_id: String,
@@ -108,6 +133,12 @@ pub(super) struct CreateRepoRequest {
     description: Option<String>,
 }
 
+impl AgentScoped for CreateRepoRequest {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
 #[derive(Deserialize)]
 pub(super) struct CreateWorktreeRequest {
     agent_id: String,
@@ -119,6 +150,12 @@ pub(super) struct CreateWorktreeRequest {
     start_point: Option<String>,
 }
 
+impl AgentScoped for CreateWorktreeRequest {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Response types
 // ---------------------------------------------------------------------------
@@ -312,7 +349,7 @@ async fn compute_and_cache_disk_usage(
 /// GET /agents/projects — list projects for an agent.
 pub(super) async fn list_projects(
     State(state): State<Arc<ApiState>>,
-    Query(query): Query<ProjectListQuery>,
+    AgentQuery(query): AgentQuery<ProjectListQuery>,
 ) -> Result<Json<ProjectListResponse>, StatusCode> {
     let stores = state.project_stores.load();
     let store = stores.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
@@ -333,7 +370,7 @@ pub(super) async fn list_projects(
 /// POST /agents/projects — create a new project.
 pub(super) async fn create_project(
     State(state): State<Arc<ApiState>>,
-    Json(request): Json<CreateProjectRequest>,
+    AgentJson(request): AgentJson<CreateProjectRequest>,
 ) -> Result<Json<ProjectResponse>, StatusCode> {
     let stores = state.project_stores.load();
     let store = stores.get(&request.agent_id).ok_or(StatusCode::NOT_FOUND)?;
@@ -417,7 +454,7 @@ pub(super) async fn create_project(
 pub(super) async fn get_project(
     State(state): State<Arc<ApiState>>,
     Path(project_id): Path<String>,
-    Query(query): Query<AgentQuery>,
+    AgentQuery(query): AgentQuery<ProjectAgentQuery>,
 ) -> Result<Json<ProjectResponse>, StatusCode> {
     let stores = state.project_stores.load();
     let store = stores.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
@@ -438,7 +475,7 @@ pub(super) async fn get_project(
 pub(super) async fn update_project(
     State(state): State<Arc<ApiState>>,
     Path(project_id): Path<String>,
-    Json(request): Json<UpdateProjectRequest>,
+    AgentJson(request): AgentJson<UpdateProjectRequest>,
 ) -> Result<Json<ProjectResponse>, StatusCode> {
     let stores = state.project_stores.load();
     let store = stores.get(&request.agent_id).ok_or(StatusCode::NOT_FOUND)?;
@@ -482,7 +519,7 @@ pub(super) async fn update_project(
 pub(super) async fn delete_project(
     State(state): State<Arc<ApiState>>,
     Path(project_id): Path<String>,
-    Query(query): Query<AgentQuery>,
+    AgentQuery(query): AgentQuery<ProjectAgentQuery>,
 ) -> Result<Json<ActionResponse>, StatusCode> {
     let stores = state.project_stores.load();
     let store = stores.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
@@ -512,7 +549,7 @@ pub(super) async fn delete_project(
 pub(super) async fn scan_project(
     State(state): State<Arc<ApiState>>,
     Path(project_id): Path<String>,
-    Query(query): Query<AgentQuery>,
+    AgentQuery(query): AgentQuery<ProjectAgentQuery>,
 ) -> Result<Json<ProjectResponse>, StatusCode> {
     let stores = state.project_stores.load();
     let store = stores.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
@@ -597,7 +634,7 @@ pub(super) async fn scan_project(
 pub(super) async fn create_repo(
     State(state): State<Arc<ApiState>>,
     Path(project_id): Path<String>,
-    Json(request): Json<CreateRepoRequest>,
+    AgentJson(request): AgentJson<CreateRepoRequest>,
 ) -> Result<Json<RepoResponse>, StatusCode> {
     let stores = state.project_stores.load();
     let store = stores.get(&request.agent_id).ok_or(StatusCode::NOT_FOUND)?;
@@ -638,7 +675,7 @@ pub(super) async fn create_repo(
 pub(super) async fn delete_repo(
     State(state): State<Arc<ApiState>>,
     Path((project_id, repo_id)): Path<(String, String)>,
-    Query(query): Query<AgentQuery>,
+    AgentQuery(query): AgentQuery<ProjectAgentQuery>,
 ) -> Result<Json<ActionResponse>, StatusCode> {
     let stores = state.project_stores.load();
     let store = stores.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
@@ -675,7 +712,7 @@ pub(super) async fn delete_repo(
 pub(super) async fn create_worktree(
     State(state): State<Arc<ApiState>>,
     Path(project_id): Path<String>,
-    Json(request): Json<CreateWorktreeRequest>,
+    AgentJson(request): AgentJson<CreateWorktreeRequest>,
 ) -> Result<Json<WorktreeResponse>, StatusCode> {
     let stores = state.project_stores.load();
     let store = stores.get(&request.agent_id).ok_or(StatusCode::NOT_FOUND)?;
@@ -763,7 +800,7 @@ pub(super) async fn create_worktree(
 pub(super) async fn delete_worktree(
     State(state): State<Arc<ApiState>>,
     Path((project_id, worktree_id)): Path<(String, String)>,
-    Query(query): Query<AgentQuery>,
+    AgentQuery(query): AgentQuery<ProjectAgentQuery>,
 ) -> Result<Json<ActionResponse>, StatusCode> {
     let stores = state.project_stores.load();
     let store = stores.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
@@ -835,7 +872,7 @@ pub(super) async fn delete_worktree(
 pub(super) async fn disk_usage(
     State(state): State<Arc<ApiState>>,
     Path(project_id): Path<String>,
-    Query(query): Query<AgentQuery>,
+    AgentQuery(query): AgentQuery<ProjectAgentQuery>,
 ) -> Result<Json<DiskUsageResponse>, StatusCode> {
     let stores = state.project_stores.load();
     let store = stores.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
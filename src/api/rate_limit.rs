@@ -0,0 +1,208 @@
+//! Per-client request rate limiting for the API.
+//!
+//! Each client gets a token bucket per tier: one for all requests and a
+//! tighter one for endpoints that run embeddings or LLM calls. Clients are
+//! the authenticated token when auth is configured, otherwise the peer IP.
+//! Over-limit requests get a 429 with `Retry-After`, so a runaway dashboard
+//! tab backs off instead of starving the agent's database pools.
+
+use super::server::ApiPrincipal;
+use super::state::ApiState;
+
+use crate::config::ApiRateLimitConfig;
+
+use axum::Json;
+use axum::extract::{ConnectInfo, Request, State};
+use axum::http::{HeaderValue, Method, StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use serde_json::json;
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Routes (relative to `/api`, or at the root for `/v1`) counted against the
+/// expensive tier.
+const EXPENSIVE_ROUTES: &[(Method, &str)] = &[
+    (Method::GET, "/agents/memories/search"),
+    (Method::GET, "/agents/memories/graph"),
+    (Method::GET, "/agents/entities/"),
+    (Method::POST, "/cortex-chat/send"),
+    (Method::POST, "/webchat/send"),
+    (Method::POST, "/v1/chat/completions"),
+];
+
+/// Buckets idle this long are full again and can be dropped.
+const IDLE_BUCKET_TTL: Duration = Duration::from_secs(120);
+
+/// Tracked buckets before idle ones are pruned.
+const PRUNE_THRESHOLD: usize = 4096;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Tier {
+    All,
+    Expensive,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+/// Token buckets keyed by client and tier.
+#[derive(Debug)]
+pub struct RateLimiter {
+    config: ApiRateLimitConfig,
+    buckets: Mutex<HashMap<(String, Tier), Bucket>>,
+}
+
+impl RateLimiter {
+    /// `None` when every limit is disabled.
+    pub fn new(config: ApiRateLimitConfig) -> Option<Self> {
+        if config.requests_per_minute == 0 && config.expensive_requests_per_minute == 0 {
+            return None;
+        }
+        Some(Self {
+            config,
+            buckets: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Take a token for each tier the request counts against. Returns how
+    /// long to wait if any bucket is empty; no tokens are taken then.
+    pub(super) fn check(
+        &self,
+        client: &str,
+        expensive: bool,
+        now: Instant,
+    ) -> Result<(), Duration> {
+        let mut tiers = vec![(Tier::All, self.config.requests_per_minute)];
+        if expensive {
+            tiers.push((Tier::Expensive, self.config.expensive_requests_per_minute));
+        }
+        tiers.retain(|(_, per_minute)| *per_minute > 0);
+
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        if buckets.len() > PRUNE_THRESHOLD {
+            buckets.retain(|_, bucket| now.duration_since(bucket.updated_at) < IDLE_BUCKET_TTL);
+        }
+
+        let mut wait = Duration::ZERO;
+        for &(tier, per_minute) in &tiers {
+            let capacity = f64::from(per_minute);
+            let refill_per_sec = capacity / 60.0;
+            let bucket = buckets.entry((client.to_string(), tier)).or_insert(Bucket {
+                tokens: capacity,
+                updated_at: now,
+            });
+            let elapsed = now.duration_since(bucket.updated_at).as_secs_f64();
+            bucket.tokens = (bucket.tokens + elapsed * refill_per_sec).min(capacity);
+            bucket.updated_at = now;
+            if bucket.tokens < 1.0 {
+                wait = wait.max(Duration::from_secs_f64(
+                    (1.0 - bucket.tokens) / refill_per_sec,
+                ));
+            }
+        }
+        if !wait.is_zero() {
+            return Err(wait);
+        }
+
+        for (tier, _) in tiers {
+            if let Some(bucket) = buckets.get_mut(&(client.to_string(), tier)) {
+                bucket.tokens -= 1.0;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn is_expensive(method: &Method, path: &str) -> bool {
+    let relative_path = path.strip_prefix("/api").unwrap_or(path);
+    EXPENSIVE_ROUTES
+        .iter()
+        .any(|(route_method, prefix)| route_method == method && relative_path.starts_with(prefix))
+}
+
+pub(super) async fn rate_limit_middleware(
+    State(state): State<Arc<ApiState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(limiter) = state.rate_limiter.as_ref() else {
+        return next.run(request).await;
+    };
+
+    let client = match request.extensions().get::<ApiPrincipal>() {
+        Some(principal) => format!("token:{}", principal.name),
+        None => match request.extensions().get::<ConnectInfo<SocketAddr>>() {
+            Some(ConnectInfo(address)) => format!("ip:{}", address.ip()),
+            None => "unknown".to_string(),
+        },
+    };
+    let expensive = is_expensive(request.method(), request.uri().path());
+
+    if let Err(wait) = limiter.check(&client, expensive, Instant::now()) {
+        tracing::debug!(%client, expensive, ?wait, "API request rate limited");
+        return rate_limited_response(wait);
+    }
+
+    next.run(request).await
+}
+
+/// 429 telling the client how long to back off.
+pub(super) fn rate_limited_response(wait: Duration) -> Response {
+    let retry_after = wait.as_secs().max(1);
+    let mut response = (
+        StatusCode::TOO_MANY_REQUESTS,
+        Json(json!({
+            "error": "rate_limited",
+            "message": format!("too many requests; retry in {retry_after}s"),
+        })),
+    )
+        .into_response();
+    response
+        .headers_mut()
+        .insert(header::RETRY_AFTER, HeaderValue::from(retry_after));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buckets_refill_and_expensive_tier_is_separate() {
+        let limiter = RateLimiter::new(ApiRateLimitConfig {
+            requests_per_minute: 3,
+            expensive_requests_per_minute: 1,
+        })
+        .unwrap();
+        let start = Instant::now();
+
+        assert!(limiter.check("a", true, start).is_ok());
+        let wait = limiter.check("a", true, start).unwrap_err();
+        assert!(wait > Duration::from_secs(59));
+        // A refused expensive request doesn't spend the overall budget.
+        assert!(limiter.check("a", false, start).is_ok());
+        assert!(limiter.check("a", false, start).is_ok());
+        assert!(limiter.check("a", false, start).is_err());
+        assert!(limiter.check("b", false, start).is_ok());
+
+        let later = start + Duration::from_secs(20);
+        assert!(limiter.check("a", false, later).is_ok());
+
+        assert!(
+            RateLimiter::new(ApiRateLimitConfig {
+                requests_per_minute: 0,
+                expensive_requests_per_minute: 0,
+            })
+            .is_none()
+        );
+        assert!(is_expensive(&Method::GET, "/api/agents/memories/search"));
+        assert!(!is_expensive(&Method::GET, "/api/agents/memories"));
+    }
+}
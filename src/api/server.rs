@@ -2,24 +2,35 @@
 
 use super::state::ApiState;
 use super::{
-    agents, bindings, channels, config, cortex, cron, factory, ingest, links, mcp, memories,
-    messaging, models, opencode_proxy, projects, providers, secrets, settings, skills, ssh, system,
-    tasks, tools, webchat, workers,
+    access, agents, approvals, attachments, audit, backfill, batch, bindings, broadcast, channels,
+    config, contacts, cortex, cron, dev_proxy, event_archive, export, factory, faq, graphql,
+    idempotency, ingest, links, locale, logs, mcp, memories, messaging, models, openai, openapi,
+    opencode_proxy, outbox, processes, projects, providers, rate_limit, secrets, settings, shares,
+    skills, ssh, system, tasks, tls, tools, uploads, usage, webchat, webhooks, widget, workers,
+    workspace,
 };
 
+use crate::config::{ApiTlsConfig, ApiTokenScope};
+
+use anyhow::Context as _;
 use axum::Json;
 
 use axum::Router;
 use axum::extract::{DefaultBodyLimit, Request, State};
-use axum::http::{StatusCode, Uri, header};
+use axum::http::{Method, StatusCode, header};
 use axum::middleware::{self, Next};
-use axum::response::{Html, IntoResponse, Response};
+use axum::response::{IntoResponse, Response};
 use axum::routing::{any, delete, get, post, put};
+use axum::serve::ListenerExt as _;
 use rust_embed::Embed;
 use serde_json::json;
+use tower_http::compression::CompressionLayer;
+use tower_http::compression::predicate::{DefaultPredicate, NotForContentType, Predicate};
 use tower_http::cors::CorsLayer;
 
 use std::net::SocketAddr;
+use std::os::unix::fs::{FileTypeExt as _, PermissionsExt as _};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 /// Embedded frontend assets from the Vite build output.
@@ -28,15 +39,122 @@ use std::sync::Arc;
 #[allow(unused)]
 struct InterfaceAssets;
 
-/// Start the HTTP server on the given address.
+/// `.gz` and `.br` variants of [`InterfaceAssets`], written by `build.rs`
+/// under the same relative paths.
+#[derive(Embed)]
+#[folder = "$OUT_DIR/interface-precompressed/"]
+struct PrecompressedAssets;
+
+/// An address the HTTP server accepts connections on.
+#[derive(Debug, Clone)]
+pub enum ApiListener {
+    Tcp(SocketAddr),
+    /// A unix domain socket at `path`, created with `mode` permissions and
+    /// removed again on shutdown.
+    Unix {
+        path: PathBuf,
+        mode: u32,
+    },
+}
+
+/// Start the HTTP server on each of the given listeners. TLS, when
+/// configured, applies to TCP listeners only.
 ///
 /// The caller provides a pre-built `ApiState` so agent event streams and
-/// DB pools can be registered after startup.
+/// DB pools can be registered after startup. The returned handle completes
+/// once every listener has shut down.
 pub async fn start_http_server(
-    bind: SocketAddr,
+    listeners: Vec<ApiListener>,
     state: Arc<ApiState>,
     shutdown_rx: tokio::sync::watch::Receiver<bool>,
+    tls: Option<ApiTlsConfig>,
 ) -> anyhow::Result<tokio::task::JoinHandle<()>> {
+    let state_dev_proxy_url = state.dev_proxy_url.clone();
+    let app = build_router(state);
+
+    if let Some(dev_proxy_url) = state_dev_proxy_url.as_deref() {
+        dev_proxy::validate_dev_proxy_url(dev_proxy_url)?;
+        tracing::info!(%dev_proxy_url, "proxying frontend requests to dev server");
+    }
+
+    // Load the certificate before binding so a bad path fails startup
+    // instead of leaving a port open that can't complete handshakes.
+    let acceptor = tls.as_ref().map(tls::load_acceptor).transpose()?;
+    if listeners.is_empty() {
+        anyhow::bail!("no API listeners configured");
+    }
+
+    let mut servers = Vec::with_capacity(listeners.len());
+    for listener in listeners {
+        let mut shutdown = shutdown_rx.clone();
+        let shutdown_signal = async move {
+            let _ = shutdown.wait_for(|v| *v).await;
+        };
+
+        let server = match listener {
+            ApiListener::Tcp(bind) => {
+                let listener = tokio::net::TcpListener::bind(bind).await?;
+                let make_service = app
+                    .clone()
+                    .into_make_service_with_connect_info::<SocketAddr>();
+                match acceptor.clone() {
+                    Some(acceptor) => {
+                        // Tapping is a no-op, but gives the listener axum's
+                        // `ConnectInfo<SocketAddr>` support.
+                        let listener = tls::TlsListener::new(listener, acceptor)?.tap_io(|_| {});
+                        tracing::info!(%bind, "HTTPS server listening");
+                        tokio::spawn(async move {
+                            if let Err(error) = axum::serve(listener, make_service)
+                                .with_graceful_shutdown(shutdown_signal)
+                                .await
+                            {
+                                tracing::error!(%error, "HTTPS server exited with error");
+                            }
+                        })
+                    }
+                    None => {
+                        tracing::info!(%bind, "HTTP server listening");
+                        tokio::spawn(async move {
+                            if let Err(error) = axum::serve(listener, make_service)
+                                .with_graceful_shutdown(shutdown_signal)
+                                .await
+                            {
+                                tracing::error!(%error, "HTTP server exited with error");
+                            }
+                        })
+                    }
+                }
+            }
+            ApiListener::Unix { path, mode } => {
+                let listener = bind_unix_socket(&path, mode)?;
+                let make_service = app.clone().into_make_service();
+                tracing::info!(path = %path.display(), "HTTP server listening on unix socket");
+                tokio::spawn(async move {
+                    if let Err(error) = axum::serve(listener, make_service)
+                        .with_graceful_shutdown(shutdown_signal)
+                        .await
+                    {
+                        tracing::error!(%error, "unix socket HTTP server exited with error");
+                    }
+                    let _ = std::fs::remove_file(&path);
+                })
+            }
+        };
+        servers.push(server);
+    }
+
+    let handle = tokio::spawn(async move {
+        for server in servers {
+            let _ = server.await;
+        }
+    });
+
+    Ok(handle)
+}
+
+/// The full application router: the API, OpenAI-compatible and widget
+/// routes, probes, share pages, and the embedded frontend.
+fn build_router(state: Arc<ApiState>) -> Router {
     let cors = CorsLayer::new()
         .allow_origin(tower_http::cors::AllowOrigin::mirror_request())
         .allow_methods([
@@ -46,74 +164,114 @@ pub async fn start_http_server(
             axum::http::Method::DELETE,
             axum::http::Method::OPTIONS,
         ])
-        .allow_headers([header::CONTENT_TYPE, header::AUTHORIZATION, header::ACCEPT]);
+        .allow_headers([
+            header::CONTENT_TYPE,
+            header::AUTHORIZATION,
+            header::ACCEPT,
+            header::HeaderName::from_static("idempotency-key"),
+        ])
+        .expose_headers([header::HeaderName::from_static("idempotent-replayed")]);
 
-    let api_routes = Router::new()
-        .route("/health", get(system::health))
-        .route("/idle", get(system::idle))
-        .route("/status", get(system::status))
-        .route("/system/storage", get(system::storage_status))
-        .route("/system/backup/export", get(system::backup_export))
-        .route("/system/backup/restore", post(system::backup_restore))
-        .route("/overview", get(agents::instance_overview))
+    // Handlers on these routes check an agent-restricted token against the
+    // agent they act on, through the extractors in `access`.
+    let agent_routes = Router::new()
         .route("/events", get(system::events_sse))
-        .route(
-            "/agents",
-            get(agents::list_agents)
-                .post(agents::create_agent)
-                .put(agents::update_agent)
-                .delete(agents::delete_agent),
-        )
+        .route("/events/history", get(event_archive::event_history))
+        .route("/graphql", post(graphql::graphql))
         .route("/agents/mcp", get(agents::list_agent_mcp))
         .route("/agents/mcp/reconnect", post(agents::reconnect_agent_mcp))
         .route(
             "/agents/warmup",
             get(agents::get_warmup_status).post(agents::trigger_warmup),
         )
+        .route("/agents/overview", get(agents::agent_overview))
+        .route("/agents/usage", get(usage::agent_usage))
+        .route("/agents/approvals", get(approvals::list_approvals))
+        .route("/agents/approvals/{id}/approve", post(approvals::approve))
+        .route("/agents/approvals/{id}/reject", post(approvals::reject))
+        .route("/agents/broadcast", post(broadcast::broadcast))
         .route(
-            "/mcp/servers",
-            get(mcp::list_mcp_servers)
-                .post(mcp::create_mcp_server)
-                .put(mcp::update_mcp_server),
+            "/agents/broadcast/groups",
+            get(broadcast::list_broadcast_groups),
         )
-        .route("/mcp/servers/{name}", delete(mcp::delete_mcp_server))
         .route(
-            "/mcp/servers/{name}/reconnect",
-            post(mcp::reconnect_mcp_server),
+            "/agents/locale/channel",
+            get(locale::get_channel_locale).put(locale::set_channel_locale),
+        )
+        .route(
+            "/agents/locale/user",
+            get(locale::get_user_locale).put(locale::set_user_locale),
+        )
+        .route(
+            "/agents/contacts",
+            get(contacts::list_contacts).post(contacts::create_contact),
+        )
+        .route(
+            "/agents/contacts/{id}",
+            get(contacts::get_contact)
+                .put(contacts::update_contact)
+                .delete(contacts::delete_contact),
         )
-        .route("/mcp/status", get(mcp::mcp_status))
-        .route("/agents/overview", get(agents::agent_overview))
         .route(
             "/channels",
             get(channels::list_channels).delete(channels::delete_channel),
         )
         .route("/channels/archive", put(channels::set_channel_archive))
+        .route("/channels/purge", post(channels::purge_channel))
+        .route("/channels/lookup-policy", put(channels::set_lookup_policy))
+        .route("/channels/mute", post(channels::mute_channel))
+        .route("/channels/unmute", post(channels::unmute_channel))
         .route("/channels/messages", get(channels::channel_messages))
-        .route("/channels/status", get(channels::channel_status))
-        .route("/channels/inspect", get(channels::inspect_prompt))
+        .route("/channels/search", get(channels::search_messages))
+        .route("/channels/export", get(export::export_channel))
+        .route("/channels/{id}/share", post(shares::create_share))
+        .route("/channels/{id}/shares", get(shares::list_shares))
         .route(
-            "/channels/inspect/capture",
-            post(channels::set_prompt_capture),
+            "/channels/{id}/shares/{share_id}",
+            delete(shares::revoke_share),
         )
+        .route("/processes", get(processes::list_processes))
+        .route("/agents/workers", get(workers::list_workers))
+        .route("/agents/workers/detail", get(workers::worker_detail))
+        .route("/agents/attachments/{id}", get(attachments::get_attachment))
         .route(
-            "/channels/inspect/snapshots",
-            get(channels::list_prompt_snapshots),
+            "/agents/memories",
+            get(memories::list_memories).post(memories::create_memory),
         )
+        .route("/agents/memories/search", get(memories::search_memories))
         .route(
-            "/channels/inspect/snapshot",
-            get(channels::get_prompt_snapshot),
+            "/agents/memories/import",
+            post(memories::import_memories).layer(DefaultBodyLimit::max(MAX_IMPORT_BODY_BYTES)),
         )
-        .route("/agents/workers", get(workers::list_workers))
-        .route("/agents/workers/detail", get(workers::worker_detail))
         .route(
-            "/opencode/{port}/{*path}",
-            any(opencode_proxy::opencode_proxy),
+            "/agents/memories/backup",
+            get(memories::export_memory_backup)
+                .post(memories::restore_memory_backup)
+                .layer(DefaultBodyLimit::max(MAX_IMPORT_BODY_BYTES)),
+        )
+        .route("/agents/memories/trash", get(memories::list_memory_trash))
+        .route(
+            "/agents/memories/trash/restore",
+            post(memories::restore_memory),
         )
-        .route("/opencode/{port}", any(opencode_proxy::opencode_proxy))
-        .route("/opencode/{port}/", any(opencode_proxy::opencode_proxy))
-        .route("/agents/memories", get(memories::list_memories))
-        .route("/agents/memories/search", get(memories::search_memories))
         .route("/agents/memories/graph", get(memories::memory_graph))
+        .route("/agents/memories/link", post(memories::link_memories))
+        .route("/agents/memories/as-of", get(memories::memories_as_of))
+        .route(
+            "/agents/memories/consolidations",
+            get(memories::list_consolidations),
+        )
+        .route("/agents/memories/rescore", post(memories::rescore_memories))
+        .route(
+            "/agents/memories/{id}/history",
+            get(memories::memory_history),
+        )
+        .route("/agents/todos", get(memories::list_todos))
+        .route("/agents/entities/{name}", get(memories::entity_profile))
+        .route(
+            "/agents/memories/{id}",
+            put(memories::update_memory).delete(memories::delete_memory),
+        )
         .route(
             "/agents/memories/graph/neighbors",
             get(memories::memory_graph_neighbors),
@@ -123,9 +281,18 @@ pub async fn start_http_server(
         .route("/cortex-chat/threads", get(cortex::cortex_chat_threads))
         .route(
             "/cortex-chat/thread",
-            delete(cortex::cortex_chat_delete_thread),
+            delete(cortex::cortex_chat_delete_thread).put(cortex::cortex_chat_rename_thread),
         )
         .route("/cortex-chat/send", post(cortex::cortex_chat_send))
+        .route(
+            "/cortex-chat/regenerate",
+            post(cortex::cortex_chat_regenerate),
+        )
+        .route("/cortex-chat/edit", post(cortex::cortex_chat_edit))
+        .route(
+            "/uploads",
+            post(uploads::upload_files).layer(DefaultBodyLimit::max(MAX_UPLOAD_BODY_BYTES)),
+        )
         .route("/agents/profile", get(agents::get_agent_profile))
         .route(
             "/agents/avatar",
@@ -141,6 +308,10 @@ pub async fn start_http_server(
             "/agents/config",
             get(config::get_agent_config).put(config::update_agent_config),
         )
+        .route(
+            "/agents/config/validate",
+            post(config::validate_agent_config),
+        )
         .route(
             "/agents/cron",
             get(cron::list_cron_jobs)
@@ -190,7 +361,6 @@ pub async fn start_http_server(
             "/agents/projects/{id}/worktrees/{wt_id}",
             delete(projects::delete_worktree),
         )
-        .route("/channels/cancel", post(channels::cancel_process))
         .route(
             "/agents/ingest/files",
             get(ingest::list_ingest_files).delete(ingest::delete_ingest_file),
@@ -201,6 +371,108 @@ pub async fn start_http_server(
         .route("/agents/skills/install", post(skills::install_skill))
         .route("/agents/skills/upload", post(skills::upload_skill))
         .route("/agents/skills/remove", delete(skills::remove_skill))
+        .route("/webchat/send", post(webchat::webchat_send))
+        .route("/webchat/history", get(webchat::webchat_history))
+        .route("/agents/{id}/health", get(agents::agent_health))
+        .route("/agents/{id}/pause", post(agents::pause_agent))
+        .route("/agents/{id}/restart", post(agents::restart_agent))
+        .route("/agents/{id}/resume", post(agents::resume_agent))
+        .route(
+            "/agents/{id}/memories/backfill",
+            get(backfill::list_backfills).post(backfill::create_backfill),
+        )
+        .route(
+            "/agents/{id}/memories/backfill/{job_id}",
+            get(backfill::get_backfill),
+        )
+        .route(
+            "/agents/{id}/memories/backfill/{job_id}/cancel",
+            post(backfill::cancel_backfill),
+        )
+        .route(
+            "/agents/{id}/memories/backfill/{job_id}/resume",
+            post(backfill::resume_backfill),
+        )
+        .route("/batch", get(batch::list_batches).post(batch::create_batch))
+        .route("/batch/{id}", get(batch::get_batch))
+        .route("/batch/{id}/cancel", post(batch::cancel_batch))
+        .route("/faq", get(faq::list_faq).post(faq::create_faq))
+        .route("/faq/import", post(faq::import_faq))
+        .route("/faq/analytics", get(faq::faq_analytics))
+        .route(
+            "/faq/{id}",
+            get(faq::get_faq)
+                .put(faq::update_faq)
+                .delete(faq::delete_faq),
+        )
+        .route("/faq/{id}/approve", post(faq::approve_faq))
+        .route("/faq/{id}/reject", post(faq::reject_faq))
+        .route("/agents/{id}/workspace/log", get(workspace::workspace_log))
+        .route(
+            "/agents/{id}/workspace/revert",
+            post(workspace::workspace_revert),
+        );
+
+    // Routes spanning the instance, closed to agent-restricted tokens.
+    let instance_routes = Router::new()
+        .route("/health", get(system::health))
+        .route("/idle", get(system::idle))
+        .route("/status", get(system::status))
+        .route("/system/storage", get(system::storage_status))
+        .route("/system/backup/export", get(system::backup_export))
+        .route("/system/backup/restore", post(system::backup_restore))
+        .route("/overview", get(agents::instance_overview))
+        .route("/logs/stream", get(logs::log_stream))
+        .route(
+            "/webhooks",
+            get(webhooks::list_webhooks).post(webhooks::create_webhook),
+        )
+        .route("/webhooks/{id}", delete(webhooks::delete_webhook))
+        .route("/audit", get(audit::list_audit))
+        .route("/outbox/dead", get(outbox::list_dead))
+        .route("/outbox/{id}/retry", post(outbox::retry_dead))
+        .route(
+            "/agents",
+            get(agents::list_agents)
+                .post(agents::create_agent)
+                .put(agents::update_agent)
+                .delete(agents::delete_agent),
+        )
+        .route("/agents/{id}", delete(agents::delete_agent_by_id))
+        .route(
+            "/mcp/servers",
+            get(mcp::list_mcp_servers)
+                .post(mcp::create_mcp_server)
+                .put(mcp::update_mcp_server),
+        )
+        .route("/mcp/servers/{name}", delete(mcp::delete_mcp_server))
+        .route(
+            "/mcp/servers/{name}/reconnect",
+            post(mcp::reconnect_mcp_server),
+        )
+        .route("/mcp/status", get(mcp::mcp_status))
+        .route("/channels/status", get(channels::channel_status))
+        .route("/channels/inspect", get(channels::inspect_prompt))
+        .route(
+            "/channels/inspect/capture",
+            post(channels::set_prompt_capture),
+        )
+        .route(
+            "/channels/inspect/snapshots",
+            get(channels::list_prompt_snapshots),
+        )
+        .route(
+            "/channels/inspect/snapshot",
+            get(channels::get_prompt_snapshot),
+        )
+        .route("/processes/{id}/cancel", post(processes::cancel_process))
+        .route(
+            "/opencode/{port}/{*path}",
+            any(opencode_proxy::opencode_proxy),
+        )
+        .route("/opencode/{port}", any(opencode_proxy::opencode_proxy))
+        .route("/opencode/{port}/", any(opencode_proxy::opencode_proxy))
+        .route("/channels/cancel", post(channels::cancel_process))
         .route("/agents/tools", get(tools::list_tools))
         // Secret store management
         .route("/secrets/status", get(secrets::secrets_status))
@@ -264,6 +536,8 @@ pub async fn start_http_server(
             "/config/raw",
             get(settings::get_raw_config).put(settings::update_raw_config),
         )
+        .route("/config/history", get(settings::config_history))
+        .route("/config/rollback", post(settings::config_rollback))
         .route(
             "/update/check",
             get(settings::update_check).post(settings::update_check_now),
@@ -272,8 +546,6 @@ pub async fn start_http_server(
         .route("/changelog", get(settings::changelog))
         .route("/ssh/authorized-key", put(ssh::set_authorized_key))
         .route("/ssh/status", get(ssh::ssh_status))
-        .route("/webchat/send", post(webchat::webchat_send))
-        .route("/webchat/history", get(webchat::webchat_history))
         .route("/links", get(links::list_links).post(links::create_link))
         .route(
             "/links/{from}/{to}",
@@ -294,7 +566,26 @@ pub async fn start_http_server(
         // Factory: preset archetypes
         .route("/factory/presets", get(factory::list_presets))
         .route("/factory/presets/{id}", get(factory::get_preset))
-        .layer(DefaultBodyLimit::max(10 * 1024 * 1024))
+        .route_layer(middleware::from_fn(access::deny_agent_restricted));
+
+    let api_routes = agent_routes
+        .merge(instance_routes)
+        .layer(DefaultBodyLimit::max(MAX_BODY_BYTES))
+        // Runs inside idempotency so a replayed response isn't logged twice.
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            audit::audit_middleware,
+        ))
+        // Runs inside auth so replays are scoped to the caller's token.
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            idempotency::idempotency_middleware,
+        ))
+        // Runs inside auth so authenticated clients are limited per token.
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            rate_limit::rate_limit_middleware,
+        ))
         .layer(middleware::from_fn_with_state(
             state.clone(),
             api_auth_middleware,
@@ -303,61 +594,336 @@ pub async fn start_http_server(
     #[cfg(feature = "metrics")]
     let api_routes = api_routes.layer(middleware::from_fn(metrics_middleware));
 
-    let app = Router::new()
+    // OpenAI clients expect `/v1` at the root, so it sits outside `/api` but
+    // behind the same auth and rate limits.
+    let openai_routes = Router::new()
+        .route(
+            openai::CHAT_COMPLETIONS_PATH,
+            post(openai::chat_completions),
+        )
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            rate_limit::rate_limit_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            api_auth_middleware,
+        ));
+
+    // Widget visitors are anonymous, so their routes skip API auth and the
+    // API's limits in favour of origin checks and their own limits.
+    let widget_routes = Router::new()
+        .route("/api/widget/session", post(widget::create_session))
+        .route(
+            "/api/widget/messages",
+            get(widget::history).post(widget::send_message),
+        )
+        .route("/api/widget/events", get(widget::events))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            widget::widget_middleware,
+        ));
+
+    Router::new()
         .nest("/api", api_routes)
+        .merge(openai_routes)
+        .merge(widget_routes)
+        // Orchestrator probes live at the root, outside API auth.
+        .route("/healthz", get(system::healthz))
+        .route("/readyz", get(system::readyz))
+        .merge(openapi::routes())
+        // Public, unauthenticated transcript share pages.
+        .route("/s/{token}", get(shares::view_share))
         .fallback(static_handler)
+        // JSON responses and small assets are compressed on the fly. Assets
+        // with a precompressed variant already carry `Content-Encoding`, so
+        // they pass through untouched, as does everything `should_compress`
+        // rules out.
+        .layer(CompressionLayer::new().compress_when(should_compress()))
         .layer(cors)
-        .layer(DefaultBodyLimit::max(10 * 1024 * 1024)) // 10 MiB
-        .with_state(state);
+        .layer(DefaultBodyLimit::max(MAX_BODY_BYTES))
+        .with_state(state)
+}
 
-    let listener = tokio::net::TcpListener::bind(bind).await?;
-    tracing::info!(%bind, "HTTP server listening");
+/// Which responses the compression layer may encode. On top of the default
+/// exclusions (event streams, raster images, tiny bodies), media and archives
+/// are already compressed, NDJSON exports are streamed, and compressing a
+/// response that advertises `Accept-Ranges` would drop the header and stop
+/// clients seeking in it.
+fn should_compress() -> impl Predicate {
+    DefaultPredicate::new()
+        .and(NotForContentType::const_new("audio/"))
+        .and(NotForContentType::const_new("video/"))
+        .and(NotForContentType::const_new("application/pdf"))
+        .and(NotForContentType::const_new("application/zip"))
+        .and(NotForContentType::const_new("application/gzip"))
+        .and(NotForContentType::const_new("application/x-ndjson"))
+        .and(
+            |_: StatusCode,
+             _: axum::http::Version,
+             headers: &axum::http::HeaderMap,
+             _: &axum::http::Extensions| {
+                !headers.contains_key(header::ACCEPT_RANGES)
+            },
+        )
+}
 
-    let handle = tokio::spawn(async move {
-        let mut shutdown = shutdown_rx;
-        if let Err(error) = axum::serve(listener, app)
-            .with_graceful_shutdown(async move {
-                let _ = shutdown.wait_for(|v| *v).await;
-            })
-            .await
-        {
-            tracing::error!(%error, "HTTP server exited with error");
+/// Bind a unix socket at `path`, replacing a stale socket left by an unclean
+/// exit, and restrict it to `mode`.
+fn bind_unix_socket(path: &Path, mode: u32) -> anyhow::Result<tokio::net::UnixListener> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    match std::fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_socket() => {
+            std::fs::remove_file(path)
+                .with_context(|| format!("failed to remove stale socket {}", path.display()))?;
         }
-    });
+        Ok(_) => anyhow::bail!("{} exists and is not a socket", path.display()),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => {}
+        Err(error) => {
+            return Err(error).with_context(|| format!("failed to inspect {}", path.display()));
+        }
+    }
 
-    Ok(handle)
+    // Bind inside a 0700 directory and move the socket into place only once
+    // `mode` is applied, so it is never reachable with umask permissions.
+    let parent = path.parent().unwrap_or(Path::new("."));
+    let staging = tempfile::Builder::new()
+        .prefix(".spacebot-socket")
+        .tempdir_in(parent)
+        .with_context(|| {
+            format!(
+                "failed to create a staging directory in {}",
+                parent.display()
+            )
+        })?;
+    let staged_path = staging.path().join("api.sock");
+    let listener = tokio::net::UnixListener::bind(&staged_path)
+        .with_context(|| format!("failed to bind unix socket {}", path.display()))?;
+    std::fs::set_permissions(&staged_path, std::fs::Permissions::from_mode(mode))
+        .with_context(|| format!("failed to set permissions on {}", path.display()))?;
+    std::fs::rename(&staged_path, path).with_context(|| {
+        format!(
+            "failed to move unix socket into place at {}",
+            path.display()
+        )
+    })?;
+    Ok(listener)
+}
+
+/// Identity of an authenticated API caller, inserted into request extensions
+/// by [`api_auth_middleware`].
+#[derive(Debug, Clone)]
+pub(super) struct ApiPrincipal {
+    pub name: String,
+    pub scope: ApiTokenScope,
+    /// Agent IDs the token is restricted to. Empty means every agent.
+    pub agents: Vec<String>,
+}
+
+impl ApiPrincipal {
+    pub fn allows_agent(&self, agent_id: &str) -> bool {
+        self.agents.is_empty() || self.agents.iter().any(|agent| agent == agent_id)
+    }
+}
+
+/// Largest request body accepted by the API.
+pub(super) const MAX_BODY_BYTES: usize = 10 * 1024 * 1024;
+
+/// Request body limit for bulk memory imports.
+const MAX_IMPORT_BODY_BYTES: usize = 100 * 1024 * 1024;
+
+/// Request body limit for `POST /uploads`.
+const MAX_UPLOAD_BODY_BYTES: usize = 50 * 1024 * 1024;
+
+/// GET routes that expose enough to require a read-write token anyway.
+const READ_WRITE_ONLY_PREFIXES: &[&str] = &["/system/backup/export", "/opencode/", "/logs/"];
+
+/// POST routes that only read. The GraphQL schema has no mutations.
+pub(super) const READ_ONLY_POST_ROUTES: &[&str] = &["/graphql"];
+
+/// POST routes that send a message to an agent, or a file to attach to one.
+/// Chat-scoped tokens may call these.
+const CHAT_ROUTES: &[&str] = &[
+    "/webchat/send",
+    "/cortex-chat/send",
+    "/cortex-chat/regenerate",
+    "/cortex-chat/edit",
+    "/uploads",
+    openai::CHAT_COMPLETIONS_PATH,
+];
+
+fn is_deep_health_check(query: Option<&str>) -> bool {
+    query.is_some_and(|query| query.split('&').any(|pair| pair == "deep=true"))
 }
 
 async fn api_auth_middleware(
     State(state): State<Arc<ApiState>>,
-    request: Request,
+    mut request: Request,
     next: Next,
 ) -> Response {
-    let Some(expected_token) = state.auth_token.as_deref() else {
+    if state.auth_token.is_none() && state.api_tokens.is_empty() {
         return next.run(request).await;
-    };
+    }
 
+    // The shallow health check stays open for load balancers; `?deep=true`
+    // touches providers and reveals agent IDs, so it needs a token.
     let path = request.uri().path();
-    if path == "/api/health" || path == "/health" {
+    if (path == "/api/health" || path == "/health") && !is_deep_health_check(request.uri().query())
+    {
         return next.run(request).await;
     }
 
-    let is_authorized = request
+    let presented = request
         .headers()
         .get(header::AUTHORIZATION)
         .and_then(|value| value.to_str().ok())
-        .and_then(|value| value.strip_prefix("Bearer "))
-        .is_some_and(|token| token == expected_token);
-
-    if is_authorized {
-        next.run(request).await
-    } else {
-        (
+        .and_then(|value| value.strip_prefix("Bearer "));
+    let Some(principal) = presented.and_then(|token| authenticate_token(&state, token)) else {
+        return (
             StatusCode::UNAUTHORIZED,
             Json(json!({"error": "unauthorized"})),
         )
-            .into_response()
+            .into_response();
+    };
+
+    let required = required_scope(request.method(), path);
+    if principal.scope < required {
+        tracing::debug!(
+            token = %principal.name,
+            method = %request.method(),
+            path,
+            "API request rejected: token scope is too narrow"
+        );
+        return (
+            StatusCode::FORBIDDEN,
+            Json(json!({
+                "error": "forbidden",
+                "message": format!("this endpoint requires a {} token", required.as_str()),
+            })),
+        )
+            .into_response();
+    }
+
+    request.extensions_mut().insert(principal);
+    next.run(request).await
+}
+
+/// Buffer a JSON request body so middleware can inspect it, handing back an
+/// equivalent request. Other content types pass through untouched.
+pub(super) async fn buffer_json_body(
+    request: Request,
+) -> Result<(Request, Option<serde_json::Value>), Response> {
+    let is_json = request
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"));
+    if !is_json {
+        return Ok((request, None));
+    }
+    let (parts, body) = request.into_parts();
+    let bytes = axum::body::to_bytes(body, MAX_BODY_BYTES)
+        .await
+        .map_err(|_| StatusCode::PAYLOAD_TOO_LARGE.into_response())?;
+    let value = serde_json::from_slice(&bytes).ok();
+    Ok((
+        Request::from_parts(parts, axum::body::Body::from(bytes)),
+        value,
+    ))
+}
+
+/// The agent a request targets, for the audit log: an `agent_id` query
+/// parameter or body field, or a configured agent ID in an `/agents/{id}/...`
+/// path. A best guess only; access checks use the extractors in `access`.
+pub(super) fn request_agent_id(
+    state: &ApiState,
+    relative_path: &str,
+    query: Option<&str>,
+    body: Option<&serde_json::Value>,
+) -> Option<String> {
+    let from_query = query.and_then(|query| {
+        query
+            .split('&')
+            .find_map(|pair| pair.strip_prefix("agent_id="))
+            .and_then(|value| urlencoding::decode(value).ok())
+            .map(|value| value.into_owned())
+    });
+    // OpenAI-compatible requests name the agent as the model.
+    let body_field = if relative_path == openai::CHAT_COMPLETIONS_PATH {
+        "model"
+    } else {
+        "agent_id"
+    };
+    let from_body = || {
+        body.and_then(|body| body.get(body_field))
+            .and_then(|value| value.as_str())
+            .map(str::to_string)
+    };
+    let from_path = || {
+        let segment = relative_path.strip_prefix("/agents/")?.split('/').next()?;
+        state
+            .agent_configs
+            .load()
+            .iter()
+            .any(|agent| agent.id == segment)
+            .then(|| segment.to_string())
+    };
+    from_query.or_else(from_body).or_else(from_path)
+}
+
+/// Match a presented bearer token against the configured tokens. The legacy
+/// `api.auth_token` grants read-write access.
+fn authenticate_token(state: &ApiState, presented: &str) -> Option<ApiPrincipal> {
+    if let Some(expected) = state.auth_token.as_deref()
+        && constant_time_eq(presented.as_bytes(), expected.as_bytes())
+    {
+        return Some(ApiPrincipal {
+            name: "auth_token".into(),
+            scope: ApiTokenScope::ReadWrite,
+            agents: Vec::new(),
+        });
+    }
+    state
+        .api_tokens
+        .iter()
+        .find(|token| constant_time_eq(presented.as_bytes(), token.token.as_bytes()))
+        .map(|token| ApiPrincipal {
+            name: token.name.clone(),
+            scope: token.scope,
+            agents: token.agents.clone(),
+        })
+}
+
+/// Scope needed for a request. Safe methods only need read access, except
+/// for a few GET routes that leak secrets or drive side effects. Sending
+/// chat messages needs chat access; everything else needs read-write.
+fn required_scope(method: &Method, path: &str) -> ApiTokenScope {
+    let relative_path = path.strip_prefix("/api").unwrap_or(path);
+    let is_safe_method = matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+        || (*method == Method::POST && READ_ONLY_POST_ROUTES.contains(&relative_path));
+    let is_read_write_only = READ_WRITE_ONLY_PREFIXES
+        .iter()
+        .any(|prefix| relative_path.starts_with(prefix));
+    if is_safe_method && !is_read_write_only {
+        ApiTokenScope::Read
+    } else if *method == Method::POST && CHAT_ROUTES.contains(&relative_path) {
+        ApiTokenScope::Chat
+    } else {
+        ApiTokenScope::ReadWrite
+    }
+}
+
+fn constant_time_eq(left: &[u8], right: &[u8]) -> bool {
+    if left.len() != right.len() {
+        return false;
     }
+    left.iter()
+        .zip(right)
+        .fold(0u8, |difference, (a, b)| difference | (a ^ b))
+        == 0
 }
 
 #[cfg(feature = "metrics")]
@@ -408,6 +974,15 @@ fn normalize_api_path(path: &str) -> String {
             let parent = parts.get(i - 1).copied().unwrap_or("");
             match parent {
                 "secrets" | "groups" | "humans" | "links" => normalized.push("{name}"),
+                "attachments" | "shares" => normalized.push("{id}"),
+                "channels"
+                    if !matches!(
+                        *part,
+                        "archive" | "messages" | "status" | "inspect" | "cancel"
+                    ) =>
+                {
+                    normalized.push("{id}")
+                }
                 "servers" | "providers" => normalized.push("{name}"),
                 "opencode" => normalized.push("{port}"),
                 "agents"
@@ -427,6 +1002,7 @@ fn normalize_api_path(path: &str) -> String {
                             | "skills"
                             | "tools"
                             | "links"
+                            | "attachments"
                     ) =>
                 {
                     normalized.push("{id}")
@@ -450,22 +1026,727 @@ fn normalize_api_path(path: &str) -> String {
     normalized.join("/")
 }
 
-async fn static_handler(uri: Uri) -> Response {
-    let path = uri.path().trim_start_matches('/');
+async fn static_handler(State(state): State<Arc<ApiState>>, request: Request) -> Response {
+    if let Some(dev_proxy_url) = state.dev_proxy_url.as_deref() {
+        return dev_proxy::proxy_to_dev_server(dev_proxy_url, request).await;
+    }
 
-    if let Some(content) = InterfaceAssets::get(path) {
-        let mime = mime_guess::from_path(path).first_or_octet_stream();
-        return (
-            StatusCode::OK,
-            [(header::CONTENT_TYPE, mime.as_ref())],
-            content.data,
+    let path = request.uri().path().trim_start_matches('/');
+    let headers = request.headers();
+
+    if !path.is_empty()
+        && let Some(response) = serve_embedded_asset(path, headers)
+    {
+        return response;
+    }
+
+    // A missing file under `assets/` is a chunk from an older build, not a
+    // client route. Answering with index.html would hand the browser HTML
+    // where it expects a script. Everything else may be an SPA route, some of
+    // which end in IDs that look like hashes.
+    if path.starts_with("assets/") {
+        return (StatusCode::NOT_FOUND, "not found").into_response();
+    }
+
+    // SPA fallback: unknown paths are client-side routes.
+    serve_embedded_asset("index.html", headers)
+        .unwrap_or_else(|| (StatusCode::NOT_FOUND, "not found").into_response())
+}
+
+/// Serve an embedded asset with ETag revalidation and, when the client
+/// accepts it, a precompressed variant produced by `build.rs`.
+fn serve_embedded_asset(path: &str, request_headers: &axum::http::HeaderMap) -> Option<Response> {
+    let content = InterfaceAssets::get(path)?;
+    let mime = mime_guess::from_path(path).first_or_octet_stream();
+
+    let accepted = request_headers
+        .get(header::ACCEPT_ENCODING)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("");
+    let (encoding, data, hash) = [("br", "br"), ("gzip", "gz")]
+        .into_iter()
+        .filter(|(encoding, _)| accepts_encoding(accepted, encoding))
+        .find_map(|(encoding, suffix)| {
+            PrecompressedAssets::get(&format!("{path}.{suffix}")).map(|variant| {
+                let hash = variant.metadata.sha256_hash();
+                (Some(encoding), variant.data, hash)
+            })
+        })
+        .unwrap_or_else(|| (None, content.data, content.metadata.sha256_hash()));
+
+    // Each encoding is a distinct representation, so it gets its own tag.
+    let etag = format!("\"{}\"", hex::encode(&hash[..16]));
+    let cache_control = if is_hashed_asset(path) {
+        "public, max-age=31536000, immutable"
+    } else {
+        "no-cache"
+    };
+
+    let not_modified = request_headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| etag_matches(value, &etag));
+
+    let mut response = if not_modified {
+        StatusCode::NOT_MODIFIED.into_response()
+    } else {
+        (StatusCode::OK, data).into_response()
+    };
+
+    let response_headers = response.headers_mut();
+    if let Ok(value) = mime.as_ref().parse() {
+        response_headers.insert(header::CONTENT_TYPE, value);
+    }
+    if let Ok(value) = etag.parse() {
+        response_headers.insert(header::ETAG, value);
+    }
+    response_headers.insert(
+        header::CACHE_CONTROL,
+        axum::http::HeaderValue::from_static(cache_control),
+    );
+    response_headers.insert(
+        header::VARY,
+        axum::http::HeaderValue::from_static("accept-encoding"),
+    );
+    if let Some(encoding) = encoding {
+        response_headers.insert(
+            header::CONTENT_ENCODING,
+            axum::http::HeaderValue::from_static(encoding),
+        );
+    }
+
+    Some(response)
+}
+
+/// Whether an `If-None-Match` header value matches `etag`. Comparison is
+/// weak, as RFC 9110 requires for `If-None-Match`, so a `W/` prefix added by
+/// a proxy still revalidates.
+fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    if_none_match.split(',').any(|candidate| {
+        let candidate = candidate.trim();
+        candidate == "*" || candidate.strip_prefix("W/").unwrap_or(candidate) == etag
+    })
+}
+
+/// Whether an `Accept-Encoding` header value allows `encoding`. Explicit
+/// `q=0` opt-outs are honored.
+fn accepts_encoding(accept_encoding: &str, encoding: &str) -> bool {
+    accept_encoding.split(',').any(|entry| {
+        let mut parts = entry.split(';');
+        let name = parts.next().unwrap_or("").trim();
+        let rejected = parts.any(|parameter| {
+            parameter
+                .trim()
+                .strip_prefix("q=")
+                .and_then(|quality| quality.trim().parse::<f32>().ok())
+                .is_some_and(|quality| quality <= 0.0)
+        });
+        name.eq_ignore_ascii_case(encoding) && !rejected
+    })
+}
+
+/// Vite emits build output as `name-<hash>.ext` under `assets/`. Those files
+/// never change content under the same name, so they can be cached forever.
+/// Files copied from `public/` keep their names and must revalidate.
+fn is_hashed_asset(path: &str) -> bool {
+    path.starts_with("assets/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        accepts_encoding, bind_unix_socket, build_router, constant_time_eq, etag_matches,
+        is_hashed_asset, required_scope,
+    };
+
+    use crate::api::RateLimiter;
+    use crate::api::state::{AgentInfo, ApiState};
+    use crate::config::{ApiRateLimitConfig, ApiToken, ApiTokenScope, ApiWidgetConfig, Config};
+
+    use axum::Router;
+    use axum::body::Body;
+    use axum::http::{Method, Request, StatusCode, header};
+    use tower::ServiceExt as _;
+
+    use std::collections::HashMap;
+    use std::path::Path;
+    use std::sync::Arc;
+
+    /// API state with `api_tokens`, and agents `alice` and `bob` configured.
+    fn test_state(api_tokens: Vec<ApiToken>) -> ApiState {
+        let (provider_setup_tx, _) = tokio::sync::mpsc::channel(1);
+        let (agent_tx, _) = tokio::sync::mpsc::channel(1);
+        let (agent_remove_tx, _) = tokio::sync::mpsc::channel(1);
+        let (injection_tx, _) = tokio::sync::mpsc::channel(1);
+        let mut state = ApiState::new_with_provider_sender(
+            provider_setup_tx,
+            agent_tx,
+            agent_remove_tx,
+            injection_tx,
+            Arc::new(arc_swap::ArcSwap::from_pointee(Default::default())),
+        );
+        state.api_tokens = api_tokens;
+        let agent = |id: &str| AgentInfo {
+            id: id.into(),
+            display_name: None,
+            role: None,
+            gradient_start: None,
+            gradient_end: None,
+            workspace: Default::default(),
+            context_window: 0,
+            max_turns: 0,
+            max_concurrent_branches: 0,
+            max_concurrent_workers: 0,
+        };
+        state
+            .agent_configs
+            .store(Arc::new(vec![agent("alice"), agent("bob")]));
+        state
+    }
+
+    fn router_with_tokens(api_tokens: Vec<ApiToken>) -> Router {
+        build_router(Arc::new(test_state(api_tokens)))
+    }
+
+    const SHOP_ORIGIN: &str = "https://shop.example.com";
+    const BLOG_ORIGIN: &str = "https://blog.example.com";
+
+    /// The router with the widget serving `alice` to the shop and blog
+    /// origins, backed by a migrated in-memory agent database.
+    async fn widget_router(messages_per_minute: u32) -> Router {
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("failed to create sqlite memory pool");
+        sqlx::migrate!("./migrations")
+            .run(&pool)
+            .await
+            .expect("migrations");
+
+        let mut state = test_state(Vec::new());
+        let widget = ApiWidgetConfig {
+            enabled: true,
+            agent_id: "alice".into(),
+            allowed_origins: vec![SHOP_ORIGIN.into(), BLOG_ORIGIN.into()],
+            messages_per_minute,
+            ..Default::default()
+        };
+        state.widget_rate_limiter = RateLimiter::new(ApiRateLimitConfig {
+            requests_per_minute: widget.requests_per_minute,
+            expensive_requests_per_minute: widget.messages_per_minute,
+        })
+        .map(Arc::new);
+        state.widget = Some(widget);
+        state
+            .agent_pools
+            .store(Arc::new(HashMap::from([("alice".to_string(), pool)])));
+        build_router(Arc::new(state))
+    }
+
+    async fn widget_request(
+        router: Router,
+        method: Method,
+        uri: &str,
+        origin: Option<&str>,
+        token: Option<&str>,
+    ) -> (StatusCode, serde_json::Value) {
+        let mut request = Request::builder().method(method).uri(uri);
+        if let Some(origin) = origin {
+            request = request.header(header::ORIGIN, origin);
+        }
+        if let Some(token) = token {
+            request = request.header(header::AUTHORIZATION, format!("Bearer {token}"));
+        }
+        let response = router
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap_or_default())
+    }
+
+    async fn send(
+        router: Router,
+        token: &str,
+        method: Method,
+        uri: &str,
+        content_type: &str,
+        body: &str,
+    ) -> (StatusCode, serde_json::Value) {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header(header::AUTHORIZATION, format!("Bearer {token}"))
+            .header(header::CONTENT_TYPE, content_type)
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        let response = router.oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap_or_default())
+    }
+
+    /// A request with `tenant-token`, restricted to agent `alice`.
+    async fn tenant_request(
+        method: Method,
+        uri: &str,
+        content_type: &str,
+        body: &str,
+    ) -> (StatusCode, serde_json::Value) {
+        let router = router_with_tokens(vec![ApiToken {
+            name: "tenant".into(),
+            token: "tenant-token".into(),
+            scope: ApiTokenScope::ReadWrite,
+            agents: vec!["alice".into()],
+        }]);
+        send(router, "tenant-token", method, uri, content_type, body).await
+    }
+
+    /// Attachments are range-capable streams: compressing them would cost
+    /// CPU on already-compressed media and drop `Accept-Ranges`.
+    #[tokio::test]
+    async fn attachments_are_never_compressed() {
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("failed to create sqlite memory pool");
+        sqlx::migrate!("./migrations")
+            .run(&pool)
+            .await
+            .expect("migrations");
+        sqlx::query(
+            "INSERT INTO channels (id, platform, is_active, created_at, last_activity_at) \
+             VALUES ('portal:1', 'portal', 1, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP)",
         )
-            .into_response();
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let workspace = tempfile::tempdir().unwrap();
+        for (id, mime_type) in [("song", "audio/mpeg"), ("notes", "text/plain")] {
+            let path = workspace.path().join(id);
+            std::fs::write(&path, "compressible ".repeat(4096)).unwrap();
+            sqlx::query(
+                "INSERT INTO saved_attachments \
+                 (id, channel_id, original_filename, saved_filename, mime_type, size_bytes, disk_path) \
+                 VALUES (?, 'portal:1', ?, ?, ?, 0, ?)",
+            )
+            .bind(id)
+            .bind(id)
+            .bind(id)
+            .bind(mime_type)
+            .bind(path.to_string_lossy().into_owned())
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let state = test_state(Vec::new());
+        state
+            .agent_pools
+            .store(Arc::new(HashMap::from([("alice".to_string(), pool)])));
+        state.set_agent_workspaces(HashMap::from([(
+            "alice".to_string(),
+            workspace.path().to_path_buf(),
+        )]));
+        let router = build_router(Arc::new(state));
+
+        for id in ["song", "notes"] {
+            let request = Request::builder()
+                .uri(format!("/api/agents/attachments/{id}?agent_id=alice"))
+                .header(header::ACCEPT_ENCODING, "gzip")
+                .body(Body::empty())
+                .unwrap();
+            let response = router.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            assert!(response.headers().get(header::CONTENT_ENCODING).is_none());
+            assert_eq!(response.headers()[header::ACCEPT_RANGES], "bytes");
+        }
+    }
+
+    #[test]
+    fn hashed_asset_detection() {
+        assert!(is_hashed_asset("assets/index-BdU3f2x_.js"));
+        assert!(is_hashed_asset("assets/vendor-react-C8kqL2mZ.css"));
+        assert!(!is_hashed_asset("index.html"));
+        assert!(!is_hashed_asset("favicon.ico"));
+        assert!(!is_hashed_asset("fonts/ibm-plex-sans.woff2"));
+        assert!(!is_hashed_asset("locales/foo-translations.json"));
+        assert!(!is_hashed_asset("agents/research-assistant"));
     }
 
-    if let Some(content) = InterfaceAssets::get("index.html") {
-        return Html(std::str::from_utf8(&content.data).unwrap_or("").to_string()).into_response();
+    #[test]
+    fn if_none_match_uses_weak_comparison() {
+        let etag = "\"0123abcd\"";
+        assert!(etag_matches("\"0123abcd\"", etag));
+        assert!(etag_matches("\"ffff\", W/\"0123abcd\"", etag));
+        assert!(etag_matches("*", etag));
+        assert!(!etag_matches("\"ffff\"", etag));
+        assert!(!etag_matches("0123abcd", etag));
     }
 
-    (StatusCode::NOT_FOUND, "not found").into_response()
+    #[test]
+    fn accept_encoding_parsing() {
+        assert!(accepts_encoding("gzip, deflate, br", "br"));
+        assert!(accepts_encoding("gzip;q=0.8, br;q=1.0", "gzip"));
+        assert!(!accepts_encoding("gzip;q=0, br", "gzip"));
+        assert!(!accepts_encoding("identity", "br"));
+        assert!(!accepts_encoding("", "gzip"));
+    }
+
+    #[test]
+    fn read_tokens_only_cover_safe_methods() {
+        assert_eq!(
+            required_scope(&Method::GET, "/api/agents"),
+            ApiTokenScope::Read
+        );
+        assert_eq!(
+            required_scope(&Method::HEAD, "/status"),
+            ApiTokenScope::Read
+        );
+        assert_eq!(
+            required_scope(&Method::PUT, "/api/agents/identity"),
+            ApiTokenScope::ReadWrite
+        );
+        assert_eq!(
+            required_scope(&Method::DELETE, "/channels"),
+            ApiTokenScope::ReadWrite
+        );
+        assert_eq!(
+            required_scope(&Method::GET, "/api/system/backup/export"),
+            ApiTokenScope::ReadWrite
+        );
+        assert_eq!(
+            required_scope(&Method::GET, "/opencode/4096/session"),
+            ApiTokenScope::ReadWrite
+        );
+        assert_eq!(
+            required_scope(&Method::GET, "/api/logs/stream"),
+            ApiTokenScope::ReadWrite
+        );
+        assert_eq!(
+            required_scope(&Method::POST, "/api/graphql"),
+            ApiTokenScope::Read
+        );
+        assert_eq!(
+            required_scope(&Method::POST, "/api/webchat/send"),
+            ApiTokenScope::Chat
+        );
+        assert_eq!(
+            required_scope(&Method::POST, "/api/uploads"),
+            ApiTokenScope::Chat
+        );
+        assert!(ApiTokenScope::Chat < ApiTokenScope::ReadWrite);
+    }
+
+    #[tokio::test]
+    async fn tenant_cannot_name_another_agent() {
+        let (status, body) =
+            tenant_request(Method::GET, "/api/agents/memories?agent_id=bob", "", "").await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["error"], "forbidden");
+
+        for uri in [
+            "/api/events?agent_id=alice,bob",
+            "/api/events/history?agent_id=bob",
+        ] {
+            let (status, _) = tenant_request(Method::GET, uri, "", "").await;
+            assert_eq!(status, StatusCode::FORBIDDEN, "{uri}");
+        }
+    }
+
+    #[tokio::test]
+    async fn tenant_query_does_not_vouch_for_the_body() {
+        for content_type in ["application/json", "application/vnd.api+json"] {
+            let (status, _) = tenant_request(
+                Method::POST,
+                "/api/agents/memories?agent_id=alice",
+                content_type,
+                r#"{"agent_id":"bob","content":"x","memory_type":"fact"}"#,
+            )
+            .await;
+            assert_eq!(status, StatusCode::FORBIDDEN, "{content_type}");
+        }
+    }
+
+    #[tokio::test]
+    async fn tenant_query_does_not_vouch_for_the_path() {
+        let (status, _) =
+            tenant_request(Method::GET, "/api/agents/bob/health?agent_id=alice", "", "").await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn tenant_is_refused_instance_routes() {
+        for (method, uri) in [
+            (Method::GET, "/api/system/backup/export?agent_id=alice"),
+            (Method::GET, "/api/logs/stream?agent_id=alice"),
+            (Method::GET, "/api/config/raw?agent_id=alice"),
+            (Method::GET, "/api/webhooks?agent_id=alice"),
+            (Method::GET, "/api/status"),
+            (Method::DELETE, "/api/agents/alice"),
+        ] {
+            let (status, _) = tenant_request(method, uri, "", "").await;
+            assert_eq!(status, StatusCode::FORBIDDEN, "{uri}");
+        }
+    }
+
+    #[tokio::test]
+    async fn tenant_graphql_only_sees_its_agents() {
+        let (status, body) = tenant_request(
+            Method::POST,
+            "/api/graphql",
+            "application/json",
+            r#"{"query":"{ agents { id } bob: agent(id: \"bob\") { id } }"}"#,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["agents"], serde_json::json!([{"id": "alice"}]));
+        assert!(body["data"]["bob"].is_null());
+    }
+
+    #[tokio::test]
+    async fn agent_api_tokens_only_reach_their_agent() {
+        let config = Config::load_from_str(
+            r#"
+[[agents]]
+id = "alice"
+
+[[agents.api_tokens]]
+token = "alice-token"
+scope = "read_write"
+"#,
+            Path::new("."),
+        )
+        .unwrap();
+        let router = router_with_tokens(config.api.tokens);
+        for (method, uri, body) in [
+            (Method::GET, "/api/agents/memories?agent_id=bob", ""),
+            (
+                Method::POST,
+                "/api/agents/memories?agent_id=alice",
+                r#"{"agent_id":"bob","content":"x"}"#,
+            ),
+            (Method::GET, "/api/agents/bob/health?agent_id=alice", ""),
+            (Method::GET, "/api/config/raw", ""),
+            (Method::GET, "/api/system/backup/export?agent_id=alice", ""),
+        ] {
+            let (status, _) = send(
+                router.clone(),
+                "alice-token",
+                method,
+                uri,
+                "application/json",
+                body,
+            )
+            .await;
+            assert_eq!(status, StatusCode::FORBIDDEN, "{uri}");
+        }
+    }
+
+    #[tokio::test]
+    async fn widget_refuses_unlisted_origins() {
+        let router = widget_router(10).await;
+        for origin in [None, Some("https://evil.example.com")] {
+            let (status, body) = widget_request(
+                router.clone(),
+                Method::POST,
+                "/api/widget/session",
+                origin,
+                None,
+            )
+            .await;
+            assert_eq!(status, StatusCode::FORBIDDEN, "{origin:?}");
+            assert_eq!(body["error"], "forbidden");
+        }
+
+        let (status, _) = widget_request(
+            router_with_tokens(Vec::new()),
+            Method::POST,
+            "/api/widget/session",
+            Some(SHOP_ORIGIN),
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn widget_sessions_only_work_from_their_origin() {
+        let router = widget_router(10).await;
+        let (status, body) = widget_request(
+            router.clone(),
+            Method::POST,
+            "/api/widget/session",
+            Some(SHOP_ORIGIN),
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let token = body["token"].as_str().unwrap().to_string();
+
+        let (status, body) = widget_request(
+            router.clone(),
+            Method::GET,
+            "/api/widget/messages",
+            Some(SHOP_ORIGIN),
+            Some(&token),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, serde_json::json!([]));
+
+        let (status, _) = widget_request(
+            router.clone(),
+            Method::GET,
+            &format!("/api/widget/messages?token={token}"),
+            Some(SHOP_ORIGIN),
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+
+        for (origin, token) in [
+            (BLOG_ORIGIN, Some(token.as_str())),
+            (SHOP_ORIGIN, Some("not-a-session")),
+            (SHOP_ORIGIN, None),
+        ] {
+            let (status, body) = widget_request(
+                router.clone(),
+                Method::GET,
+                "/api/widget/messages",
+                Some(origin),
+                token,
+            )
+            .await;
+            assert_eq!(status, StatusCode::UNAUTHORIZED, "{origin} {token:?}");
+            assert_eq!(body["error"], "invalid_session");
+        }
+    }
+
+    #[tokio::test]
+    async fn widget_session_issuance_is_rate_limited() {
+        let router = widget_router(2).await;
+        for _ in 0..2 {
+            let (status, _) = widget_request(
+                router.clone(),
+                Method::POST,
+                "/api/widget/session",
+                Some(SHOP_ORIGIN),
+                None,
+            )
+            .await;
+            assert_eq!(status, StatusCode::OK);
+        }
+        let (status, _) = widget_request(
+            router,
+            Method::POST,
+            "/api/widget/session",
+            Some(SHOP_ORIGIN),
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    /// Share links resolve publicly while live and answer 410 once expired
+    /// or revoked.
+    #[tokio::test]
+    async fn dead_share_links_are_gone() {
+        use crate::conversation::shares::ShareStore;
+
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("failed to create sqlite memory pool");
+        sqlx::migrate!("./migrations")
+            .run(&pool)
+            .await
+            .expect("migrations");
+        sqlx::query(
+            "INSERT INTO conversation_messages (id, channel_id, role, content) \
+             VALUES ('m1', 'portal:1', 'user', 'hello')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let shares = ShareStore::new(pool.clone());
+        let live = shares
+            .create("portal:1", "m1", "m1", None, None)
+            .await
+            .unwrap();
+        let revoked = shares
+            .create("portal:1", "m1", "m1", None, None)
+            .await
+            .unwrap();
+        assert!(shares.revoke("portal:1", &revoked.id).await.unwrap());
+        let expired = shares
+            .create(
+                "portal:1",
+                "m1",
+                "m1",
+                None,
+                Some(chrono::Utc::now() - chrono::Duration::minutes(1)),
+            )
+            .await
+            .unwrap();
+
+        let state = test_state(Vec::new());
+        state
+            .agent_pools
+            .store(Arc::new(HashMap::from([("alice".to_string(), pool)])));
+        let router = build_router(Arc::new(state));
+
+        for (token, expected) in [
+            (live.token.as_str(), StatusCode::OK),
+            (revoked.token.as_str(), StatusCode::GONE),
+            (expired.token.as_str(), StatusCode::GONE),
+            ("unknown", StatusCode::NOT_FOUND),
+        ] {
+            let response = router
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .uri(format!("/s/{token}"))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), expected, "token {token}");
+        }
+    }
+
+    #[test]
+    fn token_comparison() {
+        assert!(constant_time_eq(b"secret-token", b"secret-token"));
+        assert!(!constant_time_eq(b"secret-token", b"secret-tokem"));
+        assert!(!constant_time_eq(b"secret", b"secret-token"));
+    }
+
+    #[tokio::test]
+    async fn unix_socket_is_created_with_its_mode_and_replaces_a_stale_one() {
+        use std::os::unix::fs::PermissionsExt as _;
+
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("api.sock");
+        drop(bind_unix_socket(&path, 0o600).unwrap());
+
+        let listener = bind_unix_socket(&path, 0o600).unwrap();
+        let metadata = std::fs::metadata(&path).unwrap();
+        assert_eq!(metadata.permissions().mode() & 0o777, 0o600);
+        tokio::net::UnixStream::connect(&path).await.unwrap();
+        listener.accept().await.unwrap();
+        // The staging directory is gone; only the socket is left.
+        assert_eq!(std::fs::read_dir(directory.path()).unwrap().count(), 1);
+    }
 }
@@ -1,7 +1,7 @@
 use super::state::ApiState;
 
 use axum::Json;
-use axum::extract::State;
+use axum::extract::{Query, State};
 use axum::http::StatusCode;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -79,12 +79,42 @@ pub(super) struct RawConfigUpdateRequest {
     content: String,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, utoipa::ToSchema)]
 pub(super) struct RawConfigUpdateResponse {
     success: bool,
     message: String,
 }
 
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(super) struct ConfigHistoryQuery {
+    #[serde(default = "default_config_history_limit")]
+    limit: usize,
+}
+
+fn default_config_history_limit() -> usize {
+    50
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct ConfigHistoryEntry {
+    #[serde(flatten)]
+    version: crate::config::ConfigVersion,
+    /// Changes from the previous version; null for the oldest recorded one.
+    diff: Option<String>,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct ConfigHistoryResponse {
+    entries: Vec<ConfigHistoryEntry>,
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
+pub(super) struct ConfigRollbackRequest {
+    /// Hash of the version to restore, from `GET /api/config/history`.
+    hash: String,
+}
+
 pub(super) async fn get_global_settings(
     State(state): State<Arc<ApiState>>,
 ) -> Result<Json<GlobalSettingsResponse>, StatusCode> {
@@ -518,8 +548,141 @@ pub(super) async fn update_raw_config(
         })?;
 
     tracing::info!("config.toml updated via raw editor");
+    record_config_version(&state, request.content, "raw_editor").await;
+    reload_runtime_configs(&state, &config_path).await;
+
+    Ok(Json(RawConfigUpdateResponse {
+        success: true,
+        message: "Config saved and reloaded.".to_string(),
+    }))
+}
+
+/// Versions of config.toml, newest first, each with its diff from the
+/// version before it.
+#[utoipa::path(
+    get,
+    path = "/api/config/history",
+    tag = "system",
+    params(ConfigHistoryQuery),
+    responses((status = 200, body = ConfigHistoryResponse))
+)]
+pub(super) async fn config_history(
+    State(state): State<Arc<ApiState>>,
+    Query(query): Query<ConfigHistoryQuery>,
+) -> Result<Json<ConfigHistoryResponse>, StatusCode> {
+    let history = crate::config::ConfigHistory::new(state.instance_dir.load().as_ref());
+    let limit = query.limit.clamp(1, 200);
+
+    let entries =
+        tokio::task::spawn_blocking(move || -> anyhow::Result<Vec<ConfigHistoryEntry>> {
+            // One extra version so the oldest listed entry still gets a diff.
+            let versions = history.list(limit + 1)?;
+            let mut entries = Vec::with_capacity(limit);
+            for (index, version) in versions.iter().take(limit).enumerate() {
+                let content = history.content(&version.hash)?.unwrap_or_default();
+                let previous = match versions.get(index + 1) {
+                    Some(previous) => history.content(&previous.hash)?,
+                    None => None,
+                };
+                let diff = previous.map(|previous| crate::config::diff_config(&previous, &content));
+                entries.push(ConfigHistoryEntry {
+                    version: version.clone(),
+                    diff,
+                });
+            }
+            Ok(entries)
+        })
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, "config history task failed");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .map_err(|error| {
+            tracing::warn!(%error, "failed to read config history");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(Json(ConfigHistoryResponse { entries }))
+}
 
-    match crate::config::Config::load_from_path(&config_path) {
+/// Restore config.toml to a recorded version and hot-reload it. The rollback
+/// is itself recorded, so it can be undone the same way.
+#[utoipa::path(
+    post,
+    path = "/api/config/rollback",
+    tag = "system",
+    request_body = ConfigRollbackRequest,
+    responses(
+        (status = 200, description = "Rolled back, or `success: false` when the version no longer validates", body = RawConfigUpdateResponse),
+        (status = 404, description = "Unknown version hash"),
+    )
+)]
+pub(super) async fn config_rollback(
+    State(state): State<Arc<ApiState>>,
+    Json(request): Json<ConfigRollbackRequest>,
+) -> Result<Json<RawConfigUpdateResponse>, StatusCode> {
+    let config_path = state.config_path.read().await.clone();
+    if config_path.as_os_str().is_empty() {
+        tracing::error!("config_path not set in ApiState");
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    let history = crate::config::ConfigHistory::new(state.instance_dir.load().as_ref());
+    let hash = request.hash.clone();
+    let content = tokio::task::spawn_blocking(move || history.content(&hash))
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, "config history task failed");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .map_err(|error| {
+            tracing::warn!(%error, "failed to read config version");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    if let Err(error) = crate::config::Config::validate_toml(&content) {
+        return Ok(Json(RawConfigUpdateResponse {
+            success: false,
+            message: format!("Version no longer validates: {error}"),
+        }));
+    }
+
+    tokio::fs::write(&config_path, &content)
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, "failed to write config.toml");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    tracing::info!(hash = %request.hash, "config.toml rolled back");
+    record_config_version(&state, content, "rollback").await;
+    reload_runtime_configs(&state, &config_path).await;
+
+    Ok(Json(RawConfigUpdateResponse {
+        success: true,
+        message: format!(
+            "Config rolled back to {} and reloaded.",
+            &request.hash[..12]
+        ),
+    }))
+}
+
+/// Record a config.toml version written by the API, so its source is kept
+/// even though the file watcher will see the same change.
+async fn record_config_version(state: &ApiState, content: String, source: &'static str) {
+    let history = crate::config::ConfigHistory::new(state.instance_dir.load().as_ref());
+    match tokio::task::spawn_blocking(move || history.record(&content, source)).await {
+        Ok(Ok(_)) => {}
+        Ok(Err(error)) => tracing::warn!(%error, "failed to record config history"),
+        Err(error) => tracing::warn!(%error, "config history task failed"),
+    }
+}
+
+/// Apply config.toml to every running agent without waiting for the file
+/// watcher.
+async fn reload_runtime_configs(state: &ApiState, config_path: &std::path::Path) {
+    match crate::config::Config::load_from_path(config_path) {
         Ok(new_config) => {
             let runtime_configs = state.runtime_configs.load();
             let mcp_managers = state.mcp_managers.load();
@@ -548,9 +711,79 @@ pub(super) async fn update_raw_config(
             tracing::warn!(%error, "config.toml written but failed to reload immediately");
         }
     }
+}
 
-    Ok(Json(RawConfigUpdateResponse {
-        success: true,
-        message: "Config saved and reloaded.".to_string(),
-    }))
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::config::ConfigHistory;
+
+    async fn test_state(instance_dir: &std::path::Path) -> Arc<ApiState> {
+        let (provider_setup_tx, _) = tokio::sync::mpsc::channel(1);
+        let (agent_tx, _) = tokio::sync::mpsc::channel(1);
+        let (agent_remove_tx, _) = tokio::sync::mpsc::channel(1);
+        let (injection_tx, _) = tokio::sync::mpsc::channel(1);
+        let state = ApiState::new_with_provider_sender(
+            provider_setup_tx,
+            agent_tx,
+            agent_remove_tx,
+            injection_tx,
+            Arc::new(arc_swap::ArcSwap::from_pointee(Default::default())),
+        );
+        state.set_instance_dir(instance_dir.to_path_buf());
+        state
+            .set_config_path(instance_dir.join("config.toml"))
+            .await;
+        Arc::new(state)
+    }
+
+    #[tokio::test]
+    async fn rollback_to_an_unknown_hash_is_not_found() {
+        let directory = tempfile::tempdir().unwrap();
+        let state = test_state(directory.path()).await;
+
+        let result = config_rollback(
+            State(state),
+            Json(ConfigRollbackRequest {
+                hash: "0".repeat(64),
+            }),
+        )
+        .await;
+        assert_eq!(result.err(), Some(StatusCode::NOT_FOUND));
+    }
+
+    #[tokio::test]
+    async fn rollback_to_a_version_that_no_longer_validates_is_refused() {
+        let directory = tempfile::tempdir().unwrap();
+        let state = test_state(directory.path()).await;
+        let config_path = directory.path().join("config.toml");
+        std::fs::write(&config_path, "[api]\nenabled = true\n").unwrap();
+
+        let broken = ConfigHistory::new(directory.path())
+            .record("[api\nenabled = true\n", "file_change")
+            .unwrap()
+            .unwrap();
+
+        let response = config_rollback(
+            State(state.clone()),
+            Json(ConfigRollbackRequest { hash: broken.hash }),
+        )
+        .await
+        .unwrap()
+        .0;
+        assert!(!response.success);
+        assert!(response.message.starts_with("Version no longer validates"));
+        // config.toml is left alone and nothing new is recorded.
+        assert_eq!(
+            std::fs::read_to_string(&config_path).unwrap(),
+            "[api]\nenabled = true\n"
+        );
+        let history = config_history(State(state), Query(ConfigHistoryQuery { limit: 50 }))
+            .await
+            .unwrap()
+            .0;
+        assert_eq!(history.entries.len(), 1);
+        assert_eq!(history.entries[0].version.source, "file_change");
+    }
 }
@@ -0,0 +1,355 @@
+//! Shareable read-only transcript links.
+//!
+//! Operators create a share for a message range via the authenticated API.
+//! The resulting token is rendered by the public `/s/{token}` route as a
+//! minimal static HTML page — no frontend bundle, no API access.
+
+use super::access::{AgentJson, AgentQuery, AgentScoped};
+use super::state::ApiState;
+
+use crate::conversation::shares::{ChannelShare, ShareLookup, ShareStore};
+
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::{HeaderValue, StatusCode, header};
+use axum::response::{Html, IntoResponse, Response};
+use serde::{Deserialize, Serialize};
+
+use std::fmt::Write as _;
+use std::sync::Arc;
+
+/// Longest lifetime a share can be created with.
+const MAX_SHARE_LIFETIME_HOURS: i64 = 24 * 90;
+
+fn default_expires_in_hours() -> Option<i64> {
+    Some(24 * 7)
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
+pub(super) struct CreateShareRequest {
+    agent_id: String,
+    from_message_id: String,
+    to_message_id: String,
+    #[serde(default)]
+    title: Option<String>,
+    /// Lifetime of the link. `null` creates a link that never expires.
+    #[serde(default = "default_expires_in_hours")]
+    expires_in_hours: Option<i64>,
+}
+
+impl AgentScoped for CreateShareRequest {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(super) struct ShareAgentQuery {
+    agent_id: String,
+}
+
+impl AgentScoped for ShareAgentQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct ShareResponse {
+    #[serde(flatten)]
+    share: ChannelShare,
+    /// Path of the public page, relative to the instance origin.
+    url: String,
+    is_active: bool,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct ShareListResponse {
+    shares: Vec<ShareResponse>,
+}
+
+impl From<ChannelShare> for ShareResponse {
+    fn from(share: ChannelShare) -> Self {
+        Self {
+            url: format!("/s/{}", share.token),
+            is_active: !share.is_expired() && !share.is_revoked(),
+            share,
+        }
+    }
+}
+
+fn share_store(state: &ApiState, agent_id: &str) -> Result<ShareStore, StatusCode> {
+    let pools = state.agent_pools.load();
+    let pool = pools.get(agent_id).ok_or(StatusCode::NOT_FOUND)?;
+    Ok(ShareStore::new(pool.clone()))
+}
+
+/// Create a tokenized read-only link for a range of channel messages.
+#[utoipa::path(
+    post,
+    path = "/api/channels/{id}/share",
+    tag = "channels",
+    params(("id" = String, Path, description = "Channel id")),
+    request_body = CreateShareRequest,
+    responses(
+        (status = 200, body = ShareResponse),
+        (status = 400, description = "Invalid lifetime or message range"),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
+pub(super) async fn create_share(
+    State(state): State<Arc<ApiState>>,
+    Path(channel_id): Path<String>,
+    AgentJson(request): AgentJson<CreateShareRequest>,
+) -> Result<Json<ShareResponse>, (StatusCode, String)> {
+    let store = share_store(&state, &request.agent_id)
+        .map_err(|status| (status, format!("agent '{}' not found", request.agent_id)))?;
+
+    let expires_at = match request.expires_in_hours {
+        Some(hours) if !(1..=MAX_SHARE_LIFETIME_HOURS).contains(&hours) => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!("expires_in_hours must be between 1 and {MAX_SHARE_LIFETIME_HOURS}"),
+            ));
+        }
+        Some(hours) => Some(chrono::Utc::now() + chrono::Duration::hours(hours)),
+        None => None,
+    };
+
+    let share = store
+        .create(
+            &channel_id,
+            &request.from_message_id,
+            &request.to_message_id,
+            request.title.filter(|title| !title.trim().is_empty()),
+            expires_at,
+        )
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, %channel_id, "failed to create share");
+            (StatusCode::BAD_REQUEST, error.to_string())
+        })?;
+
+    tracing::info!(
+        agent_id = %request.agent_id,
+        %channel_id,
+        share_id = %share.id,
+        "transcript share created via API"
+    );
+
+    Ok(Json(share.into()))
+}
+
+/// List the share links issued for a channel.
+#[utoipa::path(
+    get,
+    path = "/api/channels/{id}/shares",
+    tag = "channels",
+    params(("id" = String, Path, description = "Channel id"), ShareAgentQuery),
+    responses(
+        (status = 200, body = ShareListResponse),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
+pub(super) async fn list_shares(
+    State(state): State<Arc<ApiState>>,
+    Path(channel_id): Path<String>,
+    AgentQuery(query): AgentQuery<ShareAgentQuery>,
+) -> Result<Json<ShareListResponse>, StatusCode> {
+    let store = share_store(&state, &query.agent_id)?;
+    let shares = store.list(&channel_id).await.map_err(|error| {
+        tracing::warn!(%error, %channel_id, "failed to list shares");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    Ok(Json(ShareListResponse {
+        shares: shares.into_iter().map(ShareResponse::from).collect(),
+    }))
+}
+
+/// Revoke a share link. The public page stops rendering immediately.
+#[utoipa::path(
+    delete,
+    path = "/api/channels/{id}/shares/{share_id}",
+    tag = "channels",
+    params(
+        ("id" = String, Path, description = "Channel id"),
+        ("share_id" = String, Path, description = "Share id"),
+        ShareAgentQuery,
+    ),
+    responses(
+        (status = 200, description = "The body carries `success`"),
+        (status = 404, description = "Unknown agent or share"),
+    )
+)]
+pub(super) async fn revoke_share(
+    State(state): State<Arc<ApiState>>,
+    Path((channel_id, share_id)): Path<(String, String)>,
+    AgentQuery(query): AgentQuery<ShareAgentQuery>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let store = share_store(&state, &query.agent_id)?;
+    let revoked = store
+        .revoke(&channel_id, &share_id)
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, %share_id, "failed to revoke share");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    if !revoked {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    tracing::info!(agent_id = %query.agent_id, %channel_id, %share_id, "transcript share revoked");
+    Ok(Json(serde_json::json!({ "success": true })))
+}
+
+/// Public, unauthenticated rendering of a share link.
+pub(super) async fn view_share(
+    State(state): State<Arc<ApiState>>,
+    Path(token): Path<String>,
+) -> Response {
+    // Tokens aren't namespaced by agent, so probe each agent database. Tokens
+    // are random 256-bit values, so a hit in more than one is not a concern.
+    let pools = state.agent_pools.load();
+    let mut outcome = ShareLookup::NotFound;
+    let mut matched_store = None;
+    for pool in pools.values() {
+        let store = ShareStore::new(pool.clone());
+        match store.lookup(&token).await {
+            Ok(ShareLookup::NotFound) => continue,
+            Ok(lookup) => {
+                outcome = lookup;
+                matched_store = Some(store);
+                break;
+            }
+            Err(error) => {
+                tracing::warn!(%error, "failed to look up share token");
+            }
+        }
+    }
+
+    let (status, body) = match (outcome, matched_store) {
+        (ShareLookup::Active(share), Some(store)) => match store.load_messages(&share).await {
+            Ok(messages) => (StatusCode::OK, render_share_page(&share, &messages)),
+            Err(error) => {
+                tracing::warn!(%error, share_id = %share.id, "failed to load shared messages");
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    render_notice_page("This transcript could not be loaded."),
+                )
+            }
+        },
+        (ShareLookup::Expired, _) => (
+            StatusCode::GONE,
+            render_notice_page("This share link has expired."),
+        ),
+        (ShareLookup::Revoked, _) => (
+            StatusCode::GONE,
+            render_notice_page("This share link has been revoked."),
+        ),
+        _ => (
+            StatusCode::NOT_FOUND,
+            render_notice_page("This share link does not exist."),
+        ),
+    };
+
+    let mut response = (status, Html(body)).into_response();
+    let headers = response.headers_mut();
+    headers.insert(header::CACHE_CONTROL, HeaderValue::from_static("no-store"));
+    headers.insert(
+        header::REFERRER_POLICY,
+        HeaderValue::from_static("no-referrer"),
+    );
+    headers.insert(
+        header::HeaderName::from_static("x-robots-tag"),
+        HeaderValue::from_static("noindex, nofollow"),
+    );
+    response
+}
+
+pub(super) const PAGE_STYLE: &str = "body{font-family:system-ui,sans-serif;max-width:760px;margin:2rem auto;\
+padding:0 1rem;color:#1f2328;background:#fff}h1{font-size:1.25rem}\
+.meta{color:#656d76;font-size:.85rem}.message{border-top:1px solid #d0d7de;padding:.75rem 0}\
+.sender{font-weight:600}.assistant .sender{color:#8250df}\
+.content{white-space:pre-wrap;word-wrap:break-word;margin-top:.25rem}";
+
+fn render_share_page(
+    share: &ChannelShare,
+    messages: &[crate::conversation::history::ConversationMessage],
+) -> String {
+    let title = share.title.as_deref().unwrap_or("Shared conversation");
+    let mut body = String::new();
+    for message in messages {
+        let sender = message
+            .sender_name
+            .as_deref()
+            .filter(|name| !name.is_empty())
+            .unwrap_or(if message.role == "assistant" {
+                "Assistant"
+            } else {
+                "User"
+            });
+        let _ = write!(
+            body,
+            "<div class=\"message {role}\"><span class=\"sender\">{sender}</span> \
+             <span class=\"meta\">{timestamp}</span><div class=\"content\">{content}</div></div>",
+            role = escape_html(&message.role),
+            sender = escape_html(sender),
+            timestamp = message.created_at.format("%Y-%m-%d %H:%M UTC"),
+            content = escape_html(&message.content),
+        );
+    }
+
+    let expiry = share
+        .expires_at
+        .map(|expires_at| format!(" · expires {}", expires_at.format("%Y-%m-%d %H:%M UTC")))
+        .unwrap_or_default();
+
+    format!(
+        "<!doctype html><html><head><meta charset=\"utf-8\">\
+         <meta name=\"viewport\" content=\"width=device-width,initial-scale=1\">\
+         <meta name=\"robots\" content=\"noindex,nofollow\">\
+         <title>{title}</title><style>{PAGE_STYLE}</style></head><body>\
+         <h1>{title}</h1><p class=\"meta\">{count} messages · read-only{expiry}</p>{body}</body></html>",
+        title = escape_html(title),
+        count = messages.len(),
+    )
+}
+
+fn render_notice_page(notice: &str) -> String {
+    format!(
+        "<!doctype html><html><head><meta charset=\"utf-8\">\
+         <meta name=\"robots\" content=\"noindex,nofollow\">\
+         <title>Spacebot</title><style>{PAGE_STYLE}</style></head>\
+         <body><p>{}</p></body></html>",
+        escape_html(notice)
+    )
+}
+
+pub(super) fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            other => escaped.push(other),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::escape_html;
+
+    #[test]
+    fn escapes_markup_in_shared_content() {
+        assert_eq!(
+            escape_html("<script>alert('x')</script> & \"more\""),
+            "&lt;script&gt;alert(&#39;x&#39;)&lt;/script&gt; &amp; &quot;more&quot;"
+        );
+    }
+}
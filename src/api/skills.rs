@@ -1,5 +1,6 @@
 use super::state::{ApiEvent, ApiState};
 
+use super::access::{AgentJson, AgentQuery, AgentScoped};
 use axum::Json;
 use axum::extract::{Query, State};
 use axum::http::StatusCode;
@@ -54,6 +55,12 @@ pub(super) struct InstallSkillRequest {
     instance: bool,
 }
 
+impl AgentScoped for InstallSkillRequest {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
 #[derive(Serialize)]
 pub(super) struct InstallSkillResponse {
     installed: Vec<String>,
@@ -65,6 +72,12 @@ pub(super) struct RemoveSkillRequest {
     name: String,
 }
 
+impl AgentScoped for RemoveSkillRequest {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
 #[derive(Serialize)]
 pub(super) struct RemoveSkillResponse {
     success: bool,
@@ -106,6 +119,12 @@ pub(super) struct SkillContentQuery {
     name: String,
 }
 
+impl AgentScoped for SkillContentQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
 #[derive(Serialize)]
 pub(super) struct SkillContentResponse {
     name: String,
@@ -128,6 +147,12 @@ pub(super) struct SkillsQuery {
     agent_id: String,
 }
 
+impl AgentScoped for SkillsQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
 #[derive(Deserialize)]
 pub(super) struct RegistryBrowseQuery {
     #[serde(default = "default_registry_view")]
@@ -169,7 +194,7 @@ pub(super) struct RegistrySkillContentResponse {
 /// List installed skills for an agent.
 pub(super) async fn list_skills(
     State(state): State<Arc<ApiState>>,
-    Query(query): Query<SkillsQuery>,
+    AgentQuery(query): AgentQuery<SkillsQuery>,
 ) -> Result<Json<SkillsListResponse>, StatusCode> {
     let configs = state.agent_configs.load();
     let agent = configs
@@ -207,7 +232,7 @@ pub(super) async fn list_skills(
 /// Install a skill from GitHub.
 pub(super) async fn install_skill(
     State(state): State<Arc<ApiState>>,
-    axum::extract::Json(req): axum::extract::Json<InstallSkillRequest>,
+    AgentJson(req): AgentJson<InstallSkillRequest>,
 ) -> Result<Json<InstallSkillResponse>, StatusCode> {
     let configs = state.agent_configs.load();
     let agent = configs
@@ -228,6 +253,19 @@ pub(super) async fn install_skill(
             StatusCode::INTERNAL_SERVER_ERROR
         })?;
 
+    if !req.instance && !installed.is_empty() {
+        record_skill_change(
+            &state,
+            &req.agent_id,
+            &format!(
+                "Install skill(s) {} from {}",
+                installed.join(", "),
+                req.spec
+            ),
+        )
+        .await;
+    }
+
     state.send_event(ApiEvent::ConfigReloaded);
 
     Ok(Json(InstallSkillResponse { installed }))
@@ -236,7 +274,7 @@ pub(super) async fn install_skill(
 /// Remove an installed skill.
 pub(super) async fn remove_skill(
     State(state): State<Arc<ApiState>>,
-    axum::extract::Json(req): axum::extract::Json<RemoveSkillRequest>,
+    AgentJson(req): AgentJson<RemoveSkillRequest>,
 ) -> Result<Json<RemoveSkillResponse>, StatusCode> {
     let configs = state.agent_configs.load();
     let agent = configs
@@ -262,6 +300,10 @@ pub(super) async fn remove_skill(
         }
     })?;
 
+    if removed_path.is_some() {
+        record_skill_change(&state, &req.agent_id, &format!("Remove skill {}", req.name)).await;
+    }
+
     state.send_event(ApiEvent::ConfigReloaded);
 
     tracing::info!(
@@ -279,7 +321,7 @@ pub(super) async fn remove_skill(
 /// Get the full content of an installed skill.
 pub(super) async fn get_skill_content(
     State(state): State<Arc<ApiState>>,
-    Query(query): Query<SkillContentQuery>,
+    AgentQuery(query): AgentQuery<SkillContentQuery>,
 ) -> Result<Json<SkillContentResponse>, StatusCode> {
     let configs = state.agent_configs.load();
     let agent = configs
@@ -312,7 +354,7 @@ pub(super) async fn get_skill_content(
 /// Upload skill files (zip archives or directories) from the user's computer.
 pub(super) async fn upload_skill(
     State(state): State<Arc<ApiState>>,
-    Query(query): Query<SkillsQuery>,
+    AgentQuery(query): AgentQuery<SkillsQuery>,
     mut multipart: axum::extract::Multipart,
 ) -> Result<Json<UploadSkillResponse>, StatusCode> {
     let configs = state.agent_configs.load();
@@ -387,6 +429,12 @@ pub(super) async fn upload_skill(
     }
 
     if !all_installed.is_empty() {
+        record_skill_change(
+            &state,
+            &query.agent_id,
+            &format!("Upload skill(s) {}", all_installed.join(", ")),
+        )
+        .await;
         state.send_event(ApiEvent::ConfigReloaded);
     }
 
@@ -401,6 +449,14 @@ pub(super) async fn upload_skill(
     }))
 }
 
+/// Commit a workspace skill change to the agent's workspace history.
+async fn record_skill_change(state: &ApiState, agent_id: &str, summary: &str) {
+    let runtime_config = state.runtime_configs.load().get(agent_id).cloned();
+    if let Some(runtime_config) = runtime_config {
+        runtime_config.record_workspace_change("api", summary).await;
+    }
+}
+
 /// Proxy browse requests to skills.sh leaderboard API.
 pub(super) async fn registry_browse(
     Query(query): Query<RegistryBrowseQuery>,
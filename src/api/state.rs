@@ -20,14 +20,14 @@ use crate::{ProcessEvent, ProcessId};
 use arc_swap::ArcSwap;
 use serde::Serialize;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Instant;
 use tokio::sync::{RwLock, broadcast, mpsc};
 
 /// Summary of an agent's configuration, exposed via the API.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
 pub struct AgentInfo {
     pub id: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -38,6 +38,7 @@ pub struct AgentInfo {
     pub gradient_start: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub gradient_end: Option<String>,
+    #[schema(value_type = String)]
     pub workspace: PathBuf,
     pub context_window: usize,
     pub max_turns: usize,
@@ -49,8 +50,45 @@ pub struct AgentInfo {
 pub struct ApiState {
     pub started_at: Instant,
     pub auth_token: Option<String>,
-    /// Aggregated event stream from all agents. SSE clients subscribe here.
+    /// Scoped bearer tokens. Together with `auth_token`, an empty set means
+    /// the API is unauthenticated.
+    pub api_tokens: Vec<crate::config::ApiToken>,
+    /// Strictness of the `/readyz` probe.
+    pub readiness: crate::config::ReadinessConfig,
+    /// Persisted responses for `Idempotency-Key` replays. `None` disables
+    /// idempotency handling.
+    pub idempotency_store: Option<Arc<super::IdempotencyStore>>,
+    /// Outbound webhook subscriptions. `None` when the store failed to open.
+    pub webhooks: Option<Arc<super::WebhookRegistry>>,
+    /// Record of mutating API calls. `None` when the store failed to open.
+    pub audit_log: Option<Arc<super::AuditLog>>,
+    /// Durable queue of outbound replies. `None` when the store failed to
+    /// open.
+    pub outbox: Option<Arc<crate::messaging::outbox::Outbox>>,
+    /// Persisted event history. `None` when disabled or the store failed to
+    /// open.
+    pub event_archive: Option<Arc<super::EventArchive>>,
+    /// Settings for `/v1/chat/completions`. `None` when the endpoint is
+    /// disabled.
+    pub openai: Option<crate::config::ApiOpenAiConfig>,
+    /// Per-client request limits. `None` when rate limiting is disabled.
+    pub rate_limiter: Option<Arc<super::RateLimiter>>,
+    /// Settings for the `/api/widget/*` routes. `None` when the widget is
+    /// disabled.
+    pub widget: Option<crate::config::ApiWidgetConfig>,
+    /// Per-visitor limits for the widget routes, separate from the API's own.
+    pub widget_rate_limiter: Option<Arc<super::RateLimiter>>,
+    /// Vite dev server URL. When set, the frontend is proxied instead of
+    /// served from the embedded assets.
+    pub dev_proxy_url: Option<String>,
+    /// Aggregated event stream from all agents. Producers send here; the
+    /// event sequencer stamps IDs and republishes on `sequenced_event_tx`.
     pub event_tx: broadcast::Sender<ApiEvent>,
+    /// ID-stamped event stream. SSE clients subscribe here.
+    pub sequenced_event_tx: broadcast::Sender<SequencedEvent>,
+    /// Recent sequenced events, replayed to clients resuming with
+    /// `Last-Event-ID`.
+    pub event_history: std::sync::Mutex<EventHistory>,
     /// Per-agent SQLite pools for querying channel/conversation data.
     pub agent_pools: arc_swap::ArcSwap<HashMap<String, sqlx::SqlitePool>>,
     /// Per-agent config summaries for the agents list endpoint.
@@ -59,6 +97,10 @@ pub struct ApiState {
     pub memory_searches: arc_swap::ArcSwap<HashMap<String, Arc<MemorySearch>>>,
     /// Live status blocks for active channels, keyed by channel_id.
     pub channel_status_blocks: RwLock<HashMap<String, Arc<tokio::sync::RwLock<StatusBlock>>>>,
+    /// Owning agent of each channel seen so far, keyed by channel_id. Filled
+    /// as channels start and as API lookups resolve, so channel reads hit a
+    /// single agent pool.
+    pub channel_agents: RwLock<HashMap<String, String>>,
     /// Live channel states for active channels, keyed by channel_id.
     /// Used by the cancel API to abort workers and branches.
     pub channel_states: RwLock<HashMap<String, ChannelState>>,
@@ -88,6 +130,14 @@ pub struct ApiState {
     pub runtime_configs: ArcSwap<HashMap<String, Arc<RuntimeConfig>>>,
     /// Per-agent MCP managers for status and reconnect APIs.
     pub mcp_managers: ArcSwap<HashMap<String, Arc<McpManager>>>,
+    /// Per-agent singleton-duty leases, for leadership status.
+    pub leader_leases: ArcSwap<HashMap<String, Arc<crate::leader::LeaderLease>>>,
+    /// Per-agent resource quotas, for agent health.
+    pub agent_quotas: ArcSwap<HashMap<String, Arc<crate::quota::AgentQuota>>>,
+    /// Leader election settings, applied to agents created at runtime.
+    pub leader_election: crate::config::LeaderElectionConfig,
+    /// Config problems that weren't corrected, refreshed on hot reload.
+    pub config_issues: Arc<ArcSwap<Vec<crate::config::ConfigIssue>>>,
     /// Per-agent sandbox instances for process containment.
     pub sandboxes: ArcSwap<HashMap<String, Arc<crate::sandbox::Sandbox>>>,
     /// Instance-level secrets store (shared across all agents).
@@ -118,6 +168,14 @@ pub struct ApiState {
     pub agent_tx: mpsc::Sender<crate::Agent>,
     /// Sender to remove agents from the main event loop.
     pub agent_remove_tx: mpsc::Sender<String>,
+    /// Wakes the main event loop to release messages held for a resumed agent.
+    pub agent_resumed: tokio::sync::Notify,
+    /// Each agent's long-running background loops (cortex, ingestion), so a
+    /// restart can stop them before starting fresh ones.
+    pub agent_tasks: std::sync::Mutex<HashMap<String, Vec<tokio::task::AbortHandle>>>,
+    /// Agents with a restart in progress. A second restart of the same agent
+    /// is refused rather than tearing down the runtime the first is building.
+    pub restarting_agents: std::sync::Mutex<HashSet<String>>,
     /// Shared webchat adapter for session management from API handlers.
     pub webchat_adapter: ArcSwap<Option<Arc<WebChatAdapter>>>,
     /// Cross-agent task store registry for delegation.
@@ -141,6 +199,68 @@ pub struct ApiState {
     pub ssh_mutex: tokio::sync::Mutex<()>,
 }
 
+/// Number of recent events kept for `Last-Event-ID` replay.
+const EVENT_HISTORY_CAPACITY: usize = 1024;
+
+/// An `ApiEvent` stamped with its position in the instance-wide stream.
+#[derive(Debug, Clone)]
+pub struct SequencedEvent {
+    pub id: u64,
+    pub event: ApiEvent,
+}
+
+/// Ring buffer of recently emitted events.
+///
+/// IDs are seeded from the boot time in microseconds, so they keep
+/// increasing across restarts and an ID from a previous process is detected
+/// as a gap rather than silently matching new events.
+#[derive(Debug)]
+pub struct EventHistory {
+    events: VecDeque<SequencedEvent>,
+    last_id: u64,
+}
+
+impl EventHistory {
+    fn new(seed: u64) -> Self {
+        Self {
+            events: VecDeque::with_capacity(EVENT_HISTORY_CAPACITY),
+            last_id: seed,
+        }
+    }
+
+    fn push(&mut self, event: ApiEvent) -> SequencedEvent {
+        self.last_id += 1;
+        let sequenced = SequencedEvent {
+            id: self.last_id,
+            event,
+        };
+        if self.events.len() == EVENT_HISTORY_CAPACITY {
+            self.events.pop_front();
+        }
+        self.events.push_back(sequenced.clone());
+        sequenced
+    }
+
+    /// Events emitted after `last_event_id`, oldest first. `None` when some
+    /// of them are no longer available, so the client must resync instead.
+    pub fn since(&self, last_event_id: u64) -> Option<Vec<SequencedEvent>> {
+        if last_event_id >= self.last_id {
+            return Some(Vec::new());
+        }
+        let oldest_id = self.events.front()?.id;
+        if last_event_id.saturating_add(1) < oldest_id {
+            return None;
+        }
+        Some(
+            self.events
+                .iter()
+                .filter(|sequenced| sequenced.id > last_event_id)
+                .cloned()
+                .collect(),
+        )
+    }
+}
+
 /// Events sent to SSE clients. Wraps ProcessEvents with agent context.
 #[derive(Debug, Clone, Serialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -172,6 +292,13 @@ pub enum ApiEvent {
         text_delta: String,
         aggregated_text: String,
     },
+    /// Token usage of one completion made by a channel.
+    ChannelUsage {
+        agent_id: String,
+        channel_id: String,
+        input_tokens: u64,
+        output_tokens: u64,
+    },
     /// A worker was started.
     WorkerStarted {
         agent_id: String,
@@ -280,6 +407,153 @@ pub enum ApiEvent {
     },
 }
 
+impl ApiEvent {
+    /// Every SSE event name, for validating client filters.
+    pub const EVENT_TYPES: &'static [&'static str] = &[
+        "inbound_message",
+        "outbound_message",
+        "outbound_message_delta",
+        "typing_state",
+        "channel_usage",
+        "worker_started",
+        "worker_status",
+        "worker_idle",
+        "worker_completed",
+        "branch_started",
+        "branch_completed",
+        "tool_started",
+        "tool_completed",
+        "config_reloaded",
+        "agent_message_sent",
+        "agent_message_received",
+        "task_updated",
+        "opencode_part_updated",
+        "worker_text",
+        "cortex_chat_message",
+    ];
+
+    /// The SSE `event:` name for this event.
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            ApiEvent::InboundMessage { .. } => "inbound_message",
+            ApiEvent::OutboundMessage { .. } => "outbound_message",
+            ApiEvent::OutboundMessageDelta { .. } => "outbound_message_delta",
+            ApiEvent::TypingState { .. } => "typing_state",
+            ApiEvent::ChannelUsage { .. } => "channel_usage",
+            ApiEvent::WorkerStarted { .. } => "worker_started",
+            ApiEvent::WorkerStatusUpdate { .. } => "worker_status",
+            ApiEvent::WorkerIdle { .. } => "worker_idle",
+            ApiEvent::WorkerCompleted { .. } => "worker_completed",
+            ApiEvent::BranchStarted { .. } => "branch_started",
+            ApiEvent::BranchCompleted { .. } => "branch_completed",
+            ApiEvent::ToolStarted { .. } => "tool_started",
+            ApiEvent::ToolCompleted { .. } => "tool_completed",
+            ApiEvent::ConfigReloaded => "config_reloaded",
+            ApiEvent::AgentMessageSent { .. } => "agent_message_sent",
+            ApiEvent::AgentMessageReceived { .. } => "agent_message_received",
+            ApiEvent::TaskUpdated { .. } => "task_updated",
+            ApiEvent::OpenCodePartUpdated { .. } => "opencode_part_updated",
+            ApiEvent::WorkerText { .. } => "worker_text",
+            ApiEvent::CortexChatMessage { .. } => "cortex_chat_message",
+        }
+    }
+
+    /// Whether this event concerns the given agent. Cross-agent messages
+    /// concern both ends; instance-wide events concern every agent.
+    pub fn involves_agent(&self, agent_id: &str) -> bool {
+        match self {
+            ApiEvent::InboundMessage { agent_id: id, .. }
+            | ApiEvent::OutboundMessage { agent_id: id, .. }
+            | ApiEvent::OutboundMessageDelta { agent_id: id, .. }
+            | ApiEvent::TypingState { agent_id: id, .. }
+            | ApiEvent::ChannelUsage { agent_id: id, .. }
+            | ApiEvent::WorkerStarted { agent_id: id, .. }
+            | ApiEvent::WorkerStatusUpdate { agent_id: id, .. }
+            | ApiEvent::WorkerIdle { agent_id: id, .. }
+            | ApiEvent::WorkerCompleted { agent_id: id, .. }
+            | ApiEvent::BranchStarted { agent_id: id, .. }
+            | ApiEvent::BranchCompleted { agent_id: id, .. }
+            | ApiEvent::ToolStarted { agent_id: id, .. }
+            | ApiEvent::ToolCompleted { agent_id: id, .. }
+            | ApiEvent::TaskUpdated { agent_id: id, .. }
+            | ApiEvent::OpenCodePartUpdated { agent_id: id, .. }
+            | ApiEvent::WorkerText { agent_id: id, .. }
+            | ApiEvent::CortexChatMessage { agent_id: id, .. } => id == agent_id,
+            ApiEvent::AgentMessageSent {
+                from_agent_id,
+                to_agent_id,
+                ..
+            }
+            | ApiEvent::AgentMessageReceived {
+                from_agent_id,
+                to_agent_id,
+                ..
+            } => from_agent_id == agent_id || to_agent_id == agent_id,
+            ApiEvent::ConfigReloaded => true,
+        }
+    }
+
+    /// The agents this event concerns. Empty for instance-wide events.
+    pub fn agent_ids(&self) -> Vec<&str> {
+        match self {
+            ApiEvent::InboundMessage { agent_id, .. }
+            | ApiEvent::OutboundMessage { agent_id, .. }
+            | ApiEvent::OutboundMessageDelta { agent_id, .. }
+            | ApiEvent::TypingState { agent_id, .. }
+            | ApiEvent::ChannelUsage { agent_id, .. }
+            | ApiEvent::WorkerStarted { agent_id, .. }
+            | ApiEvent::WorkerStatusUpdate { agent_id, .. }
+            | ApiEvent::WorkerIdle { agent_id, .. }
+            | ApiEvent::WorkerCompleted { agent_id, .. }
+            | ApiEvent::BranchStarted { agent_id, .. }
+            | ApiEvent::BranchCompleted { agent_id, .. }
+            | ApiEvent::ToolStarted { agent_id, .. }
+            | ApiEvent::ToolCompleted { agent_id, .. }
+            | ApiEvent::TaskUpdated { agent_id, .. }
+            | ApiEvent::OpenCodePartUpdated { agent_id, .. }
+            | ApiEvent::WorkerText { agent_id, .. }
+            | ApiEvent::CortexChatMessage { agent_id, .. } => vec![agent_id],
+            ApiEvent::AgentMessageSent {
+                from_agent_id,
+                to_agent_id,
+                ..
+            }
+            | ApiEvent::AgentMessageReceived {
+                from_agent_id,
+                to_agent_id,
+                ..
+            } => vec![from_agent_id, to_agent_id],
+            ApiEvent::ConfigReloaded => Vec::new(),
+        }
+    }
+
+    /// The channel this event belongs to, if any.
+    pub fn channel_id(&self) -> Option<&str> {
+        match self {
+            ApiEvent::InboundMessage { channel_id, .. }
+            | ApiEvent::OutboundMessage { channel_id, .. }
+            | ApiEvent::OutboundMessageDelta { channel_id, .. }
+            | ApiEvent::TypingState { channel_id, .. }
+            | ApiEvent::ChannelUsage { channel_id, .. }
+            | ApiEvent::BranchStarted { channel_id, .. }
+            | ApiEvent::BranchCompleted { channel_id, .. }
+            | ApiEvent::AgentMessageSent { channel_id, .. }
+            | ApiEvent::AgentMessageReceived { channel_id, .. } => Some(channel_id),
+            ApiEvent::WorkerStarted { channel_id, .. }
+            | ApiEvent::WorkerStatusUpdate { channel_id, .. }
+            | ApiEvent::WorkerIdle { channel_id, .. }
+            | ApiEvent::WorkerCompleted { channel_id, .. }
+            | ApiEvent::ToolStarted { channel_id, .. }
+            | ApiEvent::ToolCompleted { channel_id, .. } => channel_id.as_deref(),
+            ApiEvent::ConfigReloaded
+            | ApiEvent::TaskUpdated { .. }
+            | ApiEvent::OpenCodePartUpdated { .. }
+            | ApiEvent::WorkerText { .. }
+            | ApiEvent::CortexChatMessage { .. } => None,
+        }
+    }
+}
+
 impl ApiState {
     pub fn new_with_provider_sender(
         provider_setup_tx: mpsc::Sender<crate::ProviderSetupEvent>,
@@ -291,14 +565,31 @@ impl ApiState {
         >,
     ) -> Self {
         let (event_tx, _) = broadcast::channel(512);
+        let (sequenced_event_tx, _) = broadcast::channel(512);
+        let id_seed = u64::try_from(chrono::Utc::now().timestamp_micros()).unwrap_or_default();
         Self {
             started_at: Instant::now(),
             auth_token: None,
+            api_tokens: Vec::new(),
+            readiness: crate::config::ReadinessConfig::default(),
+            idempotency_store: None,
+            webhooks: None,
+            audit_log: None,
+            outbox: None,
+            event_archive: None,
+            openai: None,
+            rate_limiter: None,
+            widget: None,
+            widget_rate_limiter: None,
+            dev_proxy_url: None,
             event_tx,
+            sequenced_event_tx,
+            event_history: std::sync::Mutex::new(EventHistory::new(id_seed)),
             agent_pools: arc_swap::ArcSwap::from_pointee(HashMap::new()),
             agent_configs: arc_swap::ArcSwap::from_pointee(Vec::new()),
             memory_searches: arc_swap::ArcSwap::from_pointee(HashMap::new()),
             channel_status_blocks: RwLock::new(HashMap::new()),
+            channel_agents: RwLock::new(HashMap::new()),
             channel_states: RwLock::new(HashMap::new()),
             cortex_chat_sessions: arc_swap::ArcSwap::from_pointee(HashMap::new()),
             agent_workspaces: arc_swap::ArcSwap::from_pointee(HashMap::new()),
@@ -312,6 +603,10 @@ impl ApiState {
             project_stores: arc_swap::ArcSwap::from_pointee(HashMap::new()),
             runtime_configs: ArcSwap::from_pointee(HashMap::new()),
             mcp_managers: ArcSwap::from_pointee(HashMap::new()),
+            leader_leases: ArcSwap::from_pointee(HashMap::new()),
+            agent_quotas: ArcSwap::from_pointee(HashMap::new()),
+            leader_election: crate::config::LeaderElectionConfig::default(),
+            config_issues: Arc::new(ArcSwap::from_pointee(Vec::new())),
             sandboxes: ArcSwap::from_pointee(HashMap::new()),
             secrets_store: ArcSwap::from_pointee(None),
             discord_permissions: RwLock::new(None),
@@ -327,6 +622,9 @@ impl ApiState {
             defaults_config: RwLock::new(None),
             agent_tx,
             agent_remove_tx,
+            agent_resumed: tokio::sync::Notify::new(),
+            agent_tasks: std::sync::Mutex::new(HashMap::new()),
+            restarting_agents: std::sync::Mutex::new(HashSet::new()),
             task_store_registry,
             injection_tx,
             webchat_adapter: ArcSwap::from_pointee(None),
@@ -365,6 +663,47 @@ impl ApiState {
         self.channel_states.write().await.remove(channel_id);
     }
 
+    /// Record which agent owns a channel. Ownership outlives the live
+    /// channel, so this is only undone when the channel is deleted.
+    pub async fn register_channel_agent(&self, channel_id: String, agent_id: String) {
+        self.channel_agents
+            .write()
+            .await
+            .insert(channel_id, agent_id);
+    }
+
+    /// Forget a channel's owner after the channel is deleted.
+    pub async fn unregister_channel_agent(&self, channel_id: &str) {
+        self.channel_agents.write().await.remove(channel_id);
+    }
+
+    /// Find the agent that owns a channel. Falls back to asking each agent's
+    /// database on an index miss and caches the answer.
+    pub async fn resolve_channel_agent(&self, channel_id: &str) -> Option<String> {
+        if let Some(agent_id) = self.channel_agents.read().await.get(channel_id) {
+            return Some(agent_id.clone());
+        }
+
+        let pools = self.agent_pools.load();
+        let mut agent_ids: Vec<&String> = pools.keys().collect();
+        agent_ids.sort();
+        for agent_id in agent_ids {
+            let store = crate::conversation::channels::ChannelStore::new(pools[agent_id].clone());
+            match store.get(channel_id).await {
+                Ok(Some(_)) => {
+                    self.register_channel_agent(channel_id.to_string(), agent_id.clone())
+                        .await;
+                    return Some(agent_id.clone());
+                }
+                Ok(None) => {}
+                Err(error) => {
+                    tracing::warn!(%error, agent_id, channel_id, "failed to look up channel owner");
+                }
+            }
+        }
+        None
+    }
+
     /// Retrieve the live transcript cache for a running worker.
     ///
     /// Returns `Some` with the accumulated transcript steps if the worker is
@@ -619,6 +958,21 @@ impl ApiState {
                                     })
                                     .ok();
                             }
+                            ProcessEvent::ChannelUsage {
+                                channel_id,
+                                input_tokens,
+                                output_tokens,
+                                ..
+                            } => {
+                                api_tx
+                                    .send(ApiEvent::ChannelUsage {
+                                        agent_id: agent_id.clone(),
+                                        channel_id: channel_id.to_string(),
+                                        input_tokens: *input_tokens,
+                                        output_tokens: *output_tokens,
+                                    })
+                                    .ok();
+                            }
                             ProcessEvent::OpenCodePartUpdated {
                                 worker_id, part, ..
                             } => {
@@ -771,6 +1125,16 @@ impl ApiState {
         self.sandboxes.store(Arc::new(sandboxes));
     }
 
+    /// Set the singleton-duty leases for all agents.
+    pub fn set_leader_leases(&self, leases: HashMap<String, Arc<crate::leader::LeaderLease>>) {
+        self.leader_leases.store(Arc::new(leases));
+    }
+
+    /// Set the resource quotas for all agents.
+    pub fn set_agent_quotas(&self, quotas: HashMap<String, Arc<crate::quota::AgentQuota>>) {
+        self.agent_quotas.store(Arc::new(quotas));
+    }
+
     /// Set the instance-level secrets store.
     pub fn set_secrets_store(&self, store: Arc<crate::secrets::store::SecretsStore>) {
         self.secrets_store.store(Arc::new(Some(store)));
@@ -816,6 +1180,29 @@ impl ApiState {
         *self.prompt_engine.write().await = Some(engine);
     }
 
+    /// Record background loops belonging to an agent.
+    pub fn track_agent_tasks(&self, agent_id: &str, handles: &[tokio::task::JoinHandle<()>]) {
+        self.agent_tasks
+            .lock()
+            .expect("agent task registry poisoned")
+            .entry(agent_id.to_string())
+            .or_default()
+            .extend(handles.iter().map(tokio::task::JoinHandle::abort_handle));
+    }
+
+    /// Abort an agent's tracked background loops.
+    pub fn abort_agent_tasks(&self, agent_id: &str) {
+        let handles = self
+            .agent_tasks
+            .lock()
+            .expect("agent task registry poisoned")
+            .remove(agent_id)
+            .unwrap_or_default();
+        for handle in handles {
+            handle.abort();
+        }
+    }
+
     /// Set the instance-level defaults for runtime agent creation.
     pub async fn set_defaults_config(&self, defaults: DefaultsConfig) {
         *self.defaults_config.write().await = Some(defaults);
@@ -845,6 +1232,31 @@ impl ApiState {
     pub fn send_event(&self, event: ApiEvent) {
         let _ = self.event_tx.send(event);
     }
+
+    /// Stamp every event on `event_tx` with a monotonically increasing ID,
+    /// record it for replay, and republish it to SSE clients. Must be
+    /// started once, before the HTTP server accepts connections.
+    pub fn spawn_event_sequencer(self: &Arc<Self>) {
+        let mut event_rx = self.event_tx.subscribe();
+        let state = self.clone();
+        tokio::spawn(async move {
+            loop {
+                match crate::classify_broadcast_recv_result(event_rx.recv().await) {
+                    crate::BroadcastRecvResult::Event(event) => {
+                        let sequenced = match state.event_history.lock() {
+                            Ok(mut history) => history.push(event),
+                            Err(poisoned) => poisoned.into_inner().push(event),
+                        };
+                        let _ = state.sequenced_event_tx.send(sequenced);
+                    }
+                    crate::BroadcastRecvResult::Lagged(count) => {
+                        tracing::warn!(count, "API event sequencer lagged, events dropped");
+                    }
+                    crate::BroadcastRecvResult::Closed => break,
+                }
+            }
+        });
+    }
 }
 
 /// Extract (process_type, id_string) from a ProcessId.
@@ -855,3 +1267,44 @@ fn process_id_info(id: &ProcessId) -> (String, String) {
         ProcessId::Worker(worker_id) => ("worker".into(), worker_id.to_string()),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history_with(count: usize) -> EventHistory {
+        let mut history = EventHistory::new(100);
+        for _ in 0..count {
+            history.push(ApiEvent::ConfigReloaded);
+        }
+        history
+    }
+
+    #[test]
+    fn replays_events_after_last_event_id() {
+        let history = history_with(5);
+        let ids: Vec<u64> = history
+            .since(102)
+            .unwrap()
+            .iter()
+            .map(|event| event.id)
+            .collect();
+        assert_eq!(ids, vec![103, 104, 105]);
+        assert!(history.since(105).unwrap().is_empty());
+    }
+
+    #[test]
+    fn evicted_resume_point_is_reported_as_gap() {
+        let history = history_with(EVENT_HISTORY_CAPACITY + 10);
+        assert!(history.since(100).is_none());
+        assert_eq!(history.since(110).unwrap().len(), EVENT_HISTORY_CAPACITY);
+    }
+
+    #[test]
+    fn resume_point_from_previous_process_is_a_gap() {
+        let history = history_with(3);
+        assert!(history.since(42).is_none());
+        assert!(EventHistory::new(100).since(42).is_none());
+        assert!(EventHistory::new(100).since(100).unwrap().is_empty());
+    }
+}
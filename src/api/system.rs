@@ -1,13 +1,15 @@
-use super::state::{ApiEvent, ApiState};
+use super::access;
+use super::server::ApiPrincipal;
+use super::state::{ApiEvent, ApiState, SequencedEvent};
 
 use axum::Json;
 use axum::body::Bytes;
-use axum::extract::State;
-use axum::http::header;
+use axum::extract::{Extension, Query, State};
+use axum::http::{StatusCode, header};
 use axum::response::IntoResponse;
 use axum::response::Sse;
 use futures::stream::Stream;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::convert::Infallible;
 use std::io::Write as _;
 use std::path::Component;
@@ -16,32 +18,372 @@ use std::sync::Arc;
 use zip::CompressionMethod;
 use zip::write::SimpleFileOptions;
 
-#[derive(Serialize)]
+#[derive(Serialize, utoipa::ToSchema)]
 pub(super) struct HealthResponse {
     status: &'static str,
+    /// Per-component results, present only for `?deep=true`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    components: Option<Vec<ComponentHealth>>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct ComponentHealth {
+    /// `database`, `provider`, or `embedding`.
+    component: &'static str,
+    /// Agent ID for databases, provider ID for providers.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    target: Option<String>,
+    status: &'static str,
+    latency_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Deserialize, Default, utoipa::IntoParams)]
+pub(super) struct HealthQuery {
+    /// Also check agent databases, LLM providers, and the embedding model.
+    #[serde(default)]
+    deep: bool,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
 pub(super) struct IdleResponse {
     idle: bool,
     active_workers: usize,
     active_branches: usize,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, utoipa::ToSchema)]
 pub(super) struct StatusResponse {
     status: &'static str,
     version: &'static str,
     pid: u32,
     uptime_seconds: u64,
+    /// This replica's identifier for leader election.
+    instance_id: &'static str,
+    leadership: LeadershipStatus,
+    /// Config problems found at startup that weren't corrected and still
+    /// need fixing. Corrected values are only logged.
+    config_issues: Vec<crate::config::ConfigIssue>,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct LeadershipStatus {
+    election_enabled: bool,
+    /// Per-agent lease state. This replica runs an agent's singleton duties
+    /// (cron, bulletins, maintenance) only when `is_leader` is true.
+    agents: std::collections::BTreeMap<String, crate::leader::LeaderStatus>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/health",
+    tag = "system",
+    params(HealthQuery),
+    responses(
+        (status = 200, description = "The API is up (and, with `deep`, every component answered)", body = HealthResponse),
+        (status = 503, description = "A deep check failed", body = HealthResponse),
+    )
+)]
+pub(super) async fn health(
+    State(state): State<Arc<ApiState>>,
+    Query(query): Query<HealthQuery>,
+) -> impl IntoResponse {
+    if !query.deep {
+        return (
+            StatusCode::OK,
+            Json(HealthResponse {
+                status: "ok",
+                components: None,
+            }),
+        );
+    }
+
+    let components = deep_health_checks(&state).await;
+    let healthy = components.iter().all(|component| component.error.is_none());
+    let (status_code, status) = if healthy {
+        (StatusCode::OK, "ok")
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, "degraded")
+    };
+    (
+        status_code,
+        Json(HealthResponse {
+            status,
+            components: Some(components),
+        }),
+    )
+}
+
+/// Upper bound for a single deep health check.
+const DEEP_HEALTH_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Ping every agent database, every provider used by an agent's channel
+/// model, and the embedding model, concurrently.
+async fn deep_health_checks(state: &ApiState) -> Vec<ComponentHealth> {
+    let pools = state.agent_pools.load();
+    let runtime_configs = state.runtime_configs.load();
+    let llm_manager = state.llm_manager.read().await.clone();
+    let embedding_model = state.embedding_model.read().await.clone();
+
+    let mut agent_ids: Vec<&String> = pools.keys().collect();
+    agent_ids.sort();
+    let database_checks = agent_ids.into_iter().map(|agent_id| {
+        let pool = pools[agent_id].clone();
+        timed_check("database", Some(agent_id.clone()), async move {
+            sqlx::query("SELECT 1")
+                .execute(&pool)
+                .await
+                .map(|_| ())
+                .map_err(|error| error.to_string())
+        })
+    });
+    let mut components = futures::future::join_all(database_checks).await;
+
+    match &llm_manager {
+        Some(manager) => {
+            let provider_ids: std::collections::BTreeSet<String> = runtime_configs
+                .values()
+                .filter_map(|runtime_config| {
+                    let model = runtime_config.routing.load().channel.clone();
+                    manager
+                        .resolve_model(&model)
+                        .ok()
+                        .map(|(provider, _)| provider)
+                })
+                .collect();
+            let provider_checks = provider_ids.into_iter().map(|provider_id| {
+                let manager = manager.clone();
+                timed_check("provider", Some(provider_id.clone()), async move {
+                    let provider = manager
+                        .get_provider(&provider_id)
+                        .map_err(|error| error.to_string())?;
+                    // Any HTTP answer means the endpoint is reachable; auth
+                    // and routing errors still prove the network path works.
+                    manager
+                        .http_client()
+                        .get(&provider.base_url)
+                        .send()
+                        .await
+                        .map(|_| ())
+                        .map_err(|error| error.to_string())
+                })
+            });
+            components.extend(futures::future::join_all(provider_checks).await);
+        }
+        None => components.push(ComponentHealth {
+            component: "provider",
+            target: None,
+            status: "error",
+            latency_ms: 0,
+            error: Some("LLM manager not initialized".into()),
+        }),
+    }
+
+    components.push(match embedding_model {
+        Some(model) => {
+            timed_check("embedding", None, async move {
+                match model.embed_one("health check").await {
+                    Ok(vector) if !vector.is_empty() => Ok(()),
+                    Ok(_) => Err("embedding model returned an empty vector".to_string()),
+                    Err(error) => Err(error.to_string()),
+                }
+            })
+            .await
+        }
+        None => ComponentHealth {
+            component: "embedding",
+            target: None,
+            status: "error",
+            latency_ms: 0,
+            error: Some("embedding model not loaded".into()),
+        },
+    });
+
+    components
+}
+
+async fn timed_check(
+    component: &'static str,
+    target: Option<String>,
+    check: impl std::future::Future<Output = Result<(), String>>,
+) -> ComponentHealth {
+    let started = std::time::Instant::now();
+    let result = match tokio::time::timeout(DEEP_HEALTH_TIMEOUT, check).await {
+        Ok(result) => result,
+        Err(_) => Err(format!(
+            "timed out after {}s",
+            DEEP_HEALTH_TIMEOUT.as_secs()
+        )),
+    };
+    ComponentHealth {
+        component,
+        target,
+        status: if result.is_ok() { "ok" } else { "error" },
+        latency_ms: started.elapsed().as_millis() as u64,
+        error: result.err(),
+    }
+}
+
+/// How long a single agent database may take to answer the readiness ping.
+const READINESS_DATABASE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct ReadinessResponse {
+    ready: bool,
+    agents_initialized: bool,
+    database_reachable: bool,
+    provider_available: bool,
+    require_all_agents: bool,
+    require_provider: bool,
+    agents: Vec<AgentReadiness>,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct AgentReadiness {
+    agent_id: String,
+    ready: bool,
+    database_reachable: bool,
+    provider_available: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<String>,
+}
+
+/// Liveness probe. Answers as long as the process can serve HTTP.
+#[utoipa::path(
+    get,
+    path = "/healthz",
+    tag = "system",
+    security(()),
+    responses((status = 200, description = "The process is alive", body = HealthResponse))
+)]
+pub(super) async fn healthz() -> Json<HealthResponse> {
+    Json(HealthResponse {
+        status: "ok",
+        components: None,
+    })
 }
 
-pub(super) async fn health() -> Json<HealthResponse> {
-    Json(HealthResponse { status: "ok" })
+/// Readiness probe. Reports 503 until agents are initialized, their
+/// databases answer, and (unless disabled) a provider is usable.
+#[utoipa::path(
+    get,
+    path = "/readyz",
+    tag = "system",
+    security(()),
+    responses(
+        (status = 200, description = "Ready to serve traffic", body = ReadinessResponse),
+        (status = 503, description = "Not ready yet", body = ReadinessResponse),
+    )
+)]
+pub(super) async fn readyz(State(state): State<Arc<ApiState>>) -> impl IntoResponse {
+    let readiness_config = state.readiness;
+    let agent_configs = state.agent_configs.load();
+    let pools = state.agent_pools.load();
+    let runtime_configs = state.runtime_configs.load();
+    let llm_manager = state.llm_manager.read().await.clone();
+
+    let mut agents = Vec::with_capacity(agent_configs.len());
+    for agent in agent_configs.iter() {
+        let database_reachable = match pools.get(&agent.id) {
+            Some(pool) => matches!(
+                tokio::time::timeout(
+                    READINESS_DATABASE_TIMEOUT,
+                    sqlx::query("SELECT 1").execute(pool),
+                )
+                .await,
+                Ok(Ok(_))
+            ),
+            None => false,
+        };
+
+        let model = runtime_configs
+            .get(&agent.id)
+            .map(|runtime_config| runtime_config.routing.load().channel.clone());
+        let provider_available = match (&llm_manager, &model) {
+            (Some(manager), Some(model)) => {
+                let cooldown_secs = runtime_configs
+                    .get(&agent.id)
+                    .map(|runtime_config| runtime_config.routing.load().rate_limit_cooldown_secs)
+                    .unwrap_or_default();
+                match manager.resolve_model(model) {
+                    Ok((provider_id, _)) => {
+                        manager.has_provider_credentials(&provider_id).await
+                            && !manager.is_rate_limited(model, cooldown_secs).await
+                    }
+                    Err(_) => false,
+                }
+            }
+            _ => false,
+        };
+
+        let reason = if !database_reachable {
+            Some("database unreachable".to_string())
+        } else if readiness_config.require_provider && !provider_available {
+            Some(match &model {
+                Some(model) => format!("no usable provider for {model}"),
+                None => "agent runtime not initialized".to_string(),
+            })
+        } else {
+            None
+        };
+
+        agents.push(AgentReadiness {
+            agent_id: agent.id.clone(),
+            ready: reason.is_none(),
+            database_reachable,
+            provider_available,
+            model,
+            reason,
+        });
+    }
+
+    // A fresh install with no agents configured has nothing to wait for.
+    let no_agents = agents.is_empty();
+    let agents_initialized = agent_configs
+        .iter()
+        .all(|agent| pools.contains_key(&agent.id) && runtime_configs.contains_key(&agent.id));
+    let database_reachable = no_agents || agents.iter().any(|agent| agent.database_reachable);
+    let provider_available = no_agents || agents.iter().any(|agent| agent.provider_available);
+    let agents_ready = if readiness_config.require_all_agents {
+        agents.iter().all(|agent| agent.ready)
+    } else {
+        no_agents || agents.iter().any(|agent| agent.ready)
+    };
+    let ready = agents_initialized
+        && database_reachable
+        && (provider_available || !readiness_config.require_provider)
+        && agents_ready;
+
+    let status = if ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (
+        status,
+        Json(ReadinessResponse {
+            ready,
+            agents_initialized,
+            database_reachable,
+            provider_available,
+            require_all_agents: readiness_config.require_all_agents,
+            require_provider: readiness_config.require_provider,
+            agents,
+        }),
+    )
 }
 
 /// Reports whether the instance is idle (no active workers or branches).
 /// Used by the platform to gate rolling updates.
+#[utoipa::path(
+    get,
+    path = "/api/idle",
+    tag = "system",
+    responses((status = 200, body = IdleResponse))
+)]
 pub(super) async fn idle(State(state): State<Arc<ApiState>>) -> Json<IdleResponse> {
     let blocks = state.channel_status_blocks.read().await;
     let mut total_workers = 0;
@@ -60,75 +402,243 @@ pub(super) async fn idle(State(state): State<Arc<ApiState>>) -> Json<IdleRespons
     })
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/status",
+    tag = "system",
+    responses((status = 200, body = StatusResponse))
+)]
 pub(super) async fn status(State(state): State<Arc<ApiState>>) -> Json<StatusResponse> {
     let uptime = state.started_at.elapsed();
+    let leases = state.leader_leases.load();
+    let mut agents = std::collections::BTreeMap::new();
+    for (agent_id, lease) in leases.iter() {
+        agents.insert(agent_id.clone(), lease.status().await);
+    }
     Json(StatusResponse {
         status: "running",
         version: env!("CARGO_PKG_VERSION"),
         pid: std::process::id(),
         uptime_seconds: uptime.as_secs(),
+        instance_id: crate::leader::instance_id(),
+        leadership: LeadershipStatus {
+            election_enabled: state.leader_election.enabled,
+            agents,
+        },
+        config_issues: state.config_issues.load().as_ref().clone(),
     })
 }
 
-/// SSE endpoint streaming all agent events to connected clients.
+#[derive(Deserialize, Default, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(super) struct EventsQuery {
+    /// Comma-separated agent IDs.
+    #[serde(default)]
+    agent_id: Option<String>,
+    /// Comma-separated channel IDs.
+    #[serde(default)]
+    channel_id: Option<String>,
+    /// Comma-separated event names (e.g. `inbound_message,worker_started`).
+    #[serde(default)]
+    event_types: Option<String>,
+    /// Resume point for clients that can't set the `Last-Event-ID` header.
+    #[serde(default)]
+    last_event_id: Option<u64>,
+}
+
+/// Server-side subscription filter for the SSE stream. Each dimension left
+/// empty matches everything. When channels are given, events without a
+/// channel are dropped, except instance-wide ones like `config_reloaded`.
+#[derive(Debug, Default)]
+struct EventFilter {
+    agent_ids: Vec<String>,
+    channel_ids: Vec<String>,
+    event_types: Vec<String>,
+}
+
+impl EventFilter {
+    fn from_query(query: EventsQuery) -> Result<Self, String> {
+        let filter = Self {
+            agent_ids: split_list(query.agent_id),
+            channel_ids: split_list(query.channel_id),
+            event_types: split_list(query.event_types),
+        };
+        if let Some(unknown) = filter
+            .event_types
+            .iter()
+            .find(|event_type| !ApiEvent::EVENT_TYPES.contains(&event_type.as_str()))
+        {
+            return Err(format!("unknown event type '{unknown}'"));
+        }
+        Ok(filter)
+    }
+
+    fn matches(&self, event: &ApiEvent) -> bool {
+        if !self.event_types.is_empty()
+            && !self
+                .event_types
+                .iter()
+                .any(|event_type| event_type == event.event_type())
+        {
+            return false;
+        }
+        if matches!(event, ApiEvent::ConfigReloaded) {
+            return true;
+        }
+        if !self.agent_ids.is_empty()
+            && !self
+                .agent_ids
+                .iter()
+                .any(|agent_id| event.involves_agent(agent_id))
+        {
+            return false;
+        }
+        if !self.channel_ids.is_empty() {
+            return event.channel_id().is_some_and(|channel_id| {
+                self.channel_ids.iter().any(|wanted| wanted == channel_id)
+            });
+        }
+        true
+    }
+}
+
+pub(super) fn split_list(value: Option<String>) -> Vec<String> {
+    value
+        .map(|value| {
+            value
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// SSE endpoint streaming agent events to connected clients, optionally
+/// filtered by agent, channel, and event type so filtered-out events are
+/// never serialized.
+///
+/// Every event carries an `id`. A client reconnecting with `Last-Event-ID`
+/// (or `?last_event_id=`) first receives the events it missed from the
+/// in-memory history; if they are no longer available it gets a `lagged`
+/// event instead and should refetch state.
+#[utoipa::path(
+    get,
+    path = "/api/events",
+    tag = "system",
+    params(
+        EventsQuery,
+        ("Last-Event-ID" = Option<u64>, Header, description = "Resume after this event ID"),
+    ),
+    responses(
+        (status = 200, description = "Server-sent event stream of `ApiEvent`s", content_type = "text/event-stream", body = String),
+        (status = 400, description = "Unknown event type or malformed resume ID"),
+    )
+)]
 pub(super) async fn events_sse(
     State(state): State<Arc<ApiState>>,
-) -> Sse<impl Stream<Item = Result<axum::response::sse::Event, Infallible>>> {
-    let mut rx = state.event_tx.subscribe();
+    principal: Option<Extension<ApiPrincipal>>,
+    headers: axum::http::HeaderMap,
+    Query(mut query): Query<EventsQuery>,
+) -> Result<
+    Sse<impl Stream<Item = Result<axum::response::sse::Event, Infallible>>>,
+    (StatusCode, String),
+> {
+    let last_event_id = match headers.get("last-event-id") {
+        Some(value) => Some(
+            value
+                .to_str()
+                .ok()
+                .and_then(|value| value.trim().parse::<u64>().ok())
+                .ok_or_else(|| {
+                    (
+                        StatusCode::BAD_REQUEST,
+                        "invalid Last-Event-ID header".to_string(),
+                    )
+                })?,
+        ),
+        None => query.last_event_id.take(),
+    };
+    let mut filter =
+        EventFilter::from_query(query).map_err(|error| (StatusCode::BAD_REQUEST, error))?;
+    // Tenants only ever see their own agents' events.
+    access::restrict_to_agents(principal.as_deref(), &mut filter.agent_ids)
+        .map_err(|error| (StatusCode::FORBIDDEN, error))?;
+
+    // Subscribe before snapshotting history so nothing falls between them;
+    // overlap is removed by skipping live IDs already replayed.
+    let mut rx = state.sequenced_event_tx.subscribe();
+    let backlog = last_event_id.map(|last_event_id| {
+        let history = match state.event_history.lock() {
+            Ok(history) => history,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        history.since(last_event_id)
+    });
 
     let stream = async_stream::stream! {
-        loop {
-            match rx.recv().await {
-                Ok(event) => {
-                    if let Ok(json) = serde_json::to_string(&event) {
-                        let event_type = match &event {
-                            ApiEvent::InboundMessage { .. } => "inbound_message",
-                            ApiEvent::OutboundMessage { .. } => "outbound_message",
-                            ApiEvent::OutboundMessageDelta { .. } => "outbound_message_delta",
-                            ApiEvent::TypingState { .. } => "typing_state",
-                            ApiEvent::WorkerStarted { .. } => "worker_started",
-                            ApiEvent::WorkerStatusUpdate { .. } => "worker_status",
-                            ApiEvent::WorkerIdle { .. } => "worker_idle",
-                            ApiEvent::WorkerCompleted { .. } => "worker_completed",
-                            ApiEvent::BranchStarted { .. } => "branch_started",
-                            ApiEvent::BranchCompleted { .. } => "branch_completed",
-                            ApiEvent::ToolStarted { .. } => "tool_started",
-                            ApiEvent::ToolCompleted { .. } => "tool_completed",
-                            ApiEvent::ConfigReloaded => "config_reloaded",
-                            ApiEvent::AgentMessageSent { .. } => "agent_message_sent",
-                            ApiEvent::AgentMessageReceived { .. } => "agent_message_received",
-                            ApiEvent::TaskUpdated { .. } => "task_updated",
-                            ApiEvent::OpenCodePartUpdated { .. } => "opencode_part_updated",
-                            ApiEvent::WorkerText { .. } => "worker_text",
-                            ApiEvent::CortexChatMessage { .. } => "cortex_chat_message",
-                        };
-                        yield Ok(axum::response::sse::Event::default()
-                            .event(event_type)
-                            .data(json));
+        let mut replayed_through = last_event_id.unwrap_or(0);
+        match backlog {
+            Some(Some(missed)) => {
+                for sequenced in missed {
+                    replayed_through = sequenced.id;
+                    if let Some(event) = sse_event(&filter, &sequenced) {
+                        yield Ok(event);
                     }
                 }
-                Err(error) => {
-                    match crate::classify_broadcast_recv_result::<ApiEvent>(Err(error)) {
-                        crate::BroadcastRecvResult::Lagged(count) => {
-                            tracing::debug!(count, "SSE client lagged");
-                            yield Ok(axum::response::sse::Event::default()
-                                .event("lagged")
-                                .data(format!("{{\"skipped\":{count}}}")));
-                        }
-                        crate::BroadcastRecvResult::Closed => break,
-                        crate::BroadcastRecvResult::Event(_) => unreachable!(
-                            "classifying an Err recv result should never produce Event"
-                        ),
+            }
+            Some(None) => {
+                tracing::debug!(?last_event_id, "SSE resume point no longer in history");
+                yield Ok(axum::response::sse::Event::default()
+                    .event("lagged")
+                    .data("{\"skipped\":null,\"reason\":\"history_unavailable\"}"));
+            }
+            None => {}
+        }
+
+        loop {
+            match crate::classify_broadcast_recv_result(rx.recv().await) {
+                crate::BroadcastRecvResult::Event(sequenced) => {
+                    if sequenced.id <= replayed_through {
+                        continue;
+                    }
+                    if let Some(event) = sse_event(&filter, &sequenced) {
+                        yield Ok(event);
                     }
                 }
+                crate::BroadcastRecvResult::Lagged(count) => {
+                    tracing::debug!(count, "SSE client lagged");
+                    yield Ok(axum::response::sse::Event::default()
+                        .event("lagged")
+                        .data(format!("{{\"skipped\":{count}}}")));
+                }
+                crate::BroadcastRecvResult::Closed => break,
             }
         }
     };
 
-    Sse::new(stream).keep_alive(
+    Ok(Sse::new(stream).keep_alive(
         axum::response::sse::KeepAlive::new()
             .interval(std::time::Duration::from_secs(15))
             .text("ping"),
+    ))
+}
+
+/// Render a sequenced event for SSE, or `None` if the filter drops it.
+fn sse_event(
+    filter: &EventFilter,
+    sequenced: &SequencedEvent,
+) -> Option<axum::response::sse::Event> {
+    if !filter.matches(&sequenced.event) {
+        return None;
+    }
+    let json = serde_json::to_string(&sequenced.event).ok()?;
+    Some(
+        axum::response::sse::Event::default()
+            .id(sequenced.id.to_string())
+            .event(sequenced.event.event_type())
+            .data(json),
     )
 }
 
@@ -523,3 +1033,94 @@ fn add_file_to_zip(
     writer.write_all(&file_bytes)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{EventFilter, EventsQuery};
+
+    use crate::api::access::restrict_to_agents;
+    use crate::api::server::ApiPrincipal;
+    use crate::api::state::ApiEvent;
+    use crate::config::ApiTokenScope;
+
+    fn typing(agent_id: &str, channel_id: &str) -> ApiEvent {
+        ApiEvent::TypingState {
+            agent_id: agent_id.into(),
+            channel_id: channel_id.into(),
+            is_typing: true,
+        }
+    }
+
+    #[test]
+    fn empty_filter_matches_everything() {
+        let filter = EventFilter::from_query(EventsQuery::default()).unwrap();
+        assert!(filter.matches(&typing("main", "portal:1")));
+        assert!(filter.matches(&ApiEvent::ConfigReloaded));
+    }
+
+    #[test]
+    fn filters_by_agent_channel_and_type() {
+        let filter = EventFilter::from_query(EventsQuery {
+            agent_id: Some("main, ops".into()),
+            channel_id: Some("portal:1".into()),
+            event_types: Some("typing_state,config_reloaded".into()),
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert!(filter.matches(&typing("ops", "portal:1")));
+        assert!(!filter.matches(&typing("other", "portal:1")));
+        assert!(!filter.matches(&typing("main", "portal:2")));
+        assert!(filter.matches(&ApiEvent::ConfigReloaded));
+        assert!(!filter.matches(&ApiEvent::WorkerIdle {
+            agent_id: "main".into(),
+            channel_id: Some("portal:1".into()),
+            worker_id: "w1".into(),
+        }));
+    }
+
+    #[test]
+    fn restricted_tokens_only_see_their_agents() {
+        let tenant = ApiPrincipal {
+            name: "tenant".into(),
+            scope: ApiTokenScope::Read,
+            agents: vec!["main".into()],
+        };
+
+        let mut filter = EventFilter::from_query(EventsQuery::default()).unwrap();
+        restrict_to_agents(Some(&tenant), &mut filter.agent_ids).unwrap();
+        assert!(filter.matches(&typing("main", "portal:1")));
+        assert!(!filter.matches(&typing("ops", "portal:1")));
+
+        let mut filter = EventFilter::from_query(EventsQuery {
+            agent_id: Some("main,ops".into()),
+            ..Default::default()
+        })
+        .unwrap();
+        assert!(restrict_to_agents(Some(&tenant), &mut filter.agent_ids).is_err());
+    }
+
+    #[test]
+    fn channel_filter_drops_channelless_events() {
+        let filter = EventFilter::from_query(EventsQuery {
+            channel_id: Some("portal:1".into()),
+            ..Default::default()
+        })
+        .unwrap();
+        assert!(!filter.matches(&ApiEvent::WorkerText {
+            agent_id: "main".into(),
+            worker_id: "w1".into(),
+            text: "thinking".into(),
+        }));
+    }
+
+    #[test]
+    fn unknown_event_types_are_rejected() {
+        let error = EventFilter::from_query(EventsQuery {
+            event_types: Some("inbound_message,not_a_thing".into()),
+            ..Default::default()
+        })
+        .unwrap_err();
+        assert!(error.contains("not_a_thing"));
+    }
+}
@@ -1,7 +1,8 @@
 use super::state::ApiState;
 
+use super::access::{AgentJson, AgentQuery, AgentScoped};
 use axum::Json;
-use axum::extract::{Path, Query, State};
+use axum::extract::{Path, State};
 use axum::http::StatusCode;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -17,11 +18,23 @@ pub(super) struct TaskListQuery {
     limit: i64,
 }
 
+impl AgentScoped for TaskListQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
 #[derive(Deserialize)]
 pub(super) struct TaskGetQuery {
     agent_id: String,
 }
 
+impl AgentScoped for TaskGetQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
 #[derive(Deserialize)]
 pub(super) struct CreateTaskRequest {
     agent_id: String,
@@ -42,6 +55,12 @@ pub(super) struct CreateTaskRequest {
     created_by: Option<String>,
 }
 
+impl AgentScoped for CreateTaskRequest {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
 #[derive(Deserialize)]
 pub(super) struct UpdateTaskRequest {
     agent_id: String,
@@ -65,11 +84,23 @@ pub(super) struct UpdateTaskRequest {
     approved_by: Option<String>,
 }
 
+impl AgentScoped for UpdateTaskRequest {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
 #[derive(Deserialize)]
 pub(super) struct DeleteTaskQuery {
     agent_id: String,
 }
 
+impl AgentScoped for DeleteTaskQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
 #[derive(Serialize)]
 pub(super) struct TaskListResponse {
     tasks: Vec<crate::tasks::Task>,
@@ -92,7 +123,7 @@ fn default_task_limit() -> i64 {
 
 pub(super) async fn list_tasks(
     State(state): State<Arc<ApiState>>,
-    Query(query): Query<TaskListQuery>,
+    AgentQuery(query): AgentQuery<TaskListQuery>,
 ) -> Result<Json<TaskListResponse>, StatusCode> {
     let stores = state.task_stores.load();
     let store = stores.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
@@ -122,7 +153,7 @@ pub(super) async fn list_tasks(
 pub(super) async fn get_task(
     State(state): State<Arc<ApiState>>,
     Path(number): Path<i64>,
-    Query(query): Query<TaskGetQuery>,
+    AgentQuery(query): AgentQuery<TaskGetQuery>,
 ) -> Result<Json<TaskResponse>, StatusCode> {
     let stores = state.task_stores.load();
     let store = stores.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
@@ -141,7 +172,7 @@ pub(super) async fn get_task(
 
 pub(super) async fn create_task(
     State(state): State<Arc<ApiState>>,
-    Json(request): Json<CreateTaskRequest>,
+    AgentJson(request): AgentJson<CreateTaskRequest>,
 ) -> Result<Json<TaskResponse>, StatusCode> {
     let stores = state.task_stores.load();
     let store = stores.get(&request.agent_id).ok_or(StatusCode::NOT_FOUND)?;
@@ -189,7 +220,7 @@ pub(super) async fn create_task(
 pub(super) async fn update_task(
     State(state): State<Arc<ApiState>>,
     Path(number): Path<i64>,
-    Json(request): Json<UpdateTaskRequest>,
+    AgentJson(request): AgentJson<UpdateTaskRequest>,
 ) -> Result<Json<TaskResponse>, StatusCode> {
     let stores = state.task_stores.load();
     let store = stores.get(&request.agent_id).ok_or(StatusCode::NOT_FOUND)?;
@@ -245,7 +276,7 @@ pub(super) async fn update_task(
 pub(super) async fn delete_task(
     State(state): State<Arc<ApiState>>,
     Path(number): Path<i64>,
-    Query(query): Query<DeleteTaskQuery>,
+    AgentQuery(query): AgentQuery<DeleteTaskQuery>,
 ) -> Result<Json<TaskActionResponse>, StatusCode> {
     let stores = state.task_stores.load();
     let store = stores.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
@@ -281,7 +312,7 @@ pub(super) async fn delete_task(
 pub(super) async fn approve_task(
     State(state): State<Arc<ApiState>>,
     Path(number): Path<i64>,
-    Json(request): Json<UpdateTaskRequest>,
+    AgentJson(request): AgentJson<UpdateTaskRequest>,
 ) -> Result<Json<TaskResponse>, StatusCode> {
     let stores = state.task_stores.load();
     let store = stores.get(&request.agent_id).ok_or(StatusCode::NOT_FOUND)?;
@@ -321,7 +352,7 @@ pub(super) async fn approve_task(
 pub(super) async fn execute_task(
     State(state): State<Arc<ApiState>>,
     Path(number): Path<i64>,
-    Json(request): Json<UpdateTaskRequest>,
+    AgentJson(request): AgentJson<UpdateTaskRequest>,
 ) -> Result<Json<TaskResponse>, StatusCode> {
     let stores = state.task_stores.load();
     let store = stores.get(&request.agent_id).ok_or(StatusCode::NOT_FOUND)?;
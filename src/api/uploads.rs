@@ -0,0 +1,243 @@
+//! Files handed to the cortex from the dashboard.
+//!
+//! `POST /api/uploads` stores each file under the agent workspace's
+//! `uploads/` directory and records it in the `uploads` table. Cortex chat
+//! messages reference the returned IDs and the cortex gets the file paths to
+//! work on with its file and shell tools.
+
+use super::access::{AgentQuery, AgentScoped};
+use super::state::ApiState;
+use crate::agent::channel_attachments::sanitize_filename;
+
+use axum::Json;
+use axum::extract::State;
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use sqlx::Row as _;
+
+use std::sync::Arc;
+
+/// Most uploads a single cortex chat message can reference.
+pub(super) const MAX_ATTACHMENTS_PER_MESSAGE: usize = 10;
+
+#[derive(Deserialize, utoipa::IntoParams)]
+pub(super) struct UploadQuery {
+    agent_id: String,
+}
+
+impl AgentScoped for UploadQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
+/// A stored upload.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub(super) struct Upload {
+    /// Pass in `attachments` on `/api/cortex-chat/send`.
+    id: String,
+    /// Name the file was uploaded with.
+    filename: String,
+    mime_type: String,
+    size_bytes: i64,
+    /// Absolute path inside the agent workspace.
+    path: String,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct UploadResponse {
+    uploads: Vec<Upload>,
+}
+
+/// Store one or more files (multipart parts with a filename) in the agent
+/// workspace for the cortex to work on.
+#[utoipa::path(
+    post,
+    path = "/api/uploads",
+    tag = "cortex",
+    params(UploadQuery),
+    request_body(content = String, content_type = "multipart/form-data", description = "One file part per upload"),
+    responses(
+        (status = 200, body = UploadResponse),
+        (status = 400, description = "Malformed multipart body, or no files in it"),
+        (status = 404, description = "Unknown agent"),
+        (status = 413, description = "Body larger than the upload limit"),
+    )
+)]
+pub(super) async fn upload_files(
+    State(state): State<Arc<ApiState>>,
+    AgentQuery(query): AgentQuery<UploadQuery>,
+    mut multipart: axum::extract::Multipart,
+) -> Result<Json<UploadResponse>, StatusCode> {
+    let pools = state.agent_pools.load();
+    let pool = pools.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
+    let workspaces = state.agent_workspaces.load();
+    let workspace = workspaces
+        .get(&query.agent_id)
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let uploads_dir = workspace.join("uploads");
+
+    tokio::fs::create_dir_all(&uploads_dir)
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, "failed to create uploads directory");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let mut uploads = Vec::new();
+
+    while let Some(field) = multipart.next_field().await.map_err(|error| {
+        tracing::warn!(%error, "failed to read multipart field");
+        StatusCode::BAD_REQUEST
+    })? {
+        // Plain form fields carry no file.
+        let Some(filename) = field.file_name().map(sanitize_filename) else {
+            continue;
+        };
+        let declared_mime = field
+            .content_type()
+            .map(str::to_string)
+            .filter(|mime| mime != "application/octet-stream");
+
+        let data = field.bytes().await.map_err(|error| {
+            tracing::warn!(%error, "failed to read upload field");
+            StatusCode::BAD_REQUEST
+        })?;
+        if data.is_empty() {
+            continue;
+        }
+
+        let id = uuid::Uuid::new_v4().to_string();
+        let saved_filename = format!("{}-{filename}", &id[..8]);
+        let disk_path = uploads_dir.join(&saved_filename);
+        let mime_type = declared_mime.unwrap_or_else(|| {
+            mime_guess::from_path(&filename)
+                .first_or_octet_stream()
+                .to_string()
+        });
+
+        tokio::fs::write(&disk_path, &data).await.map_err(|error| {
+            tracing::warn!(%error, path = %disk_path.display(), "failed to write upload");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+        let upload = Upload {
+            id,
+            filename,
+            mime_type,
+            size_bytes: data.len() as i64,
+            path: disk_path.to_string_lossy().into_owned(),
+        };
+        if let Err(error) = sqlx::query(
+            "INSERT INTO uploads (id, original_filename, saved_filename, mime_type, size_bytes, disk_path) \
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&upload.id)
+        .bind(&upload.filename)
+        .bind(&saved_filename)
+        .bind(&upload.mime_type)
+        .bind(upload.size_bytes)
+        .bind(&upload.path)
+        .execute(pool)
+        .await
+        {
+            tracing::warn!(%error, "failed to record upload");
+            let _ = tokio::fs::remove_file(&disk_path).await;
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+
+        tracing::info!(
+            agent_id = %query.agent_id,
+            upload_id = %upload.id,
+            filename = %upload.filename,
+            bytes = upload.size_bytes,
+            "file uploaded"
+        );
+        uploads.push(upload);
+    }
+
+    if uploads.is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    Ok(Json(UploadResponse { uploads }))
+}
+
+/// Load uploads by ID, in the order given. An unknown ID is a 400 so a stale
+/// reference doesn't silently drop a file from the message.
+pub(super) async fn load_uploads(
+    pool: &sqlx::SqlitePool,
+    ids: &[String],
+) -> Result<Vec<Upload>, StatusCode> {
+    if ids.len() > MAX_ATTACHMENTS_PER_MESSAGE {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let mut uploads = Vec::with_capacity(ids.len());
+    for id in ids {
+        let row = sqlx::query(
+            "SELECT id, original_filename, mime_type, size_bytes, disk_path FROM uploads WHERE id = ?",
+        )
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, upload_id = %id, "failed to load upload");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .ok_or(StatusCode::BAD_REQUEST)?;
+
+        uploads.push(Upload {
+            id: row.try_get("id").unwrap_or_default(),
+            filename: row.try_get("original_filename").unwrap_or_default(),
+            mime_type: row.try_get("mime_type").unwrap_or_default(),
+            size_bytes: row.try_get("size_bytes").unwrap_or_default(),
+            path: row.try_get("disk_path").unwrap_or_default(),
+        });
+    }
+    Ok(uploads)
+}
+
+/// Append the attached files to a cortex chat message. Stored with the
+/// message, so regenerating or reloading the thread keeps the references.
+pub(super) fn with_attachment_note(message: &str, uploads: &[Upload]) -> String {
+    if uploads.is_empty() {
+        return message.to_string();
+    }
+    let mut text = format!("{message}\n\n[Attached files]");
+    for upload in uploads {
+        text.push_str(&format!(
+            "\n- {} ({}, {} bytes): {}",
+            upload.filename, upload.mime_type, upload.size_bytes, upload.path
+        ));
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filenames_lose_directories_and_notes_list_paths() {
+        assert_eq!(sanitize_filename("../../etc/passwd"), "passwd");
+        assert_eq!(
+            sanitize_filename("C:\\data\\report.csv"),
+            "C__data_report.csv"
+        );
+        assert_eq!(sanitize_filename(".."), "attachment");
+
+        let upload = Upload {
+            id: "1".into(),
+            filename: "sales.csv".into(),
+            mime_type: "text/csv".into(),
+            size_bytes: 42,
+            path: "/workspace/uploads/abcd1234-sales.csv".into(),
+        };
+        assert_eq!(with_attachment_note("hi", &[]), "hi");
+        assert_eq!(
+            with_attachment_note("sum column B", &[upload]),
+            "sum column B\n\n[Attached files]\n- sales.csv (text/csv, 42 bytes): /workspace/uploads/abcd1234-sales.csv"
+        );
+    }
+}
@@ -0,0 +1,126 @@
+//! LLM usage and estimated cost per agent, rolled up per day.
+
+use super::access::{AgentQuery, AgentScoped};
+use super::state::ApiState;
+
+use crate::llm::usage::DailyUsage;
+
+use axum::Json;
+use axum::extract::State;
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Default reporting window when `from` is omitted.
+const DEFAULT_WINDOW_DAYS: i64 = 30;
+
+#[derive(Deserialize, utoipa::IntoParams)]
+pub(super) struct UsageQuery {
+    /// Restrict to one agent. All agents when omitted.
+    agent_id: Option<String>,
+    /// First UTC day, `YYYY-MM-DD`. Defaults to 30 days before `to`.
+    from: Option<chrono::NaiveDate>,
+    /// Last UTC day (inclusive), `YYYY-MM-DD`. Defaults to today.
+    to: Option<chrono::NaiveDate>,
+}
+
+impl AgentScoped for UsageQuery {
+    fn agent_id(&self) -> Option<&str> {
+        self.agent_id.as_deref()
+    }
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct UsageResponse {
+    from: String,
+    to: String,
+    /// Agents ordered by estimated cost, highest first.
+    agents: Vec<AgentUsage>,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct AgentUsage {
+    agent_id: String,
+    total: UsageTotals,
+    daily: Vec<DailyUsage>,
+}
+
+#[derive(Serialize, Default, utoipa::ToSchema)]
+pub(super) struct UsageTotals {
+    calls: i64,
+    input_tokens: i64,
+    output_tokens: i64,
+    cached_input_tokens: i64,
+    estimated_cost: f64,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/agents/usage",
+    tag = "agents",
+    params(UsageQuery),
+    responses(
+        (status = 200, description = "Daily usage per agent", body = UsageResponse),
+        (status = 400, description = "`from` is after `to`"),
+        (status = 404, description = "Unknown agent"),
+    )
+)]
+pub(super) async fn agent_usage(
+    State(state): State<Arc<ApiState>>,
+    AgentQuery(query): AgentQuery<UsageQuery>,
+) -> Result<Json<UsageResponse>, StatusCode> {
+    let to = query.to.unwrap_or_else(|| chrono::Utc::now().date_naive());
+    let from = query
+        .from
+        .unwrap_or(to - chrono::Duration::days(DEFAULT_WINDOW_DAYS));
+    if from > to {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let pools = state.agent_pools.load();
+    let mut agent_ids: Vec<&String> = match &query.agent_id {
+        Some(agent_id) => vec![
+            pools
+                .get_key_value(agent_id)
+                .map(|(id, _)| id)
+                .ok_or(StatusCode::NOT_FOUND)?,
+        ],
+        None => pools.keys().collect(),
+    };
+    agent_ids.sort();
+
+    let mut agents = Vec::with_capacity(agent_ids.len());
+    for agent_id in agent_ids {
+        let daily = crate::llm::usage::daily_usage(&pools[agent_id], from, to)
+            .await
+            .map_err(|error| {
+                tracing::warn!(%error, %agent_id, "failed to load LLM usage");
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+        let total = daily.iter().fold(UsageTotals::default(), |mut total, day| {
+            total.calls += day.calls;
+            total.input_tokens += day.input_tokens;
+            total.output_tokens += day.output_tokens;
+            total.cached_input_tokens += day.cached_input_tokens;
+            total.estimated_cost += day.estimated_cost;
+            total
+        });
+        agents.push(AgentUsage {
+            agent_id: agent_id.clone(),
+            total,
+            daily,
+        });
+    }
+    agents.sort_by(|left, right| {
+        right
+            .total
+            .estimated_cost
+            .total_cmp(&left.total.estimated_cost)
+    });
+
+    Ok(Json(UsageResponse {
+        from: from.to_string(),
+        to: to.to_string(),
+        agents,
+    }))
+}
@@ -1,8 +1,9 @@
+use super::access::{AgentJson, AgentQuery, AgentScoped};
 use super::state::ApiState;
 use crate::{InboundMessage, MessageContent};
 
 use axum::Json;
-use axum::extract::{Query, State};
+use axum::extract::State;
 use axum::http::StatusCode;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -17,6 +18,12 @@ pub(super) struct WebChatSendRequest {
     message: String,
 }
 
+impl AgentScoped for WebChatSendRequest {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
 fn default_sender_name() -> String {
     "user".into()
 }
@@ -30,7 +37,7 @@ pub(super) struct WebChatSendResponse {
 /// event bus (`/api/events`), same as every other channel.
 pub(super) async fn webchat_send(
     State(state): State<Arc<ApiState>>,
-    axum::Json(request): axum::Json<WebChatSendRequest>,
+    AgentJson(request): AgentJson<WebChatSendRequest>,
 ) -> Result<Json<WebChatSendResponse>, StatusCode> {
     let manager = state
         .messaging_manager
@@ -76,6 +83,12 @@ pub(super) struct WebChatHistoryQuery {
     limit: i64,
 }
 
+impl AgentScoped for WebChatHistoryQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
 fn default_limit() -> i64 {
     100
 }
@@ -89,7 +102,7 @@ pub(super) struct WebChatHistoryMessage {
 
 pub(super) async fn webchat_history(
     State(state): State<Arc<ApiState>>,
-    Query(query): Query<WebChatHistoryQuery>,
+    AgentQuery(query): AgentQuery<WebChatHistoryQuery>,
 ) -> Result<Json<Vec<WebChatHistoryMessage>>, StatusCode> {
     let pools = state.agent_pools.load();
     let pool = pools.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
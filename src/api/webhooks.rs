@@ -0,0 +1,756 @@
+//! Outbound webhooks for API events.
+//!
+//! Subscriptions come from `[[api.webhooks]]` in config.toml or are
+//! registered through the API (persisted in `webhooks.redb`). A delivery task
+//! follows the sequenced event stream and POSTs each matching event as JSON,
+//! signed with HMAC-SHA256 when the subscription has a secret. Failed
+//! deliveries are retried with backoff; a receiver that stays down only loses
+//! the events it missed, it never blocks delivery to other subscribers.
+//!
+//! URLs registered through the API may not point at loopback, private, or
+//! link-local addresses, checked at registration and again before each
+//! delivery since DNS can change in between. Config webhooks are trusted.
+
+use super::state::{ApiEvent, ApiState};
+
+use crate::config::ApiWebhookConfig;
+use crate::tools::browser::is_blocked_ip;
+
+use arc_swap::ArcSwap;
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use hmac::{Hmac, Mac as _};
+use rand::RngCore as _;
+use redb::{Database, ReadableTable, TableDefinition};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Table: subscription ID -> JSON-encoded `WebhookSubscription`.
+const SUBSCRIPTIONS_TABLE: TableDefinition<&str, &[u8]> =
+    TableDefinition::new("webhook_subscriptions");
+
+/// Attempts per delivery, including the first.
+const MAX_ATTEMPTS: u32 = 5;
+
+/// Delay before the first retry; doubles with each attempt.
+const INITIAL_BACKOFF: Duration = Duration::from_secs(2);
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Requests in flight across all subscriptions. Events beyond this wait for
+/// a slot rather than piling up unbounded connections. A delivery gives its
+/// slot back while it backs off, so a receiver that is down can't starve the
+/// others.
+const MAX_CONCURRENT_DELIVERIES: usize = 16;
+
+/// Where a subscription was defined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookSource {
+    Config,
+    Api,
+}
+
+/// A URL subscribed to a set of event types.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct WebhookSubscription {
+    pub id: String,
+    pub url: String,
+    /// Event types to deliver, e.g. `worker_completed`. `*` matches all.
+    pub events: Vec<String>,
+    pub secret: Option<String>,
+    pub source: WebhookSource,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl std::fmt::Debug for WebhookSubscription {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebhookSubscription")
+            .field("id", &self.id)
+            .field("url", &self.url)
+            .field("events", &self.events)
+            .field("source", &self.source)
+            .finish()
+    }
+}
+
+impl WebhookSubscription {
+    fn matches(&self, event_type: &str) -> bool {
+        self.events
+            .iter()
+            .any(|pattern| pattern == "*" || pattern == event_type)
+    }
+}
+
+/// All webhook subscriptions: fixed ones from config plus API-registered
+/// ones persisted in redb.
+pub struct WebhookRegistry {
+    db: Database,
+    config_subscriptions: Vec<WebhookSubscription>,
+    /// Config and API subscriptions merged, for the delivery task.
+    subscriptions: ArcSwap<Vec<WebhookSubscription>>,
+}
+
+impl std::fmt::Debug for WebhookRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebhookRegistry")
+            .field("subscriptions", &self.subscriptions.load().len())
+            .finish()
+    }
+}
+
+impl WebhookRegistry {
+    /// Open or create the store at `path` and merge in the subscriptions
+    /// from `[[api.webhooks]]`.
+    pub fn new(path: &std::path::Path, config: &[ApiWebhookConfig]) -> crate::error::Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|error| anyhow::anyhow!(error))?;
+        }
+        let db = Database::create(path).map_err(|error| {
+            anyhow::anyhow!("failed to open webhook db at {}: {error}", path.display())
+        })?;
+
+        let write_txn = db.begin_write().map_err(|error| anyhow::anyhow!(error))?;
+        {
+            let _ = write_txn
+                .open_table(SUBSCRIPTIONS_TABLE)
+                .map_err(|error| anyhow::anyhow!(error))?;
+        }
+        write_txn.commit().map_err(|error| anyhow::anyhow!(error))?;
+
+        let now = chrono::Utc::now();
+        let config_subscriptions = config
+            .iter()
+            .map(|webhook| WebhookSubscription {
+                id: format!("config:{}", webhook.name),
+                url: webhook.url.clone(),
+                events: webhook.events.clone(),
+                secret: webhook.secret.clone(),
+                source: WebhookSource::Config,
+                created_at: now,
+            })
+            .collect();
+
+        let registry = Self {
+            db,
+            config_subscriptions,
+            subscriptions: ArcSwap::from_pointee(Vec::new()),
+        };
+        registry.refresh()?;
+        Ok(registry)
+    }
+
+    /// Every subscription, config-defined first.
+    pub fn list(&self) -> Arc<Vec<WebhookSubscription>> {
+        self.subscriptions.load_full()
+    }
+
+    /// Persist a new API subscription.
+    pub fn insert(&self, subscription: &WebhookSubscription) -> crate::error::Result<()> {
+        let data = serde_json::to_vec(subscription).map_err(|error| anyhow::anyhow!(error))?;
+        let write_txn = self
+            .db
+            .begin_write()
+            .map_err(|error| anyhow::anyhow!(error))?;
+        {
+            let mut table = write_txn
+                .open_table(SUBSCRIPTIONS_TABLE)
+                .map_err(|error| anyhow::anyhow!(error))?;
+            table
+                .insert(subscription.id.as_str(), data.as_slice())
+                .map_err(|error| anyhow::anyhow!(error))?;
+        }
+        write_txn.commit().map_err(|error| anyhow::anyhow!(error))?;
+        self.refresh()
+    }
+
+    /// Remove an API subscription. Returns false if there was none with
+    /// that ID.
+    pub fn remove(&self, id: &str) -> crate::error::Result<bool> {
+        let write_txn = self
+            .db
+            .begin_write()
+            .map_err(|error| anyhow::anyhow!(error))?;
+        let removed = {
+            let mut table = write_txn
+                .open_table(SUBSCRIPTIONS_TABLE)
+                .map_err(|error| anyhow::anyhow!(error))?;
+            table
+                .remove(id)
+                .map_err(|error| anyhow::anyhow!(error))?
+                .is_some()
+        };
+        write_txn.commit().map_err(|error| anyhow::anyhow!(error))?;
+        self.refresh()?;
+        Ok(removed)
+    }
+
+    fn refresh(&self) -> crate::error::Result<()> {
+        let read_txn = self
+            .db
+            .begin_read()
+            .map_err(|error| anyhow::anyhow!(error))?;
+        let table = read_txn
+            .open_table(SUBSCRIPTIONS_TABLE)
+            .map_err(|error| anyhow::anyhow!(error))?;
+
+        let mut stored = Vec::new();
+        for entry in table.iter().map_err(|error| anyhow::anyhow!(error))? {
+            let (_, value) = entry.map_err(|error| anyhow::anyhow!(error))?;
+            match serde_json::from_slice::<WebhookSubscription>(value.value()) {
+                Ok(subscription) => stored.push(subscription),
+                Err(error) => tracing::warn!(%error, "skipping unreadable webhook subscription"),
+            }
+        }
+        stored.sort_by_key(|subscription| subscription.created_at);
+
+        let mut subscriptions = self.config_subscriptions.clone();
+        subscriptions.extend(stored);
+        self.subscriptions.store(Arc::new(subscriptions));
+        Ok(())
+    }
+}
+
+/// Body of each delivery.
+#[derive(Serialize)]
+struct WebhookPayload<'a> {
+    /// Position in the instance-wide event stream; stable across retries, so
+    /// receivers can deduplicate.
+    id: u64,
+    #[serde(rename = "type")]
+    event_type: &'a str,
+    event: &'a ApiEvent,
+}
+
+/// `sha256=` followed by the hex HMAC of `{timestamp}.{body}`.
+fn signature(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Whether a failed response is worth retrying. Other client errors mean the
+/// receiver rejected the payload and will keep doing so.
+fn is_retryable(status: reqwest::StatusCode) -> bool {
+    status.is_server_error()
+        || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+        || status == reqwest::StatusCode::REQUEST_TIMEOUT
+}
+
+/// A URL's host and the addresses it resolves to.
+struct ResolvedUrl {
+    /// `None` when the host is an IP literal.
+    name: Option<String>,
+    addresses: Vec<SocketAddr>,
+}
+
+impl ResolvedUrl {
+    /// The first loopback, private, or link-local address among them.
+    fn blocked(&self) -> Option<IpAddr> {
+        self.addresses
+            .iter()
+            .map(SocketAddr::ip)
+            .find(|ip| is_blocked_ip(*ip))
+    }
+
+    /// A delivery client that can only connect to these addresses, so a
+    /// name whose DNS changes after the check can't be rebound to a private
+    /// address for the request itself.
+    fn pinned_client(&self) -> Result<reqwest::Client, reqwest::Error> {
+        let mut builder = delivery_client_builder();
+        if let Some(name) = &self.name {
+            builder = builder.resolve_to_addrs(name, &self.addresses);
+        }
+        builder.build()
+    }
+}
+
+/// Resolve `url`'s host, unless it is already an IP address.
+async fn resolve_url(url: &str) -> Result<ResolvedUrl, String> {
+    let parsed = url
+        .parse::<reqwest::Url>()
+        .map_err(|error| format!("invalid url: {error}"))?;
+    let host = parsed.host_str().ok_or("url has no host")?;
+    let host = host
+        .strip_prefix('[')
+        .and_then(|host| host.strip_suffix(']'))
+        .unwrap_or(host);
+    let port = parsed.port_or_known_default().unwrap_or(443);
+    Ok(match host.parse::<IpAddr>() {
+        Ok(ip) => ResolvedUrl {
+            name: None,
+            addresses: vec![SocketAddr::new(ip, port)],
+        },
+        Err(_) => ResolvedUrl {
+            name: Some(host.to_string()),
+            addresses: tokio::net::lookup_host((host, port))
+                .await
+                .map_err(|error| format!("failed to resolve {host}: {error}"))?
+                .collect(),
+        },
+    })
+}
+
+/// The first loopback, private, or link-local address `url` points at,
+/// resolving its host if it is a name.
+async fn blocked_address(url: &str) -> Result<Option<IpAddr>, String> {
+    Ok(resolve_url(url).await?.blocked())
+}
+
+/// Redirects are not followed: a receiver could otherwise bounce a delivery
+/// to an address registration would have refused.
+fn delivery_client_builder() -> reqwest::ClientBuilder {
+    reqwest::Client::builder().redirect(reqwest::redirect::Policy::none())
+}
+
+async fn deliver(
+    client: reqwest::Client,
+    slots: Arc<Semaphore>,
+    permit: OwnedSemaphorePermit,
+    subscription: WebhookSubscription,
+    event_id: u64,
+    event_type: String,
+    body: Arc<Vec<u8>>,
+) {
+    let mut backoff = INITIAL_BACKOFF;
+    let mut first_permit = Some(permit);
+    for attempt in 1..=MAX_ATTEMPTS {
+        let permit = match first_permit.take() {
+            Some(permit) => permit,
+            None => match slots.clone().acquire_owned().await {
+                Ok(permit) => permit,
+                Err(_) => return,
+            },
+        };
+
+        // API subscriptions are re-vetted on every attempt, and the request
+        // goes to the addresses that passed.
+        let pinned;
+        let client = if subscription.source == WebhookSource::Api {
+            let resolved = match resolve_url(&subscription.url).await {
+                Ok(resolved) => resolved,
+                Err(error) => {
+                    tracing::debug!(webhook = %subscription.id, %error, attempt, event_id, "webhook delivery failed");
+                    if attempt == MAX_ATTEMPTS {
+                        break;
+                    }
+                    drop(permit);
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                    continue;
+                }
+            };
+            if let Some(ip) = resolved.blocked() {
+                tracing::warn!(webhook = %subscription.id, %ip, event_id, "webhook url resolves to a private address, not delivering");
+                return;
+            }
+            pinned = match resolved.pinned_client() {
+                Ok(pinned) => pinned,
+                Err(error) => {
+                    tracing::warn!(webhook = %subscription.id, %error, event_id, "failed to build webhook client, not delivering");
+                    return;
+                }
+            };
+            &pinned
+        } else {
+            &client
+        };
+
+        let timestamp = chrono::Utc::now().timestamp();
+        let mut request = client
+            .post(&subscription.url)
+            .timeout(REQUEST_TIMEOUT)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header("X-Spacebot-Event", &event_type)
+            .header("X-Spacebot-Delivery", event_id.to_string())
+            .header("X-Spacebot-Timestamp", timestamp.to_string());
+        if let Some(secret) = &subscription.secret {
+            request = request.header("X-Spacebot-Signature", signature(secret, timestamp, &body));
+        }
+
+        let retryable = match request.body(body.as_ref().clone()).send().await {
+            Ok(response) if response.status().is_success() => return,
+            Ok(response) => {
+                let status = response.status();
+                tracing::debug!(webhook = %subscription.id, %status, attempt, event_id, "webhook delivery rejected");
+                is_retryable(status)
+            }
+            Err(error) => {
+                tracing::debug!(webhook = %subscription.id, %error, attempt, event_id, "webhook delivery failed");
+                true
+            }
+        };
+        if !retryable || attempt == MAX_ATTEMPTS {
+            break;
+        }
+        drop(permit);
+        tokio::time::sleep(backoff).await;
+        backoff *= 2;
+    }
+    tracing::warn!(
+        webhook = %subscription.id,
+        event_id,
+        %event_type,
+        "giving up on webhook delivery"
+    );
+}
+
+/// POST every sequenced event to the subscriptions that match it.
+pub fn spawn_webhook_delivery(state: Arc<ApiState>, registry: Arc<WebhookRegistry>) {
+    let mut event_rx = state.sequenced_event_tx.subscribe();
+    let client = delivery_client_builder().build().unwrap_or_default();
+    let slots = Arc::new(Semaphore::new(MAX_CONCURRENT_DELIVERIES));
+    tokio::spawn(async move {
+        loop {
+            let sequenced = match crate::classify_broadcast_recv_result(event_rx.recv().await) {
+                crate::BroadcastRecvResult::Event(sequenced) => sequenced,
+                crate::BroadcastRecvResult::Lagged(count) => {
+                    tracing::warn!(count, "webhook delivery lagged, events dropped");
+                    continue;
+                }
+                crate::BroadcastRecvResult::Closed => break,
+            };
+
+            let subscriptions = registry.list();
+            if subscriptions.is_empty() {
+                continue;
+            }
+            let Ok(serde_json::Value::Object(fields)) = serde_json::to_value(&sequenced.event)
+            else {
+                continue;
+            };
+            let Some(event_type) = fields.get("type").and_then(|value| value.as_str()) else {
+                continue;
+            };
+            let matching: Vec<WebhookSubscription> = subscriptions
+                .iter()
+                .filter(|subscription| subscription.matches(event_type))
+                .cloned()
+                .collect();
+            if matching.is_empty() {
+                continue;
+            }
+
+            let event_id = sequenced.id;
+            let payload = WebhookPayload {
+                id: event_id,
+                event_type,
+                event: &sequenced.event,
+            };
+            let body = match serde_json::to_vec(&payload) {
+                Ok(body) => Arc::new(body),
+                Err(error) => {
+                    tracing::warn!(%error, "failed to serialize webhook payload");
+                    continue;
+                }
+            };
+
+            for subscription in matching {
+                let Ok(permit) = slots.clone().acquire_owned().await else {
+                    return;
+                };
+                let client = client.clone();
+                let slots = slots.clone();
+                let event_type = event_type.to_string();
+                let body = body.clone();
+                tokio::spawn(deliver(
+                    client,
+                    slots,
+                    permit,
+                    subscription,
+                    event_id,
+                    event_type,
+                    body,
+                ));
+            }
+        }
+    });
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct WebhookInfo {
+    id: String,
+    url: String,
+    events: Vec<String>,
+    source: WebhookSource,
+    signed: bool,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<&WebhookSubscription> for WebhookInfo {
+    fn from(subscription: &WebhookSubscription) -> Self {
+        Self {
+            id: subscription.id.clone(),
+            url: subscription.url.clone(),
+            events: subscription.events.clone(),
+            source: subscription.source,
+            signed: subscription.secret.is_some(),
+            created_at: subscription.created_at,
+        }
+    }
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct WebhookListResponse {
+    webhooks: Vec<WebhookInfo>,
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
+pub(super) struct CreateWebhookRequest {
+    url: String,
+    events: Vec<String>,
+    /// Signing key. One is generated when omitted.
+    #[serde(default)]
+    secret: Option<String>,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct CreateWebhookResponse {
+    #[serde(flatten)]
+    webhook: WebhookInfo,
+    /// Only returned here; store it to verify `X-Spacebot-Signature`.
+    secret: String,
+}
+
+fn registry(state: &ApiState) -> Result<&Arc<WebhookRegistry>, (StatusCode, String)> {
+    state.webhooks.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "webhook store is unavailable".to_string(),
+    ))
+}
+
+/// List webhook subscriptions. Secrets are never returned.
+#[utoipa::path(
+    get,
+    path = "/api/webhooks",
+    tag = "system",
+    responses(
+        (status = 200, body = WebhookListResponse),
+        (status = 503, description = "Webhook store unavailable"),
+    )
+)]
+pub(super) async fn list_webhooks(
+    State(state): State<Arc<ApiState>>,
+) -> Result<Json<WebhookListResponse>, (StatusCode, String)> {
+    let registry = registry(&state)?;
+    Ok(Json(WebhookListResponse {
+        webhooks: registry.list().iter().map(WebhookInfo::from).collect(),
+    }))
+}
+
+/// Register a webhook for a set of event types.
+#[utoipa::path(
+    post,
+    path = "/api/webhooks",
+    tag = "system",
+    request_body = CreateWebhookRequest,
+    responses(
+        (status = 200, description = "Registered; the signing secret is only returned here", body = CreateWebhookResponse),
+        (status = 400, description = "Invalid or private URL, or no event types"),
+        (status = 503, description = "Webhook store unavailable"),
+    )
+)]
+pub(super) async fn create_webhook(
+    State(state): State<Arc<ApiState>>,
+    Json(request): Json<CreateWebhookRequest>,
+) -> Result<Json<CreateWebhookResponse>, (StatusCode, String)> {
+    let registry = registry(&state)?;
+    let url = request.url.trim().to_string();
+    if !(url.starts_with("https://") || url.starts_with("http://"))
+        || url.parse::<reqwest::Url>().is_err()
+    {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "url must be an http:// or https:// URL".to_string(),
+        ));
+    }
+    match blocked_address(&url).await {
+        Ok(None) => {}
+        Ok(Some(ip)) => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!("url points at {ip}; private and loopback addresses are not allowed"),
+            ));
+        }
+        Err(error) => return Err((StatusCode::BAD_REQUEST, error)),
+    }
+    let events: Vec<String> = request
+        .events
+        .iter()
+        .map(|event| event.trim().to_string())
+        .filter(|event| !event.is_empty())
+        .collect();
+    if events.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "events must list at least one event type (or \"*\")".to_string(),
+        ));
+    }
+    let secret = request
+        .secret
+        .filter(|secret| !secret.is_empty())
+        .unwrap_or_else(|| {
+            let mut bytes = [0u8; 32];
+            rand::rng().fill_bytes(&mut bytes);
+            hex::encode(bytes)
+        });
+
+    let subscription = WebhookSubscription {
+        id: uuid::Uuid::new_v4().to_string(),
+        url,
+        events,
+        secret: Some(secret.clone()),
+        source: WebhookSource::Api,
+        created_at: chrono::Utc::now(),
+    };
+    registry.insert(&subscription).map_err(|error| {
+        tracing::warn!(%error, "failed to save webhook subscription");
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "failed to save webhook".to_string(),
+        )
+    })?;
+
+    Ok(Json(CreateWebhookResponse {
+        webhook: WebhookInfo::from(&subscription),
+        secret,
+    }))
+}
+
+/// Remove an API-registered webhook. Config webhooks are removed by editing
+/// config.toml.
+#[utoipa::path(
+    delete,
+    path = "/api/webhooks/{id}",
+    tag = "system",
+    params(("id" = String, Path, description = "Webhook id")),
+    responses(
+        (status = 204, description = "Webhook removed"),
+        (status = 404, description = "Unknown webhook"),
+        (status = 409, description = "Webhook is defined in config.toml"),
+        (status = 503, description = "Webhook store unavailable"),
+    )
+)]
+pub(super) async fn delete_webhook(
+    State(state): State<Arc<ApiState>>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    let registry = registry(&state)?;
+    if id.starts_with("config:") {
+        return Err((
+            StatusCode::CONFLICT,
+            "webhooks from config.toml can only be removed there".to_string(),
+        ));
+    }
+    match registry.remove(&id) {
+        Ok(true) => Ok(StatusCode::NO_CONTENT),
+        Ok(false) => Err((StatusCode::NOT_FOUND, "webhook not found".to_string())),
+        Err(error) => {
+            tracing::warn!(%error, "failed to delete webhook subscription");
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to delete webhook".to_string(),
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_merges_config_and_api_subscriptions() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("webhooks.redb");
+        let config = vec![ApiWebhookConfig {
+            name: "n8n".into(),
+            url: "https://n8n.example.com/hook".into(),
+            events: vec!["worker_completed".into(), "branch_completed".into()],
+            secret: None,
+        }];
+        let registry = WebhookRegistry::new(&path, &config).unwrap();
+        assert_eq!(registry.list().len(), 1);
+        assert!(registry.list()[0].matches("branch_completed"));
+        assert!(!registry.list()[0].matches("inbound_message"));
+
+        let subscription = WebhookSubscription {
+            id: "abc".into(),
+            url: "https://zapier.example.com/hook".into(),
+            events: vec!["*".into()],
+            secret: Some("shh".into()),
+            source: WebhookSource::Api,
+            created_at: chrono::Utc::now(),
+        };
+        registry.insert(&subscription).unwrap();
+        drop(registry);
+
+        // API subscriptions survive a restart; config ones come from config.
+        let registry = WebhookRegistry::new(&path, &[]).unwrap();
+        let subscriptions = registry.list();
+        assert_eq!(subscriptions.len(), 1);
+        assert!(subscriptions[0].matches("inbound_message"));
+        assert!(registry.remove("abc").unwrap());
+        assert!(!registry.remove("abc").unwrap());
+        assert!(registry.list().is_empty());
+
+        // Receivers verify with HMAC-SHA256("{timestamp}.{body}").
+        assert_eq!(
+            signature("key", 1, b"{}"),
+            "sha256=1ba6b8171186efc613e8bcc0cbdab2748f24984d7c5a84faa2637afa0e40d224"
+        );
+    }
+
+    #[tokio::test]
+    async fn private_addresses_are_blocked() {
+        for url in [
+            "http://127.0.0.1:8080/hook",
+            "http://[::1]/hook",
+            "http://10.0.0.5/hook",
+            "http://192.168.1.20/hook",
+            "http://169.254.169.254/latest/meta-data",
+            "http://[::ffff:127.0.0.1]/hook",
+            "http://localhost/hook",
+        ] {
+            assert!(
+                blocked_address(url).await.unwrap().is_some(),
+                "{url} should be blocked"
+            );
+        }
+        assert_eq!(
+            blocked_address("https://93.184.216.34/hook").await.unwrap(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn names_are_resolved_once_and_pinned() {
+        let resolved = resolve_url("http://localhost:8080/hook").await.unwrap();
+        assert_eq!(resolved.name.as_deref(), Some("localhost"));
+        assert!(!resolved.addresses.is_empty());
+        assert!(
+            resolved
+                .addresses
+                .iter()
+                .all(|address| address.port() == 8080)
+        );
+        assert!(resolved.blocked().is_some());
+
+        let literal = resolve_url("https://[2606:4700::1111]/hook").await.unwrap();
+        assert_eq!(literal.name, None);
+        assert_eq!(
+            literal.addresses,
+            ["[2606:4700::1111]:443".parse::<SocketAddr>().unwrap()]
+        );
+        assert!(literal.pinned_client().is_ok());
+    }
+}
@@ -0,0 +1,448 @@
+//! Anonymous routes behind the embeddable website chat widget.
+//!
+//! `/api/widget/*` sits outside API auth. A page on one of
+//! `api.widget.allowed_origins` asks for a session, gets back an opaque
+//! token, and uses it to post messages into its own `widget:{session_id}`
+//! channel on the configured agent and to follow that channel's replies over
+//! SSE. Nothing else is reachable: every route is pinned to one agent and one
+//! visitor channel, checked against the request's `Origin`, and limited per
+//! client by its own rate limiter.
+
+use super::rate_limit::rate_limited_response;
+use super::state::{ApiEvent, ApiState};
+use crate::config::ApiWidgetConfig;
+use crate::conversation::{ConversationLogger, WidgetSession, WidgetSessionStore};
+use crate::{InboundMessage, MessageContent};
+
+use axum::extract::{ConnectInfo, Query, Request, State};
+use axum::http::{HeaderMap, Method, StatusCode, header};
+use axum::middleware::Next;
+use axum::response::sse::{Event, KeepAlive};
+use axum::response::{IntoResponse, Response, Sse};
+use axum::{Extension, Json};
+use futures::Stream;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tokio::sync::broadcast;
+
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Messages returned by the history route.
+const HISTORY_LIMIT: i64 = 50;
+
+/// Longest a visitor session can last, whatever `session_ttl_secs` says.
+const MAX_SESSION_TTL_SECS: u64 = 365 * 24 * 60 * 60;
+
+/// Name the agent sees for widget visitors.
+const VISITOR_NAME: &str = "Website visitor";
+
+/// The page origin a widget request came from, checked by
+/// [`widget_middleware`].
+#[derive(Debug, Clone)]
+pub(super) struct WidgetOrigin(String);
+
+/// Error in the same `{"error", "message"}` shape as the rest of the API.
+pub(super) struct WidgetError {
+    status: StatusCode,
+    code: &'static str,
+    message: String,
+}
+
+impl WidgetError {
+    fn new(status: StatusCode, code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            code,
+            message: message.into(),
+        }
+    }
+
+    fn internal(context: &str, error: impl std::fmt::Display) -> Self {
+        tracing::warn!(%error, "{context}");
+        Self::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "internal_error",
+            "something went wrong",
+        )
+    }
+}
+
+impl IntoResponse for WidgetError {
+    fn into_response(self) -> Response {
+        let body = json!({"error": self.code, "message": self.message});
+        (self.status, Json(body)).into_response()
+    }
+}
+
+/// Refuse widget requests when the widget is off or the page's origin isn't
+/// allowed, then apply the widget's per-client limits. Session issuance and
+/// sending count against the tighter message tier.
+pub(super) async fn widget_middleware(
+    State(state): State<Arc<ApiState>>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let Some(widget) = state.widget.as_ref() else {
+        return WidgetError::new(StatusCode::NOT_FOUND, "not_found", "the widget is disabled")
+            .into_response();
+    };
+
+    let origin = request
+        .headers()
+        .get(header::ORIGIN)
+        .and_then(|value| value.to_str().ok())
+        .map(|origin| origin.trim_end_matches('/').to_string());
+    let Some(origin) = origin.filter(|origin| widget.allows_origin(origin)) else {
+        tracing::debug!(
+            path = request.uri().path(),
+            "widget request from a disallowed origin"
+        );
+        return WidgetError::new(
+            StatusCode::FORBIDDEN,
+            "forbidden",
+            "this origin may not use the widget",
+        )
+        .into_response();
+    };
+
+    if let Some(limiter) = state.widget_rate_limiter.as_ref() {
+        let client = match request.extensions().get::<ConnectInfo<SocketAddr>>() {
+            Some(ConnectInfo(address)) => format!("ip:{}", address.ip()),
+            None => "unknown".to_string(),
+        };
+        let expensive = request.method() == Method::POST;
+        if let Err(wait) = limiter.check(&client, expensive, Instant::now()) {
+            tracing::debug!(%client, expensive, ?wait, "widget request rate limited");
+            return rate_limited_response(wait);
+        }
+    }
+
+    request.extensions_mut().insert(WidgetOrigin(origin));
+    next.run(request).await
+}
+
+#[derive(Serialize)]
+pub(super) struct WidgetSessionResponse {
+    session_id: String,
+    token: String,
+    expires_at: chrono::DateTime<chrono::Utc>,
+    greeting: Option<String>,
+}
+
+/// `POST /api/widget/session`: issue an anonymous visitor session.
+pub(super) async fn create_session(
+    State(state): State<Arc<ApiState>>,
+    Extension(WidgetOrigin(origin)): Extension<WidgetOrigin>,
+) -> Result<Json<WidgetSessionResponse>, WidgetError> {
+    let widget = widget_config(&state)?;
+    let store = session_store(&state, widget)?;
+
+    if let Err(error) = store.prune_expired().await {
+        tracing::warn!(%error, "failed to prune expired widget sessions");
+    }
+    let ttl = chrono::Duration::seconds(widget.session_ttl_secs.min(MAX_SESSION_TTL_SECS) as i64);
+    let session = store
+        .create(&origin, ttl)
+        .await
+        .map_err(|error| WidgetError::internal("failed to create widget session", error))?;
+
+    Ok(Json(WidgetSessionResponse {
+        session_id: session.id,
+        token: session.token,
+        expires_at: session.expires_at,
+        greeting: widget.greeting.clone(),
+    }))
+}
+
+#[derive(Deserialize)]
+pub(super) struct WidgetSendRequest {
+    message: String,
+}
+
+#[derive(Deserialize)]
+pub(super) struct WidgetTokenQuery {
+    #[serde(default)]
+    token: Option<String>,
+}
+
+/// `POST /api/widget/messages`: post a visitor message. The reply arrives on
+/// `/api/widget/events`.
+pub(super) async fn send_message(
+    State(state): State<Arc<ApiState>>,
+    Extension(WidgetOrigin(origin)): Extension<WidgetOrigin>,
+    headers: HeaderMap,
+    Query(query): Query<WidgetTokenQuery>,
+    Json(request): Json<WidgetSendRequest>,
+) -> Result<StatusCode, WidgetError> {
+    let widget = widget_config(&state)?;
+    let session = authenticate(&state, widget, &origin, &headers, &query).await?;
+
+    let message = request.message.trim();
+    if message.is_empty() {
+        return Err(WidgetError::new(
+            StatusCode::BAD_REQUEST,
+            "empty_message",
+            "message cannot be empty",
+        ));
+    }
+    if message.chars().count() > widget.max_message_chars {
+        return Err(WidgetError::new(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            "message_too_long",
+            format!(
+                "messages are limited to {} characters",
+                widget.max_message_chars
+            ),
+        ));
+    }
+
+    let manager = state
+        .messaging_manager
+        .read()
+        .await
+        .clone()
+        .ok_or_else(|| {
+            WidgetError::new(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "unavailable",
+                "messaging is not running",
+            )
+        })?;
+
+    let mut metadata = HashMap::new();
+    metadata.insert(
+        "display_name".into(),
+        serde_json::Value::String(VISITOR_NAME.into()),
+    );
+    metadata.insert(
+        "widget_origin".into(),
+        serde_json::Value::String(session.origin.clone()),
+    );
+    let inbound = InboundMessage {
+        id: uuid::Uuid::new_v4().to_string(),
+        source: "webchat".into(),
+        adapter: Some("webchat".into()),
+        conversation_id: session.channel_id(),
+        // Each visitor is its own user, so user-scoped memories stay with
+        // the visitor who created them.
+        sender_id: session.channel_id(),
+        agent_id: Some(widget.agent_id.as_str().into()),
+        content: MessageContent::Text(message.to_string()),
+        timestamp: chrono::Utc::now(),
+        metadata,
+        formatted_author: Some(VISITOR_NAME.into()),
+    };
+    manager
+        .inject_message(inbound)
+        .await
+        .map_err(|error| WidgetError::internal("failed to inject widget message", error))?;
+
+    Ok(StatusCode::ACCEPTED)
+}
+
+#[derive(Serialize)]
+pub(super) struct WidgetMessage {
+    id: String,
+    role: String,
+    content: String,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// `GET /api/widget/messages`: the visitor's conversation so far, oldest
+/// first, so a reloaded page can restore it.
+pub(super) async fn history(
+    State(state): State<Arc<ApiState>>,
+    Extension(WidgetOrigin(origin)): Extension<WidgetOrigin>,
+    headers: HeaderMap,
+    Query(query): Query<WidgetTokenQuery>,
+) -> Result<Json<Vec<WidgetMessage>>, WidgetError> {
+    let widget = widget_config(&state)?;
+    let session = authenticate(&state, widget, &origin, &headers, &query).await?;
+
+    let pool = agent_pool(&state, widget)?;
+    let channel_id: crate::ChannelId = Arc::from(session.channel_id().as_str());
+    let messages = ConversationLogger::new(pool)
+        .load_recent(&channel_id, HISTORY_LIMIT)
+        .await
+        .map_err(|error| WidgetError::internal("failed to load widget history", error))?;
+
+    Ok(Json(
+        messages
+            .into_iter()
+            .filter(|message| message.role == "user" || message.role == "assistant")
+            .map(|message| WidgetMessage {
+                id: message.id,
+                role: message.role,
+                content: message.content,
+                created_at: message.created_at,
+            })
+            .collect(),
+    ))
+}
+
+/// `GET /api/widget/events?token=`: the agent's replies and typing state for
+/// the visitor's channel. The token goes in the query because `EventSource`
+/// can't set headers.
+pub(super) async fn events(
+    State(state): State<Arc<ApiState>>,
+    Extension(WidgetOrigin(origin)): Extension<WidgetOrigin>,
+    headers: HeaderMap,
+    Query(query): Query<WidgetTokenQuery>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, WidgetError> {
+    let widget = widget_config(&state)?;
+    let session = authenticate(&state, widget, &origin, &headers, &query).await?;
+
+    let mut rx = state.event_tx.subscribe();
+    let agent_id = widget.agent_id.clone();
+    let channel_id = session.channel_id();
+    let stream = async_stream::stream! {
+        loop {
+            let event = match rx.recv().await {
+                Ok(event) => event,
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::debug!(skipped, "widget event stream lagged");
+                    continue;
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            };
+            if let Some(event) = visitor_event(&event, &agent_id, &channel_id)
+                && let Ok(data) = serde_json::to_string(&event)
+            {
+                yield Ok(Event::default().event(event.name()).data(data));
+            }
+        }
+    };
+
+    Ok(Sse::new(stream).keep_alive(
+        KeepAlive::new()
+            .interval(Duration::from_secs(15))
+            .text("ping"),
+    ))
+}
+
+/// What a visitor's event stream carries. Everything else on the event bus
+/// stays private.
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(untagged)]
+enum VisitorEvent {
+    Message { text: String },
+    Typing { typing: bool },
+}
+
+impl VisitorEvent {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Message { .. } => "message",
+            Self::Typing { .. } => "typing",
+        }
+    }
+}
+
+fn visitor_event(event: &ApiEvent, agent_id: &str, channel_id: &str) -> Option<VisitorEvent> {
+    if event.channel_id() != Some(channel_id) || !event.involves_agent(agent_id) {
+        return None;
+    }
+    match event {
+        ApiEvent::OutboundMessage { text, .. } => {
+            Some(VisitorEvent::Message { text: text.clone() })
+        }
+        ApiEvent::TypingState { is_typing, .. } => {
+            Some(VisitorEvent::Typing { typing: *is_typing })
+        }
+        _ => None,
+    }
+}
+
+fn widget_config(state: &ApiState) -> Result<&ApiWidgetConfig, WidgetError> {
+    state.widget.as_ref().ok_or_else(|| {
+        WidgetError::new(StatusCode::NOT_FOUND, "not_found", "the widget is disabled")
+    })
+}
+
+fn agent_pool(state: &ApiState, widget: &ApiWidgetConfig) -> Result<sqlx::SqlitePool, WidgetError> {
+    let pools = state.agent_pools.load();
+    pools.get(&widget.agent_id).cloned().ok_or_else(|| {
+        WidgetError::new(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "unavailable",
+            "the widget's agent is not running",
+        )
+    })
+}
+
+fn session_store(
+    state: &ApiState,
+    widget: &ApiWidgetConfig,
+) -> Result<WidgetSessionStore, WidgetError> {
+    agent_pool(state, widget).map(WidgetSessionStore::new)
+}
+
+/// Resolve the request's session token. Sessions only work from the origin
+/// they were issued to.
+async fn authenticate(
+    state: &ApiState,
+    widget: &ApiWidgetConfig,
+    origin: &str,
+    headers: &HeaderMap,
+    query: &WidgetTokenQuery,
+) -> Result<WidgetSession, WidgetError> {
+    let unauthorized = || {
+        WidgetError::new(
+            StatusCode::UNAUTHORIZED,
+            "invalid_session",
+            "the widget session is missing or expired",
+        )
+    };
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .or(query.token.as_deref())
+        .ok_or_else(unauthorized)?;
+
+    let session = session_store(state, widget)?
+        .lookup(token)
+        .await
+        .map_err(|error| WidgetError::internal("failed to look up widget session", error))?
+        .ok_or_else(unauthorized)?;
+    if !session.origin.eq_ignore_ascii_case(origin) {
+        return Err(unauthorized());
+    }
+    Ok(session)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn visitor_events_only_cover_the_visitors_channel() {
+        let reply = |channel_id: &str| ApiEvent::OutboundMessage {
+            agent_id: "agent".into(),
+            channel_id: channel_id.into(),
+            text: "hello".into(),
+        };
+        let typing = ApiEvent::TypingState {
+            agent_id: "agent".into(),
+            channel_id: "widget:a".into(),
+            is_typing: true,
+        };
+
+        assert_eq!(
+            visitor_event(&reply("widget:a"), "agent", "widget:a"),
+            Some(VisitorEvent::Message {
+                text: "hello".into()
+            })
+        );
+        assert_eq!(
+            visitor_event(&typing, "agent", "widget:a"),
+            Some(VisitorEvent::Typing { typing: true })
+        );
+        assert_eq!(visitor_event(&reply("widget:b"), "agent", "widget:a"), None);
+        assert_eq!(visitor_event(&reply("widget:a"), "other", "widget:a"), None);
+    }
+}
@@ -1,12 +1,13 @@
 //! Workers API endpoints: list and detail views for worker runs.
 
+use super::access::{AgentQuery, AgentScoped};
 use super::state::ApiState;
 
 use crate::conversation::history::ProcessRunLogger;
 use crate::conversation::worker_transcript;
 
 use axum::Json;
-use axum::extract::{Query, State};
+use axum::extract::State;
 use axum::http::StatusCode;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -21,6 +22,12 @@ pub(super) struct WorkerListQuery {
     status: Option<String>,
 }
 
+impl AgentScoped for WorkerListQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
 fn default_limit() -> i64 {
     50
 }
@@ -58,6 +65,12 @@ pub(super) struct WorkerDetailQuery {
     worker_id: String,
 }
 
+impl AgentScoped for WorkerDetailQuery {
+    fn agent_id(&self) -> Option<&str> {
+        Some(&self.agent_id)
+    }
+}
+
 #[derive(Serialize)]
 pub(super) struct WorkerDetailResponse {
     id: String,
@@ -84,7 +97,7 @@ pub(super) struct WorkerDetailResponse {
 /// List worker runs for an agent, with live status merged from StatusBlocks.
 pub(super) async fn list_workers(
     State(state): State<Arc<ApiState>>,
-    Query(query): Query<WorkerListQuery>,
+    AgentQuery(query): AgentQuery<WorkerListQuery>,
 ) -> Result<Json<WorkerListResponse>, StatusCode> {
     let pools = state.agent_pools.load();
     let pool = pools.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
@@ -155,7 +168,7 @@ pub(super) async fn list_workers(
 /// Get full detail for a single worker run, including decompressed transcript.
 pub(super) async fn worker_detail(
     State(state): State<Arc<ApiState>>,
-    Query(query): Query<WorkerDetailQuery>,
+    AgentQuery(query): AgentQuery<WorkerDetailQuery>,
 ) -> Result<Json<WorkerDetailResponse>, StatusCode> {
     let pools = state.agent_pools.load();
     let pool = pools.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
@@ -0,0 +1,127 @@
+use super::state::{ApiEvent, ApiState};
+
+use super::access::AgentPath;
+use crate::identity::git::{self, WorkspaceCommit};
+
+use axum::Json;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(super) struct WorkspaceLogQuery {
+    #[serde(default = "default_log_limit")]
+    limit: usize,
+}
+
+fn default_log_limit() -> usize {
+    50
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct WorkspaceLogResponse {
+    commits: Vec<WorkspaceCommit>,
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
+pub(super) struct WorkspaceRevertRequest {
+    /// Commit to restore identity files and skills to, full or abbreviated.
+    commit: String,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct WorkspaceRevertResponse {
+    /// The commit recording the revert, or null if nothing changed.
+    commit: Option<String>,
+}
+
+/// History of identity file and workspace skill changes, newest first.
+/// Requires `workspace_git = true` on the agent.
+#[utoipa::path(
+    get,
+    path = "/api/agents/{id}/workspace/log",
+    tag = "agents",
+    params(("id" = String, Path, description = "Agent ID"), WorkspaceLogQuery),
+    responses(
+        (status = 200, body = WorkspaceLogResponse),
+        (status = 404, description = "Unknown agent"),
+        (status = 409, description = "Workspace history is disabled for this agent"),
+    )
+)]
+pub(super) async fn workspace_log(
+    State(state): State<Arc<ApiState>>,
+    AgentPath(agent_id): AgentPath<String>,
+    Query(query): Query<WorkspaceLogQuery>,
+) -> Result<Json<WorkspaceLogResponse>, StatusCode> {
+    let runtime_config = state
+        .runtime_configs
+        .load()
+        .get(&agent_id)
+        .cloned()
+        .ok_or(StatusCode::NOT_FOUND)?;
+    if !**runtime_config.workspace_git.load() {
+        return Err(StatusCode::CONFLICT);
+    }
+
+    let commits = git::log(&runtime_config.identity_dir, query.limit.clamp(1, 500))
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, %agent_id, "failed to read workspace history");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    Ok(Json(WorkspaceLogResponse { commits }))
+}
+
+/// Restore identity files and workspace skills to a past commit. The revert
+/// is recorded as a new commit, so it can itself be undone.
+#[utoipa::path(
+    post,
+    path = "/api/agents/{id}/workspace/revert",
+    tag = "agents",
+    params(("id" = String, Path, description = "Agent ID")),
+    request_body = WorkspaceRevertRequest,
+    responses(
+        (status = 200, body = WorkspaceRevertResponse),
+        (status = 400, description = "Not a commit in this agent's workspace history"),
+        (status = 404, description = "Unknown agent"),
+        (status = 409, description = "Workspace history is disabled for this agent"),
+    )
+)]
+pub(super) async fn workspace_revert(
+    State(state): State<Arc<ApiState>>,
+    AgentPath(agent_id): AgentPath<String>,
+    Json(request): Json<WorkspaceRevertRequest>,
+) -> Result<Json<WorkspaceRevertResponse>, StatusCode> {
+    let runtime_config = state
+        .runtime_configs
+        .load()
+        .get(&agent_id)
+        .cloned()
+        .ok_or(StatusCode::NOT_FOUND)?;
+    if !**runtime_config.workspace_git.load() {
+        return Err(StatusCode::CONFLICT);
+    }
+
+    let commit = git::restore(&runtime_config.identity_dir, request.commit.trim(), "api")
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, %agent_id, commit = %request.commit, "failed to revert workspace");
+            StatusCode::BAD_REQUEST
+        })?;
+
+    // Apply the restored files now rather than waiting for the file watcher.
+    let identity = crate::identity::Identity::load(&runtime_config.identity_dir).await;
+    runtime_config.reload_identity(identity);
+    let skills = crate::skills::SkillSet::load(
+        &runtime_config.instance_dir.join("skills"),
+        &runtime_config.workspace_dir.join("skills"),
+    )
+    .await;
+    runtime_config.reload_skills(skills);
+    state.send_event(ApiEvent::ConfigReloaded);
+
+    tracing::info!(%agent_id, commit = ?commit, target = %request.commit, "workspace reverted via API");
+    Ok(Json(WorkspaceRevertResponse { commit }))
+}
@@ -0,0 +1,351 @@
+//! Approval queue for tool calls with external side effects.
+//!
+//! A tool that wants to change something outside Spacebot (create an issue,
+//! post a comment, broadcast an announcement) records the call here instead
+//! of making it, and tells the agent it's waiting on approval. A person
+//! approves or rejects it from the API; approving runs the deferred call and
+//! stores its result.
+
+use crate::config::{NotificationCategory, RuntimeConfig};
+use crate::error::Result;
+use crate::issues::{IssueMutation, IssueTrackers};
+use crate::messaging::MessagingManager;
+use crate::messaging::broadcast::{GroupBroadcast, fan_out};
+use crate::notifications::OperatorNotifier;
+use crate::tools::{BroadcastTool, IssueTrackerTool};
+
+use anyhow::{Context as _, anyhow};
+use rig::tool::Tool as _;
+use serde::Serialize;
+use sqlx::{Row as _, SqlitePool};
+
+/// Lifecycle of an approval request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalStatus {
+    Pending,
+    /// Claimed by an approver and currently executing.
+    Approved,
+    Rejected,
+    Executed,
+    Failed,
+}
+
+impl ApprovalStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Approved => "approved",
+            Self::Rejected => "rejected",
+            Self::Executed => "executed",
+            Self::Failed => "failed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(Self::Pending),
+            "approved" => Some(Self::Approved),
+            "rejected" => Some(Self::Rejected),
+            "executed" => Some(Self::Executed),
+            "failed" => Some(Self::Failed),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct ToolApproval {
+    pub id: String,
+    pub tool_name: String,
+    pub summary: String,
+    #[schema(value_type = Object)]
+    pub payload: serde_json::Value,
+    pub channel_id: Option<String>,
+    pub requested_by: String,
+    pub status: ApprovalStatus,
+    /// Tool output on success, error message on failure.
+    pub result: Option<String>,
+    pub decided_by: Option<String>,
+    pub created_at: String,
+    pub decided_at: Option<String>,
+}
+
+const COLUMNS: &str = "id, tool_name, summary, payload, channel_id, requested_by, status, \
+                       result, decided_by, created_at, decided_at";
+
+#[derive(Debug, Clone)]
+pub struct ApprovalStore {
+    pool: SqlitePool,
+    notifier: Option<OperatorNotifier>,
+}
+
+impl ApprovalStore {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self {
+            pool,
+            notifier: None,
+        }
+    }
+
+    /// Alert operators subscribed to approvals whenever a request is queued.
+    pub fn with_notifier(mut self, notifier: Option<OperatorNotifier>) -> Self {
+        self.notifier = notifier;
+        self
+    }
+
+    pub async fn request(
+        &self,
+        tool_name: &str,
+        summary: &str,
+        payload: &serde_json::Value,
+        channel_id: Option<&str>,
+        requested_by: &str,
+    ) -> Result<ToolApproval> {
+        let id = uuid::Uuid::new_v4().to_string();
+        sqlx::query(
+            "INSERT INTO tool_approvals (id, tool_name, summary, payload, channel_id, requested_by) \
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&id)
+        .bind(tool_name)
+        .bind(summary)
+        .bind(payload.to_string())
+        .bind(channel_id)
+        .bind(requested_by)
+        .execute(&self.pool)
+        .await
+        .context("failed to record tool approval request")?;
+
+        let approval = self
+            .get(&id)
+            .await?
+            .context("approval inserted but not found")?;
+
+        if let Some(notifier) = self.notifier.clone() {
+            let text = format!(
+                "Approval needed for `{tool_name}` (requested by {requested_by}): {summary}\nID: {id}"
+            );
+            tokio::spawn(async move {
+                notifier
+                    .notify(NotificationCategory::Approvals, &text)
+                    .await;
+            });
+        }
+
+        Ok(approval)
+    }
+
+    pub async fn get(&self, id: &str) -> Result<Option<ToolApproval>> {
+        let row = sqlx::query(&format!(
+            "SELECT {COLUMNS} FROM tool_approvals WHERE id = ?"
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .context("failed to load tool approval")?;
+        Ok(row.map(|row| row_to_approval(&row)))
+    }
+
+    /// Newest first, optionally filtered by status.
+    pub async fn list(
+        &self,
+        status: Option<ApprovalStatus>,
+        limit: i64,
+    ) -> Result<Vec<ToolApproval>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {COLUMNS} FROM tool_approvals \
+             WHERE (?1 IS NULL OR status = ?1) ORDER BY created_at DESC, id LIMIT ?2"
+        ))
+        .bind(status.map(ApprovalStatus::as_str))
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .context("failed to list tool approvals")?;
+        Ok(rows.iter().map(row_to_approval).collect())
+    }
+
+    /// Move a pending request to `to`. Returns `None` when it doesn't exist
+    /// or was already decided, so two approvers can't both run it.
+    pub async fn decide(
+        &self,
+        id: &str,
+        to: ApprovalStatus,
+        decided_by: &str,
+    ) -> Result<Option<ToolApproval>> {
+        let updated = sqlx::query(
+            "UPDATE tool_approvals SET status = ?, decided_by = ?, decided_at = datetime('now') \
+             WHERE id = ? AND status = 'pending'",
+        )
+        .bind(to.as_str())
+        .bind(decided_by)
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .context("failed to update tool approval")?;
+        if updated.rows_affected() == 0 {
+            return Ok(None);
+        }
+        self.get(id).await
+    }
+
+    /// Record the outcome of an approved call.
+    pub async fn finish(
+        &self,
+        id: &str,
+        outcome: &std::result::Result<String, String>,
+    ) -> Result<()> {
+        let (status, result) = match outcome {
+            Ok(output) => (ApprovalStatus::Executed, output),
+            Err(error) => (ApprovalStatus::Failed, error),
+        };
+        sqlx::query("UPDATE tool_approvals SET status = ?, result = ? WHERE id = ?")
+            .bind(status.as_str())
+            .bind(result)
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .context("failed to record tool approval result")?;
+        Ok(())
+    }
+}
+
+/// Run an approved call against the agent's current config.
+pub async fn execute(
+    approval: &ToolApproval,
+    runtime_config: &RuntimeConfig,
+    messaging_manager: Option<&MessagingManager>,
+) -> std::result::Result<String, String> {
+    let output: Result<serde_json::Value> = match approval.tool_name.as_str() {
+        name if name == IssueTrackerTool::NAME => {
+            match serde_json::from_value::<IssueMutation>(approval.payload.clone()) {
+                Ok(mutation) => {
+                    IssueTrackers::new(&runtime_config.issue_trackers.load())
+                        .apply(&mutation)
+                        .await
+                }
+                Err(error) => Err(anyhow!("malformed issue tracker request: {error}").into()),
+            }
+        }
+        name if name == BroadcastTool::NAME => {
+            execute_broadcast(&approval.payload, runtime_config, messaging_manager).await
+        }
+        other => Err(anyhow!("no executor for tool '{other}'").into()),
+    };
+    output
+        .map(|value| value.to_string())
+        .map_err(|error| error.to_string())
+}
+
+/// Sends to the group as configured now, so targets edited while the
+/// request waited are honoured.
+async fn execute_broadcast(
+    payload: &serde_json::Value,
+    runtime_config: &RuntimeConfig,
+    messaging_manager: Option<&MessagingManager>,
+) -> Result<serde_json::Value> {
+    let request: GroupBroadcast = serde_json::from_value(payload.clone())
+        .map_err(|error| anyhow!("malformed broadcast request: {error}"))?;
+    let messaging_manager = messaging_manager.ok_or_else(|| anyhow!("messaging is not running"))?;
+    let config = runtime_config.broadcast.load();
+    let targets = config
+        .groups
+        .get(&request.group)
+        .ok_or_else(|| anyhow!("broadcast group '{}' no longer exists", request.group))?;
+    let report = fan_out(
+        messaging_manager,
+        targets,
+        &request.announcement,
+        std::time::Duration::from_millis(config.stagger_ms),
+    )
+    .await;
+    serde_json::to_value(report)
+        .context("failed to serialize broadcast report")
+        .map_err(Into::into)
+}
+
+fn row_to_approval(row: &sqlx::sqlite::SqliteRow) -> ToolApproval {
+    let payload: String = row.try_get("payload").unwrap_or_default();
+    let status: String = row.try_get("status").unwrap_or_default();
+    ToolApproval {
+        id: row.try_get("id").unwrap_or_default(),
+        tool_name: row.try_get("tool_name").unwrap_or_default(),
+        summary: row.try_get("summary").unwrap_or_default(),
+        payload: serde_json::from_str(&payload).unwrap_or_default(),
+        channel_id: row.try_get("channel_id").ok().flatten(),
+        requested_by: row.try_get("requested_by").unwrap_or_default(),
+        status: ApprovalStatus::parse(&status).unwrap_or(ApprovalStatus::Failed),
+        result: row.try_get("result").ok().flatten(),
+        decided_by: row.try_get("decided_by").ok().flatten(),
+        created_at: row.try_get("created_at").unwrap_or_default(),
+        decided_at: row.try_get("decided_at").ok().flatten(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn requests_are_decided_once() {
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("failed to create sqlite memory pool");
+        sqlx::migrate!("./migrations")
+            .run(&pool)
+            .await
+            .expect("migrations");
+        let store = ApprovalStore::new(pool);
+
+        let approval = store
+            .request(
+                "issue_tracker",
+                "Comment on work/OPS-1",
+                &serde_json::json!({"action": "comment"}),
+                Some("discord:1"),
+                "branch",
+            )
+            .await
+            .unwrap();
+        assert_eq!(approval.status, ApprovalStatus::Pending);
+        assert_eq!(
+            store
+                .list(Some(ApprovalStatus::Pending), 10)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+
+        let claimed = store
+            .decide(&approval.id, ApprovalStatus::Approved, "admin")
+            .await
+            .unwrap()
+            .expect("pending request should be claimable");
+        assert_eq!(claimed.decided_by.as_deref(), Some("admin"));
+        assert!(
+            store
+                .decide(&approval.id, ApprovalStatus::Rejected, "admin")
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        store
+            .finish(&approval.id, &Err("boom".to_string()))
+            .await
+            .unwrap();
+        let finished = store.get(&approval.id).await.unwrap().unwrap();
+        assert_eq!(finished.status, ApprovalStatus::Failed);
+        assert_eq!(finished.result.as_deref(), Some("boom"));
+        assert!(
+            store
+                .list(Some(ApprovalStatus::Pending), 10)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
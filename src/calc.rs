@@ -0,0 +1,418 @@
+//! Exact decimal arithmetic and unit conversion for the `calculate` and
+//! `convert` tools.
+//!
+//! Models are unreliable at arithmetic, so numeric work is routed here.
+//! Expressions are evaluated on 96-bit decimals (28 significant digits), so
+//! `0.1 + 0.2` is exactly `0.3`; roots, logarithms, and fractional powers are
+//! rounded to that precision. Units convert through exact factors
+//! ([`units`]), and currencies through rates from a
+//! [`currency::RatesProvider`].
+
+pub mod currency;
+pub mod units;
+
+use rust_decimal::prelude::ToPrimitive as _;
+use rust_decimal::{Decimal, MathematicalOps, RoundingStrategy};
+
+use std::str::FromStr as _;
+
+/// Longest expression accepted.
+pub const MAX_EXPRESSION_CHARS: usize = 1_000;
+
+/// Deepest nesting of parentheses and unary operators.
+const MAX_DEPTH: usize = 64;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum CalcError {
+    #[error("{message} at position {position}")]
+    Syntax { message: String, position: usize },
+    #[error("division by zero")]
+    DivisionByZero,
+    #[error("result is out of range")]
+    Overflow,
+    #[error("{0}")]
+    Domain(String),
+    #[error("unknown function or constant '{0}'")]
+    UnknownName(String),
+    #[error("unknown unit '{0}'")]
+    UnknownUnit(String),
+    #[error("can't convert {from} to {to}")]
+    IncompatibleUnits { from: String, to: String },
+}
+
+/// Evaluate an arithmetic expression.
+///
+/// Supports `+ - * / % ^`, parentheses, `pi`, `e`, and the functions `abs`,
+/// `sqrt`, `round(x[, places])`, `floor`, `ceil`, `trunc`, `min`, `max`,
+/// `ln`, `log10`, and `exp`. `^` binds tighter than unary minus, so `-2^2`
+/// is `-4`. Numbers may use `_` separators and exponents (`1.5e6`).
+pub fn evaluate(expression: &str) -> Result<Decimal, CalcError> {
+    if expression.chars().count() > MAX_EXPRESSION_CHARS {
+        return Err(CalcError::Domain(format!(
+            "expression is longer than {MAX_EXPRESSION_CHARS} characters"
+        )));
+    }
+    let tokens = tokenize(expression)?;
+    let mut parser = Parser {
+        tokens,
+        index: 0,
+        depth: 0,
+        end: expression.chars().count(),
+    };
+    let value = parser.expression()?;
+    if let Some((_, position)) = parser.peek() {
+        return Err(syntax("unexpected input", *position));
+    }
+    Ok(value.normalize())
+}
+
+/// Parse a number the way expressions do, for tool arguments.
+pub fn parse_number(text: &str) -> Result<Decimal, CalcError> {
+    let text = text.trim();
+    match evaluate(text) {
+        Ok(value) => Ok(value),
+        Err(CalcError::Syntax { .. }) | Err(CalcError::UnknownName(_)) => {
+            Err(CalcError::Domain(format!("'{text}' is not a number")))
+        }
+        Err(error) => Err(error),
+    }
+}
+
+fn syntax(message: impl Into<String>, position: usize) -> CalcError {
+    CalcError::Syntax {
+        message: message.into(),
+        position,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(Decimal),
+    Name(String),
+    Operator(char),
+    Open,
+    Close,
+    Comma,
+}
+
+fn tokenize(expression: &str) -> Result<Vec<(Token, usize)>, CalcError> {
+    let chars: Vec<char> = expression.chars().collect();
+    let mut tokens = Vec::new();
+    let mut index = 0;
+
+    while index < chars.len() {
+        let character = chars[index];
+        let start = index;
+        match character {
+            _ if character.is_whitespace() => index += 1,
+            '0'..='9' | '.' => {
+                let mut literal = String::new();
+                while index < chars.len()
+                    && (chars[index].is_ascii_digit() || matches!(chars[index], '.' | '_'))
+                {
+                    if chars[index] != '_' {
+                        literal.push(chars[index]);
+                    }
+                    index += 1;
+                }
+                // Only an `e` followed by digits is an exponent; otherwise
+                // it's left for the `e` constant and reported as an error.
+                if index < chars.len() && matches!(chars[index], 'e' | 'E') {
+                    let mut lookahead = index + 1;
+                    if lookahead < chars.len() && matches!(chars[lookahead], '+' | '-') {
+                        lookahead += 1;
+                    }
+                    if lookahead < chars.len() && chars[lookahead].is_ascii_digit() {
+                        literal.push('e');
+                        literal.extend(&chars[index + 1..lookahead]);
+                        index = lookahead;
+                        while index < chars.len() && chars[index].is_ascii_digit() {
+                            literal.push(chars[index]);
+                            index += 1;
+                        }
+                    }
+                }
+                let value = if literal.contains('e') {
+                    Decimal::from_scientific(&literal)
+                } else {
+                    Decimal::from_str(&literal)
+                }
+                .map_err(|_| syntax(format!("invalid number '{literal}'"), start))?;
+                tokens.push((Token::Number(value), start));
+            }
+            _ if character.is_alphabetic() => {
+                let mut name = String::new();
+                while index < chars.len() && (chars[index].is_alphanumeric() || chars[index] == '_')
+                {
+                    name.push(chars[index]);
+                    index += 1;
+                }
+                tokens.push((Token::Name(name.to_lowercase()), start));
+            }
+            '+' | '-' | '*' | '/' | '%' | '^' => {
+                tokens.push((Token::Operator(character), start));
+                index += 1;
+            }
+            '×' => {
+                tokens.push((Token::Operator('*'), start));
+                index += 1;
+            }
+            '÷' => {
+                tokens.push((Token::Operator('/'), start));
+                index += 1;
+            }
+            '(' => {
+                tokens.push((Token::Open, start));
+                index += 1;
+            }
+            ')' => {
+                tokens.push((Token::Close, start));
+                index += 1;
+            }
+            ',' => {
+                tokens.push((Token::Comma, start));
+                index += 1;
+            }
+            other => return Err(syntax(format!("unexpected '{other}'"), start)),
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Recursive-descent evaluator; values are computed while parsing.
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    index: usize,
+    depth: usize,
+    /// Position reported for errors at the end of input.
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&(Token, usize)> {
+        self.tokens.get(self.index)
+    }
+
+    fn next(&mut self) -> Option<(Token, usize)> {
+        let token = self.tokens.get(self.index).cloned();
+        self.index += 1;
+        token
+    }
+
+    fn position(&self) -> usize {
+        self.peek().map_or(self.end, |(_, position)| *position)
+    }
+
+    fn eat_operator(&mut self, operators: &[char]) -> Option<char> {
+        match self.peek() {
+            Some((Token::Operator(operator), _)) if operators.contains(operator) => {
+                let operator = *operator;
+                self.index += 1;
+                Some(operator)
+            }
+            _ => None,
+        }
+    }
+
+    fn expect(&mut self, expected: Token, what: &str) -> Result<(), CalcError> {
+        let position = self.position();
+        match self.next() {
+            Some((token, _)) if token == expected => Ok(()),
+            _ => Err(syntax(format!("expected {what}"), position)),
+        }
+    }
+
+    fn expression(&mut self) -> Result<Decimal, CalcError> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err(syntax("expression is nested too deeply", self.position()));
+        }
+        let mut value = self.term()?;
+        while let Some(operator) = self.eat_operator(&['+', '-']) {
+            let right = self.term()?;
+            value = match operator {
+                '+' => value.checked_add(right),
+                _ => value.checked_sub(right),
+            }
+            .ok_or(CalcError::Overflow)?;
+        }
+        self.depth -= 1;
+        Ok(value)
+    }
+
+    fn term(&mut self) -> Result<Decimal, CalcError> {
+        let mut value = self.unary()?;
+        while let Some(operator) = self.eat_operator(&['*', '/', '%']) {
+            let right = self.unary()?;
+            value = match operator {
+                '*' => value.checked_mul(right).ok_or(CalcError::Overflow)?,
+                _ if right.is_zero() => return Err(CalcError::DivisionByZero),
+                '/' => value.checked_div(right).ok_or(CalcError::Overflow)?,
+                _ => value.checked_rem(right).ok_or(CalcError::Overflow)?,
+            };
+        }
+        Ok(value)
+    }
+
+    fn unary(&mut self) -> Result<Decimal, CalcError> {
+        match self.eat_operator(&['-', '+']) {
+            Some(operator) => {
+                self.depth += 1;
+                if self.depth > MAX_DEPTH {
+                    return Err(syntax("expression is nested too deeply", self.position()));
+                }
+                let value = self.unary()?;
+                self.depth -= 1;
+                Ok(if operator == '-' { -value } else { value })
+            }
+            None => self.power(),
+        }
+    }
+
+    fn power(&mut self) -> Result<Decimal, CalcError> {
+        let base = self.primary()?;
+        if self.eat_operator(&['^']).is_none() {
+            return Ok(base);
+        }
+        // Right-associative, and the exponent may carry its own sign.
+        let exponent = self.unary()?;
+        power(base, exponent)
+    }
+
+    fn primary(&mut self) -> Result<Decimal, CalcError> {
+        let position = self.position();
+        match self.next() {
+            Some((Token::Number(value), _)) => Ok(value),
+            Some((Token::Open, _)) => {
+                let value = self.expression()?;
+                self.expect(Token::Close, "')'")?;
+                Ok(value)
+            }
+            Some((Token::Name(name), _)) => {
+                if matches!(self.peek(), Some((Token::Open, _))) {
+                    self.index += 1;
+                    let mut arguments = vec![self.expression()?];
+                    while matches!(self.peek(), Some((Token::Comma, _))) {
+                        self.index += 1;
+                        arguments.push(self.expression()?);
+                    }
+                    self.expect(Token::Close, "')'")?;
+                    call(&name, &arguments)
+                } else {
+                    constant(&name)
+                }
+            }
+            Some(_) => Err(syntax("expected a number", position)),
+            None => Err(syntax("unexpected end of expression", position)),
+        }
+    }
+}
+
+fn constant(name: &str) -> Result<Decimal, CalcError> {
+    match name {
+        "pi" | "π" => Ok(Decimal::PI),
+        "e" => Ok(Decimal::E),
+        _ => Err(CalcError::UnknownName(name.to_string())),
+    }
+}
+
+fn call(name: &str, arguments: &[Decimal]) -> Result<Decimal, CalcError> {
+    let one = |arguments: &[Decimal]| match arguments {
+        [value] => Ok(*value),
+        _ => Err(CalcError::Domain(format!("{name}() takes one argument"))),
+    };
+    match name {
+        "abs" => Ok(one(arguments)?.abs()),
+        "floor" => Ok(one(arguments)?.floor()),
+        "ceil" => Ok(one(arguments)?.ceil()),
+        "trunc" => Ok(one(arguments)?.trunc()),
+        "round" => match arguments {
+            [value] => Ok(value.round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero)),
+            [value, places] => {
+                let places = places
+                    .fract()
+                    .is_zero()
+                    .then(|| places.to_u32())
+                    .flatten()
+                    .filter(|places| *places <= 28)
+                    .ok_or_else(|| {
+                        CalcError::Domain("round() places must be a whole number 0-28".into())
+                    })?;
+                Ok(value.round_dp_with_strategy(places, RoundingStrategy::MidpointAwayFromZero))
+            }
+            _ => Err(CalcError::Domain(
+                "round() takes one or two arguments".into(),
+            )),
+        },
+        "min" => arguments.iter().copied().min().ok_or(CalcError::Overflow),
+        "max" => arguments.iter().copied().max().ok_or(CalcError::Overflow),
+        "sqrt" => {
+            let value = one(arguments)?;
+            if value.is_sign_negative() && !value.is_zero() {
+                return Err(CalcError::Domain("sqrt() of a negative number".into()));
+            }
+            value.sqrt().ok_or(CalcError::Overflow)
+        }
+        "ln" | "log10" => {
+            let value = one(arguments)?;
+            if value <= Decimal::ZERO {
+                return Err(CalcError::Domain(format!(
+                    "{name}() needs a positive number"
+                )));
+            }
+            if name == "ln" {
+                value.checked_ln()
+            } else {
+                value.checked_log10()
+            }
+            .ok_or(CalcError::Overflow)
+        }
+        "exp" => one(arguments)?.checked_exp().ok_or(CalcError::Overflow),
+        _ => Err(CalcError::UnknownName(name.to_string())),
+    }
+}
+
+fn power(base: Decimal, exponent: Decimal) -> Result<Decimal, CalcError> {
+    if exponent.fract().is_zero() {
+        let exponent = exponent.to_i64().ok_or(CalcError::Overflow)?;
+        if base.is_zero() && exponent < 0 {
+            return Err(CalcError::DivisionByZero);
+        }
+        return base.checked_powi(exponent).ok_or(CalcError::Overflow);
+    }
+    if base.is_sign_negative() && !base.is_zero() {
+        return Err(CalcError::Domain(
+            "fractional power of a negative number".into(),
+        ));
+    }
+    base.checked_powd(exponent).ok_or(CalcError::Overflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(expression: &str) -> String {
+        evaluate(expression).unwrap().to_string()
+    }
+
+    #[test]
+    fn evaluates_exactly_with_precedence() {
+        assert_eq!(eval("0.1 + 0.2"), "0.3");
+        assert_eq!(eval("2 + 3 * 4 ^ 2"), "50");
+        assert_eq!(eval("-2^2"), "-4");
+        assert_eq!(eval("2^-2"), "0.25");
+        assert_eq!(eval("2^3^2"), "512");
+        assert_eq!(eval("(1_000 - 1.5e2) / 4 % 7"), "2.5");
+        assert_eq!(eval("round(19.995, 2) + max(1, 3, 2)"), "23");
+        assert_eq!(eval("abs(-12) × 2 ÷ 3"), "8");
+        assert_eq!(evaluate("1 / 0"), Err(CalcError::DivisionByZero));
+        assert!(matches!(
+            evaluate("2 +"),
+            Err(CalcError::Syntax { position: 3, .. })
+        ));
+        assert!(matches!(evaluate("foo(2)"), Err(CalcError::UnknownName(_))));
+        assert!(matches!(evaluate("sqrt(-1)"), Err(CalcError::Domain(_))));
+    }
+}
@@ -0,0 +1,196 @@
+//! Currency conversion through a pluggable exchange-rate provider.
+//!
+//! Rates are cached process-wide per base currency and reused for the agent's
+//! `rates_cache_ttl_secs`. Reference rates only move once a day, so a short
+//! TTL buys little beyond extra requests.
+
+use crate::config::{CalculatorConfig, CurrencyProvider};
+use crate::error::Result;
+
+use anyhow::{Context as _, anyhow};
+use moka::sync::Cache;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+use std::collections::HashMap;
+use std::str::FromStr as _;
+use std::sync::{Arc, LazyLock};
+use std::time::{Duration, Instant};
+
+const FRANKFURTER_URL: &str = "https://api.frankfurter.app/latest";
+
+const CACHE_CAPACITY: u64 = 200;
+/// Hard ceiling on rate reuse; the per-agent TTL is checked on read.
+const RATES_CACHE_MAX_TTL: Duration = Duration::from_secs(60 * 60 * 24);
+
+static RATES_CACHE: LazyLock<Cache<String, (Instant, Arc<ExchangeRates>)>> = LazyLock::new(|| {
+    Cache::builder()
+        .max_capacity(CACHE_CAPACITY)
+        .time_to_live(RATES_CACHE_MAX_TTL)
+        .build()
+});
+
+static HTTP_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
+    reqwest::Client::builder()
+        .gzip(true)
+        .timeout(Duration::from_secs(20))
+        .build()
+        .expect("hardcoded reqwest client config")
+});
+
+/// Rates from one base currency, as units of each quote currency per unit of
+/// base.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExchangeRates {
+    pub base: String,
+    /// Date the provider published the rates, `YYYY-MM-DD`.
+    pub date: String,
+    pub rates: HashMap<String, Decimal>,
+}
+
+#[async_trait::async_trait]
+pub trait RatesProvider: Send + Sync {
+    /// Latest rates for `base`, an uppercase ISO 4217 code.
+    async fn rates(&self, base: &str) -> Result<ExchangeRates>;
+}
+
+/// A converted amount.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conversion {
+    pub result: Decimal,
+    /// Units of the target currency per unit of the source.
+    pub rate: Decimal,
+    pub date: String,
+}
+
+/// Whether `code` looks like an ISO 4217 currency code.
+pub fn is_currency_code(code: &str) -> bool {
+    let code = code.trim();
+    code.len() == 3
+        && code
+            .chars()
+            .all(|character| character.is_ascii_alphabetic())
+}
+
+/// The rates provider configured for one agent, with shared caching.
+pub struct Currency {
+    provider_kind: CurrencyProvider,
+    provider: Arc<dyn RatesProvider>,
+    ttl: Duration,
+}
+
+impl Currency {
+    /// `None` when currency conversion is turned off.
+    pub fn new(config: &CalculatorConfig) -> Option<Self> {
+        let provider_kind = config.currency_provider?;
+        let provider: Arc<dyn RatesProvider> = match provider_kind {
+            CurrencyProvider::Frankfurter => {
+                Arc::new(FrankfurterProvider::new(HTTP_CLIENT.clone()))
+            }
+        };
+        Some(Self {
+            provider_kind,
+            provider,
+            ttl: Duration::from_secs(config.rates_cache_ttl_secs),
+        })
+    }
+
+    pub async fn convert(&self, amount: Decimal, from: &str, to: &str) -> Result<Conversion> {
+        let from = from.trim().to_uppercase();
+        let to = to.trim().to_uppercase();
+        let rates = self.rates(&from).await?;
+        let rate = if to == from {
+            Decimal::ONE
+        } else {
+            *rates
+                .rates
+                .get(&to)
+                .ok_or_else(|| anyhow!("no exchange rate from {from} to {to}"))?
+        };
+        let result = amount
+            .checked_mul(rate)
+            .ok_or_else(|| anyhow!("converted amount is out of range"))?;
+        Ok(Conversion {
+            result: result.normalize(),
+            rate: rate.normalize(),
+            date: rates.date.clone(),
+        })
+    }
+
+    async fn rates(&self, base: &str) -> Result<Arc<ExchangeRates>> {
+        let key = format!("{:?}|{base}", self.provider_kind);
+        if let Some((fetched_at, rates)) = RATES_CACHE.get(&key)
+            && fetched_at.elapsed() < self.ttl
+        {
+            return Ok(rates);
+        }
+
+        let rates = Arc::new(self.provider.rates(base).await?);
+        if !self.ttl.is_zero() {
+            RATES_CACHE.insert(key, (Instant::now(), rates.clone()));
+        }
+        Ok(rates)
+    }
+}
+
+/// Frankfurter: keyless, serving the European Central Bank's daily reference
+/// rates.
+pub struct FrankfurterProvider {
+    client: reqwest::Client,
+}
+
+impl FrankfurterProvider {
+    pub fn new(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait::async_trait]
+impl RatesProvider for FrankfurterProvider {
+    async fn rates(&self, base: &str) -> Result<ExchangeRates> {
+        let response = self
+            .client
+            .get(FRANKFURTER_URL)
+            .query(&[("from", base)])
+            .send()
+            .await
+            .context("frankfurter request failed")?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            let body: String = body.chars().take(500).collect();
+            return Err(anyhow!("frankfurter request failed with {status}: {body}").into());
+        }
+        let response: FrankfurterResponse = response
+            .json()
+            .await
+            .context("invalid frankfurter response")?;
+
+        let rates = response
+            .rates
+            .into_iter()
+            .filter_map(|(code, rate)| Some((code, decimal_from_number(&rate)?)))
+            .collect();
+        Ok(ExchangeRates {
+            base: response.base,
+            date: response.date,
+            rates,
+        })
+    }
+}
+
+fn decimal_from_number(number: &serde_json::Number) -> Option<Decimal> {
+    let text = number.to_string();
+    Decimal::from_str(&text)
+        .or_else(|_| Decimal::from_scientific(&text))
+        .ok()
+}
+
+// -- API response types (private, only model what we need) --
+
+#[derive(Debug, Deserialize)]
+struct FrankfurterResponse {
+    base: String,
+    date: String,
+    rates: HashMap<String, serde_json::Number>,
+}
@@ -0,0 +1,286 @@
+//! Physical unit conversion through exact factors.
+//!
+//! Every unit is defined as a ratio to its dimension's base unit, using the
+//! exact international definitions (an inch is 0.0254 m, a pound
+//! 0.45359237 kg), so conversions only round where a ratio doesn't
+//! terminate. Temperatures are affine and handled separately.
+
+use super::CalcError;
+
+use rust_decimal::Decimal;
+
+use std::str::FromStr as _;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dimension {
+    Length,
+    Mass,
+    Volume,
+    Area,
+    Time,
+    Speed,
+    Energy,
+    Data,
+    Temperature,
+}
+
+impl Dimension {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Length => "length",
+            Self::Mass => "mass",
+            Self::Volume => "volume",
+            Self::Area => "area",
+            Self::Time => "time",
+            Self::Speed => "speed",
+            Self::Energy => "energy",
+            Self::Data => "data size",
+            Self::Temperature => "temperature",
+        }
+    }
+}
+
+struct Unit {
+    names: &'static [&'static str],
+    dimension: Dimension,
+    /// Base units per unit, as numerator and denominator.
+    ratio: (&'static str, &'static str),
+}
+
+const fn unit(
+    names: &'static [&'static str],
+    dimension: Dimension,
+    numerator: &'static str,
+    denominator: &'static str,
+) -> Unit {
+    Unit {
+        names,
+        dimension,
+        ratio: (numerator, denominator),
+    }
+}
+
+use Dimension::*;
+
+#[rustfmt::skip]
+const UNITS: &[Unit] = &[
+    // Length, base metre.
+    unit(&["m", "meter", "metre"], Length, "1", "1"),
+    unit(&["km", "kilometer", "kilometre"], Length, "1000", "1"),
+    unit(&["cm", "centimeter", "centimetre"], Length, "0.01", "1"),
+    unit(&["mm", "millimeter", "millimetre"], Length, "0.001", "1"),
+    unit(&["um", "µm", "micrometer", "micron"], Length, "0.000001", "1"),
+    unit(&["nm", "nanometer", "nanometre"], Length, "0.000000001", "1"),
+    unit(&["in", "inch", "inches"], Length, "0.0254", "1"),
+    unit(&["ft", "foot", "feet"], Length, "0.3048", "1"),
+    unit(&["yd", "yard"], Length, "0.9144", "1"),
+    unit(&["mi", "mile"], Length, "1609.344", "1"),
+    unit(&["nmi", "nautical mile"], Length, "1852", "1"),
+    // Mass, base kilogram.
+    unit(&["kg", "kilogram", "kilo"], Mass, "1", "1"),
+    unit(&["g", "gram"], Mass, "0.001", "1"),
+    unit(&["mg", "milligram"], Mass, "0.000001", "1"),
+    unit(&["t", "tonne", "metric ton"], Mass, "1000", "1"),
+    unit(&["lb", "lbs", "pound"], Mass, "0.45359237", "1"),
+    unit(&["oz", "ounce"], Mass, "0.028349523125", "1"),
+    unit(&["st", "stone"], Mass, "6.35029318", "1"),
+    // Volume, base litre. US customary measures.
+    unit(&["l", "liter", "litre"], Volume, "1", "1"),
+    unit(&["ml", "milliliter", "millilitre"], Volume, "0.001", "1"),
+    unit(&["m3", "cubic meter", "cubic metre"], Volume, "1000", "1"),
+    unit(&["gal", "gallon"], Volume, "3.785411784", "1"),
+    unit(&["qt", "quart"], Volume, "0.946352946", "1"),
+    unit(&["pt", "pint"], Volume, "0.473176473", "1"),
+    unit(&["cup"], Volume, "0.2365882365", "1"),
+    unit(&["floz", "fl oz", "fluid ounce"], Volume, "0.0295735295625", "1"),
+    unit(&["tbsp", "tablespoon"], Volume, "0.01478676478125", "1"),
+    unit(&["tsp", "teaspoon"], Volume, "0.00492892159375", "1"),
+    // Area, base square metre.
+    unit(&["m2", "square meter", "square metre", "sqm"], Area, "1", "1"),
+    unit(&["km2", "square kilometer", "square kilometre"], Area, "1000000", "1"),
+    unit(&["cm2", "square centimeter", "square centimetre"], Area, "0.0001", "1"),
+    unit(&["ft2", "square foot", "square feet", "sqft"], Area, "0.09290304", "1"),
+    unit(&["mi2", "square mile"], Area, "2589988.110336", "1"),
+    unit(&["ha", "hectare"], Area, "10000", "1"),
+    unit(&["acre"], Area, "4046.8564224", "1"),
+    // Time, base second. A year is the Julian year.
+    unit(&["s", "sec", "second"], Time, "1", "1"),
+    unit(&["ms", "millisecond"], Time, "0.001", "1"),
+    unit(&["min", "minute"], Time, "60", "1"),
+    unit(&["h", "hr", "hour"], Time, "3600", "1"),
+    unit(&["d", "day"], Time, "86400", "1"),
+    unit(&["wk", "week"], Time, "604800", "1"),
+    unit(&["yr", "year"], Time, "31557600", "1"),
+    // Speed, base metre per second.
+    unit(&["m/s", "mps"], Speed, "1", "1"),
+    unit(&["km/h", "kmh", "kph"], Speed, "1000", "3600"),
+    unit(&["mph", "mi/h"], Speed, "1609.344", "3600"),
+    unit(&["kn", "knot"], Speed, "1852", "3600"),
+    unit(&["ft/s", "fps"], Speed, "0.3048", "1"),
+    // Energy, base joule.
+    unit(&["j", "joule"], Energy, "1", "1"),
+    unit(&["kj", "kilojoule"], Energy, "1000", "1"),
+    unit(&["cal", "calorie"], Energy, "4.184", "1"),
+    unit(&["kcal", "kilocalorie"], Energy, "4184", "1"),
+    unit(&["wh", "watt hour"], Energy, "3600", "1"),
+    unit(&["kwh", "kilowatt hour"], Energy, "3600000", "1"),
+    // Data, base byte. Names are case-insensitive, so `mb` is megabytes.
+    unit(&["b", "byte"], Data, "1", "1"),
+    unit(&["bit"], Data, "1", "8"),
+    unit(&["kb", "kilobyte"], Data, "1000", "1"),
+    unit(&["mb", "megabyte"], Data, "1000000", "1"),
+    unit(&["gb", "gigabyte"], Data, "1000000000", "1"),
+    unit(&["tb", "terabyte"], Data, "1000000000000", "1"),
+    unit(&["kib", "kibibyte"], Data, "1024", "1"),
+    unit(&["mib", "mebibyte"], Data, "1048576", "1"),
+    unit(&["gib", "gibibyte"], Data, "1073741824", "1"),
+    unit(&["tib", "tebibyte"], Data, "1099511627776", "1"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TemperatureScale {
+    Celsius,
+    Fahrenheit,
+    Kelvin,
+}
+
+enum Resolved {
+    Linear(&'static Unit),
+    Temperature(TemperatureScale),
+}
+
+impl Resolved {
+    fn dimension(&self) -> Dimension {
+        match self {
+            Self::Linear(unit) => unit.dimension,
+            Self::Temperature(_) => Dimension::Temperature,
+        }
+    }
+}
+
+/// Whether `name` is a unit this module knows.
+pub fn is_known(name: &str) -> bool {
+    resolve(name).is_some()
+}
+
+/// Convert `value` from one unit to another of the same dimension.
+pub fn convert(value: Decimal, from: &str, to: &str) -> Result<Decimal, CalcError> {
+    let source = resolve(from).ok_or_else(|| CalcError::UnknownUnit(from.to_string()))?;
+    let target = resolve(to).ok_or_else(|| CalcError::UnknownUnit(to.to_string()))?;
+    if source.dimension() != target.dimension() {
+        return Err(CalcError::IncompatibleUnits {
+            from: format!("{from} ({})", source.dimension().label()),
+            to: format!("{to} ({})", target.dimension().label()),
+        });
+    }
+
+    let result = match (source, target) {
+        (Resolved::Linear(source), Resolved::Linear(target)) => {
+            // value * source / target, multiplying before dividing so exact
+            // ratios stay exact.
+            let numerator = value
+                .checked_mul(ratio_part(source.ratio.0))
+                .and_then(|value| value.checked_mul(ratio_part(target.ratio.1)))
+                .ok_or(CalcError::Overflow)?;
+            let denominator = ratio_part(source.ratio.1) * ratio_part(target.ratio.0);
+            numerator
+                .checked_div(denominator)
+                .ok_or(CalcError::Overflow)?
+        }
+        (Resolved::Temperature(source), Resolved::Temperature(target)) => {
+            from_kelvin(to_kelvin(value, source)?, target)?
+        }
+        _ => unreachable!("dimensions were checked above"),
+    };
+    Ok(result.normalize())
+}
+
+fn ratio_part(text: &str) -> Decimal {
+    Decimal::from_str(text).expect("hardcoded unit ratio")
+}
+
+fn resolve(name: &str) -> Option<Resolved> {
+    let normalized = name
+        .trim()
+        .to_lowercase()
+        .replace(['°', 'º'], "")
+        .replace('²', "2")
+        .replace('³', "3");
+    let normalized = normalized.trim();
+
+    match normalized {
+        "c" | "celsius" | "degc" => return Some(Resolved::Temperature(TemperatureScale::Celsius)),
+        "f" | "fahrenheit" | "degf" => {
+            return Some(Resolved::Temperature(TemperatureScale::Fahrenheit));
+        }
+        "k" | "kelvin" => return Some(Resolved::Temperature(TemperatureScale::Kelvin)),
+        _ => {}
+    }
+
+    let find = |candidate: &str| {
+        UNITS
+            .iter()
+            .find(|unit| unit.names.contains(&candidate))
+            .map(Resolved::Linear)
+    };
+    // Accept plurals ("miles", "hours") without listing each one.
+    find(normalized).or_else(|| normalized.strip_suffix('s').and_then(find))
+}
+
+fn to_kelvin(value: Decimal, scale: TemperatureScale) -> Result<Decimal, CalcError> {
+    let offset = Decimal::new(27315, 2);
+    match scale {
+        TemperatureScale::Kelvin => Some(value),
+        TemperatureScale::Celsius => value.checked_add(offset),
+        TemperatureScale::Fahrenheit => value
+            .checked_sub(Decimal::from(32))
+            .and_then(|value| value.checked_mul(Decimal::from(5)))
+            .and_then(|value| value.checked_div(Decimal::from(9)))
+            .and_then(|value| value.checked_add(offset)),
+    }
+    .ok_or(CalcError::Overflow)
+}
+
+fn from_kelvin(value: Decimal, scale: TemperatureScale) -> Result<Decimal, CalcError> {
+    let offset = Decimal::new(27315, 2);
+    match scale {
+        TemperatureScale::Kelvin => Some(value),
+        TemperatureScale::Celsius => value.checked_sub(offset),
+        TemperatureScale::Fahrenheit => value
+            .checked_sub(offset)
+            .and_then(|value| value.checked_mul(Decimal::from(9)))
+            .and_then(|value| value.checked_div(Decimal::from(5)))
+            .and_then(|value| value.checked_add(Decimal::from(32))),
+    }
+    .ok_or(CalcError::Overflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(value: &str, from: &str, to: &str) -> String {
+        convert(Decimal::from_str(value).unwrap(), from, to)
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn converts_with_exact_factors() {
+        assert_eq!(run("1", "mile", "km"), "1.609344");
+        assert_eq!(run("10", "lbs", "kg"), "4.5359237");
+        assert_eq!(run("90", "km/h", "m/s"), "25");
+        assert_eq!(run("2", "GiB", "MB"), "2147.483648");
+        assert_eq!(run("212", "°F", "C"), "100");
+        assert_eq!(run("-40", "celsius", "fahrenheit"), "-40");
+        assert_eq!(run("3", "hours", "minutes"), "180");
+        assert!(matches!(
+            convert(Decimal::ONE, "kg", "m"),
+            Err(CalcError::IncompatibleUnits { .. })
+        ));
+        assert!(matches!(
+            convert(Decimal::ONE, "furlong", "m"),
+            Err(CalcError::UnknownUnit(_))
+        ));
+    }
+}
@@ -0,0 +1,208 @@
+//! Fault injection for resilience testing. Compiled only with the `chaos`
+//! feature and configured through the `SPACEBOT_CHAOS` environment variable,
+//! e.g.
+//!
+//! ```text
+//! SPACEBOT_CHAOS="llm_error_rate=0.3,llm_latency_ms=5000,adapter_drop_rate=0.1,sqlite_busy_rate=0.05,clock_skew_secs=-600,seed=7"
+//! ```
+//!
+//! | Key | Effect |
+//! |-----|--------|
+//! | `llm_error_rate` | Probability a completion attempt fails with a retriable 503 |
+//! | `llm_latency_ms` | Upper bound of random delay added before each completion attempt |
+//! | `adapter_drop_rate` | Probability a health check tick drops the adapter connection |
+//! | `sqlite_busy_rate` | Probability a memory or history query fails with `SQLITE_BUSY` |
+//! | `clock_skew_secs` | Offset applied to the leader lease and cron scheduling clocks |
+//! | `seed` | Seed for reproducible runs |
+//!
+//! Unset or empty disables every fault.
+
+use parking_lot::Mutex;
+use rand::rngs::StdRng;
+use rand::{Rng as _, SeedableRng as _};
+use std::sync::OnceLock;
+
+pub const ENV_VAR: &str = "SPACEBOT_CHAOS";
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChaosConfig {
+    pub llm_error_rate: f64,
+    pub llm_latency_ms: u64,
+    pub adapter_drop_rate: f64,
+    pub sqlite_busy_rate: f64,
+    pub clock_skew_secs: i64,
+    pub seed: Option<u64>,
+}
+
+impl ChaosConfig {
+    pub fn parse(spec: &str) -> anyhow::Result<Self> {
+        let mut config = Self::default();
+        for pair in spec
+            .split(',')
+            .map(str::trim)
+            .filter(|pair| !pair.is_empty())
+        {
+            let (key, value) = pair
+                .split_once('=')
+                .ok_or_else(|| anyhow::anyhow!("expected key=value, got '{pair}'"))?;
+            let (key, value) = (key.trim(), value.trim());
+            match key {
+                "llm_error_rate" => config.llm_error_rate = parse_rate(key, value)?,
+                "llm_latency_ms" => config.llm_latency_ms = value.parse()?,
+                "adapter_drop_rate" => config.adapter_drop_rate = parse_rate(key, value)?,
+                "sqlite_busy_rate" => config.sqlite_busy_rate = parse_rate(key, value)?,
+                "clock_skew_secs" => config.clock_skew_secs = value.parse()?,
+                "seed" => config.seed = Some(value.parse()?),
+                other => anyhow::bail!("unknown chaos setting '{other}'"),
+            }
+        }
+        Ok(config)
+    }
+
+    fn is_active(&self) -> bool {
+        self.llm_error_rate > 0.0
+            || self.llm_latency_ms > 0
+            || self.adapter_drop_rate > 0.0
+            || self.sqlite_busy_rate > 0.0
+            || self.clock_skew_secs != 0
+    }
+}
+
+fn parse_rate(key: &str, value: &str) -> anyhow::Result<f64> {
+    let rate: f64 = value.parse()?;
+    if !(0.0..=1.0).contains(&rate) {
+        anyhow::bail!("{key} must be between 0 and 1");
+    }
+    Ok(rate)
+}
+
+struct Chaos {
+    config: ChaosConfig,
+    rng: Mutex<StdRng>,
+}
+
+fn chaos() -> &'static Chaos {
+    static CHAOS: OnceLock<Chaos> = OnceLock::new();
+    CHAOS.get_or_init(|| {
+        let spec = std::env::var(ENV_VAR).unwrap_or_default();
+        let config = ChaosConfig::parse(&spec).unwrap_or_else(|error| {
+            tracing::error!(%error, "invalid {ENV_VAR}, fault injection disabled");
+            ChaosConfig::default()
+        });
+        if config.is_active() {
+            tracing::warn!(?config, "fault injection enabled");
+        }
+        let rng = match config.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_os_rng(),
+        };
+        Chaos {
+            config,
+            rng: Mutex::new(rng),
+        }
+    })
+}
+
+fn roll(rate: f64) -> bool {
+    rate > 0.0 && chaos().rng.lock().random_bool(rate)
+}
+
+/// Called before each completion attempt. Sleeps for the injected latency,
+/// then fails with a retriable provider error at `llm_error_rate`.
+pub async fn llm_fault(model: &str) -> Result<(), String> {
+    let config = &chaos().config;
+    if config.llm_latency_ms > 0 {
+        let delay_ms = chaos().rng.lock().random_range(0..=config.llm_latency_ms);
+        tokio::time::sleep(std::time::Duration::from_millis(delay_ms)).await;
+    }
+    if roll(config.llm_error_rate) {
+        tracing::warn!(model, "chaos: injecting LLM failure");
+        return Err("503 Service Unavailable (injected fault)".into());
+    }
+    Ok(())
+}
+
+/// Whether the supervisor should drop the adapter's connection this tick.
+pub fn drop_connection() -> bool {
+    roll(chaos().config.adapter_drop_rate)
+}
+
+/// Fails with `SQLITE_BUSY` at `sqlite_busy_rate`.
+pub fn sqlite_fault() -> Result<(), sqlx::Error> {
+    if roll(chaos().config.sqlite_busy_rate) {
+        tracing::warn!("chaos: injecting SQLITE_BUSY");
+        return Err(sqlx::Error::Database(Box::new(InjectedBusy)));
+    }
+    Ok(())
+}
+
+/// Shift a timestamp by `clock_skew_secs`.
+pub fn skew(now: chrono::DateTime<chrono::Utc>) -> chrono::DateTime<chrono::Utc> {
+    now + chrono::Duration::seconds(chaos().config.clock_skew_secs)
+}
+
+#[derive(Debug)]
+struct InjectedBusy;
+
+impl std::fmt::Display for InjectedBusy {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter.write_str("database is locked (injected fault)")
+    }
+}
+
+impl std::error::Error for InjectedBusy {}
+
+impl sqlx::error::DatabaseError for InjectedBusy {
+    fn message(&self) -> &str {
+        "database is locked (injected fault)"
+    }
+
+    fn code(&self) -> Option<std::borrow::Cow<'_, str>> {
+        // SQLITE_BUSY
+        Some("5".into())
+    }
+
+    fn as_error(&self) -> &(dyn std::error::Error + Send + Sync + 'static) {
+        self
+    }
+
+    fn as_error_mut(&mut self) -> &mut (dyn std::error::Error + Send + Sync + 'static) {
+        self
+    }
+
+    fn into_error(self: Box<Self>) -> Box<dyn std::error::Error + Send + Sync + 'static> {
+        self
+    }
+
+    fn kind(&self) -> sqlx::error::ErrorKind {
+        sqlx::error::ErrorKind::Other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_spec() {
+        let config = ChaosConfig::parse(
+            "llm_error_rate=0.25, llm_latency_ms=1500,adapter_drop_rate=1,clock_skew_secs=-30,seed=9",
+        )
+        .unwrap();
+        assert_eq!(
+            config,
+            ChaosConfig {
+                llm_error_rate: 0.25,
+                llm_latency_ms: 1500,
+                adapter_drop_rate: 1.0,
+                sqlite_busy_rate: 0.0,
+                clock_skew_secs: -30,
+                seed: Some(9),
+            }
+        );
+        assert!(config.is_active());
+        assert!(!ChaosConfig::parse("").unwrap().is_active());
+        assert!(ChaosConfig::parse("llm_error_rate=2").is_err());
+        assert!(ChaosConfig::parse("disk_full=1").is_err());
+    }
+}
@@ -1,16 +1,19 @@
 //! Configuration loading and validation.
 
+mod history;
 mod load;
 mod onboarding;
 mod permissions;
 mod providers;
 mod runtime;
+mod sanity;
 mod toml_schema;
 mod types;
 mod watcher;
 
 // Re-export all public types from submodules so external consumers
 // continue to use `crate::config::TypeName` unchanged.
+pub use history::{ConfigHistory, ConfigVersion, diff_config};
 pub(crate) use load::resolve_env_value;
 pub use load::set_resolve_secrets_store;
 pub use onboarding::run_onboarding;
@@ -19,6 +22,7 @@ pub use permissions::{
 };
 pub(crate) use providers::default_provider_config;
 pub use runtime::RuntimeConfig;
+pub use sanity::{ConfigIssue, IssueResolution};
 pub use types::*;
 pub use watcher::spawn_file_watcher;
 
@@ -1012,6 +1016,51 @@ startup_delay_secs = 2
         assert_eq!(resolved.warmup.startup_delay_secs, 2);
     }
 
+    #[test]
+    fn test_agent_api_tokens_are_restricted_to_their_agent() {
+        let toml = r#"
+[[api.tokens]]
+name = "admin"
+token = "admin-token"
+scope = "read_write"
+
+[[agents]]
+id = "alice"
+
+[[agents.api_tokens]]
+token = "alice-token"
+scope = "chat"
+
+[[agents]]
+id = "bob"
+
+[[agents.api_tokens]]
+name = "bob-dashboard"
+token = "bob-token"
+"#;
+        let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+        let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+        let tokens: Vec<(&str, &[String])> = config
+            .api
+            .tokens
+            .iter()
+            .map(|token| (token.name.as_str(), token.agents.as_slice()))
+            .collect();
+        assert_eq!(
+            tokens,
+            vec![
+                ("admin", &[][..]),
+                ("alice-token-0", &["alice".to_string()][..]),
+                ("bob-dashboard", &["bob".to_string()][..]),
+            ]
+        );
+        assert_eq!(config.api.tokens[1].scope, ApiTokenScope::Chat);
+
+        let shared = toml.replace("bob-token", "alice-token");
+        let parsed: TomlConfig = toml::from_str(&shared).expect("failed to parse test TOML");
+        assert!(Config::from_toml(parsed, PathBuf::from(".")).is_err());
+    }
+
     #[test]
     fn test_cortex_default_and_agent_override_resolution() {
         let toml = r#"
@@ -1031,7 +1080,9 @@ id = "main"
 branch_timeout_secs = 77
 supervisor_kill_budget_per_tick = 3
 association_max_per_pass = 55
-maintenance_decay_rate = 0.33
+
+[agents.cortex.maintenance_half_life_days]
+observation = 3
 "#;
         let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
         let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
@@ -1054,13 +1105,38 @@ maintenance_decay_rate = 0.33
         assert_eq!(resolved.cortex.supervisor_kill_budget_per_tick, 3);
         assert_eq!(resolved.cortex.bulletin_max_words, 1200);
         assert_eq!(resolved.cortex.maintenance_interval_secs, 1200);
-        assert_eq!(resolved.cortex.maintenance_decay_rate, 0.33);
+        assert_eq!(resolved.cortex.maintenance_half_life_days.observation, 3.0);
+        assert_eq!(resolved.cortex.maintenance_half_life_days.fact, 180.0);
         assert_eq!(resolved.cortex.maintenance_prune_threshold, 0.21);
         assert_eq!(resolved.cortex.maintenance_min_age_days, 17);
         assert_eq!(resolved.cortex.maintenance_merge_similarity_threshold, 0.95);
         assert_eq!(resolved.cortex.association_max_per_pass, 55);
     }
 
+    #[test]
+    fn test_legacy_maintenance_decay_rate_converts_to_half_lives() {
+        let toml = r#"
+[defaults.cortex]
+maintenance_decay_rate = 0.05
+
+[[agents]]
+id = "main"
+
+[agents.cortex.maintenance_half_life_days]
+observation = 3
+"#;
+        let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+        let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+        let resolved = config.agents[0].resolve(&config.instance_dir, &config.defaults);
+
+        assert_eq!(
+            config.defaults.cortex.maintenance_half_life_days,
+            crate::memory::maintenance::DecayHalfLives::uniform(10.0)
+        );
+        assert_eq!(resolved.cortex.maintenance_half_life_days.fact, 10.0);
+        assert_eq!(resolved.cortex.maintenance_half_life_days.observation, 3.0);
+    }
+
     #[test]
     fn test_cortex_maintenance_config_rejects_invalid_ranges() {
         let invalid_threshold = r#"
@@ -1089,14 +1165,14 @@ maintenance_min_age_days = -3
 [[agents]]
 id = "main"
 
-[agents.cortex]
-maintenance_decay_rate = -0.1
+[agents.cortex.maintenance_half_life_days]
+event = -1
 "#;
         let parsed: TomlConfig =
             toml::from_str(invalid_agent_override).expect("failed to parse invalid agent TOML");
         assert!(
             Config::from_toml(parsed, PathBuf::from(".")).is_err(),
-            "expected invalid agent maintenance_decay_rate to be rejected"
+            "expected negative agent maintenance half-life to be rejected"
         );
 
         let invalid_interval = r#"
@@ -1575,6 +1651,9 @@ maintenance_merge_similarity_threshold = 1.1
             webhook: None,
             twitch: None,
             signal: None,
+            filters: std::collections::HashMap::new(),
+            external: Vec::new(),
+            twilio: Vec::new(),
         };
         let bindings = vec![
             Binding {
@@ -1620,6 +1699,9 @@ maintenance_merge_similarity_threshold = 1.1
             webhook: None,
             twitch: None,
             signal: None,
+            filters: std::collections::HashMap::new(),
+            external: Vec::new(),
+            twilio: Vec::new(),
         };
         let bindings = vec![Binding {
             agent_id: "main".into(),
@@ -1678,11 +1760,15 @@ maintenance_merge_similarity_threshold = 1.1
                 allowed_senders: vec![],
                 max_body_bytes: 1_000_000,
                 max_attachment_bytes: 10_000_000,
+                task_address: None,
                 instances: vec![],
             }),
             webhook: None,
             twitch: None,
             signal: None,
+            filters: std::collections::HashMap::new(),
+            external: Vec::new(),
+            twilio: Vec::new(),
         };
         let bindings = vec![Binding {
             agent_id: "main".into(),
@@ -1718,6 +1804,9 @@ maintenance_merge_similarity_threshold = 1.1
             webhook: None,
             twitch: None,
             signal: None,
+            filters: std::collections::HashMap::new(),
+            external: Vec::new(),
+            twilio: Vec::new(),
         };
         // Binding targets default adapter, but no default credentials exist
         let bindings = vec![Binding {
@@ -1889,4 +1978,49 @@ command = "/usr/bin/test"
         // The mcp_servers data is silently dropped — verify it's not accessible
         assert!(parsed.defaults.mcp.is_empty());
     }
+
+    #[test]
+    fn human_notification_preferences_are_validated() {
+        let toml = r#"
+[[humans]]
+id = "ops"
+discord_id = "1234"
+
+[humans.notifications]
+categories = ["approvals", "cron_failures"]
+targets = ["telegram:111"]
+quiet_start_hour = 22
+quiet_end_hour = 7
+timezone = "Europe/Berlin"
+"#;
+        let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+        let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+        let preferences = config.humans[0]
+            .notifications
+            .as_ref()
+            .expect("notifications should be parsed");
+        assert_eq!(
+            preferences.categories,
+            [
+                NotificationCategory::Approvals,
+                NotificationCategory::CronFailures
+            ]
+        );
+        assert_eq!(preferences.targets, ["telegram:111"]);
+        assert_eq!(preferences.quiet_hours, Some((22, 7)));
+
+        for invalid in [
+            "targets = [\"nowhere\"]",
+            "quiet_start_hour = 22",
+            "quiet_start_hour = 22\nquiet_end_hour = 24",
+            "timezone = \"Mars/Olympus\"",
+        ] {
+            let toml = format!("[[humans]]\nid = \"ops\"\n\n[humans.notifications]\n{invalid}\n");
+            let parsed: TomlConfig = toml::from_str(&toml).expect("failed to parse test TOML");
+            assert!(
+                Config::from_toml(parsed, PathBuf::from(".")).is_err(),
+                "expected `{invalid}` to be rejected"
+            );
+        }
+    }
 }
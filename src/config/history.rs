@@ -0,0 +1,243 @@
+//! Versioned history of config.toml.
+//!
+//! Every distinct version of the file is stored once under
+//! `{instance_dir}/config_history/`, named by the SHA-256 of its content, with
+//! an append-only `index.jsonl` recording when each version appeared and what
+//! wrote it. The file watcher records a version whenever config.toml changes,
+//! so edits from the API, agent tools, and a text editor all land here.
+//!
+//! These functions do blocking file I/O; call them from the watcher thread or
+//! through `spawn_blocking`.
+
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context as _;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest as _, Sha256};
+
+/// Index entries kept before the oldest are pruned.
+const MAX_VERSIONS: usize = 200;
+
+/// Context lines shown around each change in a diff.
+const DIFF_CONTEXT_LINES: usize = 2;
+
+/// One recorded version of config.toml.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ConfigVersion {
+    /// SHA-256 of the file content, used to roll back to it.
+    pub hash: String,
+    pub recorded_at: chrono::DateTime<chrono::Utc>,
+    /// What produced the version: `startup`, `file_change`, `raw_editor`,
+    /// `rollback`.
+    pub source: String,
+    pub bytes: usize,
+}
+
+/// Content-addressed store of config.toml versions.
+#[derive(Debug, Clone)]
+pub struct ConfigHistory {
+    dir: PathBuf,
+}
+
+impl ConfigHistory {
+    pub fn new(instance_dir: &Path) -> Self {
+        Self {
+            dir: instance_dir.join("config_history"),
+        }
+    }
+
+    /// Record `content` as the current version. Returns `None` if it matches
+    /// the latest recorded version.
+    pub fn record(&self, content: &str, source: &str) -> anyhow::Result<Option<ConfigVersion>> {
+        let hash = hex::encode(Sha256::digest(content.as_bytes()));
+        let mut versions = self.read_index()?;
+        if versions.last().is_some_and(|latest| latest.hash == hash) {
+            return Ok(None);
+        }
+
+        std::fs::create_dir_all(&self.dir)
+            .with_context(|| format!("failed to create {}", self.dir.display()))?;
+        let blob = self.blob_path(&hash);
+        if !blob.exists() {
+            std::fs::write(&blob, content)
+                .with_context(|| format!("failed to write {}", blob.display()))?;
+        }
+
+        let version = ConfigVersion {
+            hash,
+            recorded_at: chrono::Utc::now(),
+            source: source.to_string(),
+            bytes: content.len(),
+        };
+        versions.push(version.clone());
+
+        if versions.len() > MAX_VERSIONS {
+            let pruned: Vec<ConfigVersion> =
+                versions.drain(..versions.len() - MAX_VERSIONS).collect();
+            self.write_index(&versions)?;
+            for old in pruned {
+                if !versions.iter().any(|kept| kept.hash == old.hash) {
+                    let _ = std::fs::remove_file(self.blob_path(&old.hash));
+                }
+            }
+        } else {
+            let mut index = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(self.index_path())
+                .context("failed to open config history index")?;
+            writeln!(index, "{}", serde_json::to_string(&version)?)
+                .context("failed to append to config history index")?;
+        }
+
+        Ok(Some(version))
+    }
+
+    /// Recorded versions, newest first.
+    pub fn list(&self, limit: usize) -> anyhow::Result<Vec<ConfigVersion>> {
+        let mut versions = self.read_index()?;
+        versions.reverse();
+        versions.truncate(limit);
+        Ok(versions)
+    }
+
+    /// Content of a recorded version.
+    pub fn content(&self, hash: &str) -> anyhow::Result<Option<String>> {
+        if hash.len() != 64 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Ok(None);
+        }
+        match std::fs::read_to_string(self.blob_path(hash)) {
+            Ok(content) => Ok(Some(content)),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error).context("failed to read config version"),
+        }
+    }
+
+    fn read_index(&self) -> anyhow::Result<Vec<ConfigVersion>> {
+        let content = match std::fs::read_to_string(self.index_path()) {
+            Ok(content) => content,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(error) => return Err(error).context("failed to read config history index"),
+        };
+        Ok(content
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect())
+    }
+
+    fn write_index(&self, versions: &[ConfigVersion]) -> anyhow::Result<()> {
+        let mut content = String::new();
+        for version in versions {
+            content.push_str(&serde_json::to_string(version)?);
+            content.push('\n');
+        }
+        std::fs::write(self.index_path(), content).context("failed to rewrite config history index")
+    }
+
+    fn index_path(&self) -> PathBuf {
+        self.dir.join("index.jsonl")
+    }
+
+    fn blob_path(&self, hash: &str) -> PathBuf {
+        self.dir.join(format!("{hash}.toml"))
+    }
+}
+
+/// Line diff from `old` to `new`: changed lines prefixed with `-`/`+`, a few
+/// unchanged lines of context prefixed with a space, and `...` between
+/// separate hunks. Empty when the inputs are identical.
+pub fn diff_config(old: &str, new: &str) -> String {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // Longest common subsequence table, filled from the end.
+    let mut lcs = vec![vec![0u32; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops: Vec<(char, &str)> = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            ops.push((' ', old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            ops.push(('-', old[i]));
+            i += 1;
+        } else {
+            ops.push(('+', new[j]));
+            j += 1;
+        }
+    }
+
+    let changed: Vec<usize> = ops
+        .iter()
+        .enumerate()
+        .filter(|(_, (kind, _))| *kind != ' ')
+        .map(|(index, _)| index)
+        .collect();
+    let visible = |index: usize| {
+        changed
+            .iter()
+            .any(|&change| index.abs_diff(change) <= DIFF_CONTEXT_LINES)
+    };
+
+    let mut output = String::new();
+    let mut last_shown: Option<usize> = None;
+    for (index, (kind, line)) in ops.iter().enumerate() {
+        if !visible(index) {
+            continue;
+        }
+        if let Some(last) = last_shown
+            && index > last + 1
+        {
+            output.push_str("...\n");
+        }
+        output.push(*kind);
+        output.push_str(line);
+        output.push('\n');
+        last_shown = Some(index);
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_distinct_versions_and_diffs_them() {
+        let instance_dir = tempfile::tempdir().unwrap();
+        let history = ConfigHistory::new(instance_dir.path());
+
+        let first = "[api]\nport = 19898\nbind = \"127.0.0.1\"\n";
+        let second = "[api]\nport = 8080\nbind = \"127.0.0.1\"\n";
+        let v1 = history.record(first, "startup").unwrap().unwrap();
+        assert!(history.record(first, "file_change").unwrap().is_none());
+        let v2 = history.record(second, "file_change").unwrap().unwrap();
+        history.record(first, "rollback").unwrap().unwrap();
+
+        let versions = history.list(10).unwrap();
+        assert_eq!(versions.len(), 3);
+        assert_eq!(versions[0].source, "rollback");
+        assert_eq!(versions[0].hash, v1.hash);
+        assert_eq!(versions[1].hash, v2.hash);
+        assert_eq!(history.content(&v2.hash).unwrap().as_deref(), Some(second));
+        assert!(history.content("../config").unwrap().is_none());
+
+        assert_eq!(
+            diff_config(first, second),
+            " [api]\n-port = 19898\n+port = 8080\n bind = \"127.0.0.1\"\n"
+        );
+        assert!(diff_config(first, first).is_empty());
+    }
+}
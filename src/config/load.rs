@@ -11,20 +11,28 @@ use super::providers::{
 };
 use super::toml_schema::*;
 use super::{
-    AgentConfig, ApiConfig, ApiType, Binding, BrowserConfig, ChannelConfig, ClosePolicy,
-    CoalesceConfig, CompactionConfig, Config, CortexConfig, CronDef, DefaultsConfig, DiscordConfig,
-    DiscordInstanceConfig, EmailConfig, EmailInstanceConfig, GroupDef, HumanDef, IngestionConfig,
-    LinkDef, LlmConfig, McpServerConfig, McpTransport, MemoryPersistenceConfig, MessagingConfig,
-    MetricsConfig, OpenCodeConfig, ProjectsConfig, ProviderConfig, SignalConfig,
-    SignalInstanceConfig, SlackCommandConfig, SlackConfig, SlackInstanceConfig, TelegramConfig,
-    TelegramInstanceConfig, TelemetryConfig, TwitchConfig, TwitchInstanceConfig, WarmupConfig,
+    AgentConfig, AgentRuntimeConfig, ApiConfig, ApiEventHistoryConfig, ApiOpenAiConfig,
+    ApiRateLimitConfig, ApiTlsConfig, ApiToken, ApiTokenScope, ApiType, ApiUnixSocketConfig,
+    ApiWebhookConfig, ApiWidgetConfig, Binding, BroadcastConfig, BrowserConfig, CalculatorConfig,
+    ChannelConfig, ClosePolicy, CoalesceConfig, CompactionConfig, Config, CortexConfig, CronDef,
+    CurrencyProvider, DefaultsConfig, DiscordConfig, DiscordInstanceConfig, EmailConfig,
+    EmailInstanceConfig, EmbeddingConfig, EmbeddingProviderKind, ExternalAdapterConfig,
+    ExternalTransport, GeocoderProvider, GroupDef, HumanDef, IngestionConfig, IssueTrackerConfig,
+    IssueTrackerProvider, LeaderElectionConfig, LinkDef, LlmConfig, McpServerConfig, McpTransport,
+    MemoryPersistenceConfig, MessageFilterConfig, MessagingConfig, MetricsConfig,
+    NotificationPreferences, OpenCodeConfig, PauseBehavior, ProjectsConfig, ProviderConfig,
+    QuotaConfig, ReadinessConfig, ReplyVerification, SignalConfig, SignalInstanceConfig,
+    SlackCommandConfig, SlackConfig, SlackInstanceConfig, TelegramConfig, TelegramInstanceConfig,
+    TelemetryConfig, TranslationConfig, TranslationProvider, TwilioConfig, TwitchConfig,
+    TwitchInstanceConfig, WarmupConfig, WeatherConfig, WeatherProvider, WeatherUnits,
     WebhookConfig, normalize_adapter, validate_named_messaging_adapters,
 };
 use crate::error::{ConfigError, Result};
+use crate::memory::maintenance::DecayHalfLives;
 
 use anyhow::Context as _;
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
 
 /// Resolve a value that might be an "env:VAR_NAME" or "secret:NAME" reference.
@@ -79,6 +87,7 @@ const KNOWN_TOP_LEVEL_KEYS: &[&str] = &[
     "api",
     "metrics",
     "telemetry",
+    "leader_election",
 ];
 
 /// Pre-parse check that warns about unrecognised top-level keys in a config
@@ -112,6 +121,143 @@ pub(super) fn warn_unknown_config_keys(content: &str) {
     }
 }
 
+/// Platform names built-in adapters own. External adapters can't reuse them.
+const RESERVED_ADAPTER_NAMES: &[&str] = &[
+    "discord", "slack", "telegram", "email", "webhook", "twitch", "signal", "twilio", "webchat",
+    "system", "testbed",
+];
+
+/// Validate an `[[messaging.external]]` entry. Invalid entries are skipped
+/// with a warning rather than failing the whole config.
+fn resolve_external_adapter(adapter: TomlExternalAdapterConfig) -> Option<ExternalAdapterConfig> {
+    let name = adapter.name.trim().to_string();
+    if name.is_empty() || name.contains(':') || RESERVED_ADAPTER_NAMES.contains(&name.as_str()) {
+        tracing::warn!(
+            adapter = %name,
+            "external adapter name must be non-empty, contain no ':', and not be a built-in platform — skipping"
+        );
+        return None;
+    }
+
+    let transport = match (adapter.command, adapter.url) {
+        (Some(command), None) => {
+            let env = adapter
+                .env
+                .into_iter()
+                .filter_map(|(key, value)| match resolve_env_value(&value) {
+                    Some(value) => Some((key, value)),
+                    None => {
+                        tracing::warn!(adapter = %name, variable = %key, "external adapter env value is unresolvable — omitting");
+                        None
+                    }
+                })
+                .collect();
+            ExternalTransport::Stdio {
+                command,
+                args: adapter.args,
+                env,
+            }
+        }
+        (None, Some(url)) => ExternalTransport::WebSocket { url },
+        _ => {
+            tracing::warn!(
+                adapter = %name,
+                "external adapter needs exactly one of `command` or `url` — skipping"
+            );
+            return None;
+        }
+    };
+
+    let settings = match adapter.settings {
+        Some(table) => match serde_json::to_value(table) {
+            Ok(value) => value,
+            Err(error) => {
+                tracing::warn!(adapter = %name, %error, "external adapter settings are not representable as JSON — skipping");
+                return None;
+            }
+        },
+        None => serde_json::Value::Object(serde_json::Map::new()),
+    };
+
+    Some(ExternalAdapterConfig {
+        name,
+        enabled: adapter.enabled,
+        transport,
+        settings,
+    })
+}
+
+/// Resolve `[[messaging.twilio]]` entries. Entries with missing credentials,
+/// a bad name, or a runtime key or port already taken are skipped with a
+/// warning rather than failing the whole config.
+fn parse_twilio_numbers(raw: Vec<TomlTwilioConfig>) -> Vec<TwilioConfig> {
+    let mut runtime_keys = std::collections::HashSet::new();
+    let mut ports = std::collections::HashSet::new();
+    let mut numbers = Vec::with_capacity(raw.len());
+
+    for entry in raw {
+        let name = entry.name.map(|name| name.trim().to_string());
+        if let Some(name) = &name
+            && (name.is_empty() || name.contains(':') || name.eq_ignore_ascii_case("default"))
+        {
+            tracing::warn!(adapter = %name, "twilio name must be non-empty, contain no ':', and not be 'default' — skipping");
+            continue;
+        }
+        let label = name.clone().unwrap_or_else(|| "twilio".into());
+
+        let account_sid = entry.account_sid.as_deref().and_then(resolve_env_value);
+        let auth_token = entry.auth_token.as_deref().and_then(resolve_env_value);
+        let phone_number = entry.phone_number.as_deref().and_then(resolve_env_value);
+        let public_url = entry
+            .public_url
+            .as_deref()
+            .and_then(resolve_env_value)
+            .map(|url| url.trim_end_matches('/').to_string());
+        let (Some(account_sid), Some(auth_token), Some(phone_number), Some(public_url)) =
+            (account_sid, auth_token, phone_number, public_url)
+        else {
+            tracing::warn!(
+                adapter = %label,
+                "twilio number needs account_sid, auth_token, phone_number, and public_url — skipping"
+            );
+            continue;
+        };
+        if !public_url.starts_with("https://") && !public_url.starts_with("http://") {
+            tracing::warn!(adapter = %label, "twilio public_url must be an http:// or https:// URL — skipping");
+            continue;
+        }
+
+        let number = TwilioConfig {
+            name,
+            enabled: entry.enabled,
+            account_sid,
+            auth_token,
+            phone_number,
+            public_url,
+            port: entry.port,
+            bind: entry.bind,
+            allowed_numbers: entry.allowed_numbers,
+            max_segments: entry.max_segments.clamp(1, 10),
+            voice: entry.voice,
+            voice_greeting: entry
+                .voice_greeting
+                .unwrap_or_else(|| "Please leave a message after the tone.".into()),
+            max_recording_secs: entry.max_recording_secs.max(1),
+        };
+        if !runtime_keys.insert(number.runtime_key()) {
+            tracing::warn!(adapter = %label, "duplicate twilio adapter name — skipping");
+            continue;
+        }
+        if number.enabled && !ports.insert(number.port) {
+            tracing::warn!(adapter = %label, port = number.port, "another twilio number already uses this port — skipping");
+            continue;
+        }
+        numbers.push(number);
+    }
+
+    numbers
+}
+
 fn parse_close_policy(value: Option<&str>) -> Option<ClosePolicy> {
     match value? {
         "close_browser" => Some(ClosePolicy::CloseBrowser),
@@ -142,6 +288,29 @@ fn resolve_close_policy(
     })
 }
 
+impl QuotaConfig {
+    fn resolve(overrides: TomlQuotaConfig, defaults: QuotaConfig) -> Result<QuotaConfig> {
+        let config = QuotaConfig {
+            max_concurrent_tasks: overrides
+                .max_concurrent_tasks
+                .unwrap_or(defaults.max_concurrent_tasks),
+            max_db_connections: overrides
+                .max_db_connections
+                .unwrap_or(defaults.max_db_connections),
+            max_concurrent_llm_calls: overrides
+                .max_concurrent_llm_calls
+                .unwrap_or(defaults.max_concurrent_llm_calls),
+        };
+        if config.max_concurrent_tasks < 1
+            || config.max_db_connections < 1
+            || config.max_concurrent_llm_calls < 1
+        {
+            return Err(ConfigError::Invalid("quota limits must be >= 1".to_string()).into());
+        }
+        Ok(config)
+    }
+}
+
 impl CortexConfig {
     fn resolve(overrides: TomlCortexConfig, defaults: CortexConfig) -> Result<CortexConfig> {
         let maintenance_interval_secs = overrides
@@ -182,9 +351,11 @@ impl CortexConfig {
                 .bulletin_max_turns
                 .unwrap_or(defaults.bulletin_max_turns),
             maintenance_interval_secs,
-            maintenance_decay_rate: overrides
-                .maintenance_decay_rate
-                .unwrap_or(defaults.maintenance_decay_rate),
+            maintenance_half_life_days: resolve_half_lives(
+                overrides.maintenance_half_life_days,
+                overrides.maintenance_decay_rate,
+                defaults.maintenance_half_life_days,
+            ),
             maintenance_prune_threshold: overrides
                 .maintenance_prune_threshold
                 .unwrap_or(defaults.maintenance_prune_threshold),
@@ -194,6 +365,39 @@ impl CortexConfig {
             maintenance_merge_similarity_threshold: overrides
                 .maintenance_merge_similarity_threshold
                 .unwrap_or(defaults.maintenance_merge_similarity_threshold),
+            maintenance_trash_retention_days: overrides
+                .maintenance_trash_retention_days
+                .unwrap_or(defaults.maintenance_trash_retention_days),
+            maintenance_delete_expired: overrides
+                .maintenance_delete_expired
+                .unwrap_or(defaults.maintenance_delete_expired),
+            maintenance_mutation_retention_days: overrides
+                .maintenance_mutation_retention_days
+                .unwrap_or(defaults.maintenance_mutation_retention_days),
+            recalibration_interval_secs: overrides
+                .recalibration_interval_secs
+                .unwrap_or(defaults.recalibration_interval_secs),
+            consolidation_interval_secs: overrides
+                .consolidation_interval_secs
+                .unwrap_or(defaults.consolidation_interval_secs),
+            consolidation_similarity_threshold: overrides
+                .consolidation_similarity_threshold
+                .unwrap_or(defaults.consolidation_similarity_threshold),
+            consolidation_max_clusters_per_pass: overrides
+                .consolidation_max_clusters_per_pass
+                .unwrap_or(defaults.consolidation_max_clusters_per_pass),
+            rescore_interval_secs: overrides
+                .rescore_interval_secs
+                .unwrap_or(defaults.rescore_interval_secs),
+            rescore_min_age_days: overrides
+                .rescore_min_age_days
+                .unwrap_or(defaults.rescore_min_age_days),
+            rescore_window_days: overrides
+                .rescore_window_days
+                .unwrap_or(defaults.rescore_window_days),
+            rescore_stale_ceiling: overrides
+                .rescore_stale_ceiling
+                .unwrap_or(defaults.rescore_stale_ceiling),
             association_interval_secs: overrides
                 .association_interval_secs
                 .unwrap_or(defaults.association_interval_secs),
@@ -212,6 +416,49 @@ impl CortexConfig {
     }
 }
 
+/// Per-type half-lives from config. A legacy `maintenance_decay_rate` is
+/// converted to a uniform half-life that explicit per-type values override.
+fn resolve_half_lives(
+    overrides: Option<TomlHalfLives>,
+    legacy_decay_rate: Option<f32>,
+    defaults: DecayHalfLives,
+) -> DecayHalfLives {
+    let defaults = match legacy_decay_rate {
+        Some(rate) => match DecayHalfLives::from_legacy_decay_rate(rate) {
+            Some(converted) => {
+                tracing::warn!(
+                    maintenance_decay_rate = rate,
+                    half_life_days = converted.fact,
+                    "maintenance_decay_rate is deprecated and was converted to a uniform \
+                     half-life; set [cortex.maintenance_half_life_days] instead"
+                );
+                converted
+            }
+            None => {
+                tracing::warn!(
+                    maintenance_decay_rate = rate,
+                    "maintenance_decay_rate is deprecated and outside [0, 1], ignoring it; \
+                     set [cortex.maintenance_half_life_days] instead"
+                );
+                defaults
+            }
+        },
+        None => defaults,
+    };
+    let Some(overrides) = overrides else {
+        return defaults;
+    };
+    DecayHalfLives {
+        fact: overrides.fact.unwrap_or(defaults.fact),
+        preference: overrides.preference.unwrap_or(defaults.preference),
+        decision: overrides.decision.unwrap_or(defaults.decision),
+        event: overrides.event.unwrap_or(defaults.event),
+        observation: overrides.observation.unwrap_or(defaults.observation),
+        goal: overrides.goal.unwrap_or(defaults.goal),
+        todo: overrides.todo.unwrap_or(defaults.todo),
+    }
+}
+
 fn parse_otlp_headers(value: Option<String>) -> Result<HashMap<String, String>> {
     let Some(raw) = value else {
         return Ok(HashMap::new());
@@ -246,6 +493,287 @@ fn parse_otlp_headers(value: Option<String>) -> Result<HashMap<String, String>>
     Ok(headers)
 }
 
+/// Validate a human's `[humans.notifications]` table. A target that can't
+/// be parsed is an error rather than being dropped, so a typo doesn't quietly
+/// stop someone's alerts.
+fn parse_notification_preferences(
+    human_id: &str,
+    raw: TomlNotificationPreferences,
+) -> Result<NotificationPreferences> {
+    for target in &raw.targets {
+        if crate::messaging::target::parse_delivery_target(target).is_none() {
+            return Err(ConfigError::Invalid(format!(
+                "human '{human_id}' has invalid notification target '{target}', expected adapter:target"
+            )))?;
+        }
+    }
+    let quiet_hours = match (raw.quiet_start_hour, raw.quiet_end_hour) {
+        (None, None) => None,
+        (Some(start), Some(end)) if start < 24 && end < 24 => {
+            Some((start, end)).filter(|(start, end)| start != end)
+        }
+        (Some(_), Some(_)) => {
+            return Err(ConfigError::Invalid(format!(
+                "human '{human_id}' has quiet hours outside 0-23"
+            )))?;
+        }
+        _ => {
+            return Err(ConfigError::Invalid(format!(
+                "human '{human_id}' must set both quiet_start_hour and quiet_end_hour"
+            )))?;
+        }
+    };
+    if let Some(timezone) = &raw.timezone
+        && timezone.parse::<chrono_tz::Tz>().is_err()
+    {
+        return Err(ConfigError::Invalid(format!(
+            "human '{human_id}' has unknown notification timezone '{timezone}'"
+        )))?;
+    }
+    Ok(NotificationPreferences {
+        categories: raw.categories,
+        targets: raw.targets,
+        quiet_hours,
+        timezone: raw.timezone,
+    })
+}
+
+/// Resolve `[[api.tokens]]` entries. Unresolvable tokens are a hard error
+/// rather than being dropped, since dropping the last one would silently
+/// leave the API unauthenticated.
+fn parse_api_tokens(raw: Vec<TomlApiToken>) -> Result<Vec<ApiToken>> {
+    let mut tokens = Vec::with_capacity(raw.len());
+    for (index, entry) in raw.into_iter().enumerate() {
+        let name = entry
+            .name
+            .filter(|name| !name.trim().is_empty())
+            .unwrap_or_else(|| format!("token-{index}"));
+        let Some(scope) = ApiTokenScope::parse(&entry.scope) else {
+            return Err(ConfigError::Invalid(format!(
+                "api token '{name}' has invalid scope '{}', expected 'read', 'chat', or 'read_write'",
+                entry.scope
+            )))?;
+        };
+        let Some(token) = resolve_env_value(&entry.token).filter(|token| !token.is_empty()) else {
+            return Err(ConfigError::Invalid(format!(
+                "api token '{name}' is empty or could not be resolved"
+            )))?;
+        };
+        let agents = entry
+            .agents
+            .into_iter()
+            .map(|agent| agent.trim().to_string())
+            .filter(|agent| !agent.is_empty())
+            .collect();
+        tokens.push(ApiToken {
+            name,
+            token,
+            scope,
+            agents,
+        });
+    }
+    Ok(tokens)
+}
+
+/// Resolve an agent's `[[agents.api_tokens]]` into tokens restricted to that
+/// agent, so each tenant on a shared instance gets its own credentials.
+fn parse_agent_api_tokens(agent_id: &str, raw: Vec<TomlApiToken>) -> Result<Vec<ApiToken>> {
+    let raw = raw
+        .into_iter()
+        .enumerate()
+        .map(|(index, mut entry)| {
+            if entry.agents.iter().any(|agent| agent.trim() != agent_id) {
+                return Err(ConfigError::Invalid(format!(
+                    "api tokens under agent '{agent_id}' can only act on that agent"
+                )));
+            }
+            entry.agents = vec![agent_id.to_string()];
+            if entry
+                .name
+                .as_deref()
+                .is_none_or(|name| name.trim().is_empty())
+            {
+                entry.name = Some(format!("{agent_id}-token-{index}"));
+            }
+            Ok(entry)
+        })
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    parse_api_tokens(raw)
+}
+
+/// Refuse token values used twice. The first match wins at request time, so
+/// a shared value would give one tenant another's access.
+fn ensure_unique_api_tokens(auth_token: Option<&str>, tokens: &[ApiToken]) -> Result<()> {
+    for (index, token) in tokens.iter().enumerate() {
+        if auth_token == Some(token.token.as_str()) {
+            return Err(ConfigError::Invalid(format!(
+                "api token '{}' has the same value as api.auth_token",
+                token.name
+            ))
+            .into());
+        }
+        if let Some(other) = tokens[..index]
+            .iter()
+            .find(|other| other.token == token.token)
+        {
+            return Err(ConfigError::Invalid(format!(
+                "api tokens '{}' and '{}' have the same value",
+                other.name, token.name
+            ))
+            .into());
+        }
+    }
+    Ok(())
+}
+
+/// Resolve `[[api.webhooks]]` entries.
+fn parse_api_webhooks(raw: Vec<TomlApiWebhook>) -> Result<Vec<ApiWebhookConfig>> {
+    let mut webhooks = Vec::with_capacity(raw.len());
+    for (index, entry) in raw.into_iter().enumerate() {
+        let name = entry
+            .name
+            .filter(|name| !name.trim().is_empty())
+            .unwrap_or_else(|| format!("webhook-{index}"));
+        let Some(url) = resolve_env_value(&entry.url)
+            .filter(|url| url.starts_with("https://") || url.starts_with("http://"))
+        else {
+            return Err(ConfigError::Invalid(format!(
+                "api webhook '{name}' needs an http:// or https:// url"
+            ))
+            .into());
+        };
+        if entry.events.is_empty() {
+            return Err(ConfigError::Invalid(format!(
+                "api webhook '{name}' must list at least one event type (or \"*\")"
+            ))
+            .into());
+        }
+        let secret = match entry.secret {
+            Some(secret) => Some(
+                resolve_env_value(&secret)
+                    .filter(|secret| !secret.is_empty())
+                    .ok_or_else(|| {
+                        ConfigError::Invalid(format!(
+                            "api webhook '{name}' secret is empty or could not be resolved"
+                        ))
+                    })?,
+            ),
+            None => None,
+        };
+        webhooks.push(ApiWebhookConfig {
+            name,
+            url,
+            events: entry.events,
+            secret,
+        });
+    }
+    Ok(webhooks)
+}
+
+fn parse_api_event_history(raw: TomlApiEventHistoryConfig) -> Result<ApiEventHistoryConfig> {
+    let defaults = ApiEventHistoryConfig::default();
+    let events = raw.events.unwrap_or(defaults.events);
+    if let Some(unknown) = events.iter().find(|event| {
+        event.as_str() != "*" && !crate::api::ApiEvent::EVENT_TYPES.contains(&event.as_str())
+    }) {
+        return Err(ConfigError::Invalid(format!(
+            "api.event_history.events has unknown event type '{unknown}'"
+        ))
+        .into());
+    }
+    let retention_days = raw.retention_days.unwrap_or(defaults.retention_days);
+    let max_events = raw.max_events.unwrap_or(defaults.max_events);
+    if retention_days == 0 || max_events == 0 {
+        return Err(ConfigError::Invalid(
+            "api.event_history retention_days and max_events must be >= 1".to_string(),
+        )
+        .into());
+    }
+    Ok(ApiEventHistoryConfig {
+        enabled: raw.enabled.unwrap_or(defaults.enabled),
+        events,
+        retention_days,
+        max_events,
+    })
+}
+
+fn parse_api_openai(raw: TomlApiOpenAiConfig) -> Result<ApiOpenAiConfig> {
+    let defaults = ApiOpenAiConfig::default();
+    let reply_timeout_secs = raw
+        .reply_timeout_secs
+        .unwrap_or(defaults.reply_timeout_secs);
+    if reply_timeout_secs == 0 {
+        return Err(
+            ConfigError::Invalid("api.openai.reply_timeout_secs must be >= 1".to_string()).into(),
+        );
+    }
+    Ok(ApiOpenAiConfig {
+        enabled: raw.enabled.unwrap_or(defaults.enabled),
+        reply_timeout_secs,
+    })
+}
+
+fn parse_api_widget(raw: TomlApiWidgetConfig) -> Result<ApiWidgetConfig> {
+    let defaults = ApiWidgetConfig::default();
+    let enabled = raw.enabled.unwrap_or(defaults.enabled);
+    let agent_id = raw.agent_id.unwrap_or_default().trim().to_string();
+    let allowed_origins: Vec<String> = raw
+        .allowed_origins
+        .unwrap_or_default()
+        .iter()
+        .map(|origin| origin.trim().trim_end_matches('/').to_string())
+        .filter(|origin| !origin.is_empty())
+        .collect();
+    if enabled && agent_id.is_empty() {
+        return Err(ConfigError::Invalid(
+            "api.widget.agent_id is required when the widget is enabled".to_string(),
+        )
+        .into());
+    }
+    if enabled && allowed_origins.is_empty() {
+        return Err(ConfigError::Invalid(
+            "api.widget.allowed_origins must list at least one origin".to_string(),
+        )
+        .into());
+    }
+    if let Some(origin) = allowed_origins
+        .iter()
+        .find(|origin| *origin != "*" && !origin.contains("://"))
+    {
+        return Err(ConfigError::Invalid(format!(
+            "api.widget.allowed_origins entry '{origin}' must include a scheme, e.g. https://example.com"
+        )).into());
+    }
+    let session_ttl_secs = raw.session_ttl_secs.unwrap_or(defaults.session_ttl_secs);
+    let requests_per_minute = raw
+        .requests_per_minute
+        .unwrap_or(defaults.requests_per_minute);
+    let messages_per_minute = raw
+        .messages_per_minute
+        .unwrap_or(defaults.messages_per_minute);
+    let max_message_chars = raw.max_message_chars.unwrap_or(defaults.max_message_chars);
+    if session_ttl_secs == 0
+        || requests_per_minute == 0
+        || messages_per_minute == 0
+        || max_message_chars == 0
+    {
+        return Err(ConfigError::Invalid(
+            "api.widget session_ttl_secs, requests_per_minute, messages_per_minute, and max_message_chars must be >= 1"
+                .to_string(),
+        ).into());
+    }
+    Ok(ApiWidgetConfig {
+        enabled,
+        agent_id,
+        allowed_origins,
+        session_ttl_secs,
+        requests_per_minute,
+        messages_per_minute,
+        max_message_chars,
+        greeting: raw.greeting.filter(|greeting| !greeting.trim().is_empty()),
+    })
+}
+
 fn parse_mcp_server_config(raw: TomlMcpServerConfig) -> Result<McpServerConfig> {
     if raw.name.trim().is_empty() {
         return Err(ConfigError::Invalid("mcp server name cannot be empty".into()).into());
@@ -293,6 +821,440 @@ fn parse_mcp_server_config(raw: TomlMcpServerConfig) -> Result<McpServerConfig>
     })
 }
 
+fn parse_channel_config(raw: TomlChannelConfig, base: ChannelConfig) -> Result<ChannelConfig> {
+    let pause_behavior = match raw.pause_behavior.as_deref() {
+        None => base.pause_behavior,
+        Some("queue") => PauseBehavior::Queue,
+        Some("drop") => PauseBehavior::Drop,
+        Some(other) => {
+            return Err(ConfigError::Invalid(format!(
+                "invalid channel pause_behavior '{other}', expected 'queue' or 'drop'"
+            ))
+            .into());
+        }
+    };
+
+    let draft_max_divergence = raw
+        .draft_max_divergence
+        .unwrap_or(base.draft_max_divergence);
+    if !(0.0..=1.0).contains(&draft_max_divergence) {
+        return Err(ConfigError::Invalid(format!(
+            "channel draft_max_divergence must be between 0 and 1, got {draft_max_divergence}"
+        ))
+        .into());
+    }
+
+    let faq_answer_threshold = raw
+        .faq_answer_threshold
+        .unwrap_or(base.faq_answer_threshold);
+    let faq_context_threshold = raw
+        .faq_context_threshold
+        .unwrap_or(base.faq_context_threshold);
+    if !(0.0..=1.0).contains(&faq_answer_threshold)
+        || !(0.0..=1.0).contains(&faq_context_threshold)
+        || faq_context_threshold > faq_answer_threshold
+    {
+        return Err(ConfigError::Invalid(format!(
+            "channel faq thresholds must be between 0 and 1 with faq_context_threshold \
+             ({faq_context_threshold}) no higher than faq_answer_threshold ({faq_answer_threshold})"
+        ))
+        .into());
+    }
+
+    let reply_verification = match raw.reply_verification.as_deref() {
+        None => base.reply_verification,
+        Some(value) => ReplyVerification::parse(value).ok_or_else(|| {
+            ConfigError::Invalid(format!(
+                "invalid channel reply_verification '{value}', expected 'off', 'annotate', \
+                 'soften', or 'regenerate'"
+            ))
+        })?,
+    };
+
+    let event_log_retention_days = raw
+        .event_log_retention_days
+        .unwrap_or(base.event_log_retention_days);
+    if event_log_retention_days < 0 {
+        return Err(ConfigError::Invalid(format!(
+            "channel event_log_retention_days must be >= 0, got {event_log_retention_days}"
+        ))
+        .into());
+    }
+
+    Ok(ChannelConfig {
+        listen_only_mode: raw.listen_only_mode.unwrap_or(base.listen_only_mode),
+        save_attachments: raw.save_attachments.unwrap_or(base.save_attachments),
+        pause_behavior,
+        speculative_drafts: raw.speculative_drafts.unwrap_or(base.speculative_drafts),
+        draft_max_divergence,
+        faq_matching: raw.faq_matching.unwrap_or(base.faq_matching),
+        faq_answer_threshold,
+        faq_context_threshold,
+        reply_verification,
+        event_log: raw.event_log.unwrap_or(base.event_log),
+        event_log_retention_days,
+    })
+}
+
+fn parse_weather_config(raw: TomlWeatherConfig, base: &WeatherConfig) -> Result<WeatherConfig> {
+    let provider = match raw.provider.as_deref() {
+        None => base.provider,
+        Some("open-meteo") => WeatherProvider::OpenMeteo,
+        Some(other) => {
+            return Err(ConfigError::Invalid(format!(
+                "invalid weather provider '{other}', expected 'open-meteo'"
+            ))
+            .into());
+        }
+    };
+    let geocoder = match raw.geocoder.as_deref() {
+        None => base.geocoder,
+        Some("nominatim") => GeocoderProvider::Nominatim,
+        Some("open-meteo") => GeocoderProvider::OpenMeteo,
+        Some(other) => {
+            return Err(ConfigError::Invalid(format!(
+                "invalid weather geocoder '{other}', expected 'nominatim' or 'open-meteo'"
+            ))
+            .into());
+        }
+    };
+    let units = match raw.units.as_deref() {
+        None => base.units,
+        Some("metric") => WeatherUnits::Metric,
+        Some("imperial") => WeatherUnits::Imperial,
+        Some(other) => {
+            return Err(ConfigError::Invalid(format!(
+                "invalid weather units '{other}', expected 'metric' or 'imperial'"
+            ))
+            .into());
+        }
+    };
+
+    Ok(WeatherConfig {
+        enabled: raw.enabled.unwrap_or(base.enabled),
+        provider,
+        geocoder,
+        units,
+        cache_ttl_secs: raw.cache_ttl_secs.unwrap_or(base.cache_ttl_secs),
+        bulletin_location: raw
+            .bulletin_location
+            .map(|location| location.trim().to_string())
+            .filter(|location| !location.is_empty())
+            .or_else(|| base.bulletin_location.clone()),
+    })
+}
+
+fn parse_embedding_config(
+    raw: TomlEmbeddingConfig,
+    base: &EmbeddingConfig,
+) -> Result<EmbeddingConfig> {
+    let provider = match raw.provider.as_deref() {
+        None => base.provider,
+        Some("local") => EmbeddingProviderKind::Local,
+        Some("openai") => EmbeddingProviderKind::Openai,
+        Some("voyage") => EmbeddingProviderKind::Voyage,
+        Some(other) => {
+            return Err(ConfigError::Invalid(format!(
+                "invalid embedding provider '{other}', expected 'local', 'openai', or 'voyage'"
+            ))
+            .into());
+        }
+    };
+    // Switching providers doesn't carry the old provider's settings over.
+    let inherited = (provider == base.provider).then_some(base);
+    let model = raw
+        .model
+        .map(|model| model.trim().to_string())
+        .filter(|model| !model.is_empty())
+        .or_else(|| inherited.and_then(|base| base.model.clone()));
+    let api_key = raw
+        .api_key
+        .as_deref()
+        .and_then(resolve_env_value)
+        .or_else(|| inherited.and_then(|base| base.api_key.clone()))
+        .or_else(|| match provider {
+            EmbeddingProviderKind::Local => None,
+            EmbeddingProviderKind::Openai => std::env::var("OPENAI_API_KEY").ok(),
+            EmbeddingProviderKind::Voyage => std::env::var("VOYAGE_API_KEY").ok(),
+        })
+        .filter(|key| !key.trim().is_empty());
+    let base_url = raw
+        .base_url
+        .map(|url| url.trim().trim_end_matches('/').to_string())
+        .filter(|url| !url.is_empty())
+        .or_else(|| inherited.and_then(|base| base.base_url.clone()));
+    let dimensions = raw
+        .dimensions
+        .or_else(|| inherited.and_then(|base| base.dimensions));
+
+    match provider {
+        EmbeddingProviderKind::Local => {
+            if model.is_some() || dimensions.is_some() {
+                return Err(ConfigError::Invalid(
+                    "embedding provider 'local' only runs its bundled model; remove 'model' and 'dimensions'"
+                        .into(),
+                )
+                .into());
+            }
+        }
+        EmbeddingProviderKind::Openai | EmbeddingProviderKind::Voyage => {
+            if api_key.is_none() {
+                return Err(ConfigError::Invalid(format!(
+                    "embedding provider '{}' requires 'api_key' (or its env var is unset)",
+                    provider.as_str()
+                ))
+                .into());
+            }
+        }
+    }
+    if dimensions == Some(0) {
+        return Err(ConfigError::Invalid("embedding.dimensions must be at least 1".into()).into());
+    }
+
+    Ok(EmbeddingConfig {
+        provider,
+        model,
+        api_key,
+        base_url,
+        dimensions,
+    })
+}
+
+/// Cores this process may be scheduled on. A container or `taskset` can
+/// restrict these to a sparse set, so the core count alone isn't enough to
+/// tell whether pinning to a core would succeed.
+#[cfg(target_os = "linux")]
+fn allowed_cores() -> Vec<usize> {
+    // SAFETY: cpu_set_t is plain data that sched_getaffinity fills in;
+    // CPU_ISSET only reads within it.
+    unsafe {
+        let mut set = std::mem::zeroed::<libc::cpu_set_t>();
+        if libc::sched_getaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &mut set) != 0 {
+            return all_cores();
+        }
+        (0..libc::CPU_SETSIZE as usize)
+            .filter(|&core| libc::CPU_ISSET(core, &set))
+            .collect()
+    }
+}
+
+/// Pinning is ignored off Linux, so any core the machine has is accepted.
+#[cfg(not(target_os = "linux"))]
+fn allowed_cores() -> Vec<usize> {
+    all_cores()
+}
+
+fn all_cores() -> Vec<usize> {
+    (0..std::thread::available_parallelism().map_or(1, usize::from)).collect()
+}
+
+fn parse_agent_runtime_config(
+    raw: TomlAgentRuntimeConfig,
+    base: &AgentRuntimeConfig,
+) -> Result<AgentRuntimeConfig> {
+    let worker_threads = raw.worker_threads.unwrap_or(base.worker_threads);
+    if worker_threads == 0 {
+        return Err(
+            ConfigError::Invalid("runtime.worker_threads must be at least 1".into()).into(),
+        );
+    }
+    let cpu_affinity = raw
+        .cpu_affinity
+        .unwrap_or_else(|| base.cpu_affinity.clone());
+    if !cpu_affinity.is_empty() {
+        let allowed = allowed_cores();
+        if let Some(core) = cpu_affinity.iter().find(|core| !allowed.contains(core)) {
+            let allowed = allowed
+                .iter()
+                .map(usize::to_string)
+                .collect::<Vec<_>>()
+                .join(", ");
+            return Err(ConfigError::Invalid(format!(
+                "runtime.cpu_affinity core {core} isn't available to this process; \
+                 it may run on cores {allowed}"
+            ))
+            .into());
+        }
+    }
+
+    Ok(AgentRuntimeConfig {
+        dedicated: raw.dedicated.unwrap_or(base.dedicated),
+        worker_threads,
+        cpu_affinity,
+    })
+}
+
+fn parse_translation_config(
+    raw: TomlTranslationConfig,
+    base: &TranslationConfig,
+) -> Result<TranslationConfig> {
+    let provider = match raw.provider.as_deref() {
+        None => base.provider,
+        Some("llm") => TranslationProvider::Llm,
+        Some("deepl") => TranslationProvider::Deepl,
+        Some(other) => {
+            return Err(ConfigError::Invalid(format!(
+                "invalid translation provider '{other}', expected 'llm' or 'deepl'"
+            ))
+            .into());
+        }
+    };
+    let deepl_api_key = raw
+        .deepl_api_key
+        .as_deref()
+        .and_then(resolve_env_value)
+        .or_else(|| base.deepl_api_key.clone());
+    if provider == TranslationProvider::Deepl && deepl_api_key.is_none() {
+        return Err(ConfigError::Invalid(
+            "translation provider 'deepl' requires 'deepl_api_key' (or its env var is unset)"
+                .into(),
+        )
+        .into());
+    }
+
+    Ok(TranslationConfig {
+        enabled: raw.enabled.unwrap_or(base.enabled),
+        provider,
+        deepl_api_key,
+        working_language: raw
+            .working_language
+            .map(|language| language.trim().to_lowercase())
+            .filter(|language| !language.is_empty())
+            .unwrap_or_else(|| base.working_language.clone()),
+        auto_translate: raw.auto_translate.unwrap_or(base.auto_translate),
+    })
+}
+
+fn parse_calculator_config(
+    raw: TomlCalculatorConfig,
+    base: &CalculatorConfig,
+) -> Result<CalculatorConfig> {
+    let currency_provider = match raw.currency_provider.as_deref() {
+        None => base.currency_provider,
+        Some("frankfurter") => Some(CurrencyProvider::Frankfurter),
+        Some("none") => None,
+        Some(other) => {
+            return Err(ConfigError::Invalid(format!(
+                "invalid calculator currency_provider '{other}', expected 'frankfurter' or 'none'"
+            ))
+            .into());
+        }
+    };
+
+    Ok(CalculatorConfig {
+        currency_provider,
+        rates_cache_ttl_secs: raw
+            .rates_cache_ttl_secs
+            .unwrap_or(base.rates_cache_ttl_secs),
+    })
+}
+
+fn parse_broadcast_config(
+    raw: TomlBroadcastConfig,
+    base: &BroadcastConfig,
+) -> Result<BroadcastConfig> {
+    let groups = match raw.groups {
+        None => base.groups.clone(),
+        Some(groups) => {
+            let mut parsed = BTreeMap::new();
+            for (name, targets) in groups {
+                let name = name.trim().to_string();
+                if name.is_empty() {
+                    return Err(ConfigError::Invalid(
+                        "broadcast group name cannot be empty".into(),
+                    )
+                    .into());
+                }
+                let targets = targets
+                    .iter()
+                    .map(|target| {
+                        crate::messaging::target::parse_delivery_target(target).ok_or_else(|| {
+                            ConfigError::Invalid(format!(
+                                "broadcast group '{name}' has invalid target '{target}', \
+                                 expected 'adapter:target'"
+                            ))
+                        })
+                    })
+                    .collect::<std::result::Result<Vec<_>, _>>()?;
+                parsed.insert(name, targets);
+            }
+            parsed
+        }
+    };
+
+    Ok(BroadcastConfig {
+        enabled: raw.enabled.unwrap_or(base.enabled),
+        require_approval: raw.require_approval.unwrap_or(base.require_approval),
+        stagger_ms: raw.stagger_ms.unwrap_or(base.stagger_ms),
+        groups,
+    })
+}
+
+fn parse_issue_tracker_config(raw: TomlIssueTrackerConfig) -> Result<IssueTrackerConfig> {
+    if raw.name.trim().is_empty() {
+        return Err(ConfigError::Invalid("issue tracker name cannot be empty".into()).into());
+    }
+
+    let provider = match raw.provider.as_str() {
+        "jira" => IssueTrackerProvider::Jira,
+        "linear" => IssueTrackerProvider::Linear,
+        other => {
+            return Err(ConfigError::Invalid(format!(
+                "issue tracker '{}' has invalid provider '{}', expected 'jira' or 'linear'",
+                raw.name, other
+            ))
+            .into());
+        }
+    };
+
+    let base_url = raw
+        .base_url
+        .as_deref()
+        .and_then(resolve_env_value)
+        .map(|url| url.trim_end_matches('/').to_string());
+    let email = raw.email.as_deref().and_then(resolve_env_value);
+    if provider == IssueTrackerProvider::Jira && (base_url.is_none() || email.is_none()) {
+        return Err(ConfigError::Invalid(format!(
+            "jira issue tracker '{}' requires 'base_url' and 'email'",
+            raw.name
+        ))
+        .into());
+    }
+
+    let api_token = resolve_env_value(&raw.api_token)
+        .filter(|token| !token.is_empty())
+        .ok_or_else(|| {
+            ConfigError::Invalid(format!(
+                "issue tracker '{}' has no 'api_token' (or its env var is unset)",
+                raw.name
+            ))
+        })?;
+
+    // An empty allowlist would silently expose every project, so require one.
+    let projects: Vec<String> = raw
+        .projects
+        .iter()
+        .map(|project| project.trim().to_uppercase())
+        .filter(|project| !project.is_empty())
+        .collect();
+    if projects.is_empty() {
+        return Err(ConfigError::Invalid(format!(
+            "issue tracker '{}' must list at least one project in 'projects'",
+            raw.name
+        ))
+        .into());
+    }
+
+    Ok(IssueTrackerConfig {
+        name: raw.name,
+        provider,
+        base_url,
+        email,
+        api_token,
+        projects,
+    })
+}
+
 impl Config {
     /// Resolve the instance directory from env or default (~/.spacebot).
     pub fn default_instance_dir() -> PathBuf {
@@ -844,19 +1806,30 @@ impl Config {
             ingestion: None,
             cortex: None,
             warmup: None,
+            quotas: None,
             browser: None,
             channel: None,
             mcp: None,
+            issue_trackers: None,
+            weather: None,
+            translation: None,
+            embedding: None,
+            runtime: None,
+            calculator: None,
+            broadcast: None,
             brave_search_key: None,
             cron_timezone: None,
             user_timezone: None,
             sandbox: None,
             projects: None,
             cron: Vec::new(),
+            memory_scopes: crate::memory::scope::MemoryScopes::default(),
+            workspace_git: false,
         }];
 
         let mut api = ApiConfig::default();
         api.bind = hosted_api_bind(api.bind);
+        api.dev_proxy_url = std::env::var("SPACEBOT_DEV_PROXY_URL").ok();
 
         let mut defaults = DefaultsConfig::default();
         defaults.browser.chrome_cache_dir = instance_dir.join("chrome_cache");
@@ -883,6 +1856,7 @@ impl Config {
                 telegram_id: None,
                 slack_id: None,
                 email: None,
+                notifications: None,
             }],
             messaging: MessagingConfig::default(),
             bindings: Vec::new(),
@@ -895,9 +1869,19 @@ impl Config {
                     .unwrap_or_else(|_| "spacebot".into()),
                 sample_rate: 1.0,
             },
+            leader_election: LeaderElectionConfig::default(),
+            issues: Vec::new(),
         })
     }
 
+    /// Parse and resolve config.toml content as if it lived in `instance_dir`,
+    /// without reading or writing the file.
+    pub fn load_from_str(content: &str, instance_dir: &Path) -> Result<Self> {
+        let toml_config: TomlConfig =
+            toml::from_str(content).context("failed to parse config TOML")?;
+        Self::from_toml(toml_config, instance_dir.to_path_buf())
+    }
+
     /// Validate a raw TOML string as a valid Spacebot config.
     /// Returns Ok(()) if the config is structurally valid, or an error describing what's wrong.
     pub fn validate_toml(content: &str) -> Result<()> {
@@ -1382,6 +2366,12 @@ impl Config {
             .into_iter()
             .map(parse_mcp_server_config)
             .collect::<Result<Vec<_>>>()?;
+        let default_issue_trackers = toml
+            .defaults
+            .issue_trackers
+            .into_iter()
+            .map(parse_issue_tracker_config)
+            .collect::<Result<Vec<_>>>()?;
 
         let base_defaults = DefaultsConfig::default();
         // When `[defaults.routing]` is absent, infer sane routing from the
@@ -1438,6 +2428,9 @@ impl Config {
                     message_interval: mp
                         .message_interval
                         .unwrap_or(base_defaults.memory_persistence.message_interval),
+                    embedding_batch_size: mp
+                        .embedding_batch_size
+                        .unwrap_or(base_defaults.memory_persistence.embedding_batch_size),
                 })
                 .unwrap_or(base_defaults.memory_persistence),
             coalesce: toml
@@ -1486,6 +2479,12 @@ impl Config {
                         .unwrap_or(base_defaults.warmup.startup_delay_secs),
                 })
                 .unwrap_or(base_defaults.warmup),
+            quotas: toml
+                .defaults
+                .quotas
+                .map(|q| QuotaConfig::resolve(q, base_defaults.quotas))
+                .transpose()?
+                .unwrap_or(base_defaults.quotas),
             browser: {
                 let chrome_cache_dir = instance_dir.join("chrome_cache");
                 toml.defaults
@@ -1517,19 +2516,40 @@ impl Config {
                         ..base_defaults.browser.clone()
                     })
             },
-            channel: toml
-                .defaults
-                .channel
-                .map(|channel_config| ChannelConfig {
-                    listen_only_mode: channel_config
-                        .listen_only_mode
-                        .unwrap_or(base_defaults.channel.listen_only_mode),
-                    save_attachments: channel_config
-                        .save_attachments
-                        .unwrap_or(base_defaults.channel.save_attachments),
-                })
-                .unwrap_or(base_defaults.channel),
+            channel: match toml.defaults.channel {
+                Some(channel_config) => {
+                    parse_channel_config(channel_config, base_defaults.channel)?
+                }
+                None => base_defaults.channel,
+            },
             mcp: default_mcp,
+            issue_trackers: default_issue_trackers,
+            weather: match toml.defaults.weather {
+                Some(weather) => parse_weather_config(weather, &base_defaults.weather)?,
+                None => base_defaults.weather.clone(),
+            },
+            translation: match toml.defaults.translation {
+                Some(translation) => {
+                    parse_translation_config(translation, &base_defaults.translation)?
+                }
+                None => base_defaults.translation.clone(),
+            },
+            embedding: match toml.defaults.embedding {
+                Some(embedding) => parse_embedding_config(embedding, &base_defaults.embedding)?,
+                None => base_defaults.embedding.clone(),
+            },
+            runtime: match toml.defaults.runtime {
+                Some(runtime) => parse_agent_runtime_config(runtime, &base_defaults.runtime)?,
+                None => base_defaults.runtime.clone(),
+            },
+            calculator: match toml.defaults.calculator {
+                Some(calculator) => parse_calculator_config(calculator, &base_defaults.calculator)?,
+                None => base_defaults.calculator.clone(),
+            },
+            broadcast: match toml.defaults.broadcast {
+                Some(broadcast) => parse_broadcast_config(broadcast, &base_defaults.broadcast)?,
+                None => base_defaults.broadcast.clone(),
+            },
             brave_search_key: toml
                 .defaults
                 .brave_search_key
@@ -1612,10 +2632,13 @@ impl Config {
                 .unwrap_or_else(|| base_defaults.projects.clone()),
         };
 
+        let mut agent_api_tokens = Vec::new();
         let mut agents: Vec<AgentConfig> = toml
             .agents
             .into_iter()
             .map(|a| -> Result<AgentConfig> {
+                agent_api_tokens.extend(parse_agent_api_tokens(&a.id, a.api_tokens)?);
+
                 // Per-agent routing resolves against instance defaults
                 let agent_routing = a
                     .routing
@@ -1637,9 +2660,21 @@ impl Config {
                         enabled: h.enabled,
                         run_once: h.run_once,
                         timeout_secs: h.timeout_secs,
+                        timezone: h.timezone,
                     })
                     .collect();
 
+                let memory_scopes = crate::memory::scope::MemoryScopes::new(
+                    a.memory_scopes
+                        .into_iter()
+                        .map(|scope| crate::memory::scope::MemoryScope {
+                            name: scope.name,
+                            channels: scope.channels,
+                            share_with: scope.share_with,
+                        })
+                        .collect(),
+                )?;
+
                 Ok(AgentConfig {
                     id: a.id,
                     default: a.default,
@@ -1670,6 +2705,9 @@ impl Config {
                         message_interval: mp
                             .message_interval
                             .unwrap_or(defaults.memory_persistence.message_interval),
+                        embedding_batch_size: mp
+                            .embedding_batch_size
+                            .unwrap_or(defaults.memory_persistence.embedding_batch_size),
                     }),
                     coalesce: a.coalesce.map(|c| CoalesceConfig {
                         enabled: c.enabled.unwrap_or(defaults.coalesce.enabled),
@@ -1701,6 +2739,10 @@ impl Config {
                             .startup_delay_secs
                             .unwrap_or(defaults.warmup.startup_delay_secs),
                     }),
+                    quotas: a
+                        .quotas
+                        .map(|q| QuotaConfig::resolve(q, defaults.quotas))
+                        .transpose()?,
                     browser: a.browser.map(|b| BrowserConfig {
                         enabled: b.enabled.unwrap_or(defaults.browser.enabled),
                         headless: b.headless.unwrap_or(defaults.browser.headless),
@@ -1725,14 +2767,12 @@ impl Config {
                         ),
                         chrome_cache_dir: defaults.browser.chrome_cache_dir.clone(),
                     }),
-                    channel: a.channel.map(|channel_config| ChannelConfig {
-                        listen_only_mode: channel_config
-                            .listen_only_mode
-                            .unwrap_or(defaults.channel.listen_only_mode),
-                        save_attachments: channel_config
-                            .save_attachments
-                            .unwrap_or(defaults.channel.save_attachments),
-                    }),
+                    channel: a
+                        .channel
+                        .map(|channel_config| {
+                            parse_channel_config(channel_config, defaults.channel)
+                        })
+                        .transpose()?,
                     mcp: match a.mcp {
                         Some(mcp_servers) => Some(
                             mcp_servers
@@ -1742,6 +2782,41 @@ impl Config {
                         ),
                         None => None,
                     },
+                    issue_trackers: match a.issue_trackers {
+                        Some(trackers) => Some(
+                            trackers
+                                .into_iter()
+                                .map(parse_issue_tracker_config)
+                                .collect::<Result<Vec<_>>>()?,
+                        ),
+                        None => None,
+                    },
+                    weather: a
+                        .weather
+                        .map(|weather| parse_weather_config(weather, &defaults.weather))
+                        .transpose()?,
+                    translation: a
+                        .translation
+                        .map(|translation| {
+                            parse_translation_config(translation, &defaults.translation)
+                        })
+                        .transpose()?,
+                    embedding: a
+                        .embedding
+                        .map(|embedding| parse_embedding_config(embedding, &defaults.embedding))
+                        .transpose()?,
+                    runtime: a
+                        .runtime
+                        .map(|runtime| parse_agent_runtime_config(runtime, &defaults.runtime))
+                        .transpose()?,
+                    calculator: a
+                        .calculator
+                        .map(|calculator| parse_calculator_config(calculator, &defaults.calculator))
+                        .transpose()?,
+                    broadcast: a
+                        .broadcast
+                        .map(|broadcast| parse_broadcast_config(broadcast, &defaults.broadcast))
+                        .transpose()?,
                     brave_search_key: a.brave_search_key.as_deref().and_then(resolve_env_value),
                     cron_timezone: a.cron_timezone.as_deref().and_then(resolve_env_value),
                     user_timezone: a.user_timezone.as_deref().and_then(resolve_env_value),
@@ -1768,6 +2843,8 @@ impl Config {
                         }
                     }),
                     cron,
+                    memory_scopes,
+                    workspace_git: a.workspace_git,
                 })
             })
             .collect::<Result<Vec<_>>>()?;
@@ -1793,15 +2870,25 @@ impl Config {
                 ingestion: None,
                 cortex: None,
                 warmup: None,
+                quotas: None,
                 browser: None,
                 channel: None,
                 mcp: None,
+                issue_trackers: None,
+                weather: None,
+                translation: None,
+                embedding: None,
+                runtime: None,
+                calculator: None,
+                broadcast: None,
                 brave_search_key: None,
                 cron_timezone: None,
                 user_timezone: None,
                 sandbox: None,
                 projects: None,
                 cron: Vec::new(),
+                memory_scopes: crate::memory::scope::MemoryScopes::default(),
+                workspace_git: false,
             });
         }
 
@@ -2019,6 +3106,10 @@ impl Config {
                             allowed_senders: instance.allowed_senders,
                             max_body_bytes: instance.max_body_bytes,
                             max_attachment_bytes: instance.max_attachment_bytes,
+                            task_address: instance
+                                .task_address
+                                .as_deref()
+                                .and_then(resolve_env_value),
                         }
                     })
                     .collect::<Vec<_>>();
@@ -2089,6 +3180,7 @@ impl Config {
                     allowed_senders: email.allowed_senders,
                     max_body_bytes: email.max_body_bytes,
                     max_attachment_bytes: email.max_attachment_bytes,
+                    task_address: email.task_address.as_deref().and_then(resolve_env_value),
                     instances,
                 })
             }),
@@ -2227,6 +3319,30 @@ impl Config {
                     ignore_stories: s.ignore_stories,
                 })
             }),
+            filters: toml
+                .messaging
+                .filters
+                .into_iter()
+                .map(|(key, filter)| {
+                    (
+                        key.trim().to_string(),
+                        MessageFilterConfig {
+                            allow_channels: filter.allow_channels,
+                            deny_channels: filter.deny_channels,
+                            deny_users: filter.deny_users,
+                            ignore_bots: filter.ignore_bots,
+                            allowed_bots: filter.allowed_bots,
+                        },
+                    )
+                })
+                .collect(),
+            external: toml
+                .messaging
+                .external
+                .into_iter()
+                .filter_map(resolve_external_adapter)
+                .collect(),
+            twilio: parse_twilio_numbers(toml.messaging.twilio),
         };
 
         let bindings: Vec<Binding> = toml
@@ -2247,11 +3363,47 @@ impl Config {
 
         validate_named_messaging_adapters(&messaging, &bindings)?;
 
+        let auth_token = toml.api.auth_token.as_deref().and_then(resolve_env_value);
+        let mut api_tokens = parse_api_tokens(toml.api.tokens)?;
+        api_tokens.extend(agent_api_tokens);
+        ensure_unique_api_tokens(auth_token.as_deref(), &api_tokens)?;
+
         let api = ApiConfig {
             enabled: toml.api.enabled,
             port: toml.api.port,
             bind: hosted_api_bind(toml.api.bind),
-            auth_token: toml.api.auth_token.as_deref().and_then(resolve_env_value),
+            auth_token,
+            tokens: api_tokens,
+            readiness: ReadinessConfig {
+                require_all_agents: toml.api.readiness.require_all_agents,
+                require_provider: toml.api.readiness.require_provider,
+            },
+            idempotency_window_secs: toml.api.idempotency_window_secs,
+            rate_limit: ApiRateLimitConfig {
+                requests_per_minute: toml.api.rate_limit.requests_per_minute,
+                expensive_requests_per_minute: toml.api.rate_limit.expensive_requests_per_minute,
+            },
+            // Relative certificate paths are resolved against the instance
+            // directory, next to config.toml.
+            tls: toml.api.tls.map(|tls| ApiTlsConfig {
+                cert_path: instance_dir.join(tls.cert_path),
+                key_path: instance_dir.join(tls.key_path),
+            }),
+            tcp_enabled: toml.api.tcp_enabled,
+            unix_socket: toml.api.unix_socket.map(|socket| ApiUnixSocketConfig {
+                path: instance_dir.join(socket.path),
+                mode: socket.mode,
+            }),
+            webhooks: parse_api_webhooks(toml.api.webhooks)?,
+            event_history: parse_api_event_history(toml.api.event_history)?,
+            openai: parse_api_openai(toml.api.openai)?,
+            widget: parse_api_widget(toml.api.widget)?,
+            dev_proxy_url: std::env::var("SPACEBOT_DEV_PROXY_URL").ok().or_else(|| {
+                toml.api
+                    .dev_proxy_url
+                    .as_deref()
+                    .and_then(resolve_env_value)
+            }),
         };
 
         let metrics = MetricsConfig {
@@ -2316,9 +3468,13 @@ impl Config {
         let mut humans: Vec<HumanDef> = toml
             .humans
             .into_iter()
-            .map(|h| {
+            .map(|h| -> Result<HumanDef> {
                 let description = load_human_md(&humans_dir.join(&h.id));
-                HumanDef {
+                let notifications = h
+                    .notifications
+                    .map(|raw| parse_notification_preferences(&h.id, raw))
+                    .transpose()?;
+                Ok(HumanDef {
                     id: h.id,
                     display_name: h.display_name,
                     role: h.role,
@@ -2328,9 +3484,10 @@ impl Config {
                     telegram_id: h.telegram_id,
                     slack_id: h.slack_id,
                     email: h.email,
-                }
+                    notifications,
+                })
             })
-            .collect();
+            .collect::<Result<_>>()?;
 
         // Default admin human if none defined
         if humans.is_empty() {
@@ -2345,6 +3502,7 @@ impl Config {
                 telegram_id: None,
                 slack_id: None,
                 email: None,
+                notifications: None,
             });
 
             // Link the default admin to the default agent so the agent sees
@@ -2361,7 +3519,7 @@ impl Config {
             }
         }
 
-        Ok(Config {
+        let mut config = Config {
             instance_dir,
             llm,
             defaults,
@@ -2374,7 +3532,14 @@ impl Config {
             api,
             metrics,
             telemetry,
-        })
+            leader_election: LeaderElectionConfig {
+                enabled: toml.leader_election.enabled,
+                lease_secs: toml.leader_election.lease_secs.max(3),
+            },
+            issues: Vec::new(),
+        };
+        config.issues = super::sanity::check(&mut config)?;
+        Ok(config)
     }
 }
 
@@ -1,6 +1,7 @@
-use super::toml_schema::TomlRoutingConfig;
+use super::toml_schema::{TomlRoutingConfig, TomlSmartRouting};
 use super::{ApiType, ProviderConfig};
 use crate::llm::routing::RoutingConfig;
+use crate::llm::smart_router::{Classifier, SmartRouting};
 
 use std::collections::HashMap;
 
@@ -336,5 +337,30 @@ pub(super) fn resolve_routing(
         cortex_thinking_effort: t
             .cortex_thinking_effort
             .unwrap_or_else(|| base.cortex_thinking_effort.clone()),
+        smart: resolve_smart_routing(t.smart, &base.smart),
+    }
+}
+
+fn resolve_smart_routing(toml: Option<TomlSmartRouting>, base: &SmartRouting) -> SmartRouting {
+    let Some(t) = toml else { return base.clone() };
+
+    let classifier = match t.classifier.as_deref().map(str::parse::<Classifier>) {
+        Some(Ok(classifier)) => classifier,
+        Some(Err(error)) => {
+            tracing::warn!(%error, "ignoring routing.smart.classifier");
+            base.classifier
+        }
+        None => base.classifier,
+    };
+
+    SmartRouting {
+        enabled: t.enabled.unwrap_or(base.enabled),
+        fast: t.fast.unwrap_or_else(|| base.fast.clone()),
+        strong: t.strong.unwrap_or_else(|| base.strong.clone()),
+        classifier,
+        long_message_chars: t
+            .long_message_chars
+            .unwrap_or(base.long_message_chars)
+            .max(1),
     }
 }
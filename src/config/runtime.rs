@@ -4,9 +4,11 @@ use std::sync::Arc;
 use arc_swap::ArcSwap;
 
 use super::{
-    BrowserConfig, ChannelConfig, CoalesceConfig, CompactionConfig, Config, CortexConfig,
-    DefaultsConfig, IngestionConfig, McpServerConfig, MemoryPersistenceConfig, OpenCodeConfig,
-    ResolvedAgentConfig, WarmupConfig, WarmupStatus, WorkReadiness, evaluate_work_readiness,
+    BroadcastConfig, BrowserConfig, CalculatorConfig, ChannelConfig, CoalesceConfig,
+    CompactionConfig, Config, CortexConfig, DefaultsConfig, IngestionConfig, IssueTrackerConfig,
+    McpServerConfig, MemoryPersistenceConfig, OpenCodeConfig, ResolvedAgentConfig,
+    TranslationConfig, WarmupConfig, WarmupStatus, WeatherConfig, WorkReadiness,
+    evaluate_work_readiness,
 };
 use crate::llm::routing::RoutingConfig;
 use crate::tools::browser::SharedBrowserHandle;
@@ -38,11 +40,25 @@ pub struct RuntimeConfig {
     pub max_concurrent_workers: ArcSwap<usize>,
     pub browser_config: ArcSwap<BrowserConfig>,
     pub mcp: ArcSwap<Vec<McpServerConfig>>,
+    pub issue_trackers: ArcSwap<Vec<IssueTrackerConfig>>,
+    pub weather: ArcSwap<WeatherConfig>,
+    pub translation: ArcSwap<TranslationConfig>,
+    pub calculator: ArcSwap<CalculatorConfig>,
+    pub broadcast: ArcSwap<BroadcastConfig>,
     pub history_backfill_count: ArcSwap<usize>,
     pub brave_search_key: ArcSwap<Option<String>>,
     pub cron_timezone: ArcSwap<Option<String>>,
     pub user_timezone: ArcSwap<Option<String>>,
     pub cortex: ArcSwap<CortexConfig>,
+    /// Set through the pause API. While paused, inbound messages are held or
+    /// dropped (per `channel.pause_behavior`), and cortex loops, cron jobs,
+    /// ingestion, batch jobs, and backfills hold off.
+    /// Persisted in the settings store and restored by [`Self::set_settings`].
+    pub paused: ArcSwap<bool>,
+    /// Channel-to-scope mapping enforced by the memory tools.
+    pub memory_scopes: ArcSwap<crate::memory::scope::MemoryScopes>,
+    /// Commit identity and skill changes to the agent root's git repository.
+    pub workspace_git: ArcSwap<bool>,
     pub warmup: ArcSwap<WarmupConfig>,
     /// Current warmup lifecycle status for API and observability.
     pub warmup_status: ArcSwap<WarmupStatus>,
@@ -54,6 +70,8 @@ pub struct RuntimeConfig {
     pub prompts: ArcSwap<crate::prompts::PromptEngine>,
     pub identity: ArcSwap<crate::identity::Identity>,
     pub skills: ArcSwap<crate::skills::SkillSet>,
+    /// Rendered channel prompt sections, cleared on every reload.
+    pub prompt_sections: crate::agent::prompt_cache::PromptSectionCache,
     pub opencode: ArcSwap<OpenCodeConfig>,
     /// Shared pool of OpenCode server processes. Lazily initialized on first use.
     pub opencode_server_pool: ArcSwap<crate::opencode::OpenCodeServerPool>,
@@ -119,11 +137,19 @@ impl RuntimeConfig {
             max_concurrent_workers: ArcSwap::from_pointee(agent_config.max_concurrent_workers),
             browser_config: ArcSwap::from_pointee(agent_config.browser.clone()),
             mcp: ArcSwap::from_pointee(agent_config.mcp.clone()),
+            issue_trackers: ArcSwap::from_pointee(agent_config.issue_trackers.clone()),
+            weather: ArcSwap::from_pointee(agent_config.weather.clone()),
+            translation: ArcSwap::from_pointee(agent_config.translation.clone()),
+            calculator: ArcSwap::from_pointee(agent_config.calculator.clone()),
+            broadcast: ArcSwap::from_pointee(agent_config.broadcast.clone()),
             history_backfill_count: ArcSwap::from_pointee(agent_config.history_backfill_count),
             brave_search_key: ArcSwap::from_pointee(agent_config.brave_search_key.clone()),
             cron_timezone: ArcSwap::from_pointee(agent_config.cron_timezone.clone()),
             user_timezone: ArcSwap::from_pointee(agent_config.user_timezone.clone()),
             cortex: ArcSwap::from_pointee(agent_config.cortex),
+            paused: ArcSwap::from_pointee(false),
+            memory_scopes: ArcSwap::from_pointee(agent_config.memory_scopes.clone()),
+            workspace_git: ArcSwap::from_pointee(agent_config.workspace_git),
             warmup: ArcSwap::from_pointee(agent_config.warmup),
             warmup_status: ArcSwap::from_pointee(WarmupStatus::default()),
             warmup_lock: Arc::new(tokio::sync::Mutex::new(())),
@@ -131,6 +157,7 @@ impl RuntimeConfig {
             prompts: ArcSwap::from_pointee(prompts),
             identity: ArcSwap::from_pointee(identity),
             skills: ArcSwap::from_pointee(skills),
+            prompt_sections: Default::default(),
             opencode: ArcSwap::from_pointee(defaults.opencode.clone()),
             opencode_server_pool: ArcSwap::from_pointee(server_pool),
             cron_store: ArcSwap::from_pointee(None),
@@ -166,6 +193,9 @@ impl RuntimeConfig {
         explicit_listen_only: Option<bool>,
     ) {
         self.settings.store(Arc::new(Some(settings.clone())));
+        if settings.agent_paused() {
+            self.paused.store(Arc::new(true));
+        }
         self.channel_listen_only_explicit
             .store(Arc::new(explicit_listen_only));
         if explicit_listen_only.is_none() {
@@ -202,6 +232,19 @@ impl RuntimeConfig {
         self.work_readiness().ready
     }
 
+    /// Whether the agent is paused via the API.
+    pub fn is_paused(&self) -> bool {
+        **self.paused.load()
+    }
+
+    /// Wait until the agent is resumed. Returns immediately if it isn't
+    /// paused.
+    pub async fn wait_until_resumed(&self) {
+        while self.is_paused() {
+            tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+        }
+    }
+
     /// Path to the saved attachments directory for persisted channel files.
     pub fn saved_dir(&self) -> std::path::PathBuf {
         self.workspace_dir.join("saved")
@@ -279,6 +322,13 @@ impl RuntimeConfig {
         }
         self.browser_config.store(Arc::new(resolved.browser));
         self.mcp.store(Arc::new(new_mcp.clone()));
+        self.issue_trackers
+            .store(Arc::new(resolved.issue_trackers.clone()));
+        self.weather.store(Arc::new(resolved.weather.clone()));
+        self.translation
+            .store(Arc::new(resolved.translation.clone()));
+        self.calculator.store(Arc::new(resolved.calculator.clone()));
+        self.broadcast.store(Arc::new(resolved.broadcast.clone()));
         self.history_backfill_count
             .store(Arc::new(resolved.history_backfill_count));
         self.brave_search_key
@@ -286,6 +336,8 @@ impl RuntimeConfig {
         self.cron_timezone.store(Arc::new(resolved.cron_timezone));
         self.user_timezone.store(Arc::new(resolved.user_timezone));
         self.cortex.store(Arc::new(resolved.cortex));
+        self.memory_scopes.store(Arc::new(resolved.memory_scopes));
+        self.workspace_git.store(Arc::new(resolved.workspace_git));
         self.warmup.store(Arc::new(resolved.warmup));
         // Preserve project_paths from the current sandbox config when
         // reloading — the resolved config only has user-configured paths.
@@ -317,20 +369,44 @@ impl RuntimeConfig {
 
         mcp_manager.reconcile(&old_mcp, &new_mcp).await;
 
+        self.prompt_sections.clear();
+
         tracing::info!(agent_id, "runtime config reloaded");
     }
 
     /// Reload identity files from disk.
     pub fn reload_identity(&self, identity: crate::identity::Identity) {
         self.identity.store(Arc::new(identity));
+        self.prompt_sections.clear();
         tracing::info!("identity reloaded");
     }
 
     /// Reload skills from disk.
     pub fn reload_skills(&self, skills: crate::skills::SkillSet) {
         self.skills.store(Arc::new(skills));
+        self.prompt_sections.clear();
         tracing::info!("skills reloaded");
     }
+
+    /// Commit identity and skill changes to the workspace history, if
+    /// `workspace_git` is enabled. Failures are logged, not returned: the
+    /// change itself already succeeded.
+    pub async fn record_workspace_change(&self, actor: &str, summary: &str) {
+        if !**self.workspace_git.load() {
+            return;
+        }
+
+        let root = &self.identity_dir;
+        let result = match crate::identity::git::ensure_repo(root).await {
+            Ok(()) => crate::identity::git::commit(root, actor, summary).await,
+            Err(error) => Err(error),
+        };
+        match result {
+            Ok(Some(hash)) => tracing::debug!(%hash, actor, summary, "workspace change committed"),
+            Ok(None) => {}
+            Err(error) => tracing::warn!(%error, actor, "failed to commit workspace change"),
+        }
+    }
 }
 
 impl std::fmt::Debug for RuntimeConfig {
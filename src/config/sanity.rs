@@ -0,0 +1,300 @@
+//! Sanity checks on a loaded config.
+//!
+//! Parsing accepts combinations the runtime can't use as written: compaction
+//! thresholds out of order, a `context_window` bigger than the channel model
+//! can take, a coalesce `max_wait_ms` shorter than its debounce. Each problem
+//! is either corrected in place with a logged warning, rejected with an error
+//! naming the setting, or, when there's no safe correction, kept as an
+//! unresolved issue that `/api/status` reports.
+
+use super::{CoalesceConfig, CompactionConfig, Config};
+use crate::error::{ConfigError, Result};
+use crate::llm::RoutingConfig;
+use crate::llm::limits::context_limit;
+
+use serde::Serialize;
+
+/// What happened to a problem found in the config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueResolution {
+    /// The value was changed to one the runtime can use.
+    Corrected,
+    /// Left as configured; the operator needs to fix it.
+    Unresolved,
+}
+
+/// A problem found in the config.
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+pub struct ConfigIssue {
+    /// `defaults` or `agents.<id>`.
+    pub scope: String,
+    /// Dotted path of the setting, e.g. `compaction.emergency_threshold`.
+    pub field: String,
+    pub message: String,
+    pub resolution: IssueResolution,
+}
+
+impl ConfigIssue {
+    /// Log the issue. Config is loaded before tracing starts, so this is
+    /// called once logging is up rather than when the issue is found.
+    pub fn log(&self) {
+        match self.resolution {
+            IssueResolution::Corrected => {
+                tracing::warn!(scope = %self.scope, field = %self.field, "config corrected: {}", self.message);
+            }
+            IssueResolution::Unresolved => {
+                tracing::warn!(scope = %self.scope, field = %self.field, "config issue: {}", self.message);
+            }
+        }
+    }
+
+    fn corrected(scope: &str, field: &str, message: String) -> Self {
+        Self {
+            scope: scope.to_string(),
+            field: field.to_string(),
+            message,
+            resolution: IssueResolution::Corrected,
+        }
+    }
+
+    fn unresolved(scope: &str, field: &str, message: String) -> Self {
+        Self {
+            scope: scope.to_string(),
+            field: field.to_string(),
+            message,
+            resolution: IssueResolution::Unresolved,
+        }
+    }
+}
+
+/// Check the defaults and every agent, correcting what can be corrected.
+/// Fails on values that have no sensible correction.
+pub(super) fn check(config: &mut Config) -> Result<Vec<ConfigIssue>> {
+    let mut issues = Vec::new();
+
+    let defaults = &mut config.defaults;
+    check_compaction("defaults", &mut defaults.compaction, &mut issues)?;
+    check_coalesce("defaults", &mut defaults.coalesce, &mut issues);
+    check_context_window(
+        "defaults",
+        &mut defaults.context_window,
+        &defaults.routing,
+        &mut issues,
+    );
+
+    for agent in &mut config.agents {
+        let scope = format!("agents.{}", agent.id);
+        if let Some(compaction) = &mut agent.compaction {
+            check_compaction(&scope, compaction, &mut issues)?;
+        }
+        if let Some(coalesce) = &mut agent.coalesce {
+            check_coalesce(&scope, coalesce, &mut issues);
+        }
+        // Agents that inherit both were covered by the defaults check.
+        if agent.context_window.is_some() || agent.routing.is_some() {
+            let mut context_window = agent
+                .context_window
+                .unwrap_or(config.defaults.context_window);
+            let routing = agent.routing.as_ref().unwrap_or(&config.defaults.routing);
+            let before = context_window;
+            check_context_window(&scope, &mut context_window, routing, &mut issues);
+            if context_window != before {
+                agent.context_window = Some(context_window);
+            }
+        }
+    }
+
+    Ok(issues)
+}
+
+fn check_compaction(
+    scope: &str,
+    compaction: &mut CompactionConfig,
+    issues: &mut Vec<ConfigIssue>,
+) -> Result<()> {
+    for (field, value) in [
+        ("background_threshold", compaction.background_threshold),
+        ("aggressive_threshold", compaction.aggressive_threshold),
+        ("emergency_threshold", compaction.emergency_threshold),
+    ] {
+        if !value.is_finite() || value <= 0.0 || value > 1.0 {
+            return Err(ConfigError::Invalid(format!(
+                "{scope}.compaction.{field} must be greater than 0 and at most 1, got {value}"
+            ))
+            .into());
+        }
+    }
+
+    let mut thresholds = [
+        compaction.background_threshold,
+        compaction.aggressive_threshold,
+        compaction.emergency_threshold,
+    ];
+    if thresholds.is_sorted() {
+        return Ok(());
+    }
+    thresholds.sort_by(f32::total_cmp);
+    issues.push(ConfigIssue::corrected(
+        scope,
+        "compaction",
+        format!(
+            "thresholds must satisfy background <= aggressive <= emergency, got {} / {} / {}; \
+             using {} / {} / {}",
+            compaction.background_threshold,
+            compaction.aggressive_threshold,
+            compaction.emergency_threshold,
+            thresholds[0],
+            thresholds[1],
+            thresholds[2],
+        ),
+    ));
+    [
+        compaction.background_threshold,
+        compaction.aggressive_threshold,
+        compaction.emergency_threshold,
+    ] = thresholds;
+    Ok(())
+}
+
+fn check_coalesce(scope: &str, coalesce: &mut CoalesceConfig, issues: &mut Vec<ConfigIssue>) {
+    if coalesce.max_wait_ms >= coalesce.debounce_ms {
+        return;
+    }
+    issues.push(ConfigIssue::corrected(
+        scope,
+        "coalesce.max_wait_ms",
+        format!(
+            "max_wait_ms ({}) is shorter than debounce_ms ({}); raised to {}",
+            coalesce.max_wait_ms, coalesce.debounce_ms, coalesce.debounce_ms
+        ),
+    ));
+    coalesce.max_wait_ms = coalesce.debounce_ms;
+}
+
+/// Clamp `context_window` to the channel model's limit. Other routed models
+/// with a smaller limit share the window, so they're reported instead.
+fn check_context_window(
+    scope: &str,
+    context_window: &mut usize,
+    routing: &RoutingConfig,
+    issues: &mut Vec<ConfigIssue>,
+) {
+    if let Some(limit) = context_limit(&routing.channel)
+        && *context_window > limit
+    {
+        issues.push(ConfigIssue::corrected(
+            scope,
+            "context_window",
+            format!(
+                "context_window ({}) is larger than channel model '{}' supports ({limit}); \
+                 lowered to {limit}",
+                *context_window, routing.channel
+            ),
+        ));
+        *context_window = limit;
+    }
+
+    let mut models = vec![
+        ("routing.branch".to_string(), routing.branch.as_str()),
+        ("routing.worker".to_string(), routing.worker.as_str()),
+        ("routing.compactor".to_string(), routing.compactor.as_str()),
+    ];
+    if routing.smart.enabled {
+        models.push((
+            "routing.smart.fast".to_string(),
+            routing.smart.fast.as_str(),
+        ));
+        models.push((
+            "routing.smart.strong".to_string(),
+            routing.smart.strong.as_str(),
+        ));
+    }
+    let mut task_types: Vec<_> = routing.task_overrides.iter().collect();
+    task_types.sort();
+    for (task_type, model) in task_types {
+        models.push((
+            format!("routing.task_overrides.{task_type}"),
+            model.as_str(),
+        ));
+    }
+
+    for (field, model) in models {
+        if let Some(limit) = context_limit(model)
+            && *context_window > limit
+        {
+            issues.push(ConfigIssue::unresolved(
+                scope,
+                &field,
+                format!(
+                    "model '{model}' supports {limit} tokens, less than context_window \
+                     ({}); its prompts can overflow before compaction runs",
+                    *context_window
+                ),
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn corrects_order_and_wait_and_rejects_out_of_range() {
+        let mut issues = Vec::new();
+
+        let mut compaction = CompactionConfig {
+            background_threshold: 0.9,
+            aggressive_threshold: 0.8,
+            emergency_threshold: 0.95,
+        };
+        check_compaction("defaults", &mut compaction, &mut issues).unwrap();
+        assert_eq!(compaction.background_threshold, 0.8);
+        assert_eq!(compaction.aggressive_threshold, 0.9);
+        assert_eq!(compaction.emergency_threshold, 0.95);
+
+        let mut coalesce = CoalesceConfig {
+            debounce_ms: 2000,
+            max_wait_ms: 500,
+            ..Default::default()
+        };
+        check_coalesce("agents.main", &mut coalesce, &mut issues);
+        assert_eq!(coalesce.max_wait_ms, 2000);
+
+        assert_eq!(issues.len(), 2);
+        assert!(
+            issues
+                .iter()
+                .all(|issue| issue.resolution == IssueResolution::Corrected)
+        );
+
+        compaction.emergency_threshold = 1.5;
+        let error = check_compaction("agents.main", &mut compaction, &mut issues).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("agents.main.compaction.emergency_threshold")
+        );
+    }
+
+    #[test]
+    fn clamps_to_the_channel_model_and_reports_smaller_models() {
+        let mut issues = Vec::new();
+        let routing = RoutingConfig {
+            channel: "anthropic/claude-sonnet-4".into(),
+            branch: "anthropic/claude-sonnet-4".into(),
+            worker: "openai/gpt-4o-mini".into(),
+            compactor: "anthropic/claude-haiku-4.5".into(),
+            ..Default::default()
+        };
+        let mut context_window = 500_000;
+        check_context_window("defaults", &mut context_window, &routing, &mut issues);
+
+        assert_eq!(context_window, 200_000);
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].resolution, IssueResolution::Corrected);
+        assert_eq!(issues[1].field, "routing.worker");
+        assert_eq!(issues[1].resolution, IssueResolution::Unresolved);
+    }
+}
@@ -1,7 +1,7 @@
 // -- TOML deserialization types --
 
 use serde::{Deserialize, Deserializer};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Deserialize)]
 pub(super) struct TomlConfig {
@@ -27,6 +27,29 @@ pub(super) struct TomlConfig {
     pub(super) metrics: TomlMetricsConfig,
     #[serde(default)]
     pub(super) telemetry: TomlTelemetryConfig,
+    #[serde(default)]
+    pub(super) leader_election: TomlLeaderElectionConfig,
+}
+
+#[derive(Deserialize)]
+pub(super) struct TomlLeaderElectionConfig {
+    #[serde(default)]
+    pub(super) enabled: bool,
+    #[serde(default = "default_leader_lease_secs")]
+    pub(super) lease_secs: u64,
+}
+
+impl Default for TomlLeaderElectionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            lease_secs: default_leader_lease_secs(),
+        }
+    }
+}
+
+pub(super) fn default_leader_lease_secs() -> u64 {
+    30
 }
 
 #[derive(Deserialize)]
@@ -68,6 +91,18 @@ pub(super) struct TomlHumanDef {
     pub(super) telegram_id: Option<String>,
     pub(super) slack_id: Option<String>,
     pub(super) email: Option<String>,
+    pub(super) notifications: Option<TomlNotificationPreferences>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct TomlNotificationPreferences {
+    #[serde(default)]
+    pub(super) categories: Vec<super::NotificationCategory>,
+    #[serde(default)]
+    pub(super) targets: Vec<String>,
+    pub(super) quiet_start_hour: Option<u8>,
+    pub(super) quiet_end_hour: Option<u8>,
+    pub(super) timezone: Option<String>,
 }
 
 #[derive(Deserialize, Default)]
@@ -88,6 +123,139 @@ pub(super) struct TomlApiConfig {
     pub(super) bind: String,
     #[serde(default)]
     pub(super) auth_token: Option<String>,
+    #[serde(default)]
+    pub(super) tokens: Vec<TomlApiToken>,
+    #[serde(default)]
+    pub(super) readiness: TomlReadinessConfig,
+    #[serde(default = "default_api_idempotency_window_secs")]
+    pub(super) idempotency_window_secs: u64,
+    #[serde(default)]
+    pub(super) rate_limit: TomlApiRateLimitConfig,
+    #[serde(default)]
+    pub(super) tls: Option<TomlApiTlsConfig>,
+    #[serde(default = "default_api_enabled")]
+    pub(super) tcp_enabled: bool,
+    #[serde(default)]
+    pub(super) unix_socket: Option<TomlApiUnixSocketConfig>,
+    #[serde(default)]
+    pub(super) webhooks: Vec<TomlApiWebhook>,
+    #[serde(default)]
+    pub(super) event_history: TomlApiEventHistoryConfig,
+    #[serde(default)]
+    pub(super) openai: TomlApiOpenAiConfig,
+    #[serde(default)]
+    pub(super) widget: TomlApiWidgetConfig,
+    #[serde(default)]
+    pub(super) dev_proxy_url: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+pub(super) struct TomlApiEventHistoryConfig {
+    pub(super) enabled: Option<bool>,
+    pub(super) events: Option<Vec<String>>,
+    pub(super) retention_days: Option<u64>,
+    pub(super) max_events: Option<u64>,
+}
+
+#[derive(Deserialize, Default)]
+pub(super) struct TomlApiOpenAiConfig {
+    pub(super) enabled: Option<bool>,
+    pub(super) reply_timeout_secs: Option<u64>,
+}
+
+#[derive(Deserialize, Default)]
+pub(super) struct TomlApiWidgetConfig {
+    pub(super) enabled: Option<bool>,
+    pub(super) agent_id: Option<String>,
+    pub(super) allowed_origins: Option<Vec<String>>,
+    pub(super) session_ttl_secs: Option<u64>,
+    pub(super) requests_per_minute: Option<u32>,
+    pub(super) messages_per_minute: Option<u32>,
+    pub(super) max_message_chars: Option<usize>,
+    pub(super) greeting: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct TomlApiTlsConfig {
+    pub(super) cert_path: String,
+    pub(super) key_path: String,
+}
+
+#[derive(Deserialize)]
+pub(super) struct TomlApiUnixSocketConfig {
+    pub(super) path: String,
+    #[serde(default = "default_api_unix_socket_mode")]
+    pub(super) mode: u32,
+}
+
+pub(super) fn default_api_unix_socket_mode() -> u32 {
+    0o660
+}
+
+#[derive(Deserialize)]
+pub(super) struct TomlApiRateLimitConfig {
+    #[serde(default = "default_api_requests_per_minute")]
+    pub(super) requests_per_minute: u32,
+    #[serde(default = "default_api_expensive_requests_per_minute")]
+    pub(super) expensive_requests_per_minute: u32,
+}
+
+impl Default for TomlApiRateLimitConfig {
+    fn default() -> Self {
+        Self {
+            requests_per_minute: default_api_requests_per_minute(),
+            expensive_requests_per_minute: default_api_expensive_requests_per_minute(),
+        }
+    }
+}
+
+pub(super) fn default_api_requests_per_minute() -> u32 {
+    1200
+}
+pub(super) fn default_api_expensive_requests_per_minute() -> u32 {
+    60
+}
+
+#[derive(Deserialize)]
+pub(super) struct TomlReadinessConfig {
+    #[serde(default = "default_enabled")]
+    pub(super) require_all_agents: bool,
+    #[serde(default = "default_enabled")]
+    pub(super) require_provider: bool,
+}
+
+impl Default for TomlReadinessConfig {
+    fn default() -> Self {
+        Self {
+            require_all_agents: true,
+            require_provider: true,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub(super) struct TomlApiToken {
+    #[serde(default)]
+    pub(super) name: Option<String>,
+    pub(super) token: String,
+    #[serde(default = "default_api_token_scope")]
+    pub(super) scope: String,
+    #[serde(default)]
+    pub(super) agents: Vec<String>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct TomlApiWebhook {
+    #[serde(default)]
+    pub(super) name: Option<String>,
+    pub(super) url: String,
+    pub(super) events: Vec<String>,
+    #[serde(default)]
+    pub(super) secret: Option<String>,
+}
+
+pub(super) fn default_api_token_scope() -> String {
+    "read".into()
 }
 
 impl Default for TomlApiConfig {
@@ -97,10 +265,26 @@ impl Default for TomlApiConfig {
             port: default_api_port(),
             bind: default_api_bind(),
             auth_token: None,
+            tokens: Vec::new(),
+            readiness: TomlReadinessConfig::default(),
+            idempotency_window_secs: default_api_idempotency_window_secs(),
+            rate_limit: TomlApiRateLimitConfig::default(),
+            tls: None,
+            tcp_enabled: default_api_enabled(),
+            unix_socket: None,
+            webhooks: Vec::new(),
+            event_history: TomlApiEventHistoryConfig::default(),
+            openai: TomlApiOpenAiConfig::default(),
+            widget: TomlApiWidgetConfig::default(),
+            dev_proxy_url: None,
         }
     }
 }
 
+pub(super) fn default_api_idempotency_window_secs() -> u64 {
+    24 * 60 * 60
+}
+
 pub(super) fn default_api_enabled() -> bool {
     true
 }
@@ -284,10 +468,19 @@ pub(super) struct TomlDefaultsConfig {
     pub(super) ingestion: Option<TomlIngestionConfig>,
     pub(super) cortex: Option<TomlCortexConfig>,
     pub(super) warmup: Option<TomlWarmupConfig>,
+    pub(super) quotas: Option<TomlQuotaConfig>,
     pub(super) browser: Option<TomlBrowserConfig>,
     pub(super) channel: Option<TomlChannelConfig>,
     #[serde(default)]
     pub(super) mcp: Vec<TomlMcpServerConfig>,
+    #[serde(default)]
+    pub(super) issue_trackers: Vec<TomlIssueTrackerConfig>,
+    pub(super) weather: Option<TomlWeatherConfig>,
+    pub(super) translation: Option<TomlTranslationConfig>,
+    pub(super) embedding: Option<TomlEmbeddingConfig>,
+    pub(super) runtime: Option<TomlAgentRuntimeConfig>,
+    pub(super) calculator: Option<TomlCalculatorConfig>,
+    pub(super) broadcast: Option<TomlBroadcastConfig>,
     pub(super) brave_search_key: Option<String>,
     pub(super) cron_timezone: Option<String>,
     pub(super) user_timezone: Option<String>,
@@ -313,12 +506,23 @@ pub(super) struct TomlRoutingConfig {
     #[serde(default)]
     pub(super) task_overrides: HashMap<String, String>,
     pub(super) fallbacks: Option<HashMap<String, Vec<String>>>,
+    pub(super) smart: Option<TomlSmartRouting>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct TomlSmartRouting {
+    pub(super) enabled: Option<bool>,
+    pub(super) fast: Option<String>,
+    pub(super) strong: Option<String>,
+    pub(super) classifier: Option<String>,
+    pub(super) long_message_chars: Option<usize>,
 }
 
 #[derive(Deserialize)]
 pub(super) struct TomlMemoryPersistenceConfig {
     pub(super) enabled: Option<bool>,
     pub(super) message_interval: Option<usize>,
+    pub(super) embedding_batch_size: Option<usize>,
 }
 
 #[derive(Deserialize)]
@@ -356,16 +560,40 @@ pub(super) struct TomlCortexConfig {
     pub(super) bulletin_max_words: Option<usize>,
     pub(super) bulletin_max_turns: Option<usize>,
     pub(super) maintenance_interval_secs: Option<u64>,
+    pub(super) maintenance_half_life_days: Option<TomlHalfLives>,
+    /// Retired linear decay setting, converted to half-lives on load.
     pub(super) maintenance_decay_rate: Option<f32>,
     pub(super) maintenance_prune_threshold: Option<f32>,
     pub(super) maintenance_min_age_days: Option<i64>,
     pub(super) maintenance_merge_similarity_threshold: Option<f32>,
+    pub(super) maintenance_trash_retention_days: Option<i64>,
+    pub(super) maintenance_delete_expired: Option<bool>,
+    pub(super) maintenance_mutation_retention_days: Option<i64>,
+    pub(super) recalibration_interval_secs: Option<u64>,
+    pub(super) consolidation_interval_secs: Option<u64>,
+    pub(super) consolidation_similarity_threshold: Option<f32>,
+    pub(super) consolidation_max_clusters_per_pass: Option<usize>,
+    pub(super) rescore_interval_secs: Option<u64>,
+    pub(super) rescore_min_age_days: Option<i64>,
+    pub(super) rescore_window_days: Option<i64>,
+    pub(super) rescore_stale_ceiling: Option<f32>,
     pub(super) association_interval_secs: Option<u64>,
     pub(super) association_similarity_threshold: Option<f32>,
     pub(super) association_updates_threshold: Option<f32>,
     pub(super) association_max_per_pass: Option<usize>,
 }
 
+#[derive(Deserialize)]
+pub(super) struct TomlHalfLives {
+    pub(super) fact: Option<f32>,
+    pub(super) preference: Option<f32>,
+    pub(super) decision: Option<f32>,
+    pub(super) event: Option<f32>,
+    pub(super) observation: Option<f32>,
+    pub(super) goal: Option<f32>,
+    pub(super) todo: Option<f32>,
+}
+
 #[derive(Deserialize)]
 pub(super) struct TomlWarmupConfig {
     pub(super) enabled: Option<bool>,
@@ -374,6 +602,13 @@ pub(super) struct TomlWarmupConfig {
     pub(super) startup_delay_secs: Option<u64>,
 }
 
+#[derive(Deserialize)]
+pub(super) struct TomlQuotaConfig {
+    pub(super) max_concurrent_tasks: Option<usize>,
+    pub(super) max_db_connections: Option<u32>,
+    pub(super) max_concurrent_llm_calls: Option<usize>,
+}
+
 #[derive(Deserialize)]
 pub(super) struct TomlBrowserConfig {
     pub(super) enabled: Option<bool>,
@@ -389,6 +624,15 @@ pub(super) struct TomlBrowserConfig {
 pub(super) struct TomlChannelConfig {
     pub(super) listen_only_mode: Option<bool>,
     pub(super) save_attachments: Option<bool>,
+    pub(super) pause_behavior: Option<String>,
+    pub(super) speculative_drafts: Option<bool>,
+    pub(super) draft_max_divergence: Option<f64>,
+    pub(super) faq_matching: Option<bool>,
+    pub(super) faq_answer_threshold: Option<f64>,
+    pub(super) faq_context_threshold: Option<f64>,
+    pub(super) reply_verification: Option<String>,
+    pub(super) event_log: Option<bool>,
+    pub(super) event_log_retention_days: Option<i64>,
 }
 
 #[derive(Deserialize)]
@@ -438,6 +682,67 @@ pub(super) fn default_mcp_enabled() -> bool {
     true
 }
 
+#[derive(Deserialize, Clone)]
+pub(super) struct TomlIssueTrackerConfig {
+    pub(super) name: String,
+    pub(super) provider: String,
+    pub(super) base_url: Option<String>,
+    pub(super) email: Option<String>,
+    pub(super) api_token: String,
+    #[serde(default)]
+    pub(super) projects: Vec<String>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct TomlWeatherConfig {
+    pub(super) enabled: Option<bool>,
+    pub(super) provider: Option<String>,
+    pub(super) geocoder: Option<String>,
+    pub(super) units: Option<String>,
+    pub(super) cache_ttl_secs: Option<u64>,
+    pub(super) bulletin_location: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct TomlTranslationConfig {
+    pub(super) enabled: Option<bool>,
+    pub(super) provider: Option<String>,
+    pub(super) deepl_api_key: Option<String>,
+    pub(super) working_language: Option<String>,
+    pub(super) auto_translate: Option<bool>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct TomlEmbeddingConfig {
+    pub(super) provider: Option<String>,
+    pub(super) model: Option<String>,
+    pub(super) api_key: Option<String>,
+    pub(super) base_url: Option<String>,
+    pub(super) dimensions: Option<usize>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct TomlAgentRuntimeConfig {
+    pub(super) dedicated: Option<bool>,
+    pub(super) worker_threads: Option<usize>,
+    pub(super) cpu_affinity: Option<Vec<usize>>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct TomlCalculatorConfig {
+    pub(super) currency_provider: Option<String>,
+    pub(super) rates_cache_ttl_secs: Option<u64>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct TomlBroadcastConfig {
+    pub(super) enabled: Option<bool>,
+    pub(super) require_approval: Option<bool>,
+    pub(super) stagger_ms: Option<u64>,
+    /// Replaces the inherited groups when set.
+    pub(super) groups: Option<BTreeMap<String, Vec<String>>>,
+}
+
 #[derive(Deserialize)]
 pub(super) struct TomlAgentConfig {
     pub(super) id: String,
@@ -460,9 +765,17 @@ pub(super) struct TomlAgentConfig {
     pub(super) ingestion: Option<TomlIngestionConfig>,
     pub(super) cortex: Option<TomlCortexConfig>,
     pub(super) warmup: Option<TomlWarmupConfig>,
+    pub(super) quotas: Option<TomlQuotaConfig>,
     pub(super) browser: Option<TomlBrowserConfig>,
     pub(super) channel: Option<TomlChannelConfig>,
     pub(super) mcp: Option<Vec<TomlMcpServerConfig>>,
+    pub(super) issue_trackers: Option<Vec<TomlIssueTrackerConfig>>,
+    pub(super) weather: Option<TomlWeatherConfig>,
+    pub(super) translation: Option<TomlTranslationConfig>,
+    pub(super) embedding: Option<TomlEmbeddingConfig>,
+    pub(super) runtime: Option<TomlAgentRuntimeConfig>,
+    pub(super) calculator: Option<TomlCalculatorConfig>,
+    pub(super) broadcast: Option<TomlBroadcastConfig>,
     pub(super) brave_search_key: Option<String>,
     pub(super) cron_timezone: Option<String>,
     pub(super) user_timezone: Option<String>,
@@ -470,6 +783,22 @@ pub(super) struct TomlAgentConfig {
     pub(super) projects: Option<TomlProjectsConfig>,
     #[serde(default)]
     pub(super) cron: Vec<TomlCronDef>,
+    #[serde(default)]
+    pub(super) memory_scopes: Vec<TomlMemoryScope>,
+    /// API tokens that may only act on this agent.
+    #[serde(default)]
+    pub(super) api_tokens: Vec<TomlApiToken>,
+    #[serde(default)]
+    pub(super) workspace_git: bool,
+}
+
+#[derive(Deserialize)]
+pub(super) struct TomlMemoryScope {
+    pub(super) name: String,
+    #[serde(default)]
+    pub(super) channels: Vec<String>,
+    #[serde(default)]
+    pub(super) share_with: Vec<String>,
 }
 
 #[derive(Deserialize)]
@@ -486,6 +815,7 @@ pub(super) struct TomlCronDef {
     #[serde(default)]
     pub(super) run_once: bool,
     pub(super) timeout_secs: Option<u64>,
+    pub(super) timezone: Option<String>,
 }
 
 pub(super) fn default_enabled() -> bool {
@@ -501,6 +831,41 @@ pub(super) struct TomlMessagingConfig {
     pub(super) webhook: Option<TomlWebhookConfig>,
     pub(super) twitch: Option<TomlTwitchConfig>,
     pub(super) signal: Option<TomlSignalConfig>,
+    #[serde(default)]
+    pub(super) filters: HashMap<String, TomlMessageFilterConfig>,
+    #[serde(default)]
+    pub(super) external: Vec<TomlExternalAdapterConfig>,
+    #[serde(default)]
+    pub(super) twilio: Vec<TomlTwilioConfig>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct TomlExternalAdapterConfig {
+    pub(super) name: String,
+    #[serde(default = "default_enabled")]
+    pub(super) enabled: bool,
+    pub(super) command: Option<String>,
+    #[serde(default)]
+    pub(super) args: Vec<String>,
+    #[serde(default)]
+    pub(super) env: HashMap<String, String>,
+    pub(super) url: Option<String>,
+    #[serde(default)]
+    pub(super) settings: Option<toml::Table>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct TomlMessageFilterConfig {
+    #[serde(default)]
+    pub(super) allow_channels: Vec<String>,
+    #[serde(default)]
+    pub(super) deny_channels: Vec<String>,
+    #[serde(default)]
+    pub(super) deny_users: Vec<String>,
+    #[serde(default)]
+    pub(super) ignore_bots: bool,
+    #[serde(default)]
+    pub(super) allowed_bots: Vec<String>,
 }
 
 #[derive(Deserialize)]
@@ -613,6 +978,7 @@ pub(super) struct TomlEmailConfig {
     pub(super) max_body_bytes: usize,
     #[serde(default = "default_email_max_attachment_bytes")]
     pub(super) max_attachment_bytes: usize,
+    pub(super) task_address: Option<String>,
     #[serde(default)]
     pub(super) instances: Vec<TomlEmailInstanceConfig>,
 }
@@ -648,6 +1014,7 @@ pub(super) struct TomlEmailInstanceConfig {
     pub(super) max_body_bytes: usize,
     #[serde(default = "default_email_max_attachment_bytes")]
     pub(super) max_attachment_bytes: usize,
+    pub(super) task_address: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -727,6 +1094,40 @@ pub(super) struct TomlSignalInstanceConfig {
     pub(super) ignore_stories: bool,
 }
 
+#[derive(Deserialize)]
+pub(super) struct TomlTwilioConfig {
+    pub(super) name: Option<String>,
+    #[serde(default = "default_enabled")]
+    pub(super) enabled: bool,
+    pub(super) account_sid: Option<String>,
+    pub(super) auth_token: Option<String>,
+    pub(super) phone_number: Option<String>,
+    pub(super) public_url: Option<String>,
+    #[serde(default = "default_twilio_port")]
+    pub(super) port: u16,
+    #[serde(default = "default_webhook_bind")]
+    pub(super) bind: String,
+    #[serde(default)]
+    pub(super) allowed_numbers: Vec<String>,
+    #[serde(default = "default_twilio_max_segments")]
+    pub(super) max_segments: usize,
+    #[serde(default)]
+    pub(super) voice: bool,
+    pub(super) voice_greeting: Option<String>,
+    #[serde(default = "default_twilio_max_recording_secs")]
+    pub(super) max_recording_secs: u32,
+}
+
+pub(super) fn default_twilio_port() -> u16 {
+    18791
+}
+pub(super) fn default_twilio_max_segments() -> usize {
+    4
+}
+pub(super) fn default_twilio_max_recording_secs() -> u32 {
+    120
+}
+
 pub(super) fn default_signal_ignore_stories() -> bool {
     true
 }
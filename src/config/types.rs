@@ -2,12 +2,13 @@
 
 use crate::error::{ConfigError, Result};
 use crate::llm::routing::RoutingConfig;
+use crate::messaging::target::BroadcastTarget;
 use crate::secrets::store::{InstancePattern, SecretField, SystemSecrets};
 
 use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
 
 pub(super) const CRON_TIMEZONE_ENV_VAR: &str = "SPACEBOT_CRON_TIMEZONE";
@@ -59,6 +60,11 @@ pub struct Config {
     pub metrics: MetricsConfig,
     /// OpenTelemetry export configuration.
     pub telemetry: TelemetryConfig,
+    /// Leader election for running several replicas on one instance.
+    pub leader_election: LeaderElectionConfig,
+    /// Problems found while loading: corrected values and ones the operator
+    /// still needs to fix.
+    pub issues: Vec<super::ConfigIssue>,
 }
 
 impl Config {
@@ -71,6 +77,15 @@ impl Config {
             .unwrap_or("main")
     }
 
+    /// Issues that weren't corrected and still need the operator.
+    pub fn unresolved_issues(&self) -> Vec<super::ConfigIssue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.resolution == super::IssueResolution::Unresolved)
+            .cloned()
+            .collect()
+    }
+
     /// Resolve all agent configs against defaults.
     pub fn resolve_agents(&self) -> Vec<ResolvedAgentConfig> {
         self.agents
@@ -117,6 +132,48 @@ pub struct HumanDef {
     pub slack_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub email: Option<String>,
+    /// Which operator alerts this human receives. `None` means none.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notifications: Option<NotificationPreferences>,
+}
+
+/// Kinds of operator alert a human can subscribe to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationCategory {
+    /// Something is waiting for an operator to approve it.
+    Approvals,
+    /// A copy of each cron job's delivered output.
+    CronReports,
+    /// A cron job was disabled after failing repeatedly.
+    CronFailures,
+}
+
+impl NotificationCategory {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Approvals => "approvals",
+            Self::CronReports => "cron_reports",
+            Self::CronFailures => "cron_failures",
+        }
+    }
+}
+
+/// A human's notification preferences, from `[humans.notifications]`.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct NotificationPreferences {
+    pub categories: Vec<NotificationCategory>,
+    /// Delivery targets (`adapter:target`). Empty falls back to the human's
+    /// platform IDs as direct messages.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub targets: Vec<String>,
+    /// `(start_hour, end_hour)` during which nothing is sent. Alerts that
+    /// fall inside it are held until it ends.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quiet_hours: Option<(u8, u8)>,
+    /// IANA timezone for `quiet_hours`. `None` uses UTC.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
 }
 
 /// A visual group definition for the topology UI.
@@ -137,7 +194,94 @@ pub struct ApiConfig {
     pub port: u16,
     /// Address to bind the HTTP server on.
     pub bind: String,
+    /// Legacy single bearer token. Treated as a read-write token.
     pub auth_token: Option<String>,
+    /// Scoped bearer tokens from `[[api.tokens]]`.
+    pub tokens: Vec<ApiToken>,
+    /// What `/readyz` requires before reporting ready.
+    pub readiness: ReadinessConfig,
+    /// How long responses to `Idempotency-Key` requests are kept for replay.
+    pub idempotency_window_secs: u64,
+    /// Per-client request limits.
+    pub rate_limit: ApiRateLimitConfig,
+    /// Serve the API and dashboard over HTTPS with this certificate.
+    pub tls: Option<ApiTlsConfig>,
+    /// Listen on `bind`:`port`. Turn off to serve only on the unix socket.
+    pub tcp_enabled: bool,
+    /// Also serve on a unix domain socket.
+    pub unix_socket: Option<ApiUnixSocketConfig>,
+    /// Outbound webhooks from `[[api.webhooks]]`.
+    pub webhooks: Vec<ApiWebhookConfig>,
+    /// Which API events are persisted for `/api/events/history`.
+    pub event_history: ApiEventHistoryConfig,
+    /// OpenAI-compatible `/v1/chat/completions` endpoint.
+    pub openai: ApiOpenAiConfig,
+    /// Anonymous `/api/widget/*` routes for an embeddable website chat widget.
+    pub widget: ApiWidgetConfig,
+    /// When set, non-API requests are proxied to this URL (typically a local
+    /// Vite dev server) instead of being served from the embedded assets.
+    pub dev_proxy_url: Option<String>,
+}
+
+impl ApiConfig {
+    /// Whether any bearer token is configured. When false the API is open.
+    pub fn requires_auth(&self) -> bool {
+        self.auth_token.is_some() || !self.tokens.is_empty()
+    }
+}
+
+/// A static bearer token accepted by the HTTP API.
+#[derive(Clone)]
+pub struct ApiToken {
+    /// Label used in logs. Never the token itself.
+    pub name: String,
+    pub token: String,
+    pub scope: ApiTokenScope,
+    /// Agent IDs this token may act on. Empty means every agent.
+    pub agents: Vec<String>,
+}
+
+impl std::fmt::Debug for ApiToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ApiToken")
+            .field("name", &self.name)
+            .field("token", &"[REDACTED]")
+            .field("scope", &self.scope)
+            .field("agents", &self.agents)
+            .finish()
+    }
+}
+
+/// What an API token is allowed to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ApiTokenScope {
+    /// Safe methods only (GET, HEAD, OPTIONS).
+    Read,
+    /// Read, plus sending chat messages to agents.
+    Chat,
+    /// Everything, including config and identity mutation.
+    ReadWrite,
+}
+
+impl ApiTokenScope {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "read" | "read_only" | "read-only" | "readonly" => Some(Self::Read),
+            "chat" => Some(Self::Chat),
+            "write" | "read_write" | "read-write" | "readwrite" | "configure" => {
+                Some(Self::ReadWrite)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Read => "read",
+            Self::Chat => "chat",
+            Self::ReadWrite => "read_write",
+        }
+    }
 }
 
 impl Default for ApiConfig {
@@ -147,6 +291,227 @@ impl Default for ApiConfig {
             port: 19898,
             bind: "127.0.0.1".into(),
             auth_token: None,
+            tokens: Vec::new(),
+            readiness: ReadinessConfig::default(),
+            idempotency_window_secs: 24 * 60 * 60,
+            rate_limit: ApiRateLimitConfig::default(),
+            tls: None,
+            tcp_enabled: true,
+            unix_socket: None,
+            webhooks: Vec::new(),
+            event_history: ApiEventHistoryConfig::default(),
+            openai: ApiOpenAiConfig::default(),
+            widget: ApiWidgetConfig::default(),
+            dev_proxy_url: None,
+        }
+    }
+}
+
+/// PEM certificate chain and private key for serving the API over HTTPS.
+#[derive(Debug, Clone)]
+pub struct ApiTlsConfig {
+    pub cert_path: PathBuf,
+    pub key_path: PathBuf,
+}
+
+/// Unix domain socket for the API. Access is controlled by the socket file's
+/// permissions; bearer token auth still applies when configured.
+#[derive(Debug, Clone)]
+pub struct ApiUnixSocketConfig {
+    pub path: PathBuf,
+    /// Permission bits applied to the socket file after binding.
+    pub mode: u32,
+}
+
+/// A URL that receives matching API events as signed POST requests.
+#[derive(Clone)]
+pub struct ApiWebhookConfig {
+    /// Label used in logs and the webhook list.
+    pub name: String,
+    pub url: String,
+    /// Event types to deliver, e.g. `worker_completed`. `*` matches all.
+    pub events: Vec<String>,
+    /// Key for the HMAC-SHA256 signature header. Deliveries are unsigned
+    /// without one.
+    pub secret: Option<String>,
+}
+
+impl std::fmt::Debug for ApiWebhookConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ApiWebhookConfig")
+            .field("name", &self.name)
+            .field("url", &self.url)
+            .field("events", &self.events)
+            .field("secret", &self.secret.as_ref().map(|_| "[REDACTED]"))
+            .finish()
+    }
+}
+
+/// Per-client API request limits. Clients are identified by bearer token
+/// when auth is configured, otherwise by IP address. Zero disables a limit.
+#[derive(Debug, Clone, Copy)]
+pub struct ApiRateLimitConfig {
+    /// Requests per minute across all endpoints.
+    pub requests_per_minute: u32,
+    /// Requests per minute to endpoints that hit the embedding model or an
+    /// LLM (memory search, the memory graph, entity profiles, cortex and
+    /// webchat sends). Counted in addition to the overall limit.
+    pub expensive_requests_per_minute: u32,
+}
+
+impl Default for ApiRateLimitConfig {
+    fn default() -> Self {
+        Self {
+            requests_per_minute: 1200,
+            expensive_requests_per_minute: 60,
+        }
+    }
+}
+
+/// Persisted API event history.
+#[derive(Debug, Clone)]
+pub struct ApiEventHistoryConfig {
+    pub enabled: bool,
+    /// Event types to keep, e.g. `worker_completed`. `*` keeps all.
+    pub events: Vec<String>,
+    /// Events older than this are pruned.
+    pub retention_days: u64,
+    /// The oldest events are pruned past this many.
+    pub max_events: u64,
+}
+
+impl ApiEventHistoryConfig {
+    /// Everything except streaming deltas, typing indicators, and worker
+    /// chatter, which are high-volume and only useful live.
+    pub const DEFAULT_EVENTS: &'static [&'static str] = &[
+        "inbound_message",
+        "outbound_message",
+        "worker_started",
+        "worker_status",
+        "worker_idle",
+        "worker_completed",
+        "branch_started",
+        "branch_completed",
+        "tool_started",
+        "tool_completed",
+        "config_reloaded",
+        "agent_message_sent",
+        "agent_message_received",
+        "task_updated",
+        "cortex_chat_message",
+    ];
+}
+
+/// Settings for the OpenAI-compatible chat completions endpoint.
+#[derive(Debug, Clone, Copy)]
+pub struct ApiOpenAiConfig {
+    pub enabled: bool,
+    /// How long a request waits for the agent to finish its turn.
+    pub reply_timeout_secs: u64,
+}
+
+impl Default for ApiOpenAiConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            reply_timeout_secs: 300,
+        }
+    }
+}
+
+/// Settings for the embeddable website chat widget.
+#[derive(Debug, Clone)]
+pub struct ApiWidgetConfig {
+    pub enabled: bool,
+    /// The agent every widget visitor talks to.
+    pub agent_id: String,
+    /// Page origins allowed to call the widget routes, e.g.
+    /// `https://example.com`. Requests from any other origin are rejected.
+    pub allowed_origins: Vec<String>,
+    /// How long an anonymous visitor session stays valid after it is issued.
+    pub session_ttl_secs: u64,
+    /// Per-visitor request limit across all widget routes.
+    pub requests_per_minute: u32,
+    /// Per-visitor limit on new sessions and sent messages.
+    pub messages_per_minute: u32,
+    /// Longer messages are rejected.
+    pub max_message_chars: usize,
+    /// Shown by the widget before the visitor says anything.
+    pub greeting: Option<String>,
+}
+
+impl ApiWidgetConfig {
+    /// Whether a page on `origin` may use the widget.
+    pub fn allows_origin(&self, origin: &str) -> bool {
+        let origin = origin.trim_end_matches('/');
+        self.allowed_origins
+            .iter()
+            .any(|allowed| allowed == "*" || allowed.eq_ignore_ascii_case(origin))
+    }
+}
+
+impl Default for ApiWidgetConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            agent_id: String::new(),
+            allowed_origins: Vec::new(),
+            session_ttl_secs: 24 * 60 * 60,
+            requests_per_minute: 60,
+            messages_per_minute: 10,
+            max_message_chars: 2000,
+            greeting: None,
+        }
+    }
+}
+
+impl Default for ApiEventHistoryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            events: Self::DEFAULT_EVENTS
+                .iter()
+                .map(|event| event.to_string())
+                .collect(),
+            retention_days: 7,
+            max_events: 100_000,
+        }
+    }
+}
+
+/// Strictness of the `/readyz` probe.
+#[derive(Debug, Clone, Copy)]
+pub struct ReadinessConfig {
+    /// Every agent must be ready. When false, one ready agent is enough.
+    pub require_all_agents: bool,
+    /// An agent is only ready if its channel model's provider has
+    /// credentials and isn't in rate-limit cooldown.
+    pub require_provider: bool,
+}
+
+impl Default for ReadinessConfig {
+    fn default() -> Self {
+        Self {
+            require_all_agents: true,
+            require_provider: true,
+        }
+    }
+}
+
+/// DB-lease leader election across replicas sharing an instance directory.
+#[derive(Debug, Clone, Copy)]
+pub struct LeaderElectionConfig {
+    /// When false, every replica runs singleton duties.
+    pub enabled: bool,
+    /// Lease lifetime in seconds. The holder renews at a third of this.
+    pub lease_secs: u64,
+}
+
+impl Default for LeaderElectionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            lease_secs: 30,
         }
     }
 }
@@ -534,9 +899,24 @@ pub struct DefaultsConfig {
     pub ingestion: IngestionConfig,
     pub cortex: CortexConfig,
     pub warmup: WarmupConfig,
+    pub quotas: QuotaConfig,
     pub browser: BrowserConfig,
     pub channel: ChannelConfig,
     pub mcp: Vec<McpServerConfig>,
+    /// Issue trackers for the `issue_tracker` tool.
+    pub issue_trackers: Vec<IssueTrackerConfig>,
+    /// Weather and geocoding tools.
+    pub weather: WeatherConfig,
+    /// Translation tool and cross-language channel mode.
+    pub translation: TranslationConfig,
+    /// Embedding provider for memory search. Needs a restart to change.
+    pub embedding: EmbeddingConfig,
+    /// Dedicated tokio runtime for the agent. Needs a restart to change.
+    pub runtime: AgentRuntimeConfig,
+    /// Currency rates for the `convert` tool.
+    pub calculator: CalculatorConfig,
+    /// Channel groups for the `broadcast` tool and API.
+    pub broadcast: BroadcastConfig,
     /// Brave Search API key for web search tool. Supports "env:VAR_NAME" references.
     pub brave_search_key: Option<String>,
     /// Default timezone used when evaluating cron active hours.
@@ -567,9 +947,17 @@ impl std::fmt::Debug for DefaultsConfig {
             .field("ingestion", &self.ingestion)
             .field("cortex", &self.cortex)
             .field("warmup", &self.warmup)
+            .field("quotas", &self.quotas)
             .field("browser", &self.browser)
             .field("channel", &self.channel)
             .field("mcp", &self.mcp)
+            .field("issue_trackers", &self.issue_trackers)
+            .field("weather", &self.weather)
+            .field("translation", &self.translation)
+            .field("embedding", &self.embedding)
+            .field("runtime", &self.runtime)
+            .field("calculator", &self.calculator)
+            .field("broadcast", &self.broadcast)
             .field(
                 "brave_search_key",
                 &self.brave_search_key.as_ref().map(|_| "[REDACTED]"),
@@ -599,6 +987,275 @@ impl SystemSecrets for DefaultsConfig {
     }
 }
 
+/// Issue tracker backend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IssueTrackerProvider {
+    Jira,
+    Linear,
+}
+
+/// An issue tracker the agent's `issue_tracker` tool can reach.
+#[derive(Clone, PartialEq, Eq)]
+pub struct IssueTrackerConfig {
+    /// Name the agent uses to pick this tracker.
+    pub name: String,
+    pub provider: IssueTrackerProvider,
+    /// Jira site URL (e.g. `https://acme.atlassian.net`). Linear ignores it.
+    pub base_url: Option<String>,
+    /// Account email for Jira basic auth. Unused by Linear.
+    pub email: Option<String>,
+    pub api_token: String,
+    /// Jira project keys or Linear team keys the agent may touch.
+    pub projects: Vec<String>,
+}
+
+impl std::fmt::Debug for IssueTrackerConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IssueTrackerConfig")
+            .field("name", &self.name)
+            .field("provider", &self.provider)
+            .field("base_url", &self.base_url)
+            .field("email", &self.email.as_ref().map(|_| "[REDACTED]"))
+            .field("api_token", &"[REDACTED]")
+            .field("projects", &self.projects)
+            .finish()
+    }
+}
+
+/// Forecast source for the `weather` tool.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum WeatherProvider {
+    #[default]
+    OpenMeteo,
+}
+
+/// Place-name lookup backend for the `geocode` tool.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum GeocoderProvider {
+    #[default]
+    Nominatim,
+    OpenMeteo,
+}
+
+/// Unit system for forecasts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WeatherUnits {
+    #[default]
+    Metric,
+    Imperial,
+}
+
+/// Weather and geocoding tool configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WeatherConfig {
+    /// Registers the `weather` and `geocode` tools.
+    pub enabled: bool,
+    pub provider: WeatherProvider,
+    pub geocoder: GeocoderProvider,
+    pub units: WeatherUnits,
+    /// How long a fetched forecast is reused before asking the provider again.
+    pub cache_ttl_secs: u64,
+    /// When set, the cortex adds today's forecast for this place to the
+    /// memory bulletin.
+    pub bulletin_location: Option<String>,
+}
+
+impl Default for WeatherConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            provider: WeatherProvider::default(),
+            geocoder: GeocoderProvider::default(),
+            units: WeatherUnits::default(),
+            cache_ttl_secs: 1800,
+            bulletin_location: None,
+        }
+    }
+}
+
+/// Translation backend.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TranslationProvider {
+    /// The agent's own model, routed like a branch.
+    #[default]
+    Llm,
+    Deepl,
+}
+
+/// Translation tool and automatic channel translation.
+#[derive(Clone, PartialEq, Eq)]
+pub struct TranslationConfig {
+    /// Registers the `translate` tool.
+    pub enabled: bool,
+    pub provider: TranslationProvider,
+    /// Required when `provider` is `deepl`. Free-tier keys end in `:fx`.
+    pub deepl_api_key: Option<String>,
+    /// Language the agent thinks and replies in (e.g. `en`, `de`).
+    pub working_language: String,
+    /// Translate inbound channel messages into `working_language` and replies
+    /// back into the user's language. Originals stay in history.
+    pub auto_translate: bool,
+}
+
+impl Default for TranslationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            provider: TranslationProvider::default(),
+            deepl_api_key: None,
+            working_language: "en".to_string(),
+            auto_translate: false,
+        }
+    }
+}
+
+impl std::fmt::Debug for TranslationConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TranslationConfig")
+            .field("enabled", &self.enabled)
+            .field("provider", &self.provider)
+            .field(
+                "deepl_api_key",
+                &self.deepl_api_key.as_ref().map(|_| "[REDACTED]"),
+            )
+            .field("working_language", &self.working_language)
+            .field("auto_translate", &self.auto_translate)
+            .finish()
+    }
+}
+
+/// Embedding backend for memory search.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EmbeddingProviderKind {
+    /// The bundled all-MiniLM-L6-v2 model, run locally through fastembed.
+    #[default]
+    Local,
+    Openai,
+    Voyage,
+}
+
+impl EmbeddingProviderKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Local => "local",
+            Self::Openai => "openai",
+            Self::Voyage => "voyage",
+        }
+    }
+}
+
+/// Which model embeds an agent's memories. Vectors from different models
+/// can't be compared, so the choice is recorded per agent and checked at
+/// startup.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct EmbeddingConfig {
+    pub provider: EmbeddingProviderKind,
+    /// Provider model name. Defaults to the provider's standard model; the
+    /// local provider only has its bundled model.
+    pub model: Option<String>,
+    /// Required for `openai` and `voyage`.
+    pub api_key: Option<String>,
+    /// Override for OpenAI-compatible endpoints.
+    pub base_url: Option<String>,
+    /// Vector size to request from models that support shortening, and the
+    /// size to expect from models whose default isn't known.
+    pub dimensions: Option<usize>,
+}
+
+impl std::fmt::Debug for EmbeddingConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EmbeddingConfig")
+            .field("provider", &self.provider)
+            .field("model", &self.model)
+            .field("api_key", &self.api_key.as_ref().map(|_| "[REDACTED]"))
+            .field("base_url", &self.base_url)
+            .field("dimensions", &self.dimensions)
+            .finish()
+    }
+}
+
+/// Where an agent's tasks run. By default every agent shares the main
+/// runtime; a dedicated runtime keeps one busy agent from adding latency to
+/// the others.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgentRuntimeConfig {
+    /// Run the agent's channels, cortex loops, and cron jobs on their own
+    /// multi-threaded runtime.
+    pub dedicated: bool,
+    /// Worker threads for the dedicated runtime.
+    pub worker_threads: usize,
+    /// CPU cores the dedicated runtime's threads are pinned to. Empty leaves
+    /// scheduling to the OS. Linux only.
+    pub cpu_affinity: Vec<usize>,
+}
+
+impl Default for AgentRuntimeConfig {
+    fn default() -> Self {
+        Self {
+            dedicated: false,
+            worker_threads: 2,
+            cpu_affinity: Vec::new(),
+        }
+    }
+}
+
+/// Exchange-rate source for currency conversion.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CurrencyProvider {
+    /// Frankfurter (European Central Bank reference rates).
+    #[default]
+    Frankfurter,
+}
+
+/// Settings for the `calculate` and `convert` tools.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CalculatorConfig {
+    /// `None` turns currency conversion off; unit conversion still works.
+    pub currency_provider: Option<CurrencyProvider>,
+    /// How long fetched exchange rates are reused.
+    pub rates_cache_ttl_secs: u64,
+}
+
+impl Default for CalculatorConfig {
+    fn default() -> Self {
+        Self {
+            currency_provider: Some(CurrencyProvider::default()),
+            rates_cache_ttl_secs: 3600,
+        }
+    }
+}
+
+/// Announcements fanned out to named groups of channels.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BroadcastConfig {
+    /// Registers the `broadcast` tool. The API works regardless.
+    pub enabled: bool,
+    /// Queue agent broadcasts for approval instead of sending them.
+    pub require_approval: bool,
+    /// Pause between sends, so a group doesn't hit every platform at once.
+    pub stagger_ms: u64,
+    /// Group name to delivery targets, in send order.
+    pub groups: BTreeMap<String, Vec<BroadcastTarget>>,
+}
+
+impl Default for BroadcastConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            require_approval: true,
+            stagger_ms: 1000,
+            groups: BTreeMap::new(),
+        }
+    }
+}
+
 /// MCP server configuration.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct McpServerConfig {
@@ -649,6 +1306,9 @@ pub struct MemoryPersistenceConfig {
     pub enabled: bool,
     /// Number of user messages between automatic memory persistence branches.
     pub message_interval: usize,
+    /// Most memories embedded in one provider call when a persistence branch
+    /// flushes the memories it saved.
+    pub embedding_batch_size: usize,
 }
 
 impl Default for MemoryPersistenceConfig {
@@ -656,6 +1316,7 @@ impl Default for MemoryPersistenceConfig {
         Self {
             enabled: true,
             message_interval: 50,
+            embedding_batch_size: 32,
         }
     }
 }
@@ -796,7 +1457,7 @@ impl Default for BrowserConfig {
 }
 
 /// Channel behavior configuration.
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy)]
 pub struct ChannelConfig {
     /// When true, unsolicited chat messages are ignored unless command/mention/reply.
     pub listen_only_mode: bool,
@@ -804,6 +1465,96 @@ pub struct ChannelConfig {
     /// `workspace/saved/` and tracked in the `saved_attachments` table so
     /// they can be recalled on later turns.
     pub save_attachments: bool,
+    /// What happens to inbound messages while the agent is paused.
+    pub pause_behavior: PauseBehavior,
+    /// Draft replies on the fast model and have the channel model review
+    /// them. Channels can override this with `/draft`.
+    pub speculative_drafts: bool,
+    /// Reviewed replies that differ from the draft by more than this
+    /// fraction of words replace it.
+    pub draft_max_divergence: f64,
+    /// Match inbound questions against the agent's curated Q&A store.
+    pub faq_matching: bool,
+    /// Similarity at or above which a Q&A answer is sent directly, without
+    /// an LLM call.
+    pub faq_answer_threshold: f64,
+    /// Similarity at or above which a Q&A pair is added to the turn's
+    /// context.
+    pub faq_context_threshold: f64,
+    /// Fact-check replies against the agent's memories before sending, and
+    /// what to do with unsupported claims. Channels can override this with
+    /// `/verify`.
+    pub reply_verification: ReplyVerification,
+    /// Record inbound messages, LLM responses, and tool results to the
+    /// channel event log for `spacebot replay`.
+    pub event_log: bool,
+    /// Days of event log to keep. Zero keeps it indefinitely.
+    pub event_log_retention_days: i64,
+}
+
+impl Default for ChannelConfig {
+    fn default() -> Self {
+        Self {
+            listen_only_mode: false,
+            save_attachments: false,
+            pause_behavior: PauseBehavior::default(),
+            speculative_drafts: false,
+            draft_max_divergence: 0.2,
+            faq_matching: false,
+            faq_answer_threshold: 0.92,
+            faq_context_threshold: 0.8,
+            reply_verification: ReplyVerification::default(),
+            event_log: false,
+            event_log_retention_days: 14,
+        }
+    }
+}
+
+/// Handling of inbound messages while an agent is paused.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum PauseBehavior {
+    /// Hold messages and deliver them in order on resume.
+    #[default]
+    Queue,
+    /// Discard messages received while paused.
+    Drop,
+}
+
+/// What the reply tool does with claims its fact-check couldn't support.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReplyVerification {
+    /// Replies aren't checked.
+    #[default]
+    Off,
+    /// Send the reply with a note listing the unsupported claims.
+    Annotate,
+    /// Rewrite the reply to hedge the unsupported claims.
+    Soften,
+    /// Hand the reply back to the channel model to revise once.
+    Regenerate,
+}
+
+impl ReplyVerification {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Annotate => "annotate",
+            Self::Soften => "soften",
+            Self::Regenerate => "regenerate",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "off" => Some(Self::Off),
+            "annotate" => Some(Self::Annotate),
+            "soften" => Some(Self::Soften),
+            "regenerate" => Some(Self::Regenerate),
+            _ => None,
+        }
+    }
 }
 
 /// OpenCode subprocess worker configuration.
@@ -854,14 +1605,46 @@ pub struct CortexConfig {
     pub bulletin_max_turns: usize,
     /// Interval in seconds between memory maintenance passes.
     pub maintenance_interval_secs: u64,
-    /// Per-day decay applied to memory importance during maintenance.
-    pub maintenance_decay_rate: f32,
-    /// Minimum importance score for non-identity memories to avoid pruning.
+    /// Days without access for memory importance to halve, per memory type.
+    pub maintenance_half_life_days: crate::memory::maintenance::DecayHalfLives,
+    /// Importance below which non-identity memories are archived to the trash.
     pub maintenance_prune_threshold: f32,
     /// Minimum age in days before a memory becomes prune-eligible.
     pub maintenance_min_age_days: i64,
     /// Similarity threshold above which memories are merged as near-duplicates.
     pub maintenance_merge_similarity_threshold: f32,
+    /// Days forgotten memories stay in the trash before maintenance purges
+    /// them. Zero keeps them indefinitely.
+    pub maintenance_trash_retention_days: i64,
+    /// Delete memories past their `expires_at` instead of moving them to the
+    /// trash.
+    pub maintenance_delete_expired: bool,
+    /// Days of memory mutation history to keep. Zero keeps it indefinitely.
+    pub maintenance_mutation_retention_days: i64,
+    /// Interval in seconds between importance recalibration passes, which
+    /// fold retrieval, citation, and correction signals into importance.
+    /// Zero disables recalibration.
+    pub recalibration_interval_secs: u64,
+    /// Interval in seconds between consolidation passes, which have the
+    /// compactor model merge clusters of near-duplicate memories. Zero
+    /// disables consolidation.
+    pub consolidation_interval_secs: u64,
+    /// Minimum cosine similarity for memories to share a cluster.
+    pub consolidation_similarity_threshold: f32,
+    /// Max clusters sent to the model per pass.
+    pub consolidation_max_clusters_per_pass: usize,
+    /// Interval in seconds between rescoring passes, which re-evaluate the
+    /// importance of old memories against recent activity. Zero disables
+    /// rescoring.
+    pub rescore_interval_secs: u64,
+    /// Minimum age in days before a memory is rescored.
+    pub rescore_min_age_days: i64,
+    /// Days of activity that count as recent when rescoring.
+    pub rescore_window_days: i64,
+    /// Highest importance an old memory with no recent activity keeps under
+    /// the fallback heuristic. Memories at or below it with no activity are
+    /// not rescored.
+    pub rescore_stale_ceiling: f32,
     /// Interval in seconds between association passes.
     pub association_interval_secs: u64,
     /// Minimum cosine similarity to create a RelatedTo edge.
@@ -885,10 +1668,21 @@ impl Default for CortexConfig {
             bulletin_max_words: 1500,
             bulletin_max_turns: 15,
             maintenance_interval_secs: 3600,
-            maintenance_decay_rate: 0.05,
+            maintenance_half_life_days: crate::memory::maintenance::DecayHalfLives::default(),
             maintenance_prune_threshold: 0.1,
             maintenance_min_age_days: 30,
             maintenance_merge_similarity_threshold: 0.95,
+            maintenance_trash_retention_days: 30,
+            maintenance_delete_expired: false,
+            maintenance_mutation_retention_days: 180,
+            recalibration_interval_secs: 21600,
+            consolidation_interval_secs: 86400,
+            consolidation_similarity_threshold: 0.88,
+            consolidation_max_clusters_per_pass: 20,
+            rescore_interval_secs: 604800,
+            rescore_min_age_days: 30,
+            rescore_window_days: 14,
+            rescore_stale_ceiling: 0.6,
             association_interval_secs: 300,
             association_similarity_threshold: 0.85,
             association_updates_threshold: 0.95,
@@ -900,7 +1694,9 @@ impl Default for CortexConfig {
 impl CortexConfig {
     /// Validate maintenance tuning bounds used by pruning/merge logic.
     pub fn validate_maintenance_bounds(&self) -> Result<()> {
-        validate_unit_interval_f32("maintenance_decay_rate", self.maintenance_decay_rate)?;
+        self.maintenance_half_life_days
+            .validate()
+            .map_err(|error| ConfigError::Invalid(error.to_string()))?;
         validate_unit_interval_f32(
             "maintenance_prune_threshold",
             self.maintenance_prune_threshold,
@@ -909,6 +1705,18 @@ impl CortexConfig {
             "maintenance_merge_similarity_threshold",
             self.maintenance_merge_similarity_threshold,
         )?;
+        validate_unit_interval_f32(
+            "consolidation_similarity_threshold",
+            self.consolidation_similarity_threshold,
+        )?;
+        validate_unit_interval_f32("rescore_stale_ceiling", self.rescore_stale_ceiling)?;
+        if self.rescore_min_age_days < 0 || self.rescore_window_days < 1 {
+            return Err(ConfigError::Invalid(format!(
+                "rescore_min_age_days must be >= 0 and rescore_window_days >= 1, got {} and {}",
+                self.rescore_min_age_days, self.rescore_window_days
+            ))
+            .into());
+        }
         if self.maintenance_min_age_days < 0 {
             return Err(ConfigError::Invalid(format!(
                 "maintenance_min_age_days must be >= 0, got {}",
@@ -916,6 +1724,20 @@ impl CortexConfig {
             ))
             .into());
         }
+        if self.maintenance_trash_retention_days < 0 {
+            return Err(ConfigError::Invalid(format!(
+                "maintenance_trash_retention_days must be >= 0, got {}",
+                self.maintenance_trash_retention_days
+            ))
+            .into());
+        }
+        if self.maintenance_mutation_retention_days < 0 {
+            return Err(ConfigError::Invalid(format!(
+                "maintenance_mutation_retention_days must be >= 0, got {}",
+                self.maintenance_mutation_retention_days
+            ))
+            .into());
+        }
         if self.maintenance_interval_secs == 0 {
             return Err(
                 ConfigError::Invalid("maintenance_interval_secs must be >= 1".to_string()).into(),
@@ -959,6 +1781,29 @@ impl Default for WarmupConfig {
     }
 }
 
+/// Per-agent resource budgets, so one busy agent can't starve the others.
+/// Applied when the agent starts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuotaConfig {
+    /// Branches and workers that may run at once across all of the agent's
+    /// channels. Further spawns wait for a slot.
+    pub max_concurrent_tasks: usize,
+    /// Size of the agent's SQLite connection pool.
+    pub max_db_connections: u32,
+    /// LLM requests that may be in flight at once. Further requests queue.
+    pub max_concurrent_llm_calls: usize,
+}
+
+impl Default for QuotaConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent_tasks: 64,
+            max_db_connections: 10,
+            max_concurrent_llm_calls: 16,
+        }
+    }
+}
+
 /// Projects configuration — agent-level defaults for project workspace management.
 #[derive(Debug, Clone)]
 pub struct ProjectsConfig {
@@ -996,7 +1841,7 @@ impl Default for ProjectsConfig {
 }
 
 /// Current warmup lifecycle state.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum WarmupState {
     Cold,
@@ -1006,7 +1851,7 @@ pub enum WarmupState {
 }
 
 /// Warmup runtime status snapshot for API and observability.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct WarmupStatus {
     pub state: WarmupState,
     pub embedding_ready: bool,
@@ -1125,9 +1970,24 @@ pub struct AgentConfig {
     pub ingestion: Option<IngestionConfig>,
     pub cortex: Option<CortexConfig>,
     pub warmup: Option<WarmupConfig>,
+    pub quotas: Option<QuotaConfig>,
     pub browser: Option<BrowserConfig>,
     pub channel: Option<ChannelConfig>,
     pub mcp: Option<Vec<McpServerConfig>>,
+    /// Per-agent issue trackers. Replaces the defaults list when set.
+    pub issue_trackers: Option<Vec<IssueTrackerConfig>>,
+    /// Per-agent weather settings. None inherits from defaults.
+    pub weather: Option<WeatherConfig>,
+    /// Per-agent translation settings. None inherits from defaults.
+    pub translation: Option<TranslationConfig>,
+    /// Per-agent embedding provider. None inherits from defaults.
+    pub embedding: Option<EmbeddingConfig>,
+    /// Per-agent runtime isolation. None inherits from defaults.
+    pub runtime: Option<AgentRuntimeConfig>,
+    /// Per-agent calculator settings. None inherits from defaults.
+    pub calculator: Option<CalculatorConfig>,
+    /// Per-agent broadcast settings. None inherits from defaults.
+    pub broadcast: Option<BroadcastConfig>,
     /// Per-agent Brave Search API key override. None inherits from defaults.
     pub brave_search_key: Option<String>,
     /// Optional timezone override for cron active-hours evaluation.
@@ -1140,6 +2000,12 @@ pub struct AgentConfig {
     pub projects: Option<ProjectsConfig>,
     /// Cron job definitions for this agent.
     pub cron: Vec<CronDef>,
+    /// Channel-to-scope mapping that keeps memories from leaking between
+    /// platforms or workspaces.
+    pub memory_scopes: crate::memory::scope::MemoryScopes,
+    /// Version identity files and skills in a git repository in the agent
+    /// root, committing on every change.
+    pub workspace_git: bool,
 }
 
 /// A cron job definition from config.
@@ -1160,6 +2026,9 @@ pub struct CronDef {
     /// Maximum wall-clock seconds to wait for the job to complete.
     /// `None` uses the default of 120 seconds.
     pub timeout_secs: Option<u64>,
+    /// IANA timezone for `cron_expr` and active hours. `None` uses the
+    /// agent's `cron_timezone`.
+    pub timezone: Option<String>,
 }
 
 /// Fully resolved agent config (merged with defaults, paths resolved).
@@ -1188,9 +2057,17 @@ pub struct ResolvedAgentConfig {
     pub ingestion: IngestionConfig,
     pub cortex: CortexConfig,
     pub warmup: WarmupConfig,
+    pub quotas: QuotaConfig,
     pub browser: BrowserConfig,
     pub channel: ChannelConfig,
     pub mcp: Vec<McpServerConfig>,
+    pub issue_trackers: Vec<IssueTrackerConfig>,
+    pub weather: WeatherConfig,
+    pub translation: TranslationConfig,
+    pub embedding: EmbeddingConfig,
+    pub runtime: AgentRuntimeConfig,
+    pub calculator: CalculatorConfig,
+    pub broadcast: BroadcastConfig,
     pub brave_search_key: Option<String>,
     pub cron_timezone: Option<String>,
     pub user_timezone: Option<String>,
@@ -1201,6 +2078,8 @@ pub struct ResolvedAgentConfig {
     /// Number of messages to fetch from the platform when a new channel is created.
     pub history_backfill_count: usize,
     pub cron: Vec<CronDef>,
+    pub memory_scopes: crate::memory::scope::MemoryScopes,
+    pub workspace_git: bool,
 }
 
 impl Default for DefaultsConfig {
@@ -1218,9 +2097,17 @@ impl Default for DefaultsConfig {
             ingestion: IngestionConfig::default(),
             cortex: CortexConfig::default(),
             warmup: WarmupConfig::default(),
+            quotas: QuotaConfig::default(),
             browser: BrowserConfig::default(),
             channel: ChannelConfig::default(),
             mcp: Vec::new(),
+            issue_trackers: Vec::new(),
+            weather: WeatherConfig::default(),
+            translation: TranslationConfig::default(),
+            embedding: EmbeddingConfig::default(),
+            runtime: AgentRuntimeConfig::default(),
+            calculator: CalculatorConfig::default(),
+            broadcast: BroadcastConfig::default(),
             brave_search_key: None,
             cron_timezone: None,
             user_timezone: None,
@@ -1283,12 +2170,41 @@ impl AgentConfig {
             ingestion: self.ingestion.unwrap_or(defaults.ingestion),
             cortex: self.cortex.unwrap_or(defaults.cortex),
             warmup: self.warmup.unwrap_or(defaults.warmup),
+            quotas: self.quotas.unwrap_or(defaults.quotas),
             browser: self
                 .browser
                 .clone()
                 .unwrap_or_else(|| defaults.browser.clone()),
             channel: self.channel.unwrap_or(defaults.channel),
             mcp: resolve_mcp_configs(&defaults.mcp, self.mcp.as_deref()),
+            issue_trackers: self
+                .issue_trackers
+                .clone()
+                .unwrap_or_else(|| defaults.issue_trackers.clone()),
+            weather: self
+                .weather
+                .clone()
+                .unwrap_or_else(|| defaults.weather.clone()),
+            translation: self
+                .translation
+                .clone()
+                .unwrap_or_else(|| defaults.translation.clone()),
+            embedding: self
+                .embedding
+                .clone()
+                .unwrap_or_else(|| defaults.embedding.clone()),
+            runtime: self
+                .runtime
+                .clone()
+                .unwrap_or_else(|| defaults.runtime.clone()),
+            calculator: self
+                .calculator
+                .clone()
+                .unwrap_or_else(|| defaults.calculator.clone()),
+            broadcast: self
+                .broadcast
+                .clone()
+                .unwrap_or_else(|| defaults.broadcast.clone()),
             brave_search_key: self
                 .brave_search_key
                 .clone()
@@ -1302,6 +2218,8 @@ impl AgentConfig {
                 .unwrap_or_else(|| defaults.projects.clone()),
             history_backfill_count: defaults.history_backfill_count,
             cron: self.cron.clone(),
+            memory_scopes: self.memory_scopes.clone(),
+            workspace_git: self.workspace_git,
         }
     }
 }
@@ -1670,7 +2588,7 @@ pub(super) struct AdapterValidationState {
 pub(super) fn is_named_adapter_platform(platform: &str) -> bool {
     matches!(
         platform,
-        "discord" | "slack" | "telegram" | "twitch" | "email" | "signal"
+        "discord" | "slack" | "telegram" | "twitch" | "email" | "signal" | "twilio"
     )
 }
 
@@ -1853,6 +2771,22 @@ pub(super) fn build_adapter_validation_states(
         );
     }
 
+    if !messaging.twilio.is_empty() {
+        let default_present = messaging.twilio.iter().any(|number| number.name.is_none());
+        let named_instances = messaging
+            .twilio
+            .iter()
+            .filter_map(|number| number.name.clone())
+            .collect();
+        states.insert(
+            "twilio",
+            AdapterValidationState {
+                default_present,
+                named_instances,
+            },
+        );
+    }
+
     Ok(states)
 }
 
@@ -1974,6 +2908,68 @@ pub struct MessagingConfig {
     pub webhook: Option<WebhookConfig>,
     pub twitch: Option<TwitchConfig>,
     pub signal: Option<SignalConfig>,
+    /// Inbound filters keyed by platform (`discord`) or runtime adapter key
+    /// (`discord:ops`). An adapter key entry replaces its platform's entry.
+    pub filters: HashMap<String, MessageFilterConfig>,
+    /// Out-of-process adapters speaking the external adapter protocol.
+    pub external: Vec<ExternalAdapterConfig>,
+    /// Twilio phone numbers, one adapter each.
+    pub twilio: Vec<TwilioConfig>,
+}
+
+/// An adapter run as a separate program. Its name is its platform name in
+/// bindings and its runtime adapter key.
+#[derive(Clone)]
+pub struct ExternalAdapterConfig {
+    pub name: String,
+    pub enabled: bool,
+    pub transport: ExternalTransport,
+    /// Adapter-specific settings, passed through in `initialize`.
+    pub settings: serde_json::Value,
+}
+
+#[derive(Clone)]
+pub enum ExternalTransport {
+    /// Spacebot launches `command` and talks over its stdin/stdout.
+    Stdio {
+        command: String,
+        args: Vec<String>,
+        env: HashMap<String, String>,
+    },
+    /// Spacebot connects to an adapter already listening at `url`.
+    WebSocket { url: String },
+}
+
+impl std::fmt::Debug for ExternalAdapterConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let transport = match &self.transport {
+            ExternalTransport::Stdio { command, .. } => format!("stdio: {command}"),
+            ExternalTransport::WebSocket { url } => format!("websocket: {url}"),
+        };
+        f.debug_struct("ExternalAdapterConfig")
+            .field("name", &self.name)
+            .field("enabled", &self.enabled)
+            .field("transport", &transport)
+            .field("settings", &"[REDACTED]")
+            .finish()
+    }
+}
+
+/// Adapter-level inbound filter. Applied before binding resolution, so
+/// filtered messages never reach an agent.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MessageFilterConfig {
+    /// Channel IDs the adapter listens in. Empty allows every channel.
+    /// Threads match on their parent channel; DMs are not channel-filtered.
+    pub allow_channels: Vec<String>,
+    /// Channel IDs to ignore. Checked after `allow_channels`.
+    pub deny_channels: Vec<String>,
+    /// User IDs whose messages are ignored everywhere, DMs included.
+    pub deny_users: Vec<String>,
+    /// Drop messages sent by other bots.
+    pub ignore_bots: bool,
+    /// Bot user IDs still accepted when `ignore_bots` is set.
+    pub allowed_bots: Vec<String>,
 }
 
 #[derive(Clone)]
@@ -2222,6 +3218,9 @@ pub struct EmailConfig {
     pub allowed_senders: Vec<String>,
     pub max_body_bytes: usize,
     pub max_attachment_bytes: usize,
+    /// Mail addressed to this address becomes a ready task instead of a
+    /// conversation. The sender gets a reply when the worker finishes.
+    pub task_address: Option<String>,
     pub instances: Vec<EmailInstanceConfig>,
 }
 
@@ -2247,6 +3246,9 @@ pub struct EmailInstanceConfig {
     pub allowed_senders: Vec<String>,
     pub max_body_bytes: usize,
     pub max_attachment_bytes: usize,
+    /// Mail addressed to this address becomes a ready task instead of a
+    /// conversation. The sender gets a reply when the worker finishes.
+    pub task_address: Option<String>,
 }
 
 impl std::fmt::Debug for EmailInstanceConfig {
@@ -2271,6 +3273,10 @@ impl std::fmt::Debug for EmailInstanceConfig {
             .field("allowed_senders", &"[REDACTED]")
             .field("max_body_bytes", &self.max_body_bytes)
             .field("max_attachment_bytes", &self.max_attachment_bytes)
+            .field(
+                "task_address",
+                &self.task_address.as_ref().map(|_| "[REDACTED]"),
+            )
             .finish()
     }
 }
@@ -2296,6 +3302,10 @@ impl std::fmt::Debug for EmailConfig {
             .field("allowed_senders", &"[REDACTED]")
             .field("max_body_bytes", &self.max_body_bytes)
             .field("max_attachment_bytes", &self.max_attachment_bytes)
+            .field(
+                "task_address",
+                &self.task_address.as_ref().map(|_| "[REDACTED]"),
+            )
             .finish()
     }
 }
@@ -2468,6 +3478,64 @@ pub struct WebhookConfig {
     pub auth_token: Option<String>,
 }
 
+/// A Twilio phone number from `[[messaging.twilio]]`.
+///
+/// Texts to the number arrive through Twilio webhooks on an HTTP server the
+/// adapter runs; replies go out through the Twilio REST API. With `voice`
+/// on, calls are answered, recorded, and transcribed into the caller's
+/// channel.
+#[derive(Clone)]
+pub struct TwilioConfig {
+    /// Instance name. Unnamed numbers run as the `twilio` adapter, named
+    /// ones as `twilio:<name>`.
+    pub name: Option<String>,
+    pub enabled: bool,
+    pub account_sid: String,
+    pub auth_token: String,
+    /// E.164 number replies are sent from (e.g. `+15551234567`).
+    pub phone_number: String,
+    /// Public base URL Twilio reaches this adapter at. Webhook signatures
+    /// are checked against it, and status and recording callbacks point at it.
+    pub public_url: String,
+    pub port: u16,
+    pub bind: String,
+    /// E.164 numbers allowed to text or call. Empty allows anyone.
+    pub allowed_numbers: Vec<String>,
+    /// Longest reply in SMS segments (1-10). Longer replies are truncated.
+    pub max_segments: usize,
+    /// Answer calls and transcribe a recorded message into the channel.
+    pub voice: bool,
+    /// Spoken before recording starts.
+    pub voice_greeting: String,
+    pub max_recording_secs: u32,
+}
+
+impl TwilioConfig {
+    /// Runtime adapter key: `twilio` or `twilio:<name>`.
+    pub fn runtime_key(&self) -> String {
+        binding_runtime_adapter_key("twilio", self.name.as_deref())
+    }
+}
+
+impl std::fmt::Debug for TwilioConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TwilioConfig")
+            .field("name", &self.name)
+            .field("enabled", &self.enabled)
+            .field("account_sid", &self.account_sid)
+            .field("auth_token", &"[REDACTED]")
+            .field("phone_number", &self.phone_number)
+            .field("public_url", &self.public_url)
+            .field("port", &self.port)
+            .field("bind", &self.bind)
+            .field("allowed_numbers", &"[REDACTED]")
+            .field("max_segments", &self.max_segments)
+            .field("voice", &self.voice)
+            .field("max_recording_secs", &self.max_recording_secs)
+            .finish()
+    }
+}
+
 /// Signal messaging via signal-cli JSON-RPC daemon.
 ///
 /// Connects to a running `signal-cli daemon --http` instance for sending and
@@ -37,6 +37,7 @@ pub fn spawn_file_watcher(
     llm_manager: Arc<crate::llm::LlmManager>,
     agent_links: Arc<arc_swap::ArcSwap<Vec<crate::links::AgentLink>>>,
     agent_humans: Arc<arc_swap::ArcSwap<Vec<crate::config::HumanDef>>>,
+    config_issues: Arc<arc_swap::ArcSwap<Vec<super::ConfigIssue>>>,
 ) -> tokio::task::JoinHandle<()> {
     use notify::{Event, RecursiveMode, Watcher};
     use std::time::Duration;
@@ -104,6 +105,11 @@ pub fn spawn_file_watcher(
 
         tracing::info!("file watcher started");
 
+        // Record the config.toml in effect at startup so the first edit has
+        // something to diff against and roll back to.
+        let config_history = super::ConfigHistory::new(&instance_dir);
+        record_config_version(&config_history, &config_path, "startup");
+
         // Track config.toml content hash to skip no-op reloads
         let mut last_config_hash: u64 = std::fs::read(&config_path)
             .map(|bytes| {
@@ -157,6 +163,7 @@ pub fn spawn_file_watcher(
                     }
                 } else {
                     last_config_hash = current_hash;
+                    record_config_version(&config_history, &config_path, "file_change");
                 }
             }
 
@@ -177,7 +184,13 @@ pub fn spawn_file_watcher(
             // Reload config.toml if it changed
             let new_config = if config_changed {
                 match Config::load_from_path(&config_path) {
-                    Ok(config) => Some(config),
+                    Ok(config) => {
+                        for issue in &config.issues {
+                            issue.log();
+                        }
+                        config_issues.store(Arc::new(config.unresolved_issues()));
+                        Some(config)
+                    }
                     Err(error) => {
                         tracing::error!(%error, "failed to reload config.toml, keeping previous values");
                         None
@@ -249,6 +262,10 @@ pub fn spawn_file_watcher(
                     tracing::info!("signal permissions reloaded");
                 }
 
+                if let Some(ref manager) = messaging_manager {
+                    manager.set_filters(config.messaging.filters.clone());
+                }
+
                 // Hot-start adapters that are newly enabled in the config
                 if let Some(ref manager) = messaging_manager {
                     let rt = tokio::runtime::Handle::current();
@@ -628,3 +645,26 @@ pub fn spawn_file_watcher(
         tracing::info!("file watcher stopped");
     })
 }
+
+/// Add the current config.toml to the config history. Failures are logged:
+/// history is best-effort and never blocks a reload.
+fn record_config_version(
+    history: &super::ConfigHistory,
+    config_path: &std::path::Path,
+    source: &str,
+) {
+    let content = match std::fs::read_to_string(config_path) {
+        Ok(content) => content,
+        Err(error) => {
+            tracing::debug!(%error, "config.toml not readable, skipping config history");
+            return;
+        }
+    };
+    match history.record(&content, source) {
+        Ok(Some(version)) => {
+            tracing::debug!(hash = %version.hash, source, "config version recorded")
+        }
+        Ok(None) => {}
+        Err(error) => tracing::warn!(%error, "failed to record config history"),
+    }
+}
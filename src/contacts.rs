@@ -0,0 +1,587 @@
+//! Contact book: people the agent can reach, and how.
+//!
+//! A contact has a name, aliases, email addresses, notes, and platform
+//! handles (a Discord user ID, a Signal number). Handles double as identity
+//! links: a message from a linked handle is attributed to the contact, and an
+//! identity belongs to at most one contact. A contact can also point at an
+//! org-level human (`[[humans]]`), whose platform IDs count as its handles.
+//!
+//! [`delivery_targets`] turns a contact into broadcast targets, preferred
+//! first; senders pick the first one whose adapter is running.
+
+use crate::config::HumanDef;
+use crate::error::Result;
+use crate::messaging::MessagingManager;
+use crate::messaging::target::{BroadcastTarget, normalize_target};
+
+use anyhow::{Context as _, anyhow};
+use serde::{Deserialize, Serialize};
+use sqlx::{Row as _, SqlitePool};
+
+/// A platform identity linked to a contact.
+#[derive(
+    Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema, schemars::JsonSchema,
+)]
+pub struct ContactHandle {
+    /// Adapter name: `discord`, `slack`, `telegram`, `signal`, `email`, or a
+    /// named instance like `signal:work`.
+    pub platform: String,
+    /// Platform user ID, phone number, or address.
+    pub handle: String,
+}
+
+impl ContactHandle {
+    fn normalized(&self) -> Self {
+        let platform = self.platform.trim().to_lowercase();
+        let handle = self.handle.trim();
+        let handle = if platform == "email" {
+            handle.to_lowercase()
+        } else {
+            handle.to_string()
+        };
+        Self { platform, handle }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct Contact {
+    pub id: String,
+    pub name: String,
+    pub aliases: Vec<String>,
+    /// Preferred first.
+    pub emails: Vec<String>,
+    pub notes: Option<String>,
+    /// Linked `[[humans]]` entry.
+    pub human_id: Option<String>,
+    /// Preferred first.
+    pub handles: Vec<ContactHandle>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl Contact {
+    /// Whether `name` is this contact's name or one of its aliases.
+    pub fn is_named(&self, name: &str) -> bool {
+        let name = name.trim();
+        self.name.eq_ignore_ascii_case(name)
+            || self
+                .aliases
+                .iter()
+                .any(|alias| alias.eq_ignore_ascii_case(name))
+    }
+}
+
+/// Fields for a new contact.
+#[derive(Debug, Clone, Default, Deserialize, utoipa::ToSchema, schemars::JsonSchema)]
+pub struct NewContact {
+    pub name: String,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    #[serde(default)]
+    pub emails: Vec<String>,
+    #[serde(default)]
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub human_id: Option<String>,
+    #[serde(default)]
+    pub handles: Vec<ContactHandle>,
+}
+
+/// Changes to a contact. Omitted fields are left alone; lists replace the
+/// stored list, and an empty `notes` or `human_id` clears it.
+#[derive(Debug, Clone, Default, Deserialize, utoipa::ToSchema, schemars::JsonSchema)]
+pub struct ContactUpdate {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub aliases: Option<Vec<String>>,
+    #[serde(default)]
+    pub emails: Option<Vec<String>>,
+    #[serde(default)]
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub human_id: Option<String>,
+    #[serde(default)]
+    pub handles: Option<Vec<ContactHandle>>,
+}
+
+const COLUMNS: &str = "id, name, aliases, emails, notes, human_id, created_at, updated_at";
+
+#[derive(Debug, Clone)]
+pub struct ContactStore {
+    pool: SqlitePool,
+}
+
+impl ContactStore {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn create(&self, contact: NewContact) -> Result<Contact> {
+        let name = contact.name.trim();
+        if name.is_empty() {
+            return Err(anyhow!("contact name is empty").into());
+        }
+        let id = uuid::Uuid::new_v4().to_string();
+
+        let mut transaction = self.pool.begin().await.context("failed to begin")?;
+        sqlx::query(
+            "INSERT INTO contacts (id, name, aliases, emails, notes, human_id) \
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&id)
+        .bind(name)
+        .bind(json_list(&contact.aliases))
+        .bind(json_list(&normalize_emails(&contact.emails)))
+        .bind(non_empty(contact.notes))
+        .bind(non_empty(contact.human_id))
+        .execute(&mut *transaction)
+        .await
+        .context("failed to insert contact")?;
+        replace_handles(&mut transaction, &id, &contact.handles).await?;
+        transaction
+            .commit()
+            .await
+            .context("failed to commit contact")?;
+
+        self.get(&id)
+            .await?
+            .context("contact inserted but not found")
+            .map_err(Into::into)
+    }
+
+    pub async fn get(&self, id: &str) -> Result<Option<Contact>> {
+        let row = sqlx::query(&format!("SELECT {COLUMNS} FROM contacts WHERE id = ?"))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .context("failed to load contact")?;
+        match row {
+            Some(row) => Ok(Some(self.with_handles(&row).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Contacts by name, optionally filtered by a case-insensitive match on
+    /// name, aliases, emails, notes, or handles.
+    pub async fn list(&self, query: Option<&str>, limit: i64) -> Result<Vec<Contact>> {
+        let pattern = query
+            .map(str::trim)
+            .filter(|query| !query.is_empty())
+            .map(|query| format!("%{}%", escape_like(query)));
+        let rows = sqlx::query(&format!(
+            "SELECT {COLUMNS} FROM contacts \
+             WHERE ?1 IS NULL \
+                OR name LIKE ?1 ESCAPE '\\' OR aliases LIKE ?1 ESCAPE '\\' \
+                OR emails LIKE ?1 ESCAPE '\\' OR notes LIKE ?1 ESCAPE '\\' \
+                OR EXISTS (SELECT 1 FROM contact_handles \
+                           WHERE contact_id = contacts.id AND handle LIKE ?1 ESCAPE '\\') \
+             ORDER BY name COLLATE NOCASE, id LIMIT ?2"
+        ))
+        .bind(pattern)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .context("failed to list contacts")?;
+
+        let mut contacts = Vec::with_capacity(rows.len());
+        for row in &rows {
+            contacts.push(self.with_handles(row).await?);
+        }
+        Ok(contacts)
+    }
+
+    /// Contacts a name refers to: exact name or alias matches when there are
+    /// any, otherwise names and aliases containing it. Notes and handles
+    /// don't count, so a passing mention never picks a recipient.
+    pub async fn resolve(&self, name: &str) -> Result<Vec<Contact>> {
+        let needle = name.trim().to_lowercase();
+        let candidates: Vec<Contact> = self
+            .list(Some(name), 50)
+            .await?
+            .into_iter()
+            .filter(|contact| {
+                std::iter::once(&contact.name)
+                    .chain(&contact.aliases)
+                    .any(|candidate| candidate.to_lowercase().contains(&needle))
+            })
+            .collect();
+        let exact: Vec<Contact> = candidates
+            .iter()
+            .filter(|contact| contact.is_named(name))
+            .cloned()
+            .collect();
+        Ok(if exact.is_empty() { candidates } else { exact })
+    }
+
+    /// The contact a platform identity is linked to.
+    pub async fn find_by_handle(&self, platform: &str, handle: &str) -> Result<Option<Contact>> {
+        let handle = ContactHandle {
+            platform: platform.to_string(),
+            handle: handle.to_string(),
+        }
+        .normalized();
+        let contact_id: Option<String> = sqlx::query_scalar(
+            "SELECT contact_id FROM contact_handles WHERE platform = ? AND handle = ?",
+        )
+        .bind(&handle.platform)
+        .bind(&handle.handle)
+        .fetch_optional(&self.pool)
+        .await
+        .context("failed to look up contact handle")?;
+        match contact_id {
+            Some(id) => self.get(&id).await,
+            None => Ok(None),
+        }
+    }
+
+    /// Apply `update`. `None` when the contact doesn't exist.
+    pub async fn update(&self, id: &str, update: ContactUpdate) -> Result<Option<Contact>> {
+        let Some(current) = self.get(id).await? else {
+            return Ok(None);
+        };
+        let name = update
+            .name
+            .as_deref()
+            .map(str::trim)
+            .unwrap_or(&current.name);
+        if name.is_empty() {
+            return Err(anyhow!("contact name is empty").into());
+        }
+        let aliases = update.aliases.unwrap_or(current.aliases);
+        let emails = update
+            .emails
+            .map(|emails| normalize_emails(&emails))
+            .unwrap_or(current.emails);
+        let notes = match update.notes {
+            Some(notes) => non_empty(Some(notes)),
+            None => current.notes,
+        };
+        let human_id = match update.human_id {
+            Some(human_id) => non_empty(Some(human_id)),
+            None => current.human_id,
+        };
+
+        let mut transaction = self.pool.begin().await.context("failed to begin")?;
+        sqlx::query(
+            "UPDATE contacts SET name = ?, aliases = ?, emails = ?, notes = ?, human_id = ?, \
+             updated_at = CURRENT_TIMESTAMP WHERE id = ?",
+        )
+        .bind(name)
+        .bind(json_list(&aliases))
+        .bind(json_list(&emails))
+        .bind(notes)
+        .bind(human_id)
+        .bind(id)
+        .execute(&mut *transaction)
+        .await
+        .context("failed to update contact")?;
+        if let Some(handles) = &update.handles {
+            replace_handles(&mut transaction, id, handles).await?;
+        }
+        transaction
+            .commit()
+            .await
+            .context("failed to commit contact")?;
+
+        self.get(id).await
+    }
+
+    /// Delete a contact and its handles. `false` when it didn't exist.
+    pub async fn delete(&self, id: &str) -> Result<bool> {
+        let mut transaction = self.pool.begin().await.context("failed to begin")?;
+        sqlx::query("DELETE FROM contact_handles WHERE contact_id = ?")
+            .bind(id)
+            .execute(&mut *transaction)
+            .await
+            .context("failed to delete contact handles")?;
+        let deleted = sqlx::query("DELETE FROM contacts WHERE id = ?")
+            .bind(id)
+            .execute(&mut *transaction)
+            .await
+            .context("failed to delete contact")?;
+        transaction
+            .commit()
+            .await
+            .context("failed to commit contact deletion")?;
+        Ok(deleted.rows_affected() > 0)
+    }
+
+    async fn with_handles(&self, row: &sqlx::sqlite::SqliteRow) -> Result<Contact> {
+        let mut contact = row_to_contact(row);
+        let rows = sqlx::query(
+            "SELECT platform, handle FROM contact_handles WHERE contact_id = ? \
+             ORDER BY position, platform, handle",
+        )
+        .bind(&contact.id)
+        .fetch_all(&self.pool)
+        .await
+        .context("failed to load contact handles")?;
+        contact.handles = rows
+            .iter()
+            .map(|row| ContactHandle {
+                platform: row.try_get("platform").unwrap_or_default(),
+                handle: row.try_get("handle").unwrap_or_default(),
+            })
+            .collect();
+        Ok(contact)
+    }
+}
+
+/// Replace a contact's handles. A handle linked to another contact moves to
+/// this one.
+async fn replace_handles(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    contact_id: &str,
+    handles: &[ContactHandle],
+) -> Result<()> {
+    sqlx::query("DELETE FROM contact_handles WHERE contact_id = ?")
+        .bind(contact_id)
+        .execute(&mut **transaction)
+        .await
+        .context("failed to clear contact handles")?;
+
+    for (position, handle) in handles.iter().enumerate() {
+        let handle = handle.normalized();
+        if handle.platform.is_empty() || handle.handle.is_empty() {
+            return Err(anyhow!("contact handles need a platform and a handle").into());
+        }
+        sqlx::query(
+            "INSERT INTO contact_handles (contact_id, platform, handle, position) \
+             VALUES (?, ?, ?, ?) \
+             ON CONFLICT (platform, handle) \
+             DO UPDATE SET contact_id = excluded.contact_id, position = excluded.position",
+        )
+        .bind(contact_id)
+        .bind(&handle.platform)
+        .bind(&handle.handle)
+        .bind(position as i64)
+        .execute(&mut **transaction)
+        .await
+        .context("failed to link contact handle")?;
+    }
+    Ok(())
+}
+
+/// Where a contact can be messaged, preferred first: its handles, then its
+/// email addresses, then the platform IDs of its linked human. Handles on
+/// platforms without direct messages (Twitch, webchat) are skipped.
+pub fn delivery_targets(contact: &Contact, humans: &[HumanDef]) -> Vec<BroadcastTarget> {
+    let mut handles = contact.handles.clone();
+    handles.extend(contact.emails.iter().map(|email| ContactHandle {
+        platform: "email".into(),
+        handle: email.clone(),
+    }));
+    if let Some(human) = contact
+        .human_id
+        .as_deref()
+        .and_then(|id| humans.iter().find(|human| human.id == id))
+    {
+        handles.extend(human_handles(human));
+    }
+
+    let mut targets: Vec<BroadcastTarget> = Vec::new();
+    for target in handles.iter().filter_map(handle_target) {
+        if !targets.contains(&target) {
+            targets.push(target);
+        }
+    }
+    targets
+}
+
+/// The first of `targets` whose adapter is running.
+pub async fn first_deliverable(
+    targets: Vec<BroadcastTarget>,
+    messaging_manager: &MessagingManager,
+) -> Option<BroadcastTarget> {
+    for target in targets {
+        if messaging_manager.has_adapter(&target.adapter).await {
+            return Some(target);
+        }
+    }
+    None
+}
+
+/// The platform IDs configured on an org-level human.
+pub fn human_handles(human: &HumanDef) -> Vec<ContactHandle> {
+    [
+        ("discord", &human.discord_id),
+        ("slack", &human.slack_id),
+        ("telegram", &human.telegram_id),
+        ("email", &human.email),
+    ]
+    .into_iter()
+    .filter_map(|(platform, handle)| {
+        handle
+            .as_deref()
+            .filter(|handle| !handle.trim().is_empty())
+            .map(|handle| ContactHandle {
+                platform: platform.into(),
+                handle: handle.into(),
+            })
+    })
+    .collect()
+}
+
+/// Direct-message targets for an org-level human's platform IDs.
+pub fn human_targets(human: &HumanDef) -> Vec<BroadcastTarget> {
+    human_handles(human)
+        .iter()
+        .filter_map(handle_target)
+        .collect()
+}
+
+/// A direct-message target for one handle.
+fn handle_target(handle: &ContactHandle) -> Option<BroadcastTarget> {
+    let handle = handle.normalized();
+    let platform = handle
+        .platform
+        .split(':')
+        .next()
+        .unwrap_or(&handle.platform);
+    let raw_target = match platform {
+        "discord" | "slack" => format!("dm:{}", handle.handle),
+        "telegram" | "signal" | "email" => handle.handle.clone(),
+        _ => return None,
+    };
+    normalize_target(platform, &raw_target).map(|target| BroadcastTarget {
+        adapter: handle.platform.clone(),
+        target,
+    })
+}
+
+fn row_to_contact(row: &sqlx::sqlite::SqliteRow) -> Contact {
+    let aliases: String = row.try_get("aliases").unwrap_or_default();
+    let emails: String = row.try_get("emails").unwrap_or_default();
+    Contact {
+        id: row.try_get("id").unwrap_or_default(),
+        name: row.try_get("name").unwrap_or_default(),
+        aliases: serde_json::from_str(&aliases).unwrap_or_default(),
+        emails: serde_json::from_str(&emails).unwrap_or_default(),
+        notes: row.try_get("notes").ok().flatten(),
+        human_id: row.try_get("human_id").ok().flatten(),
+        handles: Vec::new(),
+        created_at: row.try_get("created_at").unwrap_or_default(),
+        updated_at: row.try_get("updated_at").unwrap_or_default(),
+    }
+}
+
+fn json_list(values: &[String]) -> String {
+    let values: Vec<&str> = values
+        .iter()
+        .map(|value| value.trim())
+        .filter(|value| !value.is_empty())
+        .collect();
+    serde_json::to_string(&values).unwrap_or_else(|_| "[]".into())
+}
+
+fn normalize_emails(emails: &[String]) -> Vec<String> {
+    emails
+        .iter()
+        .map(|email| email.trim().to_lowercase())
+        .filter(|email| !email.is_empty())
+        .collect()
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn names_resolve_to_deliverable_targets() {
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("failed to create sqlite memory pool");
+        sqlx::migrate!("./migrations")
+            .run(&pool)
+            .await
+            .expect("migrations");
+        let store = ContactStore::new(pool);
+
+        let alex = store
+            .create(NewContact {
+                name: "Alex Kim".into(),
+                aliases: vec!["Alex".into()],
+                emails: vec!["Alex@Example.com".into()],
+                human_id: Some("alex".into()),
+                handles: vec![
+                    ContactHandle {
+                        platform: "Discord".into(),
+                        handle: "1234".into(),
+                    },
+                    ContactHandle {
+                        platform: "twitch".into(),
+                        handle: "alexk".into(),
+                    },
+                ],
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        store
+            .create(NewContact {
+                name: "Alexandra".into(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        let matches = store.resolve("alex").await.unwrap();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].id, alex.id);
+        assert_eq!(store.resolve("ale").await.unwrap().len(), 2);
+        assert_eq!(
+            store
+                .find_by_handle("discord", "1234")
+                .await
+                .unwrap()
+                .map(|contact| contact.id),
+            Some(alex.id.clone())
+        );
+
+        let human = HumanDef {
+            id: "alex".into(),
+            display_name: None,
+            role: None,
+            bio: None,
+            description: None,
+            discord_id: Some("1234".into()),
+            telegram_id: Some("99".into()),
+            slack_id: None,
+            email: None,
+            notifications: None,
+        };
+        let targets: Vec<String> = delivery_targets(&alex, &[human])
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            targets,
+            ["discord:dm:1234", "email:alex@example.com", "telegram:99"]
+        );
+
+        assert!(store.delete(&alex.id).await.unwrap());
+        assert!(
+            store
+                .find_by_handle("discord", "1234")
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+}
@@ -3,10 +3,14 @@
 pub mod channels;
 pub mod context;
 pub mod history;
+pub mod shares;
+pub mod widget_sessions;
 pub mod worker_transcript;
 
-pub use channels::ChannelStore;
+pub use channels::{ChannelLookupPolicy, ChannelStore};
 pub use history::{
     ConversationLogger, ProcessRunLogger, TimelineItem, WorkerDetailRow, WorkerRunRow,
 };
+pub use shares::{ChannelShare, ShareStore};
+pub use widget_sessions::{WidgetSession, WidgetSessionStore};
 pub use worker_transcript::{ActionContent, TranscriptStep};
@@ -25,6 +25,59 @@ pub struct ChannelInfo {
     pub last_activity_at: chrono::DateTime<chrono::Utc>,
 }
 
+/// Rows removed (or that would be removed) by [`ChannelStore::purge`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, utoipa::ToSchema)]
+pub struct ChannelPurgeCounts {
+    pub messages: u64,
+    pub branch_runs: u64,
+    pub worker_runs: u64,
+}
+
+impl ChannelPurgeCounts {
+    pub fn total(&self) -> u64 {
+        self.messages + self.branch_runs + self.worker_runs
+    }
+}
+
+/// Whether other channels may look into a channel's history with the
+/// `lookup_channel` tool.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    serde::Serialize,
+    serde::Deserialize,
+    utoipa::ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ChannelLookupPolicy {
+    /// Any channel the memory scopes allow may pull excerpts.
+    #[default]
+    Open,
+    /// History never leaves the channel.
+    Private,
+}
+
+impl ChannelLookupPolicy {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ChannelLookupPolicy::Open => "open",
+            ChannelLookupPolicy::Private => "private",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "open" => Some(ChannelLookupPolicy::Open),
+            "private" => Some(ChannelLookupPolicy::Private),
+            _ => None,
+        }
+    }
+}
+
 impl ChannelStore {
     pub fn new(pool: SqlitePool) -> Self {
         Self { pool }
@@ -218,6 +271,47 @@ impl ChannelStore {
         Ok(result.rows_affected() > 0)
     }
 
+    /// Delete a channel's messages and branch/worker runs, keeping the
+    /// channel itself. With `dry_run` nothing is deleted and the counts are
+    /// what would have been. Returns `None` if the channel is unknown.
+    pub async fn purge(
+        &self,
+        channel_id: &str,
+        dry_run: bool,
+    ) -> crate::error::Result<Option<ChannelPurgeCounts>> {
+        if self.get(channel_id).await?.is_none() {
+            return Ok(None);
+        }
+
+        let mut tx = self.pool.begin().await.map_err(|e| anyhow::anyhow!(e))?;
+        let mut counts = ChannelPurgeCounts::default();
+        for (table, count) in [
+            ("conversation_messages", &mut counts.messages),
+            ("branch_runs", &mut counts.branch_runs),
+            ("worker_runs", &mut counts.worker_runs),
+        ] {
+            *count = if dry_run {
+                sqlx::query_scalar::<_, i64>(&format!(
+                    "SELECT COUNT(*) FROM {table} WHERE channel_id = ?"
+                ))
+                .bind(channel_id)
+                .fetch_one(&mut *tx)
+                .await
+                .map_err(|e| anyhow::anyhow!(e))? as u64
+            } else {
+                sqlx::query(&format!("DELETE FROM {table} WHERE channel_id = ?"))
+                    .bind(channel_id)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(|e| anyhow::anyhow!(e))?
+                    .rows_affected()
+            };
+        }
+        tx.commit().await.map_err(|e| anyhow::anyhow!(e))?;
+
+        Ok(Some(counts))
+    }
+
     /// Set active/archive state for a channel.
     pub async fn set_active(&self, channel_id: &str, active: bool) -> crate::error::Result<bool> {
         let result = sqlx::query("UPDATE channels SET is_active = ? WHERE id = ?")
@@ -229,6 +323,63 @@ impl ChannelStore {
 
         Ok(result.rows_affected() > 0)
     }
+
+    /// Whether a channel is muted. Unknown channels aren't.
+    pub async fn is_muted(&self, channel_id: &str) -> crate::error::Result<bool> {
+        let muted: Option<i64> = sqlx::query_scalar("SELECT muted FROM channels WHERE id = ?")
+            .bind(channel_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| anyhow::anyhow!(e))?;
+
+        Ok(muted.is_some_and(|muted| muted != 0))
+    }
+
+    /// Mute or unmute a channel.
+    pub async fn set_muted(&self, channel_id: &str, muted: bool) -> crate::error::Result<bool> {
+        let result = sqlx::query("UPDATE channels SET muted = ? WHERE id = ?")
+            .bind(if muted { 1_i64 } else { 0_i64 })
+            .bind(channel_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| anyhow::anyhow!(e))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// A channel's lookup policy. Unknown channels are treated as private.
+    pub async fn lookup_policy(
+        &self,
+        channel_id: &str,
+    ) -> crate::error::Result<ChannelLookupPolicy> {
+        let policy: Option<String> =
+            sqlx::query_scalar("SELECT lookup_policy FROM channels WHERE id = ?")
+                .bind(channel_id)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(|e| anyhow::anyhow!(e))?;
+
+        Ok(match policy {
+            Some(policy) => ChannelLookupPolicy::parse(&policy).unwrap_or_default(),
+            None => ChannelLookupPolicy::Private,
+        })
+    }
+
+    /// Set whether other channels may look into this one.
+    pub async fn set_lookup_policy(
+        &self,
+        channel_id: &str,
+        policy: ChannelLookupPolicy,
+    ) -> crate::error::Result<bool> {
+        let result = sqlx::query("UPDATE channels SET lookup_policy = ? WHERE id = ?")
+            .bind(policy.as_str())
+            .bind(channel_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| anyhow::anyhow!(e))?;
+
+        Ok(result.rows_affected() > 0)
+    }
 }
 
 fn row_to_channel_info(row: sqlx::sqlite::SqliteRow) -> ChannelInfo {
@@ -459,4 +610,53 @@ mod tests {
             .expect("channel should still exist");
         assert!(channel.is_active);
     }
+
+    #[tokio::test]
+    async fn purge_dry_run_counts_without_deleting() {
+        let store = setup_store().await;
+        for ddl in [
+            "CREATE TABLE conversation_messages (id TEXT PRIMARY KEY, channel_id TEXT NOT NULL)",
+            "CREATE TABLE branch_runs (id TEXT PRIMARY KEY, channel_id TEXT NOT NULL)",
+            "CREATE TABLE worker_runs (id TEXT PRIMARY KEY, channel_id TEXT)",
+            "INSERT INTO channels (id, platform) VALUES ('chan-1', 'portal')",
+            "INSERT INTO conversation_messages VALUES ('m1', 'chan-1'), ('m2', 'chan-1'), ('m3', 'chan-2')",
+            "INSERT INTO worker_runs VALUES ('w1', 'chan-1')",
+        ] {
+            sqlx::query(ddl)
+                .execute(&store.pool)
+                .await
+                .expect("fixture should apply");
+        }
+
+        let counts = store
+            .purge("chan-1", true)
+            .await
+            .expect("dry run should succeed")
+            .expect("channel should exist");
+        assert_eq!(counts.messages, 2);
+        assert_eq!(counts.worker_runs, 1);
+        assert_eq!(counts.total(), 3);
+
+        let purged = store
+            .purge("chan-1", false)
+            .await
+            .expect("purge should succeed")
+            .expect("channel should exist");
+        assert_eq!(purged, counts);
+
+        let remaining = store
+            .purge("chan-1", true)
+            .await
+            .expect("dry run should succeed")
+            .expect("channel should still exist");
+        assert_eq!(remaining.total(), 0);
+
+        assert!(
+            store
+                .purge("missing", true)
+                .await
+                .expect("dry run should succeed")
+                .is_none()
+        );
+    }
 }
@@ -107,22 +107,56 @@ impl ConversationLogger {
         channel_id: &ChannelId,
         content: &str,
         sender_name: Option<&str>,
+    ) {
+        self.log_bot_message_with_metadata(channel_id, content, sender_name, &HashMap::new());
+    }
+
+    /// Log a bot (assistant) message with extra metadata, e.g. the
+    /// translation that was actually sent. Fire-and-forget.
+    pub fn log_bot_message_with_metadata(
+        &self,
+        channel_id: &ChannelId,
+        content: &str,
+        sender_name: Option<&str>,
+        metadata: &HashMap<String, serde_json::Value>,
+    ) {
+        self.log_bot_message_with_id(
+            uuid::Uuid::new_v4().to_string(),
+            channel_id,
+            content,
+            sender_name,
+            metadata,
+        );
+    }
+
+    /// Log a bot (assistant) message under a caller-chosen ID, e.g. one the
+    /// outbox already records delivery status under. Fire-and-forget.
+    pub fn log_bot_message_with_id(
+        &self,
+        id: String,
+        channel_id: &ChannelId,
+        content: &str,
+        sender_name: Option<&str>,
+        metadata: &HashMap<String, serde_json::Value>,
     ) {
         let pool = self.pool.clone();
-        let id = uuid::Uuid::new_v4().to_string();
         let channel_id = channel_id.to_string();
         let content = content.to_string();
         let sender_name = sender_name.map(String::from);
+        let metadata_json = (!metadata.is_empty())
+            .then(|| serde_json::to_string(metadata).ok())
+            .flatten();
 
         tokio::spawn(async move {
             if let Err(error) = sqlx::query(
-                "INSERT INTO conversation_messages (id, channel_id, role, sender_name, content) \
-                 VALUES (?, ?, 'assistant', ?, ?)",
+                "INSERT INTO conversation_messages (id, channel_id, role, sender_name, content, metadata) \
+                 VALUES (?, ?, 'assistant', ?, ?, ?)",
             )
             .bind(&id)
             .bind(&channel_id)
             .bind(&sender_name)
             .bind(&content)
+            .bind(&metadata_json)
             .execute(&pool)
             .await
             {
@@ -137,6 +171,9 @@ impl ConversationLogger {
         channel_id: &ChannelId,
         limit: i64,
     ) -> crate::error::Result<Vec<ConversationMessage>> {
+        #[cfg(feature = "chaos")]
+        crate::chaos::sqlite_fault().map_err(|e| anyhow::anyhow!(e))?;
+
         let rows = sqlx::query(
             "SELECT id, channel_id, role, sender_name, sender_id, content, metadata, created_at \
              FROM conversation_messages \
@@ -172,6 +209,33 @@ impl ConversationLogger {
         Ok(messages)
     }
 
+    /// Load the messages among `ids` that were flagged important when sent,
+    /// oldest first. IDs from another agent's history are ignored.
+    pub async fn load_important(
+        &self,
+        ids: &[String],
+    ) -> crate::error::Result<Vec<ConversationMessage>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let placeholders = ids.iter().map(|_| "?").collect::<Vec<_>>().join(",");
+        let sql = format!(
+            "SELECT id, channel_id, role, sender_name, sender_id, content, metadata, created_at \
+             FROM conversation_messages \
+             WHERE id IN ({placeholders}) AND json_extract(metadata, '$.important') = 1 \
+             ORDER BY created_at ASC"
+        );
+        let mut query = sqlx::query(&sql);
+        for id in ids {
+            query = query.bind(id);
+        }
+        let rows = query
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| anyhow::anyhow!(e))?;
+        Ok(rows.into_iter().map(row_to_message).collect())
+    }
+
     /// Load messages from any channel (not just the current one).
     ///
     /// Supports optional temporal filtering via `before` and `after` (RFC 3339 strings)
@@ -241,6 +305,200 @@ impl ConversationLogger {
         }
         Ok(messages)
     }
+
+    /// Messages in any channel whose content mentions `term`
+    /// (case-insensitive), newest first.
+    pub async fn find_mentions(
+        &self,
+        term: &str,
+        limit: i64,
+    ) -> crate::error::Result<Vec<ConversationMessage>> {
+        let rows = sqlx::query(
+            "SELECT id, channel_id, role, sender_name, sender_id, content, metadata, created_at \
+             FROM conversation_messages \
+             WHERE content LIKE ? ESCAPE '\\' \
+             ORDER BY created_at DESC \
+             LIMIT ?",
+        )
+        .bind(crate::memory::store::like_pattern(term))
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| anyhow::anyhow!(e))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| ConversationMessage {
+                id: row.try_get("id").unwrap_or_default(),
+                channel_id: row.try_get("channel_id").unwrap_or_default(),
+                role: row.try_get("role").unwrap_or_default(),
+                sender_name: row.try_get("sender_name").ok(),
+                sender_id: row.try_get("sender_id").ok(),
+                content: row.try_get("content").unwrap_or_default(),
+                metadata: row.try_get("metadata").ok(),
+                created_at: row
+                    .try_get("created_at")
+                    .unwrap_or_else(|_| chrono::Utc::now()),
+            })
+            .collect())
+    }
+
+    /// User and assistant messages in one channel whose content mentions
+    /// `term` (case-insensitive), newest first.
+    pub async fn search_channel(
+        &self,
+        channel_id: &str,
+        term: &str,
+        limit: i64,
+    ) -> crate::error::Result<Vec<ConversationMessage>> {
+        let rows = sqlx::query(
+            "SELECT id, channel_id, role, sender_name, sender_id, content, metadata, created_at \
+             FROM conversation_messages \
+             WHERE channel_id = ? AND role != 'system' AND content LIKE ? ESCAPE '\\' \
+             ORDER BY datetime(created_at) DESC, id DESC \
+             LIMIT ?",
+        )
+        .bind(channel_id)
+        .bind(crate::memory::store::like_pattern(term))
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| anyhow::anyhow!(e))?;
+
+        Ok(rows.into_iter().map(row_to_message).collect())
+    }
+
+    /// Up to `context` user and assistant messages on either side of
+    /// `anchor` in its channel, with the anchor itself, oldest first.
+    pub async fn load_context(
+        &self,
+        anchor: &ConversationMessage,
+        context: i64,
+    ) -> crate::error::Result<Vec<ConversationMessage>> {
+        // Compare at SQLite `datetime()` precision so stored timestamps in
+        // either format order consistently.
+        let anchor_time = anchor.created_at.format("%Y-%m-%d %H:%M:%S").to_string();
+        let before = sqlx::query(
+            "SELECT id, channel_id, role, sender_name, sender_id, content, metadata, created_at \
+             FROM conversation_messages \
+             WHERE channel_id = ? AND role != 'system' \
+             AND (datetime(created_at) < ? OR (datetime(created_at) = ? AND id < ?)) \
+             ORDER BY datetime(created_at) DESC, id DESC \
+             LIMIT ?",
+        )
+        .bind(&anchor.channel_id)
+        .bind(&anchor_time)
+        .bind(&anchor_time)
+        .bind(&anchor.id)
+        .bind(context)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| anyhow::anyhow!(e))?;
+
+        let after = sqlx::query(
+            "SELECT id, channel_id, role, sender_name, sender_id, content, metadata, created_at \
+             FROM conversation_messages \
+             WHERE channel_id = ? AND role != 'system' \
+             AND (datetime(created_at) > ? OR (datetime(created_at) = ? AND id > ?)) \
+             ORDER BY datetime(created_at) ASC, id ASC \
+             LIMIT ?",
+        )
+        .bind(&anchor.channel_id)
+        .bind(&anchor_time)
+        .bind(&anchor_time)
+        .bind(&anchor.id)
+        .bind(context)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| anyhow::anyhow!(e))?;
+
+        let mut messages: Vec<ConversationMessage> =
+            before.into_iter().rev().map(row_to_message).collect();
+        messages.push(anchor.clone());
+        messages.extend(after.into_iter().map(row_to_message));
+        Ok(messages)
+    }
+}
+
+/// A full-text match with a highlighted excerpt of the matching content.
+#[derive(Debug, Clone)]
+pub struct MessageSearchHit {
+    pub message: ConversationMessage,
+    /// Matched terms wrapped in `[` `]`, trimmed to the surrounding words.
+    pub snippet: String,
+}
+
+impl ConversationLogger {
+    /// Full-text search over every channel's messages (or one channel's),
+    /// best match first. Each whitespace-separated term must appear; terms
+    /// are matched literally and stemmed, so `promise` finds `promised`.
+    pub async fn search_messages(
+        &self,
+        query: &str,
+        channel_id: Option<&str>,
+        limit: i64,
+    ) -> crate::error::Result<Vec<MessageSearchHit>> {
+        let Some(match_expression) = fts_match_expression(query) else {
+            return Ok(Vec::new());
+        };
+        let rows = sqlx::query(
+            "SELECT m.id, m.channel_id, m.role, m.sender_name, m.sender_id, m.content, \
+                    m.metadata, m.created_at, \
+                    snippet(conversation_messages_fts, 0, '[', ']', '…', 16) AS snippet \
+             FROM conversation_messages_fts \
+             JOIN conversation_messages m ON m.id = conversation_messages_fts.message_id \
+             WHERE conversation_messages_fts MATCH ? \
+             AND (? IS NULL OR m.channel_id = ?) \
+             ORDER BY conversation_messages_fts.rank \
+             LIMIT ?",
+        )
+        .bind(&match_expression)
+        .bind(channel_id)
+        .bind(channel_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| anyhow::anyhow!(e))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let snippet = row.try_get("snippet").unwrap_or_default();
+                MessageSearchHit {
+                    message: row_to_message(row),
+                    snippet,
+                }
+            })
+            .collect())
+    }
+}
+
+/// Turn free text into an FTS5 expression: every term quoted (so operators
+/// and punctuation in the input are literal) and scoped to the content column.
+fn fts_match_expression(query: &str) -> Option<String> {
+    let terms: Vec<String> = query
+        .split_whitespace()
+        .map(|term| format!("\"{}\"", term.replace('"', "\"\"")))
+        .collect();
+    if terms.is_empty() {
+        return None;
+    }
+    Some(format!("content : ({})", terms.join(" ")))
+}
+
+fn row_to_message(row: sqlx::sqlite::SqliteRow) -> ConversationMessage {
+    ConversationMessage {
+        id: row.try_get("id").unwrap_or_default(),
+        channel_id: row.try_get("channel_id").unwrap_or_default(),
+        role: row.try_get("role").unwrap_or_default(),
+        sender_name: row.try_get("sender_name").ok(),
+        sender_id: row.try_get("sender_id").ok(),
+        content: row.try_get("content").unwrap_or_default(),
+        metadata: row.try_get("metadata").ok(),
+        created_at: row
+            .try_get("created_at")
+            .unwrap_or_else(|_| chrono::Utc::now()),
+    }
 }
 
 /// A unified timeline item combining messages, branch runs, and worker runs.
@@ -253,6 +511,14 @@ pub enum TimelineItem {
         sender_name: Option<String>,
         sender_id: Option<String>,
         content: String,
+        /// Extra detail stored with the message, e.g. the translation that
+        /// was sent or the reply's fact-check.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        metadata: Option<serde_json::Value>,
+        /// Outbox delivery state of an assistant reply. Attached by the API
+        /// when the reply went through the outbox; never stored here.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        delivery: Option<crate::messaging::outbox::DeliveryStatus>,
         created_at: String,
     },
     BranchRun {
@@ -272,6 +538,64 @@ pub enum TimelineItem {
     },
 }
 
+impl TimelineItem {
+    pub fn id(&self) -> &str {
+        match self {
+            TimelineItem::Message { id, .. }
+            | TimelineItem::BranchRun { id, .. }
+            | TimelineItem::WorkerRun { id, .. } => id,
+        }
+    }
+
+    /// When the item entered the timeline: message creation or run start.
+    pub fn timestamp(&self) -> &str {
+        match self {
+            TimelineItem::Message { created_at, .. } => created_at,
+            TimelineItem::BranchRun { started_at, .. }
+            | TimelineItem::WorkerRun { started_at, .. } => started_at,
+        }
+    }
+
+    /// Cursor for paging from this item, toward older or newer items.
+    pub fn cursor(&self) -> TimelineCursor {
+        TimelineCursor {
+            timestamp: self.timestamp().to_string(),
+            id: self.id().to_string(),
+        }
+    }
+}
+
+/// Position in a channel timeline, encoded as `<timestamp>|<id>`.
+///
+/// A bare timestamp is also accepted and means "strictly before this
+/// time", which is what the `before` parameter has always meant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimelineCursor {
+    pub timestamp: String,
+    pub id: String,
+}
+
+impl TimelineCursor {
+    pub fn parse(value: &str) -> Self {
+        match value.split_once('|') {
+            Some((timestamp, id)) => Self {
+                timestamp: timestamp.to_string(),
+                id: id.to_string(),
+            },
+            None => Self {
+                timestamp: value.to_string(),
+                id: String::new(),
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for TimelineCursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}|{}", self.timestamp, self.id)
+    }
+}
+
 /// Persists branch and worker run records for channel timeline history.
 ///
 /// All write methods are fire-and-forget, same pattern as ConversationLogger.
@@ -495,6 +819,26 @@ impl ProcessRunLogger {
         });
     }
 
+    /// Record a worker being cancelled before it finished. Fire-and-forget.
+    pub fn log_worker_cancelled(&self, worker_id: WorkerId, result: &str) {
+        let pool = self.pool.clone();
+        let id = worker_id.to_string();
+        let result = result.to_string();
+
+        tokio::spawn(async move {
+            if let Err(error) = sqlx::query(
+                "UPDATE worker_runs SET result = ?, status = 'cancelled', completed_at = CURRENT_TIMESTAMP WHERE id = ?"
+            )
+            .bind(&result)
+            .bind(&id)
+            .execute(&pool)
+            .await
+            {
+                tracing::warn!(%error, worker_id = %id, "failed to persist worker cancellation");
+            }
+        });
+    }
+
     /// Record OpenCode session metadata on a worker run. Fire-and-forget.
     ///
     /// Stores the session ID and server port so the frontend can construct
@@ -671,7 +1015,7 @@ impl ProcessRunLogger {
                      WHEN result IS NULL OR result = '' THEN 'Worker cancelled' \
                      ELSE result \
                  END, \
-                 status = 'failed', \
+                 status = 'cancelled', \
                  completed_at = COALESCE(completed_at, CURRENT_TIMESTAMP) \
              WHERE id = ? AND channel_id = ? AND status = 'running'",
         )
@@ -697,7 +1041,7 @@ impl ProcessRunLogger {
                      WHEN result IS NULL OR result = '' THEN 'Worker cancelled' \
                      ELSE result \
                  END, \
-                 status = 'failed', \
+                 status = 'cancelled', \
                  completed_at = COALESCE(completed_at, CURRENT_TIMESTAMP) \
              WHERE id = ? AND channel_id IS NULL AND status = 'running'",
         )
@@ -712,43 +1056,72 @@ impl ProcessRunLogger {
     /// Load a unified timeline for a channel: messages, branch runs, and worker runs
     /// interleaved chronologically (oldest first).
     ///
-    /// When `before` is provided, only items with a timestamp strictly before that
-    /// value are returned, enabling cursor-based pagination.
+    /// When `before` is provided, only items older than that cursor are
+    /// returned. Items sharing a timestamp are ordered by ID, so paging with
+    /// the cursor of each page's oldest item neither skips nor repeats them.
     pub async fn load_channel_timeline(
         &self,
         channel_id: &str,
         limit: i64,
-        before: Option<&str>,
+        before: Option<&TimelineCursor>,
+    ) -> crate::error::Result<Vec<TimelineItem>> {
+        self.query_channel_timeline(channel_id, limit, before, false)
+            .await
+    }
+
+    /// Up to `limit` items strictly after `after` (or from the start of the
+    /// timeline), in chronological order. Used to walk a whole channel
+    /// oldest-first, e.g. for export.
+    pub async fn load_channel_timeline_after(
+        &self,
+        channel_id: &str,
+        limit: i64,
+        after: Option<&TimelineCursor>,
     ) -> crate::error::Result<Vec<TimelineItem>> {
-        let before_clause = if before.is_some() {
-            "AND datetime(timestamp) < datetime(?3)"
+        self.query_channel_timeline(channel_id, limit, after, true)
+            .await
+    }
+
+    async fn query_channel_timeline(
+        &self,
+        channel_id: &str,
+        limit: i64,
+        cursor: Option<&TimelineCursor>,
+        forward: bool,
+    ) -> crate::error::Result<Vec<TimelineItem>> {
+        let (comparison, order) = if forward { (">", "ASC") } else { ("<", "DESC") };
+        let cursor_clause = if cursor.is_some() {
+            format!(
+                "AND (sort_key {comparison} strftime('%Y-%m-%d %H:%M:%f', ?3) \
+                   OR (sort_key = strftime('%Y-%m-%d %H:%M:%f', ?3) AND id {comparison} ?4))"
+            )
         } else {
-            ""
+            String::new()
         };
 
         let query_str = format!(
-            "SELECT * FROM ( \
-                SELECT 'message' AS item_type, id, role, sender_name, sender_id, content, \
+            "SELECT * FROM (SELECT *, strftime('%Y-%m-%d %H:%M:%f', timestamp) AS sort_key FROM ( \
+                SELECT 'message' AS item_type, id, role, sender_name, sender_id, content, metadata, \
                        NULL AS description, NULL AS conclusion, NULL AS task, NULL AS result, NULL AS status, \
                        created_at AS timestamp, NULL AS completed_at \
                 FROM conversation_messages WHERE channel_id = ?1 \
                 UNION ALL \
-                SELECT 'branch_run' AS item_type, id, NULL, NULL, NULL, NULL, \
+                SELECT 'branch_run' AS item_type, id, NULL, NULL, NULL, NULL, NULL, \
                        description, conclusion, NULL, NULL, NULL, \
                        started_at AS timestamp, completed_at \
                 FROM branch_runs WHERE channel_id = ?1 \
                 UNION ALL \
-                SELECT 'worker_run' AS item_type, id, NULL, NULL, NULL, NULL, \
+                SELECT 'worker_run' AS item_type, id, NULL, NULL, NULL, NULL, NULL, \
                        NULL, NULL, task, result, status, \
                        started_at AS timestamp, completed_at \
                 FROM worker_runs WHERE channel_id = ?1 \
-            ) WHERE 1=1 {before_clause} ORDER BY timestamp DESC LIMIT ?2"
+            )) WHERE 1=1 {cursor_clause} ORDER BY sort_key {order}, id {order} LIMIT ?2"
         );
 
         let mut query = sqlx::query(&query_str).bind(channel_id).bind(limit);
 
-        if let Some(before_ts) = before {
-            query = query.bind(before_ts);
+        if let Some(cursor) = cursor {
+            query = query.bind(&cursor.timestamp).bind(&cursor.id);
         }
 
         let rows = query
@@ -767,6 +1140,12 @@ impl ProcessRunLogger {
                         sender_name: row.try_get("sender_name").ok(),
                         sender_id: row.try_get("sender_id").ok(),
                         content: row.try_get("content").unwrap_or_default(),
+                        metadata: row
+                            .try_get::<Option<String>, _>("metadata")
+                            .ok()
+                            .flatten()
+                            .and_then(|raw| serde_json::from_str(&raw).ok()),
+                        delivery: None,
                         created_at: row
                             .try_get::<chrono::DateTime<chrono::Utc>, _>("timestamp")
                             .map(|t| t.to_rfc3339())
@@ -805,7 +1184,9 @@ impl ProcessRunLogger {
             .collect();
 
         // Reverse to chronological order
-        items.reverse();
+        if !forward {
+            items.reverse();
+        }
         Ok(items)
     }
 
@@ -999,7 +1380,7 @@ pub struct WorkerDetailRow {
 
 #[cfg(test)]
 mod tests {
-    use super::ProcessRunLogger;
+    use super::{ProcessRunLogger, TimelineCursor};
 
     async fn setup_worker_runs_table() -> sqlx::SqlitePool {
         let pool = sqlx::sqlite::SqlitePoolOptions::new()
@@ -1050,7 +1431,7 @@ mod tests {
 
         let status: String = sqlx::Row::try_get(&row, "status").expect("missing status");
         let result: String = sqlx::Row::try_get(&row, "result").expect("missing result");
-        assert_eq!(status, "failed");
+        assert_eq!(status, "cancelled");
         assert_eq!(result, "Worker cancelled");
     }
 
@@ -1082,4 +1463,163 @@ mod tests {
         let status: String = sqlx::Row::try_get(&row, "status").expect("missing status");
         assert_eq!(status, "running");
     }
+
+    #[tokio::test]
+    async fn timeline_cursor_pages_through_items_sharing_a_timestamp() {
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("failed to create sqlite memory pool");
+        sqlx::migrate!("./migrations")
+            .run(&pool)
+            .await
+            .expect("migrations");
+
+        for (id, created_at) in [
+            ("m1", "2026-01-01 09:59:59"),
+            ("m2", "2026-01-01 10:00:00"),
+            ("m3", "2026-01-01 10:00:00"),
+            ("m4", "2026-01-01 10:00:00"),
+            ("m5", "2026-01-01 10:00:01"),
+        ] {
+            sqlx::query(
+                "INSERT INTO conversation_messages (id, channel_id, role, content, created_at) \
+                 VALUES (?, 'channel-1', 'user', 'hi', ?)",
+            )
+            .bind(id)
+            .bind(created_at)
+            .execute(&pool)
+            .await
+            .expect("failed to insert message");
+        }
+
+        let logger = ProcessRunLogger::new(pool);
+        let mut seen = Vec::new();
+        let mut cursor: Option<TimelineCursor> = None;
+        loop {
+            let page = logger
+                .load_channel_timeline("channel-1", 2, cursor.as_ref())
+                .await
+                .expect("timeline should load");
+            let Some(oldest) = page.first() else {
+                break;
+            };
+            cursor = Some(TimelineCursor::parse(&oldest.cursor().to_string()));
+            for item in page.iter().rev() {
+                seen.push(item.id().to_string());
+            }
+        }
+
+        assert_eq!(seen, ["m5", "m4", "m3", "m2", "m1"]);
+
+        let mut seen = Vec::new();
+        let mut cursor: Option<TimelineCursor> = None;
+        loop {
+            let page = logger
+                .load_channel_timeline_after("channel-1", 2, cursor.as_ref())
+                .await
+                .expect("timeline should load");
+            let Some(newest) = page.last() else {
+                break;
+            };
+            cursor = Some(newest.cursor());
+            seen.extend(page.iter().map(|item| item.id().to_string()));
+        }
+
+        assert_eq!(seen, ["m1", "m2", "m3", "m4", "m5"]);
+    }
+
+    #[tokio::test]
+    async fn search_channel_excerpts_include_surrounding_messages() {
+        let store = crate::memory::MemoryStore::connect_in_memory().await;
+        let pool = store.pool().clone();
+        for (index, content) in [
+            "morning",
+            "which database?",
+            "we decided on SQLite",
+            "sounds good",
+            "lunch?",
+            "sure",
+        ]
+        .iter()
+        .enumerate()
+        {
+            sqlx::query(
+                "INSERT INTO conversation_messages (id, channel_id, role, content, created_at) \
+                 VALUES (?, 'planning', 'user', ?, ?)",
+            )
+            .bind(format!("m{index}"))
+            .bind(content)
+            .bind(format!("2025-01-01 10:00:0{index}"))
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let logger = super::ConversationLogger::new(pool);
+        let matches = logger
+            .search_channel("planning", "sqlite", 5)
+            .await
+            .unwrap();
+        assert_eq!(matches.len(), 1);
+
+        let excerpt = logger.load_context(&matches[0], 1).await.unwrap();
+        let ids: Vec<&str> = excerpt.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, ["m1", "m2", "m3"]);
+    }
+
+    #[tokio::test]
+    async fn full_text_search_tracks_inserts_and_deletes() {
+        let store = crate::memory::MemoryStore::connect_in_memory().await;
+        let pool = store.pool().clone();
+        for (id, channel_id, content) in [
+            ("m1", "support", "I promise the refund goes out Friday"),
+            ("m2", "support", "thanks!"),
+            ("m3", "general", "no promises on the \"refund\" date"),
+        ] {
+            sqlx::query(
+                "INSERT INTO conversation_messages (id, channel_id, role, content) \
+                 VALUES (?, ?, 'assistant', ?)",
+            )
+            .bind(id)
+            .bind(channel_id)
+            .bind(content)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let logger = super::ConversationLogger::new(pool.clone());
+        let hits = logger
+            .search_messages("promised refund", None, 10)
+            .await
+            .unwrap();
+        let mut ids: Vec<&str> = hits.iter().map(|hit| hit.message.id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, ["m1", "m3"]);
+        assert!(hits.iter().any(|hit| hit.snippet.contains("[refund]")));
+
+        let hits = logger
+            .search_messages("refund", Some("support"), 10)
+            .await
+            .unwrap();
+        assert_eq!(hits.len(), 1);
+
+        // Operator syntax in the query is taken literally rather than erroring.
+        assert!(
+            logger
+                .search_messages("refund\" OR", None, 10)
+                .await
+                .is_ok()
+        );
+
+        sqlx::query("DELETE FROM conversation_messages WHERE id = 'm1'")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let hits = logger.search_messages("refund", None, 10).await.unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].message.id, "m3");
+    }
 }
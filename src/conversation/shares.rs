@@ -0,0 +1,391 @@
+//! Read-only share links for channel transcript excerpts (SQLite).
+
+use crate::conversation::history::ConversationMessage;
+
+use rand::RngCore as _;
+use serde::Serialize;
+use sqlx::{Row as _, SqlitePool};
+
+/// Upper bound on messages rendered for a single share, regardless of how
+/// wide the requested range is.
+pub const MAX_SHARED_MESSAGES: i64 = 500;
+
+/// Creates, resolves, and revokes tokenized transcript share links.
+#[derive(Debug, Clone)]
+pub struct ShareStore {
+    pool: SqlitePool,
+}
+
+/// A persisted share link.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct ChannelShare {
+    pub id: String,
+    pub token: String,
+    pub channel_id: String,
+    pub title: Option<String>,
+    pub start_message_id: String,
+    pub end_message_id: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub revoked_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl ChannelShare {
+    pub fn is_expired(&self) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| expires_at <= chrono::Utc::now())
+    }
+
+    pub fn is_revoked(&self) -> bool {
+        self.revoked_at.is_some()
+    }
+}
+
+/// Outcome of looking up a share by its public token.
+#[derive(Debug)]
+pub enum ShareLookup {
+    Active(ChannelShare),
+    Expired,
+    Revoked,
+    NotFound,
+}
+
+impl ShareStore {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Create a share covering every message between two message IDs
+    /// (inclusive, in either order).
+    pub async fn create(
+        &self,
+        channel_id: &str,
+        first_message_id: &str,
+        second_message_id: &str,
+        title: Option<String>,
+        expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> crate::error::Result<ChannelShare> {
+        let first_at = self.message_timestamp(channel_id, first_message_id).await?;
+        let second_at = self
+            .message_timestamp(channel_id, second_message_id)
+            .await?;
+        let (Some(first_at), Some(second_at)) = (first_at, second_at) else {
+            return Err(anyhow::anyhow!(
+                "can't create share: both messages must belong to channel {channel_id}"
+            )
+            .into());
+        };
+
+        let ((start_id, range_start), (end_id, range_end)) = if first_at <= second_at {
+            ((first_message_id, first_at), (second_message_id, second_at))
+        } else {
+            ((second_message_id, second_at), (first_message_id, first_at))
+        };
+
+        let id = uuid::Uuid::new_v4().to_string();
+        let token = generate_token();
+
+        sqlx::query(
+            "INSERT INTO channel_shares \
+             (id, token, channel_id, title, start_message_id, end_message_id, range_start, range_end, expires_at) \
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&id)
+        .bind(&token)
+        .bind(channel_id)
+        .bind(&title)
+        .bind(start_id)
+        .bind(end_id)
+        .bind(&range_start)
+        .bind(&range_end)
+        .bind(expires_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| anyhow::anyhow!(e))?;
+
+        self.get(&id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("share {id} missing after insert").into())
+    }
+
+    /// Fetch a share by ID.
+    pub async fn get(&self, id: &str) -> crate::error::Result<Option<ChannelShare>> {
+        let row = sqlx::query(&format!("{SELECT_SHARE} WHERE id = ?"))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| anyhow::anyhow!(e))?;
+        Ok(row.map(row_to_share))
+    }
+
+    /// List all shares for a channel, newest first, including revoked and
+    /// expired ones so operators can audit what was handed out.
+    pub async fn list(&self, channel_id: &str) -> crate::error::Result<Vec<ChannelShare>> {
+        let rows = sqlx::query(&format!(
+            "{SELECT_SHARE} WHERE channel_id = ? ORDER BY created_at DESC"
+        ))
+        .bind(channel_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| anyhow::anyhow!(e))?;
+        Ok(rows.into_iter().map(row_to_share).collect())
+    }
+
+    /// Revoke a share. Returns false if it doesn't exist or was already revoked.
+    pub async fn revoke(&self, channel_id: &str, id: &str) -> crate::error::Result<bool> {
+        let result = sqlx::query(
+            "UPDATE channel_shares SET revoked_at = ? \
+             WHERE id = ? AND channel_id = ? AND revoked_at IS NULL",
+        )
+        .bind(chrono::Utc::now())
+        .bind(id)
+        .bind(channel_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| anyhow::anyhow!(e))?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Resolve a public token to its share, distinguishing dead links from
+    /// unknown ones.
+    pub async fn lookup(&self, token: &str) -> crate::error::Result<ShareLookup> {
+        let row = sqlx::query(&format!("{SELECT_SHARE} WHERE token = ?"))
+            .bind(token)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| anyhow::anyhow!(e))?;
+
+        Ok(match row.map(row_to_share) {
+            None => ShareLookup::NotFound,
+            Some(share) if share.is_revoked() => ShareLookup::Revoked,
+            Some(share) if share.is_expired() => ShareLookup::Expired,
+            Some(share) => ShareLookup::Active(share),
+        })
+    }
+
+    /// Load the user and assistant messages covered by a share, oldest first.
+    pub async fn load_messages(
+        &self,
+        share: &ChannelShare,
+    ) -> crate::error::Result<Vec<ConversationMessage>> {
+        let rows = sqlx::query(
+            "SELECT m.id, m.channel_id, m.role, m.sender_name, m.sender_id, m.content, m.metadata, m.created_at \
+             FROM conversation_messages m \
+             JOIN channel_shares s ON s.channel_id = m.channel_id \
+             WHERE s.id = ? \
+               AND m.role IN ('user', 'assistant') \
+               AND datetime(m.created_at) >= datetime(s.range_start) \
+               AND datetime(m.created_at) <= datetime(s.range_end) \
+             ORDER BY m.created_at ASC \
+             LIMIT ?",
+        )
+        .bind(&share.id)
+        .bind(MAX_SHARED_MESSAGES)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| anyhow::anyhow!(e))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| ConversationMessage {
+                id: row.try_get("id").unwrap_or_default(),
+                channel_id: row.try_get("channel_id").unwrap_or_default(),
+                role: row.try_get("role").unwrap_or_default(),
+                sender_name: row.try_get("sender_name").ok(),
+                sender_id: row.try_get("sender_id").ok(),
+                content: row.try_get("content").unwrap_or_default(),
+                metadata: row.try_get("metadata").ok(),
+                created_at: row
+                    .try_get("created_at")
+                    .unwrap_or_else(|_| chrono::Utc::now()),
+            })
+            .collect())
+    }
+
+    /// Raw `created_at` of a message, kept as stored so range comparisons use
+    /// the same representation as the messages table.
+    async fn message_timestamp(
+        &self,
+        channel_id: &str,
+        message_id: &str,
+    ) -> crate::error::Result<Option<String>> {
+        let row = sqlx::query(
+            "SELECT CAST(created_at AS TEXT) AS created_at FROM conversation_messages \
+             WHERE id = ? AND channel_id = ?",
+        )
+        .bind(message_id)
+        .bind(channel_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| anyhow::anyhow!(e))?;
+        Ok(row.and_then(|row| row.try_get("created_at").ok()))
+    }
+}
+
+const SELECT_SHARE: &str = "SELECT id, token, channel_id, title, start_message_id, end_message_id, \
+     created_at, expires_at, revoked_at FROM channel_shares";
+
+fn row_to_share(row: sqlx::sqlite::SqliteRow) -> ChannelShare {
+    ChannelShare {
+        id: row.try_get("id").unwrap_or_default(),
+        token: row.try_get("token").unwrap_or_default(),
+        channel_id: row.try_get("channel_id").unwrap_or_default(),
+        title: row.try_get("title").ok().flatten(),
+        start_message_id: row.try_get("start_message_id").unwrap_or_default(),
+        end_message_id: row.try_get("end_message_id").unwrap_or_default(),
+        created_at: row
+            .try_get("created_at")
+            .unwrap_or_else(|_| chrono::Utc::now()),
+        expires_at: row.try_get("expires_at").ok().flatten(),
+        revoked_at: row.try_get("revoked_at").ok().flatten(),
+    }
+}
+
+/// 32 random bytes, hex-encoded. Long enough that tokens can't be guessed.
+pub(crate) fn generate_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::rng().fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn setup() -> ShareStore {
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("failed to create sqlite memory pool");
+        sqlx::migrate!("./migrations")
+            .run(&pool)
+            .await
+            .expect("migrations");
+
+        // m1..m5 in "chan", one second apart, plus a message elsewhere that
+        // falls inside the same time range.
+        for (id, channel_id, second) in [
+            ("m1", "chan", 1),
+            ("m2", "chan", 2),
+            ("m3", "chan", 3),
+            ("m4", "chan", 4),
+            ("m5", "chan", 5),
+            ("other", "elsewhere", 3),
+        ] {
+            sqlx::query(
+                "INSERT INTO conversation_messages (id, channel_id, role, content, created_at) \
+                 VALUES (?, ?, 'user', ?, ?)",
+            )
+            .bind(id)
+            .bind(channel_id)
+            .bind(format!("message {id}"))
+            .bind(format!("2026-01-01 00:00:0{second}"))
+            .execute(&pool)
+            .await
+            .expect("failed to insert message");
+        }
+        ShareStore::new(pool)
+    }
+
+    fn ids(messages: &[ConversationMessage]) -> Vec<&str> {
+        messages.iter().map(|message| message.id.as_str()).collect()
+    }
+
+    #[tokio::test]
+    async fn token_resolves_only_its_own_range() {
+        let store = setup().await;
+        // Endpoints in either order cover the same range.
+        let share = store
+            .create("chan", "m4", "m2", None, None)
+            .await
+            .expect("failed to create share");
+        let other = store
+            .create("chan", "m5", "m5", None, None)
+            .await
+            .expect("failed to create share");
+        assert_ne!(share.token, other.token);
+
+        let ShareLookup::Active(resolved) = store.lookup(&share.token).await.unwrap() else {
+            panic!("expected an active share");
+        };
+        assert_eq!(resolved.id, share.id);
+        let messages = store.load_messages(&resolved).await.unwrap();
+        assert_eq!(ids(&messages), ["m2", "m3", "m4"]);
+
+        let messages = store.load_messages(&other).await.unwrap();
+        assert_eq!(ids(&messages), ["m5"]);
+
+        assert!(matches!(
+            store.lookup("not-a-token").await.unwrap(),
+            ShareLookup::NotFound
+        ));
+    }
+
+    #[tokio::test]
+    async fn rejects_messages_from_another_channel() {
+        let store = setup().await;
+        assert!(
+            store
+                .create("chan", "other", "m3", None, None)
+                .await
+                .is_err()
+        );
+        assert!(
+            store
+                .create("chan", "m1", "other", None, None)
+                .await
+                .is_err()
+        );
+        assert!(
+            store
+                .create("chan", "m1", "missing", None, None)
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn expired_and_revoked_shares_stop_resolving() {
+        let store = setup().await;
+
+        let expired = store
+            .create(
+                "chan",
+                "m1",
+                "m2",
+                None,
+                Some(chrono::Utc::now() - chrono::Duration::minutes(1)),
+            )
+            .await
+            .unwrap();
+        assert!(matches!(
+            store.lookup(&expired.token).await.unwrap(),
+            ShareLookup::Expired
+        ));
+
+        let live = store
+            .create(
+                "chan",
+                "m1",
+                "m2",
+                None,
+                Some(chrono::Utc::now() + chrono::Duration::hours(1)),
+            )
+            .await
+            .unwrap();
+        assert!(matches!(
+            store.lookup(&live.token).await.unwrap(),
+            ShareLookup::Active(_)
+        ));
+
+        // Revoking needs the share's own channel, and only works once.
+        assert!(!store.revoke("elsewhere", &live.id).await.unwrap());
+        assert!(store.revoke("chan", &live.id).await.unwrap());
+        assert!(!store.revoke("chan", &live.id).await.unwrap());
+        assert!(matches!(
+            store.lookup(&live.token).await.unwrap(),
+            ShareLookup::Revoked
+        ));
+    }
+}
@@ -0,0 +1,174 @@
+//! Anonymous visitor sessions for the embeddable website chat widget (SQLite).
+
+use super::shares::generate_token;
+
+use serde::Serialize;
+use sqlx::{Row as _, SqlitePool};
+
+/// Prefix of the channel each widget visitor gets.
+pub const WIDGET_CHANNEL_PREFIX: &str = "widget:";
+
+/// Issues and resolves widget session tokens.
+#[derive(Debug, Clone)]
+pub struct WidgetSessionStore {
+    pool: SqlitePool,
+}
+
+/// A visitor session. Each session maps to its own channel.
+#[derive(Debug, Clone, Serialize)]
+pub struct WidgetSession {
+    pub id: String,
+    #[serde(skip)]
+    pub token: String,
+    pub origin: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub last_seen_at: chrono::DateTime<chrono::Utc>,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl WidgetSession {
+    /// The channel this visitor's conversation lives in.
+    pub fn channel_id(&self) -> String {
+        format!("{WIDGET_CHANNEL_PREFIX}{}", self.id)
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expires_at <= chrono::Utc::now()
+    }
+}
+
+impl WidgetSessionStore {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Issue a new session for a page on `origin`, valid for `ttl`.
+    pub async fn create(
+        &self,
+        origin: &str,
+        ttl: chrono::Duration,
+    ) -> crate::error::Result<WidgetSession> {
+        let now = chrono::Utc::now();
+        let session = WidgetSession {
+            id: uuid::Uuid::new_v4().to_string(),
+            token: generate_token(),
+            origin: origin.to_string(),
+            created_at: now,
+            last_seen_at: now,
+            expires_at: now + ttl,
+        };
+
+        sqlx::query(
+            "INSERT INTO widget_sessions (id, token, origin, created_at, last_seen_at, expires_at) \
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&session.id)
+        .bind(&session.token)
+        .bind(&session.origin)
+        .bind(session.created_at)
+        .bind(session.last_seen_at)
+        .bind(session.expires_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| anyhow::anyhow!(e))?;
+
+        Ok(session)
+    }
+
+    /// Resolve a token to its session and mark it seen. Unknown and expired
+    /// tokens both resolve to `None`.
+    pub async fn lookup(&self, token: &str) -> crate::error::Result<Option<WidgetSession>> {
+        let row = sqlx::query(
+            "SELECT id, token, origin, created_at, last_seen_at, expires_at \
+             FROM widget_sessions WHERE token = ?",
+        )
+        .bind(token)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| anyhow::anyhow!(e))?;
+
+        let Some(mut session) = row.map(row_to_session) else {
+            return Ok(None);
+        };
+        if session.is_expired() {
+            return Ok(None);
+        }
+
+        session.last_seen_at = chrono::Utc::now();
+        sqlx::query("UPDATE widget_sessions SET last_seen_at = ? WHERE id = ?")
+            .bind(session.last_seen_at)
+            .bind(&session.id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| anyhow::anyhow!(e))?;
+
+        Ok(Some(session))
+    }
+
+    /// Delete expired sessions. Their channels and history are kept.
+    pub async fn prune_expired(&self) -> crate::error::Result<u64> {
+        let result = sqlx::query("DELETE FROM widget_sessions WHERE expires_at <= ?")
+            .bind(chrono::Utc::now())
+            .execute(&self.pool)
+            .await
+            .map_err(|e| anyhow::anyhow!(e))?;
+        Ok(result.rows_affected())
+    }
+}
+
+fn row_to_session(row: sqlx::sqlite::SqliteRow) -> WidgetSession {
+    WidgetSession {
+        id: row.try_get("id").unwrap_or_default(),
+        token: row.try_get("token").unwrap_or_default(),
+        origin: row.try_get("origin").unwrap_or_default(),
+        created_at: row
+            .try_get("created_at")
+            .unwrap_or_else(|_| chrono::Utc::now()),
+        last_seen_at: row
+            .try_get("last_seen_at")
+            .unwrap_or_else(|_| chrono::Utc::now()),
+        expires_at: row
+            .try_get("expires_at")
+            .unwrap_or_else(|_| chrono::Utc::now()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn store() -> WidgetSessionStore {
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("failed to create sqlite memory pool");
+        sqlx::migrate!("./migrations")
+            .run(&pool)
+            .await
+            .expect("migrations");
+        WidgetSessionStore::new(pool)
+    }
+
+    #[tokio::test]
+    async fn lookup_resolves_live_sessions_only() {
+        let store = store().await;
+        let live = store
+            .create("https://example.com", chrono::Duration::hours(1))
+            .await
+            .unwrap();
+        let expired = store
+            .create("https://example.com", chrono::Duration::seconds(-1))
+            .await
+            .unwrap();
+
+        let found = store.lookup(&live.token).await.unwrap().unwrap();
+        assert_eq!(found.id, live.id);
+        assert_eq!(found.channel_id(), format!("widget:{}", live.id));
+        assert!(store.lookup(&expired.token).await.unwrap().is_none());
+        assert!(store.lookup("unknown").await.unwrap().is_none());
+
+        assert_eq!(store.prune_expired().await.unwrap(), 1);
+        assert!(store.lookup(&live.token).await.unwrap().is_some());
+    }
+}
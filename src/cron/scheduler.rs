@@ -6,10 +6,12 @@
 //! to the delivery target via the messaging system.
 
 use crate::agent::channel::Channel;
+use crate::config::NotificationCategory;
 use crate::cron::store::CronStore;
 use crate::error::Result;
 use crate::messaging::MessagingManager;
 use crate::messaging::target::{BroadcastTarget, parse_delivery_target};
+use crate::notifications::OperatorNotifier;
 use crate::{AgentDeps, InboundMessage, MessageContent, OutboundResponse, RoutedResponse};
 use chrono::Timelike;
 use chrono_tz::Tz;
@@ -36,6 +38,9 @@ pub struct CronJob {
     /// Maximum wall-clock seconds to wait for the job to complete.
     /// `None` uses the default of 120 seconds.
     pub timeout_secs: Option<u64>,
+    /// IANA timezone for `cron_expr` and `active_hours`. `None` uses the
+    /// agent's `cron_timezone`.
+    pub timezone: Option<String>,
 }
 
 /// Serializable cron job config (for storage and TOML parsing).
@@ -57,6 +62,10 @@ pub struct CronConfig {
     /// Maximum wall-clock seconds to wait for the job to complete.
     /// `None` uses the default of 120 seconds.
     pub timeout_secs: Option<u64>,
+    /// IANA timezone for `cron_expr` and `active_hours`. `None` uses the
+    /// agent's `cron_timezone`.
+    #[serde(default)]
+    pub timezone: Option<String>,
 }
 
 fn default_interval() -> u64 {
@@ -81,6 +90,16 @@ pub struct CronContext {
     pub store: Arc<CronStore>,
 }
 
+impl CronContext {
+    fn operator_notifier(&self) -> OperatorNotifier {
+        OperatorNotifier::new(
+            self.messaging_manager.clone(),
+            self.deps.humans.clone(),
+            self.deps.sqlite_pool.clone(),
+        )
+    }
+}
+
 const MAX_CONSECUTIVE_FAILURES: u32 = 3;
 
 /// RAII guard that clears an `AtomicBool` on drop, ensuring the flag is
@@ -165,6 +184,7 @@ impl Scheduler {
             run_once: config.run_once,
             consecutive_failures: 0,
             timeout_secs: config.timeout_secs,
+            timezone: config.timezone,
         };
 
         {
@@ -228,7 +248,7 @@ impl Scheduler {
             }
         }
 
-        let handle = tokio::spawn(async move {
+        let handle = self.context.deps.spawn(async move {
             let execution_lock = Arc::new(std::sync::atomic::AtomicBool::new(false));
             let mut interval_first_tick = true;
 
@@ -249,7 +269,8 @@ impl Scheduler {
                 };
 
                 let sleep_duration = if let Some(cron_expr) = job.cron_expr.as_deref() {
-                    match next_fire_duration(&context, &job_id, cron_expr) {
+                    match next_fire_duration(&context, &job_id, cron_expr, job.timezone.as_deref())
+                    {
                         Some((duration, next_fire_utc, timezone)) => {
                             tracing::debug!(
                                 cron_id = %job_id,
@@ -307,7 +328,8 @@ impl Scheduler {
 
                 // Check active hours window
                 if let Some((start, end)) = job.active_hours {
-                    let (current_hour, timezone) = current_hour_and_timezone(&context, &job_id);
+                    let (current_hour, timezone) =
+                        current_hour_and_timezone(&context, &job_id, job.timezone.as_deref());
                     let in_window = hour_in_active_window(current_hour, start, end);
                     if !in_window {
                         tracing::debug!(
@@ -322,6 +344,16 @@ impl Scheduler {
                     }
                 }
 
+                if !context.deps.leader.is_leader() {
+                    tracing::debug!(cron_id = %job_id, "not the leader replica, skipping tick");
+                    continue;
+                }
+
+                if context.deps.runtime_config.is_paused() {
+                    tracing::debug!(cron_id = %job_id, "agent is paused, skipping tick");
+                    continue;
+                }
+
                 if execution_lock.load(std::sync::atomic::Ordering::Acquire) {
                     tracing::debug!(cron_id = %job_id, "previous execution still running, skipping tick");
                     continue;
@@ -399,6 +431,18 @@ impl Scheduler {
                                 {
                                     tracing::error!(%error, "failed to persist cron job disabled state");
                                 }
+
+                                exec_context
+                                    .operator_notifier()
+                                    .notify(
+                                        NotificationCategory::CronFailures,
+                                        &format!(
+                                            "Cron job `{exec_job_id}` was disabled after \
+                                             {MAX_CONSECUTIVE_FAILURES} consecutive failures. \
+                                             Last error: {error}"
+                                        ),
+                                    )
+                                    .await;
                             }
                         }
                     }
@@ -562,6 +606,7 @@ impl Scheduler {
                         run_once: config.run_once,
                         consecutive_failures: 0,
                         timeout_secs: config.timeout_secs,
+                        timezone: config.timezone,
                     },
                 );
             }
@@ -615,36 +660,21 @@ fn cron_timezone_label(context: &CronContext) -> String {
     }
 }
 
-fn current_hour_and_timezone(context: &CronContext, cron_id: &str) -> (u8, String) {
-    let timezone = context.deps.runtime_config.cron_timezone.load();
-    match timezone.as_deref() {
-        Some(name) => match name.parse::<Tz>() {
-            Ok(timezone) => (
-                chrono::Utc::now().with_timezone(&timezone).hour() as u8,
-                name.into(),
-            ),
-            Err(error) => {
-                tracing::warn!(
-                    agent_id = %context.deps.agent_id,
-                    cron_id,
-                    cron_timezone = %name,
-                    %error,
-                    "invalid cron timezone in runtime config, falling back to system timezone"
-                );
-                (
-                    chrono::Local::now().hour() as u8,
-                    SYSTEM_TIMEZONE_LABEL.into(),
-                )
-            }
-        },
-        None => (
-            chrono::Local::now().hour() as u8,
-            SYSTEM_TIMEZONE_LABEL.into(),
+fn current_hour_and_timezone(
+    context: &CronContext,
+    cron_id: &str,
+    job_timezone: Option<&str>,
+) -> (u8, String) {
+    match resolve_cron_timezone(context, cron_id, job_timezone) {
+        (Some(timezone), label) => (
+            chrono::Utc::now().with_timezone(&timezone).hour() as u8,
+            label,
         ),
+        (None, label) => (chrono::Local::now().hour() as u8, label),
     }
 }
 
-fn hour_in_active_window(current_hour: u8, start_hour: u8, end_hour: u8) -> bool {
+pub(crate) fn hour_in_active_window(current_hour: u8, start_hour: u8, end_hour: u8) -> bool {
     if start_hour == end_hour {
         return true;
     }
@@ -708,6 +738,8 @@ fn anchored_initial_delay(
 ) -> Duration {
     if let Some(last_run) = anchor {
         let now = chrono::Utc::now();
+        #[cfg(feature = "chaos")]
+        let now = crate::chaos::skew(now);
         let elapsed = (now - last_run).num_seconds().max(0) as u64;
         if elapsed >= interval_secs {
             // Overdue — fire soon with a small jitter to avoid thundering herd
@@ -750,7 +782,28 @@ fn expand_cron_expr(expr: &str) -> String {
     }
 }
 
-fn resolve_cron_timezone(context: &CronContext) -> (Option<chrono_tz::Tz>, String) {
+/// The job's own timezone when it names a real zone, otherwise the agent's
+/// `cron_timezone`, otherwise system local (`None`).
+fn resolve_cron_timezone(
+    context: &CronContext,
+    cron_id: &str,
+    job_timezone: Option<&str>,
+) -> (Option<chrono_tz::Tz>, String) {
+    if let Some(name) = job_timezone {
+        match name.parse::<Tz>() {
+            Ok(timezone) => return (Some(timezone), name.to_string()),
+            Err(error) => {
+                tracing::warn!(
+                    agent_id = %context.deps.agent_id,
+                    cron_id,
+                    job_timezone = %name,
+                    %error,
+                    "invalid cron job timezone, falling back to cron_timezone"
+                );
+            }
+        }
+    }
+
     let timezone = context.deps.runtime_config.cron_timezone.load();
     match timezone.as_deref() {
         Some(name) => match name.parse::<Tz>() {
@@ -758,6 +811,7 @@ fn resolve_cron_timezone(context: &CronContext) -> (Option<chrono_tz::Tz>, Strin
             Err(error) => {
                 tracing::warn!(
                     agent_id = %context.deps.agent_id,
+                    cron_id,
                     cron_timezone = %name,
                     %error,
                     "invalid cron timezone in runtime config, falling back to system timezone"
@@ -773,6 +827,7 @@ fn next_fire_duration(
     context: &CronContext,
     cron_id: &str,
     cron_expr: &str,
+    job_timezone: Option<&str>,
 ) -> Option<(Duration, chrono::DateTime<chrono::Utc>, String)> {
     // Expand 5-field standard cron to 7-field for the `cron` crate.
     let expanded = expand_cron_expr(cron_expr);
@@ -785,7 +840,9 @@ fn next_fire_duration(
     };
 
     let now_utc = chrono::Utc::now();
-    let (timezone, timezone_label) = resolve_cron_timezone(context);
+    #[cfg(feature = "chaos")]
+    let now_utc = crate::chaos::skew(now_utc);
+    let (timezone, timezone_label) = resolve_cron_timezone(context, cron_id, job_timezone);
     let next_utc = if let Some(timezone) = timezone {
         let now_local = now_utc.with_timezone(&timezone);
         schedule
@@ -793,7 +850,7 @@ fn next_fire_duration(
             .next()?
             .with_timezone(&chrono::Utc)
     } else {
-        let now_local = chrono::Local::now();
+        let now_local = now_utc.with_timezone(&chrono::Local);
         schedule
             .after(&now_local)
             .next()?
@@ -972,6 +1029,17 @@ async fn run_cron_job(job: &CronJob, context: &CronContext) -> Result<()> {
             target = %job.delivery_target,
             "cron result delivered"
         );
+
+        // Operators subscribed to reports get a copy, unless the job already
+        // delivers to them.
+        context
+            .operator_notifier()
+            .notify_excluding(
+                NotificationCategory::CronReports,
+                &format!("Cron job `{}` report:\n\n{result_text}", job.id),
+                &job.delivery_target,
+            )
+            .await;
     } else {
         tracing::debug!(cron_id = %job.id, "cron job produced no output, skipping delivery");
     }
@@ -175,6 +175,7 @@ pub fn init_background_tracing(
             tracing_subscriber::registry()
                 .with(filter)
                 .with(fmt_layer)
+                .with(crate::log_stream::BroadcastLayer)
                 .with(tracing_opentelemetry::layer().with_tracer(tracer))
                 .init();
             Some(provider)
@@ -183,6 +184,7 @@ pub fn init_background_tracing(
             tracing_subscriber::registry()
                 .with(filter)
                 .with(fmt_layer)
+                .with(crate::log_stream::BroadcastLayer)
                 .init();
             None
         }
@@ -230,6 +232,7 @@ pub fn init_foreground_tracing(
             tracing_subscriber::registry()
                 .with(filter)
                 .with(fmt_layer)
+                .with(crate::log_stream::BroadcastLayer)
                 .with(tracing_opentelemetry::layer().with_tracer(tracer))
                 .init();
             Some(provider)
@@ -238,6 +241,7 @@ pub fn init_foreground_tracing(
             tracing_subscriber::registry()
                 .with(filter)
                 .with(fmt_layer)
+                .with(crate::log_stream::BroadcastLayer)
                 .init();
             None
         }
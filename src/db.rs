@@ -3,6 +3,7 @@
 use crate::error::{DbError, Result};
 use anyhow::Context as _;
 use sqlx::SqlitePool;
+use sqlx::sqlite::SqlitePoolOptions;
 use std::path::Path;
 
 /// Database connections bundle for per-agent databases.
@@ -22,9 +23,20 @@ use std::sync::Arc;
 impl Db {
     /// Connect to all databases and run migrations.
     pub async fn connect(data_dir: &Path) -> Result<Self> {
+        Self::connect_with_pool_size(
+            data_dir,
+            crate::config::QuotaConfig::default().max_db_connections,
+        )
+        .await
+    }
+
+    /// Like [`Db::connect`], capping the SQLite pool at `max_connections`.
+    pub async fn connect_with_pool_size(data_dir: &Path, max_connections: u32) -> Result<Self> {
         // SQLite
         let sqlite_url = format!("sqlite:{}?mode=rwc", data_dir.join("spacebot.db").display());
-        let sqlite = SqlitePool::connect(&sqlite_url)
+        let sqlite = SqlitePoolOptions::new()
+            .max_connections(max_connections)
+            .connect(&sqlite_url)
             .await
             .with_context(|| "failed to connect to SQLite")?;
 
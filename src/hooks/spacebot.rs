@@ -1,5 +1,6 @@
 //! SpacebotHook: Prompt hook for channels, branches, and workers.
 
+use crate::agent::event_log::{ChannelEvent, EventLog};
 use crate::hooks::loop_guard::{LoopGuard, LoopGuardConfig, LoopGuardVerdict};
 use crate::tools::{MemoryPersistenceContractState, MemoryPersistenceTerminalOutcome};
 use crate::{AgentId, ChannelId, ProcessEvent, ProcessId, ProcessType};
@@ -61,6 +62,8 @@ pub struct SpacebotHook {
     /// append the messages to history before re-prompting.
     injected_messages: std::sync::Arc<std::sync::Mutex<Vec<String>>>,
     memory_persistence_contract: Option<Arc<MemoryPersistenceContractState>>,
+    /// Ordered record of responses and tool results for offline replay.
+    event_log: Option<EventLog>,
 }
 
 impl SpacebotHook {
@@ -115,6 +118,7 @@ impl SpacebotHook {
             inject_rx: None,
             injected_messages: std::sync::Arc::new(std::sync::Mutex::new(Vec::new())),
             memory_persistence_contract: None,
+            event_log: None,
         }
     }
 
@@ -142,6 +146,12 @@ impl SpacebotHook {
         self
     }
 
+    /// Record LLM responses and tool results to a channel event log.
+    pub fn with_event_log(mut self, event_log: EventLog) -> Self {
+        self.event_log = Some(event_log);
+        self
+    }
+
     /// Return the current tool nudge policy for this hook.
     pub fn tool_nudge_policy(&self) -> ToolNudgePolicy {
         self.tool_nudge_policy
@@ -712,6 +722,24 @@ where
             "completion response received"
         );
 
+        if let Some(event_log) = &self.event_log {
+            event_log
+                .record(ChannelEvent::from_completion(&response.choice))
+                .await;
+        }
+
+        if self.process_type == ProcessType::Channel
+            && let Some(channel_id) = self.channel_id.clone()
+        {
+            let event = ProcessEvent::ChannelUsage {
+                agent_id: self.agent_id.clone(),
+                channel_id,
+                input_tokens: response.usage.input_tokens,
+                output_tokens: response.usage.output_tokens,
+            };
+            self.event_tx.send(event).ok();
+        }
+
         if self.should_nudge_tool_usage::<M>(response) {
             return HookAction::Terminate {
                 reason: Self::TOOL_NUDGE_REASON.into(),
@@ -857,9 +885,24 @@ where
         tool_name: &str,
         _tool_call_id: Option<String>,
         internal_call_id: &str,
-        _args: &str,
+        args: &str,
         result: &str,
     ) -> HookAction {
+        if let Some(event_log) = &self.event_log {
+            let args = crate::secrets::scrub::scrub_leaks(args);
+            let result = crate::secrets::scrub::scrub_leaks(result);
+            event_log
+                .record(ChannelEvent::ToolResult {
+                    tool_name: tool_name.to_string(),
+                    args: crate::tools::truncate_output(&args, crate::tools::MAX_TOOL_OUTPUT_BYTES),
+                    result: crate::tools::truncate_output(
+                        &result,
+                        crate::tools::MAX_TOOL_OUTPUT_BYTES,
+                    ),
+                })
+                .await;
+        }
+
         let guard_action = self.guard_tool_result(tool_name, result);
         if !matches!(guard_action, HookAction::Continue) {
             self.record_tool_result_metrics(tool_name, internal_call_id);
@@ -913,7 +956,7 @@ where
         // the same call produces the same result repeatedly, and poisons the
         // call hash so the next check() in on_tool_call auto-blocks.
         if let Ok(mut guard) = self.loop_guard.lock() {
-            guard.record_outcome(tool_name, _args, result);
+            guard.record_outcome(tool_name, args, result);
         }
 
         let is_tool_error = result.starts_with("Toolset error:");
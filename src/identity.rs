@@ -1,5 +1,6 @@
 //! Identity file loading (SOUL.md, IDENTITY.md, ROLE.md).
 
 pub mod files;
+pub mod git;
 
 pub use files::{Identity, scaffold_identity_files};
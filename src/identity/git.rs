@@ -0,0 +1,260 @@
+//! Workspace history: a git repository in the agent root that versions
+//! identity files and workspace skills.
+//!
+//! Enabled per agent with `workspace_git = true`. The repository is created on
+//! first use with a `.gitignore` that excludes everything except SOUL.md,
+//! IDENTITY.md, ROLE.md, and `workspace/skills/`, so databases, archives, and
+//! worker scratch files never end up in history. Every identity or skill
+//! change made through the API or agent tools is committed with the actor in
+//! the message, and the API can restore the tracked files to any past commit.
+
+use std::path::Path;
+
+use anyhow::Context as _;
+use serde::Serialize;
+use tokio::process::Command;
+
+/// Only identity files and workspace skills are versioned.
+const GITIGNORE: &str = "\
+# Managed by spacebot: only identity files and workspace skills are versioned.
+/*
+!/.gitignore
+!/SOUL.md
+!/IDENTITY.md
+!/ROLE.md
+!/workspace/
+/workspace/*
+!/workspace/skills/
+";
+
+/// Committer identity for workspace commits. The acting user or process is
+/// recorded in the message instead.
+const COMMITTER_NAME: &str = "spacebot";
+const COMMITTER_EMAIL: &str = "spacebot@localhost";
+
+/// One commit in the workspace history.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct WorkspaceCommit {
+    pub hash: String,
+    /// Who made the change: `api`, `agent`, `factory`, and so on.
+    pub actor: Option<String>,
+    pub summary: String,
+    pub committed_at: chrono::DateTime<chrono::Utc>,
+    /// Tracked files the commit touched.
+    pub files: Vec<String>,
+}
+
+/// Create the repository with its `.gitignore` and an initial commit of the
+/// current files, if it doesn't exist yet.
+pub async fn ensure_repo(root: &Path) -> anyhow::Result<()> {
+    if root.join(".git").exists() {
+        return Ok(());
+    }
+
+    run_git(root, &["init", "--quiet"]).await?;
+    tokio::fs::write(root.join(".gitignore"), GITIGNORE)
+        .await
+        .with_context(|| format!("failed to write .gitignore in {}", root.display()))?;
+    commit(root, "system", "Start workspace history").await?;
+    Ok(())
+}
+
+/// Commit all changes to tracked files. Returns the new commit hash, or
+/// `None` if nothing changed.
+pub async fn commit(root: &Path, actor: &str, summary: &str) -> anyhow::Result<Option<String>> {
+    ensure_repo_exists(root).await?;
+    run_git(root, &["add", "--all"]).await?;
+
+    let staged = Command::new("git")
+        .args(["diff", "--cached", "--quiet"])
+        .current_dir(root)
+        .status()
+        .await
+        .context("failed to run `git diff --cached`")?;
+    if staged.success() {
+        return Ok(None);
+    }
+
+    let message = format!("[{actor}] {summary}");
+    run_git(
+        root,
+        &[
+            "-c",
+            &format!("user.name={COMMITTER_NAME}"),
+            "-c",
+            &format!("user.email={COMMITTER_EMAIL}"),
+            "commit",
+            "--quiet",
+            "--no-verify",
+            "-m",
+            &message,
+        ],
+    )
+    .await?;
+    let hash = run_git(root, &["rev-parse", "HEAD"]).await?;
+    Ok(Some(hash.trim().to_string()))
+}
+
+/// Most recent commits, newest first.
+pub async fn log(root: &Path, limit: usize) -> anyhow::Result<Vec<WorkspaceCommit>> {
+    if !root.join(".git").exists() {
+        return Ok(Vec::new());
+    }
+
+    let output = run_git(
+        root,
+        &[
+            "log",
+            &format!("--max-count={limit}"),
+            "--name-only",
+            "--format=%x1e%H%x1f%ct%x1f%s",
+        ],
+    )
+    .await?;
+    Ok(parse_log(&output))
+}
+
+/// Restore the tracked files to how they were at `commit_hash` and record
+/// that as a new commit. Returns the new commit hash, or `None` if the
+/// files already match.
+pub async fn restore(
+    root: &Path,
+    commit_hash: &str,
+    actor: &str,
+) -> anyhow::Result<Option<String>> {
+    if commit_hash.len() < 7 || !commit_hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        anyhow::bail!("'{commit_hash}' is not a commit hash");
+    }
+    ensure_repo_exists(root).await?;
+
+    let resolved = run_git(
+        root,
+        &[
+            "rev-parse",
+            "--verify",
+            &format!("{commit_hash}^{{commit}}"),
+        ],
+    )
+    .await
+    .with_context(|| format!("unknown commit {commit_hash}"))?;
+    let resolved = resolved.trim();
+
+    // Make the index and working tree match the commit exactly, removing
+    // tracked files it didn't have.
+    run_git(root, &["read-tree", "-u", "--reset", resolved]).await?;
+    commit(
+        root,
+        actor,
+        &format!("Restore workspace to {}", &resolved[..7]),
+    )
+    .await
+}
+
+async fn ensure_repo_exists(root: &Path) -> anyhow::Result<()> {
+    if !root.join(".git").exists() {
+        anyhow::bail!("workspace history is not initialized in {}", root.display());
+    }
+    Ok(())
+}
+
+async fn run_git(root: &Path, args: &[&str]) -> anyhow::Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(root)
+        .output()
+        .await
+        .with_context(|| format!("failed to run `git {}`", args.join(" ")))?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "`git {}` failed in {}: {}",
+            args.first().copied().unwrap_or_default(),
+            root.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Parse `git log --name-only` output where each commit starts with a record
+/// separator and its header fields are unit-separated.
+fn parse_log(output: &str) -> Vec<WorkspaceCommit> {
+    output
+        .split('\u{1e}')
+        .filter_map(|record| {
+            let mut lines = record.lines();
+            let header = lines.next()?;
+            let mut fields = header.split('\u{1f}');
+            let hash = fields.next()?.to_string();
+            let timestamp: i64 = fields.next()?.parse().ok()?;
+            let subject = fields.next().unwrap_or_default();
+            let (actor, summary) = match subject
+                .strip_prefix('[')
+                .and_then(|rest| rest.split_once("] "))
+            {
+                Some((actor, summary)) => (Some(actor.to_string()), summary.to_string()),
+                None => (None, subject.to_string()),
+            };
+            Some(WorkspaceCommit {
+                hash,
+                actor,
+                summary,
+                committed_at: chrono::DateTime::from_timestamp(timestamp, 0).unwrap_or_default(),
+                files: lines
+                    .filter(|line| !line.trim().is_empty())
+                    .map(str::to_string)
+                    .collect(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn commits_identity_changes_and_restores_them() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path();
+        tokio::fs::write(root.join("SOUL.md"), "curious")
+            .await
+            .unwrap();
+        tokio::fs::create_dir_all(root.join("data")).await.unwrap();
+        tokio::fs::write(root.join("data/spacebot.db"), "binary")
+            .await
+            .unwrap();
+        ensure_repo(root).await.unwrap();
+
+        tokio::fs::write(root.join("SOUL.md"), "terse")
+            .await
+            .unwrap();
+        tokio::fs::write(root.join("ROLE.md"), "support")
+            .await
+            .unwrap();
+        let edit = commit(root, "api", "Update SOUL.md, ROLE.md")
+            .await
+            .unwrap()
+            .expect("changes should commit");
+        assert!(commit(root, "api", "no-op").await.unwrap().is_none());
+
+        let history = log(root, 10).await.unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].hash, edit);
+        assert_eq!(history[0].actor.as_deref(), Some("api"));
+        assert_eq!(history[0].files, ["ROLE.md", "SOUL.md"]);
+        assert!(
+            history
+                .iter()
+                .all(|c| !c.files.iter().any(|f| f.starts_with("data/")))
+        );
+
+        let initial = &history[1].hash;
+        restore(root, initial, "api").await.unwrap();
+        let soul = tokio::fs::read_to_string(root.join("SOUL.md"))
+            .await
+            .unwrap();
+        assert_eq!(soul, "curious");
+        assert!(!root.join("ROLE.md").exists());
+        assert!(root.join("data/spacebot.db").exists());
+    }
+}
@@ -0,0 +1,382 @@
+//! Issue trackers (Jira, Linear) behind a common trait.
+//!
+//! Each agent lists its trackers under `[[defaults.issue_trackers]]` or
+//! `[[agents.issue_trackers]]`, with an allowlist of projects (Jira project
+//! keys, Linear team keys). Reads go straight to the provider; mutations are
+//! described as an [`IssueMutation`] and only run once approved through
+//! [`crate::approvals`].
+
+pub mod jira;
+pub mod linear;
+
+use crate::config::{IssueTrackerConfig, IssueTrackerProvider};
+use crate::error::Result;
+
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+
+use std::sync::Arc;
+
+/// Upper bound on search results per call.
+pub const MAX_SEARCH_RESULTS: usize = 50;
+
+/// An issue as listed in search results.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct IssueSummary {
+    /// Human-facing key, e.g. `OPS-42`.
+    pub key: String,
+    pub title: String,
+    pub status: String,
+    pub assignee: Option<String>,
+    pub url: Option<String>,
+}
+
+/// A single issue with its description and comments.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Issue {
+    #[serde(flatten)]
+    pub summary: IssueSummary,
+    pub description: Option<String>,
+    pub comments: Vec<IssueComment>,
+    /// Statuses the issue can move to next, when the provider reports them.
+    pub available_transitions: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssueComment {
+    pub author: Option<String>,
+    pub body: String,
+    pub created_at: Option<String>,
+}
+
+/// A change to a tracker, held for approval before it runs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum IssueMutation {
+    Create {
+        tracker: String,
+        project: String,
+        title: String,
+        description: Option<String>,
+    },
+    Comment {
+        tracker: String,
+        key: String,
+        body: String,
+    },
+    Transition {
+        tracker: String,
+        key: String,
+        status: String,
+    },
+}
+
+impl IssueMutation {
+    pub fn tracker(&self) -> &str {
+        match self {
+            Self::Create { tracker, .. }
+            | Self::Comment { tracker, .. }
+            | Self::Transition { tracker, .. } => tracker,
+        }
+    }
+
+    /// The project the mutation touches, for the allowlist check.
+    pub fn project(&self) -> Option<&str> {
+        match self {
+            Self::Create { project, .. } => Some(project),
+            Self::Comment { key, .. } | Self::Transition { key, .. } => project_of(key),
+        }
+    }
+
+    /// One-line description shown to whoever approves it.
+    pub fn summary(&self) -> String {
+        match self {
+            Self::Create {
+                tracker,
+                project,
+                title,
+                ..
+            } => format!("Create issue in {tracker}/{project}: {title}"),
+            Self::Comment { tracker, key, body } => {
+                let preview: String = body.chars().take(120).collect();
+                format!("Comment on {tracker}/{key}: {preview}")
+            }
+            Self::Transition {
+                tracker,
+                key,
+                status,
+            } => format!("Move {tracker}/{key} to \"{status}\""),
+        }
+    }
+}
+
+/// Operations every tracker backend supports.
+#[async_trait::async_trait]
+pub trait IssueTracker: Send + Sync {
+    /// Full-text search restricted to `projects`.
+    async fn search(
+        &self,
+        query: &str,
+        projects: &[String],
+        limit: usize,
+    ) -> Result<Vec<IssueSummary>>;
+
+    async fn get(&self, key: &str) -> Result<Issue>;
+
+    async fn create(
+        &self,
+        project: &str,
+        title: &str,
+        description: Option<&str>,
+    ) -> Result<IssueSummary>;
+
+    async fn comment(&self, key: &str, body: &str) -> Result<()>;
+
+    /// Move an issue to the status named `status` (case-insensitive).
+    async fn transition(&self, key: &str, status: &str) -> Result<IssueSummary>;
+}
+
+/// Project (or team) part of an issue key: `OPS` for `OPS-42`.
+pub fn project_of(key: &str) -> Option<&str> {
+    let (project, number) = key.rsplit_once('-')?;
+    (!project.is_empty() && !number.is_empty() && number.bytes().all(|b| b.is_ascii_digit()))
+        .then_some(project)
+}
+
+/// One configured tracker with its allowlist.
+pub struct ConfiguredTracker {
+    pub name: String,
+    pub provider: IssueTrackerProvider,
+    pub projects: Vec<String>,
+    backend: Arc<dyn IssueTracker>,
+}
+
+impl ConfiguredTracker {
+    pub fn from_config(config: &IssueTrackerConfig, client: reqwest::Client) -> Self {
+        let backend: Arc<dyn IssueTracker> = match config.provider {
+            IssueTrackerProvider::Jira => Arc::new(jira::JiraTracker::new(config, client)),
+            IssueTrackerProvider::Linear => Arc::new(linear::LinearTracker::new(config, client)),
+        };
+        Self {
+            name: config.name.clone(),
+            provider: config.provider,
+            projects: config.projects.clone(),
+            backend,
+        }
+    }
+
+    pub fn allows(&self, project: &str) -> bool {
+        self.projects
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(project))
+    }
+
+    fn check_key(&self, key: &str) -> Result<()> {
+        match project_of(key) {
+            Some(project) if self.allows(project) => Ok(()),
+            Some(project) => Err(anyhow!(
+                "project '{project}' is not in the allowlist for tracker '{}'",
+                self.name
+            )
+            .into()),
+            None => Err(anyhow!("'{key}' is not an issue key like PROJ-123").into()),
+        }
+    }
+
+    /// Search within the allowlist, or within `project` if it's allowed.
+    pub async fn search(
+        &self,
+        query: &str,
+        project: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<IssueSummary>> {
+        let projects = match project {
+            Some(project) if self.allows(project) => vec![project.to_uppercase()],
+            Some(project) => {
+                return Err(anyhow!(
+                    "project '{project}' is not in the allowlist for tracker '{}'",
+                    self.name
+                )
+                .into());
+            }
+            None => self.projects.clone(),
+        };
+        let limit = limit.clamp(1, MAX_SEARCH_RESULTS);
+        self.backend.search(query, &projects, limit).await
+    }
+
+    pub async fn get(&self, key: &str) -> Result<Issue> {
+        self.check_key(key)?;
+        self.backend.get(key).await
+    }
+
+    /// Run an approved mutation. The allowlist is checked again here since
+    /// the config may have changed while the request waited.
+    pub async fn apply(&self, mutation: &IssueMutation) -> Result<serde_json::Value> {
+        match mutation {
+            IssueMutation::Create {
+                project,
+                title,
+                description,
+                ..
+            } => {
+                if !self.allows(project) {
+                    return Err(anyhow!(
+                        "project '{project}' is not in the allowlist for tracker '{}'",
+                        self.name
+                    )
+                    .into());
+                }
+                let issue = self
+                    .backend
+                    .create(project, title, description.as_deref())
+                    .await?;
+                Ok(serde_json::to_value(issue).unwrap_or_default())
+            }
+            IssueMutation::Comment { key, body, .. } => {
+                self.check_key(key)?;
+                self.backend.comment(key, body).await?;
+                Ok(serde_json::json!({ "key": key, "commented": true }))
+            }
+            IssueMutation::Transition { key, status, .. } => {
+                self.check_key(key)?;
+                let issue = self.backend.transition(key, status).await?;
+                Ok(serde_json::to_value(issue).unwrap_or_default())
+            }
+        }
+    }
+}
+
+/// The trackers configured for one agent.
+pub struct IssueTrackers {
+    trackers: Vec<ConfiguredTracker>,
+}
+
+impl IssueTrackers {
+    pub fn new(configs: &[IssueTrackerConfig]) -> Self {
+        let client = reqwest::Client::builder()
+            .gzip(true)
+            .timeout(std::time::Duration::from_secs(30))
+            .build()
+            .expect("hardcoded reqwest client config");
+        Self {
+            trackers: configs
+                .iter()
+                .map(|config| ConfiguredTracker::from_config(config, client.clone()))
+                .collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.trackers.is_empty()
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.trackers
+            .iter()
+            .map(|tracker| tracker.name.as_str())
+            .collect()
+    }
+
+    /// Look up a tracker by name. `None` picks the only tracker when exactly
+    /// one is configured.
+    pub fn get(&self, name: Option<&str>) -> Result<&ConfiguredTracker> {
+        match name {
+            Some(name) => self
+                .trackers
+                .iter()
+                .find(|tracker| tracker.name == name)
+                .ok_or_else(|| {
+                    anyhow!(
+                        "unknown issue tracker '{name}' (configured: {})",
+                        self.names().join(", ")
+                    )
+                    .into()
+                }),
+            None => match self.trackers.as_slice() {
+                [only] => Ok(only),
+                [] => Err(anyhow!("no issue trackers are configured").into()),
+                _ => Err(anyhow!(
+                    "several issue trackers are configured, pass `tracker` (one of: {})",
+                    self.names().join(", ")
+                )
+                .into()),
+            },
+        }
+    }
+
+    /// Check a mutation against the config without running it.
+    pub fn validate(&self, mutation: &IssueMutation) -> Result<()> {
+        let tracker = self.get(Some(mutation.tracker()))?;
+        match mutation.project() {
+            Some(project) if tracker.allows(project) => Ok(()),
+            Some(project) => Err(anyhow!(
+                "project '{project}' is not in the allowlist for tracker '{}'",
+                tracker.name
+            )
+            .into()),
+            None => Err(anyhow!("mutation does not name a valid issue key").into()),
+        }
+    }
+
+    pub async fn apply(&self, mutation: &IssueMutation) -> Result<serde_json::Value> {
+        self.get(Some(mutation.tracker()))?.apply(mutation).await
+    }
+}
+
+/// Map an HTTP error response to an error carrying the provider's message.
+async fn response_error(provider: &str, response: reqwest::Response) -> crate::Error {
+    let status = response.status();
+    let body = response.text().await.unwrap_or_default();
+    let body: String = body.chars().take(500).collect();
+    anyhow!("{provider} request failed with {status}: {body}").into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(name: &str, projects: &[&str]) -> IssueTrackerConfig {
+        IssueTrackerConfig {
+            name: name.into(),
+            provider: IssueTrackerProvider::Linear,
+            base_url: None,
+            email: None,
+            api_token: "token".into(),
+            projects: projects.iter().map(|project| project.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn mutations_are_checked_against_the_allowlist() {
+        assert_eq!(project_of("OPS-42"), Some("OPS"));
+        assert_eq!(project_of("MY-TEAM-7"), Some("MY-TEAM"));
+        assert_eq!(project_of("OPS"), None);
+        assert_eq!(project_of("OPS-4a"), None);
+
+        let trackers = IssueTrackers::new(&[config("work", &["OPS"])]);
+        assert_eq!(trackers.get(None).unwrap().name, "work");
+
+        let comment = |key: &str| IssueMutation::Comment {
+            tracker: "work".into(),
+            key: key.into(),
+            body: "on it".into(),
+        };
+        assert!(trackers.validate(&comment("ops-1")).is_ok());
+        assert!(trackers.validate(&comment("SEC-1")).is_err());
+        assert!(trackers.validate(&comment("nonsense")).is_err());
+        assert!(
+            trackers
+                .validate(&IssueMutation::Create {
+                    tracker: "home".into(),
+                    project: "OPS".into(),
+                    title: "x".into(),
+                    description: None,
+                })
+                .is_err()
+        );
+
+        let encoded = serde_json::to_value(comment("OPS-1")).unwrap();
+        assert_eq!(encoded["action"], "comment");
+    }
+}
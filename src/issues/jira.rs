@@ -0,0 +1,292 @@
+//! Jira Cloud backend (REST API v3, basic auth with an API token).
+
+use super::{Issue, IssueComment, IssueSummary, IssueTracker, response_error};
+use crate::config::IssueTrackerConfig;
+use crate::error::Result;
+
+use anyhow::{Context as _, anyhow};
+use serde_json::{Value, json};
+
+const SEARCH_FIELDS: &str = "summary,status,assignee";
+
+pub struct JiraTracker {
+    client: reqwest::Client,
+    base_url: String,
+    email: String,
+    api_token: String,
+}
+
+impl JiraTracker {
+    pub fn new(config: &IssueTrackerConfig, client: reqwest::Client) -> Self {
+        Self {
+            client,
+            base_url: config.base_url.clone().unwrap_or_default(),
+            email: config.email.clone().unwrap_or_default(),
+            api_token: config.api_token.clone(),
+        }
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/rest/api/3{path}", self.base_url)
+    }
+
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<reqwest::Response> {
+        let response = request
+            .basic_auth(&self.email, Some(&self.api_token))
+            .header(reqwest::header::ACCEPT, "application/json")
+            .send()
+            .await
+            .context("jira request failed")?;
+        if !response.status().is_success() {
+            return Err(response_error("jira", response).await);
+        }
+        Ok(response)
+    }
+
+    async fn send_json(&self, request: reqwest::RequestBuilder) -> Result<Value> {
+        let response = self.send(request).await?;
+        Ok(response
+            .json()
+            .await
+            .context("invalid jira response body")?)
+    }
+
+    fn summary(&self, issue: &Value) -> IssueSummary {
+        let key = issue["key"].as_str().unwrap_or_default().to_string();
+        let fields = &issue["fields"];
+        IssueSummary {
+            url: Some(format!("{}/browse/{key}", self.base_url)),
+            key,
+            title: fields["summary"].as_str().unwrap_or_default().to_string(),
+            status: fields["status"]["name"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+            assignee: fields["assignee"]["displayName"]
+                .as_str()
+                .map(str::to_string),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl IssueTracker for JiraTracker {
+    async fn search(
+        &self,
+        query: &str,
+        projects: &[String],
+        limit: usize,
+    ) -> Result<Vec<IssueSummary>> {
+        let jql = search_jql(query, projects);
+        let body = self
+            .send_json(self.client.post(self.url("/search/jql")).json(&json!({
+                "jql": jql,
+                "maxResults": limit,
+                "fields": SEARCH_FIELDS.split(',').collect::<Vec<_>>(),
+            })))
+            .await?;
+        Ok(body["issues"]
+            .as_array()
+            .map(|issues| issues.iter().map(|issue| self.summary(issue)).collect())
+            .unwrap_or_default())
+    }
+
+    async fn get(&self, key: &str) -> Result<Issue> {
+        let issue = self
+            .send_json(
+                self.client
+                    .get(self.url(&format!("/issue/{key}")))
+                    .query(&[("fields", "summary,status,assignee,description,comment")]),
+            )
+            .await?;
+        let transitions = self
+            .send_json(
+                self.client
+                    .get(self.url(&format!("/issue/{key}/transitions"))),
+            )
+            .await?;
+
+        let fields = &issue["fields"];
+        let comments = fields["comment"]["comments"]
+            .as_array()
+            .map(|comments| {
+                comments
+                    .iter()
+                    .map(|comment| IssueComment {
+                        author: comment["author"]["displayName"]
+                            .as_str()
+                            .map(str::to_string),
+                        body: adf_to_text(&comment["body"]),
+                        created_at: comment["created"].as_str().map(str::to_string),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(Issue {
+            summary: self.summary(&issue),
+            description: Some(adf_to_text(&fields["description"])).filter(|text| !text.is_empty()),
+            comments,
+            available_transitions: transition_names(&transitions),
+        })
+    }
+
+    async fn create(
+        &self,
+        project: &str,
+        title: &str,
+        description: Option<&str>,
+    ) -> Result<IssueSummary> {
+        let mut fields = json!({
+            "project": { "key": project },
+            "summary": title,
+            "issuetype": { "name": "Task" },
+        });
+        if let Some(description) = description {
+            fields["description"] = text_to_adf(description);
+        }
+        let created = self
+            .send_json(
+                self.client
+                    .post(self.url("/issue"))
+                    .json(&json!({ "fields": fields })),
+            )
+            .await?;
+        let key = created["key"]
+            .as_str()
+            .ok_or_else(|| anyhow!("jira did not return the new issue key"))?;
+        Ok(self.get(key).await?.summary)
+    }
+
+    async fn comment(&self, key: &str, body: &str) -> Result<()> {
+        self.send(
+            self.client
+                .post(self.url(&format!("/issue/{key}/comment")))
+                .json(&json!({ "body": text_to_adf(body) })),
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn transition(&self, key: &str, status: &str) -> Result<IssueSummary> {
+        let transitions = self
+            .send_json(
+                self.client
+                    .get(self.url(&format!("/issue/{key}/transitions"))),
+            )
+            .await?;
+        let transition_id = transitions["transitions"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .find(|transition| {
+                [&transition["name"], &transition["to"]["name"]]
+                    .iter()
+                    .any(|name| {
+                        name.as_str()
+                            .is_some_and(|name| name.eq_ignore_ascii_case(status))
+                    })
+            })
+            .and_then(|transition| transition["id"].as_str())
+            .ok_or_else(|| {
+                anyhow!(
+                    "{key} cannot move to \"{status}\" (available: {})",
+                    transition_names(&transitions).join(", ")
+                )
+            })?
+            .to_string();
+
+        self.send(
+            self.client
+                .post(self.url(&format!("/issue/{key}/transitions")))
+                .json(&json!({ "transition": { "id": transition_id } })),
+        )
+        .await?;
+        Ok(self.get(key).await?.summary)
+    }
+}
+
+fn search_jql(query: &str, projects: &[String]) -> String {
+    let projects = projects
+        .iter()
+        .map(|project| format!("\"{}\"", escape_jql(project)))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut jql = format!("project in ({projects})");
+    if !query.trim().is_empty() {
+        jql.push_str(&format!(" AND text ~ \"{}\"", escape_jql(query.trim())));
+    }
+    jql.push_str(" ORDER BY updated DESC");
+    jql
+}
+
+fn escape_jql(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn transition_names(transitions: &Value) -> Vec<String> {
+    transitions["transitions"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|transition| transition["to"]["name"].as_str().map(str::to_string))
+        .collect()
+}
+
+/// Plain text as an Atlassian Document Format document, one paragraph per
+/// line.
+fn text_to_adf(text: &str) -> Value {
+    let content: Vec<Value> = text
+        .lines()
+        .map(|line| {
+            if line.is_empty() {
+                json!({ "type": "paragraph", "content": [] })
+            } else {
+                json!({ "type": "paragraph", "content": [{ "type": "text", "text": line }] })
+            }
+        })
+        .collect();
+    json!({ "type": "doc", "version": 1, "content": content })
+}
+
+/// Flatten an Atlassian Document Format node to plain text.
+fn adf_to_text(node: &Value) -> String {
+    fn walk(node: &Value, out: &mut String) {
+        if let Some(text) = node["text"].as_str() {
+            out.push_str(text);
+        }
+        if node["type"] == "hardBreak" {
+            out.push('\n');
+        }
+        if let Some(children) = node["content"].as_array() {
+            for child in children {
+                walk(child, out);
+            }
+        }
+        if matches!(
+            node["type"].as_str(),
+            Some("paragraph" | "heading" | "listItem" | "codeBlock" | "blockquote")
+        ) {
+            out.push('\n');
+        }
+    }
+
+    let mut out = String::new();
+    walk(node, &mut out);
+    out.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jql_is_scoped_and_escaped() {
+        let jql = search_jql(r#"login "broken""#, &["OPS".into(), "WEB".into()]);
+        assert_eq!(
+            jql,
+            r#"project in ("OPS", "WEB") AND text ~ "login \"broken\"" ORDER BY updated DESC"#
+        );
+        assert_eq!(adf_to_text(&text_to_adf("first\nsecond")), "first\nsecond");
+    }
+}
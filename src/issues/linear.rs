@@ -0,0 +1,237 @@
+//! Linear backend (GraphQL API, personal API key).
+//!
+//! Linear's "projects" in the allowlist are team keys, which prefix issue
+//! identifiers (`ENG-123`).
+
+use super::{Issue, IssueComment, IssueSummary, IssueTracker, response_error};
+use crate::config::IssueTrackerConfig;
+use crate::error::Result;
+
+use anyhow::{Context as _, anyhow};
+use serde_json::{Value, json};
+
+const LINEAR_GRAPHQL_URL: &str = "https://api.linear.app/graphql";
+
+const ISSUE_FIELDS: &str = "identifier title url state { name } assignee { name }";
+
+pub struct LinearTracker {
+    client: reqwest::Client,
+    api_token: String,
+}
+
+impl LinearTracker {
+    pub fn new(config: &IssueTrackerConfig, client: reqwest::Client) -> Self {
+        Self {
+            client,
+            api_token: config.api_token.clone(),
+        }
+    }
+
+    async fn query(&self, query: &str, variables: Value) -> Result<Value> {
+        let response = self
+            .client
+            .post(LINEAR_GRAPHQL_URL)
+            .header(reqwest::header::AUTHORIZATION, &self.api_token)
+            .json(&json!({ "query": query, "variables": variables }))
+            .send()
+            .await
+            .context("linear request failed")?;
+        if !response.status().is_success() {
+            return Err(response_error("linear", response).await);
+        }
+        let mut body: Value = response
+            .json()
+            .await
+            .context("invalid linear response body")?;
+        if let Some(errors) = body["errors"]
+            .as_array()
+            .filter(|errors| !errors.is_empty())
+        {
+            let messages: Vec<&str> = errors
+                .iter()
+                .filter_map(|error| error["message"].as_str())
+                .collect();
+            return Err(anyhow!("linear returned errors: {}", messages.join("; ")).into());
+        }
+        Ok(body["data"].take())
+    }
+
+    /// Team id and workflow states for the team with key `team_key`.
+    async fn team(&self, team_key: &str) -> Result<Value> {
+        let data = self
+            .query(
+                "query($key: String!) { teams(filter: { key: { eqIgnoreCase: $key } }) { \
+                 nodes { id key states { nodes { id name } } } } }",
+                json!({ "key": team_key }),
+            )
+            .await?;
+        data["teams"]["nodes"]
+            .as_array()
+            .and_then(|teams| teams.first())
+            .cloned()
+            .ok_or_else(|| anyhow!("linear team '{team_key}' not found").into())
+    }
+}
+
+fn summary(issue: &Value) -> IssueSummary {
+    IssueSummary {
+        key: issue["identifier"].as_str().unwrap_or_default().to_string(),
+        title: issue["title"].as_str().unwrap_or_default().to_string(),
+        status: issue["state"]["name"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string(),
+        assignee: issue["assignee"]["name"].as_str().map(str::to_string),
+        url: issue["url"].as_str().map(str::to_string),
+    }
+}
+
+#[async_trait::async_trait]
+impl IssueTracker for LinearTracker {
+    async fn search(
+        &self,
+        query: &str,
+        projects: &[String],
+        limit: usize,
+    ) -> Result<Vec<IssueSummary>> {
+        let mut filter = json!({ "team": { "key": { "in": projects } } });
+        if !query.trim().is_empty() {
+            filter["or"] = json!([
+                { "title": { "containsIgnoreCase": query.trim() } },
+                { "description": { "containsIgnoreCase": query.trim() } },
+            ]);
+        }
+        let data = self
+            .query(
+                &format!(
+                    "query($filter: IssueFilter, $first: Int) {{ \
+                     issues(filter: $filter, first: $first, orderBy: updatedAt) {{ \
+                     nodes {{ {ISSUE_FIELDS} }} }} }}"
+                ),
+                json!({ "filter": filter, "first": limit }),
+            )
+            .await?;
+        Ok(data["issues"]["nodes"]
+            .as_array()
+            .map(|issues| issues.iter().map(summary).collect())
+            .unwrap_or_default())
+    }
+
+    async fn get(&self, key: &str) -> Result<Issue> {
+        let data = self
+            .query(
+                &format!(
+                    "query($id: String!) {{ issue(id: $id) {{ {ISSUE_FIELDS} description \
+                     comments {{ nodes {{ body createdAt user {{ name }} }} }} \
+                     team {{ states {{ nodes {{ name }} }} }} }} }}"
+                ),
+                json!({ "id": key }),
+            )
+            .await?;
+        let issue = &data["issue"];
+        if issue.is_null() {
+            return Err(anyhow!("linear issue '{key}' not found").into());
+        }
+
+        let comments = issue["comments"]["nodes"]
+            .as_array()
+            .map(|comments| {
+                comments
+                    .iter()
+                    .map(|comment| IssueComment {
+                        author: comment["user"]["name"].as_str().map(str::to_string),
+                        body: comment["body"].as_str().unwrap_or_default().to_string(),
+                        created_at: comment["createdAt"].as_str().map(str::to_string),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let current = issue["state"]["name"].as_str();
+        let available_transitions = issue["team"]["states"]["nodes"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|state| state["name"].as_str())
+            .filter(|name| Some(*name) != current)
+            .map(str::to_string)
+            .collect();
+
+        Ok(Issue {
+            summary: summary(issue),
+            description: issue["description"].as_str().map(str::to_string),
+            comments,
+            available_transitions,
+        })
+    }
+
+    async fn create(
+        &self,
+        project: &str,
+        title: &str,
+        description: Option<&str>,
+    ) -> Result<IssueSummary> {
+        let team = self.team(project).await?;
+        let data = self
+            .query(
+                &format!(
+                    "mutation($input: IssueCreateInput!) {{ issueCreate(input: $input) {{ \
+                     success issue {{ {ISSUE_FIELDS} }} }} }}"
+                ),
+                json!({ "input": {
+                    "teamId": team["id"],
+                    "title": title,
+                    "description": description,
+                } }),
+            )
+            .await?;
+        Ok(summary(&data["issueCreate"]["issue"]))
+    }
+
+    async fn comment(&self, key: &str, body: &str) -> Result<()> {
+        self.query(
+            "mutation($input: CommentCreateInput!) { commentCreate(input: $input) { success } }",
+            json!({ "input": { "issueId": key, "body": body } }),
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn transition(&self, key: &str, status: &str) -> Result<IssueSummary> {
+        let team_key = super::project_of(key)
+            .ok_or_else(|| anyhow!("'{key}' is not a Linear issue identifier"))?;
+        let team = self.team(team_key).await?;
+        let states = team["states"]["nodes"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default();
+        let state_id = states
+            .iter()
+            .find(|state| {
+                state["name"]
+                    .as_str()
+                    .is_some_and(|name| name.eq_ignore_ascii_case(status))
+            })
+            .and_then(|state| state["id"].as_str())
+            .ok_or_else(|| {
+                let names: Vec<&str> = states
+                    .iter()
+                    .filter_map(|state| state["name"].as_str())
+                    .collect();
+                anyhow!(
+                    "{key} cannot move to \"{status}\" (available: {})",
+                    names.join(", ")
+                )
+            })?;
+
+        let data = self
+            .query(
+                &format!(
+                    "mutation($id: String!, $input: IssueUpdateInput!) {{ \
+                     issueUpdate(id: $id, input: $input) {{ success issue {{ {ISSUE_FIELDS} }} }} }}"
+                ),
+                json!({ "id": key, "input": { "stateId": state_id } }),
+            )
+            .await?;
+        Ok(summary(&data["issueUpdate"]["issue"]))
+    }
+}
@@ -0,0 +1,349 @@
+//! DB-lease leader election for singleton duties.
+//!
+//! When several daemon replicas share an instance directory, each agent's
+//! SQLite database holds a single lease row. Whichever replica holds the lease
+//! runs that agent's singleton subsystems (cron, bulletins, maintenance,
+//! associations, ready-task pickup); the others keep serving traffic but skip
+//! those duties until the lease expires and they win it. A holder whose
+//! renewal stalls stops acting as leader shortly before its lease could
+//! expire for the others. Startup warmup still runs on every replica so each
+//! has a bulletin to prompt with.
+
+use crate::error::Result;
+
+use serde::Serialize;
+use sqlx::{Row as _, SqlitePool};
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+/// Lease row name inside each agent database.
+const AGENT_LEASE_NAME: &str = "agent_singletons";
+
+/// Identifier of this process among replicas. `SPACEBOT_INSTANCE_ID`
+/// overrides the generated `<hostname>-<pid>-<random>` value.
+static INSTANCE_ID: LazyLock<String> = LazyLock::new(|| {
+    std::env::var("SPACEBOT_INSTANCE_ID")
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| {
+            let hostname = std::env::var("HOSTNAME")
+                .ok()
+                .filter(|value| !value.is_empty())
+                .unwrap_or_else(|| "spacebot".into());
+            let suffix = uuid::Uuid::new_v4().simple().to_string();
+            format!("{hostname}-{}-{}", std::process::id(), &suffix[..8])
+        })
+});
+
+/// This replica's identifier.
+pub fn instance_id() -> &'static str {
+    &INSTANCE_ID
+}
+
+/// A lease on an agent's singleton duties.
+#[derive(Debug)]
+pub struct LeaderLease {
+    /// `None` when election is disabled; the lease is then always held.
+    pool: Option<SqlitePool>,
+    lease_duration: Duration,
+    is_leader: AtomicBool,
+    /// When the lease last acquired runs out by this replica's clock, less a
+    /// safety margin. Leadership lapses here even if renewal is stuck, since
+    /// another replica may take the expired lease.
+    held_until: Mutex<Option<Instant>>,
+    /// Set on shutdown so renewal stops re-acquiring a released lease.
+    released: AtomicBool,
+}
+
+/// Snapshot of a lease for status reporting.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct LeaderStatus {
+    pub election_enabled: bool,
+    pub is_leader: bool,
+    /// Replica currently holding the lease, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub holder_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl LeaderLease {
+    /// A lease that is always held. Used when election is disabled.
+    pub fn disabled() -> Arc<Self> {
+        Arc::new(Self {
+            pool: None,
+            lease_duration: Duration::ZERO,
+            is_leader: AtomicBool::new(true),
+            held_until: Mutex::new(None),
+            released: AtomicBool::new(false),
+        })
+    }
+
+    /// A lease contended through the given agent database. Starts as a
+    /// follower until the first acquisition attempt succeeds.
+    pub fn new(pool: SqlitePool, lease_duration: Duration) -> Arc<Self> {
+        Arc::new(Self {
+            pool: Some(pool),
+            lease_duration: lease_duration.max(Duration::from_secs(3)),
+            is_leader: AtomicBool::new(false),
+            held_until: Mutex::new(None),
+            released: AtomicBool::new(false),
+        })
+    }
+
+    /// Build a lease from config: contended when election is enabled,
+    /// always held otherwise.
+    pub fn from_config(
+        pool: &SqlitePool,
+        config: &crate::config::LeaderElectionConfig,
+    ) -> Arc<Self> {
+        if config.enabled {
+            Self::new(pool.clone(), Duration::from_secs(config.lease_secs))
+        } else {
+            Self::disabled()
+        }
+    }
+
+    /// Whether this replica holds the lease. False once the lease's local
+    /// deadline passes without a renewal.
+    pub fn is_leader(&self) -> bool {
+        if !self.is_leader.load(Ordering::Acquire) {
+            return false;
+        }
+        if self.pool.is_none() {
+            return true;
+        }
+        let lapsed = self
+            .held_until
+            .lock()
+            .expect("lease deadline lock poisoned")
+            .is_none_or(|held_until| Instant::now() >= held_until);
+        if lapsed && self.is_leader.swap(false, Ordering::AcqRel) {
+            tracing::warn!(
+                instance_id = instance_id(),
+                "singleton lease lapsed before it could be renewed"
+            );
+        }
+        !lapsed
+    }
+
+    /// Margin taken off the local deadline so leadership lapses before the
+    /// lease can expire for other replicas, allowing for clock drift.
+    fn safety_margin(&self) -> Duration {
+        self.lease_duration / 5
+    }
+
+    /// Acquire or renew the lease. Succeeds when this replica already holds
+    /// it or the current holder's lease has expired.
+    pub async fn try_acquire(&self) -> Result<bool> {
+        let Some(pool) = &self.pool else {
+            return Ok(true);
+        };
+        if self.released.load(Ordering::Acquire) {
+            return Ok(false);
+        }
+
+        // Taken before the write, so the deadline never outlives the lease.
+        let started = Instant::now();
+        let now = chrono::Utc::now();
+        #[cfg(feature = "chaos")]
+        let now = crate::chaos::skew(now);
+        let now = now.timestamp_millis();
+        let expires_at = now + self.lease_duration.as_millis() as i64;
+        let result = sqlx::query(
+            "INSERT INTO leader_leases (name, holder_id, acquired_at, expires_at) \
+             VALUES (?, ?, ?, ?) \
+             ON CONFLICT(name) DO UPDATE SET \
+               acquired_at = CASE WHEN leader_leases.holder_id = excluded.holder_id \
+                 THEN leader_leases.acquired_at ELSE excluded.acquired_at END, \
+               holder_id = excluded.holder_id, \
+               expires_at = excluded.expires_at \
+             WHERE leader_leases.holder_id = excluded.holder_id \
+                OR leader_leases.expires_at < ?",
+        )
+        .bind(AGENT_LEASE_NAME)
+        .bind(instance_id())
+        .bind(now)
+        .bind(expires_at)
+        .bind(now)
+        .execute(pool)
+        .await
+        .map_err(|e| anyhow::anyhow!(e))?;
+
+        let acquired = result.rows_affected() > 0;
+        *self
+            .held_until
+            .lock()
+            .expect("lease deadline lock poisoned") =
+            acquired.then(|| started + self.lease_duration - self.safety_margin());
+        let was_leader = self.is_leader.swap(acquired, Ordering::AcqRel);
+        if acquired && !was_leader {
+            tracing::info!(instance_id = instance_id(), "acquired singleton lease");
+        } else if !acquired && was_leader {
+            tracing::warn!(instance_id = instance_id(), "lost singleton lease");
+        }
+        Ok(acquired)
+    }
+
+    /// Give up the lease so another replica can take over without waiting
+    /// for expiry.
+    pub async fn release(&self) {
+        let Some(pool) = &self.pool else {
+            return;
+        };
+        self.released.store(true, Ordering::Release);
+        self.is_leader.store(false, Ordering::Release);
+        *self
+            .held_until
+            .lock()
+            .expect("lease deadline lock poisoned") = None;
+        if let Err(error) =
+            sqlx::query("DELETE FROM leader_leases WHERE name = ? AND holder_id = ?")
+                .bind(AGENT_LEASE_NAME)
+                .bind(instance_id())
+                .execute(pool)
+                .await
+        {
+            tracing::warn!(%error, "failed to release singleton lease");
+        }
+    }
+
+    /// Current lease state, including which replica holds it.
+    pub async fn status(&self) -> LeaderStatus {
+        let Some(pool) = &self.pool else {
+            return LeaderStatus {
+                election_enabled: false,
+                is_leader: true,
+                holder_id: Some(instance_id().to_string()),
+                expires_at: None,
+            };
+        };
+
+        let row = sqlx::query("SELECT holder_id, expires_at FROM leader_leases WHERE name = ?")
+            .bind(AGENT_LEASE_NAME)
+            .fetch_optional(pool)
+            .await
+            .ok()
+            .flatten();
+        let holder_id = row
+            .as_ref()
+            .and_then(|row| row.try_get::<String, _>("holder_id").ok());
+        let expires_at = row
+            .as_ref()
+            .and_then(|row| row.try_get::<i64, _>("expires_at").ok())
+            .and_then(chrono::DateTime::from_timestamp_millis);
+
+        LeaderStatus {
+            election_enabled: true,
+            is_leader: self.is_leader(),
+            holder_id,
+            expires_at,
+        }
+    }
+
+    /// Keep trying to acquire and renew the lease at a third of its
+    /// duration. A database error drops leadership until the next success,
+    /// since a replica that can't renew can't prove it still holds the lease.
+    pub fn spawn_renewal(self: &Arc<Self>) -> Option<tokio::task::JoinHandle<()>> {
+        self.pool.as_ref()?;
+        let lease = self.clone();
+        Some(tokio::spawn(async move {
+            let period = lease.lease_duration / 3;
+            while !lease.released.load(Ordering::Acquire) {
+                if let Err(error) = lease.try_acquire().await {
+                    tracing::warn!(%error, "singleton lease renewal failed");
+                    if lease.is_leader.swap(false, Ordering::AcqRel) {
+                        tracing::warn!(instance_id = instance_id(), "lost singleton lease");
+                    }
+                }
+                tokio::time::sleep(period).await;
+            }
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn setup_pool() -> SqlitePool {
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("failed to create sqlite memory pool");
+        sqlx::migrate!("./migrations")
+            .run(&pool)
+            .await
+            .expect("migrations");
+        pool
+    }
+
+    #[tokio::test]
+    async fn disabled_lease_is_always_held() {
+        let lease = LeaderLease::disabled();
+        assert!(lease.is_leader());
+        assert!(lease.try_acquire().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn live_lease_held_by_another_replica_blocks_acquisition() {
+        let pool = setup_pool().await;
+        let far_future = chrono::Utc::now().timestamp_millis() + 60_000;
+        sqlx::query("INSERT INTO leader_leases VALUES (?, 'other-replica', 0, ?)")
+            .bind(AGENT_LEASE_NAME)
+            .bind(far_future)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let lease = LeaderLease::new(pool.clone(), Duration::from_secs(30));
+        assert!(!lease.try_acquire().await.unwrap());
+        assert!(!lease.is_leader());
+
+        sqlx::query("UPDATE leader_leases SET expires_at = 0")
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert!(lease.try_acquire().await.unwrap());
+        assert!(lease.is_leader());
+        assert_eq!(
+            lease.status().await.holder_id.as_deref(),
+            Some(instance_id())
+        );
+    }
+
+    #[tokio::test]
+    async fn leadership_lapses_when_renewal_stalls_past_the_deadline() {
+        let pool = setup_pool().await;
+        let lease = LeaderLease::new(pool, Duration::from_secs(30));
+        assert!(lease.try_acquire().await.unwrap());
+        assert!(lease.is_leader());
+
+        // No renewal before the local deadline: another replica may have the
+        // lease by now.
+        *lease.held_until.lock().unwrap() = Some(Instant::now() - Duration::from_millis(1));
+        assert!(!lease.is_leader());
+
+        assert!(lease.try_acquire().await.unwrap(), "renewal restores it");
+        assert!(lease.is_leader());
+    }
+
+    #[tokio::test]
+    async fn release_lets_another_replica_take_over() {
+        let pool = setup_pool().await;
+        let lease = LeaderLease::new(pool.clone(), Duration::from_secs(30));
+        assert!(lease.try_acquire().await.unwrap());
+        assert!(lease.try_acquire().await.unwrap(), "renewal by holder");
+
+        lease.release().await;
+        assert!(!lease.is_leader());
+        let remaining: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM leader_leases")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(remaining, 0);
+    }
+}
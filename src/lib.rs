@@ -2,8 +2,13 @@
 
 pub mod agent;
 pub mod api;
+pub mod approvals;
 pub mod auth;
+pub mod calc;
+#[cfg(feature = "chaos")]
+pub mod chaos;
 pub mod config;
+pub mod contacts;
 pub mod conversation;
 pub mod cron;
 pub mod daemon;
@@ -13,25 +18,33 @@ pub mod factory;
 pub mod github_copilot_auth;
 pub mod hooks;
 pub mod identity;
+pub mod issues;
+pub mod leader;
 pub mod links;
 pub mod llm;
+pub mod log_stream;
 pub mod mcp;
 pub mod memory;
 pub mod messaging;
+pub mod notifications;
 pub mod openai_auth;
 pub mod opencode;
 pub mod projects;
 pub mod prompts;
+pub mod quota;
 pub mod sandbox;
 pub mod secrets;
 pub mod self_awareness;
 pub mod settings;
+pub mod simulate;
 pub mod skills;
 pub mod tasks;
 #[cfg(feature = "metrics")]
 pub mod telemetry;
 pub mod tools;
+pub mod translation;
 pub mod update;
+pub mod weather;
 
 pub use error::{Error, Result};
 
@@ -292,6 +305,13 @@ pub enum ProcessEvent {
         text_delta: String,
         aggregated_text: String,
     },
+    /// Token usage of one channel completion.
+    ChannelUsage {
+        agent_id: AgentId,
+        channel_id: ChannelId,
+        input_tokens: u64,
+        output_tokens: u64,
+    },
     /// A cortex chat auto-triggered turn completed (e.g. after a worker delivered
     /// its result). The frontend appends this message to the cortex chat panel.
     CortexChatUpdate {
@@ -409,6 +429,15 @@ pub struct AgentDeps {
     /// Sender for injecting messages into channels from outside the normal
     /// inbound message flow (e.g. cross-agent task completion notifications).
     pub injection_tx: tokio::sync::mpsc::Sender<ChannelInjection>,
+    /// Lease deciding whether this replica runs the agent's singleton duties
+    /// (cron, bulletins, maintenance). Always held unless leader election is
+    /// enabled.
+    pub leader: Arc<leader::LeaderLease>,
+    /// Task, LLM, and database budgets for this agent.
+    pub quota: Arc<quota::AgentQuota>,
+    /// Dedicated runtime, when the agent is configured for one. `None` runs
+    /// on the main runtime.
+    pub runtime: Option<Arc<agent::runtime::AgentRuntime>>,
 }
 
 impl AgentDeps {
@@ -423,6 +452,26 @@ impl AgentDeps {
     pub fn routing(&self) -> arc_swap::Guard<Arc<llm::RoutingConfig>> {
         self.runtime_config.routing.load()
     }
+
+    /// Spawn a task on this agent's runtime. Tasks it spawns stay there.
+    pub fn spawn<F>(&self, future: F) -> tokio::task::JoinHandle<F::Output>
+    where
+        F: std::future::Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        match &self.runtime {
+            Some(runtime) => runtime.handle().spawn(future),
+            None => tokio::spawn(future),
+        }
+    }
+
+    /// Make this agent's runtime current, so `tokio::spawn` calls made while
+    /// the guard is held land on it. Must not be held across an `.await`.
+    pub fn enter_runtime(&self) -> Option<tokio::runtime::EnterGuard<'_>> {
+        self.runtime
+            .as_ref()
+            .map(|runtime| runtime.handle().enter())
+    }
 }
 
 /// A running agent instance with all its isolated resources.
@@ -546,6 +595,11 @@ pub enum MessageContent {
         /// Platform-specific message reference (`ts` on Slack, message ID on Discord).
         message_ts: Option<String>,
     },
+    /// A native slash command invocation with structured arguments.
+    ///
+    /// Produced by Discord and Slack. Displays as the command the user would have
+    /// typed, so text-based command handling sees the same thing.
+    Command(crate::messaging::commands::SlashCommand),
 }
 
 impl std::fmt::Display for MessageContent {
@@ -573,6 +627,7 @@ impl std::fmt::Display for MessageContent {
                     write!(f, "[interaction: {}]", action_id)
                 }
             }
+            MessageContent::Command(command) => write!(f, "{}", command),
         }
     }
 }
@@ -600,6 +655,9 @@ pub struct Attachment {
 pub struct RoutedResponse {
     pub response: OutboundResponse,
     pub target: InboundMessage,
+    /// ID of the conversation message this response delivers, when it was
+    /// logged. The outbox records delivery status under it.
+    pub message_id: Option<String>,
 }
 
 /// A sender that automatically pairs outbound responses with a captured
@@ -619,11 +677,22 @@ impl RoutedSender {
     pub async fn send(
         &self,
         response: OutboundResponse,
+    ) -> std::result::Result<(), mpsc::error::SendError<RoutedResponse>> {
+        self.send_logged(response, None).await
+    }
+
+    /// Send a response that delivers the logged conversation message
+    /// `message_id`.
+    pub async fn send_logged(
+        &self,
+        response: OutboundResponse,
+        message_id: Option<String>,
     ) -> std::result::Result<(), mpsc::error::SendError<RoutedResponse>> {
         self.inner
             .send(RoutedResponse {
                 response,
                 target: self.target.clone(),
+                message_id,
             })
             .await
     }
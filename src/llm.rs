@@ -1,11 +1,14 @@
 //! LLM provider management and routing.
 
 pub mod anthropic;
+pub mod limits;
 pub mod manager;
 pub mod model;
 pub mod pricing;
 pub mod providers;
 pub mod routing;
+pub mod smart_router;
+pub mod usage;
 
 pub use manager::LlmManager;
 pub use model::SpacebotModel;
@@ -0,0 +1,53 @@
+//! Known context window sizes.
+//!
+//! Used to sanity-check `context_window` against the models an agent routes
+//! to. Only families with a well-known, stable limit are listed; anything
+//! else returns `None` and is not checked.
+
+/// Context window in tokens for a model, if known. Matches on the last
+/// path segment so "anthropic/claude-sonnet-4" and
+/// "openrouter/anthropic/claude-sonnet-4" both match.
+pub fn context_limit(model_name: &str) -> Option<usize> {
+    let model = model_name.rsplit('/').next().unwrap_or(model_name);
+
+    match model {
+        m if m.starts_with("claude-") => Some(200_000),
+
+        m if m.starts_with("gpt-5") => Some(400_000),
+        m if m.starts_with("gpt-4.1") => Some(1_047_576),
+        m if m.starts_with("gpt-4o") || m.starts_with("gpt-4-turbo") => Some(128_000),
+        m if m.starts_with("gpt-4-32k") => Some(32_768),
+        m if m == "gpt-4" || m.starts_with("gpt-4-0") => Some(8_192),
+        m if m.starts_with("gpt-3.5-turbo") => Some(16_385),
+        m if m.starts_with("o1-mini") => Some(128_000),
+        m if m.starts_with("o1") || m.starts_with("o3") || m.starts_with("o4-mini") => {
+            Some(200_000)
+        }
+
+        m if m.starts_with("gemini-1.5")
+            || m.starts_with("gemini-2.0")
+            || m.starts_with("gemini-2.5") =>
+        {
+            Some(1_048_576)
+        }
+
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::context_limit;
+
+    #[test]
+    fn matches_on_the_model_segment() {
+        assert_eq!(context_limit("anthropic/claude-sonnet-4"), Some(200_000));
+        assert_eq!(
+            context_limit("openrouter/anthropic/claude-haiku-4.5"),
+            Some(200_000)
+        );
+        assert_eq!(context_limit("openai/gpt-4o-mini"), Some(128_000));
+        assert_eq!(context_limit("openai/gpt-4"), Some(8_192));
+        assert_eq!(context_limit("ollama/llama3"), None);
+    }
+}
@@ -44,6 +44,10 @@ pub struct LlmManager {
     openai_oauth_credentials: RwLock<Option<OpenAiOAuthCredentials>>,
     /// Cached GitHub Copilot API token (exchanged from PAT, refreshed lazily).
     copilot_token: RwLock<Option<CopilotToken>>,
+    /// Agent databases that completions record their usage into.
+    usage_pools: ArcSwap<HashMap<String, sqlx::SqlitePool>>,
+    /// Per-agent quotas bounding concurrent completions.
+    quotas: ArcSwap<HashMap<String, Arc<crate::quota::AgentQuota>>>,
 }
 
 impl LlmManager {
@@ -62,6 +66,8 @@ impl LlmManager {
             anthropic_oauth_credentials: RwLock::new(None),
             openai_oauth_credentials: RwLock::new(None),
             copilot_token: RwLock::new(None),
+            usage_pools: ArcSwap::from_pointee(HashMap::new()),
+            quotas: ArcSwap::from_pointee(HashMap::new()),
         })
     }
 
@@ -142,6 +148,8 @@ impl LlmManager {
             anthropic_oauth_credentials: RwLock::new(anthropic_oauth_credentials),
             openai_oauth_credentials: RwLock::new(openai_oauth_credentials),
             copilot_token: RwLock::new(copilot_token),
+            usage_pools: ArcSwap::from_pointee(HashMap::new()),
+            quotas: ArcSwap::from_pointee(HashMap::new()),
         })
     }
 
@@ -162,6 +170,26 @@ impl LlmManager {
             .ok_or_else(|| LlmError::UnknownProvider(provider_id.to_string()).into())
     }
 
+    /// Whether credentials for a provider are available. Only consults config
+    /// and cached OAuth credentials, so it never refreshes tokens or touches
+    /// the network.
+    pub async fn has_provider_credentials(&self, provider_id: &str) -> bool {
+        let normalized_provider_id = provider_id.to_lowercase();
+        if self
+            .config
+            .load()
+            .providers
+            .contains_key(&normalized_provider_id)
+        {
+            return true;
+        }
+        match normalized_provider_id.as_str() {
+            "anthropic" => self.anthropic_oauth_credentials.read().await.is_some(),
+            "openai-chatgpt" => self.openai_oauth_credentials.read().await.is_some(),
+            _ => false,
+        }
+    }
+
     /// Get the appropriate API key for a provider, with OAuth override for Anthropic.
     ///
     /// If OAuth credentials are available and the provider is Anthropic,
@@ -434,6 +462,80 @@ impl LlmManager {
         self.config.load().ollama_base_url.clone()
     }
 
+    /// Record completions made for `agent_id` into its database.
+    pub fn register_usage_pool(&self, agent_id: &str, pool: sqlx::SqlitePool) {
+        self.usage_pools.rcu(|pools| {
+            let mut pools = HashMap::clone(pools);
+            pools.insert(agent_id.to_string(), pool.clone());
+            pools
+        });
+    }
+
+    pub fn unregister_usage_pool(&self, agent_id: &str) {
+        self.usage_pools.rcu(|pools| {
+            let mut pools = HashMap::clone(pools);
+            pools.remove(agent_id);
+            pools
+        });
+    }
+
+    /// Queue completions made for `agent_id` behind its LLM quota.
+    pub fn register_quota(&self, agent_id: &str, quota: Arc<crate::quota::AgentQuota>) {
+        self.quotas.rcu(|quotas| {
+            let mut quotas = HashMap::clone(quotas);
+            quotas.insert(agent_id.to_string(), quota.clone());
+            quotas
+        });
+    }
+
+    pub fn unregister_quota(&self, agent_id: &str) {
+        self.quotas.rcu(|quotas| {
+            let mut quotas = HashMap::clone(quotas);
+            quotas.remove(agent_id);
+            quotas
+        });
+    }
+
+    pub fn quota(&self, agent_id: &str) -> Option<Arc<crate::quota::AgentQuota>> {
+        self.quotas.load().get(agent_id).cloned()
+    }
+
+    /// Persist one completion's usage in the background. No-op for agents
+    /// without a registered database and for calls that reported no tokens.
+    pub fn record_usage(
+        &self,
+        agent_id: &str,
+        process_type: &str,
+        model: &str,
+        usage: &rig::completion::Usage,
+    ) {
+        if usage.input_tokens == 0 && usage.output_tokens == 0 {
+            return;
+        }
+        let Some(pool) = self.usage_pools.load().get(agent_id).cloned() else {
+            return;
+        };
+        let record = crate::llm::usage::UsageRecord {
+            process_type: process_type.to_string(),
+            model: model.to_string(),
+            input_tokens: usage.input_tokens,
+            output_tokens: usage.output_tokens,
+            cached_input_tokens: usage.cached_input_tokens,
+            estimated_cost: crate::llm::pricing::estimate_cost(
+                model,
+                usage.input_tokens,
+                usage.output_tokens,
+                usage.cached_input_tokens,
+            ),
+        };
+        let agent_id = agent_id.to_string();
+        tokio::spawn(async move {
+            if let Err(error) = crate::llm::usage::record(&pool, &record).await {
+                tracing::warn!(%error, %agent_id, "failed to record LLM usage");
+            }
+        });
+    }
+
     /// Get the HTTP client.
     pub fn http_client(&self) -> &reqwest::Client {
         &self.http_client
@@ -129,6 +129,11 @@ impl SpacebotModel {
         &self,
         request: CompletionRequest,
     ) -> Result<completion::CompletionResponse<RawResponse>, CompletionError> {
+        #[cfg(feature = "chaos")]
+        crate::chaos::llm_fault(&self.full_model_name)
+            .await
+            .map_err(CompletionError::ProviderError)?;
+
         let provider_config = self.provider_config_for_current_model().await?;
 
         match provider_config.api_type {
@@ -278,6 +283,17 @@ impl CompletionModel for SpacebotModel {
         &self,
         request: CompletionRequest,
     ) -> Result<completion::CompletionResponse<RawResponse>, CompletionError> {
+        // Wait for the agent's LLM quota before the request (and its retries)
+        // starts, so queueing time doesn't count toward request latency.
+        let _llm_permit = match self
+            .agent_id
+            .as_deref()
+            .and_then(|agent_id| self.llm_manager.quota(agent_id))
+        {
+            Some(quota) => Some(quota.acquire(crate::quota::QuotaResource::Llm).await),
+            None => None,
+        };
+
         #[cfg(feature = "metrics")]
         let start = std::time::Instant::now();
 
@@ -486,6 +502,17 @@ impl CompletionModel for SpacebotModel {
             }
         }
 
+        if let Ok(response) = &result
+            && let Some(agent_id) = &self.agent_id
+        {
+            self.llm_manager.record_usage(
+                agent_id,
+                self.process_type.as_deref().unwrap_or("unknown"),
+                &self.full_model_name,
+                &response.usage,
+            );
+        }
+
         result
     }
 
@@ -1,6 +1,8 @@
 //! Model routing configuration and resolution.
 
 use crate::ProcessType;
+use crate::llm::smart_router::SmartRouting;
+
 use std::collections::HashMap;
 
 /// Model routing configuration. Lives on the agent config (via defaults).
@@ -32,6 +34,9 @@ pub struct RoutingConfig {
     pub worker_thinking_effort: String,
     pub compactor_thinking_effort: String,
     pub cortex_thinking_effort: String,
+
+    /// Per-turn choice between a fast and a strong channel model.
+    pub smart: SmartRouting,
 }
 
 impl Default for RoutingConfig {
@@ -58,6 +63,7 @@ impl RoutingConfig {
             worker_thinking_effort: "auto".into(),
             compactor_thinking_effort: "auto".into(),
             cortex_thinking_effort: "auto".into(),
+            smart: SmartRouting::default(),
         }
     }
 }
@@ -0,0 +1,331 @@
+//! Per-turn model choice between a fast and a strong model.
+//!
+//! When `[defaults.routing.smart]` is enabled, each channel turn is rated
+//! before the LLM call: short chit-chat goes to the fast model, turns with
+//! code, attachments, long or multi-part requests, or work that will likely
+//! need tools go to the strong one. Rating is done by heuristics, or by a
+//! one-shot call to the fast model with `classifier = "model"`. A channel can
+//! pin either model with `/model fast` or `/model strong`.
+
+use crate::error::Result;
+use crate::llm::{LlmManager, RoutingConfig, SpacebotModel};
+use crate::prompts::PromptEngine;
+
+use anyhow::Context as _;
+use rig::agent::AgentBuilder;
+use rig::completion::{CompletionModel, TypedPrompt};
+use serde::{Deserialize, Serialize};
+
+use std::sync::Arc;
+
+/// Smart routing settings, part of [`RoutingConfig`].
+#[derive(Debug, Clone)]
+pub struct SmartRouting {
+    pub enabled: bool,
+    /// Model for easy turns. Empty uses `routing.worker`.
+    pub fast: String,
+    /// Model for hard turns. Empty uses `routing.channel`.
+    pub strong: String,
+    pub classifier: Classifier,
+    /// Messages at least this long count as hard.
+    pub long_message_chars: usize,
+}
+
+impl Default for SmartRouting {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            fast: String::new(),
+            strong: String::new(),
+            classifier: Classifier::Heuristic,
+            long_message_chars: 600,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Classifier {
+    /// Length, code, attachments, and keyword checks. No extra LLM call.
+    #[default]
+    Heuristic,
+    /// Ask the fast model to rate the turn, falling back to heuristics when
+    /// that fails.
+    Model,
+}
+
+impl std::str::FromStr for Classifier {
+    type Err = String;
+
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        match value {
+            "heuristic" => Ok(Self::Heuristic),
+            "model" => Ok(Self::Model),
+            other => Err(format!(
+                "unknown smart routing classifier '{other}', expected \"heuristic\" or \"model\""
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Tier {
+    Fast,
+    Strong,
+}
+
+impl std::fmt::Display for Tier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Fast => write!(f, "fast"),
+            Self::Strong => write!(f, "strong"),
+        }
+    }
+}
+
+impl std::str::FromStr for Tier {
+    type Err = String;
+
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        match value {
+            "fast" => Ok(Self::Fast),
+            "strong" => Ok(Self::Strong),
+            other => Err(format!("unknown model tier '{other}'")),
+        }
+    }
+}
+
+/// What is known about a turn before it runs.
+#[derive(Debug, Clone, Copy)]
+pub struct TurnSignals<'a> {
+    pub text: &'a str,
+    pub has_attachments: bool,
+    /// Relaying branch or worker results back to the user.
+    pub is_retrigger: bool,
+}
+
+/// The model picked for a turn and why.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteDecision {
+    pub tier: Tier,
+    pub model: String,
+    pub reason: String,
+}
+
+/// Words that usually mean the turn needs research, tools, or reasoning.
+const HARD_KEYWORDS: &[&str] = &[
+    "analyze",
+    "analyse",
+    "compare",
+    "debug",
+    "design",
+    "explain why",
+    "implement",
+    "investigate",
+    "plan",
+    "refactor",
+    "research",
+    "review",
+    "step by step",
+    "summarize",
+    "write a",
+    "write an",
+];
+
+impl SmartRouting {
+    pub fn fast_model<'a>(&'a self, routing: &'a RoutingConfig) -> &'a str {
+        if self.fast.is_empty() {
+            &routing.worker
+        } else {
+            &self.fast
+        }
+    }
+
+    pub fn strong_model<'a>(&'a self, routing: &'a RoutingConfig) -> &'a str {
+        if self.strong.is_empty() {
+            &routing.channel
+        } else {
+            &self.strong
+        }
+    }
+
+    /// Rate a turn without calling a model.
+    pub fn classify_heuristic(&self, signals: &TurnSignals<'_>) -> (Tier, &'static str) {
+        if signals.is_retrigger {
+            return (Tier::Fast, "relaying background results");
+        }
+        if signals.has_attachments {
+            return (Tier::Strong, "attachments");
+        }
+        let text = signals.text.trim();
+        if text.chars().count() >= self.long_message_chars {
+            return (Tier::Strong, "long message");
+        }
+        if looks_like_code(text) {
+            return (Tier::Strong, "code");
+        }
+        let lower = text.to_lowercase();
+        if HARD_KEYWORDS.iter().any(|keyword| lower.contains(keyword)) {
+            return (Tier::Strong, "likely needs tools or reasoning");
+        }
+        if text.matches('?').count() > 1
+            || text.lines().filter(|line| !line.trim().is_empty()).count() > 3
+        {
+            return (Tier::Strong, "multi-part request");
+        }
+        (Tier::Fast, "short conversational turn")
+    }
+
+    /// Pick the model for a turn. `pinned` is the channel's override, which
+    /// skips classification.
+    pub async fn route(
+        &self,
+        routing: &RoutingConfig,
+        llm_manager: &Arc<LlmManager>,
+        prompts: &PromptEngine,
+        signals: &TurnSignals<'_>,
+        pinned: Option<Tier>,
+    ) -> RouteDecision {
+        let (tier, reason) = if let Some(tier) = pinned {
+            (tier, "pinned for this channel".to_string())
+        } else if self.classifier == Classifier::Model && !signals.is_retrigger {
+            match self
+                .classify_with_model(routing, llm_manager, prompts, signals)
+                .await
+            {
+                Ok(tier) => (tier, "rated by classifier model".to_string()),
+                Err(error) => {
+                    tracing::warn!(%error, "smart routing classifier failed, using heuristics");
+                    let (tier, reason) = self.classify_heuristic(signals);
+                    (tier, reason.to_string())
+                }
+            }
+        } else {
+            let (tier, reason) = self.classify_heuristic(signals);
+            (tier, reason.to_string())
+        };
+
+        let model = match tier {
+            Tier::Fast => self.fast_model(routing),
+            Tier::Strong => self.strong_model(routing),
+        };
+        RouteDecision {
+            tier,
+            model: model.to_string(),
+            reason,
+        }
+    }
+
+    async fn classify_with_model(
+        &self,
+        routing: &RoutingConfig,
+        llm_manager: &Arc<LlmManager>,
+        prompts: &PromptEngine,
+        signals: &TurnSignals<'_>,
+    ) -> Result<Tier> {
+        #[derive(Deserialize, schemars::JsonSchema)]
+        struct Rating {
+            /// `fast` or `strong`.
+            tier: String,
+        }
+
+        let model = SpacebotModel::make(llm_manager, self.fast_model(routing))
+            .with_routing(routing.clone());
+        let preamble = prompts.render_static("smart_router")?;
+        let agent = AgentBuilder::new(model).preamble(&preamble).build();
+        let mut prompt = String::new();
+        if signals.has_attachments {
+            prompt.push_str("(The message has attachments.)\n");
+        }
+        prompt.push_str(signals.text);
+        let rating = agent
+            .prompt_typed::<Rating>(&prompt)
+            .await
+            .context("smart routing classifier call failed")?;
+        let tier = rating
+            .tier
+            .trim()
+            .to_lowercase()
+            .parse::<Tier>()
+            .map_err(anyhow::Error::msg)?;
+        Ok(tier)
+    }
+}
+
+/// Fenced blocks, or several lines that read like source code.
+fn looks_like_code(text: &str) -> bool {
+    if text.contains("```") {
+        return true;
+    }
+    let code_lines = text
+        .lines()
+        .filter(|line| {
+            let line = line.trim();
+            line.ends_with(';')
+                || line.ends_with('{')
+                || line.starts_with("fn ")
+                || line.starts_with("def ")
+                || line.starts_with("import ")
+                || line.starts_with("SELECT ")
+                || line.contains("=>")
+        })
+        .count();
+    code_lines >= 2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signals(text: &str) -> TurnSignals<'_> {
+        TurnSignals {
+            text,
+            has_attachments: false,
+            is_retrigger: false,
+        }
+    }
+
+    #[test]
+    fn heuristics_separate_chat_from_work() {
+        let smart = SmartRouting {
+            enabled: true,
+            ..SmartRouting::default()
+        };
+        assert_eq!(smart.classify_heuristic(&signals("thanks!")).0, Tier::Fast);
+        assert_eq!(
+            smart
+                .classify_heuristic(&signals("what time is standup?"))
+                .0,
+            Tier::Fast
+        );
+        assert_eq!(
+            smart
+                .classify_heuristic(&signals("can you research vector databases for us"))
+                .0,
+            Tier::Strong
+        );
+        assert_eq!(
+            smart
+                .classify_heuristic(&signals("why does this fail?\n```\nlet x = 1;\n```"))
+                .0,
+            Tier::Strong
+        );
+        assert_eq!(
+            smart.classify_heuristic(&signals(&"word ".repeat(150))).0,
+            Tier::Strong
+        );
+        let retrigger = TurnSignals {
+            is_retrigger: true,
+            ..signals("research results are in, please analyze")
+        };
+        assert_eq!(smart.classify_heuristic(&retrigger).0, Tier::Fast);
+    }
+
+    #[test]
+    fn empty_models_fall_back_to_routing_slots() {
+        let routing = RoutingConfig::default();
+        let smart = SmartRouting::default();
+        assert_eq!(smart.fast_model(&routing), routing.worker);
+        assert_eq!(smart.strong_model(&routing), routing.channel);
+    }
+}
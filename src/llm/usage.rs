@@ -0,0 +1,90 @@
+//! Persistent per-call token usage and cost estimates.
+//!
+//! Every completion made on behalf of an agent appends a row to that agent's
+//! `llm_usage` table. Reporting rolls the rows up per day, process type, and
+//! model.
+
+use anyhow::Context as _;
+use serde::Serialize;
+use sqlx::{Row as _, SqlitePool};
+
+/// One completion's usage, as recorded.
+#[derive(Debug, Clone)]
+pub struct UsageRecord {
+    /// `channel`, `branch`, `worker`, `compactor`, or `cortex`.
+    pub process_type: String,
+    pub model: String,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cached_input_tokens: u64,
+    pub estimated_cost: f64,
+}
+
+/// Usage summed over one day, process type, and model.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct DailyUsage {
+    /// UTC date, `YYYY-MM-DD`.
+    pub date: String,
+    pub process_type: String,
+    pub model: String,
+    pub calls: i64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cached_input_tokens: i64,
+    pub estimated_cost: f64,
+}
+
+pub async fn record(pool: &SqlitePool, record: &UsageRecord) -> crate::error::Result<()> {
+    sqlx::query(
+        "INSERT INTO llm_usage \
+         (process_type, model, input_tokens, output_tokens, cached_input_tokens, estimated_cost) \
+         VALUES (?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&record.process_type)
+    .bind(&record.model)
+    .bind(record.input_tokens as i64)
+    .bind(record.output_tokens as i64)
+    .bind(record.cached_input_tokens as i64)
+    .bind(record.estimated_cost)
+    .execute(pool)
+    .await
+    .context("failed to record LLM usage")?;
+    Ok(())
+}
+
+/// Daily rollups between `from` and `to` (inclusive, UTC dates), oldest first.
+pub async fn daily_usage(
+    pool: &SqlitePool,
+    from: chrono::NaiveDate,
+    to: chrono::NaiveDate,
+) -> crate::error::Result<Vec<DailyUsage>> {
+    let rows = sqlx::query(
+        "SELECT date(created_at) AS day, process_type, model, COUNT(*) AS calls, \
+                SUM(input_tokens) AS input_tokens, SUM(output_tokens) AS output_tokens, \
+                SUM(cached_input_tokens) AS cached_input_tokens, \
+                SUM(estimated_cost) AS estimated_cost \
+         FROM llm_usage \
+         WHERE date(created_at) BETWEEN ? AND ? \
+         GROUP BY day, process_type, model \
+         ORDER BY day, process_type, model",
+    )
+    .bind(from.to_string())
+    .bind(to.to_string())
+    .fetch_all(pool)
+    .await
+    .context("failed to load LLM usage")?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| DailyUsage {
+            date: row.try_get("day").unwrap_or_default(),
+            process_type: row.try_get("process_type").unwrap_or_default(),
+            model: row.try_get("model").unwrap_or_default(),
+            calls: row.try_get("calls").unwrap_or_default(),
+            input_tokens: row.try_get("input_tokens").unwrap_or_default(),
+            output_tokens: row.try_get("output_tokens").unwrap_or_default(),
+            cached_input_tokens: row.try_get("cached_input_tokens").unwrap_or_default(),
+            estimated_cost: row.try_get("estimated_cost").unwrap_or_default(),
+        })
+        .collect())
+}
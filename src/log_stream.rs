@@ -0,0 +1,203 @@
+//! In-process broadcast of tracing events for live log streaming.
+//!
+//! [`BroadcastLayer`] is installed next to the fmt layer and republishes every
+//! event that passes the global filter as a [`LogRecord`]. `GET
+//! /api/logs/stream` subscribes to it. Nothing is formatted while no client
+//! is listening.
+
+use serde::Serialize;
+use tokio::sync::broadcast;
+use tracing::field::{Field, Visit};
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+
+use std::sync::{Arc, LazyLock};
+
+/// Records buffered per subscriber before it starts lagging.
+const CHANNEL_CAPACITY: usize = 1024;
+
+/// Field values longer than this are cut.
+const MAX_VALUE_CHARS: usize = 2000;
+
+/// Fields carrying whole prompts or tool payloads. Left out like in the
+/// log files.
+const SKIPPED_FIELDS: &[&str] = &[
+    "gen_ai.system_instructions",
+    "gen_ai.tool.call.arguments",
+    "gen_ai.tool.call.result",
+];
+
+static LOG_TX: LazyLock<broadcast::Sender<Arc<LogRecord>>> =
+    LazyLock::new(|| broadcast::channel(CHANNEL_CAPACITY).0);
+
+/// One tracing event.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct LogRecord {
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// `TRACE`, `DEBUG`, `INFO`, `WARN`, or `ERROR`.
+    pub level: String,
+    /// Module path of the emitter, e.g. `spacebot::agent::worker`.
+    pub target: String,
+    pub message: String,
+    /// Structured fields other than the message.
+    #[schema(value_type = Object)]
+    pub fields: serde_json::Map<String, serde_json::Value>,
+    /// Enclosing spans, outermost first.
+    pub spans: Vec<String>,
+}
+
+/// Receive every record emitted from now on.
+pub fn subscribe() -> broadcast::Receiver<Arc<LogRecord>> {
+    LOG_TX.subscribe()
+}
+
+/// Tracing layer feeding [`subscribe`].
+pub struct BroadcastLayer;
+
+impl<S> tracing_subscriber::Layer<S> for BroadcastLayer
+where
+    S: tracing::Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_event(&self, event: &tracing::Event<'_>, ctx: Context<'_, S>) {
+        if LOG_TX.receiver_count() == 0 {
+            return;
+        }
+
+        let metadata = event.metadata();
+        let mut visitor = RecordVisitor::default();
+        event.record(&mut visitor);
+        let spans = ctx
+            .event_scope(event)
+            .map(|scope| {
+                scope
+                    .from_root()
+                    .map(|span| span.name().to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let _ = LOG_TX.send(Arc::new(LogRecord {
+            timestamp: chrono::Utc::now(),
+            level: metadata.level().to_string(),
+            target: metadata.target().to_string(),
+            message: visitor.message,
+            fields: visitor.fields,
+            spans,
+        }));
+    }
+}
+
+#[derive(Default)]
+struct RecordVisitor {
+    message: String,
+    fields: serde_json::Map<String, serde_json::Value>,
+}
+
+impl RecordVisitor {
+    fn insert(&mut self, field: &Field, value: serde_json::Value) {
+        if SKIPPED_FIELDS.contains(&field.name()) {
+            return;
+        }
+        self.fields.insert(field.name().to_string(), value);
+    }
+}
+
+impl Visit for RecordVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        let value = truncate(value);
+        if field.name() == "message" {
+            self.message = value;
+        } else {
+            self.insert(field, value.into());
+        }
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.insert(field, value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.record_str(field, &format!("{value:?}"));
+    }
+}
+
+fn truncate(value: &str) -> String {
+    match value.char_indices().nth(MAX_VALUE_CHARS) {
+        Some((end, _)) => format!("{}...", &value[..end]),
+        None => value.to_string(),
+    }
+}
+
+/// Which records a stream client wants.
+#[derive(Debug, Clone)]
+pub struct LogFilter {
+    /// Least severe level to pass.
+    pub level: tracing::Level,
+    /// Target prefixes; empty passes every target.
+    pub targets: Vec<String>,
+}
+
+impl LogFilter {
+    pub fn matches(&self, record: &LogRecord) -> bool {
+        // `Level` orders by verbosity: ERROR < WARN < ... < TRACE.
+        record
+            .level
+            .parse::<tracing::Level>()
+            .is_ok_and(|level| level <= self.level)
+            && (self.targets.is_empty()
+                || self
+                    .targets
+                    .iter()
+                    .any(|target| target_matches(&record.target, target)))
+    }
+}
+
+/// `prefix` matches itself and its submodules, not sibling names that
+/// happen to share a prefix (`spacebot::agent` doesn't match
+/// `spacebot::agents`).
+fn target_matches(target: &str, prefix: &str) -> bool {
+    target
+        .strip_prefix(prefix)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(level: tracing::Level, target: &str) -> LogRecord {
+        LogRecord {
+            timestamp: chrono::Utc::now(),
+            level: level.to_string(),
+            target: target.into(),
+            message: String::new(),
+            fields: Default::default(),
+            spans: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn filter_applies_level_floor_and_target_prefixes() {
+        let filter = LogFilter {
+            level: tracing::Level::WARN,
+            targets: vec!["spacebot::agent".into()],
+        };
+        assert!(filter.matches(&record(tracing::Level::ERROR, "spacebot::agent::worker")));
+        assert!(filter.matches(&record(tracing::Level::WARN, "spacebot::agent")));
+        assert!(!filter.matches(&record(tracing::Level::INFO, "spacebot::agent::worker")));
+        assert!(!filter.matches(&record(tracing::Level::ERROR, "spacebot::agents")));
+        assert!(!filter.matches(&record(tracing::Level::ERROR, "spacebot::api")));
+    }
+}
@@ -5,7 +5,7 @@ use arc_swap::ArcSwap;
 use clap::{Parser, Subcommand};
 use futures::StreamExt as _;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
 use tokio::sync::mpsc;
 
@@ -52,6 +52,27 @@ enum Command {
     /// Manage secrets stored in the running instance
     #[command(subcommand)]
     Secrets(SecretsCommand),
+    /// Run a scripted conversation against the testbed platform and report
+    /// whether its expectations held. Exits non-zero on failure.
+    Simulate {
+        /// Scenario file (YAML)
+        script: std::path::PathBuf,
+    },
+    /// Step through a channel's recorded event log, reconstructing its state
+    /// after each event. Read-only: no providers, tools, or adapters are used.
+    Replay {
+        /// Channel ID (as shown in the dashboard)
+        channel_id: String,
+        /// Agent ID (defaults to first agent)
+        #[arg(short, long)]
+        agent: Option<String>,
+        /// Pause for Enter after each step
+        #[arg(short, long)]
+        step: bool,
+        /// Stop after this sequence number
+        #[arg(long)]
+        until: Option<i64>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -179,6 +200,7 @@ enum SecretsCommand {
 /// Tracks an active conversation channel and its message sender.
 struct ActiveChannel {
     message_tx: mpsc::Sender<spacebot::InboundMessage>,
+    agent_id: String,
     /// Retained so the outbound routing task stays alive.
     _outbound_handle: tokio::task::JoinHandle<()>,
 }
@@ -313,6 +335,7 @@ async fn route_outbound(
     messaging: &std::sync::Arc<spacebot::messaging::MessagingManager>,
     target: &spacebot::InboundMessage,
     response: spacebot::OutboundResponse,
+    message_id: Option<String>,
 ) {
     match response {
         spacebot::OutboundResponse::Status(status) => {
@@ -321,7 +344,7 @@ async fn route_outbound(
             }
         }
         response => {
-            if let Err(error) = messaging.respond(target, response).await {
+            if let Err(error) = messaging.respond_logged(target, response, message_id).await {
                 tracing::error!(%error, "failed to send outbound response");
             }
         }
@@ -347,6 +370,13 @@ fn main() -> anyhow::Result<()> {
         Command::Skill(skill_cmd) => cmd_skill(cli.config, skill_cmd),
         Command::Auth(auth_cmd) => cmd_auth(cli.config, auth_cmd),
         Command::Secrets(secrets_cmd) => cmd_secrets(cli.config, secrets_cmd),
+        Command::Simulate { script } => cmd_simulate(cli.config, cli.debug, script),
+        Command::Replay {
+            channel_id,
+            agent,
+            step,
+            until,
+        } => cmd_replay(cli.config, channel_id, agent, step, until),
     }
 }
 
@@ -417,10 +447,120 @@ fn cmd_start(
             spacebot::daemon::init_background_tracing(&paths, debug, &config.telemetry)
         };
 
-        run(config, foreground, otel_provider, bootstrapped_store).await
+        run(config, foreground, otel_provider, bootstrapped_store, None).await
     })
 }
 
+/// Boot the full daemon in the foreground with the testbed adapter attached,
+/// play the scenario, and exit with its result.
+fn cmd_simulate(
+    config_path: Option<std::path::PathBuf>,
+    debug: bool,
+    script: std::path::PathBuf,
+) -> anyhow::Result<()> {
+    let scenario = spacebot::simulate::Scenario::from_path(&script)?;
+
+    let instance_dir = resolve_instance_dir(&config_path);
+    let paths = spacebot::daemon::DaemonPaths::new(&instance_dir);
+    if let Some(pid) = spacebot::daemon::is_running(&paths) {
+        eprintln!(
+            "spacebot is running (pid {pid}); stop it before simulating against the same instance"
+        );
+        std::process::exit(1);
+    }
+
+    let bootstrapped_store = bootstrap_secrets_store(&config_path);
+    let config = load_config(&config_path)?;
+
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .context("failed to build Tokio runtime")?;
+
+    runtime.block_on(async {
+        let otel_provider = spacebot::daemon::init_foreground_tracing(debug, &config.telemetry);
+        run(
+            config,
+            true,
+            otel_provider,
+            bootstrapped_store,
+            Some(scenario),
+        )
+        .await
+    })
+}
+
+fn cmd_replay(
+    config_path: Option<std::path::PathBuf>,
+    channel_id: String,
+    agent_id: Option<String>,
+    step: bool,
+    until: Option<i64>,
+) -> anyhow::Result<()> {
+    use spacebot::agent::event_log::{ReplayRole, ReplayState};
+
+    let config = load_config(&config_path)?;
+    let agent_config = get_agent_config(&config, agent_id.as_deref())?;
+    let resolved = agent_config.resolve(&config.instance_dir, &config.defaults);
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .context("failed to build tokio runtime")?;
+
+    let events = runtime.block_on(async {
+        let pool = spacebot::agent::event_log::open_read_only(&resolved.data_dir).await?;
+        let events = spacebot::agent::event_log::load(&pool, &channel_id).await;
+        pool.close().await;
+        events
+    })?;
+    if events.is_empty() {
+        anyhow::bail!("no events recorded for channel '{channel_id}'");
+    }
+
+    let mut state = ReplayState::default();
+    let stdin = std::io::stdin();
+    for logged in events
+        .iter()
+        .take_while(|logged| until.is_none_or(|until| logged.seq <= until))
+    {
+        let before = state.transcript.len();
+        let anomalies = state.apply(&logged.event);
+        println!(
+            "#{} {} {}",
+            logged.seq,
+            logged.created_at,
+            logged.event.kind()
+        );
+        for (role, entry) in &state.transcript[before..] {
+            let label = match role {
+                ReplayRole::User => "user",
+                ReplayRole::Assistant => "assistant",
+                ReplayRole::Tool => "tool",
+            };
+            println!("  {label}: {entry}");
+        }
+        println!(
+            "  state: {} transcript entries, {} LLM turns, pending tool calls [{}]",
+            state.transcript.len(),
+            state.llm_turns,
+            state.pending_tool_calls.join(", ")
+        );
+        if let Some(reply) = &state.last_reply {
+            println!("  last reply: {reply}");
+        }
+        for anomaly in anomalies {
+            println!("  ! {anomaly}");
+        }
+        if step {
+            let mut line = String::new();
+            stdin.read_line(&mut line)?;
+        }
+    }
+
+    Ok(())
+}
+
 /// Resolve the instance directory from the config path without loading the
 /// full config or touching platform credential stores. Used to determine
 /// daemon file paths (PID, socket) before fork.
@@ -1304,6 +1444,9 @@ fn load_config(
 /// Keystore identifier for the instance-level master key.
 const KEYSTORE_INSTANCE_ID: &str = "instance";
 
+/// Inbound messages held per paused agent before the oldest are dropped.
+const MAX_PAUSED_MESSAGES: usize = 1000;
+
 /// Open the instance-level secrets store at `<instance_dir>/data/secrets.redb`
 /// before config loading so that `secret:` references in config.toml resolve.
 ///
@@ -1480,11 +1623,15 @@ async fn run(
     foreground: bool,
     otel_provider: Option<opentelemetry_sdk::trace::SdkTracerProvider>,
     bootstrapped_store: Option<Arc<spacebot::secrets::store::SecretsStore>>,
+    simulation: Option<spacebot::simulate::Scenario>,
 ) -> anyhow::Result<()> {
     let paths = spacebot::daemon::DaemonPaths::new(&config.instance_dir);
 
     tracing::info!("starting spacebot");
     tracing::info!(instance_dir = %config.instance_dir.display(), "configuration loaded");
+    for issue in &config.issues {
+        issue.log();
+    }
 
     // Start the IPC server for stop/status commands
     let (mut shutdown_rx, _ipc_handle) = spacebot::daemon::start_ipc_server(&paths)
@@ -1517,7 +1664,97 @@ async fn run(
         task_store_registry.clone(),
     );
     api_state.auth_token = config.api.auth_token.clone();
+    api_state.api_tokens = config.api.tokens.clone();
+    api_state.readiness = config.api.readiness;
+    api_state.leader_election = config.leader_election;
+    api_state
+        .config_issues
+        .store(Arc::new(config.unresolved_issues()));
+    // Non-fatal: without the store, Idempotency-Key headers are ignored.
+    let idempotency_path = config.instance_dir.join("data").join("idempotency.redb");
+    match spacebot::api::IdempotencyStore::new(
+        &idempotency_path,
+        config.api.idempotency_window_secs,
+    ) {
+        Ok(store) => api_state.idempotency_store = Some(Arc::new(store)),
+        Err(error) => tracing::warn!(
+            path = %idempotency_path.display(),
+            %error,
+            "failed to open idempotency store; Idempotency-Key replay disabled"
+        ),
+    }
+    // Non-fatal: without the store, no webhooks are delivered.
+    let webhooks_path = config.instance_dir.join("data").join("webhooks.redb");
+    match spacebot::api::WebhookRegistry::new(&webhooks_path, &config.api.webhooks) {
+        Ok(registry) => api_state.webhooks = Some(Arc::new(registry)),
+        Err(error) => tracing::warn!(
+            path = %webhooks_path.display(),
+            %error,
+            "failed to open webhook store; outbound webhooks disabled"
+        ),
+    }
+    // Non-fatal: without the store, API mutations go unrecorded.
+    let audit_path = config.instance_dir.join("data").join("audit.redb");
+    match spacebot::api::AuditLog::new(&audit_path) {
+        Ok(audit_log) => api_state.audit_log = Some(Arc::new(audit_log)),
+        Err(error) => tracing::warn!(
+            path = %audit_path.display(),
+            %error,
+            "failed to open audit log; API mutations will not be recorded"
+        ),
+    }
+    // Non-fatal: without the outbox, failed platform sends are not retried.
+    let outbox_path = config.instance_dir.join("data").join("outbox.redb");
+    match spacebot::messaging::outbox::Outbox::open(&outbox_path) {
+        Ok(outbox) => api_state.outbox = Some(Arc::new(outbox)),
+        Err(error) => tracing::warn!(
+            path = %outbox_path.display(),
+            %error,
+            "failed to open outbox; failed replies will not be retried"
+        ),
+    }
+    // Non-fatal: without the store, `/api/events/history` returns 404.
+    if config.api.event_history.enabled {
+        let events_path = config.instance_dir.join("data").join("events.redb");
+        match spacebot::api::EventArchive::new(&events_path, &config.api.event_history) {
+            Ok(archive) => api_state.event_archive = Some(Arc::new(archive)),
+            Err(error) => tracing::warn!(
+                path = %events_path.display(),
+                %error,
+                "failed to open event history; past events will not be recorded"
+            ),
+        }
+    }
+    api_state.rate_limiter = spacebot::api::RateLimiter::new(config.api.rate_limit).map(Arc::new);
+    api_state.dev_proxy_url = config.api.dev_proxy_url.clone();
+    api_state.openai = config.api.openai.enabled.then_some(config.api.openai);
+    if config.api.widget.enabled {
+        if !config
+            .agents
+            .iter()
+            .any(|agent| agent.id == config.api.widget.agent_id)
+        {
+            tracing::warn!(
+                agent_id = %config.api.widget.agent_id,
+                "api.widget.agent_id doesn't match a configured agent; widget requests will fail"
+            );
+        }
+        api_state.widget_rate_limiter =
+            spacebot::api::RateLimiter::new(spacebot::config::ApiRateLimitConfig {
+                requests_per_minute: config.api.widget.requests_per_minute,
+                expensive_requests_per_minute: config.api.widget.messages_per_minute,
+            })
+            .map(Arc::new);
+        api_state.widget = Some(config.api.widget.clone());
+    }
     let api_state = Arc::new(api_state);
+    api_state.spawn_event_sequencer();
+    if let Some(registry) = api_state.webhooks.clone() {
+        spacebot::api::spawn_webhook_delivery(api_state.clone(), registry);
+    }
+    if let Some(archive) = api_state.event_archive.clone() {
+        spacebot::api::spawn_event_archiver(api_state.clone(), archive);
+    }
 
     // Start background update checker
     spacebot::update::spawn_update_checker(api_state.update_status.clone());
@@ -1535,28 +1772,47 @@ async fn run(
     };
 
     let _http_handle = if config.api.enabled {
-        // IPv6 addresses need brackets when combined with port: [::]:19898
-        let raw_bind = config
-            .api
-            .bind
-            .trim_start_matches('[')
-            .trim_end_matches(']');
-        let bind_str = if raw_bind.contains(':') {
-            format!("[{}]:{}", raw_bind, config.api.port)
-        } else {
-            format!("{}:{}", raw_bind, config.api.port)
-        };
-        let bind: std::net::SocketAddr = bind_str.parse().context("invalid API bind address")?;
+        let mut listeners = Vec::new();
+        if config.api.tcp_enabled {
+            // IPv6 addresses need brackets when combined with port: [::]:19898
+            let raw_bind = config
+                .api
+                .bind
+                .trim_start_matches('[')
+                .trim_end_matches(']');
+            let bind_str = if raw_bind.contains(':') {
+                format!("[{}]:{}", raw_bind, config.api.port)
+            } else {
+                format!("{}:{}", raw_bind, config.api.port)
+            };
+            let bind: std::net::SocketAddr =
+                bind_str.parse().context("invalid API bind address")?;
+            listeners.push(spacebot::api::ApiListener::Tcp(bind));
+        }
+        if let Some(socket) = &config.api.unix_socket {
+            listeners.push(spacebot::api::ApiListener::Unix {
+                path: socket.path.clone(),
+                mode: socket.mode,
+            });
+        }
         let http_shutdown = shutdown_rx.clone();
         Some(
-            spacebot::api::start_http_server(bind, api_state.clone(), http_shutdown)
-                .await
-                .context("failed to start HTTP server")?,
+            spacebot::api::start_http_server(
+                listeners,
+                api_state.clone(),
+                http_shutdown,
+                config.api.tls.clone(),
+            )
+            .await
+            .context("failed to start HTTP server")?,
         )
     } else {
         None
     };
 
+    let presence_handle =
+        spacebot::messaging::presence::spawn_presence_sync(api_state.clone(), shutdown_rx.clone());
+
     // Check if we have provider configuration (API keys or OAuth credentials)
     let has_providers = has_provider_credentials(&config.llm, &config.instance_dir);
 
@@ -1564,10 +1820,23 @@ async fn run(
         tracing::info!("No LLM providers configured. Starting in setup mode.");
         if foreground {
             eprintln!("No LLM provider keys configured.");
-            eprintln!(
-                "Please add a provider key via the web UI at http://{}:{}",
-                config.api.bind, config.api.port
-            );
+            if config.api.tcp_enabled {
+                eprintln!(
+                    "Please add a provider key via the web UI at {}://{}:{}",
+                    if config.api.tls.is_some() {
+                        "https"
+                    } else {
+                        "http"
+                    },
+                    config.api.bind,
+                    config.api.port
+                );
+            } else if let Some(socket) = &config.api.unix_socket {
+                eprintln!(
+                    "Please add a provider key through the API on {}",
+                    socket.path.display()
+                );
+            }
         }
     }
 
@@ -1585,10 +1854,21 @@ async fn run(
 
     // Shared embedding model (stateless, agent-agnostic)
     let embedding_cache_dir = config.instance_dir.join("embedding_cache");
-    let embedding_model = Arc::new(
-        spacebot::memory::EmbeddingModel::new(&embedding_cache_dir)
-            .context("failed to initialize embedding model")?,
-    );
+    let mut embedding_model = spacebot::memory::EmbeddingModel::new(&embedding_cache_dir)
+        .context("failed to initialize embedding model")?;
+    // Non-fatal: without the vector cache every text is embedded afresh.
+    let vector_cache_path = embedding_cache_dir.join("vectors.redb");
+    match spacebot::memory::embedding::cache::EmbeddingCache::open(&vector_cache_path) {
+        Ok(cache) => embedding_model = embedding_model.with_cache(cache),
+        Err(error) => {
+            tracing::warn!(
+                path = %vector_cache_path.display(),
+                %error,
+                "failed to open embedding cache; embeddings will not be cached"
+            );
+        }
+    }
+    let embedding_model = Arc::new(embedding_model);
 
     tracing::info!("shared resources initialized");
 
@@ -1694,6 +1974,7 @@ async fn run(
             llm_manager.clone(),
             agent_links.clone(),
             agent_humans.clone(),
+            api_state.config_issues.clone(),
         );
     } else {
         // Start file watcher in setup mode (no agents to watch yet)
@@ -1711,6 +1992,7 @@ async fn run(
             llm_manager.clone(),
             agent_links.clone(),
             agent_humans.clone(),
+            api_state.config_issues.clone(),
         );
     }
 
@@ -1845,6 +2127,12 @@ async fn run(
                     api_state
                         .register_channel_state(conversation_id.clone(), channel.state.clone())
                         .await;
+                    api_state
+                        .register_channel_agent(
+                            conversation_id.clone(),
+                            agent.deps.agent_id.to_string(),
+                        )
+                        .await;
 
                     let backfill_count = agent.config.history_backfill_count();
                     if backfill_count > 0 {
@@ -1921,7 +2209,7 @@ async fn run(
                     let cleanup_channel_id = conversation_id.clone();
                     let process_control_registry = agent.deps.process_control_registry.clone();
                     let api_state_for_cleanup = api_state.clone();
-                    tokio::spawn(async move {
+                    agent.deps.spawn(async move {
                         if let Err(error) = channel.run().await {
                             tracing::error!(%error, "channel event loop failed");
                         }
@@ -1944,14 +2232,19 @@ async fn run(
                     let sse_channel_id = conversation_id.clone();
                     let outbound_handle = tokio::spawn(async move {
                         while let Some(routed) = response_rx.recv().await {
-                            let spacebot::RoutedResponse { response, target } = routed;
+                            let spacebot::RoutedResponse {
+                                response,
+                                target,
+                                message_id,
+                            } = routed;
                             forward_sse_event(
                                 &api_event_tx,
                                 &sse_agent_id,
                                 &sse_channel_id,
                                 &response,
                             );
-                            route_outbound(&messaging_for_outbound, &target, response).await;
+                            route_outbound(&messaging_for_outbound, &target, response, message_id)
+                                .await;
                         }
                     });
 
@@ -1959,6 +2252,7 @@ async fn run(
                         conversation_id.clone(),
                         ActiveChannel {
                             message_tx: channel_tx,
+                            agent_id: agent_id.to_string(),
                             _outbound_handle: outbound_handle,
                         },
                     );
@@ -1974,10 +2268,63 @@ async fn run(
         }
     }
 
+    // `spacebot simulate`: attach the testbed platform and play the scenario
+    // while the main loop below serves it like any other adapter.
+    let (simulation_done_tx, mut simulation_done_rx) = mpsc::channel::<bool>(1);
+    let mut exit_code = 0;
+    if let Some(scenario) = simulation {
+        let agent_id = scenario
+            .agent
+            .clone()
+            .unwrap_or_else(|| default_agent_id.clone());
+        let Some(agent) = agents.get(agent_id.as_str()).filter(|_| agents_initialized) else {
+            spacebot::daemon::cleanup(&paths);
+            anyhow::bail!(
+                "cannot simulate: agent '{agent_id}' is not running (is an LLM provider configured?)"
+            );
+        };
+        let (adapter, handle) = spacebot::messaging::testbed::TestbedAdapter::new();
+        handle.set_agent(agent_id.as_str());
+        messaging_manager
+            .register_and_start(adapter)
+            .await
+            .context("failed to start testbed adapter")?;
+        let mut runner = spacebot::simulate::ScenarioRunner::new(handle);
+        runner.watch_events(agent.deps.event_tx.subscribe());
+        runner.watch_events(agent.deps.memory_event_tx.subscribe());
+        tokio::spawn(async move {
+            let report = runner.run(&scenario).await;
+            println!("{report}");
+            simulation_done_tx.send(report.passed()).await.ok();
+        });
+    }
+
+    // Messages held for paused agents, and released messages waiting to be
+    // routed ahead of new inbound traffic.
+    let mut paused_messages: HashMap<spacebot::AgentId, VecDeque<spacebot::InboundMessage>> =
+        HashMap::new();
+    let mut released_messages: VecDeque<spacebot::InboundMessage> = VecDeque::new();
+
     // Main event loop: route inbound messages to agent channels
     loop {
+        if !paused_messages.is_empty() {
+            paused_messages.retain(|agent_id, queue| {
+                let still_paused = agents
+                    .get(agent_id)
+                    .is_some_and(|agent| agent.deps.runtime_config.is_paused());
+                if !still_paused {
+                    tracing::info!(agent_id = %agent_id, count = queue.len(), "releasing messages held while paused");
+                    released_messages.extend(queue.drain(..));
+                }
+                still_paused
+            });
+        }
+
         // Poll the inbound stream if it exists, otherwise yield a never-resolving future
         let inbound_next = async {
+            if let Some(message) = released_messages.pop_front() {
+                return Some(message);
+            }
             match inbound_stream.as_mut() {
                 Some(stream) => stream.next().await,
                 None => std::future::pending().await,
@@ -2001,6 +2348,66 @@ async fn run(
                     resolved
                 };
 
+                if let Some(agent) = agents.get(&agent_id)
+                    && agent.deps.runtime_config.is_paused()
+                {
+                    match agent.deps.runtime_config.channel_config.load().pause_behavior {
+                        spacebot::config::PauseBehavior::Queue => {
+                            let queue = paused_messages.entry(agent_id.clone()).or_default();
+                            if queue.len() >= MAX_PAUSED_MESSAGES {
+                                queue.pop_front();
+                                tracing::warn!(agent_id = %agent_id, "paused message queue full, dropping oldest");
+                            }
+                            queue.push_back(message);
+                        }
+                        spacebot::config::PauseBehavior::Drop => {
+                            tracing::debug!(agent_id = %agent_id, "agent paused, dropping inbound message");
+                        }
+                    }
+                    continue;
+                }
+
+                // Mail to an email adapter's task address goes to the task
+                // queue instead of a channel.
+                if spacebot::tasks::email::is_email_task(&message) {
+                    let Some(agent) = agents.get(&agent_id) else {
+                        tracing::warn!(agent_id = %agent_id, "task email routed to unknown agent, dropping");
+                        continue;
+                    };
+                    let deps = agent.deps.clone();
+                    agent.deps.spawn(async move {
+                        match spacebot::tasks::email::create_task(
+                            &deps.task_store,
+                            &deps.agent_id,
+                            &deps.runtime_config.workspace_dir,
+                            &message,
+                        )
+                        .await
+                        {
+                            Ok(task) => {
+                                tracing::info!(
+                                    agent_id = %deps.agent_id,
+                                    task_number = task.task_number,
+                                    sender = %message.sender_id,
+                                    "created task from email"
+                                );
+                                deps.event_tx
+                                    .send(spacebot::ProcessEvent::TaskUpdated {
+                                        agent_id: deps.agent_id.clone(),
+                                        task_number: task.task_number,
+                                        status: task.status.to_string(),
+                                        action: "created".to_string(),
+                                    })
+                                    .ok();
+                            }
+                            Err(error) => {
+                                tracing::warn!(%error, agent_id = %deps.agent_id, "failed to create task from email");
+                            }
+                        }
+                    });
+                    continue;
+                }
+
                 let conversation_id = message.conversation_id.clone();
 
                 // Find or create a channel for this conversation
@@ -2056,6 +2463,10 @@ async fn run(
                         conversation_id.clone(),
                         channel.state.clone(),
                     ).await;
+                    api_state.register_channel_agent(
+                        conversation_id.clone(),
+                        agent.deps.agent_id.to_string(),
+                    ).await;
 
                     // Backfill recent message history from the platform.
                     // The transcript is injected into the system prompt (not chat
@@ -2087,7 +2498,7 @@ async fn run(
                     let cleanup_channel_id = conversation_id.clone();
                     let process_control_registry = agent.deps.process_control_registry.clone();
                     let api_state_for_cleanup = api_state.clone();
-                    tokio::spawn(async move {
+                    agent.deps.spawn(async move {
                         if let Err(error) = channel.run().await {
                             tracing::error!(%error, "channel event loop failed");
                         }
@@ -2114,9 +2525,13 @@ async fn run(
                     let sse_channel_id = conversation_id.clone();
                     let outbound_handle = tokio::spawn(async move {
                         while let Some(routed) = response_rx.recv().await {
-                            let spacebot::RoutedResponse { response, target } = routed;
+                            let spacebot::RoutedResponse {
+                                response,
+                                target,
+                                message_id,
+                            } = routed;
                             forward_sse_event(&api_event_tx, &sse_agent_id, &sse_channel_id, &response);
-                            route_outbound(&messaging_for_outbound, &target, response).await;
+                            route_outbound(&messaging_for_outbound, &target, response, message_id).await;
                         }
                         tracing::debug!(
                             conversation_id = %outbound_conversation_id,
@@ -2126,6 +2541,7 @@ async fn run(
 
                     active_channels.insert(conversation_id.clone(), ActiveChannel {
                         message_tx: channel_tx,
+                        agent_id: agent_id.to_string(),
                         _outbound_handle: outbound_handle,
                     });
 
@@ -2164,13 +2580,25 @@ async fn run(
                     }
                 }
             }
+            // Loop back around so the release check at the top runs.
+            _ = api_state.agent_resumed.notified() => {}
             Some(agent) = agent_rx.recv() => {
-                tracing::info!(agent_id = %agent.id, "registering new agent in main loop");
+                if agents.contains_key(&agent.id) {
+                    // A restart: drop the old agent's channels so the next
+                    // message opens them against the new runtime.
+                    active_channels.retain(|_, active| *active.agent_id != *agent.id);
+                    tracing::info!(agent_id = %agent.id, "replacing restarted agent in main loop");
+                } else {
+                    tracing::info!(agent_id = %agent.id, "registering new agent in main loop");
+                }
                 agents.insert(agent.id.clone(), agent);
             }
             Some(agent_id) = agent_remove_rx.recv() => {
                 let key: spacebot::AgentId = Arc::from(agent_id.as_str());
                 if let Some(agent) = agents.remove(&key) {
+                    active_channels.retain(|_, active| active.agent_id != agent_id);
+                    agent.deps.llm_manager.unregister_usage_pool(&agent_id);
+                    agent.deps.llm_manager.unregister_quota(&agent_id);
                     agent.deps.mcp_manager.disconnect_all().await;
                     tracing::info!(agent_id = %agent_id, "removed agent from main loop");
                 } else {
@@ -2284,6 +2712,7 @@ async fn run(
                                             new_llm_manager.clone(),
                                             agent_links.clone(),
                                             agent_humans.clone(),
+                                            api_state.config_issues.clone(),
                                         );
                                         tracing::info!("agents initialized after provider setup");
                                     }
@@ -2305,6 +2734,11 @@ async fn run(
                     }
                 }
             }
+            Some(passed) = simulation_done_rx.recv() => {
+                tracing::info!(passed, "simulation finished");
+                exit_code = if passed { 0 } else { 1 };
+                break;
+            }
             _ = shutdown_rx.wait_for(|shutdown| *shutdown) => {
                 tracing::info!("shutdown signal received via IPC");
                 break;
@@ -2316,7 +2750,12 @@ async fn run(
         }
     }
 
-    // Graceful shutdown
+    // Graceful shutdown. Stop presence sync first so a late sample can't
+    // replace the draining presence while in-flight work finishes.
+    presence_handle.abort();
+    messaging_manager
+        .set_presence(&spacebot::messaging::presence::Presence::draining())
+        .await;
     drop(active_channels);
 
     for scheduler in &cron_schedulers_for_shutdown {
@@ -2329,6 +2768,7 @@ async fn run(
     for (agent_id, agent) in agents {
         tracing::info!(%agent_id, "shutting down agent");
         agent.deps.mcp_manager.disconnect_all().await;
+        agent.deps.leader.release().await;
         agent.db.close().await;
     }
 
@@ -2346,7 +2786,7 @@ async fn run(
 
     // Force exit — detached tasks (e.g. the serenity gateway client) may keep
     // the tokio runtime alive after all owned resources have been cleaned up.
-    std::process::exit(0);
+    std::process::exit(exit_code);
 }
 
 /// Initialize agents, messaging adapters, cron, cortex, and ingestion.
@@ -2465,14 +2905,25 @@ async fn initialize_agents(
         })?;
 
         // Per-agent database connections
-        let db = spacebot::db::Db::connect(&agent_config.data_dir)
-            .await
-            .with_context(|| {
-                format!(
-                    "failed to connect databases for agent '{}'",
-                    agent_config.id
-                )
-            })?;
+        let db = spacebot::db::Db::connect_with_pool_size(
+            &agent_config.data_dir,
+            agent_config.quotas.max_db_connections,
+        )
+        .await
+        .with_context(|| {
+            format!(
+                "failed to connect databases for agent '{}'",
+                agent_config.id
+            )
+        })?;
+
+        llm_manager.register_usage_pool(&agent_config.id, db.sqlite.clone());
+        let quota = spacebot::quota::AgentQuota::new(
+            Arc::from(agent_config.id.as_str()),
+            &agent_config.quotas,
+            db.sqlite.clone(),
+        );
+        llm_manager.register_quota(&agent_config.id, quota.clone());
 
         let run_logger = spacebot::conversation::ProcessRunLogger::new(db.sqlite.clone());
         let orphaned_workers = run_logger
@@ -2528,21 +2979,46 @@ async fn initialize_agents(
             spacebot::memory::MemoryStore::with_agent_id(db.sqlite.clone(), &agent_config.id);
         let task_store = Arc::new(spacebot::tasks::TaskStore::new(db.sqlite.clone()));
         let project_store = Arc::new(spacebot::projects::ProjectStore::new(db.sqlite.clone()));
-        let embedding_table = spacebot::memory::EmbeddingTable::open_or_create(&db.lance)
-            .await
-            .with_context(|| {
-                format!("failed to init embeddings for agent '{}'", agent_config.id)
-            })?;
+        let agent_embedding_model = spacebot::memory::EmbeddingModel::for_config(
+            &agent_config.embedding,
+            embedding_model,
+            llm_manager.http_client(),
+        )
+        .with_context(|| {
+            format!(
+                "failed to init embedding model for agent '{}'",
+                agent_config.id
+            )
+        })?;
+        let embedding_table = spacebot::memory::EmbeddingTable::open_or_create(
+            &db.lance,
+            agent_embedding_model.dimensions(),
+        )
+        .await
+        .with_context(|| format!("failed to init embeddings for agent '{}'", agent_config.id))?;
+        spacebot::memory::embedding::check_metadata(
+            &db.sqlite,
+            &agent_config.id,
+            &agent_embedding_model,
+            &embedding_table,
+        )
+        .await?;
 
         // Ensure FTS index exists for full-text search queries
         if let Err(error) = embedding_table.ensure_fts_index().await {
             tracing::warn!(%error, agent = %agent_config.id, "failed to create FTS index");
         }
 
+        // Memories saved before the mutation log existed need a baseline entry
+        // to show up in past states.
+        if let Err(error) = spacebot::memory::mutations::seed_baselines(&memory_store).await {
+            tracing::warn!(%error, agent = %agent_config.id, "failed to seed mutation baselines");
+        }
+
         let memory_search = Arc::new(spacebot::memory::MemorySearch::new(
             memory_store,
             embedding_table,
-            embedding_model.clone(),
+            agent_embedding_model,
         ));
 
         // Per-agent control and memory event buses (broadcast fan-out).
@@ -2650,7 +3126,15 @@ async fn initialize_agents(
                 spacebot::agent::process_control::ProcessControlRegistry::new(),
             ),
             injection_tx: injection_tx.clone(),
+            leader: spacebot::leader::LeaderLease::from_config(&db.sqlite, &config.leader_election),
+            quota,
+            runtime: spacebot::agent::runtime::AgentRuntime::start(
+                &agent_config.id,
+                &agent_config.runtime,
+            )?,
         };
+        deps.leader.spawn_renewal();
+        deps.quota.spawn_db_sampler();
 
         let agent = spacebot::Agent {
             id: agent_id.clone(),
@@ -2712,6 +3196,8 @@ async fn initialize_agents(
         let mut agent_data_dirs = std::collections::HashMap::new();
         let mut runtime_configs = std::collections::HashMap::new();
         let mut sandboxes = std::collections::HashMap::new();
+        let mut leader_leases = std::collections::HashMap::new();
+        let mut agent_quotas = std::collections::HashMap::new();
         for (agent_id, agent) in agents.iter() {
             let event_rx = agent.deps.event_tx.subscribe();
             api_state.register_agent_events(agent_id.to_string(), event_rx);
@@ -2725,6 +3211,8 @@ async fn initialize_agents(
             agent_data_dirs.insert(agent_id.to_string(), agent.config.data_dir.clone());
             runtime_configs.insert(agent_id.to_string(), agent.deps.runtime_config.clone());
             sandboxes.insert(agent_id.to_string(), agent.deps.sandbox.clone());
+            leader_leases.insert(agent_id.to_string(), agent.deps.leader.clone());
+            agent_quotas.insert(agent_id.to_string(), agent.deps.quota.clone());
             agent_configs.push(spacebot::api::AgentInfo {
                 id: agent.config.id.clone(),
                 display_name: agent.config.display_name.clone(),
@@ -2749,6 +3237,8 @@ async fn initialize_agents(
         api_state.set_agent_identity_dirs(agent_identity_dirs);
         api_state.set_agent_data_dirs(agent_data_dirs);
         api_state.set_sandboxes(sandboxes);
+        api_state.set_leader_leases(leader_leases);
+        api_state.set_agent_quotas(agent_quotas);
         // Wire the instance-level secrets store into the API state.
         if let Some(store) = &bootstrapped_store {
             api_state.set_secrets_store(store.clone());
@@ -2802,6 +3292,7 @@ async fn initialize_agents(
 
     // Initialize messaging adapters
     let new_messaging_manager = spacebot::messaging::MessagingManager::new();
+    new_messaging_manager.set_filters(config.messaging.filters.clone());
 
     // Shared Discord permissions (hot-reloadable via file watcher)
     *discord_permissions = config.messaging.discord.as_ref().map(|discord_config| {
@@ -3023,6 +3514,26 @@ async fn initialize_agents(
         new_messaging_manager.register(adapter).await;
     }
 
+    for twilio_config in config
+        .messaging
+        .twilio
+        .iter()
+        .filter(|twilio_config| twilio_config.enabled)
+    {
+        let adapter = spacebot::messaging::twilio::TwilioAdapter::new(twilio_config.clone());
+        new_messaging_manager.register(adapter).await;
+    }
+
+    for external_config in config
+        .messaging
+        .external
+        .iter()
+        .filter(|external_config| external_config.enabled)
+    {
+        let adapter = spacebot::messaging::external::ExternalAdapter::new(external_config.clone());
+        new_messaging_manager.register(adapter).await;
+    }
+
     // Shared Twitch permissions (hot-reloadable via file watcher)
     *twitch_permissions = config.messaging.twitch.as_ref().map(|twitch_config| {
         let perms =
@@ -3166,6 +3677,9 @@ async fn initialize_agents(
         .register_shared(webchat_adapter.clone())
         .await;
     api_state.set_webchat_adapter(webchat_adapter);
+    if let Some(outbox) = &api_state.outbox {
+        new_messaging_manager.set_outbox(outbox.clone());
+    }
 
     *messaging_manager = Arc::new(new_messaging_manager);
     api_state
@@ -3178,6 +3692,7 @@ async fn initialize_agents(
         .await
         .context("failed to start messaging adapters")?;
     *inbound_stream = Some(new_inbound);
+    messaging_manager.spawn_outbox_worker();
 
     tracing::info!("messaging adapters started");
 
@@ -3201,6 +3716,7 @@ async fn initialize_agents(
                 enabled: cron_def.enabled,
                 run_once: cron_def.run_once,
                 timeout_secs: cron_def.timeout_secs,
+                timezone: cron_def.timezone.clone(),
             };
             if let Err(error) = store.save(&cron_config).await {
                 tracing::warn!(
@@ -3271,41 +3787,106 @@ async fn initialize_agents(
     for (agent_id, agent) in agents.iter() {
         let ingestion_config = **agent.deps.runtime_config.ingestion.load();
         if ingestion_config.enabled {
+            let _runtime = agent.deps.enter_runtime();
             let handle = spacebot::agent::ingestion::spawn_ingestion_loop(
                 agent.config.ingest_dir(),
                 agent.deps.clone(),
             );
+            api_state.track_agent_tasks(agent_id, std::slice::from_ref(&handle));
             ingestion_handles.push(handle);
             tracing::info!(agent_id = %agent_id, "memory ingestion loop started");
         }
     }
 
+    // Resume memory backfills interrupted by the last shutdown
+    for (agent_id, agent) in agents.iter() {
+        match spacebot::agent::backfill::resume_running_jobs(&agent.deps).await {
+            Ok(0) => {}
+            Ok(count) => tracing::info!(agent_id = %agent_id, count, "memory backfills resumed"),
+            Err(error) => {
+                tracing::warn!(agent_id = %agent_id, %error, "failed to resume memory backfills")
+            }
+        }
+    }
+
+    // Resume batch jobs interrupted by the last shutdown
+    for (agent_id, agent) in agents.iter() {
+        match spacebot::agent::batch::resume_running_jobs(&agent.deps).await {
+            Ok(0) => {}
+            Ok(count) => tracing::info!(agent_id = %agent_id, count, "batch jobs resumed"),
+            Err(error) => {
+                tracing::warn!(agent_id = %agent_id, %error, "failed to resume batch jobs")
+            }
+        }
+    }
+
     // Start cortex warmup, runtime, and association loops for each agent
     for (agent_id, agent) in agents.iter() {
+        let _runtime = agent.deps.enter_runtime();
+        let mut agent_handles = Vec::new();
         let cortex_logger = spacebot::agent::cortex::CortexLogger::new(agent.db.sqlite.clone());
         let warmup_handle =
             spacebot::agent::cortex::spawn_warmup_loop(agent.deps.clone(), cortex_logger.clone());
-        cortex_handles.push(warmup_handle);
+        agent_handles.push(warmup_handle);
         tracing::info!(agent_id = %agent_id, "warmup loop started");
 
         let cortex_handle =
             spacebot::agent::cortex::spawn_cortex_loop(agent.deps.clone(), cortex_logger.clone());
-        cortex_handles.push(cortex_handle);
+        agent_handles.push(cortex_handle);
         tracing::info!(agent_id = %agent_id, "cortex loop started");
 
-        let association_handle =
-            spacebot::agent::cortex::spawn_association_loop(agent.deps.clone(), cortex_logger);
-        cortex_handles.push(association_handle);
+        let association_handle = spacebot::agent::cortex::spawn_association_loop(
+            agent.deps.clone(),
+            cortex_logger.clone(),
+        );
+        agent_handles.push(association_handle);
         tracing::info!(agent_id = %agent_id, "cortex association loop started");
 
+        let recalibration_handle = spacebot::agent::cortex::spawn_recalibration_loop(
+            agent.deps.clone(),
+            cortex_logger.clone(),
+        );
+        agent_handles.push(recalibration_handle);
+        tracing::info!(agent_id = %agent_id, "cortex recalibration loop started");
+
+        let rescore_handle =
+            spacebot::agent::cortex::spawn_rescore_loop(agent.deps.clone(), cortex_logger.clone());
+        agent_handles.push(rescore_handle);
+        tracing::info!(agent_id = %agent_id, "cortex rescore loop started");
+
+        let consolidation_handle =
+            spacebot::agent::cortex::spawn_consolidation_loop(agent.deps.clone(), cortex_logger);
+        agent_handles.push(consolidation_handle);
+        tracing::info!(agent_id = %agent_id, "cortex consolidation loop started");
+
         let ready_task_handle = spacebot::agent::cortex::spawn_ready_task_loop(
             agent.deps.clone(),
             spacebot::agent::cortex::CortexLogger::new(agent.db.sqlite.clone()),
         );
-        cortex_handles.push(ready_task_handle);
+        agent_handles.push(ready_task_handle);
         tracing::info!(agent_id = %agent_id, "cortex ready-task loop started");
+
+        agent_handles.push(spacebot::notifications::spawn_release_loop(
+            agent.deps.clone(),
+        ));
+        tracing::info!(agent_id = %agent_id, "held notification release loop started");
+
+        // Tracked per agent so a restart can stop them.
+        api_state.track_agent_tasks(agent_id, &agent_handles);
+        cortex_handles.extend(agent_handles);
     }
 
+    #[cfg(feature = "metrics")]
+    cortex_handles.push(spacebot::agent::runtime::spawn_metrics_sampler(
+        agents
+            .iter()
+            .filter_map(|(agent_id, agent)| {
+                let runtime = agent.deps.runtime.as_ref()?;
+                Some((agent_id.to_string(), runtime.handle().clone()))
+            })
+            .collect(),
+    ));
+
     // Create cortex chat sessions for each agent
     {
         let mut sessions = std::collections::HashMap::new();
@@ -1,14 +1,23 @@
 //! Memory storage and retrieval system.
 
+pub mod backup;
+pub mod consolidation;
 pub mod embedding;
+pub mod entities;
+pub mod feedback;
+pub mod import;
 pub mod lance;
 pub mod maintenance;
+pub mod mutations;
+pub mod rescore;
+pub mod scope;
 pub mod search;
 pub mod store;
+pub mod todos;
 pub mod types;
 
 pub use embedding::EmbeddingModel;
 pub use lance::EmbeddingTable;
-pub use search::{MemorySearch, SearchConfig, SearchMode, SearchSort, curate_results};
+pub use search::{EdgeWeights, MemorySearch, SearchConfig, SearchMode, SearchSort, curate_results};
 pub use store::MemoryStore;
 pub use types::{Association, Memory, MemoryType, RelationType};
@@ -0,0 +1,402 @@
+//! Portable JSONL backups of an agent's memory.
+//!
+//! A backup is one JSON object per line: a header, then every memory
+//! (forgotten ones included) with its embedding, then every association.
+//! Exports are streamed page by page.
+//! Restoring keeps IDs, timestamps, and graph edges, so an agent's memory
+//! can be moved between machines without copying the SQLite and LanceDB
+//! files. Unlike [`crate::memory::import`], which takes bare content from
+//! other tools, this is a lossless round trip.
+
+use crate::error::Result;
+use crate::memory::lance::EmbeddingTable;
+use crate::memory::mutations::{self, MutationKind};
+use crate::memory::{Association, Memory, MemorySearch};
+
+use chrono::{DateTime, Utc};
+use futures::Stream;
+use serde::{Deserialize, Serialize};
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+/// Identifies a backup file in its header line.
+pub const BACKUP_FORMAT: &str = "spacebot-memory";
+/// Current backup format version. Restoring accepts this and older.
+pub const BACKUP_VERSION: u32 = 1;
+
+/// Memories embedded and indexed together on restore.
+const RESTORE_BATCH_SIZE: usize = 64;
+/// Memories or associations read per database round trip on export.
+const EXPORT_PAGE_SIZE: i64 = 200;
+
+/// First line of a backup.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupHeader {
+    pub format: String,
+    pub version: u32,
+    pub agent_id: String,
+    pub exported_at: DateTime<Utc>,
+    pub memories: usize,
+    pub associations: usize,
+}
+
+/// A memory line: every stored field plus its embedding.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryRecord {
+    #[serde(flatten)]
+    pub memory: Memory,
+    /// Missing when the memory had no embedding at export time; one is
+    /// computed on restore.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding: Option<Vec<f32>>,
+}
+
+/// One line of a backup.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum BackupRecord {
+    Header(BackupHeader),
+    Memory(MemoryRecord),
+    Association(Association),
+}
+
+/// A parsed backup, ready to restore.
+#[derive(Debug, Default)]
+pub struct Backup {
+    pub memories: Vec<MemoryRecord>,
+    pub associations: Vec<Association>,
+}
+
+/// Outcome of a restore.
+#[derive(Debug, Default, Serialize, utoipa::ToSchema)]
+pub struct RestoreSummary {
+    /// Agent the backup was taken from.
+    pub source_agent_id: String,
+    pub memories: usize,
+    /// Memories skipped because one with the same ID already exists.
+    pub skipped: usize,
+    pub associations: usize,
+    /// Restored memories whose embedding had to be recomputed, because it
+    /// was missing or from a model with a different dimension.
+    pub reembedded: usize,
+}
+
+/// Dump every memory, embedding, and association of an agent as JSONL.
+///
+/// Memories and associations are read a page at a time and each page is
+/// yielded as one chunk of lines, so a large memory never has to be held in
+/// full. An error ends the stream after the chunks already yielded.
+pub fn export_stream(
+    memory_search: Arc<MemorySearch>,
+) -> impl Stream<Item = Result<String>> + Send + 'static {
+    async_stream::try_stream! {
+        let store = memory_search.store();
+        let (memory_count, association_count) = store.export_counts().await?;
+
+        let mut output = String::new();
+        push_line(
+            &mut output,
+            &BackupRecord::Header(BackupHeader {
+                format: BACKUP_FORMAT.into(),
+                version: BACKUP_VERSION,
+                agent_id: store.agent_id().to_string(),
+                exported_at: Utc::now(),
+                memories: memory_count,
+                associations: association_count,
+            }),
+        )?;
+        yield output;
+
+        let mut after: Option<String> = None;
+        loop {
+            let memories = store
+                .export_memories_page(after.as_deref(), EXPORT_PAGE_SIZE)
+                .await?;
+            let Some(last) = memories.last() else {
+                break;
+            };
+            after = Some(last.id.clone());
+
+            let ids: Vec<String> = memories.iter().map(|memory| memory.id.clone()).collect();
+            let mut embeddings = memory_search.embedding_table().embeddings_for(&ids).await?;
+            let mut output = String::new();
+            for memory in memories {
+                let embedding = embeddings.remove(&memory.id);
+                push_line(
+                    &mut output,
+                    &BackupRecord::Memory(MemoryRecord { memory, embedding }),
+                )?;
+            }
+            yield output;
+        }
+
+        let mut after: Option<String> = None;
+        loop {
+            let associations = store
+                .export_associations_page(after.as_deref(), EXPORT_PAGE_SIZE)
+                .await?;
+            let Some(last) = associations.last() else {
+                break;
+            };
+            after = Some(last.id.clone());
+
+            let mut output = String::new();
+            for association in associations {
+                push_line(&mut output, &BackupRecord::Association(association))?;
+            }
+            yield output;
+        }
+    }
+}
+
+fn push_line(output: &mut String, record: &BackupRecord) -> Result<()> {
+    let line = serde_json::to_string(record)
+        .map_err(|error| anyhow::anyhow!("failed to serialize backup record: {error}"))?;
+    output.push_str(&line);
+    output.push('\n');
+    Ok(())
+}
+
+/// Parse and validate a backup. Any bad line rejects the whole file, so a
+/// restore never lands half a graph.
+pub fn parse_backup(body: &str) -> std::result::Result<(BackupHeader, Backup), String> {
+    let mut lines = body
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty());
+
+    let header = match lines.next() {
+        Some((_, line)) => match serde_json::from_str::<BackupRecord>(line) {
+            Ok(BackupRecord::Header(header)) if header.format == BACKUP_FORMAT => header,
+            _ => return Err("line 1: not a spacebot memory backup header".into()),
+        },
+        None => return Err("backup is empty".into()),
+    };
+    if header.version > BACKUP_VERSION {
+        return Err(format!(
+            "backup version {} is newer than this build supports ({BACKUP_VERSION})",
+            header.version
+        ));
+    }
+
+    let mut backup = Backup::default();
+    let mut memory_ids = HashSet::new();
+    for (index, line) in lines {
+        let line_number = index + 1;
+        let record: BackupRecord =
+            serde_json::from_str(line).map_err(|error| format!("line {line_number}: {error}"))?;
+        match record {
+            BackupRecord::Header(_) => {
+                return Err(format!("line {line_number}: unexpected second header"));
+            }
+            BackupRecord::Memory(record) => {
+                EmbeddingTable::validate_memory_id(&record.memory.id)
+                    .map_err(|_| format!("line {line_number}: invalid memory id"))?;
+                if record.memory.content.trim().is_empty() {
+                    return Err(format!("line {line_number}: content is empty"));
+                }
+                if !(0.0..=1.0).contains(&record.memory.importance) {
+                    return Err(format!(
+                        "line {line_number}: importance {} is outside 0-1",
+                        record.memory.importance
+                    ));
+                }
+                if !memory_ids.insert(record.memory.id.clone()) {
+                    return Err(format!(
+                        "line {line_number}: duplicate memory {}",
+                        record.memory.id
+                    ));
+                }
+                backup.memories.push(record);
+            }
+            BackupRecord::Association(association) => backup.associations.push(association),
+        }
+    }
+    Ok((header, backup))
+}
+
+/// Restore a parsed backup into an agent's memory. Memories already present
+/// by ID are left as they are. Restored memories are recorded in the
+/// mutation history under `actor`.
+pub async fn restore(
+    memory_search: &MemorySearch,
+    source_agent_id: &str,
+    backup: Backup,
+    actor: &str,
+) -> Result<RestoreSummary> {
+    let store = memory_search.store();
+    let memories: Vec<Memory> = backup
+        .memories
+        .iter()
+        .map(|record| record.memory.clone())
+        .collect();
+    let outcome = store.import(&memories, &backup.associations).await?;
+
+    let dimension = memory_search.embedding_table().dimension();
+    let inserted: HashSet<&str> = outcome.inserted.iter().map(String::as_str).collect();
+    let mut embeddings: HashMap<&str, &[f32]> = HashMap::new();
+    let mut to_embed: Vec<&Memory> = Vec::new();
+    for record in &backup.memories {
+        if !inserted.contains(record.memory.id.as_str()) {
+            continue;
+        }
+        match &record.embedding {
+            Some(embedding) if embedding.len() == dimension => {
+                embeddings.insert(&record.memory.id, embedding);
+            }
+            _ => to_embed.push(&record.memory),
+        }
+    }
+
+    let mut computed: HashMap<&str, Vec<f32>> = HashMap::new();
+    for batch in to_embed.chunks(RESTORE_BATCH_SIZE) {
+        let vectors = memory_search
+            .embedding_model_arc()
+            .embed_batch(batch.iter().map(|memory| memory.content.clone()).collect())
+            .await;
+        match vectors {
+            Ok(vectors) if vectors.len() == batch.len() => {
+                for (memory, vector) in batch.iter().zip(vectors) {
+                    computed.insert(&memory.id, vector);
+                }
+            }
+            Ok(vectors) => {
+                return Err(compensate(
+                    memory_search,
+                    &outcome.inserted,
+                    anyhow::anyhow!(
+                        "embedding model returned {} vectors for {} memories",
+                        vectors.len(),
+                        batch.len()
+                    )
+                    .into(),
+                )
+                .await);
+            }
+            Err(error) => return Err(compensate(memory_search, &outcome.inserted, error).await),
+        }
+    }
+
+    let rows: Vec<(&str, &str, &[f32])> = backup
+        .memories
+        .iter()
+        .filter_map(|record| {
+            let id = record.memory.id.as_str();
+            let embedding = embeddings
+                .get(id)
+                .copied()
+                .or_else(|| computed.get(id).map(Vec::as_slice))?;
+            Some((id, record.memory.content.as_str(), embedding))
+        })
+        .collect();
+    for batch in rows.chunks(RESTORE_BATCH_SIZE) {
+        if let Err(error) = memory_search.embedding_table().store_batch(batch).await {
+            return Err(compensate(memory_search, &outcome.inserted, error).await);
+        }
+    }
+    if !rows.is_empty()
+        && let Err(error) = memory_search.embedding_table().ensure_fts_index().await
+    {
+        tracing::warn!(%error, "failed to ensure FTS index after memory restore");
+    }
+
+    for record in &backup.memories {
+        if inserted.contains(record.memory.id.as_str()) {
+            mutations::record_or_warn(
+                store,
+                MutationKind::Create,
+                &record.memory.id,
+                Some(&record.memory),
+                actor,
+                Some(&format!("restored from backup of {source_agent_id}")),
+            )
+            .await;
+        }
+    }
+
+    Ok(RestoreSummary {
+        source_agent_id: source_agent_id.to_string(),
+        memories: outcome.inserted.len(),
+        skipped: outcome.skipped,
+        associations: outcome.associations,
+        reembedded: computed.len(),
+    })
+}
+
+/// Undo a restore whose embeddings couldn't be written, so no memory is
+/// left without one. Associations go with their memories.
+async fn compensate(
+    memory_search: &MemorySearch,
+    inserted: &[String],
+    error: crate::error::Error,
+) -> crate::error::Error {
+    for memory_id in inserted {
+        if let Err(delete_error) = memory_search.embedding_table().delete(memory_id).await {
+            tracing::warn!(%memory_id, %delete_error, "compensating embedding delete failed after restore error");
+        }
+        if let Err(delete_error) = memory_search.store().delete(memory_id).await {
+            tracing::warn!(%memory_id, %delete_error, "compensating delete failed after restore error");
+        }
+    }
+    error
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::{MemoryType, RelationType};
+
+    #[test]
+    fn backup_lines_round_trip_and_bad_files_are_rejected() {
+        let memory = Memory::new("Ships on Fridays", MemoryType::Decision);
+        let other = Memory::new("Release checklist", MemoryType::Fact);
+        let association = Association::new(&memory.id, &other.id, RelationType::PartOf);
+
+        let mut body = String::new();
+        for record in [
+            BackupRecord::Header(BackupHeader {
+                format: BACKUP_FORMAT.into(),
+                version: BACKUP_VERSION,
+                agent_id: "main".into(),
+                exported_at: Utc::now(),
+                memories: 2,
+                associations: 1,
+            }),
+            BackupRecord::Memory(MemoryRecord {
+                memory: memory.clone(),
+                embedding: Some(vec![0.5; 384]),
+            }),
+            BackupRecord::Memory(MemoryRecord {
+                memory: other.clone(),
+                embedding: None,
+            }),
+            BackupRecord::Association(association.clone()),
+        ] {
+            push_line(&mut body, &record).unwrap();
+        }
+        assert!(
+            body.lines()
+                .nth(1)
+                .unwrap()
+                .starts_with(r#"{"kind":"memory","id":"#)
+        );
+
+        let (header, backup) = parse_backup(&body).unwrap();
+        assert_eq!(header.agent_id, "main");
+        assert_eq!(backup.memories[0].memory, memory);
+        assert_eq!(
+            backup.memories[0].embedding.as_ref().map(Vec::len),
+            Some(384)
+        );
+        assert!(backup.memories[1].embedding.is_none());
+        assert_eq!(backup.associations, vec![association]);
+
+        let no_header = body.lines().skip(1).collect::<Vec<_>>().join("\n");
+        assert!(parse_backup(&no_header).unwrap_err().contains("header"));
+        let duplicated = format!("{body}{}\n", body.lines().nth(1).unwrap());
+        assert!(parse_backup(&duplicated).unwrap_err().contains("duplicate"));
+        let newer = body.replacen(r#""version":1"#, r#""version":99"#, 1);
+        assert!(parse_backup(&newer).unwrap_err().contains("newer"));
+    }
+}
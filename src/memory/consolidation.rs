@@ -0,0 +1,388 @@
+//! Memory consolidation: fold clusters of near-duplicate memories into one
+//! canonical memory.
+//!
+//! Clustering is pure embedding similarity and runs here. Deciding which
+//! members really say the same thing, and writing the canonical text, is
+//! left to the caller; the cortex asks the compactor model. Every reviewed
+//! cluster is recorded in `memory_consolidations`, whether it was merged or
+//! judged distinct, so merges can be traced back and distinct clusters
+//! aren't sent to the model again.
+
+use crate::error::Result;
+use crate::memory::mutations::{self, ACTOR_CONSOLIDATION, MutationKind};
+use crate::memory::{EmbeddingModel, EmbeddingTable, Memory, MemoryStore, MemoryType};
+
+use anyhow::Context as _;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::Row as _;
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Memories considered as cluster seeds per pass.
+const MAX_SEED_MEMORIES: i64 = 1_000;
+/// Members per cluster, seed included.
+pub const MAX_CLUSTER_SIZE: usize = 8;
+
+/// Whether a reviewed cluster was merged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ConsolidationOutcome {
+    Merged,
+    /// The model judged the members distinct; nothing changed.
+    Distinct,
+}
+
+impl ConsolidationOutcome {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ConsolidationOutcome::Merged => "merged",
+            ConsolidationOutcome::Distinct => "distinct",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "merged" => Some(ConsolidationOutcome::Merged),
+            "distinct" => Some(ConsolidationOutcome::Distinct),
+            _ => None,
+        }
+    }
+}
+
+/// One reviewed cluster.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct Consolidation {
+    pub id: String,
+    pub outcome: ConsolidationOutcome,
+    /// The memory that now holds the canonical text. Absent when distinct.
+    pub canonical_id: Option<String>,
+    /// Memories absorbed into the canonical one and moved to the trash.
+    pub merged_ids: Vec<String>,
+    /// Every member of the cluster that was reviewed.
+    pub memory_ids: Vec<String>,
+    /// Model that reviewed the cluster.
+    pub model: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Group live memories into clusters of near-duplicates. Members share a
+/// type and scope; identity memories are never clustered. Clusters already
+/// judged distinct are skipped.
+pub async fn find_clusters(
+    store: &MemoryStore,
+    embedding_table: &EmbeddingTable,
+    similarity_threshold: f32,
+    max_clusters: usize,
+) -> Result<Vec<Vec<Memory>>> {
+    let seed_ids: Vec<String> = sqlx::query_scalar(
+        "SELECT id FROM memories WHERE forgotten = 0 AND memory_type != 'identity' \
+         ORDER BY importance DESC, created_at DESC, id ASC LIMIT ?",
+    )
+    .bind(MAX_SEED_MEMORIES)
+    .fetch_all(store.pool())
+    .await
+    .context("failed to fetch consolidation seed memories")?;
+
+    let mut clustered = HashSet::new();
+    let mut clusters = Vec::new();
+
+    for seed_id in seed_ids {
+        if clusters.len() >= max_clusters {
+            break;
+        }
+        if clustered.contains(&seed_id) {
+            continue;
+        }
+        let Some(seed) = store.load(&seed_id).await? else {
+            continue;
+        };
+        if seed.forgotten {
+            continue;
+        }
+
+        let similar = embedding_table
+            .find_similar(&seed.id, similarity_threshold, MAX_CLUSTER_SIZE * 2)
+            .await
+            .with_context(|| format!("failed to find memories similar to {}", seed.id))?;
+
+        let mut cluster = vec![seed];
+        for (candidate_id, _similarity) in similar {
+            if cluster.len() >= MAX_CLUSTER_SIZE {
+                break;
+            }
+            if clustered.contains(&candidate_id) || candidate_id == cluster[0].id {
+                continue;
+            }
+            let Some(candidate) = store.load(&candidate_id).await? else {
+                continue;
+            };
+            // Merging across scopes would carry content into a scope that
+            // must not see it.
+            if candidate.forgotten
+                || candidate.memory_type != cluster[0].memory_type
+                || candidate.memory_type == MemoryType::Identity
+                || candidate.scope != cluster[0].scope
+            {
+                continue;
+            }
+            cluster.push(candidate);
+        }
+
+        if cluster.len() < 2 || judged_distinct(store, &cluster).await? {
+            continue;
+        }
+        clustered.extend(cluster.iter().map(|memory| memory.id.clone()));
+        clusters.push(cluster);
+    }
+
+    Ok(clusters)
+}
+
+/// Which cluster members to merge, and which of them survives.
+#[derive(Debug, Clone)]
+pub struct MergePlan {
+    pub survivor: Memory,
+    pub absorbed: Vec<Memory>,
+}
+
+/// Pick which cluster members to merge from the 1-based labels the model
+/// marked as duplicates. The survivor is the most important of them.
+/// Returns `None` unless at least two valid labels remain.
+pub fn resolve_merge(cluster: &[Memory], duplicate_labels: &[usize]) -> Option<MergePlan> {
+    let mut seen = HashSet::new();
+    let mut duplicates: Vec<&Memory> = duplicate_labels
+        .iter()
+        .filter(|label| **label >= 1 && seen.insert(**label))
+        .filter_map(|label| cluster.get(label - 1))
+        .collect();
+    if duplicates.len() < 2 {
+        return None;
+    }
+
+    duplicates.sort_by(|a, b| {
+        b.importance
+            .total_cmp(&a.importance)
+            .then_with(|| a.id.cmp(&b.id))
+    });
+    Some(MergePlan {
+        survivor: duplicates[0].clone(),
+        absorbed: duplicates[1..]
+            .iter()
+            .map(|memory| (*memory).clone())
+            .collect(),
+    })
+}
+
+/// Rewrite the survivor with the canonical text and merge the others into
+/// it: their associations move to the survivor, they go to the trash, and
+/// the survivor gets an `Updates` edge to each.
+pub async fn consolidate(
+    store: &MemoryStore,
+    embedding_table: &EmbeddingTable,
+    embedding_model: &Arc<EmbeddingModel>,
+    cluster: &[Memory],
+    plan: MergePlan,
+    canonical_content: &str,
+    model: &str,
+) -> Result<Consolidation> {
+    let MergePlan { survivor, absorbed } = plan;
+    let now = Utc::now();
+    let mut canonical = survivor;
+    canonical.content = canonical_content.trim().to_string();
+    canonical.updated_at = now;
+    for memory in &absorbed {
+        canonical.importance = canonical.importance.max(memory.importance);
+        canonical.access_count += memory.access_count;
+        canonical.last_accessed_at = canonical.last_accessed_at.max(memory.last_accessed_at);
+    }
+
+    for memory in &absorbed {
+        store.merge_memories_atomic(&canonical, memory).await?;
+        let mut merged_snapshot = memory.clone();
+        merged_snapshot.forgotten = true;
+        merged_snapshot.forgotten_at = Some(now);
+        mutations::record_or_warn(
+            store,
+            MutationKind::Merge,
+            &memory.id,
+            Some(&merged_snapshot),
+            ACTOR_CONSOLIDATION,
+            Some(&format!("consolidated into {}", canonical.id)),
+        )
+        .await;
+    }
+    let absorbed_ids: Vec<String> = absorbed.iter().map(|memory| memory.id.clone()).collect();
+    mutations::record_or_warn(
+        store,
+        MutationKind::Merge,
+        &canonical.id,
+        Some(&canonical),
+        ACTOR_CONSOLIDATION,
+        Some(&format!("consolidated {}", absorbed_ids.join(", "))),
+    )
+    .await;
+
+    let embedding = embedding_model.embed_one(&canonical.content).await?;
+    embedding_table.delete(&canonical.id).await?;
+    embedding_table
+        .store(&canonical.id, &canonical.content, &embedding)
+        .await?;
+    for id in &absorbed_ids {
+        embedding_table.delete(id).await?;
+    }
+
+    record(
+        store,
+        ConsolidationOutcome::Merged,
+        Some(&canonical.id),
+        &absorbed_ids,
+        cluster,
+        model,
+    )
+    .await
+}
+
+/// Record that the model found nothing to merge in a cluster, so it isn't
+/// reviewed again.
+pub async fn record_distinct(
+    store: &MemoryStore,
+    cluster: &[Memory],
+    model: &str,
+) -> Result<Consolidation> {
+    record(
+        store,
+        ConsolidationOutcome::Distinct,
+        None,
+        &[],
+        cluster,
+        model,
+    )
+    .await
+}
+
+/// Reviewed clusters, newest first.
+pub async fn list(store: &MemoryStore, limit: i64) -> Result<Vec<Consolidation>> {
+    let rows = sqlx::query(
+        "SELECT id, outcome, canonical_id, merged_ids, memory_ids, model, created_at \
+         FROM memory_consolidations ORDER BY created_at DESC, id ASC LIMIT ?",
+    )
+    .bind(limit)
+    .fetch_all(store.pool())
+    .await
+    .context("failed to list memory consolidations")?;
+
+    Ok(rows
+        .iter()
+        .filter_map(|row| {
+            let outcome: String = row.try_get("outcome").ok()?;
+            let merged_ids: String = row.try_get("merged_ids").ok()?;
+            let memory_ids: String = row.try_get("memory_ids").ok()?;
+            Some(Consolidation {
+                id: row.try_get("id").ok()?,
+                outcome: ConsolidationOutcome::parse(&outcome)?,
+                canonical_id: row.try_get("canonical_id").ok()?,
+                merged_ids: serde_json::from_str(&merged_ids).unwrap_or_default(),
+                memory_ids: serde_json::from_str(&memory_ids).unwrap_or_default(),
+                model: row.try_get("model").ok()?,
+                created_at: row.try_get("created_at").ok()?,
+            })
+        })
+        .collect())
+}
+
+/// Sorted member IDs as stored, so the same cluster always has the same key.
+fn cluster_key(cluster: &[Memory]) -> String {
+    let mut ids: Vec<&str> = cluster.iter().map(|memory| memory.id.as_str()).collect();
+    ids.sort_unstable();
+    serde_json::to_string(&ids).unwrap_or_default()
+}
+
+async fn judged_distinct(store: &MemoryStore, cluster: &[Memory]) -> Result<bool> {
+    let found: Option<i64> = sqlx::query_scalar(
+        "SELECT 1 FROM memory_consolidations WHERE outcome = 'distinct' AND memory_ids = ? LIMIT 1",
+    )
+    .bind(cluster_key(cluster))
+    .fetch_optional(store.pool())
+    .await
+    .context("failed to check earlier consolidation reviews")?;
+    Ok(found.is_some())
+}
+
+async fn record(
+    store: &MemoryStore,
+    outcome: ConsolidationOutcome,
+    canonical_id: Option<&str>,
+    merged_ids: &[String],
+    cluster: &[Memory],
+    model: &str,
+) -> Result<Consolidation> {
+    let consolidation = Consolidation {
+        id: uuid::Uuid::new_v4().to_string(),
+        outcome,
+        canonical_id: canonical_id.map(str::to_string),
+        merged_ids: merged_ids.to_vec(),
+        memory_ids: serde_json::from_str(&cluster_key(cluster)).unwrap_or_default(),
+        model: model.to_string(),
+        created_at: Utc::now(),
+    };
+    sqlx::query(
+        "INSERT INTO memory_consolidations \
+         (id, outcome, canonical_id, merged_ids, memory_ids, model, created_at) \
+         VALUES (?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&consolidation.id)
+    .bind(outcome.as_str())
+    .bind(&consolidation.canonical_id)
+    .bind(serde_json::to_string(&consolidation.merged_ids).context("failed to encode merged ids")?)
+    .bind(cluster_key(cluster))
+    .bind(model)
+    .bind(consolidation.created_at)
+    .execute(store.pool())
+    .await
+    .context("failed to record memory consolidation")?;
+    Ok(consolidation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_merge_keeps_the_most_important_duplicate() {
+        let cluster = vec![
+            Memory::new("uses vim", MemoryType::Preference).with_importance(0.5),
+            Memory::new("prefers vim", MemoryType::Preference).with_importance(0.7),
+            Memory::new("likes tea", MemoryType::Preference).with_importance(0.9),
+        ];
+
+        let plan = resolve_merge(&cluster, &[1, 2, 2, 7]).unwrap();
+        assert_eq!(plan.survivor.content, "prefers vim");
+        assert_eq!(plan.absorbed.len(), 1);
+        assert_eq!(plan.absorbed[0].content, "uses vim");
+
+        assert!(resolve_merge(&cluster, &[3]).is_none());
+        assert!(resolve_merge(&cluster, &[0, 9]).is_none());
+    }
+
+    #[tokio::test]
+    async fn distinct_clusters_are_not_reviewed_again() {
+        let store = MemoryStore::connect_in_memory().await;
+        let first = Memory::new("deploys on fridays", MemoryType::Fact);
+        let second = Memory::new("deploy freeze on fridays", MemoryType::Fact);
+
+        let cluster = vec![first.clone(), second.clone()];
+        assert!(!judged_distinct(&store, &cluster).await.unwrap());
+        record_distinct(&store, &cluster, "test-model")
+            .await
+            .unwrap();
+
+        // Member order doesn't matter.
+        assert!(judged_distinct(&store, &[second, first]).await.unwrap());
+        let reviews = list(&store, 10).await.unwrap();
+        assert_eq!(reviews.len(), 1);
+        assert_eq!(reviews[0].outcome, ConsolidationOutcome::Distinct);
+        assert_eq!(reviews[0].memory_ids.len(), 2);
+    }
+}
@@ -1,19 +1,56 @@
-//! Embedding generation via fastembed.
+//! Embedding generation behind pluggable providers.
+//!
+//! Agents pick a provider with `[defaults.embedding]` or `[agents.embedding]`:
+//! the bundled fastembed model (the default), OpenAI, or Voyage. Vectors from
+//! different models live in different spaces, so the provider, model, and
+//! dimension an agent's memories were embedded with are recorded in its
+//! database and checked at startup; see [`check_metadata`]. Computed
+//! vectors are kept in an on-disk [`cache::EmbeddingCache`] so repeated text
+//! isn't embedded twice.
+
+pub mod batch;
+pub mod cache;
+pub mod openai;
+pub mod voyage;
+
+use crate::config::{EmbeddingConfig, EmbeddingProviderKind};
+use crate::error::{ConfigError, LlmError, Result};
+use crate::memory::EmbeddingTable;
+use cache::EmbeddingCache;
+
+use anyhow::Context as _;
+use serde::Deserialize;
+use sqlx::SqlitePool;
 
-use crate::error::{LlmError, Result};
 use std::path::Path;
 use std::sync::Arc;
 
-/// Embedding model wrapper with thread-safe sharing.
+/// Name and dimension of the bundled local model.
+pub const LOCAL_MODEL: &str = "all-MiniLM-L6-v2";
+pub const LOCAL_DIMENSIONS: usize = 384;
+
+/// Embedding backend.
+#[async_trait::async_trait]
+pub trait EmbeddingProvider: Send + Sync {
+    /// Provider name as written in config, e.g. `openai`.
+    fn name(&self) -> &'static str;
+    fn model(&self) -> &str;
+    /// Length of every vector this provider returns.
+    fn dimensions(&self) -> usize;
+    /// Embed `texts`, returning one vector per text in order.
+    async fn embed(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>>;
+}
+
+/// The bundled all-MiniLM-L6-v2 model, run through fastembed.
 ///
 /// fastembed's TextEmbedding is not Send, so we hold it behind an Arc and
 /// use spawn_blocking to call into it from async contexts.
-pub struct EmbeddingModel {
+pub struct LocalProvider {
     model: Arc<fastembed::TextEmbedding>,
 }
 
-impl EmbeddingModel {
-    /// Create a new embedding model, storing downloaded model files in `cache_dir`.
+impl LocalProvider {
+    /// Load the model, storing downloaded model files in `cache_dir`.
     pub fn new(cache_dir: &Path) -> Result<Self> {
         let options = fastembed::InitOptions::default()
             .with_cache_dir(cache_dir.to_path_buf())
@@ -26,38 +63,204 @@ impl EmbeddingModel {
             model: Arc::new(model),
         })
     }
+}
 
-    /// Generate embeddings for multiple texts (blocking).
-    pub fn embed(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>> {
-        self.model
-            .embed(texts, None)
-            .map_err(|e| LlmError::EmbeddingFailed(e.to_string()).into())
+#[async_trait::async_trait]
+impl EmbeddingProvider for LocalProvider {
+    fn name(&self) -> &'static str {
+        EmbeddingProviderKind::Local.as_str()
     }
 
-    /// Generate embedding for a single text (blocking).
-    pub fn embed_one_blocking(&self, text: &str) -> Result<Vec<f32>> {
-        let embeddings = self.embed(vec![text.to_string()])?;
-        Ok(embeddings.into_iter().next().unwrap_or_default())
+    fn model(&self) -> &str {
+        LOCAL_MODEL
     }
 
-    /// Generate embedding for a single text (async, spawns blocking task).
-    pub async fn embed_one(self: &Arc<Self>, text: &str) -> Result<Vec<f32>> {
-        #[cfg(feature = "metrics")]
-        let _timer = crate::telemetry::Metrics::global()
-            .memory_embedding_duration_seconds
-            .start_timer();
+    fn dimensions(&self) -> usize {
+        LOCAL_DIMENSIONS
+    }
 
-        let text = text.to_string();
+    async fn embed(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>> {
         let model = self.model.clone();
-        let result = tokio::task::spawn_blocking(move || {
-            model.embed(vec![text], None).map_err(|e| {
+        tokio::task::spawn_blocking(move || {
+            model.embed(texts, None).map_err(|e| {
                 crate::Error::from(crate::error::LlmError::EmbeddingFailed(e.to_string()))
             })
         })
         .await
-        .map_err(|e| crate::Error::Other(anyhow::anyhow!("embedding task failed: {}", e)))??;
+        .map_err(|e| crate::Error::Other(anyhow::anyhow!("embedding task failed: {}", e)))?
+    }
+}
+
+/// Shared handle to an agent's embedding provider.
+pub struct EmbeddingModel {
+    provider: Arc<dyn EmbeddingProvider>,
+    cache: Option<EmbeddingCache>,
+}
+
+impl EmbeddingModel {
+    /// Load the bundled local model, storing downloaded model files in `cache_dir`.
+    pub fn new(cache_dir: &Path) -> Result<Self> {
+        Ok(Self::from_provider(Arc::new(LocalProvider::new(
+            cache_dir,
+        )?)))
+    }
+
+    pub fn from_provider(provider: Arc<dyn EmbeddingProvider>) -> Self {
+        Self {
+            provider,
+            cache: None,
+        }
+    }
+
+    /// Look up and store vectors in `cache`.
+    pub fn with_cache(mut self, cache: EmbeddingCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Build the model an agent's config selects. The local model is
+    /// expensive to load, so agents using it share `local`.
+    pub fn for_config(
+        config: &EmbeddingConfig,
+        local: &Arc<Self>,
+        client: &reqwest::Client,
+    ) -> Result<Arc<Self>> {
+        let provider: Arc<dyn EmbeddingProvider> = match config.provider {
+            EmbeddingProviderKind::Local => return Ok(local.clone()),
+            EmbeddingProviderKind::Openai => {
+                Arc::new(openai::OpenAiProvider::new(client.clone(), config)?)
+            }
+            EmbeddingProviderKind::Voyage => {
+                Arc::new(voyage::VoyageProvider::new(client.clone(), config)?)
+            }
+        };
+        Ok(Arc::new(Self {
+            provider,
+            cache: local.cache.clone(),
+        }))
+    }
+
+    pub fn provider_name(&self) -> &'static str {
+        self.provider.name()
+    }
+
+    pub fn model_name(&self) -> &str {
+        self.provider.model()
+    }
+
+    pub fn dimensions(&self) -> usize {
+        self.provider.dimensions()
+    }
+
+    /// Generate embedding for a single text.
+    pub async fn embed_one(self: &Arc<Self>, text: &str) -> Result<Vec<f32>> {
+        let embeddings = self.embed_batch(vec![text.to_string()]).await?;
+        Ok(embeddings.into_iter().next().unwrap_or_default())
+    }
+
+    /// Generate embeddings for many texts in one provider call. Bulk imports
+    /// use this instead of one call per memory. Texts already in the cache
+    /// are not sent to the provider.
+    pub async fn embed_batch(self: &Arc<Self>, texts: Vec<String>) -> Result<Vec<Vec<f32>>> {
+        let Some(cache) = &self.cache else {
+            return self.embed_uncached(texts).await;
+        };
+
+        let tag = self.cache_tag();
+        let mut cached = match cache.get_many(&tag, self.dimensions(), &texts).await {
+            Ok(cached) => cached,
+            Err(error) => {
+                tracing::warn!(%error, "embedding cache lookup failed");
+                vec![None; texts.len()]
+            }
+        };
+        let misses: Vec<usize> = (0..texts.len())
+            .filter(|&index| cached[index].is_none())
+            .collect();
+        record_cache(self.provider.name(), "hit", texts.len() - misses.len());
+        record_cache(self.provider.name(), "miss", misses.len());
+        if misses.is_empty() {
+            return Ok(cached.into_iter().flatten().collect());
+        }
+
+        let computed = self
+            .embed_uncached(misses.iter().map(|&index| texts[index].clone()).collect())
+            .await?;
+        let entries: Vec<(&str, &[f32])> = misses
+            .iter()
+            .zip(&computed)
+            .map(|(&index, vector)| (texts[index].as_str(), vector.as_slice()))
+            .collect();
+        if let Err(error) = cache.put_many(&tag, &entries).await {
+            tracing::warn!(%error, "failed to store embeddings in cache");
+        }
+        for (index, vector) in misses.into_iter().zip(computed) {
+            cached[index] = Some(vector);
+        }
+        Ok(cached.into_iter().flatten().collect())
+    }
+
+    /// Drop cached vectors for `texts`, e.g. the content of deleted memories.
+    pub async fn evict(&self, texts: &[String]) {
+        let Some(cache) = &self.cache else {
+            return;
+        };
+        if let Err(error) = cache.remove_many(&self.cache_tag(), texts).await {
+            tracing::warn!(%error, "failed to evict embeddings from cache");
+        }
+    }
+
+    /// Cache namespace for this model's vectors.
+    fn cache_tag(&self) -> String {
+        format!(
+            "{}/{}/{}",
+            self.provider.name(),
+            self.provider.model(),
+            self.dimensions()
+        )
+    }
+
+    async fn embed_uncached(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::Metrics::global()
+            .memory_embedding_duration_seconds
+            .start_timer();
+
+        let count = texts.len();
+        let embeddings = self.provider.embed(texts).await?;
+        if embeddings.len() != count {
+            return Err(LlmError::EmbeddingFailed(format!(
+                "{} returned {} vectors for {count} texts",
+                self.provider.name(),
+                embeddings.len()
+            ))
+            .into());
+        }
+        if let Some(embedding) = embeddings
+            .iter()
+            .find(|embedding| embedding.len() != self.dimensions())
+        {
+            return Err(LlmError::EmbeddingFailed(format!(
+                "{}/{} returned a {}-dimension vector, expected {}",
+                self.provider.name(),
+                self.provider.model(),
+                embedding.len(),
+                self.dimensions()
+            ))
+            .into());
+        }
+        Ok(embeddings)
+    }
+}
 
-        Ok(result.into_iter().next().unwrap_or_default())
+#[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+fn record_cache(provider: &str, result: &str, count: usize) {
+    #[cfg(feature = "metrics")]
+    if count > 0 {
+        crate::telemetry::Metrics::global()
+            .memory_embedding_cache_total
+            .with_label_values(&[provider, result])
+            .inc_by(count as u64);
     }
 }
 
@@ -65,3 +268,136 @@ impl EmbeddingModel {
 pub async fn embed_text(model: &Arc<EmbeddingModel>, text: &str) -> Result<Vec<f32>> {
     model.embed_one(text).await
 }
+
+/// Compare the provider, model, and dimension an agent's memories were
+/// embedded with against `model`. The first run records them; a mismatch
+/// fails startup rather than mixing incompatible vectors. Databases from
+/// before this check are assumed to hold local-model vectors.
+pub async fn check_metadata(
+    pool: &SqlitePool,
+    agent_id: &str,
+    model: &EmbeddingModel,
+    table: &EmbeddingTable,
+) -> Result<()> {
+    let recorded: Option<(String, String, i64)> =
+        sqlx::query_as("SELECT provider, model, dimensions FROM embedding_metadata WHERE id = 1")
+            .fetch_optional(pool)
+            .await
+            .context("failed to read embedding metadata")?;
+
+    let legacy = recorded.is_none();
+    let (provider, model_name, dimensions) = match recorded {
+        Some(recorded) => recorded,
+        None if table.row_count().await? == 0 => {
+            record_metadata(pool, model).await?;
+            return Ok(());
+        }
+        None => (
+            EmbeddingProviderKind::Local.as_str().to_string(),
+            LOCAL_MODEL.to_string(),
+            LOCAL_DIMENSIONS as i64,
+        ),
+    };
+
+    if provider == model.provider_name()
+        && model_name == model.model_name()
+        && dimensions == model.dimensions() as i64
+    {
+        if legacy {
+            record_metadata(pool, model).await?;
+        }
+        return Ok(());
+    }
+
+    Err(ConfigError::Invalid(format!(
+        "agent '{agent_id}' memories were embedded with {provider}/{model_name} \
+         ({dimensions} dimensions), but its config selects {}/{} ({} dimensions). \
+         Switch embedding back, or take a memory backup, point the agent at an empty \
+         data directory, and restore the backup so every memory is re-embedded",
+        model.provider_name(),
+        model.model_name(),
+        model.dimensions()
+    ))
+    .into())
+}
+
+async fn record_metadata(pool: &SqlitePool, model: &EmbeddingModel) -> Result<()> {
+    sqlx::query(
+        "INSERT INTO embedding_metadata (id, provider, model, dimensions) VALUES (1, ?, ?, ?) \
+         ON CONFLICT(id) DO UPDATE SET provider = excluded.provider, model = excluded.model, \
+         dimensions = excluded.dimensions, recorded_at = CURRENT_TIMESTAMP",
+    )
+    .bind(model.provider_name())
+    .bind(model.model_name())
+    .bind(model.dimensions() as i64)
+    .execute(pool)
+    .await
+    .context("failed to record embedding metadata")?;
+    Ok(())
+}
+
+/// `{"data": [{"embedding": [...], "index": 0}, ...]}`, the response shape
+/// OpenAI and Voyage share.
+#[derive(Debug, Deserialize)]
+struct EmbeddingResponse {
+    data: Vec<EmbeddingDatum>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EmbeddingDatum {
+    embedding: Vec<f32>,
+    index: usize,
+}
+
+/// POST one batch to an embeddings endpoint and return the vectors in
+/// input order.
+async fn post_embeddings(
+    provider: &str,
+    request: reqwest::RequestBuilder,
+    body: &serde_json::Value,
+) -> Result<Vec<Vec<f32>>> {
+    let response = request
+        .json(body)
+        .send()
+        .await
+        .with_context(|| format!("{provider} embedding request failed"))?;
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        let body: String = body.chars().take(500).collect();
+        return Err(LlmError::EmbeddingFailed(format!(
+            "{provider} request failed with {status}: {body}"
+        ))
+        .into());
+    }
+    let response: EmbeddingResponse = response
+        .json()
+        .await
+        .with_context(|| format!("invalid {provider} embedding response"))?;
+    Ok(in_input_order(response.data))
+}
+
+fn in_input_order(mut data: Vec<EmbeddingDatum>) -> Vec<Vec<f32>> {
+    data.sort_by_key(|datum| datum.index);
+    data.into_iter().map(|datum| datum.embedding).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn response_vectors_follow_input_order() {
+        let response: EmbeddingResponse = serde_json::from_str(
+            r#"{"object": "list", "data": [
+                {"object": "embedding", "embedding": [0.2, 0.3], "index": 1},
+                {"object": "embedding", "embedding": [0.0, 0.1], "index": 0}
+            ], "model": "text-embedding-3-small"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            in_input_order(response.data),
+            vec![vec![0.0, 0.1], vec![0.2, 0.3]]
+        );
+    }
+}
@@ -0,0 +1,208 @@
+//! Deferred, batched embedding of memories saved in bulk.
+//!
+//! A memory persistence run can save a dozen memories, and embedding each one
+//! as it is saved costs a provider request apiece. Instead the run queues its
+//! saved memories here and [`PendingEmbeddings::flush`] embeds the queue in
+//! provider calls of at most `max_batch_size` texts once the run completes. A
+//! memory whose batch fails to embed or store is deleted, so none is left
+//! behind without a vector.
+
+use crate::memory::MemorySearch;
+
+use std::sync::Mutex;
+
+/// Memories saved to SQLite whose embeddings haven't been computed yet.
+#[derive(Debug)]
+pub struct PendingEmbeddings {
+    max_batch_size: usize,
+    /// (memory ID, content) in save order.
+    queue: Mutex<Vec<(String, String)>>,
+}
+
+impl PendingEmbeddings {
+    pub fn new(max_batch_size: usize) -> Self {
+        Self {
+            max_batch_size: max_batch_size.max(1),
+            queue: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Queue a saved memory for embedding.
+    pub fn push(&self, memory_id: impl Into<String>, content: impl Into<String>) {
+        self.queue().push((memory_id.into(), content.into()));
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue().is_empty()
+    }
+
+    /// Embed and store everything queued, one provider call per batch.
+    /// Returns the IDs of memories that were deleted because their batch
+    /// failed.
+    pub async fn flush(&self, memory_search: &MemorySearch) -> Vec<String> {
+        let pending = std::mem::take(&mut *self.queue());
+        if pending.is_empty() {
+            return Vec::new();
+        }
+
+        let mut stored = 0;
+        let mut failed = Vec::new();
+        for batch in pending.chunks(self.max_batch_size) {
+            match embed_and_store(memory_search, batch).await {
+                Ok(()) => stored += batch.len(),
+                Err(error) => {
+                    tracing::warn!(
+                        %error,
+                        count = batch.len(),
+                        "failed to embed memory batch, deleting its memories"
+                    );
+                    for (memory_id, _) in batch {
+                        discard(memory_search, memory_id).await;
+                        failed.push(memory_id.clone());
+                    }
+                }
+            }
+        }
+
+        if stored > 0
+            && let Err(error) = memory_search.embedding_table().ensure_fts_index().await
+        {
+            tracing::warn!(%error, "failed to ensure FTS index after batched embedding");
+        }
+        tracing::debug!(
+            stored,
+            failed = failed.len(),
+            max_batch_size = self.max_batch_size,
+            "flushed pending memory embeddings"
+        );
+        failed
+    }
+
+    fn queue(&self) -> std::sync::MutexGuard<'_, Vec<(String, String)>> {
+        self.queue
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+async fn embed_and_store(
+    memory_search: &MemorySearch,
+    batch: &[(String, String)],
+) -> crate::error::Result<()> {
+    let embeddings = memory_search
+        .embedding_model_arc()
+        .embed_batch(batch.iter().map(|(_, content)| content.clone()).collect())
+        .await?;
+    let rows: Vec<(&str, &str, &[f32])> = batch
+        .iter()
+        .zip(&embeddings)
+        .map(|((memory_id, content), embedding)| {
+            (memory_id.as_str(), content.as_str(), embedding.as_slice())
+        })
+        .collect();
+    memory_search.embedding_table().store_batch(&rows).await
+}
+
+/// Remove a memory that couldn't be embedded, along with its associations.
+async fn discard(memory_search: &MemorySearch, memory_id: &str) {
+    let store = memory_search.store();
+    if let Err(error) = store.delete_associations_for_memory(memory_id).await {
+        tracing::error!(
+            %memory_id,
+            %error,
+            "compensating association delete failed after batched embedding error"
+        );
+    }
+    if let Err(error) = store.delete(memory_id).await {
+        tracing::error!(
+            %memory_id,
+            %error,
+            "compensating delete failed after batched embedding error"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::embedding::{EmbeddingModel, EmbeddingProvider};
+    use crate::memory::{EmbeddingTable, Memory, MemoryStore, MemoryType};
+
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct CountingProvider {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl EmbeddingProvider for CountingProvider {
+        fn name(&self) -> &'static str {
+            "test"
+        }
+
+        fn model(&self) -> &str {
+            "counting"
+        }
+
+        fn dimensions(&self) -> usize {
+            2
+        }
+
+        async fn embed(&self, texts: Vec<String>) -> crate::error::Result<Vec<Vec<f32>>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(texts
+                .iter()
+                .map(|text| vec![text.len() as f32, 1.0])
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn flush_embeds_in_batches_of_max_size() {
+        let provider = Arc::new(CountingProvider {
+            calls: AtomicUsize::new(0),
+        });
+        let lance_dir = tempfile::tempdir().unwrap();
+        let connection = lancedb::connect(lance_dir.path().to_str().unwrap())
+            .execute()
+            .await
+            .unwrap();
+        let search = MemorySearch::new(
+            MemoryStore::connect_in_memory().await,
+            EmbeddingTable::open_or_create(&connection, 2)
+                .await
+                .unwrap(),
+            Arc::new(EmbeddingModel::from_provider(provider.clone())),
+        );
+
+        let pending = PendingEmbeddings::new(2);
+        let mut ids = Vec::new();
+        for content in ["one", "two", "three", "four", "five"] {
+            let memory = Memory::new(content, MemoryType::Fact);
+            search.store().save(&memory).await.unwrap();
+            pending.push(&memory.id, content);
+            ids.push(memory.id);
+        }
+        assert_eq!(pending.len(), 5);
+
+        let failed = pending.flush(&search).await;
+        assert!(failed.is_empty());
+        assert!(pending.is_empty());
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 3);
+        assert_eq!(search.embedding_table().row_count().await.unwrap(), 5);
+        assert_eq!(
+            search
+                .embedding_table()
+                .embeddings_for(&ids)
+                .await
+                .unwrap()
+                .len(),
+            5
+        );
+    }
+}
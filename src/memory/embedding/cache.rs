@@ -0,0 +1,415 @@
+//! On-disk cache of computed embeddings.
+//!
+//! Memory saves, searches, and imports often embed text that was embedded
+//! before: the same query asked twice, a memory re-saved after an edit that
+//! didn't touch its content. Vectors are stored in a dedicated redb
+//! (`embedding_cache/vectors.redb`), keyed by a hash of the text and the
+//! provider, model, and dimension that produced them, so switching models
+//! never returns a stale vector. The file holds nothing that can't be
+//! recomputed and can be deleted at any time. Entries carry a last-used
+//! time: the cache is capped at a fixed entry count, evicting the least
+//! recently used, and entries unused past an age limit are swept out.
+
+use crate::error::Result;
+
+use anyhow::Context as _;
+use redb::{Database, ReadableTable as _, ReadableTableMetadata as _, TableDefinition};
+use sha2::{Digest as _, Sha256};
+
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicI64, Ordering};
+
+/// Table: sha256(model tag, text) -> little-endian i64 last-used unix
+/// seconds followed by the little-endian f32 vector
+const VECTORS_TABLE: TableDefinition<&[u8], &[u8]> = TableDefinition::new("embedding_vectors_v2");
+/// Vectors stored before entries carried a last-used time.
+const LEGACY_VECTORS_TABLE: TableDefinition<&[u8], &[u8]> =
+    TableDefinition::new("embedding_vectors");
+
+/// Entries kept before the least recently used are evicted.
+const DEFAULT_MAX_ENTRIES: u64 = 100_000;
+/// Entries unused for this long are dropped.
+const DEFAULT_MAX_AGE_SECS: i64 = 30 * 24 * 60 * 60;
+/// A hit only rewrites an entry's last-used time once it is this old, so
+/// most lookups stay read-only.
+const TOUCH_INTERVAL_SECS: i64 = 24 * 60 * 60;
+/// How often writes sweep out entries past the age limit.
+const PRUNE_INTERVAL_SECS: i64 = 60 * 60;
+
+/// Persistent embedding cache shared by every agent.
+#[derive(Clone)]
+pub struct EmbeddingCache {
+    db: Arc<Database>,
+    max_entries: u64,
+    max_age_secs: i64,
+    last_pruned: Arc<AtomicI64>,
+}
+
+impl EmbeddingCache {
+    /// Open or create the cache at `path`, dropping entries past the age
+    /// limit.
+    pub fn open(path: &Path) -> Result<Self> {
+        let db = Database::create(path).context("failed to open embedding cache")?;
+        let write_txn = db.begin_write().context("failed to begin write txn")?;
+        write_txn
+            .open_table(VECTORS_TABLE)
+            .context("failed to open embedding cache table")?;
+        write_txn
+            .delete_table(LEGACY_VECTORS_TABLE)
+            .context("failed to drop legacy embedding cache table")?;
+        write_txn.commit().context("failed to commit write txn")?;
+        let cache = Self {
+            db: Arc::new(db),
+            max_entries: DEFAULT_MAX_ENTRIES,
+            max_age_secs: DEFAULT_MAX_AGE_SECS,
+            last_pruned: Arc::new(AtomicI64::new(0)),
+        };
+        cache.prune_blocking(now())?;
+        Ok(cache)
+    }
+
+    /// Override the entry cap and age limit.
+    pub fn with_limits(mut self, max_entries: u64, max_age: std::time::Duration) -> Self {
+        self.max_entries = max_entries;
+        self.max_age_secs = max_age.as_secs() as i64;
+        self
+    }
+
+    /// Cached vectors for `texts`, in order. `None` where there is no entry
+    /// for `model_tag` or the entry isn't `dimensions` long. Hits count as a
+    /// use for eviction.
+    pub async fn get_many(
+        &self,
+        model_tag: &str,
+        dimensions: usize,
+        texts: &[String],
+    ) -> Result<Vec<Option<Vec<f32>>>> {
+        let db = self.db.clone();
+        let keys: Vec<[u8; 32]> = texts.iter().map(|text| key(model_tag, text)).collect();
+        tokio::task::spawn_blocking(move || -> Result<Vec<Option<Vec<f32>>>> {
+            let now = now();
+            let mut stale = Vec::new();
+            let hits = {
+                let read_txn = db.begin_read().context("failed to begin read txn")?;
+                let table = read_txn
+                    .open_table(VECTORS_TABLE)
+                    .context("failed to open embedding cache table")?;
+                keys.iter()
+                    .map(|key| {
+                        let value = table
+                            .get(key.as_slice())
+                            .context("failed to read embedding cache")?;
+                        let Some(value) = value else {
+                            return Ok::<_, crate::Error>(None);
+                        };
+                        let Some((last_used, vector)) = decode(value.value(), dimensions) else {
+                            return Ok(None);
+                        };
+                        if now - last_used >= TOUCH_INTERVAL_SECS {
+                            stale.push((*key, encode(now, &vector)));
+                        }
+                        Ok(Some(vector))
+                    })
+                    .collect::<Result<Vec<_>>>()?
+            };
+
+            if !stale.is_empty() {
+                let write_txn = db.begin_write().context("failed to begin write txn")?;
+                {
+                    let mut table = write_txn
+                        .open_table(VECTORS_TABLE)
+                        .context("failed to open embedding cache table")?;
+                    for (key, value) in &stale {
+                        table
+                            .insert(key.as_slice(), value.as_slice())
+                            .context("failed to write embedding cache")?;
+                    }
+                }
+                write_txn
+                    .commit()
+                    .context("failed to commit embedding cache")?;
+            }
+            Ok(hits)
+        })
+        .await
+        .context("embedding cache task failed")?
+    }
+
+    /// Store computed vectors under `model_tag`, evicting the least recently
+    /// used entries once the cache is over its cap.
+    pub async fn put_many(&self, model_tag: &str, entries: &[(&str, &[f32])]) -> Result<()> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+        let cache = self.clone();
+        let now = now();
+        let entries: Vec<([u8; 32], Vec<u8>)> = entries
+            .iter()
+            .map(|(text, vector)| (key(model_tag, text), encode(now, vector)))
+            .collect();
+        tokio::task::spawn_blocking(move || -> Result<()> {
+            let write_txn = cache
+                .db
+                .begin_write()
+                .context("failed to begin write txn")?;
+            {
+                let mut table = write_txn
+                    .open_table(VECTORS_TABLE)
+                    .context("failed to open embedding cache table")?;
+                for (key, value) in &entries {
+                    table
+                        .insert(key.as_slice(), value.as_slice())
+                        .context("failed to write embedding cache")?;
+                }
+                let len = table.len().context("failed to count embedding cache")?;
+                if len > cache.max_entries {
+                    // Evict down to 90% of the cap so the scan isn't repeated
+                    // on every following write.
+                    let keep = cache.max_entries - cache.max_entries / 10;
+                    let mut by_age = Vec::with_capacity(len as usize);
+                    for entry in table.iter().context("failed to scan embedding cache")? {
+                        let (key, value) = entry.context("failed to read embedding cache")?;
+                        by_age.push((last_used(value.value()), key.value().to_vec()));
+                    }
+                    by_age.sort_unstable();
+                    let evict = by_age.len().saturating_sub(keep as usize);
+                    for (_, key) in &by_age[..evict] {
+                        table
+                            .remove(key.as_slice())
+                            .context("failed to evict from embedding cache")?;
+                    }
+                }
+            }
+            write_txn
+                .commit()
+                .context("failed to commit embedding cache")?;
+            if now - cache.last_pruned.load(Ordering::Relaxed) >= PRUNE_INTERVAL_SECS {
+                cache.prune_blocking(now)?;
+            }
+            Ok(())
+        })
+        .await
+        .context("embedding cache task failed")?
+    }
+
+    /// Drop the vectors for `texts` under `model_tag`, e.g. once the memory
+    /// they were computed for is deleted.
+    pub async fn remove_many(&self, model_tag: &str, texts: &[String]) -> Result<()> {
+        if texts.is_empty() {
+            return Ok(());
+        }
+        let db = self.db.clone();
+        let keys: Vec<[u8; 32]> = texts.iter().map(|text| key(model_tag, text)).collect();
+        tokio::task::spawn_blocking(move || -> Result<()> {
+            let write_txn = db.begin_write().context("failed to begin write txn")?;
+            {
+                let mut table = write_txn
+                    .open_table(VECTORS_TABLE)
+                    .context("failed to open embedding cache table")?;
+                for key in &keys {
+                    table
+                        .remove(key.as_slice())
+                        .context("failed to remove from embedding cache")?;
+                }
+            }
+            write_txn
+                .commit()
+                .context("failed to commit embedding cache")?;
+            Ok(())
+        })
+        .await
+        .context("embedding cache task failed")?
+    }
+
+    /// Drop entries unused for longer than the age limit. Blocking; scans
+    /// the whole table.
+    fn prune_blocking(&self, now: i64) -> Result<()> {
+        self.last_pruned.store(now, Ordering::Relaxed);
+        let cutoff = now - self.max_age_secs;
+        let write_txn = self.db.begin_write().context("failed to begin write txn")?;
+        let removed = {
+            let mut table = write_txn
+                .open_table(VECTORS_TABLE)
+                .context("failed to open embedding cache table")?;
+            let before = table.len().context("failed to count embedding cache")?;
+            table
+                .retain(|_, value| last_used(value) >= cutoff)
+                .context("failed to prune embedding cache")?;
+            before - table.len().context("failed to count embedding cache")?
+        };
+        write_txn
+            .commit()
+            .context("failed to commit embedding cache")?;
+        if removed > 0 {
+            tracing::debug!(removed, "pruned stale embedding cache entries");
+        }
+        Ok(())
+    }
+}
+
+fn now() -> i64 {
+    chrono::Utc::now().timestamp()
+}
+
+fn key(model_tag: &str, text: &str) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(model_tag.as_bytes());
+    hasher.update([0]);
+    hasher.update(text.as_bytes());
+    hasher.finalize().into()
+}
+
+fn encode(last_used: i64, vector: &[f32]) -> Vec<u8> {
+    last_used
+        .to_le_bytes()
+        .into_iter()
+        .chain(vector.iter().flat_map(|value| value.to_le_bytes()))
+        .collect()
+}
+
+/// Last-used time of a stored value; unreadable values sort first.
+fn last_used(bytes: &[u8]) -> i64 {
+    bytes
+        .get(..8)
+        .and_then(|prefix| prefix.try_into().ok())
+        .map(i64::from_le_bytes)
+        .unwrap_or(i64::MIN)
+}
+
+fn decode(bytes: &[u8], dimensions: usize) -> Option<(i64, Vec<f32>)> {
+    if bytes.len() != 8 + dimensions * 4 {
+        return None;
+    }
+    let (prefix, vector) = bytes.split_at(8);
+    Some((
+        i64::from_le_bytes(prefix.try_into().ok()?),
+        vector
+            .chunks_exact(4)
+            .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+            .collect(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn vectors_are_keyed_by_model_and_text() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = EmbeddingCache::open(&dir.path().join("vectors.redb")).unwrap();
+        let vector = [0.25_f32, -1.5, 3.0];
+        cache
+            .put_many("local/all-MiniLM-L6-v2/3", &[("hello", vector.as_slice())])
+            .await
+            .unwrap();
+
+        let texts = vec!["hello".to_string(), "other".to_string()];
+        let hits = cache
+            .get_many("local/all-MiniLM-L6-v2/3", 3, &texts)
+            .await
+            .unwrap();
+        assert_eq!(hits, vec![Some(vector.to_vec()), None]);
+
+        let other_model = cache
+            .get_many("openai/text-embedding-3-small/3", 3, &texts)
+            .await
+            .unwrap();
+        assert_eq!(other_model, vec![None, None]);
+        let wrong_length = cache
+            .get_many("local/all-MiniLM-L6-v2/3", 4, &texts)
+            .await
+            .unwrap();
+        assert_eq!(wrong_length[0], None);
+    }
+
+    #[tokio::test]
+    async fn removed_vectors_are_misses() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = EmbeddingCache::open(&dir.path().join("vectors.redb")).unwrap();
+        let vector = [1.0_f32, 2.0];
+        cache
+            .put_many(
+                "tag",
+                &[("keep", vector.as_slice()), ("drop", vector.as_slice())],
+            )
+            .await
+            .unwrap();
+
+        cache
+            .remove_many("tag", &["drop".to_string()])
+            .await
+            .unwrap();
+        let hits = cache
+            .get_many("tag", 2, &["keep".to_string(), "drop".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(hits, vec![Some(vector.to_vec()), None]);
+    }
+
+    #[tokio::test]
+    async fn least_recently_used_entries_are_evicted_past_the_cap() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = EmbeddingCache::open(&dir.path().join("vectors.redb"))
+            .unwrap()
+            .with_limits(10, std::time::Duration::from_secs(3600));
+        let vector = [1.0_f32];
+        let texts: Vec<String> = (0..11).map(|index| format!("text {index}")).collect();
+        // Write the first ten as if last used long ago, oldest first.
+        {
+            let write_txn = cache.db.begin_write().unwrap();
+            {
+                let mut table = write_txn.open_table(VECTORS_TABLE).unwrap();
+                for (index, text) in texts[..10].iter().enumerate() {
+                    let value = encode(now() - 1000 + index as i64, &vector);
+                    table
+                        .insert(key("tag", text).as_slice(), value.as_slice())
+                        .unwrap();
+                }
+            }
+            write_txn.commit().unwrap();
+        }
+
+        cache
+            .put_many("tag", &[(texts[10].as_str(), vector.as_slice())])
+            .await
+            .unwrap();
+        let hits = cache.get_many("tag", 1, &texts).await.unwrap();
+        let kept: Vec<bool> = hits.iter().map(Option::is_some).collect();
+        // Evicted down to 9: the two least recently used are gone.
+        assert_eq!(kept.iter().filter(|kept| **kept).count(), 9);
+        assert!(!kept[0] && !kept[1]);
+        assert!(kept[10]);
+    }
+
+    #[tokio::test]
+    async fn entries_past_the_age_limit_are_dropped_on_open() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("vectors.redb");
+        let vector = [1.0_f32];
+        {
+            let cache = EmbeddingCache::open(&path).unwrap();
+            let write_txn = cache.db.begin_write().unwrap();
+            {
+                let mut table = write_txn.open_table(VECTORS_TABLE).unwrap();
+                let stale = encode(now() - DEFAULT_MAX_AGE_SECS - 1, &vector);
+                table
+                    .insert(key("tag", "stale").as_slice(), stale.as_slice())
+                    .unwrap();
+                let fresh = encode(now(), &vector);
+                table
+                    .insert(key("tag", "fresh").as_slice(), fresh.as_slice())
+                    .unwrap();
+            }
+            write_txn.commit().unwrap();
+        }
+
+        let cache = EmbeddingCache::open(&path).unwrap();
+        let hits = cache
+            .get_many("tag", 1, &["stale".to_string(), "fresh".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(hits, vec![None, Some(vector.to_vec())]);
+    }
+}
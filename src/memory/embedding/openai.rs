@@ -0,0 +1,96 @@
+//! OpenAI embeddings, or any OpenAI-compatible endpoint via `base_url`.
+
+use super::{EmbeddingProvider, post_embeddings};
+use crate::config::{EmbeddingConfig, EmbeddingProviderKind};
+use crate::error::{ConfigError, Result};
+
+const DEFAULT_BASE_URL: &str = "https://api.openai.com/v1";
+const DEFAULT_MODEL: &str = "text-embedding-3-small";
+/// Inputs per request; the API accepts up to 2048.
+const MAX_BATCH: usize = 512;
+
+pub struct OpenAiProvider {
+    client: reqwest::Client,
+    api_key: String,
+    base_url: String,
+    model: String,
+    dimensions: usize,
+    /// Sent as `dimensions` when the config asked for shortened vectors.
+    requested_dimensions: Option<usize>,
+}
+
+impl OpenAiProvider {
+    pub fn new(client: reqwest::Client, config: &EmbeddingConfig) -> Result<Self> {
+        let model = config.model.as_deref().unwrap_or(DEFAULT_MODEL).to_string();
+        let dimensions = config
+            .dimensions
+            .or_else(|| default_dimensions(&model))
+            .ok_or_else(|| {
+                ConfigError::Invalid(format!(
+                    "embedding model '{model}' has no known dimension; set embedding.dimensions"
+                ))
+            })?;
+        let api_key = config.api_key.clone().ok_or_else(|| {
+            ConfigError::Invalid(format!(
+                "embedding provider '{}' requires 'api_key'",
+                EmbeddingProviderKind::Openai.as_str()
+            ))
+        })?;
+
+        Ok(Self {
+            client,
+            api_key,
+            base_url: config
+                .base_url
+                .clone()
+                .unwrap_or_else(|| DEFAULT_BASE_URL.to_string()),
+            model,
+            dimensions,
+            requested_dimensions: config.dimensions,
+        })
+    }
+}
+
+/// Native vector size of OpenAI's embedding models.
+fn default_dimensions(model: &str) -> Option<usize> {
+    match model {
+        "text-embedding-3-small" | "text-embedding-ada-002" => Some(1536),
+        "text-embedding-3-large" => Some(3072),
+        _ => None,
+    }
+}
+
+#[async_trait::async_trait]
+impl EmbeddingProvider for OpenAiProvider {
+    fn name(&self) -> &'static str {
+        EmbeddingProviderKind::Openai.as_str()
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    async fn embed(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>> {
+        let mut embeddings = Vec::with_capacity(texts.len());
+        for batch in texts.chunks(MAX_BATCH) {
+            let mut body = serde_json::json!({
+                "model": self.model,
+                "input": batch,
+                "encoding_format": "float",
+            });
+            if let Some(dimensions) = self.requested_dimensions {
+                body["dimensions"] = dimensions.into();
+            }
+            let request = self
+                .client
+                .post(format!("{}/embeddings", self.base_url))
+                .bearer_auth(&self.api_key);
+            embeddings.extend(post_embeddings(self.name(), request, &body).await?);
+        }
+        Ok(embeddings)
+    }
+}
@@ -0,0 +1,96 @@
+//! Voyage AI embeddings.
+
+use super::{EmbeddingProvider, post_embeddings};
+use crate::config::{EmbeddingConfig, EmbeddingProviderKind};
+use crate::error::{ConfigError, Result};
+
+const DEFAULT_BASE_URL: &str = "https://api.voyageai.com/v1";
+const DEFAULT_MODEL: &str = "voyage-3.5";
+/// Inputs per request; the API accepts up to 1000 within a token budget.
+const MAX_BATCH: usize = 128;
+
+pub struct VoyageProvider {
+    client: reqwest::Client,
+    api_key: String,
+    base_url: String,
+    model: String,
+    dimensions: usize,
+    /// Sent as `output_dimension` when the config asked for a non-default size.
+    requested_dimensions: Option<usize>,
+}
+
+impl VoyageProvider {
+    pub fn new(client: reqwest::Client, config: &EmbeddingConfig) -> Result<Self> {
+        let model = config.model.as_deref().unwrap_or(DEFAULT_MODEL).to_string();
+        let dimensions = config
+            .dimensions
+            .or_else(|| default_dimensions(&model))
+            .ok_or_else(|| {
+                ConfigError::Invalid(format!(
+                    "embedding model '{model}' has no known dimension; set embedding.dimensions"
+                ))
+            })?;
+        let api_key = config.api_key.clone().ok_or_else(|| {
+            ConfigError::Invalid(format!(
+                "embedding provider '{}' requires 'api_key'",
+                EmbeddingProviderKind::Voyage.as_str()
+            ))
+        })?;
+
+        Ok(Self {
+            client,
+            api_key,
+            base_url: config
+                .base_url
+                .clone()
+                .unwrap_or_else(|| DEFAULT_BASE_URL.to_string()),
+            model,
+            dimensions,
+            requested_dimensions: config.dimensions,
+        })
+    }
+}
+
+/// Default vector size of Voyage's current models.
+fn default_dimensions(model: &str) -> Option<usize> {
+    match model {
+        "voyage-3.5" | "voyage-3.5-lite" | "voyage-3-large" | "voyage-3" | "voyage-code-3"
+        | "voyage-finance-2" | "voyage-law-2" => Some(1024),
+        "voyage-3-lite" => Some(512),
+        _ => None,
+    }
+}
+
+#[async_trait::async_trait]
+impl EmbeddingProvider for VoyageProvider {
+    fn name(&self) -> &'static str {
+        EmbeddingProviderKind::Voyage.as_str()
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    async fn embed(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>> {
+        let mut embeddings = Vec::with_capacity(texts.len());
+        for batch in texts.chunks(MAX_BATCH) {
+            let mut body = serde_json::json!({
+                "model": self.model,
+                "input": batch,
+            });
+            if let Some(dimensions) = self.requested_dimensions {
+                body["output_dimension"] = dimensions.into();
+            }
+            let request = self
+                .client
+                .post(format!("{}/embeddings", self.base_url))
+                .bearer_auth(&self.api_key);
+            embeddings.extend(post_embeddings(self.name(), request, &body).await?);
+        }
+        Ok(embeddings)
+    }
+}
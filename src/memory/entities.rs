@@ -0,0 +1,300 @@
+//! Entity profiles: everything known about a named person, project, or place.
+//!
+//! There is no entity table. An entity is whatever a name refers to, and its
+//! profile is assembled on demand from memories that mention the name, the
+//! graph edges around them, and recent conversation messages that mention it.
+
+use crate::conversation::history::ConversationLogger;
+use crate::conversation::{ChannelLookupPolicy, ChannelStore};
+use crate::error::Result;
+use crate::memory::scope::{MemoryScopes, ScopeFilter};
+use crate::memory::{Association, Memory, MemoryStore};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+/// Names shorter than this match too much to be useful.
+pub const MIN_ENTITY_NAME_CHARS: usize = 2;
+
+/// Everything known about one entity.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct EntityProfile {
+    pub name: String,
+    /// Memories that mention the entity by name, most important first.
+    pub memories: Vec<Memory>,
+    /// Memories linked to those through the graph without naming the entity.
+    pub related: Vec<Memory>,
+    /// Edges touching the entity's memories.
+    pub associations: Vec<Association>,
+    /// Recent conversation messages that mention the entity, newest first.
+    pub mentions: Vec<EntityMention>,
+}
+
+/// A conversation message that mentions an entity.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct EntityMention {
+    pub message_id: String,
+    pub channel_id: String,
+    pub role: String,
+    pub sender_name: Option<String>,
+    pub content: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Which channels' messages a profile may quote.
+#[derive(Debug, Clone, Default)]
+pub enum MentionFilter {
+    /// Every channel, for the operator and processes not tied to a channel.
+    #[default]
+    All,
+    /// What `lookup_channel` would let `channel_id` read: its own messages,
+    /// plus channels that allow lookups and whose memory scope it can recall.
+    FromChannel {
+        channel_id: String,
+        memory_scopes: MemoryScopes,
+        channel_store: ChannelStore,
+    },
+}
+
+impl MentionFilter {
+    async fn allows(&self, channel_id: &str) -> Result<bool> {
+        let MentionFilter::FromChannel {
+            channel_id: own,
+            memory_scopes,
+            channel_store,
+        } = self
+        else {
+            return Ok(true);
+        };
+        if channel_id == own {
+            return Ok(true);
+        }
+        if channel_store.lookup_policy(channel_id).await? == ChannelLookupPolicy::Private {
+            return Ok(false);
+        }
+        Ok(memory_scopes
+            .filter_for_channel(own)
+            .allows(memory_scopes.scope_for_channel(channel_id)))
+    }
+}
+
+/// Trim a requested entity name, rejecting ones too short to search for.
+pub fn normalize_name(name: &str) -> Option<&str> {
+    let name = name.trim();
+    (name.chars().count() >= MIN_ENTITY_NAME_CHARS).then_some(name)
+}
+
+/// Assemble the profile of `name`. Only memories visible under
+/// `scope_filter` are included, along with the edges between them, and only
+/// messages from channels `mention_filter` allows.
+pub async fn build_profile(
+    store: &MemoryStore,
+    conversation_logger: &ConversationLogger,
+    name: &str,
+    scope_filter: &ScopeFilter,
+    mention_filter: &MentionFilter,
+    memory_limit: i64,
+    mention_limit: i64,
+) -> Result<EntityProfile> {
+    let memories = store
+        .find_mentioning(name, memory_limit, scope_filter)
+        .await?;
+    let direct_ids: HashSet<&str> = memories.iter().map(|memory| memory.id.as_str()).collect();
+
+    let mut associations = Vec::new();
+    let mut seen_associations = HashSet::new();
+    let mut related_ids = Vec::new();
+    for memory in &memories {
+        for association in store.get_associations(&memory.id).await? {
+            let other = if association.source_id == memory.id {
+                &association.target_id
+            } else {
+                &association.source_id
+            };
+            if !direct_ids.contains(other.as_str()) && !related_ids.contains(other) {
+                related_ids.push(other.clone());
+            }
+            if seen_associations.insert(association.id.clone()) {
+                associations.push(association);
+            }
+        }
+    }
+
+    let mut related = Vec::new();
+    for id in &related_ids {
+        if let Some(memory) = store.load(id).await?
+            && !memory.forgotten
+            && scope_filter.allows(memory.scope.as_deref())
+        {
+            related.push(memory);
+        }
+    }
+    // Drop edges that lead to memories the caller can't see.
+    let visible: HashSet<&str> = direct_ids
+        .iter()
+        .copied()
+        .chain(related.iter().map(|memory| memory.id.as_str()))
+        .collect();
+    associations.retain(|association| {
+        visible.contains(association.source_id.as_str())
+            && visible.contains(association.target_id.as_str())
+    });
+    related.sort_by(|a, b| b.importance.total_cmp(&a.importance));
+
+    // Over-fetch when filtering so hidden channels don't crowd out the
+    // visible ones.
+    let fetch_limit = match mention_filter {
+        MentionFilter::All => mention_limit,
+        MentionFilter::FromChannel { .. } => mention_limit.saturating_mul(4),
+    };
+    let mut allowed_channels: HashMap<String, bool> = HashMap::new();
+    let mut mentions = Vec::new();
+    for message in conversation_logger.find_mentions(name, fetch_limit).await? {
+        if mentions.len() as i64 >= mention_limit {
+            break;
+        }
+        let allowed = match allowed_channels.get(&message.channel_id) {
+            Some(allowed) => *allowed,
+            None => {
+                let allowed = mention_filter.allows(&message.channel_id).await?;
+                allowed_channels.insert(message.channel_id.clone(), allowed);
+                allowed
+            }
+        };
+        if allowed {
+            mentions.push(EntityMention {
+                message_id: message.id,
+                channel_id: message.channel_id,
+                role: message.role,
+                sender_name: message.sender_name,
+                content: message.content,
+                created_at: message.created_at,
+            });
+        }
+    }
+
+    Ok(EntityProfile {
+        name: name.to_string(),
+        memories,
+        related,
+        associations,
+        mentions,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::scope::MemoryScope;
+    use crate::memory::{MemoryType, RelationType};
+
+    #[tokio::test]
+    async fn profile_collects_mentions_and_neighbors() {
+        let store = MemoryStore::connect_in_memory().await;
+        let direct = Memory::new("Priya leads the billing migration", MemoryType::Fact);
+        let tagged = Memory::new("Prefers async updates", MemoryType::Preference)
+            .with_tags(vec!["priya".into()]);
+        let neighbor = Memory::new("Billing migration ships in May", MemoryType::Event);
+        let unrelated = Memory::new("The office moved to Lisbon", MemoryType::Fact);
+        for memory in [&direct, &tagged, &neighbor, &unrelated] {
+            store.save(memory).await.unwrap();
+        }
+        store
+            .create_association(&Association::new(
+                &direct.id,
+                &neighbor.id,
+                RelationType::RelatedTo,
+            ))
+            .await
+            .unwrap();
+
+        let logger = ConversationLogger::new(store.pool().clone());
+        let profile = build_profile(
+            &store,
+            &logger,
+            "priya",
+            &ScopeFilter::All,
+            &MentionFilter::All,
+            10,
+            10,
+        )
+        .await
+        .unwrap();
+
+        let ids: Vec<&str> = profile.memories.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids.len(), 2);
+        assert!(ids.contains(&direct.id.as_str()) && ids.contains(&tagged.id.as_str()));
+        assert_eq!(profile.related.len(), 1);
+        assert_eq!(profile.related[0].id, neighbor.id);
+        assert_eq!(profile.associations.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn mentions_respect_lookup_policy_and_scopes() {
+        let store = MemoryStore::connect_in_memory().await;
+        let pool = store.pool().clone();
+        for (channel_id, policy) in [
+            ("discord:1", "open"),
+            ("discord:2", "open"),
+            ("discord:3", "private"),
+            ("slack:1", "open"),
+        ] {
+            sqlx::query(
+                "INSERT INTO channels (id, platform, lookup_policy, created_at, last_activity_at) \
+                 VALUES (?, 'test', ?, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP)",
+            )
+            .bind(channel_id)
+            .bind(policy)
+            .execute(&pool)
+            .await
+            .unwrap();
+            sqlx::query(
+                "INSERT INTO conversation_messages (id, channel_id, role, content) \
+                 VALUES (?, ?, 'user', 'Priya said hi')",
+            )
+            .bind(format!("msg-{channel_id}"))
+            .bind(channel_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let mention_filter = MentionFilter::FromChannel {
+            channel_id: "discord:1".into(),
+            memory_scopes: MemoryScopes::new(vec![MemoryScope {
+                name: "work".into(),
+                channels: vec!["slack:*".into()],
+                share_with: vec![],
+            }])
+            .unwrap(),
+            channel_store: ChannelStore::new(pool.clone()),
+        };
+        let logger = ConversationLogger::new(pool);
+        let profile = build_profile(
+            &store,
+            &logger,
+            "priya",
+            &ScopeFilter::All,
+            &mention_filter,
+            10,
+            10,
+        )
+        .await
+        .unwrap();
+
+        let mut channels: Vec<&str> = profile
+            .mentions
+            .iter()
+            .map(|mention| mention.channel_id.as_str())
+            .collect();
+        channels.sort();
+        assert_eq!(channels, vec!["discord:1", "discord:2"]);
+    }
+
+    #[test]
+    fn rejects_names_too_short_to_search() {
+        assert_eq!(normalize_name("  Priya "), Some("Priya"));
+        assert_eq!(normalize_name(" x "), None);
+    }
+}
@@ -0,0 +1,378 @@
+//! Usage feedback and importance recalibration.
+//!
+//! Importance is assigned when a memory is written and drifts from how useful
+//! the memory turns out to be. Three signals are recorded as they happen:
+//! a memory was retrieved by `memory_recall`, a retrieved memory was actually
+//! cited in the branch conclusion, or a newer memory was saved as an explicit
+//! correction of it. A periodic pass folds the accumulated signals into each
+//! memory's importance and logs every adjustment it makes.
+
+use crate::error::Result;
+use crate::memory::{MemoryStore, MemoryType};
+
+use anyhow::Context as _;
+use serde::Serialize;
+use sqlx::Row as _;
+
+use std::collections::HashSet;
+
+/// Importance gained per citation, up to `MAX_COUNTED_CITATIONS`.
+const CITATION_BOOST: f32 = 0.02;
+const MAX_COUNTED_CITATIONS: i64 = 5;
+/// Importance gained per retrieval, up to `MAX_COUNTED_RETRIEVALS`.
+const RETRIEVAL_BOOST: f32 = 0.005;
+const MAX_COUNTED_RETRIEVALS: i64 = 10;
+/// Importance lost per explicit correction, up to `MAX_COUNTED_CORRECTIONS`.
+const CORRECTION_PENALTY: f32 = 0.15;
+const MAX_COUNTED_CORRECTIONS: i64 = 3;
+/// Memories retrieved at least this often without ever being cited are
+/// surfacing without helping: they lose importance instead of gaining the
+/// retrieval boost.
+const UNCITED_RETRIEVAL_THRESHOLD: i64 = 3;
+const UNCITED_RETRIEVAL_PENALTY: f32 = 0.02;
+/// Changes smaller than this are not applied or logged.
+const MIN_ADJUSTMENT: f32 = 0.005;
+/// Fraction of a memory's significant words that must appear in a response
+/// for the memory to count as cited.
+const CITATION_OVERLAP_THRESHOLD: f32 = 0.5;
+
+/// A usage signal recorded against a memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemorySignal {
+    /// Returned by `memory_recall`.
+    Retrieved,
+    /// Retrieved and then used in the conclusion it was retrieved for.
+    Cited,
+    /// Superseded or contradicted by a memory saved as a correction.
+    Corrected,
+}
+
+impl MemorySignal {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MemorySignal::Retrieved => "retrieved",
+            MemorySignal::Cited => "cited",
+            MemorySignal::Corrected => "corrected",
+        }
+    }
+}
+
+/// Record a signal for each of the given memories.
+pub async fn record_signals(
+    store: &MemoryStore,
+    memory_ids: &[String],
+    signal: MemorySignal,
+) -> Result<()> {
+    for memory_id in memory_ids {
+        sqlx::query("INSERT INTO memory_signals (memory_id, signal) VALUES (?, ?)")
+            .bind(memory_id)
+            .bind(signal.as_str())
+            .execute(store.pool())
+            .await
+            .with_context(|| format!("failed to record {} signal", signal.as_str()))?;
+    }
+    Ok(())
+}
+
+/// Signal counts accumulated for one memory since the last pass.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct SignalCounts {
+    pub retrieved: i64,
+    pub cited: i64,
+    pub corrected: i64,
+}
+
+/// Importance change implied by a memory's signals.
+pub fn importance_delta(counts: SignalCounts) -> f32 {
+    let usage = if counts.retrieved >= UNCITED_RETRIEVAL_THRESHOLD && counts.cited == 0 {
+        -UNCITED_RETRIEVAL_PENALTY
+    } else {
+        RETRIEVAL_BOOST * counts.retrieved.min(MAX_COUNTED_RETRIEVALS) as f32
+    };
+    CITATION_BOOST * counts.cited.min(MAX_COUNTED_CITATIONS) as f32 + usage
+        - CORRECTION_PENALTY * counts.corrected.min(MAX_COUNTED_CORRECTIONS) as f32
+}
+
+/// One importance change made by a recalibration pass.
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportanceAdjustment {
+    pub memory_id: String,
+    pub previous_importance: f32,
+    pub new_importance: f32,
+    #[serde(flatten)]
+    pub signals: SignalCounts,
+}
+
+/// Outcome of a recalibration pass.
+#[derive(Debug, Default, Serialize)]
+pub struct RecalibrationReport {
+    /// Memories that had at least one signal.
+    pub considered: usize,
+    pub adjustments: Vec<ImportanceAdjustment>,
+}
+
+/// Fold all pending signals into importance scores, log each adjustment,
+/// and consume the signals. Identity memories keep their fixed importance.
+pub async fn recalibrate_importance(store: &MemoryStore) -> Result<RecalibrationReport> {
+    let pool = store.pool();
+    let Some(high_water_mark) =
+        sqlx::query_scalar::<_, Option<i64>>("SELECT MAX(id) FROM memory_signals")
+            .fetch_one(pool)
+            .await
+            .context("failed to read pending memory signals")?
+    else {
+        return Ok(RecalibrationReport::default());
+    };
+
+    let rows = sqlx::query(
+        "SELECT memory_id, \
+           SUM(signal = 'retrieved') AS retrieved, \
+           SUM(signal = 'cited') AS cited, \
+           SUM(signal = 'corrected') AS corrected \
+         FROM memory_signals WHERE id <= ? GROUP BY memory_id",
+    )
+    .bind(high_water_mark)
+    .fetch_all(pool)
+    .await
+    .context("failed to aggregate memory signals")?;
+
+    let mut report = RecalibrationReport {
+        considered: rows.len(),
+        adjustments: Vec::new(),
+    };
+
+    for row in rows {
+        let memory_id: String = row.try_get("memory_id").unwrap_or_default();
+        let signals = SignalCounts {
+            retrieved: row.try_get("retrieved").unwrap_or(0),
+            cited: row.try_get("cited").unwrap_or(0),
+            corrected: row.try_get("corrected").unwrap_or(0),
+        };
+
+        let Some(memory) = store.load(&memory_id).await? else {
+            continue;
+        };
+        if memory.forgotten || memory.memory_type == MemoryType::Identity {
+            continue;
+        }
+
+        let new_importance = (memory.importance + importance_delta(signals)).clamp(0.0, 1.0);
+        if (new_importance - memory.importance).abs() < MIN_ADJUSTMENT {
+            continue;
+        }
+
+        // Leave `updated_at` alone: decay measures age from it, and a usage
+        // adjustment isn't a content change.
+        sqlx::query("UPDATE memories SET importance = ? WHERE id = ?")
+            .bind(new_importance)
+            .bind(&memory_id)
+            .execute(pool)
+            .await
+            .with_context(|| format!("failed to recalibrate memory {memory_id}"))?;
+        sqlx::query(
+            "INSERT INTO memory_importance_adjustments \
+             (id, memory_id, previous_importance, new_importance, retrieved, cited, corrected) \
+             VALUES (?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(uuid::Uuid::new_v4().to_string())
+        .bind(&memory_id)
+        .bind(memory.importance)
+        .bind(new_importance)
+        .bind(signals.retrieved)
+        .bind(signals.cited)
+        .bind(signals.corrected)
+        .execute(pool)
+        .await
+        .with_context(|| format!("failed to log importance adjustment for {memory_id}"))?;
+
+        let mut snapshot = memory.clone();
+        snapshot.importance = new_importance;
+        crate::memory::mutations::record_or_warn(
+            store,
+            crate::memory::mutations::MutationKind::Update,
+            &memory_id,
+            Some(&snapshot),
+            crate::memory::mutations::ACTOR_RECALIBRATION,
+            Some(&format!(
+                "retrieved {}, cited {}, corrected {}",
+                signals.retrieved, signals.cited, signals.corrected
+            )),
+        )
+        .await;
+
+        tracing::debug!(
+            %memory_id,
+            previous = memory.importance,
+            new = new_importance,
+            ?signals,
+            "memory importance recalibrated"
+        );
+        report.adjustments.push(ImportanceAdjustment {
+            memory_id,
+            previous_importance: memory.importance,
+            new_importance,
+            signals,
+        });
+    }
+
+    sqlx::query("DELETE FROM memory_signals WHERE id <= ?")
+        .bind(high_water_mark)
+        .execute(pool)
+        .await
+        .context("failed to consume memory signals")?;
+
+    Ok(report)
+}
+
+/// Drop every pending signal without applying it. Used while recalibration
+/// is disabled, since nothing else would ever consume them.
+pub async fn discard_signals(store: &MemoryStore) -> Result<u64> {
+    let result = sqlx::query("DELETE FROM memory_signals")
+        .execute(store.pool())
+        .await
+        .context("failed to discard memory signals")?;
+    Ok(result.rows_affected())
+}
+
+/// IDs of recalled memories that a response draws on: either the ID is
+/// quoted, or most of the memory's significant words appear in the response.
+pub fn cited_memory_ids(response: &str, recalled: &[(String, String)]) -> Vec<String> {
+    let response_words = significant_words(response);
+    recalled
+        .iter()
+        .filter(|(memory_id, content)| {
+            if response.contains(memory_id.as_str()) {
+                return true;
+            }
+            let memory_words = significant_words(content);
+            if memory_words.is_empty() {
+                return false;
+            }
+            let shared = memory_words.intersection(&response_words).count();
+            shared >= memory_words.len().min(2)
+                && shared as f32 / memory_words.len() as f32 >= CITATION_OVERLAP_THRESHOLD
+        })
+        .map(|(memory_id, _)| memory_id.clone())
+        .collect()
+}
+
+/// Lowercased words of four or more characters, which skips most filler.
+fn significant_words(text: &str) -> HashSet<String> {
+    text.split(|character: char| !character.is_alphanumeric())
+        .filter(|word| word.chars().count() >= 4)
+        .map(str::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::Memory;
+
+    #[test]
+    fn citations_lift_importance_and_corrections_sink_it() {
+        let cited = SignalCounts {
+            retrieved: 2,
+            cited: 2,
+            corrected: 0,
+        };
+        assert!(importance_delta(cited) > 0.0);
+
+        let ignored = SignalCounts {
+            retrieved: 4,
+            cited: 0,
+            corrected: 0,
+        };
+        assert!(importance_delta(ignored) < 0.0);
+
+        let corrected = SignalCounts {
+            retrieved: 10,
+            cited: 5,
+            corrected: 1,
+        };
+        assert!(importance_delta(corrected) < 0.0);
+    }
+
+    #[test]
+    fn detects_citation_by_word_overlap() {
+        let recalled = vec![
+            (
+                "m1".to_string(),
+                "User prefers Postgres over MySQL for production databases".to_string(),
+            ),
+            (
+                "m2".to_string(),
+                "The office coffee machine is broken".to_string(),
+            ),
+        ];
+        let response = "Since the user prefers Postgres over MySQL for production databases, go with Postgres.";
+        assert_eq!(
+            cited_memory_ids(response, &recalled),
+            vec!["m1".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn recalibration_applies_and_consumes_signals() {
+        let store = MemoryStore::connect_in_memory().await;
+        let useful = Memory::new("useful", MemoryType::Fact).with_importance(0.5);
+        let wrong = Memory::new("wrong", MemoryType::Fact).with_importance(0.5);
+        let identity = Memory::new("name", MemoryType::Identity);
+        for memory in [&useful, &wrong, &identity] {
+            store.save(memory).await.unwrap();
+        }
+
+        record_signals(
+            &store,
+            std::slice::from_ref(&useful.id),
+            MemorySignal::Cited,
+        )
+        .await
+        .unwrap();
+        record_signals(
+            &store,
+            &[wrong.id.clone(), identity.id.clone()],
+            MemorySignal::Corrected,
+        )
+        .await
+        .unwrap();
+
+        let report = recalibrate_importance(&store).await.unwrap();
+        assert_eq!(report.considered, 3);
+        assert_eq!(report.adjustments.len(), 2);
+
+        let useful = store.load(&useful.id).await.unwrap().unwrap();
+        let wrong = store.load(&wrong.id).await.unwrap().unwrap();
+        let identity = store.load(&identity.id).await.unwrap().unwrap();
+        assert!(useful.importance > 0.5);
+        assert!(wrong.importance < 0.5);
+        assert_eq!(identity.importance, 1.0);
+
+        let logged: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM memory_importance_adjustments")
+            .fetch_one(store.pool())
+            .await
+            .unwrap();
+        assert_eq!(logged, 2);
+
+        let second = recalibrate_importance(&store).await.unwrap();
+        assert_eq!(second.considered, 0);
+    }
+
+    #[tokio::test]
+    async fn discarded_signals_are_never_applied() {
+        let store = MemoryStore::connect_in_memory().await;
+        let memory = Memory::new("useful", MemoryType::Fact).with_importance(0.5);
+        store.save(&memory).await.unwrap();
+        record_signals(
+            &store,
+            std::slice::from_ref(&memory.id),
+            MemorySignal::Cited,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(discard_signals(&store).await.unwrap(), 1);
+        let report = recalibrate_importance(&store).await.unwrap();
+        assert_eq!(report.considered, 0);
+    }
+}
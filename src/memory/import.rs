@@ -0,0 +1,268 @@
+//! Bulk import of memories from JSONL.
+//!
+//! Each line is one memory: `{"content": ..., "type": ..., "importance": ...,
+//! "created_at": ...}`. Lines are validated up front, then imported in
+//! batches: one embedding call per batch and one SQLite transaction per
+//! batch, so a failing batch leaves nothing behind and the rest still land.
+//! Meant for moving an agent's knowledge over from another bot framework.
+
+use crate::memory::mutations::{self, MutationKind};
+use crate::memory::{Memory, MemorySearch, MemoryType};
+use crate::tools::memory_save::{MAX_MEMORY_CONTENT_BYTES, normalize_tags};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Memories embedded and written together.
+pub const IMPORT_BATCH_SIZE: usize = 64;
+
+/// Source recorded on imported memories that don't name one.
+const DEFAULT_SOURCE: &str = "import";
+
+/// One line of an import file.
+/// Unknown fields are ignored so exports from other tools import as-is.
+#[derive(Debug, Deserialize)]
+struct ImportRecord {
+    content: String,
+    #[serde(default, alias = "type")]
+    memory_type: Option<MemoryType>,
+    #[serde(default)]
+    importance: Option<f32>,
+    /// When the memory was originally learned. Defaults to now.
+    #[serde(default)]
+    created_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    source: Option<String>,
+}
+
+/// A line that was not imported.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct ImportLineError {
+    /// 1-based line number in the request body.
+    pub line: usize,
+    pub error: String,
+}
+
+/// Outcome of one batch.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct ImportBatchResult {
+    pub first_line: usize,
+    pub last_line: usize,
+    pub imported: usize,
+    /// Set when the whole batch was rolled back.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, utoipa::ToSchema)]
+pub struct ImportSummary {
+    /// Non-blank lines read.
+    pub total: usize,
+    pub imported: usize,
+    pub failed: usize,
+    pub batches: Vec<ImportBatchResult>,
+    /// Lines rejected during validation or lost with a failed batch.
+    pub errors: Vec<ImportLineError>,
+}
+
+/// Parse and validate one line into a memory ready to save.
+fn parse_line(line: &str) -> Result<Memory, String> {
+    let record: ImportRecord = serde_json::from_str(line).map_err(|error| error.to_string())?;
+
+    let content = record.content.trim();
+    if content.is_empty() {
+        return Err("content is empty".into());
+    }
+    if content.len() > MAX_MEMORY_CONTENT_BYTES {
+        return Err(format!(
+            "content is {} bytes, over the {MAX_MEMORY_CONTENT_BYTES} byte limit",
+            content.len()
+        ));
+    }
+    if let Some(importance) = record.importance
+        && !(0.0..=1.0).contains(&importance)
+    {
+        return Err(format!("importance {importance} is outside 0-1"));
+    }
+
+    let mut memory = Memory::new(content, record.memory_type.unwrap_or(MemoryType::Fact))
+        .with_source(
+            record
+                .source
+                .filter(|source| !source.trim().is_empty())
+                .unwrap_or_else(|| DEFAULT_SOURCE.into()),
+        )
+        .with_tags(normalize_tags(record.tags));
+    if let Some(importance) = record.importance {
+        memory = memory.with_importance(importance);
+    }
+    if let Some(created_at) = record.created_at {
+        memory.created_at = created_at;
+        memory.updated_at = created_at;
+        memory.last_accessed_at = created_at;
+    }
+    Ok(memory)
+}
+
+/// Import every valid line of `body`.
+pub async fn import_jsonl(memory_search: &MemorySearch, body: &str, actor: &str) -> ImportSummary {
+    let mut summary = ImportSummary::default();
+    let mut parsed: Vec<(usize, Memory)> = Vec::new();
+    for (index, line) in body.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        summary.total += 1;
+        match parse_line(line) {
+            Ok(memory) => parsed.push((index + 1, memory)),
+            Err(error) => summary.errors.push(ImportLineError {
+                line: index + 1,
+                error,
+            }),
+        }
+    }
+
+    for batch in parsed.chunks(IMPORT_BATCH_SIZE) {
+        let first_line = batch[0].0;
+        let last_line = batch[batch.len() - 1].0;
+        let memories: Vec<Memory> = batch.iter().map(|(_, memory)| memory.clone()).collect();
+
+        match import_batch(memory_search, &memories, actor).await {
+            Ok(()) => {
+                summary.imported += memories.len();
+                summary.batches.push(ImportBatchResult {
+                    first_line,
+                    last_line,
+                    imported: memories.len(),
+                    error: None,
+                });
+                tracing::info!(
+                    first_line,
+                    last_line,
+                    imported = summary.imported,
+                    total = parsed.len(),
+                    "memory import batch written"
+                );
+            }
+            Err(error) => {
+                tracing::warn!(%error, first_line, last_line, "memory import batch failed");
+                let error = error.to_string();
+                summary
+                    .errors
+                    .extend(batch.iter().map(|(line, _)| ImportLineError {
+                        line: *line,
+                        error: format!("batch failed: {error}"),
+                    }));
+                summary.batches.push(ImportBatchResult {
+                    first_line,
+                    last_line,
+                    imported: 0,
+                    error: Some(error),
+                });
+            }
+        }
+    }
+
+    if summary.imported > 0
+        && let Err(error) = memory_search.embedding_table().ensure_fts_index().await
+    {
+        tracing::warn!(%error, "failed to ensure FTS index after memory import");
+    }
+
+    summary.errors.sort_by_key(|error| error.line);
+    summary.failed = summary.errors.len();
+    summary
+}
+
+/// Embed, write, and index one batch. Nothing is left behind on failure.
+async fn import_batch(
+    memory_search: &MemorySearch,
+    memories: &[Memory],
+    actor: &str,
+) -> crate::error::Result<()> {
+    let embeddings = memory_search
+        .embedding_model_arc()
+        .embed_batch(
+            memories
+                .iter()
+                .map(|memory| memory.content.clone())
+                .collect(),
+        )
+        .await?;
+    if embeddings.len() != memories.len() {
+        return Err(anyhow::anyhow!(
+            "embedding model returned {} vectors for {} memories",
+            embeddings.len(),
+            memories.len()
+        )
+        .into());
+    }
+
+    let store = memory_search.store();
+    store.save_batch(memories).await?;
+
+    let rows: Vec<(&str, &str, &[f32])> = memories
+        .iter()
+        .zip(&embeddings)
+        .map(|(memory, embedding)| {
+            (
+                memory.id.as_str(),
+                memory.content.as_str(),
+                embedding.as_slice(),
+            )
+        })
+        .collect();
+    if let Err(error) = memory_search.embedding_table().store_batch(&rows).await {
+        // Compensate so no memory exists without an embedding.
+        for memory in memories {
+            if let Err(delete_error) = store.delete(&memory.id).await {
+                tracing::warn!(memory_id = %memory.id, %delete_error, "compensating delete failed after embedding store error");
+            }
+        }
+        return Err(error);
+    }
+
+    for memory in memories {
+        mutations::record_or_warn(
+            store,
+            MutationKind::Create,
+            &memory.id,
+            Some(memory),
+            actor,
+            Some("bulk import"),
+        )
+        .await;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines_are_validated_before_import() {
+        let memory = parse_line(
+            r#"{"content": " Likes tea ", "type": "preference", "importance": 0.8,
+                "created_at": "2024-05-01T12:00:00Z", "tags": ["drinks", "drinks"]}"#,
+        )
+        .unwrap();
+        assert_eq!(memory.content, "Likes tea");
+        assert_eq!(memory.memory_type, MemoryType::Preference);
+        assert_eq!(memory.importance, 0.8);
+        assert_eq!(memory.created_at.to_rfc3339(), "2024-05-01T12:00:00+00:00");
+        assert_eq!(memory.tags, vec!["drinks".to_string()]);
+        assert_eq!(memory.source.as_deref(), Some("import"));
+
+        assert_eq!(
+            parse_line(r#"{"content": "x"}"#).unwrap().memory_type,
+            MemoryType::Fact
+        );
+        assert!(parse_line(r#"{"content": "  "}"#).is_err());
+        assert!(parse_line(r#"{"content": "x", "importance": 2}"#).is_err());
+        assert!(parse_line(r#"{"content": "x", "type": "rumor"}"#).is_err());
+        assert!(parse_line("not json").is_err());
+    }
+}
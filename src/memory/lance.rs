@@ -9,38 +9,70 @@ use std::sync::Arc;
 
 /// Schema constants for the embeddings table.
 const TABLE_NAME: &str = "memory_embeddings";
-const EMBEDDING_DIM: i32 = 384; // all-MiniLM-L6-v2 dimension
 
 /// LanceDB table for memory embeddings with HNSW index and FTS.
 pub struct EmbeddingTable {
     table: lancedb::Table,
+    /// Vector length, fixed by the agent's embedding model.
+    dimension: usize,
 }
 
 impl Clone for EmbeddingTable {
     fn clone(&self) -> Self {
         Self {
             table: self.table.clone(),
+            dimension: self.dimension,
         }
     }
 }
 
 impl EmbeddingTable {
-    /// Open existing table or create a new one.
+    /// Open existing table or create a new one holding `dimension`-length
+    /// vectors.
     ///
     /// If the table exists but is corrupted (e.g. process killed mid-write),
     /// it is dropped and recreated. Embeddings can be regenerated from SQLite.
-    pub async fn open_or_create(connection: &lancedb::Connection) -> Result<Self> {
+    /// An empty table with a different vector length is recreated too; a
+    /// populated one is an error, since its vectors can't be searched with
+    /// the new model.
+    pub async fn open_or_create(
+        connection: &lancedb::Connection,
+        dimension: usize,
+    ) -> Result<Self> {
         // Try to open existing table
         match connection.open_table(TABLE_NAME).execute().await {
-            Ok(table) => return Ok(Self { table }),
+            Ok(table) => match Self::stored_dimension(&table).await {
+                Some(stored) if stored != dimension => {
+                    let rows = table
+                        .count_rows(None)
+                        .await
+                        .map_err(|e| DbError::LanceDb(e.to_string()))?;
+                    if rows > 0 {
+                        return Err(DbError::LanceDb(format!(
+                            "embeddings table holds {rows} {stored}-dimension vectors, \
+                             but the embedding model produces {dimension}"
+                        ))
+                        .into());
+                    }
+                    tracing::info!(
+                        stored,
+                        dimension,
+                        "recreating empty embeddings table for new embedding dimension"
+                    );
+                    if let Err(error) = connection.drop_table(TABLE_NAME, &[]).await {
+                        tracing::warn!(%error, "failed to drop empty embeddings table");
+                    }
+                }
+                _ => return Ok(Self { table, dimension }),
+            },
             Err(error) => {
                 tracing::debug!(%error, "failed to open embeddings table, will create");
             }
         }
 
         // Table doesn't exist or is unreadable — try creating it
-        match Self::create_empty_table(connection).await {
-            Ok(table) => return Ok(Self { table }),
+        match Self::create_empty_table(connection, dimension).await {
+            Ok(table) => return Ok(Self { table, dimension }),
             Err(error) => {
                 tracing::warn!(
                     %error,
@@ -55,15 +87,27 @@ impl EmbeddingTable {
             tracing::warn!(%error, "drop_table failed during recovery, proceeding anyway");
         }
 
-        let table = Self::create_empty_table(connection).await?;
+        let table = Self::create_empty_table(connection, dimension).await?;
         tracing::info!("embeddings table recovered — embeddings will be rebuilt from memory store");
 
-        Ok(Self { table })
+        Ok(Self { table, dimension })
+    }
+
+    /// Vector length of an existing table's `embedding` column.
+    async fn stored_dimension(table: &lancedb::Table) -> Option<usize> {
+        let schema = table.schema().await.ok()?;
+        match schema.field_with_name("embedding").ok()?.data_type() {
+            arrow_schema::DataType::FixedSizeList(_, size) => usize::try_from(*size).ok(),
+            _ => None,
+        }
     }
 
     /// Create an empty embeddings table.
-    async fn create_empty_table(connection: &lancedb::Connection) -> Result<lancedb::Table> {
-        let schema = Self::schema();
+    async fn create_empty_table(
+        connection: &lancedb::Connection,
+        dimension: usize,
+    ) -> Result<lancedb::Table> {
+        let schema = Self::schema(dimension);
         let batches = RecordBatchIterator::new(vec![].into_iter().map(Ok), Arc::new(schema));
 
         connection
@@ -76,10 +120,21 @@ impl EmbeddingTable {
     /// Store an embedding with content for a memory.
     /// The content is stored for FTS search capability.
     pub async fn store(&self, memory_id: &str, content: &str, embedding: &[f32]) -> Result<()> {
-        if embedding.len() != EMBEDDING_DIM as usize {
+        self.store_batch(&[(memory_id, content, embedding)]).await
+    }
+
+    /// Store several embeddings in one write, e.g. for a bulk import.
+    pub async fn store_batch(&self, rows: &[(&str, &str, &[f32])]) -> Result<()> {
+        if rows.is_empty() {
+            return Ok(());
+        }
+        if let Some((_, _, embedding)) = rows
+            .iter()
+            .find(|(_, _, embedding)| embedding.len() != self.dimension)
+        {
             return Err(DbError::LanceDb(format!(
                 "Embedding dimension mismatch: expected {}, got {}",
-                EMBEDDING_DIM,
+                self.dimension,
                 embedding.len()
             ))
             .into());
@@ -87,21 +142,27 @@ impl EmbeddingTable {
 
         use arrow_array::{RecordBatch, StringArray};
 
-        let schema = Self::schema();
+        let schema = Arc::new(Self::schema(self.dimension));
 
         // Build arrays for the record batch
-        let id_array = StringArray::from(vec![memory_id]);
-        let content_array = StringArray::from(vec![content]);
-
-        // Convert embedding to FixedSizeListArray
+        let id_array = StringArray::from(rows.iter().map(|(id, _, _)| *id).collect::<Vec<_>>());
+        let content_array = StringArray::from(
+            rows.iter()
+                .map(|(_, content, _)| *content)
+                .collect::<Vec<_>>(),
+        );
+
+        // Convert embeddings to FixedSizeListArray
         let embedding_array =
             arrow_array::FixedSizeListArray::from_iter_primitive::<Float32Type, _, _>(
-                vec![Some(embedding.iter().map(|v| Some(*v)).collect::<Vec<_>>())],
-                EMBEDDING_DIM,
+                rows.iter().map(|(_, _, embedding)| {
+                    Some(embedding.iter().map(|v| Some(*v)).collect::<Vec<_>>())
+                }),
+                self.dimension as i32,
             );
 
         let batch = RecordBatch::try_new(
-            Arc::new(schema),
+            schema.clone(),
             vec![
                 Arc::new(id_array) as arrow_array::ArrayRef,
                 Arc::new(content_array) as arrow_array::ArrayRef,
@@ -111,7 +172,7 @@ impl EmbeddingTable {
         .map_err(|e| DbError::LanceDb(e.to_string()))?;
 
         // Create iterator for IntoArrow trait
-        let batches = RecordBatchIterator::new(vec![Ok(batch)], Arc::new(Self::schema()));
+        let batches = RecordBatchIterator::new(vec![Ok(batch)], schema);
 
         self.table
             .add(Box::new(batches))
@@ -141,10 +202,10 @@ impl EmbeddingTable {
         query_embedding: &[f32],
         limit: usize,
     ) -> Result<Vec<(String, f32)>> {
-        if query_embedding.len() != EMBEDDING_DIM as usize {
+        if query_embedding.len() != self.dimension {
             return Err(DbError::LanceDb(format!(
                 "Query embedding dimension mismatch: expected {}, got {}",
-                EMBEDDING_DIM,
+                self.dimension,
                 query_embedding.len()
             ))
             .into());
@@ -253,6 +314,68 @@ impl EmbeddingTable {
         Ok(similar)
     }
 
+    /// Stored embeddings for `memory_ids`, keyed by memory ID. Memories
+    /// without one are left out.
+    pub async fn embeddings_for(
+        &self,
+        memory_ids: &[String],
+    ) -> Result<std::collections::HashMap<String, Vec<f32>>> {
+        use lancedb::query::{ExecutableQuery, QueryBase, Select};
+
+        if memory_ids.is_empty() {
+            return Ok(std::collections::HashMap::new());
+        }
+        for memory_id in memory_ids {
+            Self::validate_memory_id(memory_id)?;
+        }
+        let predicate = format!(
+            "id IN ({})",
+            memory_ids
+                .iter()
+                .map(|memory_id| format!("'{memory_id}'"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+
+        let batches: Vec<arrow_array::RecordBatch> = self
+            .table
+            .query()
+            .only_if(predicate)
+            .select(Select::columns(&["id", "embedding"]))
+            .execute()
+            .await
+            .map_err(|e| DbError::LanceDb(e.to_string()))?
+            .try_collect()
+            .await
+            .map_err(|e| DbError::LanceDb(e.to_string()))?;
+
+        let mut embeddings = std::collections::HashMap::new();
+        for batch in batches {
+            let (Some(id_col), Some(embedding_col)) = (
+                batch.column_by_name("id"),
+                batch.column_by_name("embedding"),
+            ) else {
+                continue;
+            };
+            let ids: &arrow_array::StringArray = id_col.as_string::<i32>();
+            let Some(list_array) = embedding_col
+                .as_any()
+                .downcast_ref::<arrow_array::FixedSizeListArray>()
+            else {
+                continue;
+            };
+            for i in 0..ids.len() {
+                if ids.is_valid(i) && list_array.is_valid(i) {
+                    let values = list_array.value(i);
+                    let embedding = values.as_primitive::<Float32Type>().values().to_vec();
+                    embeddings.insert(ids.value(i).to_string(), embedding);
+                }
+            }
+        }
+
+        Ok(embeddings)
+    }
+
     /// Full-text search using Tantivy FTS.
     /// Returns (memory_id, score) pairs sorted by score (descending).
     pub async fn text_search(&self, query: &str, limit: usize) -> Result<Vec<(String, f32)>> {
@@ -340,7 +463,7 @@ impl EmbeddingTable {
     }
 
     /// Get the Arrow schema for the embeddings table.
-    fn schema() -> arrow_schema::Schema {
+    fn schema(dimension: usize) -> arrow_schema::Schema {
         arrow_schema::Schema::new(vec![
             arrow_schema::Field::new("id", arrow_schema::DataType::Utf8, false),
             arrow_schema::Field::new("content", arrow_schema::DataType::Utf8, false),
@@ -352,15 +475,28 @@ impl EmbeddingTable {
                         arrow_schema::DataType::Float32,
                         true,
                     )),
-                    EMBEDDING_DIM,
+                    dimension as i32,
                 ),
                 false,
             ),
         ])
     }
 
+    /// Length of the vectors this table stores.
+    pub fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    /// Number of stored embeddings.
+    pub async fn row_count(&self) -> Result<usize> {
+        self.table
+            .count_rows(None)
+            .await
+            .map_err(|e| DbError::LanceDb(e.to_string()).into())
+    }
+
     /// Validate that a memory ID is a well-formed UUID to prevent predicate injection.
-    fn validate_memory_id(memory_id: &str) -> Result<()> {
+    pub(crate) fn validate_memory_id(memory_id: &str) -> Result<()> {
         if memory_id.len() != 36 || !memory_id.chars().all(|c| c.is_ascii_hexdigit() || c == '-') {
             return Err(
                 DbError::LanceDb(format!("invalid memory ID format: {}", memory_id)).into(),
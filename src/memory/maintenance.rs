@@ -1,6 +1,14 @@
-//! Memory maintenance: decay, prune, merge, reindex.
+//! Memory maintenance: expire, decay, archive, merge, reindex.
+//!
+//! Decay follows a forgetting curve: a memory's importance halves for every
+//! half-life it goes without being accessed, with the half-life set per
+//! [`MemoryType`]. Memories that sink below the prune threshold are archived
+//! to the trash, where they can still be restored until trash retention
+//! purges them. Memories past their `expires_at` go the same way, or are
+//! deleted outright when `delete_expired` is set.
 
 use crate::error::Result;
+use crate::memory::mutations::{self, ACTOR_MAINTENANCE, MutationKind};
 use crate::memory::{EmbeddingModel, EmbeddingTable, Memory, MemoryStore, MemoryType};
 use anyhow::Context;
 
@@ -16,31 +24,151 @@ const MAX_MAINTENANCE_MERGE_SOURCE_MEMORIES: i64 = 2_000;
 const MAX_MAINTENANCE_MERGES_PER_PASS: usize = 500;
 const MAX_MAINTENANCE_SIMILAR_CANDIDATES: usize = 25;
 const MAX_MERGED_MEMORY_CONTENT_BYTES: usize = 50_000;
+const MAX_TRASH_PURGES_PER_PASS: i64 = 1_000;
+const MAX_EXPIRED_PER_PASS: i64 = 1_000;
 
 /// Maintenance configuration.
 #[derive(Debug, Clone)]
 pub struct MaintenanceConfig {
-    /// Importance below which memories are considered for pruning.
+    /// Importance below which memories are archived to the trash.
     pub prune_threshold: f32,
-    /// Decay rate per day (0.0 - 1.0).
-    pub decay_rate: f32,
-    /// Minimum age in days before a memory can be pruned.
+    /// Decay half-life for each memory type.
+    pub half_lives: DecayHalfLives,
+    /// Minimum age in days before a memory can be archived.
     pub min_age_days: i64,
     /// Similarity threshold for merging memories (0.0 - 1.0).
     pub merge_similarity_threshold: f32,
+    /// Days a forgotten memory stays in the trash before it is purged.
+    /// Zero keeps trashed memories indefinitely.
+    pub trash_retention_days: i64,
+    /// Delete memories past their `expires_at` outright instead of moving
+    /// them to the trash.
+    pub delete_expired: bool,
+    /// Days of mutation history to keep. Zero keeps it indefinitely.
+    pub mutation_retention_days: i64,
 }
 
 impl Default for MaintenanceConfig {
     fn default() -> Self {
         Self {
             prune_threshold: 0.1,
-            decay_rate: 0.05,
+            half_lives: DecayHalfLives::default(),
             min_age_days: 30,
             merge_similarity_threshold: 0.95,
+            trash_retention_days: 30,
+            delete_expired: false,
+            mutation_retention_days: 180,
         }
     }
 }
 
+/// Days without access for a memory's importance to halve, per memory type.
+/// Zero means the type never decays. Identity memories never decay.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DecayHalfLives {
+    pub fact: f32,
+    pub preference: f32,
+    pub decision: f32,
+    pub event: f32,
+    pub observation: f32,
+    pub goal: f32,
+    pub todo: f32,
+}
+
+impl Default for DecayHalfLives {
+    fn default() -> Self {
+        Self {
+            fact: 180.0,
+            preference: 180.0,
+            decision: 120.0,
+            event: 30.0,
+            observation: 7.0,
+            goal: 90.0,
+            todo: 60.0,
+        }
+    }
+}
+
+impl DecayHalfLives {
+    /// The same half-life for every memory type.
+    pub fn uniform(days: f32) -> Self {
+        Self {
+            fact: days,
+            preference: days,
+            decision: days,
+            event: days,
+            observation: days,
+            goal: days,
+            todo: days,
+        }
+    }
+
+    /// Half-lives matching the retired `maintenance_decay_rate` setting, which
+    /// cut importance linearly by `rate` per idle day until it had halved.
+    /// The half-life is the day that linear decay reached one half; a rate of
+    /// 0 never decayed. `None` if the rate is outside [0, 1].
+    pub fn from_legacy_decay_rate(rate: f32) -> Option<Self> {
+        if !rate.is_finite() || !(0.0..=1.0).contains(&rate) {
+            return None;
+        }
+        Some(if rate == 0.0 {
+            Self::uniform(0.0)
+        } else {
+            Self::uniform(0.5 / rate)
+        })
+    }
+
+    /// Half-life in days for a memory type, or `None` if it never decays.
+    pub fn for_type(&self, memory_type: MemoryType) -> Option<f32> {
+        self.configured(memory_type).filter(|days| *days > 0.0)
+    }
+
+    fn configured(&self, memory_type: MemoryType) -> Option<f32> {
+        match memory_type {
+            MemoryType::Identity => None,
+            MemoryType::Fact => Some(self.fact),
+            MemoryType::Preference => Some(self.preference),
+            MemoryType::Decision => Some(self.decision),
+            MemoryType::Event => Some(self.event),
+            MemoryType::Observation => Some(self.observation),
+            MemoryType::Goal => Some(self.goal),
+            MemoryType::Todo => Some(self.todo),
+        }
+    }
+
+    /// Importance after decaying from `since` to `now`. Decay starts from the
+    /// later of `since` and the memory's last access, so reading a memory
+    /// stops it fading.
+    pub fn effective_importance(
+        &self,
+        memory: &Memory,
+        since: chrono::DateTime<chrono::Utc>,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> f32 {
+        let Some(half_life) = self.for_type(memory.memory_type) else {
+            return memory.importance;
+        };
+        let idle_since = since.max(memory.last_accessed_at);
+        let idle_days = (now - idle_since).num_seconds().max(0) as f64 / 86_400.0;
+        let factor = 0.5_f64.powf(idle_days / f64::from(half_life));
+        (memory.importance as f64 * factor) as f32
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        for memory_type in MemoryType::ALL {
+            if let Some(days) = self.configured(*memory_type)
+                && (!days.is_finite() || days < 0.0)
+            {
+                return Err(anyhow::anyhow!(
+                    "maintenance half-life for {memory_type} must be finite and >= 0, got {days}"
+                )
+                .into());
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Run maintenance tasks on the memory store.
 pub async fn run_maintenance(
     memory_store: &MemoryStore,
@@ -73,13 +201,21 @@ pub async fn run_maintenance_with_cancel(
     check_maintenance_cancellation(&mut maintenance_cancel_rx).await?;
     validate_maintenance_config(config)?;
 
-    // Apply decay to all non-identity memories
     // Fields are assigned sequentially because the values are async — can't use struct literal.
     #[allow(clippy::field_reassign_with_default)]
     {
+        report.expired = expire_memories(
+            memory_store,
+            embedding_table,
+            embedding_model,
+            config.delete_expired,
+            &mut maintenance_cancel_rx,
+        )
+        .await?;
         report.decayed =
-            apply_decay(memory_store, config.decay_rate, &mut maintenance_cancel_rx).await?;
-        report.pruned = prune_memories(memory_store, config, &mut maintenance_cancel_rx).await?;
+            apply_decay(memory_store, &config.half_lives, &mut maintenance_cancel_rx).await?;
+        report.archived =
+            archive_memories(memory_store, config, &mut maintenance_cancel_rx).await?;
         report.merged = merge_similar_memories(
             memory_store,
             embedding_table,
@@ -88,29 +224,44 @@ pub async fn run_maintenance_with_cancel(
             &mut maintenance_cancel_rx,
         )
         .await?;
+        report.purged = purge_trash(
+            memory_store,
+            embedding_table,
+            embedding_model,
+            config.trash_retention_days,
+            &mut maintenance_cancel_rx,
+        )
+        .await?;
+        report.mutations_pruned = maintenance_cancelable_op(
+            &mut maintenance_cancel_rx,
+            mutations::prune(memory_store, config.mutation_retention_days),
+        )
+        .await?;
     }
 
     Ok(report)
 }
 
-/// Apply importance decay based on recency and access patterns.
+/// Decay importance along each type's forgetting curve.
+///
+/// `updated_at` marks how far decay has been applied, so each pass only
+/// decays the time since the last write or access.
 async fn apply_decay(
     memory_store: &MemoryStore,
-    decay_rate: f32,
+    half_lives: &DecayHalfLives,
     maintenance_cancel_rx: &mut watch::Receiver<bool>,
 ) -> Result<usize> {
     check_maintenance_cancellation(maintenance_cancel_rx).await?;
 
-    // Get all non-identity memories
-    let all_types: Vec<_> = MemoryType::ALL
+    let decaying_types: Vec<_> = MemoryType::ALL
         .iter()
         .copied()
-        .filter(|t| *t != MemoryType::Identity)
+        .filter(|memory_type| half_lives.for_type(*memory_type).is_some())
         .collect();
 
     let mut decayed_count = 0;
 
-    for mem_type in all_types {
+    for mem_type in decaying_types {
         let memories = maintenance_cancelable_op(
             maintenance_cancel_rx,
             memory_store.get_by_type(mem_type, 1000),
@@ -121,26 +272,28 @@ async fn apply_decay(
             check_maintenance_cancellation(maintenance_cancel_rx).await?;
 
             let now = chrono::Utc::now();
-            let days_old = (now - memory.updated_at).num_days();
-            let days_since_access = (now - memory.last_accessed_at).num_days();
-
-            // Calculate decay multiplier
-            let age_decay = 1.0 - (days_old as f32 * decay_rate).min(0.5);
-            let access_boost = if days_since_access < 7 {
-                1.1 // Recent access boosts importance
-            } else if days_since_access > 30 {
-                0.9 // Long time since access reduces importance
-            } else {
-                1.0
-            };
-
-            let new_importance = memory.importance * age_decay * access_boost;
+            let new_importance = half_lives.effective_importance(&memory, memory.updated_at, now);
 
+            // Small changes wait for a later pass; `updated_at` stays put so
+            // the idle time keeps accumulating.
             if (new_importance - memory.importance).abs() > 0.01 {
+                let previous_importance = memory.importance;
                 memory.importance = new_importance.clamp(0.0, 1.0);
                 memory.updated_at = now;
                 maintenance_cancelable_op(maintenance_cancel_rx, memory_store.update(&memory))
                     .await?;
+                mutations::record_or_warn(
+                    memory_store,
+                    MutationKind::Decay,
+                    &memory.id,
+                    Some(&memory),
+                    ACTOR_MAINTENANCE,
+                    Some(&format!(
+                        "importance {previous_importance:.2} -> {:.2}",
+                        memory.importance
+                    )),
+                )
+                .await;
                 decayed_count += 1;
             }
         }
@@ -149,8 +302,9 @@ async fn apply_decay(
     Ok(decayed_count)
 }
 
-/// Prune memories that have fallen below the importance threshold.
-async fn prune_memories(
+/// Archive memories that have fallen below the importance threshold by
+/// moving them to the trash.
+async fn archive_memories(
     memory_store: &MemoryStore,
     config: &MaintenanceConfig,
     maintenance_cancel_rx: &mut watch::Receiver<bool>,
@@ -161,14 +315,15 @@ async fn prune_memories(
     let min_age = chrono::Duration::days(config.min_age_days);
     let cutoff_date = now - min_age;
 
-    // Get all memories below threshold that are old enough
+    // Get all live memories below threshold that are old enough
     let candidates: Vec<SqliteRow> = maintenance_cancelable_op(
         maintenance_cancel_rx,
         sqlx::query(
             r#"
         SELECT id FROM memories
-        WHERE importance < ? 
+        WHERE importance < ?
         AND memory_type != 'identity'
+        AND forgotten = 0
         AND created_at < ?
         "#,
         )
@@ -178,16 +333,164 @@ async fn prune_memories(
     )
     .await?;
 
-    let mut pruned_count = 0;
+    let mut archived_count = 0;
 
     for row in candidates {
         let id: String = row.try_get("id")?;
         check_maintenance_cancellation(maintenance_cancel_rx).await?;
-        maintenance_cancelable_op(maintenance_cancel_rx, memory_store.delete(&id)).await?;
-        pruned_count += 1;
+        if !maintenance_cancelable_op(maintenance_cancel_rx, memory_store.forget(&id)).await? {
+            continue;
+        }
+        let snapshot =
+            maintenance_cancelable_op(maintenance_cancel_rx, memory_store.load(&id)).await?;
+        mutations::record_or_warn(
+            memory_store,
+            MutationKind::Forget,
+            &id,
+            snapshot.as_ref(),
+            ACTOR_MAINTENANCE,
+            Some("importance fell below the prune threshold"),
+        )
+        .await;
+        archived_count += 1;
     }
 
-    Ok(pruned_count)
+    Ok(archived_count)
+}
+
+/// Permanently delete memories that have sat in the trash longer than the
+/// retention period, along with their embeddings and graph edges.
+async fn purge_trash(
+    memory_store: &MemoryStore,
+    embedding_table: &EmbeddingTable,
+    embedding_model: &EmbeddingModel,
+    retention_days: i64,
+    maintenance_cancel_rx: &mut watch::Receiver<bool>,
+) -> Result<usize> {
+    if retention_days == 0 {
+        return Ok(0);
+    }
+    check_maintenance_cancellation(maintenance_cancel_rx).await?;
+
+    let cutoff = chrono::Utc::now() - chrono::Duration::days(retention_days);
+    let expired_ids = maintenance_cancelable_op(
+        maintenance_cancel_rx,
+        memory_store.forgotten_before(cutoff, MAX_TRASH_PURGES_PER_PASS),
+    )
+    .await?;
+
+    let mut purged_count = 0;
+    for id in expired_ids {
+        check_maintenance_cancellation(maintenance_cancel_rx).await?;
+        delete_memory(
+            memory_store,
+            embedding_table,
+            embedding_model,
+            &id,
+            "trash retention expired",
+            maintenance_cancel_rx,
+        )
+        .await?;
+        purged_count += 1;
+    }
+
+    Ok(purged_count)
+}
+
+/// Move memories past their `expires_at` to the trash, or delete them when
+/// `delete_expired` is set.
+async fn expire_memories(
+    memory_store: &MemoryStore,
+    embedding_table: &EmbeddingTable,
+    embedding_model: &EmbeddingModel,
+    delete_expired: bool,
+    maintenance_cancel_rx: &mut watch::Receiver<bool>,
+) -> Result<usize> {
+    check_maintenance_cancellation(maintenance_cancel_rx).await?;
+
+    let expired_ids = maintenance_cancelable_op(
+        maintenance_cancel_rx,
+        memory_store.expired(MAX_EXPIRED_PER_PASS),
+    )
+    .await?;
+
+    let mut expired_count = 0;
+    for id in expired_ids {
+        check_maintenance_cancellation(maintenance_cancel_rx).await?;
+        if delete_expired {
+            delete_memory(
+                memory_store,
+                embedding_table,
+                embedding_model,
+                &id,
+                "memory expired",
+                maintenance_cancel_rx,
+            )
+            .await?;
+        } else {
+            if !maintenance_cancelable_op(maintenance_cancel_rx, memory_store.forget(&id)).await? {
+                continue;
+            }
+            let snapshot =
+                maintenance_cancelable_op(maintenance_cancel_rx, memory_store.load(&id)).await?;
+            mutations::record_or_warn(
+                memory_store,
+                MutationKind::Forget,
+                &id,
+                snapshot.as_ref(),
+                ACTOR_MAINTENANCE,
+                Some("memory expired"),
+            )
+            .await;
+        }
+        expired_count += 1;
+    }
+
+    Ok(expired_count)
+}
+
+/// Permanently delete a memory along with its embedding, cached vector,
+/// graph edges, and todo details.
+async fn delete_memory(
+    memory_store: &MemoryStore,
+    embedding_table: &EmbeddingTable,
+    embedding_model: &EmbeddingModel,
+    id: &str,
+    reason: &str,
+    maintenance_cancel_rx: &mut watch::Receiver<bool>,
+) -> Result<()> {
+    let content = maintenance_cancelable_op(maintenance_cancel_rx, memory_store.load(id))
+        .await?
+        .map(|memory| memory.content);
+    if let Err(error) =
+        maintenance_cancelable_op(maintenance_cancel_rx, embedding_table.delete(id)).await
+    {
+        tracing::warn!(%error, memory_id = %id, "failed to delete embedding for purged memory");
+    }
+    maintenance_cancelable_op(
+        maintenance_cancel_rx,
+        memory_store.delete_associations_for_memory(id),
+    )
+    .await?;
+    maintenance_cancelable_op(
+        maintenance_cancel_rx,
+        crate::memory::todos::delete_details(memory_store, id),
+    )
+    .await?;
+    maintenance_cancelable_op(maintenance_cancel_rx, memory_store.delete(id)).await?;
+    if let Some(content) = content {
+        embedding_model.evict(&[content]).await;
+    }
+    mutations::record_or_warn(
+        memory_store,
+        MutationKind::Delete,
+        id,
+        None,
+        ACTOR_MAINTENANCE,
+        Some(reason),
+    )
+    .await;
+    Ok(())
 }
 
 /// Merge near-duplicate memories.
@@ -267,7 +570,9 @@ async fn merge_similar_memories(
             else {
                 continue;
             };
-            if candidate_memory.forgotten {
+            // Merging across scopes would carry content into a scope that
+            // must not see it.
+            if candidate_memory.forgotten || candidate_memory.scope != active_survivor.scope {
                 continue;
             }
 
@@ -357,6 +662,28 @@ async fn merge_pair(
     )
     .await?;
 
+    mutations::record_or_warn(
+        memory_store,
+        MutationKind::Merge,
+        &updated_survivor.id,
+        Some(&updated_survivor),
+        ACTOR_MAINTENANCE,
+        Some(&format!("absorbed near-duplicate {}", merged.id)),
+    )
+    .await;
+    let mut merged_snapshot = merged.clone();
+    merged_snapshot.forgotten = true;
+    merged_snapshot.forgotten_at = Some(updated_survivor.updated_at);
+    mutations::record_or_warn(
+        memory_store,
+        MutationKind::Merge,
+        &merged.id,
+        Some(&merged_snapshot),
+        ACTOR_MAINTENANCE,
+        Some(&format!("merged into {}", updated_survivor.id)),
+    )
+    .await;
+
     let updated_survivor_embedding = maintenance_cancelable_op(
         maintenance_cancel_rx,
         embedding_model.embed_one(&updated_survivor.content),
@@ -410,7 +737,7 @@ async fn fetch_candidate_memory_ids(
 
 fn validate_maintenance_config(config: &MaintenanceConfig) -> Result<()> {
     validate_unit_interval("prune_threshold", config.prune_threshold)?;
-    validate_unit_interval("decay_rate", config.decay_rate)?;
+    config.half_lives.validate()?;
     validate_unit_interval(
         "merge_similarity_threshold",
         config.merge_similarity_threshold,
@@ -422,6 +749,20 @@ fn validate_maintenance_config(config: &MaintenanceConfig) -> Result<()> {
         )
         .into());
     }
+    if config.trash_retention_days < 0 {
+        return Err(anyhow::anyhow!(
+            "maintenance trash_retention_days must be >= 0, got {}",
+            config.trash_retention_days
+        )
+        .into());
+    }
+    if config.mutation_retention_days < 0 {
+        return Err(anyhow::anyhow!(
+            "maintenance mutation_retention_days must be >= 0, got {}",
+            config.mutation_retention_days
+        )
+        .into());
+    }
     Ok(())
 }
 
@@ -479,9 +820,12 @@ where
 /// Maintenance report.
 #[derive(Debug, Default)]
 pub struct MaintenanceReport {
+    pub expired: usize,
     pub decayed: usize,
-    pub pruned: usize,
+    pub archived: usize,
     pub merged: usize,
+    pub purged: usize,
+    pub mutations_pruned: u64,
 }
 
 #[cfg(test)]
@@ -532,9 +876,12 @@ mod tests {
             .execute()
             .await
             .expect("failed to connect to lancedb");
-        let embedding_table = crate::memory::EmbeddingTable::open_or_create(&lance_conn)
-            .await
-            .expect("failed to create embedding table");
+        let embedding_table = crate::memory::EmbeddingTable::open_or_create(
+            &lance_conn,
+            crate::memory::embedding::LOCAL_DIMENSIONS,
+        )
+        .await
+        .expect("failed to create embedding table");
 
         let survivor = create_memory_with_embedding(
             &store,
@@ -586,9 +933,12 @@ mod tests {
 
         let config = super::MaintenanceConfig {
             prune_threshold: 0.2,
-            decay_rate: 0.05,
+            half_lives: DecayHalfLives::default(),
             min_age_days: 30,
             merge_similarity_threshold: 0.95,
+            trash_retention_days: 30,
+            delete_expired: false,
+            mutation_retention_days: 0,
         };
 
         let embedding_model = shared_embedding_model();
@@ -672,9 +1022,12 @@ mod tests {
             .execute()
             .await
             .expect("failed to connect to lancedb");
-        let embedding_table = crate::memory::EmbeddingTable::open_or_create(&lance_conn)
-            .await
-            .expect("failed to create embedding table");
+        let embedding_table = crate::memory::EmbeddingTable::open_or_create(
+            &lance_conn,
+            crate::memory::embedding::LOCAL_DIMENSIONS,
+        )
+        .await
+        .expect("failed to create embedding table");
 
         let survivor = create_memory_with_embedding(
             &store,
@@ -756,9 +1109,12 @@ mod tests {
             &embedding_model,
             &MaintenanceConfig {
                 prune_threshold: 0.2,
-                decay_rate: 0.05,
+                half_lives: DecayHalfLives::default(),
                 min_age_days: 30,
                 merge_similarity_threshold: 0.95,
+                trash_retention_days: 30,
+                delete_expired: false,
+                mutation_retention_days: 0,
             },
         )
         .await
@@ -824,9 +1180,12 @@ mod tests {
             .execute()
             .await
             .expect("failed to connect to lancedb");
-        let embedding_table = crate::memory::EmbeddingTable::open_or_create(&lance_conn)
-            .await
-            .expect("failed to create embedding table");
+        let embedding_table = crate::memory::EmbeddingTable::open_or_create(
+            &lance_conn,
+            crate::memory::embedding::LOCAL_DIMENSIONS,
+        )
+        .await
+        .expect("failed to create embedding table");
 
         let (_cancel_tx, maintenance_cancel_rx) = tokio::sync::watch::channel(true);
         let embedding_model = shared_embedding_model();
@@ -863,15 +1222,21 @@ mod tests {
             .execute()
             .await
             .expect("failed to connect to lancedb");
-        let embedding_table = crate::memory::EmbeddingTable::open_or_create(&lance_conn)
-            .await
-            .expect("failed to create embedding table");
+        let embedding_table = crate::memory::EmbeddingTable::open_or_create(
+            &lance_conn,
+            crate::memory::embedding::LOCAL_DIMENSIONS,
+        )
+        .await
+        .expect("failed to create embedding table");
 
         let invalid_config = MaintenanceConfig {
             prune_threshold: 0.2,
-            decay_rate: 0.05,
+            half_lives: DecayHalfLives::default(),
             min_age_days: -1,
             merge_similarity_threshold: 0.95,
+            trash_retention_days: 30,
+            delete_expired: false,
+            mutation_retention_days: 0,
         };
 
         let embedding_model = shared_embedding_model();
@@ -886,4 +1251,58 @@ mod tests {
                 .contains("min_age_days must be >= 0")
         );
     }
+
+    #[test]
+    fn importance_halves_per_half_life_unless_accessed() {
+        let half_lives = DecayHalfLives::default();
+        let now = chrono::Utc::now();
+        let week_ago = now - chrono::Duration::days(7);
+
+        let mut observation =
+            Memory::new("saw a thing", MemoryType::Observation).with_importance(0.8);
+        observation.last_accessed_at = week_ago;
+        let decayed = half_lives.effective_importance(&observation, week_ago, now);
+        assert!((decayed - 0.4).abs() < 0.001);
+
+        // A recent read means there's nothing to decay yet.
+        observation.last_accessed_at = now;
+        assert_eq!(
+            half_lives.effective_importance(&observation, week_ago, now),
+            0.8
+        );
+
+        let mut identity = Memory::new("my name", MemoryType::Identity).with_importance(1.0);
+        identity.last_accessed_at = now - chrono::Duration::days(3650);
+        assert_eq!(
+            half_lives.effective_importance(&identity, identity.last_accessed_at, now),
+            1.0
+        );
+
+        let never = DecayHalfLives {
+            observation: 0.0,
+            ..DecayHalfLives::default()
+        };
+        assert_eq!(never.for_type(MemoryType::Observation), None);
+        assert!(
+            DecayHalfLives {
+                event: -1.0,
+                ..DecayHalfLives::default()
+            }
+            .validate()
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn legacy_decay_rate_converts_to_the_day_it_halved() {
+        // 0.05 per day took ten days to take half the importance away.
+        let converted = DecayHalfLives::from_legacy_decay_rate(0.05).unwrap();
+        assert_eq!(converted, DecayHalfLives::uniform(10.0));
+        assert_eq!(
+            DecayHalfLives::from_legacy_decay_rate(0.0),
+            Some(DecayHalfLives::uniform(0.0))
+        );
+        assert_eq!(DecayHalfLives::from_legacy_decay_rate(1.5), None);
+        assert_eq!(DecayHalfLives::from_legacy_decay_rate(f32::NAN), None);
+    }
 }
@@ -0,0 +1,523 @@
+//! Append-only log of memory mutations.
+//!
+//! Every change to a memory — creation, edits, merges, decay, trashing,
+//! restoring, and deletion — is appended here with who made it and why,
+//! along with a snapshot of the memory as it stood afterwards, so it answers
+//! "why does the agent believe X" after the fact and lets the store be
+//! reconstructed as of a past time.
+//!
+//! Three things keep the log bounded and honest. Consecutive decay entries
+//! for a memory collapse into one, since maintenance decays most memories
+//! on every pass. Permanent deletion clears the memory's earlier snapshots,
+//! so its content doesn't outlive it. Maintenance prunes entries past the
+//! retention window, keeping each memory's newest older entry as a baseline.
+
+use crate::error::Result;
+use crate::memory::{Memory, MemoryStore};
+
+use anyhow::Context as _;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::Row as _;
+
+/// What happened to a memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MutationKind {
+    Create,
+    /// Content, type, or importance edited.
+    Update,
+    /// Absorbed another memory, or was absorbed into one.
+    Merge,
+    /// Importance lowered by maintenance decay.
+    Decay,
+    /// Moved to the trash.
+    Forget,
+    /// Taken back out of the trash.
+    Restore,
+    /// Removed permanently.
+    Delete,
+}
+
+impl MutationKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MutationKind::Create => "create",
+            MutationKind::Update => "update",
+            MutationKind::Merge => "merge",
+            MutationKind::Decay => "decay",
+            MutationKind::Forget => "forget",
+            MutationKind::Restore => "restore",
+            MutationKind::Delete => "delete",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "create" => Some(MutationKind::Create),
+            "update" => Some(MutationKind::Update),
+            "merge" => Some(MutationKind::Merge),
+            "decay" => Some(MutationKind::Decay),
+            "forget" => Some(MutationKind::Forget),
+            "restore" => Some(MutationKind::Restore),
+            "delete" => Some(MutationKind::Delete),
+            _ => None,
+        }
+    }
+}
+
+/// Mutation made by an agent process through its memory tools.
+pub const ACTOR_AGENT: &str = "agent";
+/// Mutation made by a dashboard or API client.
+pub const ACTOR_API: &str = "api";
+/// Mutation made by periodic memory maintenance.
+pub const ACTOR_MAINTENANCE: &str = "maintenance";
+/// Mutation made by importance recalibration from usage signals.
+pub const ACTOR_RECALIBRATION: &str = "recalibration";
+/// Mutation made by rescoring old memories against recent activity.
+pub const ACTOR_RESCORE: &str = "rescore";
+/// Mutation made by consolidation of near-duplicate memories.
+pub const ACTOR_CONSOLIDATION: &str = "consolidation";
+/// Baseline entry for a memory saved before the log existed.
+pub const ACTOR_BASELINE: &str = "baseline";
+
+/// One entry in a memory's history.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct MemoryMutation {
+    pub id: i64,
+    pub memory_id: String,
+    pub kind: MutationKind,
+    pub actor: String,
+    pub reason: Option<String>,
+    /// The memory after the mutation. Absent for deletions.
+    pub snapshot: Option<Memory>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Append a mutation to the log. `snapshot` is the memory after the change;
+/// pass `None` when it no longer exists.
+///
+/// A decay directly after another decay replaces it rather than adding an
+/// entry, and a deletion clears the memory's earlier snapshots.
+pub async fn record(
+    store: &MemoryStore,
+    kind: MutationKind,
+    memory_id: &str,
+    snapshot: Option<&Memory>,
+    actor: &str,
+    reason: Option<&str>,
+) -> Result<()> {
+    let snapshot = snapshot
+        .map(serde_json::to_string)
+        .transpose()
+        .context("failed to serialize memory snapshot")?;
+
+    match kind {
+        MutationKind::Decay => {
+            let replaced = sqlx::query(
+                "UPDATE memory_mutations SET actor = ?, reason = ?, snapshot = ?, created_at = ? \
+                 WHERE id = (SELECT MAX(id) FROM memory_mutations WHERE memory_id = ?) \
+                 AND kind = 'decay'",
+            )
+            .bind(actor)
+            .bind(reason)
+            .bind(&snapshot)
+            .bind(Utc::now())
+            .bind(memory_id)
+            .execute(store.pool())
+            .await
+            .with_context(|| format!("failed to record decay of memory {memory_id}"))?;
+            if replaced.rows_affected() > 0 {
+                return Ok(());
+            }
+        }
+        MutationKind::Delete => {
+            sqlx::query("UPDATE memory_mutations SET snapshot = NULL WHERE memory_id = ?")
+                .bind(memory_id)
+                .execute(store.pool())
+                .await
+                .with_context(|| format!("failed to clear snapshots of memory {memory_id}"))?;
+        }
+        _ => {}
+    }
+
+    insert(store, kind, memory_id, snapshot, actor, reason, Utc::now()).await
+}
+
+async fn insert(
+    store: &MemoryStore,
+    kind: MutationKind,
+    memory_id: &str,
+    snapshot: Option<String>,
+    actor: &str,
+    reason: Option<&str>,
+    created_at: DateTime<Utc>,
+) -> Result<()> {
+    sqlx::query(
+        "INSERT INTO memory_mutations (memory_id, kind, actor, reason, snapshot, created_at) \
+         VALUES (?, ?, ?, ?, ?, ?)",
+    )
+    .bind(memory_id)
+    .bind(kind.as_str())
+    .bind(actor)
+    .bind(reason)
+    .bind(snapshot)
+    .bind(created_at)
+    .execute(store.pool())
+    .await
+    .with_context(|| format!("failed to record {} of memory {memory_id}", kind.as_str()))?;
+    Ok(())
+}
+
+/// Give every memory without log entries a baseline `create` entry holding
+/// its current state, dated to its creation. Without one, a memory saved
+/// before the log existed would drop out of past states as soon as it
+/// changed. Returns how many baselines were written.
+pub async fn seed_baselines(store: &MemoryStore) -> Result<usize> {
+    let unlogged_ids: Vec<String> = sqlx::query_scalar(
+        "SELECT id FROM memories WHERE id NOT IN (SELECT memory_id FROM memory_mutations)",
+    )
+    .fetch_all(store.pool())
+    .await
+    .context("failed to find memories without history")?;
+
+    let mut seeded = 0;
+    for memory_id in unlogged_ids {
+        let Some(memory) = store.load(&memory_id).await? else {
+            continue;
+        };
+        let snapshot =
+            serde_json::to_string(&memory).context("failed to serialize memory snapshot")?;
+        insert(
+            store,
+            MutationKind::Create,
+            &memory_id,
+            Some(snapshot),
+            ACTOR_BASELINE,
+            Some("state when the mutation log was introduced"),
+            memory.created_at,
+        )
+        .await?;
+        seeded += 1;
+    }
+    Ok(seeded)
+}
+
+/// Drop entries older than `retention_days`, keeping each memory's newest
+/// older entry so past states inside the window still reconstruct. That
+/// entry goes too when it records a deletion. Zero keeps everything.
+/// Returns how many entries were removed.
+pub async fn prune(store: &MemoryStore, retention_days: i64) -> Result<u64> {
+    if retention_days <= 0 {
+        return Ok(0);
+    }
+    let cutoff = Utc::now() - chrono::Duration::days(retention_days);
+    let result = sqlx::query(
+        "DELETE FROM memory_mutations WHERE created_at < ? AND (kind = 'delete' OR id NOT IN \
+         (SELECT MAX(id) FROM memory_mutations WHERE created_at < ? GROUP BY memory_id))",
+    )
+    .bind(cutoff)
+    .bind(cutoff)
+    .execute(store.pool())
+    .await
+    .context("failed to prune memory mutations")?;
+    Ok(result.rows_affected())
+}
+
+/// Record a mutation, logging instead of failing. The log is diagnostic;
+/// a write to it failing should never undo or block the mutation itself.
+pub async fn record_or_warn(
+    store: &MemoryStore,
+    kind: MutationKind,
+    memory_id: &str,
+    snapshot: Option<&Memory>,
+    actor: &str,
+    reason: Option<&str>,
+) {
+    if let Err(error) = record(store, kind, memory_id, snapshot, actor, reason).await {
+        tracing::warn!(%error, %memory_id, kind = kind.as_str(), "failed to record memory mutation");
+    }
+}
+
+/// A memory's mutations, oldest first.
+pub async fn history(
+    store: &MemoryStore,
+    memory_id: &str,
+    limit: i64,
+) -> Result<Vec<MemoryMutation>> {
+    let rows = sqlx::query(
+        "SELECT id, memory_id, kind, actor, reason, snapshot, created_at \
+         FROM memory_mutations WHERE memory_id = ? ORDER BY id ASC LIMIT ?",
+    )
+    .bind(memory_id)
+    .bind(limit)
+    .fetch_all(store.pool())
+    .await
+    .with_context(|| format!("failed to load history of memory {memory_id}"))?;
+
+    Ok(rows.iter().filter_map(row_to_mutation).collect())
+}
+
+/// Reconstruct the store as it stood at `at`: each memory's most recent
+/// snapshot at or before that time. Deleted memories are left out, since
+/// deletion clears their snapshots, and times before the retention window
+/// come out approximate. Memories that still have no entries (see
+/// [`seed_baselines`]) are included in their current state if they were
+/// created by `at`.
+pub async fn state_as_of(
+    store: &MemoryStore,
+    at: DateTime<Utc>,
+    include_forgotten: bool,
+) -> Result<Vec<Memory>> {
+    let rows = sqlx::query(
+        "SELECT m.id, m.memory_id, m.kind, m.actor, m.reason, m.snapshot, m.created_at \
+         FROM memory_mutations m \
+         JOIN (SELECT memory_id, MAX(id) AS id FROM memory_mutations \
+               WHERE created_at <= ? GROUP BY memory_id) latest ON latest.id = m.id \
+         WHERE m.kind != 'delete'",
+    )
+    .bind(at)
+    .fetch_all(store.pool())
+    .await
+    .context("failed to reconstruct memory state")?;
+
+    let mut memories: Vec<Memory> = rows
+        .iter()
+        .filter_map(row_to_mutation)
+        .filter_map(|mutation| mutation.snapshot)
+        .collect();
+
+    let unlogged_ids: Vec<String> = sqlx::query_scalar(
+        "SELECT id FROM memories WHERE created_at <= ? \
+         AND id NOT IN (SELECT memory_id FROM memory_mutations)",
+    )
+    .bind(at)
+    .fetch_all(store.pool())
+    .await
+    .context("failed to find memories without history")?;
+    for memory_id in unlogged_ids {
+        if let Some(memory) = store.load(&memory_id).await? {
+            memories.push(memory);
+        }
+    }
+
+    if !include_forgotten {
+        memories.retain(|memory| !memory.forgotten);
+    }
+    memories.sort_by_key(|memory| std::cmp::Reverse(memory.created_at));
+    Ok(memories)
+}
+
+fn row_to_mutation(row: &sqlx::sqlite::SqliteRow) -> Option<MemoryMutation> {
+    let kind: String = row.try_get("kind").ok()?;
+    let snapshot: Option<String> = row.try_get("snapshot").ok().flatten();
+    Some(MemoryMutation {
+        id: row.try_get("id").ok()?,
+        memory_id: row.try_get("memory_id").ok()?,
+        kind: MutationKind::parse(&kind)?,
+        actor: row.try_get("actor").unwrap_or_default(),
+        reason: row.try_get("reason").ok().flatten(),
+        snapshot: snapshot.and_then(|snapshot| serde_json::from_str(&snapshot).ok()),
+        created_at: row.try_get("created_at").unwrap_or_else(|_| Utc::now()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::MemoryType;
+
+    #[tokio::test]
+    async fn reconstructs_state_at_a_past_time() {
+        let store = MemoryStore::connect_in_memory().await;
+        let mut memory = Memory::new("the deploy target is staging", MemoryType::Fact);
+        store.save(&memory).await.unwrap();
+        record(
+            &store,
+            MutationKind::Create,
+            &memory.id,
+            Some(&memory),
+            ACTOR_AGENT,
+            None,
+        )
+        .await
+        .unwrap();
+        let doomed = Memory::new("temporary note", MemoryType::Observation);
+        store.save(&doomed).await.unwrap();
+        record(
+            &store,
+            MutationKind::Create,
+            &doomed.id,
+            Some(&doomed),
+            ACTOR_AGENT,
+            None,
+        )
+        .await
+        .unwrap();
+
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        let before_edit = Utc::now();
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+
+        memory.content = "the deploy target is production".into();
+        store.update(&memory).await.unwrap();
+        record(
+            &store,
+            MutationKind::Update,
+            &memory.id,
+            Some(&memory),
+            ACTOR_API,
+            Some("user correction"),
+        )
+        .await
+        .unwrap();
+        store.delete(&doomed.id).await.unwrap();
+        record(
+            &store,
+            MutationKind::Delete,
+            &doomed.id,
+            None,
+            ACTOR_API,
+            None,
+        )
+        .await
+        .unwrap();
+
+        // The deletion cleared the doomed memory's snapshots, so it no longer
+        // shows up even at times it existed.
+        let then = state_as_of(&store, before_edit, false).await.unwrap();
+        assert_eq!(then.len(), 1);
+        assert!(
+            then.iter()
+                .any(|m| m.content == "the deploy target is staging")
+        );
+
+        let now = state_as_of(&store, Utc::now(), false).await.unwrap();
+        assert_eq!(now.len(), 1);
+        assert_eq!(now[0].content, "the deploy target is production");
+
+        let log = history(&store, &memory.id, 10).await.unwrap();
+        assert_eq!(log.len(), 2);
+        assert_eq!(log[1].kind, MutationKind::Update);
+        assert_eq!(log[1].reason.as_deref(), Some("user correction"));
+    }
+
+    #[tokio::test]
+    async fn consecutive_decays_collapse_into_one_entry() {
+        let store = MemoryStore::connect_in_memory().await;
+        let mut memory = Memory::new("prefers short answers", MemoryType::Preference);
+        store.save(&memory).await.unwrap();
+        record(
+            &store,
+            MutationKind::Create,
+            &memory.id,
+            Some(&memory),
+            ACTOR_AGENT,
+            None,
+        )
+        .await
+        .unwrap();
+
+        for importance in [0.8, 0.6, 0.4] {
+            memory.importance = importance;
+            record(
+                &store,
+                MutationKind::Decay,
+                &memory.id,
+                Some(&memory),
+                ACTOR_MAINTENANCE,
+                None,
+            )
+            .await
+            .unwrap();
+        }
+
+        let log = history(&store, &memory.id, 10).await.unwrap();
+        assert_eq!(log.len(), 2);
+        assert_eq!(log[1].kind, MutationKind::Decay);
+        assert_eq!(log[1].snapshot.as_ref().unwrap().importance, 0.4);
+    }
+
+    #[tokio::test]
+    async fn seeds_baselines_for_unlogged_memories() {
+        let store = MemoryStore::connect_in_memory().await;
+        let mut memory = Memory::new("the office is in Berlin", MemoryType::Fact);
+        store.save(&memory).await.unwrap();
+
+        assert_eq!(seed_baselines(&store).await.unwrap(), 1);
+        assert_eq!(seed_baselines(&store).await.unwrap(), 0);
+
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        let before_edit = Utc::now();
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+
+        memory.content = "the office is in Lisbon".into();
+        store.update(&memory).await.unwrap();
+        record(
+            &store,
+            MutationKind::Update,
+            &memory.id,
+            Some(&memory),
+            ACTOR_API,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let then = state_as_of(&store, before_edit, false).await.unwrap();
+        assert_eq!(then.len(), 1);
+        assert_eq!(then[0].content, "the office is in Berlin");
+    }
+
+    #[tokio::test]
+    async fn prune_keeps_the_newest_entry_before_the_cutoff() {
+        let store = MemoryStore::connect_in_memory().await;
+        let memory = Memory::new("uses vim", MemoryType::Preference);
+        store.save(&memory).await.unwrap();
+        let old = Utc::now() - chrono::Duration::days(60);
+        for kind in [MutationKind::Create, MutationKind::Update] {
+            let snapshot = serde_json::to_string(&memory).unwrap();
+            insert(
+                &store,
+                kind,
+                &memory.id,
+                Some(snapshot),
+                ACTOR_AGENT,
+                None,
+                old,
+            )
+            .await
+            .unwrap();
+        }
+        insert(
+            &store,
+            MutationKind::Create,
+            "gone",
+            None,
+            ACTOR_AGENT,
+            None,
+            old,
+        )
+        .await
+        .unwrap();
+        insert(
+            &store,
+            MutationKind::Delete,
+            "gone",
+            None,
+            ACTOR_AGENT,
+            None,
+            old,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(prune(&store, 0).await.unwrap(), 0);
+        assert_eq!(prune(&store, 30).await.unwrap(), 3);
+
+        let log = history(&store, &memory.id, 10).await.unwrap();
+        assert_eq!(log.len(), 1);
+        assert_eq!(log[0].kind, MutationKind::Update);
+        assert!(history(&store, "gone", 10).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,299 @@
+//! Importance rescoring of old memories against recent activity.
+//!
+//! Importance is set when a memory is written, and decay only looks at how
+//! long the memory itself has gone unused. A memory saved as important months
+//! ago can keep seeding graph traversal in hybrid search long after the
+//! conversation has moved on. Rescoring looks at old memories in light of
+//! what the agent has been doing lately: the memory's own recent accesses,
+//! and the semantically related memories created or accessed in the recent
+//! window.
+//!
+//! Deciding the new importance is left to the caller; cortex asks the
+//! compactor model to score batches of candidates and falls back to
+//! [`rescored_importance`] when it can't. [`rescore_importance`] runs the
+//! heuristic alone.
+
+use crate::error::Result;
+use crate::memory::mutations::{self, ACTOR_RESCORE, MutationKind};
+use crate::memory::{EmbeddingTable, Memory, MemoryStore, MemoryType};
+
+use anyhow::Context as _;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// Old memories examined per pass, most important first.
+const MAX_RESCORED_PER_PASS: i64 = 500;
+/// Similarity at which another memory counts as related activity.
+const RELATED_SIMILARITY: f32 = 0.75;
+/// Related memories looked up per examined memory.
+const MAX_RELATED: usize = 20;
+/// Fraction of the gap to the ceiling that active memories close per pass.
+const RAISE_RATE: f32 = 0.25;
+/// Changes smaller than this are not applied or logged.
+const MIN_ADJUSTMENT: f32 = 0.01;
+
+/// Rescoring parameters, from the cortex config.
+#[derive(Debug, Clone, Copy)]
+pub struct RescoreConfig {
+    /// Memories younger than this are left alone.
+    pub min_age_days: i64,
+    /// How far back counts as recent activity.
+    pub window_days: i64,
+    /// Highest importance a memory with no recent activity keeps.
+    pub stale_ceiling: f32,
+}
+
+impl RescoreConfig {
+    pub fn from_cortex(cortex: &crate::config::CortexConfig) -> Self {
+        Self {
+            min_age_days: cortex.rescore_min_age_days,
+            window_days: cortex.rescore_window_days,
+            stale_ceiling: cortex.rescore_stale_ceiling,
+        }
+    }
+}
+
+/// One importance change made by a rescoring pass.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct RescoreAdjustment {
+    pub memory_id: String,
+    pub previous_importance: f32,
+    pub new_importance: f32,
+    /// Recent accesses of the memory plus related memories created or
+    /// accessed in the window.
+    pub recent_activity: usize,
+    /// The model's explanation, when the model did the scoring.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Outcome of a rescoring pass.
+#[derive(Debug, Default, Serialize, utoipa::ToSchema)]
+pub struct RescoreReport {
+    pub examined: usize,
+    pub lowered: usize,
+    pub raised: usize,
+    pub adjustments: Vec<RescoreAdjustment>,
+    /// Candidates scored by the activity heuristic rather than the model.
+    pub heuristic_scored: usize,
+}
+
+/// Importance a memory should have given `recent_activity` signals.
+///
+/// Each signal halves the remaining distance between the stale ceiling and
+/// full importance. Memories above that ceiling drop to it; memories below
+/// it rise by a fraction of the gap, and only when there was activity.
+pub fn rescored_importance(importance: f32, recent_activity: usize, stale_ceiling: f32) -> f32 {
+    let relevance = 1.0 - 0.5_f32.powi(recent_activity.min(16) as i32);
+    let ceiling = stale_ceiling + (1.0 - stale_ceiling) * relevance;
+    if importance > ceiling {
+        ceiling
+    } else if recent_activity > 0 {
+        importance + (ceiling - importance) * RAISE_RATE
+    } else {
+        importance
+    }
+}
+
+/// An old memory up for rescoring, with what the recent window says about it.
+#[derive(Debug, Clone)]
+pub struct RescoreCandidate {
+    pub memory: Memory,
+    /// Recent accesses of the memory plus related memories created or
+    /// accessed in the window.
+    pub recent_activity: usize,
+    /// Related memories created or accessed in the window, most similar first.
+    pub related: Vec<Memory>,
+}
+
+impl RescoreCandidate {
+    /// Importance the activity heuristic gives this memory.
+    pub fn heuristic_importance(&self, config: &RescoreConfig) -> f32 {
+        rescored_importance(
+            self.memory.importance,
+            self.recent_activity,
+            config.stale_ceiling,
+        )
+    }
+}
+
+/// Old memories whose importance is worth re-evaluating, most important
+/// first. Memories at or below the stale ceiling with no recent activity are
+/// skipped: nothing has happened that could change their score. Identity
+/// memories keep their fixed importance.
+pub async fn find_candidates(
+    store: &MemoryStore,
+    embedding_table: &EmbeddingTable,
+    config: &RescoreConfig,
+) -> Result<Vec<RescoreCandidate>> {
+    let now = Utc::now();
+    let cutoff = now - chrono::Duration::days(config.min_age_days);
+    let window_start = now - chrono::Duration::days(config.window_days);
+
+    let candidate_ids: Vec<String> = sqlx::query_scalar(
+        "SELECT id FROM memories WHERE forgotten = 0 AND memory_type != 'identity' \
+         AND created_at < ? ORDER BY importance DESC, id ASC LIMIT ?",
+    )
+    .bind(cutoff)
+    .bind(MAX_RESCORED_PER_PASS)
+    .fetch_all(store.pool())
+    .await
+    .context("failed to fetch memories to rescore")?;
+
+    let mut candidates = Vec::new();
+    for memory_id in candidate_ids {
+        let Some(memory) = store.load(&memory_id).await? else {
+            continue;
+        };
+        if memory.forgotten || memory.memory_type == MemoryType::Identity {
+            continue;
+        }
+
+        let (recent_activity, related) =
+            recent_activity(store, embedding_table, &memory, window_start).await?;
+        if recent_activity == 0 && memory.importance <= config.stale_ceiling {
+            continue;
+        }
+        candidates.push(RescoreCandidate {
+            memory,
+            recent_activity,
+            related,
+        });
+    }
+
+    Ok(candidates)
+}
+
+/// Set a candidate's importance, record the change in the mutation history,
+/// and add it to `report`. Changes below [`MIN_ADJUSTMENT`] are dropped.
+pub async fn apply(
+    store: &MemoryStore,
+    candidate: &RescoreCandidate,
+    new_importance: f32,
+    reason: Option<&str>,
+    report: &mut RescoreReport,
+) -> Result<()> {
+    let memory = &candidate.memory;
+    let reason = reason.map(str::trim).filter(|reason| !reason.is_empty());
+    let new_importance = new_importance.clamp(0.0, 1.0);
+    if (new_importance - memory.importance).abs() < MIN_ADJUSTMENT {
+        return Ok(());
+    }
+
+    // Leave `updated_at` alone: decay measures idle time from it, and a
+    // rescore isn't a content change.
+    sqlx::query("UPDATE memories SET importance = ? WHERE id = ?")
+        .bind(new_importance)
+        .bind(&memory.id)
+        .execute(store.pool())
+        .await
+        .with_context(|| format!("failed to rescore memory {}", memory.id))?;
+
+    let mut snapshot = memory.clone();
+    snapshot.importance = new_importance;
+    let mut summary = format!(
+        "importance {:.2} -> {new_importance:.2}, recent activity {}",
+        memory.importance, candidate.recent_activity
+    );
+    if let Some(reason) = reason {
+        summary.push_str(": ");
+        summary.push_str(reason);
+    }
+    mutations::record_or_warn(
+        store,
+        MutationKind::Update,
+        &memory.id,
+        Some(&snapshot),
+        ACTOR_RESCORE,
+        Some(&summary),
+    )
+    .await;
+
+    if new_importance < memory.importance {
+        report.lowered += 1;
+    } else {
+        report.raised += 1;
+    }
+    report.adjustments.push(RescoreAdjustment {
+        memory_id: memory.id.clone(),
+        previous_importance: memory.importance,
+        new_importance,
+        recent_activity: candidate.recent_activity,
+        reason: reason.map(str::to_string),
+    });
+    Ok(())
+}
+
+/// Rescore old memories with the activity heuristic alone, log each change,
+/// and record it in the mutation history.
+pub async fn rescore_importance(
+    store: &MemoryStore,
+    embedding_table: &EmbeddingTable,
+    config: &RescoreConfig,
+) -> Result<RescoreReport> {
+    let candidates = find_candidates(store, embedding_table, config).await?;
+    let mut report = RescoreReport {
+        examined: candidates.len(),
+        heuristic_scored: candidates.len(),
+        ..Default::default()
+    };
+    for candidate in &candidates {
+        let new_importance = candidate.heuristic_importance(config);
+        apply(store, candidate, new_importance, None, &mut report).await?;
+    }
+    Ok(report)
+}
+
+/// Count the memory's own recent access plus related memories that were
+/// created or accessed since `window_start`, and return those memories.
+async fn recent_activity(
+    store: &MemoryStore,
+    embedding_table: &EmbeddingTable,
+    memory: &Memory,
+    window_start: DateTime<Utc>,
+) -> Result<(usize, Vec<Memory>)> {
+    let mut activity =
+        usize::from(memory.access_count > 0 && memory.last_accessed_at >= window_start);
+
+    let related = embedding_table
+        .find_similar(&memory.id, RELATED_SIMILARITY, MAX_RELATED)
+        .await
+        .with_context(|| format!("failed to find memories related to {}", memory.id))?;
+    let mut recent = Vec::new();
+    for (related_id, _similarity) in related {
+        let Some(related) = store.load(&related_id).await? else {
+            continue;
+        };
+        // Scoped memories only count toward memories in the same scope.
+        if related.forgotten || related.scope != memory.scope {
+            continue;
+        }
+        let accessed_recently =
+            related.access_count > 0 && related.last_accessed_at >= window_start;
+        if related.created_at >= window_start || accessed_recently {
+            activity += 1;
+            recent.push(related);
+        }
+    }
+
+    Ok((activity, recent))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stale_memories_drop_to_the_ceiling_and_active_ones_rise() {
+        // No activity: capped at the ceiling, lower scores untouched.
+        assert_eq!(rescored_importance(0.95, 0, 0.6), 0.6);
+        assert_eq!(rescored_importance(0.4, 0, 0.6), 0.4);
+
+        // One signal puts the ceiling halfway to 1.0.
+        assert!((rescored_importance(0.95, 1, 0.6) - 0.8).abs() < 1e-6);
+        assert!((rescored_importance(0.4, 1, 0.6) - 0.5).abs() < 1e-6);
+
+        // Lots of activity leaves important memories where they are.
+        assert!((rescored_importance(0.95, 8, 0.6) - 0.95).abs() < 0.02);
+    }
+}
@@ -0,0 +1,360 @@
+//! Memory scopes: namespaces that keep memories from one context out of
+//! another.
+//!
+//! An agent that serves several platforms or workspaces can map groups of
+//! channels to named scopes. A memory saved from a scoped channel is tagged
+//! with that scope and only recalled from channels in the same scope, or in a
+//! scope it is explicitly shared with. Memories saved outside any scope (from
+//! unmapped channels, ingestion, or the cortex) stay visible everywhere.
+//!
+//! Independently of configuration, a memory can be saved into a context
+//! scope: `channel:<id>` for one conversation, or `user:<platform>:<id>` for
+//! one person wherever they talk to the agent. Context-scoped memories are
+//! only recalled in that conversation or with that person, where they rank
+//! above global ones.
+
+use crate::error::{ConfigError, Result};
+
+use std::collections::HashSet;
+
+/// The scope name that means "no scope": visible everywhere.
+pub const GLOBAL_SCOPE: &str = "global";
+
+const CHANNEL_SCOPE_PREFIX: &str = "channel:";
+const USER_SCOPE_PREFIX: &str = "user:";
+
+/// Context scope for memories about one conversation.
+pub fn channel_scope(channel_id: &str) -> String {
+    format!("{CHANNEL_SCOPE_PREFIX}{channel_id}")
+}
+
+/// Context scope for memories about one person, keyed by the platform they
+/// write from and their ID there.
+pub fn user_scope(platform: &str, sender_id: &str) -> String {
+    format!("{USER_SCOPE_PREFIX}{platform}:{sender_id}")
+}
+
+/// Whether a scope is a per-channel or per-user context scope rather than a
+/// configured one.
+pub fn is_context_scope(scope: &str) -> bool {
+    scope.starts_with(CHANNEL_SCOPE_PREFIX) || scope.starts_with(USER_SCOPE_PREFIX)
+}
+
+/// A named group of channels whose memories are kept together.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryScope {
+    pub name: String,
+    /// Channel ID patterns. A trailing `*` matches any suffix, so
+    /// `slack:T0123:*` covers every channel in one Slack workspace.
+    pub channels: Vec<String>,
+    /// Other scopes allowed to recall this scope's memories.
+    pub share_with: Vec<String>,
+}
+
+impl MemoryScope {
+    fn matches(&self, channel_id: &str) -> bool {
+        self.channels
+            .iter()
+            .any(|pattern| match pattern.strip_suffix('*') {
+                Some(prefix) => channel_id.starts_with(prefix),
+                None => channel_id == pattern,
+            })
+    }
+}
+
+/// An agent's configured scopes. Empty means memories are unscoped.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemoryScopes {
+    scopes: Vec<MemoryScope>,
+}
+
+impl MemoryScopes {
+    /// Build from config, rejecting duplicate names and sharing with scopes
+    /// that don't exist.
+    pub fn new(scopes: Vec<MemoryScope>) -> Result<Self> {
+        let mut names = HashSet::new();
+        for scope in &scopes {
+            if scope.name.trim().is_empty() {
+                return Err(
+                    ConfigError::Invalid("memory scope name must not be empty".into()).into(),
+                );
+            }
+            if scope.name == GLOBAL_SCOPE || is_context_scope(&scope.name) {
+                return Err(ConfigError::Invalid(format!(
+                    "memory scope name '{}' is reserved",
+                    scope.name
+                ))
+                .into());
+            }
+            if !names.insert(scope.name.as_str()) {
+                return Err(ConfigError::Invalid(format!(
+                    "duplicate memory scope '{}'",
+                    scope.name
+                ))
+                .into());
+            }
+        }
+        for scope in &scopes {
+            if let Some(unknown) = scope
+                .share_with
+                .iter()
+                .find(|name| !names.contains(name.as_str()))
+            {
+                return Err(ConfigError::Invalid(format!(
+                    "memory scope '{}' shares with unknown scope '{unknown}'",
+                    scope.name
+                ))
+                .into());
+            }
+        }
+        Ok(Self { scopes })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.scopes.is_empty()
+    }
+
+    /// The scope a channel writes into. The first matching scope wins.
+    pub fn scope_for_channel(&self, channel_id: &str) -> Option<&str> {
+        self.scopes
+            .iter()
+            .find(|scope| scope.matches(channel_id))
+            .map(|scope| scope.name.as_str())
+    }
+
+    /// What a channel may recall: unscoped memories, its own scope, any
+    /// scope shared with it, and its channel context scope. Unmapped
+    /// channels only see unscoped and context memories once scopes are
+    /// configured.
+    pub fn filter_for_channel(&self, channel_id: &str) -> ScopeFilter {
+        self.filter_for_context(vec![channel_scope(channel_id)], channel_id)
+    }
+
+    /// [`filter_for_channel`](Self::filter_for_channel), plus the context
+    /// scope of the person the channel is talking to.
+    pub fn filter_for_conversation(
+        &self,
+        channel_id: &str,
+        user_scope: Option<&str>,
+    ) -> ScopeFilter {
+        let mut context = vec![channel_scope(channel_id)];
+        context.extend(user_scope.map(str::to_string));
+        self.filter_for_context(context, channel_id)
+    }
+
+    fn filter_for_context(&self, mut visible: Vec<String>, channel_id: &str) -> ScopeFilter {
+        if self.is_empty() {
+            return ScopeFilter::Context(visible);
+        }
+        let Some(own) = self.scope_for_channel(channel_id) else {
+            return ScopeFilter::Only(visible);
+        };
+        visible.push(own.to_string());
+        for scope in &self.scopes {
+            if scope.name != own && scope.share_with.iter().any(|name| name == own) {
+                visible.push(scope.name.clone());
+            }
+        }
+        ScopeFilter::Only(visible)
+    }
+
+    /// What every channel may see, for context shared across channels such
+    /// as the memory bulletin: no context-scoped memories, and only unscoped
+    /// ones once scopes are configured.
+    pub fn shared_filter(&self) -> ScopeFilter {
+        if self.is_empty() {
+            ScopeFilter::Context(Vec::new())
+        } else {
+            ScopeFilter::Only(Vec::new())
+        }
+    }
+
+    /// Scope enforcement for a process working on behalf of a channel,
+    /// talking to the person with context scope `user_scope` if known.
+    pub fn access_for_channel(&self, channel_id: &str, user_scope: Option<&str>) -> MemoryAccess {
+        MemoryAccess {
+            write_scope: self.scope_for_channel(channel_id).map(str::to_string),
+            read: self.filter_for_conversation(channel_id, user_scope),
+            channel_scope: Some(channel_scope(channel_id)),
+            user_scope: user_scope.map(str::to_string),
+        }
+    }
+}
+
+/// What a memory tool may write and read. The default is unrestricted, used
+/// by processes not tied to a channel (ingestion, cortex chat).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemoryAccess {
+    /// Scope new memories are tagged with.
+    pub write_scope: Option<String>,
+    pub read: ScopeFilter,
+    /// Context scope for memories saved as specific to this channel.
+    pub channel_scope: Option<String>,
+    /// Context scope for memories saved as specific to the current user.
+    pub user_scope: Option<String>,
+}
+
+/// Which scoped memories a reader may see. Unscoped memories are always
+/// visible.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ScopeFilter {
+    /// No restriction.
+    #[default]
+    All,
+    /// Unscoped memories plus memories in these scopes.
+    Only(Vec<String>),
+    /// Unscoped memories, every configured scope, and these context scopes.
+    /// Other channels' and users' memories stay hidden.
+    Context(Vec<String>),
+}
+
+impl ScopeFilter {
+    pub fn allows(&self, scope: Option<&str>) -> bool {
+        match (self, scope) {
+            (ScopeFilter::All, _) | (_, None) => true,
+            (ScopeFilter::Only(visible), Some(scope)) => visible.iter().any(|name| name == scope),
+            (ScopeFilter::Context(visible), Some(scope)) => {
+                !is_context_scope(scope) || visible.iter().any(|name| name == scope)
+            }
+        }
+    }
+
+    /// Whether a memory belongs to the reader's own context rather than
+    /// being global. Recall ranks these first. Unrestricted readers have no
+    /// context, so nothing is preferred.
+    pub fn prefers(&self, scope: Option<&str>) -> bool {
+        match (self, scope) {
+            (ScopeFilter::All, _) | (_, None) => false,
+            (ScopeFilter::Only(_), Some(_)) => self.allows(scope),
+            (ScopeFilter::Context(visible), Some(scope)) => {
+                visible.iter().any(|name| name == scope)
+            }
+        }
+    }
+
+    /// SQL condition (with a leading `AND`) restricting the `scope` column,
+    /// plus the values to bind for its placeholders.
+    pub(crate) fn sql_condition(&self) -> (String, &[String]) {
+        match self {
+            ScopeFilter::All => (String::new(), &[]),
+            ScopeFilter::Only(visible) if visible.is_empty() => {
+                (" AND scope IS NULL".to_string(), &[])
+            }
+            ScopeFilter::Only(visible) => {
+                let placeholders = vec!["?"; visible.len()].join(", ");
+                (
+                    format!(" AND (scope IS NULL OR scope IN ({placeholders}))"),
+                    visible,
+                )
+            }
+            ScopeFilter::Context(visible) => {
+                let placeholders = vec!["?"; visible.len()].join(", ");
+                let listed = if visible.is_empty() {
+                    String::new()
+                } else {
+                    format!(" OR scope IN ({placeholders})")
+                };
+                (
+                    format!(
+                        " AND (scope IS NULL OR (scope NOT LIKE '{CHANNEL_SCOPE_PREFIX}%' \
+                         AND scope NOT LIKE '{USER_SCOPE_PREFIX}%'){listed})"
+                    ),
+                    visible,
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scopes() -> MemoryScopes {
+        MemoryScopes::new(vec![
+            MemoryScope {
+                name: "personal".into(),
+                channels: vec!["discord:*".into()],
+                share_with: vec![],
+            },
+            MemoryScope {
+                name: "company".into(),
+                channels: vec!["slack:T0123:*".into()],
+                share_with: vec!["personal".into()],
+            },
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn channels_map_to_scopes_by_pattern() {
+        let scopes = scopes();
+        assert_eq!(scopes.scope_for_channel("discord:42:7"), Some("personal"));
+        assert_eq!(scopes.scope_for_channel("slack:T0123:C9"), Some("company"));
+        assert_eq!(scopes.scope_for_channel("slack:T9999:C9"), None);
+    }
+
+    #[test]
+    fn sharing_is_one_directional() {
+        let scopes = scopes();
+        let personal = scopes.filter_for_channel("discord:42:7");
+        assert!(personal.allows(Some("company")));
+        assert!(personal.allows(None));
+
+        let company = scopes.filter_for_channel("slack:T0123:C9");
+        assert!(company.allows(Some("company")));
+        assert!(!company.allows(Some("personal")));
+
+        let unmapped = scopes.filter_for_channel("telegram:1");
+        assert!(unmapped.allows(None));
+        assert!(!unmapped.allows(Some("company")));
+    }
+
+    #[test]
+    fn context_scopes_stay_in_their_conversation() {
+        let alice = user_scope("discord", "alice");
+        let server = ScopeFilter::Context(vec![channel_scope("discord:1:2")]);
+        let dm = MemoryScopes::default()
+            .filter_for_conversation("discord:dm:alice", Some(alice.as_str()));
+
+        assert!(server.allows(Some(channel_scope("discord:1:2").as_str())));
+        assert!(!dm.allows(Some(channel_scope("discord:1:2").as_str())));
+        assert!(dm.allows(Some(alice.as_str())));
+        assert!(!server.allows(Some(alice.as_str())));
+        assert!(dm.allows(None));
+
+        assert!(dm.prefers(Some(alice.as_str())));
+        assert!(!dm.prefers(None));
+
+        let scoped = scopes().filter_for_conversation("discord:42:7", Some(alice.as_str()));
+        assert!(scoped.allows(Some(alice.as_str())));
+        assert!(scoped.allows(Some("personal")));
+        assert!(!scoped.allows(Some(user_scope("discord", "bob").as_str())));
+
+        let shared = MemoryScopes::default().shared_filter();
+        assert!(!shared.allows(Some(alice.as_str())));
+        assert!(shared.allows(None));
+    }
+
+    #[test]
+    fn rejects_reserved_scope_names() {
+        for name in [GLOBAL_SCOPE, "channel:x", "user:y"] {
+            let result = MemoryScopes::new(vec![MemoryScope {
+                name: name.into(),
+                channels: vec![],
+                share_with: vec![],
+            }]);
+            assert!(result.is_err(), "{name} should be reserved");
+        }
+    }
+
+    #[test]
+    fn rejects_sharing_with_unknown_scope() {
+        let result = MemoryScopes::new(vec![MemoryScope {
+            name: "personal".into(),
+            channels: vec![],
+            share_with: vec!["nope".into()],
+        }]);
+        assert!(result.is_err());
+    }
+}
@@ -1,7 +1,8 @@
 //! Memory search: hybrid (vector + FTS + RRF + graph), temporal, importance, and typed queries.
 
 use crate::error::Result;
-use crate::memory::types::{Memory, MemorySearchResult, MemoryType, RelationType};
+use crate::memory::scope::ScopeFilter;
+use crate::memory::types::{LinkedMemory, Memory, MemorySearchResult, MemoryType, RelationType};
 use crate::memory::{EmbeddingModel, EmbeddingTable, MemoryStore};
 
 use std::collections::HashMap;
@@ -92,6 +93,43 @@ impl MemorySearch {
         &self.embedding_model
     }
 
+    /// Permanently delete a memory along with its embedding, graph edges, and
+    /// todo state. Returns false if the memory doesn't exist.
+    pub async fn delete_memory(&self, id: &str) -> Result<bool> {
+        let Some(memory) = self.store.load(id).await? else {
+            return Ok(false);
+        };
+        if let Err(error) = self.embedding_table.delete(id).await {
+            tracing::warn!(%error, memory_id = %id, "failed to delete embedding for deleted memory");
+        }
+        self.store.delete_associations_for_memory(id).await?;
+        crate::memory::todos::delete_details(&self.store, id).await?;
+        self.store.delete(id).await?;
+        self.embedding_model.evict(&[memory.content]).await;
+        Ok(true)
+    }
+
+    /// Write an edited memory back to the store. When the content changed,
+    /// the embedding is regenerated so vector and text search match the
+    /// new text.
+    pub async fn update_memory(&self, memory: &Memory, content_changed: bool) -> Result<()> {
+        let embedding = if content_changed {
+            Some(self.embedding_model.embed_one(&memory.content).await?)
+        } else {
+            None
+        };
+
+        self.store.update(memory).await?;
+
+        if let Some(embedding) = embedding {
+            self.embedding_table.delete(&memory.id).await?;
+            self.embedding_table
+                .store(&memory.id, &memory.content, &embedding)
+                .await?;
+        }
+        Ok(())
+    }
+
     /// Unified search entry point. Dispatches to the appropriate strategy
     /// based on `config.mode`.
     pub async fn search(
@@ -132,7 +170,12 @@ impl MemorySearch {
     ) -> Result<Vec<MemorySearchResult>> {
         let memories = self
             .store
-            .get_sorted(sort, config.max_results as i64, config.memory_type)
+            .get_sorted_scoped(
+                sort,
+                config.max_results as i64,
+                config.memory_type,
+                &config.scope_filter,
+            )
             .await?;
 
         let total = memories.len();
@@ -151,6 +194,7 @@ impl MemorySearch {
                     memory,
                     score,
                     rank: rank + 1,
+                    links: Vec::new(),
                 }
             })
             .collect();
@@ -180,7 +224,7 @@ impl MemorySearch {
             Ok(fts_matches) => {
                 for (memory_id, score) in fts_matches {
                     if let Some(memory) = self.store.load(&memory_id).await?
-                        && !memory.forgotten
+                        && (config.include_forgotten || (!memory.forgotten && !memory.is_expired()))
                     {
                         fts_results.push(ScoredMemory {
                             memory,
@@ -205,7 +249,7 @@ impl MemorySearch {
                 for (memory_id, distance) in vector_matches {
                     let similarity = 1.0 - distance;
                     if let Some(memory) = self.store.load(&memory_id).await?
-                        && !memory.forgotten
+                        && (config.include_forgotten || (!memory.forgotten && !memory.is_expired()))
                     {
                         vector_results.push(ScoredMemory {
                             memory,
@@ -236,28 +280,49 @@ impl MemorySearch {
                 });
 
                 // Traverse graph to find related memories
-                self.traverse_graph(&seed.id, config.max_graph_depth, &mut graph_results)
-                    .await?;
+                self.traverse_graph(
+                    &seed.id,
+                    config.graph_depth,
+                    &config.edge_weights,
+                    &mut graph_results,
+                )
+                .await?;
             }
         }
+        // RRF goes by rank, so order the graph list by its weighted scores.
+        graph_results.sort_by(|left, right| right.score.total_cmp(&left.score));
 
         // 4. Merge results using Reciprocal Rank Fusion (RRF)
         let fused_results =
             reciprocal_rank_fusion(&vector_results, &fts_results, &graph_results, config.rrf_k);
 
-        // Convert to MemorySearchResult with ranks, applying optional type filter
-        let results: Vec<MemorySearchResult> = fused_results
+        // Apply optional type filter, and rank the reader's own scoped
+        // memories above global ones
+        let mut fused_results: Vec<ScoredMemory> = fused_results
             .into_iter()
             .filter(|scored| {
                 config
                     .memory_type
                     .is_none_or(|t| scored.memory.memory_type == t)
+                    && config.scope_filter.allows(scored.memory.scope.as_deref())
             })
+            .collect();
+        for scored in &mut fused_results {
+            if config.scope_filter.prefers(scored.memory.scope.as_deref()) {
+                scored.score *= IN_SCOPE_BOOST;
+            }
+        }
+        fused_results.sort_by(|left, right| right.score.total_cmp(&left.score));
+
+        // Convert to MemorySearchResult with ranks
+        let results: Vec<MemorySearchResult> = fused_results
+            .into_iter()
             .enumerate()
             .map(|(rank, scored)| MemorySearchResult {
                 memory: scored.memory,
                 score: scored.score as f32,
                 rank: rank + 1,
+                links: Vec::new(),
             })
             .filter(|r| r.score >= config.min_score)
             .take(config.max_results_per_source)
@@ -266,11 +331,51 @@ impl MemorySearch {
         Ok(results)
     }
 
+    /// Fill in each result's `links` with up to `max_per_result` of its
+    /// graph neighbors, strongest edges first. Forgotten neighbors are
+    /// skipped.
+    pub async fn attach_links(
+        &self,
+        results: &mut [MemorySearchResult],
+        max_per_result: usize,
+    ) -> Result<()> {
+        for result in results.iter_mut() {
+            let mut associations = self.store.get_associations(&result.memory.id).await?;
+            associations.sort_by(|left, right| right.weight.total_cmp(&left.weight));
+
+            let mut links = Vec::new();
+            for association in associations {
+                if links.len() >= max_per_result {
+                    break;
+                }
+                let neighbor_id = if association.source_id == result.memory.id {
+                    &association.target_id
+                } else {
+                    &association.source_id
+                };
+                if let Some(memory) = self.store.load(neighbor_id).await?
+                    && !memory.forgotten
+                    && !memory.is_expired()
+                {
+                    links.push(LinkedMemory {
+                        association,
+                        memory,
+                    });
+                }
+            }
+            result.links = links;
+        }
+        Ok(())
+    }
+
     /// Traverse the memory graph to find related memories (iterative to avoid async recursion).
+    /// Memories up to `graph_depth` edges from the start are scored by
+    /// importance, edge weight, and the edge type's weight in `edge_weights`.
     async fn traverse_graph(
         &self,
         start_id: &str,
-        max_depth: usize,
+        graph_depth: usize,
+        edge_weights: &EdgeWeights,
         results: &mut Vec<ScoredMemory>,
     ) -> Result<()> {
         use std::collections::VecDeque;
@@ -283,7 +388,7 @@ impl MemorySearch {
         visited.insert(start_id.to_string());
 
         while let Some((current_id, depth)) = queue.pop_front() {
-            if depth > max_depth {
+            if depth >= graph_depth {
                 continue;
             }
 
@@ -297,24 +402,17 @@ impl MemorySearch {
                     &assoc.source_id
                 };
 
-                if visited.contains(related_id) {
+                let type_multiplier = edge_weights.weight(assoc.relation_type);
+                if type_multiplier <= 0.0 || visited.contains(related_id) {
                     continue;
                 }
                 visited.insert(related_id.clone());
 
                 if let Some(memory) = self.store.load(related_id).await? {
-                    if memory.forgotten {
+                    if memory.forgotten || memory.is_expired() {
                         continue;
                     }
                     // Score based on relation type and weight
-                    let type_multiplier = match assoc.relation_type {
-                        RelationType::Updates => 1.5,
-                        RelationType::CausedBy | RelationType::ResultOf => 1.3,
-                        RelationType::RelatedTo => 1.0,
-                        RelationType::Contradicts => 0.5,
-                        RelationType::PartOf => 0.8,
-                    };
-
                     let score = memory.importance as f64 * assoc.weight as f64 * type_multiplier;
 
                     results.push(ScoredMemory {
@@ -354,8 +452,18 @@ pub struct SearchConfig {
     pub rrf_k: f64,
     /// Minimum score threshold for results. Only used in hybrid mode.
     pub min_score: f32,
-    /// Maximum graph traversal depth. Only used in hybrid mode.
-    pub max_graph_depth: usize,
+    /// How many edges from a seed memory graph traversal reaches: 1 pulls
+    /// in direct neighbors only, 2 also second-degree memories, 0 turns
+    /// traversal off. Only used in hybrid mode.
+    pub graph_depth: usize,
+    /// How much each edge type counts when scoring graph results. Only used
+    /// in hybrid mode.
+    pub edge_weights: EdgeWeights,
+    /// Also match forgotten (trashed) memories in full-text and vector
+    /// results. Only used in hybrid mode.
+    pub include_forgotten: bool,
+    /// Which scoped memories may be returned.
+    pub scope_filter: ScopeFilter,
 }
 
 impl Default for SearchConfig {
@@ -370,7 +478,75 @@ impl Default for SearchConfig {
             // RRF scores are 1/(k+rank), so with k=60 the max single-source
             // score is ~0.016. Set threshold low enough to not discard everything.
             min_score: 0.0,
-            max_graph_depth: 2,
+            graph_depth: 2,
+            edge_weights: EdgeWeights::default(),
+            include_forgotten: false,
+            scope_filter: ScopeFilter::All,
+        }
+    }
+}
+
+/// Score multiplier for memories in the reader's own scope, so they outrank
+/// global memories of similar relevance.
+const IN_SCOPE_BOOST: f64 = 1.5;
+
+/// Graph depth for recall during a conversation: direct neighbors only, so
+/// replies aren't held up walking the graph.
+pub const CONVERSATION_GRAPH_DEPTH: usize = 1;
+
+/// Graph depth for cortex reflection, which can afford to pull in
+/// second-degree memories.
+pub const REFLECTION_GRAPH_DEPTH: usize = 2;
+
+/// Multiplier applied to a graph result's score per edge type. A weight of
+/// zero leaves that edge type out of traversal.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EdgeWeights {
+    pub related_to: f64,
+    pub updates: f64,
+    pub contradicts: f64,
+    pub caused_by: f64,
+    pub result_of: f64,
+    pub part_of: f64,
+    pub supports: f64,
+    pub about_person: f64,
+}
+
+impl EdgeWeights {
+    /// Weights for cortex reflection: contradictions rank as high as
+    /// updates, since resolving them is part of the job.
+    pub fn reflection() -> Self {
+        Self {
+            contradicts: 1.5,
+            ..Self::default()
+        }
+    }
+
+    pub fn weight(&self, relation_type: RelationType) -> f64 {
+        match relation_type {
+            RelationType::RelatedTo => self.related_to,
+            RelationType::Updates => self.updates,
+            RelationType::Contradicts => self.contradicts,
+            RelationType::CausedBy => self.caused_by,
+            RelationType::ResultOf => self.result_of,
+            RelationType::PartOf => self.part_of,
+            RelationType::Supports => self.supports,
+            RelationType::AboutPerson => self.about_person,
+        }
+    }
+}
+
+impl Default for EdgeWeights {
+    fn default() -> Self {
+        Self {
+            related_to: 1.0,
+            updates: 1.5,
+            contradicts: 0.5,
+            caused_by: 1.3,
+            result_of: 1.3,
+            part_of: 0.8,
+            supports: 1.2,
+            about_person: 1.0,
         }
     }
 }
@@ -488,6 +664,185 @@ mod tests {
         assert!(fused[0].score > fused[1].score);
     }
 
+    struct NoopProvider;
+
+    #[async_trait::async_trait]
+    impl crate::memory::embedding::EmbeddingProvider for NoopProvider {
+        fn name(&self) -> &'static str {
+            "test"
+        }
+
+        fn model(&self) -> &str {
+            "noop"
+        }
+
+        fn dimensions(&self) -> usize {
+            2
+        }
+
+        async fn embed(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>> {
+            Ok(texts.iter().map(|_| vec![0.0, 0.0]).collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn traverse_graph_stops_at_depth_and_skips_zero_weight_edges() {
+        use crate::memory::types::Association;
+
+        let lance_dir = tempfile::tempdir().unwrap();
+        let connection = lancedb::connect(lance_dir.path().to_str().unwrap())
+            .execute()
+            .await
+            .unwrap();
+        let search = MemorySearch::new(
+            MemoryStore::connect_in_memory().await,
+            EmbeddingTable::open_or_create(&connection, 2)
+                .await
+                .unwrap(),
+            Arc::new(EmbeddingModel::from_provider(Arc::new(NoopProvider))),
+        );
+
+        // a -related_to-> b -related_to-> c, and a -contradicts-> d
+        let mut ids = Vec::new();
+        for content in ["a", "b", "c", "d"] {
+            let memory = Memory::new(content, MemoryType::Fact);
+            search.store().save(&memory).await.unwrap();
+            ids.push(memory.id);
+        }
+        for (source, target, relation_type) in [
+            (0, 1, RelationType::RelatedTo),
+            (1, 2, RelationType::RelatedTo),
+            (0, 3, RelationType::Contradicts),
+        ] {
+            search
+                .store()
+                .create_association(&Association::new(&ids[source], &ids[target], relation_type))
+                .await
+                .unwrap();
+        }
+
+        let reached = async |graph_depth: usize, edge_weights: EdgeWeights| {
+            let mut results = Vec::new();
+            search
+                .traverse_graph(&ids[0], graph_depth, &edge_weights, &mut results)
+                .await
+                .unwrap();
+            let mut contents: Vec<String> = results
+                .into_iter()
+                .map(|scored| scored.memory.content)
+                .collect();
+            contents.sort();
+            contents
+        };
+
+        assert!(reached(0, EdgeWeights::default()).await.is_empty());
+        assert_eq!(reached(1, EdgeWeights::default()).await, ["b", "d"]);
+        assert_eq!(reached(2, EdgeWeights::default()).await, ["b", "c", "d"]);
+        let no_contradictions = EdgeWeights {
+            contradicts: 0.0,
+            ..EdgeWeights::default()
+        };
+        assert_eq!(reached(2, no_contradictions).await, ["b", "c"]);
+    }
+
+    /// Embeds text as `[length, 1.0]`, so an edit shows up in the vector.
+    struct LengthProvider;
+
+    #[async_trait::async_trait]
+    impl crate::memory::embedding::EmbeddingProvider for LengthProvider {
+        fn name(&self) -> &'static str {
+            "test"
+        }
+
+        fn model(&self) -> &str {
+            "length"
+        }
+
+        fn dimensions(&self) -> usize {
+            2
+        }
+
+        async fn embed(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>> {
+            Ok(texts
+                .iter()
+                .map(|text| vec![text.len() as f32, 1.0])
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn update_replaces_embedding_and_delete_cleans_up() {
+        use crate::memory::types::Association;
+
+        let lance_dir = tempfile::tempdir().unwrap();
+        let connection = lancedb::connect(lance_dir.path().to_str().unwrap())
+            .execute()
+            .await
+            .unwrap();
+        let search = MemorySearch::new(
+            MemoryStore::connect_in_memory().await,
+            EmbeddingTable::open_or_create(&connection, 2)
+                .await
+                .unwrap(),
+            Arc::new(EmbeddingModel::from_provider(Arc::new(LengthProvider))),
+        );
+
+        let mut memory = Memory::new("short", MemoryType::Fact);
+        let other = Memory::new("other", MemoryType::Fact);
+        for memory in [&memory, &other] {
+            search.store().save(memory).await.unwrap();
+            let embedding = search
+                .embedding_model_arc()
+                .embed_one(&memory.content)
+                .await
+                .unwrap();
+            search
+                .embedding_table()
+                .store(&memory.id, &memory.content, &embedding)
+                .await
+                .unwrap();
+        }
+        search
+            .store()
+            .create_association(&Association::new(
+                &memory.id,
+                &other.id,
+                RelationType::RelatedTo,
+            ))
+            .await
+            .unwrap();
+
+        memory.content = "a much longer edit".into();
+        search.update_memory(&memory, true).await.unwrap();
+        let embeddings = search
+            .embedding_table()
+            .embeddings_for(std::slice::from_ref(&memory.id))
+            .await
+            .unwrap();
+        assert_eq!(embeddings[&memory.id], vec![18.0, 1.0]);
+        assert_eq!(search.embedding_table().row_count().await.unwrap(), 2);
+
+        assert!(search.delete_memory(&memory.id).await.unwrap());
+        assert!(search.store().load(&memory.id).await.unwrap().is_none());
+        assert!(
+            search
+                .embedding_table()
+                .embeddings_for(std::slice::from_ref(&memory.id))
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(search.embedding_table().row_count().await.unwrap(), 1);
+        assert!(
+            search
+                .store()
+                .get_associations(&other.id)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
     #[test]
     fn test_rrf_empty_lists() {
         let fused = reciprocal_rank_fusion(&[], &[], &[], 60.0);
@@ -501,6 +856,7 @@ mod tests {
                 memory: Memory::new(format!("mem {i}"), MemoryType::Fact),
                 score: 1.0 - (i as f32 * 0.1),
                 rank: i + 1,
+                links: Vec::new(),
             })
             .collect();
 
@@ -579,7 +935,10 @@ mod tests {
             .execute()
             .await
             .unwrap();
-        let embedding_table = EmbeddingTable::open_or_create(&lance_conn).await.unwrap();
+        let embedding_table =
+            EmbeddingTable::open_or_create(&lance_conn, crate::memory::embedding::LOCAL_DIMENSIONS)
+                .await
+                .unwrap();
         let embedding_model = Arc::new(EmbeddingModel::new(lance_dir.path()).unwrap());
         let search = MemorySearch::new(store, embedding_table, embedding_model);
 
@@ -607,7 +966,10 @@ mod tests {
             .execute()
             .await
             .unwrap();
-        let embedding_table = EmbeddingTable::open_or_create(&lance_conn).await.unwrap();
+        let embedding_table =
+            EmbeddingTable::open_or_create(&lance_conn, crate::memory::embedding::LOCAL_DIMENSIONS)
+                .await
+                .unwrap();
         let embedding_model = Arc::new(EmbeddingModel::new(lance_dir.path()).unwrap());
         let search = MemorySearch::new(store, embedding_table, embedding_model);
 
@@ -632,7 +994,10 @@ mod tests {
             .execute()
             .await
             .unwrap();
-        let embedding_table = EmbeddingTable::open_or_create(&lance_conn).await.unwrap();
+        let embedding_table =
+            EmbeddingTable::open_or_create(&lance_conn, crate::memory::embedding::LOCAL_DIMENSIONS)
+                .await
+                .unwrap();
         let embedding_model = Arc::new(EmbeddingModel::new(lance_dir.path()).unwrap());
         let search = MemorySearch::new(store, embedding_table, embedding_model);
 
@@ -657,7 +1022,10 @@ mod tests {
             .execute()
             .await
             .unwrap();
-        let embedding_table = EmbeddingTable::open_or_create(&lance_conn).await.unwrap();
+        let embedding_table =
+            EmbeddingTable::open_or_create(&lance_conn, crate::memory::embedding::LOCAL_DIMENSIONS)
+                .await
+                .unwrap();
         let embedding_model = Arc::new(EmbeddingModel::new(lance_dir.path()).unwrap());
         let search = MemorySearch::new(store, embedding_table, embedding_model);
 
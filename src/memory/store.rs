@@ -1,6 +1,7 @@
 //! Memory graph storage (SQLite).
 
 use crate::error::Result;
+use crate::memory::scope::ScopeFilter;
 use crate::memory::search::SearchSort;
 use crate::memory::types::{Association, Memory, MemoryType, RelationType};
 
@@ -9,6 +10,9 @@ use sqlx::{Row, SqlitePool};
 
 use std::sync::Arc;
 
+/// SQL condition matching memories whose expiry time hasn't passed.
+const NOT_EXPIRED: &str = "(expires_at IS NULL OR datetime(expires_at) > datetime('now'))";
+
 /// Memory store for CRUD and graph operations.
 pub struct MemoryStore {
     pool: SqlitePool,
@@ -66,11 +70,16 @@ impl MemoryStore {
                 .start_timer()
         };
 
+        #[cfg(feature = "chaos")]
+        crate::chaos::sqlite_fault()
+            .with_context(|| format!("failed to save memory {}", memory.id))?;
+
         sqlx::query(
             r#"
             INSERT INTO memories (id, content, memory_type, importance, created_at, updated_at,
-                                 last_accessed_at, access_count, source, channel_id, forgotten)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                                 last_accessed_at, access_count, source, channel_id, forgotten, scope, tags,
+                                 expires_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(&memory.id)
@@ -84,6 +93,9 @@ impl MemoryStore {
         .bind(&memory.source)
         .bind(memory.channel_id.as_ref().map(|id| id.as_ref()))
         .bind(memory.forgotten)
+        .bind(&memory.scope)
+        .bind(tags_to_json(&memory.tags))
+        .bind(memory.expires_at)
         .execute(&self.pool)
         .await
         .with_context(|| format!("failed to save memory {}", memory.id))?;
@@ -109,12 +121,74 @@ impl MemoryStore {
         Ok(())
     }
 
+    /// Save several new memories in one transaction: either all are written
+    /// or none are.
+    pub async fn save_batch(&self, memories: &[Memory]) -> Result<()> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("failed to open memory batch transaction")?;
+        for memory in memories {
+            sqlx::query(
+                r#"
+                INSERT INTO memories (id, content, memory_type, importance, created_at, updated_at,
+                                     last_accessed_at, access_count, source, channel_id, forgotten, scope, tags,
+                                     expires_at)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(&memory.id)
+            .bind(&memory.content)
+            .bind(memory.memory_type.to_string())
+            .bind(memory.importance)
+            .bind(memory.created_at)
+            .bind(memory.updated_at)
+            .bind(memory.last_accessed_at)
+            .bind(memory.access_count)
+            .bind(&memory.source)
+            .bind(memory.channel_id.as_ref().map(|id| id.as_ref()))
+            .bind(memory.forgotten)
+            .bind(&memory.scope)
+            .bind(tags_to_json(&memory.tags))
+            .bind(memory.expires_at)
+            .execute(&mut *tx)
+            .await
+            .with_context(|| format!("failed to save memory {}", memory.id))?;
+        }
+        tx.commit().await.context("failed to commit memory batch")?;
+
+        #[cfg(feature = "metrics")]
+        {
+            let agent_label = if self.agent_id.is_empty() {
+                "unknown"
+            } else {
+                &self.agent_id
+            };
+            let metrics = crate::telemetry::Metrics::global();
+            metrics
+                .memory_entry_count
+                .with_label_values(&[agent_label])
+                .add(memories.len() as i64);
+            metrics
+                .memory_updates_total
+                .with_label_values(&[agent_label, "save"])
+                .inc_by(memories.len() as u64);
+        }
+
+        Ok(())
+    }
+
     /// Load a memory by ID.
     pub async fn load(&self, id: &str) -> Result<Option<Memory>> {
+        #[cfg(feature = "chaos")]
+        crate::chaos::sqlite_fault().with_context(|| format!("failed to load memory {}", id))?;
+
         let row = sqlx::query(
             r#"
             SELECT id, content, memory_type, importance, created_at, updated_at,
-                   last_accessed_at, access_count, source, channel_id, forgotten
+                   last_accessed_at, access_count, source, channel_id, forgotten, forgotten_at, scope, tags,
+                   expires_at
             FROM memories
             WHERE id = ?
             "#,
@@ -148,7 +222,7 @@ impl MemoryStore {
             UPDATE memories
             SET content = ?, memory_type = ?, importance = ?, updated_at = ?,
                 last_accessed_at = ?, access_count = ?, source = ?, channel_id = ?,
-                forgotten = ?
+                forgotten = ?, tags = ?, expires_at = ?
             WHERE id = ?
             "#,
         )
@@ -161,6 +235,8 @@ impl MemoryStore {
         .bind(&memory.source)
         .bind(memory.channel_id.as_ref().map(|id| id.as_ref()))
         .bind(memory.forgotten)
+        .bind(tags_to_json(&memory.tags))
+        .bind(memory.expires_at)
         .bind(&memory.id)
         .execute(&self.pool)
         .await
@@ -244,13 +320,17 @@ impl MemoryStore {
         Ok(())
     }
 
-    /// Mark a memory as forgotten. The memory stays in the database but is
-    /// excluded from search results and recall.
+    /// Mark a memory as forgotten. The memory moves to the trash: it stays in
+    /// the database but is excluded from search results and recall until it
+    /// is restored or purged.
     pub async fn forget(&self, id: &str) -> Result<bool> {
+        let now = chrono::Utc::now();
         let result = sqlx::query(
-            "UPDATE memories SET forgotten = 1, updated_at = ? WHERE id = ? AND forgotten = 0",
+            "UPDATE memories SET forgotten = 1, forgotten_at = ?, updated_at = ? \
+             WHERE id = ? AND forgotten = 0",
         )
-        .bind(chrono::Utc::now())
+        .bind(now)
+        .bind(now)
         .bind(id)
         .execute(&self.pool)
         .await
@@ -259,6 +339,93 @@ impl MemoryStore {
         Ok(result.rows_affected() > 0)
     }
 
+    /// Take a memory back out of the trash. Returns false if it doesn't exist
+    /// or isn't forgotten. An expiry that has already passed is cleared, so
+    /// the memory isn't expired again by the next maintenance pass.
+    pub async fn restore(&self, id: &str) -> Result<bool> {
+        let result = sqlx::query(&format!(
+            "UPDATE memories SET forgotten = 0, forgotten_at = NULL, updated_at = ?, \
+             expires_at = CASE WHEN {NOT_EXPIRED} THEN expires_at ELSE NULL END \
+             WHERE id = ? AND forgotten = 1"
+        ))
+        .bind(chrono::Utc::now())
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .with_context(|| format!("failed to restore memory {}", id))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// List forgotten memories, most recently trashed first.
+    pub async fn list_forgotten(&self, limit: i64, offset: i64) -> Result<Vec<Memory>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, content, memory_type, importance, created_at, updated_at,
+                   last_accessed_at, access_count, source, channel_id, forgotten, forgotten_at, scope, tags,
+                   expires_at
+            FROM memories
+            WHERE forgotten = 1
+            ORDER BY COALESCE(forgotten_at, updated_at) DESC, id ASC
+            LIMIT ? OFFSET ?
+            "#,
+        )
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .with_context(|| "failed to list forgotten memories")?;
+
+        Ok(rows.into_iter().map(|row| row_to_memory(&row)).collect())
+    }
+
+    /// Count forgotten memories.
+    pub async fn count_forgotten(&self) -> Result<i64> {
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM memories WHERE forgotten = 1")
+            .fetch_one(&self.pool)
+            .await
+            .with_context(|| "failed to count forgotten memories")?;
+        Ok(count)
+    }
+
+    /// IDs of memories that have been in the trash since before `cutoff`.
+    /// Rows forgotten before trash timestamps existed fall back to
+    /// `updated_at`.
+    pub async fn forgotten_before(
+        &self,
+        cutoff: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> Result<Vec<String>> {
+        let ids = sqlx::query_scalar(
+            "SELECT id FROM memories \
+             WHERE forgotten = 1 AND COALESCE(forgotten_at, updated_at) < ? \
+             ORDER BY COALESCE(forgotten_at, updated_at) ASC \
+             LIMIT ?",
+        )
+        .bind(cutoff)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .with_context(|| "failed to find expired forgotten memories")?;
+        Ok(ids)
+    }
+
+    /// IDs of live memories whose expiry time has passed, longest-expired
+    /// first.
+    pub async fn expired(&self, limit: i64) -> Result<Vec<String>> {
+        let ids = sqlx::query_scalar(&format!(
+            "SELECT id FROM memories \
+             WHERE forgotten = 0 AND expires_at IS NOT NULL AND NOT {NOT_EXPIRED} \
+             ORDER BY datetime(expires_at) ASC \
+             LIMIT ?"
+        ))
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .with_context(|| "failed to find expired memories")?;
+        Ok(ids)
+    }
+
     /// Merge one memory into a survivor with atomic SQLite updates.
     ///
     /// This updates survivor content/metadata, rewires associations, records an
@@ -367,10 +534,13 @@ impl MemoryStore {
             )
         })?;
 
+        let forgotten_at = chrono::Utc::now();
         sqlx::query(
-            "UPDATE memories SET forgotten = 1, updated_at = ? WHERE id = ? AND forgotten = 0",
+            "UPDATE memories SET forgotten = 1, forgotten_at = ?, updated_at = ? \
+             WHERE id = ? AND forgotten = 0",
         )
-        .bind(chrono::Utc::now())
+        .bind(forgotten_at)
+        .bind(forgotten_at)
         .bind(&merged_memory.id)
         .execute(&mut *transaction)
         .await
@@ -384,6 +554,166 @@ impl MemoryStore {
         Ok(())
     }
 
+    /// Number of memories, forgotten ones included, and associations.
+    pub async fn export_counts(&self) -> Result<(usize, usize)> {
+        let (memories, associations): (i64, i64) = sqlx::query_as(
+            "SELECT (SELECT COUNT(*) FROM memories), (SELECT COUNT(*) FROM associations)",
+        )
+        .fetch_one(&self.pool)
+        .await
+        .context("failed to count memories for export")?;
+        Ok((memories as usize, associations as usize))
+    }
+
+    /// Up to `limit` memories, forgotten ones included, with IDs after
+    /// `after`, ordered by ID. A full export pages through the table with
+    /// this instead of loading it whole.
+    pub async fn export_memories_page(
+        &self,
+        after: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<Memory>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, content, memory_type, importance, created_at, updated_at,
+                   last_accessed_at, access_count, source, channel_id, forgotten, forgotten_at, scope, tags,
+                   expires_at
+            FROM memories
+            WHERE ?1 IS NULL OR id > ?1
+            ORDER BY id ASC
+            LIMIT ?2
+            "#,
+        )
+        .bind(after)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .context("failed to export memories")?;
+
+        Ok(rows.iter().map(row_to_memory).collect())
+    }
+
+    /// Up to `limit` associations with IDs after `after`, ordered by ID.
+    pub async fn export_associations_page(
+        &self,
+        after: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<Association>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, source_id, target_id, relation_type, weight, created_at
+            FROM associations
+            WHERE ?1 IS NULL OR id > ?1
+            ORDER BY id ASC
+            LIMIT ?2
+            "#,
+        )
+        .bind(after)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .context("failed to export associations")?;
+
+        Ok(rows.iter().map(row_to_association).collect())
+    }
+
+    /// Import exported memories and associations in one transaction, keeping
+    /// their IDs and timestamps. Memories whose ID already exists are left
+    /// alone, as are associations that already exist or whose endpoints
+    /// aren't in the store after the import.
+    pub async fn import(
+        &self,
+        memories: &[Memory],
+        associations: &[Association],
+    ) -> Result<StoreImport> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("failed to open memory import transaction")?;
+
+        let mut outcome = StoreImport::default();
+        for memory in memories {
+            let result = sqlx::query(
+                r#"
+                INSERT INTO memories (id, content, memory_type, importance, created_at, updated_at,
+                                     last_accessed_at, access_count, source, channel_id, forgotten,
+                                     forgotten_at, scope, tags, expires_at)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                ON CONFLICT(id) DO NOTHING
+                "#,
+            )
+            .bind(&memory.id)
+            .bind(&memory.content)
+            .bind(memory.memory_type.to_string())
+            .bind(memory.importance)
+            .bind(memory.created_at)
+            .bind(memory.updated_at)
+            .bind(memory.last_accessed_at)
+            .bind(memory.access_count)
+            .bind(&memory.source)
+            .bind(memory.channel_id.as_ref().map(|id| id.as_ref()))
+            .bind(memory.forgotten)
+            .bind(memory.forgotten_at)
+            .bind(&memory.scope)
+            .bind(tags_to_json(&memory.tags))
+            .bind(memory.expires_at)
+            .execute(&mut *tx)
+            .await
+            .with_context(|| format!("failed to import memory {}", memory.id))?;
+            if result.rows_affected() > 0 {
+                outcome.inserted.push(memory.id.clone());
+            } else {
+                outcome.skipped += 1;
+            }
+        }
+
+        for association in associations {
+            let result = sqlx::query(
+                r#"
+                INSERT INTO associations (id, source_id, target_id, relation_type, weight, created_at)
+                SELECT ?, ?, ?, ?, ?, ?
+                WHERE EXISTS (SELECT 1 FROM memories WHERE id = ?)
+                  AND EXISTS (SELECT 1 FROM memories WHERE id = ?)
+                ON CONFLICT DO NOTHING
+                "#,
+            )
+            .bind(&association.id)
+            .bind(&association.source_id)
+            .bind(&association.target_id)
+            .bind(association.relation_type.to_string())
+            .bind(association.weight)
+            .bind(association.created_at)
+            .bind(&association.source_id)
+            .bind(&association.target_id)
+            .execute(&mut *tx)
+            .await
+            .with_context(|| format!("failed to import association {}", association.id))?;
+            if result.rows_affected() > 0 {
+                outcome.associations += 1;
+            }
+        }
+
+        tx.commit()
+            .await
+            .context("failed to commit memory import")?;
+
+        #[cfg(feature = "metrics")]
+        {
+            let agent_label = if self.agent_id.is_empty() {
+                "unknown"
+            } else {
+                &self.agent_id
+            };
+            crate::telemetry::Metrics::global()
+                .memory_entry_count
+                .with_label_values(&[agent_label])
+                .add(outcome.inserted.len() as i64);
+        }
+
+        Ok(outcome)
+    }
+
     /// Create an association between two memories.
     pub async fn create_association(&self, association: &Association) -> Result<()> {
         sqlx::query(
@@ -551,16 +881,16 @@ impl MemoryStore {
     pub async fn get_by_type(&self, memory_type: MemoryType, limit: i64) -> Result<Vec<Memory>> {
         let type_str = memory_type.to_string();
 
-        let rows = sqlx::query(
+        let rows = sqlx::query(&format!(
             r#"
             SELECT id, content, memory_type, importance, created_at, updated_at,
-                   last_accessed_at, access_count, source, channel_id, forgotten
+                   last_accessed_at, access_count, source, channel_id, forgotten, scope, tags, expires_at
             FROM memories
-            WHERE memory_type = ? AND forgotten = 0
+            WHERE memory_type = ? AND forgotten = 0 AND {NOT_EXPIRED}
             ORDER BY importance DESC, updated_at DESC
             LIMIT ?
-            "#,
-        )
+            "#
+        ))
         .bind(&type_str)
         .bind(limit)
         .fetch_all(&self.pool)
@@ -572,16 +902,16 @@ impl MemoryStore {
 
     /// Get high-importance memories for injection into context.
     pub async fn get_high_importance(&self, threshold: f32, limit: i64) -> Result<Vec<Memory>> {
-        let rows = sqlx::query(
+        let rows = sqlx::query(&format!(
             r#"
             SELECT id, content, memory_type, importance, created_at, updated_at,
-                   last_accessed_at, access_count, source, channel_id, forgotten
+                   last_accessed_at, access_count, source, channel_id, forgotten, scope, tags, expires_at
             FROM memories
-            WHERE importance >= ? AND forgotten = 0
+            WHERE importance >= ? AND forgotten = 0 AND {NOT_EXPIRED}
             ORDER BY importance DESC, updated_at DESC
             LIMIT ?
-            "#,
-        )
+            "#
+        ))
         .bind(threshold)
         .bind(limit)
         .fetch_all(&self.pool)
@@ -600,46 +930,82 @@ impl MemoryStore {
         sort: SearchSort,
         limit: i64,
         memory_type: Option<MemoryType>,
+    ) -> Result<Vec<Memory>> {
+        self.get_sorted_scoped(sort, limit, memory_type, &ScopeFilter::All)
+            .await
+    }
+
+    /// [`get_sorted`](Self::get_sorted), restricted to memories visible
+    /// under a scope filter.
+    pub async fn get_sorted_scoped(
+        &self,
+        sort: SearchSort,
+        limit: i64,
+        memory_type: Option<MemoryType>,
+        scope_filter: &ScopeFilter,
     ) -> Result<Vec<Memory>> {
         let order_clause = match sort {
             SearchSort::Recent => "ORDER BY created_at DESC",
             SearchSort::Importance => "ORDER BY importance DESC, created_at DESC",
             SearchSort::MostAccessed => "ORDER BY access_count DESC, created_at DESC",
         };
-
-        let (query_str, type_filter) = if let Some(ref memory_type) = memory_type {
-            (
-                format!(
-                    "SELECT id, content, memory_type, importance, created_at, updated_at, \
-                     last_accessed_at, access_count, source, channel_id, forgotten \
-                     FROM memories WHERE memory_type = ? AND forgotten = 0 {order_clause} LIMIT ?"
-                ),
-                Some(memory_type.to_string()),
-            )
+        let type_clause = if memory_type.is_some() {
+            " AND memory_type = ?"
         } else {
-            (
-                format!(
-                    "SELECT id, content, memory_type, importance, created_at, updated_at, \
-                     last_accessed_at, access_count, source, channel_id, forgotten \
-                     FROM memories WHERE forgotten = 0 {order_clause} LIMIT ?"
-                ),
-                None,
-            )
+            ""
         };
+        let (scope_clause, scopes) = scope_filter.sql_condition();
 
-        let rows = if let Some(type_str) = type_filter {
-            sqlx::query(&query_str)
-                .bind(type_str)
-                .bind(limit)
-                .fetch_all(&self.pool)
-                .await
-        } else {
-            sqlx::query(&query_str)
-                .bind(limit)
-                .fetch_all(&self.pool)
-                .await
+        let query_str = format!(
+            "SELECT id, content, memory_type, importance, created_at, updated_at, \
+             last_accessed_at, access_count, source, channel_id, forgotten, scope, tags, expires_at \
+             FROM memories WHERE forgotten = 0 AND {NOT_EXPIRED}{type_clause}{scope_clause} \
+             {order_clause} LIMIT ?"
+        );
+
+        let mut query = sqlx::query(&query_str);
+        if let Some(memory_type) = memory_type {
+            query = query.bind(memory_type.to_string());
+        }
+        for scope in scopes {
+            query = query.bind(scope);
+        }
+        let rows = query
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await
+            .with_context(|| format!("failed to get sorted memories ({sort:?})"))?;
+
+        Ok(rows.into_iter().map(|row| row_to_memory(&row)).collect())
+    }
+
+    /// Memories whose content or tags mention `term` (case-insensitive),
+    /// most important first.
+    pub async fn find_mentioning(
+        &self,
+        term: &str,
+        limit: i64,
+        scope_filter: &ScopeFilter,
+    ) -> Result<Vec<Memory>> {
+        let (scope_clause, scopes) = scope_filter.sql_condition();
+        let query_str = format!(
+            "SELECT id, content, memory_type, importance, created_at, updated_at, \
+             last_accessed_at, access_count, source, channel_id, forgotten, scope, tags, expires_at \
+             FROM memories WHERE forgotten = 0 AND {NOT_EXPIRED} \
+             AND (content LIKE ? ESCAPE '\\' OR tags LIKE ? ESCAPE '\\'){scope_clause} \
+             ORDER BY importance DESC, updated_at DESC LIMIT ?"
+        );
+
+        let pattern = like_pattern(term);
+        let mut query = sqlx::query(&query_str).bind(&pattern).bind(&pattern);
+        for scope in scopes {
+            query = query.bind(scope);
         }
-        .with_context(|| format!("failed to get sorted memories ({sort:?})"))?;
+        let rows = query
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await
+            .with_context(|| format!("failed to find memories mentioning '{term}'"))?;
 
         Ok(rows.into_iter().map(|row| row_to_memory(&row)).collect())
     }
@@ -671,6 +1037,35 @@ impl MemoryStore {
     }
 }
 
+/// What [`MemoryStore::import`] wrote.
+#[derive(Debug, Default)]
+pub struct StoreImport {
+    /// IDs of the memories that were inserted.
+    pub inserted: Vec<String>,
+    /// Memories skipped because their ID already existed.
+    pub skipped: usize,
+    pub associations: usize,
+}
+
+/// A `LIKE` pattern matching `term` anywhere, with wildcards in the term
+/// escaped so they match literally.
+pub(crate) fn like_pattern(term: &str) -> String {
+    let escaped = term
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("%{escaped}%")
+}
+
+/// Tags are stored as a JSON array; untagged memories store NULL.
+fn tags_to_json(tags: &[String]) -> Option<String> {
+    if tags.is_empty() {
+        None
+    } else {
+        serde_json::to_string(tags).ok()
+    }
+}
+
 /// Helper: Convert a database row to a Memory.
 fn row_to_memory(row: &sqlx::sqlite::SqliteRow) -> Memory {
     let mem_type_str: String = row.try_get("memory_type").unwrap_or_default();
@@ -696,6 +1091,15 @@ fn row_to_memory(row: &sqlx::sqlite::SqliteRow) -> Memory {
         source: row.try_get("source").ok(),
         channel_id: channel_id.map(|id| Arc::from(id) as crate::ChannelId),
         forgotten: row.try_get::<bool, _>("forgotten").unwrap_or(false),
+        forgotten_at: row.try_get("forgotten_at").ok().flatten(),
+        scope: row.try_get("scope").ok().flatten(),
+        tags: row
+            .try_get::<Option<String>, _>("tags")
+            .ok()
+            .flatten()
+            .and_then(|tags| serde_json::from_str(&tags).ok())
+            .unwrap_or_default(),
+        expires_at: row.try_get("expires_at").ok().flatten(),
     }
 }
 
@@ -740,6 +1144,8 @@ fn parse_relation_type(s: &str) -> RelationType {
         "caused_by" => RelationType::CausedBy,
         "result_of" => RelationType::ResultOf,
         "part_of" => RelationType::PartOf,
+        "supports" => RelationType::Supports,
+        "about_person" => RelationType::AboutPerson,
         _ => RelationType::RelatedTo,
     }
 }
@@ -776,6 +1182,58 @@ mod tests {
         assert_eq!(loaded.memory_type, MemoryType::Fact);
     }
 
+    #[tokio::test]
+    async fn test_tags_round_trip() {
+        let store = MemoryStore::connect_in_memory().await;
+        let mut memory = Memory::new("Launch is in March", MemoryType::Event)
+            .with_tags(vec!["launch".into(), "q1".into()]);
+        store.save(&memory).await.unwrap();
+        let loaded = store.load(&memory.id).await.unwrap().unwrap();
+        assert_eq!(loaded.tags, vec!["launch", "q1"]);
+
+        memory.tags.clear();
+        store.update(&memory).await.unwrap();
+        let loaded = store.load(&memory.id).await.unwrap().unwrap();
+        assert!(loaded.tags.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_expired_memories_are_left_out_of_recall() {
+        let store = MemoryStore::connect_in_memory().await;
+        let expired = Memory::new("The meeting is at 3pm Friday", MemoryType::Event)
+            .with_expires_at(Utc::now() - Duration::hours(1));
+        let upcoming = Memory::new("The launch is next month", MemoryType::Event)
+            .with_expires_at(Utc::now() + Duration::days(30));
+        let permanent = Memory::new("The office is in Berlin", MemoryType::Event);
+        for memory in [&expired, &upcoming, &permanent] {
+            store.save(memory).await.unwrap();
+        }
+
+        let loaded = store.load(&upcoming.id).await.unwrap().unwrap();
+        assert_eq!(loaded.expires_at, upcoming.expires_at);
+        assert!(!loaded.is_expired());
+        assert!(store.load(&expired.id).await.unwrap().unwrap().is_expired());
+
+        let recalled: Vec<String> = store
+            .get_sorted(SearchSort::Recent, 10, Some(MemoryType::Event))
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|memory| memory.id)
+            .collect();
+        assert_eq!(recalled.len(), 2);
+        assert!(!recalled.contains(&expired.id));
+        assert_eq!(store.expired(10).await.unwrap(), vec![expired.id.clone()]);
+
+        store.forget(&expired.id).await.unwrap();
+        assert!(store.expired(10).await.unwrap().is_empty());
+
+        store.restore(&expired.id).await.unwrap();
+        let restored = store.load(&expired.id).await.unwrap().unwrap();
+        assert_eq!(restored.expires_at, None);
+        assert!(store.expired(10).await.unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn test_get_sorted_recent() {
         let store = MemoryStore::connect_in_memory().await;
@@ -903,4 +1361,135 @@ mod tests {
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].id, visible.id);
     }
+
+    #[tokio::test]
+    async fn test_forget_and_restore_round_trip_through_trash() {
+        let store = MemoryStore::connect_in_memory().await;
+        let memory = insert_memory_at(&store, "oops", MemoryType::Fact, 0.5, Utc::now()).await;
+
+        assert!(store.forget(&memory.id).await.unwrap());
+        let trash = store.list_forgotten(10, 0).await.unwrap();
+        assert_eq!(trash.len(), 1);
+        assert!(trash[0].forgotten_at.is_some());
+        assert_eq!(store.count_forgotten().await.unwrap(), 1);
+
+        assert!(store.restore(&memory.id).await.unwrap());
+        assert!(!store.restore(&memory.id).await.unwrap());
+        let restored = store.load(&memory.id).await.unwrap().unwrap();
+        assert!(!restored.forgotten);
+        assert!(restored.forgotten_at.is_none());
+        assert!(store.list_forgotten(10, 0).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_forgotten_before_respects_cutoff() {
+        let store = MemoryStore::connect_in_memory().await;
+        let memory = insert_memory_at(&store, "stale", MemoryType::Fact, 0.5, Utc::now()).await;
+        store.forget(&memory.id).await.unwrap();
+
+        let past = Utc::now() - Duration::days(1);
+        assert!(store.forgotten_before(past, 10).await.unwrap().is_empty());
+        let future = Utc::now() + Duration::seconds(5);
+        assert_eq!(
+            store.forgotten_before(future, 10).await.unwrap(),
+            vec![memory.id]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_sorted_scoped_hides_other_scopes() {
+        let store = MemoryStore::connect_in_memory().await;
+        let shared = Memory::new("shared", MemoryType::Fact);
+        let personal = Memory::new("personal", MemoryType::Fact).with_scope("personal");
+        let company = Memory::new("company", MemoryType::Fact).with_scope("company");
+        for memory in [&shared, &personal, &company] {
+            store.save(memory).await.unwrap();
+        }
+
+        let filter = ScopeFilter::Only(vec!["personal".into()]);
+        let visible = store
+            .get_sorted_scoped(SearchSort::Recent, 10, None, &filter)
+            .await
+            .unwrap();
+        let mut contents: Vec<_> = visible.iter().map(|m| m.content.as_str()).collect();
+        contents.sort();
+        assert_eq!(contents, ["personal", "shared"]);
+
+        let unscoped_only = store
+            .get_sorted_scoped(SearchSort::Recent, 10, None, &ScopeFilter::Only(Vec::new()))
+            .await
+            .unwrap();
+        assert_eq!(unscoped_only.len(), 1);
+        assert_eq!(
+            store
+                .load(&company.id)
+                .await
+                .unwrap()
+                .unwrap()
+                .scope
+                .as_deref(),
+            Some("company")
+        );
+    }
+
+    #[tokio::test]
+    async fn export_then_import_keeps_ids_and_edges() {
+        let source = MemoryStore::connect_in_memory().await;
+        let kept = insert_memory_at(
+            &source,
+            "Prefers short answers",
+            MemoryType::Preference,
+            0.9,
+            Utc::now() - Duration::days(40),
+        )
+        .await;
+        let forgotten =
+            Memory::new("Old phone number", MemoryType::Fact).with_tags(vec!["contact".into()]);
+        source.save(&forgotten).await.unwrap();
+        source.forget(&forgotten.id).await.unwrap();
+        source
+            .create_association(&Association::new(
+                &kept.id,
+                &forgotten.id,
+                RelationType::RelatedTo,
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(source.export_counts().await.unwrap(), (2, 1));
+        let mut memories = Vec::new();
+        loop {
+            let after = memories.last().map(|memory: &Memory| memory.id.clone());
+            let page = source
+                .export_memories_page(after.as_deref(), 1)
+                .await
+                .unwrap();
+            if page.is_empty() {
+                break;
+            }
+            memories.extend(page);
+        }
+        assert_eq!(memories.len(), 2);
+        assert!(memories[0].id < memories[1].id);
+        let associations = source.export_associations_page(None, 10).await.unwrap();
+        assert_eq!(associations.len(), 1);
+
+        let target = MemoryStore::connect_in_memory().await;
+        let outcome = target.import(&memories, &associations).await.unwrap();
+        assert_eq!(outcome.inserted.len(), 2);
+        assert_eq!(outcome.associations, 1);
+
+        let restored = target.load(&kept.id).await.unwrap().unwrap();
+        assert_eq!(restored.created_at, kept.created_at);
+        assert_eq!(restored.importance, 0.9);
+        let restored = target.load(&forgotten.id).await.unwrap().unwrap();
+        assert!(restored.forgotten && restored.forgotten_at.is_some());
+        assert_eq!(restored.tags, vec!["contact".to_string()]);
+
+        // A second import of the same dump changes nothing.
+        let again = target.import(&memories, &associations).await.unwrap();
+        assert!(again.inserted.is_empty());
+        assert_eq!(again.skipped, 2);
+        assert_eq!(again.associations, 0);
+    }
 }
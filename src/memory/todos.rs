@@ -0,0 +1,409 @@
+//! Structured state for todo and goal memories.
+//!
+//! A todo or goal is still an ordinary memory: its content is embedded,
+//! recalled, and decays like any other. The fields that make it trackable —
+//! status, priority, due date, and the channel to follow up in — live in a
+//! side table keyed by memory ID, so the memory row and search path stay
+//! unchanged. The cortex reviews open items past their due date once each.
+
+use crate::error::Result;
+use crate::memory::{Memory, MemoryStore, MemoryType};
+use crate::tasks::TaskPriority;
+
+use anyhow::Context as _;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Serialize;
+use sqlx::Row as _;
+
+/// Lifecycle of a todo or goal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TodoStatus {
+    Open,
+    InProgress,
+    Done,
+    Cancelled,
+}
+
+impl TodoStatus {
+    pub const ALL: [TodoStatus; 4] = [
+        TodoStatus::Open,
+        TodoStatus::InProgress,
+        TodoStatus::Done,
+        TodoStatus::Cancelled,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TodoStatus::Open => "open",
+            TodoStatus::InProgress => "in_progress",
+            TodoStatus::Done => "done",
+            TodoStatus::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "open" => Some(TodoStatus::Open),
+            "in_progress" => Some(TodoStatus::InProgress),
+            "done" => Some(TodoStatus::Done),
+            "cancelled" => Some(TodoStatus::Cancelled),
+            _ => None,
+        }
+    }
+
+    /// Done and cancelled items are closed; the rest still need attention.
+    pub fn is_closed(self) -> bool {
+        matches!(self, TodoStatus::Done | TodoStatus::Cancelled)
+    }
+}
+
+impl std::fmt::Display for TodoStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Whether a memory type carries structured todo state.
+pub fn is_trackable(memory_type: MemoryType) -> bool {
+    matches!(memory_type, MemoryType::Todo | MemoryType::Goal)
+}
+
+/// Parse a due date given as RFC 3339 or a bare `YYYY-MM-DD` (end of that
+/// day, UTC).
+pub fn parse_due_date(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(value) {
+        return Some(timestamp.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(23, 59, 59))
+        .map(|timestamp| timestamp.and_utc())
+}
+
+/// Structured fields of a todo or goal.
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+pub struct TodoDetails {
+    pub status: TodoStatus,
+    #[schema(value_type = String)]
+    pub priority: TaskPriority,
+    pub due_at: Option<DateTime<Utc>>,
+    /// Channel the item should be followed up in.
+    pub channel_id: Option<String>,
+    pub completed_at: Option<DateTime<Utc>>,
+    /// When the cortex last flagged the item as overdue. Cleared whenever
+    /// the due date changes so a new deadline gets its own review.
+    pub overdue_reviewed_at: Option<DateTime<Utc>>,
+}
+
+impl Default for TodoDetails {
+    fn default() -> Self {
+        Self {
+            status: TodoStatus::Open,
+            priority: TaskPriority::Medium,
+            due_at: None,
+            channel_id: None,
+            completed_at: None,
+            overdue_reviewed_at: None,
+        }
+    }
+}
+
+impl TodoDetails {
+    /// Move to a new status, stamping or clearing the completion time.
+    pub fn set_status(&mut self, status: TodoStatus) {
+        if status.is_closed() && !self.status.is_closed() {
+            self.completed_at = Some(Utc::now());
+        } else if !status.is_closed() {
+            self.completed_at = None;
+        }
+        self.status = status;
+    }
+
+    /// Change the due date. A new deadline earns a fresh overdue review.
+    pub fn set_due_at(&mut self, due_at: Option<DateTime<Utc>>) {
+        if due_at != self.due_at {
+            self.overdue_reviewed_at = None;
+        }
+        self.due_at = due_at;
+    }
+
+    pub fn is_overdue(&self, now: DateTime<Utc>) -> bool {
+        !self.status.is_closed() && self.due_at.is_some_and(|due_at| due_at < now)
+    }
+}
+
+/// A todo or goal memory with its structured state.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct TodoItem {
+    pub memory: Memory,
+    #[serde(flatten)]
+    pub details: TodoDetails,
+}
+
+/// Filter for [`list_todos`].
+#[derive(Debug, Clone, Default)]
+pub struct TodoFilter {
+    pub status: Option<TodoStatus>,
+    /// Include done and cancelled items when no status is given.
+    pub include_closed: bool,
+    pub memory_type: Option<MemoryType>,
+    pub channel_id: Option<String>,
+}
+
+/// Insert or replace the structured state of a memory.
+pub async fn save_details(
+    store: &MemoryStore,
+    memory_id: &str,
+    details: &TodoDetails,
+) -> Result<()> {
+    sqlx::query(
+        "INSERT INTO memory_todos \
+         (memory_id, status, priority, due_at, channel_id, completed_at, overdue_reviewed_at, updated_at) \
+         VALUES (?, ?, ?, ?, ?, ?, ?, CURRENT_TIMESTAMP) \
+         ON CONFLICT(memory_id) DO UPDATE SET \
+           status = excluded.status, priority = excluded.priority, due_at = excluded.due_at, \
+           channel_id = excluded.channel_id, completed_at = excluded.completed_at, \
+           overdue_reviewed_at = excluded.overdue_reviewed_at, updated_at = excluded.updated_at",
+    )
+    .bind(memory_id)
+    .bind(details.status.as_str())
+    .bind(details.priority.as_str())
+    .bind(details.due_at)
+    .bind(&details.channel_id)
+    .bind(details.completed_at)
+    .bind(details.overdue_reviewed_at)
+    .execute(store.pool())
+    .await
+    .with_context(|| format!("failed to save todo state for memory {memory_id}"))?;
+    Ok(())
+}
+
+/// Structured state of a memory. Trackable memories saved without any
+/// state yet get the defaults; other memory types have none.
+pub async fn load_details(store: &MemoryStore, memory: &Memory) -> Result<Option<TodoDetails>> {
+    if !is_trackable(memory.memory_type) {
+        return Ok(None);
+    }
+    let row = sqlx::query(
+        "SELECT status, priority, due_at, channel_id, completed_at, overdue_reviewed_at \
+         FROM memory_todos WHERE memory_id = ?",
+    )
+    .bind(&memory.id)
+    .fetch_optional(store.pool())
+    .await
+    .with_context(|| format!("failed to load todo state for memory {}", memory.id))?;
+
+    Ok(Some(
+        row.map(|row| row_to_details(&row)).unwrap_or_default(),
+    ))
+}
+
+/// Todos and goals matching the filter, overdue and soonest-due first.
+/// Forgotten memories are excluded.
+pub async fn list_todos(
+    store: &MemoryStore,
+    filter: &TodoFilter,
+    limit: i64,
+) -> Result<Vec<TodoItem>> {
+    let mut sql = String::from(
+        "SELECT m.id AS memory_id, t.status, t.priority, t.due_at, t.channel_id, \
+           t.completed_at, t.overdue_reviewed_at \
+         FROM memories m LEFT JOIN memory_todos t ON t.memory_id = m.id \
+         WHERE m.memory_type IN ('todo', 'goal') AND m.forgotten = 0",
+    );
+    if filter.memory_type.is_some() {
+        sql.push_str(" AND m.memory_type = ?");
+    }
+    if filter.status.is_some() {
+        sql.push_str(" AND COALESCE(t.status, 'open') = ?");
+    } else if !filter.include_closed {
+        sql.push_str(" AND COALESCE(t.status, 'open') NOT IN ('done', 'cancelled')");
+    }
+    if filter.channel_id.is_some() {
+        sql.push_str(" AND t.channel_id = ?");
+    }
+    sql.push_str(
+        " ORDER BY t.due_at IS NULL, t.due_at ASC, m.importance DESC, m.created_at DESC LIMIT ?",
+    );
+
+    let mut query = sqlx::query(&sql);
+    if let Some(memory_type) = filter.memory_type {
+        query = query.bind(memory_type.to_string());
+    }
+    if let Some(status) = filter.status {
+        query = query.bind(status.as_str());
+    }
+    if let Some(channel_id) = &filter.channel_id {
+        query = query.bind(channel_id);
+    }
+    let rows = query
+        .bind(limit)
+        .fetch_all(store.pool())
+        .await
+        .context("failed to list todos")?;
+
+    let mut items = Vec::with_capacity(rows.len());
+    for row in rows {
+        let memory_id: String = row.try_get("memory_id").unwrap_or_default();
+        let Some(memory) = store.load(&memory_id).await? else {
+            continue;
+        };
+        let details = if row
+            .try_get::<Option<String>, _>("status")
+            .ok()
+            .flatten()
+            .is_some()
+        {
+            row_to_details(&row)
+        } else {
+            TodoDetails::default()
+        };
+        items.push(TodoItem { memory, details });
+    }
+    Ok(items)
+}
+
+/// Open items past their due date that the cortex hasn't reviewed yet.
+pub async fn unreviewed_overdue(
+    store: &MemoryStore,
+    now: DateTime<Utc>,
+    limit: i64,
+) -> Result<Vec<TodoItem>> {
+    let rows = sqlx::query(
+        "SELECT t.memory_id FROM memory_todos t JOIN memories m ON m.id = t.memory_id \
+         WHERE m.forgotten = 0 AND t.status NOT IN ('done', 'cancelled') \
+           AND t.due_at IS NOT NULL AND t.due_at < ? AND t.overdue_reviewed_at IS NULL \
+         ORDER BY t.due_at ASC LIMIT ?",
+    )
+    .bind(now)
+    .bind(limit)
+    .fetch_all(store.pool())
+    .await
+    .context("failed to find overdue todos")?;
+
+    let mut items = Vec::with_capacity(rows.len());
+    for row in rows {
+        let memory_id: String = row.try_get("memory_id").unwrap_or_default();
+        let Some(memory) = store.load(&memory_id).await? else {
+            continue;
+        };
+        if let Some(details) = load_details(store, &memory).await? {
+            items.push(TodoItem { memory, details });
+        }
+    }
+    Ok(items)
+}
+
+/// Record that the cortex has reviewed an overdue item.
+pub async fn mark_overdue_reviewed(
+    store: &MemoryStore,
+    memory_id: &str,
+    now: DateTime<Utc>,
+) -> Result<()> {
+    sqlx::query("UPDATE memory_todos SET overdue_reviewed_at = ? WHERE memory_id = ?")
+        .bind(now)
+        .bind(memory_id)
+        .execute(store.pool())
+        .await
+        .with_context(|| format!("failed to mark todo {memory_id} reviewed"))?;
+    Ok(())
+}
+
+/// Drop the structured state of a deleted memory.
+pub async fn delete_details(store: &MemoryStore, memory_id: &str) -> Result<()> {
+    sqlx::query("DELETE FROM memory_todos WHERE memory_id = ?")
+        .bind(memory_id)
+        .execute(store.pool())
+        .await
+        .with_context(|| format!("failed to delete todo state for memory {memory_id}"))?;
+    Ok(())
+}
+
+fn row_to_details(row: &sqlx::sqlite::SqliteRow) -> TodoDetails {
+    let status: String = row.try_get("status").unwrap_or_default();
+    let priority: String = row.try_get("priority").unwrap_or_default();
+    TodoDetails {
+        status: TodoStatus::parse(&status).unwrap_or(TodoStatus::Open),
+        priority: TaskPriority::parse(&priority).unwrap_or(TaskPriority::Medium),
+        due_at: row.try_get("due_at").ok().flatten(),
+        channel_id: row.try_get("channel_id").ok().flatten(),
+        completed_at: row.try_get("completed_at").ok().flatten(),
+        overdue_reviewed_at: row.try_get("overdue_reviewed_at").ok().flatten(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_bare_dates_as_end_of_day() {
+        let due_at = parse_due_date("2026-03-14").unwrap();
+        assert_eq!(due_at.to_rfc3339(), "2026-03-14T23:59:59+00:00");
+        assert!(parse_due_date("2026-03-14T09:00:00Z").is_some());
+        assert!(parse_due_date("next tuesday").is_none());
+    }
+
+    #[tokio::test]
+    async fn overdue_items_are_reviewed_once_per_deadline() {
+        let store = MemoryStore::connect_in_memory().await;
+        let todo = Memory::new("send the invoice", MemoryType::Todo);
+        let done = Memory::new("book flights", MemoryType::Todo);
+        store.save(&todo).await.unwrap();
+        store.save(&done).await.unwrap();
+
+        let yesterday = Utc::now() - chrono::Duration::days(1);
+        let mut details = TodoDetails::default();
+        details.set_due_at(Some(yesterday));
+        save_details(&store, &todo.id, &details).await.unwrap();
+
+        let mut finished = TodoDetails::default();
+        finished.set_due_at(Some(yesterday));
+        finished.set_status(TodoStatus::Done);
+        assert!(finished.completed_at.is_some());
+        save_details(&store, &done.id, &finished).await.unwrap();
+
+        let overdue = unreviewed_overdue(&store, Utc::now(), 10).await.unwrap();
+        assert_eq!(overdue.len(), 1);
+        assert_eq!(overdue[0].memory.id, todo.id);
+
+        mark_overdue_reviewed(&store, &todo.id, Utc::now())
+            .await
+            .unwrap();
+        assert!(
+            unreviewed_overdue(&store, Utc::now(), 10)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        let mut details = load_details(&store, &todo).await.unwrap().unwrap();
+        details.set_due_at(Some(yesterday - chrono::Duration::hours(1)));
+        save_details(&store, &todo.id, &details).await.unwrap();
+        assert_eq!(
+            unreviewed_overdue(&store, Utc::now(), 10)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+
+        let open = list_todos(&store, &TodoFilter::default(), 10)
+            .await
+            .unwrap();
+        assert_eq!(open.len(), 1);
+        let all = list_todos(
+            &store,
+            &TodoFilter {
+                include_closed: true,
+                ..TodoFilter::default()
+            },
+            10,
+        )
+        .await
+        .unwrap();
+        assert_eq!(all.len(), 2);
+    }
+}
@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 /// Memory structure.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, utoipa::ToSchema)]
 pub struct Memory {
     pub id: String,
     pub content: String,
@@ -15,10 +15,27 @@ pub struct Memory {
     pub last_accessed_at: chrono::DateTime<chrono::Utc>,
     pub access_count: i64,
     pub source: Option<String>,
+    #[schema(value_type = Option<String>)]
     pub channel_id: Option<crate::ChannelId>,
     /// Soft-delete flag. Forgotten memories are excluded from search and recall
     /// but remain in the database.
     pub forgotten: bool,
+    /// When the memory was moved to the trash. Set only for forgotten memories
+    /// loaded by ID or from the trash listing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub forgotten_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Memory scope this was saved under. Unscoped memories are visible
+    /// from every channel; see [`crate::memory::scope`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    /// Free-form labels for organizing memories, e.g. the topic of a batch
+    /// seeded from the dashboard.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// When the memory stops being true. Expired memories are left out of
+    /// recall and moved to the trash by maintenance.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 impl Memory {
@@ -40,6 +57,10 @@ impl Memory {
             source: None,
             channel_id: None,
             forgotten: false,
+            forgotten_at: None,
+            scope: None,
+            tags: Vec::new(),
+            expires_at: None,
         }
     }
 
@@ -61,6 +82,30 @@ impl Memory {
         self
     }
 
+    /// Set the tags.
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    /// Set the memory scope.
+    pub fn with_scope(mut self, scope: impl Into<String>) -> Self {
+        self.scope = Some(scope.into());
+        self
+    }
+
+    /// Set when the memory expires.
+    pub fn with_expires_at(mut self, expires_at: chrono::DateTime<chrono::Utc>) -> Self {
+        self.expires_at = Some(expires_at);
+        self
+    }
+
+    /// Whether the memory's expiry time has passed.
+    pub fn is_expired(&self) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| expires_at <= chrono::Utc::now())
+    }
+
     /// Identity memories have maximum importance and don't decay.
     pub const fn identity_importance() -> f32 {
         1.0
@@ -89,7 +134,7 @@ impl MemoryType {
 }
 
 /// Memory types.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum MemoryType {
     /// Something that is true.
@@ -140,7 +185,7 @@ impl std::fmt::Display for MemoryType {
 }
 
 /// Association between memories.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, utoipa::ToSchema)]
 pub struct Association {
     pub id: String,
     pub source_id: String,
@@ -175,7 +220,7 @@ impl Association {
 }
 
 /// Relation types for memory associations.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum RelationType {
     /// General semantic connection.
@@ -190,6 +235,10 @@ pub enum RelationType {
     ResultOf,
     /// Hierarchical relationship.
     PartOf,
+    /// Evidence for the target.
+    Supports,
+    /// The source is about the person the target describes.
+    AboutPerson,
 }
 
 impl std::fmt::Display for RelationType {
@@ -201,16 +250,29 @@ impl std::fmt::Display for RelationType {
             RelationType::CausedBy => write!(f, "caused_by"),
             RelationType::ResultOf => write!(f, "result_of"),
             RelationType::PartOf => write!(f, "part_of"),
+            RelationType::Supports => write!(f, "supports"),
+            RelationType::AboutPerson => write!(f, "about_person"),
         }
     }
 }
 
 /// Search result combining memory with relevance score.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
 pub struct MemorySearchResult {
     pub memory: Memory,
     pub score: f32,
     pub rank: usize,
+    /// Memories one edge away. Only filled in where a caller asks for them
+    /// (the API's search, for the dashboard graph).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<LinkedMemory>,
+}
+
+/// A memory linked to a search result, with the edge that links them.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct LinkedMemory {
+    pub association: Association,
+    pub memory: Memory,
 }
 
 /// Input for memory creation.
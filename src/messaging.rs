@@ -1,13 +1,25 @@
-//! Messaging adapters (Discord, Slack, Telegram, Twitch, Signal, Email, Webhook, WebChat).
+//! Messaging adapters (Discord, Slack, Telegram, Twitch, Signal, Twilio, Email, Webhook, WebChat).
 
+pub mod broadcast;
+pub mod chunking;
+pub mod commands;
+pub mod connection;
 pub mod discord;
 pub mod email;
+pub mod external;
+pub mod filter;
+pub mod format;
 pub mod manager;
+pub mod outbox;
+pub mod presence;
+pub mod receipts;
 pub mod signal;
 pub mod slack;
 pub mod target;
 pub mod telegram;
+pub mod testbed;
 pub mod traits;
+pub mod twilio;
 pub mod twitch;
 pub mod webchat;
 pub mod webhook;
@@ -0,0 +1,166 @@
+//! Fanning one announcement out to a group of channels.
+//!
+//! Each target gets the announcement's Markdown, or the per-platform variant
+//! when one is given, and its adapter renders it in the platform's own
+//! dialect. Sends go out one at a time with a pause between them, and every
+//! target gets a line in the delivery report whether it worked or not.
+
+use crate::OutboundResponse;
+use crate::messaging::MessagingManager;
+use crate::messaging::target::BroadcastTarget;
+
+use serde::{Deserialize, Serialize};
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// What to send.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct Announcement {
+    /// Markdown, rendered for each platform by its adapter.
+    pub message: String,
+    /// Replacement text per platform (`discord`, `slack`, `telegram`, ...),
+    /// for when one audience needs different wording or length.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub platform_messages: HashMap<String, String>,
+}
+
+impl Announcement {
+    /// The text to send through `adapter`. Named instances such as
+    /// `telegram:ops` use their platform's variant.
+    pub fn text_for(&self, adapter: &str) -> &str {
+        let platform = adapter.split(':').next().unwrap_or(adapter);
+        self.platform_messages
+            .get(platform)
+            .filter(|text| !text.trim().is_empty())
+            .unwrap_or(&self.message)
+    }
+}
+
+/// A broadcast to a configured group, as queued for approval.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GroupBroadcast {
+    pub group: String,
+    #[serde(flatten)]
+    pub announcement: Announcement,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryStatus {
+    Sent,
+    Failed,
+    /// The target's adapter isn't running, so nothing was attempted.
+    Unavailable,
+}
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct Delivery {
+    /// `adapter:target`.
+    pub target: String,
+    pub status: DeliveryStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Outcome of a broadcast, one entry per target in send order.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct BroadcastReport {
+    pub sent: usize,
+    pub failed: usize,
+    pub deliveries: Vec<Delivery>,
+}
+
+/// Send `announcement` to each target in turn, waiting `stagger` between
+/// sends. Failures are recorded and don't stop the rest.
+pub async fn fan_out(
+    messaging_manager: &MessagingManager,
+    targets: &[BroadcastTarget],
+    announcement: &Announcement,
+    stagger: Duration,
+) -> BroadcastReport {
+    let mut deliveries = Vec::with_capacity(targets.len());
+    let mut attempted = false;
+
+    for target in targets {
+        if !messaging_manager.has_adapter(&target.adapter).await {
+            deliveries.push(Delivery {
+                target: target.to_string(),
+                status: DeliveryStatus::Unavailable,
+                error: Some(format!("adapter '{}' is not connected", target.adapter)),
+            });
+            continue;
+        }
+        if attempted && !stagger.is_zero() {
+            tokio::time::sleep(stagger).await;
+        }
+        attempted = true;
+
+        let text = announcement.text_for(&target.adapter).to_string();
+        let result = messaging_manager
+            .broadcast(
+                &target.adapter,
+                &target.target,
+                OutboundResponse::Text(text),
+            )
+            .await;
+        let (status, error) = match result {
+            Ok(()) => (DeliveryStatus::Sent, None),
+            Err(error) => {
+                tracing::warn!(%error, target = %target, "broadcast delivery failed");
+                (DeliveryStatus::Failed, Some(error.to_string()))
+            }
+        };
+        deliveries.push(Delivery {
+            target: target.to_string(),
+            status,
+            error,
+        });
+    }
+
+    let sent = deliveries
+        .iter()
+        .filter(|delivery| delivery.status == DeliveryStatus::Sent)
+        .count();
+    BroadcastReport {
+        sent,
+        failed: deliveries.len() - sent,
+        deliveries,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messaging::target::parse_delivery_target;
+
+    #[tokio::test]
+    async fn platform_variants_and_unavailable_targets() {
+        let announcement = Announcement {
+            message: "**Maintenance** tonight".to_string(),
+            platform_messages: HashMap::from([("telegram".to_string(), "Short".to_string())]),
+        };
+        assert_eq!(announcement.text_for("telegram:ops"), "Short");
+        assert_eq!(announcement.text_for("slack"), "**Maintenance** tonight");
+
+        let targets = vec![
+            parse_delivery_target("discord:123456").unwrap(),
+            parse_delivery_target("slack:C0123").unwrap(),
+        ];
+        let report = fan_out(
+            &MessagingManager::new(),
+            &targets,
+            &announcement,
+            Duration::from_secs(60),
+        )
+        .await;
+        assert_eq!(report.sent, 0);
+        assert_eq!(report.failed, 2);
+        assert!(
+            report
+                .deliveries
+                .iter()
+                .all(|delivery| delivery.status == DeliveryStatus::Unavailable)
+        );
+    }
+}
@@ -0,0 +1,270 @@
+//! Splitting long replies into messages that fit platform length limits.
+//!
+//! Splits prefer paragraph breaks, then line breaks, and avoid landing inside
+//! a fenced code block. When a code block is too long for one message it is
+//! closed at the end of the chunk and reopened (with its language) at the
+//! start of the next, so every chunk renders on its own. [`chunk_message`]
+//! numbers the parts; [`ChunkPacer`] spaces them out to stay under
+//! per-channel send limits.
+
+use std::time::Duration;
+
+/// Bytes reserved for the `(n/m)` part marker.
+const PART_MARKER_RESERVE: usize = 16;
+
+/// Closes a code block that continues in the next chunk.
+const FENCE_CLOSE: &str = "\n```";
+
+/// A line of the message, with the opening fence of the code block it sits
+/// in (for body lines and the closing fence).
+struct Line<'a> {
+    text: &'a str,
+    fence: Option<&'a str>,
+}
+
+/// Split `text` into chunks of at most `max_len` bytes, numbering them
+/// `(1/3)`, `(2/3)`, ... when more than one is needed.
+pub fn chunk_message(text: &str, max_len: usize) -> Vec<String> {
+    if text.len() <= max_len {
+        return vec![text.to_string()];
+    }
+    let chunks = split_message(text, max_len.saturating_sub(PART_MARKER_RESERVE));
+    let total = chunks.len();
+    if total == 1 {
+        return chunks;
+    }
+    chunks
+        .into_iter()
+        .enumerate()
+        .map(|(index, chunk)| format!("{chunk}\n\n({}/{total})", index + 1))
+        .collect()
+}
+
+/// Split `text` into chunks of at most `max_len` bytes without numbering.
+pub fn split_message(text: &str, max_len: usize) -> Vec<String> {
+    if text.len() <= max_len {
+        return vec![text.to_string()];
+    }
+    let max_len = max_len.max(8);
+    let lines = split_lines(text, max_len);
+
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    while start < lines.len() {
+        // Blank lines left over from a paragraph split aren't worth a message.
+        while start < lines.len()
+            && lines[start].fence.is_none()
+            && lines[start].text.trim().is_empty()
+        {
+            start += 1;
+        }
+        if start == lines.len() {
+            break;
+        }
+
+        let reopen = lines[start].fence;
+        let mut length = reopen.map_or(0, |fence| fence.len() + 1);
+        let mut end = start;
+        // Best split points seen so far, as (line index, chunk length).
+        let mut paragraph_break = None;
+        let mut line_break = None;
+
+        while end < lines.len() {
+            let added = lines[end].text.len() + usize::from(end > start);
+            let closing = match lines.get(end + 1) {
+                Some(next) if next.fence.is_some() => FENCE_CLOSE.len(),
+                _ => 0,
+            };
+            if end > start && length + added + closing > max_len {
+                break;
+            }
+            length += added;
+            end += 1;
+
+            if let Some(next) = lines.get(end)
+                && next.fence.is_none()
+            {
+                let next_text = next.text.trim_start();
+                if next_text.is_empty() || next_text.starts_with("```") {
+                    paragraph_break = Some((end, length));
+                }
+                line_break = Some((end, length));
+            }
+        }
+
+        let split = if end == lines.len() {
+            end
+        } else {
+            // Don't leave a tiny chunk behind just to avoid splitting a code
+            // block that won't fit in the next chunk either.
+            paragraph_break
+                .filter(|(_, length)| *length >= max_len / 2)
+                .or(line_break.filter(|(_, length)| *length >= max_len / 3))
+                .map_or(end, |(index, _)| index)
+        };
+
+        let mut chunk = String::with_capacity(length + FENCE_CLOSE.len());
+        if let Some(fence) = reopen {
+            chunk.push_str(fence);
+            chunk.push('\n');
+        }
+        let body: Vec<&str> = lines[start..split].iter().map(|line| line.text).collect();
+        chunk.push_str(body.join("\n").trim_end_matches('\n'));
+        if lines.get(split).is_some_and(|line| line.fence.is_some()) {
+            chunk.push_str(FENCE_CLOSE);
+        }
+        chunks.push(chunk);
+        start = split;
+    }
+
+    if chunks.is_empty() {
+        chunks.push(String::new());
+    }
+    chunks
+}
+
+/// Break text into lines tagged with their enclosing code fence. Lines too
+/// long to fit in a chunk (leaving room to reopen and close a fence) are cut
+/// at the last space, or at a character boundary if there is none.
+fn split_lines(text: &str, max_len: usize) -> Vec<Line<'_>> {
+    let mut lines = Vec::new();
+    let mut open_fence: Option<&str> = None;
+
+    for raw in text.split('\n') {
+        let fence = open_fence;
+        if raw.trim_start().starts_with("```") {
+            open_fence = match open_fence {
+                Some(_) => None,
+                None => Some(raw),
+            };
+        }
+
+        let limit = match fence {
+            Some(opening) => max_len.saturating_sub(opening.len() + 1 + FENCE_CLOSE.len()),
+            None => max_len,
+        }
+        .max(max_len / 2);
+
+        let mut rest = raw;
+        while rest.len() > limit {
+            let mut cut = limit;
+            while !rest.is_char_boundary(cut) {
+                cut -= 1;
+            }
+            let cut = match rest[..cut].rfind(' ') {
+                Some(space) if space > 0 => space,
+                _ if cut == 0 => rest.chars().next().map_or(rest.len(), char::len_utf8),
+                _ => cut,
+            };
+            lines.push(Line {
+                text: &rest[..cut],
+                fence,
+            });
+            rest = rest[cut..].trim_start_matches(' ');
+        }
+        lines.push(Line { text: rest, fence });
+    }
+    lines
+}
+
+/// Spaces out consecutive chunks of one reply. The first chunk goes out
+/// immediately; each later one waits until `interval` has passed since the
+/// previous send.
+pub struct ChunkPacer {
+    interval: Duration,
+    last_sent: Option<tokio::time::Instant>,
+}
+
+impl ChunkPacer {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            last_sent: None,
+        }
+    }
+
+    /// Wait until the next chunk may be sent.
+    pub async fn wait(&mut self) {
+        if let Some(last_sent) = self.last_sent {
+            tokio::time::sleep_until(last_sent + self.interval).await;
+        }
+        self.last_sent = Some(tokio::time::Instant::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_fences_balanced(chunk: &str) {
+        let fences = chunk
+            .lines()
+            .filter(|line| line.trim_start().starts_with("```"))
+            .count();
+        assert_eq!(fences % 2, 0, "unbalanced fences in {chunk:?}");
+    }
+
+    #[test]
+    fn splits_on_paragraphs_and_carries_code_blocks_across_chunks() {
+        assert_eq!(split_message("short", 100), vec!["short"]);
+        assert_eq!(split_message("", 100), vec![""]);
+
+        let first = "a".repeat(60);
+        let second = "b".repeat(60);
+        let text = format!("{first}\nmore\n\n{second}");
+        assert_eq!(
+            split_message(&text, 80),
+            vec![format!("{first}\nmore"), second]
+        );
+
+        let code: Vec<String> = (0..30)
+            .map(|index| format!("let x{index} = {index};"))
+            .collect();
+        let text = format!("Here you go:\n\n```rust\n{}\n```\n\nDone.", code.join("\n"));
+        let chunks = split_message(&text, 200);
+        assert!(chunks.len() > 2);
+        assert!(chunks[0].starts_with("Here you go:\n\n```rust\n"));
+        for chunk in &chunks {
+            assert!(chunk.len() <= 200, "{} bytes: {chunk:?}", chunk.len());
+            assert_fences_balanced(chunk);
+        }
+        assert!(
+            chunks[1..]
+                .iter()
+                .all(|chunk| chunk.starts_with("```rust\n"))
+        );
+        let rejoined: String = chunks.concat();
+        assert!(code.iter().all(|line| rejoined.contains(line.as_str())));
+        assert!(chunks.last().unwrap().ends_with("Done."));
+    }
+
+    #[test]
+    fn numbers_parts_and_cuts_long_lines_on_char_boundaries() {
+        let text = "é".repeat(300);
+        let chunks = chunk_message(&text, 100);
+        let total = chunks.len();
+        for (index, chunk) in chunks.iter().enumerate() {
+            assert!(chunk.len() <= 100);
+            assert!(chunk.ends_with(&format!("\n\n({}/{total})", index + 1)));
+        }
+        assert_eq!(chunk_message("fits", 100), vec!["fits"]);
+
+        let words = "word ".repeat(50);
+        for chunk in split_message(words.trim_end(), 32) {
+            assert!(chunk.len() <= 32);
+            assert!(!chunk.starts_with(' ') && !chunk.ends_with(' '));
+        }
+    }
+
+    #[tokio::test]
+    async fn pacer_spaces_out_sends() {
+        let interval = Duration::from_millis(50);
+        let mut pacer = ChunkPacer::new(interval);
+        let start = tokio::time::Instant::now();
+        pacer.wait().await;
+        assert!(start.elapsed() < interval);
+        pacer.wait().await;
+        pacer.wait().await;
+        assert!(start.elapsed() >= interval * 2);
+    }
+}
@@ -0,0 +1,242 @@
+//! Native slash commands.
+//!
+//! Adapters whose platforms support native commands register [`builtin_commands`]
+//! through [`Messaging::register_commands`](super::Messaging::register_commands)
+//! and deliver invocations as [`MessageContent::Command`](crate::MessageContent::Command).
+//! Platforms without them keep sending plain text; [`SlashCommand::parse`]
+//! turns `/memory search budget` into the same structured form, so the channel
+//! handles both the same way.
+
+use serde::{Deserialize, Serialize};
+
+/// A command the platform should offer in its command picker.
+#[derive(Debug, Clone, Serialize)]
+pub struct CommandSpec {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub options: Vec<CommandOptionSpec>,
+    /// When non-empty, the command is a group (`/memory search`) and its
+    /// options live on the subcommands instead.
+    pub subcommands: Vec<CommandSpec>,
+}
+
+/// A free-text argument of a command.
+#[derive(Debug, Clone, Serialize)]
+pub struct CommandOptionSpec {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub required: bool,
+}
+
+impl CommandSpec {
+    fn new(name: &'static str, description: &'static str) -> Self {
+        Self {
+            name,
+            description,
+            options: Vec::new(),
+            subcommands: Vec::new(),
+        }
+    }
+
+    fn option(mut self, name: &'static str, description: &'static str, required: bool) -> Self {
+        self.options.push(CommandOptionSpec {
+            name,
+            description,
+            required,
+        });
+        self
+    }
+
+    fn subcommand(mut self, subcommand: CommandSpec) -> Self {
+        self.subcommands.push(subcommand);
+        self
+    }
+}
+
+/// Commands every channel understands.
+pub fn builtin_commands() -> Vec<CommandSpec> {
+    vec![
+        CommandSpec::new("remind", "Schedule a one-time reminder in this channel")
+            .option(
+                "when",
+                "When to remind, e.g. \"tomorrow 9am\" or \"in 2 hours\"",
+                true,
+            )
+            .option("what", "What to be reminded about", true),
+        CommandSpec::new("summarize", "Summarize the recent conversation").option(
+            "since",
+            "How far back to go, e.g. \"this morning\" or \"last 50 messages\"",
+            false,
+        ),
+        CommandSpec::new("memory", "Work with the agent's memory").subcommand(
+            CommandSpec::new("search", "Search the agent's memories").option(
+                "query",
+                "What to look for",
+                true,
+            ),
+        ),
+        CommandSpec::new("status", "Current mode, models, and binding snapshot"),
+        CommandSpec::new("today", "In-progress and ready task snapshot"),
+        CommandSpec::new("tasks", "Ready task list"),
+        CommandSpec::new("digest", "One-shot day digest (00:00 to now)"),
+        CommandSpec::new("timezone", "Show or set the timezone for this chat or you").option(
+            "zone",
+            "An IANA zone like \"Europe/Berlin\", optionally followed by a location; \"me\" first to set your own",
+            false,
+        ),
+        CommandSpec::new("model", "Show or pin the model used in this chat").option(
+            "tier",
+            "\"fast\" or \"strong\" to pin one, \"auto\" to pick per message",
+            false,
+        ),
+        CommandSpec::new("draft", "Turn speculative draft replies on or off here").option(
+            "mode",
+            "\"on\", \"off\", or \"default\" for the agent setting",
+            false,
+        ),
+        CommandSpec::new("verify", "Fact-check replies against memory here").option(
+            "mode",
+            "\"annotate\", \"soften\", \"regenerate\", \"off\", or \"default\"",
+            false,
+        ),
+        CommandSpec::new("quiet", "Only reply to commands, mentions, and replies"),
+        CommandSpec::new("active", "Reply normally in this chat"),
+        CommandSpec::new("help", "List available commands"),
+    ]
+}
+
+/// A command invocation with its arguments.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SlashCommand {
+    pub name: String,
+    pub subcommand: Option<String>,
+    /// Named arguments in the order they were given. Commands parsed from
+    /// text carry everything after the command in a single `text` argument.
+    pub options: Vec<(String, String)>,
+}
+
+impl SlashCommand {
+    /// Parse a typed command like `/memory search budget numbers`. Returns
+    /// `None` if the text isn't a command.
+    pub fn parse(text: &str) -> Option<Self> {
+        let rest = text.trim().strip_prefix('/')?;
+        let (head, mut rest) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        // Telegram addresses commands to a bot with `/status@botname`.
+        let name = head.split('@').next().unwrap_or_default().to_lowercase();
+        if name.is_empty() {
+            return None;
+        }
+
+        let mut subcommand = None;
+        let is_group = builtin_commands()
+            .iter()
+            .find(|spec| spec.name == name)
+            .is_some_and(|spec| !spec.subcommands.is_empty());
+        if is_group {
+            let (candidate, remainder) = rest
+                .trim_start()
+                .split_once(char::is_whitespace)
+                .unwrap_or((rest.trim_start(), ""));
+            if !candidate.is_empty() {
+                subcommand = Some(candidate.to_lowercase());
+                rest = remainder;
+            }
+        }
+
+        let rest = rest.trim();
+        let options = if rest.is_empty() {
+            Vec::new()
+        } else {
+            vec![("text".to_string(), rest.to_string())]
+        };
+        Some(Self {
+            name,
+            subcommand,
+            options,
+        })
+    }
+
+    /// Value of a named argument, if given and non-empty.
+    pub fn option(&self, name: &str) -> Option<&str> {
+        self.options
+            .iter()
+            .find(|(option_name, _)| option_name == name)
+            .map(|(_, value)| value.trim())
+            .filter(|value| !value.is_empty())
+    }
+
+    /// Free text after the command: the `text` argument for typed commands,
+    /// otherwise all argument values joined in order.
+    pub fn text(&self) -> String {
+        if let Some(text) = self.option("text") {
+            return text.to_string();
+        }
+        self.options
+            .iter()
+            .map(|(_, value)| value.trim())
+            .filter(|value| !value.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+impl std::fmt::Display for SlashCommand {
+    /// Renders as the user would have typed it, so text-based built-ins and
+    /// conversation history see `/memory search query: budget`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "/{}", self.name)?;
+        if let Some(subcommand) = &self.subcommand {
+            write!(f, " {subcommand}")?;
+        }
+        for (name, value) in &self.options {
+            if name == "text" {
+                write!(f, " {value}")?;
+            } else {
+                write!(f, " {name}: {value}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_typed_commands_into_structured_form() {
+        let command = SlashCommand::parse("  /Memory search budget numbers ").unwrap();
+        assert_eq!(command.name, "memory");
+        assert_eq!(command.subcommand.as_deref(), Some("search"));
+        assert_eq!(command.text(), "budget numbers");
+        assert_eq!(command.to_string(), "/memory search budget numbers");
+
+        let command = SlashCommand::parse("/status@spacebot").unwrap();
+        assert_eq!(command.name, "status");
+        assert!(command.options.is_empty());
+        assert_eq!(command.to_string(), "/status");
+
+        // Only command groups take a subcommand.
+        let command = SlashCommand::parse("/remind tomorrow call mom").unwrap();
+        assert_eq!(command.subcommand, None);
+        assert_eq!(command.option("text"), Some("tomorrow call mom"));
+
+        assert!(SlashCommand::parse("hello /status").is_none());
+        assert!(SlashCommand::parse("/ status").is_none());
+
+        let command = SlashCommand {
+            name: "remind".into(),
+            subcommand: None,
+            options: vec![
+                ("when".into(), "in 2 hours".into()),
+                ("what".into(), "stretch".into()),
+            ],
+        };
+        assert_eq!(command.option("what"), Some("stretch"));
+        assert_eq!(command.text(), "in 2 hours stretch");
+        assert_eq!(
+            command.to_string(),
+            "/remind when: in 2 hours what: stretch"
+        );
+    }
+}
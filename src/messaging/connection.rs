@@ -0,0 +1,328 @@
+//! Connection tracking and outbound buffering for supervised adapters.
+//!
+//! The [`MessagingManager`](super::MessagingManager) runs one supervisor task
+//! per adapter. It health-checks the adapter on an interval and, when the
+//! platform connection is lost, restarts the adapter with exponential backoff.
+//! Missed inbound events are recovered by the platforms themselves on
+//! reconnect: the Discord gateway resumes the session and replays dispatches,
+//! Telegram keeps unconfirmed updates for the next `getUpdates`, and Slack
+//! redelivers unacknowledged socket-mode envelopes.
+//!
+//! Outbound replies have no platform-side replay, so complete replies sent
+//! while an adapter is down are held here and delivered in order once it
+//! reconnects. Streaming deltas, typing indicators, and reactions are dropped
+//! since they are meaningless after the fact.
+
+use crate::messaging::traits::MessagingDyn;
+use crate::{InboundMessage, OutboundResponse};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
+
+/// Most replies held per adapter while disconnected. The oldest is dropped
+/// when the buffer is full.
+const MAX_BUFFERED: usize = 100;
+
+/// Buffered replies older than this are dropped instead of delivered; the
+/// conversation has moved on.
+const MAX_BUFFERED_AGE: Duration = Duration::from_secs(15 * 60);
+
+/// Where an adapter's platform connection stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectionState {
+    Connecting,
+    Connected,
+    Reconnecting,
+    /// Gave up after the maximum number of attempts.
+    Failed,
+    Stopped,
+}
+
+/// Snapshot of an adapter's connection, as reported by the health endpoint.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct ConnectionStatus {
+    pub adapter: String,
+    pub state: ConnectionState,
+    /// When the adapter entered `state`.
+    pub since: DateTime<Utc>,
+    pub last_connected_at: Option<DateTime<Utc>>,
+    /// Attempts made in the current connect or reconnect cycle.
+    pub attempts: u32,
+    pub last_error: Option<String>,
+    pub buffered_messages: usize,
+}
+
+/// A reply waiting for its adapter to reconnect, or queued in the
+/// [`Outbox`](super::outbox::Outbox).
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub(super) enum PendingDelivery {
+    Respond {
+        message: Box<InboundMessage>,
+        response: OutboundResponse,
+    },
+    Broadcast {
+        target: String,
+        response: OutboundResponse,
+    },
+}
+
+impl PendingDelivery {
+    pub(super) fn response(&self) -> &OutboundResponse {
+        match self {
+            Self::Respond { response, .. } | Self::Broadcast { response, .. } => response,
+        }
+    }
+
+    /// The conversation being replied to, or the broadcast target.
+    pub(super) fn target(&self) -> &str {
+        match self {
+            Self::Respond { message, .. } => &message.conversation_id,
+            Self::Broadcast { target, .. } => target,
+        }
+    }
+
+    /// Complete replies are worth delivering late; streaming deltas, status
+    /// updates, reactions, and ephemeral messages are not.
+    pub(super) fn is_bufferable(&self) -> bool {
+        matches!(
+            self.response(),
+            OutboundResponse::Text(_)
+                | OutboundResponse::ThreadReply { .. }
+                | OutboundResponse::RichMessage { .. }
+                | OutboundResponse::File { .. }
+        )
+    }
+
+    pub(super) async fn send(self, adapter: &dyn MessagingDyn) -> crate::Result<()> {
+        match self {
+            Self::Respond { message, response } => adapter.respond(&message, response).await,
+            Self::Broadcast { target, response } => adapter.broadcast(&target, response).await,
+        }
+    }
+}
+
+struct Buffered {
+    delivery: PendingDelivery,
+    queued_at: Instant,
+}
+
+struct Inner {
+    state: ConnectionState,
+    since: DateTime<Utc>,
+    last_connected_at: Option<DateTime<Utc>>,
+    attempts: u32,
+    last_error: Option<String>,
+    /// Bumped on every successful (re)start so a stale stream ending doesn't
+    /// trigger a reconnect of the new one.
+    generation: u64,
+}
+
+/// Shared connection state for one adapter, updated by its supervisor and
+/// read by the manager's send paths.
+pub(super) struct AdapterConnection {
+    name: String,
+    inner: Mutex<Inner>,
+    outbox: Mutex<VecDeque<Buffered>>,
+    /// Wakes the supervisor early, on a lost stream or a failed send.
+    pub(super) wake: Notify,
+}
+
+impl AdapterConnection {
+    pub(super) fn new(name: String) -> Self {
+        Self {
+            name,
+            inner: Mutex::new(Inner {
+                state: ConnectionState::Connecting,
+                since: Utc::now(),
+                last_connected_at: None,
+                attempts: 0,
+                last_error: None,
+                generation: 0,
+            }),
+            outbox: Mutex::new(VecDeque::new()),
+            wake: Notify::new(),
+        }
+    }
+
+    pub(super) fn state(&self) -> ConnectionState {
+        self.inner.lock().expect("connection lock poisoned").state
+    }
+
+    pub(super) fn generation(&self) -> u64 {
+        self.inner
+            .lock()
+            .expect("connection lock poisoned")
+            .generation
+    }
+
+    fn transition(&self, state: ConnectionState, update: impl FnOnce(&mut Inner)) {
+        let mut inner = self.inner.lock().expect("connection lock poisoned");
+        if inner.state != state {
+            inner.state = state;
+            inner.since = Utc::now();
+        }
+        update(&mut inner);
+    }
+
+    /// Record a successful start. Returns the new stream generation.
+    pub(super) fn mark_connected(&self) -> u64 {
+        let mut generation = 0;
+        self.transition(ConnectionState::Connected, |inner| {
+            inner.generation += 1;
+            inner.attempts = 0;
+            inner.last_error = None;
+            inner.last_connected_at = Some(Utc::now());
+            generation = inner.generation;
+        });
+        generation
+    }
+
+    /// Record a lost connection. Does nothing if the adapter is stopped or
+    /// already reconnecting.
+    pub(super) fn mark_disconnected(&self, error: impl std::fmt::Display) {
+        let mut inner = self.inner.lock().expect("connection lock poisoned");
+        if inner.state != ConnectionState::Connected {
+            return;
+        }
+        inner.state = ConnectionState::Reconnecting;
+        inner.since = Utc::now();
+        inner.last_error = Some(error.to_string());
+        drop(inner);
+        self.wake.notify_one();
+    }
+
+    /// Record a lost stream, ignoring streams from earlier generations.
+    pub(super) fn stream_ended(&self, generation: u64) {
+        if self.generation() == generation {
+            self.mark_disconnected("inbound stream ended");
+        }
+    }
+
+    pub(super) fn record_attempt_failure(&self, error: impl std::fmt::Display) {
+        self.transition(self.state(), |inner| {
+            inner.attempts += 1;
+            inner.last_error = Some(error.to_string());
+        });
+    }
+
+    pub(super) fn mark_failed(&self) {
+        self.transition(ConnectionState::Failed, |_| {});
+        let dropped = self.drain().len();
+        if dropped > 0 {
+            tracing::warn!(adapter = %self.name, dropped, "dropping buffered replies for failed adapter");
+        }
+    }
+
+    pub(super) fn mark_stopped(&self) {
+        self.transition(ConnectionState::Stopped, |_| {});
+        self.wake.notify_one();
+    }
+
+    /// Hold a reply until the adapter reconnects.
+    pub(super) fn buffer(&self, delivery: PendingDelivery) {
+        let mut outbox = self.outbox.lock().expect("outbox lock poisoned");
+        if outbox.len() >= MAX_BUFFERED {
+            outbox.pop_front();
+            tracing::warn!(adapter = %self.name, "outbound buffer full, dropped oldest reply");
+        }
+        outbox.push_back(Buffered {
+            delivery,
+            queued_at: Instant::now(),
+        });
+    }
+
+    /// Take every buffered reply still fresh enough to deliver, oldest first.
+    pub(super) fn drain(&self) -> Vec<PendingDelivery> {
+        let buffered: Vec<Buffered> = self
+            .outbox
+            .lock()
+            .expect("outbox lock poisoned")
+            .drain(..)
+            .collect();
+        let total = buffered.len();
+        let fresh: Vec<PendingDelivery> = buffered
+            .into_iter()
+            .filter(|entry| entry.queued_at.elapsed() <= MAX_BUFFERED_AGE)
+            .map(|entry| entry.delivery)
+            .collect();
+        if fresh.len() < total {
+            tracing::warn!(
+                adapter = %self.name,
+                expired = total - fresh.len(),
+                "dropping buffered replies that waited too long"
+            );
+        }
+        fresh
+    }
+
+    pub(super) fn status(&self) -> ConnectionStatus {
+        let inner = self.inner.lock().expect("connection lock poisoned");
+        ConnectionStatus {
+            adapter: self.name.clone(),
+            state: inner.state,
+            since: inner.since,
+            last_connected_at: inner.last_connected_at,
+            attempts: inner.attempts,
+            last_error: inner.last_error.clone(),
+            buffered_messages: self.outbox.lock().expect("outbox lock poisoned").len(),
+        }
+    }
+}
+
+/// Exponential backoff between reconnect attempts.
+pub(super) struct Backoff {
+    next: Duration,
+    max: Duration,
+}
+
+impl Backoff {
+    pub(super) fn new(initial: Duration, max: Duration) -> Self {
+        Self { next: initial, max }
+    }
+
+    /// The delay before the next attempt. Doubles on each call up to `max`.
+    pub(super) fn next_delay(&mut self) -> Duration {
+        let delay = self.next.min(self.max);
+        self.next = (self.next * 2).min(self.max);
+        delay
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buffers_replies_only_while_relevant_and_ignores_stale_streams() {
+        let connection = AdapterConnection::new("discord".into());
+        let generation = connection.mark_connected();
+
+        connection.stream_ended(generation - 1);
+        assert_eq!(connection.state(), ConnectionState::Connected);
+        connection.stream_ended(generation);
+        assert_eq!(connection.state(), ConnectionState::Reconnecting);
+
+        let broadcast = |response| PendingDelivery::Broadcast {
+            target: "123".into(),
+            response,
+        };
+        let reply = broadcast(OutboundResponse::Text("hello".into()));
+        assert!(reply.is_bufferable());
+        assert!(!broadcast(OutboundResponse::StreamChunk("he".into())).is_bufferable());
+        connection.buffer(reply);
+        assert_eq!(connection.status().buffered_messages, 1);
+
+        assert_eq!(connection.drain().len(), 1);
+        assert_eq!(connection.status().buffered_messages, 0);
+
+        let mut backoff = Backoff::new(Duration::from_secs(5), Duration::from_secs(12));
+        let delays: Vec<_> = (0..4).map(|_| backoff.next_delay().as_secs()).collect();
+        assert_eq!(delays, vec![5, 10, 12, 12]);
+    }
+}
@@ -2,6 +2,9 @@
 
 use crate::config::DiscordPermissions;
 use crate::messaging::apply_runtime_adapter_to_conversation_id;
+use crate::messaging::chunking::{ChunkPacer, chunk_message};
+use crate::messaging::commands::{CommandSpec, SlashCommand};
+use crate::messaging::presence::{Presence, PresenceStatus};
 use crate::messaging::traits::{HistoryMessage, InboundStream, Messaging};
 use crate::{InboundMessage, MessageContent, OutboundResponse, StatusUpdate};
 
@@ -9,16 +12,26 @@ use anyhow::Context as _;
 use arc_swap::ArcSwap;
 use async_trait::async_trait;
 use serenity::all::{
-    ButtonStyle, ChannelId, ChannelType, Context, CreateActionRow, CreateAttachment, CreateButton,
-    CreateEmbed, CreateEmbedFooter, CreateInteractionResponse, CreateInteractionResponseMessage,
-    CreateMessage, CreatePoll, CreatePollAnswer, CreateSelectMenu, CreateSelectMenuKind,
-    CreateSelectMenuOption, CreateThread, EditMessage, EventHandler, GatewayIntents, GetMessages,
-    Http, Interaction, Message, MessageId, ReactionType, Ready, ShardManager, User, UserId,
+    ActivityData, ButtonStyle, ChannelId, ChannelType, Command, CommandDataOption,
+    CommandDataOptionValue, CommandInteraction, CommandOptionType, Context, CreateActionRow,
+    CreateAttachment, CreateButton, CreateCommand, CreateCommandOption, CreateEmbed,
+    CreateEmbedFooter, CreateInteractionResponse, CreateInteractionResponseMessage, CreateMessage,
+    CreatePoll, CreatePollAnswer, CreateSelectMenu, CreateSelectMenuKind, CreateSelectMenuOption,
+    CreateThread, EditMessage, EventHandler, GatewayIntents, GetMessages, Http, Interaction,
+    Message, MessageId, OnlineStatus, ReactionType, Ready, ShardManager, User, UserId,
 };
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::{RwLock, mpsc};
 
+/// Discord's message content limit.
+const MAX_MESSAGE_LENGTH: usize = 2000;
+
+/// Gap between chunks of one reply; Discord allows 5 messages per 5 seconds
+/// in a channel.
+const CHUNK_INTERVAL: Duration = Duration::from_secs(1);
+
 /// Discord adapter state.
 pub struct DiscordAdapter {
     runtime_key: String,
@@ -31,6 +44,10 @@ pub struct DiscordAdapter {
     /// Typing handles per message. Typing stops when the handle is dropped.
     typing_tasks: Arc<RwLock<HashMap<String, serenity::http::Typing>>>,
     shard_manager: Arc<RwLock<Option<Arc<ShardManager>>>>,
+    /// Slash commands to register once the gateway is ready.
+    commands: Arc<RwLock<Vec<CommandSpec>>>,
+    /// Last presence set, re-applied when the gateway reconnects.
+    presence: Arc<RwLock<Option<Presence>>>,
 }
 
 impl DiscordAdapter {
@@ -48,6 +65,8 @@ impl DiscordAdapter {
             active_messages: Arc::new(RwLock::new(HashMap::new())),
             typing_tasks: Arc::new(RwLock::new(HashMap::new())),
             shard_manager: Arc::new(RwLock::new(None)),
+            commands: Arc::new(RwLock::new(Vec::new())),
+            presence: Arc::new(RwLock::new(None)),
         }
     }
 
@@ -112,6 +131,8 @@ impl Messaging for DiscordAdapter {
             permissions: self.permissions.clone(),
             http_slot: self.http.clone(),
             bot_user_id_slot: self.bot_user_id.clone(),
+            commands_slot: self.commands.clone(),
+            presence_slot: self.presence.clone(),
         };
 
         let intents = GatewayIntents::GUILD_MESSAGES
@@ -150,7 +171,12 @@ impl Messaging for DiscordAdapter {
                 self.stop_typing(message).await;
                 let reply_to = Self::extract_reply_message_id(message);
 
-                for (index, chunk) in split_message(&text, 2000).into_iter().enumerate() {
+                let mut pacer = ChunkPacer::new(CHUNK_INTERVAL);
+                for (index, chunk) in chunk_message(&text, MAX_MESSAGE_LENGTH)
+                    .into_iter()
+                    .enumerate()
+                {
+                    pacer.wait().await;
                     let mut builder = CreateMessage::new().content(chunk);
                     if index == 0
                         && let Some(reply_message_id) = reply_to
@@ -180,8 +206,10 @@ impl Messaging for DiscordAdapter {
                     );
                 }
 
-                let chunks = split_message(&parts.text, 2000);
+                let chunks = chunk_message(&parts.text, MAX_MESSAGE_LENGTH);
+                let mut pacer = ChunkPacer::new(CHUNK_INTERVAL);
                 for (i, chunk) in chunks.iter().enumerate() {
+                    pacer.wait().await;
                     let is_last = i == chunks.len() - 1;
                     let mut msg = CreateMessage::new();
                     if !chunk.is_empty() {
@@ -249,7 +277,9 @@ impl Messaging for DiscordAdapter {
 
                 match thread_result {
                     Ok(thread) => {
-                        for chunk in split_message(&text, 2000) {
+                        let mut pacer = ChunkPacer::new(CHUNK_INTERVAL);
+                        for chunk in chunk_message(&text, MAX_MESSAGE_LENGTH) {
+                            pacer.wait().await;
                             thread
                                 .id
                                 .say(&*http, &chunk)
@@ -265,7 +295,9 @@ impl Messaging for DiscordAdapter {
                             thread_name = %thread_name,
                             "failed to create thread, falling back to regular message"
                         );
-                        for chunk in split_message(&text, 2000) {
+                        let mut pacer = ChunkPacer::new(CHUNK_INTERVAL);
+                        for chunk in chunk_message(&text, MAX_MESSAGE_LENGTH) {
+                            pacer.wait().await;
                             channel_id
                                 .say(&*http, &chunk)
                                 .await
@@ -329,8 +361,8 @@ impl Messaging for DiscordAdapter {
             OutboundResponse::StreamChunk(text) => {
                 let active = self.active_messages.read().await;
                 if let Some(&message_id) = active.get(&message.id) {
-                    let display_text = if text.len() > 2000 {
-                        let end = text.floor_char_boundary(1997);
+                    let display_text = if text.len() > MAX_MESSAGE_LENGTH {
+                        let end = text.floor_char_boundary(MAX_MESSAGE_LENGTH - 3);
                         format!("{}...", &text[..end])
                     } else {
                         text
@@ -422,7 +454,9 @@ impl Messaging for DiscordAdapter {
         };
 
         if let OutboundResponse::Text(text) = response {
-            for chunk in split_message(&text, 2000) {
+            let mut pacer = ChunkPacer::new(CHUNK_INTERVAL);
+            for chunk in chunk_message(&text, MAX_MESSAGE_LENGTH) {
+                pacer.wait().await;
                 channel_id
                     .say(&*http, &chunk)
                     .await
@@ -444,8 +478,10 @@ impl Messaging for DiscordAdapter {
                 );
             }
 
-            let chunks = split_message(&parts.text, 2000);
+            let chunks = chunk_message(&parts.text, MAX_MESSAGE_LENGTH);
+            let mut pacer = ChunkPacer::new(CHUNK_INTERVAL);
             for (i, chunk) in chunks.iter().enumerate() {
+                pacer.wait().await;
                 let is_last = i == chunks.len() - 1;
                 let mut msg = CreateMessage::new();
                 if !chunk.is_empty() {
@@ -560,6 +596,30 @@ impl Messaging for DiscordAdapter {
         Ok(history)
     }
 
+    async fn register_commands(&self, commands: &[CommandSpec]) -> crate::Result<()> {
+        *self.commands.write().await = commands.to_vec();
+
+        // Before the gateway is ready the handler registers them from `ready`.
+        if self.bot_user_id.read().await.is_some() {
+            let http = self.get_http().await?;
+            set_global_commands(&http, commands).await?;
+        }
+        Ok(())
+    }
+
+    async fn set_presence(&self, presence: &Presence) -> crate::Result<()> {
+        *self.presence.write().await = Some(presence.clone());
+
+        // Before the gateway is ready the handler applies it from `ready`.
+        if let Some(shard_manager) = self.shard_manager.read().await.as_ref() {
+            let (activity, status) = discord_presence(presence);
+            for runner in shard_manager.runners.lock().await.values() {
+                runner.runner_tx.set_presence(activity.clone(), status);
+            }
+        }
+        Ok(())
+    }
+
     async fn health_check(&self) -> crate::Result<()> {
         let http = self.get_http().await?;
         http.get_current_user()
@@ -588,6 +648,8 @@ struct Handler {
     permissions: Arc<ArcSwap<DiscordPermissions>>,
     http_slot: Arc<RwLock<Option<Arc<Http>>>>,
     bot_user_id_slot: Arc<RwLock<Option<UserId>>>,
+    commands_slot: Arc<RwLock<Vec<CommandSpec>>>,
+    presence_slot: Arc<RwLock<Option<Presence>>>,
 }
 
 #[async_trait]
@@ -598,6 +660,18 @@ impl EventHandler for Handler {
         *self.http_slot.write().await = Some(ctx.http.clone());
         *self.bot_user_id_slot.write().await = Some(ready.user.id);
         tracing::info!(guild_count = ready.guilds.len(), "discord guilds available");
+
+        if let Some(presence) = self.presence_slot.read().await.as_ref() {
+            let (activity, status) = discord_presence(presence);
+            ctx.set_presence(activity, status);
+        }
+
+        let commands = self.commands_slot.read().await.clone();
+        if !commands.is_empty()
+            && let Err(error) = set_global_commands(&ctx.http, &commands).await
+        {
+            tracing::warn!(%error, "failed to register discord slash commands");
+        }
     }
 
     async fn message(&self, ctx: Context, message: Message) {
@@ -677,7 +751,11 @@ impl EventHandler for Handler {
     async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
         let component = match interaction {
             Interaction::Component(c) => c,
-            _ => return, // Only handle component interactions
+            Interaction::Command(command) => {
+                self.handle_command(&ctx, command).await;
+                return;
+            }
+            _ => return,
         };
 
         // Acknowledge the interaction immediately to prevent "This interaction failed" in the UI.
@@ -785,6 +863,185 @@ impl EventHandler for Handler {
     }
 }
 
+impl Handler {
+    /// Turn a slash command invocation into an inbound `MessageContent::Command`.
+    async fn handle_command(&self, ctx: &Context, command: CommandInteraction) {
+        let slash_command = slash_command_from_interaction(&command);
+
+        // Discord requires a response within three seconds. Echo the invocation
+        // so the channel shows what was asked; the agent replies separately.
+        if let Err(error) = command
+            .create_response(
+                &ctx.http,
+                CreateInteractionResponse::Message(
+                    CreateInteractionResponseMessage::new().content(format!("`{slash_command}`")),
+                ),
+            )
+            .await
+        {
+            tracing::warn!(%error, "failed to acknowledge slash command");
+        }
+
+        let user = &command.user;
+        let permissions = self.permissions.load();
+
+        if command.guild_id.is_none()
+            && (permissions.dm_allowed_users.is_empty()
+                || !permissions.dm_allowed_users.contains(&user.id.get()))
+        {
+            return;
+        }
+
+        if let Some(filter) = &permissions.guild_filter
+            && let Some(guild_id) = command.guild_id
+            && !filter.contains(&guild_id.get())
+        {
+            return;
+        }
+
+        let base_conversation_id = match command.guild_id {
+            Some(guild_id) => format!("discord:{}:{}", guild_id, command.channel_id),
+            None => format!("discord:dm:{}", user.id),
+        };
+        let conversation_id =
+            apply_runtime_adapter_to_conversation_id(&self.runtime_key, base_conversation_id);
+
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "discord_channel_id".into(),
+            serde_json::Value::Number(command.channel_id.get().into()),
+        );
+        metadata.insert(
+            "discord_command".into(),
+            serde_json::Value::String(slash_command.name.clone()),
+        );
+        // A slash command is always addressed to this bot.
+        metadata.insert("discord_mentioned_bot".into(), true.into());
+        metadata.insert("discord_reply_to_bot".into(), false.into());
+        metadata.insert("discord_mentions_or_replies_to_bot".into(), true.into());
+        if let Some(guild_id) = command.guild_id {
+            metadata.insert(
+                "discord_guild_id".into(),
+                serde_json::Value::Number(guild_id.get().into()),
+            );
+        }
+
+        let formatted_author = format!("{} (<@{}>)", user.name, user.id);
+        metadata.insert(
+            "discord_user_id".into(),
+            serde_json::Value::Number(user.id.get().into()),
+        );
+        metadata.insert(
+            "sender_display_name".into(),
+            serde_json::Value::String(formatted_author.clone()),
+        );
+
+        let inbound = InboundMessage {
+            id: command.id.to_string(),
+            source: "discord".into(),
+            adapter: Some(self.runtime_key.clone()),
+            conversation_id,
+            sender_id: user.id.to_string(),
+            agent_id: None,
+            content: MessageContent::Command(slash_command),
+            timestamp: chrono::Utc::now(),
+            metadata,
+            formatted_author: Some(formatted_author),
+        };
+
+        if let Err(error) = self.inbound_tx.send(inbound).await {
+            tracing::warn!(
+                %error,
+                "failed to send inbound slash command from Discord (receiver dropped)"
+            );
+        }
+    }
+}
+
+fn discord_presence(presence: &Presence) -> (Option<ActivityData>, OnlineStatus) {
+    let status = match presence.status {
+        PresenceStatus::Online => OnlineStatus::Online,
+        PresenceStatus::Idle => OnlineStatus::Idle,
+        PresenceStatus::DoNotDisturb => OnlineStatus::DoNotDisturb,
+    };
+    (
+        presence.activity.as_deref().map(ActivityData::custom),
+        status,
+    )
+}
+
+/// Replace the bot's global slash commands with `commands`.
+async fn set_global_commands(http: &Http, commands: &[CommandSpec]) -> anyhow::Result<()> {
+    let commands: Vec<CreateCommand> = commands.iter().map(build_command).collect();
+    let registered = Command::set_global_commands(http, commands)
+        .await
+        .context("failed to register discord slash commands")?;
+    tracing::info!(
+        count = registered.len(),
+        "discord slash commands registered"
+    );
+    Ok(())
+}
+
+fn build_command(spec: &CommandSpec) -> CreateCommand {
+    let mut command = CreateCommand::new(spec.name).description(spec.description);
+    for option in &spec.options {
+        command = command.add_option(
+            CreateCommandOption::new(CommandOptionType::String, option.name, option.description)
+                .required(option.required),
+        );
+    }
+    for subcommand in &spec.subcommands {
+        let mut sub_option = CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            subcommand.name,
+            subcommand.description,
+        );
+        for option in &subcommand.options {
+            sub_option = sub_option.add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    option.name,
+                    option.description,
+                )
+                .required(option.required),
+            );
+        }
+        command = command.add_option(sub_option);
+    }
+    command
+}
+
+fn slash_command_from_interaction(command: &CommandInteraction) -> SlashCommand {
+    let mut subcommand = None;
+    let mut options = Vec::new();
+    for option in &command.data.options {
+        match &option.value {
+            CommandDataOptionValue::SubCommand(sub_options) => {
+                subcommand = Some(option.name.clone());
+                options.extend(sub_options.iter().filter_map(command_option_pair));
+            }
+            _ => options.extend(command_option_pair(option)),
+        }
+    }
+    SlashCommand {
+        name: command.data.name.clone(),
+        subcommand,
+        options,
+    }
+}
+
+fn command_option_pair(option: &CommandDataOption) -> Option<(String, String)> {
+    let value = match &option.value {
+        CommandDataOptionValue::String(value) => value.clone(),
+        CommandDataOptionValue::Integer(value) => value.to_string(),
+        CommandDataOptionValue::Number(value) => value.to_string(),
+        CommandDataOptionValue::Boolean(value) => value.to_string(),
+        _ => return None,
+    };
+    Some((option.name.clone(), value))
+}
+
 fn is_mention_or_reply_to_bot(message: &Message, bot_user_id: Option<UserId>) -> bool {
     is_mention_to_bot(message, bot_user_id) || is_reply_to_bot(message, bot_user_id)
 }
@@ -984,42 +1241,6 @@ async fn build_metadata(
     (metadata, formatted_author)
 }
 
-/// Split a message into chunks that fit within Discord's 2000 char limit.
-/// Tries to split at newlines, then spaces, then hard-cuts.
-fn split_message(text: &str, max_len: usize) -> Vec<String> {
-    if text.len() <= max_len {
-        return vec![text.to_string()];
-    }
-
-    let mut chunks = Vec::new();
-    let mut remaining = text;
-
-    while !remaining.is_empty() {
-        if remaining.len() <= max_len {
-            chunks.push(remaining.to_string());
-            break;
-        }
-
-        let safe_max = {
-            let mut i = max_len.min(remaining.len());
-            while !remaining.is_char_boundary(i) {
-                i -= 1;
-            }
-            i
-        };
-
-        let split_at = remaining[..safe_max]
-            .rfind('\n')
-            .or_else(|| remaining[..safe_max].rfind(' '))
-            .unwrap_or(safe_max);
-
-        chunks.push(remaining[..split_at].to_string());
-        remaining = remaining[split_at..].trim_start();
-    }
-
-    chunks
-}
-
 // --- Rich Message Builders ---
 
 fn build_embed(card: &crate::Card) -> CreateEmbed {
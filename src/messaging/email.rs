@@ -1,6 +1,7 @@
 //! Email messaging adapter using IMAP polling and SMTP delivery.
 
 use crate::config::EmailConfig;
+use crate::messaging::format::RichText;
 use crate::messaging::traits::{HistoryMessage, InboundStream, Messaging};
 use crate::{InboundMessage, MessageContent, OutboundResponse};
 
@@ -22,6 +23,9 @@ use tokio::task::JoinHandle;
 
 const EMAIL_MAX_RETRY_BACKOFF_SECS: u64 = 300;
 
+/// Plain-text bodies are wrapped at the conventional email line length.
+const BODY_WRAP_WIDTH: usize = 78;
+
 /// Wraps both TLS and plaintext IMAP sessions behind a common interface.
 ///
 /// Proton Bridge (and similar local bridges) expose IMAP/SMTP over plain TCP
@@ -90,6 +94,8 @@ struct EmailPollConfig {
     poll_interval: Duration,
     allowed_senders: Vec<String>,
     max_body_bytes: usize,
+    max_attachment_bytes: usize,
+    task_address: Option<String>,
     runtime_key: String,
 }
 
@@ -142,6 +148,7 @@ pub struct EmailAdapter {
     allowed_senders: Vec<String>,
     max_body_bytes: usize,
     max_attachment_bytes: usize,
+    task_address: Option<String>,
     smtp_transport: AsyncSmtpTransport<Tokio1Executor>,
     shutdown_tx: Arc<RwLock<Option<watch::Sender<bool>>>>,
     poll_task: Arc<RwLock<Option<JoinHandle<()>>>>,
@@ -166,6 +173,10 @@ impl std::fmt::Debug for EmailAdapter {
             .field("allowed_senders", &"[REDACTED]")
             .field("max_body_bytes", &self.max_body_bytes)
             .field("max_attachment_bytes", &self.max_attachment_bytes)
+            .field(
+                "task_address",
+                &self.task_address.as_ref().map(|_| "[REDACTED]"),
+            )
             .finish()
     }
 }
@@ -199,6 +210,7 @@ impl EmailAdapter {
             allowed_senders: config.allowed_senders.clone(),
             max_body_bytes: config.max_body_bytes,
             max_attachment_bytes: config.max_attachment_bytes,
+            task_address: config.task_address.clone(),
             instances: Vec::new(),
         };
         Self::build(runtime_key.into(), &email_config)
@@ -238,6 +250,12 @@ impl EmailAdapter {
             allowed_senders: config.allowed_senders.clone(),
             max_body_bytes: config.max_body_bytes.max(1024),
             max_attachment_bytes: config.max_attachment_bytes.max(1024),
+            task_address: config
+                .task_address
+                .as_deref()
+                .map(str::trim)
+                .filter(|address| !address.is_empty())
+                .map(str::to_string),
             smtp_transport,
             shutdown_tx: Arc::new(RwLock::new(None)),
             poll_task: Arc::new(RwLock::new(None)),
@@ -257,6 +275,8 @@ impl EmailAdapter {
             poll_interval: self.poll_interval,
             allowed_senders: self.allowed_senders.clone(),
             max_body_bytes: self.max_body_bytes,
+            max_attachment_bytes: self.max_attachment_bytes,
+            task_address: self.task_address.clone(),
             runtime_key: self.runtime_key.clone(),
         }
     }
@@ -280,6 +300,7 @@ impl EmailAdapter {
     ) -> crate::Result<()> {
         let recipient_mailbox = parse_mailbox(recipient)
             .with_context(|| format!("invalid recipient address '{recipient}'"))?;
+        let body = RichText::parse(&body).to_plain_text(Some(BODY_WRAP_WIDTH));
 
         let mut builder = Message::builder()
             .from(self.sender_mailbox()?)
@@ -329,6 +350,19 @@ impl EmailAdapter {
 
         Ok(())
     }
+
+    /// Stop the IMAP poll task and wait for it to exit.
+    async fn stop_polling(&self) {
+        if let Some(shutdown_tx) = self.shutdown_tx.write().await.take() {
+            shutdown_tx.send(true).ok();
+        }
+
+        if let Some(poll_task) = self.poll_task.write().await.take()
+            && let Err(error) = poll_task.await
+        {
+            tracing::warn!(%error, "email poll task join failed during shutdown");
+        }
+    }
 }
 
 impl Messaging for EmailAdapter {
@@ -657,17 +691,15 @@ impl Messaging for EmailAdapter {
         Ok(())
     }
 
-    async fn shutdown(&self) -> crate::Result<()> {
-        if let Some(shutdown_tx) = self.shutdown_tx.write().await.take() {
-            shutdown_tx.send(true).ok();
-        }
-
-        if let Some(poll_task) = self.poll_task.write().await.take()
-            && let Err(error) = poll_task.await
-        {
-            tracing::warn!(%error, "email poll task join failed during shutdown");
-        }
+    async fn reconnect(&self) -> crate::Result<InboundStream> {
+        // SMTP connections are pooled per send; only the IMAP poller needs
+        // restarting.
+        self.stop_polling().await;
+        self.start().await
+    }
 
+    async fn shutdown(&self) -> crate::Result<()> {
+        self.stop_polling().await;
         self.smtp_transport.shutdown().await;
 
         tracing::info!("email adapter shut down");
@@ -1011,6 +1043,27 @@ fn parse_inbound_email(
         |name| format!("{name} <{sender_email}>"),
     );
 
+    // Mail to the task address carries its attachments inline so they can be
+    // saved alongside the task it becomes.
+    let is_task = config
+        .task_address
+        .as_deref()
+        .is_some_and(|task_address| is_addressed_to(headers, task_address));
+    let content = if is_task {
+        metadata.insert(
+            crate::tasks::email::EMAIL_TASK_KEY.into(),
+            serde_json::Value::Bool(true),
+        );
+        let mut attachments = Vec::new();
+        collect_attachment_data(&parsed, config.max_attachment_bytes, &mut attachments);
+        MessageContent::Media {
+            text: Some(body_text),
+            attachments,
+        }
+    } else {
+        MessageContent::Text(body_text)
+    };
+
     Ok(Some(InboundMessage {
         id: message_id,
         source: "email".into(),
@@ -1018,7 +1071,7 @@ fn parse_inbound_email(
         conversation_id,
         sender_id: sender_email,
         agent_id: None,
-        content: MessageContent::Text(body_text),
+        content,
         timestamp,
         metadata,
         formatted_author: Some(formatted_author),
@@ -1210,6 +1263,8 @@ pub fn search_mailbox(
         poll_interval: Duration::from_secs(config.poll_interval_secs.max(5)),
         allowed_senders: config.allowed_senders.clone(),
         max_body_bytes: config.max_body_bytes.max(1024),
+        max_attachment_bytes: config.max_attachment_bytes.max(1024),
+        task_address: None,
         runtime_key: "email".to_string(),
     })?;
 
@@ -1440,6 +1495,21 @@ fn is_own_sender(sender_email: &str, config: &EmailPollConfig) -> bool {
         || sender_email.eq_ignore_ascii_case(&config.smtp_username)
 }
 
+/// Whether `address` is among the message's recipients, including envelope
+/// headers set by the receiving server (so Bcc and aliases still match).
+fn is_addressed_to(headers: &[mailparse::MailHeader<'_>], address: &str) -> bool {
+    ["To", "Cc", "Delivered-To", "X-Original-To"]
+        .iter()
+        .flat_map(|name| headers.get_all_values(name))
+        .filter_map(|value| mailparse::addrparse(&value).ok())
+        .flat_map(|list| list.into_inner())
+        .flat_map(|entry| match entry {
+            MailAddr::Single(single) => vec![single.addr],
+            MailAddr::Group(group) => group.addrs.into_iter().map(|single| single.addr).collect(),
+        })
+        .any(|recipient| recipient.trim().eq_ignore_ascii_case(address))
+}
+
 fn is_allowed_sender(sender_email: &str, allowed_senders: &[String]) -> bool {
     if allowed_senders.is_empty() {
         return true;
@@ -1581,6 +1651,59 @@ fn collect_parts(
     }
 }
 
+/// Attachments as self-contained `data:` URLs. Parts over `max_bytes` are
+/// skipped; their names still appear in the body's attachment list.
+fn collect_attachment_data(
+    part: &mailparse::ParsedMail<'_>,
+    max_bytes: usize,
+    attachments: &mut Vec<crate::Attachment>,
+) {
+    use base64::Engine as _;
+
+    if !part.subparts.is_empty() {
+        for subpart in &part.subparts {
+            collect_attachment_data(subpart, max_bytes, attachments);
+        }
+        return;
+    }
+
+    let disposition = part.get_content_disposition();
+    let Some(filename) = disposition
+        .params
+        .get("filename")
+        .cloned()
+        .or_else(|| part.ctype.params.get("name").cloned())
+    else {
+        return;
+    };
+    let data = match part.get_body_raw() {
+        Ok(data) => data,
+        Err(error) => {
+            tracing::warn!(%error, filename, "failed to decode email attachment");
+            return;
+        }
+    };
+    if data.len() > max_bytes {
+        tracing::info!(
+            filename,
+            size = data.len(),
+            max_bytes,
+            "skipping email attachment over max_attachment_bytes"
+        );
+        return;
+    }
+
+    let mime_type = part.ctype.mimetype.to_ascii_lowercase();
+    let encoded = base64::engine::general_purpose::STANDARD.encode(&data);
+    attachments.push(crate::Attachment {
+        url: format!("data:{mime_type};base64,{encoded}"),
+        filename,
+        mime_type,
+        size_bytes: Some(data.len() as u64),
+        auth_header: None,
+    });
+}
+
 fn html_to_text(html: &str) -> String {
     let without_tags = html_tag_regex().replace_all(html, " ");
     let decoded = without_tags
@@ -1756,8 +1879,9 @@ struct EmailReplyContext {
 mod tests {
     use super::{
         EmailSearchHit, EmailSearchQuery, build_imap_search_criterion, derive_thread_key,
-        extract_message_ids, is_local_mail_host, normalize_email_target, normalize_reply_subject,
-        normalize_search_folders, parse_primary_mailbox, sort_and_limit_search_hits,
+        extract_message_ids, is_addressed_to, is_local_mail_host, normalize_email_target,
+        normalize_reply_subject, normalize_search_folders, parse_primary_mailbox,
+        sort_and_limit_search_hits,
     };
 
     #[test]
@@ -1917,4 +2041,16 @@ mod tests {
         assert_eq!(results[0].subject, "newest");
         assert_eq!(results[1].subject, "middle");
     }
+
+    #[test]
+    fn is_addressed_to_checks_cc_and_envelope_headers() {
+        let raw = b"From: alice@example.com\r\nTo: bot@example.com\r\nCc: Tasks <TASKS@example.com>\r\n\r\nbody";
+        let parsed = mailparse::parse_mail(raw).unwrap();
+        assert!(is_addressed_to(&parsed.headers, "tasks@example.com"));
+        assert!(!is_addressed_to(&parsed.headers, "other@example.com"));
+
+        let raw = b"From: alice@example.com\r\nTo: bot@example.com\r\nDelivered-To: tasks@example.com\r\n\r\nbody";
+        let parsed = mailparse::parse_mail(raw).unwrap();
+        assert!(is_addressed_to(&parsed.headers, "tasks@example.com"));
+    }
 }
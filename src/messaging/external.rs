@@ -0,0 +1,476 @@
+//! Out-of-process messaging adapters.
+//!
+//! An external adapter is a separate program that bridges one chat platform
+//! and speaks the JSON-RPC protocol in [`protocol`] with Spacebot, either
+//! over its stdio (Spacebot launches it) or over a WebSocket (Spacebot
+//! connects to it). [`ExternalAdapter`] is the host side and plugs into the
+//! [`MessagingManager`](super::MessagingManager) like any built-in adapter.
+//! Adapter authors implement [`sdk::PlatformAdapter`] and hand it to
+//! [`sdk::serve_stdio`] or [`sdk::serve_websocket`].
+
+pub mod protocol;
+pub mod sdk;
+
+use crate::config::{ExternalAdapterConfig, ExternalTransport};
+use crate::messaging::commands::CommandSpec;
+use crate::messaging::presence::Presence;
+use crate::messaging::traits::{HistoryMessage, InboundStream, Messaging};
+use crate::{InboundMessage, OutboundResponse, StatusUpdate};
+
+use protocol::{
+    BroadcastParams, FetchHistoryParams, HistoryEntry, InboundParams, InitializeParams,
+    InitializeResult, LogLevel, LogParams, PROTOCOL_VERSION, RegisterCommandsParams, RespondParams,
+    RpcError, RpcMessage, SendStatusParams,
+};
+
+use anyhow::Context as _;
+use futures::{SinkExt as _, StreamExt as _};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use tokio::io::{AsyncBufReadExt as _, AsyncWriteExt as _, BufReader};
+use tokio::sync::{RwLock, mpsc, oneshot};
+use tokio::task::JoinHandle;
+
+use std::collections::HashMap;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// How long the adapter has to answer `initialize`.
+const INITIALIZE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How long any other request may take.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How long `shutdown` may take before the process is killed.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
+type PendingRequests =
+    Arc<parking_lot::Mutex<HashMap<u64, oneshot::Sender<Result<serde_json::Value, RpcError>>>>>;
+
+/// Host side of an out-of-process adapter.
+pub struct ExternalAdapter {
+    config: ExternalAdapterConfig,
+    session: RwLock<Option<Arc<Session>>>,
+}
+
+impl ExternalAdapter {
+    pub fn new(config: ExternalAdapterConfig) -> Self {
+        Self {
+            config,
+            session: RwLock::new(None),
+        }
+    }
+
+    async fn session(&self) -> anyhow::Result<Arc<Session>> {
+        self.session
+            .read()
+            .await
+            .clone()
+            .with_context(|| format!("external adapter '{}' is not connected", self.config.name))
+    }
+
+    async fn call<P: Serialize, R: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: Option<P>,
+    ) -> crate::Result<R> {
+        let session = self.session().await?;
+        let params = params
+            .map(serde_json::to_value)
+            .transpose()
+            .context("failed to encode request params")?;
+        let result = session.request(method, params, REQUEST_TIMEOUT).await?;
+        Ok(serde_json::from_value(result)
+            .with_context(|| format!("invalid '{method}' result from adapter"))?)
+    }
+}
+
+impl Messaging for ExternalAdapter {
+    fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    async fn start(&self) -> crate::Result<InboundStream> {
+        let (inbound_tx, inbound_rx) = mpsc::channel(256);
+        let session = Session::open(&self.config, inbound_tx).await?;
+
+        let params = InitializeParams {
+            protocol_version: PROTOCOL_VERSION,
+            adapter: self.config.name.clone(),
+            settings: self.config.settings.clone(),
+        };
+        let result = session
+            .request(
+                protocol::METHOD_INITIALIZE,
+                Some(serde_json::to_value(params).context("failed to encode initialize")?),
+                INITIALIZE_TIMEOUT,
+            )
+            .await?;
+        let result: InitializeResult =
+            serde_json::from_value(result).context("invalid initialize result from adapter")?;
+        if result.protocol_version != PROTOCOL_VERSION {
+            return Err(anyhow::anyhow!(
+                "external adapter '{}' speaks protocol version {}, expected {PROTOCOL_VERSION}",
+                self.config.name,
+                result.protocol_version
+            )
+            .into());
+        }
+        tracing::info!(
+            adapter = %self.config.name,
+            server_info = result.server_info.as_deref().unwrap_or("unknown"),
+            "external adapter initialized"
+        );
+
+        *self.session.write().await = Some(Arc::new(session));
+        Ok(Box::pin(tokio_stream::wrappers::ReceiverStream::new(
+            inbound_rx,
+        )))
+    }
+
+    async fn respond(
+        &self,
+        message: &InboundMessage,
+        response: OutboundResponse,
+    ) -> crate::Result<()> {
+        let params = RespondParams {
+            message: message.clone(),
+            response,
+        };
+        self.call::<_, serde_json::Value>(protocol::METHOD_RESPOND, Some(params))
+            .await
+            .map(drop)
+    }
+
+    async fn send_status(
+        &self,
+        message: &InboundMessage,
+        status: StatusUpdate,
+    ) -> crate::Result<()> {
+        let params = SendStatusParams {
+            message: message.clone(),
+            status,
+        };
+        self.call::<_, serde_json::Value>(protocol::METHOD_SEND_STATUS, Some(params))
+            .await
+            .map(drop)
+    }
+
+    async fn broadcast(&self, target: &str, response: OutboundResponse) -> crate::Result<()> {
+        let params = BroadcastParams {
+            target: target.to_string(),
+            response,
+        };
+        self.call::<_, serde_json::Value>(protocol::METHOD_BROADCAST, Some(params))
+            .await
+            .map(drop)
+    }
+
+    async fn fetch_history(
+        &self,
+        message: &InboundMessage,
+        limit: usize,
+    ) -> crate::Result<Vec<HistoryMessage>> {
+        let params = FetchHistoryParams {
+            message: message.clone(),
+            limit,
+        };
+        let entries: Vec<HistoryEntry> = self
+            .call(protocol::METHOD_FETCH_HISTORY, Some(params))
+            .await?;
+        Ok(entries.into_iter().map(HistoryMessage::from).collect())
+    }
+
+    async fn register_commands(&self, commands: &[CommandSpec]) -> crate::Result<()> {
+        let params = RegisterCommandsParams { commands };
+        self.call::<_, serde_json::Value>(protocol::METHOD_REGISTER_COMMANDS, Some(params))
+            .await
+            .map(drop)
+    }
+
+    async fn set_presence(&self, presence: &Presence) -> crate::Result<()> {
+        self.call::<_, serde_json::Value>(protocol::METHOD_SET_PRESENCE, Some(presence))
+            .await
+            .map(drop)
+    }
+
+    async fn health_check(&self) -> crate::Result<()> {
+        self.call::<(), serde_json::Value>(protocol::METHOD_HEALTH_CHECK, None)
+            .await
+            .map(drop)
+    }
+
+    async fn shutdown(&self) -> crate::Result<()> {
+        let Some(session) = self.session.write().await.take() else {
+            return Ok(());
+        };
+        if let Err(error) = session
+            .request(protocol::METHOD_SHUTDOWN, None, SHUTDOWN_TIMEOUT)
+            .await
+        {
+            tracing::debug!(adapter = %self.config.name, %error, "external adapter shutdown request failed");
+        }
+        // Dropping the session aborts its tasks and kills a child process.
+        drop(session);
+        tracing::info!(adapter = %self.config.name, "external adapter shut down");
+        Ok(())
+    }
+}
+
+/// One live connection to an adapter process.
+struct Session {
+    outgoing: mpsc::Sender<String>,
+    pending: PendingRequests,
+    next_id: AtomicU64,
+    tasks: Vec<JoinHandle<()>>,
+    /// Held so the process is killed when the session is dropped.
+    _child: Option<tokio::process::Child>,
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+impl Session {
+    async fn open(
+        config: &ExternalAdapterConfig,
+        inbound_tx: mpsc::Sender<InboundMessage>,
+    ) -> anyhow::Result<Self> {
+        let (outgoing, outgoing_rx) = mpsc::channel::<String>(256);
+        let pending = PendingRequests::default();
+        let dispatcher = Dispatcher {
+            name: config.name.clone(),
+            pending: Arc::clone(&pending),
+            inbound_tx,
+            outgoing: outgoing.clone(),
+        };
+
+        let (tasks, child) = match &config.transport {
+            ExternalTransport::Stdio { command, args, env } => {
+                let (tasks, child) =
+                    spawn_stdio(&config.name, command, args, env, outgoing_rx, dispatcher)?;
+                (tasks, Some(child))
+            }
+            ExternalTransport::WebSocket { url } => {
+                (connect_websocket(url, outgoing_rx, dispatcher).await?, None)
+            }
+        };
+
+        Ok(Self {
+            outgoing,
+            pending,
+            next_id: AtomicU64::new(1),
+            tasks,
+            _child: child,
+        })
+    }
+
+    async fn request(
+        &self,
+        method: &str,
+        params: Option<serde_json::Value>,
+        timeout: Duration,
+    ) -> anyhow::Result<serde_json::Value> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.pending.lock().insert(id, reply_tx);
+
+        let line = serde_json::to_string(&RpcMessage::request(id, method, params))
+            .context("failed to encode request")?;
+        if self.outgoing.send(line).await.is_err() {
+            self.pending.lock().remove(&id);
+            anyhow::bail!("external adapter disconnected");
+        }
+
+        match tokio::time::timeout(timeout, reply_rx).await {
+            Ok(Ok(result)) => Ok(result.with_context(|| format!("'{method}' failed"))?),
+            Ok(Err(_)) => anyhow::bail!("external adapter disconnected during '{method}'"),
+            Err(_) => {
+                self.pending.lock().remove(&id);
+                anyhow::bail!("'{method}' timed out after {}s", timeout.as_secs())
+            }
+        }
+    }
+}
+
+/// Routes messages read from the adapter.
+struct Dispatcher {
+    name: String,
+    pending: PendingRequests,
+    inbound_tx: mpsc::Sender<InboundMessage>,
+    outgoing: mpsc::Sender<String>,
+}
+
+impl Dispatcher {
+    async fn handle(&self, line: &str) {
+        let line = line.trim();
+        if line.is_empty() {
+            return;
+        }
+        let message: RpcMessage = match serde_json::from_str(line) {
+            Ok(message) => message,
+            Err(error) => {
+                tracing::warn!(adapter = %self.name, %error, "unparseable message from external adapter");
+                return;
+            }
+        };
+
+        match (message.id, message.method.as_deref()) {
+            (Some(id), None) => {
+                let result = match message.error {
+                    Some(error) => Err(error),
+                    None => Ok(message.result.unwrap_or(serde_json::Value::Null)),
+                };
+                if let Some(reply_tx) = self.pending.lock().remove(&id) {
+                    let _ = reply_tx.send(result);
+                }
+            }
+            (None, Some(protocol::NOTIFY_INBOUND)) => {
+                let params: InboundParams = match serde_json::from_value(
+                    message.params.unwrap_or_default(),
+                ) {
+                    Ok(params) => params,
+                    Err(error) => {
+                        tracing::warn!(adapter = %self.name, %error, "invalid inbound notification");
+                        return;
+                    }
+                };
+                let mut inbound = params.message;
+                // The adapter's config name is its platform for bindings and routing.
+                inbound.source = self.name.clone();
+                inbound.adapter = None;
+                inbound.agent_id = None;
+                if self.inbound_tx.send(inbound).await.is_err() {
+                    tracing::debug!(adapter = %self.name, "inbound stream closed, dropping message");
+                }
+            }
+            (None, Some(protocol::NOTIFY_LOG)) => {
+                let Ok(params) =
+                    serde_json::from_value::<LogParams>(message.params.unwrap_or_default())
+                else {
+                    return;
+                };
+                let adapter = self.name.as_str();
+                match params.level {
+                    LogLevel::Debug => tracing::debug!(adapter, "{}", params.message),
+                    LogLevel::Info => tracing::info!(adapter, "{}", params.message),
+                    LogLevel::Warn => tracing::warn!(adapter, "{}", params.message),
+                    LogLevel::Error => tracing::error!(adapter, "{}", params.message),
+                }
+            }
+            (Some(id), Some(method)) => {
+                let error = RpcError::new(
+                    protocol::METHOD_NOT_FOUND,
+                    format!("host does not handle '{method}'"),
+                );
+                if let Ok(line) = serde_json::to_string(&RpcMessage::response(id, Err(error))) {
+                    let _ = self.outgoing.send(line).await;
+                }
+            }
+            (None, method) => {
+                tracing::debug!(adapter = %self.name, ?method, "ignoring unknown notification");
+            }
+        }
+    }
+
+    /// Fail every in-flight request once the connection is gone.
+    fn disconnected(&self) {
+        self.pending.lock().clear();
+        tracing::info!(adapter = %self.name, "external adapter connection closed");
+    }
+}
+
+fn spawn_stdio(
+    name: &str,
+    command: &str,
+    args: &[String],
+    env: &HashMap<String, String>,
+    mut outgoing_rx: mpsc::Receiver<String>,
+    dispatcher: Dispatcher,
+) -> anyhow::Result<(Vec<JoinHandle<()>>, tokio::process::Child)> {
+    let mut child = tokio::process::Command::new(command)
+        .args(args)
+        .envs(env)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .with_context(|| format!("failed to launch external adapter '{name}' ({command})"))?;
+
+    let mut stdin = child.stdin.take().context("adapter stdin not captured")?;
+    let stdout = child.stdout.take().context("adapter stdout not captured")?;
+    let stderr = child.stderr.take().context("adapter stderr not captured")?;
+
+    let writer = tokio::spawn(async move {
+        while let Some(line) = outgoing_rx.recv().await {
+            let written = async {
+                stdin.write_all(line.as_bytes()).await?;
+                stdin.write_all(b"\n").await?;
+                stdin.flush().await
+            };
+            if written.await.is_err() {
+                break;
+            }
+        }
+    });
+
+    let reader = tokio::spawn(async move {
+        let mut lines = BufReader::new(stdout).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            dispatcher.handle(&line).await;
+        }
+        dispatcher.disconnected();
+    });
+
+    let adapter = name.to_string();
+    let stderr_forwarder = tokio::spawn(async move {
+        let mut lines = BufReader::new(stderr).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            tracing::debug!(adapter = %adapter, "{line}");
+        }
+    });
+
+    Ok((vec![writer, reader, stderr_forwarder], child))
+}
+
+async fn connect_websocket(
+    url: &str,
+    mut outgoing_rx: mpsc::Receiver<String>,
+    dispatcher: Dispatcher,
+) -> anyhow::Result<Vec<JoinHandle<()>>> {
+    use tokio_tungstenite::tungstenite::Message;
+
+    let (socket, _) = tokio_tungstenite::connect_async(url)
+        .await
+        .with_context(|| format!("failed to connect to external adapter at {url}"))?;
+    let (mut sink, mut stream) = socket.split();
+
+    let writer = tokio::spawn(async move {
+        while let Some(line) = outgoing_rx.recv().await {
+            if sink.send(Message::Text(line.into())).await.is_err() {
+                break;
+            }
+        }
+        let _ = sink.close().await;
+    });
+
+    let reader = tokio::spawn(async move {
+        while let Some(Ok(frame)) = stream.next().await {
+            match frame {
+                Message::Text(text) => dispatcher.handle(text.as_str()).await,
+                Message::Close(_) => break,
+                _ => {}
+            }
+        }
+        dispatcher.disconnected();
+    });
+
+    Ok(vec![writer, reader])
+}
@@ -0,0 +1,241 @@
+//! Wire format for out-of-process adapters.
+//!
+//! JSON-RPC 2.0, one JSON object per line over stdio or one per text frame
+//! over WebSocket. Spacebot is the client for the `host → adapter` methods
+//! below; the adapter sends `inbound` and `log` notifications back. Adding
+//! optional fields is backwards compatible; anything else bumps
+//! [`PROTOCOL_VERSION`].
+
+use crate::messaging::commands::CommandSpec;
+use crate::messaging::traits::HistoryMessage;
+use crate::{InboundMessage, OutboundResponse, StatusUpdate};
+
+use serde::{Deserialize, Serialize};
+
+/// Protocol revision. Sent in `initialize`; adapters reject versions they
+/// don't speak.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Host → adapter: handshake. Params [`InitializeParams`], result [`InitializeResult`].
+pub const METHOD_INITIALIZE: &str = "initialize";
+/// Host → adapter: reply to an inbound message. Params [`RespondParams`].
+pub const METHOD_RESPOND: &str = "respond";
+/// Host → adapter: typing/status indicator. Params [`SendStatusParams`].
+pub const METHOD_SEND_STATUS: &str = "send_status";
+/// Host → adapter: proactive message to a target. Params [`BroadcastParams`].
+pub const METHOD_BROADCAST: &str = "broadcast";
+/// Host → adapter: recent history for context backfill. Params
+/// [`FetchHistoryParams`], result `Vec<`[`HistoryEntry`]`>`.
+pub const METHOD_FETCH_HISTORY: &str = "fetch_history";
+/// Host → adapter: native command registration. Params [`RegisterCommandsParams`].
+pub const METHOD_REGISTER_COMMANDS: &str = "register_commands";
+/// Host → adapter: bot presence. Params [`Presence`](crate::messaging::presence::Presence).
+pub const METHOD_SET_PRESENCE: &str = "set_presence";
+/// Host → adapter: liveness probe. No params.
+pub const METHOD_HEALTH_CHECK: &str = "health_check";
+/// Host → adapter: disconnect; the process may exit after replying. No params.
+pub const METHOD_SHUTDOWN: &str = "shutdown";
+/// Adapter → host notification: a message arrived. Params [`InboundParams`].
+pub const NOTIFY_INBOUND: &str = "inbound";
+/// Adapter → host notification: a log line for Spacebot's log. Params [`LogParams`].
+pub const NOTIFY_LOG: &str = "log";
+
+/// Methods whose handler isn't implemented return this code.
+pub const METHOD_NOT_FOUND: i64 = -32601;
+/// Malformed params.
+pub const INVALID_PARAMS: i64 = -32602;
+/// The adapter failed to carry out the request.
+pub const ADAPTER_ERROR: i64 = -32000;
+
+/// Any message on the wire. Requests have `id` and `method`, notifications
+/// only `method`, responses `id` and one of `result` / `error`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RpcMessage {
+    pub jsonrpc: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub method: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub params: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<RpcError>,
+}
+
+impl RpcMessage {
+    pub fn request(id: u64, method: &str, params: Option<serde_json::Value>) -> Self {
+        Self {
+            jsonrpc: "2.0".into(),
+            id: Some(id),
+            method: Some(method.into()),
+            params,
+            ..Self::default()
+        }
+    }
+
+    pub fn notification(method: &str, params: serde_json::Value) -> Self {
+        Self {
+            jsonrpc: "2.0".into(),
+            method: Some(method.into()),
+            params: Some(params),
+            ..Self::default()
+        }
+    }
+
+    pub fn response(id: u64, result: Result<serde_json::Value, RpcError>) -> Self {
+        let (result, error) = match result {
+            Ok(value) => (Some(value), None),
+            Err(error) => (None, Some(error)),
+        };
+        Self {
+            jsonrpc: "2.0".into(),
+            id: Some(id),
+            result,
+            error,
+            ..Self::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, thiserror::Error)]
+#[error("{message} (code {code})")]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+impl RpcError {
+    pub fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InitializeParams {
+    pub protocol_version: u32,
+    /// The adapter's name in config, which is also its platform name in
+    /// bindings and the `source` of its messages.
+    pub adapter: String,
+    /// The `[messaging.external.settings]` table, passed through as-is.
+    #[serde(default)]
+    pub settings: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InitializeResult {
+    pub protocol_version: u32,
+    /// Free-form adapter identification for logs, e.g. `matrix-adapter 0.3.1`.
+    #[serde(default)]
+    pub server_info: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InboundParams {
+    pub message: InboundMessage,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RespondParams {
+    pub message: InboundMessage,
+    pub response: OutboundResponse,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SendStatusParams {
+    pub message: InboundMessage,
+    pub status: StatusUpdate,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BroadcastParams {
+    pub target: String,
+    pub response: OutboundResponse,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FetchHistoryParams {
+    pub message: InboundMessage,
+    pub limit: usize,
+}
+
+/// Wire form of [`HistoryMessage`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub author: String,
+    pub content: String,
+    #[serde(default)]
+    pub is_bot: bool,
+    #[serde(default)]
+    pub timestamp: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl From<HistoryEntry> for HistoryMessage {
+    fn from(entry: HistoryEntry) -> Self {
+        Self {
+            author: entry.author,
+            content: entry.content,
+            is_bot: entry.is_bot,
+            timestamp: entry.timestamp,
+        }
+    }
+}
+
+impl From<HistoryMessage> for HistoryEntry {
+    fn from(message: HistoryMessage) -> Self {
+        Self {
+            author: message.author,
+            content: message.content,
+            is_bot: message.is_bot,
+            timestamp: message.timestamp,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RegisterCommandsParams<'a> {
+    pub commands: &'a [CommandSpec],
+}
+
+/// Owned form of [`CommandSpec`] for adapters decoding `register_commands`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommandDefinition {
+    pub name: String,
+    pub description: String,
+    #[serde(default)]
+    pub options: Vec<CommandOptionDefinition>,
+    #[serde(default)]
+    pub subcommands: Vec<CommandDefinition>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommandOptionDefinition {
+    pub name: String,
+    pub description: String,
+    #[serde(default)]
+    pub required: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegisteredCommands {
+    pub commands: Vec<CommandDefinition>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogParams {
+    pub level: LogLevel,
+    pub message: String,
+}
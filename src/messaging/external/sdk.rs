@@ -0,0 +1,471 @@
+//! Adapter side of the external adapter protocol.
+//!
+//! Implement [`PlatformAdapter`] for the platform's client and run it with
+//! [`serve_stdio`] (Spacebot launches the binary) or [`serve_websocket`]
+//! (Spacebot connects to it). The trait is the stable surface for
+//! third-party adapters; it changes only with [`PROTOCOL_VERSION`].
+
+use super::protocol::{
+    self, BroadcastParams, CommandDefinition, FetchHistoryParams, HistoryEntry, InboundParams,
+    InitializeParams, InitializeResult, LogLevel, LogParams, PROTOCOL_VERSION, RegisteredCommands,
+    RespondParams, RpcError, RpcMessage, SendStatusParams,
+};
+use crate::messaging::presence::Presence;
+use crate::{InboundMessage, OutboundResponse, StatusUpdate};
+
+use futures::{SinkExt as _, StreamExt as _};
+use serde::de::DeserializeOwned;
+use tokio::io::{AsyncBufReadExt as _, AsyncWriteExt as _, BufReader};
+use tokio::sync::mpsc;
+
+use std::future::Future;
+use std::sync::Arc;
+
+/// A chat platform bridged into Spacebot from a separate process.
+///
+/// Every method but [`initialize`](Self::initialize) and
+/// [`respond`](Self::respond) has a default, so a minimal adapter only
+/// connects, forwards messages through the [`InboundSender`], and posts
+/// replies. Errors are returned to Spacebot, which logs them and retries
+/// the delivery where that makes sense.
+pub trait PlatformAdapter: Send + Sync + 'static {
+    /// Connect to the platform. Keep `inbound` to forward messages for as
+    /// long as the connection lives.
+    fn initialize(
+        &self,
+        params: InitializeParams,
+        inbound: InboundSender,
+    ) -> impl Future<Output = anyhow::Result<()>> + Send;
+
+    /// Post a reply to a message this adapter forwarded.
+    fn respond(
+        &self,
+        message: InboundMessage,
+        response: OutboundResponse,
+    ) -> impl Future<Output = anyhow::Result<()>> + Send;
+
+    /// Show a typing indicator or similar.
+    fn send_status(
+        &self,
+        message: InboundMessage,
+        status: StatusUpdate,
+    ) -> impl Future<Output = anyhow::Result<()>> + Send {
+        let _ = (message, status);
+        async { Ok(()) }
+    }
+
+    /// Post to a target (channel, user, room) without a triggering message,
+    /// e.g. cron output.
+    fn broadcast(
+        &self,
+        target: String,
+        response: OutboundResponse,
+    ) -> impl Future<Output = anyhow::Result<()>> + Send {
+        let _ = response;
+        async move { anyhow::bail!("broadcast to '{target}' is not supported") }
+    }
+
+    /// Messages before `message` in its conversation, oldest first.
+    fn fetch_history(
+        &self,
+        message: InboundMessage,
+        limit: usize,
+    ) -> impl Future<Output = anyhow::Result<Vec<HistoryEntry>>> + Send {
+        let _ = (message, limit);
+        async { Ok(Vec::new()) }
+    }
+
+    /// Offer Spacebot's commands in the platform's command picker.
+    fn register_commands(
+        &self,
+        commands: Vec<CommandDefinition>,
+    ) -> impl Future<Output = anyhow::Result<()>> + Send {
+        let _ = commands;
+        async { Ok(()) }
+    }
+
+    /// Show the bot as online, idle, or do-not-disturb.
+    fn set_presence(&self, presence: Presence) -> impl Future<Output = anyhow::Result<()>> + Send {
+        let _ = presence;
+        async { Ok(()) }
+    }
+
+    /// Report whether the platform connection is usable.
+    fn health_check(&self) -> impl Future<Output = anyhow::Result<()>> + Send {
+        async { Ok(()) }
+    }
+
+    /// Disconnect from the platform. The serve loop returns afterwards.
+    fn shutdown(&self) -> impl Future<Output = anyhow::Result<()>> + Send {
+        async { Ok(()) }
+    }
+}
+
+/// Sends notifications to Spacebot.
+#[derive(Clone)]
+pub struct InboundSender {
+    outgoing: mpsc::Sender<String>,
+}
+
+impl InboundSender {
+    /// Forward a platform message. `source` and `adapter` are overwritten
+    /// by the host, so set only the conversation, sender, content, and
+    /// whatever metadata the adapter needs back in `respond`.
+    pub async fn send(&self, message: InboundMessage) -> anyhow::Result<()> {
+        self.notify(protocol::NOTIFY_INBOUND, InboundParams { message })
+            .await
+    }
+
+    /// Write a line to Spacebot's log, attributed to this adapter.
+    pub async fn log(&self, level: LogLevel, message: impl Into<String>) -> anyhow::Result<()> {
+        let params = LogParams {
+            level,
+            message: message.into(),
+        };
+        self.notify(protocol::NOTIFY_LOG, params).await
+    }
+
+    async fn notify(&self, method: &str, params: impl serde::Serialize) -> anyhow::Result<()> {
+        let message = RpcMessage::notification(method, serde_json::to_value(params)?);
+        self.outgoing
+            .send(serde_json::to_string(&message)?)
+            .await
+            .map_err(|_| anyhow::anyhow!("connection to spacebot closed"))
+    }
+}
+
+/// Serve the protocol over stdin/stdout until Spacebot sends `shutdown` or
+/// closes stdin. Log with stderr or [`InboundSender::log`]; stdout is
+/// reserved for the protocol.
+pub async fn serve_stdio<A: PlatformAdapter>(adapter: A) -> anyhow::Result<()> {
+    let (outgoing_tx, mut outgoing_rx) = mpsc::channel::<String>(256);
+    let writer = tokio::spawn(async move {
+        let mut stdout = tokio::io::stdout();
+        while let Some(line) = outgoing_rx.recv().await {
+            if stdout.write_all(line.as_bytes()).await.is_err()
+                || stdout.write_all(b"\n").await.is_err()
+                || stdout.flush().await.is_err()
+            {
+                break;
+            }
+        }
+    });
+
+    let server = Server::new(adapter, outgoing_tx);
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    while let Some(line) = lines.next_line().await? {
+        if server.handle(&line).await {
+            break;
+        }
+    }
+    drop(server);
+    flush(writer).await;
+    Ok(())
+}
+
+/// Serve the protocol over an accepted WebSocket until Spacebot sends
+/// `shutdown` or disconnects.
+pub async fn serve_websocket<A, S>(
+    adapter: A,
+    socket: tokio_tungstenite::WebSocketStream<S>,
+) -> anyhow::Result<()>
+where
+    A: PlatformAdapter,
+    S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin + Send + 'static,
+{
+    use tokio_tungstenite::tungstenite::Message;
+
+    let (mut sink, mut stream) = socket.split();
+    let (outgoing_tx, mut outgoing_rx) = mpsc::channel::<String>(256);
+    let writer = tokio::spawn(async move {
+        while let Some(line) = outgoing_rx.recv().await {
+            if sink.send(Message::Text(line.into())).await.is_err() {
+                break;
+            }
+        }
+        let _ = sink.close().await;
+    });
+
+    let server = Server::new(adapter, outgoing_tx);
+    while let Some(frame) = stream.next().await {
+        match frame? {
+            Message::Text(text) if server.handle(text.as_str()).await => break,
+            Message::Close(_) => break,
+            _ => {}
+        }
+    }
+    drop(server);
+    flush(writer).await;
+    Ok(())
+}
+
+/// Give queued replies a moment to go out. Senders cloned into the
+/// adapter's own tasks may keep the writer alive indefinitely.
+async fn flush(writer: tokio::task::JoinHandle<()>) {
+    let _ = tokio::time::timeout(std::time::Duration::from_secs(2), writer).await;
+}
+
+struct Server<A> {
+    adapter: Arc<A>,
+    outgoing: mpsc::Sender<String>,
+}
+
+impl<A: PlatformAdapter> Server<A> {
+    fn new(adapter: A, outgoing: mpsc::Sender<String>) -> Self {
+        Self {
+            adapter: Arc::new(adapter),
+            outgoing,
+        }
+    }
+
+    /// Handle one incoming line. Returns true once `shutdown` was answered.
+    async fn handle(&self, line: &str) -> bool {
+        let line = line.trim();
+        if line.is_empty() {
+            return false;
+        }
+        let Ok(message) = serde_json::from_str::<RpcMessage>(line) else {
+            return false;
+        };
+        let (Some(id), Some(method)) = (message.id, message.method) else {
+            // The host sends no notifications and expects no responses.
+            return false;
+        };
+
+        // Shutdown and initialize are answered in order; everything else
+        // runs concurrently so a slow send doesn't hold up the rest.
+        let is_shutdown = method == protocol::METHOD_SHUTDOWN;
+        if is_shutdown || method == protocol::METHOD_INITIALIZE {
+            let result = dispatch(&*self.adapter, &self.outgoing, &method, message.params).await;
+            self.reply(id, result).await;
+            return is_shutdown;
+        }
+
+        let adapter = Arc::clone(&self.adapter);
+        let outgoing = self.outgoing.clone();
+        tokio::spawn(async move {
+            let result = dispatch(&*adapter, &outgoing, &method, message.params).await;
+            reply(&outgoing, id, result).await;
+        });
+        false
+    }
+
+    async fn reply(&self, id: u64, result: Result<serde_json::Value, RpcError>) {
+        reply(&self.outgoing, id, result).await;
+    }
+}
+
+async fn reply(
+    outgoing: &mpsc::Sender<String>,
+    id: u64,
+    result: Result<serde_json::Value, RpcError>,
+) {
+    if let Ok(line) = serde_json::to_string(&RpcMessage::response(id, result)) {
+        let _ = outgoing.send(line).await;
+    }
+}
+
+fn params<T: DeserializeOwned>(params: Option<serde_json::Value>) -> Result<T, RpcError> {
+    serde_json::from_value(params.unwrap_or_default())
+        .map_err(|error| RpcError::new(protocol::INVALID_PARAMS, error.to_string()))
+}
+
+async fn dispatch<A: PlatformAdapter>(
+    adapter: &A,
+    outgoing: &mpsc::Sender<String>,
+    method: &str,
+    raw: Option<serde_json::Value>,
+) -> Result<serde_json::Value, RpcError> {
+    let adapter_error =
+        |error: anyhow::Error| RpcError::new(protocol::ADAPTER_ERROR, format!("{error:#}"));
+    let null = |()| serde_json::Value::Null;
+
+    match method {
+        protocol::METHOD_INITIALIZE => {
+            let params: InitializeParams = params(raw)?;
+            if params.protocol_version != PROTOCOL_VERSION {
+                return Err(RpcError::new(
+                    protocol::INVALID_PARAMS,
+                    format!(
+                        "unsupported protocol version {}, this adapter speaks {PROTOCOL_VERSION}",
+                        params.protocol_version
+                    ),
+                ));
+            }
+            let inbound = InboundSender {
+                outgoing: outgoing.clone(),
+            };
+            adapter
+                .initialize(params, inbound)
+                .await
+                .map_err(adapter_error)?;
+            let result = InitializeResult {
+                protocol_version: PROTOCOL_VERSION,
+                server_info: None,
+            };
+            Ok(serde_json::to_value(result).unwrap_or_default())
+        }
+        protocol::METHOD_RESPOND => {
+            let RespondParams { message, response } = params(raw)?;
+            adapter
+                .respond(message, response)
+                .await
+                .map(null)
+                .map_err(adapter_error)
+        }
+        protocol::METHOD_SEND_STATUS => {
+            let SendStatusParams { message, status } = params(raw)?;
+            adapter
+                .send_status(message, status)
+                .await
+                .map(null)
+                .map_err(adapter_error)
+        }
+        protocol::METHOD_BROADCAST => {
+            let BroadcastParams { target, response } = params(raw)?;
+            adapter
+                .broadcast(target, response)
+                .await
+                .map(null)
+                .map_err(adapter_error)
+        }
+        protocol::METHOD_FETCH_HISTORY => {
+            let FetchHistoryParams { message, limit } = params(raw)?;
+            let entries = adapter
+                .fetch_history(message, limit)
+                .await
+                .map_err(adapter_error)?;
+            Ok(serde_json::to_value(entries).unwrap_or_default())
+        }
+        protocol::METHOD_REGISTER_COMMANDS => {
+            let RegisteredCommands { commands } = params(raw)?;
+            adapter
+                .register_commands(commands)
+                .await
+                .map(null)
+                .map_err(adapter_error)
+        }
+        protocol::METHOD_SET_PRESENCE => {
+            let presence: Presence = params(raw)?;
+            adapter
+                .set_presence(presence)
+                .await
+                .map(null)
+                .map_err(adapter_error)
+        }
+        protocol::METHOD_HEALTH_CHECK => adapter
+            .health_check()
+            .await
+            .map(null)
+            .map_err(adapter_error),
+        protocol::METHOD_SHUTDOWN => adapter.shutdown().await.map(null).map_err(adapter_error),
+        other => Err(RpcError::new(
+            protocol::METHOD_NOT_FOUND,
+            format!("unknown method '{other}'"),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MessageContent;
+
+    use parking_lot::Mutex;
+
+    #[derive(Default)]
+    struct EchoAdapter {
+        inbound: Mutex<Option<InboundSender>>,
+        replies: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl PlatformAdapter for EchoAdapter {
+        async fn initialize(
+            &self,
+            _params: InitializeParams,
+            inbound: InboundSender,
+        ) -> anyhow::Result<()> {
+            *self.inbound.lock() = Some(inbound);
+            Ok(())
+        }
+
+        async fn respond(
+            &self,
+            _message: InboundMessage,
+            response: OutboundResponse,
+        ) -> anyhow::Result<()> {
+            if let OutboundResponse::Text(text) = response {
+                self.replies.lock().push(text);
+            }
+            Ok(())
+        }
+    }
+
+    fn request(id: u64, method: &str, params: serde_json::Value) -> String {
+        serde_json::to_string(&RpcMessage::request(id, method, Some(params))).unwrap()
+    }
+
+    async fn next(outgoing: &mut mpsc::Receiver<String>) -> RpcMessage {
+        serde_json::from_str(&outgoing.recv().await.unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn serves_handshake_requests_and_notifications() {
+        let (outgoing_tx, mut outgoing_rx) = mpsc::channel(16);
+        let adapter = EchoAdapter::default();
+        let replies = Arc::clone(&adapter.replies);
+        let server = Server::new(adapter, outgoing_tx);
+
+        let initialize = InitializeParams {
+            protocol_version: PROTOCOL_VERSION,
+            adapter: "matrix".into(),
+            settings: serde_json::Value::Null,
+        };
+        let line = request(
+            1,
+            protocol::METHOD_INITIALIZE,
+            serde_json::to_value(initialize).unwrap(),
+        );
+        assert!(!server.handle(&line).await);
+        let response = next(&mut outgoing_rx).await;
+        assert_eq!(response.id, Some(1));
+        assert!(response.error.is_none());
+
+        let mut message = InboundMessage::empty();
+        message.conversation_id = "matrix:!room".into();
+        message.content = MessageContent::Text("hi".into());
+        let inbound = server.adapter.inbound.lock().clone().unwrap();
+        inbound.send(message.clone()).await.unwrap();
+        let notification = next(&mut outgoing_rx).await;
+        assert_eq!(
+            notification.method.as_deref(),
+            Some(protocol::NOTIFY_INBOUND)
+        );
+        assert!(notification.id.is_none());
+
+        let respond = RespondParams {
+            message,
+            response: OutboundResponse::Text("hello".into()),
+        };
+        let line = request(
+            2,
+            protocol::METHOD_RESPOND,
+            serde_json::to_value(respond).unwrap(),
+        );
+        assert!(!server.handle(&line).await);
+        let response = next(&mut outgoing_rx).await;
+        assert_eq!(response.id, Some(2));
+        assert_eq!(replies.lock().as_slice(), ["hello".to_string()]);
+
+        let line = request(3, "unknown", serde_json::Value::Null);
+        server.handle(&line).await;
+        let response = next(&mut outgoing_rx).await;
+        assert_eq!(
+            response.error.map(|error| error.code),
+            Some(protocol::METHOD_NOT_FOUND)
+        );
+
+        let line = request(4, protocol::METHOD_SHUTDOWN, serde_json::Value::Null);
+        assert!(server.handle(&line).await);
+    }
+}
@@ -0,0 +1,187 @@
+//! Adapter-level inbound filters: channel allow/deny lists, user denylist,
+//! and bot-message rules from `[messaging.filters.<adapter>]`.
+
+use crate::InboundMessage;
+use crate::config::MessageFilterConfig;
+
+use std::collections::HashMap;
+
+/// Why a message was dropped. Used as the metrics label.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterReason {
+    ChannelNotAllowed,
+    ChannelDenied,
+    UserDenied,
+    BotMessage,
+}
+
+impl FilterReason {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::ChannelNotAllowed => "channel_not_allowed",
+            Self::ChannelDenied => "channel_denied",
+            Self::UserDenied => "user_denied",
+            Self::BotMessage => "bot_message",
+        }
+    }
+}
+
+/// The filter for a runtime adapter: its own entry, else its platform's.
+pub fn filter_for<'a>(
+    filters: &'a HashMap<String, MessageFilterConfig>,
+    adapter_key: &str,
+) -> Option<&'a MessageFilterConfig> {
+    filters.get(adapter_key).or_else(|| {
+        let platform = adapter_key.split(':').next().unwrap_or(adapter_key);
+        filters.get(platform)
+    })
+}
+
+/// Check a message against a filter. `None` means the message passes.
+pub fn check(filter: &MessageFilterConfig, message: &InboundMessage) -> Option<FilterReason> {
+    if filter.deny_users.contains(&message.sender_id) {
+        return Some(FilterReason::UserDenied);
+    }
+
+    let is_bot = message
+        .metadata
+        .get("sender_is_bot")
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false);
+    if is_bot && filter.ignore_bots && !filter.allowed_bots.contains(&message.sender_id) {
+        return Some(FilterReason::BotMessage);
+    }
+
+    if is_direct_message(message) {
+        return None;
+    }
+    let channel_ids = channel_ids(message);
+    if channel_ids.is_empty() {
+        return None;
+    }
+    if !filter.allow_channels.is_empty()
+        && !channel_ids
+            .iter()
+            .any(|id| filter.allow_channels.contains(id))
+    {
+        return Some(FilterReason::ChannelNotAllowed);
+    }
+    if channel_ids
+        .iter()
+        .any(|id| filter.deny_channels.contains(id))
+    {
+        return Some(FilterReason::ChannelDenied);
+    }
+    None
+}
+
+fn is_direct_message(message: &InboundMessage) -> bool {
+    let metadata = &message.metadata;
+    match message.source.as_str() {
+        "discord" => !metadata.contains_key("discord_guild_id"),
+        "slack" => metadata
+            .get("slack_channel_id")
+            .and_then(|value| value.as_str())
+            .is_some_and(|id| id.starts_with('D')),
+        "telegram" => {
+            metadata
+                .get("telegram_chat_type")
+                .and_then(|value| value.as_str())
+                == Some("private")
+        }
+        "signal" => !metadata.contains_key("signal_group_id"),
+        _ => false,
+    }
+}
+
+/// Channel IDs a message belongs to: the channel itself and, for threads,
+/// the parent channel.
+fn channel_ids(message: &InboundMessage) -> Vec<String> {
+    const KEYS: &[&str] = &[
+        "discord_channel_id",
+        "discord_parent_channel_id",
+        "slack_channel_id",
+        "telegram_chat_id",
+        "twitch_channel",
+        "signal_group_id",
+    ];
+    KEYS.iter()
+        .filter_map(|key| message.metadata.get(*key))
+        .filter_map(|value| match value {
+            serde_json::Value::String(id) => Some(id.clone()),
+            serde_json::Value::Number(id) => Some(id.to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn discord_message(channel_id: u64, parent_id: Option<u64>, sender: &str) -> InboundMessage {
+        let mut message = InboundMessage::empty();
+        message.source = "discord".into();
+        message.sender_id = sender.into();
+        message
+            .metadata
+            .insert("discord_guild_id".into(), 1_u64.into());
+        message
+            .metadata
+            .insert("discord_channel_id".into(), channel_id.into());
+        if let Some(parent_id) = parent_id {
+            message
+                .metadata
+                .insert("discord_parent_channel_id".into(), parent_id.into());
+        }
+        message
+    }
+
+    #[test]
+    fn applies_channel_user_and_bot_rules() {
+        let filter = MessageFilterConfig {
+            allow_channels: vec!["10".into(), "11".into()],
+            deny_channels: vec!["11".into()],
+            deny_users: vec!["666".into()],
+            ignore_bots: true,
+            allowed_bots: vec!["42".into()],
+        };
+
+        assert_eq!(check(&filter, &discord_message(10, None, "1")), None);
+        // Threads follow their parent channel.
+        assert_eq!(check(&filter, &discord_message(99, Some(10), "1")), None);
+        assert_eq!(
+            check(&filter, &discord_message(12, None, "1")),
+            Some(FilterReason::ChannelNotAllowed)
+        );
+        assert_eq!(
+            check(&filter, &discord_message(11, None, "1")),
+            Some(FilterReason::ChannelDenied)
+        );
+        assert_eq!(
+            check(&filter, &discord_message(10, None, "666")),
+            Some(FilterReason::UserDenied)
+        );
+
+        let mut bot = discord_message(10, None, "7");
+        bot.metadata.insert("sender_is_bot".into(), true.into());
+        assert_eq!(check(&filter, &bot), Some(FilterReason::BotMessage));
+        bot.sender_id = "42".into();
+        assert_eq!(check(&filter, &bot), None);
+
+        let mut dm = discord_message(12, None, "1");
+        dm.metadata.remove("discord_guild_id");
+        assert_eq!(check(&filter, &dm), None);
+
+        let filters = HashMap::from([
+            ("discord".to_string(), filter.clone()),
+            ("discord:ops".to_string(), MessageFilterConfig::default()),
+        ]);
+        assert_eq!(filter_for(&filters, "discord:support"), Some(&filter));
+        assert_eq!(
+            filter_for(&filters, "discord:ops"),
+            Some(&MessageFilterConfig::default())
+        );
+        assert_eq!(filter_for(&filters, "slack"), None);
+    }
+}
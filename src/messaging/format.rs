@@ -0,0 +1,649 @@
+//! Platform-specific rendering of agent output.
+//!
+//! Agents write Markdown. [`RichText::parse`] reads it into a small canonical
+//! tree of blocks and inline spans, and each adapter renders that tree in the
+//! dialect its platform understands: Slack mrkdwn, Telegram HTML, or plain
+//! text (optionally wrapped) for IRC-style chat, SMS-like messengers, and
+//! email. Tables have no native form anywhere, so they are laid out as
+//! aligned monospace text.
+
+/// A parsed message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RichText {
+    pub blocks: Vec<Block>,
+}
+
+/// A block-level element. Blocks are separated by a blank line when rendered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Block {
+    /// Consecutive lines of text; line breaks are kept.
+    Paragraph(Vec<Vec<Inline>>),
+    Heading(Vec<Inline>),
+    Code {
+        language: Option<String>,
+        text: String,
+    },
+    Quote(Vec<Vec<Inline>>),
+    List(Vec<ListItem>),
+    Table {
+        header: Vec<Vec<Inline>>,
+        rows: Vec<Vec<Vec<Inline>>>,
+    },
+    Rule,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListItem {
+    /// Nesting level, from indentation in steps of two spaces.
+    pub depth: usize,
+    /// Item number for ordered lists.
+    pub number: Option<u64>,
+    pub content: Vec<Inline>,
+}
+
+/// An inline span.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Inline {
+    Text(String),
+    Bold(Vec<Inline>),
+    Italic(Vec<Inline>),
+    Strikethrough(Vec<Inline>),
+    Code(String),
+    Link { text: Vec<Inline>, url: String },
+}
+
+impl RichText {
+    /// Parse the Markdown subset agents produce: fenced code blocks, headings,
+    /// blockquotes, bulleted and numbered lists, pipe tables, horizontal rules,
+    /// and bold, italic, strikethrough, inline code, and links within text.
+    pub fn parse(markdown: &str) -> Self {
+        let lines: Vec<&str> = markdown.lines().collect();
+        let mut blocks = Vec::new();
+        let mut paragraph: Vec<Vec<Inline>> = Vec::new();
+        let mut index = 0;
+
+        while index < lines.len() {
+            let line = lines[index];
+            let trimmed = line.trim();
+
+            let block = if let Some(rest) = trimmed.strip_prefix("```") {
+                let mut code_lines = Vec::new();
+                let mut closed = false;
+                index += 1;
+                while index < lines.len() {
+                    if lines[index].trim_start().starts_with("```") {
+                        closed = true;
+                        break;
+                    }
+                    code_lines.push(lines[index]);
+                    index += 1;
+                }
+                let language = rest.trim();
+                // An unclosed fence is usually a truncated message; don't
+                // trust the language tag of a block we never saw the end of.
+                Some(Block::Code {
+                    language: (closed && !language.is_empty()).then(|| language.to_string()),
+                    text: code_lines.join("\n"),
+                })
+            } else if trimmed.starts_with('>') {
+                let mut quote = Vec::new();
+                while index < lines.len() {
+                    let Some(rest) = lines[index].trim().strip_prefix('>') else {
+                        break;
+                    };
+                    quote.push(parse_inline(rest.strip_prefix(' ').unwrap_or(rest)));
+                    index += 1;
+                }
+                index -= 1;
+                Some(Block::Quote(quote))
+            } else if let Some(heading) = heading_text(trimmed) {
+                Some(Block::Heading(parse_inline(heading)))
+            } else if is_rule(trimmed) {
+                Some(Block::Rule)
+            } else if list_marker(line).is_some() {
+                let mut items = Vec::new();
+                while index < lines.len() {
+                    let Some((depth, number, content)) = list_marker(lines[index]) else {
+                        break;
+                    };
+                    items.push(ListItem {
+                        depth,
+                        number,
+                        content: parse_inline(content),
+                    });
+                    index += 1;
+                }
+                index -= 1;
+                Some(Block::List(items))
+            } else if trimmed.starts_with('|')
+                && lines
+                    .get(index + 1)
+                    .is_some_and(|next| is_table_separator(next.trim()))
+            {
+                let header = table_cells(trimmed);
+                let mut rows = Vec::new();
+                index += 2;
+                while index < lines.len() && lines[index].trim().starts_with('|') {
+                    rows.push(table_cells(lines[index].trim()));
+                    index += 1;
+                }
+                index -= 1;
+                Some(Block::Table { header, rows })
+            } else if trimmed.is_empty() {
+                None
+            } else {
+                paragraph.push(parse_inline(trimmed));
+                index += 1;
+                continue;
+            };
+
+            if !paragraph.is_empty() {
+                blocks.push(Block::Paragraph(std::mem::take(&mut paragraph)));
+            }
+            blocks.extend(block);
+            index += 1;
+        }
+
+        if !paragraph.is_empty() {
+            blocks.push(Block::Paragraph(paragraph));
+        }
+        Self { blocks }
+    }
+
+    /// Render as Slack mrkdwn, for message text and notification fallbacks.
+    pub fn to_mrkdwn(&self) -> String {
+        self.render(|block| match block {
+            Block::Paragraph(lines) => join_lines(lines, "", mrkdwn_inline),
+            Block::Heading(content) => format!("*{}*", mrkdwn_inline(content)),
+            Block::Code { text, .. } => format!("```\n{}\n```", escape_mrkdwn(text)),
+            Block::Quote(lines) => join_lines(lines, "> ", mrkdwn_inline),
+            Block::List(items) => render_list(items, "•", mrkdwn_inline),
+            Block::Table { header, rows } => {
+                format!("```\n{}\n```", escape_mrkdwn(&layout_table(header, rows)))
+            }
+            Block::Rule => RULE.to_string(),
+        })
+    }
+
+    /// Render as HTML for Telegram's `HTML` parse mode.
+    pub fn to_telegram_html(&self) -> String {
+        self.render(|block| match block {
+            Block::Paragraph(lines) => join_lines(lines, "", html_inline),
+            Block::Heading(content) => format!("<b>{}</b>", html_inline(content)),
+            Block::Code {
+                language: Some(language),
+                text,
+            } => format!(
+                "<pre><code class=\"language-{}\">{}</code></pre>",
+                escape_html(language),
+                escape_html(text)
+            ),
+            Block::Code {
+                language: None,
+                text,
+            } => format!("<pre>{}</pre>", escape_html(text)),
+            Block::Quote(lines) => {
+                format!(
+                    "<blockquote>{}</blockquote>",
+                    join_lines(lines, "", html_inline)
+                )
+            }
+            Block::List(items) => render_list(items, "-", html_inline),
+            Block::Table { header, rows } => {
+                format!("<pre>{}</pre>", escape_html(&layout_table(header, rows)))
+            }
+            Block::Rule => RULE.to_string(),
+        })
+    }
+
+    /// Render as plain text. With `width`, prose is word-wrapped to that many
+    /// characters; code blocks and tables are left as-is so they stay aligned.
+    pub fn to_plain_text(&self, width: Option<usize>) -> String {
+        let wrap = |text: &str, first: &str, rest: &str| match width {
+            Some(width) => wrap_text(text, width, first, rest),
+            None => format!("{first}{text}"),
+        };
+        self.render(|block| match block {
+            Block::Paragraph(lines) => lines
+                .iter()
+                .map(|line| wrap(&plain_inline(line), "", ""))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            Block::Heading(content) => wrap(&plain_inline(content), "", ""),
+            Block::Code { text, .. } => text.clone(),
+            Block::Quote(lines) => lines
+                .iter()
+                .map(|line| wrap(&plain_inline(line), "> ", "> "))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            Block::List(items) => items
+                .iter()
+                .map(|item| {
+                    let marker = list_prefix(item, "-");
+                    let hanging = " ".repeat(marker.chars().count());
+                    wrap(&plain_inline(&item.content), &marker, &hanging)
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+            Block::Table { header, rows } => layout_table(header, rows),
+            Block::Rule => RULE.to_string(),
+        })
+    }
+
+    fn render(&self, render_block: impl Fn(&Block) -> String) -> String {
+        self.blocks
+            .iter()
+            .map(render_block)
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+const RULE: &str = "──────────";
+
+fn heading_text(line: &str) -> Option<&str> {
+    let hashes = line.len() - line.trim_start_matches('#').len();
+    if !(1..=6).contains(&hashes) {
+        return None;
+    }
+    line[hashes..].strip_prefix(' ').map(str::trim)
+}
+
+fn is_rule(line: &str) -> bool {
+    line.len() >= 3
+        && ["-", "*", "_"]
+            .iter()
+            .any(|marker| line.chars().all(|c| c.to_string() == *marker))
+}
+
+/// Split a list line into its depth, item number, and content.
+fn list_marker(line: &str) -> Option<(usize, Option<u64>, &str)> {
+    let content = line.trim_start();
+    let depth = (line.len() - content.len()) / 2;
+    for bullet in ["- ", "* ", "+ "] {
+        if let Some(rest) = content.strip_prefix(bullet) {
+            return Some((depth, None, rest.trim()));
+        }
+    }
+    let digits = content.len()
+        - content
+            .trim_start_matches(|c: char| c.is_ascii_digit())
+            .len();
+    if digits == 0 {
+        return None;
+    }
+    let rest = content[digits..]
+        .strip_prefix(". ")
+        .or_else(|| content[digits..].strip_prefix(") "))?;
+    Some((depth, content[..digits].parse().ok(), rest.trim()))
+}
+
+fn is_table_separator(line: &str) -> bool {
+    line.starts_with('|')
+        && line.contains('-')
+        && line.chars().all(|c| matches!(c, '|' | '-' | ':' | ' '))
+}
+
+fn table_cells(line: &str) -> Vec<Vec<Inline>> {
+    let inner = line.trim_start_matches('|');
+    let inner = inner.strip_suffix('|').unwrap_or(inner);
+    inner
+        .split('|')
+        .map(|cell| parse_inline(cell.trim()))
+        .collect()
+}
+
+/// Parse inline spans within a single line.
+fn parse_inline(text: &str) -> Vec<Inline> {
+    let mut spans = Vec::new();
+    let mut plain = String::new();
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        if let Some((span, remainder)) = parse_span(rest, plain.chars().last()) {
+            if !plain.is_empty() {
+                spans.push(Inline::Text(std::mem::take(&mut plain)));
+            }
+            spans.push(span);
+            rest = remainder;
+        } else {
+            plain.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    if !plain.is_empty() {
+        spans.push(Inline::Text(plain));
+    }
+    spans
+}
+
+/// Try to parse a span starting at the beginning of `text`. Returns the span
+/// and the text after it.
+fn parse_span(text: &str, previous: Option<char>) -> Option<(Inline, &str)> {
+    if let Some(rest) = text.strip_prefix('`') {
+        let end = rest.find('`')?;
+        return Some((Inline::Code(rest[..end].to_string()), &rest[end + 1..]));
+    }
+
+    if let Some(rest) = text.strip_prefix('[') {
+        let label_end = rest.find("](")?;
+        let after_label = &rest[label_end + 2..];
+        let url_end = after_label.find(')')?;
+        return Some((
+            Inline::Link {
+                text: parse_inline(&rest[..label_end]),
+                url: after_label[..url_end].to_string(),
+            },
+            &after_label[url_end + 1..],
+        ));
+    }
+
+    // Longer delimiters first so `**` isn't read as two italics.
+    for delimiter in ["***", "**", "__", "~~", "*", "_"] {
+        let Some(rest) = text.strip_prefix(delimiter) else {
+            continue;
+        };
+        // Underscores inside words (snake_case) aren't emphasis.
+        let underscore = delimiter.starts_with('_');
+        if underscore && previous.is_some_and(char::is_alphanumeric) {
+            return None;
+        }
+        if rest.starts_with(char::is_whitespace) {
+            continue;
+        }
+        let Some(end) = rest.find(delimiter).filter(|&end| end > 0) else {
+            continue;
+        };
+        let after = &rest[end + delimiter.len()..];
+        if underscore && after.starts_with(char::is_alphanumeric) {
+            continue;
+        }
+        let content = parse_inline(&rest[..end]);
+        let span = match delimiter {
+            "***" => Inline::Bold(vec![Inline::Italic(content)]),
+            "**" | "__" => Inline::Bold(content),
+            "~~" => Inline::Strikethrough(content),
+            _ => Inline::Italic(content),
+        };
+        return Some((span, after));
+    }
+    None
+}
+
+fn join_lines(lines: &[Vec<Inline>], prefix: &str, render: fn(&[Inline]) -> String) -> String {
+    lines
+        .iter()
+        .map(|line| format!("{prefix}{}", render(line)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn list_prefix(item: &ListItem, bullet: &str) -> String {
+    let indent = "  ".repeat(item.depth);
+    match item.number {
+        Some(number) => format!("{indent}{number}. "),
+        None => format!("{indent}{bullet} "),
+    }
+}
+
+fn render_list(items: &[ListItem], bullet: &str, render: fn(&[Inline]) -> String) -> String {
+    items
+        .iter()
+        .map(|item| format!("{}{}", list_prefix(item, bullet), render(&item.content)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn mrkdwn_inline(spans: &[Inline]) -> String {
+    spans
+        .iter()
+        .map(|span| match span {
+            Inline::Text(text) => escape_mrkdwn(text),
+            Inline::Bold(content) => format!("*{}*", mrkdwn_inline(content)),
+            Inline::Italic(content) => format!("_{}_", mrkdwn_inline(content)),
+            Inline::Strikethrough(content) => format!("~{}~", mrkdwn_inline(content)),
+            Inline::Code(code) => format!("`{}`", escape_mrkdwn(code)),
+            Inline::Link { text, url } => {
+                format!("<{}|{}>", escape_mrkdwn(url), mrkdwn_inline(text))
+            }
+        })
+        .collect()
+}
+
+fn html_inline(spans: &[Inline]) -> String {
+    spans
+        .iter()
+        .map(|span| match span {
+            Inline::Text(text) => escape_html(text),
+            Inline::Bold(content) => format!("<b>{}</b>", html_inline(content)),
+            Inline::Italic(content) => format!("<i>{}</i>", html_inline(content)),
+            Inline::Strikethrough(content) => format!("<s>{}</s>", html_inline(content)),
+            Inline::Code(code) => format!("<code>{}</code>", escape_html(code)),
+            Inline::Link { text, url } => format!(
+                "<a href=\"{}\">{}</a>",
+                escape_html(url).replace('"', "&quot;"),
+                html_inline(text)
+            ),
+        })
+        .collect()
+}
+
+fn plain_inline(spans: &[Inline]) -> String {
+    spans
+        .iter()
+        .map(|span| match span {
+            Inline::Text(text) | Inline::Code(text) => text.clone(),
+            Inline::Bold(content) | Inline::Italic(content) | Inline::Strikethrough(content) => {
+                plain_inline(content)
+            }
+            Inline::Link { text, url } => {
+                let label = plain_inline(text);
+                if label == *url {
+                    label
+                } else {
+                    format!("{label} ({url})")
+                }
+            }
+        })
+        .collect()
+}
+
+/// Slack treats `&`, `<` and `>` as control characters in mrkdwn.
+fn escape_mrkdwn(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Escape characters that have special meaning in Telegram's HTML parse mode.
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Lay out a table as aligned plain-text columns with a rule under the header.
+fn layout_table(header: &[Vec<Inline>], rows: &[Vec<Vec<Inline>>]) -> String {
+    let to_plain =
+        |cells: &[Vec<Inline>]| -> Vec<String> { cells.iter().map(|c| plain_inline(c)).collect() };
+    let header = to_plain(header);
+    let rows: Vec<Vec<String>> = rows.iter().map(|row| to_plain(row)).collect();
+
+    let columns = rows
+        .iter()
+        .map(Vec::len)
+        .chain([header.len()])
+        .max()
+        .unwrap_or(0);
+    let mut widths = vec![0; columns];
+    for row in std::iter::once(&header).chain(&rows) {
+        for (column, cell) in row.iter().enumerate() {
+            widths[column] = widths[column].max(cell.chars().count());
+        }
+    }
+
+    let format_row = |row: &[String]| {
+        widths
+            .iter()
+            .enumerate()
+            .map(|(column, width)| {
+                let cell = row.get(column).map(String::as_str).unwrap_or("");
+                format!("{cell}{}", " ".repeat(width - cell.chars().count()))
+            })
+            .collect::<Vec<_>>()
+            .join(" | ")
+            .trim_end()
+            .to_string()
+    };
+
+    let mut lines = vec![format_row(&header)];
+    lines.push(
+        widths
+            .iter()
+            .map(|width| "-".repeat(*width))
+            .collect::<Vec<_>>()
+            .join("-+-"),
+    );
+    lines.extend(rows.iter().map(|row| format_row(row)));
+    lines.join("\n")
+}
+
+/// Greedy word wrap. `first` prefixes the first line and `rest` the others;
+/// both count toward the width. Words longer than the width get a line of
+/// their own rather than being broken.
+fn wrap_text(text: &str, width: usize, first: &str, rest: &str) -> String {
+    let mut lines = Vec::new();
+    let mut line = first.to_string();
+    let mut line_has_words = false;
+
+    for word in text.split_whitespace() {
+        let fits = line.chars().count() + 1 + word.chars().count() <= width;
+        if line_has_words && !fits {
+            lines.push(std::mem::replace(&mut line, rest.to_string()));
+            line_has_words = false;
+        }
+        if line_has_words {
+            line.push(' ');
+        }
+        line.push_str(word);
+        line_has_words = true;
+    }
+    lines.push(line);
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GOLDEN_INPUT: &str = "\
+## Deploy report
+
+The **api** rollout finished with _one_ warning; see [the runbook](https://example.com/runbook) for `retry_budget` details.
+
+- canary passed
+  - p99 < 200ms & steady
+1. drain old pods
+
+> Ship it ~~today~~ tomorrow.
+
+```rust
+let x = a < b && c > d;
+```
+
+| Service | Status |
+|---------|:------:|
+| api | **ok** |
+| worker-pool | degraded |
+
+---";
+
+    #[test]
+    fn slack_mrkdwn_golden() {
+        let expected = "\
+*Deploy report*
+
+The *api* rollout finished with _one_ warning; see <https://example.com/runbook|the runbook> for `retry_budget` details.
+
+• canary passed
+  • p99 &lt; 200ms &amp; steady
+1. drain old pods
+
+> Ship it ~today~ tomorrow.
+
+```
+let x = a &lt; b &amp;&amp; c &gt; d;
+```
+
+```
+Service     | Status
+------------+---------
+api         | ok
+worker-pool | degraded
+```
+
+──────────";
+        assert_eq!(RichText::parse(GOLDEN_INPUT).to_mrkdwn(), expected);
+    }
+
+    #[test]
+    fn telegram_html_golden() {
+        let expected = "\
+<b>Deploy report</b>
+
+The <b>api</b> rollout finished with <i>one</i> warning; see <a href=\"https://example.com/runbook\">the runbook</a> for <code>retry_budget</code> details.
+
+- canary passed
+  - p99 &lt; 200ms &amp; steady
+1. drain old pods
+
+<blockquote>Ship it <s>today</s> tomorrow.</blockquote>
+
+<pre><code class=\"language-rust\">let x = a &lt; b &amp;&amp; c &gt; d;</code></pre>
+
+<pre>Service     | Status
+------------+---------
+api         | ok
+worker-pool | degraded</pre>
+
+──────────";
+        assert_eq!(RichText::parse(GOLDEN_INPUT).to_telegram_html(), expected);
+    }
+
+    #[test]
+    fn plain_text_golden() {
+        let expected = "\
+Deploy report
+
+The api rollout finished with one
+warning; see the runbook
+(https://example.com/runbook) for
+retry_budget details.
+
+- canary passed
+  - p99 < 200ms & steady
+1. drain old pods
+
+> Ship it today tomorrow.
+
+let x = a < b && c > d;
+
+Service     | Status
+------------+---------
+api         | ok
+worker-pool | degraded
+
+──────────";
+        assert_eq!(
+            RichText::parse(GOLDEN_INPUT).to_plain_text(Some(40)),
+            expected
+        );
+
+        let unwrapped = RichText::parse(GOLDEN_INPUT).to_plain_text(None);
+        assert!(
+            unwrapped.contains(
+                "see the runbook (https://example.com/runbook) for retry_budget details."
+            )
+        );
+    }
+}
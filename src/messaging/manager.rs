@@ -1,24 +1,57 @@
 //! MessagingManager: Fan-in and routing for all adapters.
 
+use crate::config::MessageFilterConfig;
+use crate::messaging::connection::{
+    AdapterConnection, Backoff, ConnectionState, ConnectionStatus, PendingDelivery,
+};
+use crate::messaging::filter;
+use crate::messaging::outbox::{Outbox, OutboxEntry, RetryPolicy};
+use crate::messaging::presence::Presence;
+use crate::messaging::receipts::{self, Receipts};
 use crate::messaging::traits::{HistoryMessage, InboundStream, Messaging, MessagingDyn};
 use crate::{InboundMessage, OutboundResponse, StatusUpdate};
 
 use anyhow::Context as _;
+use arc_swap::ArcSwap;
 use futures::StreamExt as _;
-use std::collections::HashMap;
-use std::sync::Arc;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
 use tokio::sync::{RwLock, mpsc};
 
+/// How often each connected adapter is health-checked.
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Consecutive failed health checks before an adapter is reconnected, so a
+/// single slow API call doesn't tear down a working connection.
+const HEALTH_CHECK_FAILURES: u32 = 2;
+
+/// How often the outbox is checked for replies due a retry.
+const OUTBOX_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How often stale delivery statuses are pruned from the outbox.
+const OUTBOX_PRUNE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
 /// Manages all messaging adapters with support for runtime addition.
 ///
 /// Adapters forward messages into a shared mpsc channel, so new adapters
 /// can be registered after `start()` without replacing the inbound stream.
 pub struct MessagingManager {
     adapters: RwLock<HashMap<String, Arc<dyn MessagingDyn>>>,
+    /// Connection state and held replies per adapter, shared with its supervisor.
+    connections: RwLock<HashMap<String, Arc<AdapterConnection>>>,
     /// Sender side of the fan-in channel. Cloned for each adapter's forwarding task.
     fan_in_tx: mpsc::Sender<InboundMessage>,
     /// Receiver side, taken once by `start()`.
     fan_in_rx: RwLock<Option<mpsc::Receiver<InboundMessage>>>,
+    /// Adapter-level inbound filters, keyed by platform or adapter key.
+    filters: Arc<ArcSwap<HashMap<String, MessageFilterConfig>>>,
+    /// Durable queue for complete replies. Unset when the store failed to
+    /// open, in which case replies are only held in memory during disconnects.
+    outbox: OnceLock<Arc<Outbox>>,
+    /// Outbox entries being sent right now, so the retry task doesn't send
+    /// one that `deliver` is still attempting.
+    outbox_in_flight: std::sync::Mutex<HashSet<String>>,
 }
 
 impl MessagingManager {
@@ -26,8 +59,32 @@ impl MessagingManager {
         let (fan_in_tx, fan_in_rx) = mpsc::channel(512);
         Self {
             adapters: RwLock::new(HashMap::new()),
+            connections: RwLock::new(HashMap::new()),
             fan_in_tx,
             fan_in_rx: RwLock::new(Some(fan_in_rx)),
+            filters: Arc::new(ArcSwap::from_pointee(HashMap::new())),
+            outbox: OnceLock::new(),
+            outbox_in_flight: std::sync::Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Queue complete replies in `outbox` so failed sends are retried, and
+    /// record receipts for them. Call before `start()`.
+    pub fn set_outbox(&self, outbox: Arc<Outbox>) {
+        if self.outbox.set(outbox).is_err() {
+            tracing::warn!("messaging outbox already set, ignoring");
+        }
+    }
+
+    pub fn outbox(&self) -> Option<Arc<Outbox>> {
+        self.outbox.get().cloned()
+    }
+
+    /// Receipts are recorded on outbox delivery statuses, so adapters only
+    /// get a sink when there is an outbox.
+    fn attach_receipts(&self, adapter: &dyn MessagingDyn) {
+        if let Some(outbox) = self.outbox.get() {
+            adapter.set_receipts(Receipts::new(outbox.clone()));
         }
     }
 
@@ -45,33 +102,52 @@ impl MessagingManager {
         self.adapters.write().await.insert(name, adapter);
     }
 
-    /// Maximum number of retry attempts for failed adapters before giving up.
+    /// Maximum number of connect attempts, at startup or after a lost
+    /// connection, before an adapter is marked failed.
     const MAX_RETRY_ATTEMPTS: u32 = 12;
 
     /// Start all registered adapters and return the merged inbound stream.
     ///
     /// Each adapter's stream is forwarded into a shared channel, so adapters
     /// added later via `register_and_start` feed into the same stream.
-    /// Adapters that fail to start (e.g. due to network not being ready) are
-    /// retried in the background with exponential backoff.
+    /// Every adapter gets a supervisor that retries failed starts and
+    /// reconnects lost connections with exponential backoff.
     pub async fn start(&self) -> crate::Result<InboundStream> {
         let adapters = self.adapters.read().await;
         for (name, adapter) in adapters.iter() {
+            let connection = Arc::new(AdapterConnection::new(name.clone()));
+            self.attach_receipts(adapter.as_ref());
             match adapter.start().await {
-                Ok(stream) => Self::spawn_forwarder(name.clone(), stream, self.fan_in_tx.clone()),
+                Ok(stream) => {
+                    let generation = connection.mark_connected();
+                    Self::spawn_forwarder(
+                        name.clone(),
+                        stream,
+                        self.fan_in_tx.clone(),
+                        Arc::clone(&connection),
+                        generation,
+                    );
+                    Self::spawn_command_registration(name.clone(), Arc::clone(adapter));
+                }
                 Err(error) => {
                     tracing::warn!(
                         adapter = %name,
                         %error,
                         "adapter failed to start, will retry in background"
                     );
-                    Self::spawn_retry_task(
-                        name.clone(),
-                        Arc::clone(adapter),
-                        self.fan_in_tx.clone(),
-                    );
+                    connection.record_attempt_failure(&error);
                 }
             }
+            Self::spawn_supervisor(
+                name.clone(),
+                Arc::clone(adapter),
+                Arc::clone(&connection),
+                self.fan_in_tx.clone(),
+            );
+            self.connections
+                .write()
+                .await
+                .insert(name.clone(), connection);
         }
         drop(adapters);
 
@@ -82,9 +158,42 @@ impl MessagingManager {
             .take()
             .context("start() already called")?;
 
-        Ok(Box::pin(tokio_stream::wrappers::ReceiverStream::new(
-            receiver,
-        )))
+        let filters = Arc::clone(&self.filters);
+        let stream = tokio_stream::wrappers::ReceiverStream::new(receiver).filter(move |message| {
+            futures::future::ready(Self::passes_filters(&filters.load(), message))
+        });
+        Ok(Box::pin(stream))
+    }
+
+    /// Replace the inbound filters. Takes effect for the next message.
+    pub fn set_filters(&self, filters: HashMap<String, MessageFilterConfig>) {
+        self.filters.store(Arc::new(filters));
+    }
+
+    fn passes_filters(
+        filters: &HashMap<String, MessageFilterConfig>,
+        message: &InboundMessage,
+    ) -> bool {
+        let adapter = message.adapter_key();
+        let Some(filter) = filter::filter_for(filters, adapter) else {
+            return true;
+        };
+        let Some(reason) = filter::check(filter, message) else {
+            return true;
+        };
+        tracing::debug!(
+            adapter,
+            conversation_id = %message.conversation_id,
+            sender_id = %message.sender_id,
+            reason = reason.as_str(),
+            "inbound message filtered"
+        );
+        #[cfg(feature = "metrics")]
+        crate::telemetry::Metrics::global()
+            .messages_filtered_total
+            .with_label_values(&[adapter, reason.as_str()])
+            .inc();
+        false
     }
 
     /// Register and start a new adapter at runtime.
@@ -97,6 +206,9 @@ impl MessagingManager {
 
         // Shut down existing adapter with the same name if present
         {
+            if let Some(connection) = self.connections.write().await.remove(&name) {
+                connection.mark_stopped();
+            }
             let adapters = self.adapters.read().await;
             if let Some(existing) = adapters.get(&name) {
                 tracing::info!(adapter = %name, "shutting down existing adapter before replacement");
@@ -107,14 +219,34 @@ impl MessagingManager {
         }
 
         let adapter: Arc<dyn MessagingDyn> = Arc::new(adapter);
+        self.attach_receipts(adapter.as_ref());
 
         let stream = adapter
             .start()
             .await
             .with_context(|| format!("failed to start adapter '{name}'"))?;
-        Self::spawn_forwarder(name.clone(), stream, self.fan_in_tx.clone());
+        let connection = Arc::new(AdapterConnection::new(name.clone()));
+        let generation = connection.mark_connected();
+        Self::spawn_forwarder(
+            name.clone(),
+            stream,
+            self.fan_in_tx.clone(),
+            Arc::clone(&connection),
+            generation,
+        );
+        Self::spawn_command_registration(name.clone(), Arc::clone(&adapter));
+        Self::spawn_supervisor(
+            name.clone(),
+            Arc::clone(&adapter),
+            Arc::clone(&connection),
+            self.fan_in_tx.clone(),
+        );
 
         self.adapters.write().await.insert(name.clone(), adapter);
+        self.connections
+            .write()
+            .await
+            .insert(name.clone(), connection);
 
         tracing::info!(adapter = %name, "adapter registered and started at runtime");
         Ok(())
@@ -140,69 +272,395 @@ impl MessagingManager {
         self.adapters.read().await.keys().cloned().collect()
     }
 
-    /// Spawn a background task that retries starting a failed adapter with exponential backoff.
+    /// Connection state for every adapter, sorted by name.
+    pub async fn connection_statuses(&self) -> Vec<ConnectionStatus> {
+        let mut statuses: Vec<_> = self
+            .connections
+            .read()
+            .await
+            .values()
+            .map(|connection| connection.status())
+            .collect();
+        statuses.sort_by(|left, right| left.adapter.cmp(&right.adapter));
+        statuses
+    }
+
+    /// Spawn the task that keeps an adapter connected.
     ///
-    /// Once the adapter starts successfully, its stream is forwarded into the
-    /// existing fan-in channel — the same mechanism used by `register_and_start`.
-    fn spawn_retry_task(
+    /// While connected it health-checks the adapter every
+    /// `HEALTH_CHECK_INTERVAL`. When the connection is lost (repeated failed
+    /// checks, the inbound stream ending, or a send failing while the
+    /// platform is unreachable) it reconnects with exponential backoff and
+    /// then delivers any replies held in the meantime. Exits when the
+    /// adapter is removed or gives up.
+    fn spawn_supervisor(
         name: String,
         adapter: Arc<dyn MessagingDyn>,
+        connection: Arc<AdapterConnection>,
         fan_in_tx: mpsc::Sender<InboundMessage>,
     ) {
         tokio::spawn(async move {
-            let mut delay = std::time::Duration::from_secs(5);
-            let max_delay = std::time::Duration::from_secs(60);
+            let mut failed_checks = 0;
+            loop {
+                match connection.state() {
+                    ConnectionState::Stopped | ConnectionState::Failed => return,
+                    ConnectionState::Connecting | ConnectionState::Reconnecting => {
+                        if !Self::reconnect(&name, &adapter, &connection, &fan_in_tx).await {
+                            return;
+                        }
+                        failed_checks = 0;
+                        continue;
+                    }
+                    ConnectionState::Connected => {}
+                }
+
+                tokio::select! {
+                    _ = tokio::time::sleep(HEALTH_CHECK_INTERVAL) => {}
+                    _ = connection.wake.notified() => continue,
+                }
+                if connection.state() != ConnectionState::Connected {
+                    continue;
+                }
 
-            for attempt in 1..=Self::MAX_RETRY_ATTEMPTS {
-                tokio::time::sleep(delay).await;
+                #[cfg(feature = "chaos")]
+                if crate::chaos::drop_connection() {
+                    tracing::warn!(adapter = %name, "chaos: dropping adapter connection");
+                    connection.mark_disconnected("connection dropped by fault injection");
+                    continue;
+                }
 
-                match adapter.start().await {
-                    Ok(stream) => {
-                        tracing::info!(
-                            adapter = %name,
-                            attempt,
-                            "adapter started successfully after retry"
-                        );
-                        Self::spawn_forwarder(name, stream, fan_in_tx);
-                        return;
-                    }
+                match adapter.health_check().await {
+                    Ok(()) => failed_checks = 0,
                     Err(error) => {
+                        failed_checks += 1;
                         tracing::warn!(
                             adapter = %name,
-                            attempt,
-                            max_attempts = Self::MAX_RETRY_ATTEMPTS,
                             %error,
-                            "adapter retry failed, next attempt in {:?}",
-                            delay.min(max_delay)
+                            failed_checks,
+                            "adapter health check failed"
                         );
+                        if failed_checks >= HEALTH_CHECK_FAILURES {
+                            connection.mark_disconnected(&error);
+                        }
                     }
                 }
+            }
+        });
+    }
+
+    /// Try to (re)start an adapter with exponential backoff. Returns false
+    /// if the adapter was removed or every attempt failed.
+    async fn reconnect(
+        name: &str,
+        adapter: &Arc<dyn MessagingDyn>,
+        connection: &Arc<AdapterConnection>,
+        fan_in_tx: &mpsc::Sender<InboundMessage>,
+    ) -> bool {
+        // A start that never succeeded has nothing to tear down.
+        let initial = connection.state() == ConnectionState::Connecting;
+        if !initial {
+            tracing::warn!(adapter = %name, "adapter connection lost, reconnecting");
+        }
+        let mut backoff = Backoff::new(Duration::from_secs(5), Duration::from_secs(300));
 
-                delay = (delay * 2).min(max_delay);
+        for attempt in 1..=Self::MAX_RETRY_ATTEMPTS {
+            let delay = backoff.next_delay();
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {}
+                _ = connection.wake.notified() => {}
+            }
+            if connection.state() == ConnectionState::Stopped {
+                return false;
             }
 
-            tracing::error!(
-                adapter = %name,
-                "adapter failed to start after {} attempts, giving up",
-                Self::MAX_RETRY_ATTEMPTS
-            );
+            let result = if initial {
+                adapter.start().await
+            } else {
+                adapter.reconnect().await
+            };
+            match result {
+                Ok(stream) => {
+                    if connection.state() == ConnectionState::Stopped {
+                        adapter.shutdown().await.ok();
+                        return false;
+                    }
+                    let generation = connection.mark_connected();
+                    tracing::info!(adapter = %name, attempt, "adapter connected after retry");
+                    Self::spawn_forwarder(
+                        name.to_string(),
+                        stream,
+                        fan_in_tx.clone(),
+                        Arc::clone(connection),
+                        generation,
+                    );
+                    Self::spawn_command_registration(name.to_string(), Arc::clone(adapter));
+                    Self::flush_buffered(name, adapter.as_ref(), connection).await;
+                    return true;
+                }
+                Err(error) => {
+                    connection.record_attempt_failure(&error);
+                    tracing::warn!(
+                        adapter = %name,
+                        attempt,
+                        max_attempts = Self::MAX_RETRY_ATTEMPTS,
+                        %error,
+                        "adapter connect attempt failed"
+                    );
+                }
+            }
+        }
+
+        tracing::error!(
+            adapter = %name,
+            "adapter failed to connect after {} attempts, giving up",
+            Self::MAX_RETRY_ATTEMPTS
+        );
+        connection.mark_failed();
+        false
+    }
+
+    /// Deliver replies held while the adapter was disconnected, oldest first.
+    async fn flush_buffered(
+        name: &str,
+        adapter: &dyn MessagingDyn,
+        connection: &AdapterConnection,
+    ) {
+        let pending = connection.drain();
+        if pending.is_empty() {
+            return;
+        }
+        tracing::info!(adapter = %name, count = pending.len(), "delivering replies held during disconnect");
+        for delivery in pending {
+            if let Err(error) = delivery.send(adapter).await {
+                tracing::warn!(adapter = %name, %error, "failed to deliver held reply");
+            }
+        }
+    }
+
+    /// Send through an adapter. While it is reconnecting, complete replies
+    /// are held and delivered once it is back. A failed send also holds the
+    /// reply if the platform turns out to be unreachable. With an outbox,
+    /// complete replies are queued durably and failed sends retried; see
+    /// [`outbox`](super::outbox).
+    async fn deliver(
+        &self,
+        adapter_name: &str,
+        delivery: PendingDelivery,
+        message_id: Option<String>,
+    ) -> crate::Result<()> {
+        let adapter = self
+            .adapters
+            .read()
+            .await
+            .get(adapter_name)
+            .cloned()
+            .with_context(|| format!("no messaging adapter named '{adapter_name}'"))?;
+        if let Some(outbox) = self.outbox.get()
+            && delivery.is_bufferable()
+        {
+            let entry = outbox.enqueue(adapter_name, delivery, message_id)?;
+            self.attempt_outboxed(outbox, adapter.as_ref(), entry).await;
+            return Ok(());
+        }
+        let connection = self.connections.read().await.get(adapter_name).cloned();
+        let Some(connection) = connection.filter(|_| delivery.is_bufferable()) else {
+            return delivery.send(adapter.as_ref()).await;
+        };
+
+        if matches!(
+            connection.state(),
+            ConnectionState::Connecting | ConnectionState::Reconnecting
+        ) {
+            tracing::info!(adapter = %adapter_name, "adapter disconnected, holding reply until it reconnects");
+            connection.buffer(delivery);
+            return Ok(());
+        }
+
+        let retained = delivery.clone();
+        let Err(error) = delivery.send(adapter.as_ref()).await else {
+            return Ok(());
+        };
+        // Most send failures (permissions, bad targets) aren't connection
+        // problems; only hold the reply if the platform is unreachable.
+        if adapter.health_check().await.is_ok() {
+            return Err(error);
+        }
+        tracing::warn!(adapter = %adapter_name, %error, "send failed on a lost connection, holding reply");
+        connection.mark_disconnected(&error);
+        connection.buffer(retained);
+        Ok(())
+    }
+
+    /// Try to send a queued reply. While the adapter is reconnecting the
+    /// entry stays queued without using up an attempt; a failed send on a
+    /// healthy connection schedules a retry, or dead-letters the reply once
+    /// its platform's attempts are exhausted.
+    async fn attempt_outboxed(
+        &self,
+        outbox: &Outbox,
+        adapter: &dyn MessagingDyn,
+        entry: OutboxEntry,
+    ) {
+        let id = entry.id.clone();
+        if !self.in_flight().insert(id.clone()) {
+            return;
+        }
+        self.send_outboxed(outbox, adapter, entry).await;
+        self.in_flight().remove(&id);
+    }
+
+    fn in_flight(&self) -> std::sync::MutexGuard<'_, HashSet<String>> {
+        self.outbox_in_flight
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    async fn send_outboxed(&self, outbox: &Outbox, adapter: &dyn MessagingDyn, entry: OutboxEntry) {
+        let connection = self.connections.read().await.get(&entry.adapter).cloned();
+        if let Some(connection) = &connection
+            && connection.state() != ConnectionState::Connected
+        {
+            return;
+        }
+
+        let sent = receipts::sending(
+            entry.message_id.clone(),
+            entry.delivery.clone().send(adapter),
+        );
+        let Err(error) = sent.await else {
+            if entry.attempts > 0 {
+                record_outbox(&entry.adapter, "recovered");
+            }
+            if let Err(error) = outbox.mark_delivered(&entry) {
+                tracing::warn!(adapter = %entry.adapter, %error, "failed to record outbox delivery");
+            }
+            return;
+        };
+        if let Some(connection) = &connection
+            && adapter.health_check().await.is_err()
+        {
+            tracing::warn!(adapter = %entry.adapter, %error, "send failed on a lost connection, holding reply");
+            connection.mark_disconnected(&error);
+            return;
+        }
+
+        let policy = RetryPolicy::for_adapter(&entry.adapter);
+        match outbox.record_failure(entry, &error.to_string(), &policy) {
+            Ok(entry) if entry.dead => {
+                tracing::error!(
+                    adapter = %entry.adapter,
+                    outbox_id = %entry.id,
+                    attempts = entry.attempts,
+                    %error,
+                    "giving up on reply, moved to dead letters"
+                );
+                record_outbox(&entry.adapter, "dead");
+            }
+            Ok(entry) => {
+                tracing::warn!(
+                    adapter = %entry.adapter,
+                    outbox_id = %entry.id,
+                    attempts = entry.attempts,
+                    next_attempt_at = %entry.next_attempt_at,
+                    %error,
+                    "failed to send reply, will retry"
+                );
+                record_outbox(&entry.adapter, "retry");
+            }
+            Err(store_error) => {
+                tracing::error!(%store_error, %error, "failed to record outbox send failure");
+            }
+        }
+    }
+
+    /// Spawn the task that retries queued replies as they come due and
+    /// dead-letters ones that waited too long. Exits once the manager is
+    /// dropped. Does nothing without an outbox.
+    pub fn spawn_outbox_worker(self: &Arc<Self>) {
+        let Some(outbox) = self.outbox() else {
+            return;
+        };
+        let manager = Arc::downgrade(self);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(OUTBOX_POLL_INTERVAL);
+            let mut last_prune: Option<std::time::Instant> = None;
+            loop {
+                interval.tick().await;
+                let Some(manager) = manager.upgrade() else {
+                    return;
+                };
+                manager.retry_due(&outbox).await;
+
+                if last_prune.is_none_or(|at| at.elapsed() >= OUTBOX_PRUNE_INTERVAL) {
+                    last_prune = Some(std::time::Instant::now());
+                    match outbox.prune(chrono::Utc::now()) {
+                        Ok(0) => {}
+                        Ok(pruned) => tracing::debug!(pruned, "pruned old delivery statuses"),
+                        Err(error) => tracing::warn!(%error, "failed to prune delivery statuses"),
+                    }
+                }
+            }
         });
     }
 
+    async fn retry_due(&self, outbox: &Outbox) {
+        let now = chrono::Utc::now();
+        let due = match outbox.due(now) {
+            Ok(due) => due,
+            Err(error) => {
+                tracing::warn!(%error, "failed to read outbox");
+                return;
+            }
+        };
+        for entry in due {
+            let policy = RetryPolicy::for_adapter(&entry.adapter);
+            if entry.is_expired(&policy, now) {
+                tracing::warn!(adapter = %entry.adapter, outbox_id = %entry.id, "reply waited too long, moved to dead letters");
+                record_outbox(&entry.adapter, "dead");
+                if let Err(error) = outbox.kill(entry, "expired before it could be delivered") {
+                    tracing::warn!(%error, "failed to dead-letter expired reply");
+                }
+                continue;
+            }
+            let adapter = self.adapters.read().await.get(&entry.adapter).cloned();
+            let Some(adapter) = adapter else {
+                // The adapter may come back with a config reload; the reply
+                // expires if it doesn't.
+                continue;
+            };
+            self.attempt_outboxed(outbox, adapter.as_ref(), entry).await;
+        }
+    }
+
     /// Spawn a task that forwards messages from an adapter stream into the fan-in channel.
     fn spawn_forwarder(
         name: String,
         mut stream: InboundStream,
         fan_in_tx: mpsc::Sender<InboundMessage>,
+        connection: Arc<AdapterConnection>,
+        generation: u64,
     ) {
         tokio::spawn(async move {
             while let Some(message) = stream.next().await {
                 if fan_in_tx.send(message).await.is_err() {
                     tracing::warn!(adapter = %name, "fan-in channel closed, stopping forwarder");
-                    break;
+                    return;
                 }
             }
             tracing::info!(adapter = %name, "adapter stream ended");
+            connection.stream_ended(generation);
+        });
+    }
+
+    /// Register the built-in slash commands with a freshly started adapter.
+    ///
+    /// Runs in the background so a slow platform API never delays startup.
+    fn spawn_command_registration(name: String, adapter: Arc<dyn MessagingDyn>) {
+        tokio::spawn(async move {
+            let commands = crate::messaging::commands::builtin_commands();
+            if let Err(error) = adapter.register_commands(&commands).await {
+                tracing::warn!(adapter = %name, %error, "failed to register slash commands");
+            }
         });
     }
 
@@ -220,12 +678,23 @@ impl MessagingManager {
         message: &InboundMessage,
         response: OutboundResponse,
     ) -> crate::Result<()> {
-        let adapters = self.adapters.read().await;
-        let adapter_key = message.adapter_key();
-        let adapter = adapters
-            .get(adapter_key)
-            .with_context(|| format!("no messaging adapter named '{}'", adapter_key))?;
-        adapter.respond(message, response).await
+        self.respond_logged(message, response, None).await
+    }
+
+    /// Like `respond`, for a reply logged as conversation message
+    /// `message_id`. Its delivery status is recorded under that ID.
+    pub async fn respond_logged(
+        &self,
+        message: &InboundMessage,
+        response: OutboundResponse,
+        message_id: Option<String>,
+    ) -> crate::Result<()> {
+        let delivery = PendingDelivery::Respond {
+            message: Box::new(message.clone()),
+            response,
+        };
+        self.deliver(message.adapter_key(), delivery, message_id)
+            .await
     }
 
     /// Route a status update to the correct adapter.
@@ -249,11 +718,11 @@ impl MessagingManager {
         target: &str,
         response: OutboundResponse,
     ) -> crate::Result<()> {
-        let adapters = self.adapters.read().await;
-        let adapter = adapters
-            .get(adapter_name)
-            .with_context(|| format!("no messaging adapter named '{adapter_name}'"))?;
-        adapter.broadcast(target, response).await
+        let delivery = PendingDelivery::Broadcast {
+            target: target.to_string(),
+            response,
+        };
+        self.deliver(adapter_name, delivery, None).await
     }
 
     /// Fetch recent message history from the platform for context backfill.
@@ -272,6 +741,9 @@ impl MessagingManager {
 
     /// Remove and shut down a single adapter by name.
     pub async fn remove_adapter(&self, name: &str) -> crate::Result<()> {
+        if let Some(connection) = self.connections.write().await.remove(name) {
+            connection.mark_stopped();
+        }
         let adapter = self.adapters.write().await.remove(name);
         if let Some(adapter) = adapter {
             adapter.shutdown().await?;
@@ -300,8 +772,21 @@ impl MessagingManager {
         Ok(())
     }
 
+    /// Update bot presence on every adapter that supports it.
+    pub async fn set_presence(&self, presence: &Presence) {
+        let adapters = self.adapters.read().await;
+        for (name, adapter) in adapters.iter() {
+            if let Err(error) = adapter.set_presence(presence).await {
+                tracing::warn!(adapter = %name, %error, "failed to update presence");
+            }
+        }
+    }
+
     /// Shut down all adapters gracefully.
     pub async fn shutdown(&self) {
+        for connection in self.connections.read().await.values() {
+            connection.mark_stopped();
+        }
         let adapters = self.adapters.read().await;
         for (name, adapter) in adapters.iter() {
             if let Err(error) = adapter.shutdown().await {
@@ -311,6 +796,18 @@ impl MessagingManager {
     }
 }
 
+#[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+fn record_outbox(adapter: &str, result: &str) {
+    #[cfg(feature = "metrics")]
+    {
+        let platform = adapter.split(':').next().unwrap_or(adapter);
+        crate::telemetry::Metrics::global()
+            .messaging_outbox_total
+            .with_label_values(&[platform, result])
+            .inc();
+    }
+}
+
 impl Default for MessagingManager {
     fn default() -> Self {
         Self::new()
@@ -0,0 +1,737 @@
+//! Durable outbound queue with per-platform retry and dead-lettering.
+//!
+//! Complete replies are written to `data/outbox.redb` before they are sent.
+//! A send that fails on a healthy connection (a rate limit, a transient 5xx)
+//! is retried with the platform's backoff instead of being dropped, and a
+//! reply held while its adapter reconnects survives a restart. A reply that
+//! runs out of attempts, or waits longer than the platform's maximum age,
+//! moves to the dead-letter state: `GET /api/outbox/dead` lists it and
+//! `POST /api/outbox/{id}/retry` queues it again.
+//!
+//! Replies sent for a logged conversation message also record a delivery
+//! status under that message's ID, which the channel timeline attaches to
+//! the message. On platforms that report receipts, the status also carries
+//! when the reply reached the user's device and when they read it; see
+//! [`receipts`](super::receipts).
+
+use crate::OutboundResponse;
+use crate::error::Result;
+use crate::messaging::connection::PendingDelivery;
+use crate::messaging::receipts::ReceiptKind;
+
+use anyhow::Context as _;
+use chrono::{DateTime, Utc};
+use redb::{Database, ReadableTable as _, TableDefinition};
+use serde::{Deserialize, Serialize};
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+/// Table: entry ID -> JSON-encoded `OutboxEntry`. Delivered entries are removed.
+const ENTRIES_TABLE: TableDefinition<&str, &[u8]> = TableDefinition::new("outbox_entries");
+
+/// Table: conversation message ID -> JSON-encoded `DeliveryStatus`.
+const STATUS_TABLE: TableDefinition<&str, &[u8]> = TableDefinition::new("outbox_delivery_status");
+
+/// Table: `adapter` + newline + platform message ID -> JSON-encoded
+/// `TrackedMessage`, for resolving receipts.
+const RECEIPTS_TABLE: TableDefinition<&str, &[u8]> = TableDefinition::new("outbox_receipts");
+
+/// Delivery statuses and receipt mappings older than this are pruned.
+const STATUS_RETENTION_DAYS: i64 = 7;
+
+/// Characters of the reply shown in dead-letter listings.
+const PREVIEW_CHARS: usize = 200;
+
+/// How persistently replies through one platform are retried.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct RetryPolicy {
+    /// Attempts before the reply is dead-lettered, including the first.
+    pub(super) max_attempts: u32,
+    /// Delay before the first retry; doubles with each failure.
+    pub(super) initial_backoff: Duration,
+    pub(super) max_backoff: Duration,
+    /// Replies still undelivered this long after they were queued are
+    /// dead-lettered; the conversation has moved on.
+    pub(super) max_age: Duration,
+}
+
+impl RetryPolicy {
+    pub(super) fn for_adapter(adapter: &str) -> Self {
+        let platform = adapter.split(':').next().unwrap_or(adapter);
+        match platform {
+            // Chat rate limits clear within seconds to a minute.
+            "discord" | "slack" | "telegram" | "twitch" | "signal" | "twilio" => Self {
+                max_attempts: 6,
+                initial_backoff: Duration::from_secs(2),
+                max_backoff: Duration::from_secs(60),
+                max_age: Duration::from_secs(15 * 60),
+            },
+            // Mail servers defer for minutes, and a late reply is still useful.
+            "email" => Self {
+                max_attempts: 8,
+                initial_backoff: Duration::from_secs(60),
+                max_backoff: Duration::from_secs(30 * 60),
+                max_age: Duration::from_secs(24 * 60 * 60),
+            },
+            _ => Self {
+                max_attempts: 5,
+                initial_backoff: Duration::from_secs(5),
+                max_backoff: Duration::from_secs(5 * 60),
+                max_age: Duration::from_secs(60 * 60),
+            },
+        }
+    }
+
+    /// Delay after the `failures`-th failed attempt.
+    pub(super) fn backoff(&self, failures: u32) -> Duration {
+        let factor = 2u32.saturating_pow(failures.saturating_sub(1).min(16));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+/// Where a reply's delivery stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryState {
+    /// Queued, not yet attempted or held while the adapter reconnects.
+    Pending,
+    /// At least one attempt failed; another is scheduled.
+    Retrying,
+    Delivered,
+    /// Gave up; listed at `GET /api/outbox/dead`.
+    Dead,
+}
+
+/// Delivery status of a logged conversation message.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct DeliveryStatus {
+    pub state: DeliveryState,
+    pub attempts: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    pub updated_at: DateTime<Utc>,
+    /// The platform reports receipts for this reply, so a missing `read_at`
+    /// means the user hasn't opened it.
+    #[serde(default)]
+    pub receipts: bool,
+    /// When the reply reached the user's device, per the platform.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delivered_at: Option<DateTime<Utc>>,
+    /// When the user read the reply, per the platform.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub read_at: Option<DateTime<Utc>>,
+}
+
+impl DeliveryStatus {
+    fn pending() -> Self {
+        Self {
+            state: DeliveryState::Pending,
+            attempts: 0,
+            last_error: None,
+            updated_at: Utc::now(),
+            receipts: false,
+            delivered_at: None,
+            read_at: None,
+        }
+    }
+}
+
+/// A platform message sent for a logged conversation message.
+#[derive(Serialize, Deserialize)]
+struct TrackedMessage {
+    message_id: String,
+    sent_at: DateTime<Utc>,
+}
+
+/// A queued reply.
+#[derive(Clone, Serialize, Deserialize)]
+pub(super) struct OutboxEntry {
+    pub(super) id: String,
+    pub(super) adapter: String,
+    /// Conversation message this reply delivers, if it was logged.
+    pub(super) message_id: Option<String>,
+    pub(super) delivery: PendingDelivery,
+    pub(super) dead: bool,
+    pub(super) attempts: u32,
+    pub(super) last_error: Option<String>,
+    pub(super) created_at: DateTime<Utc>,
+    pub(super) updated_at: DateTime<Utc>,
+    pub(super) next_attempt_at: DateTime<Utc>,
+}
+
+impl OutboxEntry {
+    fn status(&self) -> DeliveryStatus {
+        let state = if self.dead {
+            DeliveryState::Dead
+        } else if self.attempts > 0 {
+            DeliveryState::Retrying
+        } else {
+            DeliveryState::Pending
+        };
+        DeliveryStatus {
+            state,
+            attempts: self.attempts,
+            last_error: self.last_error.clone(),
+            updated_at: self.updated_at,
+            ..DeliveryStatus::pending()
+        }
+    }
+
+    /// Whether the entry has waited longer than its platform allows.
+    pub(super) fn is_expired(&self, policy: &RetryPolicy, now: DateTime<Utc>) -> bool {
+        let max_age = chrono::Duration::from_std(policy.max_age).unwrap_or(chrono::Duration::MAX);
+        now - self.created_at > max_age
+    }
+}
+
+/// A reply that was given up on.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct DeadLetter {
+    pub id: String,
+    pub adapter: String,
+    /// Conversation the reply was for, or the broadcast target.
+    pub target: String,
+    pub message_id: Option<String>,
+    /// Start of the reply's text.
+    pub preview: String,
+    pub attempts: u32,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub dead_at: DateTime<Utc>,
+}
+
+impl From<&OutboxEntry> for DeadLetter {
+    fn from(entry: &OutboxEntry) -> Self {
+        let preview = match entry.delivery.response() {
+            OutboundResponse::Text(text)
+            | OutboundResponse::ThreadReply { text, .. }
+            | OutboundResponse::RichMessage { text, .. } => text.clone(),
+            OutboundResponse::File { filename, .. } => format!("[file: {filename}]"),
+            _ => String::new(),
+        };
+        Self {
+            id: entry.id.clone(),
+            adapter: entry.adapter.clone(),
+            target: entry.delivery.target().to_string(),
+            message_id: entry.message_id.clone(),
+            preview: preview.chars().take(PREVIEW_CHARS).collect(),
+            attempts: entry.attempts,
+            last_error: entry.last_error.clone(),
+            created_at: entry.created_at,
+            dead_at: entry.updated_at,
+        }
+    }
+}
+
+/// Persistent queue of outbound replies, shared by every adapter.
+pub struct Outbox {
+    db: Database,
+}
+
+impl std::fmt::Debug for Outbox {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Outbox").finish_non_exhaustive()
+    }
+}
+
+impl Outbox {
+    /// Open or create the queue at `path`.
+    pub fn open(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).context("failed to create outbox directory")?;
+        }
+        let db = Database::create(path).context("failed to open outbox")?;
+        let write_txn = db.begin_write().context("failed to begin write txn")?;
+        write_txn
+            .open_table(ENTRIES_TABLE)
+            .context("failed to open outbox table")?;
+        write_txn
+            .open_table(STATUS_TABLE)
+            .context("failed to open delivery status table")?;
+        write_txn
+            .open_table(RECEIPTS_TABLE)
+            .context("failed to open receipts table")?;
+        write_txn.commit().context("failed to commit write txn")?;
+        Ok(Self { db })
+    }
+
+    /// Queue a reply, due immediately.
+    pub(super) fn enqueue(
+        &self,
+        adapter: &str,
+        delivery: PendingDelivery,
+        message_id: Option<String>,
+    ) -> Result<OutboxEntry> {
+        let now = Utc::now();
+        let entry = OutboxEntry {
+            id: uuid::Uuid::new_v4().to_string(),
+            adapter: adapter.to_string(),
+            message_id,
+            delivery,
+            dead: false,
+            attempts: 0,
+            last_error: None,
+            created_at: now,
+            updated_at: now,
+            next_attempt_at: now,
+        };
+        self.write(&entry)?;
+        Ok(entry)
+    }
+
+    /// Live entries due for an attempt at `now`, oldest first.
+    pub(super) fn due(&self, now: DateTime<Utc>) -> Result<Vec<OutboxEntry>> {
+        let mut due: Vec<OutboxEntry> = self
+            .entries()?
+            .into_iter()
+            .filter(|entry| !entry.dead && entry.next_attempt_at <= now)
+            .collect();
+        due.sort_by_key(|entry| entry.created_at);
+        Ok(due)
+    }
+
+    /// Record a successful send and drop the entry.
+    pub(super) fn mark_delivered(&self, entry: &OutboxEntry) -> Result<()> {
+        let write_txn = self.db.begin_write().context("failed to begin write txn")?;
+        {
+            let mut entries = write_txn
+                .open_table(ENTRIES_TABLE)
+                .context("failed to open outbox table")?;
+            entries
+                .remove(entry.id.as_str())
+                .context("failed to remove outbox entry")?;
+            if let Some(message_id) = &entry.message_id {
+                let mut statuses = write_txn
+                    .open_table(STATUS_TABLE)
+                    .context("failed to open delivery status table")?;
+                // Keep receipt fields: the adapter tracks the message while
+                // sending, and a fast receipt can land before this.
+                let previous = read_status(&statuses, message_id)?;
+                let status = DeliveryStatus {
+                    state: DeliveryState::Delivered,
+                    attempts: entry.attempts + 1,
+                    last_error: None,
+                    updated_at: Utc::now(),
+                    ..previous.unwrap_or_else(DeliveryStatus::pending)
+                };
+                statuses
+                    .insert(message_id.as_str(), encode(&status)?.as_slice())
+                    .context("failed to write delivery status")?;
+            }
+        }
+        write_txn.commit().context("failed to commit outbox")?;
+        Ok(())
+    }
+
+    /// Record a failed attempt and schedule the next one, or dead-letter the
+    /// entry once `policy` is exhausted. Returns the updated entry.
+    pub(super) fn record_failure(
+        &self,
+        mut entry: OutboxEntry,
+        error: &str,
+        policy: &RetryPolicy,
+    ) -> Result<OutboxEntry> {
+        let now = Utc::now();
+        entry.attempts += 1;
+        entry.last_error = Some(error.to_string());
+        entry.updated_at = now;
+        if entry.attempts >= policy.max_attempts {
+            entry.dead = true;
+        } else {
+            let backoff = chrono::Duration::from_std(policy.backoff(entry.attempts))
+                .unwrap_or(chrono::Duration::MAX);
+            entry.next_attempt_at = now + backoff;
+        }
+        self.write(&entry)?;
+        Ok(entry)
+    }
+
+    /// Dead-letter an entry without another attempt.
+    pub(super) fn kill(&self, mut entry: OutboxEntry, reason: &str) -> Result<()> {
+        entry.dead = true;
+        entry.last_error = Some(reason.to_string());
+        entry.updated_at = Utc::now();
+        self.write(&entry)
+    }
+
+    /// Every dead-lettered reply, most recent first.
+    pub fn dead_letters(&self) -> Result<Vec<DeadLetter>> {
+        let mut dead: Vec<DeadLetter> = self
+            .entries()?
+            .iter()
+            .filter(|entry| entry.dead)
+            .map(DeadLetter::from)
+            .collect();
+        dead.sort_by_key(|entry| std::cmp::Reverse(entry.dead_at));
+        Ok(dead)
+    }
+
+    /// Queue a dead-lettered reply again with a fresh set of attempts.
+    /// Returns false if there is no dead entry with that ID.
+    pub fn retry(&self, id: &str) -> Result<bool> {
+        let Some(mut entry) = self.entry(id)? else {
+            return Ok(false);
+        };
+        if !entry.dead {
+            return Ok(false);
+        }
+        let now = Utc::now();
+        entry.dead = false;
+        entry.attempts = 0;
+        entry.created_at = now;
+        entry.updated_at = now;
+        entry.next_attempt_at = now;
+        self.write(&entry)?;
+        Ok(true)
+    }
+
+    /// Delivery statuses for whichever of `message_ids` have one.
+    pub fn statuses(&self, message_ids: &[&str]) -> Result<HashMap<String, DeliveryStatus>> {
+        let read_txn = self.db.begin_read().context("failed to begin read txn")?;
+        let table = read_txn
+            .open_table(STATUS_TABLE)
+            .context("failed to open delivery status table")?;
+        let mut statuses = HashMap::new();
+        for message_id in message_ids {
+            let Some(value) = table
+                .get(*message_id)
+                .context("failed to read delivery status")?
+            else {
+                continue;
+            };
+            match serde_json::from_slice::<DeliveryStatus>(value.value()) {
+                Ok(status) => {
+                    statuses.insert(message_id.to_string(), status);
+                }
+                Err(error) => {
+                    tracing::warn!(%error, %message_id, "skipping unreadable delivery status")
+                }
+            }
+        }
+        Ok(statuses)
+    }
+
+    /// Link platform message `platform_message_id` sent through `adapter`
+    /// to conversation message `message_id`, and mark the message's status
+    /// as one that will receive receipts.
+    pub(super) fn track_receipts(
+        &self,
+        adapter: &str,
+        platform_message_id: &str,
+        message_id: &str,
+    ) -> Result<()> {
+        let tracked = TrackedMessage {
+            message_id: message_id.to_string(),
+            sent_at: Utc::now(),
+        };
+        let write_txn = self.db.begin_write().context("failed to begin write txn")?;
+        {
+            let mut receipts = write_txn
+                .open_table(RECEIPTS_TABLE)
+                .context("failed to open receipts table")?;
+            receipts
+                .insert(
+                    receipt_key(adapter, platform_message_id).as_str(),
+                    encode(&tracked)?.as_slice(),
+                )
+                .context("failed to write receipt mapping")?;
+            let mut statuses = write_txn
+                .open_table(STATUS_TABLE)
+                .context("failed to open delivery status table")?;
+            let mut status =
+                read_status(&statuses, message_id)?.unwrap_or_else(DeliveryStatus::pending);
+            status.receipts = true;
+            statuses
+                .insert(message_id, encode(&status)?.as_slice())
+                .context("failed to write delivery status")?;
+        }
+        write_txn.commit().context("failed to commit outbox")?;
+        Ok(())
+    }
+
+    /// Apply a receipt for a tracked platform message. Returns false if the
+    /// message wasn't tracked.
+    pub(super) fn record_receipt(
+        &self,
+        adapter: &str,
+        platform_message_id: &str,
+        kind: ReceiptKind,
+        at: DateTime<Utc>,
+    ) -> Result<bool> {
+        let write_txn = self.db.begin_write().context("failed to begin write txn")?;
+        let recorded = {
+            let receipts = write_txn
+                .open_table(RECEIPTS_TABLE)
+                .context("failed to open receipts table")?;
+            let tracked = receipts
+                .get(receipt_key(adapter, platform_message_id).as_str())
+                .context("failed to read receipt mapping")?
+                .map(|value| serde_json::from_slice::<TrackedMessage>(value.value()))
+                .transpose()
+                .context("failed to decode receipt mapping")?;
+            let mut statuses = write_txn
+                .open_table(STATUS_TABLE)
+                .context("failed to open delivery status table")?;
+            let status = match &tracked {
+                Some(tracked) => read_status(&statuses, &tracked.message_id)?,
+                None => None,
+            };
+            match (tracked, status) {
+                (Some(tracked), Some(mut status)) => {
+                    // A read implies delivery; platforms don't always send both.
+                    status.delivered_at.get_or_insert(at);
+                    if kind == ReceiptKind::Read {
+                        status.read_at.get_or_insert(at);
+                    }
+                    statuses
+                        .insert(tracked.message_id.as_str(), encode(&status)?.as_slice())
+                        .context("failed to write delivery status")?;
+                    true
+                }
+                _ => false,
+            }
+        };
+        write_txn.commit().context("failed to commit outbox")?;
+        Ok(recorded)
+    }
+
+    /// Replies sent before `sent_before` on platforms that report receipts
+    /// and not read yet, keyed by conversation message ID.
+    pub fn unread(&self, sent_before: DateTime<Utc>) -> Result<Vec<(String, DeliveryStatus)>> {
+        let read_txn = self.db.begin_read().context("failed to begin read txn")?;
+        let table = read_txn
+            .open_table(STATUS_TABLE)
+            .context("failed to open delivery status table")?;
+        let mut unread = Vec::new();
+        for item in table.iter().context("failed to scan delivery statuses")? {
+            let (key, value) = item.context("failed to read delivery status")?;
+            let Ok(status) = serde_json::from_slice::<DeliveryStatus>(value.value()) else {
+                continue;
+            };
+            if status.receipts
+                && status.state == DeliveryState::Delivered
+                && status.read_at.is_none()
+                && status.updated_at < sent_before
+            {
+                unread.push((key.value().to_string(), status));
+            }
+        }
+        Ok(unread)
+    }
+
+    /// Drop delivery statuses older than the retention window. Returns how
+    /// many were removed.
+    pub(super) fn prune(&self, now: DateTime<Utc>) -> Result<usize> {
+        let cutoff = now - chrono::Duration::days(STATUS_RETENTION_DAYS);
+        let write_txn = self.db.begin_write().context("failed to begin write txn")?;
+        let removed = {
+            let mut table = write_txn
+                .open_table(STATUS_TABLE)
+                .context("failed to open delivery status table")?;
+            let mut stale = Vec::new();
+            for item in table.iter().context("failed to scan delivery statuses")? {
+                let (key, value) = item.context("failed to read delivery status")?;
+                let expired = serde_json::from_slice::<DeliveryStatus>(value.value())
+                    .map(|status| status.updated_at < cutoff)
+                    .unwrap_or(true);
+                if expired {
+                    stale.push(key.value().to_string());
+                }
+            }
+            for key in &stale {
+                table
+                    .remove(key.as_str())
+                    .context("failed to prune delivery status")?;
+            }
+
+            let mut receipts = write_txn
+                .open_table(RECEIPTS_TABLE)
+                .context("failed to open receipts table")?;
+            let mut stale_receipts = Vec::new();
+            for item in receipts.iter().context("failed to scan receipt mappings")? {
+                let (key, value) = item.context("failed to read receipt mapping")?;
+                let expired = serde_json::from_slice::<TrackedMessage>(value.value())
+                    .map(|tracked| tracked.sent_at < cutoff)
+                    .unwrap_or(true);
+                if expired {
+                    stale_receipts.push(key.value().to_string());
+                }
+            }
+            for key in &stale_receipts {
+                receipts
+                    .remove(key.as_str())
+                    .context("failed to prune receipt mapping")?;
+            }
+            stale.len()
+        };
+        write_txn.commit().context("failed to commit outbox")?;
+        Ok(removed)
+    }
+
+    /// Store `entry` and mirror its state into the delivery status table.
+    fn write(&self, entry: &OutboxEntry) -> Result<()> {
+        let data = encode(entry)?;
+        let write_txn = self.db.begin_write().context("failed to begin write txn")?;
+        {
+            let mut entries = write_txn
+                .open_table(ENTRIES_TABLE)
+                .context("failed to open outbox table")?;
+            entries
+                .insert(entry.id.as_str(), data.as_slice())
+                .context("failed to write outbox entry")?;
+            if let Some(message_id) = &entry.message_id {
+                let mut statuses = write_txn
+                    .open_table(STATUS_TABLE)
+                    .context("failed to open delivery status table")?;
+                statuses
+                    .insert(message_id.as_str(), encode(&entry.status())?.as_slice())
+                    .context("failed to write delivery status")?;
+            }
+        }
+        write_txn.commit().context("failed to commit outbox")?;
+        Ok(())
+    }
+
+    fn entry(&self, id: &str) -> Result<Option<OutboxEntry>> {
+        let read_txn = self.db.begin_read().context("failed to begin read txn")?;
+        let table = read_txn
+            .open_table(ENTRIES_TABLE)
+            .context("failed to open outbox table")?;
+        let Some(value) = table.get(id).context("failed to read outbox entry")? else {
+            return Ok(None);
+        };
+        Ok(Some(
+            serde_json::from_slice(value.value()).context("failed to decode outbox entry")?,
+        ))
+    }
+
+    fn entries(&self) -> Result<Vec<OutboxEntry>> {
+        let read_txn = self.db.begin_read().context("failed to begin read txn")?;
+        let table = read_txn
+            .open_table(ENTRIES_TABLE)
+            .context("failed to open outbox table")?;
+        let mut entries = Vec::new();
+        for item in table.iter().context("failed to scan outbox")? {
+            let (_, value) = item.context("failed to read outbox entry")?;
+            match serde_json::from_slice::<OutboxEntry>(value.value()) {
+                Ok(entry) => entries.push(entry),
+                Err(error) => tracing::warn!(%error, "skipping unreadable outbox entry"),
+            }
+        }
+        Ok(entries)
+    }
+}
+
+fn receipt_key(adapter: &str, platform_message_id: &str) -> String {
+    format!("{adapter}\n{platform_message_id}")
+}
+
+fn read_status(
+    table: &impl redb::ReadableTable<&'static str, &'static [u8]>,
+    message_id: &str,
+) -> Result<Option<DeliveryStatus>> {
+    let Some(value) = table
+        .get(message_id)
+        .context("failed to read delivery status")?
+    else {
+        return Ok(None);
+    };
+    Ok(serde_json::from_slice(value.value()).ok())
+}
+
+fn encode(value: &impl Serialize) -> Result<Vec<u8>> {
+    Ok(serde_json::to_vec(value).context("failed to encode outbox record")?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failed_replies_back_off_then_dead_letter_and_can_be_retried() {
+        let directory = tempfile::tempdir().unwrap();
+        let outbox = Outbox::open(&directory.path().join("outbox.redb")).unwrap();
+        let policy = RetryPolicy {
+            max_attempts: 2,
+            initial_backoff: Duration::from_secs(30),
+            max_backoff: Duration::from_secs(60),
+            max_age: Duration::from_secs(600),
+        };
+        let delivery = PendingDelivery::Broadcast {
+            target: "123".into(),
+            response: OutboundResponse::Text("hello".into()),
+        };
+        let entry = outbox
+            .enqueue("discord", delivery, Some("msg-1".into()))
+            .unwrap();
+        assert_eq!(outbox.due(Utc::now()).unwrap().len(), 1);
+
+        let entry = outbox.record_failure(entry, "429", &policy).unwrap();
+        assert!(!entry.dead);
+        assert!(outbox.due(Utc::now()).unwrap().is_empty());
+        let status = &outbox.statuses(&["msg-1"]).unwrap()["msg-1"];
+        assert_eq!(status.state, DeliveryState::Retrying);
+
+        outbox.record_failure(entry, "429", &policy).unwrap();
+        let dead = outbox.dead_letters().unwrap();
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[0].preview, "hello");
+        assert_eq!(dead[0].target, "123");
+        let status = &outbox.statuses(&["msg-1"]).unwrap()["msg-1"];
+        assert_eq!(status.state, DeliveryState::Dead);
+
+        assert!(outbox.retry(&dead[0].id).unwrap());
+        assert!(!outbox.retry(&dead[0].id).unwrap());
+        let due = outbox.due(Utc::now()).unwrap();
+        assert_eq!(due.len(), 1);
+        outbox.mark_delivered(&due[0]).unwrap();
+        assert!(outbox.due(Utc::now()).unwrap().is_empty());
+        assert!(outbox.dead_letters().unwrap().is_empty());
+        let status = &outbox.statuses(&["msg-1"]).unwrap()["msg-1"];
+        assert_eq!(status.state, DeliveryState::Delivered);
+
+        assert_eq!(policy.backoff(1), Duration::from_secs(30));
+        assert_eq!(policy.backoff(3), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn receipts_survive_delivery_and_clear_unread() {
+        let directory = tempfile::tempdir().unwrap();
+        let outbox = Outbox::open(&directory.path().join("outbox.redb")).unwrap();
+        let delivery = PendingDelivery::Broadcast {
+            target: "+15550100".into(),
+            response: OutboundResponse::Text("your flight moved".into()),
+        };
+        let entry = outbox
+            .enqueue("signal", delivery, Some("msg-1".into()))
+            .unwrap();
+
+        // The adapter tracks the message mid-send, before it's marked delivered.
+        outbox
+            .track_receipts("signal", "1700000000000", "msg-1")
+            .unwrap();
+        outbox.mark_delivered(&entry).unwrap();
+        let later = Utc::now() + chrono::Duration::seconds(1);
+        assert_eq!(outbox.unread(later).unwrap().len(), 1);
+
+        assert!(
+            !outbox
+                .record_receipt("signal", "999", ReceiptKind::Read, later)
+                .unwrap()
+        );
+        assert!(
+            outbox
+                .record_receipt("signal", "1700000000000", ReceiptKind::Read, later)
+                .unwrap()
+        );
+        let status = &outbox.statuses(&["msg-1"]).unwrap()["msg-1"];
+        assert!(status.receipts);
+        assert_eq!(status.read_at, Some(later));
+        assert_eq!(status.delivered_at, Some(later));
+        assert!(outbox.unread(later).unwrap().is_empty());
+    }
+}
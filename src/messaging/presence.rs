@@ -0,0 +1,235 @@
+//! Bot presence that reflects what the agents behind it can do right now.
+//!
+//! A single bot account usually fronts every agent on the instance, so the
+//! presence is instance-wide: the worst state across agents wins. The sync
+//! task samples agent state on an interval and pushes a new presence to every
+//! adapter only when it changes. Adapters for platforms without presence keep
+//! the default no-op [`Messaging::set_presence`](super::Messaging::set_presence).
+
+use crate::api::ApiState;
+use crate::config::WarmupState;
+
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How often agent state is sampled.
+const SYNC_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Online status, mapped onto each platform's closest equivalent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PresenceStatus {
+    Online,
+    Idle,
+    DoNotDisturb,
+}
+
+/// Status plus a short human-readable reason, shown as the bot's custom
+/// status where the platform supports one.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Presence {
+    pub status: PresenceStatus,
+    pub activity: Option<String>,
+}
+
+impl Presence {
+    fn new(status: PresenceStatus, activity: Option<&str>) -> Self {
+        Self {
+            status,
+            activity: activity.map(str::to_string),
+        }
+    }
+
+    /// Shown while the instance finishes in-flight work before shutting down.
+    pub fn draining() -> Self {
+        Self::new(PresenceStatus::Idle, Some("shutting down"))
+    }
+}
+
+/// The slice of an agent's state that affects presence.
+#[derive(Debug, Clone, Copy)]
+pub struct AgentPresenceSignals {
+    pub warmup: WarmupState,
+    /// Credentials exist for the provider of the agent's channel model.
+    pub provider_available: bool,
+    /// The agent's channel model is in rate-limit cooldown.
+    pub rate_limited: bool,
+    /// Paused through the API.
+    pub paused: bool,
+    /// Channels only answer commands, mentions, and replies by default.
+    pub listen_only: bool,
+}
+
+/// Map agent states to a single presence. Unusable agents (paused, no
+/// provider, rate limited, degraded) show do-not-disturb; agents still
+/// warming up or in listen-only mode show idle; otherwise the bot is online.
+pub fn presence_for(agents: &[AgentPresenceSignals]) -> Presence {
+    if agents.is_empty() {
+        return Presence::new(PresenceStatus::Idle, Some("no agents running"));
+    }
+    if agents.iter().any(|agent| agent.paused) {
+        return Presence::new(PresenceStatus::DoNotDisturb, Some("paused"));
+    }
+    if agents.iter().any(|agent| !agent.provider_available) {
+        return Presence::new(
+            PresenceStatus::DoNotDisturb,
+            Some("no LLM provider configured"),
+        );
+    }
+    if agents.iter().any(|agent| agent.rate_limited) {
+        return Presence::new(
+            PresenceStatus::DoNotDisturb,
+            Some("LLM provider rate limited"),
+        );
+    }
+    if agents
+        .iter()
+        .any(|agent| agent.warmup == WarmupState::Degraded)
+    {
+        return Presence::new(PresenceStatus::DoNotDisturb, Some("degraded"));
+    }
+    if agents
+        .iter()
+        .any(|agent| matches!(agent.warmup, WarmupState::Cold | WarmupState::Warming))
+    {
+        return Presence::new(PresenceStatus::Idle, Some("warming up"));
+    }
+    if agents.iter().any(|agent| agent.listen_only) {
+        return Presence::new(PresenceStatus::Idle, Some("quiet mode"));
+    }
+    Presence::new(PresenceStatus::Online, None)
+}
+
+/// Sample every agent's presence signals from the API state.
+async fn collect_signals(state: &ApiState) -> Vec<AgentPresenceSignals> {
+    let runtime_configs = state.runtime_configs.load_full();
+    let llm_manager = state.llm_manager.read().await.clone();
+
+    let mut signals = Vec::with_capacity(runtime_configs.len());
+    for runtime_config in runtime_configs.values() {
+        let routing = runtime_config.routing.load_full();
+        let provider = crate::llm::routing::provider_from_model(&routing.channel);
+        let (provider_available, rate_limited) = match &llm_manager {
+            Some(manager) => (
+                manager.has_provider_credentials(provider).await,
+                manager
+                    .is_rate_limited(&routing.channel, routing.rate_limit_cooldown_secs)
+                    .await,
+            ),
+            None => (false, false),
+        };
+        signals.push(AgentPresenceSignals {
+            warmup: runtime_config.warmup_status.load().state,
+            provider_available,
+            rate_limited,
+            paused: runtime_config.is_paused(),
+            listen_only: runtime_config.channel_config.load().listen_only_mode,
+        });
+    }
+    signals
+}
+
+/// Keep adapter presence in sync with agent state until shutdown.
+pub fn spawn_presence_sync(
+    state: Arc<ApiState>,
+    mut shutdown_rx: tokio::sync::watch::Receiver<bool>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(SYNC_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        let mut last_sent: Option<(usize, Presence)> = None;
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = shutdown_rx.wait_for(|shutdown| *shutdown) => return,
+            }
+
+            let Some(manager) = state.messaging_manager.read().await.clone() else {
+                continue;
+            };
+            let presence = presence_for(&collect_signals(&state).await);
+
+            // The manager is replaced when adapters are reconfigured; push the
+            // current presence to a new one even if nothing else changed.
+            let manager_key = Arc::as_ptr(&manager) as usize;
+            if last_sent
+                .as_ref()
+                .is_some_and(|(key, sent)| *key == manager_key && *sent == presence)
+            {
+                continue;
+            }
+
+            tracing::debug!(status = ?presence.status, activity = ?presence.activity, "updating bot presence");
+            manager.set_presence(&presence).await;
+            last_sent = Some((manager_key, presence));
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn agent(warmup: WarmupState) -> AgentPresenceSignals {
+        AgentPresenceSignals {
+            warmup,
+            provider_available: true,
+            rate_limited: false,
+            paused: false,
+            listen_only: false,
+        }
+    }
+
+    #[test]
+    fn worst_agent_state_wins() {
+        let warm = agent(WarmupState::Warm);
+        assert_eq!(presence_for(&[warm]).status, PresenceStatus::Online);
+        assert_eq!(
+            presence_for(&[warm, agent(WarmupState::Warming)]),
+            Presence::new(PresenceStatus::Idle, Some("warming up"))
+        );
+
+        let limited = AgentPresenceSignals {
+            rate_limited: true,
+            ..warm
+        };
+        assert_eq!(
+            presence_for(&[agent(WarmupState::Cold), limited]).status,
+            PresenceStatus::DoNotDisturb
+        );
+
+        let no_provider = AgentPresenceSignals {
+            provider_available: false,
+            ..limited
+        };
+        assert_eq!(
+            presence_for(&[no_provider]).activity.as_deref(),
+            Some("no LLM provider configured")
+        );
+        assert_eq!(presence_for(&[]).status, PresenceStatus::Idle);
+    }
+
+    #[test]
+    fn paused_and_quiet_agents_are_reflected() {
+        let warm = agent(WarmupState::Warm);
+        let quiet = AgentPresenceSignals {
+            listen_only: true,
+            ..warm
+        };
+        assert_eq!(
+            presence_for(&[warm, quiet]),
+            Presence::new(PresenceStatus::Idle, Some("quiet mode"))
+        );
+
+        let paused = AgentPresenceSignals {
+            paused: true,
+            ..warm
+        };
+        assert_eq!(
+            presence_for(&[quiet, paused, agent(WarmupState::Degraded)]),
+            Presence::new(PresenceStatus::DoNotDisturb, Some("paused"))
+        );
+    }
+}
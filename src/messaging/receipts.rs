@@ -0,0 +1,100 @@
+//! Delivery and read receipts for outbound replies.
+//!
+//! Adapters for platforms that report receipts get a [`Receipts`] handle
+//! through [`Messaging::set_receipts`](super::traits::Messaging::set_receipts).
+//! When one sends a reply it calls [`Receipts::sent`] with the platform's ID
+//! for the sent message, which links that ID to the conversation message
+//! being delivered. Receipts arriving later name only the platform ID;
+//! [`Receipts::received`] resolves it and records the delivery or read time on
+//! the message's delivery status in the outbox.
+
+use crate::messaging::outbox::Outbox;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use std::future::Future;
+use std::sync::Arc;
+
+tokio::task_local! {
+    /// Conversation message the current task is sending, set by the manager
+    /// around each outbox delivery.
+    static SENDING_MESSAGE_ID: Option<String>;
+}
+
+/// Run `send` with `message_id` as the conversation message being sent, so
+/// adapters can link the platform's message ID to it.
+pub(super) async fn sending<F: Future>(message_id: Option<String>, send: F) -> F::Output {
+    SENDING_MESSAGE_ID.scope(message_id, send).await
+}
+
+fn sending_message_id() -> Option<String> {
+    SENDING_MESSAGE_ID.try_with(Clone::clone).ok().flatten()
+}
+
+/// What a receipt confirms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiptKind {
+    /// The message reached the recipient's device.
+    Delivered,
+    /// The recipient opened it.
+    Read,
+}
+
+/// Handle adapters use to report sent messages and the receipts for them.
+#[derive(Clone)]
+pub struct Receipts {
+    outbox: Arc<Outbox>,
+}
+
+impl std::fmt::Debug for Receipts {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Receipts").finish_non_exhaustive()
+    }
+}
+
+impl Receipts {
+    pub fn new(outbox: Arc<Outbox>) -> Self {
+        Self { outbox }
+    }
+
+    /// Record that `adapter` sent the reply being delivered as platform
+    /// message `platform_message_id`. Does nothing outside an outbox
+    /// delivery of a logged message.
+    pub fn sent(&self, adapter: &str, platform_message_id: &str) {
+        let Some(message_id) = sending_message_id() else {
+            return;
+        };
+        if let Err(error) = self
+            .outbox
+            .track_receipts(adapter, platform_message_id, &message_id)
+        {
+            tracing::warn!(%adapter, %error, "failed to track message for receipts");
+        }
+    }
+
+    /// Record a receipt for platform message `platform_message_id`. Receipts
+    /// for messages that weren't tracked (sent before receipts were on, or
+    /// by another client on the same account) are ignored.
+    pub fn received(
+        &self,
+        adapter: &str,
+        platform_message_id: &str,
+        kind: ReceiptKind,
+        at: DateTime<Utc>,
+    ) {
+        match self
+            .outbox
+            .record_receipt(adapter, platform_message_id, kind, at)
+        {
+            Ok(true) => {
+                tracing::debug!(%adapter, %platform_message_id, ?kind, "recorded message receipt");
+            }
+            Ok(false) => {}
+            Err(error) => {
+                tracing::warn!(%adapter, %error, "failed to record message receipt");
+            }
+        }
+    }
+}
@@ -16,6 +16,8 @@
 //!   in the `attachments` JSON array, cleaned up after send.
 //! - **Attachments inbound:** signal-cli provides file paths on disk; currently treated
 //!   as opaque (message text falls back to `[Attachment]` for attachment-only messages).
+//! - **Receipts:** `send` results carry the message's timestamp, which delivery
+//!   and read receipts (`receiptMessage` envelopes) refer back to.
 //! - **Streaming:** Not supported (Signal can't edit sent messages). `StreamStart`,
 //!   `StreamChunk`, and `StreamEnd` are no-ops.
 //!
@@ -31,6 +33,8 @@
 //! - **Scheduled messages:** Not supported; sent immediately.
 
 use crate::config::SignalPermissions;
+use crate::messaging::format::RichText;
+use crate::messaging::receipts::{ReceiptKind, Receipts};
 use crate::messaging::traits::{
     InboundStream, Messaging, apply_runtime_adapter_to_conversation_id,
 };
@@ -42,7 +46,7 @@ use futures::StreamExt;
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::path::PathBuf;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 use std::time::Duration;
 use tokio::sync::{RwLock, mpsc};
 use tokio::task::JoinHandle;
@@ -154,6 +158,10 @@ struct Envelope {
     /// Present when the envelope is a story message (dropped when `ignore_stories` is true).
     #[serde(rename = "storyMessage", default)]
     story_message: Option<serde_json::Value>,
+    /// Present when the envelope is a delivery or read receipt for messages
+    /// this account sent.
+    #[serde(rename = "receiptMessage", default)]
+    receipt_message: Option<ReceiptMessage>,
     #[serde(default)]
     timestamp: Option<u64>,
 }
@@ -172,6 +180,22 @@ struct DataMessage {
     attachments: Option<Vec<serde_json::Value>>,
 }
 
+#[derive(Debug, Deserialize)]
+struct ReceiptMessage {
+    /// When the receipt was generated, in epoch milliseconds.
+    #[serde(default)]
+    when: Option<u64>,
+    #[serde(rename = "isDelivery", default)]
+    is_delivery: bool,
+    #[serde(rename = "isRead", default)]
+    is_read: bool,
+    #[serde(rename = "isViewed", default)]
+    is_viewed: bool,
+    /// Send timestamps of the messages the receipt covers.
+    #[serde(default)]
+    timestamps: Vec<u64>,
+}
+
 #[derive(Debug, Deserialize)]
 struct GroupInfo {
     #[serde(rename = "groupId", default)]
@@ -214,6 +238,8 @@ pub struct SignalAdapter {
     typing_tasks: Arc<RwLock<HashMap<String, JoinHandle<()>>>>,
     /// Shutdown signal for the SSE listener loop.
     shutdown_tx: Arc<RwLock<Option<mpsc::Sender<()>>>>,
+    /// Sink for delivery and read receipts, shared with the SSE listener.
+    receipts: Arc<OnceLock<Receipts>>,
 }
 
 impl SignalAdapter {
@@ -255,6 +281,7 @@ impl SignalAdapter {
             tmp_dir,
             typing_tasks: Arc::new(RwLock::new(HashMap::new())),
             shutdown_tx: Arc::new(RwLock::new(None)),
+            receipts: Arc::new(OnceLock::new()),
         }
     }
 
@@ -404,10 +431,13 @@ impl SignalAdapter {
         None
     }
 
-    /// Send a text message to the resolved target.
+    /// Send a text message to the resolved target. Signal has no markup, so
+    /// Markdown is flattened to plain text first.
     async fn send_text(&self, target: &RecipientTarget, text: &str) -> anyhow::Result<()> {
-        let params = self.build_rpc_params(target, Some(text), None);
-        self.rpc_request("send", params).await?;
+        let text = RichText::parse(text).to_plain_text(None);
+        let params = self.build_rpc_params(target, Some(&text), None);
+        let result = self.rpc_request("send", params).await?;
+        self.track_sent(result.as_ref());
         Ok(())
     }
 
@@ -465,10 +495,45 @@ impl SignalAdapter {
             );
         }
 
-        result?;
+        self.track_sent(result?.as_ref());
         Ok(())
     }
 
+    /// Report a sent message for receipt tracking. signal-cli identifies
+    /// messages by their send timestamp, which receipts refer back to.
+    fn track_sent(&self, send_result: Option<&serde_json::Value>) {
+        let Some(receipts) = self.receipts.get() else {
+            return;
+        };
+        if let Some(timestamp) = send_result
+            .and_then(|result| result.get("timestamp"))
+            .and_then(|timestamp| timestamp.as_u64())
+        {
+            receipts.sent(&self.runtime_key, &timestamp.to_string());
+        }
+    }
+
+    /// Record a receipt envelope against the messages it covers.
+    fn process_receipt(&self, receipt: &ReceiptMessage) {
+        let Some(receipts) = self.receipts.get() else {
+            return;
+        };
+        let kind = if receipt.is_read || receipt.is_viewed {
+            ReceiptKind::Read
+        } else if receipt.is_delivery {
+            ReceiptKind::Delivered
+        } else {
+            return;
+        };
+        let at = receipt
+            .when
+            .and_then(|when| chrono::DateTime::from_timestamp_millis(when as i64))
+            .unwrap_or_else(chrono::Utc::now);
+        for timestamp in &receipt.timestamps {
+            receipts.received(&self.runtime_key, &timestamp.to_string(), kind, at);
+        }
+    }
+
     /// Cancel the repeating typing indicator for a conversation.
     async fn stop_typing(&self, conversation_id: &str) {
         if let Some(handle) = self.typing_tasks.write().await.remove(conversation_id) {
@@ -717,6 +782,10 @@ impl Messaging for SignalAdapter {
         &self.runtime_key
     }
 
+    fn set_receipts(&self, receipts: Receipts) {
+        let _ = self.receipts.set(receipts);
+    }
+
     async fn start(&self) -> crate::Result<InboundStream> {
         let (inbound_tx, inbound_rx) = mpsc::channel(256);
         let (shutdown_tx, shutdown_rx) = mpsc::channel::<()>(1);
@@ -740,6 +809,7 @@ impl Messaging for SignalAdapter {
         let ignore_stories = self.ignore_stories;
         let permissions = self.permissions.clone();
         let tmp_dir = self.tmp_dir.clone();
+        let receipts = self.receipts.clone();
 
         tokio::spawn(async move {
             // Build a temporary adapter for envelope processing inside the task.
@@ -754,6 +824,7 @@ impl Messaging for SignalAdapter {
                 tmp_dir,
                 typing_tasks: Arc::new(RwLock::new(HashMap::new())),
                 shutdown_tx: Arc::new(RwLock::new(None)),
+                receipts,
             };
 
             sse_listener(adapter, client, http_url, account, inbound_tx, shutdown_rx).await;
@@ -1270,6 +1341,11 @@ async fn process_sse_event(
         return;
     };
 
+    if let Some(receipt) = &envelope.receipt_message {
+        adapter.process_receipt(receipt);
+        return;
+    }
+
     let Some((inbound, _reply_target)) = adapter.process_envelope(envelope) else {
         return;
     };
@@ -1547,6 +1623,29 @@ mod tests {
         assert_eq!(dm.attachments.unwrap().len(), 2);
     }
 
+    #[test]
+    fn parse_sse_envelope_read_receipt() {
+        let json = r#"{
+            "envelope": {
+                "sourceNumber": "+1234567890",
+                "receiptMessage": {
+                    "when": 1700000005000,
+                    "isDelivery": false,
+                    "isRead": true,
+                    "isViewed": false,
+                    "timestamps": [1700000000000, 1700000001000]
+                }
+            }
+        }"#;
+        let sse: SseEnvelope = serde_json::from_str(json).unwrap();
+        let envelope = sse.envelope.unwrap();
+        assert!(envelope.data_message.is_none());
+        let receipt = envelope.receipt_message.unwrap();
+        assert!(receipt.is_read);
+        assert_eq!(receipt.when, Some(1700000005000));
+        assert_eq!(receipt.timestamps, vec![1700000000000, 1700000001000]);
+    }
+
     #[test]
     fn parse_sse_envelope_empty() {
         let json = r#"{}"#;
@@ -1619,6 +1718,7 @@ mod tests {
                 attachments: None,
             }),
             story_message: None,
+            receipt_message: None,
             timestamp: None,
         };
 
@@ -1664,6 +1764,7 @@ mod tests {
                 attachments: None,
             }),
             story_message: None,
+            receipt_message: None,
             timestamp: None,
         };
 
@@ -1697,6 +1798,7 @@ mod tests {
                 attachments: None,
             }),
             story_message: None,
+            receipt_message: None,
             timestamp: None,
         };
 
@@ -1718,6 +1820,7 @@ mod tests {
             source_uuid: None,
             data_message: None,
             story_message: Some(serde_json::json!({"text": "story"})),
+            receipt_message: None,
             timestamp: None,
         };
         assert!(adapter.process_envelope(&envelope).is_none());
@@ -1738,6 +1841,7 @@ mod tests {
                 attachments: None,
             }),
             story_message: None,
+            receipt_message: None,
             timestamp: None,
         };
         assert!(adapter.process_envelope(&envelope).is_none());
@@ -1758,6 +1862,7 @@ mod tests {
                 attachments: Some(vec![serde_json::json!({"contentType": "image/png"})]),
             }),
             story_message: None,
+            receipt_message: None,
             timestamp: None,
         };
         let result = adapter.process_envelope(&envelope);
@@ -1868,6 +1973,7 @@ mod tests {
                 attachments: None,
             }),
             story_message: None,
+            receipt_message: None,
             timestamp: None,
         };
         let (msg, _) = adapter.process_envelope(&envelope).unwrap();
@@ -1910,6 +2016,7 @@ mod tests {
             tmp_dir: PathBuf::from("/tmp/spacebot-test"),
             typing_tasks: Arc::new(RwLock::new(HashMap::new())),
             shutdown_tx: Arc::new(RwLock::new(None)),
+            receipts: Arc::new(OnceLock::new()),
         }
     }
 
@@ -1926,6 +2033,7 @@ mod tests {
                 attachments: None,
             }),
             story_message: None,
+            receipt_message: None,
             timestamp: None,
         }
     }
@@ -1945,6 +2053,7 @@ mod tests {
                 attachments: None,
             }),
             story_message: None,
+            receipt_message: None,
             timestamp: None,
         }
     }
@@ -1970,6 +2079,7 @@ mod tests {
                 attachments: None,
             }),
             story_message: None,
+            receipt_message: None,
             timestamp: None,
         };
         let result = adapter.process_envelope(&envelope);
@@ -2052,6 +2162,7 @@ mod rpc_error_tests {
             tmp_dir: PathBuf::from("/tmp/spacebot-test"),
             typing_tasks: Arc::new(RwLock::new(HashMap::new())),
             shutdown_tx: Arc::new(RwLock::new(None)),
+            receipts: Arc::new(OnceLock::new()),
         }
     }
 }
@@ -23,6 +23,9 @@
 
 use crate::config::{SlackCommandConfig, SlackPermissions};
 use crate::messaging::apply_runtime_adapter_to_conversation_id;
+use crate::messaging::chunking::{ChunkPacer, chunk_message};
+use crate::messaging::commands::SlashCommand;
+use crate::messaging::format::RichText;
 use crate::messaging::traits::{HistoryMessage, InboundStream, Messaging};
 use crate::{InboundMessage, MessageContent, OutboundResponse, StatusUpdate};
 
@@ -34,6 +37,13 @@ use std::sync::Arc;
 use tokio::sync::{RwLock, mpsc};
 use tokio::time::{Duration, timeout};
 
+/// Longest message text sent in one `chat.postMessage` call.
+const MAX_MESSAGE_LENGTH: usize = 12_000;
+
+/// Gap between chunks of one reply; `chat.postMessage` allows roughly one
+/// message per second per channel.
+const CHUNK_INTERVAL: Duration = Duration::from_secs(1);
+
 /// State shared with socket mode callbacks via `SlackClientEventsUserState`.
 struct SlackAdapterState {
     inbound_tx: mpsc::Sender<InboundMessage>,
@@ -258,6 +268,9 @@ async fn handle_message_event(
     )
     .await;
     let mut metadata = metadata;
+    if msg_event.sender.bot_id.is_some() {
+        metadata.insert("sender_is_bot".into(), true.into());
+    }
     let bot_mention = format!("<@{}>", adapter_state.bot_user_id);
     let mentioned_bot = msg_event
         .content
@@ -548,7 +561,11 @@ async fn handle_command_event(
         serde_json::Value::String(agent_id),
     );
 
-    let content = MessageContent::Text(format!("{} {}", command_str, text).trim().to_string());
+    let command_text = format!("{} {}", command_str, text).trim().to_string();
+    let content = match SlashCommand::parse(&command_text) {
+        Some(command) => MessageContent::Command(command),
+        None => MessageContent::Text(command_text),
+    };
 
     let inbound = InboundMessage {
         id: msg_id,
@@ -911,7 +928,9 @@ impl Messaging for SlackAdapter {
             OutboundResponse::Text(text) => {
                 let thread_ts = extract_thread_ts(message);
 
-                for chunk in split_message(&text, 12_000) {
+                let mut pacer = ChunkPacer::new(CHUNK_INTERVAL);
+                for chunk in chunk_message(&text, MAX_MESSAGE_LENGTH) {
+                    pacer.wait().await;
                     let mut req = SlackApiChatPostMessageRequest::new(
                         channel_id.clone(),
                         markdown_content(chunk),
@@ -929,7 +948,9 @@ impl Messaging for SlackAdapter {
             } => {
                 let thread_ts = extract_thread_ts(message).or_else(|| extract_message_ts(message));
 
-                for chunk in split_message(&text, 12_000) {
+                let mut pacer = ChunkPacer::new(CHUNK_INTERVAL);
+                for chunk in chunk_message(&text, MAX_MESSAGE_LENGTH) {
+                    pacer.wait().await;
                     let mut req = SlackApiChatPostMessageRequest::new(
                         channel_id.clone(),
                         markdown_content(chunk),
@@ -1144,7 +1165,9 @@ impl Messaging for SlackAdapter {
 
         match response {
             OutboundResponse::Text(text) => {
-                for chunk in split_message(&text, 12_000) {
+                let mut pacer = ChunkPacer::new(CHUNK_INTERVAL);
+                for chunk in chunk_message(&text, MAX_MESSAGE_LENGTH) {
+                    pacer.wait().await;
                     let mut req = SlackApiChatPostMessageRequest::new(
                         channel_id.clone(),
                         markdown_content(chunk),
@@ -1348,25 +1371,27 @@ fn parse_slack_history_timestamp(raw_timestamp: &str) -> Option<chrono::DateTime
     }
 }
 
-/// Build a `SlackMessageContent` using a Markdown block with plain text fallback.
+/// Build a `SlackMessageContent` using a Markdown block with mrkdwn fallback.
 ///
 /// The Markdown block supports standard markdown (bold, italic, lists, code,
-/// headings, quotes, links) natively — no mrkdwn conversion needed. The `text`
-/// field is set as fallback for notifications and accessibility.
+/// headings, quotes, links) natively. The `text` field is rendered as mrkdwn
+/// and used for notifications, accessibility, and clients without block
+/// support.
 ///
 /// Cumulative limit for all markdown blocks in a payload is 12,000 characters.
-/// For content exceeding 12,000 chars we fall back to plain text to avoid
+/// For content exceeding 12,000 chars we send only the mrkdwn text to avoid
 /// Slack rejecting the payload.
 fn markdown_content(text: impl Into<String>) -> SlackMessageContent {
     let text = text.into();
+    let mrkdwn = RichText::parse(&text).to_mrkdwn();
     if text.len() <= 12_000 {
-        let block = SlackBlock::Markdown(SlackMarkdownBlock::new(text.clone()));
+        let block = SlackBlock::Markdown(SlackMarkdownBlock::new(text));
         SlackMessageContent::new()
-            .with_text(text)
+            .with_text(mrkdwn)
             .with_blocks(vec![block])
     } else {
-        // Exceeds markdown block limit — send as plain text
-        SlackMessageContent::new().with_text(text)
+        // Exceeds markdown block limit — send as mrkdwn text
+        SlackMessageContent::new().with_text(mrkdwn)
     }
 }
 
@@ -1643,39 +1668,6 @@ fn variant_name(response: &OutboundResponse) -> &'static str {
     }
 }
 
-/// Split a message into UTF-8-safe chunks at line/word boundaries.
-fn split_message(text: &str, max_len: usize) -> Vec<String> {
-    if text.len() <= max_len {
-        return vec![text.to_string()];
-    }
-
-    let mut chunks = Vec::new();
-    let mut remaining = text;
-
-    while !remaining.is_empty() {
-        if remaining.len() <= max_len {
-            chunks.push(remaining.to_string());
-            break;
-        }
-
-        // Walk back to a valid char boundary before slicing
-        let mut safe_max = max_len.min(remaining.len());
-        while !remaining.is_char_boundary(safe_max) {
-            safe_max -= 1;
-        }
-
-        let split_at = remaining[..safe_max]
-            .rfind('\n')
-            .or_else(|| remaining[..safe_max].rfind(' '))
-            .unwrap_or(safe_max);
-
-        chunks.push(remaining[..split_at].to_string());
-        remaining = remaining[split_at..].trim_start();
-    }
-
-    chunks
-}
-
 /// Convert an emoji input to a Slack reaction short-code name.
 ///
 /// Handles three input forms:
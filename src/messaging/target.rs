@@ -123,6 +123,17 @@ pub fn resolve_broadcast_target(channel: &ChannelInfo) -> Option<BroadcastTarget
             // Skip "signal" prefix and use shared parser for the rest
             return parse_signal_target_parts(parts.get(1..).unwrap_or(&[]));
         }
+        "twilio" => {
+            // Format: twilio:{number} or twilio:{instance}:{number}
+            let parts: Vec<&str> = channel.id.split(':').collect();
+            let (adapter, number) = match parts.as_slice() {
+                ["twilio", number] => ("twilio".to_string(), *number),
+                ["twilio", instance, number] => (format!("twilio:{instance}"), *number),
+                _ => return None,
+            };
+            let target = normalize_twilio_target(number)?;
+            return Some(BroadcastTarget { adapter, target });
+        }
         "email" => {
             let reply_to = channel
                 .platform_meta
@@ -166,6 +177,7 @@ pub fn normalize_target(adapter: &str, raw_target: &str) -> Option<String> {
         // Webchat targets are full conversation IDs (e.g. "portal:chat:main")
         "webchat" => Some(trimmed.to_string()),
         "signal" => normalize_signal_target(trimmed),
+        "twilio" => normalize_twilio_target(trimmed),
         _ => Some(trimmed.to_string()),
     }
 }
@@ -238,6 +250,17 @@ fn normalize_twitch_target(raw_target: &str) -> Option<String> {
     }
 }
 
+/// Twilio targets are E.164 numbers. Spaces and dashes are dropped.
+fn normalize_twilio_target(raw_target: &str) -> Option<String> {
+    let target = strip_repeated_prefix(raw_target, "twilio");
+    let digits = target.strip_prefix('+')?.replace([' ', '-'], "");
+    if !digits.is_empty() && digits.chars().all(|character| character.is_ascii_digit()) {
+        Some(format!("+{digits}"))
+    } else {
+        None
+    }
+}
+
 fn normalize_email_target(raw_target: &str) -> Option<String> {
     let target = strip_repeated_prefix(raw_target, "email").trim();
     if target.is_empty() {
@@ -703,4 +726,17 @@ mod tests {
         assert!(super::parse_signal_target_parts(&["uuid"]).is_none()); // missing UUID value
         assert!(super::parse_signal_target_parts(&["gvoice1", "unknown"]).is_none());
     }
+
+    #[test]
+    fn twilio_targets_are_e164_numbers() {
+        assert_eq!(
+            super::parse_delivery_target("twilio:+1 555-123-4567"),
+            Some(super::BroadcastTarget {
+                adapter: "twilio".to_string(),
+                target: "+15551234567".to_string(),
+            })
+        );
+        assert!(super::parse_delivery_target("twilio:5551234567").is_none());
+        assert!(super::parse_delivery_target("twilio:+").is_none());
+    }
 }
@@ -2,6 +2,8 @@
 
 use crate::config::TelegramPermissions;
 use crate::messaging::apply_runtime_adapter_to_conversation_id;
+use crate::messaging::chunking::{ChunkPacer, chunk_message, split_message};
+use crate::messaging::format::RichText;
 use crate::messaging::traits::{InboundStream, Messaging};
 use crate::{Attachment, InboundMessage, MessageContent, OutboundResponse, StatusUpdate};
 
@@ -53,6 +55,10 @@ const MAX_MESSAGE_LENGTH: usize = 4096;
 /// Smaller source-chunk target for markdown that expands heavily when HTML-escaped.
 const FORMATTED_SPLIT_LENGTH: usize = MAX_MESSAGE_LENGTH / 2;
 
+/// Gap between chunks of one reply; Telegram allows about one message per
+/// second in a chat.
+const CHUNK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
 /// Minimum interval between streaming edits to avoid rate limits.
 const STREAM_EDIT_INTERVAL: std::time::Duration = std::time::Duration::from_millis(1000);
 
@@ -834,6 +840,9 @@ fn build_metadata(
             "telegram_user_id".into(),
             serde_json::Value::Number(from.id.0.into()),
         );
+        if from.is_bot {
+            metadata.insert("sender_is_bot".into(), true.into());
+        }
 
         let display_name = build_display_name(from);
         metadata.insert("display_name".into(), display_name.clone().into());
@@ -1000,34 +1009,6 @@ async fn send_poll(bot: &Bot, chat_id: ChatId, poll: &crate::Poll) -> anyhow::Re
     Ok(())
 }
 
-/// Split a message into chunks that fit within Telegram's character limit.
-/// Tries to split at newlines, then spaces, then hard-cuts.
-fn split_message(text: &str, max_len: usize) -> Vec<String> {
-    if text.len() <= max_len {
-        return vec![text.to_string()];
-    }
-
-    let mut chunks = Vec::new();
-    let mut remaining = text;
-
-    while !remaining.is_empty() {
-        if remaining.len() <= max_len {
-            chunks.push(remaining.to_string());
-            break;
-        }
-
-        let split_at = remaining[..max_len]
-            .rfind('\n')
-            .or_else(|| remaining[..max_len].rfind(' '))
-            .unwrap_or(max_len);
-
-        chunks.push(remaining[..split_at].to_string());
-        remaining = remaining[split_at..].trim_start();
-    }
-
-    chunks
-}
-
 /// Return true when Telegram rejected rich text entities and a plain-caption retry is safe.
 fn should_retry_plain_caption(error: &RequestError) -> bool {
     matches!(error, RequestError::Api(ApiError::CantParseEntities(_)))
@@ -1035,28 +1016,6 @@ fn should_retry_plain_caption(error: &RequestError) -> bool {
 
 // -- Markdown-to-Telegram-HTML formatting --
 
-static BOLD_ITALIC_PATTERN: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"\*\*\*(.+?)\*\*\*").expect("hardcoded regex"));
-static BOLD_PATTERN: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"\*\*(.+?)\*\*").expect("hardcoded regex"));
-static BOLD_UNDERSCORE_PATTERN: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"__(.+?)__").expect("hardcoded regex"));
-static ITALIC_PATTERN: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"\*(.+?)\*").expect("hardcoded regex"));
-static ITALIC_UNDERSCORE_PATTERN: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"_(.+?)_").expect("hardcoded regex"));
-static STRIKETHROUGH_PATTERN: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"~~(.+?)~~").expect("hardcoded regex"));
-static LINK_PATTERN: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"\[([^\]]+)\]\(([^)]+)\)").expect("hardcoded regex"));
-
-/// Escape characters that have special meaning in Telegram's HTML parse mode.
-fn escape_html(text: &str) -> String {
-    text.replace('&', "&amp;")
-        .replace('<', "&lt;")
-        .replace('>', "&gt;")
-}
-
 /// Strip HTML tags and unescape entities, producing plain text for fallback.
 fn strip_html_tags(html: &str) -> String {
     static TAG_PATTERN: LazyLock<Regex> =
@@ -1069,138 +1028,8 @@ fn strip_html_tags(html: &str) -> String {
 }
 
 /// Convert markdown to Telegram-compatible HTML.
-///
-/// Handles fenced code blocks, inline code, bold, italic, strikethrough,
-/// links, headers (rendered as bold), and blockquotes.
 fn markdown_to_telegram_html(markdown: &str) -> String {
-    let mut result = String::with_capacity(markdown.len());
-    let mut in_code_block = false;
-    let mut code_language = String::new();
-    let mut code_lines: Vec<&str> = Vec::new();
-    let mut blockquote_lines: Vec<String> = Vec::new();
-
-    for line in markdown.lines() {
-        if let Some(rest) = line.strip_prefix("```") {
-            flush_blockquote(&mut result, &mut blockquote_lines);
-
-            if in_code_block {
-                let content = escape_html(&code_lines.join("\n"));
-                if code_language.is_empty() {
-                    result.push_str("<pre>");
-                    result.push_str(&content);
-                    result.push_str("</pre>\n");
-                } else {
-                    result.push_str("<pre><code class=\"language-");
-                    result.push_str(&code_language);
-                    result.push_str("\">");
-                    result.push_str(&content);
-                    result.push_str("</code></pre>\n");
-                }
-                in_code_block = false;
-                code_language.clear();
-                code_lines.clear();
-            } else {
-                in_code_block = true;
-                code_language = rest.trim().to_string();
-            }
-            continue;
-        }
-
-        if in_code_block {
-            code_lines.push(line);
-            continue;
-        }
-
-        if let Some(quote_text) = line.strip_prefix("> ") {
-            blockquote_lines.push(format_inline(quote_text));
-            continue;
-        } else if line == ">" {
-            blockquote_lines.push(String::new());
-            continue;
-        }
-
-        flush_blockquote(&mut result, &mut blockquote_lines);
-
-        if let Some(header_text) = line
-            .strip_prefix("### ")
-            .or_else(|| line.strip_prefix("## "))
-            .or_else(|| line.strip_prefix("# "))
-        {
-            result.push_str("<b>");
-            result.push_str(&format_inline(header_text));
-            result.push_str("</b>\n");
-            continue;
-        }
-
-        result.push_str(&format_inline(line));
-        result.push('\n');
-    }
-
-    if in_code_block {
-        result.push_str("<pre>");
-        result.push_str(&escape_html(&code_lines.join("\n")));
-        result.push_str("</pre>\n");
-    }
-
-    flush_blockquote(&mut result, &mut blockquote_lines);
-
-    while result.ends_with('\n') {
-        result.pop();
-    }
-
-    result
-}
-
-/// Append buffered blockquote lines to the result and clear the buffer.
-fn flush_blockquote(result: &mut String, lines: &mut Vec<String>) {
-    if lines.is_empty() {
-        return;
-    }
-    result.push_str("<blockquote>");
-    result.push_str(&lines.join("\n"));
-    result.push_str("</blockquote>\n");
-    lines.clear();
-}
-
-/// Convert inline markdown elements to HTML within a single line.
-///
-/// Splits on backticks to isolate inline code spans, then converts bold,
-/// italic, strikethrough and links in the remaining text. Content inside
-/// backticks is HTML-escaped but not processed for markdown.
-fn format_inline(line: &str) -> String {
-    let segments: Vec<&str> = line.split('`').collect();
-    let mut result = String::new();
-
-    for (index, segment) in segments.iter().enumerate() {
-        if index % 2 == 1 && index < segments.len() - 1 {
-            result.push_str("<code>");
-            result.push_str(&escape_html(segment));
-            result.push_str("</code>");
-        } else if index % 2 == 0 {
-            result.push_str(&format_markdown_spans(&escape_html(segment)));
-        } else {
-            // Unmatched trailing backtick — treat as literal
-            result.push('`');
-            result.push_str(&format_markdown_spans(&escape_html(segment)));
-        }
-    }
-
-    result
-}
-
-/// Replace markdown span markers with HTML tags in already-escaped text.
-///
-/// Bold-italic (`***`) is processed first, then bold (`**`), then italic
-/// (`*`) so longer patterns are consumed before shorter ones.
-fn format_markdown_spans(text: &str) -> String {
-    let text = BOLD_ITALIC_PATTERN.replace_all(text, "<b><i>$1</i></b>");
-    let text = BOLD_PATTERN.replace_all(&text, "<b>$1</b>");
-    let text = BOLD_UNDERSCORE_PATTERN.replace_all(&text, "<b>$1</b>");
-    let text = ITALIC_PATTERN.replace_all(&text, "<i>$1</i>");
-    let text = ITALIC_UNDERSCORE_PATTERN.replace_all(&text, "<i>$1</i>");
-    let text = STRIKETHROUGH_PATTERN.replace_all(&text, "<s>$1</s>");
-    let text = LINK_PATTERN.replace_all(&text, r#"<a href="$2">$1</a>"#);
-    text.into_owned()
+    RichText::parse(markdown).to_telegram_html()
 }
 
 /// Send a plain text Telegram message for formatting fallback paths.
@@ -1222,7 +1051,7 @@ async fn send_plain_text(
 }
 
 /// Send a message with Telegram HTML formatting, splitting at the message
-/// length limit. Falls back to plain text if the API rejects the HTML.
+/// length limit and pacing the parts. Falls back to plain text if the API rejects the HTML.
 async fn send_formatted(
     bot: &Bot,
     chat_id: ChatId,
@@ -1230,7 +1059,8 @@ async fn send_formatted(
     reply_to: Option<MessageId>,
 ) -> anyhow::Result<()> {
     let mut pending_chunks: VecDeque<String> =
-        VecDeque::from(split_message(text, MAX_MESSAGE_LENGTH));
+        VecDeque::from(chunk_message(text, MAX_MESSAGE_LENGTH));
+    let mut pacer = ChunkPacer::new(CHUNK_INTERVAL);
     while let Some(markdown_chunk) = pending_chunks.pop_front() {
         let html_chunk = markdown_to_telegram_html(&markdown_chunk);
 
@@ -1244,10 +1074,12 @@ async fn send_formatted(
             }
 
             let plain_chunk = strip_html_tags(&html_chunk);
+            pacer.wait().await;
             send_plain_text(bot, chat_id, &plain_chunk, reply_to).await?;
             continue;
         }
 
+        pacer.wait().await;
         let mut request = bot
             .send_message(chat_id, &html_chunk)
             .parse_mode(ParseMode::Html);
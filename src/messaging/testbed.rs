@@ -0,0 +1,214 @@
+//! In-memory test platform for scripted end-to-end conversations.
+//!
+//! The adapter owns no network connection. A [`TestbedHandle`] injects
+//! messages as any number of simulated users and records everything the
+//! agent sends back, so tests and `spacebot simulate` can drive the real
+//! routing → channel → outbound pipeline and assert on the result.
+
+use crate::messaging::traits::{InboundStream, Messaging};
+use crate::{AgentId, InboundMessage, MessageContent, OutboundResponse, StatusUpdate};
+
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+/// Platform name, adapter name, and conversation ID prefix.
+pub const TESTBED: &str = "testbed";
+
+/// Something the agent sent to the testbed.
+#[derive(Debug, Clone)]
+pub struct TestbedOutbound {
+    /// Conversation name as passed to [`TestbedHandle::send`], without the
+    /// `testbed:` prefix. Broadcasts use their raw target.
+    pub conversation: String,
+    pub kind: TestbedOutboundKind,
+}
+
+#[derive(Debug, Clone)]
+pub enum TestbedOutboundKind {
+    Response(OutboundResponse),
+    Status(StatusUpdate),
+    Broadcast(OutboundResponse),
+}
+
+impl TestbedOutbound {
+    /// User-visible text, if this is a message rather than a reaction or status.
+    pub fn text(&self) -> Option<&str> {
+        let response = match &self.kind {
+            TestbedOutboundKind::Response(response) | TestbedOutboundKind::Broadcast(response) => {
+                response
+            }
+            TestbedOutboundKind::Status(_) => return None,
+        };
+        match response {
+            OutboundResponse::Text(text)
+            | OutboundResponse::ThreadReply { text, .. }
+            | OutboundResponse::RichMessage { text, .. }
+            | OutboundResponse::Ephemeral { text, .. }
+            | OutboundResponse::ScheduledMessage { text, .. } => Some(text),
+            OutboundResponse::File { caption, .. } => caption.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+struct Shared {
+    inbound_tx: Mutex<Option<mpsc::Sender<InboundMessage>>>,
+    transcript: Mutex<Vec<TestbedOutbound>>,
+    agent_id: Mutex<Option<AgentId>>,
+    next_message_id: Mutex<u64>,
+}
+
+pub struct TestbedAdapter {
+    shared: Arc<Shared>,
+}
+
+/// Drives a [`TestbedAdapter`] from tests or the simulator. Cheap to clone.
+#[derive(Clone)]
+pub struct TestbedHandle {
+    shared: Arc<Shared>,
+}
+
+impl TestbedAdapter {
+    pub fn new() -> (Self, TestbedHandle) {
+        let shared = Arc::new(Shared {
+            inbound_tx: Mutex::new(None),
+            transcript: Mutex::new(Vec::new()),
+            agent_id: Mutex::new(None),
+            next_message_id: Mutex::new(0),
+        });
+        (
+            Self {
+                shared: shared.clone(),
+            },
+            TestbedHandle { shared },
+        )
+    }
+
+    fn record(&self, outbound: TestbedOutbound) {
+        self.shared.transcript.lock().push(outbound);
+    }
+}
+
+impl TestbedHandle {
+    /// Route every injected message to this agent instead of resolving
+    /// bindings.
+    pub fn set_agent(&self, agent_id: impl Into<AgentId>) {
+        *self.shared.agent_id.lock() = Some(agent_id.into());
+    }
+
+    /// Send `text` from `user` into `conversation`. Fails if the adapter
+    /// hasn't been started.
+    pub async fn send(&self, conversation: &str, user: &str, text: &str) -> crate::Result<()> {
+        let message = {
+            let mut next_id = self.shared.next_message_id.lock();
+            *next_id += 1;
+            let metadata = HashMap::from([
+                ("sender_display_name".to_string(), user.into()),
+                (
+                    crate::metadata_keys::MESSAGE_ID.to_string(),
+                    next_id.to_string().into(),
+                ),
+                (
+                    crate::metadata_keys::CHANNEL_NAME.to_string(),
+                    conversation.into(),
+                ),
+            ]);
+            InboundMessage {
+                id: format!("testbed-{next_id}"),
+                source: TESTBED.into(),
+                adapter: Some(TESTBED.into()),
+                conversation_id: format!("{TESTBED}:{conversation}"),
+                sender_id: user.into(),
+                agent_id: self.shared.agent_id.lock().clone(),
+                content: MessageContent::Text(text.into()),
+                timestamp: chrono::Utc::now(),
+                metadata,
+                formatted_author: Some(user.into()),
+            }
+        };
+        let sender = self.shared.inbound_tx.lock().clone();
+        let Some(sender) = sender else {
+            return Err(anyhow::anyhow!("testbed adapter is not started").into());
+        };
+        sender
+            .send(message)
+            .await
+            .map_err(|_| anyhow::anyhow!("testbed inbound stream closed"))?;
+        Ok(())
+    }
+
+    /// Everything the agent has sent so far, in order.
+    pub fn transcript(&self) -> Vec<TestbedOutbound> {
+        self.shared.transcript.lock().clone()
+    }
+}
+
+fn conversation_of(message: &InboundMessage) -> String {
+    message
+        .conversation_id
+        .strip_prefix(TESTBED)
+        .and_then(|rest| rest.strip_prefix(':'))
+        .unwrap_or(&message.conversation_id)
+        .to_string()
+}
+
+impl Messaging for TestbedAdapter {
+    fn name(&self) -> &str {
+        TESTBED
+    }
+
+    async fn start(&self) -> crate::Result<InboundStream> {
+        let (inbound_tx, inbound_rx) = mpsc::channel(64);
+        *self.shared.inbound_tx.lock() = Some(inbound_tx);
+        Ok(Box::pin(tokio_stream::wrappers::ReceiverStream::new(
+            inbound_rx,
+        )))
+    }
+
+    async fn respond(
+        &self,
+        message: &InboundMessage,
+        response: OutboundResponse,
+    ) -> crate::Result<()> {
+        self.record(TestbedOutbound {
+            conversation: conversation_of(message),
+            kind: TestbedOutboundKind::Response(response),
+        });
+        Ok(())
+    }
+
+    async fn send_status(
+        &self,
+        message: &InboundMessage,
+        status: StatusUpdate,
+    ) -> crate::Result<()> {
+        self.record(TestbedOutbound {
+            conversation: conversation_of(message),
+            kind: TestbedOutboundKind::Status(status),
+        });
+        Ok(())
+    }
+
+    async fn broadcast(&self, target: &str, response: OutboundResponse) -> crate::Result<()> {
+        let conversation = target
+            .strip_prefix("testbed:")
+            .unwrap_or(target)
+            .to_string();
+        self.record(TestbedOutbound {
+            conversation,
+            kind: TestbedOutboundKind::Broadcast(response),
+        });
+        Ok(())
+    }
+
+    async fn health_check(&self) -> crate::Result<()> {
+        Ok(())
+    }
+
+    async fn shutdown(&self) -> crate::Result<()> {
+        self.shared.inbound_tx.lock().take();
+        Ok(())
+    }
+}
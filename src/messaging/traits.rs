@@ -1,6 +1,9 @@
 //! Messaging trait and dynamic dispatch companion.
 
 use crate::error::Result;
+use crate::messaging::commands::CommandSpec;
+use crate::messaging::presence::Presence;
+use crate::messaging::receipts::Receipts;
 use crate::{InboundMessage, OutboundResponse, StatusUpdate};
 use futures::Stream;
 use std::pin::Pin;
@@ -65,9 +68,48 @@ pub trait Messaging: Send + Sync + 'static {
         async { Ok(Vec::new()) }
     }
 
+    /// Register native slash commands with the platform. Called after every
+    /// successful start. Adapters for platforms without native commands, or
+    /// where commands are declared outside the API, keep the default no-op.
+    fn register_commands(
+        &self,
+        commands: &[CommandSpec],
+    ) -> impl std::future::Future<Output = Result<()>> + Send {
+        let _ = commands;
+        async { Ok(()) }
+    }
+
+    /// Show the bot as online, idle, or do-not-disturb. Adapters for
+    /// platforms without bot presence keep the default no-op.
+    fn set_presence(
+        &self,
+        presence: &Presence,
+    ) -> impl std::future::Future<Output = Result<()>> + Send {
+        let _ = presence;
+        async { Ok(()) }
+    }
+
+    /// Hand the adapter a sink for delivery and read receipts. Called
+    /// before `start()`. Adapters for platforms without receipts keep the
+    /// default no-op.
+    fn set_receipts(&self, receipts: Receipts) {
+        let _ = receipts;
+    }
+
     /// Health check.
     fn health_check(&self) -> impl std::future::Future<Output = Result<()>> + Send;
 
+    /// Tear down the platform connection and establish a new one, returning
+    /// a fresh inbound stream. Called by the manager when the connection is
+    /// lost. Adapters holding resources that shouldn't be rebuilt can
+    /// override this to restart only the connection.
+    fn reconnect(&self) -> impl std::future::Future<Output = Result<InboundStream>> + Send {
+        async {
+            self.shutdown().await?;
+            self.start().await
+        }
+    }
+
     /// Graceful shutdown.
     fn shutdown(&self) -> impl std::future::Future<Output = Result<()>> + Send {
         async { Ok(()) }
@@ -107,10 +149,26 @@ pub trait MessagingDyn: Send + Sync + 'static {
         limit: usize,
     ) -> Pin<Box<dyn std::future::Future<Output = Result<Vec<HistoryMessage>>> + Send + 'a>>;
 
+    fn register_commands<'a>(
+        &'a self,
+        commands: &'a [CommandSpec],
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<()>> + Send + 'a>>;
+
+    fn set_presence<'a>(
+        &'a self,
+        presence: &'a Presence,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<()>> + Send + 'a>>;
+
+    fn set_receipts(&self, receipts: Receipts);
+
     fn health_check<'a>(
         &'a self,
     ) -> Pin<Box<dyn std::future::Future<Output = Result<()>> + Send + 'a>>;
 
+    fn reconnect<'a>(
+        &'a self,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<InboundStream>> + Send + 'a>>;
+
     fn shutdown<'a>(&'a self)
     -> Pin<Box<dyn std::future::Future<Output = Result<()>> + Send + 'a>>;
 }
@@ -159,12 +217,36 @@ impl<T: Messaging> MessagingDyn for T {
         Box::pin(Messaging::fetch_history(self, message, limit))
     }
 
+    fn register_commands<'a>(
+        &'a self,
+        commands: &'a [CommandSpec],
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<()>> + Send + 'a>> {
+        Box::pin(Messaging::register_commands(self, commands))
+    }
+
+    fn set_presence<'a>(
+        &'a self,
+        presence: &'a Presence,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<()>> + Send + 'a>> {
+        Box::pin(Messaging::set_presence(self, presence))
+    }
+
+    fn set_receipts(&self, receipts: Receipts) {
+        Messaging::set_receipts(self, receipts)
+    }
+
     fn health_check<'a>(
         &'a self,
     ) -> Pin<Box<dyn std::future::Future<Output = Result<()>> + Send + 'a>> {
         Box::pin(Messaging::health_check(self))
     }
 
+    fn reconnect<'a>(
+        &'a self,
+    ) -> Pin<Box<dyn std::future::Future<Output = Result<InboundStream>> + Send + 'a>> {
+        Box::pin(Messaging::reconnect(self))
+    }
+
     fn shutdown<'a>(
         &'a self,
     ) -> Pin<Box<dyn std::future::Future<Output = Result<()>> + Send + 'a>> {
@@ -0,0 +1,925 @@
+//! Twilio messaging adapter for SMS and, optionally, voice calls.
+//!
+//! ## Architecture
+//!
+//! - **Inbound:** Twilio webhooks, POSTed to an HTTP server the adapter runs.
+//!   Point the number's messaging webhook at `{public_url}/sms` and, for voice,
+//!   its call webhook at `{public_url}/voice`. Every request must carry a valid
+//!   `X-Twilio-Signature`, which is computed over the public URL, so
+//!   `public_url` has to be exactly what Twilio calls.
+//! - **Outbound:** REST `Messages` API calls. Each texter is one channel,
+//!   `twilio:{number}` (or `twilio:{name}:{number}` for a named adapter).
+//! - **Segments:** Replies are rendered as plain text and truncated to
+//!   `max_segments` SMS segments, counted as GSM-7 when every character fits
+//!   and as UCS-2 otherwise.
+//! - **Opt-out:** The standard keywords (`STOP`, `UNSUBSCRIBE`, ...) mark the
+//!   sender opted out and are not passed to the agent; `START` reverses it.
+//!   Opt-in keywords from a sender who isn't opted out reach the agent.
+//!   Twilio sends the confirmation itself. Replies to opted-out numbers are
+//!   dropped, and a send Twilio refuses with error 21610 opts the number out.
+//!   The opt-out set lives in memory; after a restart Twilio's own refusal
+//!   rebuilds it.
+//! - **Voice:** With `voice` on, calls are answered with a greeting and
+//!   recorded. The finished recording arrives as an audio attachment on the
+//!   caller's channel, where the channel's transcription turns it into text.
+//!   Replies go back by SMS.
+//! - **Receipts:** Sends ask for status callbacks at `{public_url}/status`;
+//!   `delivered` and `read` statuses are recorded as receipts.
+//!
+//! ## Limitations
+//!
+//! - **Files:** MMS needs a publicly reachable media URL, so outbound files
+//!   are not sent; the caption goes out with a note naming the file.
+//! - **Streaming, reactions, typing:** Not available over SMS; no-ops.
+
+use crate::config::TwilioConfig;
+use crate::messaging::format::RichText;
+use crate::messaging::receipts::{ReceiptKind, Receipts};
+use crate::messaging::traits::{
+    InboundStream, Messaging, apply_runtime_adapter_to_conversation_id,
+};
+use crate::{Attachment, InboundMessage, MessageContent, OutboundResponse, metadata_keys};
+
+use anyhow::Context as _;
+use axum::Router;
+use axum::extract::{Form, State};
+use axum::http::header::CONTENT_TYPE;
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use base64::Engine as _;
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use sha1::Sha1;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, RwLock, mpsc};
+
+// ── constants ───────────────────────────────────────────────────
+
+/// Twilio REST API base.
+const API_BASE: &str = "https://api.twilio.com/2010-04-01";
+
+const SMS_PATH: &str = "/sms";
+const VOICE_PATH: &str = "/voice";
+const VOICE_DONE_PATH: &str = "/voice/done";
+const RECORDING_PATH: &str = "/voice/recording";
+const STATUS_PATH: &str = "/status";
+
+/// Per-request timeout for REST calls.
+const API_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How long a call waits for its recording before it is forgotten.
+const PENDING_CALL_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Twilio error for a send to a number that has opted out.
+const OPTED_OUT_ERROR: u64 = 21610;
+
+/// Keywords that opt a sender out of texts (Twilio's default set).
+const OPT_OUT_KEYWORDS: &[&str] = &[
+    "STOP",
+    "STOPALL",
+    "UNSUBSCRIBE",
+    "CANCEL",
+    "END",
+    "QUIT",
+    "REVOKE",
+    "OPTOUT",
+];
+
+/// Keywords that opt a sender back in.
+const OPT_IN_KEYWORDS: &[&str] = &["START", "YES", "UNSTOP"];
+
+/// Appended to replies cut short to fit the segment limit. Plain ASCII so it
+/// never forces a GSM-7 reply into UCS-2.
+const TRUNCATION_MARKER: &str = "...";
+
+const EMPTY_TWIML: &str = r#"<?xml version="1.0" encoding="UTF-8"?><Response/>"#;
+const REJECT_TWIML: &str =
+    r#"<?xml version="1.0" encoding="UTF-8"?><Response><Reject/></Response>"#;
+const HANGUP_TWIML: &str =
+    r#"<?xml version="1.0" encoding="UTF-8"?><Response><Hangup/></Response>"#;
+
+/// GSM 03.38 basic character set, one septet each.
+const GSM7_BASIC: &str = "@£$¥èéùìòÇ\nØø\rÅåΔ_ΦΓΛΩΠΨΣΘΞÆæßÉ !\"#¤%&'()*+,-./0123456789:;<=>?\
+    ¡ABCDEFGHIJKLMNOPQRSTUVWXYZÄÖÑÜ§¿abcdefghijklmnopqrstuvwxyzäöñüà";
+
+/// GSM 03.38 extension table, two septets each (escape + character).
+const GSM7_EXTENSION: &str = "\u{c}^{}\\[~]|€";
+
+type HmacSha1 = Hmac<Sha1>;
+
+// ── adapter ─────────────────────────────────────────────────────
+
+/// State shared between the adapter and its webhook handlers.
+struct Shared {
+    runtime_key: String,
+    config: TwilioConfig,
+    inbound_tx: RwLock<Option<mpsc::Sender<InboundMessage>>>,
+    /// Numbers that texted an opt-out keyword or that Twilio refused to text.
+    opted_out: RwLock<HashSet<String>>,
+    receipts: OnceLock<Receipts>,
+    /// Callers of answered calls by call SID, until their recording arrives.
+    calls: Mutex<HashMap<String, (String, Instant)>>,
+}
+
+/// Twilio adapter for one phone number.
+pub struct TwilioAdapter {
+    shared: Arc<Shared>,
+    client: reqwest::Client,
+    shutdown_tx: Arc<RwLock<Option<mpsc::Sender<()>>>>,
+}
+
+impl TwilioAdapter {
+    pub fn new(config: TwilioConfig) -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                runtime_key: config.runtime_key(),
+                config,
+                inbound_tx: RwLock::new(None),
+                opted_out: RwLock::new(HashSet::new()),
+                receipts: OnceLock::new(),
+                calls: Mutex::new(HashMap::new()),
+            }),
+            client: reqwest::Client::new(),
+            shutdown_tx: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    /// Text `to`, fitting the reply to the segment limit.
+    async fn send_sms(&self, to: &str, text: &str) -> crate::Result<()> {
+        let config = &self.shared.config;
+        let plain = RichText::parse(text).to_plain_text(None);
+        let body = fit_segments(plain.trim(), config.max_segments);
+        if body.is_empty() {
+            return Ok(());
+        }
+        if self.shared.opted_out.read().await.contains(to) {
+            tracing::info!(to = %redact_number(to), "recipient opted out of texts, dropping reply");
+            return Ok(());
+        }
+
+        let url = format!("{API_BASE}/Accounts/{}/Messages.json", config.account_sid);
+        let status_callback = format!("{}{STATUS_PATH}", config.public_url);
+        let response = self
+            .client
+            .post(&url)
+            .basic_auth(&config.account_sid, Some(&config.auth_token))
+            .timeout(API_REQUEST_TIMEOUT)
+            .form(&[
+                ("To", to),
+                ("From", config.phone_number.as_str()),
+                ("Body", body.as_str()),
+                ("StatusCallback", status_callback.as_str()),
+            ])
+            .send()
+            .await
+            .context("failed to reach twilio")?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error = response.json::<ApiError>().await.unwrap_or_default();
+            if error.code == Some(OPTED_OUT_ERROR) {
+                tracing::info!(to = %redact_number(to), "twilio reports recipient opted out, dropping reply");
+                self.shared.opted_out.write().await.insert(to.to_string());
+                return Ok(());
+            }
+            return Err(anyhow::anyhow!(
+                "twilio rejected message ({status}): {}",
+                error.message.unwrap_or_default()
+            )
+            .into());
+        }
+
+        let message = response
+            .json::<MessageResource>()
+            .await
+            .context("failed to parse twilio message response")?;
+        if let Some(receipts) = self.shared.receipts.get() {
+            receipts.sent(&self.shared.runtime_key, &message.sid);
+        }
+        Ok(())
+    }
+
+    /// Text `to` with whatever of `response` SMS can carry.
+    async fn send_response(&self, to: &str, response: OutboundResponse) -> crate::Result<()> {
+        match response {
+            OutboundResponse::Text(text)
+            | OutboundResponse::RichMessage { text, .. }
+            | OutboundResponse::ThreadReply { text, .. }
+            | OutboundResponse::Ephemeral { text, .. }
+            | OutboundResponse::ScheduledMessage { text, .. } => self.send_sms(to, &text).await,
+            OutboundResponse::File {
+                filename, caption, ..
+            } => {
+                let note = format!("[File not sent over SMS: {filename}]");
+                let text = match caption {
+                    Some(caption) => format!("{caption}\n{note}"),
+                    None => note,
+                };
+                self.send_sms(to, &text).await
+            }
+            OutboundResponse::Reaction(_)
+            | OutboundResponse::RemoveReaction(_)
+            | OutboundResponse::Status(_)
+            | OutboundResponse::StreamStart
+            | OutboundResponse::StreamChunk(_)
+            | OutboundResponse::StreamEnd => Ok(()),
+        }
+    }
+}
+
+impl Messaging for TwilioAdapter {
+    fn name(&self) -> &str {
+        &self.shared.runtime_key
+    }
+
+    async fn start(&self) -> crate::Result<InboundStream> {
+        let (inbound_tx, inbound_rx) = mpsc::channel(256);
+        let (shutdown_tx, mut shutdown_rx) = mpsc::channel::<()>(1);
+
+        *self.shared.inbound_tx.write().await = Some(inbound_tx);
+        *self.shutdown_tx.write().await = Some(shutdown_tx);
+
+        let app = Router::new()
+            .route(SMS_PATH, post(handle_sms))
+            .route(VOICE_PATH, post(handle_voice))
+            .route(VOICE_DONE_PATH, post(handle_voice_done))
+            .route(RECORDING_PATH, post(handle_recording))
+            .route(STATUS_PATH, post(handle_status))
+            .route("/health", get(handle_health))
+            .with_state(self.shared.clone());
+
+        let config = &self.shared.config;
+        let bind = if config.bind.contains(':') {
+            format!("[{}]:{}", config.bind, config.port)
+        } else {
+            format!("{}:{}", config.bind, config.port)
+        };
+        let listener = tokio::net::TcpListener::bind(&bind)
+            .await
+            .with_context(|| format!("failed to bind twilio server to {bind}"))?;
+        tracing::info!(adapter = %self.shared.runtime_key, %bind, "twilio server listening");
+
+        let runtime_key = self.shared.runtime_key.clone();
+        tokio::spawn(async move {
+            if let Err(error) = axum::serve(listener, app)
+                .with_graceful_shutdown(async move {
+                    let _ = shutdown_rx.recv().await;
+                })
+                .await
+            {
+                tracing::error!(adapter = %runtime_key, %error, "twilio server exited with error");
+            }
+        });
+
+        let stream = tokio_stream::wrappers::ReceiverStream::new(inbound_rx);
+        Ok(Box::pin(stream))
+    }
+
+    async fn respond(
+        &self,
+        message: &InboundMessage,
+        response: OutboundResponse,
+    ) -> crate::Result<()> {
+        let Some(to) = reply_target(message) else {
+            return Err(anyhow::anyhow!(
+                "twilio message has no sender number: {}",
+                message.conversation_id
+            )
+            .into());
+        };
+        self.send_response(&to, response).await
+    }
+
+    async fn broadcast(&self, target: &str, response: OutboundResponse) -> crate::Result<()> {
+        if !is_phone_number(target) {
+            return Err(anyhow::anyhow!("invalid twilio broadcast target: {target}").into());
+        }
+        self.send_response(target, response).await
+    }
+
+    fn set_receipts(&self, receipts: Receipts) {
+        let _ = self.shared.receipts.set(receipts);
+    }
+
+    async fn health_check(&self) -> crate::Result<()> {
+        let config = &self.shared.config;
+        let url = format!("{API_BASE}/Accounts/{}.json", config.account_sid);
+        let response = self
+            .client
+            .get(&url)
+            .basic_auth(&config.account_sid, Some(&config.auth_token))
+            .timeout(API_REQUEST_TIMEOUT)
+            .send()
+            .await
+            .context("failed to reach twilio")?;
+        if !response.status().is_success() {
+            return Err(
+                anyhow::anyhow!("twilio health check failed: {}", response.status()).into(),
+            );
+        }
+        Ok(())
+    }
+
+    async fn shutdown(&self) -> crate::Result<()> {
+        if let Some(tx) = self.shutdown_tx.read().await.as_ref() {
+            tx.send(()).await.ok();
+        }
+        tracing::info!(adapter = %self.shared.runtime_key, "twilio adapter shut down");
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+struct MessageResource {
+    sid: String,
+}
+
+#[derive(Deserialize, Default)]
+struct ApiError {
+    code: Option<u64>,
+    message: Option<String>,
+}
+
+// ── webhook handlers ────────────────────────────────────────────
+
+type Params = Vec<(String, String)>;
+
+async fn handle_sms(
+    State(shared): State<Arc<Shared>>,
+    headers: HeaderMap,
+    Form(params): Form<Params>,
+) -> Result<Response, StatusCode> {
+    shared.verify(&headers, SMS_PATH, &params)?;
+
+    let from = param(&params, "From").unwrap_or_default().to_string();
+    if !shared.allows(&from) {
+        tracing::debug!(from = %redact_number(&from), "ignoring text from number not in allowed_numbers");
+        return Ok(twiml(EMPTY_TWIML));
+    }
+
+    let body = param(&params, "Body")
+        .unwrap_or_default()
+        .trim()
+        .to_string();
+    match opt_keyword(&body) {
+        Some(OptChange::Out) => {
+            tracing::info!(from = %redact_number(&from), "sender opted out of texts");
+            shared.opted_out.write().await.insert(from);
+            return Ok(twiml(EMPTY_TWIML));
+        }
+        // Only an opted-out sender is opting back in; from anyone else "yes"
+        // is an ordinary reply for the agent.
+        Some(OptChange::In) if shared.opted_out.write().await.remove(&from) => {
+            tracing::info!(from = %redact_number(&from), "sender opted back in to texts");
+            return Ok(twiml(EMPTY_TWIML));
+        }
+        Some(OptChange::In) | None => {}
+    }
+    if shared.opted_out.read().await.contains(&from) {
+        tracing::debug!(from = %redact_number(&from), "ignoring text from opted-out sender");
+        return Ok(twiml(EMPTY_TWIML));
+    }
+
+    let message_sid = param(&params, "MessageSid").unwrap_or_default().to_string();
+    let media_count = param(&params, "NumMedia")
+        .and_then(|count| count.parse::<usize>().ok())
+        .unwrap_or(0);
+    let attachments = (0..media_count)
+        .filter_map(|index| {
+            let url = param(&params, &format!("MediaUrl{index}"))?;
+            let mime_type = param(&params, &format!("MediaContentType{index}"))
+                .unwrap_or("application/octet-stream");
+            let extension = mime_type.split('/').nth(1).unwrap_or("bin");
+            Some(Attachment {
+                filename: format!("{message_sid}-{index}.{extension}"),
+                mime_type: mime_type.to_string(),
+                url: url.to_string(),
+                size_bytes: None,
+                auth_header: Some(shared.basic_auth()),
+            })
+        })
+        .collect::<Vec<_>>();
+    let content = if attachments.is_empty() {
+        MessageContent::Text(body)
+    } else {
+        MessageContent::Media {
+            text: (!body.is_empty()).then_some(body),
+            attachments,
+        }
+    };
+
+    shared.forward(&from, &message_sid, content).await?;
+    Ok(twiml(EMPTY_TWIML))
+}
+
+async fn handle_voice(
+    State(shared): State<Arc<Shared>>,
+    headers: HeaderMap,
+    Form(params): Form<Params>,
+) -> Result<Response, StatusCode> {
+    shared.verify(&headers, VOICE_PATH, &params)?;
+
+    let from = param(&params, "From").unwrap_or_default().to_string();
+    let call_sid = param(&params, "CallSid").unwrap_or_default().to_string();
+    if !shared.config.voice || !shared.allows(&from) || call_sid.is_empty() {
+        return Ok(twiml(REJECT_TWIML));
+    }
+
+    {
+        let mut calls = shared.calls.lock().await;
+        calls.retain(|_, (_, answered)| answered.elapsed() < PENDING_CALL_TTL);
+        calls.insert(call_sid, (from, Instant::now()));
+    }
+
+    let config = &shared.config;
+    let response = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?><Response><Say>{}</Say><Record maxLength="{}" playBeep="true" action="{}" recordingStatusCallback="{}"/></Response>"#,
+        xml_escape(&config.voice_greeting),
+        config.max_recording_secs,
+        xml_escape(&format!("{}{VOICE_DONE_PATH}", config.public_url)),
+        xml_escape(&format!("{}{RECORDING_PATH}", config.public_url)),
+    );
+    Ok(twiml(response))
+}
+
+/// Called when recording ends. Without an `action`, Twilio would re-request
+/// the call webhook and record again.
+async fn handle_voice_done(
+    State(shared): State<Arc<Shared>>,
+    headers: HeaderMap,
+    Form(params): Form<Params>,
+) -> Result<Response, StatusCode> {
+    shared.verify(&headers, VOICE_DONE_PATH, &params)?;
+    Ok(twiml(HANGUP_TWIML))
+}
+
+async fn handle_recording(
+    State(shared): State<Arc<Shared>>,
+    headers: HeaderMap,
+    Form(params): Form<Params>,
+) -> Result<StatusCode, StatusCode> {
+    shared.verify(&headers, RECORDING_PATH, &params)?;
+
+    let call_sid = param(&params, "CallSid").unwrap_or_default();
+    let Some((from, _)) = shared.calls.lock().await.remove(call_sid) else {
+        tracing::warn!(%call_sid, "recording for unknown call, dropping");
+        return Ok(StatusCode::OK);
+    };
+    if param(&params, "RecordingStatus") != Some("completed") {
+        tracing::warn!(%call_sid, "call recording did not complete");
+        return Ok(StatusCode::OK);
+    }
+    let (Some(recording_sid), Some(recording_url)) = (
+        param(&params, "RecordingSid"),
+        param(&params, "RecordingUrl"),
+    ) else {
+        return Ok(StatusCode::OK);
+    };
+
+    let content = MessageContent::Media {
+        text: Some("[Voice message]".into()),
+        attachments: vec![Attachment {
+            filename: format!("{recording_sid}.wav"),
+            mime_type: "audio/wav".into(),
+            url: format!("{recording_url}.wav"),
+            size_bytes: None,
+            auth_header: Some(shared.basic_auth()),
+        }],
+    };
+    shared.forward(&from, call_sid, content).await?;
+    Ok(StatusCode::OK)
+}
+
+async fn handle_status(
+    State(shared): State<Arc<Shared>>,
+    headers: HeaderMap,
+    Form(params): Form<Params>,
+) -> Result<StatusCode, StatusCode> {
+    shared.verify(&headers, STATUS_PATH, &params)?;
+
+    let message_sid = param(&params, "MessageSid").unwrap_or_default();
+    let kind = match param(&params, "MessageStatus") {
+        Some("delivered") => ReceiptKind::Delivered,
+        Some("read") => ReceiptKind::Read,
+        Some(status @ ("undelivered" | "failed")) => {
+            let error_code = param(&params, "ErrorCode").unwrap_or_default();
+            if error_code == OPTED_OUT_ERROR.to_string()
+                && let Some(to) = param(&params, "To")
+            {
+                shared.opted_out.write().await.insert(to.to_string());
+            }
+            tracing::warn!(%message_sid, %status, %error_code, "twilio could not deliver message");
+            return Ok(StatusCode::OK);
+        }
+        _ => return Ok(StatusCode::OK),
+    };
+    if let Some(receipts) = shared.receipts.get() {
+        receipts.received(&shared.runtime_key, message_sid, kind, chrono::Utc::now());
+    }
+    Ok(StatusCode::OK)
+}
+
+async fn handle_health() -> StatusCode {
+    StatusCode::OK
+}
+
+impl Shared {
+    /// Reject requests that weren't signed by Twilio with our auth token.
+    fn verify(&self, headers: &HeaderMap, path: &str, params: &Params) -> Result<(), StatusCode> {
+        let signature = headers
+            .get("x-twilio-signature")
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default();
+        let url = format!("{}{path}", self.config.public_url);
+        if verify_signature(&self.config.auth_token, &url, params, signature) {
+            Ok(())
+        } else {
+            tracing::warn!(adapter = %self.runtime_key, %path, "rejecting twilio webhook with bad signature");
+            Err(StatusCode::FORBIDDEN)
+        }
+    }
+
+    fn allows(&self, number: &str) -> bool {
+        self.config.allowed_numbers.is_empty()
+            || self
+                .config
+                .allowed_numbers
+                .iter()
+                .any(|allowed| allowed == number)
+    }
+
+    /// Basic auth for Twilio-hosted media, which requires account credentials.
+    fn basic_auth(&self) -> String {
+        let credentials = format!("{}:{}", self.config.account_sid, self.config.auth_token);
+        format!(
+            "Basic {}",
+            base64::engine::general_purpose::STANDARD.encode(credentials)
+        )
+    }
+
+    /// Pass a message from `from` to the agent on the sender's channel.
+    async fn forward(
+        &self,
+        from: &str,
+        message_id: &str,
+        content: MessageContent,
+    ) -> Result<(), StatusCode> {
+        let conversation_id =
+            apply_runtime_adapter_to_conversation_id(&self.runtime_key, format!("twilio:{from}"));
+
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "twilio_from".into(),
+            serde_json::Value::String(from.to_string()),
+        );
+        metadata.insert(
+            metadata_keys::MESSAGE_ID.into(),
+            serde_json::Value::String(message_id.to_string()),
+        );
+        metadata.insert(
+            metadata_keys::CHANNEL_NAME.into(),
+            serde_json::Value::String(from.to_string()),
+        );
+        metadata.insert(
+            "sender_display_name".into(),
+            serde_json::Value::String(from.to_string()),
+        );
+
+        let inbound = InboundMessage {
+            id: uuid::Uuid::new_v4().to_string(),
+            source: "twilio".into(),
+            adapter: Some(self.runtime_key.clone()),
+            conversation_id,
+            sender_id: from.to_string(),
+            agent_id: None,
+            content,
+            timestamp: chrono::Utc::now(),
+            metadata,
+            formatted_author: Some(from.to_string()),
+        };
+
+        let tx = self.inbound_tx.read().await;
+        let Some(tx) = tx.as_ref() else {
+            return Err(StatusCode::SERVICE_UNAVAILABLE);
+        };
+        tx.send(inbound)
+            .await
+            .map_err(|_| StatusCode::SERVICE_UNAVAILABLE)
+    }
+}
+
+// ── helpers ─────────────────────────────────────────────────────
+
+fn param<'a>(params: &'a Params, key: &str) -> Option<&'a str> {
+    params
+        .iter()
+        .find(|(name, _)| name == key)
+        .map(|(_, value)| value.as_str())
+}
+
+fn twiml(body: impl Into<String>) -> Response {
+    ([(CONTENT_TYPE, "text/xml")], body.into()).into_response()
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Number to reply to: the sender recorded on the message, else the last
+/// part of the conversation ID.
+fn reply_target(message: &InboundMessage) -> Option<String> {
+    message
+        .metadata
+        .get("twilio_from")
+        .and_then(|value| value.as_str())
+        .map(str::to_string)
+        .or_else(|| {
+            message
+                .conversation_id
+                .rsplit(':')
+                .next()
+                .filter(|number| is_phone_number(number))
+                .map(str::to_string)
+        })
+}
+
+/// Whether `target` looks like an E.164 number.
+fn is_phone_number(target: &str) -> bool {
+    target
+        .strip_prefix('+')
+        .is_some_and(|digits| !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Mask all but the last four digits of a phone number for logging.
+fn redact_number(number: &str) -> String {
+    let visible = number.len().saturating_sub(4);
+    match number.get(visible..) {
+        Some(tail) if visible > 0 => format!("***{tail}"),
+        _ => "[redacted]".into(),
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum OptChange {
+    Out,
+    In,
+}
+
+/// Whether a text is an opt-out or opt-in keyword. Only a message that is
+/// exactly the keyword counts, as Twilio does.
+fn opt_keyword(body: &str) -> Option<OptChange> {
+    let keyword = body.trim().to_ascii_uppercase();
+    if OPT_OUT_KEYWORDS.contains(&keyword.as_str()) {
+        Some(OptChange::Out)
+    } else if OPT_IN_KEYWORDS.contains(&keyword.as_str()) {
+        Some(OptChange::In)
+    } else {
+        None
+    }
+}
+
+fn expected_signature(auth_token: &str, url: &str, params: &Params) -> HmacSha1 {
+    let mut sorted = params.iter().collect::<Vec<_>>();
+    sorted.sort();
+    let mut mac =
+        HmacSha1::new_from_slice(auth_token.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(url.as_bytes());
+    for (name, value) in sorted {
+        mac.update(name.as_bytes());
+        mac.update(value.as_bytes());
+    }
+    mac
+}
+
+/// Check Twilio's request signature: HMAC-SHA1 over the URL followed by
+/// each form parameter's name and value, sorted by name.
+fn verify_signature(auth_token: &str, url: &str, params: &Params, signature: &str) -> bool {
+    let Ok(signature) = base64::engine::general_purpose::STANDARD.decode(signature) else {
+        return false;
+    };
+    expected_signature(auth_token, url, params)
+        .verify_slice(&signature)
+        .is_ok()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Encoding {
+    Gsm7,
+    Ucs2,
+}
+
+impl Encoding {
+    fn of(text: &str) -> Self {
+        if text
+            .chars()
+            .all(|c| GSM7_BASIC.contains(c) || GSM7_EXTENSION.contains(c))
+        {
+            Encoding::Gsm7
+        } else {
+            Encoding::Ucs2
+        }
+    }
+
+    /// Units `c` takes: septets for GSM-7, UTF-16 code units for UCS-2.
+    fn units(self, c: char) -> usize {
+        match self {
+            Encoding::Gsm7 if GSM7_EXTENSION.contains(c) => 2,
+            Encoding::Gsm7 => 1,
+            Encoding::Ucs2 => c.len_utf16(),
+        }
+    }
+
+    /// Units in a lone segment, and in each part of a concatenated message
+    /// (which loses room to the concatenation header).
+    fn segment_units(self) -> (usize, usize) {
+        match self {
+            Encoding::Gsm7 => (160, 153),
+            Encoding::Ucs2 => (70, 67),
+        }
+    }
+}
+
+/// Number of SMS segments `text` is sent as.
+fn segment_count(text: &str) -> usize {
+    let encoding = Encoding::of(text);
+    let units: usize = text.chars().map(|c| encoding.units(c)).sum();
+    let (single, part) = encoding.segment_units();
+    if units <= single {
+        1
+    } else {
+        units.div_ceil(part)
+    }
+}
+
+/// Truncate `text` to at most `max_segments` segments, marking the cut.
+fn fit_segments(text: &str, max_segments: usize) -> String {
+    if segment_count(text) <= max_segments {
+        return text.to_string();
+    }
+    let encoding = Encoding::of(text);
+    let (single, part) = encoding.segment_units();
+    let capacity = if max_segments <= 1 {
+        single
+    } else {
+        max_segments * part
+    };
+    let budget = capacity - TRUNCATION_MARKER.len();
+
+    let mut used = 0;
+    let mut end = 0;
+    for (index, c) in text.char_indices() {
+        used += encoding.units(c);
+        if used > budget {
+            break;
+        }
+        end = index + c.len_utf8();
+    }
+    format!("{}{TRUNCATION_MARKER}", text[..end].trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_gsm7_and_ucs2_segments() {
+        assert_eq!(segment_count(&"a".repeat(160)), 1);
+        assert_eq!(segment_count(&"a".repeat(161)), 2);
+        assert_eq!(segment_count(&"a".repeat(306)), 2);
+        assert_eq!(segment_count(&"a".repeat(307)), 3);
+        // Extension characters take two septets.
+        assert_eq!(segment_count(&"€".repeat(80)), 1);
+        assert_eq!(segment_count(&"€".repeat(81)), 2);
+        // One character outside GSM-7 switches the whole message to UCS-2.
+        assert_eq!(segment_count(&format!("{}é", "a".repeat(159))), 1);
+        assert_eq!(segment_count(&format!("{}ç", "a".repeat(69))), 1);
+        assert_eq!(segment_count(&format!("{}ç", "a".repeat(70))), 2);
+    }
+
+    #[test]
+    fn truncates_long_replies_to_segment_limit() {
+        let short = "short reply";
+        assert_eq!(fit_segments(short, 1), short);
+
+        let long = "word ".repeat(200);
+        let fitted = fit_segments(&long, 2);
+        assert!(fitted.ends_with(TRUNCATION_MARKER));
+        assert_eq!(segment_count(&fitted), 2);
+
+        let unicode = "ça ".repeat(100);
+        let fitted = fit_segments(&unicode, 1);
+        assert!(fitted.chars().count() <= 70);
+        assert_eq!(segment_count(&fitted), 1);
+    }
+
+    #[test]
+    fn recognizes_opt_keywords() {
+        assert_eq!(opt_keyword(" stop "), Some(OptChange::Out));
+        assert_eq!(opt_keyword("Unsubscribe"), Some(OptChange::Out));
+        assert_eq!(opt_keyword("START"), Some(OptChange::In));
+        assert_eq!(opt_keyword("please stop"), None);
+    }
+
+    #[test]
+    fn verifies_signatures_over_url_and_sorted_params() {
+        let params: Params = vec![
+            ("To".into(), "+15550000000".into()),
+            ("Body".into(), "hello".into()),
+            ("From".into(), "+15551234567".into()),
+        ];
+        let url = "https://bot.example.com/twilio/sms";
+        let signature = base64::engine::general_purpose::STANDARD.encode(
+            expected_signature("token", url, &params)
+                .finalize()
+                .into_bytes(),
+        );
+
+        let mut reordered = params.clone();
+        reordered.reverse();
+        assert!(verify_signature("token", url, &reordered, &signature));
+        assert!(!verify_signature("other", url, &params, &signature));
+        assert!(!verify_signature(
+            "token",
+            "https://bot.example.com/sms",
+            &params,
+            &signature
+        ));
+
+        let mut tampered = params.clone();
+        tampered[1].1 = "goodbye".into();
+        assert!(!verify_signature("token", url, &tampered, &signature));
+        assert!(!verify_signature("token", url, &params, "not base64!"));
+    }
+
+    fn test_adapter() -> TwilioAdapter {
+        TwilioAdapter::new(TwilioConfig {
+            name: None,
+            enabled: true,
+            account_sid: "AC123".into(),
+            auth_token: "token".into(),
+            phone_number: "+15550000000".into(),
+            public_url: "https://bot.example.com/twilio".into(),
+            port: 0,
+            bind: "127.0.0.1".into(),
+            allowed_numbers: Vec::new(),
+            max_segments: 3,
+            voice: false,
+            voice_greeting: String::new(),
+            max_recording_secs: 60,
+        })
+    }
+
+    async fn text(shared: &Arc<Shared>, from: &str, body: &str) -> StatusCode {
+        let params: Params = vec![
+            ("From".into(), from.into()),
+            ("Body".into(), body.into()),
+            ("MessageSid".into(), "SM1".into()),
+        ];
+        let url = format!("{}{SMS_PATH}", shared.config.public_url);
+        let signature = base64::engine::general_purpose::STANDARD.encode(
+            expected_signature(&shared.config.auth_token, &url, &params)
+                .finalize()
+                .into_bytes(),
+        );
+        let mut headers = HeaderMap::new();
+        headers.insert("x-twilio-signature", signature.parse().unwrap());
+        match handle_sms(State(shared.clone()), headers, Form(params)).await {
+            Ok(response) => response.status(),
+            Err(status) => status,
+        }
+    }
+
+    #[tokio::test]
+    async fn opt_in_keywords_only_opt_in_opted_out_senders() {
+        let adapter = test_adapter();
+        let (tx, mut rx) = mpsc::channel(4);
+        *adapter.shared.inbound_tx.write().await = Some(tx);
+
+        // "yes" from someone who never opted out is an answer for the agent.
+        assert_eq!(
+            text(&adapter.shared, "+15551234567", "yes").await,
+            StatusCode::OK
+        );
+        let inbound = rx.try_recv().expect("reply should reach the agent");
+        assert!(matches!(inbound.content, MessageContent::Text(ref body) if body == "yes"));
+
+        // From an opted-out sender it opts them back in and goes no further.
+        assert_eq!(
+            text(&adapter.shared, "+15551234567", "STOP").await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            text(&adapter.shared, "+15551234567", "yes").await,
+            StatusCode::OK
+        );
+        assert!(rx.try_recv().is_err());
+        assert!(adapter.shared.opted_out.read().await.is_empty());
+    }
+}
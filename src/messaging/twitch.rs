@@ -2,6 +2,8 @@
 
 use crate::config::TwitchPermissions;
 use crate::messaging::apply_runtime_adapter_to_conversation_id;
+use crate::messaging::chunking::{ChunkPacer, chunk_message};
+use crate::messaging::format::RichText;
 use crate::messaging::traits::{InboundStream, Messaging};
 use crate::{InboundMessage, MessageContent, OutboundResponse};
 
@@ -110,6 +112,10 @@ pub struct TwitchAdapter {
 /// Twitch chat messages are limited to 500 characters.
 const MAX_MESSAGE_LENGTH: usize = 500;
 
+/// Gap between chunks of one reply; Twitch allows 20 messages per 30 seconds
+/// for accounts that aren't channel moderators.
+const CHUNK_INTERVAL: std::time::Duration = std::time::Duration::from_millis(1500);
+
 impl TwitchAdapter {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
@@ -375,7 +381,9 @@ impl Messaging for TwitchAdapter {
 
         match response {
             OutboundResponse::Text(text) => {
-                for chunk in split_message(&text, MAX_MESSAGE_LENGTH) {
+                let mut pacer = ChunkPacer::new(CHUNK_INTERVAL);
+                for chunk in plain_chunks(&text) {
+                    pacer.wait().await;
                     client
                         .say(channel.to_owned(), chunk)
                         .await
@@ -383,7 +391,9 @@ impl Messaging for TwitchAdapter {
                 }
             }
             OutboundResponse::RichMessage { text, .. } => {
-                for chunk in split_message(&text, MAX_MESSAGE_LENGTH) {
+                let mut pacer = ChunkPacer::new(CHUNK_INTERVAL);
+                for chunk in plain_chunks(&text) {
+                    pacer.wait().await;
                     client
                         .say(channel.to_owned(), chunk)
                         .await
@@ -397,7 +407,9 @@ impl Messaging for TwitchAdapter {
                     .get("twitch_message_id")
                     .and_then(|v| v.as_str());
 
-                for chunk in split_message(&text, MAX_MESSAGE_LENGTH) {
+                let mut pacer = ChunkPacer::new(CHUNK_INTERVAL);
+                for chunk in plain_chunks(&text) {
+                    pacer.wait().await;
                     if let Some(parent_id) = reply_to_id {
                         let reply_ref = (channel, parent_id);
                         client
@@ -464,7 +476,9 @@ impl Messaging for TwitchAdapter {
 
         if let OutboundResponse::Text(text) = response {
             let channel = target.strip_prefix('#').unwrap_or(target);
-            for chunk in split_message(&text, MAX_MESSAGE_LENGTH) {
+            let mut pacer = ChunkPacer::new(CHUNK_INTERVAL);
+            for chunk in plain_chunks(&text) {
+                pacer.wait().await;
                 client
                     .say(channel.to_owned(), chunk)
                     .await
@@ -472,7 +486,9 @@ impl Messaging for TwitchAdapter {
             }
         } else if let OutboundResponse::RichMessage { text, .. } = response {
             let channel = target.strip_prefix('#').unwrap_or(target);
-            for chunk in split_message(&text, MAX_MESSAGE_LENGTH) {
+            let mut pacer = ChunkPacer::new(CHUNK_INTERVAL);
+            for chunk in plain_chunks(&text) {
+                pacer.wait().await;
                 client
                     .say(channel.to_owned(), chunk)
                     .await
@@ -505,30 +521,11 @@ impl Messaging for TwitchAdapter {
     }
 }
 
-/// Split a message into chunks that fit within Twitch's character limit.
-/// Tries to split at newlines, then spaces, then hard-cuts.
-fn split_message(text: &str, max_len: usize) -> Vec<String> {
-    if text.len() <= max_len {
-        return vec![text.to_string()];
-    }
-
-    let mut chunks = Vec::new();
-    let mut remaining = text;
-
-    while !remaining.is_empty() {
-        if remaining.len() <= max_len {
-            chunks.push(remaining.to_string());
-            break;
-        }
-
-        let split_at = remaining[..max_len]
-            .rfind('\n')
-            .or_else(|| remaining[..max_len].rfind(' '))
-            .unwrap_or(max_len);
-
-        chunks.push(remaining[..split_at].to_string());
-        remaining = remaining[split_at..].trim_start();
-    }
-
-    chunks
+/// Flatten Markdown to plain text, since Twitch chat has no markup, and split
+/// it into sendable chunks.
+fn plain_chunks(text: &str) -> Vec<String> {
+    chunk_message(
+        &RichText::parse(text).to_plain_text(None),
+        MAX_MESSAGE_LENGTH,
+    )
 }
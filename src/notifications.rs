@@ -0,0 +1,513 @@
+//! Operator notifications.
+//!
+//! Humans in `[[humans]]` opt in to categories of alert with a
+//! `[humans.notifications]` table: which categories, where to send them, and
+//! the hours when they don't want to hear anything. Approval requests, cron
+//! reports, and disabled cron jobs all go through [`OperatorNotifier`], so
+//! each person's preferences are applied the same way everywhere.
+//!
+//! An alert that lands in someone's quiet hours is held in the agent's
+//! database and sent by [`spawn_release_loop`] once their quiet hours end.
+
+use crate::AgentDeps;
+use crate::OutboundResponse;
+use crate::config::{HumanDef, NotificationCategory, NotificationPreferences};
+use crate::error::Result;
+use crate::messaging::MessagingManager;
+use crate::messaging::target::{BroadcastTarget, parse_delivery_target};
+
+use anyhow::Context as _;
+use arc_swap::ArcSwap;
+use chrono::{DateTime, TimeZone as _, Timelike as _, Utc};
+use chrono_tz::Tz;
+use sqlx::{Row as _, SqlitePool};
+
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How often held alerts are checked for release.
+const RELEASE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// One target for an alert, and when it may be sent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recipient {
+    pub target: BroadcastTarget,
+    /// When the human's quiet hours end, if the alert arrived inside them.
+    pub hold_until: Option<DateTime<Utc>>,
+}
+
+/// Sends operator alerts to the humans who asked for them. Reads the humans
+/// list on every send, so preference changes apply on hot reload.
+#[derive(Clone)]
+pub struct OperatorNotifier {
+    messaging_manager: Arc<MessagingManager>,
+    humans: Arc<ArcSwap<Vec<HumanDef>>>,
+    pool: SqlitePool,
+}
+
+impl std::fmt::Debug for OperatorNotifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OperatorNotifier").finish_non_exhaustive()
+    }
+}
+
+impl OperatorNotifier {
+    pub fn new(
+        messaging_manager: Arc<MessagingManager>,
+        humans: Arc<ArcSwap<Vec<HumanDef>>>,
+        pool: SqlitePool,
+    ) -> Self {
+        Self {
+            messaging_manager,
+            humans,
+            pool,
+        }
+    }
+
+    /// `None` when the agent has no messaging, since there'd be nowhere to
+    /// send anything.
+    pub fn from_deps(deps: &AgentDeps) -> Option<Self> {
+        deps.messaging_manager.clone().map(|messaging_manager| {
+            Self::new(
+                messaging_manager,
+                deps.humans.clone(),
+                deps.sqlite_pool.clone(),
+            )
+        })
+    }
+
+    /// Send `text` to everyone subscribed to `category`, holding it for
+    /// anyone in their quiet hours.
+    pub async fn notify(&self, category: NotificationCategory, text: &str) {
+        self.notify_at(category, text, None, Utc::now()).await;
+    }
+
+    /// Like [`Self::notify`], but skips `already_sent`, a target that got
+    /// `text` some other way.
+    pub async fn notify_excluding(
+        &self,
+        category: NotificationCategory,
+        text: &str,
+        already_sent: &BroadcastTarget,
+    ) {
+        self.notify_at(category, text, Some(already_sent), Utc::now())
+            .await;
+    }
+
+    async fn notify_at(
+        &self,
+        category: NotificationCategory,
+        text: &str,
+        already_sent: Option<&BroadcastTarget>,
+        now: DateTime<Utc>,
+    ) {
+        for recipient in recipients(&self.humans.load(), category, now) {
+            if already_sent == Some(&recipient.target) {
+                continue;
+            }
+            match recipient.hold_until {
+                Some(release_at) => {
+                    if let Err(error) = self
+                        .hold(category, &recipient.target, text, release_at)
+                        .await
+                    {
+                        tracing::warn!(
+                            ?category,
+                            target = %recipient.target,
+                            %error,
+                            "failed to hold operator notification"
+                        );
+                    }
+                }
+                None => {
+                    self.send(category.as_str(), &recipient.target, text).await;
+                }
+            }
+        }
+    }
+
+    async fn hold(
+        &self,
+        category: NotificationCategory,
+        target: &BroadcastTarget,
+        text: &str,
+        release_at: DateTime<Utc>,
+    ) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO held_notifications (category, adapter, target, text, release_at) \
+             VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(category.as_str())
+        .bind(&target.adapter)
+        .bind(&target.target)
+        .bind(text)
+        .bind(release_at)
+        .execute(&self.pool)
+        .await
+        .context("failed to hold operator notification")?;
+        Ok(())
+    }
+
+    /// Send every held alert whose quiet hours have ended by `now`. Each is
+    /// sent once; a failed send is logged like a live one, not retried.
+    pub async fn release_due(&self, now: DateTime<Utc>) -> Result<usize> {
+        let rows = sqlx::query(
+            "SELECT id, category, adapter, target, text FROM held_notifications \
+             WHERE datetime(release_at) <= datetime(?) ORDER BY id",
+        )
+        .bind(now)
+        .fetch_all(&self.pool)
+        .await
+        .context("failed to load held operator notifications")?;
+
+        let mut sent = 0;
+        for row in rows {
+            let id: i64 = row.try_get("id").unwrap_or_default();
+            let category: String = row.try_get("category").unwrap_or_default();
+            let target = BroadcastTarget {
+                adapter: row.try_get("adapter").unwrap_or_default(),
+                target: row.try_get("target").unwrap_or_default(),
+            };
+            let text: String = row.try_get("text").unwrap_or_default();
+
+            sqlx::query("DELETE FROM held_notifications WHERE id = ?")
+                .bind(id)
+                .execute(&self.pool)
+                .await
+                .context("failed to release held operator notification")?;
+            if self.send(&category, &target, &text).await {
+                sent += 1;
+            }
+        }
+        Ok(sent)
+    }
+
+    async fn send(&self, category: &str, target: &BroadcastTarget, text: &str) -> bool {
+        if !self.messaging_manager.has_adapter(&target.adapter).await {
+            tracing::warn!(
+                category,
+                %target,
+                "operator notification target's adapter is not connected"
+            );
+            return false;
+        }
+        match self
+            .messaging_manager
+            .broadcast(
+                &target.adapter,
+                &target.target,
+                OutboundResponse::Text(text.to_string()),
+            )
+            .await
+        {
+            Ok(()) => true,
+            Err(error) => {
+                tracing::warn!(
+                    category,
+                    %target,
+                    %error,
+                    "failed to deliver operator notification"
+                );
+                false
+            }
+        }
+    }
+}
+
+/// Send held operator alerts as quiet hours end. Only the leader replica
+/// sends, so each held alert goes out once.
+pub fn spawn_release_loop(deps: AgentDeps) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let Some(notifier) = OperatorNotifier::from_deps(&deps) else {
+            return;
+        };
+        loop {
+            tokio::time::sleep(RELEASE_INTERVAL).await;
+            if !deps.leader.is_leader() {
+                continue;
+            }
+            if let Err(error) = notifier.release_due(Utc::now()).await {
+                tracing::warn!(%error, "failed to release held operator notifications");
+            }
+        }
+    })
+}
+
+/// The targets of every human subscribed to `category`, without duplicates.
+/// Targets whose humans are in their quiet hours at `now` are held until
+/// those hours end; a target shared with someone who isn't is sent now.
+pub fn recipients(
+    humans: &[HumanDef],
+    category: NotificationCategory,
+    now: DateTime<Utc>,
+) -> Vec<Recipient> {
+    let mut recipients: Vec<Recipient> = Vec::new();
+    for human in humans {
+        let Some(preferences) = &human.notifications else {
+            continue;
+        };
+        if !preferences.categories.contains(&category) {
+            continue;
+        }
+        let hold_until = quiet_hours_end(preferences, now);
+        let human_targets = if preferences.targets.is_empty() {
+            crate::contacts::human_targets(human)
+        } else {
+            preferences
+                .targets
+                .iter()
+                .filter_map(|target| parse_delivery_target(target))
+                .collect()
+        };
+        for target in human_targets {
+            match recipients
+                .iter_mut()
+                .find(|recipient| recipient.target == target)
+            {
+                Some(existing) => {
+                    existing.hold_until =
+                        existing.hold_until.zip(hold_until).map(|(a, b)| a.min(b));
+                }
+                None => recipients.push(Recipient { target, hold_until }),
+            }
+        }
+    }
+    recipients
+}
+
+/// When the quiet hours `now` falls in end, or `None` if it isn't in them.
+fn quiet_hours_end(
+    preferences: &NotificationPreferences,
+    now: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    let (start, end) = preferences.quiet_hours?;
+    let timezone = preferences
+        .timezone
+        .as_deref()
+        .and_then(|timezone| timezone.parse::<Tz>().ok())
+        .unwrap_or(Tz::UTC);
+    let local = now.with_timezone(&timezone);
+    let hour = local.hour() as u8;
+    if start == end || !crate::cron::scheduler::hour_in_active_window(hour, start, end) {
+        return None;
+    }
+
+    let mut date = local.date_naive();
+    if hour >= end {
+        date = date.succ_opt()?;
+    }
+    let end_of_quiet = date.and_hms_opt(end.into(), 0, 0)?;
+    // A DST jump can skip the hour the window ends on; the next one exists.
+    timezone
+        .from_local_datetime(&end_of_quiet)
+        .earliest()
+        .or_else(|| {
+            timezone
+                .from_local_datetime(&(end_of_quiet + chrono::Duration::hours(1)))
+                .earliest()
+        })
+        .map(|release_at| release_at.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::messaging::testbed::{TestbedAdapter, TestbedOutboundKind};
+
+    fn human(id: &str, preferences: Option<NotificationPreferences>) -> HumanDef {
+        HumanDef {
+            id: id.into(),
+            display_name: None,
+            role: None,
+            bio: None,
+            description: None,
+            discord_id: Some("1234".into()),
+            telegram_id: None,
+            slack_id: None,
+            email: None,
+            notifications: preferences,
+        }
+    }
+
+    fn at_utc_hour(hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, 10, hour, 30, 0).unwrap()
+    }
+
+    fn target(raw: &str) -> BroadcastTarget {
+        parse_delivery_target(raw).unwrap()
+    }
+
+    fn sent_now(raw: &str) -> Recipient {
+        Recipient {
+            target: target(raw),
+            hold_until: None,
+        }
+    }
+
+    #[test]
+    fn only_subscribed_humans_are_notified() {
+        let humans = vec![
+            human(
+                "ops",
+                Some(NotificationPreferences {
+                    categories: vec![NotificationCategory::Approvals],
+                    targets: vec!["telegram:111".into()],
+                    ..Default::default()
+                }),
+            ),
+            human(
+                "reports",
+                Some(NotificationPreferences {
+                    categories: vec![NotificationCategory::CronReports],
+                    targets: vec!["telegram:222".into()],
+                    ..Default::default()
+                }),
+            ),
+            human("silent", None),
+        ];
+
+        assert_eq!(
+            recipients(&humans, NotificationCategory::Approvals, at_utc_hour(12)),
+            vec![sent_now("telegram:111")]
+        );
+        assert_eq!(
+            recipients(&humans, NotificationCategory::CronReports, at_utc_hour(12)),
+            vec![sent_now("telegram:222")]
+        );
+        assert!(
+            recipients(&humans, NotificationCategory::CronFailures, at_utc_hour(12)).is_empty()
+        );
+    }
+
+    #[test]
+    fn no_targets_falls_back_to_platform_ids() {
+        let humans = vec![human(
+            "jamie",
+            Some(NotificationPreferences {
+                categories: vec![NotificationCategory::Approvals],
+                ..Default::default()
+            }),
+        )];
+
+        assert_eq!(
+            recipients(&humans, NotificationCategory::Approvals, at_utc_hour(12)),
+            vec![sent_now("discord:dm:1234")]
+        );
+    }
+
+    #[test]
+    fn shared_targets_are_sent_once() {
+        let preferences = NotificationPreferences {
+            categories: vec![NotificationCategory::Approvals],
+            targets: vec!["telegram:111".into()],
+            ..Default::default()
+        };
+        let quiet = NotificationPreferences {
+            quiet_hours: Some((0, 23)),
+            ..preferences.clone()
+        };
+        let humans = vec![human("a", Some(quiet)), human("b", Some(preferences))];
+
+        assert_eq!(
+            recipients(&humans, NotificationCategory::Approvals, at_utc_hour(12)),
+            vec![sent_now("telegram:111")]
+        );
+    }
+
+    #[test]
+    fn quiet_hours_wrap_midnight_in_the_humans_timezone() {
+        let humans = vec![human(
+            "night",
+            Some(NotificationPreferences {
+                categories: vec![NotificationCategory::CronFailures],
+                targets: vec!["telegram:111".into()],
+                quiet_hours: Some((22, 7)),
+                timezone: Some("America/New_York".into()),
+            }),
+        )];
+        let hold_until = |hour| {
+            recipients(
+                &humans,
+                NotificationCategory::CronFailures,
+                at_utc_hour(hour),
+            )[0]
+            .hold_until
+        };
+
+        // New York is UTC-4 on this date, so quiet hours end at 11:00 UTC:
+        // 03:30 UTC is 23:30 local, 10:30 UTC is 06:30 local, 12:30 UTC is
+        // 08:30 local, 01:30 UTC is 21:30 local.
+        let morning = Utc.with_ymd_and_hms(2026, 3, 10, 11, 0, 0).unwrap();
+        assert_eq!(hold_until(3), Some(morning));
+        assert_eq!(hold_until(10), Some(morning));
+        assert_eq!(hold_until(12), None);
+        assert_eq!(hold_until(1), None);
+    }
+
+    #[tokio::test]
+    async fn alerts_in_quiet_hours_are_held_until_they_end() {
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("failed to create sqlite memory pool");
+        sqlx::migrate!("./migrations")
+            .run(&pool)
+            .await
+            .expect("migrations");
+        let messaging_manager = Arc::new(MessagingManager::new());
+        let (adapter, handle) = TestbedAdapter::new();
+        messaging_manager.register(adapter).await;
+        let humans = Arc::new(ArcSwap::from_pointee(vec![human(
+            "night",
+            Some(NotificationPreferences {
+                categories: vec![NotificationCategory::Approvals],
+                targets: vec!["testbed:night".into(), "testbed:pager".into()],
+                quiet_hours: Some((22, 7)),
+                timezone: None,
+            }),
+        )]));
+        let notifier = OperatorNotifier::new(messaging_manager, humans, pool);
+        let broadcasts = || {
+            handle
+                .transcript()
+                .into_iter()
+                .filter(|outbound| matches!(outbound.kind, TestbedOutboundKind::Broadcast(_)))
+                .map(|outbound| {
+                    (
+                        outbound.conversation.clone(),
+                        outbound.text().map(str::to_string),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+
+        notifier
+            .notify_at(
+                NotificationCategory::Approvals,
+                "approve me",
+                Some(&target("testbed:pager")),
+                at_utc_hour(23),
+            )
+            .await;
+        assert!(
+            broadcasts().is_empty(),
+            "nothing is sent during quiet hours"
+        );
+
+        assert_eq!(notifier.release_due(at_utc_hour(23)).await.unwrap(), 0);
+        assert!(broadcasts().is_empty());
+
+        let morning = Utc.with_ymd_and_hms(2026, 3, 11, 7, 0, 0).unwrap();
+        assert_eq!(notifier.release_due(morning).await.unwrap(), 1);
+        assert_eq!(
+            broadcasts(),
+            vec![("night".to_string(), Some("approve me".to_string()))]
+        );
+
+        // Released alerts are gone, not sent again.
+        assert_eq!(notifier.release_due(morning).await.unwrap(), 0);
+        assert_eq!(broadcasts().len(), 1);
+    }
+}
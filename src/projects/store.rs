@@ -7,6 +7,9 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use sqlx::{Row as _, SqlitePool};
 
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
 // Enums
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -179,11 +182,27 @@ pub struct CreateWorktreeInput {
 #[derive(Debug, Clone)]
 pub struct ProjectStore {
     pool: SqlitePool,
+    /// Bumped by writes that change what the channel prompt shows.
+    generation: Arc<AtomicU64>,
 }
 
 impl ProjectStore {
     pub fn new(pool: SqlitePool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            generation: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Counter that changes whenever a project, repo, or worktree is
+    /// created, edited, or removed through this store. Branch and disk
+    /// usage refreshes don't count.
+    pub fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Relaxed)
+    }
+
+    fn changed(&self) {
+        self.generation.fetch_add(1, Ordering::Relaxed);
     }
 
     // -- Projects -----------------------------------------------------------
@@ -211,6 +230,7 @@ impl ProjectStore {
         .execute(&self.pool)
         .await
         .context("failed to insert project")?;
+        self.changed();
 
         Ok(self
             .get_project(&input.agent_id, &id)
@@ -294,6 +314,7 @@ impl ProjectStore {
         .execute(&self.pool)
         .await
         .context("failed to update project")?;
+        self.changed();
 
         self.get_project(agent_id, project_id).await
     }
@@ -305,6 +326,7 @@ impl ProjectStore {
             .execute(&self.pool)
             .await
             .context("failed to delete project")?;
+        self.changed();
 
         Ok(result.rows_affected() > 0)
     }
@@ -349,6 +371,7 @@ impl ProjectStore {
         .execute(&self.pool)
         .await
         .context("failed to insert repo")?;
+        self.changed();
 
         Ok(self
             .get_repo(&id)
@@ -383,6 +406,7 @@ impl ProjectStore {
             .execute(&self.pool)
             .await
             .context("failed to delete repo")?;
+        self.changed();
 
         Ok(result.rows_affected() > 0)
     }
@@ -424,6 +448,7 @@ impl ProjectStore {
         .execute(&self.pool)
         .await
         .context("failed to insert worktree")?;
+        self.changed();
 
         Ok(self
             .get_worktree(&id)
@@ -489,6 +514,7 @@ impl ProjectStore {
             .execute(&self.pool)
             .await
             .context("failed to delete worktree")?;
+        self.changed();
 
         Ok(result.rows_affected() > 0)
     }
@@ -669,6 +695,40 @@ mod tests {
         assert_eq!(projects[0].id, project.id);
     }
 
+    #[tokio::test]
+    async fn generation_moves_only_on_prompt_visible_writes() {
+        let pool = setup_pool().await;
+        let store = ProjectStore::new(pool);
+        let shared = store.clone();
+        assert_eq!(store.generation(), 0);
+
+        let project = store
+            .create_project(CreateProjectInput {
+                agent_id: "agent-1".into(),
+                name: "Spacebot".into(),
+                description: "".into(),
+                icon: "".into(),
+                tags: Vec::new(),
+                root_path: "/tmp/spacebot".into(),
+                settings: Value::Object(Default::default()),
+            })
+            .await
+            .expect("failed to create project");
+        assert_eq!(shared.generation(), 1);
+
+        store
+            .list_projects("agent-1", None)
+            .await
+            .expect("failed to list projects");
+        assert_eq!(store.generation(), 1);
+
+        store
+            .delete_project("agent-1", &project.id)
+            .await
+            .expect("failed to delete project");
+        assert_eq!(store.generation(), 2);
+    }
+
     #[tokio::test]
     async fn create_repo_and_worktree() {
         let pool = setup_pool().await;
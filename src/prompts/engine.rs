@@ -62,6 +62,14 @@ impl PromptEngine {
             "memory_persistence",
             crate::prompts::text::get("memory_persistence"),
         )?;
+        env.add_template(
+            "memory_consolidation",
+            crate::prompts::text::get("memory_consolidation"),
+        )?;
+        env.add_template(
+            "memory_rescore",
+            crate::prompts::text::get("memory_rescore"),
+        )?;
         env.add_template("ingestion", crate::prompts::text::get("ingestion"))?;
         env.add_template("cortex_chat", crate::prompts::text::get("cortex_chat"))?;
         env.add_template(
@@ -69,6 +77,16 @@ impl PromptEngine {
             crate::prompts::text::get("cortex_profile"),
         )?;
         env.add_template("factory", crate::prompts::text::get("factory"))?;
+        env.add_template("translator", crate::prompts::text::get("translator"))?;
+        env.add_template(
+            "reply_verifier",
+            crate::prompts::text::get("reply_verifier"),
+        )?;
+        env.add_template(
+            "reply_softener",
+            crate::prompts::text::get("reply_softener"),
+        )?;
+        env.add_template("smart_router", crate::prompts::text::get("smart_router"))?;
 
         // Adapter-specific prompt fragments
         env.add_template(
@@ -80,6 +98,10 @@ impl PromptEngine {
             "adapters/signal",
             crate::prompts::text::get("adapters/signal"),
         )?;
+        env.add_template(
+            "adapters/twilio",
+            crate::prompts::text::get("adapters/twilio"),
+        )?;
 
         // Fragment templates
         env.add_template(
@@ -144,6 +166,18 @@ impl PromptEngine {
             "fragments/system/ingestion_chunk",
             crate::prompts::text::get("fragments/system/ingestion_chunk"),
         )?;
+        env.add_template(
+            "fragments/system/memory_backfill_batch",
+            crate::prompts::text::get("fragments/system/memory_backfill_batch"),
+        )?;
+        env.add_template(
+            "fragments/system/memory_consolidation_cluster",
+            crate::prompts::text::get("fragments/system/memory_consolidation_cluster"),
+        )?;
+        env.add_template(
+            "fragments/system/memory_rescore_batch",
+            crate::prompts::text::get("fragments/system/memory_rescore_batch"),
+        )?;
         env.add_template(
             "fragments/system/history_backfill",
             crate::prompts::text::get("fragments/system/history_backfill"),
@@ -156,6 +190,18 @@ impl PromptEngine {
             "fragments/coalesce_hint",
             crate::prompts::text::get("fragments/coalesce_hint"),
         )?;
+        env.add_template(
+            "fragments/speculative_draft",
+            crate::prompts::text::get("fragments/speculative_draft"),
+        )?;
+        env.add_template(
+            "fragments/speculative_verify",
+            crate::prompts::text::get("fragments/speculative_verify"),
+        )?;
+        env.add_template(
+            "fragments/faq_match",
+            crate::prompts::text::get("fragments/faq_match"),
+        )?;
 
         Ok(Self {
             env: Arc::new(env),
@@ -417,6 +463,57 @@ impl PromptEngine {
         )
     }
 
+    /// Convenience method for rendering a memory backfill batch prompt.
+    pub fn render_system_memory_backfill_batch(
+        &self,
+        channel_id: &str,
+        batch_number: i64,
+        first_at: &str,
+        last_at: &str,
+        transcript: &str,
+    ) -> Result<String> {
+        self.render(
+            "fragments/system/memory_backfill_batch",
+            context! {
+                channel_id => channel_id,
+                batch_number => batch_number,
+                first_at => first_at,
+                last_at => last_at,
+                transcript => transcript,
+            },
+        )
+    }
+
+    /// Convenience method for rendering a memory consolidation cluster prompt.
+    pub fn render_system_memory_consolidation_cluster(
+        &self,
+        memory_type: &str,
+        memories: &str,
+    ) -> Result<String> {
+        self.render(
+            "fragments/system/memory_consolidation_cluster",
+            context! {
+                memory_type => memory_type,
+                memories => memories,
+            },
+        )
+    }
+
+    /// Convenience method for rendering a memory rescore batch prompt.
+    pub fn render_system_memory_rescore_batch(
+        &self,
+        window_days: i64,
+        memories: &str,
+    ) -> Result<String> {
+        self.render(
+            "fragments/system/memory_rescore_batch",
+            context! {
+                window_days => window_days,
+                memories => memories,
+            },
+        )
+    }
+
     /// Render the history backfill wrapper with instructions not to act on it.
     pub fn render_system_history_backfill(&self, transcript: &str) -> Result<String> {
         self.render(
@@ -444,6 +541,23 @@ impl PromptEngine {
         )
     }
 
+    /// Render a curated Q&A pair that may answer the user's message.
+    pub fn render_faq_match(
+        &self,
+        question: &str,
+        answer: &str,
+        similarity: f32,
+    ) -> Result<String> {
+        self.render(
+            "fragments/faq_match",
+            context! {
+                question => question,
+                answer => answer,
+                similarity => format!("{similarity:.2}"),
+            },
+        )
+    }
+
     /// Render the complete channel system prompt with all dynamic components.
     #[allow(clippy::too_many_arguments)]
     pub fn render_channel_prompt(
@@ -481,6 +595,7 @@ impl PromptEngine {
             "email" => "adapters/email",
             "cron" => "adapters/cron",
             "signal" => "adapters/signal",
+            "twilio" => "adapters/twilio",
             _ => return None,
         };
 
@@ -62,14 +62,23 @@ fn lookup(lang: &str, key: &str) -> &'static str {
         ("en", "cortex_profile") => include_str!("../../prompts/en/cortex_profile.md.j2"),
         ("en", "compactor") => include_str!("../../prompts/en/compactor.md.j2"),
         ("en", "memory_persistence") => include_str!("../../prompts/en/memory_persistence.md.j2"),
+        ("en", "memory_consolidation") => {
+            include_str!("../../prompts/en/memory_consolidation.md.j2")
+        }
+        ("en", "memory_rescore") => include_str!("../../prompts/en/memory_rescore.md.j2"),
         ("en", "ingestion") => include_str!("../../prompts/en/ingestion.md.j2"),
         ("en", "cortex_chat") => include_str!("../../prompts/en/cortex_chat.md.j2"),
         ("en", "factory") => include_str!("../../prompts/en/factory.md.j2"),
+        ("en", "translator") => include_str!("../../prompts/en/translator.md.j2"),
+        ("en", "reply_verifier") => include_str!("../../prompts/en/reply_verifier.md.j2"),
+        ("en", "reply_softener") => include_str!("../../prompts/en/reply_softener.md.j2"),
+        ("en", "smart_router") => include_str!("../../prompts/en/smart_router.md.j2"),
 
         // Adapter-specific prompt fragments
         ("en", "adapters/email") => include_str!("../../prompts/en/adapters/email.md.j2"),
         ("en", "adapters/cron") => include_str!("../../prompts/en/adapters/cron.md.j2"),
         ("en", "adapters/signal") => include_str!("../../prompts/en/adapters/signal.md.j2"),
+        ("en", "adapters/twilio") => include_str!("../../prompts/en/adapters/twilio.md.j2"),
 
         // Fragment Templates
         ("en", "fragments/worker_capabilities") => {
@@ -113,6 +122,15 @@ fn lookup(lang: &str, key: &str) -> &'static str {
         ("en", "fragments/system/ingestion_chunk") => {
             include_str!("../../prompts/en/fragments/system/ingestion_chunk.md.j2")
         }
+        ("en", "fragments/system/memory_backfill_batch") => {
+            include_str!("../../prompts/en/fragments/system/memory_backfill_batch.md.j2")
+        }
+        ("en", "fragments/system/memory_consolidation_cluster") => {
+            include_str!("../../prompts/en/fragments/system/memory_consolidation_cluster.md.j2")
+        }
+        ("en", "fragments/system/memory_rescore_batch") => {
+            include_str!("../../prompts/en/fragments/system/memory_rescore_batch.md.j2")
+        }
         ("en", "fragments/system/history_backfill") => {
             include_str!("../../prompts/en/fragments/system/history_backfill.md.j2")
         }
@@ -127,6 +145,17 @@ fn lookup(lang: &str, key: &str) -> &'static str {
         ("en", "fragments/coalesce_hint") => {
             include_str!("../../prompts/en/fragments/coalesce_hint.md.j2")
         }
+        // Speculative draft-and-verify replies
+        ("en", "fragments/speculative_draft") => {
+            include_str!("../../prompts/en/fragments/speculative_draft.md.j2")
+        }
+        ("en", "fragments/speculative_verify") => {
+            include_str!("../../prompts/en/fragments/speculative_verify.md.j2")
+        }
+        // FAQ matching
+        ("en", "fragments/faq_match") => {
+            include_str!("../../prompts/en/fragments/faq_match.md.j2")
+        }
         // Projects Context
         ("en", "fragments/projects_context") => {
             include_str!("../../prompts/en/fragments/projects_context.md.j2")
@@ -162,9 +191,29 @@ fn lookup(lang: &str, key: &str) -> &'static str {
             include_str!("../../prompts/en/tools/file_list_description.md.j2")
         }
         ("en", "tools/browser") => include_str!("../../prompts/en/tools/browser_description.md.j2"),
+        ("en", "tools/chart") => include_str!("../../prompts/en/tools/chart_description.md.j2"),
+        ("en", "tools/issue_tracker") => {
+            include_str!("../../prompts/en/tools/issue_tracker_description.md.j2")
+        }
         ("en", "tools/web_search") => {
             include_str!("../../prompts/en/tools/web_search_description.md.j2")
         }
+        ("en", "tools/weather") => include_str!("../../prompts/en/tools/weather_description.md.j2"),
+        ("en", "tools/geocode") => include_str!("../../prompts/en/tools/geocode_description.md.j2"),
+        ("en", "tools/calculate") => {
+            include_str!("../../prompts/en/tools/calculate_description.md.j2")
+        }
+        ("en", "tools/convert") => include_str!("../../prompts/en/tools/convert_description.md.j2"),
+        ("en", "tools/contacts") => {
+            include_str!("../../prompts/en/tools/contacts_description.md.j2")
+        }
+        ("en", "tools/locale") => include_str!("../../prompts/en/tools/locale_description.md.j2"),
+        ("en", "tools/broadcast") => {
+            include_str!("../../prompts/en/tools/broadcast_description.md.j2")
+        }
+        ("en", "tools/translate") => {
+            include_str!("../../prompts/en/tools/translate_description.md.j2")
+        }
         ("en", "tools/memory_save") => {
             include_str!("../../prompts/en/tools/memory_save_description.md.j2")
         }
@@ -208,6 +257,18 @@ fn lookup(lang: &str, key: &str) -> &'static str {
         ("en", "tools/task_update") => {
             include_str!("../../prompts/en/tools/task_update_description.md.j2")
         }
+        ("en", "tools/todo_update") => {
+            include_str!("../../prompts/en/tools/todo_update_description.md.j2")
+        }
+        ("en", "tools/entity_profile") => {
+            include_str!("../../prompts/en/tools/entity_profile_description.md.j2")
+        }
+        ("en", "tools/faq_propose") => {
+            include_str!("../../prompts/en/tools/faq_propose_description.md.j2")
+        }
+        ("en", "tools/lookup_channel") => {
+            include_str!("../../prompts/en/tools/lookup_channel_description.md.j2")
+        }
         ("en", "tools/skills_search") => {
             include_str!("../../prompts/en/tools/skills_search_description.md.j2")
         }
@@ -0,0 +1,286 @@
+//! Per-agent resource quotas.
+//!
+//! Each agent gets an [`AgentQuota`] sized from its `[quotas]` config. It
+//! bounds how many branches and workers the agent runs at once, how many LLM
+//! requests it has in flight, and (through the pool size set at connect time)
+//! how many SQLite connections it holds, so one runaway agent queues behind
+//! its own limits instead of starving the rest of the process.
+
+use crate::AgentId;
+use crate::config::QuotaConfig;
+
+use serde::Serialize;
+use sqlx::SqlitePool;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// How often the DB sampler checks pool occupancy.
+const DB_SAMPLE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// A budgeted resource.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuotaResource {
+    Tasks,
+    Llm,
+    Db,
+}
+
+impl QuotaResource {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Tasks => "tasks",
+            Self::Llm => "llm",
+            Self::Db => "db",
+        }
+    }
+}
+
+/// Current usage of one budget.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct QuotaUsage {
+    /// `tasks`, `llm`, or `db`.
+    pub resource: &'static str,
+    pub in_use: u64,
+    pub limit: u64,
+    /// Times a request found the budget exhausted and had to wait. For `db`,
+    /// the number of samples that found every connection checked out.
+    pub saturated_total: u64,
+}
+
+struct Budget {
+    resource: QuotaResource,
+    semaphore: Arc<Semaphore>,
+    limit: usize,
+    saturated: AtomicU64,
+}
+
+impl Budget {
+    fn new(resource: QuotaResource, limit: usize) -> Self {
+        Self {
+            resource,
+            semaphore: Arc::new(Semaphore::new(limit)),
+            limit,
+            saturated: AtomicU64::new(0),
+        }
+    }
+
+    async fn acquire(&self, agent_id: &str) -> OwnedSemaphorePermit {
+        let permit = match self.semaphore.clone().try_acquire_owned() {
+            Ok(permit) => permit,
+            Err(_) => {
+                // Counted rather than logged: an agent pinned at its limit
+                // hits this on every task or LLM call.
+                self.saturated.fetch_add(1, Ordering::Relaxed);
+                #[cfg(feature = "metrics")]
+                crate::telemetry::Metrics::global()
+                    .agent_quota_saturated_total
+                    .with_label_values(&[agent_id, self.resource.as_str()])
+                    .inc();
+                self.semaphore
+                    .clone()
+                    .acquire_owned()
+                    .await
+                    .expect("quota semaphore is never closed")
+            }
+        };
+        self.report(agent_id);
+        permit
+    }
+
+    fn in_use(&self) -> usize {
+        self.limit - self.semaphore.available_permits()
+    }
+
+    fn report(&self, _agent_id: &str) {
+        #[cfg(feature = "metrics")]
+        crate::telemetry::Metrics::global()
+            .agent_quota_in_use
+            .with_label_values(&[_agent_id, self.resource.as_str()])
+            .set(self.in_use() as i64);
+    }
+
+    fn usage(&self) -> QuotaUsage {
+        QuotaUsage {
+            resource: self.resource.as_str(),
+            in_use: self.in_use() as u64,
+            limit: self.limit as u64,
+            saturated_total: self.saturated.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// A held slot. Releases (and updates metrics) on drop.
+pub struct QuotaPermit {
+    quota: Arc<AgentQuota>,
+    resource: QuotaResource,
+    permit: Option<OwnedSemaphorePermit>,
+}
+
+impl Drop for QuotaPermit {
+    fn drop(&mut self) {
+        drop(self.permit.take());
+        self.quota
+            .budget(self.resource)
+            .report(&self.quota.agent_id);
+    }
+}
+
+/// Per-agent runtime handle enforcing the agent's [`QuotaConfig`].
+pub struct AgentQuota {
+    agent_id: AgentId,
+    tasks: Budget,
+    llm: Budget,
+    db_pool: SqlitePool,
+    db_limit: u32,
+    db_saturated: AtomicU64,
+}
+
+impl AgentQuota {
+    /// `db_pool` should have been opened with `config.max_db_connections`
+    /// (see [`crate::db::Db::connect_with_pool_size`]).
+    pub fn new(agent_id: AgentId, config: &QuotaConfig, db_pool: SqlitePool) -> Arc<Self> {
+        let quota = Self {
+            tasks: Budget::new(QuotaResource::Tasks, config.max_concurrent_tasks),
+            llm: Budget::new(QuotaResource::Llm, config.max_concurrent_llm_calls),
+            db_pool,
+            db_limit: config.max_db_connections,
+            db_saturated: AtomicU64::new(0),
+            agent_id,
+        };
+        #[cfg(feature = "metrics")]
+        {
+            let metrics = crate::telemetry::Metrics::global();
+            for (resource, limit) in [
+                (QuotaResource::Tasks, config.max_concurrent_tasks as i64),
+                (QuotaResource::Llm, config.max_concurrent_llm_calls as i64),
+                (QuotaResource::Db, config.max_db_connections as i64),
+            ] {
+                metrics
+                    .agent_quota_limit
+                    .with_label_values(&[&*quota.agent_id, resource.as_str()])
+                    .set(limit);
+            }
+        }
+        Arc::new(quota)
+    }
+
+    fn budget(&self, resource: QuotaResource) -> &Budget {
+        match resource {
+            QuotaResource::Tasks => &self.tasks,
+            QuotaResource::Llm => &self.llm,
+            QuotaResource::Db => unreachable!("db usage is bounded by the pool, not a permit"),
+        }
+    }
+
+    /// Wait for a task or LLM slot. The slot is held until the permit drops.
+    pub async fn acquire(self: &Arc<Self>, resource: QuotaResource) -> QuotaPermit {
+        let permit = self.budget(resource).acquire(&self.agent_id).await;
+        QuotaPermit {
+            quota: self.clone(),
+            resource,
+            permit: Some(permit),
+        }
+    }
+
+    /// Wrap a branch or worker future so it waits for a task slot before
+    /// running and releases it when done.
+    pub fn gate<F>(self: &Arc<Self>, future: F) -> impl Future<Output = F::Output> + use<F>
+    where
+        F: Future,
+    {
+        let quota = self.clone();
+        async move {
+            let _permit = quota.acquire(QuotaResource::Tasks).await;
+            future.await
+        }
+    }
+
+    fn db_in_use(&self) -> u32 {
+        self.db_pool
+            .size()
+            .saturating_sub(self.db_pool.num_idle() as u32)
+    }
+
+    /// Periodically sample SQLite pool occupancy for metrics and health.
+    pub fn spawn_db_sampler(self: &Arc<Self>) {
+        let quota = Arc::downgrade(self);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(DB_SAMPLE_INTERVAL);
+            loop {
+                interval.tick().await;
+                let Some(quota) = quota.upgrade() else {
+                    break;
+                };
+                if quota.db_pool.is_closed() {
+                    break;
+                }
+                let in_use = quota.db_in_use();
+                if in_use >= quota.db_limit {
+                    quota.db_saturated.fetch_add(1, Ordering::Relaxed);
+                    #[cfg(feature = "metrics")]
+                    crate::telemetry::Metrics::global()
+                        .agent_quota_saturated_total
+                        .with_label_values(&[&*quota.agent_id, QuotaResource::Db.as_str()])
+                        .inc();
+                }
+                #[cfg(feature = "metrics")]
+                crate::telemetry::Metrics::global()
+                    .agent_quota_in_use
+                    .with_label_values(&[&*quota.agent_id, QuotaResource::Db.as_str()])
+                    .set(in_use as i64);
+            }
+        });
+    }
+
+    /// Usage of every budget, for the agent health endpoint.
+    pub fn usage(&self) -> Vec<QuotaUsage> {
+        vec![
+            self.tasks.usage(),
+            self.llm.usage(),
+            QuotaUsage {
+                resource: QuotaResource::Db.as_str(),
+                in_use: self.db_in_use() as u64,
+                limit: self.db_limit as u64,
+                saturated_total: self.db_saturated.load(Ordering::Relaxed),
+            },
+        ]
+    }
+
+    /// Whether any budget is fully in use right now.
+    pub fn is_saturated(&self) -> bool {
+        self.usage().iter().any(|usage| usage.in_use >= usage.limit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn task_budget_queues_past_limit() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        let config = QuotaConfig {
+            max_concurrent_tasks: 1,
+            ..QuotaConfig::default()
+        };
+        let quota = AgentQuota::new(Arc::from("agent"), &config, pool);
+
+        let first = quota.acquire(QuotaResource::Tasks).await;
+        assert!(quota.is_saturated());
+
+        let waiting = tokio::spawn(quota.gate(async { 7 }));
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!waiting.is_finished());
+
+        drop(first);
+        assert_eq!(waiting.await.unwrap(), 7);
+
+        let tasks = &quota.usage()[0];
+        assert_eq!(tasks.resource, "tasks");
+        assert_eq!(tasks.in_use, 0);
+        assert_eq!(tasks.saturated_total, 1);
+    }
+}
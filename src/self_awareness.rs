@@ -192,6 +192,7 @@ pub fn runtime_snapshot_value(agent_id: &str, runtime_config: &RuntimeConfig) ->
         "memory_persistence": {
             "enabled": memory_persistence.enabled,
             "message_interval": memory_persistence.message_interval,
+            "embedding_batch_size": memory_persistence.embedding_batch_size,
         },
         "coalesce": {
             "enabled": coalesce.enabled,
@@ -213,6 +214,9 @@ pub fn runtime_snapshot_value(agent_id: &str, runtime_config: &RuntimeConfig) ->
             "bulletin_interval_secs": cortex.bulletin_interval_secs,
             "bulletin_max_words": cortex.bulletin_max_words,
             "bulletin_max_turns": cortex.bulletin_max_turns,
+            "recalibration_interval_secs": cortex.recalibration_interval_secs,
+            "consolidation_interval_secs": cortex.consolidation_interval_secs,
+            "rescore_interval_secs": cortex.rescore_interval_secs,
             "association_interval_secs": cortex.association_interval_secs,
             "association_similarity_threshold": cortex.association_similarity_threshold,
             "association_updates_threshold": cortex.association_updates_threshold,
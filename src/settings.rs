@@ -2,4 +2,6 @@
 
 pub mod store;
 
-pub use store::{CHANNEL_LISTEN_ONLY_MODE_KEY, SettingsStore, WORKER_LOG_MODE_KEY, WorkerLogMode};
+pub use store::{
+    CHANNEL_LISTEN_ONLY_MODE_KEY, Locale, SettingsStore, WORKER_LOG_MODE_KEY, WorkerLogMode,
+};
@@ -1,6 +1,8 @@
 //! Key-value settings storage (redb).
 
+use crate::config::ReplyVerification;
 use crate::error::{Result, SettingsError};
+use crate::llm::smart_router::Tier;
 use redb::{Database, TableDefinition};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
@@ -15,6 +17,38 @@ pub const WORKER_LOG_MODE_KEY: &str = "worker_log_mode";
 pub const CHANNEL_LISTEN_ONLY_MODE_KEY: &str = "channel_listen_only_mode";
 const CHANNEL_LISTEN_ONLY_MODE_PREFIX: &str = "channel_listen_only_mode:";
 const PROMPT_CAPTURE_PREFIX: &str = "prompt_capture:";
+const CHANNEL_LOCALE_PREFIX: &str = "locale:channel:";
+const USER_LOCALE_PREFIX: &str = "locale:user:";
+const CHANNEL_MODEL_TIER_PREFIX: &str = "model_tier:";
+const CHANNEL_SPECULATIVE_DRAFTS_PREFIX: &str = "speculative_drafts:";
+const CHANNEL_REPLY_VERIFICATION_PREFIX: &str = "reply_verification:";
+/// Key for the agent's pause flag, set through the pause API.
+const AGENT_PAUSED_KEY: &str = "agent_paused";
+
+/// Where a channel or user is and which timezone their times are in.
+///
+/// Either field may be set on its own. Times resolve user first, then
+/// channel, then the agent's `user_timezone` and `cron_timezone`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct Locale {
+    /// IANA timezone name, e.g. `Europe/Berlin`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    /// Free-form place name, e.g. `Berlin, Germany`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+}
+
+impl Locale {
+    pub fn is_empty(&self) -> bool {
+        self.timezone.is_none() && self.location.is_none()
+    }
+
+    /// The timezone, if it names a real IANA zone.
+    pub fn tz(&self) -> Option<chrono_tz::Tz> {
+        self.timezone.as_deref()?.parse().ok()
+    }
+}
 
 /// How worker execution logs are stored.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
@@ -120,6 +154,39 @@ impl SettingsStore {
         Ok(value.value().to_string())
     }
 
+    /// Remove a key. Missing keys are not an error.
+    fn remove_raw(&self, key: &str) -> Result<()> {
+        let write_txn = self
+            .db
+            .begin_write()
+            .map_err(|e| SettingsError::WriteFailed {
+                key: key.to_string(),
+                details: e.to_string(),
+            })?;
+
+        {
+            let mut table =
+                write_txn
+                    .open_table(SETTINGS_TABLE)
+                    .map_err(|e| SettingsError::WriteFailed {
+                        key: key.to_string(),
+                        details: e.to_string(),
+                    })?;
+
+            table.remove(key).map_err(|e| SettingsError::WriteFailed {
+                key: key.to_string(),
+                details: e.to_string(),
+            })?;
+        }
+
+        write_txn.commit().map_err(|e| SettingsError::WriteFailed {
+            key: key.to_string(),
+            details: e.to_string(),
+        })?;
+
+        Ok(())
+    }
+
     /// Set a raw string value by key.
     fn set_raw(&self, key: &str, value: &str) -> Result<()> {
         let write_txn = self
@@ -230,6 +297,125 @@ impl SettingsStore {
         let key = format!("{PROMPT_CAPTURE_PREFIX}{channel_id}");
         self.set_raw(&key, if enabled { "true" } else { "false" })
     }
+
+    /// The locale set for a channel, if any.
+    pub fn channel_locale(&self, channel_id: &str) -> Result<Option<Locale>> {
+        self.get_locale(&format!("{CHANNEL_LOCALE_PREFIX}{channel_id}"))
+    }
+
+    /// Persist a channel's locale. An empty locale clears it.
+    pub fn set_channel_locale(&self, channel_id: &str, locale: &Locale) -> Result<()> {
+        self.set_locale(&format!("{CHANNEL_LOCALE_PREFIX}{channel_id}"), locale)
+    }
+
+    /// The locale set for a user on a platform, if any.
+    pub fn user_locale(&self, platform: &str, user_id: &str) -> Result<Option<Locale>> {
+        self.get_locale(&format!("{USER_LOCALE_PREFIX}{platform}:{user_id}"))
+    }
+
+    /// Persist a user's locale. An empty locale clears it.
+    pub fn set_user_locale(&self, platform: &str, user_id: &str, locale: &Locale) -> Result<()> {
+        self.set_locale(&format!("{USER_LOCALE_PREFIX}{platform}:{user_id}"), locale)
+    }
+
+    /// The smart routing tier pinned for a channel, if any.
+    pub fn channel_model_tier(&self, channel_id: &str) -> Option<Tier> {
+        let key = format!("{CHANNEL_MODEL_TIER_PREFIX}{channel_id}");
+        self.get_raw(&key).ok()?.parse().ok()
+    }
+
+    /// Pin a channel to a smart routing tier. `None` goes back to automatic.
+    pub fn set_channel_model_tier(&self, channel_id: &str, tier: Option<Tier>) -> Result<()> {
+        let key = format!("{CHANNEL_MODEL_TIER_PREFIX}{channel_id}");
+        match tier {
+            Some(tier) => self.set_raw(&key, &tier.to_string()),
+            None => self.remove_raw(&key),
+        }
+    }
+
+    /// Whether speculative drafts are switched on or off for a channel, if
+    /// it overrides the agent's setting.
+    pub fn channel_speculative_drafts(&self, channel_id: &str) -> Option<bool> {
+        let key = format!("{CHANNEL_SPECULATIVE_DRAFTS_PREFIX}{channel_id}");
+        self.get_raw(&key).ok()?.parse().ok()
+    }
+
+    /// Override speculative drafts for a channel. `None` follows the agent's
+    /// setting again.
+    pub fn set_channel_speculative_drafts(
+        &self,
+        channel_id: &str,
+        enabled: Option<bool>,
+    ) -> Result<()> {
+        let key = format!("{CHANNEL_SPECULATIVE_DRAFTS_PREFIX}{channel_id}");
+        match enabled {
+            Some(enabled) => self.set_raw(&key, if enabled { "true" } else { "false" }),
+            None => self.remove_raw(&key),
+        }
+    }
+
+    /// How a channel fact-checks replies, if it overrides the agent's
+    /// setting.
+    pub fn channel_reply_verification(&self, channel_id: &str) -> Option<ReplyVerification> {
+        let key = format!("{CHANNEL_REPLY_VERIFICATION_PREFIX}{channel_id}");
+        ReplyVerification::parse(&self.get_raw(&key).ok()?)
+    }
+
+    /// Override reply verification for a channel. `None` follows the agent's
+    /// setting again.
+    pub fn set_channel_reply_verification(
+        &self,
+        channel_id: &str,
+        verification: Option<ReplyVerification>,
+    ) -> Result<()> {
+        let key = format!("{CHANNEL_REPLY_VERIFICATION_PREFIX}{channel_id}");
+        match verification {
+            Some(verification) => self.set_raw(&key, verification.as_str()),
+            None => self.remove_raw(&key),
+        }
+    }
+
+    /// Whether the agent was paused through the API and not yet resumed.
+    pub fn agent_paused(&self) -> bool {
+        matches!(self.get_raw(AGENT_PAUSED_KEY), Ok(value) if value == "true")
+    }
+
+    /// Persist the agent's pause flag so it survives a restart.
+    pub fn set_agent_paused(&self, paused: bool) -> Result<()> {
+        if paused {
+            self.set_raw(AGENT_PAUSED_KEY, "true")
+        } else {
+            self.remove_raw(AGENT_PAUSED_KEY)
+        }
+    }
+
+    fn get_locale(&self, key: &str) -> Result<Option<Locale>> {
+        match self.get_raw(key) {
+            Ok(raw) => serde_json::from_str(&raw).map(Some).map_err(|error| {
+                SettingsError::ReadFailed {
+                    key: key.to_string(),
+                    details: format!("invalid locale '{raw}': {error}"),
+                }
+                .into()
+            }),
+            Err(crate::error::Error::Settings(settings_error)) => match *settings_error {
+                SettingsError::NotFound { .. } => Ok(None),
+                other => Err(other.into()),
+            },
+            Err(other) => Err(other),
+        }
+    }
+
+    fn set_locale(&self, key: &str, locale: &Locale) -> Result<()> {
+        if locale.is_empty() {
+            return self.remove_raw(key);
+        }
+        let raw = serde_json::to_string(locale).map_err(|error| SettingsError::WriteFailed {
+            key: key.to_string(),
+            details: error.to_string(),
+        })?;
+        self.set_raw(key, &raw)
+    }
 }
 
 impl std::fmt::Debug for SettingsStore {
@@ -0,0 +1,524 @@
+//! Scripted conversation scenarios over the testbed platform.
+//!
+//! A [`Scenario`] is a list of steps — users sending messages, and
+//! expectations on replies, memory writes, spawned branches/workers, and
+//! process events. [`ScenarioRunner`] plays it against a
+//! [`TestbedHandle`](crate::messaging::testbed::TestbedHandle) while
+//! watching the agent's event buses. Used by `spacebot simulate` and
+//! available to integration tests.
+//!
+//! ```yaml
+//! name: remembers birthdays
+//! agent: main
+//! steps:
+//!   - send: { user: alice, text: "My birthday is May 3rd, please remember it" }
+//!   - expect_reply: { contains: "May 3" }
+//!   - expect_memory: { contains: "birthday" }
+//!   - send: { user: bob, conversation: ops, text: "Research the latest Rust release" }
+//!   - expect_process: { kind: worker }
+//!   - expect_event: { type: worker_complete, timeout_secs: 300 }
+//! ```
+
+use crate::ProcessEvent;
+use crate::messaging::testbed::TestbedHandle;
+
+use anyhow::Context as _;
+use parking_lot::Mutex;
+use serde::Deserialize;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+
+/// How often expectations re-check the transcript and event log.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Scenario {
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Agent that receives every message. Defaults to the default agent.
+    #[serde(default)]
+    pub agent: Option<String>,
+    /// Default timeout for each expectation.
+    #[serde(default = "default_step_timeout_secs")]
+    pub step_timeout_secs: u64,
+    /// Written as `- send: { ... }`. serde_yaml 0.9 otherwise only accepts
+    /// enum variants as `!send` tags.
+    #[serde(with = "serde_yaml::with::singleton_map_recursive")]
+    pub steps: Vec<Step>,
+}
+
+fn default_step_timeout_secs() -> u64 {
+    60
+}
+
+fn default_conversation() -> String {
+    "main".into()
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum Step {
+    /// A user posts a message.
+    Send {
+        user: String,
+        text: String,
+        #[serde(default = "default_conversation")]
+        conversation: String,
+    },
+    /// The next reply in the conversation (optionally) contains `contains`.
+    ExpectReply {
+        #[serde(default = "default_conversation")]
+        conversation: String,
+        #[serde(default)]
+        contains: Option<String>,
+        #[serde(default)]
+        timeout_secs: Option<u64>,
+    },
+    /// A memory was saved since the last `send`.
+    ExpectMemory {
+        #[serde(default)]
+        contains: Option<String>,
+        #[serde(default)]
+        memory_type: Option<String>,
+        #[serde(default)]
+        timeout_secs: Option<u64>,
+    },
+    /// A branch or worker was spawned since the last `send`.
+    ExpectProcess {
+        kind: ProcessKind,
+        #[serde(default)]
+        contains: Option<String>,
+        #[serde(default)]
+        timeout_secs: Option<u64>,
+    },
+    /// A process event of this type (e.g. `tool_started`) fired since the
+    /// last `send`.
+    ExpectEvent {
+        #[serde(rename = "type")]
+        event_type: String,
+        #[serde(default)]
+        contains: Option<String>,
+        #[serde(default)]
+        timeout_secs: Option<u64>,
+    },
+    /// Pause, e.g. to let background work settle.
+    Wait { secs: u64 },
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProcessKind {
+    Branch,
+    Worker,
+}
+
+impl Scenario {
+    pub fn from_yaml(source: &str) -> anyhow::Result<Self> {
+        let scenario: Self = serde_yaml::from_str(source)?;
+        if scenario.steps.is_empty() {
+            anyhow::bail!("scenario has no steps");
+        }
+        Ok(scenario)
+    }
+
+    pub fn from_path(path: &Path) -> anyhow::Result<Self> {
+        let source = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        Self::from_yaml(&source).with_context(|| format!("invalid scenario {}", path.display()))
+    }
+}
+
+impl Step {
+    fn describe(&self) -> String {
+        match self {
+            Self::Send {
+                user,
+                text,
+                conversation,
+            } => format!("send [{conversation}] {user}: {text}"),
+            Self::ExpectReply {
+                conversation,
+                contains,
+                ..
+            } => match contains {
+                Some(needle) => format!("expect reply in [{conversation}] containing {needle:?}"),
+                None => format!("expect reply in [{conversation}]"),
+            },
+            Self::ExpectMemory { contains, .. } => match contains {
+                Some(needle) => format!("expect memory containing {needle:?}"),
+                None => "expect memory".into(),
+            },
+            Self::ExpectProcess { kind, .. } => format!("expect {kind:?} spawned").to_lowercase(),
+            Self::ExpectEvent { event_type, .. } => format!("expect event {event_type}"),
+            Self::Wait { secs } => format!("wait {secs}s"),
+        }
+    }
+}
+
+/// A process event as the runner saw it: its `type` tag and JSON body.
+#[derive(Debug, Clone)]
+struct ObservedEvent {
+    event_type: String,
+    body: serde_json::Value,
+}
+
+#[derive(Debug, Clone)]
+pub struct StepReport {
+    pub description: String,
+    pub passed: bool,
+    pub detail: Option<String>,
+    pub elapsed: Duration,
+}
+
+#[derive(Debug, Clone)]
+pub struct ScenarioReport {
+    pub name: String,
+    pub steps: Vec<StepReport>,
+    /// Steps not run because an earlier one failed.
+    pub skipped: usize,
+}
+
+impl ScenarioReport {
+    pub fn passed(&self) -> bool {
+        self.skipped == 0 && self.steps.iter().all(|step| step.passed)
+    }
+}
+
+impl std::fmt::Display for ScenarioReport {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(formatter, "scenario: {}", self.name)?;
+        for (index, step) in self.steps.iter().enumerate() {
+            let mark = if step.passed { "ok  " } else { "FAIL" };
+            writeln!(
+                formatter,
+                "  {mark} {:>2}. {} ({} ms)",
+                index + 1,
+                step.description,
+                step.elapsed.as_millis()
+            )?;
+            if let Some(detail) = &step.detail {
+                writeln!(formatter, "          {detail}")?;
+            }
+        }
+        if self.skipped > 0 {
+            writeln!(formatter, "  {} step(s) skipped", self.skipped)?;
+        }
+        write!(
+            formatter,
+            "{}",
+            if self.passed() { "PASSED" } else { "FAILED" }
+        )
+    }
+}
+
+/// Plays scenarios against a testbed handle.
+pub struct ScenarioRunner {
+    handle: TestbedHandle,
+    events: Arc<Mutex<Vec<ObservedEvent>>>,
+    watchers: Vec<tokio::task::JoinHandle<()>>,
+}
+
+impl ScenarioRunner {
+    pub fn new(handle: TestbedHandle) -> Self {
+        Self {
+            handle,
+            events: Arc::new(Mutex::new(Vec::new())),
+            watchers: Vec::new(),
+        }
+    }
+
+    /// Record events from an agent bus. Pass both `event_tx` and
+    /// `memory_event_tx` subscriptions; memory saves only go to the latter.
+    pub fn watch_events(&mut self, mut receiver: broadcast::Receiver<ProcessEvent>) {
+        let events = self.events.clone();
+        self.watchers.push(tokio::spawn(async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(event) => {
+                        let body = serde_json::to_value(&event).unwrap_or_default();
+                        let event_type = body
+                            .get("type")
+                            .and_then(|value| value.as_str())
+                            .unwrap_or_default()
+                            .to_string();
+                        events.lock().push(ObservedEvent { event_type, body });
+                    }
+                    Err(broadcast::error::RecvError::Lagged(count)) => {
+                        tracing::warn!(count, "scenario runner lagged behind the event bus");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        }));
+    }
+
+    pub async fn run(&self, scenario: &Scenario) -> ScenarioReport {
+        if let Some(agent) = &scenario.agent {
+            self.handle.set_agent(agent.as_str());
+        }
+        let default_timeout = Duration::from_secs(scenario.step_timeout_secs);
+
+        // Replies are consumed in order per conversation; events are matched
+        // against everything seen since the most recent `send`.
+        let mut reply_cursors: std::collections::HashMap<String, usize> =
+            std::collections::HashMap::new();
+        let mut event_window_start = self.events.lock().len();
+
+        let timeout = |secs: &Option<u64>| secs.map_or(default_timeout, Duration::from_secs);
+
+        let mut steps = Vec::with_capacity(scenario.steps.len());
+        for step in &scenario.steps {
+            let started = Instant::now();
+            let result = match step {
+                Step::Send {
+                    user,
+                    text,
+                    conversation,
+                } => {
+                    event_window_start = self.events.lock().len();
+                    self.handle
+                        .send(conversation, user, text)
+                        .await
+                        .map_err(|error| error.to_string())
+                }
+                Step::ExpectReply {
+                    conversation,
+                    contains,
+                    timeout_secs,
+                } => {
+                    let cursor = reply_cursors.entry(conversation.clone()).or_default();
+                    self.expect_reply(
+                        conversation,
+                        contains.as_deref(),
+                        cursor,
+                        timeout(timeout_secs),
+                    )
+                    .await
+                }
+                Step::ExpectMemory {
+                    contains,
+                    memory_type,
+                    timeout_secs,
+                } => {
+                    self.expect_event(event_window_start, timeout(timeout_secs), |event| {
+                        event.event_type == "memory_saved"
+                            && field_matches(&event.body, "content_summary", contains.as_deref())
+                            && field_matches(&event.body, "memory_type", memory_type.as_deref())
+                    })
+                    .await
+                }
+                Step::ExpectProcess {
+                    kind,
+                    contains,
+                    timeout_secs,
+                } => {
+                    let (event_type, field) = match kind {
+                        ProcessKind::Branch => ("branch_started", "description"),
+                        ProcessKind::Worker => ("worker_started", "task"),
+                    };
+                    self.expect_event(event_window_start, timeout(timeout_secs), |event| {
+                        event.event_type == event_type
+                            && field_matches(&event.body, field, contains.as_deref())
+                    })
+                    .await
+                }
+                Step::ExpectEvent {
+                    event_type,
+                    contains,
+                    timeout_secs,
+                } => {
+                    self.expect_event(event_window_start, timeout(timeout_secs), |event| {
+                        event.event_type == *event_type
+                            && contains.as_deref().is_none_or(|needle| {
+                                contains_ignore_case(&event.body.to_string(), needle)
+                            })
+                    })
+                    .await
+                }
+                Step::Wait { secs } => {
+                    tokio::time::sleep(Duration::from_secs(*secs)).await;
+                    Ok(())
+                }
+            };
+
+            let passed = result.is_ok();
+            steps.push(StepReport {
+                description: step.describe(),
+                passed,
+                detail: result.err(),
+                elapsed: started.elapsed(),
+            });
+            if !passed {
+                break;
+            }
+        }
+
+        ScenarioReport {
+            name: scenario.name.clone().unwrap_or_else(|| "unnamed".into()),
+            skipped: scenario.steps.len() - steps.len(),
+            steps,
+        }
+    }
+
+    async fn expect_reply(
+        &self,
+        conversation: &str,
+        contains: Option<&str>,
+        cursor: &mut usize,
+        timeout: Duration,
+    ) -> Result<(), String> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let replies: Vec<String> = self
+                .handle
+                .transcript()
+                .into_iter()
+                .filter(|outbound| outbound.conversation == conversation)
+                .filter_map(|outbound| outbound.text().map(str::to_string))
+                .collect();
+            if let Some(reply) = replies.get(*cursor) {
+                *cursor += 1;
+                return match contains {
+                    Some(needle) if !contains_ignore_case(reply, needle) => {
+                        Err(format!("reply did not contain {needle:?}: {reply:?}"))
+                    }
+                    _ => Ok(()),
+                };
+            }
+            if Instant::now() >= deadline {
+                return Err(format!("no reply within {}s", timeout.as_secs()));
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    async fn expect_event(
+        &self,
+        since: usize,
+        timeout: Duration,
+        matches: impl Fn(&ObservedEvent) -> bool,
+    ) -> Result<(), String> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            {
+                let events = self.events.lock();
+                if events.iter().skip(since).any(&matches) {
+                    return Ok(());
+                }
+            }
+            if Instant::now() >= deadline {
+                let seen: Vec<String> = self
+                    .events
+                    .lock()
+                    .iter()
+                    .skip(since)
+                    .map(|event| event.event_type.clone())
+                    .collect();
+                return Err(format!(
+                    "no matching event within {}s (saw: {})",
+                    timeout.as_secs(),
+                    if seen.is_empty() {
+                        "nothing".into()
+                    } else {
+                        seen.join(", ")
+                    }
+                ));
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+}
+
+impl Drop for ScenarioRunner {
+    fn drop(&mut self) {
+        for watcher in &self.watchers {
+            watcher.abort();
+        }
+    }
+}
+
+fn contains_ignore_case(haystack: &str, needle: &str) -> bool {
+    haystack.to_lowercase().contains(&needle.to_lowercase())
+}
+
+fn field_matches(body: &serde_json::Value, field: &str, needle: Option<&str>) -> bool {
+    let Some(needle) = needle else {
+        return true;
+    };
+    body.get(field)
+        .map(|value| match value {
+            serde_json::Value::String(text) => contains_ignore_case(text, needle),
+            other => contains_ignore_case(&other.to_string(), needle),
+        })
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messaging::testbed::TestbedAdapter;
+    use crate::messaging::traits::Messaging as _;
+    use crate::{MessageContent, OutboundResponse};
+    use futures::StreamExt as _;
+
+    #[tokio::test]
+    async fn runs_scenario_against_testbed() {
+        let (adapter, handle) = TestbedAdapter::new();
+        let mut inbound = adapter.start().await.unwrap();
+        let (event_tx, memory_event_tx) = crate::create_process_event_buses();
+
+        let mut runner = ScenarioRunner::new(handle);
+        runner.watch_events(event_tx.subscribe());
+        runner.watch_events(memory_event_tx.subscribe());
+
+        // Stand-in agent: echoes each message and saves it as a memory.
+        tokio::spawn(async move {
+            while let Some(message) = inbound.next().await {
+                let MessageContent::Text(text) = &message.content else {
+                    continue;
+                };
+                memory_event_tx
+                    .send(ProcessEvent::MemorySaved {
+                        agent_id: message.agent_id.clone().unwrap(),
+                        memory_id: "m1".into(),
+                        channel_id: None,
+                        memory_type: crate::memory::MemoryType::Fact,
+                        importance: 0.5,
+                        content_summary: text.clone(),
+                    })
+                    .ok();
+                adapter
+                    .respond(&message, OutboundResponse::Text(format!("echo: {text}")))
+                    .await
+                    .unwrap();
+            }
+        });
+
+        let scenario = Scenario::from_yaml(
+            r#"
+name: echo
+agent: main
+step_timeout_secs: 2
+steps:
+  - send: { user: alice, text: "remember the blue door" }
+  - expect_reply: { contains: "BLUE DOOR" }
+  - expect_memory: { contains: "blue door", memory_type: fact }
+  - send: { user: bob, conversation: ops, text: "hi" }
+  - expect_reply: { conversation: ops, contains: "hi" }
+  - expect_process: { kind: worker, timeout_secs: 0 }
+  - expect_reply: { contains: "never" }
+"#,
+        )
+        .unwrap();
+
+        let report = runner.run(&scenario).await;
+        assert!(!report.passed());
+        assert_eq!(report.steps.len(), 6);
+        assert!(report.steps[..5].iter().all(|step| step.passed));
+        assert_eq!(report.skipped, 1);
+
+        assert!(Scenario::from_yaml("steps: [{ shout: {} }]").is_err());
+    }
+}
@@ -113,6 +113,18 @@ impl SkillSet {
         self.skills.is_empty()
     }
 
+    /// Hash of what the channel listing shows, used as its prompt cache key.
+    pub fn channel_listing_hash(&self) -> u64 {
+        let mut sorted_skills: Vec<&Skill> = self.skills.values().collect();
+        sorted_skills.sort_by(|a, b| a.name.cmp(&b.name));
+        crate::agent::prompt_cache::content_hash(
+            sorted_skills
+                .iter()
+                .map(|s| (&s.name, &s.description, &s.file_path))
+                .collect::<Vec<_>>(),
+        )
+    }
+
     /// Render the skills summary for injection into the channel system prompt.
     ///
     /// The channel sees skill names and descriptions but is instructed to
@@ -1,5 +1,6 @@
 //! Task tracking data model and storage.
 
+pub mod email;
 pub mod store;
 
 pub use store::{
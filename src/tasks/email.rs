@@ -0,0 +1,190 @@
+//! Tasks created from email.
+//!
+//! Mail sent to an email adapter's `task_address` skips the conversational
+//! channel and becomes a ready task: the subject is the title, the body the
+//! instructions, and attachments are saved to the workspace for the worker.
+//! The original message is kept in the task's metadata so the sender gets a
+//! threaded reply when the worker finishes.
+
+use crate::agent::channel_attachments::{numbered_filename, sanitize_filename};
+use crate::error::Result;
+use crate::messaging::MessagingManager;
+use crate::tasks::{CreateTaskInput, Task, TaskPriority, TaskStatus, TaskStore};
+use crate::{InboundMessage, MessageContent, OutboundResponse};
+
+use anyhow::Context as _;
+use sha2::{Digest as _, Sha256};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Inbound metadata flag the email adapter sets on mail to the task address.
+pub const EMAIL_TASK_KEY: &str = "email_task";
+
+/// Task metadata key holding the originating message, for the reply.
+const ORIGIN_KEY: &str = "email_origin";
+
+const MAX_TITLE_CHARS: usize = 200;
+
+/// Whether an inbound message should become a task rather than a conversation.
+pub fn is_email_task(message: &InboundMessage) -> bool {
+    message
+        .metadata
+        .get(EMAIL_TASK_KEY)
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false)
+}
+
+/// Create a ready task from a task email, saving its attachments under
+/// `workspace/saved/email-tasks/`.
+pub async fn create_task(
+    task_store: &TaskStore,
+    agent_id: &str,
+    workspace_dir: &Path,
+    message: &InboundMessage,
+) -> Result<Task> {
+    let (body, attachments) = match &message.content {
+        MessageContent::Media { text, attachments } => {
+            (text.clone().unwrap_or_default(), attachments.as_slice())
+        }
+        MessageContent::Text(text) => (text.clone(), [].as_slice()),
+        other => (other.to_string(), [].as_slice()),
+    };
+
+    let subject = message
+        .metadata
+        .get("email_subject")
+        .and_then(|value| value.as_str())
+        .map(str::trim)
+        .filter(|subject| !subject.is_empty())
+        .unwrap_or("(no subject)");
+    let title: String = subject.chars().take(MAX_TITLE_CHARS).collect();
+
+    let mut description = body.clone();
+    if !attachments.is_empty() {
+        let directory = attachment_dir(workspace_dir, &message.id);
+        let saved = save_attachments(&directory, attachments).await?;
+        if !saved.is_empty() {
+            description.push_str("\n\nAttachments saved to the workspace:\n");
+            for path in saved {
+                description.push_str(&format!("- {}\n", path.display()));
+            }
+        }
+    }
+
+    // Keep the message for threading the reply, minus the attachment data.
+    let mut origin = message.clone();
+    origin.content = MessageContent::Text(body);
+
+    task_store
+        .create(CreateTaskInput {
+            agent_id: agent_id.to_string(),
+            title,
+            description: Some(description),
+            status: TaskStatus::Ready,
+            priority: TaskPriority::Medium,
+            subtasks: Vec::new(),
+            metadata: serde_json::json!({ ORIGIN_KEY: origin }),
+            source_memory_id: None,
+            created_by: format!("email:{}", message.sender_id),
+        })
+        .await
+}
+
+/// Reply to the sender of a task email with the worker's result. No-op for
+/// tasks that didn't come from email.
+pub async fn send_completion_reply(
+    task: &Task,
+    result: &str,
+    messaging_manager: &MessagingManager,
+) -> Result<()> {
+    let Some(origin) = task.metadata.get(ORIGIN_KEY) else {
+        return Ok(());
+    };
+    let origin: InboundMessage =
+        serde_json::from_value(origin.clone()).context("malformed email task origin")?;
+
+    let text = format!(
+        "Task #{} (\"{}\") is done.\n\n{result}",
+        task.task_number, task.title
+    );
+    messaging_manager
+        .respond(&origin, OutboundResponse::Text(text))
+        .await
+}
+
+fn attachment_dir(workspace_dir: &Path, message_id: &str) -> PathBuf {
+    let digest = Sha256::digest(message_id.as_bytes());
+    let key: String = digest
+        .iter()
+        .take(8)
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    workspace_dir.join("saved").join("email-tasks").join(key)
+}
+
+async fn save_attachments(
+    directory: &Path,
+    attachments: &[crate::Attachment],
+) -> Result<Vec<PathBuf>> {
+    use base64::Engine as _;
+
+    tokio::fs::create_dir_all(directory)
+        .await
+        .with_context(|| format!("failed to create {}", directory.display()))?;
+
+    let mut saved = Vec::new();
+    let mut taken = HashSet::new();
+    for attachment in attachments {
+        let Some((_, encoded)) = attachment
+            .url
+            .strip_prefix("data:")
+            .and_then(|rest| rest.split_once(";base64,"))
+        else {
+            tracing::warn!(filename = %attachment.filename, "email task attachment is not inline, skipping");
+            continue;
+        };
+        let data = match base64::engine::general_purpose::STANDARD.decode(encoded) {
+            Ok(data) => data,
+            Err(error) => {
+                tracing::warn!(%error, filename = %attachment.filename, "failed to decode email task attachment");
+                continue;
+            }
+        };
+
+        let path = directory.join(unique_filename(&attachment.filename, &mut taken));
+        tokio::fs::write(&path, data)
+            .await
+            .with_context(|| format!("failed to write {}", path.display()))?;
+        saved.push(path);
+    }
+    Ok(saved)
+}
+
+/// A sanitized name for `name` that no earlier attachment of the same email
+/// has taken, so two attachments called `scan.pdf` both survive.
+fn unique_filename(name: &str, taken: &mut HashSet<String>) -> String {
+    let base = sanitize_filename(name);
+    let mut candidate = base.clone();
+    let mut counter = 2;
+    while !taken.insert(candidate.clone()) {
+        candidate = numbered_filename(&base, counter);
+        counter += 1;
+    }
+    candidate
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn attachment_names_are_sanitized_and_never_collide() {
+        let mut taken = HashSet::new();
+        assert_eq!(unique_filename("../../etc/passwd", &mut taken), "passwd");
+        assert_eq!(unique_filename("report.pdf", &mut taken), "report.pdf");
+        assert_eq!(unique_filename("a/report.pdf", &mut taken), "report_2.pdf");
+        assert_eq!(unique_filename("report.pdf", &mut taken), "report_3.pdf");
+        assert_eq!(unique_filename("..", &mut taken), "attachment");
+        assert_eq!(unique_filename(".", &mut taken), "attachment_2");
+    }
+}
@@ -1,7 +1,8 @@
 //! Global metrics registry and metric handle definitions.
 
 use prometheus::{
-    CounterVec, Histogram, HistogramOpts, HistogramVec, IntCounterVec, IntGaugeVec, Opts, Registry,
+    CounterVec, GaugeVec, Histogram, HistogramOpts, HistogramVec, IntCounterVec, IntGaugeVec, Opts,
+    Registry,
 };
 
 use std::sync::LazyLock;
@@ -47,6 +48,18 @@ pub struct Metrics {
     /// Label: agent_id.
     pub active_workers: IntGaugeVec,
 
+    /// Slots currently held against each per-agent quota.
+    /// Labels: agent_id, resource (tasks, llm, db).
+    pub agent_quota_in_use: IntGaugeVec,
+
+    /// Configured size of each per-agent quota.
+    /// Labels: agent_id, resource.
+    pub agent_quota_limit: IntGaugeVec,
+
+    /// Times a per-agent quota was exhausted and a request had to wait.
+    /// Labels: agent_id, resource.
+    pub agent_quota_saturated_total: IntCounterVec,
+
     /// Total memory entries per agent.
     /// Label: agent_id.
     pub memory_entry_count: IntGaugeVec,
@@ -90,6 +103,10 @@ pub struct Metrics {
     /// Labels: agent_id, dispatch_type.
     pub warmup_recovery_latency_ms: HistogramVec,
 
+    /// How far reviewed speculative replies diverged from their drafts.
+    /// Labels: agent_id, outcome (kept/replaced).
+    pub speculative_draft_divergence: HistogramVec,
+
     // =====================================================================
     // NEW METRICS
     // =====================================================================
@@ -140,6 +157,14 @@ pub struct Metrics {
     /// Labels: agent_id, channel_type, error_type.
     pub channel_errors_total: IntCounterVec,
 
+    /// Inbound messages dropped by adapter-level filters.
+    /// Labels: adapter, reason.
+    pub messages_filtered_total: IntCounterVec,
+
+    /// Outbox replies that failed and were retried, recovered, or dead-lettered.
+    /// Labels: platform, result.
+    pub messaging_outbox_total: IntCounterVec,
+
     // -- Memory operations --
     /// Memory operation duration.
     /// Labels: agent_id, operation.
@@ -152,6 +177,10 @@ pub struct Metrics {
     /// Embedding generation duration.
     pub memory_embedding_duration_seconds: Histogram,
 
+    /// Embedding cache lookups, counted per text.
+    /// Labels: provider, result.
+    pub memory_embedding_cache_total: IntCounterVec,
+
     // -- API --
     /// Total HTTP requests.
     /// Labels: method, handler, status.
@@ -169,6 +198,22 @@ pub struct Metrics {
     /// Context overflow events.
     /// Labels: agent_id, process_type.
     pub context_overflow_total: IntCounterVec,
+    /// Channel prompt section cache lookups.
+    /// Labels: agent_id, section, result (hit, miss).
+    pub prompt_section_cache_total: IntCounterVec,
+
+    // -- Runtimes --
+    /// Live tasks per tokio runtime.
+    /// Labels: runtime (`shared` or an agent ID).
+    pub runtime_alive_tasks: IntGaugeVec,
+
+    /// Tasks waiting in a runtime's global queue.
+    /// Labels: runtime.
+    pub runtime_global_queue_depth: IntGaugeVec,
+
+    /// Fraction of worker thread time spent busy over the last sample.
+    /// Labels: runtime.
+    pub runtime_busy_ratio: GaugeVec,
 
     // -- Cost --
     /// Worker cost tracking in USD.
@@ -253,6 +298,30 @@ impl Metrics {
         )
         .expect("hardcoded metric descriptor");
 
+        let agent_quota_in_use = IntGaugeVec::new(
+            Opts::new(
+                "spacebot_agent_quota_in_use",
+                "Slots held against a per-agent quota",
+            ),
+            &["agent_id", "resource"],
+        )
+        .expect("hardcoded metric descriptor");
+
+        let agent_quota_limit = IntGaugeVec::new(
+            Opts::new("spacebot_agent_quota_limit", "Size of a per-agent quota"),
+            &["agent_id", "resource"],
+        )
+        .expect("hardcoded metric descriptor");
+
+        let agent_quota_saturated_total = IntCounterVec::new(
+            Opts::new(
+                "spacebot_agent_quota_saturated_total",
+                "Times a per-agent quota was exhausted",
+            ),
+            &["agent_id", "resource"],
+        )
+        .expect("hardcoded metric descriptor");
+
         let memory_entry_count = IntGaugeVec::new(
             Opts::new(
                 "spacebot_memory_entry_count",
@@ -340,6 +409,16 @@ impl Metrics {
         )
         .expect("hardcoded metric descriptor");
 
+        let speculative_draft_divergence = HistogramVec::new(
+            HistogramOpts::new(
+                "spacebot_speculative_draft_divergence",
+                "Word edit distance between speculative drafts and their reviewed replies, as a fraction",
+            )
+            .buckets(vec![0.0, 0.05, 0.1, 0.2, 0.3, 0.5, 0.75, 1.0]),
+            &["agent_id", "outcome"],
+        )
+        .expect("hardcoded metric descriptor");
+
         // === NEW metrics ===
 
         // MCP (7)
@@ -402,7 +481,7 @@ impl Metrics {
         )
         .expect("hardcoded metric descriptor");
 
-        // Channel/Messaging (4)
+        // Channel/Messaging (6)
         let messages_received_total = IntCounterVec::new(
             Opts::new(
                 "spacebot_messages_received_total",
@@ -434,7 +513,25 @@ impl Metrics {
         )
         .expect("hardcoded metric descriptor");
 
-        // Memory (3)
+        let messages_filtered_total = IntCounterVec::new(
+            Opts::new(
+                "spacebot_messages_filtered_total",
+                "Inbound messages dropped by adapter filters",
+            ),
+            &["adapter", "reason"],
+        )
+        .expect("hardcoded metric descriptor");
+
+        let messaging_outbox_total = IntCounterVec::new(
+            Opts::new(
+                "spacebot_messaging_outbox_total",
+                "Outbox replies retried, recovered, or dead-lettered",
+            ),
+            &["platform", "result"],
+        )
+        .expect("hardcoded metric descriptor");
+
+        // Memory (4)
         let memory_operation_duration_seconds = HistogramVec::new(
             HistogramOpts::new(
                 "spacebot_memory_operation_duration_seconds",
@@ -466,6 +563,15 @@ impl Metrics {
         )
         .expect("hardcoded metric descriptor");
 
+        let memory_embedding_cache_total = IntCounterVec::new(
+            Opts::new(
+                "spacebot_memory_embedding_cache_total",
+                "Embedding cache lookups",
+            ),
+            &["provider", "result"],
+        )
+        .expect("hardcoded metric descriptor");
+
         // API (2)
         let http_requests_total = IntCounterVec::new(
             Opts::new("spacebot_http_requests_total", "Total HTTP requests"),
@@ -498,6 +604,43 @@ impl Metrics {
         )
         .expect("hardcoded metric descriptor");
 
+        let prompt_section_cache_total = IntCounterVec::new(
+            Opts::new(
+                "spacebot_prompt_section_cache_total",
+                "Channel prompt section cache lookups",
+            ),
+            &["agent_id", "section", "result"],
+        )
+        .expect("hardcoded metric descriptor");
+
+        // Runtimes (3)
+        let runtime_alive_tasks = IntGaugeVec::new(
+            Opts::new(
+                "spacebot_runtime_alive_tasks",
+                "Live tasks per tokio runtime",
+            ),
+            &["runtime"],
+        )
+        .expect("hardcoded metric descriptor");
+
+        let runtime_global_queue_depth = IntGaugeVec::new(
+            Opts::new(
+                "spacebot_runtime_global_queue_depth",
+                "Tasks waiting in a tokio runtime's global queue",
+            ),
+            &["runtime"],
+        )
+        .expect("hardcoded metric descriptor");
+
+        let runtime_busy_ratio = GaugeVec::new(
+            Opts::new(
+                "spacebot_runtime_busy_ratio",
+                "Fraction of tokio worker time spent busy",
+            ),
+            &["runtime"],
+        )
+        .expect("hardcoded metric descriptor");
+
         // Cost (1)
         let worker_cost_dollars = CounterVec::new(
             Opts::new(
@@ -549,6 +692,15 @@ impl Metrics {
         registry
             .register(Box::new(active_workers.clone()))
             .expect("hardcoded metric");
+        registry
+            .register(Box::new(agent_quota_in_use.clone()))
+            .expect("hardcoded metric");
+        registry
+            .register(Box::new(agent_quota_limit.clone()))
+            .expect("hardcoded metric");
+        registry
+            .register(Box::new(agent_quota_saturated_total.clone()))
+            .expect("hardcoded metric");
         registry
             .register(Box::new(memory_entry_count.clone()))
             .expect("hardcoded metric");
@@ -579,6 +731,9 @@ impl Metrics {
         registry
             .register(Box::new(warmup_recovery_latency_ms.clone()))
             .expect("hardcoded metric");
+        registry
+            .register(Box::new(speculative_draft_divergence.clone()))
+            .expect("hardcoded metric");
 
         // New: MCP
         registry
@@ -616,6 +771,12 @@ impl Metrics {
         registry
             .register(Box::new(channel_errors_total.clone()))
             .expect("hardcoded metric");
+        registry
+            .register(Box::new(messages_filtered_total.clone()))
+            .expect("hardcoded metric");
+        registry
+            .register(Box::new(messaging_outbox_total.clone()))
+            .expect("hardcoded metric");
 
         // New: Memory operations
         registry
@@ -627,6 +788,9 @@ impl Metrics {
         registry
             .register(Box::new(memory_embedding_duration_seconds.clone()))
             .expect("hardcoded metric");
+        registry
+            .register(Box::new(memory_embedding_cache_total.clone()))
+            .expect("hardcoded metric");
 
         // New: API
         registry
@@ -643,6 +807,20 @@ impl Metrics {
         registry
             .register(Box::new(context_overflow_total.clone()))
             .expect("hardcoded metric");
+        registry
+            .register(Box::new(prompt_section_cache_total.clone()))
+            .expect("hardcoded metric");
+
+        // New: Runtimes
+        registry
+            .register(Box::new(runtime_alive_tasks.clone()))
+            .expect("hardcoded metric");
+        registry
+            .register(Box::new(runtime_global_queue_depth.clone()))
+            .expect("hardcoded metric");
+        registry
+            .register(Box::new(runtime_busy_ratio.clone()))
+            .expect("hardcoded metric");
 
         // New: Cost
         registry
@@ -668,6 +846,9 @@ impl Metrics {
             llm_request_duration_seconds,
             tool_call_duration_seconds,
             active_workers,
+            agent_quota_in_use,
+            agent_quota_limit,
+            agent_quota_saturated_total,
             memory_entry_count,
             llm_tokens_total,
             llm_estimated_cost_dollars,
@@ -678,6 +859,7 @@ impl Metrics {
             dispatch_while_cold_count,
             event_receiver_lagged_events_total,
             warmup_recovery_latency_ms,
+            speculative_draft_divergence,
             // New
             mcp_connections,
             mcp_tools_registered,
@@ -690,13 +872,20 @@ impl Metrics {
             messages_sent_total,
             message_handling_duration_seconds,
             channel_errors_total,
+            messages_filtered_total,
+            messaging_outbox_total,
             memory_operation_duration_seconds,
             memory_search_results,
             memory_embedding_duration_seconds,
+            memory_embedding_cache_total,
             http_requests_total,
             http_request_duration_seconds,
             branches_spawned_total,
             context_overflow_total,
+            prompt_section_cache_total,
+            runtime_alive_tasks,
+            runtime_global_queue_depth,
+            runtime_busy_ratio,
             worker_cost_dollars,
             cron_executions_total,
             ingestion_files_processed_total,
@@ -9,35 +9,65 @@
 //! - `reply`, `branch`, `spawn_worker`, `route`, `cancel`, `skip`, `react` — added
 //!   dynamically per conversation turn via `add_channel_tools()` /
 //!   `remove_channel_tools()` because they hold per-channel state.
+//! - `lookup_channel` for bounded excerpts from another channel's history
 //! - No memory tools — the channel delegates memory work to branches.
 //!
 //! **Branch ToolServer** (one per branch, isolated):
 //! - `memory_save` + `memory_recall` + `memory_delete` + `channel_recall`
+//! - `todo_update` for todo and goal memory status, priority, and due dates
+//! - `entity_profile` for everything known about a named person, project, or place
+//! - `lookup_channel` for excerpts from another channel, recorded in the timeline
 //! - `spacebot_docs` for embedded self-documentation lookup
+//! - `calculate` + `convert` for exact arithmetic and unit/currency conversion
 //! - `task_create` + `task_list` + `task_update`
 //! - `spawn_worker` is included for channel-originated branches only
+//! - `issue_tracker` for channel-originated branches when trackers are configured
+//! - `weather` + `geocode` when weather is enabled for the agent
+//! - `translate` for channel-originated branches when translation is enabled
+//! - `contacts` for channel-originated branches
+//! - `locale` for channel-originated branches, to save a confirmed timezone
+//! - `broadcast` for channel-originated branches when broadcasting is enabled;
+//!   sends go through approvals unless the agent is trusted to send directly
+//! - `faq_propose` for channel-originated branches when FAQ matching is on;
+//!   proposals wait for operator approval
 //!
 //! **Worker ToolServer** (one per worker, created at spawn time):
 //! - `shell`, `file_read`/`file_write`/`file_edit`/`file_list` — stateless, registered at creation
 //! - `task_update` — scoped to the worker's assigned task
 //! - `set_status` — per-worker instance, registered at creation
+//! - `chart` — renders series data to PNG/SVG in the attachment store
+//! - `calculate` + `convert` — exact arithmetic and unit/currency conversion
+//! - `issue_tracker` — when trackers are configured; writes go through approvals
+//! - `weather` + `geocode` — when weather is enabled for the agent
 //!
 //! **Cortex ToolServer** (one per agent):
 //! - `memory_save` — registered at startup
 //!
 //! **Cortex Chat ToolServer** (interactive admin chat):
 //! - branch + worker tool superset plus `spacebot_docs`, `config_inspect`, and `spawn_worker`
+//! - `broadcast` when broadcasting is enabled and messaging is running
 
 pub mod attachment_recall;
 pub mod branch_tool;
+pub mod broadcast_tool;
 pub mod browser;
+pub mod calculate;
 pub mod cancel;
 pub mod channel_recall;
+pub mod chart;
 pub mod config_inspect;
+pub mod contacts;
+pub mod convert;
 pub mod cron;
 pub mod email_search;
+pub mod entity_profile;
+pub mod faq_propose;
 pub mod file;
+pub mod geocode;
 pub mod install_skill;
+pub mod issue_tracker;
+pub mod locale;
+pub mod lookup_channel;
 pub mod mcp;
 pub mod memory_delete;
 pub mod memory_persistence_complete;
@@ -61,6 +91,9 @@ pub mod spawn_worker;
 pub mod task_create;
 pub mod task_list;
 pub mod task_update;
+pub mod todo_update;
+pub mod translate;
+pub mod weather;
 pub mod web_search;
 pub mod worker_inspect;
 
@@ -75,27 +108,44 @@ pub use attachment_recall::{
     AttachmentRecallArgs, AttachmentRecallError, AttachmentRecallOutput, AttachmentRecallTool,
 };
 pub use branch_tool::{BranchArgs, BranchError, BranchOutput, BranchTool};
+pub use broadcast_tool::{BroadcastArgs, BroadcastError, BroadcastOutput, BroadcastTool};
 pub use browser::{
     BrowserError, BrowserOutput, SharedBrowserHandle, TabInfo, new_shared_browser_handle,
     register_browser_tools,
 };
+pub use calculate::{CalculateArgs, CalculateError, CalculateOutput, CalculateTool};
 pub use cancel::{CancelArgs, CancelError, CancelOutput, CancelTool};
 pub use channel_recall::{
     ChannelRecallArgs, ChannelRecallError, ChannelRecallOutput, ChannelRecallTool,
 };
+pub use chart::{ChartArgs, ChartError, ChartOutput, ChartTool};
 pub use config_inspect::{
     ConfigInspectArgs, ConfigInspectError, ConfigInspectOutput, ConfigInspectTool,
 };
+pub use contacts::{ContactsArgs, ContactsError, ContactsOutput, ContactsTool};
+pub use convert::{ConvertArgs, ConvertError, ConvertOutput, ConvertTool};
 pub use cron::{CronArgs, CronError, CronOutput, CronTool};
 pub use email_search::{EmailSearchArgs, EmailSearchError, EmailSearchOutput, EmailSearchTool};
+pub use entity_profile::{
+    EntityProfileArgs, EntityProfileError, EntityProfileOutput, EntityProfileTool,
+};
+pub use faq_propose::{FaqProposeArgs, FaqProposeError, FaqProposeOutput, FaqProposeTool};
 pub use file::{
     FileEditArgs, FileEditTool, FileEntry, FileEntryOutput, FileError, FileListArgs, FileListTool,
     FileOutput, FileReadArgs, FileReadTool, FileType, FileWriteArgs, FileWriteTool,
     register_file_tools,
 };
+pub use geocode::{GeocodeArgs, GeocodeError, GeocodeOutput, GeocodeTool};
 pub use install_skill::{
     InstallSkillArgs, InstallSkillError, InstallSkillOutput, InstallSkillTool,
 };
+pub use issue_tracker::{
+    IssueTrackerArgs, IssueTrackerError, IssueTrackerOutput, IssueTrackerTool,
+};
+pub use locale::{LocaleArgs, LocaleError, LocaleOutput, LocaleTool};
+pub use lookup_channel::{
+    LookupChannelArgs, LookupChannelError, LookupChannelOutput, LookupChannelTool,
+};
 pub use mcp::{McpToolAdapter, McpToolError, McpToolOutput};
 pub use memory_delete::{
     MemoryDeleteArgs, MemoryDeleteError, MemoryDeleteOutput, MemoryDeleteTool,
@@ -116,7 +166,9 @@ pub use project_manage::{
 };
 pub use react::{ReactArgs, ReactError, ReactOutput, ReactTool};
 pub use read_skill::{ReadSkillArgs, ReadSkillError, ReadSkillOutput, ReadSkillTool};
-pub use reply::{RepliedFlag, ReplyArgs, ReplyError, ReplyOutput, ReplyTool, new_replied_flag};
+pub use reply::{
+    RepliedFlag, ReplyArgs, ReplyError, ReplyOutput, ReplyTool, ReplyTranslation, new_replied_flag,
+};
 pub use route::{RouteArgs, RouteError, RouteOutput, RouteTool};
 pub use secret_set::{SecretSetArgs, SecretSetError, SecretSetOutput, SecretSetTool};
 pub use send_agent_message::{
@@ -141,6 +193,9 @@ pub use spawn_worker::{
 pub use task_create::{TaskCreateArgs, TaskCreateError, TaskCreateOutput, TaskCreateTool};
 pub use task_list::{TaskListArgs, TaskListError, TaskListOutput, TaskListTool};
 pub use task_update::{TaskUpdateArgs, TaskUpdateError, TaskUpdateOutput, TaskUpdateTool};
+pub use todo_update::{TodoUpdateArgs, TodoUpdateError, TodoUpdateOutput, TodoUpdateTool};
+pub use translate::{TranslateArgs, TranslateError, TranslateOutput, TranslateTool};
+pub use weather::{WeatherArgs, WeatherError, WeatherOutput, WeatherTool};
 pub use web_search::{SearchResult, WebSearchArgs, WebSearchError, WebSearchOutput, WebSearchTool};
 pub use worker_inspect::{
     WorkerInspectArgs, WorkerInspectError, WorkerInspectOutput, WorkerInspectTool,
@@ -171,8 +226,12 @@ pub use factory_update_identity::{
 };
 
 use crate::agent::channel::ChannelState;
-use crate::config::{BrowserConfig, RuntimeConfig};
+use crate::agent::verification::ReplyVerifier;
+use crate::config::{BrowserConfig, ReplyVerification, RuntimeConfig};
+use crate::contacts::ContactStore;
 use crate::memory::MemorySearch;
+use crate::memory::entities::MentionFilter;
+use crate::memory::search::{CONVERSATION_GRAPH_DEPTH, EdgeWeights, REFLECTION_GRAPH_DEPTH};
 use crate::sandbox::Sandbox;
 use crate::tasks::TaskStore;
 use crate::{AgentId, ChannelId, ProcessEvent, RoutedSender, WorkerId};
@@ -339,6 +398,53 @@ pub fn should_block_user_visible_text(value: &str) -> bool {
     lower.starts_with("<system-reminder>") || lower.starts_with("<path>")
 }
 
+/// Outbound translation for the reply tool when the channel auto-translates
+/// and the user writes in a language other than the agent's.
+async fn reply_translation(state: &ChannelState) -> Option<ReplyTranslation> {
+    let config = state.deps.runtime_config.translation.load();
+    if !config.auto_translate {
+        return None;
+    }
+    let user_language = state.user_language.read().await.clone()?;
+    if crate::translation::same_language(&user_language, &config.working_language) {
+        return None;
+    }
+    let translator = crate::translation::translator(
+        &config,
+        state.deps.llm_manager.clone(),
+        state.deps.runtime_config.clone(),
+        state.deps.agent_id.clone(),
+    )
+    .inspect_err(|error| tracing::warn!(%error, "failed to build reply translator"))
+    .ok()?;
+    Some(ReplyTranslation {
+        translator,
+        working_language: config.working_language.clone(),
+        user_language,
+    })
+}
+
+/// Build the reply fact-checker when verification is on for the channel.
+fn reply_verifier(state: &ChannelState) -> Option<ReplyVerifier> {
+    let runtime_config = &state.deps.runtime_config;
+    let action =
+        crate::agent::verification::channel_setting(runtime_config, state.channel_id.as_ref());
+    if action == ReplyVerification::Off {
+        return None;
+    }
+    Some(ReplyVerifier::new(
+        action,
+        state.deps.memory_search.clone(),
+        runtime_config
+            .memory_scopes
+            .load()
+            .filter_for_channel(state.channel_id.as_ref()),
+        state.deps.llm_manager.clone(),
+        runtime_config.clone(),
+        state.deps.agent_id.clone(),
+    ))
+}
+
 /// Add per-turn tools to a channel's ToolServer.
 ///
 /// Called when a conversation turn begins. These tools hold per-turn state
@@ -367,20 +473,33 @@ pub async fn add_channel_tools(
             .get(state.deps.agent_id.as_ref())
             .cloned()
             .unwrap_or_else(|| state.deps.agent_id.to_string());
-        handle
-            .add_tool(ReplyTool::new(
-                response_tx.clone(),
-                conversation_id.clone(),
-                state.conversation_logger.clone(),
-                state.channel_id.clone(),
-                replied_flag.clone(),
-                agent_display_name,
-            ))
-            .await?;
+        let mut reply_tool = ReplyTool::new(
+            response_tx.clone(),
+            conversation_id.clone(),
+            state.conversation_logger.clone(),
+            state.channel_id.clone(),
+            replied_flag.clone(),
+            agent_display_name,
+        );
+        if let Some(translation) = reply_translation(&state).await {
+            reply_tool = reply_tool.with_translation(translation);
+        }
+        if let Some(verifier) = reply_verifier(&state) {
+            reply_tool = reply_tool.with_verifier(verifier);
+        }
+        handle.add_tool(reply_tool).await?;
     }
     handle.add_tool(BranchTool::new(state.clone())).await?;
     handle.add_tool(SpawnWorkerTool::new(state.clone())).await?;
     handle.add_tool(RouteTool::new(state.clone())).await?;
+    handle
+        .add_tool(LookupChannelTool::new(
+            state.conversation_logger.clone(),
+            state.channel_store.clone(),
+            (**state.deps.runtime_config.memory_scopes.load()).clone(),
+            Some(state.channel_id.to_string()),
+        ))
+        .await?;
     if let Some(messaging_manager) = &state.deps.messaging_manager {
         let send_message_display_name = state
             .deps
@@ -389,13 +508,19 @@ pub async fn add_channel_tools(
             .cloned()
             .unwrap_or_else(|| state.deps.agent_id.to_string());
         handle
-            .add_tool(SendMessageTool::new(
-                messaging_manager.clone(),
-                state.channel_store.clone(),
-                state.conversation_logger.clone(),
-                send_message_display_name,
-                current_adapter.clone(),
-            ))
+            .add_tool(
+                SendMessageTool::new(
+                    messaging_manager.clone(),
+                    state.channel_store.clone(),
+                    state.conversation_logger.clone(),
+                    send_message_display_name,
+                    current_adapter.clone(),
+                )
+                .with_contacts(
+                    ContactStore::new(state.deps.sqlite_pool.clone()),
+                    state.deps.humans.clone(),
+                ),
+            )
             .await?;
     }
     handle
@@ -483,6 +608,7 @@ pub async fn remove_channel_tools(
     handle.remove_tool(BranchTool::NAME).await?;
     handle.remove_tool(SpawnWorkerTool::NAME).await?;
     handle.remove_tool(RouteTool::NAME).await?;
+    handle.remove_tool(LookupChannelTool::NAME).await?;
     handle.remove_tool(CancelTool::NAME).await?;
     handle.remove_tool(SkipTool::NAME).await?;
     handle.remove_tool(SendFileTool::NAME).await?;
@@ -510,9 +636,13 @@ fn memory_save_with_events(
 /// Each branch gets its own isolated ToolServer so `memory_recall` is never
 /// visible to the channel. Includes memory tools, task-board tools, and
 /// `spacebot_docs` for on-demand self-documentation lookup.
+///
+/// Memory tools are restricted to the scope of `scope_channel_id`, falling
+/// back to the channel in `state`. Branches with neither are unscoped.
 #[allow(clippy::too_many_arguments)]
 pub fn create_branch_tool_server(
     state: Option<ChannelState>,
+    scope_channel_id: Option<&str>,
     agent_id: AgentId,
     task_store: Arc<TaskStore>,
     memory_search: Arc<MemorySearch>,
@@ -523,36 +653,149 @@ pub fn create_branch_tool_server(
     run_logger: crate::conversation::history::ProcessRunLogger,
     profile: BranchToolProfile,
 ) -> ToolServerHandle {
+    // Branches working for a channel only see and write that channel's
+    // memory scope, plus the context scopes of the channel and its current
+    // user.
+    let channel_id = scope_channel_id.or(state.as_ref().map(|state| &*state.channel_id));
+    let user_scope = state.as_ref().and_then(|state| {
+        state
+            .user_scope
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    });
+    let memory_scopes = (**runtime_config.memory_scopes.load()).clone();
+    let memory_access = channel_id
+        .map(|channel_id| memory_scopes.access_for_channel(channel_id, user_scope.as_deref()))
+        .unwrap_or_default();
+    let mention_filter = channel_id
+        .map(|channel_id| MentionFilter::FromChannel {
+            channel_id: channel_id.to_string(),
+            memory_scopes: memory_scopes.clone(),
+            channel_store: channel_store.clone(),
+        })
+        .unwrap_or_default();
+    let lookup_channel = LookupChannelTool::new(
+        conversation_logger.clone(),
+        channel_store.clone(),
+        memory_scopes,
+        channel_id.map(str::to_string),
+    );
+
     let mut memory_save = memory_save_with_events(
         memory_search.clone(),
         agent_id.clone(),
         memory_event_tx.clone(),
-    );
+    )
+    .with_access(memory_access.clone());
     if let BranchToolProfile::MemoryPersistence { contract_state } = &profile {
         memory_save = memory_save.with_contract_state(contract_state.clone());
     }
 
+    let weather_enabled = runtime_config.weather.load().enabled;
+    let weather_runtime_config = runtime_config.clone();
+    let convert_tool = ConvertTool::new(runtime_config.clone());
+
     let mut server = ToolServer::new()
         .tool(memory_save)
-        .tool(MemoryRecallTool::new(memory_search.clone()))
-        .tool(MemoryDeleteTool::new(memory_search))
+        .tool(
+            MemoryRecallTool::new(memory_search.clone())
+                .with_scope_filter(memory_access.read.clone())
+                .with_graph(CONVERSATION_GRAPH_DEPTH, EdgeWeights::default()),
+        )
+        .tool(
+            TodoUpdateTool::new(memory_search.clone())
+                .with_scope_filter(memory_access.read.clone()),
+        )
+        .tool(
+            EntityProfileTool::new(memory_search.clone(), conversation_logger.clone())
+                .with_scope_filter(memory_access.read.clone())
+                .with_mention_filter(mention_filter),
+        )
+        .tool(MemoryDeleteTool::new(memory_search.clone()).with_access(memory_access))
         .tool(ChannelRecallTool::new(conversation_logger, channel_store))
+        .tool(lookup_channel)
         .tool(SpacebotDocsTool::new())
+        .tool(CalculateTool::new())
+        .tool(convert_tool)
         .tool(EmailSearchTool::new(runtime_config))
         .tool(WorkerInspectTool::new(run_logger, agent_id.to_string()))
-        .tool(TaskCreateTool::new(
-            task_store.clone(),
-            agent_id.to_string(),
-            "branch",
-        ))
+        .tool(
+            TaskCreateTool::new(task_store.clone(), agent_id.to_string(), "branch").with_notifier(
+                state.as_ref().and_then(|state| {
+                    crate::notifications::OperatorNotifier::from_deps(&state.deps)
+                }),
+            ),
+        )
         .tool(TaskListTool::new(task_store.clone(), agent_id.to_string()))
         .tool(TaskUpdateTool::for_branch(task_store, agent_id.clone()));
 
     if let BranchToolProfile::MemoryPersistence { contract_state } = profile {
-        server = server.tool(MemoryPersistenceCompleteTool::new(contract_state));
+        server = server.tool(
+            MemoryPersistenceCompleteTool::new(contract_state).with_memory_search(memory_search),
+        );
+    }
+
+    if weather_enabled {
+        server = server
+            .tool(WeatherTool::new(weather_runtime_config.clone()))
+            .tool(GeocodeTool::new(weather_runtime_config));
     }
 
     if let Some(state) = state {
+        if !state.deps.runtime_config.issue_trackers.load().is_empty() {
+            server = server.tool(IssueTrackerTool::new(
+                state.deps.runtime_config.clone(),
+                crate::approvals::ApprovalStore::new(state.deps.sqlite_pool.clone()).with_notifier(
+                    crate::notifications::OperatorNotifier::from_deps(&state.deps),
+                ),
+                Some(state.channel_id.to_string()),
+                "branch",
+            ));
+        }
+        if state.deps.runtime_config.translation.load().enabled {
+            server = server.tool(TranslateTool::new(
+                state.deps.llm_manager.clone(),
+                state.deps.runtime_config.clone(),
+                state.deps.agent_id.clone(),
+            ));
+        }
+        server = server.tool(ContactsTool::new(
+            ContactStore::new(state.deps.sqlite_pool.clone()),
+            state.deps.humans.clone(),
+            state.deps.messaging_manager.clone(),
+        ));
+        server = server.tool(LocaleTool::new(
+            state.deps.runtime_config.clone(),
+            state.channel_id.to_string(),
+        ));
+        if state.deps.runtime_config.broadcast.load().enabled
+            && let Some(messaging_manager) = &state.deps.messaging_manager
+        {
+            server = server.tool(BroadcastTool::new(
+                state.deps.runtime_config.clone(),
+                messaging_manager.clone(),
+                crate::approvals::ApprovalStore::new(state.deps.sqlite_pool.clone()).with_notifier(
+                    crate::notifications::OperatorNotifier::from_deps(&state.deps),
+                ),
+                Some(state.channel_id.to_string()),
+                "branch",
+            ));
+        }
+        if state.deps.runtime_config.channel_config.load().faq_matching {
+            server = server.tool(FaqProposeTool::new(
+                state.deps.sqlite_pool.clone(),
+                state.deps.memory_search.embedding_model_arc().clone(),
+                state.channel_id.to_string(),
+                state
+                    .deps
+                    .runtime_config
+                    .memory_scopes
+                    .load()
+                    .scope_for_channel(&state.channel_id)
+                    .map(str::to_string),
+            ));
+        }
         server = server.tool(SpawnWorkerTool::new(state));
     }
 
@@ -580,6 +823,8 @@ pub fn create_worker_tool_server(
     workspace: PathBuf,
     sandbox: Arc<Sandbox>,
     mcp_tools: Vec<McpToolAdapter>,
+    chart: ChartTool,
+    issue_tracker: Option<IssueTrackerTool>,
     runtime_config: Arc<RuntimeConfig>,
 ) -> ToolServerHandle {
     let mut server = ToolServer::new()
@@ -596,7 +841,10 @@ pub fn create_worker_tool_server(
             }
             status_tool
         })
-        .tool(ReadSkillTool::new(runtime_config.clone()));
+        .tool(ReadSkillTool::new(runtime_config.clone()))
+        .tool(chart)
+        .tool(CalculateTool::new())
+        .tool(ConvertTool::new(runtime_config.clone()));
 
     server = register_file_tools(server, workspace, sandbox);
 
@@ -612,6 +860,16 @@ pub fn create_worker_tool_server(
         server = server.tool(WebSearchTool::new(key));
     }
 
+    if let Some(tool) = issue_tracker {
+        server = server.tool(tool);
+    }
+
+    if runtime_config.weather.load().enabled {
+        server = server
+            .tool(WeatherTool::new(runtime_config.clone()))
+            .tool(GeocodeTool::new(runtime_config.clone()));
+    }
+
     for mcp_tool in mcp_tools {
         server = server.tool(mcp_tool);
     }
@@ -664,6 +922,37 @@ pub fn create_cortex_chat_tool_server(
     cortex_ctx: Option<crate::tools::spawn_worker::CortexChatContext>,
 ) -> ToolServerHandle {
     let logs_dir = workspace.join(".spacebot").join("logs");
+    let chart_tool = ChartTool::new(deps.sqlite_pool.clone(), runtime_config.saved_dir(), None);
+    let notifier = crate::notifications::OperatorNotifier::from_deps(&deps);
+
+    let translate_tool = runtime_config.translation.load().enabled.then(|| {
+        TranslateTool::new(
+            deps.llm_manager.clone(),
+            runtime_config.clone(),
+            agent_id.clone(),
+        )
+    });
+
+    let contacts_tool = ContactsTool::new(
+        ContactStore::new(deps.sqlite_pool.clone()),
+        deps.humans.clone(),
+        deps.messaging_manager.clone(),
+    );
+
+    let broadcast_tool = deps
+        .messaging_manager
+        .clone()
+        .filter(|_| runtime_config.broadcast.load().enabled)
+        .map(|messaging_manager| {
+            BroadcastTool::new(
+                runtime_config.clone(),
+                messaging_manager,
+                crate::approvals::ApprovalStore::new(deps.sqlite_pool.clone())
+                    .with_notifier(notifier.clone()),
+                None,
+                "cortex",
+            )
+        });
 
     let spawn_tool = {
         let tool = DetachedSpawnWorkerTool::new(deps, screenshot_dir.clone(), logs_dir);
@@ -679,10 +968,19 @@ pub fn create_cortex_chat_tool_server(
             agent_id.clone(),
             memory_event_tx,
         ))
-        .tool(MemoryRecallTool::new(memory_search.clone()))
+        .tool(
+            MemoryRecallTool::new(memory_search.clone())
+                .with_graph(REFLECTION_GRAPH_DEPTH, EdgeWeights::reflection()),
+        )
+        .tool(TodoUpdateTool::new(memory_search.clone()))
+        .tool(EntityProfileTool::new(
+            memory_search.clone(),
+            conversation_logger.clone(),
+        ))
         .tool(MemoryDeleteTool::new(memory_search))
         .tool(ChannelRecallTool::new(conversation_logger, channel_store))
         .tool(SpacebotDocsTool::new())
+        .tool(contacts_tool)
         .tool(ConfigInspectTool::new(
             agent_id.to_string(),
             runtime_config.clone(),
@@ -691,14 +989,16 @@ pub fn create_cortex_chat_tool_server(
         .tool(InstallSkillTool::new(runtime_config.clone(), api_state))
         .tool(WorkerInspectTool::new(run_logger, agent_id.to_string()))
         .tool(spawn_tool)
-        .tool(TaskCreateTool::new(
-            task_store.clone(),
-            agent_id.to_string(),
-            "cortex",
-        ))
+        .tool(
+            TaskCreateTool::new(task_store.clone(), agent_id.to_string(), "cortex")
+                .with_notifier(notifier),
+        )
         .tool(TaskListTool::new(task_store.clone(), agent_id.to_string()))
         .tool(TaskUpdateTool::for_branch(task_store, agent_id.clone()))
-        .tool(ShellTool::new(workspace.clone(), sandbox.clone()));
+        .tool(ShellTool::new(workspace.clone(), sandbox.clone()))
+        .tool(chart_tool)
+        .tool(CalculateTool::new())
+        .tool(ConvertTool::new(runtime_config.clone()));
 
     server = register_file_tools(server, workspace, sandbox);
 
@@ -710,6 +1010,20 @@ pub fn create_cortex_chat_tool_server(
         server = server.tool(WebSearchTool::new(key));
     }
 
+    if runtime_config.weather.load().enabled {
+        server = server
+            .tool(WeatherTool::new(runtime_config.clone()))
+            .tool(GeocodeTool::new(runtime_config));
+    }
+
+    if let Some(tool) = translate_tool {
+        server = server.tool(tool);
+    }
+
+    if let Some(tool) = broadcast_tool {
+        server = server.tool(tool);
+    }
+
     server.run()
 }
 
@@ -0,0 +1,242 @@
+//! Broadcast tool: post one announcement to a configured group of channels,
+//! after a person approves it unless the agent is trusted to send directly.
+
+use crate::approvals::ApprovalStore;
+use crate::config::RuntimeConfig;
+use crate::messaging::MessagingManager;
+use crate::messaging::broadcast::{Announcement, BroadcastReport, GroupBroadcast, fan_out};
+use rig::completion::ToolDefinition;
+use rig::tool::Tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Tool for announcing to the agent's broadcast groups.
+#[derive(Clone)]
+pub struct BroadcastTool {
+    runtime_config: Arc<RuntimeConfig>,
+    messaging_manager: Arc<MessagingManager>,
+    approvals: ApprovalStore,
+    channel_id: Option<String>,
+    requested_by: String,
+}
+
+impl std::fmt::Debug for BroadcastTool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BroadcastTool").finish_non_exhaustive()
+    }
+}
+
+impl BroadcastTool {
+    pub fn new(
+        runtime_config: Arc<RuntimeConfig>,
+        messaging_manager: Arc<MessagingManager>,
+        approvals: ApprovalStore,
+        channel_id: Option<String>,
+        requested_by: impl Into<String>,
+    ) -> Self {
+        Self {
+            runtime_config,
+            messaging_manager,
+            approvals,
+            channel_id,
+            requested_by: requested_by.into(),
+        }
+    }
+}
+
+/// Error type for broadcast tool.
+#[derive(Debug, thiserror::Error)]
+#[error("broadcast failed: {0}")]
+pub struct BroadcastError(String);
+
+/// Arguments for broadcast.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct BroadcastArgs {
+    /// `groups` or `send`.
+    pub action: String,
+    /// Group to send to (`send`).
+    #[serde(default)]
+    pub group: Option<String>,
+    /// Announcement in Markdown (`send`).
+    #[serde(default)]
+    pub message: Option<String>,
+    /// Replacement text per platform (`send`).
+    #[serde(default)]
+    pub platform_messages: HashMap<String, String>,
+}
+
+/// A group and the channels in it.
+#[derive(Debug, Serialize)]
+pub struct BroadcastGroup {
+    pub name: String,
+    pub targets: Vec<String>,
+}
+
+/// Output for broadcast.
+#[derive(Debug, Serialize)]
+pub struct BroadcastOutput {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub groups: Option<Vec<BroadcastGroup>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub report: Option<BroadcastReport>,
+    /// Set when the broadcast was queued for approval instead of sent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub approval_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl Tool for BroadcastTool {
+    const NAME: &'static str = "broadcast";
+
+    type Error = BroadcastError;
+    type Args = BroadcastArgs;
+    type Output = BroadcastOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: crate::prompts::text::get("tools/broadcast").to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "action": {
+                        "type": "string",
+                        "enum": ["groups", "send"],
+                        "description": "`groups` lists the broadcast groups and their channels; `send` posts the announcement to one group."
+                    },
+                    "group": {
+                        "type": "string",
+                        "description": "Group to send to (`send`)."
+                    },
+                    "message": {
+                        "type": "string",
+                        "description": "Announcement in Markdown. Each platform gets it in its own formatting."
+                    },
+                    "platform_messages": {
+                        "type": "object",
+                        "additionalProperties": { "type": "string" },
+                        "description": "Optional replacement text keyed by platform (discord, slack, telegram, signal, email, ...), e.g. a shorter version for SMS-like chats."
+                    }
+                },
+                "required": ["action"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let config = self.runtime_config.broadcast.load();
+
+        match args.action.as_str() {
+            "groups" => {
+                let groups = config
+                    .groups
+                    .iter()
+                    .map(|(name, targets)| BroadcastGroup {
+                        name: name.clone(),
+                        targets: targets.iter().map(ToString::to_string).collect(),
+                    })
+                    .collect();
+                return Ok(BroadcastOutput {
+                    groups: Some(groups),
+                    report: None,
+                    approval_id: None,
+                    message: None,
+                });
+            }
+            "send" => {}
+            other => {
+                return Err(BroadcastError(format!(
+                    "unknown action '{other}', expected groups or send"
+                )));
+            }
+        }
+
+        let group = args
+            .group
+            .map(|group| group.trim().to_string())
+            .filter(|group| !group.is_empty())
+            .ok_or_else(|| BroadcastError("`group` is required for send".into()))?;
+        let Some(targets) = config.groups.get(&group) else {
+            let names: Vec<&str> = config.groups.keys().map(String::as_str).collect();
+            return Err(BroadcastError(format!(
+                "no broadcast group named '{group}'; available: {}",
+                if names.is_empty() {
+                    "none".to_string()
+                } else {
+                    names.join(", ")
+                }
+            )));
+        };
+        let message = args
+            .message
+            .filter(|message| !message.trim().is_empty())
+            .ok_or_else(|| BroadcastError("`message` is required for send".into()))?;
+        let announcement = Announcement {
+            message,
+            platform_messages: args.platform_messages,
+        };
+
+        if !config.require_approval {
+            let report = fan_out(
+                &self.messaging_manager,
+                targets,
+                &announcement,
+                Duration::from_millis(config.stagger_ms),
+            )
+            .await;
+            tracing::info!(
+                group = %group,
+                sent = report.sent,
+                failed = report.failed,
+                "broadcast sent"
+            );
+            return Ok(BroadcastOutput {
+                groups: None,
+                message: Some(format!(
+                    "Sent to {} of {} channels in '{group}'.",
+                    report.sent,
+                    report.deliveries.len()
+                )),
+                report: Some(report),
+                approval_id: None,
+            });
+        }
+
+        let summary = format!("Broadcast to '{group}' ({} channels)", targets.len());
+        let payload = serde_json::to_value(GroupBroadcast {
+            group,
+            announcement,
+        })
+        .map_err(|error| BroadcastError(error.to_string()))?;
+        let approval = self
+            .approvals
+            .request(
+                Self::NAME,
+                &summary,
+                &payload,
+                self.channel_id.as_deref(),
+                &self.requested_by,
+            )
+            .await
+            .map_err(|error| BroadcastError(error.to_string()))?;
+
+        tracing::info!(
+            approval_id = %approval.id,
+            summary = %approval.summary,
+            "broadcast queued for approval"
+        );
+
+        Ok(BroadcastOutput {
+            groups: None,
+            report: None,
+            approval_id: Some(approval.id),
+            message: Some(format!(
+                "Queued for approval: {summary}. Nothing has been sent yet; tell the user it needs approving."
+            )),
+        })
+    }
+}
@@ -93,7 +93,9 @@ fn validate_url(url: &str) -> Result<(), BrowserError> {
     Ok(())
 }
 
-fn is_blocked_ip(ip: IpAddr) -> bool {
+/// Loopback, private, link-local, CGNAT, and unspecified addresses, which
+/// server-side requests must not be steered at.
+pub(crate) fn is_blocked_ip(ip: IpAddr) -> bool {
     match ip {
         IpAddr::V4(v4) => {
             v4.is_loopback()
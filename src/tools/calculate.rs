@@ -0,0 +1,73 @@
+//! Calculate tool: evaluate arithmetic exactly.
+
+use crate::calc::{MAX_EXPRESSION_CHARS, evaluate};
+use rig::completion::ToolDefinition;
+use rig::tool::Tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Tool for exact decimal arithmetic.
+#[derive(Debug, Clone, Default)]
+pub struct CalculateTool;
+
+impl CalculateTool {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Error type for calculate tool.
+#[derive(Debug, thiserror::Error)]
+#[error("calculate failed: {0}")]
+pub struct CalculateError(String);
+
+/// Arguments for calculate.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct CalculateArgs {
+    /// Arithmetic expression, e.g. `(1299.99 * 3) * 1.0825`.
+    pub expression: String,
+}
+
+/// Output for calculate.
+#[derive(Debug, Serialize)]
+pub struct CalculateOutput {
+    pub expression: String,
+    /// Exact result as a decimal string.
+    pub result: String,
+}
+
+impl Tool for CalculateTool {
+    const NAME: &'static str = "calculate";
+
+    type Error = CalculateError;
+    type Args = CalculateArgs;
+    type Output = CalculateOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: crate::prompts::text::get("tools/calculate").to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "expression": {
+                        "type": "string",
+                        "maxLength": MAX_EXPRESSION_CHARS,
+                        "description": "Arithmetic expression using + - * / % ^, parentheses, pi, e, and abs, sqrt, round(x, places), floor, ceil, trunc, min, max, ln, log10, exp"
+                    }
+                },
+                "required": ["expression"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let result =
+            evaluate(&args.expression).map_err(|error| CalculateError(error.to_string()))?;
+
+        Ok(CalculateOutput {
+            expression: args.expression,
+            result: result.to_string(),
+        })
+    }
+}
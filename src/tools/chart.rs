@@ -0,0 +1,742 @@
+//! Chart tool: renders series data as a line, bar, or scatter chart (SVG or
+//! PNG) into the attachment store so it can be sent to a channel or opened
+//! from the dashboard.
+
+use crate::ChannelId;
+use crate::agent::channel_attachments::persist_attachment;
+
+use rig::completion::ToolDefinition;
+use rig::tool::Tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::sync::{Arc, LazyLock};
+
+const WIDTH: u32 = 800;
+const HEIGHT: u32 = 480;
+const MARGIN_LEFT: f64 = 72.0;
+const MARGIN_RIGHT: f64 = 24.0;
+const MARGIN_TOP: f64 = 56.0;
+const MARGIN_BOTTOM: f64 = 84.0;
+
+const MAX_SERIES: usize = 12;
+const MAX_POINTS: usize = 5_000;
+
+/// Tableau 10.
+const PALETTE: &[&str] = &[
+    "#4e79a7", "#f28e2b", "#e15759", "#76b7b2", "#59a14f", "#edc948", "#b07aa1", "#ff9da7",
+    "#9c755f", "#bab0ac",
+];
+
+/// System fonts for PNG text, loaded once.
+static FONTS: LazyLock<Arc<resvg::usvg::fontdb::Database>> = LazyLock::new(|| {
+    let mut database = resvg::usvg::fontdb::Database::new();
+    database.load_system_fonts();
+    Arc::new(database)
+});
+
+/// Tool for rendering charts from structured data.
+///
+/// Charts are written to `workspace/saved/`. When the process belongs to a
+/// channel the file is also recorded in `saved_attachments`, so it shows up in
+/// `attachment_recall` and is served by the attachments API.
+#[derive(Debug, Clone)]
+pub struct ChartTool {
+    pool: SqlitePool,
+    saved_dir: PathBuf,
+    channel_id: Option<ChannelId>,
+}
+
+impl ChartTool {
+    pub fn new(pool: SqlitePool, saved_dir: PathBuf, channel_id: Option<ChannelId>) -> Self {
+        Self {
+            pool,
+            saved_dir,
+            channel_id,
+        }
+    }
+}
+
+/// Error type for chart tool.
+#[derive(Debug, thiserror::Error)]
+#[error("Chart failed: {0}")]
+pub struct ChartError(String);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ChartKind {
+    Line,
+    Bar,
+    Scatter,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ChartFormat {
+    #[default]
+    Png,
+    Svg,
+}
+
+impl ChartFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Svg => "svg",
+        }
+    }
+
+    fn mime_type(self) -> &'static str {
+        match self {
+            Self::Png => "image/png",
+            Self::Svg => "image/svg+xml",
+        }
+    }
+}
+
+/// One named data series.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct ChartSeries {
+    pub name: String,
+    /// Y values.
+    pub values: Vec<f64>,
+    /// Numeric X values for line and scatter charts, one per value. Defaults
+    /// to the value's position (or `labels`).
+    #[serde(default)]
+    pub x: Option<Vec<f64>>,
+}
+
+/// Arguments for chart tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ChartArgs {
+    pub kind: ChartKind,
+    pub series: Vec<ChartSeries>,
+    /// Category labels along the X axis, one per value position.
+    #[serde(default)]
+    pub labels: Vec<String>,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub x_label: Option<String>,
+    #[serde(default)]
+    pub y_label: Option<String>,
+    #[serde(default)]
+    pub format: ChartFormat,
+    /// File name without extension. Defaults to one derived from the title.
+    #[serde(default)]
+    pub filename: Option<String>,
+}
+
+/// Output from chart tool.
+#[derive(Debug, Serialize)]
+pub struct ChartOutput {
+    /// Absolute path of the rendered chart, suitable for `send_file`.
+    pub file_path: String,
+    pub filename: String,
+    pub mime_type: String,
+    pub size_bytes: u64,
+    /// Saved attachment id when the chart was recorded against a channel.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attachment_id: Option<String>,
+}
+
+impl Tool for ChartTool {
+    const NAME: &'static str = "chart";
+
+    type Error = ChartError;
+    type Args = ChartArgs;
+    type Output = ChartOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: crate::prompts::text::get("tools/chart").to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "kind": {
+                        "type": "string",
+                        "enum": ["line", "bar", "scatter"],
+                        "description": "Chart type."
+                    },
+                    "series": {
+                        "type": "array",
+                        "description": "Data series (at most 12).",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "name": { "type": "string", "description": "Legend name." },
+                                "values": {
+                                    "type": "array",
+                                    "items": { "type": "number" },
+                                    "description": "Y values."
+                                },
+                                "x": {
+                                    "type": "array",
+                                    "items": { "type": "number" },
+                                    "description": "Numeric X values for line/scatter charts, one per value. Omit to use positions."
+                                }
+                            },
+                            "required": ["name", "values"]
+                        }
+                    },
+                    "labels": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Category labels along the X axis, one per value position (e.g. months)."
+                    },
+                    "title": { "type": "string", "description": "Chart title." },
+                    "x_label": { "type": "string", "description": "X axis label." },
+                    "y_label": { "type": "string", "description": "Y axis label." },
+                    "format": {
+                        "type": "string",
+                        "enum": ["png", "svg"],
+                        "description": "Output format (default png)."
+                    },
+                    "filename": {
+                        "type": "string",
+                        "description": "File name without extension. Defaults to one derived from the title."
+                    }
+                },
+                "required": ["kind", "series"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let svg = render_svg(&args)?;
+        let bytes = match args.format {
+            ChartFormat::Svg => svg.into_bytes(),
+            ChartFormat::Png => tokio::task::spawn_blocking(move || render_png(&svg))
+                .await
+                .map_err(|error| ChartError(format!("render task failed: {error}")))??,
+        };
+
+        let stem = args
+            .filename
+            .as_deref()
+            .or(args.title.as_deref())
+            .map(slugify)
+            .filter(|stem| !stem.is_empty())
+            .unwrap_or_else(|| "chart".to_string());
+        let filename = format!("{stem}.{}", args.format.extension());
+        let mime_type = args.format.mime_type();
+
+        tokio::fs::create_dir_all(&self.saved_dir)
+            .await
+            .map_err(|error| ChartError(format!("failed to create saved directory: {error}")))?;
+
+        let (saved_filename, attachment_id) = match &self.channel_id {
+            Some(channel_id) => {
+                let meta = persist_attachment(
+                    &self.pool,
+                    channel_id,
+                    &self.saved_dir,
+                    &filename,
+                    mime_type,
+                    &bytes,
+                )
+                .await
+                .map_err(ChartError)?;
+                (meta.saved_filename, Some(meta.id))
+            }
+            None => {
+                // No channel to record against; a unique name is enough.
+                let short_id = uuid::Uuid::new_v4().simple().to_string();
+                let saved_filename =
+                    format!("{stem}-{}.{}", &short_id[..8], args.format.extension());
+                tokio::fs::write(self.saved_dir.join(&saved_filename), &bytes)
+                    .await
+                    .map_err(|error| ChartError(format!("failed to write chart: {error}")))?;
+                (saved_filename, None)
+            }
+        };
+
+        let file_path = self.saved_dir.join(&saved_filename);
+        tracing::info!(
+            path = %file_path.display(),
+            kind = ?args.kind,
+            series = args.series.len(),
+            "chart rendered"
+        );
+
+        Ok(ChartOutput {
+            file_path: file_path.to_string_lossy().into_owned(),
+            filename: saved_filename,
+            mime_type: mime_type.to_string(),
+            size_bytes: bytes.len() as u64,
+            attachment_id,
+        })
+    }
+}
+
+fn validate(args: &ChartArgs) -> Result<(), ChartError> {
+    if args.series.is_empty() {
+        return Err(ChartError("at least one series is required".into()));
+    }
+    if args.series.len() > MAX_SERIES {
+        return Err(ChartError(format!(
+            "too many series ({}, max {MAX_SERIES})",
+            args.series.len()
+        )));
+    }
+    for series in &args.series {
+        if series.values.is_empty() {
+            return Err(ChartError(format!(
+                "series '{}' has no values",
+                series.name
+            )));
+        }
+        if series.values.len() > MAX_POINTS {
+            return Err(ChartError(format!(
+                "series '{}' has {} values, max {MAX_POINTS}",
+                series.name,
+                series.values.len()
+            )));
+        }
+        if series.values.iter().any(|value| !value.is_finite()) {
+            return Err(ChartError(format!(
+                "series '{}' contains a non-finite value",
+                series.name
+            )));
+        }
+        if let Some(x) = &series.x {
+            if args.kind == ChartKind::Bar {
+                return Err(ChartError(
+                    "bar charts take `labels` for categories, not numeric `x`".into(),
+                ));
+            }
+            if x.len() != series.values.len() {
+                return Err(ChartError(format!(
+                    "series '{}' has {} x values for {} values",
+                    series.name,
+                    x.len(),
+                    series.values.len()
+                )));
+            }
+            if x.iter().any(|value| !value.is_finite()) {
+                return Err(ChartError(format!(
+                    "series '{}' contains a non-finite x value",
+                    series.name
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Maps data coordinates onto the plot area.
+struct Frame {
+    x_min: f64,
+    x_max: f64,
+    y_min: f64,
+    y_max: f64,
+}
+
+impl Frame {
+    fn left() -> f64 {
+        MARGIN_LEFT
+    }
+
+    fn right() -> f64 {
+        f64::from(WIDTH) - MARGIN_RIGHT
+    }
+
+    fn top() -> f64 {
+        MARGIN_TOP
+    }
+
+    fn bottom() -> f64 {
+        f64::from(HEIGHT) - MARGIN_BOTTOM
+    }
+
+    fn px(&self, x: f64) -> f64 {
+        Self::left() + (x - self.x_min) / (self.x_max - self.x_min) * (Self::right() - Self::left())
+    }
+
+    fn py(&self, y: f64) -> f64 {
+        Self::bottom()
+            - (y - self.y_min) / (self.y_max - self.y_min) * (Self::bottom() - Self::top())
+    }
+}
+
+/// Render the chart as a standalone SVG document.
+fn render_svg(args: &ChartArgs) -> Result<String, ChartError> {
+    validate(args)?;
+
+    let positions = args
+        .series
+        .iter()
+        .map(|series| series.values.len())
+        .max()
+        .unwrap_or(0)
+        .max(args.labels.len());
+    let numeric_x = args.kind != ChartKind::Bar && args.series.iter().any(|s| s.x.is_some());
+    let x_of = |series: &ChartSeries, index: usize| match &series.x {
+        Some(x) => x[index],
+        None => index as f64,
+    };
+
+    let (mut y_min, mut y_max) = bounds(args.series.iter().flat_map(|s| s.values.iter().copied()));
+    if args.kind == ChartKind::Bar {
+        y_min = y_min.min(0.0);
+        y_max = y_max.max(0.0);
+    }
+    let y_ticks = nice_ticks(y_min, y_max, 6);
+    let y_min = y_ticks.first().copied().unwrap_or(y_min);
+    let y_max = y_ticks.last().copied().unwrap_or(y_max);
+
+    let (x_min, x_max, x_ticks) = match args.kind {
+        // Bars sit in bands centred on each position.
+        ChartKind::Bar => (-0.5, positions as f64 - 0.5, Vec::new()),
+        _ if numeric_x => {
+            let (low, high) = bounds(
+                args.series
+                    .iter()
+                    .flat_map(|s| (0..s.values.len()).map(|index| x_of(s, index))),
+            );
+            let ticks = nice_ticks(low, high, 8);
+            (
+                ticks.first().copied().unwrap_or(low),
+                ticks.last().copied().unwrap_or(high),
+                ticks,
+            )
+        }
+        _ => {
+            let high = positions.saturating_sub(1).max(1) as f64;
+            (0.0, high, Vec::new())
+        }
+    };
+    let frame = Frame {
+        x_min,
+        x_max,
+        y_min,
+        y_max,
+    };
+
+    let mut svg = String::new();
+    let _ = write!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{HEIGHT}" viewBox="0 0 {WIDTH} {HEIGHT}" font-family="DejaVu Sans, Helvetica, Arial, sans-serif">"#
+    );
+    let _ = write!(
+        svg,
+        r##"<rect width="{WIDTH}" height="{HEIGHT}" fill="#ffffff"/>"##
+    );
+
+    if let Some(title) = args.title.as_deref().filter(|t| !t.trim().is_empty()) {
+        let _ = write!(
+            svg,
+            r##"<text x="{}" y="32" font-size="18" font-weight="bold" text-anchor="middle" fill="#222">{}</text>"##,
+            f64::from(WIDTH) / 2.0,
+            escape(title)
+        );
+    }
+
+    // Horizontal grid and Y tick labels.
+    for tick in &y_ticks {
+        let y = frame.py(*tick);
+        let _ = write!(
+            svg,
+            r##"<line x1="{:.1}" y1="{y:.1}" x2="{:.1}" y2="{y:.1}" stroke="#e5e5e5"/><text x="{:.1}" y="{:.1}" font-size="11" text-anchor="end" fill="#555">{}</text>"##,
+            Frame::left(),
+            Frame::right(),
+            Frame::left() - 8.0,
+            y + 4.0,
+            format_number(*tick)
+        );
+    }
+
+    // X tick labels.
+    let label_at = |index: usize| {
+        args.labels
+            .get(index)
+            .cloned()
+            .unwrap_or_else(|| index.to_string())
+    };
+    if numeric_x {
+        for tick in &x_ticks {
+            x_tick(&mut svg, frame.px(*tick), &format_number(*tick));
+        }
+    } else if positions > 0 {
+        // Thin out labels so they don't overlap.
+        let step = positions.div_ceil(12).max(1);
+        for index in (0..positions).step_by(step) {
+            x_tick(&mut svg, frame.px(index as f64), &label_at(index));
+        }
+    }
+
+    // Axes.
+    let _ = write!(
+        svg,
+        r##"<line x1="{left:.1}" y1="{top:.1}" x2="{left:.1}" y2="{bottom:.1}" stroke="#888"/><line x1="{left:.1}" y1="{bottom:.1}" x2="{right:.1}" y2="{bottom:.1}" stroke="#888"/>"##,
+        left = Frame::left(),
+        right = Frame::right(),
+        top = Frame::top(),
+        bottom = Frame::bottom(),
+    );
+    if let Some(label) = args.x_label.as_deref().filter(|l| !l.trim().is_empty()) {
+        let _ = write!(
+            svg,
+            r##"<text x="{:.1}" y="{:.1}" font-size="13" text-anchor="middle" fill="#333">{}</text>"##,
+            (Frame::left() + Frame::right()) / 2.0,
+            Frame::bottom() + 44.0,
+            escape(label)
+        );
+    }
+    if let Some(label) = args.y_label.as_deref().filter(|l| !l.trim().is_empty()) {
+        let x = 18.0;
+        let y = (Frame::top() + Frame::bottom()) / 2.0;
+        let _ = write!(
+            svg,
+            r##"<text x="{x:.1}" y="{y:.1}" font-size="13" text-anchor="middle" fill="#333" transform="rotate(-90 {x:.1} {y:.1})">{}</text>"##,
+            escape(label)
+        );
+    }
+
+    // Data.
+    let series_count = args.series.len() as f64;
+    for (series_index, series) in args.series.iter().enumerate() {
+        let color = PALETTE[series_index % PALETTE.len()];
+        match args.kind {
+            ChartKind::Bar => {
+                let band = frame.px(1.0) - frame.px(0.0);
+                let bar_width = band * 0.8 / series_count;
+                let zero = frame.py(0.0_f64.clamp(frame.y_min, frame.y_max));
+                for (index, value) in series.values.iter().enumerate() {
+                    let x = frame.px(index as f64) - band * 0.4 + bar_width * series_index as f64;
+                    let y = frame.py(*value);
+                    let _ = write!(
+                        svg,
+                        r#"<rect x="{x:.1}" y="{:.1}" width="{:.1}" height="{:.1}" fill="{color}"/>"#,
+                        y.min(zero),
+                        (bar_width - 1.0).max(1.0),
+                        (zero - y).abs()
+                    );
+                }
+            }
+            ChartKind::Line => {
+                let points: Vec<String> = series
+                    .values
+                    .iter()
+                    .enumerate()
+                    .map(|(index, value)| {
+                        format!(
+                            "{:.1},{:.1}",
+                            frame.px(x_of(series, index)),
+                            frame.py(*value)
+                        )
+                    })
+                    .collect();
+                let _ = write!(
+                    svg,
+                    r#"<polyline points="{}" fill="none" stroke="{color}" stroke-width="2"/>"#,
+                    points.join(" ")
+                );
+                if series.values.len() <= 60 {
+                    for (index, value) in series.values.iter().enumerate() {
+                        point(
+                            &mut svg,
+                            frame.px(x_of(series, index)),
+                            frame.py(*value),
+                            3.0,
+                            color,
+                        );
+                    }
+                }
+            }
+            ChartKind::Scatter => {
+                for (index, value) in series.values.iter().enumerate() {
+                    point(
+                        &mut svg,
+                        frame.px(x_of(series, index)),
+                        frame.py(*value),
+                        4.0,
+                        color,
+                    );
+                }
+            }
+        }
+    }
+
+    // Legend, in a row along the bottom.
+    if args.series.len() > 1 || !args.series[0].name.trim().is_empty() {
+        let mut x = Frame::left();
+        let y = f64::from(HEIGHT) - 16.0;
+        for (series_index, series) in args.series.iter().enumerate() {
+            let color = PALETTE[series_index % PALETTE.len()];
+            let _ = write!(
+                svg,
+                r##"<rect x="{x:.1}" y="{:.1}" width="12" height="12" fill="{color}"/><text x="{:.1}" y="{y:.1}" font-size="12" fill="#333">{}</text>"##,
+                y - 10.0,
+                x + 16.0,
+                escape(&series.name)
+            );
+            // Rough width estimate; fonts differ between viewers anyway.
+            x += 16.0 + series.name.chars().count() as f64 * 7.0 + 20.0;
+        }
+    }
+
+    svg.push_str("</svg>");
+    Ok(svg)
+}
+
+fn x_tick(svg: &mut String, x: f64, label: &str) {
+    let _ = write!(
+        svg,
+        r##"<text x="{x:.1}" y="{:.1}" font-size="11" text-anchor="middle" fill="#555">{}</text>"##,
+        Frame::bottom() + 18.0,
+        escape(label)
+    );
+}
+
+fn point(svg: &mut String, x: f64, y: f64, radius: f64, color: &str) {
+    let _ = write!(
+        svg,
+        r#"<circle cx="{x:.1}" cy="{y:.1}" r="{radius}" fill="{color}"/>"#
+    );
+}
+
+fn render_png(svg: &str) -> Result<Vec<u8>, ChartError> {
+    let options = resvg::usvg::Options {
+        fontdb: FONTS.clone(),
+        ..Default::default()
+    };
+    let tree = resvg::usvg::Tree::from_str(svg, &options)
+        .map_err(|error| ChartError(format!("failed to parse chart svg: {error}")))?;
+    let mut pixmap = resvg::tiny_skia::Pixmap::new(WIDTH, HEIGHT)
+        .ok_or_else(|| ChartError("failed to allocate image".into()))?;
+    resvg::render(
+        &tree,
+        resvg::tiny_skia::Transform::default(),
+        &mut pixmap.as_mut(),
+    );
+    pixmap
+        .encode_png()
+        .map_err(|error| ChartError(format!("failed to encode png: {error}")))
+}
+
+/// Min and max of `values`, widened when they coincide so the axis has a span.
+fn bounds(values: impl Iterator<Item = f64>) -> (f64, f64) {
+    let (low, high) = values.fold((f64::INFINITY, f64::NEG_INFINITY), |(low, high), value| {
+        (low.min(value), high.max(value))
+    });
+    if !low.is_finite() {
+        return (0.0, 1.0);
+    }
+    if low == high {
+        let pad = if low == 0.0 { 1.0 } else { low.abs() * 0.1 };
+        return (low - pad, high + pad);
+    }
+    (low, high)
+}
+
+/// Round tick values covering `low..=high`, roughly `target` of them.
+fn nice_ticks(low: f64, high: f64, target: usize) -> Vec<f64> {
+    let span = high - low;
+    if span <= 0.0 || !span.is_finite() {
+        return vec![low, high];
+    }
+    let raw_step = span / target.max(1) as f64;
+    let magnitude = 10f64.powf(raw_step.log10().floor());
+    let step = [1.0, 2.0, 2.5, 5.0, 10.0]
+        .iter()
+        .map(|factor| factor * magnitude)
+        .find(|step| *step >= raw_step)
+        .unwrap_or(10.0 * magnitude);
+
+    let start = (low / step).floor() as i64;
+    let end = (high / step).ceil() as i64;
+    (start..=end).map(|index| index as f64 * step).collect()
+}
+
+fn format_number(value: f64) -> String {
+    let abs = value.abs();
+    if abs >= 1e6 || (abs > 0.0 && abs < 1e-3) {
+        return format!("{value:.2e}");
+    }
+    let text = format!("{value:.3}");
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" {
+        "0".to_string()
+    } else {
+        text.to_string()
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Lowercase ASCII file stem, words joined with `-`.
+fn slugify(text: &str) -> String {
+    let mut slug = String::new();
+    for character in text.chars() {
+        if character.is_ascii_alphanumeric() {
+            slug.push(character.to_ascii_lowercase());
+        } else if !slug.ends_with('-') && !slug.is_empty() {
+            slug.push('-');
+        }
+        if slug.len() >= 60 {
+            break;
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(kind: ChartKind, series: Vec<ChartSeries>) -> ChartArgs {
+        ChartArgs {
+            kind,
+            series,
+            labels: vec!["Jan".into(), "Feb".into(), "Mar".into()],
+            title: Some("Revenue <Q1>".into()),
+            x_label: None,
+            y_label: Some("USD".into()),
+            format: ChartFormat::Svg,
+            filename: None,
+        }
+    }
+
+    fn series(name: &str, values: Vec<f64>) -> ChartSeries {
+        ChartSeries {
+            name: name.into(),
+            values,
+            x: None,
+        }
+    }
+
+    #[test]
+    fn renders_bars_and_rejects_bad_data() {
+        let svg = render_svg(&args(
+            ChartKind::Bar,
+            vec![
+                series("2024", vec![10.0, 20.0, 15.0]),
+                series("2025", vec![12.0, 25.0, 18.0]),
+            ],
+        ))
+        .unwrap();
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("Revenue &lt;Q1&gt;"));
+        assert!(svg.contains(">Feb</text>"));
+        assert_eq!(svg.matches(r##"fill="#4e79a7"/>"##).count(), 4); // 3 bars + legend
+
+        assert!(render_svg(&args(ChartKind::Line, vec![])).is_err());
+        assert!(render_svg(&args(ChartKind::Line, vec![series("a", vec![f64::NAN])])).is_err());
+
+        assert_eq!(
+            nice_ticks(0.0, 25.0, 6),
+            vec![0.0, 5.0, 10.0, 15.0, 20.0, 25.0]
+        );
+        assert_eq!(format_number(2.50), "2.5");
+        assert_eq!(slugify("Revenue: Q1 / 2025"), "revenue-q1-2025");
+    }
+}
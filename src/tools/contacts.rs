@@ -0,0 +1,337 @@
+//! Contacts tool: look up, record, and resolve the people the agent can reach.
+
+use crate::config::HumanDef;
+use crate::contacts::{
+    Contact, ContactHandle, ContactStore, ContactUpdate, NewContact, delivery_targets,
+};
+use crate::messaging::MessagingManager;
+use arc_swap::ArcSwap;
+use rig::completion::ToolDefinition;
+use rig::tool::Tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Tool for the agent's contact book.
+#[derive(Clone)]
+pub struct ContactsTool {
+    store: ContactStore,
+    humans: Arc<ArcSwap<Vec<HumanDef>>>,
+    messaging_manager: Option<Arc<MessagingManager>>,
+}
+
+impl std::fmt::Debug for ContactsTool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ContactsTool").finish_non_exhaustive()
+    }
+}
+
+impl ContactsTool {
+    pub fn new(
+        store: ContactStore,
+        humans: Arc<ArcSwap<Vec<HumanDef>>>,
+        messaging_manager: Option<Arc<MessagingManager>>,
+    ) -> Self {
+        Self {
+            store,
+            humans,
+            messaging_manager,
+        }
+    }
+}
+
+/// Error type for contacts tool.
+#[derive(Debug, thiserror::Error)]
+#[error("contacts failed: {0}")]
+pub struct ContactsError(String);
+
+/// Arguments for contacts.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ContactsArgs {
+    /// One of `search`, `resolve`, `get`, `create`, `update`, `link`, `delete`.
+    pub action: String,
+    /// Search text (`search`) or the name to resolve (`resolve`).
+    #[serde(default)]
+    pub query: Option<String>,
+    /// Contact ID (`get`, `update`, `link`, `delete`).
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub aliases: Option<Vec<String>>,
+    #[serde(default)]
+    pub emails: Option<Vec<String>>,
+    #[serde(default)]
+    pub notes: Option<String>,
+    /// ID of an org-level human to link (`create`, `update`).
+    #[serde(default)]
+    pub human_id: Option<String>,
+    /// Platform identities. `link` adds them; `create` and `update` set them.
+    #[serde(default)]
+    pub handles: Option<Vec<ContactHandle>>,
+}
+
+/// Where a resolved contact can be messaged.
+#[derive(Debug, Serialize)]
+pub struct ContactTarget {
+    /// `adapter:target`, usable as a `send_message_to_another_channel` target
+    /// by contact name.
+    pub target: String,
+    /// Whether the adapter is running, so a message can go out now.
+    pub available: bool,
+}
+
+/// Output for contacts.
+#[derive(Debug, Serialize)]
+pub struct ContactsOutput {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contacts: Option<Vec<Contact>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact: Option<Contact>,
+    /// Delivery targets for `resolve`, preferred first.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub targets: Option<Vec<ContactTarget>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl ContactsOutput {
+    fn contact(contact: Contact) -> Self {
+        Self {
+            contacts: None,
+            contact: Some(contact),
+            targets: None,
+            message: None,
+        }
+    }
+
+    fn contacts(contacts: Vec<Contact>) -> Self {
+        Self {
+            contacts: Some(contacts),
+            contact: None,
+            targets: None,
+            message: None,
+        }
+    }
+}
+
+impl Tool for ContactsTool {
+    const NAME: &'static str = "contacts";
+
+    type Error = ContactsError;
+    type Args = ContactsArgs;
+    type Output = ContactsOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: crate::prompts::text::get("tools/contacts").to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "action": {
+                        "type": "string",
+                        "enum": ["search", "resolve", "get", "create", "update", "link", "delete"],
+                        "description": "What to do. `resolve` turns a name into the contact and where they can be messaged."
+                    },
+                    "query": {
+                        "type": "string",
+                        "description": "Search text for `search`, or the person's name for `resolve`."
+                    },
+                    "id": {
+                        "type": "string",
+                        "description": "Contact ID for get, update, link, and delete."
+                    },
+                    "name": {
+                        "type": "string",
+                        "description": "Full name (`create`, `update`)."
+                    },
+                    "aliases": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Nicknames and other names the person goes by."
+                    },
+                    "emails": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Email addresses, preferred first."
+                    },
+                    "notes": {
+                        "type": "string",
+                        "description": "Free-form notes. An empty string clears them on `update`."
+                    },
+                    "human_id": {
+                        "type": "string",
+                        "description": "ID of an org-level human this contact is, so their configured platform IDs are used too."
+                    },
+                    "handles": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "platform": {
+                                    "type": "string",
+                                    "description": "discord, slack, telegram, signal, or email"
+                                },
+                                "handle": {
+                                    "type": "string",
+                                    "description": "Platform user ID, phone number, or address"
+                                }
+                            },
+                            "required": ["platform", "handle"]
+                        },
+                        "description": "Platform identities, preferred first. `link` adds these to the contact; `create` and `update` replace the list."
+                    }
+                },
+                "required": ["action"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let error = |error: crate::Error| ContactsError(error.to_string());
+
+        match args.action.as_str() {
+            "search" => {
+                let contacts = self
+                    .store
+                    .list(args.query.as_deref(), 20)
+                    .await
+                    .map_err(error)?;
+                Ok(ContactsOutput::contacts(contacts))
+            }
+            "resolve" => {
+                let name = required(args.query.or(args.name), "query")?;
+                self.resolve(&name).await
+            }
+            "get" => {
+                let id = required(args.id, "id")?;
+                let contact = self.store.get(&id).await.map_err(error)?;
+                contact
+                    .map(ContactsOutput::contact)
+                    .ok_or_else(|| ContactsError(format!("no contact with id '{id}'")))
+            }
+            "create" => {
+                let contact = self
+                    .store
+                    .create(NewContact {
+                        name: required(args.name, "name")?,
+                        aliases: args.aliases.unwrap_or_default(),
+                        emails: args.emails.unwrap_or_default(),
+                        notes: args.notes,
+                        human_id: args.human_id,
+                        handles: args.handles.unwrap_or_default(),
+                    })
+                    .await
+                    .map_err(error)?;
+                Ok(ContactsOutput::contact(contact))
+            }
+            "update" | "link" => {
+                let id = required(args.id, "id")?;
+                let handles = if args.action == "link" {
+                    let new_handles = args
+                        .handles
+                        .filter(|handles| !handles.is_empty())
+                        .ok_or_else(|| ContactsError("`handles` is required for link".into()))?;
+                    let current = self
+                        .store
+                        .get(&id)
+                        .await
+                        .map_err(error)?
+                        .ok_or_else(|| ContactsError(format!("no contact with id '{id}'")))?;
+                    let mut handles = current.handles;
+                    handles.extend(new_handles);
+                    Some(handles)
+                } else {
+                    args.handles
+                };
+                let update = ContactUpdate {
+                    name: args.name,
+                    aliases: args.aliases,
+                    emails: args.emails,
+                    notes: args.notes,
+                    human_id: args.human_id,
+                    handles,
+                };
+                let contact = self.store.update(&id, update).await.map_err(error)?;
+                contact
+                    .map(ContactsOutput::contact)
+                    .ok_or_else(|| ContactsError(format!("no contact with id '{id}'")))
+            }
+            "delete" => {
+                let id = required(args.id, "id")?;
+                if !self.store.delete(&id).await.map_err(error)? {
+                    return Err(ContactsError(format!("no contact with id '{id}'")));
+                }
+                Ok(ContactsOutput {
+                    contacts: None,
+                    contact: None,
+                    targets: None,
+                    message: Some(format!("Deleted contact {id}.")),
+                })
+            }
+            other => Err(ContactsError(format!(
+                "unknown action '{other}', expected search, resolve, get, create, update, link, or delete"
+            ))),
+        }
+    }
+}
+
+impl ContactsTool {
+    async fn resolve(&self, name: &str) -> Result<ContactsOutput, ContactsError> {
+        let mut matches = self
+            .store
+            .resolve(name)
+            .await
+            .map_err(|error| ContactsError(error.to_string()))?;
+        if matches.is_empty() {
+            return Err(ContactsError(format!(
+                "no contact named '{name}'. Ask the user how to reach them and save a contact."
+            )));
+        }
+        if matches.len() > 1 {
+            return Ok(ContactsOutput {
+                message: Some(format!(
+                    "'{name}' matches {} contacts; ask the user which one they mean.",
+                    matches.len()
+                )),
+                ..ContactsOutput::contacts(matches)
+            });
+        }
+
+        let contact = matches.remove(0);
+        let humans = self.humans.load();
+        let mut targets = Vec::new();
+        for target in delivery_targets(&contact, &humans) {
+            let available = match &self.messaging_manager {
+                Some(manager) => manager.has_adapter(&target.adapter).await,
+                None => false,
+            };
+            targets.push(ContactTarget {
+                target: target.to_string(),
+                available,
+            });
+        }
+        let message = (!targets.iter().any(|target| target.available)).then(|| {
+            format!(
+                "{} has no handle on a connected platform, so they can't be messaged right now.",
+                contact.name
+            )
+        });
+
+        Ok(ContactsOutput {
+            contacts: None,
+            contact: Some(contact),
+            targets: Some(targets),
+            message,
+        })
+    }
+}
+
+fn required(value: Option<String>, field: &str) -> Result<String, ContactsError> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .ok_or_else(|| ContactsError(format!("`{field}` is required for this action")))
+}
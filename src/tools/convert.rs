@@ -0,0 +1,129 @@
+//! Convert tool: exact unit and currency conversion.
+
+use crate::calc::currency::{Currency, is_currency_code};
+use crate::calc::{parse_number, units};
+use crate::config::RuntimeConfig;
+use rig::completion::ToolDefinition;
+use rig::tool::Tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Tool for converting between units and currencies.
+#[derive(Debug, Clone)]
+pub struct ConvertTool {
+    runtime_config: Arc<RuntimeConfig>,
+}
+
+impl ConvertTool {
+    pub fn new(runtime_config: Arc<RuntimeConfig>) -> Self {
+        Self { runtime_config }
+    }
+}
+
+/// Error type for convert tool.
+#[derive(Debug, thiserror::Error)]
+#[error("convert failed: {0}")]
+pub struct ConvertError(String);
+
+/// Arguments for convert.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ConvertArgs {
+    /// Amount to convert. A string keeps every digit; a JSON number works too.
+    pub value: serde_json::Value,
+    /// Source unit (`mi`, `lb`, `°F`, `GiB`) or currency code (`USD`).
+    pub from: String,
+    /// Target unit or currency code.
+    pub to: String,
+}
+
+/// Output for convert.
+#[derive(Debug, Serialize)]
+pub struct ConvertOutput {
+    pub value: String,
+    pub from: String,
+    pub to: String,
+    /// Exact result as a decimal string.
+    pub result: String,
+    /// Exchange rate applied, for currency conversions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rate: Option<String>,
+    /// Publication date of the exchange rate.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rates_date: Option<String>,
+}
+
+impl Tool for ConvertTool {
+    const NAME: &'static str = "convert";
+
+    type Error = ConvertError;
+    type Args = ConvertArgs;
+    type Output = ConvertOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: crate::prompts::text::get("tools/convert").to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "value": {
+                        "type": ["string", "number"],
+                        "description": "Amount to convert, preferably as a string to keep every digit"
+                    },
+                    "from": {
+                        "type": "string",
+                        "description": "Source unit (e.g. mi, kg, °F, GiB, km/h) or ISO currency code (e.g. USD)"
+                    },
+                    "to": {
+                        "type": "string",
+                        "description": "Target unit or ISO currency code"
+                    }
+                },
+                "required": ["value", "from", "to"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let text = match &args.value {
+            serde_json::Value::String(text) => text.clone(),
+            serde_json::Value::Number(number) => number.to_string(),
+            other => return Err(ConvertError(format!("'{other}' is not a number"))),
+        };
+        let value = parse_number(&text).map_err(|error| ConvertError(error.to_string()))?;
+
+        let mut output = ConvertOutput {
+            value: value.to_string(),
+            from: args.from.clone(),
+            to: args.to.clone(),
+            result: String::new(),
+            rate: None,
+            rates_date: None,
+        };
+
+        // Units win over currencies, so `min` and `cal` stay units.
+        let is_currency = !units::is_known(&args.from)
+            && !units::is_known(&args.to)
+            && is_currency_code(&args.from)
+            && is_currency_code(&args.to);
+        if !is_currency {
+            let result = units::convert(value, &args.from, &args.to)
+                .map_err(|error| ConvertError(error.to_string()))?;
+            output.result = result.to_string();
+            return Ok(output);
+        }
+
+        let config = self.runtime_config.calculator.load();
+        let currency = Currency::new(&config)
+            .ok_or_else(|| ConvertError("currency conversion is disabled for this agent".into()))?;
+        let conversion = currency
+            .convert(value, &args.from, &args.to)
+            .await
+            .map_err(|error| ConvertError(error.to_string()))?;
+        output.result = conversion.result.to_string();
+        output.rate = Some(conversion.rate.to_string());
+        output.rates_date = Some(conversion.date);
+        Ok(output)
+    }
+}
@@ -21,6 +21,7 @@ pub struct CronTool {
     store: Arc<CronStore>,
     scheduler: Arc<Scheduler>,
     default_delivery_target: Option<String>,
+    default_timezone: Option<String>,
 }
 
 impl CronTool {
@@ -29,9 +30,17 @@ impl CronTool {
             store,
             scheduler,
             default_delivery_target: None,
+            default_timezone: None,
         }
     }
 
+    /// Timezone for new jobs that don't name one, usually the one set for
+    /// the conversation or its user.
+    pub fn with_default_timezone(mut self, default_timezone: Option<String>) -> Self {
+        self.default_timezone = default_timezone;
+        self
+    }
+
     pub fn with_default_delivery_target(mut self, default_delivery_target: Option<String>) -> Self {
         self.default_delivery_target = default_delivery_target;
         self
@@ -78,6 +87,10 @@ pub struct CronArgs {
     /// Optional for "create": if true, run only once and disable after first execution attempt.
     #[serde(default)]
     pub run_once: Option<bool>,
+    /// Optional for "create": IANA timezone for `cron_expr` and active hours.
+    /// Defaults to the conversation's timezone, then the agent's.
+    #[serde(default)]
+    pub timezone: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -98,6 +111,8 @@ pub struct CronEntry {
     pub delivery_target: String,
     pub run_once: bool,
     pub active_hours: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
 }
 
 impl Tool for CronTool {
@@ -158,6 +173,10 @@ impl Tool for CronTool {
                     "run_once": {
                         "type": "boolean",
                         "description": "For 'create': if true, run this job once and auto-disable after the first execution attempt."
+                    },
+                    "timezone": {
+                        "type": "string",
+                        "description": "For 'create': IANA timezone the schedule and active hours are in (e.g. 'Europe/Berlin'). Defaults to the timezone set for this conversation or user."
                     }
                 },
                 "required": ["action"]
@@ -268,6 +287,20 @@ impl CronTool {
             _ => None,
         };
         let run_once = args.run_once.unwrap_or(false);
+        let timezone = args
+            .timezone
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(ToString::to_string)
+            .or_else(|| self.default_timezone.clone());
+        if let Some(timezone) = timezone.as_deref()
+            && timezone.parse::<chrono_tz::Tz>().is_err()
+        {
+            return Err(CronError(format!(
+                "'timezone' must be an IANA timezone like 'Europe/Berlin' (got '{timezone}')"
+            )));
+        }
 
         let config = CronConfig {
             id: id.clone(),
@@ -279,6 +312,7 @@ impl CronTool {
             enabled: true,
             run_once,
             timeout_secs: args.timeout_secs,
+            timezone: timezone.clone(),
         };
 
         // Persist to database
@@ -297,7 +331,7 @@ impl CronTool {
             .as_deref()
             .map(|expr| format!("on schedule `{expr}`"))
             .unwrap_or_else(|| format_interval(interval_secs));
-        let timezone = self.scheduler.cron_timezone_label();
+        let timezone = timezone.unwrap_or_else(|| self.scheduler.cron_timezone_label());
         let mut message = if run_once {
             format!("Cron job '{id}' created. First run {schedule_desc}; it then disables itself.")
         } else {
@@ -316,6 +350,12 @@ impl CronTool {
         } else {
             message.push_str(&format!(" Active-hours timezone: {timezone}."));
         }
+        if let Some(next_run) = cron_expr
+            .as_deref()
+            .and_then(|expr| next_run_preview(expr, &timezone))
+        {
+            message.push_str(&format!(" Next run: {next_run}."));
+        }
 
         tracing::info!(cron_id = %id, %interval_secs, %delivery_target, "cron job created via tool");
 
@@ -345,15 +385,16 @@ impl CronTool {
                 active_hours: config
                     .active_hours
                     .map(|(s, e)| format!("{s:02}:00-{e:02}:00")),
+                timezone: config.timezone,
             })
             .collect();
 
         let count = entries.len();
         let timezone = self.scheduler.cron_timezone_label();
         let timezone_note = if timezone == "system" {
-            "active hours use server local time".to_string()
+            "jobs without their own timezone use server local time".to_string()
         } else {
-            format!("active hours use {timezone}")
+            format!("jobs without their own timezone use {timezone}")
         };
         Ok(CronOutput {
             success: true,
@@ -385,6 +426,26 @@ impl CronTool {
     }
 }
 
+/// When a 5-field `cron_expr` next fires, in `timezone` (or server local
+/// time for `"system"`), for confirming a new job's schedule.
+fn next_run_preview(cron_expr: &str, timezone: &str) -> Option<String> {
+    let schedule = cron::Schedule::from_str(&format!("0 {cron_expr} *")).ok()?;
+    let now = chrono::Utc::now();
+    match timezone.parse::<chrono_tz::Tz>() {
+        Ok(tz) => {
+            let next = schedule.after(&now.with_timezone(&tz)).next()?;
+            Some(format!("{} ({timezone})", next.format("%Y-%m-%d %H:%M %Z")))
+        }
+        Err(_) => {
+            let next = schedule.after(&now.with_timezone(&chrono::Local)).next()?;
+            Some(format!(
+                "{} (server local time)",
+                next.format("%Y-%m-%d %H:%M")
+            ))
+        }
+    }
+}
+
 fn format_interval(secs: u64) -> String {
     if secs.is_multiple_of(86400) {
         let days = secs / 86400;
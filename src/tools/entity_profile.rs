@@ -0,0 +1,172 @@
+//! Entity profile tool for branches.
+//!
+//! Pulls everything known about a named person, project, or place into
+//! context in one call: the memories that mention it, what they link to in
+//! the graph, and where it came up in conversation recently.
+
+use crate::conversation::history::ConversationLogger;
+use crate::memory::MemorySearch;
+use crate::memory::entities::{self, EntityProfile, MIN_ENTITY_NAME_CHARS, MentionFilter};
+use crate::memory::scope::ScopeFilter;
+use rig::completion::ToolDefinition;
+use rig::tool::Tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+const MAX_MEMORIES: i64 = 25;
+const MAX_MENTIONS: i64 = 10;
+const MENTION_PREVIEW_CHARS: usize = 200;
+
+/// Tool for loading an entity profile.
+#[derive(Debug, Clone)]
+pub struct EntityProfileTool {
+    memory_search: Arc<MemorySearch>,
+    conversation_logger: ConversationLogger,
+    scope_filter: ScopeFilter,
+    mention_filter: MentionFilter,
+}
+
+impl EntityProfileTool {
+    /// Create a new entity profile tool.
+    pub fn new(memory_search: Arc<MemorySearch>, conversation_logger: ConversationLogger) -> Self {
+        Self {
+            memory_search,
+            conversation_logger,
+            scope_filter: ScopeFilter::All,
+            mention_filter: MentionFilter::All,
+        }
+    }
+
+    /// Only include memories the caller can recall.
+    pub fn with_scope_filter(mut self, scope_filter: ScopeFilter) -> Self {
+        self.scope_filter = scope_filter;
+        self
+    }
+
+    /// Only quote messages from channels the caller could look up.
+    pub fn with_mention_filter(mut self, mention_filter: MentionFilter) -> Self {
+        self.mention_filter = mention_filter;
+        self
+    }
+}
+
+/// Error type for entity profile tool.
+#[derive(Debug, thiserror::Error)]
+#[error("entity_profile failed: {0}")]
+pub struct EntityProfileError(String);
+
+/// Arguments for entity profile tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct EntityProfileArgs {
+    /// Name of the person, project, or place.
+    pub name: String,
+}
+
+/// Output from entity profile tool.
+#[derive(Debug, Serialize)]
+pub struct EntityProfileOutput {
+    pub name: String,
+    pub memory_count: usize,
+    pub mention_count: usize,
+    /// Formatted profile.
+    pub summary: String,
+}
+
+impl Tool for EntityProfileTool {
+    const NAME: &'static str = "entity_profile";
+
+    type Error = EntityProfileError;
+    type Args = EntityProfileArgs;
+    type Output = EntityProfileOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: crate::prompts::text::get("tools/entity_profile").to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "name": {
+                        "type": "string",
+                        "description": "Name of the person, project, or place, as it appears in conversation (e.g. 'Priya', 'billing migration')"
+                    }
+                },
+                "required": ["name"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> std::result::Result<Self::Output, Self::Error> {
+        let name = entities::normalize_name(&args.name).ok_or_else(|| {
+            EntityProfileError(format!(
+                "name must be at least {MIN_ENTITY_NAME_CHARS} characters"
+            ))
+        })?;
+
+        let profile = entities::build_profile(
+            self.memory_search.store(),
+            &self.conversation_logger,
+            name,
+            &self.scope_filter,
+            &self.mention_filter,
+            MAX_MEMORIES,
+            MAX_MENTIONS,
+        )
+        .await
+        .map_err(|e| EntityProfileError(format!("failed to build profile: {e}")))?;
+
+        Ok(EntityProfileOutput {
+            name: profile.name.clone(),
+            memory_count: profile.memories.len() + profile.related.len(),
+            mention_count: profile.mentions.len(),
+            summary: format_profile(&profile),
+        })
+    }
+}
+
+fn format_profile(profile: &EntityProfile) -> String {
+    if profile.memories.is_empty() && profile.mentions.is_empty() {
+        return format!("Nothing is known about \"{}\".", profile.name);
+    }
+
+    let mut output = format!("## {}\n\n", profile.name);
+    if !profile.memories.is_empty() {
+        output.push_str("### Memories\n\n");
+        for memory in &profile.memories {
+            output.push_str(&format!(
+                "- [{}] {} (id: {})\n",
+                memory.memory_type, memory.content, memory.id
+            ));
+        }
+        output.push('\n');
+    }
+    if !profile.related.is_empty() {
+        output.push_str("### Related\n\n");
+        for memory in &profile.related {
+            let preview = memory.content.lines().next().unwrap_or(&memory.content);
+            output.push_str(&format!(
+                "- [{}] {preview} (id: {})\n",
+                memory.memory_type, memory.id
+            ));
+        }
+        output.push('\n');
+    }
+    if !profile.mentions.is_empty() {
+        output.push_str("### Recent mentions\n\n");
+        for mention in &profile.mentions {
+            let speaker = mention.sender_name.as_deref().unwrap_or(&mention.role);
+            let preview: String = mention
+                .content
+                .chars()
+                .take(MENTION_PREVIEW_CHARS)
+                .collect();
+            output.push_str(&format!(
+                "- {} in {}, {speaker}: {preview}\n",
+                mention.created_at.format("%Y-%m-%d %H:%M UTC"),
+                mention.channel_id
+            ));
+        }
+    }
+    output
+}
@@ -157,8 +157,12 @@ impl Tool for FactoryUpdateIdentityTool {
         // Reload identity into runtime config so the agent picks up changes
         // immediately without requiring a restart.
         let identity = crate::identity::Identity::load(identity_dir).await;
-        if let Some(runtime_config) = self.state.runtime_configs.load().get(&agent_id) {
+        let runtime_config = self.state.runtime_configs.load().get(&agent_id).cloned();
+        if let Some(runtime_config) = runtime_config {
             runtime_config.identity.store(Arc::new(identity));
+            runtime_config
+                .record_workspace_change("factory", &format!("Update {}", files_updated.join(", ")))
+                .await;
         } else {
             tracing::warn!(
                 agent_id = %agent_id,
@@ -0,0 +1,141 @@
+//! FAQ proposal tool for branches.
+//!
+//! Once a conversation has resolved a question that's likely to come up
+//! again, a branch can propose it for the agent's curated Q&A store. The
+//! entry waits as a proposal until an operator approves it through the API;
+//! until then it's never matched.
+
+use crate::agent::faq::{self, EntryStatus, NewEntry};
+use crate::memory::EmbeddingModel;
+use rig::completion::ToolDefinition;
+use rig::tool::Tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+
+const MAX_QUESTION_CHARS: usize = 500;
+const MAX_ANSWER_CHARS: usize = 4_000;
+
+/// Tool for proposing Q&A entries.
+#[derive(Clone)]
+pub struct FaqProposeTool {
+    pool: SqlitePool,
+    embedding_model: Arc<EmbeddingModel>,
+    channel_id: String,
+    /// Memory scope of the proposing channel; proposals stay in it.
+    scope: Option<String>,
+}
+
+impl FaqProposeTool {
+    pub fn new(
+        pool: SqlitePool,
+        embedding_model: Arc<EmbeddingModel>,
+        channel_id: String,
+        scope: Option<String>,
+    ) -> Self {
+        Self {
+            pool,
+            embedding_model,
+            channel_id,
+            scope,
+        }
+    }
+}
+
+/// Error type for FAQ proposal tool.
+#[derive(Debug, thiserror::Error)]
+#[error("faq_propose failed: {0}")]
+pub struct FaqProposeError(String);
+
+/// Arguments for FAQ proposal tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct FaqProposeArgs {
+    /// The question as users tend to ask it.
+    pub question: String,
+    /// The answer that resolved it.
+    pub answer: String,
+}
+
+/// Output from FAQ proposal tool.
+#[derive(Debug, Serialize)]
+pub struct FaqProposeOutput {
+    pub entry_id: String,
+    pub message: String,
+}
+
+impl Tool for FaqProposeTool {
+    const NAME: &'static str = "faq_propose";
+
+    type Error = FaqProposeError;
+    type Args = FaqProposeArgs;
+    type Output = FaqProposeOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: crate::prompts::text::get("tools/faq_propose").to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "question": {
+                        "type": "string",
+                        "description": "The question in the general form users ask it, without names or details specific to this conversation"
+                    },
+                    "answer": {
+                        "type": "string",
+                        "description": "The complete answer, written to stand on its own"
+                    }
+                },
+                "required": ["question", "answer"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> std::result::Result<Self::Output, Self::Error> {
+        let question = args.question.trim().to_string();
+        let answer = args.answer.trim().to_string();
+        if question.is_empty() || answer.is_empty() {
+            return Err(FaqProposeError(
+                "question and answer must not be empty".into(),
+            ));
+        }
+        if question.chars().count() > MAX_QUESTION_CHARS
+            || answer.chars().count() > MAX_ANSWER_CHARS
+        {
+            return Err(FaqProposeError(format!(
+                "question is limited to {MAX_QUESTION_CHARS} characters and answer to {MAX_ANSWER_CHARS}"
+            )));
+        }
+
+        let created = faq::create_entries(
+            &self.pool,
+            &self.embedding_model,
+            vec![NewEntry {
+                question,
+                answer,
+                channels: Vec::new(),
+                scope: self.scope.clone(),
+            }],
+            EntryStatus::Proposed,
+            Some(&self.channel_id),
+        )
+        .await
+        .map_err(|e| FaqProposeError(format!("failed to save proposal: {e}")))?;
+        let entry = created
+            .into_iter()
+            .next()
+            .ok_or_else(|| FaqProposeError("proposal was not saved".into()))?;
+
+        tracing::info!(
+            entry_id = %entry.id,
+            channel_id = %self.channel_id,
+            "faq entry proposed"
+        );
+        Ok(FaqProposeOutput {
+            entry_id: entry.id,
+            message: "Proposed. An operator reviews it before it's used to answer questions."
+                .to_string(),
+        })
+    }
+}
@@ -0,0 +1,97 @@
+//! Geocode tool: resolve a place name to coordinates.
+
+use crate::config::RuntimeConfig;
+use crate::weather::{MAX_GEOCODE_RESULTS, Place, Weather};
+use rig::completion::ToolDefinition;
+use rig::tool::Tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Tool for looking up places with the agent's configured geocoder.
+#[derive(Debug, Clone)]
+pub struct GeocodeTool {
+    runtime_config: Arc<RuntimeConfig>,
+}
+
+impl GeocodeTool {
+    pub fn new(runtime_config: Arc<RuntimeConfig>) -> Self {
+        Self { runtime_config }
+    }
+}
+
+/// Error type for geocode tool.
+#[derive(Debug, thiserror::Error)]
+#[error("geocode failed: {0}")]
+pub struct GeocodeError(String);
+
+/// Arguments for geocode.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct GeocodeArgs {
+    /// Place name, address, or landmark.
+    pub query: String,
+    /// Maximum matches (1-10, default 5).
+    #[serde(default = "default_limit")]
+    pub limit: usize,
+}
+
+fn default_limit() -> usize {
+    5
+}
+
+/// Output for geocode.
+#[derive(Debug, Serialize)]
+pub struct GeocodeOutput {
+    pub query: String,
+    /// Matches, most relevant first.
+    pub places: Vec<Place>,
+}
+
+impl Tool for GeocodeTool {
+    const NAME: &'static str = "geocode";
+
+    type Error = GeocodeError;
+    type Args = GeocodeArgs;
+    type Output = GeocodeOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: crate::prompts::text::get("tools/geocode").to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "query": {
+                        "type": "string",
+                        "description": "Place name, address, or landmark"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "maximum": MAX_GEOCODE_RESULTS,
+                        "default": 5,
+                        "description": "Maximum number of matches"
+                    }
+                },
+                "required": ["query"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let config = self.runtime_config.weather.load();
+        if !config.enabled {
+            return Err(GeocodeError("weather is disabled for this agent".into()));
+        }
+
+        let places = Weather::new(&config)
+            .geocode(&args.query, args.limit)
+            .await
+            .map_err(|error| GeocodeError(error.to_string()))?;
+
+        Ok(GeocodeOutput {
+            query: args.query,
+            places,
+        })
+    }
+}
@@ -131,6 +131,12 @@ impl Tool for InstallSkillTool {
         let instance_skills_dir = target_config.instance_dir.join("skills");
         let skills = SkillSet::load(&instance_skills_dir, &target_dir).await;
         target_config.reload_skills(skills);
+        target_config
+            .record_workspace_change(
+                "agent",
+                &format!("Install skill(s) {} from {source}", installed.join(", ")),
+            )
+            .await;
 
         let agent_label = args.agent_id.as_deref().unwrap_or("current agent");
         let names = installed.join(", ");
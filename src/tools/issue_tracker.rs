@@ -0,0 +1,253 @@
+//! Issue tracker tool: search and read issues, and request changes that a
+//! person approves before they run.
+
+use crate::approvals::ApprovalStore;
+use crate::config::RuntimeConfig;
+use crate::issues::{Issue, IssueMutation, IssueSummary, IssueTrackers};
+use rig::completion::ToolDefinition;
+use rig::tool::Tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Tool for working with the agent's configured Jira and Linear trackers.
+#[derive(Debug, Clone)]
+pub struct IssueTrackerTool {
+    runtime_config: Arc<RuntimeConfig>,
+    approvals: ApprovalStore,
+    channel_id: Option<String>,
+    requested_by: String,
+}
+
+impl IssueTrackerTool {
+    pub fn new(
+        runtime_config: Arc<RuntimeConfig>,
+        approvals: ApprovalStore,
+        channel_id: Option<String>,
+        requested_by: impl Into<String>,
+    ) -> Self {
+        Self {
+            runtime_config,
+            approvals,
+            channel_id,
+            requested_by: requested_by.into(),
+        }
+    }
+}
+
+/// Error type for issue_tracker tool.
+#[derive(Debug, thiserror::Error)]
+#[error("issue_tracker failed: {0}")]
+pub struct IssueTrackerError(String);
+
+/// Arguments for issue_tracker.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct IssueTrackerArgs {
+    /// One of `search`, `get`, `create`, `comment`, `transition`.
+    pub action: String,
+    /// Tracker name. Optional when only one is configured.
+    #[serde(default)]
+    pub tracker: Option<String>,
+    /// Search text (`search`).
+    #[serde(default)]
+    pub query: Option<String>,
+    /// Project or team key (`search` filter, required for `create`).
+    #[serde(default)]
+    pub project: Option<String>,
+    /// Issue key such as `OPS-42` (`get`, `comment`, `transition`).
+    #[serde(default)]
+    pub key: Option<String>,
+    /// Issue title (`create`).
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Issue description (`create`) or comment text (`comment`).
+    #[serde(default)]
+    pub body: Option<String>,
+    /// Target status name (`transition`).
+    #[serde(default)]
+    pub status: Option<String>,
+    /// Maximum search results (1-50, default 10).
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+/// Output for issue_tracker.
+#[derive(Debug, Serialize)]
+pub struct IssueTrackerOutput {
+    pub tracker: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issues: Option<Vec<IssueSummary>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issue: Option<Issue>,
+    /// Set when a change was queued for approval instead of run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub approval_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl Tool for IssueTrackerTool {
+    const NAME: &'static str = "issue_tracker";
+
+    type Error = IssueTrackerError;
+    type Args = IssueTrackerArgs;
+    type Output = IssueTrackerOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        let trackers = self.runtime_config.issue_trackers.load();
+        let configured = trackers
+            .iter()
+            .map(|tracker| format!("{} ({})", tracker.name, tracker.projects.join(", ")))
+            .collect::<Vec<_>>()
+            .join("; ");
+
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: format!(
+                "{}\n\nConfigured trackers: {configured}",
+                crate::prompts::text::get("tools/issue_tracker")
+            ),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "action": {
+                        "type": "string",
+                        "enum": ["search", "get", "create", "comment", "transition"],
+                        "description": "What to do. create, comment, and transition are queued for human approval."
+                    },
+                    "tracker": {
+                        "type": "string",
+                        "description": "Tracker name. Optional when only one is configured."
+                    },
+                    "query": {
+                        "type": "string",
+                        "description": "Search text for `search`. Omit to list recently updated issues."
+                    },
+                    "project": {
+                        "type": "string",
+                        "description": "Project or team key. Narrows `search`; required for `create`."
+                    },
+                    "key": {
+                        "type": "string",
+                        "description": "Issue key such as OPS-42, for get, comment, and transition."
+                    },
+                    "title": {
+                        "type": "string",
+                        "description": "Title for `create`."
+                    },
+                    "body": {
+                        "type": "string",
+                        "description": "Description for `create`, or comment text for `comment`."
+                    },
+                    "status": {
+                        "type": "string",
+                        "description": "Target status name for `transition` (see available_transitions from `get`)."
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Maximum search results (1-50, default 10)."
+                    }
+                },
+                "required": ["action"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let trackers = IssueTrackers::new(&self.runtime_config.issue_trackers.load());
+        let tracker = trackers
+            .get(args.tracker.as_deref())
+            .map_err(|error| IssueTrackerError(error.to_string()))?;
+        let tracker_name = tracker.name.clone();
+
+        let output = |issues, issue| IssueTrackerOutput {
+            tracker: tracker_name.clone(),
+            issues,
+            issue,
+            approval_id: None,
+            message: None,
+        };
+
+        let mutation = match args.action.as_str() {
+            "search" => {
+                let issues = tracker
+                    .search(
+                        args.query.as_deref().unwrap_or_default(),
+                        args.project.as_deref(),
+                        args.limit.unwrap_or(10),
+                    )
+                    .await
+                    .map_err(|error| IssueTrackerError(error.to_string()))?;
+                return Ok(output(Some(issues), None));
+            }
+            "get" => {
+                let key = required(args.key, "key")?;
+                let issue = tracker
+                    .get(&key)
+                    .await
+                    .map_err(|error| IssueTrackerError(error.to_string()))?;
+                return Ok(output(None, Some(issue)));
+            }
+            "create" => IssueMutation::Create {
+                tracker: tracker_name.clone(),
+                project: required(args.project, "project")?.to_uppercase(),
+                title: required(args.title, "title")?,
+                description: args.body.filter(|body| !body.trim().is_empty()),
+            },
+            "comment" => IssueMutation::Comment {
+                tracker: tracker_name.clone(),
+                key: required(args.key, "key")?,
+                body: required(args.body, "body")?,
+            },
+            "transition" => IssueMutation::Transition {
+                tracker: tracker_name.clone(),
+                key: required(args.key, "key")?,
+                status: required(args.status, "status")?,
+            },
+            other => {
+                return Err(IssueTrackerError(format!(
+                    "unknown action '{other}', expected search, get, create, comment, or transition"
+                )));
+            }
+        };
+
+        trackers
+            .validate(&mutation)
+            .map_err(|error| IssueTrackerError(error.to_string()))?;
+        let payload = serde_json::to_value(&mutation)
+            .map_err(|error| IssueTrackerError(error.to_string()))?;
+        let approval = self
+            .approvals
+            .request(
+                Self::NAME,
+                &mutation.summary(),
+                &payload,
+                self.channel_id.as_deref(),
+                &self.requested_by,
+            )
+            .await
+            .map_err(|error| IssueTrackerError(error.to_string()))?;
+
+        tracing::info!(
+            approval_id = %approval.id,
+            summary = %approval.summary,
+            "issue tracker change queued for approval"
+        );
+
+        Ok(IssueTrackerOutput {
+            approval_id: Some(approval.id),
+            message: Some(format!(
+                "Queued for approval: {}. It has not been applied yet; tell the user it needs approving.",
+                approval.summary
+            )),
+            ..output(None, None)
+        })
+    }
+}
+
+fn required(value: Option<String>, field: &str) -> Result<String, IssueTrackerError> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .ok_or_else(|| IssueTrackerError(format!("`{field}` is required for this action")))
+}
@@ -0,0 +1,209 @@
+//! Locale tool: read and record the timezone and location of the current
+//! channel or one of its users.
+
+use crate::config::RuntimeConfig;
+use crate::settings::Locale;
+use rig::completion::ToolDefinition;
+use rig::tool::Tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Tool for per-channel and per-user timezones.
+#[derive(Clone)]
+pub struct LocaleTool {
+    runtime_config: Arc<RuntimeConfig>,
+    channel_id: String,
+}
+
+impl std::fmt::Debug for LocaleTool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LocaleTool")
+            .field("channel_id", &self.channel_id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl LocaleTool {
+    pub fn new(runtime_config: Arc<RuntimeConfig>, channel_id: impl Into<String>) -> Self {
+        Self {
+            runtime_config,
+            channel_id: channel_id.into(),
+        }
+    }
+
+    /// The platform users of this channel are on, from its `adapter:...` ID.
+    fn platform(&self) -> &str {
+        self.channel_id.split(':').next().unwrap_or_default()
+    }
+}
+
+/// Error type for locale tool.
+#[derive(Debug, thiserror::Error)]
+#[error("locale failed: {0}")]
+pub struct LocaleError(String);
+
+/// Arguments for locale.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct LocaleArgs {
+    /// `get` or `set`.
+    pub action: String,
+    /// `channel` (default) or `user`.
+    #[serde(default)]
+    pub scope: Option<String>,
+    /// The user's platform ID, for the `user` scope.
+    #[serde(default)]
+    pub user_id: Option<String>,
+    /// IANA timezone name (`set`).
+    #[serde(default)]
+    pub timezone: Option<String>,
+    /// Place name (`set`).
+    #[serde(default)]
+    pub location: Option<String>,
+    /// Must be true for `set`: the user confirmed this is right.
+    #[serde(default)]
+    pub confirmed: bool,
+}
+
+/// Output for locale.
+#[derive(Debug, Serialize)]
+pub struct LocaleOutput {
+    pub scope: String,
+    pub locale: Locale,
+    pub message: String,
+}
+
+impl Tool for LocaleTool {
+    const NAME: &'static str = "locale";
+
+    type Error = LocaleError;
+    type Args = LocaleArgs;
+    type Output = LocaleOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: crate::prompts::text::get("tools/locale").to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "action": {
+                        "type": "string",
+                        "enum": ["get", "set"],
+                        "description": "Read the saved timezone and location, or save new ones."
+                    },
+                    "scope": {
+                        "type": "string",
+                        "enum": ["channel", "user"],
+                        "description": "The whole conversation (default) or one user in it."
+                    },
+                    "user_id": {
+                        "type": "string",
+                        "description": "For the user scope: the user's platform ID as shown in the conversation."
+                    },
+                    "timezone": {
+                        "type": "string",
+                        "description": "IANA timezone name, e.g. 'Europe/Berlin' or 'America/New_York'."
+                    },
+                    "location": {
+                        "type": "string",
+                        "description": "Where they are, e.g. 'Berlin, Germany'."
+                    },
+                    "confirmed": {
+                        "type": "boolean",
+                        "description": "Required true for set: the user confirmed the timezone or location when asked."
+                    }
+                },
+                "required": ["action"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let settings = self
+            .runtime_config
+            .settings
+            .load()
+            .as_ref()
+            .clone()
+            .ok_or_else(|| LocaleError("settings store is unavailable".into()))?;
+        let error = |error: crate::Error| LocaleError(error.to_string());
+
+        let user_id = match args.scope.as_deref().unwrap_or("channel") {
+            "channel" => None,
+            "user" => Some(
+                args.user_id
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|user_id| !user_id.is_empty())
+                    .ok_or_else(|| LocaleError("`user_id` is required for the user scope".into()))?
+                    .to_string(),
+            ),
+            other => {
+                return Err(LocaleError(format!(
+                    "unknown scope '{other}', expected channel or user"
+                )));
+            }
+        };
+        let scope = String::from(if user_id.is_some() { "user" } else { "channel" });
+
+        let current = match &user_id {
+            Some(user_id) => settings.user_locale(self.platform(), user_id),
+            None => settings.channel_locale(&self.channel_id),
+        }
+        .map_err(error)?
+        .unwrap_or_default();
+
+        match args.action.as_str() {
+            "get" => {
+                let message = if current.is_empty() {
+                    format!("No {scope} timezone or location is set.")
+                } else {
+                    format!("Saved {scope} locale.")
+                };
+                Ok(LocaleOutput {
+                    scope,
+                    locale: current,
+                    message,
+                })
+            }
+            "set" => {
+                if !args.confirmed {
+                    return Err(LocaleError(
+                        "ask the user to confirm the timezone or location first, then call again with `confirmed: true`".into(),
+                    ));
+                }
+                let trimmed = |value: Option<String>| {
+                    value
+                        .map(|value| value.trim().to_string())
+                        .filter(|value| !value.is_empty())
+                };
+                let locale = Locale {
+                    timezone: trimmed(args.timezone).or(current.timezone),
+                    location: trimmed(args.location).or(current.location),
+                };
+                if let Some(timezone) = locale.timezone.as_deref()
+                    && locale.tz().is_none()
+                {
+                    return Err(LocaleError(format!(
+                        "'{timezone}' is not an IANA timezone; use a name like 'Europe/Berlin'"
+                    )));
+                }
+                match &user_id {
+                    Some(user_id) => settings.set_user_locale(self.platform(), user_id, &locale),
+                    None => settings.set_channel_locale(&self.channel_id, &locale),
+                }
+                .map_err(error)?;
+                tracing::info!(channel_id = %self.channel_id, %scope, "locale saved via tool");
+                Ok(LocaleOutput {
+                    scope,
+                    locale,
+                    message: "Saved. Times in this conversation now use it.".into(),
+                })
+            }
+            other => Err(LocaleError(format!(
+                "unknown action '{other}', expected get or set"
+            ))),
+        }
+    }
+}
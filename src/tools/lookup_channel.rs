@@ -0,0 +1,358 @@
+//! Cross-channel context lookup for channels and branches.
+//!
+//! When a user points at a discussion elsewhere ("like we decided in
+//! #planning"), this pulls a few bounded excerpts from that channel's history
+//! instead of the whole transcript. Lookups respect the target channel's
+//! lookup policy and the agent's memory scopes, and each one is recorded as a
+//! system message in the requesting channel's timeline so it's visible where
+//! outside context came in.
+
+use crate::conversation::channels::{ChannelInfo, ChannelLookupPolicy, ChannelStore};
+use crate::conversation::history::{ConversationLogger, ConversationMessage};
+use crate::memory::scope::MemoryScopes;
+
+use rig::completion::ToolDefinition;
+use rig::tool::Tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Maximum excerpts returned by one lookup.
+const MAX_EXCERPTS: usize = 5;
+/// Messages of context on either side of a match.
+const CONTEXT_MESSAGES: i64 = 2;
+/// Recent messages returned when no query is given.
+const RECENT_MESSAGES: i64 = 10;
+/// Cap on a single message's length in an excerpt.
+const MAX_EXCERPT_MESSAGE_CHARS: usize = 500;
+
+/// Tool for pulling excerpts from another channel's history.
+#[derive(Debug, Clone)]
+pub struct LookupChannelTool {
+    conversation_logger: ConversationLogger,
+    channel_store: ChannelStore,
+    memory_scopes: MemoryScopes,
+    /// The channel the lookup is made from, if any. Lookups are recorded in
+    /// its timeline and limited to channels its memory scope can see.
+    current_channel_id: Option<String>,
+}
+
+impl LookupChannelTool {
+    pub fn new(
+        conversation_logger: ConversationLogger,
+        channel_store: ChannelStore,
+        memory_scopes: MemoryScopes,
+        current_channel_id: Option<String>,
+    ) -> Self {
+        Self {
+            conversation_logger,
+            channel_store,
+            memory_scopes,
+            current_channel_id,
+        }
+    }
+}
+
+/// Error type for lookup channel tool.
+#[derive(Debug, thiserror::Error)]
+#[error("lookup_channel failed: {0}")]
+pub struct LookupChannelError(String);
+
+/// Arguments for lookup channel tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct LookupChannelArgs {
+    /// Channel name (e.g. "planning"), partial name, or full channel ID.
+    pub channel: String,
+    /// What to look for. Omit to get the channel's most recent messages.
+    #[serde(default)]
+    pub query: Option<String>,
+    /// Maximum number of excerpts (default 3, max 5).
+    #[serde(default = "default_excerpt_limit")]
+    pub limit: usize,
+}
+
+fn default_excerpt_limit() -> usize {
+    3
+}
+
+/// A message in an excerpt.
+#[derive(Debug, Serialize)]
+pub struct ExcerptMessage {
+    pub role: String,
+    pub sender: Option<String>,
+    pub content: String,
+    pub timestamp: String,
+}
+
+/// Output from lookup channel tool.
+#[derive(Debug, Serialize)]
+pub struct LookupChannelOutput {
+    pub channel_id: String,
+    pub channel_name: Option<String>,
+    /// Runs of consecutive messages around each match, oldest first.
+    pub excerpts: Vec<Vec<ExcerptMessage>>,
+    /// Formatted excerpts for the agent.
+    pub summary: String,
+}
+
+impl Tool for LookupChannelTool {
+    const NAME: &'static str = "lookup_channel";
+
+    type Error = LookupChannelError;
+    type Args = LookupChannelArgs;
+    type Output = LookupChannelOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: crate::prompts::text::get("tools/lookup_channel").to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "channel": {
+                        "type": "string",
+                        "description": "Channel name (e.g. \"planning\") or full channel ID"
+                    },
+                    "query": {
+                        "type": "string",
+                        "description": "Words to look for in that channel's messages (e.g. \"database choice\"). Omit to get its most recent messages."
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "maximum": MAX_EXCERPTS,
+                        "default": 3,
+                        "description": "Maximum number of excerpts to return"
+                    }
+                },
+                "required": ["channel"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> std::result::Result<Self::Output, Self::Error> {
+        let channel = self
+            .channel_store
+            .find_by_name(&args.channel)
+            .await
+            .map_err(|e| LookupChannelError(format!("failed to search channels: {e}")))?
+            .ok_or_else(|| {
+                LookupChannelError(format!(
+                    "no channel matching \"{}\" was found",
+                    args.channel
+                ))
+            })?;
+
+        let policy = self
+            .channel_store
+            .lookup_policy(&channel.id)
+            .await
+            .map_err(|e| LookupChannelError(format!("failed to load lookup policy: {e}")))?;
+        check_access(
+            policy,
+            &self.memory_scopes,
+            self.current_channel_id.as_deref(),
+            &channel.id,
+        )
+        .map_err(LookupChannelError)?;
+
+        let query = args
+            .query
+            .as_deref()
+            .map(str::trim)
+            .filter(|query| !query.is_empty());
+        let excerpts = match query {
+            Some(query) => self.search_excerpts(&channel, query, args.limit).await?,
+            None => {
+                let recent = self
+                    .conversation_logger
+                    .load_channel_transcript(&channel.id, RECENT_MESSAGES, None, None, false)
+                    .await
+                    .map_err(|e| LookupChannelError(format!("failed to load messages: {e}")))?;
+                let recent: Vec<ConversationMessage> = recent
+                    .into_iter()
+                    .filter(|message| message.role != "system")
+                    .collect();
+                if recent.is_empty() {
+                    Vec::new()
+                } else {
+                    vec![recent]
+                }
+            }
+        };
+
+        let label = channel.display_name.as_deref().unwrap_or(&channel.id);
+        if let Some(current_channel_id) = &self.current_channel_id {
+            let looked_for = query
+                .map(|query| format!(" for \"{query}\""))
+                .unwrap_or_default();
+            self.conversation_logger.log_system_message(
+                current_channel_id,
+                &format!(
+                    "Looked up #{label}{looked_for}: {} excerpt(s)",
+                    excerpts.len()
+                ),
+            );
+        }
+        tracing::debug!(
+            from = ?self.current_channel_id,
+            to = %channel.id,
+            excerpts = excerpts.len(),
+            "cross-channel lookup"
+        );
+
+        let excerpts: Vec<Vec<ExcerptMessage>> = excerpts
+            .iter()
+            .map(|excerpt| excerpt.iter().map(to_excerpt_message).collect())
+            .collect();
+        let summary = format_excerpts(label, query, &excerpts);
+
+        Ok(LookupChannelOutput {
+            channel_id: channel.id,
+            channel_name: channel.display_name,
+            excerpts,
+            summary,
+        })
+    }
+}
+
+impl LookupChannelTool {
+    /// Matches with their surrounding messages, newest match first. Matches
+    /// that fall inside an earlier excerpt are folded into it.
+    async fn search_excerpts(
+        &self,
+        channel: &ChannelInfo,
+        query: &str,
+        limit: usize,
+    ) -> std::result::Result<Vec<Vec<ConversationMessage>>, LookupChannelError> {
+        let limit = limit.clamp(1, MAX_EXCERPTS);
+        let matches = self
+            .conversation_logger
+            .search_channel(&channel.id, query, (limit * 4) as i64)
+            .await
+            .map_err(|e| LookupChannelError(format!("failed to search messages: {e}")))?;
+
+        let mut excerpts: Vec<Vec<ConversationMessage>> = Vec::new();
+        for anchor in &matches {
+            if excerpts.len() >= limit {
+                break;
+            }
+            let already_shown = excerpts
+                .iter()
+                .flatten()
+                .any(|message| message.id == anchor.id);
+            if already_shown {
+                continue;
+            }
+            let excerpt = self
+                .conversation_logger
+                .load_context(anchor, CONTEXT_MESSAGES)
+                .await
+                .map_err(|e| LookupChannelError(format!("failed to load excerpt: {e}")))?;
+            excerpts.push(excerpt);
+        }
+        Ok(excerpts)
+    }
+}
+
+/// Whether `from` may look into `target`. Private channels are never
+/// visible, and scoped channels are only visible from channels whose
+/// memory scope could recall them. Processes not tied to a channel are
+/// unrestricted by scope.
+fn check_access(
+    policy: ChannelLookupPolicy,
+    memory_scopes: &MemoryScopes,
+    from: Option<&str>,
+    target: &str,
+) -> Result<(), String> {
+    if from == Some(target) {
+        return Err(
+            "that is the current channel; its history is already in the conversation".into(),
+        );
+    }
+    if policy == ChannelLookupPolicy::Private {
+        return Err("that channel is private and can't be looked up from elsewhere".into());
+    }
+    if let Some(from) = from
+        && !memory_scopes
+            .filter_for_channel(from)
+            .allows(memory_scopes.scope_for_channel(target))
+    {
+        return Err("that channel belongs to a memory scope this channel can't see".into());
+    }
+    Ok(())
+}
+
+fn to_excerpt_message(message: &ConversationMessage) -> ExcerptMessage {
+    ExcerptMessage {
+        role: message.role.clone(),
+        sender: message.sender_name.clone(),
+        content: message
+            .content
+            .chars()
+            .take(MAX_EXCERPT_MESSAGE_CHARS)
+            .collect(),
+        timestamp: message.created_at.to_rfc3339(),
+    }
+}
+
+fn format_excerpts(label: &str, query: Option<&str>, excerpts: &[Vec<ExcerptMessage>]) -> String {
+    if excerpts.is_empty() {
+        return match query {
+            Some(query) => format!("Nothing in #{label} mentions \"{query}\"."),
+            None => format!("#{label} has no messages."),
+        };
+    }
+
+    let mut output = format!("## Excerpts from #{label}\n\n");
+    for (index, excerpt) in excerpts.iter().enumerate() {
+        if index > 0 {
+            output.push_str("---\n\n");
+        }
+        for message in excerpt {
+            let sender = message.sender.as_deref().unwrap_or("assistant");
+            output.push_str(&format!(
+                "**{sender}** ({}, {}): {}\n\n",
+                message.role, message.timestamp, message.content
+            ));
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::scope::MemoryScope;
+
+    #[test]
+    fn private_channels_and_unshared_scopes_are_refused() {
+        let scopes = MemoryScopes::new(vec![
+            MemoryScope {
+                name: "personal".into(),
+                channels: vec!["discord:*".into()],
+                share_with: vec![],
+            },
+            MemoryScope {
+                name: "company".into(),
+                channels: vec!["slack:*".into()],
+                share_with: vec![],
+            },
+        ])
+        .unwrap();
+
+        let open = ChannelLookupPolicy::Open;
+        assert!(check_access(open, &scopes, Some("discord:1"), "discord:2").is_ok());
+        assert!(check_access(open, &scopes, Some("discord:1"), "slack:C1").is_err());
+        assert!(check_access(open, &scopes, Some("discord:1"), "discord:1").is_err());
+        assert!(check_access(open, &scopes, None, "slack:C1").is_ok());
+        assert!(
+            check_access(
+                ChannelLookupPolicy::Private,
+                &scopes,
+                Some("discord:1"),
+                "discord:2"
+            )
+            .is_err()
+        );
+    }
+}
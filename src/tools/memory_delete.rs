@@ -2,8 +2,11 @@
 //!
 //! Soft-deletes a memory by setting its `forgotten` flag. The memory stays in
 //! the database but is excluded from all search and recall operations.
+//! Memories outside the caller's read scope can't be deleted.
 
 use crate::memory::MemorySearch;
+use crate::memory::mutations::{self, MutationKind};
+use crate::memory::scope::MemoryAccess;
 use rig::completion::ToolDefinition;
 use rig::tool::Tool;
 use schemars::JsonSchema;
@@ -14,12 +17,22 @@ use std::sync::Arc;
 #[derive(Debug, Clone)]
 pub struct MemoryDeleteTool {
     memory_search: Arc<MemorySearch>,
+    access: MemoryAccess,
 }
 
 impl MemoryDeleteTool {
     /// Create a new memory delete tool.
     pub fn new(memory_search: Arc<MemorySearch>) -> Self {
-        Self { memory_search }
+        Self {
+            memory_search,
+            access: MemoryAccess::default(),
+        }
+    }
+
+    /// Only delete memories the caller can see.
+    pub fn with_access(mut self, access: MemoryAccess) -> Self {
+        self.access = access;
+        self
     }
 }
 
@@ -83,7 +96,10 @@ impl Tool for MemoryDeleteTool {
             .await
             .map_err(|e| MemoryDeleteError(format!("Failed to look up memory: {e}")))?;
 
-        let Some(memory) = memory else {
+        // A memory in a scope the caller can't read is reported the same as
+        // a missing one, so IDs from other scopes don't leak.
+        let Some(memory) = memory.filter(|memory| self.access.read.allows(memory.scope.as_deref()))
+        else {
             return Ok(MemoryDeleteOutput {
                 forgotten: false,
                 message: format!("No memory found with ID: {}", args.memory_id),
@@ -122,6 +138,19 @@ impl Tool for MemoryDeleteTool {
                 "memory forgotten"
             );
 
+            let mut snapshot = memory.clone();
+            snapshot.forgotten = true;
+            snapshot.forgotten_at = Some(chrono::Utc::now());
+            mutations::record_or_warn(
+                store,
+                MutationKind::Forget,
+                &memory.id,
+                Some(&snapshot),
+                mutations::ACTOR_AGENT,
+                args.reason.as_deref(),
+            )
+            .await;
+
             let preview = memory.content.lines().next().unwrap_or("(empty)");
             Ok(MemoryDeleteOutput {
                 forgotten: true,
@@ -147,3 +176,77 @@ fn truncate(s: &str, max: usize) -> &str {
         &s[..s.floor_char_boundary(max)]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::scope::ScopeFilter;
+    use crate::memory::{EmbeddingModel, EmbeddingTable, Memory, MemoryStore, MemoryType};
+
+    struct FixedProvider;
+
+    #[async_trait::async_trait]
+    impl crate::memory::embedding::EmbeddingProvider for FixedProvider {
+        fn name(&self) -> &'static str {
+            "test"
+        }
+
+        fn model(&self) -> &str {
+            "fixed"
+        }
+
+        fn dimensions(&self) -> usize {
+            2
+        }
+
+        async fn embed(&self, texts: Vec<String>) -> crate::error::Result<Vec<Vec<f32>>> {
+            Ok(texts.iter().map(|_| vec![1.0, 0.0]).collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn memories_outside_the_read_scope_are_not_deleted() {
+        let lance_dir = tempfile::tempdir().unwrap();
+        let connection = lancedb::connect(lance_dir.path().to_str().unwrap())
+            .execute()
+            .await
+            .unwrap();
+        let search = Arc::new(MemorySearch::new(
+            MemoryStore::connect_in_memory().await,
+            EmbeddingTable::open_or_create(&connection, 2)
+                .await
+                .unwrap(),
+            Arc::new(EmbeddingModel::from_provider(Arc::new(FixedProvider))),
+        ));
+        let mut hidden = Memory::new("the other team's plans", MemoryType::Fact);
+        hidden.scope = Some("company".into());
+        let mut visible = Memory::new("our plans", MemoryType::Fact);
+        visible.scope = Some("personal".into());
+        search.store().save(&hidden).await.unwrap();
+        search.store().save(&visible).await.unwrap();
+
+        let tool = MemoryDeleteTool::new(search.clone()).with_access(MemoryAccess {
+            read: ScopeFilter::Only(vec!["personal".into()]),
+            ..MemoryAccess::default()
+        });
+        let delete = |memory_id: &str| MemoryDeleteArgs {
+            memory_id: memory_id.to_string(),
+            reason: None,
+        };
+
+        let output = tool.call(delete(&hidden.id)).await.unwrap();
+        assert!(!output.forgotten);
+        assert!(output.message.starts_with("No memory found"));
+        assert!(
+            !search
+                .store()
+                .load(&hidden.id)
+                .await
+                .unwrap()
+                .unwrap()
+                .forgotten
+        );
+
+        assert!(tool.call(delete(&visible.id)).await.unwrap().forgotten);
+    }
+}
@@ -1,5 +1,8 @@
 //! Terminal completion tool for memory persistence branches.
 
+use crate::memory::MemorySearch;
+use crate::memory::embedding::batch::PendingEmbeddings;
+
 use rig::completion::ToolDefinition;
 use rig::tool::Tool;
 use schemars::JsonSchema;
@@ -17,9 +20,64 @@ pub enum MemoryPersistenceTerminalOutcome {
 pub struct MemoryPersistenceContractState {
     saved_memory_ids: Mutex<BTreeSet<String>>,
     terminal_outcome: Mutex<Option<MemoryPersistenceTerminalOutcome>>,
+    /// When set, memory_save queues embeddings here instead of computing them
+    /// per save, and they're computed in batches when the run completes.
+    pending_embeddings: Option<PendingEmbeddings>,
+    /// Backfill job whose uncommitted batch this run is, as (pool, job ID).
+    /// Each save is journaled there before the memory is written.
+    backfill_journal: Option<(sqlx::SqlitePool, String)>,
 }
 
 impl MemoryPersistenceContractState {
+    /// Contract state for a run that embeds its saved memories in batches of
+    /// at most `max_batch_size`.
+    pub fn with_batched_embeddings(max_batch_size: usize) -> Self {
+        Self {
+            pending_embeddings: Some(PendingEmbeddings::new(max_batch_size)),
+            ..Self::default()
+        }
+    }
+
+    /// Contract state for a backfill batch that journals each save against
+    /// `job_id`.
+    pub fn for_backfill(pool: sqlx::SqlitePool, job_id: impl Into<String>) -> Self {
+        Self {
+            backfill_journal: Some((pool, job_id.into())),
+            ..Self::default()
+        }
+    }
+
+    /// Journal a memory about to be saved, for runs that are backfill
+    /// batches. Must succeed before the memory is written.
+    pub async fn journal_save(&self, memory_id: &str) -> crate::error::Result<()> {
+        if let Some((pool, job_id)) = &self.backfill_journal {
+            crate::agent::backfill::journal_save(pool, job_id, memory_id).await?;
+        }
+        Ok(())
+    }
+
+    pub fn pending_embeddings(&self) -> Option<&PendingEmbeddings> {
+        self.pending_embeddings.as_ref()
+    }
+
+    /// Embed every memory saved without one so far. Memories that couldn't
+    /// be embedded are deleted and no longer count as saved; their IDs are
+    /// returned.
+    pub async fn flush_pending_embeddings(&self, memory_search: &MemorySearch) -> Vec<String> {
+        let Some(pending) = &self.pending_embeddings else {
+            return Vec::new();
+        };
+        let failed = pending.flush(memory_search).await;
+        if !failed.is_empty()
+            && let Ok(mut saved_memory_ids) = self.saved_memory_ids.lock()
+        {
+            for memory_id in &failed {
+                saved_memory_ids.remove(memory_id);
+            }
+        }
+        failed
+    }
+
     pub fn record_saved_memory_id(&self, memory_id: impl Into<String>) {
         if let Ok(mut saved_memory_ids) = self.saved_memory_ids.lock() {
             saved_memory_ids.insert(memory_id.into());
@@ -58,11 +116,22 @@ impl MemoryPersistenceContractState {
 #[derive(Debug, Clone)]
 pub struct MemoryPersistenceCompleteTool {
     state: Arc<MemoryPersistenceContractState>,
+    memory_search: Option<Arc<MemorySearch>>,
 }
 
 impl MemoryPersistenceCompleteTool {
     pub fn new(state: Arc<MemoryPersistenceContractState>) -> Self {
-        Self { state }
+        Self {
+            state,
+            memory_search: None,
+        }
+    }
+
+    /// Flush the run's pending embeddings into `memory_search` before
+    /// checking the outcome.
+    pub fn with_memory_search(mut self, memory_search: Arc<MemorySearch>) -> Self {
+        self.memory_search = Some(memory_search);
+        self
     }
 }
 
@@ -128,6 +197,15 @@ impl Tool for MemoryPersistenceCompleteTool {
     }
 
     async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        if let Some(memory_search) = &self.memory_search {
+            let failed = self.state.flush_pending_embeddings(memory_search).await;
+            if !failed.is_empty() {
+                return Err(MemoryPersistenceCompleteError(format!(
+                    "embedding failed for memories {failed:?}; they were discarded and must be saved again"
+                )));
+            }
+        }
+
         let outcome = args.outcome.trim();
         let recorded_ids = self.state.saved_memory_ids();
 
@@ -2,7 +2,10 @@
 
 use crate::error::Result;
 use crate::memory::MemorySearch;
-use crate::memory::search::{SearchConfig, SearchMode, SearchSort, curate_results};
+use crate::memory::feedback::{MemorySignal, record_signals};
+use crate::memory::scope::ScopeFilter;
+use crate::memory::search::{EdgeWeights, SearchConfig, SearchMode, SearchSort, curate_results};
+use crate::memory::todos::{TodoDetails, load_details};
 use crate::memory::types::Memory;
 
 use rig::completion::ToolDefinition;
@@ -16,12 +19,34 @@ use std::sync::Arc;
 #[derive(Debug, Clone)]
 pub struct MemoryRecallTool {
     memory_search: Arc<MemorySearch>,
+    scope_filter: ScopeFilter,
+    graph_depth: usize,
+    edge_weights: EdgeWeights,
 }
 
 impl MemoryRecallTool {
     /// Create a new memory recall tool.
     pub fn new(memory_search: Arc<MemorySearch>) -> Self {
-        Self { memory_search }
+        Self {
+            memory_search,
+            scope_filter: ScopeFilter::All,
+            graph_depth: SearchConfig::default().graph_depth,
+            edge_weights: EdgeWeights::default(),
+        }
+    }
+
+    /// Only recall memories visible under the given scope filter.
+    pub fn with_scope_filter(mut self, scope_filter: ScopeFilter) -> Self {
+        self.scope_filter = scope_filter;
+        self
+    }
+
+    /// Set how far hybrid recall follows memory associations and how much
+    /// each edge type counts.
+    pub fn with_graph(mut self, graph_depth: usize, edge_weights: EdgeWeights) -> Self {
+        self.graph_depth = graph_depth;
+        self.edge_weights = edge_weights;
+        self
     }
 }
 
@@ -130,6 +155,9 @@ pub struct MemoryOutput {
     pub created_at: String,
     /// The relevance score from the search.
     pub relevance_score: f32,
+    /// Status, priority, and due date for todo and goal memories.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub todo: Option<TodoDetails>,
 }
 
 impl Tool for MemoryRecallTool {
@@ -217,6 +245,9 @@ impl Tool for MemoryRecallTool {
             sort_by,
             max_results: args.max_results,
             max_results_per_source: args.max_results * 2,
+            scope_filter: self.scope_filter.clone(),
+            graph_depth: self.graph_depth,
+            edge_weights: self.edge_weights,
             ..Default::default()
         };
 
@@ -241,6 +272,18 @@ impl Tool for MemoryRecallTool {
                 );
             }
 
+            let todo = match load_details(store, &result.memory).await {
+                Ok(todo) => todo,
+                Err(error) => {
+                    tracing::warn!(
+                        memory_id = %result.memory.id,
+                        %error,
+                        "failed to load todo state"
+                    );
+                    None
+                }
+            };
+
             memories.push(MemoryOutput {
                 id: result.memory.id.clone(),
                 content: result.memory.content.clone(),
@@ -248,9 +291,15 @@ impl Tool for MemoryRecallTool {
                 importance: result.memory.importance,
                 created_at: result.memory.created_at.to_rfc3339(),
                 relevance_score: result.score,
+                todo,
             });
         }
 
+        let retrieved_ids: Vec<String> = memories.iter().map(|memory| memory.id.clone()).collect();
+        if let Err(error) = record_signals(store, &retrieved_ids, MemorySignal::Retrieved).await {
+            tracing::warn!(%error, "failed to record memory retrieval signals");
+        }
+
         let total_found = search_results.len();
         let summary = format_memories(&memories);
 
@@ -286,8 +335,19 @@ pub fn format_memories(memories: &[MemoryOutput]) -> String {
 
     for (i, memory) in memories.iter().enumerate() {
         let preview = memory.content.lines().next().unwrap_or(&memory.content);
+        let todo_state = memory
+            .todo
+            .as_ref()
+            .map(|todo| {
+                let due = todo
+                    .due_at
+                    .map(|due_at| format!(", due {}", due_at.format("%Y-%m-%d %H:%M UTC")))
+                    .unwrap_or_default();
+                format!(", {}, {} priority{due}", todo.status, todo.priority)
+            })
+            .unwrap_or_default();
         output.push_str(&format!(
-            "{}. [{}] (importance: {:.2}, relevance: {:.2})\n   {}\n\n",
+            "{}. [{}] (importance: {:.2}, relevance: {:.2}{todo_state})\n   {}\n\n",
             i + 1,
             memory.memory_type,
             memory.importance,
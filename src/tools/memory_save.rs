@@ -1,6 +1,7 @@
 //! Memory save tool for channels and branches.
 
 use crate::error::Result;
+use crate::memory::scope::MemoryAccess;
 use crate::memory::types::Association;
 use crate::memory::{Memory, MemorySearch, MemoryType};
 use crate::{AgentId, ProcessEvent};
@@ -12,7 +13,7 @@ use std::sync::Arc;
 
 /// Maximum allowed memory content length (bytes). Prevents oversized memories
 /// from bloating the database and embedding index.
-const MAX_MEMORY_CONTENT_BYTES: usize = 50_000;
+pub(crate) const MAX_MEMORY_CONTENT_BYTES: usize = 50_000;
 
 /// Tool for saving memories to the store.
 #[derive(Debug, Clone)]
@@ -20,6 +21,9 @@ pub struct MemorySaveTool {
     memory_search: Arc<MemorySearch>,
     event_context: Option<MemorySaveEventContext>,
     contract_state: Option<Arc<super::memory_persistence_complete::MemoryPersistenceContractState>>,
+    access: MemoryAccess,
+    /// Recorded as the creator in the memory's mutation history.
+    actor: &'static str,
 }
 
 #[derive(Debug, Clone)]
@@ -35,6 +39,44 @@ impl MemorySaveTool {
             memory_search,
             event_context: None,
             contract_state: None,
+            access: MemoryAccess::default(),
+            actor: crate::memory::mutations::ACTOR_AGENT,
+        }
+    }
+
+    /// Attribute saves to someone other than the agent, e.g. the API.
+    pub fn with_actor(mut self, actor: &'static str) -> Self {
+        self.actor = actor;
+        self
+    }
+
+    /// Tag saved memories with the caller's scope and only associate them
+    /// with memories the caller can see.
+    pub fn with_access(mut self, access: MemoryAccess) -> Self {
+        self.access = access;
+        self
+    }
+
+    /// The scope to tag a new memory with for the requested `scope` arg.
+    /// Global memories take the caller's configured scope, if any.
+    fn resolve_scope(
+        &self,
+        scope: Option<&str>,
+    ) -> std::result::Result<Option<String>, MemorySaveError> {
+        let context_scope = |context: &Option<String>, what: &str| {
+            context.clone().map(Some).ok_or_else(|| {
+                MemorySaveError(format!(
+                    "scope '{what}' needs a conversation; save it as global instead"
+                ))
+            })
+        };
+        match scope.unwrap_or(crate::memory::scope::GLOBAL_SCOPE) {
+            crate::memory::scope::GLOBAL_SCOPE => Ok(self.access.write_scope.clone()),
+            "channel" => context_scope(&self.access.channel_scope, "channel"),
+            "user" => context_scope(&self.access.user_scope, "user"),
+            other => Err(MemorySaveError(format!(
+                "unknown scope '{other}'. Valid scopes: global, channel, user"
+            ))),
         }
     }
 
@@ -58,6 +100,77 @@ impl MemorySaveTool {
         self.contract_state = Some(contract_state);
         self
     }
+
+    /// Generate and store the embedding for a just-saved memory. On failure,
+    /// compensate by deleting the SQLite row (and any associations already
+    /// written) so there is no orphan.
+    async fn embed_and_store(
+        &self,
+        memory_id: &str,
+        content: &str,
+    ) -> std::result::Result<(), MemorySaveError> {
+        let embedding = match self
+            .memory_search
+            .embedding_model_arc()
+            .embed_one(content)
+            .await
+        {
+            Ok(emb) => emb,
+            Err(embed_err) => {
+                self.discard(memory_id, "embedding generation").await;
+                return Err(MemorySaveError(format!(
+                    "Failed to generate embedding: {embed_err}"
+                )));
+            }
+        };
+
+        if let Err(embed_err) = self
+            .memory_search
+            .embedding_table()
+            .store(memory_id, content, &embedding)
+            .await
+        {
+            self.discard(memory_id, "embedding store").await;
+            return Err(MemorySaveError(format!(
+                "Failed to store embedding: {embed_err}"
+            )));
+        }
+
+        // Ensure the FTS index exists so full_text_search queries work.
+        // Safe to call repeatedly — no-ops if the index already exists.
+        if let Err(error) = self
+            .memory_search
+            .embedding_table()
+            .ensure_fts_index()
+            .await
+        {
+            tracing::warn!(%error, "failed to ensure FTS index after memory save");
+        }
+        Ok(())
+    }
+
+    /// Delete a memory and its associations after `stage` failed.
+    async fn discard(&self, memory_id: &str, stage: &str) {
+        if let Err(assoc_err) = self
+            .memory_search
+            .store()
+            .delete_associations_for_memory(memory_id)
+            .await
+        {
+            tracing::error!(
+                %memory_id,
+                error = %assoc_err,
+                "compensating association delete failed after {stage} error"
+            );
+        }
+        if let Err(del_err) = self.memory_search.store().delete(memory_id).await {
+            tracing::error!(
+                %memory_id,
+                %del_err,
+                "compensating delete failed after {stage} error"
+            );
+        }
+    }
 }
 
 /// Error type for memory save tool.
@@ -88,6 +201,21 @@ pub struct MemorySaveArgs {
     /// Optional associations to create with other memories.
     #[serde(default)]
     pub associations: Vec<AssociationInput>,
+    /// Priority of a todo or goal (critical, high, medium, low).
+    #[serde(default)]
+    pub priority: Option<String>,
+    /// Due date of a todo or goal, as RFC 3339 or `YYYY-MM-DD`.
+    #[serde(default)]
+    pub due_at: Option<String>,
+    /// Optional free-form labels.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Who may recall this memory: "global" (default), "channel", or "user".
+    #[serde(default)]
+    pub scope: Option<String>,
+    /// When the memory stops being true, as RFC 3339 or `YYYY-MM-DD`.
+    #[serde(default)]
+    pub expires_at: Option<String>,
 }
 
 fn default_memory_type() -> String {
@@ -99,7 +227,7 @@ fn default_memory_type() -> String {
 pub struct AssociationInput {
     /// The ID of the target memory to associate with.
     pub target_id: String,
-    /// The type of relation (related_to, updates, contradicts, caused_by, result_of, part_of).
+    /// The type of relation (related_to, updates, contradicts, caused_by, result_of, part_of, supports, about_person).
     #[serde(default = "default_relation_type")]
     pub relation_type: String,
     /// The weight of the association (0.0-1.0).
@@ -178,7 +306,7 @@ impl Tool for MemorySaveTool {
                                 },
                                 "relation_type": {
                                     "type": "string",
-                                    "enum": ["related_to", "updates", "contradicts", "caused_by", "result_of", "part_of"],
+                                    "enum": ["related_to", "updates", "contradicts", "caused_by", "result_of", "part_of", "supports", "about_person"],
                                     "description": "The type of relationship"
                                 },
                                 "weight": {
@@ -190,6 +318,29 @@ impl Tool for MemorySaveTool {
                             },
                             "required": ["target_id"]
                         }
+                    },
+                    "priority": {
+                        "type": "string",
+                        "enum": crate::tasks::TaskPriority::ALL.iter().map(|p| p.to_string()).collect::<Vec<_>>(),
+                        "description": "For todo and goal memories: how urgent the item is (default medium)"
+                    },
+                    "due_at": {
+                        "type": "string",
+                        "description": "For todo and goal memories: due date as YYYY-MM-DD or an RFC 3339 timestamp"
+                    },
+                    "tags": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Optional short labels for organizing the memory (e.g. a project or topic)"
+                    },
+                    "scope": {
+                        "type": "string",
+                        "enum": ["global", "channel", "user"],
+                        "description": "Who may recall this memory. 'global' (default) for general knowledge, 'channel' for something only relevant to this conversation, 'user' for something private to the person you're talking to, recalled wherever they talk to you"
+                    },
+                    "expires_at": {
+                        "type": "string",
+                        "description": "Optional time after which the memory is no longer true and should be forgotten (e.g. the end of a meeting or event), as YYYY-MM-DD or an RFC 3339 timestamp"
                     }
                 },
                 "required": ["content"]
@@ -217,6 +368,28 @@ impl Tool for MemorySaveTool {
             )));
         }
 
+        let scope = self.resolve_scope(args.scope.as_deref())?;
+
+        let expires_at = args
+            .expires_at
+            .as_deref()
+            .map(|expires_at| {
+                crate::memory::todos::parse_due_date(expires_at).ok_or_else(|| {
+                    MemorySaveError(format!(
+                        "expires_at must be YYYY-MM-DD or an RFC 3339 timestamp (got '{expires_at}')"
+                    ))
+                })
+            })
+            .transpose()?;
+        if let Some(expires_at) = expires_at
+            && expires_at <= chrono::Utc::now()
+        {
+            return Err(MemorySaveError(format!(
+                "expires_at must be in the future (got {})",
+                expires_at.to_rfc3339()
+            )));
+        }
+
         // Parse memory type
         let memory_type = match args.memory_type.as_str() {
             "fact" => MemoryType::Fact,
@@ -230,6 +403,30 @@ impl Tool for MemorySaveTool {
             _ => MemoryType::Fact,
         };
 
+        // Todos and goals carry structured state; validate it before
+        // anything is written.
+        let todo_details = if crate::memory::todos::is_trackable(memory_type) {
+            let mut details = crate::memory::todos::TodoDetails {
+                channel_id: args.channel_id.clone(),
+                ..Default::default()
+            };
+            if let Some(priority) = args.priority.as_deref() {
+                details.priority = crate::tasks::TaskPriority::parse(priority)
+                    .ok_or_else(|| MemorySaveError(format!("unknown priority '{priority}'")))?;
+            }
+            if let Some(due_at) = args.due_at.as_deref() {
+                let due_at = crate::memory::todos::parse_due_date(due_at).ok_or_else(|| {
+                    MemorySaveError(format!(
+                        "due_at must be YYYY-MM-DD or an RFC 3339 timestamp (got '{due_at}')"
+                    ))
+                })?;
+                details.set_due_at(Some(due_at));
+            }
+            Some(details)
+        } else {
+            None
+        };
+
         let mut memory = Memory::new(&args.content, memory_type);
 
         if let Some(importance) = args.importance {
@@ -244,6 +441,26 @@ impl Tool for MemorySaveTool {
             memory = memory.with_channel_id(Arc::from(channel_id.as_str()));
         }
 
+        if let Some(scope) = scope {
+            memory = memory.with_scope(scope);
+        }
+
+        let tags = normalize_tags(args.tags);
+        if !tags.is_empty() {
+            memory = memory.with_tags(tags);
+        }
+
+        if let Some(expires_at) = expires_at {
+            memory = memory.with_expires_at(expires_at);
+        }
+
+        if let Some(contract_state) = &self.contract_state {
+            contract_state
+                .journal_save(&memory.id)
+                .await
+                .map_err(|e| MemorySaveError(format!("Failed to journal memory: {e}")))?;
+        }
+
         // Save to SQLite database
         let store = self.memory_search.store();
         store
@@ -260,18 +477,20 @@ impl Tool for MemorySaveTool {
                 "caused_by" => crate::memory::types::RelationType::CausedBy,
                 "result_of" => crate::memory::types::RelationType::ResultOf,
                 "part_of" => crate::memory::types::RelationType::PartOf,
+                "supports" => crate::memory::types::RelationType::Supports,
+                "about_person" => crate::memory::types::RelationType::AboutPerson,
                 _ => crate::memory::types::RelationType::RelatedTo,
             };
 
             // Verify the target memory exists before creating a graph edge
             // to prevent dangling associations from LLM hallucinated IDs.
             match store.load(&assoc.target_id).await {
-                Ok(Some(_)) => {}
-                Ok(None) => {
+                Ok(Some(target)) if self.access.read.allows(target.scope.as_deref()) => {}
+                Ok(_) => {
                     tracing::warn!(
                         memory_id = %memory.id,
                         target_id = %assoc.target_id,
-                        "skipping association to non-existent memory"
+                        "skipping association to missing or out-of-scope memory"
                     );
                     continue;
                 }
@@ -296,90 +515,55 @@ impl Tool for MemorySaveTool {
                     %error,
                     "failed to create memory association"
                 );
-            }
-        }
-
-        // Generate and store embedding. On failure, compensate by deleting the
-        // SQLite row (and any associations already written) so there is no orphan.
-        let embedding = match self
-            .memory_search
-            .embedding_model_arc()
-            .embed_one(&args.content)
+            } else if matches!(
+                relation_type,
+                crate::memory::types::RelationType::Updates
+                    | crate::memory::types::RelationType::Contradicts
+            ) && let Err(error) = crate::memory::feedback::record_signals(
+                store,
+                std::slice::from_ref(&assoc.target_id),
+                crate::memory::feedback::MemorySignal::Corrected,
+            )
             .await
-        {
-            Ok(emb) => emb,
-            Err(embed_err) => {
-                if let Err(assoc_err) = self
-                    .memory_search
-                    .store()
-                    .delete_associations_for_memory(&memory.id)
-                    .await
-                {
-                    tracing::error!(
-                        memory_id = %memory.id,
-                        error = %assoc_err,
-                        "compensating association delete failed after embedding generation error"
-                    );
-                }
-                if let Err(del_err) = self.memory_search.store().delete(&memory.id).await {
-                    tracing::error!(
-                        memory_id = %memory.id,
-                        %del_err,
-                        "compensating delete failed after embedding generation error"
-                    );
-                }
-                return Err(MemorySaveError(format!(
-                    "Failed to generate embedding: {embed_err}"
-                )));
+            {
+                tracing::warn!(
+                    target_id = %assoc.target_id,
+                    %error,
+                    "failed to record memory correction signal"
+                );
             }
-        };
+        }
 
-        match self
-            .memory_search
-            .embedding_table()
-            .store(&memory.id, &args.content, &embedding)
-            .await
-        {
-            Ok(()) => {
-                if let Some(contract_state) = &self.contract_state {
-                    contract_state.record_saved_memory_id(memory.id.clone());
-                }
-            }
-            Err(embed_err) => {
-                if let Err(assoc_err) = self
-                    .memory_search
-                    .store()
-                    .delete_associations_for_memory(&memory.id)
-                    .await
-                {
-                    tracing::error!(
-                        memory_id = %memory.id,
-                        error = %assoc_err,
-                        "compensating association delete failed after embedding store error"
-                    );
-                }
-                if let Err(del_err) = self.memory_search.store().delete(&memory.id).await {
-                    tracing::error!(
-                        memory_id = %memory.id,
-                        %del_err,
-                        "compensating delete failed after embedding store error"
-                    );
-                }
-                return Err(MemorySaveError(format!(
-                    "Failed to store embedding: {embed_err}"
-                )));
-            }
+        // Persistence runs embed their memories together when they complete.
+        let pending_embeddings = self
+            .contract_state
+            .as_ref()
+            .and_then(|contract_state| contract_state.pending_embeddings());
+        if let Some(pending) = pending_embeddings {
+            pending.push(&memory.id, &args.content);
+        } else {
+            self.embed_and_store(&memory.id, &args.content).await?;
+        }
+        if let Some(contract_state) = &self.contract_state {
+            contract_state.record_saved_memory_id(memory.id.clone());
         }
 
-        // Ensure the FTS index exists so full_text_search queries work.
-        // Safe to call repeatedly — no-ops if the index already exists.
-        if let Err(error) = self
-            .memory_search
-            .embedding_table()
-            .ensure_fts_index()
-            .await
+        crate::memory::mutations::record_or_warn(
+            self.memory_search.store(),
+            crate::memory::mutations::MutationKind::Create,
+            &memory.id,
+            Some(&memory),
+            self.actor,
+            None,
+        )
+        .await;
+
+        if let Some(details) = &todo_details
+            && let Err(error) =
+                crate::memory::todos::save_details(self.memory_search.store(), &memory.id, details)
+                    .await
         {
-            tracing::warn!(%error, "failed to ensure FTS index after memory save");
+            tracing::warn!(memory_id = %memory.id, %error, "failed to save todo state");
         }
 
         if let Some(event_context) = &self.event_context
@@ -443,6 +627,11 @@ pub async fn save_fact(
         source: None,
         channel_id: channel_id.map(|id| id.to_string()),
         associations: vec![],
+        priority: None,
+        due_at: None,
+        tags: vec![],
+        scope: None,
+        expires_at: None,
     };
 
     let output = tool
@@ -452,13 +641,37 @@ pub async fn save_fact(
     Ok(output.memory_id)
 }
 
+/// Trim tags, drop empty ones, and remove duplicates, keeping first-seen
+/// order.
+pub(crate) fn normalize_tags(tags: Vec<String>) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+    for tag in tags {
+        let tag = tag.trim();
+        if !tag.is_empty() && !normalized.iter().any(|existing| existing == tag) {
+            normalized.push(tag.to_string());
+        }
+    }
+    normalized
+}
+
 fn summarize_memory_content(content: &str) -> String {
     crate::summarize_first_non_empty_line(content, crate::EVENT_SUMMARY_MAX_CHARS)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::summarize_memory_content;
+    use super::{normalize_tags, summarize_memory_content};
+
+    #[test]
+    fn normalize_tags_trims_and_dedupes() {
+        let tags = vec![
+            " onboarding ".to_string(),
+            "".to_string(),
+            "billing".to_string(),
+            "onboarding".to_string(),
+        ];
+        assert_eq!(normalize_tags(tags), vec!["onboarding", "billing"]);
+    }
 
     #[test]
     fn summarize_memory_content_prefers_first_non_empty_line() {
@@ -1,6 +1,9 @@
 //! Reply tool for sending messages to users (channel only).
 
+use crate::agent::verification::{self, ReplyVerifier};
+use crate::config::ReplyVerification;
 use crate::conversation::ConversationLogger;
+use crate::translation::Translator;
 
 use crate::{ChannelId, OutboundResponse, RoutedSender};
 use regex::Regex;
@@ -45,6 +48,30 @@ pub struct ReplyTool {
     channel_id: ChannelId,
     replied_flag: RepliedFlag,
     agent_display_name: String,
+    translation: Option<ReplyTranslation>,
+    verifier: Option<ReplyVerifier>,
+    /// Set once a reply has been sent back for regeneration this turn, so
+    /// the retry goes out annotated instead of looping.
+    regenerated: Arc<AtomicBool>,
+}
+
+/// Translates replies into the user's language in auto-translate channels.
+#[derive(Clone)]
+pub struct ReplyTranslation {
+    pub translator: Arc<dyn Translator>,
+    /// Language the agent writes its replies in.
+    pub working_language: String,
+    /// Language detected on the user's latest message.
+    pub user_language: String,
+}
+
+impl std::fmt::Debug for ReplyTranslation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReplyTranslation")
+            .field("working_language", &self.working_language)
+            .field("user_language", &self.user_language)
+            .finish_non_exhaustive()
+    }
 }
 
 impl ReplyTool {
@@ -64,6 +91,125 @@ impl ReplyTool {
             channel_id,
             replied_flag,
             agent_display_name: agent_display_name.into(),
+            translation: None,
+            verifier: None,
+            regenerated: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Send replies in the user's language instead of the working language.
+    pub fn with_translation(mut self, translation: ReplyTranslation) -> Self {
+        self.translation = Some(translation);
+        self
+    }
+
+    /// Fact-check replies against memory before sending them.
+    pub fn with_verifier(mut self, verifier: ReplyVerifier) -> Self {
+        self.verifier = Some(verifier);
+        self
+    }
+
+    /// Fact-check `content` and apply the channel's action to unsupported
+    /// claims. Returns the text to send and the `verification` metadata, or
+    /// an error sending the reply back to the model to revise. A failed
+    /// check lets the reply through unchanged.
+    async fn verify(
+        &self,
+        content: &str,
+    ) -> Result<(String, Option<serde_json::Value>), ReplyError> {
+        let Some(verifier) = &self.verifier else {
+            return Ok((content.to_string(), None));
+        };
+        let verification = match verifier.check(content).await {
+            Ok(verification) => verification,
+            Err(error) => {
+                tracing::warn!(
+                    %error,
+                    conversation_id = %self.conversation_id,
+                    "reply verification failed, sending it unchecked"
+                );
+                return Ok((content.to_string(), None));
+            }
+        };
+
+        let unsupported = verification.unsupported();
+        let regenerated = self.regenerated.load(Ordering::Relaxed);
+        let (text, outcome) = if unsupported.is_empty() {
+            let outcome = if regenerated {
+                verification::Outcome::Regenerated
+            } else {
+                verification::Outcome::Verified
+            };
+            (content.to_string(), outcome)
+        } else {
+            match verifier.action {
+                ReplyVerification::Regenerate if !regenerated => {
+                    self.regenerated.store(true, Ordering::Relaxed);
+                    tracing::info!(
+                        conversation_id = %self.conversation_id,
+                        unsupported = unsupported.len(),
+                        "reply sent back for unsupported claims"
+                    );
+                    return Err(ReplyError(verification::regenerate_feedback(&unsupported)));
+                }
+                ReplyVerification::Soften => match verifier.soften(content, &unsupported).await {
+                    Ok(softened) if !softened.trim().is_empty() => {
+                        (softened, verification::Outcome::Softened)
+                    }
+                    result => {
+                        if let Err(error) = result {
+                            tracing::warn!(
+                                %error,
+                                conversation_id = %self.conversation_id,
+                                "failed to soften reply, annotating it instead"
+                            );
+                        }
+                        (
+                            verification::annotate(content, &unsupported),
+                            verification::Outcome::Annotated,
+                        )
+                    }
+                },
+                _ => (
+                    verification::annotate(content, &unsupported),
+                    verification::Outcome::Annotated,
+                ),
+            }
+        };
+
+        tracing::debug!(
+            conversation_id = %self.conversation_id,
+            claims = verification.claims.len(),
+            unsupported = unsupported.len(),
+            "reply verified"
+        );
+        let metadata = verification.metadata(verifier.action, outcome);
+        Ok((text, Some(metadata)))
+    }
+
+    /// Translate `content` for the user. Falls back to the untranslated text
+    /// if the provider fails so the reply still goes out.
+    async fn translate_for_user(&self, content: &str) -> Option<String> {
+        let translation = self.translation.as_ref()?;
+        match translation
+            .translator
+            .translate(
+                content,
+                &translation.user_language,
+                Some(&translation.working_language),
+            )
+            .await
+        {
+            Ok(translated) => Some(translated.text),
+            Err(error) => {
+                tracing::warn!(
+                    %error,
+                    conversation_id = %self.conversation_id,
+                    user_language = %translation.user_language,
+                    "failed to translate reply, sending it untranslated"
+                );
+                None
+            }
         }
     }
 }
@@ -95,6 +241,10 @@ pub struct ReplyArgs {
     /// Optional: a poll to attach to the message.
     #[serde(default)]
     pub poll: Option<crate::Poll>,
+    /// Optional: the user needs to see this reply. On platforms that report
+    /// read receipts, the cortex flags it if it stays unread.
+    #[serde(default)]
+    pub important: bool,
 }
 
 /// Output from reply tool.
@@ -346,6 +496,10 @@ impl Tool for ReplyTool {
                         "duration_hours": { "type": "integer", "description": "Defaults to 24 if omitted" }
                     },
                     "required": ["question", "answers"]
+                },
+                "important": {
+                    "type": "boolean",
+                    "description": "Set when the user needs to see this reply (an answer they are waiting on, a deadline, a warning). If the platform reports it unread for a while, it is flagged so you can follow up."
                 }
             },
             "required": ["content"]
@@ -377,9 +531,17 @@ impl Tool for ReplyTool {
         // Extract source from conversation_id (format: "platform:id")
         let source = self.conversation_id.split(':').next().unwrap_or("unknown");
 
+        // Fact-check before translating so claims are compared in the
+        // language the memories are written in.
+        let (content, verification) = self.verify(&args.content).await?;
+
+        // In auto-translate channels the agent writes in its working language
+        // and the user gets the reply in theirs.
+        let translated = self.translate_for_user(&content).await;
+
         // Auto-convert @mentions to platform-specific syntax
         let converted_content = convert_mentions(
-            &args.content,
+            translated.as_deref().unwrap_or(&content),
             &self.channel_id,
             &self.conversation_logger,
             source,
@@ -437,15 +599,48 @@ impl Tool for ReplyTool {
             OutboundResponse::Text(converted_content.clone())
         };
 
+        // The logged message shares its ID with the outbox entry, so the
+        // timeline can show whether the reply was delivered.
+        let message_id = uuid::Uuid::new_v4().to_string();
         self.response_tx
-            .send(response)
+            .send_logged(response, Some(message_id.clone()))
             .await
             .map_err(|e| ReplyError(format!("failed to send reply: {e}")))?;
 
-        self.conversation_logger.log_bot_message_with_name(
+        let mut metadata = HashMap::new();
+        if args.important {
+            metadata.insert("important".to_string(), serde_json::Value::Bool(true));
+        }
+        if let Some(verification) = verification {
+            metadata.insert("verification".to_string(), verification);
+        }
+        let logged_content = match (&translated, &self.translation) {
+            (Some(_), Some(translation)) => {
+                // History keeps the agent's original; the sent text rides along.
+                metadata.insert(
+                    "translation".to_string(),
+                    serde_json::json!({
+                        "text": converted_content,
+                        "language": translation.user_language,
+                        "source_language": translation.working_language,
+                    }),
+                );
+                convert_mentions(
+                    &content,
+                    &self.channel_id,
+                    &self.conversation_logger,
+                    source,
+                )
+                .await
+            }
+            _ => converted_content.clone(),
+        };
+        self.conversation_logger.log_bot_message_with_id(
+            message_id,
             &self.channel_id,
-            &converted_content,
+            &logged_content,
             Some(&self.agent_display_name),
+            &metadata,
         );
 
         // Mark the turn as handled so handle_agent_result skips the fallback send.
@@ -1,10 +1,13 @@
 //! Send message tool for cross-channel messaging and DMs.
 
 use crate::ChannelId;
+use crate::config::HumanDef;
+use crate::contacts::ContactStore;
 use crate::conversation::ChannelStore;
 use crate::conversation::history::ConversationLogger;
 use crate::messaging::MessagingManager;
 
+use arc_swap::ArcSwap;
 use rig::completion::ToolDefinition;
 use rig::tool::Tool;
 use schemars::JsonSchema;
@@ -30,6 +33,14 @@ pub struct SendMessageTool {
     conversation_logger: ConversationLogger,
     agent_display_name: String,
     current_adapter: Option<String>,
+    contacts: Option<ContactLookup>,
+}
+
+/// Contact book used to resolve a person's name when no channel matches.
+#[derive(Clone)]
+struct ContactLookup {
+    store: ContactStore,
+    humans: Arc<ArcSwap<Vec<HumanDef>>>,
 }
 
 impl std::fmt::Debug for SendMessageTool {
@@ -52,8 +63,83 @@ impl SendMessageTool {
             conversation_logger,
             agent_display_name,
             current_adapter,
+            contacts: None,
         }
     }
+
+    /// Fall back to the contact book for targets that aren't channels, so a
+    /// person's name reaches them on a connected platform.
+    pub fn with_contacts(
+        mut self,
+        store: ContactStore,
+        humans: Arc<ArcSwap<Vec<HumanDef>>>,
+    ) -> Self {
+        self.contacts = Some(ContactLookup { store, humans });
+        self
+    }
+
+    /// Deliver to a contact matched by name. `Ok(None)` when no contact
+    /// matches.
+    async fn send_to_contact(
+        &self,
+        name: &str,
+        message: &str,
+    ) -> Result<Option<SendMessageOutput>, SendMessageError> {
+        let Some(lookup) = &self.contacts else {
+            return Ok(None);
+        };
+        let mut matches = lookup
+            .store
+            .resolve(name)
+            .await
+            .map_err(|error| SendMessageError(format!("failed to search contacts: {error}")))?;
+        match matches.len() {
+            0 => return Ok(None),
+            1 => {}
+            count => {
+                let names: Vec<&str> = matches
+                    .iter()
+                    .map(|contact| contact.name.as_str())
+                    .collect();
+                return Err(SendMessageError(format!(
+                    "'{name}' matches {count} contacts ({}); ask which one is meant",
+                    names.join(", ")
+                )));
+            }
+        }
+
+        let contact = matches.remove(0);
+        let targets = crate::contacts::delivery_targets(&contact, &lookup.humans.load());
+        let target = crate::contacts::first_deliverable(targets, &self.messaging_manager)
+            .await
+            .ok_or_else(|| {
+                SendMessageError(format!(
+                    "contact '{}' has no handle on a connected platform",
+                    contact.name
+                ))
+            })?;
+
+        self.messaging_manager
+            .broadcast(
+                &target.adapter,
+                &target.target,
+                crate::OutboundResponse::Text(message.to_string()),
+            )
+            .await
+            .map_err(|error| SendMessageError(format!("failed to send message: {error}")))?;
+
+        tracing::info!(
+            adapter = %target.adapter,
+            contact_id = %contact.id,
+            "message sent to contact"
+        );
+
+        Ok(Some(SendMessageOutput {
+            success: true,
+            target: contact.name,
+            platform: target.adapter,
+        }))
+    }
 }
 
 /// Error type for send_message tool.
@@ -64,7 +150,7 @@ pub struct SendMessageError(String);
 /// Arguments for send_message tool.
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct SendMessageArgs {
-    /// The target channel name, channel ID, or user identifier.
+    /// The target channel name, channel ID, contact name, or user identifier.
     /// Use a channel name like "general" or a full channel ID.
     pub target: String,
     /// The message content to send.
@@ -97,7 +183,7 @@ impl Tool for SendMessageTool {
 
         let mut description =
             crate::prompts::text::get("tools/send_message_to_another_channel").to_string();
-        let mut target_description = "The target channel name, channel ID, or user identifier. Use a channel name like 'general' or a full channel ID from the available channels list.".to_string();
+        let mut target_description = "The target channel name, channel ID, or user identifier. Use a channel name like 'general' or a full channel ID from the available channels list. A person's name from the contact book also works.".to_string();
 
         if email_adapter_available {
             description.push_str(
@@ -261,8 +347,11 @@ impl Tool for SendMessageTool {
         let channel = match channel_result {
             Some(ch) => ch,
             None => {
+                if let Some(output) = self.send_to_contact(&args.target, &args.message).await? {
+                    return Ok(output);
+                }
                 return Err(SendMessageError(format!(
-                    "no channel found matching '{}'. Use a channel name/ID from the available channels list, an explicit email target like email:alice@example.com, or signal: prefix for Signal targets.",
+                    "no channel or contact found matching '{}'. Use a channel name/ID from the available channels list, a contact's name, an explicit email target like email:alice@example.com, or signal: prefix for Signal targets.",
                     args.target
                 )));
             }
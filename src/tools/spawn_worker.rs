@@ -482,7 +482,7 @@ impl Tool for DetachedSpawnWorkerTool {
             None,
             secrets_store,
             "builtin",
-            worker.run().instrument(worker_span),
+            self.deps.quota.gate(worker.run().instrument(worker_span)),
         );
 
         // Register the worker with the cortex chat event loop so it can
@@ -1,5 +1,7 @@
 //! Task creation tool for branch processes.
 
+use crate::config::NotificationCategory;
+use crate::notifications::OperatorNotifier;
 use crate::tasks::{CreateTaskInput, TaskPriority, TaskStatus, TaskStore, TaskSubtask};
 use rig::completion::ToolDefinition;
 use rig::tool::Tool;
@@ -12,6 +14,7 @@ pub struct TaskCreateTool {
     task_store: Arc<TaskStore>,
     agent_id: String,
     created_by: String,
+    notifier: Option<OperatorNotifier>,
 }
 
 impl TaskCreateTool {
@@ -24,8 +27,16 @@ impl TaskCreateTool {
             task_store,
             agent_id: agent_id.into(),
             created_by: created_by.into(),
+            notifier: None,
         }
     }
+
+    /// Alert operators subscribed to approvals when a task is created
+    /// waiting for approval.
+    pub fn with_notifier(mut self, notifier: Option<OperatorNotifier>) -> Self {
+        self.notifier = notifier;
+        self
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -133,6 +144,20 @@ impl Tool for TaskCreateTool {
             .await
             .map_err(|error| TaskCreateError(format!("{error}")))?;
 
+        if task.status == TaskStatus::PendingApproval
+            && let Some(notifier) = self.notifier.clone()
+        {
+            let text = format!(
+                "Task #{} needs approval (created by {}): {}",
+                task.task_number, self.created_by, task.title
+            );
+            tokio::spawn(async move {
+                notifier
+                    .notify(NotificationCategory::Approvals, &text)
+                    .await;
+            });
+        }
+
         Ok(TaskCreateOutput {
             success: true,
             task_number: task.task_number,
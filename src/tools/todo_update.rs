@@ -0,0 +1,180 @@
+//! Todo update tool for branches.
+//!
+//! Completes, reprioritizes, or reschedules a todo or goal memory by editing
+//! its structured state. The memory content itself is left alone; corrections
+//! to the text go through `memory_save` with an `updates` association.
+
+use crate::memory::MemorySearch;
+use crate::memory::scope::ScopeFilter;
+use crate::memory::todos::{self, TodoStatus};
+use crate::tasks::TaskPriority;
+use rig::completion::ToolDefinition;
+use rig::tool::Tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Tool for updating todo and goal memories.
+#[derive(Debug, Clone)]
+pub struct TodoUpdateTool {
+    memory_search: Arc<MemorySearch>,
+    scope_filter: ScopeFilter,
+}
+
+impl TodoUpdateTool {
+    /// Create a new todo update tool.
+    pub fn new(memory_search: Arc<MemorySearch>) -> Self {
+        Self {
+            memory_search,
+            scope_filter: ScopeFilter::All,
+        }
+    }
+
+    /// Only allow updating items the caller can recall.
+    pub fn with_scope_filter(mut self, scope_filter: ScopeFilter) -> Self {
+        self.scope_filter = scope_filter;
+        self
+    }
+}
+
+/// Error type for todo update tool.
+#[derive(Debug, thiserror::Error)]
+#[error("todo_update failed: {0}")]
+pub struct TodoUpdateError(String);
+
+/// Arguments for todo update tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct TodoUpdateArgs {
+    /// The ID of the todo or goal memory.
+    pub memory_id: String,
+    /// New status (open, in_progress, done, cancelled).
+    pub status: Option<String>,
+    /// New priority (critical, high, medium, low).
+    pub priority: Option<String>,
+    /// New due date as RFC 3339 or `YYYY-MM-DD`; an empty string clears it.
+    pub due_at: Option<String>,
+    /// Channel to follow up in.
+    pub channel_id: Option<String>,
+}
+
+/// Output from todo update tool.
+#[derive(Debug, Serialize)]
+pub struct TodoUpdateOutput {
+    pub success: bool,
+    pub memory_id: String,
+    pub status: String,
+    pub message: String,
+}
+
+impl Tool for TodoUpdateTool {
+    const NAME: &'static str = "todo_update";
+
+    type Error = TodoUpdateError;
+    type Args = TodoUpdateArgs;
+    type Output = TodoUpdateOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: crate::prompts::text::get("tools/todo_update").to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "memory_id": {
+                        "type": "string",
+                        "description": "The ID of the todo or goal memory (from memory_recall results)"
+                    },
+                    "status": {
+                        "type": "string",
+                        "enum": TodoStatus::ALL.iter().map(|s| s.to_string()).collect::<Vec<_>>(),
+                        "description": "New status. Use done when the item is finished."
+                    },
+                    "priority": {
+                        "type": "string",
+                        "enum": TaskPriority::ALL.iter().map(|p| p.to_string()).collect::<Vec<_>>(),
+                        "description": "New priority"
+                    },
+                    "due_at": {
+                        "type": "string",
+                        "description": "New due date as YYYY-MM-DD or an RFC 3339 timestamp. An empty string clears the due date."
+                    },
+                    "channel_id": {
+                        "type": "string",
+                        "description": "Channel to follow up in"
+                    }
+                },
+                "required": ["memory_id"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> std::result::Result<Self::Output, Self::Error> {
+        let store = self.memory_search.store();
+
+        let memory = store
+            .load(&args.memory_id)
+            .await
+            .map_err(|e| TodoUpdateError(format!("failed to look up memory: {e}")))?
+            .filter(|memory| !memory.forgotten && self.scope_filter.allows(memory.scope.as_deref()))
+            .ok_or_else(|| {
+                TodoUpdateError(format!("no memory found with ID {}", args.memory_id))
+            })?;
+
+        let mut details = todos::load_details(store, &memory)
+            .await
+            .map_err(|e| TodoUpdateError(format!("failed to load todo state: {e}")))?
+            .ok_or_else(|| {
+                TodoUpdateError(format!(
+                    "memory {} is a {}, not a todo or goal",
+                    memory.id, memory.memory_type
+                ))
+            })?;
+
+        if let Some(status) = args.status.as_deref() {
+            let status = TodoStatus::parse(status)
+                .ok_or_else(|| TodoUpdateError(format!("unknown status '{status}'")))?;
+            details.set_status(status);
+        }
+        if let Some(priority) = args.priority.as_deref() {
+            details.priority = TaskPriority::parse(priority)
+                .ok_or_else(|| TodoUpdateError(format!("unknown priority '{priority}'")))?;
+        }
+        if let Some(due_at) = args.due_at.as_deref() {
+            let due_at = if due_at.trim().is_empty() {
+                None
+            } else {
+                Some(todos::parse_due_date(due_at).ok_or_else(|| {
+                    TodoUpdateError(format!(
+                        "due_at must be YYYY-MM-DD or an RFC 3339 timestamp (got '{due_at}')"
+                    ))
+                })?)
+            };
+            details.set_due_at(due_at);
+        }
+        if let Some(channel_id) = args.channel_id {
+            details.channel_id = Some(channel_id).filter(|id| !id.is_empty());
+        }
+
+        todos::save_details(store, &memory.id, &details)
+            .await
+            .map_err(|e| TodoUpdateError(format!("failed to save todo state: {e}")))?;
+
+        tracing::info!(
+            memory_id = %memory.id,
+            status = %details.status,
+            priority = %details.priority,
+            "todo updated"
+        );
+
+        let preview = memory.content.lines().next().unwrap_or("(empty)");
+        Ok(TodoUpdateOutput {
+            success: true,
+            memory_id: memory.id.clone(),
+            status: details.status.to_string(),
+            message: format!(
+                "Updated [{}] \"{preview}\": now {}.",
+                memory.memory_type, details.status
+            ),
+        })
+    }
+}
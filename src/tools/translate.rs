@@ -0,0 +1,141 @@
+//! Translate tool: translate text between languages.
+
+use crate::config::RuntimeConfig;
+use crate::llm::LlmManager;
+use crate::translation::{self, MAX_TEXT_CHARS};
+use rig::completion::ToolDefinition;
+use rig::tool::Tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Tool for translating text with the agent's configured translation provider.
+#[derive(Clone)]
+pub struct TranslateTool {
+    llm_manager: Arc<LlmManager>,
+    runtime_config: Arc<RuntimeConfig>,
+    agent_id: crate::AgentId,
+}
+
+impl std::fmt::Debug for TranslateTool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TranslateTool")
+            .field("agent_id", &self.agent_id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl TranslateTool {
+    pub fn new(
+        llm_manager: Arc<LlmManager>,
+        runtime_config: Arc<RuntimeConfig>,
+        agent_id: crate::AgentId,
+    ) -> Self {
+        Self {
+            llm_manager,
+            runtime_config,
+            agent_id,
+        }
+    }
+}
+
+/// Error type for translate tool.
+#[derive(Debug, thiserror::Error)]
+#[error("translate failed: {0}")]
+pub struct TranslateError(String);
+
+/// Arguments for translate.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct TranslateArgs {
+    pub text: String,
+    /// ISO 639-1 code of the language to translate into, e.g. `de`.
+    pub target_language: String,
+    /// Language of `text`, when known. Detected otherwise.
+    #[serde(default)]
+    pub source_language: Option<String>,
+}
+
+/// Output for translate.
+#[derive(Debug, Serialize)]
+pub struct TranslateOutput {
+    pub text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_language: Option<String>,
+    pub target_language: String,
+}
+
+impl Tool for TranslateTool {
+    const NAME: &'static str = "translate";
+
+    type Error = TranslateError;
+    type Args = TranslateArgs;
+    type Output = TranslateOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: crate::prompts::text::get("tools/translate").to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "text": {
+                        "type": "string",
+                        "description": "Text to translate. Markdown and code blocks are kept intact."
+                    },
+                    "target_language": {
+                        "type": "string",
+                        "description": "ISO 639-1 code of the language to translate into, e.g. \"de\", \"ja\", \"pt-BR\""
+                    },
+                    "source_language": {
+                        "type": "string",
+                        "description": "ISO 639-1 code of the text's language. Omit to detect it."
+                    }
+                },
+                "required": ["text", "target_language"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let config = self.runtime_config.translation.load();
+        if !config.enabled {
+            return Err(TranslateError(
+                "translation is disabled for this agent".into(),
+            ));
+        }
+        if args.text.trim().is_empty() {
+            return Err(TranslateError("text is empty".into()));
+        }
+        if args.text.chars().count() > MAX_TEXT_CHARS {
+            return Err(TranslateError(format!(
+                "text is longer than {MAX_TEXT_CHARS} characters; split it up"
+            )));
+        }
+        let target_language = args.target_language.trim();
+        if translation::primary_language(target_language).is_empty() {
+            return Err(TranslateError("target_language is empty".into()));
+        }
+
+        let translator = translation::translator(
+            &config,
+            self.llm_manager.clone(),
+            self.runtime_config.clone(),
+            self.agent_id.clone(),
+        )
+        .map_err(|error| TranslateError(error.to_string()))?;
+        let translated = translator
+            .translate(
+                &args.text,
+                target_language,
+                args.source_language.as_deref().map(str::trim),
+            )
+            .await
+            .map_err(|error| TranslateError(error.to_string()))?;
+
+        Ok(TranslateOutput {
+            text: translated.text,
+            source_language: translated.source_language,
+            target_language: target_language.to_string(),
+        })
+    }
+}
@@ -0,0 +1,129 @@
+//! Weather tool: current conditions and daily forecast for a place.
+
+use crate::config::RuntimeConfig;
+use crate::weather::{Forecast, MAX_FORECAST_DAYS, Place, Weather};
+use rig::completion::ToolDefinition;
+use rig::tool::Tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Tool for fetching forecasts from the agent's configured weather provider.
+#[derive(Debug, Clone)]
+pub struct WeatherTool {
+    runtime_config: Arc<RuntimeConfig>,
+}
+
+impl WeatherTool {
+    pub fn new(runtime_config: Arc<RuntimeConfig>) -> Self {
+        Self { runtime_config }
+    }
+}
+
+/// Error type for weather tool.
+#[derive(Debug, thiserror::Error)]
+#[error("weather failed: {0}")]
+pub struct WeatherError(String);
+
+/// Arguments for weather.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct WeatherArgs {
+    /// Place name to geocode. Ignored when coordinates are given.
+    #[serde(default)]
+    pub location: Option<String>,
+    #[serde(default)]
+    pub latitude: Option<f64>,
+    #[serde(default)]
+    pub longitude: Option<f64>,
+    /// Number of days including today (1-16, default 3).
+    #[serde(default = "default_days")]
+    pub days: u8,
+}
+
+fn default_days() -> u8 {
+    3
+}
+
+/// Output for weather.
+#[derive(Debug, Serialize)]
+pub struct WeatherOutput {
+    /// The place the location resolved to; absent for raw coordinates.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub place: Option<Place>,
+    #[serde(flatten)]
+    pub forecast: Forecast,
+}
+
+impl Tool for WeatherTool {
+    const NAME: &'static str = "weather";
+
+    type Error = WeatherError;
+    type Args = WeatherArgs;
+    type Output = WeatherOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: crate::prompts::text::get("tools/weather").to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "location": {
+                        "type": "string",
+                        "description": "Place name, e.g. \"Lisbon\" or \"Springfield, Illinois\". Add region or country when the name is ambiguous."
+                    },
+                    "latitude": {
+                        "type": "number",
+                        "description": "Latitude in degrees. Use with longitude instead of location."
+                    },
+                    "longitude": {
+                        "type": "number",
+                        "description": "Longitude in degrees. Use with latitude instead of location."
+                    },
+                    "days": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "maximum": MAX_FORECAST_DAYS,
+                        "default": 3,
+                        "description": "Days of forecast starting today"
+                    }
+                }
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let config = self.runtime_config.weather.load();
+        if !config.enabled {
+            return Err(WeatherError("weather is disabled for this agent".into()));
+        }
+        let weather = Weather::new(&config);
+
+        let (place, latitude, longitude) = match (args.latitude, args.longitude, args.location) {
+            (Some(latitude), Some(longitude), _) => (None, latitude, longitude),
+            (_, _, Some(location)) => {
+                let place = weather
+                    .resolve(&location)
+                    .await
+                    .map_err(|error| WeatherError(error.to_string()))?;
+                let (latitude, longitude) = (place.latitude, place.longitude);
+                (Some(place), latitude, longitude)
+            }
+            _ => {
+                return Err(WeatherError(
+                    "pass a location, or both latitude and longitude".into(),
+                ));
+            }
+        };
+
+        let forecast = weather
+            .forecast(latitude, longitude, args.days)
+            .await
+            .map_err(|error| WeatherError(error.to_string()))?;
+
+        Ok(WeatherOutput {
+            place,
+            forecast: (*forecast).clone(),
+        })
+    }
+}
@@ -195,6 +195,7 @@ impl WorkerInspectTool {
                 "running" => "[running]",
                 "done" => "[done]",
                 "failed" => "[failed]",
+                "cancelled" => "[cancelled]",
                 _ => "[-]",
             };
             summary.push_str(&format!(
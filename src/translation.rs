@@ -0,0 +1,107 @@
+//! Text translation behind pluggable providers.
+//!
+//! Agents opt in with `[defaults.translation]` or `[agents.translation]`. The
+//! `translate` tool calls a [`Translator`] directly; with `auto_translate` the
+//! channel also translates inbound messages into the agent's working language
+//! and replies back into the user's, keeping the originals in history.
+//! Translation runs on the agent's own model unless DeepL is configured.
+
+pub mod deepl;
+pub mod llm;
+
+use crate::config::{RuntimeConfig, TranslationConfig, TranslationProvider};
+use crate::error::Result;
+use crate::llm::LlmManager;
+
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+
+use std::sync::Arc;
+
+/// Longest text accepted in one call.
+pub const MAX_TEXT_CHARS: usize = 20_000;
+
+/// A translated text.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Translated {
+    pub text: String,
+    /// Language the input was in, as a lowercase ISO 639-1 code when the
+    /// provider reports one.
+    pub source_language: Option<String>,
+}
+
+/// Translation backend.
+#[async_trait::async_trait]
+pub trait Translator: Send + Sync {
+    /// Translate `text` into `target_language` (ISO 639-1, e.g. `de`).
+    /// `source_language` is a hint; providers detect it when absent.
+    async fn translate(
+        &self,
+        text: &str,
+        target_language: &str,
+        source_language: Option<&str>,
+    ) -> Result<Translated>;
+}
+
+/// Build the translator configured for an agent.
+pub fn translator(
+    config: &TranslationConfig,
+    llm_manager: Arc<LlmManager>,
+    runtime_config: Arc<RuntimeConfig>,
+    agent_id: crate::AgentId,
+) -> Result<Arc<dyn Translator>> {
+    Ok(match config.provider {
+        TranslationProvider::Llm => Arc::new(llm::LlmTranslator::new(
+            llm_manager,
+            runtime_config,
+            agent_id,
+        )),
+        TranslationProvider::Deepl => {
+            let api_key = config
+                .deepl_api_key
+                .clone()
+                .ok_or_else(|| anyhow!("translation provider 'deepl' has no api key"))?;
+            Arc::new(deepl::DeeplTranslator::new(
+                llm_manager.http_client().clone(),
+                api_key,
+            ))
+        }
+    })
+}
+
+/// Reduce a language tag to its lowercase primary subtag: `pt-BR` -> `pt`.
+pub fn primary_language(tag: &str) -> String {
+    tag.trim()
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_lowercase()
+}
+
+/// Whether two language tags name the same language, ignoring region and
+/// case (`en-US` and `EN` match).
+pub fn same_language(left: &str, right: &str) -> bool {
+    let left = primary_language(left);
+    !left.is_empty() && left == primary_language(right)
+}
+
+async fn response_error(provider: &str, response: reqwest::Response) -> crate::Error {
+    let status = response.status();
+    let body = response.text().await.unwrap_or_default();
+    let body: String = body.chars().take(500).collect();
+    anyhow!("{provider} request failed with {status}: {body}").into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn languages_compare_on_primary_subtag() {
+        assert!(same_language("en-US", "EN"));
+        assert!(same_language("pt_BR", "pt"));
+        assert!(!same_language("de", "en"));
+        assert!(!same_language("", ""));
+        assert_eq!(primary_language(" ZH-Hans "), "zh");
+    }
+}
@@ -0,0 +1,100 @@
+//! DeepL translator.
+//!
+//! Free-tier keys (suffix `:fx`) must use the free API host; everything else
+//! goes to the pro host.
+
+use super::{Translated, Translator, primary_language, response_error};
+use crate::error::Result;
+
+use anyhow::{Context as _, anyhow};
+use serde::Deserialize;
+
+const FREE_API_URL: &str = "https://api-free.deepl.com/v2/translate";
+const PRO_API_URL: &str = "https://api.deepl.com/v2/translate";
+
+pub struct DeeplTranslator {
+    client: reqwest::Client,
+    api_key: String,
+}
+
+impl DeeplTranslator {
+    pub fn new(client: reqwest::Client, api_key: String) -> Self {
+        Self { client, api_key }
+    }
+
+    fn endpoint(&self) -> &'static str {
+        if self.api_key.ends_with(":fx") {
+            FREE_API_URL
+        } else {
+            PRO_API_URL
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Translator for DeeplTranslator {
+    async fn translate(
+        &self,
+        text: &str,
+        target_language: &str,
+        source_language: Option<&str>,
+    ) -> Result<Translated> {
+        let mut body = serde_json::json!({
+            "text": [text],
+            "target_lang": target_lang(target_language),
+            "preserve_formatting": true,
+        });
+        if let Some(source) = source_language {
+            body["source_lang"] = primary_language(source).to_uppercase().into();
+        }
+
+        let response = self
+            .client
+            .post(self.endpoint())
+            .header("Authorization", format!("DeepL-Auth-Key {}", self.api_key))
+            .json(&body)
+            .send()
+            .await
+            .context("deepl request failed")?;
+        if !response.status().is_success() {
+            return Err(response_error("deepl", response).await);
+        }
+        let response: TranslateResponse =
+            response.json().await.context("invalid deepl response")?;
+        let translation = response
+            .translations
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow!("deepl returned no translation"))?;
+
+        Ok(Translated {
+            text: translation.text,
+            source_language: translation
+                .detected_source_language
+                .map(|language| primary_language(&language)),
+        })
+    }
+}
+
+/// DeepL rejects bare `EN` and `PT` as targets; pick the common variant.
+fn target_lang(language: &str) -> String {
+    let language = language.trim().replace('_', "-").to_uppercase();
+    match language.as_str() {
+        "EN" => "EN-US".to_string(),
+        "PT" => "PT-BR".to_string(),
+        _ => language,
+    }
+}
+
+// -- API response types (private, only model what we need) --
+
+#[derive(Debug, Deserialize)]
+struct TranslateResponse {
+    translations: Vec<TranslationResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TranslationResult {
+    detected_source_language: Option<String>,
+    text: String,
+}
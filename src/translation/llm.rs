@@ -0,0 +1,88 @@
+//! Translation on the agent's own model.
+//!
+//! Routed like a branch with the `translation` task type, so a cheaper model
+//! can be assigned through `routing.task_overrides.translation`.
+
+use super::{Translated, Translator, primary_language};
+use crate::ProcessType;
+use crate::config::RuntimeConfig;
+use crate::error::Result;
+use crate::llm::{LlmManager, SpacebotModel};
+
+use anyhow::Context as _;
+use rig::agent::AgentBuilder;
+use rig::completion::{CompletionModel, TypedPrompt};
+
+use std::sync::Arc;
+
+pub struct LlmTranslator {
+    llm_manager: Arc<LlmManager>,
+    runtime_config: Arc<RuntimeConfig>,
+    agent_id: crate::AgentId,
+}
+
+impl LlmTranslator {
+    pub fn new(
+        llm_manager: Arc<LlmManager>,
+        runtime_config: Arc<RuntimeConfig>,
+        agent_id: crate::AgentId,
+    ) -> Self {
+        Self {
+            llm_manager,
+            runtime_config,
+            agent_id,
+        }
+    }
+}
+
+/// Structured model output.
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+struct LlmTranslation {
+    text: String,
+    source_language: Option<String>,
+}
+
+#[async_trait::async_trait]
+impl Translator for LlmTranslator {
+    async fn translate(
+        &self,
+        text: &str,
+        target_language: &str,
+        source_language: Option<&str>,
+    ) -> Result<Translated> {
+        let preamble = self
+            .runtime_config
+            .prompts
+            .load()
+            .render_static("translator")?;
+
+        let routing = self.runtime_config.routing.load();
+        let model_name = routing
+            .resolve(ProcessType::Branch, Some("translation"))
+            .to_string();
+        let model = SpacebotModel::make(&self.llm_manager, &model_name)
+            .with_context(&*self.agent_id, "translation")
+            .with_routing((**routing).clone());
+        let agent = AgentBuilder::new(model).preamble(&preamble).build();
+
+        let mut prompt = format!("Target language: {target_language}\n");
+        if let Some(source) = source_language {
+            prompt.push_str(&format!("Source language: {source}\n"));
+        }
+        prompt.push_str(&format!("\nMessage:\n{text}"));
+
+        let translation = agent
+            .prompt_typed::<LlmTranslation>(&prompt)
+            .await
+            .context("translation model call failed")?;
+
+        Ok(Translated {
+            text: translation.text,
+            source_language: translation
+                .source_language
+                .map(|language| primary_language(&language))
+                .filter(|language| !language.is_empty())
+                .or_else(|| source_language.map(primary_language)),
+        })
+    }
+}
@@ -0,0 +1,371 @@
+//! Weather forecasts and place-name lookup behind pluggable providers.
+//!
+//! Agents opt in with `[defaults.weather]` or `[agents.weather]`. Forecasts
+//! come from a [`Forecaster`] (Open-Meteo) and place names are resolved by a
+//! [`Geocoder`] (Nominatim or Open-Meteo's geocoding API); both are free and
+//! keyless. Lookups are cached process-wide so a chatty agent doesn't hammer
+//! the providers: places for a week, forecasts for the agent's
+//! `cache_ttl_secs`.
+
+pub mod nominatim;
+pub mod open_meteo;
+
+use crate::config::{GeocoderProvider, WeatherConfig, WeatherProvider, WeatherUnits};
+use crate::error::Result;
+
+use anyhow::anyhow;
+use moka::sync::Cache;
+use serde::{Deserialize, Serialize};
+
+use std::sync::{Arc, LazyLock};
+use std::time::{Duration, Instant};
+
+/// Longest forecast the providers return.
+pub const MAX_FORECAST_DAYS: u8 = 16;
+
+/// Upper bound on geocoding results per lookup.
+pub const MAX_GEOCODE_RESULTS: usize = 10;
+
+const CACHE_CAPACITY: u64 = 1_000;
+const GEOCODE_CACHE_TTL: Duration = Duration::from_secs(60 * 60 * 24 * 7);
+/// Hard ceiling on forecast reuse; the per-agent TTL is checked on read.
+const FORECAST_CACHE_MAX_TTL: Duration = Duration::from_secs(60 * 60 * 6);
+
+static GEOCODE_CACHE: LazyLock<Cache<String, Arc<Vec<Place>>>> = LazyLock::new(|| {
+    Cache::builder()
+        .max_capacity(CACHE_CAPACITY)
+        .time_to_live(GEOCODE_CACHE_TTL)
+        .build()
+});
+
+static FORECAST_CACHE: LazyLock<Cache<String, (Instant, Arc<Forecast>)>> = LazyLock::new(|| {
+    Cache::builder()
+        .max_capacity(CACHE_CAPACITY)
+        .time_to_live(FORECAST_CACHE_MAX_TTL)
+        .build()
+});
+
+/// Nominatim's usage policy requires an identifying user agent.
+static HTTP_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
+    reqwest::Client::builder()
+        .gzip(true)
+        .timeout(Duration::from_secs(20))
+        .user_agent(concat!(
+            "spacebot/",
+            env!("CARGO_PKG_VERSION"),
+            " (+https://github.com/spacedriveapp/spacebot)"
+        ))
+        .build()
+        .expect("hardcoded reqwest client config")
+});
+
+/// A resolved place.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Place {
+    /// Short name, e.g. `Berlin`.
+    pub name: String,
+    /// Full label with region and country.
+    pub display_name: String,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub country: Option<String>,
+    /// IANA timezone, when the geocoder reports one.
+    pub timezone: Option<String>,
+}
+
+/// Unit labels for the values in a [`Forecast`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ForecastUnits {
+    pub temperature: &'static str,
+    pub wind_speed: &'static str,
+    pub precipitation: &'static str,
+}
+
+impl ForecastUnits {
+    pub fn for_units(units: WeatherUnits) -> Self {
+        match units {
+            WeatherUnits::Metric => Self {
+                temperature: "°C",
+                wind_speed: "km/h",
+                precipitation: "mm",
+            },
+            WeatherUnits::Imperial => Self {
+                temperature: "°F",
+                wind_speed: "mph",
+                precipitation: "in",
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CurrentConditions {
+    /// Local time of the observation.
+    pub time: String,
+    pub summary: String,
+    pub temperature: Option<f64>,
+    pub apparent_temperature: Option<f64>,
+    pub relative_humidity: Option<f64>,
+    pub wind_speed: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DailyForecast {
+    /// Local date, `YYYY-MM-DD`.
+    pub date: String,
+    pub summary: String,
+    pub temperature_max: Option<f64>,
+    pub temperature_min: Option<f64>,
+    pub precipitation_sum: Option<f64>,
+    /// Percent.
+    pub precipitation_probability: Option<f64>,
+    pub wind_speed_max: Option<f64>,
+    pub sunrise: Option<String>,
+    pub sunset: Option<String>,
+}
+
+impl DailyForecast {
+    /// One-line description, e.g. `Light rain, 9–14 °C, 80% chance of
+    /// precipitation (3.2 mm), wind up to 25 km/h`.
+    pub fn describe(&self, units: &ForecastUnits) -> String {
+        let mut parts = vec![self.summary.clone()];
+        match (self.temperature_min, self.temperature_max) {
+            (Some(min), Some(max)) => {
+                parts.push(format!("{min:.0}–{max:.0} {}", units.temperature))
+            }
+            (None, Some(max)) => parts.push(format!("high {max:.0} {}", units.temperature)),
+            (Some(min), None) => parts.push(format!("low {min:.0} {}", units.temperature)),
+            (None, None) => {}
+        }
+        if let Some(probability) = self.precipitation_probability {
+            let amount = self
+                .precipitation_sum
+                .filter(|amount| *amount > 0.0)
+                .map(|amount| format!(" ({amount:.1} {})", units.precipitation))
+                .unwrap_or_default();
+            parts.push(format!("{probability:.0}% chance of precipitation{amount}"));
+        }
+        if let Some(wind) = self.wind_speed_max {
+            parts.push(format!("wind up to {wind:.0} {}", units.wind_speed));
+        }
+        parts.join(", ")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Forecast {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub timezone: Option<String>,
+    pub units: ForecastUnits,
+    pub current: Option<CurrentConditions>,
+    pub daily: Vec<DailyForecast>,
+}
+
+/// A forecast source.
+#[async_trait::async_trait]
+pub trait Forecaster: Send + Sync {
+    /// Current conditions plus `days` daily forecasts starting today, in the
+    /// location's local time.
+    async fn forecast(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        days: u8,
+        units: WeatherUnits,
+    ) -> Result<Forecast>;
+}
+
+/// A place-name lookup backend.
+#[async_trait::async_trait]
+pub trait Geocoder: Send + Sync {
+    /// Best matches for `query`, most relevant first.
+    async fn search(&self, query: &str, limit: usize) -> Result<Vec<Place>>;
+}
+
+/// The providers configured for one agent, with shared caching.
+pub struct Weather {
+    config: WeatherConfig,
+    forecaster: Arc<dyn Forecaster>,
+    geocoder: Arc<dyn Geocoder>,
+}
+
+impl Weather {
+    pub fn new(config: &WeatherConfig) -> Self {
+        let client = HTTP_CLIENT.clone();
+        let forecaster: Arc<dyn Forecaster> = match config.provider {
+            WeatherProvider::OpenMeteo => {
+                Arc::new(open_meteo::OpenMeteoForecaster::new(client.clone()))
+            }
+        };
+        let geocoder: Arc<dyn Geocoder> = match config.geocoder {
+            GeocoderProvider::Nominatim => Arc::new(nominatim::NominatimGeocoder::new(client)),
+            GeocoderProvider::OpenMeteo => Arc::new(open_meteo::OpenMeteoGeocoder::new(client)),
+        };
+        Self {
+            config: config.clone(),
+            forecaster,
+            geocoder,
+        }
+    }
+
+    pub async fn geocode(&self, query: &str, limit: usize) -> Result<Vec<Place>> {
+        let query = query.trim();
+        if query.is_empty() {
+            return Err(anyhow!("location is empty").into());
+        }
+        let limit = limit.clamp(1, MAX_GEOCODE_RESULTS);
+        let key = format!(
+            "{:?}|{limit}|{}",
+            self.config.geocoder,
+            query.to_lowercase()
+        );
+        if let Some(places) = GEOCODE_CACHE.get(&key) {
+            return Ok((*places).clone());
+        }
+
+        let places = self.geocoder.search(query, limit).await?;
+        // Misses aren't cached so a typo fixed upstream resolves next time.
+        if !places.is_empty() {
+            GEOCODE_CACHE.insert(key, Arc::new(places.clone()));
+        }
+        Ok(places)
+    }
+
+    /// The best match for a place name.
+    pub async fn resolve(&self, location: &str) -> Result<Place> {
+        self.geocode(location, 1)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow!("no place found for '{}'", location.trim()).into())
+    }
+
+    pub async fn forecast(&self, latitude: f64, longitude: f64, days: u8) -> Result<Arc<Forecast>> {
+        if !(-90.0..=90.0).contains(&latitude) || !(-180.0..=180.0).contains(&longitude) {
+            return Err(anyhow!("coordinates {latitude}, {longitude} are out of range").into());
+        }
+        let days = days.clamp(1, MAX_FORECAST_DAYS);
+        // ~100 m grid so nearby lookups share an entry.
+        let key = format!(
+            "{:?}|{:?}|{latitude:.3},{longitude:.3}|{days}",
+            self.config.provider, self.config.units
+        );
+        let ttl = Duration::from_secs(self.config.cache_ttl_secs);
+        if let Some((fetched_at, forecast)) = FORECAST_CACHE.get(&key)
+            && fetched_at.elapsed() < ttl
+        {
+            return Ok(forecast);
+        }
+
+        let forecast = Arc::new(
+            self.forecaster
+                .forecast(latitude, longitude, days, self.config.units)
+                .await?,
+        );
+        if !ttl.is_zero() {
+            FORECAST_CACHE.insert(key, (Instant::now(), forecast.clone()));
+        }
+        Ok(forecast)
+    }
+}
+
+/// Today's forecast for `bulletin_location`, formatted as a bulletin section.
+/// Empty when weather is disabled or no location is set.
+pub async fn bulletin_section(config: &WeatherConfig) -> Result<String> {
+    let Some(location) = config
+        .bulletin_location
+        .as_deref()
+        .filter(|_| config.enabled)
+    else {
+        return Ok(String::new());
+    };
+
+    let weather = Weather::new(config);
+    let place = weather.resolve(location).await?;
+    let forecast = weather.forecast(place.latitude, place.longitude, 1).await?;
+    let Some(today) = forecast.daily.first() else {
+        return Ok(String::new());
+    };
+    Ok(format!(
+        "### Today's Weather\n\n- {} ({}): {}\n\n",
+        place.name,
+        today.date,
+        today.describe(&forecast.units)
+    ))
+}
+
+/// Human-readable text for a WMO weather interpretation code.
+pub fn describe_weather_code(code: u8) -> &'static str {
+    match code {
+        0 => "Clear sky",
+        1 => "Mainly clear",
+        2 => "Partly cloudy",
+        3 => "Overcast",
+        45 | 48 => "Fog",
+        51 => "Light drizzle",
+        53 => "Drizzle",
+        55 => "Dense drizzle",
+        56 | 57 => "Freezing drizzle",
+        61 => "Light rain",
+        63 => "Rain",
+        65 => "Heavy rain",
+        66 | 67 => "Freezing rain",
+        71 => "Light snow",
+        73 => "Snow",
+        75 => "Heavy snow",
+        77 => "Snow grains",
+        80 => "Light rain showers",
+        81 => "Rain showers",
+        82 => "Violent rain showers",
+        85 => "Light snow showers",
+        86 => "Heavy snow showers",
+        95 => "Thunderstorm",
+        96 | 99 => "Thunderstorm with hail",
+        _ => "Unknown conditions",
+    }
+}
+
+/// Map an HTTP error response to an error carrying the provider's message.
+async fn response_error(provider: &str, response: reqwest::Response) -> crate::Error {
+    let status = response.status();
+    let body = response.text().await.unwrap_or_default();
+    let body: String = body.chars().take(500).collect();
+    anyhow!("{provider} request failed with {status}: {body}").into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn daily_forecast_reads_as_one_line() {
+        let day = DailyForecast {
+            date: "2026-03-23".into(),
+            summary: describe_weather_code(61).into(),
+            temperature_max: Some(14.2),
+            temperature_min: Some(8.6),
+            precipitation_sum: Some(3.24),
+            precipitation_probability: Some(80.0),
+            wind_speed_max: Some(24.6),
+            sunrise: None,
+            sunset: None,
+        };
+        assert_eq!(
+            day.describe(&ForecastUnits::for_units(WeatherUnits::Metric)),
+            "Light rain, 9–14 °C, 80% chance of precipitation (3.2 mm), wind up to 25 km/h"
+        );
+
+        let dry = DailyForecast {
+            summary: describe_weather_code(0).into(),
+            precipitation_sum: Some(0.0),
+            precipitation_probability: Some(0.0),
+            wind_speed_max: None,
+            ..day
+        };
+        assert_eq!(
+            dry.describe(&ForecastUnits::for_units(WeatherUnits::Imperial)),
+            "Clear sky, 9–14 °F, 0% chance of precipitation"
+        );
+    }
+}
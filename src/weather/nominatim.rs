@@ -0,0 +1,112 @@
+//! OpenStreetMap Nominatim geocoder.
+//!
+//! The public instance allows at most one request per second per client, so
+//! requests from every agent are spaced out here. Results are also cached
+//! upstream in [`super::Weather`].
+
+use super::{Geocoder, Place, response_error};
+use crate::error::Result;
+
+use anyhow::Context as _;
+use serde::Deserialize;
+
+use std::sync::LazyLock;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+const SEARCH_URL: &str = "https://nominatim.openstreetmap.org/search";
+const MIN_REQUEST_INTERVAL: Duration = Duration::from_secs(1);
+
+/// When the last request went out, shared by every agent in the process.
+static LAST_REQUEST: LazyLock<Mutex<Option<Instant>>> = LazyLock::new(|| Mutex::new(None));
+
+pub struct NominatimGeocoder {
+    client: reqwest::Client,
+}
+
+impl NominatimGeocoder {
+    pub fn new(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait::async_trait]
+impl Geocoder for NominatimGeocoder {
+    async fn search(&self, query: &str, limit: usize) -> Result<Vec<Place>> {
+        {
+            let mut last_request = LAST_REQUEST.lock().await;
+            if let Some(last) = *last_request {
+                tokio::time::sleep_until(last + MIN_REQUEST_INTERVAL).await;
+            }
+            *last_request = Some(Instant::now());
+        }
+
+        let response = self
+            .client
+            .get(SEARCH_URL)
+            .query(&[
+                ("q", query.to_string()),
+                ("format", "jsonv2".to_string()),
+                ("limit", limit.to_string()),
+                ("addressdetails", "1".to_string()),
+                ("accept-language", "en".to_string()),
+            ])
+            .send()
+            .await
+            .context("nominatim request failed")?;
+        if !response.status().is_success() {
+            return Err(response_error("nominatim", response).await);
+        }
+        let results: Vec<SearchResult> = response
+            .json()
+            .await
+            .context("invalid nominatim response")?;
+        Ok(results
+            .into_iter()
+            .filter_map(SearchResult::into_place)
+            .collect())
+    }
+}
+
+// -- API response types (private, only model what we need) --
+
+#[derive(Debug, Deserialize)]
+struct SearchResult {
+    /// Nominatim returns coordinates as strings.
+    lat: String,
+    lon: String,
+    display_name: String,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    address: Option<Address>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Address {
+    country: Option<String>,
+}
+
+impl SearchResult {
+    fn into_place(self) -> Option<Place> {
+        let name = self
+            .name
+            .filter(|name| !name.is_empty())
+            .or_else(|| {
+                self.display_name
+                    .split(',')
+                    .next()
+                    .map(|part| part.trim().to_string())
+            })
+            .unwrap_or_default();
+        Some(Place {
+            name,
+            latitude: self.lat.parse().ok()?,
+            longitude: self.lon.parse().ok()?,
+            display_name: self.display_name,
+            country: self.address.and_then(|address| address.country),
+            timezone: None,
+        })
+    }
+}
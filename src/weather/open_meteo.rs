@@ -0,0 +1,276 @@
+//! Open-Meteo forecast and geocoding APIs (no API key).
+
+use super::{
+    CurrentConditions, DailyForecast, Forecast, ForecastUnits, Forecaster, Geocoder, Place,
+    describe_weather_code, response_error,
+};
+use crate::config::WeatherUnits;
+use crate::error::Result;
+
+use anyhow::Context as _;
+use serde::Deserialize;
+
+const FORECAST_URL: &str = "https://api.open-meteo.com/v1/forecast";
+const GEOCODING_URL: &str = "https://geocoding-api.open-meteo.com/v1/search";
+
+const CURRENT_FIELDS: &str =
+    "temperature_2m,apparent_temperature,relative_humidity_2m,weather_code,wind_speed_10m";
+const DAILY_FIELDS: &str = "weather_code,temperature_2m_max,temperature_2m_min,precipitation_sum,\
+                            precipitation_probability_max,wind_speed_10m_max,sunrise,sunset";
+
+pub struct OpenMeteoForecaster {
+    client: reqwest::Client,
+}
+
+impl OpenMeteoForecaster {
+    pub fn new(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait::async_trait]
+impl Forecaster for OpenMeteoForecaster {
+    async fn forecast(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        days: u8,
+        units: WeatherUnits,
+    ) -> Result<Forecast> {
+        let mut query = vec![
+            ("latitude", latitude.to_string()),
+            ("longitude", longitude.to_string()),
+            ("current", CURRENT_FIELDS.to_string()),
+            ("daily", DAILY_FIELDS.to_string()),
+            ("timezone", "auto".to_string()),
+            ("forecast_days", days.to_string()),
+        ];
+        if units == WeatherUnits::Imperial {
+            query.extend([
+                ("temperature_unit", "fahrenheit".to_string()),
+                ("wind_speed_unit", "mph".to_string()),
+                ("precipitation_unit", "inch".to_string()),
+            ]);
+        }
+
+        let response = self
+            .client
+            .get(FORECAST_URL)
+            .query(&query)
+            .send()
+            .await
+            .context("open-meteo request failed")?;
+        if !response.status().is_success() {
+            return Err(response_error("open-meteo", response).await);
+        }
+        let body: ForecastResponse = response
+            .json()
+            .await
+            .context("invalid open-meteo forecast response")?;
+        Ok(body.into_forecast(units))
+    }
+}
+
+pub struct OpenMeteoGeocoder {
+    client: reqwest::Client,
+}
+
+impl OpenMeteoGeocoder {
+    pub fn new(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait::async_trait]
+impl Geocoder for OpenMeteoGeocoder {
+    async fn search(&self, query: &str, limit: usize) -> Result<Vec<Place>> {
+        let response = self
+            .client
+            .get(GEOCODING_URL)
+            .query(&[
+                ("name", query.to_string()),
+                ("count", limit.to_string()),
+                ("language", "en".to_string()),
+                ("format", "json".to_string()),
+            ])
+            .send()
+            .await
+            .context("open-meteo geocoding request failed")?;
+        if !response.status().is_success() {
+            return Err(response_error("open-meteo geocoding", response).await);
+        }
+        let body: GeocodingResponse = response
+            .json()
+            .await
+            .context("invalid open-meteo geocoding response")?;
+        Ok(body
+            .results
+            .into_iter()
+            .map(|result| {
+                let display_name = [
+                    Some(result.name.as_str()),
+                    result.admin1.as_deref(),
+                    result.country.as_deref(),
+                ]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join(", ");
+                Place {
+                    name: result.name,
+                    display_name,
+                    latitude: result.latitude,
+                    longitude: result.longitude,
+                    country: result.country,
+                    timezone: result.timezone,
+                }
+            })
+            .collect())
+    }
+}
+
+// -- API response types (private, only model what we need) --
+
+#[derive(Debug, Deserialize)]
+struct ForecastResponse {
+    latitude: f64,
+    longitude: f64,
+    timezone: Option<String>,
+    current: Option<CurrentResponse>,
+    daily: Option<DailyResponse>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CurrentResponse {
+    time: String,
+    temperature_2m: Option<f64>,
+    apparent_temperature: Option<f64>,
+    relative_humidity_2m: Option<f64>,
+    weather_code: Option<u8>,
+    wind_speed_10m: Option<f64>,
+}
+
+/// Daily values come back as parallel arrays indexed like `time`.
+#[derive(Debug, Deserialize)]
+struct DailyResponse {
+    time: Vec<String>,
+    #[serde(default)]
+    weather_code: Vec<Option<u8>>,
+    #[serde(default)]
+    temperature_2m_max: Vec<Option<f64>>,
+    #[serde(default)]
+    temperature_2m_min: Vec<Option<f64>>,
+    #[serde(default)]
+    precipitation_sum: Vec<Option<f64>>,
+    #[serde(default)]
+    precipitation_probability_max: Vec<Option<f64>>,
+    #[serde(default)]
+    wind_speed_10m_max: Vec<Option<f64>>,
+    #[serde(default)]
+    sunrise: Vec<Option<String>>,
+    #[serde(default)]
+    sunset: Vec<Option<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeocodingResponse {
+    #[serde(default)]
+    results: Vec<GeocodingResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeocodingResult {
+    name: String,
+    latitude: f64,
+    longitude: f64,
+    country: Option<String>,
+    admin1: Option<String>,
+    timezone: Option<String>,
+}
+
+fn summary(code: Option<u8>) -> String {
+    code.map(describe_weather_code)
+        .unwrap_or("Unknown conditions")
+        .to_string()
+}
+
+impl ForecastResponse {
+    fn into_forecast(self, units: WeatherUnits) -> Forecast {
+        fn at<T: Clone>(values: &[Option<T>], index: usize) -> Option<T> {
+            values.get(index).cloned().flatten()
+        }
+
+        let daily = self
+            .daily
+            .map(|daily| {
+                daily
+                    .time
+                    .iter()
+                    .enumerate()
+                    .map(|(index, date)| DailyForecast {
+                        date: date.clone(),
+                        summary: summary(at(&daily.weather_code, index)),
+                        temperature_max: at(&daily.temperature_2m_max, index),
+                        temperature_min: at(&daily.temperature_2m_min, index),
+                        precipitation_sum: at(&daily.precipitation_sum, index),
+                        precipitation_probability: at(&daily.precipitation_probability_max, index),
+                        wind_speed_max: at(&daily.wind_speed_10m_max, index),
+                        sunrise: at(&daily.sunrise, index),
+                        sunset: at(&daily.sunset, index),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Forecast {
+            latitude: self.latitude,
+            longitude: self.longitude,
+            timezone: self.timezone,
+            units: ForecastUnits::for_units(units),
+            current: self.current.map(|current| CurrentConditions {
+                time: current.time,
+                summary: summary(current.weather_code),
+                temperature: current.temperature_2m,
+                apparent_temperature: current.apparent_temperature,
+                relative_humidity: current.relative_humidity_2m,
+                wind_speed: current.wind_speed_10m,
+            }),
+            daily,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn forecast_response_is_zipped_by_day() {
+        let body: ForecastResponse = serde_json::from_str(
+            r#"{
+                "latitude": 52.52, "longitude": 13.42, "timezone": "Europe/Berlin",
+                "current": {"time": "2026-03-23T14:00", "temperature_2m": 11.3,
+                            "weather_code": 3, "wind_speed_10m": 9.0},
+                "daily": {
+                    "time": ["2026-03-23", "2026-03-24"],
+                    "weather_code": [3, 61],
+                    "temperature_2m_max": [12.1, 10.4],
+                    "temperature_2m_min": [4.0, null],
+                    "precipitation_probability_max": [5, 70]
+                }
+            }"#,
+        )
+        .unwrap();
+        let forecast = body.into_forecast(WeatherUnits::Metric);
+
+        assert_eq!(forecast.timezone.as_deref(), Some("Europe/Berlin"));
+        assert_eq!(forecast.current.unwrap().summary, "Overcast");
+        assert_eq!(forecast.daily.len(), 2);
+        let tomorrow = &forecast.daily[1];
+        assert_eq!(tomorrow.date, "2026-03-24");
+        assert_eq!(tomorrow.summary, "Light rain");
+        assert_eq!(tomorrow.temperature_min, None);
+        assert_eq!(tomorrow.precipitation_probability, Some(70.0));
+        assert_eq!(tomorrow.sunrise, None);
+    }
+}
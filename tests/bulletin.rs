@@ -58,9 +58,12 @@ async fn bootstrap_deps() -> anyhow::Result<spacebot::AgentDeps> {
 
     let memory_store = spacebot::memory::MemoryStore::new(db.sqlite.clone());
 
-    let embedding_table = spacebot::memory::EmbeddingTable::open_or_create(&db.lance)
-        .await
-        .context("failed to init embedding table")?;
+    let embedding_table = spacebot::memory::EmbeddingTable::open_or_create(
+        &db.lance,
+        spacebot::memory::embedding::LOCAL_DIMENSIONS,
+    )
+    .await
+    .context("failed to init embedding table")?;
 
     if let Err(error) = embedding_table.ensure_fts_index().await {
         eprintln!("warning: FTS index creation failed: {error}");
@@ -130,6 +133,13 @@ async fn bootstrap_deps() -> anyhow::Result<spacebot::AgentDeps> {
             spacebot::agent::process_control::ProcessControlRegistry::new(),
         ),
         injection_tx: tokio::sync::mpsc::channel(1).0,
+        leader: spacebot::leader::LeaderLease::disabled(),
+        quota: spacebot::quota::AgentQuota::new(
+            Arc::from(agent_config.id.as_str()),
+            &agent_config.quotas,
+            db.sqlite.clone(),
+        ),
+        runtime: None,
     })
 }
 
@@ -57,9 +57,12 @@ async fn bootstrap_deps() -> anyhow::Result<(spacebot::AgentDeps, spacebot::conf
 
     let memory_store = spacebot::memory::MemoryStore::new(db.sqlite.clone());
 
-    let embedding_table = spacebot::memory::EmbeddingTable::open_or_create(&db.lance)
-        .await
-        .context("failed to init embedding table")?;
+    let embedding_table = spacebot::memory::EmbeddingTable::open_or_create(
+        &db.lance,
+        spacebot::memory::embedding::LOCAL_DIMENSIONS,
+    )
+    .await
+    .context("failed to init embedding table")?;
 
     if let Err(error) = embedding_table.ensure_fts_index().await {
         eprintln!("warning: FTS index creation failed: {error}");
@@ -129,6 +132,13 @@ async fn bootstrap_deps() -> anyhow::Result<(spacebot::AgentDeps, spacebot::conf
             spacebot::agent::process_control::ProcessControlRegistry::new(),
         ),
         injection_tx: tokio::sync::mpsc::channel(1).0,
+        leader: spacebot::leader::LeaderLease::disabled(),
+        quota: spacebot::quota::AgentQuota::new(
+            Arc::from(agent_config.id.as_str()),
+            &agent_config.quotas,
+            db.sqlite.clone(),
+        ),
+        runtime: None,
     };
 
     Ok((deps, config))
@@ -245,6 +255,8 @@ async fn dump_channel_context() {
         screenshot_dir: std::path::PathBuf::from("/tmp/screenshots"),
         logs_dir: std::path::PathBuf::from("/tmp/logs"),
         reply_target_message_id: Arc::new(tokio::sync::RwLock::new(None)),
+        user_language: Arc::new(tokio::sync::RwLock::new(None)),
+        user_scope: Arc::new(std::sync::RwLock::new(None)),
         prompt_snapshot_store: None,
         live_worker_transcripts: Arc::new(tokio::sync::RwLock::new(
             std::collections::HashMap::new(),
@@ -319,6 +331,7 @@ async fn dump_branch_context() {
     let channel_store = spacebot::conversation::ChannelStore::new(deps.sqlite_pool.clone());
     let run_logger = spacebot::conversation::ProcessRunLogger::new(deps.sqlite_pool.clone());
     let branch_tool_server = spacebot::tools::create_branch_tool_server(
+        None,
         None,
         deps.agent_id.clone(),
         deps.task_store.clone(),
@@ -399,6 +412,12 @@ async fn dump_worker_context() {
         std::path::PathBuf::from("/tmp"),
         deps.sandbox.clone(),
         vec![],
+        spacebot::tools::ChartTool::new(
+            deps.sqlite_pool.clone(),
+            deps.runtime_config.saved_dir(),
+            None,
+        ),
+        None,
         deps.runtime_config.clone(),
     );
 
@@ -485,6 +504,8 @@ async fn dump_all_contexts() {
         screenshot_dir: std::path::PathBuf::from("/tmp/screenshots"),
         logs_dir: std::path::PathBuf::from("/tmp/logs"),
         reply_target_message_id: Arc::new(tokio::sync::RwLock::new(None)),
+        user_language: Arc::new(tokio::sync::RwLock::new(None)),
+        user_scope: Arc::new(std::sync::RwLock::new(None)),
         prompt_snapshot_store: None,
         live_worker_transcripts: Arc::new(tokio::sync::RwLock::new(
             std::collections::HashMap::new(),
@@ -527,6 +548,7 @@ async fn dump_all_contexts() {
         .expect("failed to render branch prompt");
     let run_logger = spacebot::conversation::ProcessRunLogger::new(deps.sqlite_pool.clone());
     let branch_tool_server = spacebot::tools::create_branch_tool_server(
+        None,
         None,
         deps.agent_id.clone(),
         deps.task_store.clone(),
@@ -579,6 +601,12 @@ async fn dump_all_contexts() {
         std::path::PathBuf::from("/tmp"),
         deps.sandbox.clone(),
         vec![],
+        spacebot::tools::ChartTool::new(
+            deps.sqlite_pool.clone(),
+            deps.runtime_config.saved_dir(),
+            None,
+        ),
+        None,
         deps.runtime_config.clone(),
     );
     let worker_tool_defs = worker_tool_server.get_tool_defs(None).await.unwrap();
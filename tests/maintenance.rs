@@ -1,6 +1,6 @@
 //! Memory maintenance integration coverage.
 
-use spacebot::memory::maintenance::{run_maintenance, run_maintenance_with_cancel};
+use spacebot::memory::maintenance::{DecayHalfLives, run_maintenance, run_maintenance_with_cancel};
 use spacebot::memory::{MemoryStore, RelationType, maintenance::MaintenanceConfig};
 use std::sync::{Arc, OnceLock};
 use tempfile::tempdir;
@@ -44,9 +44,12 @@ async fn make_memory_maintenance_fixture() -> (
         .execute()
         .await
         .expect("failed to connect to lancedb");
-    let embedding_table = spacebot::memory::EmbeddingTable::open_or_create(&lance_conn)
-        .await
-        .expect("failed to create embedding table");
+    let embedding_table = spacebot::memory::EmbeddingTable::open_or_create(
+        &lance_conn,
+        spacebot::memory::embedding::LOCAL_DIMENSIONS,
+    )
+    .await
+    .expect("failed to create embedding table");
 
     (store, embedding_table, shared_embedding_model(), dir)
 }
@@ -129,9 +132,12 @@ async fn maintenance_run_merges_duplicate_memory_and_links_updates_edge() {
         &embedding_model,
         &MaintenanceConfig {
             prune_threshold: 0.2,
-            decay_rate: 0.05,
+            half_lives: DecayHalfLives::default(),
             min_age_days: 30,
             merge_similarity_threshold: 0.95,
+            trash_retention_days: 30,
+            delete_expired: false,
+            mutation_retention_days: 0,
         },
     )
     .await
@@ -222,9 +228,12 @@ async fn maintenance_config_validation_rejects_negative_min_age() {
         &embedding_model,
         &MaintenanceConfig {
             prune_threshold: 0.2,
-            decay_rate: 0.05,
+            half_lives: DecayHalfLives::default(),
             min_age_days: -5,
             merge_similarity_threshold: 0.95,
+            trash_retention_days: 30,
+            delete_expired: false,
+            mutation_retention_days: 0,
         },
     )
     .await;